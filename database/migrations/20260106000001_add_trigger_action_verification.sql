@@ -0,0 +1,27 @@
+-- Migration: Add webhook verification status to trigger actions
+-- Description: Tracks whether a REST action's endpoint has completed the
+--              challenge/echo verification handshake
+-- Created: 2026-01-06
+
+-- not_required: action type has no endpoint to verify (telegram, mcp)
+-- unverified:   REST action created while verification was disabled
+-- verified:     endpoint echoed the challenge token
+-- failed:       handshake failed (only persisted in "flag" mode)
+ALTER TABLE trigger_actions
+    ADD COLUMN IF NOT EXISTS verification_status VARCHAR(20) NOT NULL DEFAULT 'not_required',
+    ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ;
+
+ALTER TABLE trigger_actions
+    DROP CONSTRAINT IF EXISTS chk_trigger_actions_verification_status;
+
+ALTER TABLE trigger_actions
+    ADD CONSTRAINT chk_trigger_actions_verification_status
+    CHECK (verification_status IN ('not_required', 'unverified', 'verified', 'failed'));
+
+-- Existing REST actions predate the handshake
+UPDATE trigger_actions
+SET verification_status = 'unverified'
+WHERE action_type = 'rest' AND verification_status = 'not_required';
+
+COMMENT ON COLUMN trigger_actions.verification_status IS 'Webhook verification handshake state: not_required, unverified, verified, failed';
+COMMENT ON COLUMN trigger_actions.verified_at IS 'When the endpoint last completed the verification handshake';
@@ -0,0 +1,30 @@
+-- Migration: Create organization_rate_limits table
+-- Description: Per-organization rate limit overrides, one row per query tier.
+--              Organizations without a row for a tier use their plan limit.
+-- Created: 2026-01-10
+
+CREATE TABLE IF NOT EXISTS organization_rate_limits (
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    tier SMALLINT NOT NULL,
+    request_limit INTEGER NOT NULL,
+    window_seconds INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, tier),
+    CONSTRAINT chk_org_rate_limits_tier CHECK (tier BETWEEN 0 AND 3),
+    CONSTRAINT chk_org_rate_limits_limit CHECK (request_limit > 0),
+    -- The sliding window uses 1-minute buckets
+    CONSTRAINT chk_org_rate_limits_window CHECK (
+        window_seconds BETWEEN 60 AND 86400 AND window_seconds % 60 = 0
+    )
+);
+
+CREATE TRIGGER update_organization_rate_limits_updated_at
+    BEFORE UPDATE ON organization_rate_limits
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE organization_rate_limits IS 'Per-organization rate limit overrides by query tier';
+COMMENT ON COLUMN organization_rate_limits.tier IS 'Query tier (0-3) the override applies to';
+COMMENT ON COLUMN organization_rate_limits.request_limit IS 'Maximum request cost allowed per window';
+COMMENT ON COLUMN organization_rate_limits.window_seconds IS 'Sliding window size in seconds (multiple of 60)';
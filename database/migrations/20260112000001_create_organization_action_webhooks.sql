@@ -0,0 +1,25 @@
+-- Migration: Create organization_action_webhooks table
+-- Description: Optional per-organization endpoint that receives a signed
+--              summary after each action executes (success or failure).
+-- Created: 2026-01-12
+
+CREATE TABLE IF NOT EXISTS organization_action_webhooks (
+    organization_id TEXT PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 signing key; the worker needs it in clear to sign deliveries
+    secret TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_org_action_webhooks_url CHECK (url ~ '^https?://')
+);
+
+CREATE TRIGGER update_organization_action_webhooks_updated_at
+    BEFORE UPDATE ON organization_action_webhooks
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE organization_action_webhooks IS 'Per-organization action result webhook (observability)';
+COMMENT ON COLUMN organization_action_webhooks.url IS 'Endpoint that receives action result summaries';
+COMMENT ON COLUMN organization_action_webhooks.secret IS 'Key used to sign deliveries (X-AgentAuri-Signature)';
+COMMENT ON COLUMN organization_action_webhooks.enabled IS 'Deliveries are paused while false';
@@ -0,0 +1,62 @@
+-- Migration: Create webhooks and webhook_deliveries tables
+-- Description: Organization-level endpoints that receive every trigger fire
+--              as a signed event, plus a delivery log used as the retry queue.
+-- Created: 2026-01-14
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 signing key; the dispatcher needs it in clear to sign deliveries
+    secret TEXT NOT NULL,
+    -- Blockchain event types to deliver (empty = every trigger fire)
+    event_types TEXT[] NOT NULL DEFAULT '{}',
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_webhooks_url CHECK (url ~ '^https?://')
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_organization
+    ON webhooks(organization_id) WHERE enabled = TRUE;
+
+CREATE TRIGGER update_webhooks_updated_at
+    BEFORE UPDATE ON webhooks
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    webhook_id TEXT NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    trigger_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_response_status INTEGER,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    CONSTRAINT chk_webhook_deliveries_status CHECK (status IN ('pending', 'succeeded', 'failed')),
+    -- A trigger fire is delivered once per webhook, even if the event is reprocessed
+    CONSTRAINT uq_webhook_deliveries_fire UNIQUE (webhook_id, trigger_id, event_id)
+);
+
+-- Dispatcher queue scan
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+    ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+
+-- Delivery history per webhook (GET /webhooks/{id}/deliveries)
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook
+    ON webhook_deliveries(webhook_id, created_at DESC);
+
+COMMENT ON TABLE webhooks IS 'Organization endpoints notified of every trigger fire';
+COMMENT ON COLUMN webhooks.secret IS 'Key used to sign deliveries (X-AgentAuri-Signature)';
+COMMENT ON COLUMN webhooks.event_types IS 'Event types to deliver; empty delivers all';
+COMMENT ON TABLE webhook_deliveries IS 'Delivery attempts for trigger fire webhooks (also the retry queue)';
+COMMENT ON COLUMN webhook_deliveries.status IS 'pending (queued or retrying), succeeded, or failed (attempts exhausted)';
+COMMENT ON COLUMN webhook_deliveries.next_attempt_at IS 'When the dispatcher may next attempt (or reclaim) the delivery';
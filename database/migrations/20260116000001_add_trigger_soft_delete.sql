@@ -0,0 +1,18 @@
+-- Migration: Add soft delete to triggers
+-- Description: Deleting a trigger sets deleted_at instead of removing the row,
+--              so it can be restored until the API Gateway purges it after the
+--              retention window (TRIGGER_PURGE_RETENTION_DAYS).
+-- Created: 2026-01-16
+
+ALTER TABLE triggers
+ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Listing and evaluation only ever look at live triggers
+CREATE INDEX IF NOT EXISTS idx_triggers_organization_live
+    ON triggers(organization_id, created_at DESC) WHERE deleted_at IS NULL;
+
+-- Purge scans soft-deleted triggers by age
+CREATE INDEX IF NOT EXISTS idx_triggers_deleted_at
+    ON triggers(deleted_at) WHERE deleted_at IS NOT NULL;
+
+COMMENT ON COLUMN triggers.deleted_at IS 'When the trigger was soft-deleted; NULL for live triggers';
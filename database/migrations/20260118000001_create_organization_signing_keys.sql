@@ -0,0 +1,37 @@
+-- Migration: Create organization_signing_keys table
+-- Description: Per-organization HMAC keys for outbound deliveries (REST
+--              actions, action result webhooks). One key is current; a
+--              rotated-out key keeps signing until its grace window ends.
+-- Created: 2026-01-18
+
+CREATE TABLE IF NOT EXISTS organization_signing_keys (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- HMAC-SHA256 key; the worker needs it in clear to sign deliveries
+    secret TEXT NOT NULL,
+    -- First characters of the secret, to identify the key without revealing it
+    key_prefix TEXT NOT NULL,
+    created_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set when the key is rotated out; NULL for the current key
+    retired_at TIMESTAMPTZ,
+    -- End of the grace window of a retired key
+    expires_at TIMESTAMPTZ,
+    CONSTRAINT chk_org_signing_keys_retired CHECK (
+        (retired_at IS NULL AND expires_at IS NULL)
+        OR (retired_at IS NOT NULL AND expires_at IS NOT NULL AND expires_at >= retired_at)
+    )
+);
+
+-- At most one current key per organization
+CREATE UNIQUE INDEX IF NOT EXISTS idx_org_signing_keys_current
+    ON organization_signing_keys(organization_id)
+    WHERE retired_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_org_signing_keys_org_created
+    ON organization_signing_keys(organization_id, created_at DESC);
+
+COMMENT ON TABLE organization_signing_keys IS 'Per-organization keys signing outbound deliveries (X-AgentAuri-Signature)';
+COMMENT ON COLUMN organization_signing_keys.secret IS 'HMAC-SHA256 key, shown to the customer only when created';
+COMMENT ON COLUMN organization_signing_keys.retired_at IS 'When the key was rotated out (NULL while current)';
+COMMENT ON COLUMN organization_signing_keys.expires_at IS 'Retired keys keep signing alongside the current key until this time';
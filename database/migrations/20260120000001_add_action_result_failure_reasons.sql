@@ -0,0 +1,28 @@
+-- Migration: Add structured failure reasons to action_results
+-- Description: Action workers classify each failure into a category and a
+--              machine-readable code, and record which trigger action ran,
+--              so failures can be grouped per action without parsing
+--              error_message.
+-- Created: 2026-01-20
+
+ALTER TABLE action_results
+    ADD COLUMN IF NOT EXISTS action_id INTEGER REFERENCES trigger_actions(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS error_category TEXT,
+    ADD COLUMN IF NOT EXISTS error_code TEXT;
+
+ALTER TABLE action_results
+    ADD CONSTRAINT chk_action_results_error_category CHECK (
+        error_category IS NULL OR error_category IN (
+            'timeout', 'connection', 'client_error', 'server_error', 'rate_limited',
+            'upstream', 'template', 'config', 'internal'
+        )
+    );
+
+-- Per-action outcome summaries
+CREATE INDEX IF NOT EXISTS idx_action_results_action_executed
+    ON action_results(action_id, executed_at DESC)
+    WHERE action_id IS NOT NULL;
+
+COMMENT ON COLUMN action_results.action_id IS 'Trigger action the job executed (NULL for results logged before this column existed)';
+COMMENT ON COLUMN action_results.error_category IS 'Broad failure cause (timeout, connection, client_error, server_error, rate_limited, upstream, template, config, internal)';
+COMMENT ON COLUMN action_results.error_code IS 'Machine-readable failure code within the category (e.g. http_503, request_timeout)';
@@ -0,0 +1,36 @@
+-- Migration: Authorization code grant with PKCE
+-- Description: Public OAuth clients (native and browser apps that cannot keep
+--              a secret) and RFC 7636 PKCE challenges stored with the
+--              authorization codes issued to OAuth clients. Codes issued by
+--              social login leave the client columns NULL.
+-- Created: 2026-01-22
+
+ALTER TABLE oauth_clients
+    ADD COLUMN IF NOT EXISTS is_public BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN oauth_clients.is_public IS 'Public clients have no usable secret and must use PKCE for the authorization code grant';
+
+ALTER TABLE oauth_temp_codes
+    ADD COLUMN IF NOT EXISTS client_id TEXT REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS redirect_uri TEXT,
+    ADD COLUMN IF NOT EXISTS organization_id TEXT REFERENCES organizations(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS scopes TEXT[],
+    ADD COLUMN IF NOT EXISTS code_challenge TEXT,
+    ADD COLUMN IF NOT EXISTS code_challenge_method TEXT;
+
+ALTER TABLE oauth_temp_codes
+    ADD CONSTRAINT chk_oauth_temp_codes_client_grant CHECK (
+        client_id IS NULL
+        OR (redirect_uri IS NOT NULL AND organization_id IS NOT NULL AND scopes IS NOT NULL)
+    ),
+    ADD CONSTRAINT chk_oauth_temp_codes_code_challenge CHECK (
+        (code_challenge IS NULL AND code_challenge_method IS NULL)
+        OR (code_challenge IS NOT NULL AND code_challenge_method IN ('S256', 'plain'))
+    );
+
+COMMENT ON COLUMN oauth_temp_codes.client_id IS 'OAuth client the code was issued to (NULL for social login codes)';
+COMMENT ON COLUMN oauth_temp_codes.redirect_uri IS 'Redirect URI from the authorization request; the token request must repeat it';
+COMMENT ON COLUMN oauth_temp_codes.organization_id IS 'Organization the user granted the client access to';
+COMMENT ON COLUMN oauth_temp_codes.scopes IS 'Scopes granted to the client';
+COMMENT ON COLUMN oauth_temp_codes.code_challenge IS 'PKCE code challenge (RFC 7636)';
+COMMENT ON COLUMN oauth_temp_codes.code_challenge_method IS 'PKCE challenge method: S256 or plain';
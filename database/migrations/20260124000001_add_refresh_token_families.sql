@@ -0,0 +1,33 @@
+-- Migration: Refresh token families and reuse detection
+-- Description: Every refresh token belongs to the family started by the login
+--              that issued its first member; rotation links the old token to
+--              its replacement. Presenting a token that has already been
+--              replaced means it was copied, so the whole family is revoked
+--              and the event recorded in auth_failures.
+-- Created: 2026-01-24
+
+ALTER TABLE user_refresh_tokens
+    ADD COLUMN IF NOT EXISTS family_id TEXT,
+    ADD COLUMN IF NOT EXISTS replaced_by TEXT REFERENCES user_refresh_tokens(id) ON DELETE SET NULL;
+
+-- Existing tokens each start their own family
+UPDATE user_refresh_tokens SET family_id = id WHERE family_id IS NULL;
+
+ALTER TABLE user_refresh_tokens ALTER COLUMN family_id SET NOT NULL;
+
+-- Revoking a family touches only its live tokens
+CREATE INDEX IF NOT EXISTS idx_user_refresh_tokens_family_id ON user_refresh_tokens (family_id)
+    WHERE revoked_at IS NULL;
+
+COMMENT ON COLUMN user_refresh_tokens.family_id IS 'ID of the first token issued at login; shared by all tokens rotated from it';
+COMMENT ON COLUMN user_refresh_tokens.replaced_by IS 'Token issued when this one was rotated (NULL if never rotated)';
+
+-- Reuse of a rotated refresh token is a security event
+ALTER TABLE auth_failures DROP CONSTRAINT IF EXISTS auth_failures_failure_type_check;
+ALTER TABLE auth_failures ADD CONSTRAINT auth_failures_failure_type_check CHECK (failure_type IN (
+    'invalid_format',
+    'prefix_not_found',
+    'rate_limited',
+    'invalid_key',
+    'refresh_token_reuse' -- Already-rotated refresh token presented; its family was revoked
+));
@@ -0,0 +1,13 @@
+-- Migration: Login session start time on refresh tokens
+-- Description: A refresh token family is a login session. Rotation replaces
+--              the family's token (and its created_at), and cleanup deletes
+--              the original after 7 days, so the time the session started is
+--              copied forward onto every token of the family.
+-- Created: 2026-01-25
+
+ALTER TABLE user_refresh_tokens
+    ADD COLUMN IF NOT EXISTS session_started_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE user_refresh_tokens SET session_started_at = created_at WHERE created_at IS NOT NULL;
+
+COMMENT ON COLUMN user_refresh_tokens.session_started_at IS 'When the login that started this token''s family happened';
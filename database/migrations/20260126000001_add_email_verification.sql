@@ -0,0 +1,36 @@
+-- Migration: Email verification
+-- Description: Password registrations start unverified and receive a
+--              single-use verification link. Social logins with an email the
+--              provider verified are verified on creation. Wallet users have
+--              a placeholder address and are never asked to verify.
+-- Created: 2026-01-26
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ;
+
+-- Existing accounts predate verification
+UPDATE users SET email_verified_at = created_at
+WHERE email_verified_at IS NULL
+  AND primary_auth_provider IS DISTINCT FROM 'wallet';
+
+COMMENT ON COLUMN users.email_verified_at IS 'When the user proved ownership of their email (NULL = not verified)';
+
+CREATE TABLE IF NOT EXISTS email_verification_tokens (
+    id TEXT PRIMARY KEY DEFAULT gen_random_uuid()::text,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Token hash (SHA-256 hex); the token itself is only sent by email
+    token_hash TEXT NOT NULL UNIQUE,
+
+    -- Address the token was sent to; it verifies only that address
+    email TEXT NOT NULL,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_verification_tokens_user_id ON email_verification_tokens (user_id);
+CREATE INDEX IF NOT EXISTS idx_email_verification_tokens_expires ON email_verification_tokens (expires_at)
+    WHERE used_at IS NULL;
+
+COMMENT ON TABLE email_verification_tokens IS 'Single-use email verification links (24-hour validity)';
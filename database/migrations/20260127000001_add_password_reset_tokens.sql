@@ -0,0 +1,26 @@
+-- Migration: Password reset tokens
+-- Description: Single-use password reset links sent by
+--              POST /api/v1/auth/forgot-password. Resetting the password
+--              revokes all of the user's refresh tokens.
+-- Created: 2026-01-27
+
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+    id TEXT PRIMARY KEY DEFAULT gen_random_uuid()::text,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Token hash (SHA-256 hex); the token itself is only sent by email
+    token_hash TEXT NOT NULL UNIQUE,
+
+    -- Address the token was sent to; the token stops working if it changes
+    email TEXT NOT NULL,
+
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user_id ON password_reset_tokens (user_id);
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_expires ON password_reset_tokens (expires_at)
+    WHERE used_at IS NULL;
+
+COMMENT ON TABLE password_reset_tokens IS 'Single-use password reset links (1-hour validity)';
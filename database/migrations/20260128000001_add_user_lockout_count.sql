@@ -0,0 +1,13 @@
+-- Migration: Progressive lockout counter
+-- Description: The account lockout policy is now configurable
+--              (AUTH_MAX_FAILED_ATTEMPTS, AUTH_LOCKOUT_SECS,
+--              AUTH_ATTEMPT_WINDOW_SECS). Failed attempts reset after each
+--              lockout, so the number of lockouts since the last successful
+--              login is stored separately to double the next one.
+-- Created: 2026-01-28
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS lockout_count INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN users.failed_login_attempts IS 'Failed login attempts toward the next lockout. Forgotten after AUTH_ATTEMPT_WINDOW_SECS without failures; reset by a lockout or a successful login.';
+COMMENT ON COLUMN users.locked_until IS 'Account locked until this timestamp. NULL means not locked. Duration is AUTH_LOCKOUT_SECS doubled per earlier lockout (at most 16x).';
+COMMENT ON COLUMN users.lockout_count IS 'Lockouts since the last successful login (or quiet attempt window); doubles the next lockout.';
@@ -0,0 +1,17 @@
+-- Migration: Trigger cooldown
+-- Description: A trigger with cooldown_secs > 0 does not fire again until the
+--              cooldown has elapsed since it last fired. The last fire is kept
+--              with the trigger's state so the cooldown survives restarts.
+-- Created: 2026-01-29
+
+ALTER TABLE triggers ADD COLUMN IF NOT EXISTS cooldown_secs INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE triggers DROP CONSTRAINT IF EXISTS triggers_cooldown_secs_check;
+ALTER TABLE triggers ADD CONSTRAINT triggers_cooldown_secs_check CHECK (cooldown_secs >= 0);
+
+-- Stateless triggers with a cooldown get a state row without evaluator state
+ALTER TABLE trigger_state ALTER COLUMN state_data SET DEFAULT '{}'::jsonb;
+ALTER TABLE trigger_state ADD COLUMN IF NOT EXISTS last_fired_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN triggers.cooldown_secs IS 'Minimum seconds between two fires of the trigger (0 = no cooldown)';
+COMMENT ON COLUMN trigger_state.last_fired_at IS 'When the trigger last fired (only tracked for triggers with a cooldown)';
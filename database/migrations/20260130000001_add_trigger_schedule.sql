@@ -0,0 +1,11 @@
+-- Migration: Trigger schedule
+-- Description: A trigger with a schedule only fires inside a weekly time
+--              window, e.g. business hours. The window's local times are
+--              interpreted in the IANA time zone stored with it, so it
+--              follows daylight saving time. Events outside the window still
+--              update stateful conditions; the trigger just does not fire.
+-- Created: 2026-01-30
+
+ALTER TABLE triggers ADD COLUMN IF NOT EXISTS schedule JSONB;
+
+COMMENT ON COLUMN triggers.schedule IS 'Weekly window in which the trigger may fire: {"days": ["mon", ...], "start": "HH:MM", "end": "HH:MM", "timezone": "Europe/Rome"}. NULL = always.';
@@ -0,0 +1,8 @@
+-- Migration: Action priority lanes
+-- Description: Action jobs are queued on priority lanes that workers drain
+--              high-first. Higher priorities are more urgent (actions of a
+--              trigger already run in descending priority order); the old
+--              column comment said the opposite.
+-- Created: 2026-01-31
+
+COMMENT ON COLUMN trigger_actions.priority IS 'Action priority, higher = more urgent (NOT NULL, defaults to 1). Jobs with priority >= 10 use the high queue lane, <= 0 the low lane.';
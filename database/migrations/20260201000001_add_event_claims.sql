@@ -0,0 +1,18 @@
+-- Migration: Event claims
+-- Description: The NOTIFY listener and the polling fallback (of one or several
+--              processor instances) can pick up the same event at once. Before
+--              evaluating triggers a processor claims the event here; a
+--              concurrent claim fails, so only one of them fires the triggers.
+--              The claim is removed once the event is in processed_events (or
+--              processing failed). Claims left by a crashed processor expire
+--              and can then be taken over.
+-- Created: 2026-02-01
+
+CREATE TABLE IF NOT EXISTS event_claims (
+    event_id TEXT PRIMARY KEY,
+    claimed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processor_instance TEXT NOT NULL
+);
+
+COMMENT ON TABLE event_claims IS 'Events currently being processed, so concurrent processing paths skip them';
+COMMENT ON COLUMN event_claims.claimed_at IS 'When the claim was taken; older claims than the processor''s claim timeout can be taken over';
@@ -0,0 +1,8 @@
+-- Migration: Agent follows listing index
+-- Description: GET /api/v1/agents/following pages through an organization's
+--              follows newest first with a (created_at, id) keyset cursor.
+--              This index serves both the order and the cursor comparison.
+-- Created: 2026-02-02
+
+CREATE INDEX IF NOT EXISTS idx_agent_follows_org_created
+    ON agent_follows(organization_id, created_at DESC, id DESC);
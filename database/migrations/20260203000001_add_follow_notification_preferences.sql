@@ -0,0 +1,12 @@
+-- Migration: Follow notification preferences
+-- Description: A follow notifies about every event of the followed agent
+--              through all its actions. notification_preferences narrows this
+--              to some event types and some channels (action types), e.g.
+--              {"event_types": ["NewFeedback"], "channels": ["telegram"]}.
+--              NULL keeps notifying about everything.
+-- Created: 2026-02-03
+
+ALTER TABLE agent_follows
+    ADD COLUMN IF NOT EXISTS notification_preferences JSONB;
+
+COMMENT ON COLUMN agent_follows.notification_preferences IS 'Event types and channels the follow notifies about (NULL = all); see shared::NotificationPreferences';
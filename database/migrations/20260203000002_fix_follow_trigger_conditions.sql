@@ -0,0 +1,10 @@
+-- Migration: Fix agent follow trigger conditions
+-- Description: Follow triggers were created with a 'field_match' condition,
+--              which the event-processor does not know, so their evaluation
+--              always failed and follows never notified. Rewrite them to the
+--              equivalent agent_id_equals condition.
+-- Created: 2026-02-03
+
+UPDATE trigger_conditions
+SET condition_type = 'agent_id_equals', operator = '='
+WHERE condition_type = 'field_match' AND field = 'agent_id' AND operator = 'equals';
@@ -0,0 +1,19 @@
+-- Migration: Credit low-balance alerts
+-- Description: An organization can set a low-water mark for its credit
+--              balance. The deduction that takes the balance below it queues
+--              one LowCreditBalance webhook delivery and records
+--              low_balance_notified_at, so further deductions stay quiet. A
+--              top-up back to the threshold (or a threshold change) clears it
+--              and re-arms the alert.
+-- Created: 2026-02-04
+
+ALTER TABLE credits
+    ADD COLUMN IF NOT EXISTS low_balance_threshold BIGINT,
+    ADD COLUMN IF NOT EXISTS low_balance_notified_at TIMESTAMPTZ;
+
+ALTER TABLE credits
+    ADD CONSTRAINT credits_low_balance_threshold_positive
+    CHECK (low_balance_threshold IS NULL OR low_balance_threshold > 0);
+
+COMMENT ON COLUMN credits.low_balance_threshold IS 'Balance (micro-USDC) below which a low-balance alert is sent; NULL disables alerts';
+COMMENT ON COLUMN credits.low_balance_notified_at IS 'When the current low-balance alert was sent; NULL while the alert is armed';
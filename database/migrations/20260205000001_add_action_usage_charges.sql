@@ -0,0 +1,25 @@
+-- Migration: Usage charges for action executions
+-- Description: Action workers deduct a per-action-type cost from the
+--              organization's credits after each successful execution,
+--              recorded as a 'usage' credit transaction referencing the job.
+--              A job is charged at most once, and an action the organization
+--              cannot pay for fails with the new 'billing' category.
+-- Created: 2026-02-05
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_credit_transactions_usage_reference_unique
+    ON credit_transactions(reference_id)
+    WHERE reference_id IS NOT NULL AND transaction_type = 'usage';
+
+ALTER TABLE action_results
+    DROP CONSTRAINT IF EXISTS chk_action_results_error_category;
+
+ALTER TABLE action_results
+    ADD CONSTRAINT chk_action_results_error_category CHECK (
+        error_category IS NULL OR error_category IN (
+            'timeout', 'connection', 'client_error', 'server_error', 'rate_limited',
+            'upstream', 'template', 'config', 'internal', 'billing'
+        )
+    );
+
+COMMENT ON INDEX idx_credit_transactions_usage_reference_unique IS 'Charges each action job at most once';
+COMMENT ON COLUMN action_results.error_category IS 'Broad failure cause (timeout, connection, client_error, server_error, rate_limited, upstream, template, config, internal, billing)';
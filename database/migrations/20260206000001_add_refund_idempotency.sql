@@ -0,0 +1,17 @@
+-- Migration: Idempotent Stripe refunds
+-- Description: Refunded or disputed Stripe purchases take their credits back
+--              through a 'refund' transaction keyed on the Stripe refund or
+--              dispute ID, so a redelivered webhook is applied once. Purchases
+--              record their payment intent in metadata, which is how refunds
+--              and disputes find the organization.
+-- Created: 2026-02-06
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_credit_transactions_refund_reference_unique
+    ON credit_transactions(reference_id)
+    WHERE reference_id IS NOT NULL AND transaction_type = 'refund';
+
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_purchase_payment_intent
+    ON credit_transactions((metadata->>'payment_intent_id'))
+    WHERE transaction_type = 'purchase';
+
+COMMENT ON INDEX idx_credit_transactions_refund_reference_unique IS 'Ensures each Stripe refund or dispute is applied once';
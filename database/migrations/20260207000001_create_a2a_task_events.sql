@@ -0,0 +1,24 @@
+-- Migration: A2A task progress events
+-- Description: Progress, completion and error events of A2A tasks, written by
+--              the task processor (and by cancellation) as tasks change state.
+--              The SSE stream at /api/v1/a2a/tasks/{id}/stream sends the event
+--              ID with each event, so a client reconnecting with Last-Event-ID
+--              is sent only the events it missed.
+-- Created: 2026-02-07
+
+CREATE TABLE IF NOT EXISTS a2a_task_events (
+    -- Monotonic: later events of a task always have larger IDs
+    id BIGSERIAL PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES a2a_tasks(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL CHECK (event_type IN ('progress', 'complete', 'error')),
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Replay a task's events after a given ID
+CREATE INDEX IF NOT EXISTS idx_a2a_task_events_task_id
+    ON a2a_task_events (task_id, id);
+
+COMMENT ON TABLE a2a_task_events IS 'Progress events of A2A tasks, replayed to SSE clients that reconnect';
+COMMENT ON COLUMN a2a_task_events.id IS 'SSE event ID (Last-Event-ID on reconnect)';
+COMMENT ON COLUMN a2a_task_events.data IS 'Task status at the time of the event (TaskGetResult)';
@@ -2,27 +2,111 @@
 //!
 //! Provides a trait-based abstraction for job consumption with blocking pop.
 //!
+//! # Reliable Queue
+//!
+//! Jobs are consumed with `RPOPLPUSH`, which atomically moves each job from the
+//! shared queue into a per-consumer processing list. The job stays there until
+//! the worker acknowledges it with [`JobConsumer::ack`], so a job that was popped
+//! but never finished (crash, shutdown timeout) can be returned to the queue
+//! with [`JobConsumer::requeue_in_flight`] instead of being lost.
+//!
+//! Every claim is also recorded with its timestamp in a shared sorted set so
+//! the [`crate::reaper`] can requeue jobs stuck in a processing list past the
+//! visibility timeout, e.g. when a worker instance disappears for good. The
+//! claim is written by the same script that moves the job, so no job is ever
+//! in a processing list without one.
+//!
+//! # Priority Lanes
+//!
+//! A queue is made of three lists, one per [`QueuePriority`]. The consumer
+//! takes the oldest job of the highest non-empty lane, so a high-priority job
+//! overtakes normal and low ones already waiting. To keep a flood of urgent
+//! jobs from starving the rest, every [`FAIRNESS_INTERVAL`]th job is taken
+//! from the lower lanes first (alternating normal-first and low-first).
+//!
+//! When every lane is empty the consumer blocks on the normal lane, checking
+//! the others again at least every [`LANE_BLOCK_SLICE`].
+//!
 //! # Security
 //!
 //! - Jobs have a TTL (time-to-live) to prevent processing of stale jobs
 //! - Expired jobs are rejected and not processed
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use async_trait::async_trait;
 use chrono::Utc;
 use redis::aio::MultiplexedConnection;
-use redis::AsyncCommands;
-use shared::{ActionJob, ACTION_JOBS_QUEUE};
+use redis::{AsyncCommands, Direction, Script};
+use shared::{ActionJob, QueuePriority, ACTION_JOBS_QUEUE};
 
 use crate::error::{WorkerError, WorkerResult};
 
 /// Default job TTL in seconds (1 hour)
 pub const DEFAULT_JOB_TTL_SECS: i64 = 3600;
 
+/// Key prefix for per-consumer processing lists
+pub const PROCESSING_LIST_PREFIX: &str = "action_jobs:processing";
+
+/// Sorted set of in-flight claims (`<processing list>|<job id>` scored by claim time)
+pub const CLAIMS_KEY: &str = "action_jobs:claims";
+
+/// Every this many jobs, the lower priority lanes are tried first
+pub const FAIRNESS_INTERVAL: u64 = 8;
+
+/// Longest block on the normal lane before the other lanes are checked again
+pub const LANE_BLOCK_SLICE: Duration = Duration::from_secs(1);
+
+/// Move the next job of the first non-empty lane into a processing list and
+/// record its claim
+///
+/// KEYS[1..n-2] = lanes in the order to try, KEYS[n-1] = processing list,
+/// KEYS[n] = claims set
+/// ARGV[1] = claim timestamp
+///
+/// A payload without a job ID gets no claim; the consumer drops it right away.
+const POP_SCRIPT: &str = r#"
+local processing = KEYS[#KEYS - 1]
+for i = 1, #KEYS - 2 do
+    local job = redis.call('RPOPLPUSH', KEYS[i], processing)
+    if job then
+        local ok, decoded = pcall(cjson.decode, job)
+        if ok and type(decoded) == 'table' and type(decoded.id) == 'string' then
+            redis.call('ZADD', KEYS[#KEYS], ARGV[1], processing .. '|' .. decoded.id)
+        end
+        return job
+    end
+end
+return false
+"#;
+
+/// Atomically move a payload from a processing list back to a queue lane
+///
+/// KEYS[1] = processing list, KEYS[2] = lane, KEYS[3] = claims set
+/// ARGV[1] = payload, ARGV[2] = claim member
+///
+/// Only requeues when LREM actually removed the payload, so a concurrent ack
+/// or a second reaper can't cause the job to be pushed twice. The payload goes
+/// to the consuming end of the lane so it is picked up next.
+pub(crate) const REQUEUE_SCRIPT: &str = r#"
+local removed = redis.call('LREM', KEYS[1], 1, ARGV[1])
+if removed == 1 then
+    redis.call('RPUSH', KEYS[2], ARGV[1])
+end
+redis.call('ZREM', KEYS[3], ARGV[2])
+return removed
+"#;
+
 /// Job consumer trait for testability
 #[async_trait]
 pub trait JobConsumer: Send + Sync {
     /// Block and wait for next job from queue
     ///
+    /// The returned job is held in flight until [`JobConsumer::ack`] is called.
+    ///
     /// # Arguments
     ///
     /// * `timeout_secs` - Maximum time to block waiting for a job
@@ -32,62 +116,220 @@ pub trait JobConsumer: Send + Sync {
     /// `Some(ActionJob)` if a job was received, `None` if timeout
     async fn consume(&self, timeout_secs: u64) -> WorkerResult<Option<ActionJob>>;
 
-    /// Get current queue length
+    /// Acknowledge a consumed job, removing it from the processing list
+    ///
+    /// Call this once the job has been handled (successfully or moved to the DLQ).
+    async fn ack(&self, job: &ActionJob) -> WorkerResult<()>;
+
+    /// Return every unacknowledged job in this consumer's processing list to
+    /// its priority lane
+    ///
+    /// # Returns
+    ///
+    /// Number of jobs requeued
+    async fn requeue_in_flight(&self) -> WorkerResult<u64>;
+
+    /// Get current queue length (all priority lanes)
     async fn queue_len(&self) -> WorkerResult<u64>;
 }
 
 /// Redis-backed job consumer implementation
+///
+/// Each worker should own a consumer with a unique `consumer_id`, which names
+/// its processing list. The ID must be stable across restarts of the same
+/// worker so it can recover its own in-flight jobs on startup.
 #[derive(Clone)]
 pub struct RedisJobConsumer {
     conn: MultiplexedConnection,
     queue_name: String,
+    processing_list: String,
+    claims_key: String,
+    /// Raw payloads of in-flight jobs by job ID (LREM needs the exact bytes)
+    in_flight: Arc<Mutex<HashMap<String, String>>>,
+    /// Jobs consumed so far, for the lane fairness rotation
+    consumed: Arc<AtomicU64>,
+    pop_script: Script,
+    requeue_script: Script,
 }
 
 impl RedisJobConsumer {
-    /// Create a new Redis job consumer
+    /// Create a consumer for a queue (already prefixed with [`shared::redis::queue_key`])
     ///
     /// # Arguments
     ///
     /// * `conn` - Multiplexed Redis connection
-    pub fn new(conn: MultiplexedConnection) -> Self {
+    /// * `queue_name` - Queue to consume, e.g. the shared action job queue
+    /// * `consumer_id` - Unique, restart-stable consumer identifier
+    pub fn with_queue_name(
+        conn: MultiplexedConnection,
+        queue_name: &str,
+        consumer_id: &str,
+    ) -> Self {
         Self {
             conn,
-            queue_name: ACTION_JOBS_QUEUE.to_string(),
+            queue_name: queue_name.to_string(),
+            processing_list: processing_list_key(queue_name, consumer_id),
+            claims_key: claims_key(queue_name),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            consumed: Arc::new(AtomicU64::new(0)),
+            pop_script: Script::new(POP_SCRIPT),
+            requeue_script: Script::new(REQUEUE_SCRIPT),
         }
     }
 
-    /// Create with custom queue name (for testing)
-    #[cfg(test)]
-    #[allow(dead_code)]
-    pub fn with_queue_name(conn: MultiplexedConnection, queue_name: &str) -> Self {
-        Self {
-            conn,
-            queue_name: queue_name.to_string(),
+    /// Take the next job by lane priority into the processing list and claim
+    /// it, without blocking
+    async fn pop_by_priority(&self) -> WorkerResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        let order = lane_order(self.consumed.load(Ordering::Relaxed));
+
+        let mut invocation = self.pop_script.prepare_invoke();
+        for priority in order {
+            invocation.key(priority.lane(&self.queue_name));
         }
+        invocation
+            .key(&self.processing_list)
+            .key(&self.claims_key)
+            .arg(Utc::now().timestamp())
+            .invoke_async(&mut conn)
+            .await
+            .map_err(WorkerError::Redis)
+    }
+
+    /// Take the next job into the processing list, waiting up to `timeout`
+    async fn pop(&self, timeout: Duration) -> WorkerResult<Option<String>> {
+        let deadline = Instant::now() + timeout;
+        let mut conn = self.conn.clone();
+
+        loop {
+            if let Some(json) = self.pop_by_priority().await? {
+                return Ok(Some(json));
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            // Wait for a job without taking it: moving the tail of the lane
+            // onto itself leaves the lane unchanged. The job is then taken by
+            // the pop script, which claims it in the same step. Only one list
+            // can be watched, so block on the lane that gets most jobs.
+            let _: Option<String> = conn
+                .blmove(
+                    &self.queue_name,
+                    &self.queue_name,
+                    Direction::Right,
+                    Direction::Right,
+                    remaining.min(LANE_BLOCK_SLICE).as_secs_f64(),
+                )
+                .await
+                .map_err(WorkerError::Redis)?;
+        }
+    }
+
+    /// Name of this consumer's processing list
+    #[cfg(test)]
+    pub fn processing_list(&self) -> &str {
+        &self.processing_list
+    }
+
+    /// Remove a raw payload from the processing list
+    async fn remove_from_processing(&self, payload: &str) -> WorkerResult<()> {
+        let mut conn = self.conn.clone();
+        conn.lrem::<_, _, ()>(&self.processing_list, 1, payload)
+            .await
+            .map_err(WorkerError::Redis)
+    }
+
+    /// Drop the visibility-timeout claim for a job
+    async fn release_claim(&self, job_id: &str) -> WorkerResult<()> {
+        let mut conn = self.conn.clone();
+        conn.zrem::<_, _, ()>(
+            &self.claims_key,
+            claim_member(&self.processing_list, job_id),
+        )
+        .await
+        .map_err(WorkerError::Redis)
+    }
+}
+
+/// Order in which the priority lanes are tried for the next job
+///
+/// # Arguments
+///
+/// * `consumed` - Jobs this consumer has taken so far
+pub fn lane_order(consumed: u64) -> [QueuePriority; 3] {
+    if consumed % FAIRNESS_INTERVAL != FAIRNESS_INTERVAL - 1 {
+        return QueuePriority::ALL;
+    }
+    if (consumed / FAIRNESS_INTERVAL).is_multiple_of(2) {
+        [
+            QueuePriority::Normal,
+            QueuePriority::Low,
+            QueuePriority::High,
+        ]
+    } else {
+        [
+            QueuePriority::Low,
+            QueuePriority::Normal,
+            QueuePriority::High,
+        ]
+    }
+}
+
+/// Build the claims sorted set key for a queue
+pub fn claims_key(queue_name: &str) -> String {
+    if queue_name == ACTION_JOBS_QUEUE {
+        CLAIMS_KEY.to_string()
+    } else {
+        format!("{}:claims", queue_name)
+    }
+}
+
+/// Build the claims set member for a job held in a processing list
+pub fn claim_member(processing_list: &str, job_id: &str) -> String {
+    format!("{}|{}", processing_list, job_id)
+}
+
+/// Split a claims set member into (processing list, job id)
+pub fn parse_claim_member(member: &str) -> Option<(&str, &str)> {
+    member.rsplit_once('|')
+}
+
+/// Build the processing list key for a consumer
+///
+/// The default queue uses [`PROCESSING_LIST_PREFIX`]; custom queues get their
+/// own namespace so tests never touch production lists.
+pub fn processing_list_key(queue_name: &str, consumer_id: &str) -> String {
+    if queue_name == ACTION_JOBS_QUEUE {
+        format!("{}:{}", PROCESSING_LIST_PREFIX, consumer_id)
+    } else {
+        format!("{}:processing:{}", queue_name, consumer_id)
     }
 }
 
 #[async_trait]
 impl JobConsumer for RedisJobConsumer {
     async fn consume(&self, timeout_secs: u64) -> WorkerResult<Option<ActionJob>> {
-        let mut conn = self.conn.clone();
-
-        // BRPOP blocks until a job is available or timeout
-        // Returns (queue_name, value) tuple
-        let result: Option<(String, String)> = conn
-            .brpop(&self.queue_name, timeout_secs as f64)
-            .await
-            .map_err(WorkerError::Redis)?;
+        let result = self.pop(Duration::from_secs(timeout_secs)).await?;
 
         match result {
-            Some((_, json)) => {
-                let job: ActionJob = serde_json::from_str(&json).map_err(|e| {
-                    tracing::warn!(
-                        error = %e,
-                        "Failed to parse job JSON from queue (payload omitted for security)"
-                    );
-                    WorkerError::Serialization(e)
-                })?;
+            Some(json) => {
+                self.consumed.fetch_add(1, Ordering::Relaxed);
+
+                let job: ActionJob = match serde_json::from_str(&json) {
+                    Ok(job) => job,
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            "Failed to parse job JSON from queue (payload omitted for security)"
+                        );
+                        // A malformed payload can never succeed, don't keep it in flight
+                        self.remove_from_processing(&json).await?;
+                        return Err(WorkerError::Serialization(e));
+                    }
+                };
 
                 // Check job TTL (security: reject stale jobs)
                 let age_secs = (Utc::now() - job.created_at).num_seconds();
@@ -98,6 +340,8 @@ impl JobConsumer for RedisJobConsumer {
                         ttl_secs = DEFAULT_JOB_TTL_SECS,
                         "Job expired, skipping"
                     );
+                    self.remove_from_processing(&json).await?;
+                    self.release_claim(&job.id).await?;
                     return Ok(None); // Treat as no job available
                 }
 
@@ -106,9 +350,15 @@ impl JobConsumer for RedisJobConsumer {
                     trigger_id = %job.trigger_id,
                     action_type = %job.action_type,
                     age_secs = age_secs,
+                    processing_list = %self.processing_list,
                     "Consumed job from queue"
                 );
 
+                self.in_flight
+                    .lock()
+                    .expect("in-flight map poisoned")
+                    .insert(job.id.clone(), json);
+
                 Ok(Some(job))
             }
             None => {
@@ -118,13 +368,87 @@ impl JobConsumer for RedisJobConsumer {
         }
     }
 
+    async fn ack(&self, job: &ActionJob) -> WorkerResult<()> {
+        let payload = self
+            .in_flight
+            .lock()
+            .expect("in-flight map poisoned")
+            .remove(&job.id);
+
+        match payload {
+            Some(payload) => {
+                self.remove_from_processing(&payload).await?;
+                self.release_claim(&job.id).await
+            }
+            None => Err(WorkerError::JobNotFound(job.id.clone())),
+        }
+    }
+
+    async fn requeue_in_flight(&self) -> WorkerResult<u64> {
+        let mut conn = self.conn.clone();
+        let payloads: Vec<String> = conn
+            .lrange(&self.processing_list, 0, -1)
+            .await
+            .map_err(WorkerError::Redis)?;
+
+        // Each job is moved atomically, so a crash during recovery can't lose
+        // jobs either. The list is newest first: the oldest job is pushed last
+        // and is first in line again.
+        let mut requeued = 0;
+        for payload in payloads {
+            let job: ActionJob = match serde_json::from_str(&payload) {
+                Ok(job) => job,
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        processing_list = %self.processing_list,
+                        "Dropping unparseable job from processing list (payload omitted for security)"
+                    );
+                    self.remove_from_processing(&payload).await?;
+                    continue;
+                }
+            };
+
+            let removed: i64 = self
+                .requeue_script
+                .key(&self.processing_list)
+                .key(job.queue_priority().lane(&self.queue_name))
+                .key(&self.claims_key)
+                .arg(&payload)
+                .arg(claim_member(&self.processing_list, &job.id))
+                .invoke_async(&mut conn)
+                .await
+                .map_err(WorkerError::Redis)?;
+            requeued += removed as u64;
+        }
+
+        self.in_flight
+            .lock()
+            .expect("in-flight map poisoned")
+            .clear();
+
+        if requeued > 0 {
+            tracing::warn!(
+                processing_list = %self.processing_list,
+                requeued = requeued,
+                "Returned unacknowledged jobs to the queue"
+            );
+        }
+
+        Ok(requeued)
+    }
+
     async fn queue_len(&self) -> WorkerResult<u64> {
         let mut conn = self.conn.clone();
-        let len: u64 = conn
-            .llen(&self.queue_name)
+        let mut pipe = redis::pipe();
+        for priority in QueuePriority::ALL {
+            pipe.llen(priority.lane(&self.queue_name));
+        }
+        let lens: Vec<u64> = pipe
+            .query_async(&mut conn)
             .await
             .map_err(WorkerError::Redis)?;
-        Ok(len)
+        Ok(lens.into_iter().sum())
     }
 }
 
@@ -140,6 +464,8 @@ mod tests {
         #[async_trait]
         impl JobConsumer for JobConsumer {
             async fn consume(&self, timeout_secs: u64) -> WorkerResult<Option<ActionJob>>;
+            async fn ack(&self, job: &ActionJob) -> WorkerResult<()>;
+            async fn requeue_in_flight(&self) -> WorkerResult<u64>;
             async fn queue_len(&self) -> WorkerResult<u64>;
         }
     }
@@ -184,4 +510,253 @@ mod tests {
         let len = mock.queue_len().await.unwrap();
         assert_eq!(len, 42);
     }
+
+    #[test]
+    fn test_claim_member_round_trip() {
+        let member = claim_member("action_jobs:processing:host-a:0", "job-1");
+        assert_eq!(
+            parse_claim_member(&member),
+            Some(("action_jobs:processing:host-a:0", "job-1"))
+        );
+        assert_eq!(claims_key(ACTION_JOBS_QUEUE), CLAIMS_KEY);
+        assert_eq!(claims_key("test_queue"), "test_queue:claims");
+    }
+
+    #[test]
+    fn test_processing_list_key() {
+        assert_eq!(
+            processing_list_key(ACTION_JOBS_QUEUE, "host-a:0"),
+            "action_jobs:processing:host-a:0"
+        );
+        assert_eq!(
+            processing_list_key("test_queue", "host-a:0"),
+            "test_queue:processing:host-a:0"
+        );
+    }
+
+    #[test]
+    fn test_prefixed_queue_keys_stay_in_environment() {
+        let queue = shared::redis::KeySpace::new(Some("staging"))
+            .unwrap()
+            .queue(ACTION_JOBS_QUEUE);
+        assert_eq!(
+            processing_list_key(&queue, "host-a:0"),
+            "staging:action_jobs:processing:host-a:0"
+        );
+        assert_eq!(claims_key(&queue), "staging:action_jobs:claims");
+    }
+
+    #[test]
+    fn test_lane_order_serves_lower_lanes_periodically() {
+        let orders: Vec<_> = (0..2 * FAIRNESS_INTERVAL).map(lane_order).collect();
+        let high_first = orders
+            .iter()
+            .filter(|order| **order == QueuePriority::ALL)
+            .count() as u64;
+        assert_eq!(high_first, 2 * (FAIRNESS_INTERVAL - 1));
+
+        let fairness_turn = (FAIRNESS_INTERVAL - 1) as usize;
+        assert_eq!(orders[fairness_turn][0], QueuePriority::Normal);
+        assert_eq!(
+            orders[fairness_turn + FAIRNESS_INTERVAL as usize][0],
+            QueuePriority::Low
+        );
+        // High still comes before an empty lower lane is waited on
+        assert_eq!(orders[fairness_turn][2], QueuePriority::High);
+    }
+
+    async fn redis_conn() -> MultiplexedConnection {
+        let url =
+            std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        redis::Client::open(url)
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .expect("Redis must be running for this test")
+    }
+
+    fn create_test_job() -> ActionJob {
+        create_test_job_with_priority(1)
+    }
+
+    fn create_test_job_with_priority(priority: i32) -> ActionJob {
+        ActionJob::new(
+            "trigger-1",
+            "event-1",
+            shared::ActionType::Rest,
+            priority,
+            serde_json::json!({"url": "https://example.com"}),
+            serde_json::json!({"agent_id": 42}),
+        )
+    }
+
+    /// Enqueue like the event processor: LPUSH onto the job's lane
+    async fn enqueue(conn: &mut MultiplexedConnection, queue: &str, job: &ActionJob) {
+        conn.lpush::<_, _, ()>(
+            job.queue_priority().lane(queue),
+            serde_json::to_string(job).unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_consume_holds_job_until_ack() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+
+        let job = create_test_job();
+        conn.lpush::<_, _, ()>(&queue, serde_json::to_string(&job).unwrap())
+            .await
+            .unwrap();
+
+        let consumed = consumer.consume(1).await.unwrap().unwrap();
+        assert_eq!(consumed.id, job.id);
+
+        // In flight: gone from the queue but present in the processing list,
+        // already claimed for the reaper
+        let processing: u64 = conn.llen(consumer.processing_list()).await.unwrap();
+        assert_eq!(consumer.queue_len().await.unwrap(), 0);
+        assert_eq!(processing, 1);
+        let member = claim_member(consumer.processing_list(), &job.id);
+        let claimed: Option<i64> = conn.zscore(claims_key(&queue), &member).await.unwrap();
+        assert!(claimed.is_some());
+
+        consumer.ack(&consumed).await.unwrap();
+        let processing: u64 = conn.llen(consumer.processing_list()).await.unwrap();
+        assert_eq!(processing, 0);
+        let claimed: Option<i64> = conn.zscore(claims_key(&queue), &member).await.unwrap();
+        assert!(claimed.is_none());
+
+        // Acking twice is reported
+        assert!(consumer.ack(&consumed).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_unacked_job_is_requeued() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let job = create_test_job();
+        conn.lpush::<_, _, ()>(&queue, serde_json::to_string(&job).unwrap())
+            .await
+            .unwrap();
+
+        // First consumer takes the job and "crashes" without acking
+        let crashed = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+        let taken = crashed.consume(1).await.unwrap().unwrap();
+        drop(crashed);
+
+        // The restarted worker (same consumer ID) recovers it
+        let restarted = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+        assert_eq!(restarted.requeue_in_flight().await.unwrap(), 1);
+        assert_eq!(restarted.queue_len().await.unwrap(), 1);
+
+        let redelivered = restarted.consume(1).await.unwrap().unwrap();
+        assert_eq!(redelivered.id, taken.id);
+        restarted.ack(&redelivered).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_requeued_jobs_keep_their_lane() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+
+        let high = create_test_job_with_priority(10);
+        let low = create_test_job_with_priority(0);
+        enqueue(&mut conn, &queue, &high).await;
+        enqueue(&mut conn, &queue, &low).await;
+        consumer.consume(1).await.unwrap().unwrap();
+        consumer.consume(1).await.unwrap().unwrap();
+
+        assert_eq!(consumer.requeue_in_flight().await.unwrap(), 2);
+        for (priority, job) in [(QueuePriority::High, &high), (QueuePriority::Low, &low)] {
+            let lane: Vec<String> = conn.lrange(priority.lane(&queue), 0, -1).await.unwrap();
+            assert_eq!(lane, vec![serde_json::to_string(job).unwrap()]);
+        }
+        // Their claims are released with them
+        let claims: u64 = conn.zcard(claims_key(&queue)).await.unwrap();
+        assert_eq!(claims, 0);
+
+        let _: () = conn
+            .del(&[
+                QueuePriority::High.lane(&queue),
+                QueuePriority::Low.lane(&queue),
+            ])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_high_priority_job_is_consumed_first() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+
+        let low = create_test_job_with_priority(0);
+        let normal = create_test_job_with_priority(1);
+        let high = create_test_job_with_priority(10);
+        enqueue(&mut conn, &queue, &low).await;
+        enqueue(&mut conn, &queue, &normal).await;
+        enqueue(&mut conn, &queue, &high).await;
+        assert_eq!(consumer.queue_len().await.unwrap(), 3);
+
+        for expected in [&high, &normal, &low] {
+            let consumed = consumer.consume(1).await.unwrap().unwrap();
+            assert_eq!(consumed.id, expected.id);
+            consumer.ack(&consumed).await.unwrap();
+        }
+        assert_eq!(consumer.queue_len().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_lower_lanes_are_not_starved() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+
+        let low = create_test_job_with_priority(0);
+        enqueue(&mut conn, &queue, &low).await;
+        for _ in 0..2 * FAIRNESS_INTERVAL {
+            enqueue(&mut conn, &queue, &create_test_job_with_priority(10)).await;
+        }
+
+        let mut position = None;
+        for i in 0..FAIRNESS_INTERVAL {
+            let consumed = consumer.consume(1).await.unwrap().unwrap();
+            consumer.ack(&consumed).await.unwrap();
+            if consumed.id == low.id {
+                position = Some(i);
+            }
+        }
+        assert_eq!(position, Some(FAIRNESS_INTERVAL - 1));
+
+        let _: () = conn.del(QueuePriority::High.lane(&queue)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_consume_wakes_for_job_on_any_lane() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker-0");
+
+        let high = create_test_job_with_priority(10);
+        let waiting = tokio::spawn({
+            let consumer = consumer.clone();
+            async move { consumer.consume(3).await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        enqueue(&mut conn, &queue, &high).await;
+
+        let consumed = waiting.await.unwrap().unwrap().unwrap();
+        assert_eq!(consumed.id, high.id);
+        consumer.ack(&consumed).await.unwrap();
+    }
 }
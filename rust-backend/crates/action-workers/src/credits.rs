@@ -0,0 +1,419 @@
+//! Usage-based billing of action executions
+//!
+//! Each successful action costs its organization the price of its type, in
+//! micro-USDC (`ACTION_COST_<TYPE>`, see [`ActionCostsConfig`]). The event
+//! processor does not enqueue actions the organization cannot pay for; before
+//! running a job, [`ActionBilling::authorize`] checks again, failing closed for
+//! balances that dropped since and for jobs replayed from the DLQ. Once the
+//! job succeeds, [`ActionBilling::charge`] deducts the cost and records a
+//! `usage` credit transaction referencing the job, at most once per job.
+//!
+//! A charge behaves like `CreditRepository::deduct_credits` in the API
+//! gateway: the one taking the balance below the organization's low-balance
+//! threshold queues its `LowCreditBalance` webhook deliveries.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared::{ActionCostsConfig, ActionJob};
+use sqlx::PgPool;
+
+use crate::error::{WorkerError, WorkerResult};
+use crate::metrics;
+
+/// Event type of low-balance webhook deliveries (as in the API gateway)
+const LOW_BALANCE_EVENT_TYPE: &str = "LowCreditBalance";
+
+/// Stands in for the trigger ID of low-balance webhook deliveries
+const LOW_BALANCE_SOURCE: &str = "credits";
+
+/// Outcome of [`CreditLedger::charge`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charge {
+    /// Deducted; the new balance
+    Charged(i64),
+    /// The job was charged before (it ran again after a requeue)
+    AlreadyCharged,
+    /// The organization has no credits, or fewer than the cost
+    Insufficient,
+}
+
+/// Credit balances and usage charges of organizations
+#[async_trait]
+pub trait CreditLedger: Send + Sync {
+    /// Balance of the job's organization (0 if it has no credits)
+    ///
+    /// `organization_id` is absent on jobs from older producers, in which case
+    /// the organization is resolved from the trigger.
+    async fn balance(&self, organization_id: Option<&str>, trigger_id: &str) -> WorkerResult<i64>;
+
+    /// Deduct `amount` for `job` and record the usage transaction
+    async fn charge(&self, job: &ActionJob, amount: i64) -> WorkerResult<Charge>;
+}
+
+/// PostgreSQL-backed credit ledger (`credits` and `credit_transactions`)
+pub struct PostgresCreditLedger {
+    pool: PgPool,
+}
+
+impl PostgresCreditLedger {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CreditLedger for PostgresCreditLedger {
+    async fn balance(&self, organization_id: Option<&str>, trigger_id: &str) -> WorkerResult<i64> {
+        let balance: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT balance FROM credits
+            WHERE organization_id = COALESCE(
+                $1, (SELECT organization_id FROM triggers WHERE id = $2)
+            )
+            "#,
+        )
+        .bind(organization_id)
+        .bind(trigger_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(balance.unwrap_or(0))
+    }
+
+    async fn charge(&self, job: &ActionJob, amount: i64) -> WorkerResult<Charge> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the balance first, so the duplicate check below sees a
+        // concurrent charge of the same job
+        let locked: Option<(String, i64, bool)> = sqlx::query_as(
+            r#"
+            SELECT organization_id, balance, low_balance_notified_at IS NULL
+            FROM credits
+            WHERE organization_id = COALESCE(
+                $1, (SELECT organization_id FROM triggers WHERE id = $2)
+            )
+            FOR UPDATE
+            "#,
+        )
+        .bind(job.organization_id.as_deref())
+        .bind(&job.trigger_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some((organization_id, balance, armed)) = locked else {
+            return Ok(Charge::Insufficient);
+        };
+
+        let already_charged: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM credit_transactions
+                WHERE reference_id = $1 AND transaction_type = 'usage'
+            )
+            "#,
+        )
+        .bind(&job.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if already_charged {
+            return Ok(Charge::AlreadyCharged);
+        }
+        if balance < amount {
+            return Ok(Charge::Insufficient);
+        }
+
+        let metadata = serde_json::json!({
+            "trigger_id": job.trigger_id,
+            "action_id": job.action_id,
+            "event_id": job.event_id,
+            "action_type": job.action_type,
+        });
+
+        let new_balance: i64 = sqlx::query_scalar(
+            r#"
+            WITH updated AS (
+                UPDATE credits
+                SET balance = balance - $2,
+                    low_balance_notified_at = CASE
+                        WHEN $3 AND balance - $2 < low_balance_threshold THEN NOW()
+                        ELSE low_balance_notified_at
+                    END,
+                    updated_at = NOW()
+                WHERE organization_id = $1
+                RETURNING organization_id, balance, low_balance_threshold,
+                          low_balance_notified_at,
+                          $3 AND low_balance_notified_at IS NOT NULL AS low_balance_alert
+            ),
+            recorded AS (
+                INSERT INTO credit_transactions (
+                    organization_id, amount, transaction_type,
+                    description, reference_id, balance_after, metadata, created_at
+                )
+                SELECT organization_id, -$2, 'usage', $4, $5, balance, $6, NOW()
+                FROM updated
+            ),
+            alerts AS (
+                INSERT INTO webhook_deliveries (webhook_id, trigger_id, event_id, event_type, payload)
+                SELECT w.id, $7,
+                       u.organization_id || ':' || EXTRACT(EPOCH FROM u.low_balance_notified_at)::TEXT,
+                       $8,
+                       jsonb_build_object(
+                           'type', 'credits.low_balance',
+                           'fired_at', u.low_balance_notified_at,
+                           'organization_id', u.organization_id,
+                           'balance', u.balance,
+                           'threshold', u.low_balance_threshold,
+                           'currency', 'USDC'
+                       )
+                FROM updated u
+                JOIN webhooks w ON w.organization_id = u.organization_id
+                WHERE u.low_balance_alert
+                  AND w.enabled = TRUE
+                  AND (cardinality(w.event_types) = 0 OR $8 = ANY(w.event_types))
+                ON CONFLICT (webhook_id, trigger_id, event_id) DO NOTHING
+            )
+            SELECT balance FROM updated
+            "#,
+        )
+        .bind(&organization_id)
+        .bind(amount)
+        .bind(armed)
+        .bind(format!("{} action", job.action_type))
+        .bind(&job.id)
+        .bind(metadata)
+        .bind(LOW_BALANCE_SOURCE)
+        .bind(LOW_BALANCE_EVENT_TYPE)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Charge::Charged(new_balance))
+    }
+}
+
+/// Checks and charges the cost of jobs (see the [module docs](self))
+pub struct ActionBilling {
+    ledger: Arc<dyn CreditLedger>,
+    costs: ActionCostsConfig,
+}
+
+impl ActionBilling {
+    pub fn new(ledger: Arc<dyn CreditLedger>, costs: ActionCostsConfig) -> Self {
+        Self { ledger, costs }
+    }
+
+    /// Fail a job whose organization cannot pay for it
+    ///
+    /// If the balance cannot be read, the job runs: the event processor
+    /// already checked it when enqueueing.
+    pub async fn authorize(&self, job: &ActionJob) -> Result<(), WorkerError> {
+        let cost = self.costs.cost(&job.action_type);
+        if cost == 0 {
+            return Ok(());
+        }
+
+        let balance = match self
+            .ledger
+            .balance(job.organization_id.as_deref(), &job.trigger_id)
+            .await
+        {
+            Ok(balance) => balance,
+            Err(e) => {
+                tracing::error!(
+                    job_id = %job.id,
+                    error = %e,
+                    error_id = "CREDIT_BALANCE_CHECK_FAILED",
+                    "Failed to check credit balance, running the action anyway"
+                );
+                return Ok(());
+            }
+        };
+
+        if balance < cost {
+            metrics::record_credit_charge("blocked");
+            return Err(WorkerError::insufficient_credits(format!(
+                "balance {} is below the {} action cost of {} (micro-USDC)",
+                balance, job.action_type, cost
+            )));
+        }
+        Ok(())
+    }
+
+    /// Deduct the cost of a job that succeeded
+    ///
+    /// Never fails the job: the action already ran.
+    pub async fn charge(&self, job: &ActionJob) {
+        let cost = self.costs.cost(&job.action_type);
+        if cost == 0 {
+            return;
+        }
+
+        match self.ledger.charge(job, cost).await {
+            Ok(Charge::Charged(balance)) => {
+                metrics::record_credit_charge("charged");
+                tracing::debug!(job_id = %job.id, cost = cost, balance = balance, "Charged action");
+            }
+            Ok(Charge::AlreadyCharged) => {
+                tracing::debug!(job_id = %job.id, "Action already charged");
+            }
+            Ok(Charge::Insufficient) => {
+                metrics::record_credit_charge("failed");
+                tracing::warn!(
+                    job_id = %job.id,
+                    cost = cost,
+                    error_id = "CREDIT_CHARGE_FAILED",
+                    "Balance no longer covers the action it ran, not charged"
+                );
+            }
+            Err(e) => {
+                metrics::record_credit_charge("failed");
+                tracing::error!(
+                    job_id = %job.id,
+                    cost = cost,
+                    error = %e,
+                    error_id = "CREDIT_CHARGE_FAILED",
+                    "Failed to charge action"
+                );
+            }
+        }
+    }
+}
+
+/// In-memory credit ledger keyed by organization (tests)
+#[cfg(test)]
+#[derive(Default)]
+pub struct InMemoryCreditLedger {
+    balances: std::sync::Mutex<std::collections::HashMap<String, i64>>,
+    charged_jobs: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl InMemoryCreditLedger {
+    pub fn with_balance(organization_id: &str, balance: i64) -> Self {
+        let ledger = Self::default();
+        ledger
+            .balances
+            .lock()
+            .unwrap()
+            .insert(organization_id.to_string(), balance);
+        ledger
+    }
+
+    pub fn balance_of(&self, organization_id: &str) -> i64 {
+        self.balances
+            .lock()
+            .unwrap()
+            .get(organization_id)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl CreditLedger for InMemoryCreditLedger {
+    async fn balance(&self, organization_id: Option<&str>, _trigger_id: &str) -> WorkerResult<i64> {
+        Ok(organization_id.map_or(0, |org| self.balance_of(org)))
+    }
+
+    async fn charge(&self, job: &ActionJob, amount: i64) -> WorkerResult<Charge> {
+        let mut charged_jobs = self.charged_jobs.lock().unwrap();
+        if charged_jobs.contains(&job.id) {
+            return Ok(Charge::AlreadyCharged);
+        }
+        let mut balances = self.balances.lock().unwrap();
+        let Some(balance) = job
+            .organization_id
+            .as_ref()
+            .and_then(|org| balances.get_mut(org))
+            .filter(|balance| **balance >= amount)
+        else {
+            return Ok(Charge::Insufficient);
+        };
+        *balance -= amount;
+        charged_jobs.push(job.id.clone());
+        Ok(Charge::Charged(*balance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::ActionType;
+
+    fn job(action_type: ActionType) -> ActionJob {
+        ActionJob::new(
+            "trigger-1",
+            "event-1",
+            action_type,
+            1,
+            serde_json::json!({}),
+            serde_json::json!({}),
+        )
+        .with_organization_id("org-1")
+    }
+
+    fn billing(ledger: Arc<InMemoryCreditLedger>) -> ActionBilling {
+        let costs = ActionCostsConfig {
+            telegram: 2_000,
+            rest: 0,
+            mcp: 5_000,
+        };
+        ActionBilling::new(ledger, costs)
+    }
+
+    #[tokio::test]
+    async fn test_authorize_blocks_organization_that_cannot_pay() {
+        let ledger = Arc::new(InMemoryCreditLedger::with_balance("org-1", 4_999));
+        let billing = billing(ledger);
+
+        assert!(billing.authorize(&job(ActionType::Telegram)).await.is_ok());
+
+        let err = billing.authorize(&job(ActionType::Mcp)).await.unwrap_err();
+        assert!(matches!(err, WorkerError::InsufficientCredits(_)));
+        assert_eq!(
+            err.to_string(),
+            "Insufficient credits: balance 4999 is below the mcp action cost of 5000 (micro-USDC)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorize_blocks_zero_balance_but_not_free_actions() {
+        let billing = billing(Arc::new(InMemoryCreditLedger::default()));
+
+        assert!(billing.authorize(&job(ActionType::Telegram)).await.is_err());
+        assert!(billing.authorize(&job(ActionType::Rest)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_charge_deducts_cost_once_per_job() {
+        let ledger = Arc::new(InMemoryCreditLedger::with_balance("org-1", 10_000));
+        let billing = billing(ledger.clone());
+        let telegram = job(ActionType::Telegram);
+
+        billing.charge(&telegram).await;
+        assert_eq!(ledger.balance_of("org-1"), 8_000);
+
+        // A requeued job that ran again is not charged twice
+        billing.charge(&telegram).await;
+        assert_eq!(ledger.balance_of("org-1"), 8_000);
+
+        billing.charge(&job(ActionType::Mcp)).await;
+        assert_eq!(ledger.balance_of("org-1"), 3_000);
+
+        // Free action types are not charged
+        billing.charge(&job(ActionType::Rest)).await;
+        assert_eq!(ledger.balance_of("org-1"), 3_000);
+    }
+
+    #[tokio::test]
+    async fn test_charge_leaves_balance_that_no_longer_covers_cost() {
+        let ledger = Arc::new(InMemoryCreditLedger::with_balance("org-1", 1_000));
+        billing(ledger.clone())
+            .charge(&job(ActionType::Telegram))
+            .await;
+        assert_eq!(ledger.balance_of("org-1"), 1_000);
+    }
+}
@@ -1,44 +1,129 @@
 //! Dead Letter Queue (DLQ) for failed jobs
 //!
 //! Jobs that fail after all retries are moved to the DLQ for manual review.
+//!
+//! # Retention
+//!
+//! Old failures are rarely actionable, so [`DlqTrimmer`] periodically drops
+//! entries older than `DLQ_RETENTION_DAYS` and caps the queue at
+//! `DLQ_MAX_ENTRIES`. Trimming always removes the oldest entries first, so
+//! [`DeadLetterQueue::recent`] keeps returning the latest failures for replay.
+//!
+//! Entries are inspected and replayed from the API gateway through
+//! [`shared::dlq::DlqAccessor`].
 
 #![allow(dead_code)]
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use redis::aio::MultiplexedConnection;
-use redis::AsyncCommands;
-use serde::{Deserialize, Serialize};
-use shared::{ActionJob, ACTION_JOBS_DLQ};
+use redis::{AsyncCommands, Script};
+use shared::redis::queue_key;
+use shared::ACTION_JOBS_DLQ;
+use tokio_util::sync::CancellationToken;
 
 use crate::error::{WorkerError, WorkerResult};
 use crate::metrics;
 
-/// Entry in the Dead Letter Queue
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DlqEntry {
-    /// Original job that failed
-    pub job: ActionJob,
-    /// Error message from the last failure
-    pub error: String,
-    /// Number of attempts made
-    pub attempts: u32,
-    /// When the job was moved to DLQ
-    pub failed_at: DateTime<Utc>,
+pub use shared::dlq::DlqEntry;
+
+/// Default retention for DLQ entries in days
+pub const DEFAULT_DLQ_RETENTION_DAYS: u64 = 14;
+
+/// Default maximum number of DLQ entries kept
+pub const DEFAULT_DLQ_MAX_ENTRIES: u64 = 10_000;
+
+/// Default interval between DLQ trims in seconds
+pub const DEFAULT_DLQ_TRIM_INTERVAL_SECS: u64 = 300;
+
+/// Number of entries inspected per round of age-based trimming
+const TRIM_BATCH_SIZE: isize = 100;
+
+/// Pop expired entries off the oldest end of the DLQ
+///
+/// KEYS[1] = DLQ list, ARGV = expired payloads, oldest first
+///
+/// Stops at the first entry that is no longer the expected payload, so an
+/// entry popped concurrently by replay tooling never causes a newer one to be
+/// dropped in its place.
+const TRIM_EXPIRED_SCRIPT: &str = r#"
+local removed = 0
+for _, payload in ipairs(ARGV) do
+    if redis.call('LINDEX', KEYS[1], -1) ~= payload then
+        break
+    end
+    redis.call('RPOP', KEYS[1])
+    removed = removed + 1
+end
+return removed
+"#;
+
+/// Keep only the newest ARGV[1] entries of the DLQ
+///
+/// KEYS[1] = DLQ list, ARGV[1] = max entries
+const TRIM_OVERFLOW_SCRIPT: &str = r#"
+local max = tonumber(ARGV[1])
+local len = redis.call('LLEN', KEYS[1])
+if len <= max then
+    return 0
+end
+redis.call('LTRIM', KEYS[1], 0, max - 1)
+return len - max
+"#;
+
+/// Retention policy for DLQ entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DlqRetention {
+    /// Drop entries that failed longer ago than this (`None` keeps them)
+    pub max_age: Option<chrono::Duration>,
+    /// Keep at most this many entries, dropping the oldest (`None` is unbounded)
+    pub max_entries: Option<u64>,
 }
 
-impl DlqEntry {
-    /// Create a new DLQ entry
-    pub fn new(job: ActionJob, error: String, attempts: u32) -> Self {
+impl Default for DlqRetention {
+    fn default() -> Self {
         Self {
-            job,
-            error,
-            attempts,
-            failed_at: Utc::now(),
+            max_age: Some(chrono::Duration::days(DEFAULT_DLQ_RETENTION_DAYS as i64)),
+            max_entries: Some(DEFAULT_DLQ_MAX_ENTRIES),
         }
     }
 }
 
+impl DlqRetention {
+    /// Read the policy from `DLQ_RETENTION_DAYS` and `DLQ_MAX_ENTRIES`
+    ///
+    /// Either limit can be disabled by setting it to 0.
+    pub fn from_env() -> Self {
+        let env_u64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+
+        let days = env_u64("DLQ_RETENTION_DAYS").unwrap_or(DEFAULT_DLQ_RETENTION_DAYS);
+        let max_entries = env_u64("DLQ_MAX_ENTRIES").unwrap_or(DEFAULT_DLQ_MAX_ENTRIES);
+
+        Self {
+            max_age: (days > 0).then(|| chrono::Duration::days(days as i64)),
+            max_entries: (max_entries > 0).then_some(max_entries),
+        }
+    }
+
+    /// Whether an entry that failed at `failed_at` is past the retention age
+    fn is_expired(&self, failed_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.max_age
+            .is_some_and(|max_age| failed_at < now - max_age)
+    }
+}
+
+/// Entries dropped by one trim pass
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrimReport {
+    /// Entries older than the retention age
+    pub expired: u64,
+    /// Oldest entries dropped to stay within the entry cap
+    pub overflow: u64,
+}
+
 /// Dead Letter Queue trait for testability
 #[async_trait]
 pub trait DeadLetterQueue: Send + Sync {
@@ -53,6 +138,13 @@ pub trait DeadLetterQueue: Send + Sync {
 
     /// Peek at the first job in the DLQ without removing it
     async fn peek(&self) -> WorkerResult<Option<DlqEntry>>;
+
+    /// Get up to `limit` of the most recent entries, newest first, without
+    /// removing them (for replay tooling)
+    async fn recent(&self, limit: usize) -> WorkerResult<Vec<DlqEntry>>;
+
+    /// Drop entries outside the retention policy, oldest first
+    async fn trim(&self, retention: &DlqRetention, now: DateTime<Utc>) -> WorkerResult<TrimReport>;
 }
 
 /// Redis-backed Dead Letter Queue
@@ -67,7 +159,7 @@ impl RedisDlq {
     pub fn new(conn: MultiplexedConnection) -> Self {
         Self {
             conn,
-            queue_name: ACTION_JOBS_DLQ.to_string(),
+            queue_name: queue_key(ACTION_JOBS_DLQ),
         }
     }
 
@@ -150,6 +242,150 @@ impl DeadLetterQueue for RedisDlq {
             None => Ok(None),
         }
     }
+
+    async fn recent(&self, limit: usize) -> WorkerResult<Vec<DlqEntry>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.clone();
+        let payloads: Vec<String> = conn
+            .lrange(&self.queue_name, 0, limit as isize - 1)
+            .await
+            .map_err(WorkerError::Redis)?;
+
+        // Skip entries that no longer parse rather than failing the whole read
+        Ok(payloads
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect())
+    }
+
+    async fn trim(&self, retention: &DlqRetention, now: DateTime<Utc>) -> WorkerResult<TrimReport> {
+        let mut conn = self.conn.clone();
+        let mut report = TrimReport::default();
+
+        if retention.max_age.is_some() {
+            let script = Script::new(TRIM_EXPIRED_SCRIPT);
+            loop {
+                // Oldest entries are at the tail of the list
+                let tail: Vec<String> = conn
+                    .lrange(&self.queue_name, -TRIM_BATCH_SIZE, -1)
+                    .await
+                    .map_err(WorkerError::Redis)?;
+
+                let expired: Vec<&String> = tail
+                    .iter()
+                    .rev()
+                    .take_while(|json| {
+                        // Unparseable entries can't be replayed; drop them too
+                        serde_json::from_str::<DlqEntry>(json)
+                            .map(|entry| retention.is_expired(entry.failed_at, now))
+                            .unwrap_or(true)
+                    })
+                    .collect();
+                if expired.is_empty() {
+                    break;
+                }
+
+                let mut invocation = script.key(&self.queue_name);
+                for payload in &expired {
+                    invocation.arg(payload.as_str());
+                }
+                let removed: u64 = invocation
+                    .invoke_async(&mut conn)
+                    .await
+                    .map_err(WorkerError::Redis)?;
+                report.expired += removed;
+
+                // Done unless the whole batch was expired and removed
+                if removed < expired.len() as u64 || expired.len() < tail.len() {
+                    break;
+                }
+            }
+        }
+
+        if let Some(max_entries) = retention.max_entries {
+            report.overflow = Script::new(TRIM_OVERFLOW_SCRIPT)
+                .key(&self.queue_name)
+                .arg(max_entries)
+                .invoke_async(&mut conn)
+                .await
+                .map_err(WorkerError::Redis)?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Periodically applies the DLQ retention policy
+pub struct DlqTrimmer {
+    dlq: Arc<dyn DeadLetterQueue>,
+    retention: DlqRetention,
+}
+
+impl DlqTrimmer {
+    /// Create a trimmer for a DLQ
+    ///
+    /// # Arguments
+    ///
+    /// * `dlq` - Queue to trim
+    /// * `retention` - Age and size limits to enforce
+    pub fn new(dlq: Arc<dyn DeadLetterQueue>, retention: DlqRetention) -> Self {
+        Self { dlq, retention }
+    }
+
+    /// Apply the retention policy once and record what was dropped
+    pub async fn trim_once(&self) -> WorkerResult<TrimReport> {
+        let report = self.dlq.trim(&self.retention, Utc::now()).await?;
+
+        if report.expired > 0 {
+            metrics::record_dlq_trimmed("age", report.expired);
+        }
+        if report.overflow > 0 {
+            metrics::record_dlq_trimmed("count", report.overflow);
+        }
+        if let Ok(len) = self.dlq.len().await {
+            metrics::set_dlq_size(len);
+        }
+
+        Ok(report)
+    }
+
+    /// Run the trimmer until cancelled
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between trims
+    /// * `cancel_token` - Shutdown signal
+    pub async fn run(self, interval: Duration, cancel_token: CancellationToken) {
+        tracing::info!(
+            retention_days = self.retention.max_age.map(|age| age.num_days()),
+            max_entries = self.retention.max_entries,
+            interval_secs = interval.as_secs(),
+            "DLQ trimmer started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    tracing::debug!("DLQ trimmer stopping");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    match self.trim_once().await {
+                        Ok(TrimReport { expired: 0, overflow: 0 }) => {}
+                        Ok(report) => tracing::info!(
+                            expired = report.expired,
+                            overflow = report.overflow,
+                            "Trimmed DLQ entries"
+                        ),
+                        Err(e) => tracing::warn!(error = %e, "DLQ trim failed"),
+                    }
+                }
+            }
+        }
+    }
 }
 
 /// In-memory DLQ for testing
@@ -187,12 +423,38 @@ impl DeadLetterQueue for InMemoryDlq {
     async fn peek(&self) -> WorkerResult<Option<DlqEntry>> {
         Ok(self.entries.lock().unwrap().last().cloned())
     }
+
+    async fn recent(&self, limit: usize) -> WorkerResult<Vec<DlqEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.iter().rev().take(limit).cloned().collect())
+    }
+
+    async fn trim(&self, retention: &DlqRetention, now: DateTime<Utc>) -> WorkerResult<TrimReport> {
+        let mut entries = self.entries.lock().unwrap();
+
+        // Entries are stored oldest first
+        let expired = entries
+            .iter()
+            .take_while(|entry| retention.is_expired(entry.failed_at, now))
+            .count();
+        entries.drain(..expired);
+
+        let overflow = retention
+            .max_entries
+            .map_or(0, |max| entries.len().saturating_sub(max as usize));
+        entries.drain(..overflow);
+
+        Ok(TrimReport {
+            expired: expired as u64,
+            overflow: overflow as u64,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use shared::ActionType;
+    use shared::{ActionJob, ActionType};
 
     fn create_test_job() -> ActionJob {
         ActionJob::new(
@@ -249,6 +511,216 @@ mod tests {
         assert_eq!(deserialized.job.trigger_id, "trigger-123");
     }
 
+    /// Entry that failed `age_days` days ago
+    fn entry_aged(age_days: i64, error: &str) -> DlqEntry {
+        DlqEntry {
+            failed_at: Utc::now() - chrono::Duration::days(age_days),
+            ..DlqEntry::new(create_test_job(), error.to_string(), 3)
+        }
+    }
+
+    fn retention(max_age_days: Option<i64>, max_entries: Option<u64>) -> DlqRetention {
+        DlqRetention {
+            max_age: max_age_days.map(chrono::Duration::days),
+            max_entries,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trim_drops_entries_past_retention_age() {
+        let dlq = Arc::new(InMemoryDlq::new());
+        for (age, error) in [
+            (30, "old-1"),
+            (20, "old-2"),
+            (3, "recent-1"),
+            (0, "recent-2"),
+        ] {
+            dlq.push(entry_aged(age, error)).await.unwrap();
+        }
+
+        let trimmer = DlqTrimmer::new(dlq.clone(), retention(Some(14), None));
+        let report = trimmer.trim_once().await.unwrap();
+
+        assert_eq!(
+            report,
+            TrimReport {
+                expired: 2,
+                overflow: 0
+            }
+        );
+        let errors: Vec<_> = dlq.entries().into_iter().map(|e| e.error).collect();
+        assert_eq!(errors, vec!["recent-1", "recent-2"]);
+
+        // Nothing left to expire
+        assert_eq!(trimmer.trim_once().await.unwrap(), TrimReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_trim_caps_entry_count_keeping_newest() {
+        let dlq = Arc::new(InMemoryDlq::new());
+        for i in 0..5 {
+            dlq.push(entry_aged(0, &format!("job-{}", i)))
+                .await
+                .unwrap();
+        }
+
+        let trimmer = DlqTrimmer::new(dlq.clone(), retention(None, Some(3)));
+        let report = trimmer.trim_once().await.unwrap();
+
+        assert_eq!(
+            report,
+            TrimReport {
+                expired: 0,
+                overflow: 2
+            }
+        );
+        let errors: Vec<_> = dlq.entries().into_iter().map(|e| e.error).collect();
+        assert_eq!(errors, vec!["job-2", "job-3", "job-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_trim_applies_age_before_count() {
+        let dlq = InMemoryDlq::new();
+        for (age, error) in [(30, "expired"), (2, "a"), (1, "b"), (0, "c")] {
+            dlq.push(entry_aged(age, error)).await.unwrap();
+        }
+
+        let report = dlq
+            .trim(&retention(Some(7), Some(2)), Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            TrimReport {
+                expired: 1,
+                overflow: 1
+            }
+        );
+        assert_eq!(dlq.len().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_trim_disabled_keeps_everything() {
+        let dlq = InMemoryDlq::new();
+        dlq.push(entry_aged(365, "ancient")).await.unwrap();
+
+        let report = dlq.trim(&retention(None, None), Utc::now()).await.unwrap();
+
+        assert_eq!(report, TrimReport::default());
+        assert_eq!(dlq.len().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recent_entries_survive_trimming() {
+        let dlq = Arc::new(InMemoryDlq::new());
+        for i in 0..10 {
+            dlq.push(entry_aged(10 - i, &format!("job-{}", i)))
+                .await
+                .unwrap();
+        }
+
+        DlqTrimmer::new(dlq.clone(), retention(Some(5), Some(3)))
+            .trim_once()
+            .await
+            .unwrap();
+
+        let recent: Vec<_> = dlq
+            .recent(2)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.error)
+            .collect();
+        assert_eq!(recent, vec!["job-9", "job-8"]);
+    }
+
+    #[test]
+    fn test_default_retention() {
+        let retention = DlqRetention::default();
+        assert_eq!(retention.max_age, Some(chrono::Duration::days(14)));
+        assert_eq!(retention.max_entries, Some(10_000));
+    }
+
+    async fn redis_conn() -> MultiplexedConnection {
+        let url =
+            std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        redis::Client::open(url)
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .expect("Redis must be running for this test")
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_redis_trim_by_age() {
+        let conn = redis_conn().await;
+        let queue = format!("test_dlq_{}", uuid::Uuid::new_v4());
+        let dlq = RedisDlq::with_queue_name(conn.clone(), &queue);
+        for (age, error) in [
+            (30, "old-1"),
+            (20, "old-2"),
+            (3, "recent-1"),
+            (0, "recent-2"),
+        ] {
+            dlq.push(entry_aged(age, error)).await.unwrap();
+        }
+
+        let report = dlq
+            .trim(&retention(Some(14), None), Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            TrimReport {
+                expired: 2,
+                overflow: 0
+            }
+        );
+        assert_eq!(dlq.len().await.unwrap(), 2);
+        assert_eq!(dlq.peek().await.unwrap().unwrap().error, "recent-1");
+
+        let _: () = conn.clone().del(&queue).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_redis_trim_by_count() {
+        let conn = redis_conn().await;
+        let queue = format!("test_dlq_{}", uuid::Uuid::new_v4());
+        let dlq = RedisDlq::with_queue_name(conn.clone(), &queue);
+        for i in 0..5 {
+            dlq.push(entry_aged(0, &format!("job-{}", i)))
+                .await
+                .unwrap();
+        }
+
+        let report = dlq
+            .trim(&retention(None, Some(3)), Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            TrimReport {
+                expired: 0,
+                overflow: 2
+            }
+        );
+        let recent: Vec<_> = dlq
+            .recent(10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.error)
+            .collect();
+        assert_eq!(recent, vec!["job-4", "job-3", "job-2"]);
+
+        let _: () = conn.clone().del(&queue).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_empty_dlq() {
         let dlq = InMemoryDlq::new();
@@ -0,0 +1,216 @@
+//! Graceful shutdown for in-flight jobs
+//!
+//! When shutdown is requested a worker stops consuming immediately, but the job
+//! it is currently processing gets a bounded grace period to finish. A job that
+//! finishes is acknowledged; one that runs past the grace period is abandoned
+//! and returned to the queue so another worker can pick it up.
+
+use std::future::Future;
+use std::time::Duration;
+
+use shared::ActionJob;
+use tokio_util::sync::CancellationToken;
+
+use crate::consumer::JobConsumer;
+
+/// Result of running a job under the shutdown drain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainOutcome {
+    /// Processing finished and the job was acknowledged
+    Completed,
+    /// Processing exceeded the shutdown grace period and the job was requeued
+    Requeued,
+}
+
+/// Run `processing` for `job`, allowing it at most `grace` after shutdown begins
+///
+/// # Arguments
+///
+/// * `consumer` - Consumer the job was taken from (used to ack or requeue)
+/// * `job` - The in-flight job
+/// * `processing` - Future that handles the job
+/// * `cancel_token` - Shutdown signal
+/// * `grace` - How long an in-flight job may keep running after shutdown
+pub async fn run_with_drain<C, F>(
+    consumer: &C,
+    job: &ActionJob,
+    processing: F,
+    cancel_token: &CancellationToken,
+    grace: Duration,
+) -> DrainOutcome
+where
+    C: JobConsumer + ?Sized,
+    F: Future<Output = ()>,
+{
+    tokio::pin!(processing);
+
+    let completed = tokio::select! {
+        _ = &mut processing => true,
+        _ = async {
+            cancel_token.cancelled().await;
+            tracing::info!(
+                job_id = %job.id,
+                grace_secs = grace.as_secs_f64(),
+                "Shutdown requested, waiting for in-flight job to finish"
+            );
+            tokio::time::sleep(grace).await;
+        } => false,
+    };
+
+    if completed {
+        if let Err(e) = consumer.ack(job).await {
+            // The job stays in the processing list and will be requeued later,
+            // which may cause a duplicate delivery but never a lost job
+            tracing::error!(
+                job_id = %job.id,
+                error = %e,
+                "Failed to acknowledge completed job"
+            );
+        }
+        return DrainOutcome::Completed;
+    }
+
+    tracing::warn!(
+        job_id = %job.id,
+        grace_secs = grace.as_secs_f64(),
+        "In-flight job did not finish within shutdown grace period, requeueing"
+    );
+    if let Err(e) = consumer.requeue_in_flight().await {
+        tracing::error!(
+            job_id = %job.id,
+            error = %e,
+            "Failed to requeue in-flight job, it will be recovered on next startup"
+        );
+    }
+
+    DrainOutcome::Requeued
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::WorkerResult;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Consumer that records acknowledgements and requeues
+    #[derive(Default)]
+    struct RecordingConsumer {
+        acked: Mutex<Vec<String>>,
+        requeues: AtomicU32,
+    }
+
+    #[async_trait]
+    impl JobConsumer for RecordingConsumer {
+        async fn consume(&self, _timeout_secs: u64) -> WorkerResult<Option<ActionJob>> {
+            Ok(None)
+        }
+
+        async fn ack(&self, job: &ActionJob) -> WorkerResult<()> {
+            self.acked.lock().unwrap().push(job.id.clone());
+            Ok(())
+        }
+
+        async fn requeue_in_flight(&self) -> WorkerResult<u64> {
+            self.requeues.fetch_add(1, Ordering::SeqCst);
+            Ok(1)
+        }
+
+        async fn queue_len(&self) -> WorkerResult<u64> {
+            Ok(0)
+        }
+    }
+
+    fn create_test_job() -> ActionJob {
+        ActionJob::new(
+            "trigger-1",
+            "event-1",
+            shared::ActionType::Rest,
+            1,
+            serde_json::json!({"url": "https://example.com"}),
+            serde_json::json!({"agent_id": 42}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_job_completes_without_shutdown() {
+        let consumer = RecordingConsumer::default();
+        let job = create_test_job();
+        let token = CancellationToken::new();
+
+        let outcome =
+            run_with_drain(&consumer, &job, async {}, &token, Duration::from_secs(1)).await;
+
+        assert_eq!(outcome, DrainOutcome::Completed);
+        assert_eq!(*consumer.acked.lock().unwrap(), vec![job.id.clone()]);
+        assert_eq!(consumer.requeues.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_during_processing_lets_job_finish() {
+        let consumer = RecordingConsumer::default();
+        let job = create_test_job();
+        let token = CancellationToken::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let processing = {
+            let finished = finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                finished.store(true, Ordering::SeqCst);
+            }
+        };
+
+        // Shutdown arrives while the job is mid-flight
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+
+        let outcome =
+            run_with_drain(&consumer, &job, processing, &token, Duration::from_secs(5)).await;
+
+        assert_eq!(outcome, DrainOutcome::Completed);
+        assert!(finished.load(Ordering::SeqCst));
+        assert_eq!(consumer.acked.lock().unwrap().len(), 1);
+        assert_eq!(consumer.requeues.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_requeues_job_exceeding_grace_period() {
+        let consumer = RecordingConsumer::default();
+        let job = create_test_job();
+        let token = CancellationToken::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let processing = {
+            let finished = finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                finished.store(true, Ordering::SeqCst);
+            }
+        };
+
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cancel.cancel();
+        });
+
+        let outcome = run_with_drain(
+            &consumer,
+            &job,
+            processing,
+            &token,
+            Duration::from_millis(50),
+        )
+        .await;
+
+        assert_eq!(outcome, DrainOutcome::Requeued);
+        assert!(!finished.load(Ordering::SeqCst));
+        assert!(consumer.acked.lock().unwrap().is_empty());
+        assert_eq!(consumer.requeues.load(Ordering::SeqCst), 1);
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! Provides structured error handling for all action worker operations.
 
+use shared::error::{is_transient_db_error, is_transient_redis_error};
 use thiserror::Error;
 
+use crate::result_logger::{FailureCategory, FailureReason};
+
 /// Worker error types
 #[derive(Debug, Error)]
 pub enum WorkerError {
@@ -19,6 +22,18 @@ pub enum WorkerError {
     #[error("MCP API error: {0}")]
     McpApi(String),
 
+    /// Request to the action's target timed out
+    #[error("Request timeout: {0}")]
+    Timeout(String),
+
+    /// Could not connect to the action's target
+    #[error("Connection failed: {0}")]
+    Connection(String),
+
+    /// Action's target answered with an unexpected HTTP status
+    #[error("{message}")]
+    HttpStatus { status: u16, message: String },
+
     /// Rate limit exceeded
     #[error("Rate limit exceeded: {0}")]
     RateLimitExceeded(String),
@@ -41,13 +56,16 @@ pub enum WorkerError {
 
     /// Job not found
     #[error("Job not found: {0}")]
-    #[allow(dead_code)]
     JobNotFound(String),
 
     /// Queue operation error
     #[error("Queue error: {0}")]
     Queue(String),
 
+    /// The job's organization cannot pay for the action
+    #[error("Insufficient credits: {0}")]
+    InsufficientCredits(String),
+
     /// Generic internal error
     #[error("Internal error: {0}")]
     #[allow(dead_code)]
@@ -57,16 +75,23 @@ pub enum WorkerError {
 impl WorkerError {
     /// Check if this error is retryable
     ///
-    /// Transient errors (rate limits, timeouts) are retryable.
-    /// Permanent errors (invalid config, serialization) are not.
+    /// Transient errors (rate limits, timeouts, 5xx responses) are retryable.
+    /// Permanent errors (invalid config, serialization, 4xx responses) are not.
+    /// Redis and database errors are classified as in [`shared::Error::is_retryable`].
     pub fn is_retryable(&self) -> bool {
+        match self {
+            WorkerError::HttpStatus { status, .. } => return !(400..500).contains(status),
+            WorkerError::Redis(e) => return is_transient_redis_error(e),
+            WorkerError::Database(e) => return is_transient_db_error(e),
+            _ => {}
+        }
         matches!(
             self,
-            WorkerError::Redis(_)
+            WorkerError::Timeout(_)
+                | WorkerError::Connection(_)
                 | WorkerError::TelegramApi(_)
                 | WorkerError::McpApi(_)
                 | WorkerError::RateLimitExceeded(_)
-                | WorkerError::Database(_)
                 | WorkerError::Queue(_)
         )
     }
@@ -87,6 +112,11 @@ impl WorkerError {
     pub fn safe_message(&self) -> String {
         match self {
             WorkerError::Redis(_) => "Database connection error".to_string(),
+            WorkerError::Timeout(_) => "Request timed out".to_string(),
+            WorkerError::Connection(_) => "Could not connect to the target".to_string(),
+            WorkerError::HttpStatus { status, .. } => {
+                format!("Target responded with HTTP {}", status)
+            }
             WorkerError::TelegramApi(_) => "Failed to send notification".to_string(),
             WorkerError::McpApi(_) => "Failed to call MCP tool".to_string(),
             WorkerError::RateLimitExceeded(_) => {
@@ -103,10 +133,93 @@ impl WorkerError {
             }
             WorkerError::JobNotFound(_) => "Job not found".to_string(),
             WorkerError::Queue(_) => "Queue operation failed".to_string(),
+            WorkerError::InsufficientCredits(_) => {
+                "Insufficient credits, top up to resume actions".to_string()
+            }
             WorkerError::Internal(_) => "Internal server error".to_string(),
         }
     }
 
+    /// Classify this error for the result log
+    ///
+    /// The category groups failures for reporting; the code is a stable,
+    /// machine-readable identifier within it (e.g. `http_404`).
+    pub fn failure_reason(&self) -> FailureReason {
+        let (category, code) = match self {
+            WorkerError::Timeout(_) => (FailureCategory::Timeout, "request_timeout".into()),
+            WorkerError::Connection(_) => (FailureCategory::Connection, "connection_failed".into()),
+            WorkerError::HttpStatus { status, .. } => {
+                let category = match status {
+                    400..=499 => FailureCategory::ClientError,
+                    500..=599 => FailureCategory::ServerError,
+                    _ => FailureCategory::Upstream,
+                };
+                (category, format!("http_{}", status))
+            }
+            WorkerError::RateLimitExceeded(_) => {
+                (FailureCategory::RateLimited, "rate_limited".into())
+            }
+            WorkerError::TelegramApi(_) => (FailureCategory::Upstream, "telegram_api_error".into()),
+            WorkerError::McpApi(_) => (FailureCategory::Upstream, "mcp_error".into()),
+            WorkerError::Template(_) => (FailureCategory::Template, "template_error".into()),
+            WorkerError::InvalidConfig(_) => (FailureCategory::Config, "invalid_config".into()),
+            WorkerError::Redis(_) => (FailureCategory::Internal, "redis_error".into()),
+            WorkerError::Database(_) => (FailureCategory::Internal, "database_error".into()),
+            WorkerError::Serialization(_) => {
+                (FailureCategory::Internal, "serialization_error".into())
+            }
+            WorkerError::JobNotFound(_) => (FailureCategory::Internal, "job_not_found".into()),
+            WorkerError::Queue(_) => (FailureCategory::Internal, "queue_error".into()),
+            WorkerError::InsufficientCredits(_) => {
+                (FailureCategory::Billing, "insufficient_credits".into())
+            }
+            WorkerError::Internal(_) => (FailureCategory::Internal, "internal_error".into()),
+        };
+        FailureReason { category, code }
+    }
+
+    /// Copy of this error for test doubles that return it repeatedly
+    ///
+    /// Errors wrapping a source error become [`WorkerError::Internal`].
+    #[cfg(test)]
+    pub fn duplicate(&self) -> Self {
+        match self {
+            WorkerError::Timeout(msg) => WorkerError::Timeout(msg.clone()),
+            WorkerError::Connection(msg) => WorkerError::Connection(msg.clone()),
+            WorkerError::HttpStatus { status, message } => WorkerError::HttpStatus {
+                status: *status,
+                message: message.clone(),
+            },
+            WorkerError::TelegramApi(msg) => WorkerError::TelegramApi(msg.clone()),
+            WorkerError::McpApi(msg) => WorkerError::McpApi(msg.clone()),
+            WorkerError::RateLimitExceeded(msg) => WorkerError::RateLimitExceeded(msg.clone()),
+            WorkerError::InvalidConfig(msg) => WorkerError::InvalidConfig(msg.clone()),
+            WorkerError::Template(msg) => WorkerError::Template(msg.clone()),
+            WorkerError::JobNotFound(msg) => WorkerError::JobNotFound(msg.clone()),
+            WorkerError::Queue(msg) => WorkerError::Queue(msg.clone()),
+            WorkerError::InsufficientCredits(msg) => WorkerError::InsufficientCredits(msg.clone()),
+            other => WorkerError::Internal(other.to_string()),
+        }
+    }
+
+    /// Create a timeout error
+    pub fn timeout(details: impl Into<String>) -> Self {
+        WorkerError::Timeout(details.into())
+    }
+
+    /// Create a connection error
+    pub fn connection(details: impl Into<String>) -> Self {
+        WorkerError::Connection(details.into())
+    }
+
+    /// Create an unexpected HTTP status error
+    pub fn http_status(status: u16, message: impl Into<String>) -> Self {
+        WorkerError::HttpStatus {
+            status,
+            message: message.into(),
+        }
+    }
+
     /// Create a rate limit error with details
     pub fn rate_limit(details: impl Into<String>) -> Self {
         WorkerError::RateLimitExceeded(details.into())
@@ -132,6 +245,11 @@ impl WorkerError {
         WorkerError::McpApi(details.into())
     }
 
+    /// Create an insufficient credits error
+    pub fn insufficient_credits(details: impl Into<String>) -> Self {
+        WorkerError::InsufficientCredits(details.into())
+    }
+
     /// Create a queue error
     #[allow(dead_code)]
     pub fn queue(details: impl Into<String>) -> Self {
@@ -183,6 +301,63 @@ mod tests {
         assert!(!WorkerError::invalid_config("missing field").is_retryable());
         assert!(!WorkerError::template("invalid syntax").is_retryable());
         assert!(!WorkerError::Internal("unknown".into()).is_retryable());
+        assert!(!WorkerError::insufficient_credits("balance 0").is_retryable());
+    }
+
+    #[test]
+    fn test_http_status_retryable_unless_client_error() {
+        assert!(!WorkerError::http_status(404, "Unexpected status code 404").is_retryable());
+        assert!(WorkerError::http_status(503, "Unexpected status code 503").is_retryable());
+        assert!(WorkerError::timeout("no response after 30s").is_retryable());
+        assert!(WorkerError::connection("could not reach host").is_retryable());
+    }
+
+    #[test]
+    fn test_redis_and_database_errors_retryable_when_transient() {
+        let refused = redis::RedisError::from((redis::ErrorKind::IoError, "Connection refused"));
+        assert!(WorkerError::Redis(refused).is_retryable());
+        let wrong_type = redis::RedisError::from((redis::ErrorKind::TypeError, "WRONGTYPE"));
+        assert!(!WorkerError::Redis(wrong_type).is_retryable());
+
+        assert!(WorkerError::Database(sqlx::Error::PoolTimedOut).is_retryable());
+        assert!(!WorkerError::Database(sqlx::Error::RowNotFound).is_retryable());
+    }
+
+    #[test]
+    fn test_failure_reason() {
+        let reason = |e: WorkerError| {
+            let r = e.failure_reason();
+            (r.category, r.code)
+        };
+
+        assert_eq!(
+            reason(WorkerError::timeout("no response after 30s")),
+            (FailureCategory::Timeout, "request_timeout".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::http_status(404, "Unexpected status code 404")),
+            (FailureCategory::ClientError, "http_404".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::http_status(502, "HTTP 502 from MCP server")),
+            (FailureCategory::ServerError, "http_502".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::http_status(302, "Unexpected status code 302")),
+            (FailureCategory::Upstream, "http_302".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::template("unknown variable")),
+            (FailureCategory::Template, "template_error".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::insufficient_credits("balance 0 < cost 2000")),
+            (FailureCategory::Billing, "insufficient_credits".to_string())
+        );
+        assert_eq!(
+            reason(WorkerError::Internal("boom".into())),
+            (FailureCategory::Internal, "internal_error".to_string())
+        );
     }
 
     #[test]
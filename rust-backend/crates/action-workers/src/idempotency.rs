@@ -0,0 +1,407 @@
+//! Worker-side idempotency (duplicate job suppression)
+//!
+//! A producer may enqueue the same action twice (e.g. the polling fallback
+//! re-processing an event, or the reaper redelivering a job whose worker had
+//! actually finished it). Each job maps to an idempotency key derived from
+//! trigger, event, action type and action config. Once a job completes the key
+//! is stored in Redis with a TTL; a job whose key is still present is skipped.
+//!
+//! The key is only recorded after processing, so a job that was taken but
+//! never finished is still processed when it is redelivered.
+//!
+//! # Dedup Window
+//!
+//! The TTL trades dedup effectiveness against Redis memory and can be tuned
+//! per action type and per organization. A duplicate arriving after the window
+//! has expired is processed as a new job.
+//!
+//! `IDEMPOTENCY_TTL_SECS` sets the default window (1 hour).
+//! `IDEMPOTENCY_TTL_OVERRIDES` is a comma-separated list of `scope=seconds`
+//! entries, where scope is one of:
+//!
+//! ```text
+//! rest                   # action type
+//! org:<org_id>           # organization
+//! org:<org_id>:telegram  # organization + action type
+//! ```
+//!
+//! The most specific matching scope wins.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sha2::{Digest, Sha256};
+use shared::redis::RedisKey;
+use shared::{ActionJob, ActionType};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// Default dedup window in seconds (matches the job TTL)
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 3600;
+
+/// Build the idempotency key for a job
+///
+/// Jobs for the same trigger, event and action configuration share a key even
+/// when they have different job IDs.
+pub fn idempotency_key(job: &ActionJob) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(job.trigger_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(job.event_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(job.action_type.to_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(job.config.to_string().as_bytes());
+    let digest = hasher.finalize();
+
+    RedisKey::dedup("action_jobs")
+        .part(format!("{:x}", digest))
+        .into()
+}
+
+/// Dedup window configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupConfig {
+    default_ttl: Duration,
+    by_action_type: HashMap<String, Duration>,
+    by_org: HashMap<String, Duration>,
+    by_org_action_type: HashMap<(String, String), Duration>,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_IDEMPOTENCY_TTL_SECS))
+    }
+}
+
+impl DedupConfig {
+    /// Create a configuration with only a default window
+    pub fn new(default_ttl: Duration) -> Self {
+        Self {
+            default_ttl,
+            by_action_type: HashMap::new(),
+            by_org: HashMap::new(),
+            by_org_action_type: HashMap::new(),
+        }
+    }
+
+    /// Load configuration from `IDEMPOTENCY_TTL_SECS` and `IDEMPOTENCY_TTL_OVERRIDES`
+    ///
+    /// Invalid override entries are logged and ignored.
+    pub fn from_env() -> Self {
+        let default_ttl = std::env::var("IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECS);
+
+        let mut config = Self::new(Duration::from_secs(default_ttl));
+
+        if let Ok(overrides) = std::env::var("IDEMPOTENCY_TTL_OVERRIDES") {
+            for entry in overrides
+                .split(',')
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+            {
+                if let Err(e) = config.add_override(entry) {
+                    tracing::warn!(entry = %entry, error = %e, "Ignoring invalid idempotency TTL override");
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Parse and add one `scope=seconds` override
+    pub fn add_override(&mut self, entry: &str) -> WorkerResult<()> {
+        let invalid = |reason: &str| WorkerError::InvalidConfig(format!("{}: {}", reason, entry));
+
+        let (scope, secs) = entry
+            .split_once('=')
+            .ok_or_else(|| invalid("expected scope=seconds"))?;
+        let ttl = secs
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .ok_or_else(|| invalid("TTL must be a positive number of seconds"))?;
+
+        let parse_action_type = |s: &str| {
+            ActionType::from_str(s)
+                .map(|t| t.to_string())
+                .map_err(|_| invalid("unknown action type"))
+        };
+
+        match scope.trim().split(':').collect::<Vec<_>>().as_slice() {
+            ["org", org_id] if !org_id.is_empty() => {
+                self.by_org.insert(org_id.to_string(), ttl);
+            }
+            ["org", org_id, action_type] if !org_id.is_empty() => {
+                self.by_org_action_type
+                    .insert((org_id.to_string(), parse_action_type(action_type)?), ttl);
+            }
+            [action_type] => {
+                self.by_action_type
+                    .insert(parse_action_type(action_type)?, ttl);
+            }
+            _ => return Err(invalid("unrecognized scope")),
+        }
+
+        Ok(())
+    }
+
+    /// Dedup window for a job
+    pub fn ttl_for(&self, job: &ActionJob) -> Duration {
+        let action_type = job.action_type.to_string();
+
+        if let Some(org_id) = &job.organization_id {
+            if let Some(ttl) = self
+                .by_org_action_type
+                .get(&(org_id.clone(), action_type.clone()))
+            {
+                return *ttl;
+            }
+            if let Some(ttl) = self.by_org.get(org_id) {
+                return *ttl;
+            }
+        }
+
+        self.by_action_type
+            .get(&action_type)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+/// Storage for idempotency markers
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Check whether a marker exists for `key`
+    async fn contains(&self, key: &str) -> WorkerResult<bool>;
+
+    /// Store a marker for `key` that expires after `ttl`
+    async fn insert(&self, key: &str, ttl: Duration) -> WorkerResult<()>;
+}
+
+/// Redis-backed idempotency store
+#[derive(Clone)]
+pub struct RedisIdempotencyStore {
+    conn: MultiplexedConnection,
+}
+
+impl RedisIdempotencyStore {
+    /// Create a new Redis idempotency store
+    pub fn new(conn: MultiplexedConnection) -> Self {
+        Self { conn }
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn contains(&self, key: &str) -> WorkerResult<bool> {
+        let mut conn = self.conn.clone();
+        conn.exists(key).await.map_err(WorkerError::Redis)
+    }
+
+    async fn insert(&self, key: &str, ttl: Duration) -> WorkerResult<()> {
+        let mut conn = self.conn.clone();
+        conn.set_ex::<_, _, ()>(key, 1, ttl.as_secs().max(1))
+            .await
+            .map_err(WorkerError::Redis)
+    }
+}
+
+/// In-memory idempotency store for testing
+#[derive(Default)]
+#[allow(dead_code)]
+pub struct InMemoryIdempotencyStore {
+    entries: Mutex<HashMap<String, Instant>>,
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn contains(&self, key: &str) -> WorkerResult<bool> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .get(key)
+            .is_some_and(|expires_at| *expires_at > Instant::now()))
+    }
+
+    async fn insert(&self, key: &str, ttl: Duration) -> WorkerResult<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), Instant::now() + ttl);
+        Ok(())
+    }
+}
+
+/// Duplicate job detector combining a store and the dedup window configuration
+pub struct Deduplicator<S: IdempotencyStore> {
+    store: S,
+    config: DedupConfig,
+}
+
+impl<S: IdempotencyStore> Deduplicator<S> {
+    /// Create a new deduplicator
+    pub fn new(store: S, config: DedupConfig) -> Self {
+        Self { store, config }
+    }
+
+    /// Check whether an equivalent job already completed within its window
+    ///
+    /// Store errors are logged and treated as "not a duplicate" so a Redis
+    /// outage never blocks job processing.
+    pub async fn is_duplicate(&self, job: &ActionJob) -> bool {
+        match self.store.contains(&idempotency_key(job)).await {
+            Ok(duplicate) => duplicate,
+            Err(e) => {
+                tracing::warn!(
+                    job_id = %job.id,
+                    error = %e,
+                    "Idempotency check failed, processing job"
+                );
+                false
+            }
+        }
+    }
+
+    /// Record that a job completed so duplicates within the window are skipped
+    pub async fn mark_completed(&self, job: &ActionJob) {
+        let ttl = self.config.ttl_for(job);
+        if let Err(e) = self.store.insert(&idempotency_key(job), ttl).await {
+            tracing::warn!(
+                job_id = %job.id,
+                error = %e,
+                "Failed to record idempotency key, a duplicate may be processed"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn create_job(action_type: ActionType, org_id: Option<&str>) -> ActionJob {
+        let job = ActionJob::new(
+            "trigger-1",
+            "event-1",
+            action_type,
+            1,
+            json!({"url": "https://example.com"}),
+            json!({"agent_id": 42}),
+        );
+        match org_id {
+            Some(org_id) => job.with_organization_id(org_id),
+            None => job,
+        }
+    }
+
+    #[test]
+    fn test_idempotency_key_ignores_job_id() {
+        let job1 = create_job(ActionType::Rest, None);
+        let job2 = create_job(ActionType::Rest, None);
+
+        assert_ne!(job1.id, job2.id);
+        assert_eq!(idempotency_key(&job1), idempotency_key(&job2));
+        assert!(idempotency_key(&job1).starts_with("agentauri:dedup:action_jobs:"));
+    }
+
+    #[test]
+    fn test_idempotency_key_differs_by_action() {
+        let rest = create_job(ActionType::Rest, None);
+        let mut other_url = create_job(ActionType::Rest, None);
+        other_url.config = json!({"url": "https://other.example.com"});
+        let telegram = create_job(ActionType::Telegram, None);
+
+        assert_ne!(idempotency_key(&rest), idempotency_key(&other_url));
+        assert_ne!(idempotency_key(&rest), idempotency_key(&telegram));
+    }
+
+    #[test]
+    fn test_ttl_precedence() {
+        let mut config = DedupConfig::new(Duration::from_secs(3600));
+        config.add_override("rest=600").unwrap();
+        config.add_override("org:org-a=120").unwrap();
+        config.add_override("org:org-a:rest=30").unwrap();
+
+        let ttl = |action_type, org| config.ttl_for(&create_job(action_type, org));
+
+        assert_eq!(
+            ttl(ActionType::Rest, Some("org-a")),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            ttl(ActionType::Telegram, Some("org-a")),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            ttl(ActionType::Rest, Some("org-b")),
+            Duration::from_secs(600)
+        );
+        assert_eq!(ttl(ActionType::Rest, None), Duration::from_secs(600));
+        assert_eq!(ttl(ActionType::Mcp, None), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_invalid_overrides_rejected() {
+        let mut config = DedupConfig::default();
+
+        assert!(config.add_override("rest").is_err());
+        assert!(config.add_override("rest=0").is_err());
+        assert!(config.add_override("rest=abc").is_err());
+        assert!(config.add_override("email=60").is_err());
+        assert!(config.add_override("org:=60").is_err());
+        assert!(config.add_override("org:a:rest:x=60").is_err());
+        assert_eq!(config, DedupConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_within_window_is_skipped() {
+        let dedup = Deduplicator::new(
+            InMemoryIdempotencyStore::default(),
+            DedupConfig::new(Duration::from_secs(60)),
+        );
+        let job = create_job(ActionType::Rest, Some("org-a"));
+        let duplicate = create_job(ActionType::Rest, Some("org-a"));
+
+        assert!(!dedup.is_duplicate(&job).await);
+        dedup.mark_completed(&job).await;
+
+        assert!(dedup.is_duplicate(&duplicate).await);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_after_window_is_processed() {
+        let mut config = DedupConfig::new(Duration::from_secs(60));
+        config.add_override("org:org-a:rest=1").unwrap();
+        let dedup = Deduplicator::new(InMemoryIdempotencyStore::default(), config);
+
+        let job = create_job(ActionType::Rest, Some("org-a"));
+        dedup.mark_completed(&job).await;
+        assert!(dedup.is_duplicate(&job).await);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        let late_duplicate = create_job(ActionType::Rest, Some("org-a"));
+        assert!(!dedup.is_duplicate(&late_duplicate).await);
+    }
+
+    #[tokio::test]
+    async fn test_unfinished_job_is_not_a_duplicate() {
+        let dedup = Deduplicator::new(InMemoryIdempotencyStore::default(), DedupConfig::default());
+        let job = create_job(ActionType::Mcp, None);
+
+        // Checking alone never records the key, so redelivery still processes it
+        assert!(!dedup.is_duplicate(&job).await);
+        assert!(!dedup.is_duplicate(&job).await);
+    }
+}
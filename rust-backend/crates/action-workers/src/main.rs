@@ -2,43 +2,66 @@
 //!
 //! Consumes jobs from Redis queue and executes actions (Telegram, REST, MCP).
 //! Implements a worker pool with configurable parallelism and graceful shutdown.
+//!
+//! The shared pool (`ACTION_WORKER_COUNT` workers) consumes `action_jobs` and
+//! handles every action type. Setting `ACTION_WORKERS_<TYPE>` adds a dedicated
+//! pool on `action_jobs:<type>`, so a slow or rate-limited type cannot starve
+//! the others (see [`shared::ActionWorkerPools`]).
 
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use shared::{db, ActionType, Config};
+use shared::diagnostics::{self, DiagnosticsReport};
+use shared::redis::queue_key;
+use shared::{
+    action_type_queue, db, ActionType, ActionWorkerPools, Config, PoolMetricsReporter,
+    ACTION_JOBS_QUEUE, DEFAULT_POOL_METRICS_INTERVAL_SECS,
+};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 mod consumer;
+mod credits;
 mod dlq;
+mod drain;
 mod error;
+mod idempotency;
 mod mcp;
 mod metrics;
 mod rate_limiter;
+mod reaper;
 mod rest;
 mod result_logger;
+mod result_webhook;
 mod retry;
+mod signing_keys;
 mod telegram;
+mod telegram_format;
 mod template;
 mod workers;
 
 use consumer::{JobConsumer, RedisJobConsumer};
-use dlq::RedisDlq;
+use credits::{ActionBilling, PostgresCreditLedger};
+use dlq::{DlqRetention, DlqTrimmer, RedisDlq, DEFAULT_DLQ_TRIM_INTERVAL_SECS};
+use idempotency::{DedupConfig, Deduplicator, IdempotencyStore, RedisIdempotencyStore};
 use mcp::HttpMcpClient;
 use rate_limiter::TelegramRateLimiter;
+use reaper::{ProcessingReaper, DEFAULT_REAP_INTERVAL_SECS};
 use rest::ReqwestHttpClient;
-use result_logger::PostgresResultLogger;
+use result_logger::{BatchConfig, BufferedResultLogger, PostgresResultLogger};
+use result_webhook::{PostgresResultWebhookStore, ResultWebhookConfig, ResultWebhookNotifier};
 use retry::RetryPolicy;
+use signing_keys::PostgresSigningKeyStore;
 use telegram::TeloxideTelegramClient;
 use workers::{McpWorker, RestWorker, TelegramWorker};
 
-/// Number of concurrent workers
-const NUM_WORKERS: usize = 5;
-
-/// Timeout for BRPOP in seconds
+/// Timeout for waiting on the job queue in seconds
 const CONSUME_TIMEOUT_SECS: u64 = 5;
 
+/// How long an in-flight job may keep running after shutdown is requested
+const SHUTDOWN_GRACE_SECS: u64 = 30;
+
 /// Interval for queue depth metric updates
 const METRICS_UPDATE_INTERVAL_SECS: u64 = 5;
 
@@ -47,13 +70,25 @@ async fn main() -> Result<()> {
     // Initialize tracing
     shared::init_tracing();
 
+    if diagnostics::is_check_mode() {
+        return run_diagnostics().await;
+    }
+
     tracing::info!("Starting Action Workers...");
 
     // Initialize Prometheus metrics exporter with default address (0.0.0.0:9090)
     metrics::init_metrics_default();
 
     // Load configuration
-    let config = Config::from_env().context("Failed to load configuration")?;
+    let config = Config::load().context("Failed to load configuration")?;
+    let config_summary = config
+        .validate_and_summarize()
+        .context("Invalid configuration")?;
+    tracing::info!("Effective configuration: {}", config_summary);
+
+    // Namespace Redis keys before anything builds one
+    shared::redis::init_key_prefix(config.redis.key_prefix.as_deref())
+        .context("Invalid Redis key prefix")?;
 
     // Create database connection pool (for result logging)
     let db_pool = db::create_pool(&config.database)
@@ -77,10 +112,40 @@ async fn main() -> Result<()> {
     tracing::info!("Connected to Redis");
 
     // Create shared components
-    let consumer = Arc::new(RedisJobConsumer::new(redis_conn.clone()));
+    // Consumer IDs combine the hostname and worker index so each worker has its
+    // own processing list that survives a restart of this instance
+    let instance_id = hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown".to_string());
     let dlq = Arc::new(RedisDlq::new(redis_conn.clone()));
-    let logger = Arc::new(PostgresResultLogger::new(db_pool));
+    let batch_config = BatchConfig::from_env();
+    tracing::info!(
+        batch_size = batch_config.batch_size,
+        flush_interval_ms = batch_config.flush_interval.as_millis() as u64,
+        "Result logger batching configured"
+    );
+    // Per-organization keys signing REST actions and result webhooks
+    let signing_keys = Arc::new(PostgresSigningKeyStore::new(db_pool.clone()));
+    // Customer-facing action result webhooks (delivered in the background)
+    let result_webhooks = Arc::new(
+        ResultWebhookNotifier::new(
+            Arc::new(PostgresResultWebhookStore::new(db_pool.clone())),
+            ResultWebhookConfig::from_env(),
+        )
+        .context("Failed to create result webhook notifier")?
+        .with_signing_keys(signing_keys.clone()),
+    );
+    let logger = Arc::new(BufferedResultLogger::new(
+        Arc::new(PostgresResultLogger::new(db_pool.clone())),
+        batch_config,
+    ));
     let rate_limiter = Arc::new(TelegramRateLimiter::new());
+    // Usage-based billing: each successful action is charged to its organization
+    let billing = Arc::new(ActionBilling::new(
+        Arc::new(PostgresCreditLedger::new(db_pool.clone())),
+        config.action_costs,
+    ));
 
     // Create Telegram client (from environment variable)
     let telegram_client = match TeloxideTelegramClient::from_env() {
@@ -127,55 +192,165 @@ async fn main() -> Result<()> {
         logger.clone(),
         dlq.clone(),
         rate_limiter,
-        RetryPolicy::default(),
-    );
+        RetryPolicy::from_env(ActionType::Telegram),
+    )
+    .with_billing(billing.clone());
 
     // Create REST worker
     let rest_worker = RestWorker::new(
         http_client,
         logger.clone(),
         dlq.clone(),
-        RetryPolicy::default(),
-    );
+        RetryPolicy::from_env(ActionType::Rest),
+    )
+    .with_signing_keys(signing_keys)
+    .with_billing(billing.clone());
 
     // Create MCP worker
-    let mcp_worker = McpWorker::new(mcp_client, logger, dlq, RetryPolicy::default());
+    let mcp_worker = McpWorker::new(
+        mcp_client,
+        logger.clone(),
+        dlq,
+        RetryPolicy::from_env(ActionType::Mcp),
+    )
+    .with_billing(billing);
+
+    // Duplicate job suppression
+    let dedup = Arc::new(Deduplicator::new(
+        RedisIdempotencyStore::new(redis_conn.clone()),
+        DedupConfig::from_env(),
+    ));
+
+    // Spawn worker pools: the shared queue plus one queue per dedicated pool
+    let pools = ActionWorkerPools::from_env();
+    let mut pool_queues = vec![(
+        queue_key(ACTION_JOBS_QUEUE),
+        "shared".to_string(),
+        pools.shared,
+    )];
+    for (action_type, size) in &pools.dedicated {
+        pool_queues.push((
+            queue_key(&action_type_queue(action_type)),
+            action_type.to_string(),
+            *size,
+        ));
+    }
 
-    // Spawn worker pool
     let mut handles = Vec::new();
-    metrics::set_active_workers(NUM_WORKERS);
-
-    for worker_id in 0..NUM_WORKERS {
-        let consumer = consumer.clone();
-        let telegram_worker = telegram_worker.clone();
-        let rest_worker = rest_worker.clone();
-        let mcp_worker = mcp_worker.clone();
-        let token = cancel_token.clone();
-
-        let handle = tokio::spawn(async move {
-            run_worker(
-                worker_id,
-                consumer,
-                telegram_worker,
-                rest_worker,
-                mcp_worker,
-                token,
-            )
-            .await;
-        });
-        handles.push(handle);
+    metrics::set_active_workers(pools.total_workers());
+
+    // One consumer per queue, for the queue depth metric
+    let mut metrics_consumers = Vec::with_capacity(pool_queues.len());
+    let mut worker_id = 0;
+
+    for (queue_name, pool_name, size) in &pool_queues {
+        for index in 0..*size {
+            // The shared pool keeps its original IDs so in-flight jobs from
+            // before an upgrade are recovered
+            let consumer_id = if *pool_name == "shared" {
+                format!("{}:{}", instance_id, index)
+            } else {
+                format!("{}:{}:{}", instance_id, pool_name, index)
+            };
+            let consumer = Arc::new(RedisJobConsumer::with_queue_name(
+                redis_conn.clone(),
+                queue_name,
+                &consumer_id,
+            ));
+            if index == 0 {
+                metrics_consumers.push(consumer.clone());
+            }
+            let telegram_worker = telegram_worker.clone();
+            let rest_worker = rest_worker.clone();
+            let mcp_worker = mcp_worker.clone();
+            let dedup = dedup.clone();
+            let result_webhooks = result_webhooks.clone();
+            let token = cancel_token.clone();
+
+            let handle = tokio::spawn(async move {
+                run_worker(
+                    worker_id,
+                    consumer,
+                    telegram_worker,
+                    rest_worker,
+                    mcp_worker,
+                    dedup,
+                    result_webhooks,
+                    token,
+                )
+                .await;
+            });
+            handles.push(handle);
+            worker_id += 1;
+        }
+
+        tracing::info!(
+            pool = %pool_name,
+            queue = %queue_name,
+            num_workers = size,
+            "Worker pool started"
+        );
     }
 
     tracing::info!(
-        num_workers = NUM_WORKERS,
-        "Worker pool started, ready to process jobs"
+        num_workers = pools.total_workers(),
+        num_pools = pool_queues.len(),
+        "All worker pools started, ready to process jobs"
+    );
+
+    // Spawn a reaper per queue for jobs stranded in processing lists of dead workers
+    for (queue_name, _, _) in &pool_queues {
+        let reaper = ProcessingReaper::with_queue_name(
+            redis_conn.clone(),
+            queue_name,
+            ProcessingReaper::visibility_timeout_from_env(),
+        );
+        let reaper_token = cancel_token.clone();
+        tokio::spawn(async move {
+            reaper
+                .run(
+                    Duration::from_secs(DEFAULT_REAP_INTERVAL_SECS),
+                    reaper_token,
+                )
+                .await;
+        });
+    }
+
+    // Spawn DLQ trimmer so old failures don't accumulate forever
+    let trimmer = DlqTrimmer::new(
+        Arc::new(RedisDlq::new(redis_conn.clone())),
+        DlqRetention::from_env(),
+    );
+    let trimmer_token = cancel_token.clone();
+    tokio::spawn(async move {
+        trimmer
+            .run(
+                Duration::from_secs(DEFAULT_DLQ_TRIM_INTERVAL_SECS),
+                trimmer_token,
+            )
+            .await;
+    });
+
+    // Spawn database pool metrics (connections in use/idle, acquire wait)
+    let pool_metrics = PoolMetricsReporter::new(
+        db_pool,
+        "primary",
+        PoolMetricsReporter::acquire_warn_threshold_from_env(),
     );
+    let pool_metrics_token = cancel_token.clone();
+    tokio::spawn(async move {
+        pool_metrics
+            .run(
+                Duration::from_secs(DEFAULT_POOL_METRICS_INTERVAL_SECS),
+                pool_metrics_token,
+            )
+            .await;
+    });
 
     // Spawn metrics updater (queue depth)
-    let metrics_consumer = consumer.clone();
     let metrics_token = cancel_token.clone();
     tokio::spawn(async move {
-        update_metrics_loop(metrics_consumer, metrics_token).await;
+        update_metrics_loop(metrics_consumers, metrics_token).await;
     });
 
     // Wait for all workers to finish
@@ -183,6 +358,9 @@ async fn main() -> Result<()> {
         let _ = handle.await;
     }
 
+    // Write any buffered action results before exiting
+    logger.shutdown().await;
+
     metrics::set_active_workers(0);
     tracing::info!("All workers stopped, exiting");
 
@@ -190,12 +368,15 @@ async fn main() -> Result<()> {
 }
 
 /// Run a single worker that consumes jobs from the queue
-async fn run_worker<C, T, L1, D1, R, H, L2, D2, M, L3, D3>(
+#[allow(clippy::too_many_arguments)]
+async fn run_worker<C, T, L1, D1, R, H, L2, D2, M, L3, D3, S>(
     worker_id: usize,
     consumer: Arc<C>,
     telegram_worker: TelegramWorker<T, L1, D1, R>,
     rest_worker: RestWorker<H, L2, D2>,
     mcp_worker: McpWorker<M, L3, D3>,
+    dedup: Arc<Deduplicator<S>>,
+    result_webhooks: Arc<ResultWebhookNotifier>,
     cancel_token: CancellationToken,
 ) where
     C: JobConsumer,
@@ -209,9 +390,25 @@ async fn run_worker<C, T, L1, D1, R, H, L2, D2, M, L3, D3>(
     M: mcp::McpClient + 'static,
     L3: result_logger::ResultLogger + 'static,
     D3: dlq::DeadLetterQueue + 'static,
+    S: IdempotencyStore,
 {
     tracing::info!(worker_id = worker_id, "Worker started");
 
+    // Recover jobs left in our processing list by a previous crash
+    match consumer.requeue_in_flight().await {
+        Ok(0) => {}
+        Ok(count) => tracing::warn!(
+            worker_id = worker_id,
+            requeued = count,
+            "Requeued jobs left in flight by a previous run"
+        ),
+        Err(e) => tracing::error!(
+            worker_id = worker_id,
+            error = %e,
+            "Failed to recover in-flight jobs from previous run"
+        ),
+    }
+
     loop {
         tokio::select! {
             // Check for cancellation
@@ -224,40 +421,81 @@ async fn run_worker<C, T, L1, D1, R, H, L2, D2, M, L3, D3>(
             result = consumer.consume(CONSUME_TIMEOUT_SECS) => {
                 match result {
                     Ok(Some(job)) => {
+                        // Every log line emitted while processing carries the
+                        // job's origin so it can be correlated with the event
+                        let span = tracing::info_span!(
+                            "action_job",
+                            worker_id = worker_id,
+                            job_id = %job.id,
+                            correlation_id = %job.correlation_id,
+                            trigger_id = %job.trigger_id,
+                            event_id = %job.event_id,
+                            action_type = %job.action_type,
+                        );
+
+                        // Skip jobs equivalent to one completed within the dedup window
+                        // (replays from the DLQ are deliberate re-runs of such a job)
+                        if job.replay_count == 0 && dedup.is_duplicate(&job).await {
+                            span.in_scope(|| {
+                                tracing::info!("Skipping duplicate job within idempotency window");
+                            });
+                            metrics::record_duplicate_skipped(&job.action_type.to_string());
+                            if let Err(e) = consumer.ack(&job).await {
+                                tracing::error!(
+                                    worker_id = worker_id,
+                                    job_id = %job.id,
+                                    error = %e,
+                                    "Failed to acknowledge duplicate job"
+                                );
+                            }
+                            continue;
+                        }
+
                         // Use event_data from the job (populated by event-processor)
                         let event_data = job.event_data.clone();
 
-                        match job.action_type {
-                            ActionType::Telegram => {
-                                if let Err(e) = telegram_worker.process(&job, &event_data).await {
-                                    tracing::error!(
-                                        worker_id = worker_id,
-                                        job_id = %job.id,
-                                        error = %e,
-                                        "Telegram job processing failed (already moved to DLQ)"
-                                    );
+                        let processing = async {
+                            let (kind, result) = match job.action_type {
+                                ActionType::Telegram => {
+                                    ("Telegram", telegram_worker.process(&job, &event_data).await)
                                 }
-                            }
-                            ActionType::Rest => {
-                                if let Err(e) = rest_worker.process(&job, &event_data).await {
-                                    tracing::error!(
-                                        worker_id = worker_id,
-                                        job_id = %job.id,
-                                        error = %e,
-                                        "REST job processing failed (already moved to DLQ)"
-                                    );
+                                ActionType::Rest => {
+                                    ("REST", rest_worker.process(&job, &event_data).await)
                                 }
-                            }
-                            ActionType::Mcp => {
-                                if let Err(e) = mcp_worker.process(&job, &event_data).await {
-                                    tracing::error!(
-                                        worker_id = worker_id,
-                                        job_id = %job.id,
-                                        error = %e,
-                                        "MCP job processing failed (already moved to DLQ)"
-                                    );
+                                ActionType::Mcp => {
+                                    ("MCP", mcp_worker.process(&job, &event_data).await)
                                 }
+                            };
+
+                            let error = result.err().map(|e| e.to_string());
+                            if let Some(error) = &error {
+                                tracing::error!(
+                                    worker_id = worker_id,
+                                    job_id = %job.id,
+                                    error = %error,
+                                    "{} job processing failed (already moved to DLQ)",
+                                    kind
+                                );
                             }
+
+                            // Observability only: never affects the job outcome
+                            result_webhooks.notify(&job, error.as_deref());
+                        }
+                        .instrument(span);
+
+                        // On shutdown, let the job finish within the grace period;
+                        // otherwise it is requeued rather than lost
+                        let outcome = drain::run_with_drain(
+                            consumer.as_ref(),
+                            &job,
+                            processing,
+                            &cancel_token,
+                            Duration::from_secs(SHUTDOWN_GRACE_SECS),
+                        )
+                        .await;
+
+                        if outcome == drain::DrainOutcome::Completed {
+                            dedup.mark_completed(&job).await;
                         }
                     }
                     Ok(None) => {
@@ -281,8 +519,11 @@ async fn run_worker<C, T, L1, D1, R, H, L2, D2, M, L3, D3>(
     tracing::info!(worker_id = worker_id, "Worker stopped");
 }
 
-/// Periodically update queue depth metric
-async fn update_metrics_loop<C: JobConsumer>(consumer: Arc<C>, cancel_token: CancellationToken) {
+/// Periodically update queue depth metric (summed over every pool's queue)
+async fn update_metrics_loop<C: JobConsumer>(
+    consumers: Vec<Arc<C>>,
+    cancel_token: CancellationToken,
+) {
     loop {
         tokio::select! {
             _ = cancel_token.cancelled() => {
@@ -290,7 +531,7 @@ async fn update_metrics_loop<C: JobConsumer>(consumer: Arc<C>, cancel_token: Can
                 break;
             }
             _ = tokio::time::sleep(Duration::from_secs(METRICS_UPDATE_INTERVAL_SECS)) => {
-                match consumer.queue_len().await {
+                match total_queue_len(&consumers).await {
                     Ok(len) => {
                         metrics::set_queue_depth(len);
                         tracing::trace!(queue_depth = len, "Updated queue depth metric");
@@ -303,3 +544,30 @@ async fn update_metrics_loop<C: JobConsumer>(consumer: Arc<C>, cancel_token: Can
         }
     }
 }
+
+/// Jobs waiting across the queues of `consumers`
+async fn total_queue_len<C: JobConsumer>(consumers: &[Arc<C>]) -> error::WorkerResult<u64> {
+    let mut total = 0;
+    for consumer in consumers {
+        total += consumer.queue_len().await?;
+    }
+    Ok(total)
+}
+
+/// Check every dependency once and print a report (`--check`)
+async fn run_diagnostics() -> Result<()> {
+    let mut report = DiagnosticsReport::new("action-workers");
+    diagnostics::check_core(&mut report).await;
+
+    let token = std::env::var("TELEGRAM_BOT_TOKEN").or_else(|_| std::env::var("TELOXIDE_TOKEN"));
+    match token {
+        Ok(token) => {
+            report
+                .run("telegram", diagnostics::ping_telegram(&token))
+                .await;
+        }
+        Err(_) => report.skip("telegram", "TELEGRAM_BOT_TOKEN not set"),
+    }
+
+    report.finish()
+}
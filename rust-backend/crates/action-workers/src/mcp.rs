@@ -337,9 +337,9 @@ impl McpClient for HttpMcpClient {
             .await
             .map_err(|e| {
                 if e.is_timeout() {
-                    WorkerError::mcp(format!("Request timeout after {}ms", config.timeout_ms))
+                    WorkerError::timeout(format!("no response after {}ms", config.timeout_ms))
                 } else if e.is_connect() {
-                    WorkerError::mcp("Connection failed")
+                    WorkerError::connection("could not reach the MCP server")
                 } else {
                     WorkerError::mcp(format!("HTTP request failed: {}", e))
                 }
@@ -348,11 +348,14 @@ impl McpClient for HttpMcpClient {
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
-            return Err(WorkerError::mcp(format!(
-                "HTTP {} from MCP server: {}",
-                status,
-                truncate_string(&body, 200)
-            )));
+            return Err(WorkerError::http_status(
+                status.as_u16(),
+                format!(
+                    "HTTP {} from MCP server: {}",
+                    status,
+                    truncate_string(&body, 200)
+                ),
+            ));
         }
 
         // Parse JSON-RPC response
@@ -474,7 +477,7 @@ impl McpClient for MockMcpClient {
 
         // Return error if configured
         if let Some(ref error) = *self.error.lock().unwrap() {
-            return Err(WorkerError::mcp(error.to_string()));
+            return Err(error.duplicate());
         }
 
         // Return response if configured
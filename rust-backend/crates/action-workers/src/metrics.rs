@@ -110,6 +110,15 @@ pub fn record_job_dlq(action_type: &str) {
     counter!("action_worker_jobs_processed_total", "action_type" => action_type.to_string(), "status" => "dlq").increment(1);
 }
 
+/// Record a job skipped as a duplicate within the idempotency window
+///
+/// # Arguments
+///
+/// * `action_type` - Type of action
+pub fn record_duplicate_skipped(action_type: &str) {
+    counter!("action_worker_jobs_processed_total", "action_type" => action_type.to_string(), "status" => "duplicate").increment(1);
+}
+
 /// Record a retry attempt
 ///
 /// # Arguments
@@ -143,6 +152,35 @@ pub fn set_dlq_size(size: u64) {
     gauge!("action_worker_dlq_size").set(size as f64);
 }
 
+/// Record DLQ entries dropped by the retention trimmer
+///
+/// # Arguments
+///
+/// * `reason` - Why the entries were dropped (`age` or `count`)
+/// * `count` - Number of entries dropped
+pub fn record_dlq_trimmed(reason: &'static str, count: u64) {
+    counter!("action_worker_dlq_trimmed_total", "reason" => reason).increment(count);
+}
+
+/// Record an action result webhook delivery attempt
+///
+/// # Arguments
+///
+/// * `outcome` - `delivered` or `failed`
+pub fn record_result_webhook(outcome: &'static str) {
+    counter!("action_worker_result_webhooks_total", "outcome" => outcome).increment(1);
+}
+
+/// Record a credit charge for an action execution
+///
+/// # Arguments
+///
+/// * `outcome` - `charged`, `blocked` (insufficient credits before running)
+///   or `failed` (the charge after a successful run did not go through)
+pub fn record_credit_charge(outcome: &'static str) {
+    counter!("action_worker_credit_charges_total", "outcome" => outcome).increment(1);
+}
+
 /// Update the active workers count
 ///
 /// # Arguments
@@ -165,10 +203,13 @@ mod tests {
         record_job_success("telegram", 0.5);
         record_job_failure("rest", 1.0);
         record_job_dlq("mcp");
+        record_duplicate_skipped("rest");
         record_retry("telegram", 1);
         set_queue_depth(100);
         record_rate_limit_hit();
         set_dlq_size(5);
+        record_dlq_trimmed("age", 2);
+        record_credit_charge("charged");
         set_active_workers(3);
     }
 
@@ -1,27 +1,31 @@
 //! Rate limiting for action workers
 //!
-//! Provides rate limiting for Telegram messages using the governor crate.
-//! Telegram has a global limit of ~30 messages per second.
+//! Provides rate limiting for Telegram messages. Telegram limits bots to
+//! ~30 messages per second overall, and to about 1 message per second per
+//! chat (20 per minute to a group).
+//!
+//! Each limit is a GCRA bucket (the algorithm behind the `governor` crate)
+//! kept in memory. A send waits until the global bucket and the chat's
+//! buckets all have room, i.e. for the longest of their waits, and only then
+//! takes a permit from each.
 //!
 //! # Security
 //!
 //! - Global rate limiting prevents API abuse
 //! - Per-chat rate limiting prevents spamming individual chats
 //! - Configurable limits for different use cases
+//! - Per-chat state is bounded: idle chats are evicted first
 
-use governor::{
-    clock::DefaultClock,
-    state::{InMemoryState, NotKeyed},
-    Quota, RateLimiter as GovernorRateLimiter,
-};
 use std::collections::HashMap;
-use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::error::WorkerError;
 use crate::metrics;
 
+/// Default maximum number of chats tracked at once
+pub const DEFAULT_MAX_TRACKED_CHATS: usize = 10_000;
+
 /// Rate limiter trait for testability
 #[allow(dead_code)]
 pub trait RateLimiter: Send + Sync {
@@ -60,27 +64,162 @@ pub trait RateLimiter: Send + Sync {
     ) -> impl std::future::Future<Output = Result<(), WorkerError>> + Send;
 }
 
-/// Type alias for the rate limiter to reduce complexity
-type ChatRateLimiter = GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+/// At most `count` messages per `period`, sent in bursts of up to `count`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limit {
+    count: u32,
+    period: Duration,
+}
+
+impl Limit {
+    /// # Panics
+    ///
+    /// If `count` is zero or `period` is zero
+    pub fn new(count: u32, period: Duration) -> Self {
+        assert!(count > 0, "Rate limit count must be > 0");
+        assert!(!period.is_zero(), "Rate limit period must be > 0");
+        Self { count, period }
+    }
+
+    pub fn per_second(count: u32) -> Self {
+        Self::new(count, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(count: u32) -> Self {
+        Self::new(count, Duration::from_secs(60))
+    }
+
+    /// Time for one message's permit to come back
+    fn interval(&self) -> Duration {
+        self.period / self.count
+    }
+
+    /// How far ahead of schedule a burst may run
+    fn burst_tolerance(&self) -> Duration {
+        self.interval() * (self.count - 1)
+    }
+}
+
+/// Per-chat allowance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChatLimits {
+    /// Short-term limit (Telegram: about 1 message per second)
+    pub per_second: Limit,
+    /// Sustained limit (Telegram: 20 messages per minute to a group)
+    pub per_minute: Limit,
+}
+
+impl Default for ChatLimits {
+    fn default() -> Self {
+        Self {
+            per_second: Limit::per_second(1),
+            per_minute: Limit::per_minute(20),
+        }
+    }
+}
+
+/// GCRA state for one limit: when the next message is due if sent on schedule
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    theoretical_arrival: Instant,
+}
+
+impl Bucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            theoretical_arrival: now,
+        }
+    }
+
+    /// How long until this bucket allows a message (zero if it does now)
+    fn wait(&self, limit: &Limit, now: Instant) -> Duration {
+        self.theoretical_arrival
+            .saturating_duration_since(now + limit.burst_tolerance())
+    }
+
+    fn record(&mut self, limit: &Limit, now: Instant) {
+        self.theoretical_arrival = self.theoretical_arrival.max(now) + limit.interval();
+    }
+}
+
+/// Both of a chat's buckets
+#[derive(Debug, Clone, Copy)]
+struct ChatBuckets {
+    per_second: Bucket,
+    per_minute: Bucket,
+}
+
+impl ChatBuckets {
+    fn new(now: Instant) -> Self {
+        Self {
+            per_second: Bucket::new(now),
+            per_minute: Bucket::new(now),
+        }
+    }
+
+    fn wait(&self, limits: &ChatLimits, now: Instant) -> Duration {
+        self.per_second
+            .wait(&limits.per_second, now)
+            .max(self.per_minute.wait(&limits.per_minute, now))
+    }
+
+    fn record(&mut self, limits: &ChatLimits, now: Instant) {
+        self.per_second.record(&limits.per_second, now);
+        self.per_minute.record(&limits.per_minute, now);
+    }
+
+    /// When both buckets are full again; forgetting the chat after this
+    /// changes nothing
+    fn idle_at(&self) -> Instant {
+        self.per_second
+            .theoretical_arrival
+            .max(self.per_minute.theoretical_arrival)
+    }
+}
+
+/// Shared limiter state
+#[derive(Debug)]
+struct LimiterState {
+    global: Bucket,
+    chats: HashMap<String, ChatBuckets>,
+}
+
+/// Which limit held a send back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Blocker {
+    Global,
+    PerChat,
+}
+
+impl Blocker {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::PerChat => "per-chat",
+        }
+    }
+}
 
 /// Telegram-specific rate limiter
 ///
 /// Enforces both:
 /// - Global rate limit of 30 messages per second (Telegram API limit)
-/// - Per-chat rate limit of 1 message per second (prevent spam)
+/// - Per-chat rate limits of 1 message per second and 20 per minute
+///
+/// Clones share state.
+#[derive(Clone)]
 pub struct TelegramRateLimiter {
-    /// Global rate limiter
-    global_limiter: Arc<ChatRateLimiter>,
-    /// Per-chat rate limiters
-    per_chat_limiters: Arc<Mutex<HashMap<String, Arc<ChatRateLimiter>>>>,
-    /// Rate for per-chat limiting (messages per second)
-    per_chat_rate: u32,
+    state: Arc<Mutex<LimiterState>>,
+    global_limit: Limit,
+    chat_limits: ChatLimits,
+    /// Most chats tracked at once
+    max_tracked_chats: usize,
 }
 
 impl TelegramRateLimiter {
     /// Create a new Telegram rate limiter
     ///
-    /// Default: 30 messages/sec globally, 1 message/sec per chat
+    /// Default: 30 messages/sec globally, 1 message/sec and 20 messages/min per chat
     pub fn new() -> Self {
         Self::with_rates(30, 1)
     }
@@ -100,109 +239,169 @@ impl TelegramRateLimiter {
     /// # Arguments
     ///
     /// * `global_rate` - Maximum messages per second globally
-    /// * `per_chat_rate` - Maximum messages per second per chat
+    /// * `per_chat_rate` - Maximum messages per second per chat (the
+    ///   per-minute chat limit keeps its default)
     pub fn with_rates(global_rate: u32, per_chat_rate: u32) -> Self {
-        let global_quota =
-            Quota::per_second(NonZeroU32::new(global_rate).expect("Global rate must be > 0"));
+        Self::with_limits(
+            Limit::per_second(global_rate),
+            ChatLimits {
+                per_second: Limit::per_second(per_chat_rate),
+                ..ChatLimits::default()
+            },
+        )
+    }
+
+    /// Create with custom global and per-chat limits
+    pub fn with_limits(global_limit: Limit, chat_limits: ChatLimits) -> Self {
         Self {
-            global_limiter: Arc::new(GovernorRateLimiter::direct(global_quota)),
-            per_chat_limiters: Arc::new(Mutex::new(HashMap::new())),
-            per_chat_rate,
+            state: Arc::new(Mutex::new(LimiterState {
+                global: Bucket::new(Instant::now()),
+                chats: HashMap::new(),
+            })),
+            global_limit,
+            chat_limits,
+            max_tracked_chats: DEFAULT_MAX_TRACKED_CHATS,
         }
     }
 
+    /// Set the most chats tracked at once
+    ///
+    /// When full, idle chats are forgotten first, then the chats closest to
+    /// idle.
+    #[allow(dead_code)]
+    pub fn with_max_tracked_chats(mut self, max_tracked_chats: usize) -> Self {
+        self.max_tracked_chats = max_tracked_chats.max(1);
+        self
+    }
+
     /// Check if rate limit would allow immediate execution
     #[allow(dead_code)]
     pub fn check(&self) -> bool {
-        self.global_limiter.check().is_ok()
+        let state = self.state.lock().unwrap();
+        state
+            .global
+            .wait(&self.global_limit, Instant::now())
+            .is_zero()
     }
 
-    /// Get or create a rate limiter for a specific chat
-    fn get_chat_limiter(&self, chat_id: &str) -> Arc<ChatRateLimiter> {
-        let mut limiters = self.per_chat_limiters.lock().unwrap();
-
-        limiters
-            .entry(chat_id.to_string())
-            .or_insert_with(|| {
-                let quota = Quota::per_second(
-                    NonZeroU32::new(self.per_chat_rate).expect("Per-chat rate must be > 0"),
-                );
-                Arc::new(ChatRateLimiter::direct(quota))
-            })
-            .clone()
-    }
-}
+    /// Take a permit from the global bucket and the chat's buckets if all
+    /// have room
+    ///
+    /// Nothing is taken otherwise; returns how long until they all have room
+    /// and which limit is the longest wait.
+    fn try_reserve(&self, chat_id: Option<&str>) -> Result<(), (Duration, Blocker)> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        let global_wait = state.global.wait(&self.global_limit, now);
+        let chat_wait = chat_id
+            .and_then(|id| state.chats.get(id))
+            .map_or(Duration::ZERO, |chat| chat.wait(&self.chat_limits, now));
+
+        if chat_wait > global_wait {
+            return Err((chat_wait, Blocker::PerChat));
+        }
+        if !global_wait.is_zero() {
+            return Err((global_wait, Blocker::Global));
+        }
 
-impl Default for TelegramRateLimiter {
-    fn default() -> Self {
-        Self::new()
+        state.global.record(&self.global_limit, now);
+        if let Some(id) = chat_id {
+            if !state.chats.contains_key(id) {
+                self.make_room(&mut state.chats, now);
+            }
+            state
+                .chats
+                .entry(id.to_string())
+                .or_insert_with(|| ChatBuckets::new(now))
+                .record(&self.chat_limits, now);
+        }
+        Ok(())
     }
-}
 
-impl Clone for TelegramRateLimiter {
-    fn clone(&self) -> Self {
-        Self {
-            global_limiter: self.global_limiter.clone(),
-            per_chat_limiters: self.per_chat_limiters.clone(),
-            per_chat_rate: self.per_chat_rate,
+    /// Evict chats until there is room for one more
+    fn make_room(&self, chats: &mut HashMap<String, ChatBuckets>, now: Instant) {
+        if chats.len() < self.max_tracked_chats {
+            return;
         }
-    }
-}
 
-impl RateLimiter for TelegramRateLimiter {
-    async fn acquire(&self, timeout: Duration) -> Result<(), WorkerError> {
-        // Try to acquire global permit within timeout
-        match tokio::time::timeout(timeout, self.global_limiter.until_ready()).await {
-            Ok(()) => Ok(()),
-            Err(_) => {
-                metrics::record_rate_limit_hit();
-                tracing::warn!(
-                    timeout_ms = timeout.as_millis(),
-                    "Global rate limit acquisition timed out"
-                );
-                Err(WorkerError::rate_limit(format!(
-                    "Timed out waiting for global rate limit after {}ms",
-                    timeout.as_millis()
-                )))
-            }
+        chats.retain(|_, chat| chat.idle_at() > now);
+
+        while chats.len() >= self.max_tracked_chats {
+            let Some(chat_id) = chats
+                .iter()
+                .min_by_key(|(_, chat)| chat.idle_at())
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            tracing::debug!(
+                chat_id = %chat_id,
+                max_tracked_chats = self.max_tracked_chats,
+                "Evicting active chat from rate limiter"
+            );
+            chats.remove(&chat_id);
         }
     }
 
-    async fn acquire_for_key(&self, key: &str, timeout: Duration) -> Result<(), WorkerError> {
-        // First acquire global limit
-        match tokio::time::timeout(timeout, self.global_limiter.until_ready()).await {
-            Ok(()) => {}
-            Err(_) => {
+    /// Wait until the global limit (and the chat's, if any) allow a send
+    ///
+    /// Fails without waiting once it is clear the wait would outlast `timeout`.
+    async fn acquire_inner(
+        &self,
+        chat_id: Option<&str>,
+        timeout: Duration,
+    ) -> Result<(), WorkerError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let (wait, blocker) = match self.try_reserve(chat_id) {
+                Ok(()) => return Ok(()),
+                Err(blocked) => blocked,
+            };
+
+            if Instant::now() + wait > deadline {
                 metrics::record_rate_limit_hit();
                 tracing::warn!(
+                    chat_id = chat_id,
+                    limit = blocker.as_str(),
+                    wait_ms = wait.as_millis(),
                     timeout_ms = timeout.as_millis(),
-                    "Global rate limit acquisition timed out"
+                    "Rate limit acquisition timed out"
                 );
                 return Err(WorkerError::rate_limit(format!(
-                    "Timed out waiting for global rate limit after {}ms",
+                    "Timed out waiting for {} rate limit after {}ms",
+                    blocker.as_str(),
                     timeout.as_millis()
                 )));
             }
-        }
 
-        // Then acquire per-chat limit
-        let chat_limiter = self.get_chat_limiter(key);
-        match tokio::time::timeout(timeout, chat_limiter.until_ready()).await {
-            Ok(()) => Ok(()),
-            Err(_) => {
-                metrics::record_rate_limit_hit();
-                tracing::warn!(
-                    key = key,
-                    timeout_ms = timeout.as_millis(),
-                    "Per-chat rate limit acquisition timed out"
-                );
-                Err(WorkerError::rate_limit(format!(
-                    "Timed out waiting for per-chat rate limit after {}ms",
-                    timeout.as_millis()
-                )))
-            }
+            // Other senders may take the permit first; try again after waking
+            tokio::time::sleep(wait).await;
         }
     }
+
+    /// Number of chats currently tracked
+    #[cfg(test)]
+    fn tracked_chats(&self) -> usize {
+        self.state.lock().unwrap().chats.len()
+    }
+}
+
+impl Default for TelegramRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimiter for TelegramRateLimiter {
+    async fn acquire(&self, timeout: Duration) -> Result<(), WorkerError> {
+        self.acquire_inner(None, timeout).await
+    }
+
+    async fn acquire_for_key(&self, key: &str, timeout: Duration) -> Result<(), WorkerError> {
+        self.acquire_inner(Some(key), timeout).await
+    }
 }
 
 /// No-op rate limiter for testing
@@ -315,6 +514,179 @@ mod tests {
             .is_ok());
     }
 
+    /// Limiter with a generous global limit and the given per-chat limit
+    fn per_chat_limiter(per_chat: Limit) -> TelegramRateLimiter {
+        TelegramRateLimiter::with_limits(
+            Limit::per_second(1000),
+            ChatLimits {
+                per_second: per_chat,
+                per_minute: Limit::per_minute(1000),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rapid_sends_to_same_chat_are_throttled() {
+        let limiter = per_chat_limiter(Limit::new(1, Duration::from_millis(100)));
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter
+                .acquire_for_key("chat1", Duration::from_secs(1))
+                .await
+                .unwrap();
+        }
+
+        // The 2nd and 3rd sends each wait out the 100ms interval
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_sends_to_different_chats_proceed() {
+        let limiter = per_chat_limiter(Limit::new(1, Duration::from_secs(1)));
+
+        let start = Instant::now();
+        for i in 0..5 {
+            limiter
+                .acquire_for_key(&format!("chat{}", i), Duration::from_secs(5))
+                .await
+                .unwrap();
+        }
+
+        // Throttling any of them would take a full second
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_per_chat_timeout_fails_without_waiting() {
+        let limiter = per_chat_limiter(Limit::new(1, Duration::from_secs(10)));
+        limiter
+            .acquire_for_key("chat1", Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        let start = Instant::now();
+        let err = limiter
+            .acquire_for_key("chat1", Duration::from_secs(1))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, WorkerError::RateLimitExceeded(_)));
+        assert!(err.to_string().contains("per-chat"));
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // Other chats are unaffected
+        assert!(limiter
+            .acquire_for_key("chat2", Duration::from_millis(10))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_per_minute_chat_limit_applies_after_burst() {
+        let limiter = TelegramRateLimiter::with_limits(
+            Limit::per_second(1000),
+            ChatLimits {
+                per_second: Limit::per_second(1000),
+                per_minute: Limit::per_minute(3),
+            },
+        );
+
+        for _ in 0..3 {
+            limiter
+                .acquire_for_key("group", Duration::from_millis(10))
+                .await
+                .unwrap();
+        }
+        assert!(limiter
+            .acquire_for_key("group", Duration::from_millis(10))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_waits_for_longer_of_global_and_chat_limits() {
+        let limiter = TelegramRateLimiter::with_limits(
+            Limit::new(1, Duration::from_secs(30)),
+            ChatLimits {
+                per_second: Limit::new(1, Duration::from_secs(10)),
+                per_minute: Limit::per_minute(1000),
+            },
+        );
+        limiter.try_reserve(Some("chat1")).unwrap();
+
+        // Both limits are exhausted; the global one clears last
+        let (wait, blocker) = limiter.try_reserve(Some("chat1")).unwrap_err();
+        assert_eq!(blocker, Blocker::Global);
+        assert!(wait > Duration::from_secs(10) && wait <= Duration::from_secs(30));
+
+        let limiter = TelegramRateLimiter::with_limits(
+            Limit::new(1, Duration::from_secs(10)),
+            ChatLimits {
+                per_second: Limit::new(1, Duration::from_secs(30)),
+                per_minute: Limit::per_minute(1000),
+            },
+        );
+        limiter.try_reserve(Some("chat1")).unwrap();
+
+        let (wait, blocker) = limiter.try_reserve(Some("chat1")).unwrap_err();
+        assert_eq!(blocker, Blocker::PerChat);
+        assert!(wait > Duration::from_secs(10) && wait <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_blocked_send_takes_no_permits() {
+        let limiter = TelegramRateLimiter::with_limits(
+            Limit::new(2, Duration::from_secs(30)),
+            ChatLimits {
+                per_second: Limit::new(1, Duration::from_secs(30)),
+                per_minute: Limit::per_minute(1000),
+            },
+        );
+        limiter.try_reserve(Some("chat1")).unwrap();
+
+        // Held back by chat1's limit, so the second global permit is left
+        assert!(limiter.try_reserve(Some("chat1")).is_err());
+        assert!(limiter.try_reserve(Some("chat2")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tracked_chats_are_bounded() {
+        let limiter =
+            per_chat_limiter(Limit::new(1, Duration::from_secs(60))).with_max_tracked_chats(3);
+
+        for i in 0..10 {
+            limiter
+                .acquire_for_key(&format!("chat{}", i), Duration::from_millis(10))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(limiter.tracked_chats(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_idle_chats_are_evicted_first() {
+        let limiter =
+            per_chat_limiter(Limit::new(1, Duration::from_millis(1))).with_max_tracked_chats(2);
+        for chat_id in ["idle1", "idle2"] {
+            limiter
+                .acquire_for_key(chat_id, Duration::from_millis(10))
+                .await
+                .unwrap();
+        }
+        // Long enough for both buckets (1ms and 60ms intervals) to refill
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        limiter
+            .acquire_for_key("new_chat", Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        // Both idle chats were dropped to make room
+        assert_eq!(limiter.tracked_chats(), 1);
+    }
+
     #[tokio::test]
     async fn test_noop_limiter_per_key() {
         let limiter = NoopRateLimiter;
@@ -0,0 +1,263 @@
+//! Visibility-timeout reaper for the reliable job queue
+//!
+//! Workers keep each consumed job in their own processing list until it is
+//! acknowledged (see [`crate::consumer`]). A worker that restarts recovers its
+//! own list, but a worker instance that never comes back (scaled down, host
+//! replaced) would strand its jobs. The reaper periodically scans the claims
+//! set and requeues any job that has been in flight longer than the visibility
+//! timeout onto its priority lane.
+//!
+//! The timeout must exceed the longest expected processing time (including
+//! retries), otherwise slow jobs are delivered twice.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Script};
+use shared::ActionJob;
+use tokio_util::sync::CancellationToken;
+
+use crate::consumer::{claims_key, parse_claim_member, REQUEUE_SCRIPT};
+use crate::error::{WorkerError, WorkerResult};
+
+/// Default visibility timeout in seconds (5 minutes)
+pub const DEFAULT_VISIBILITY_TIMEOUT_SECS: u64 = 300;
+
+/// Default interval between reaper scans in seconds
+pub const DEFAULT_REAP_INTERVAL_SECS: u64 = 30;
+
+/// Maximum number of expired claims handled per scan
+const REAP_BATCH_SIZE: isize = 100;
+
+/// Requeues jobs stuck in processing lists past the visibility timeout
+pub struct ProcessingReaper {
+    conn: MultiplexedConnection,
+    queue_name: String,
+    claims_key: String,
+    visibility_timeout: Duration,
+    script: Script,
+}
+
+impl ProcessingReaper {
+    /// Create a reaper for a queue (already prefixed with [`shared::redis::queue_key`])
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - Multiplexed Redis connection
+    /// * `queue_name` - Queue whose processing lists are reaped
+    /// * `visibility_timeout` - How long a job may stay in flight before it is requeued
+    pub fn with_queue_name(
+        conn: MultiplexedConnection,
+        queue_name: &str,
+        visibility_timeout: Duration,
+    ) -> Self {
+        Self {
+            conn,
+            queue_name: queue_name.to_string(),
+            claims_key: claims_key(queue_name),
+            visibility_timeout,
+            script: Script::new(REQUEUE_SCRIPT),
+        }
+    }
+
+    /// Read the visibility timeout from `JOB_VISIBILITY_TIMEOUT_SECS`
+    pub fn visibility_timeout_from_env() -> Duration {
+        let secs = std::env::var("JOB_VISIBILITY_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_VISIBILITY_TIMEOUT_SECS);
+        Duration::from_secs(secs)
+    }
+
+    /// Requeue every job whose claim is older than the visibility timeout
+    ///
+    /// # Returns
+    ///
+    /// Number of jobs returned to the queue
+    pub async fn reap_once(&self) -> WorkerResult<u64> {
+        let mut conn = self.conn.clone();
+        let cutoff = Utc::now().timestamp() - self.visibility_timeout.as_secs() as i64;
+
+        let expired: Vec<String> = conn
+            .zrangebyscore_limit(&self.claims_key, "-inf", cutoff, 0, REAP_BATCH_SIZE)
+            .await
+            .map_err(WorkerError::Redis)?;
+
+        let mut requeued = 0;
+
+        for member in expired {
+            let Some((processing_list, job_id)) = parse_claim_member(&member) else {
+                conn.zrem::<_, _, ()>(&self.claims_key, &member)
+                    .await
+                    .map_err(WorkerError::Redis)?;
+                continue;
+            };
+
+            let payloads: Vec<String> = conn
+                .lrange(processing_list, 0, -1)
+                .await
+                .map_err(WorkerError::Redis)?;
+
+            let found = payloads.into_iter().find_map(|payload| {
+                let job = serde_json::from_str::<ActionJob>(&payload).ok()?;
+                (job.id == job_id).then(|| (payload, job.queue_priority()))
+            });
+
+            let Some((payload, priority)) = found else {
+                // Acked or recovered by its worker since the claim was made
+                conn.zrem::<_, _, ()>(&self.claims_key, &member)
+                    .await
+                    .map_err(WorkerError::Redis)?;
+                continue;
+            };
+
+            let removed: i64 = self
+                .script
+                .key(processing_list)
+                .key(priority.lane(&self.queue_name))
+                .key(&self.claims_key)
+                .arg(&payload)
+                .arg(&member)
+                .invoke_async(&mut conn)
+                .await
+                .map_err(WorkerError::Redis)?;
+
+            if removed == 1 {
+                requeued += 1;
+                tracing::warn!(
+                    job_id = %job_id,
+                    processing_list = %processing_list,
+                    visibility_timeout_secs = self.visibility_timeout.as_secs(),
+                    "Requeued job stuck in processing past visibility timeout"
+                );
+            }
+        }
+
+        Ok(requeued)
+    }
+
+    /// Run the reaper until cancelled
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between scans
+    /// * `cancel_token` - Shutdown signal
+    pub async fn run(self, interval: Duration, cancel_token: CancellationToken) {
+        tracing::info!(
+            visibility_timeout_secs = self.visibility_timeout.as_secs(),
+            interval_secs = interval.as_secs(),
+            "Processing list reaper started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    tracing::debug!("Processing list reaper stopping");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    match self.reap_once().await {
+                        Ok(0) => {}
+                        Ok(count) => tracing::info!(requeued = count, "Reaper requeued stuck jobs"),
+                        Err(e) => tracing::warn!(error = %e, "Reaper scan failed"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consumer::{JobConsumer, RedisJobConsumer};
+
+    async fn redis_conn() -> MultiplexedConnection {
+        let url =
+            std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        redis::Client::open(url)
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .expect("Redis must be running for this test")
+    }
+
+    fn create_test_job() -> ActionJob {
+        ActionJob::new(
+            "trigger-1",
+            "event-1",
+            shared::ActionType::Rest,
+            1,
+            serde_json::json!({"url": "https://example.com"}),
+            serde_json::json!({"agent_id": 42}),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_job_from_killed_worker_is_reprocessed() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let job = create_test_job();
+        conn.lpush::<_, _, ()>(&queue, serde_json::to_string(&job).unwrap())
+            .await
+            .unwrap();
+
+        // A worker takes the job mid-flight and is killed: it never acks and
+        // never comes back to recover its own processing list
+        let killed = tokio::spawn({
+            let conn = conn.clone();
+            let queue = queue.clone();
+            async move {
+                let consumer = RedisJobConsumer::with_queue_name(conn, &queue, "doomed:0");
+                let _job = consumer.consume(1).await.unwrap().unwrap();
+                std::future::pending::<()>().await;
+            }
+        });
+        while conn.llen::<_, u64>(&queue).await.unwrap() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // Claim timestamps have one-second resolution
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        killed.abort();
+
+        // Not yet past the visibility timeout: nothing happens
+        let patient =
+            ProcessingReaper::with_queue_name(conn.clone(), &queue, Duration::from_secs(3600));
+        assert_eq!(patient.reap_once().await.unwrap(), 0);
+
+        let reaper = ProcessingReaper::with_queue_name(conn.clone(), &queue, Duration::ZERO);
+        assert_eq!(reaper.reap_once().await.unwrap(), 1);
+        // Reaping is idempotent
+        assert_eq!(reaper.reap_once().await.unwrap(), 0);
+
+        // Another worker picks the job up again
+        let survivor = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "survivor:0");
+        let redelivered = survivor.consume(1).await.unwrap().unwrap();
+        assert_eq!(redelivered.id, job.id);
+        survivor.ack(&redelivered).await.unwrap();
+
+        let _: () = conn.del(claims_key(&queue)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_acked_job_is_not_requeued() {
+        let mut conn = redis_conn().await;
+        let queue = format!("test_queue_{}", uuid::Uuid::new_v4());
+        let job = create_test_job();
+        conn.lpush::<_, _, ()>(&queue, serde_json::to_string(&job).unwrap())
+            .await
+            .unwrap();
+
+        let consumer = RedisJobConsumer::with_queue_name(conn.clone(), &queue, "worker:0");
+        let consumed = consumer.consume(1).await.unwrap().unwrap();
+        consumer.ack(&consumed).await.unwrap();
+
+        let reaper = ProcessingReaper::with_queue_name(conn.clone(), &queue, Duration::ZERO);
+        assert_eq!(reaper.reap_once().await.unwrap(), 0);
+        assert_eq!(consumer.queue_len().await.unwrap(), 0);
+    }
+}
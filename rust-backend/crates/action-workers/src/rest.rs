@@ -1,6 +1,13 @@
 //! REST/HTTP action worker
 //!
-//! Executes HTTP requests to external APIs.
+//! Executes HTTP requests to external APIs. Requests for organizations with
+//! a signing key ([`crate::signing_keys`]) carry the same timestamp and
+//! signature headers as other deliveries, computed over the request body
+//! (empty for methods without one).
+//!
+//! Also provides the URL checks (SSRF protection) and HMAC-SHA256 request
+//! signing used for other deliveries to customer endpoints, such as the
+//! [`crate::result_webhook`] notifications.
 
 use async_trait::async_trait;
 use reqwest::{header, Client, Method};
@@ -21,6 +28,8 @@ const MAX_URL_LENGTH: usize = 2048;
 /// Maximum header value length for security
 const MAX_HEADER_VALUE_LENGTH: usize = 1024;
 
+pub use shared::signing::{SigningKeys, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
 /// REST action configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct RestConfig {
@@ -101,6 +110,14 @@ impl RestConfig {
 
 /// Validate URL format and security constraints
 fn validate_url(url: &str) -> Result<(), WorkerError> {
+    validate_target_url(url, false)
+}
+
+/// Validate a customer-supplied URL before sending anything to it
+///
+/// `allow_private_hosts` disables the SSRF check and exists for tests against
+/// local servers only.
+pub fn validate_target_url(url: &str, allow_private_hosts: bool) -> Result<(), WorkerError> {
     if url.is_empty() {
         return Err(WorkerError::invalid_config("URL cannot be empty"));
     }
@@ -126,7 +143,7 @@ fn validate_url(url: &str) -> Result<(), WorkerError> {
     }
 
     // Security: Block requests to private/internal IP ranges (SSRF protection)
-    if let Some(host) = parsed.host() {
+    if let Some(host) = parsed.host().filter(|_| !allow_private_hosts) {
         if is_private_host(&host) {
             return Err(WorkerError::invalid_config(format!(
                 "URL host '{}' is a private/internal address (SSRF protection)",
@@ -273,6 +290,7 @@ pub trait HttpClient: Send + Sync {
     ///
     /// * `config` - REST action configuration
     /// * `event_data` - Event data for template variable substitution
+    /// * `signing_keys` - Keys to sign the request with, if the organization has any
     ///
     /// # Returns
     ///
@@ -281,6 +299,7 @@ pub trait HttpClient: Send + Sync {
         &self,
         config: &RestConfig,
         event_data: &serde_json::Value,
+        signing_keys: Option<&SigningKeys>,
     ) -> Result<RestResponse, WorkerError>;
 }
 
@@ -334,6 +353,7 @@ impl HttpClient for ReqwestHttpClient {
         &self,
         config: &RestConfig,
         event_data: &serde_json::Value,
+        signing_keys: Option<&SigningKeys>,
     ) -> Result<RestResponse, WorkerError> {
         // Validate configuration
         config.validate()?;
@@ -366,6 +386,7 @@ impl HttpClient for ReqwestHttpClient {
         }
 
         // Add body for POST/PUT/PATCH
+        let mut body_bytes = Vec::new();
         if matches!(method, Method::POST | Method::PUT | Method::PATCH) {
             if let Some(body_template) = &config.body {
                 // Render template in body JSON
@@ -376,12 +397,24 @@ impl HttpClient for ReqwestHttpClient {
                     "Adding request body"
                 );
 
+                // Serialized once so the signature covers the exact bytes sent
+                body_bytes = serde_json::to_vec(&rendered_body).map_err(|e| {
+                    WorkerError::invalid_config(format!("Failed to serialize body: {}", e))
+                })?;
                 request_builder = request_builder
                     .header(header::CONTENT_TYPE, "application/json")
-                    .json(&rendered_body);
+                    .body(body_bytes.clone());
             }
         }
 
+        // Sign with the organization's keys (current, plus previous in its grace window)
+        if let Some(keys) = signing_keys {
+            let timestamp = chrono::Utc::now().timestamp();
+            request_builder = request_builder
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, keys.sign(timestamp, &body_bytes));
+        }
+
         // Execute request
         tracing::info!(
             method = %method,
@@ -392,9 +425,9 @@ impl HttpClient for ReqwestHttpClient {
 
         let response = request_builder.send().await.map_err(|e| {
             if e.is_timeout() {
-                WorkerError::telegram(format!("Request timeout after {}s", config.timeout_seconds))
+                WorkerError::timeout(format!("no response after {}s", config.timeout_seconds))
             } else if e.is_connect() {
-                WorkerError::telegram("Connection failed")
+                WorkerError::connection("could not reach the target host")
             } else {
                 WorkerError::telegram(format!("HTTP request failed: {}", e))
             }
@@ -422,13 +455,8 @@ impl HttpClient for ReqwestHttpClient {
                 format!("Unexpected status code {}", status)
             };
 
-            // 4xx errors are not retryable (client errors)
-            if (400..500).contains(&status) {
-                return Err(WorkerError::invalid_config(error_msg));
-            } else {
-                // 5xx errors are retryable (server errors)
-                return Err(WorkerError::telegram(error_msg));
-            }
+            // 4xx errors are not retryable (client errors), 5xx are
+            return Err(WorkerError::http_status(status, error_msg));
         }
 
         tracing::info!(
@@ -508,6 +536,7 @@ pub struct ExecutedRequest {
     pub url: String,
     pub headers: HashMap<String, String>,
     pub body: Option<serde_json::Value>,
+    pub signing_keys: Option<SigningKeys>,
 }
 
 #[cfg(test)]
@@ -546,6 +575,7 @@ impl HttpClient for MockHttpClient {
         &self,
         config: &RestConfig,
         event_data: &serde_json::Value,
+        signing_keys: Option<&SigningKeys>,
     ) -> Result<RestResponse, WorkerError> {
         // Validate config
         config.validate()?;
@@ -572,11 +602,12 @@ impl HttpClient for MockHttpClient {
             url,
             headers,
             body,
+            signing_keys: signing_keys.cloned(),
         });
 
         // Return error if configured
         if let Some(ref error) = *self.error.lock().unwrap() {
-            return Err(WorkerError::telegram(error.to_string()));
+            return Err(error.duplicate());
         }
 
         // Return response if configured
@@ -599,13 +630,8 @@ impl HttpClient for MockHttpClient {
                 format!("Unexpected status code {}", response.status)
             };
 
-            // 4xx errors are not retryable (client errors)
-            if (400..500).contains(&response.status) {
-                return Err(WorkerError::invalid_config(error_msg));
-            } else {
-                // 5xx errors are retryable (server errors)
-                return Err(WorkerError::telegram(error_msg));
-            }
+            // 4xx errors are not retryable (client errors), 5xx are
+            return Err(WorkerError::http_status(response.status, error_msg));
         }
 
         Ok(response)
@@ -705,6 +731,14 @@ mod tests {
         assert!(validate_url("http://0.0.0.0/path").is_err());
     }
 
+    #[test]
+    fn test_validate_target_url_allow_private_hosts() {
+        assert!(validate_target_url("http://127.0.0.1:8080/hook", false).is_err());
+        assert!(validate_target_url("http://127.0.0.1:8080/hook", true).is_ok());
+        // Scheme checks still apply
+        assert!(validate_target_url("file:///etc/passwd", true).is_err());
+    }
+
     #[test]
     fn test_is_private_ip() {
         // IPv4 private ranges
@@ -844,7 +878,7 @@ mod tests {
             expected_status_codes: vec![200],
         };
 
-        let result = client.execute_request(&config, &json!({})).await;
+        let result = client.execute_request(&config, &json!({}), None).await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -855,7 +889,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_mock_client_error() {
-        let client = MockHttpClient::new().with_error(WorkerError::telegram("Connection failed"));
+        let client =
+            MockHttpClient::new().with_error(WorkerError::connection("could not reach host"));
 
         let config = RestConfig {
             method: "GET".to_string(),
@@ -866,7 +901,7 @@ mod tests {
             expected_status_codes: vec![200],
         };
 
-        let result = client.execute_request(&config, &json!({})).await;
+        let result = client.execute_request(&config, &json!({}), None).await;
         assert!(result.is_err());
     }
 
@@ -888,7 +923,7 @@ mod tests {
 
         let vars = json!({"agent_id": "42", "score": 85});
 
-        let result = client.execute_request(&config, &vars).await;
+        let result = client.execute_request(&config, &vars, None).await;
         assert!(result.is_ok());
 
         let requests = client.requests();
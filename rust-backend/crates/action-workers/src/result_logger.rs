@@ -1,14 +1,41 @@
 //! Result logging for action execution
 //!
 //! Logs action execution results to PostgreSQL for audit and analytics.
+//!
+//! Failed results carry a [`FailureReason`]: a [`FailureCategory`] plus a
+//! machine-readable code, derived from the [`WorkerError`] that ended the job
+//! (see [`WorkerError::failure_reason`]). Both are stored in their own columns
+//! so failures can be grouped without parsing error messages.
+//!
+//! # Batching
+//!
+//! [`BufferedResultLogger`] wraps a [`BatchResultWriter`] and buffers results in
+//! memory, writing them with one multi-row INSERT when the buffer reaches
+//! `batch_size` or every `flush_interval`, whichever comes first. Call
+//! [`BufferedResultLogger::shutdown`] before exiting to flush what is left.
+
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use crate::error::{WorkerError, WorkerResult};
 
+/// Default number of results written per batch
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// Default maximum time a result waits in the buffer
+pub const DEFAULT_FLUSH_INTERVAL_MS: u64 = 1000;
+
+/// Upper bound on batch size (11 bind parameters per row, Postgres allows 65535)
+const MAX_BATCH_SIZE: usize = 1000;
+
 /// Action execution status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -28,6 +55,65 @@ impl std::fmt::Display for ActionStatus {
     }
 }
 
+/// Broad cause of a failed action
+///
+/// Stored in `action_results.error_category`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    /// The target did not answer in time
+    Timeout,
+    /// The target could not be reached
+    Connection,
+    /// The target rejected the request (HTTP 4xx)
+    ClientError,
+    /// The target failed to handle the request (HTTP 5xx)
+    ServerError,
+    /// A rate limit stopped the action
+    RateLimited,
+    /// The target or its API reported another error
+    Upstream,
+    /// The action's template could not be rendered
+    Template,
+    /// The action's configuration is invalid
+    Config,
+    /// The organization could not pay for the action
+    Billing,
+    /// The worker itself failed
+    Internal,
+}
+
+impl FailureCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureCategory::Timeout => "timeout",
+            FailureCategory::Connection => "connection",
+            FailureCategory::ClientError => "client_error",
+            FailureCategory::ServerError => "server_error",
+            FailureCategory::RateLimited => "rate_limited",
+            FailureCategory::Upstream => "upstream",
+            FailureCategory::Template => "template",
+            FailureCategory::Config => "config",
+            FailureCategory::Billing => "billing",
+            FailureCategory::Internal => "internal",
+        }
+    }
+}
+
+impl std::fmt::Display for FailureCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why an action failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureReason {
+    pub category: FailureCategory,
+    /// Machine-readable code within the category (e.g. `http_503`)
+    pub code: String,
+}
+
 /// Action execution result for logging
 #[derive(Debug, Clone)]
 pub struct ActionResult {
@@ -35,6 +121,8 @@ pub struct ActionResult {
     pub job_id: String,
     /// Trigger that created this job
     pub trigger_id: String,
+    /// Trigger action the job executed (absent on older jobs)
+    pub action_id: Option<i32>,
     /// Event that triggered the action
     pub event_id: String,
     /// Type of action (telegram, rest, mcp)
@@ -45,6 +133,8 @@ pub struct ActionResult {
     pub duration_ms: i64,
     /// Error message (if failed)
     pub error_message: Option<String>,
+    /// Classified cause (if failed)
+    pub failure: Option<FailureReason>,
     /// Number of retry attempts made
     pub retry_count: i32,
 }
@@ -61,36 +151,46 @@ impl ActionResult {
         Self {
             job_id,
             trigger_id,
+            action_id: None,
             event_id,
             action_type,
             status: ActionStatus::Success,
             duration_ms,
             error_message: None,
+            failure: None,
             retry_count: 0,
         }
     }
 
-    /// Create a failure result
+    /// Create a failure result, classifying the error that ended the job
     pub fn failure(
         job_id: String,
         trigger_id: String,
         event_id: String,
         action_type: String,
         duration_ms: i64,
-        error: String,
+        error: &WorkerError,
         retry_count: i32,
     ) -> Self {
         Self {
             job_id,
             trigger_id,
+            action_id: None,
             event_id,
             action_type,
             status: ActionStatus::Failed,
             duration_ms,
-            error_message: Some(error),
+            error_message: Some(error.to_string()),
+            failure: Some(error.failure_reason()),
             retry_count,
         }
     }
+
+    /// Set the trigger action the job executed
+    pub fn with_action_id(mut self, action_id: Option<i32>) -> Self {
+        self.action_id = action_id;
+        self
+    }
 }
 
 /// Result logger trait for testability
@@ -104,6 +204,13 @@ pub trait ResultLogger: Send + Sync {
     async fn get_recent(&self, trigger_id: &str, limit: i64) -> WorkerResult<Vec<LoggedResult>>;
 }
 
+/// Result logger that can also write many results at once
+#[async_trait]
+pub trait BatchResultWriter: ResultLogger + 'static {
+    /// Write a batch of results in a single operation
+    async fn log_batch(&self, results: &[ActionResult]) -> WorkerResult<()>;
+}
+
 /// Logged result from database
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -138,17 +245,21 @@ impl ResultLogger for PostgresResultLogger {
         sqlx::query(
             r#"
             INSERT INTO action_results
-            (job_id, trigger_id, event_id, action_type, status, duration_ms, error_message, retry_count)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            (job_id, trigger_id, action_id, event_id, action_type, status, duration_ms,
+             error_message, error_category, error_code, retry_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(&result.job_id)
         .bind(&result.trigger_id)
+        .bind(result.action_id)
         .bind(&result.event_id)
         .bind(&result.action_type)
         .bind(result.status.to_string())
         .bind(result.duration_ms)
         .bind(&result.error_message)
+        .bind(result.failure.as_ref().map(|f| f.category.as_str()))
+        .bind(result.failure.as_ref().map(|f| f.code.as_str()))
         .bind(result.retry_count)
         .execute(&self.pool)
         .await
@@ -213,6 +324,242 @@ impl ResultLogger for PostgresResultLogger {
     }
 }
 
+#[async_trait]
+impl BatchResultWriter for PostgresResultLogger {
+    async fn log_batch(&self, results: &[ActionResult]) -> WorkerResult<()> {
+        if results.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "INSERT INTO action_results \
+             (job_id, trigger_id, action_id, event_id, action_type, status, duration_ms, \
+             error_message, error_category, error_code, retry_count) ",
+        );
+        builder.push_values(results, |mut row, result| {
+            row.push_bind(&result.job_id)
+                .push_bind(&result.trigger_id)
+                .push_bind(result.action_id)
+                .push_bind(&result.event_id)
+                .push_bind(&result.action_type)
+                .push_bind(result.status.to_string())
+                .push_bind(result.duration_ms)
+                .push_bind(&result.error_message)
+                .push_bind(result.failure.as_ref().map(|f| f.category.as_str()))
+                .push_bind(result.failure.as_ref().map(|f| f.code.as_str()))
+                .push_bind(result.retry_count);
+        });
+
+        builder
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(WorkerError::Database)?;
+
+        tracing::debug!(count = results.len(), "Logged action result batch");
+
+        Ok(())
+    }
+}
+
+/// Configuration for [`BufferedResultLogger`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    /// Flush as soon as this many results are buffered
+    pub batch_size: usize,
+    /// Flush buffered results at least this often
+    pub flush_interval: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: Duration::from_millis(DEFAULT_FLUSH_INTERVAL_MS),
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Load configuration from environment variables
+    ///
+    /// - `RESULT_LOG_BATCH_SIZE` (default 100, max 1000; 1 writes every result immediately)
+    /// - `RESULT_LOG_FLUSH_INTERVAL_MS` (default 1000)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let batch_size = std::env::var("RESULT_LOG_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(defaults.batch_size);
+
+        let flush_interval = std::env::var("RESULT_LOG_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.flush_interval);
+
+        Self {
+            batch_size,
+            flush_interval,
+        }
+        .normalized()
+    }
+
+    /// Clamp values into the supported range
+    fn normalized(self) -> Self {
+        Self {
+            batch_size: self.batch_size.clamp(1, MAX_BATCH_SIZE),
+            flush_interval: self.flush_interval,
+        }
+    }
+}
+
+/// Result logger that buffers results and writes them in batches
+///
+/// `log` only enqueues the result; a background task performs the writes.
+/// Write errors are therefore reported in the worker logs rather than to the
+/// caller. If a batch insert fails, the rows are retried one at a time so a
+/// single bad row doesn't take the whole batch down with it.
+pub struct BufferedResultLogger<W: BatchResultWriter> {
+    writer: Arc<W>,
+    sender: mpsc::Sender<ActionResult>,
+    shutdown_token: CancellationToken,
+    flush_task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<W: BatchResultWriter> BufferedResultLogger<W> {
+    /// Create a buffered logger and start its background flush task
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn new(writer: Arc<W>, config: BatchConfig) -> Self {
+        let config = config.normalized();
+        // Bounded so a stalled database applies backpressure instead of growing memory
+        let (sender, receiver) = mpsc::channel(config.batch_size * 4);
+        let shutdown_token = CancellationToken::new();
+
+        let flush_task = tokio::spawn(run_flush_loop(
+            writer.clone(),
+            receiver,
+            config,
+            shutdown_token.clone(),
+        ));
+
+        Self {
+            writer,
+            sender,
+            shutdown_token,
+            flush_task: Mutex::new(Some(flush_task)),
+        }
+    }
+
+    /// Flush all buffered results and stop the background task
+    ///
+    /// Results logged after shutdown are written directly, without buffering.
+    pub async fn shutdown(&self) {
+        self.shutdown_token.cancel();
+
+        if let Some(handle) = self.flush_task.lock().await.take() {
+            if let Err(e) = handle.await {
+                tracing::error!(error = %e, "Result logger flush task panicked");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<W: BatchResultWriter> ResultLogger for BufferedResultLogger<W> {
+    async fn log(&self, result: ActionResult) -> WorkerResult<()> {
+        if self.shutdown_token.is_cancelled() {
+            return self.writer.log(result).await;
+        }
+
+        match self.sender.send(result).await {
+            Ok(()) => Ok(()),
+            // Flush task already gone: write through so the result isn't lost
+            Err(mpsc::error::SendError(result)) => self.writer.log(result).await,
+        }
+    }
+
+    async fn get_recent(&self, trigger_id: &str, limit: i64) -> WorkerResult<Vec<LoggedResult>> {
+        self.writer.get_recent(trigger_id, limit).await
+    }
+}
+
+/// Background loop collecting buffered results and flushing them
+async fn run_flush_loop<W: BatchResultWriter>(
+    writer: Arc<W>,
+    mut receiver: mpsc::Receiver<ActionResult>,
+    config: BatchConfig,
+    shutdown_token: CancellationToken,
+) {
+    let mut buffer: Vec<ActionResult> = Vec::with_capacity(config.batch_size);
+    let mut interval = tokio::time::interval(config.flush_interval);
+    // The first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            maybe_result = receiver.recv() => {
+                match maybe_result {
+                    Some(result) => {
+                        buffer.push(result);
+                        if buffer.len() >= config.batch_size {
+                            flush_buffer(writer.as_ref(), &mut buffer).await;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            _ = interval.tick() => {
+                flush_buffer(writer.as_ref(), &mut buffer).await;
+            }
+            _ = shutdown_token.cancelled() => break,
+        }
+    }
+
+    // Drain anything still queued in the channel before the final flush
+    receiver.close();
+    while let Some(result) = receiver.recv().await {
+        buffer.push(result);
+        if buffer.len() >= config.batch_size {
+            flush_buffer(writer.as_ref(), &mut buffer).await;
+        }
+    }
+    flush_buffer(writer.as_ref(), &mut buffer).await;
+
+    tracing::debug!("Result logger flush task stopped");
+}
+
+/// Write the buffered results, falling back to row-by-row inserts on failure
+async fn flush_buffer<W: BatchResultWriter + ?Sized>(writer: &W, buffer: &mut Vec<ActionResult>) {
+    if buffer.is_empty() {
+        return;
+    }
+
+    let batch = std::mem::take(buffer);
+
+    if let Err(e) = writer.log_batch(&batch).await {
+        tracing::warn!(
+            error = %e,
+            count = batch.len(),
+            "Batch insert of action results failed, retrying individually"
+        );
+
+        for result in batch {
+            let job_id = result.job_id.clone();
+            if let Err(e) = writer.log(result).await {
+                tracing::error!(
+                    job_id = %job_id,
+                    error = %e,
+                    "Failed to log action result"
+                );
+            }
+        }
+    }
+}
+
 /// In-memory result logger for testing
 #[derive(Default)]
 #[allow(dead_code)]
@@ -231,6 +578,16 @@ impl InMemoryResultLogger {
         self.results.lock().unwrap().clone()
     }
 
+    /// Get the failure reasons logged, in order
+    pub fn failure_reasons(&self) -> Vec<FailureReason> {
+        self.results
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|r| r.failure.clone())
+            .collect()
+    }
+
     /// Get count of results by status
     pub fn count_by_status(&self, status: ActionStatus) -> usize {
         self.results
@@ -272,10 +629,116 @@ impl ResultLogger for InMemoryResultLogger {
     }
 }
 
+#[async_trait]
+impl BatchResultWriter for InMemoryResultLogger {
+    async fn log_batch(&self, results: &[ActionResult]) -> WorkerResult<()> {
+        self.results.lock().unwrap().extend_from_slice(results);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn sample_result(i: usize) -> ActionResult {
+        ActionResult::success(
+            format!("job-{}", i),
+            "trigger-1".to_string(),
+            format!("event-{}", i),
+            "rest".to_string(),
+            10,
+        )
+    }
+
+    /// Give the flush task a chance to run
+    async fn settle() {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    #[tokio::test]
+    async fn test_buffered_logger_flushes_on_batch_size() {
+        let inner = Arc::new(InMemoryResultLogger::new());
+        let logger = BufferedResultLogger::new(
+            inner.clone(),
+            BatchConfig {
+                batch_size: 3,
+                flush_interval: Duration::from_secs(3600),
+            },
+        );
+
+        logger.log(sample_result(0)).await.unwrap();
+        logger.log(sample_result(1)).await.unwrap();
+        settle().await;
+        assert!(
+            inner.results().is_empty(),
+            "below batch size, nothing written"
+        );
+
+        logger.log(sample_result(2)).await.unwrap();
+        settle().await;
+        assert_eq!(inner.results().len(), 3);
+
+        logger.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_buffered_logger_flushes_on_interval() {
+        let inner = Arc::new(InMemoryResultLogger::new());
+        let logger = BufferedResultLogger::new(
+            inner.clone(),
+            BatchConfig {
+                batch_size: 100,
+                flush_interval: Duration::from_millis(30),
+            },
+        );
+
+        logger.log(sample_result(0)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(inner.results().len(), 1);
+
+        logger.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_buffered_logger_flushes_on_shutdown() {
+        let inner = Arc::new(InMemoryResultLogger::new());
+        let logger = BufferedResultLogger::new(
+            inner.clone(),
+            BatchConfig {
+                batch_size: 100,
+                flush_interval: Duration::from_secs(3600),
+            },
+        );
+
+        for i in 0..5 {
+            logger.log(sample_result(i)).await.unwrap();
+        }
+        logger.shutdown().await;
+        assert_eq!(inner.results().len(), 5);
+
+        // Results logged after shutdown are written through
+        logger.log(sample_result(5)).await.unwrap();
+        assert_eq!(inner.results().len(), 6);
+    }
+
+    #[test]
+    fn test_batch_config_normalized() {
+        let config = BatchConfig {
+            batch_size: 0,
+            flush_interval: Duration::from_millis(10),
+        }
+        .normalized();
+        assert_eq!(config.batch_size, 1);
+
+        let config = BatchConfig {
+            batch_size: 50_000,
+            flush_interval: Duration::from_millis(10),
+        }
+        .normalized();
+        assert_eq!(config.batch_size, MAX_BATCH_SIZE);
+    }
+
     #[tokio::test]
     async fn test_in_memory_logger() {
         let logger = InMemoryResultLogger::new();
@@ -305,9 +768,10 @@ mod tests {
             "event-2".to_string(),
             "telegram".to_string(),
             500,
-            "Connection timeout".to_string(),
+            &WorkerError::timeout("no response after 30s"),
             3,
-        );
+        )
+        .with_action_id(Some(7));
 
         logger.log(result).await.unwrap();
 
@@ -315,8 +779,16 @@ mod tests {
         assert_eq!(results[0].status, ActionStatus::Failed);
         assert_eq!(
             results[0].error_message,
-            Some("Connection timeout".to_string())
+            Some("Request timeout: no response after 30s".to_string())
         );
+        assert_eq!(
+            results[0].failure,
+            Some(FailureReason {
+                category: FailureCategory::Timeout,
+                code: "request_timeout".to_string(),
+            })
+        );
+        assert_eq!(results[0].action_id, Some(7));
         assert_eq!(results[0].retry_count, 3);
     }
 
@@ -346,4 +818,20 @@ mod tests {
         assert_eq!(ActionStatus::Failed.to_string(), "failed");
         assert_eq!(ActionStatus::Retrying.to_string(), "retrying");
     }
+
+    #[test]
+    fn test_failure_category_matches_serde_name() {
+        for category in [
+            FailureCategory::Timeout,
+            FailureCategory::ClientError,
+            FailureCategory::RateLimited,
+            FailureCategory::Billing,
+            FailureCategory::Internal,
+        ] {
+            assert_eq!(
+                serde_json::to_value(category).unwrap(),
+                serde_json::json!(category.as_str())
+            );
+        }
+    }
 }
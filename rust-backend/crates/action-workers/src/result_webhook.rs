@@ -0,0 +1,550 @@
+//! Action result webhooks
+//!
+//! Organizations can register an endpoint (`organization_action_webhooks`)
+//! that receives a signed summary after each of their actions executes, so
+//! they can observe successes and failures without polling.
+//!
+//! # Payload
+//!
+//! ```json
+//! {
+//!   "type": "action_result",
+//!   "trigger_id": "...", "action_id": 42, "job_id": "...", "event_id": "...",
+//!   "action_type": "rest", "status": "failed",
+//!   "timestamp": "2026-01-12T10:00:00.000000Z", "error": "Unexpected status code 500"
+//! }
+//! ```
+//!
+//! Requests are signed like other customer deliveries: `X-AgentAuri-Timestamp`
+//! holds the Unix time and `X-AgentAuri-Signature` is
+//! `sha256=HMAC(secret, "{timestamp}.{body}")` (see [`shared::signing`]).
+//! Organizations with a signing key ([`crate::signing_keys`]) are signed with
+//! it, including the previous key during a rotation grace window; others with
+//! the webhook's own secret.
+//!
+//! # Isolation
+//!
+//! Deliveries run in a background task after the action has finished, are
+//! attempted once with a short timeout, and never change the action's
+//! outcome: a slow or failing observability endpoint only shows up in the
+//! logs and in `action_worker_result_webhooks_total{outcome="failed"}`.
+//!
+//! # Configuration
+//!
+//! - `ACTION_RESULT_WEBHOOK_TIMEOUT_MS`: delivery timeout (default: 5000)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+use shared::signing::SigningKeys;
+use shared::ActionJob;
+use sqlx::PgPool;
+
+use crate::error::{WorkerError, WorkerResult};
+use crate::metrics;
+use crate::rest::{validate_target_url, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::result_logger::ActionStatus;
+use crate::signing_keys::SigningKeyStore;
+
+/// Default delivery timeout in milliseconds
+pub const DEFAULT_RESULT_WEBHOOK_TIMEOUT_MS: u64 = 5000;
+
+/// Maximum length of the error message included in a payload
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// Where and how to deliver an organization's action results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultWebhookTarget {
+    pub url: String,
+    pub secret: String,
+}
+
+/// Looks up the result webhook configured for a job's organization
+#[async_trait]
+pub trait ResultWebhookStore: Send + Sync {
+    /// Get the enabled webhook for the organization owning the trigger, if any
+    ///
+    /// `organization_id` is absent on jobs from older producers, in which case
+    /// the organization is resolved from the trigger.
+    async fn find_target(
+        &self,
+        organization_id: Option<&str>,
+        trigger_id: &str,
+    ) -> WorkerResult<Option<ResultWebhookTarget>>;
+}
+
+/// PostgreSQL-backed webhook lookup
+pub struct PostgresResultWebhookStore {
+    pool: PgPool,
+}
+
+impl PostgresResultWebhookStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ResultWebhookStore for PostgresResultWebhookStore {
+    async fn find_target(
+        &self,
+        organization_id: Option<&str>,
+        trigger_id: &str,
+    ) -> WorkerResult<Option<ResultWebhookTarget>> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT url, secret FROM organization_action_webhooks
+            WHERE enabled
+              AND organization_id = COALESCE(
+                  $1, (SELECT organization_id FROM triggers WHERE id = $2)
+              )
+            "#,
+        )
+        .bind(organization_id)
+        .bind(trigger_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|(url, secret)| ResultWebhookTarget { url, secret }))
+    }
+}
+
+/// Summary of one action execution, as delivered to the customer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActionResultPayload {
+    /// Always `action_result`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub trigger_id: String,
+    pub action_id: Option<i32>,
+    pub job_id: String,
+    pub event_id: String,
+    pub action_type: String,
+    pub status: ActionStatus,
+    #[serde(with = "shared::timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+impl ActionResultPayload {
+    /// Build the payload for a finished job (`error` is `None` on success)
+    pub fn new(job: &ActionJob, error: Option<&str>, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            kind: "action_result".to_string(),
+            trigger_id: job.trigger_id.clone(),
+            action_id: job.action_id,
+            job_id: job.id.clone(),
+            event_id: job.event_id.clone(),
+            action_type: job.action_type.to_string(),
+            status: if error.is_some() {
+                ActionStatus::Failed
+            } else {
+                ActionStatus::Success
+            },
+            timestamp,
+            error: error.map(|e| e.chars().take(MAX_ERROR_LENGTH).collect()),
+        }
+    }
+}
+
+/// Configuration for [`ResultWebhookNotifier`]
+#[derive(Debug, Clone)]
+pub struct ResultWebhookConfig {
+    /// Timeout for a single delivery
+    pub timeout: Duration,
+    /// Skip the SSRF check (tests against local servers only)
+    pub allow_private_hosts: bool,
+}
+
+impl Default for ResultWebhookConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(DEFAULT_RESULT_WEBHOOK_TIMEOUT_MS),
+            allow_private_hosts: false,
+        }
+    }
+}
+
+impl ResultWebhookConfig {
+    /// Load from `ACTION_RESULT_WEBHOOK_TIMEOUT_MS`, falling back to defaults
+    pub fn from_env() -> Self {
+        let timeout_ms = std::env::var("ACTION_RESULT_WEBHOOK_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|ms| *ms > 0)
+            .unwrap_or(DEFAULT_RESULT_WEBHOOK_TIMEOUT_MS);
+
+        Self {
+            timeout: Duration::from_millis(timeout_ms),
+            ..Self::default()
+        }
+    }
+}
+
+/// Sends action results to organizations' observability endpoints
+pub struct ResultWebhookNotifier {
+    store: Arc<dyn ResultWebhookStore>,
+    signing_keys: Option<Arc<dyn SigningKeyStore>>,
+    client: Client,
+    config: ResultWebhookConfig,
+}
+
+impl ResultWebhookNotifier {
+    /// Create a notifier backed by `store`
+    pub fn new(
+        store: Arc<dyn ResultWebhookStore>,
+        config: ResultWebhookConfig,
+    ) -> WorkerResult<Self> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent("agentauri-action-worker/1.0")
+            .build()
+            .map_err(|e| {
+                WorkerError::invalid_config(format!("Failed to create HTTP client: {}", e))
+            })?;
+
+        Ok(Self {
+            store,
+            signing_keys: None,
+            client,
+            config,
+        })
+    }
+
+    /// Sign with the organization's signing keys when it has any
+    pub fn with_signing_keys(mut self, signing_keys: Arc<dyn SigningKeyStore>) -> Self {
+        self.signing_keys = Some(signing_keys);
+        self
+    }
+
+    /// Report a finished job in the background
+    ///
+    /// Returns immediately; delivery errors are logged and counted but never
+    /// reach the caller.
+    pub fn notify(self: &Arc<Self>, job: &ActionJob, error: Option<&str>) {
+        let payload = ActionResultPayload::new(job, error, Utc::now());
+        let organization_id = job.organization_id.clone();
+        let notifier = Arc::clone(self);
+
+        tokio::spawn(async move {
+            match notifier.deliver(organization_id.as_deref(), &payload).await {
+                Ok(true) => metrics::record_result_webhook("delivered"),
+                Ok(false) => {} // No webhook configured
+                Err(e) => {
+                    metrics::record_result_webhook("failed");
+                    tracing::warn!(
+                        job_id = %payload.job_id,
+                        trigger_id = %payload.trigger_id,
+                        error = %e,
+                        "Failed to deliver action result webhook"
+                    );
+                }
+            }
+        });
+    }
+
+    /// Deliver a payload to the organization's webhook
+    ///
+    /// Returns `Ok(false)` when the organization has no enabled webhook.
+    pub async fn deliver(
+        &self,
+        organization_id: Option<&str>,
+        payload: &ActionResultPayload,
+    ) -> WorkerResult<bool> {
+        let Some(target) = self
+            .store
+            .find_target(organization_id, &payload.trigger_id)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        validate_target_url(&target.url, self.config.allow_private_hosts)?;
+
+        let org_keys = match &self.signing_keys {
+            Some(signing_keys) => {
+                signing_keys
+                    .find_keys(organization_id, &payload.trigger_id)
+                    .await?
+            }
+            None => None,
+        };
+        let keys = org_keys.unwrap_or_else(|| SigningKeys::new(target.secret));
+
+        let body = serde_json::to_vec(payload)?;
+        let timestamp = Utc::now().timestamp();
+        let signature = keys.sign(timestamp, &body);
+
+        let response = self
+            .client
+            .post(&target.url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| WorkerError::Internal(format!("Result webhook request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(WorkerError::Internal(format!(
+                "Result webhook returned status {}",
+                status.as_u16()
+            )));
+        }
+
+        tracing::debug!(
+            job_id = %payload.job_id,
+            status = status.as_u16(),
+            "Delivered action result webhook"
+        );
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing_keys::StaticSigningKeyStore;
+    use serde_json::json;
+    use shared::signing::{sign_payload, verify_signature};
+    use shared::ActionType;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    /// Store that always returns the same target
+    struct StaticStore(Option<ResultWebhookTarget>);
+
+    #[async_trait]
+    impl ResultWebhookStore for StaticStore {
+        async fn find_target(
+            &self,
+            _organization_id: Option<&str>,
+            _trigger_id: &str,
+        ) -> WorkerResult<Option<ResultWebhookTarget>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn notifier(target: Option<ResultWebhookTarget>) -> Arc<ResultWebhookNotifier> {
+        let config = ResultWebhookConfig {
+            timeout: Duration::from_millis(500),
+            allow_private_hosts: true,
+        };
+        Arc::new(ResultWebhookNotifier::new(Arc::new(StaticStore(target)), config).unwrap())
+    }
+
+    fn target(url: String) -> ResultWebhookTarget {
+        ResultWebhookTarget {
+            url,
+            secret: "whsec_test".to_string(),
+        }
+    }
+
+    fn create_test_job() -> ActionJob {
+        ActionJob::new(
+            "trigger-1",
+            "event-1",
+            ActionType::Rest,
+            1,
+            json!({"url": "https://api.example.com"}),
+            json!({}),
+        )
+        .with_organization_id("org-1")
+        .with_action_id(7)
+    }
+
+    /// Accept one request, answer 200 and hand back its raw text
+    async fn spawn_receiver() -> (String, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the headers and the declared body have arrived
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let _ = tx.send(String::from_utf8_lossy(&request).to_string());
+        });
+
+        (format!("http://{}/hooks/results", addr), rx)
+    }
+
+    fn header_value<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+        request.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    #[test]
+    fn test_payload_from_job() {
+        let job = create_test_job();
+        let now = Utc::now();
+
+        let success = ActionResultPayload::new(&job, None, now);
+        assert_eq!(success.kind, "action_result");
+        assert_eq!(success.trigger_id, "trigger-1");
+        assert_eq!(success.action_id, Some(7));
+        assert_eq!(success.action_type, "rest");
+        assert_eq!(success.status, ActionStatus::Success);
+        assert!(success.error.is_none());
+
+        let long_error = "x".repeat(2 * MAX_ERROR_LENGTH);
+        let failure = ActionResultPayload::new(&job, Some(&long_error), now);
+        assert_eq!(failure.status, ActionStatus::Failed);
+        assert_eq!(failure.error.unwrap().len(), MAX_ERROR_LENGTH);
+    }
+
+    #[tokio::test]
+    async fn test_result_webhook_delivered_and_signed() {
+        let (url, received) = spawn_receiver().await;
+        let notifier = notifier(Some(target(url)));
+        let job = create_test_job();
+
+        let payload =
+            ActionResultPayload::new(&job, Some("Unexpected status code 500"), Utc::now());
+        let delivered = notifier.deliver(Some("org-1"), &payload).await.unwrap();
+        assert!(delivered);
+
+        let request = received.await.unwrap();
+        assert!(request.starts_with("POST /hooks/results HTTP/1.1"));
+
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let sent: ActionResultPayload = serde_json::from_str(body).unwrap();
+        assert_eq!(sent, payload);
+        assert_eq!(sent.status, ActionStatus::Failed);
+        assert_eq!(sent.error.as_deref(), Some("Unexpected status code 500"));
+
+        let timestamp: i64 = header_value(&request, TIMESTAMP_HEADER)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            header_value(&request, SIGNATURE_HEADER).unwrap(),
+            sign_payload("whsec_test", timestamp, body.as_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_org_signing_keys_used_during_rotation_grace_window() {
+        let (url, received) = spawn_receiver().await;
+        let config = ResultWebhookConfig {
+            timeout: Duration::from_millis(500),
+            allow_private_hosts: true,
+        };
+        let keys = SigningKeys::new("whsec_new").with_previous("whsec_old");
+        let notifier = ResultWebhookNotifier::new(Arc::new(StaticStore(Some(target(url)))), config)
+            .unwrap()
+            .with_signing_keys(Arc::new(StaticSigningKeyStore(Some(keys))));
+
+        let payload = ActionResultPayload::new(&create_test_job(), None, Utc::now());
+        assert!(notifier.deliver(Some("org-1"), &payload).await.unwrap());
+
+        let request = received.await.unwrap();
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let timestamp: i64 = header_value(&request, TIMESTAMP_HEADER)
+            .unwrap()
+            .parse()
+            .unwrap();
+        let signature = header_value(&request, SIGNATURE_HEADER).unwrap();
+
+        // Receivers holding either key accept the delivery; the webhook's own
+        // secret is no longer used once the organization has a signing key
+        assert!(verify_signature(
+            "whsec_new",
+            timestamp,
+            body.as_bytes(),
+            signature
+        ));
+        assert!(verify_signature(
+            "whsec_old",
+            timestamp,
+            body.as_bytes(),
+            signature
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            timestamp,
+            body.as_bytes(),
+            signature
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_webhook_configured_is_skipped() {
+        let notifier = notifier(None);
+        let payload = ActionResultPayload::new(&create_test_job(), None, Utc::now());
+
+        assert!(!notifier.deliver(Some("org-1"), &payload).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_private_hosts_blocked_by_default() {
+        let store = Arc::new(StaticStore(Some(target(
+            "http://169.254.169.254/latest".to_string(),
+        ))));
+        let notifier = ResultWebhookNotifier::new(store, ResultWebhookConfig::default()).unwrap();
+        let payload = ActionResultPayload::new(&create_test_job(), None, Utc::now());
+
+        let err = notifier.deliver(None, &payload).await.unwrap_err();
+        assert!(matches!(err, WorkerError::InvalidConfig(_)));
+    }
+
+    #[tokio::test]
+    async fn test_observability_endpoint_down_is_isolated() {
+        // Reserve a port and close it so connections are refused
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/down", listener.local_addr().unwrap());
+        drop(listener);
+
+        let notifier = notifier(Some(target(url)));
+        let job = create_test_job();
+
+        // The delivery itself fails...
+        let payload = ActionResultPayload::new(&job, None, Utc::now());
+        assert!(notifier.deliver(Some("org-1"), &payload).await.is_err());
+
+        // ...but notify() returns straight away and the failure stays in the
+        // background task instead of surfacing to the worker
+        let started = std::time::Instant::now();
+        notifier.notify(&job, None);
+        assert!(started.elapsed() < Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -1,57 +1,217 @@
 //! Retry logic for action workers
 //!
 //! Provides exponential backoff retry policy with configurable parameters.
+//!
+//! # Configuration
+//!
+//! Each worker builds its policy with [`RetryPolicy::from_env`]. A variable
+//! prefixed with the action type (e.g. `REST_RETRY_MAX_ATTEMPTS`) overrides
+//! the shared one (`RETRY_MAX_ATTEMPTS`); unset or invalid values keep the
+//! default.
+//!
+//! - `RETRY_MAX_ATTEMPTS`: attempts including the first (default: 3)
+//! - `RETRY_BASE_DELAY_MS`: delay before the first retry (default: 1000)
+//! - `RETRY_MULTIPLIER`: growth factor per attempt, at least 1.0 (default: 2.0)
+//! - `RETRY_MAX_DELAY_MS`: delay cap (default: 4000)
+//! - `RETRY_JITTER`: `full`, `equal`, `none`, or the fraction (0.0-1.0) of each
+//!   delay that is randomized (default: `full`)
+//!
+//! # Jitter
+//!
+//! Jobs that fail together (e.g. when a downstream goes away) would retry in
+//! lockstep with pure exponential backoff and hit the recovering service all
+//! at once. Delays are therefore randomized as described in AWS's "Exponential
+//! Backoff And Jitter": full jitter sleeps anywhere in `[0, backoff]`, equal
+//! jitter in `[backoff / 2, backoff]`.
 
 use std::time::Duration;
 
+use rand::Rng;
+use shared::ActionType;
+use std::str::FromStr;
+
 use crate::error::WorkerError;
 use crate::metrics;
 
+/// Default number of attempts
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry in milliseconds
+pub const DEFAULT_BASE_DELAY_MS: u64 = 1000;
+
+/// Default backoff multiplier
+pub const DEFAULT_MULTIPLIER: f64 = 2.0;
+
+/// Default delay cap in milliseconds
+pub const DEFAULT_MAX_DELAY_MS: u64 = 4000;
+
+/// How much of each backoff is randomized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jitter {
+    /// Sleep exactly the backoff
+    None,
+    /// Sleep a random duration in `[0, backoff]`
+    Full,
+    /// Sleep half the backoff plus a random duration in `[0, backoff / 2]`
+    Equal,
+    /// Remove up to this fraction (0.0-1.0) of the backoff at random
+    Fraction(f64),
+}
+
+impl Jitter {
+    /// Fraction of the backoff that may be removed
+    fn fraction(self) -> f64 {
+        match self {
+            Jitter::None => 0.0,
+            Jitter::Full => 1.0,
+            Jitter::Equal => 0.5,
+            Jitter::Fraction(f) if f.is_finite() => f.clamp(0.0, 1.0),
+            Jitter::Fraction(_) => 0.0,
+        }
+    }
+}
+
+impl FromStr for Jitter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Jitter::None),
+            "full" => Ok(Jitter::Full),
+            "equal" => Ok(Jitter::Equal),
+            other => other
+                .parse::<f64>()
+                .ok()
+                .filter(|f| (0.0..=1.0).contains(f))
+                .map(Jitter::Fraction)
+                .ok_or_else(|| format!("Invalid jitter: {}", s)),
+        }
+    }
+}
+
 /// Retry policy configuration
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
     /// Maximum number of retry attempts
     pub max_attempts: u32,
-    /// Base delay between retries (doubles each attempt)
+    /// Base delay between retries (grows by `multiplier` each attempt)
     pub base_delay: Duration,
+    /// Factor the delay grows by after each attempt
+    pub multiplier: f64,
     /// Maximum delay cap
     pub max_delay: Duration,
+    /// Randomization applied to each delay, so jobs that failed together
+    /// don't retry together
+    pub jitter: Jitter,
 }
 
 impl Default for RetryPolicy {
-    /// Default policy: 3 attempts with delays of 1s, 2s, 4s
+    /// Default policy: 3 attempts with backoffs of 1s, 2s, 4s, fully jittered
     fn default() -> Self {
         Self {
-            max_attempts: 3,
-            base_delay: Duration::from_secs(1),
-            max_delay: Duration::from_secs(4),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: Duration::from_millis(DEFAULT_BASE_DELAY_MS),
+            multiplier: DEFAULT_MULTIPLIER,
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
+            jitter: Jitter::Full,
         }
     }
 }
 
 impl RetryPolicy {
-    /// Create a new retry policy
-    #[allow(dead_code)]
+    /// Create a new retry policy that doubles the delay, with full jitter
     pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
         Self {
             max_attempts,
             base_delay,
+            multiplier: DEFAULT_MULTIPLIER,
             max_delay,
+            jitter: Jitter::Full,
         }
     }
 
-    /// Calculate delay for given attempt (1-indexed)
+    /// Set the backoff multiplier (values below 1.0 are raised to 1.0)
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = if multiplier.is_finite() {
+            multiplier.max(1.0)
+        } else {
+            DEFAULT_MULTIPLIER
+        };
+        self
+    }
+
+    /// Set the jitter applied to each delay
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Build the policy for an action type from environment variables
     ///
-    /// Uses exponential backoff: base_delay * 2^(attempt-1)
+    /// See the module docs for the variables and their defaults.
+    pub fn from_env(action_type: ActionType) -> Self {
+        Self::from_lookup(&action_type.to_string(), |name| std::env::var(name).ok())
+    }
+
+    /// Build a policy from `lookup`, preferring `<ACTION>_RETRY_*` over `RETRY_*`
+    fn from_lookup(action_type: &str, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let prefix = action_type.to_uppercase();
+        let read = |name: &str| lookup(&format!("{}_{}", prefix, name)).or_else(|| lookup(name));
+        let read_u64 = |name: &str| read(name).and_then(|v| v.parse::<u64>().ok());
+        let read_f64 = |name: &str| read(name).and_then(|v| v.parse::<f64>().ok());
+        let read_jitter = |name: &str| read(name).and_then(|v| v.parse::<Jitter>().ok());
+
+        let defaults = Self::default();
+        let max_attempts = read_u64("RETRY_MAX_ATTEMPTS")
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.max_attempts);
+        let base_delay = read_u64("RETRY_BASE_DELAY_MS")
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.base_delay);
+        // Never cap below the first delay
+        let max_delay = read_u64("RETRY_MAX_DELAY_MS")
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.max_delay)
+            .max(base_delay);
+
+        Self::new(max_attempts, base_delay, max_delay)
+            .with_multiplier(read_f64("RETRY_MULTIPLIER").unwrap_or(defaults.multiplier))
+            .with_jitter(read_jitter("RETRY_JITTER").unwrap_or(defaults.jitter))
+    }
+
+    /// Calculate the backoff for given attempt (1-indexed), before jitter
+    ///
+    /// Uses exponential backoff: base_delay * multiplier^(attempt-1)
     /// Capped at max_delay
     ///
     /// # Arguments
     ///
     /// * `attempt` - Current attempt number (1-indexed)
     pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
-        let multiplier = 2u32.saturating_pow(attempt.saturating_sub(1));
-        let delay = self.base_delay.saturating_mul(multiplier);
-        std::cmp::min(delay, self.max_delay)
+        let exponent = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let secs = self.base_delay.as_secs_f64() * self.multiplier.powi(exponent);
+
+        if !secs.is_finite() || secs >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(secs)
+        }
+    }
+
+    /// Calculate the delay to sleep after given attempt, with jitter applied
+    ///
+    /// A random part of the backoff is removed according to `jitter`, so the
+    /// result lies in `[backoff * (1 - fraction), backoff]`. `rng` is the
+    /// randomness source; pass a seeded one for deterministic results.
+    pub fn jittered_delay(&self, attempt: u32, rng: &mut impl Rng) -> Duration {
+        let delay = self.delay_for_attempt(attempt);
+        let fraction = self.jitter.fraction();
+        if fraction <= 0.0 {
+            return delay;
+        }
+        let reduction = fraction * rng.gen::<f64>();
+        delay.mul_f64(1.0 - reduction)
     }
 
     /// Check if another retry should be attempted
@@ -94,7 +254,7 @@ where
             Err(e) => {
                 // Check if error is retryable and we have attempts left
                 if e.is_retryable() && policy.should_retry(attempt) {
-                    let delay = policy.delay_for_attempt(attempt);
+                    let delay = policy.jittered_delay(attempt, &mut rand::thread_rng());
 
                     tracing::warn!(
                         attempt = attempt,
@@ -134,6 +294,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
     use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
 
@@ -172,6 +333,140 @@ mod tests {
         assert!(!policy.should_retry(4)); // Cannot retry after attempt 4
     }
 
+    #[test]
+    fn test_custom_policy_delays() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(1000))
+            .with_multiplier(3.0);
+
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(300));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(900));
+        // 2700ms capped at max_delay
+        assert_eq!(policy.delay_for_attempt(4), Duration::from_millis(1000));
+        assert_eq!(
+            policy.delay_for_attempt(u32::MAX),
+            Duration::from_millis(1000)
+        );
+        assert!(policy.should_retry(4));
+        assert!(!policy.should_retry(5));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(1), Duration::from_secs(8))
+            .with_jitter(Jitter::Equal);
+        let mut rng = rand::thread_rng();
+
+        for attempt in 1..=4 {
+            let backoff = policy.delay_for_attempt(attempt);
+            for _ in 0..100 {
+                let delay = policy.jittered_delay(attempt, &mut rng);
+                assert!(delay <= backoff);
+                assert!(delay >= backoff.mul_f64(0.5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_jitter_is_default_and_varies() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.jitter, Jitter::Full);
+
+        // Identical inputs give different delays within [0, backoff]
+        let mut rng = StdRng::seed_from_u64(7);
+        let delays: Vec<Duration> = (0..20)
+            .map(|_| policy.jittered_delay(2, &mut rng))
+            .collect();
+        assert!(delays.iter().all(|d| *d <= Duration::from_secs(2)));
+        assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn test_jitter_is_deterministic_for_a_seeded_rng() {
+        let policy = RetryPolicy::default();
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+
+        for attempt in 1..=3 {
+            assert_eq!(
+                policy.jittered_delay(attempt, &mut first),
+                policy.jittered_delay(attempt, &mut second)
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_jitter_returns_backoff() {
+        let policy = RetryPolicy::default().with_jitter(Jitter::None);
+        let mut rng = rand::thread_rng();
+        assert_eq!(policy.jittered_delay(2, &mut rng), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_builders_clamp_values() {
+        let policy = RetryPolicy::default().with_multiplier(0.5);
+        assert_eq!(policy.multiplier, 1.0);
+
+        let policy = RetryPolicy::default().with_multiplier(f64::NAN);
+        assert_eq!(policy.multiplier, DEFAULT_MULTIPLIER);
+
+        assert_eq!(Jitter::Fraction(1.5).fraction(), 1.0);
+        assert_eq!(Jitter::Fraction(-1.0).fraction(), 0.0);
+        assert_eq!(Jitter::Fraction(f64::NAN).fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_jitter() {
+        assert_eq!("full".parse::<Jitter>().unwrap(), Jitter::Full);
+        assert_eq!(" Equal ".parse::<Jitter>().unwrap(), Jitter::Equal);
+        assert_eq!("none".parse::<Jitter>().unwrap(), Jitter::None);
+        assert_eq!("0.25".parse::<Jitter>().unwrap(), Jitter::Fraction(0.25));
+        assert!("1.5".parse::<Jitter>().is_err());
+        assert!("random".parse::<Jitter>().is_err());
+    }
+
+    #[test]
+    fn test_from_lookup_prefers_action_specific_values() {
+        let env = std::collections::HashMap::from([
+            ("RETRY_MAX_ATTEMPTS", "5"),
+            ("RETRY_BASE_DELAY_MS", "200"),
+            ("REST_RETRY_BASE_DELAY_MS", "50"),
+            ("RETRY_MULTIPLIER", "1.5"),
+            ("RETRY_MAX_DELAY_MS", "10000"),
+            ("REST_RETRY_JITTER", "0.25"),
+        ]);
+        let lookup = |name: &str| env.get(name).map(|v| v.to_string());
+
+        let rest = RetryPolicy::from_lookup("rest", lookup);
+        assert_eq!(rest.max_attempts, 5);
+        assert_eq!(rest.base_delay, Duration::from_millis(50));
+        assert_eq!(rest.multiplier, 1.5);
+        assert_eq!(rest.max_delay, Duration::from_secs(10));
+        assert_eq!(rest.jitter, Jitter::Fraction(0.25));
+
+        let telegram = RetryPolicy::from_lookup("telegram", lookup);
+        assert_eq!(telegram.base_delay, Duration::from_millis(200));
+        assert_eq!(telegram.jitter, Jitter::Full);
+    }
+
+    #[test]
+    fn test_from_lookup_ignores_invalid_values() {
+        let env = std::collections::HashMap::from([
+            ("RETRY_MAX_ATTEMPTS", "0"),
+            ("RETRY_BASE_DELAY_MS", "soon"),
+            ("RETRY_MAX_DELAY_MS", "10"),
+        ]);
+        let policy = RetryPolicy::from_lookup("mcp", |name| env.get(name).map(|v| v.to_string()));
+
+        assert_eq!(policy.max_attempts, DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(
+            policy.base_delay,
+            Duration::from_millis(DEFAULT_BASE_DELAY_MS)
+        );
+        // The cap is raised to the base delay
+        assert_eq!(policy.max_delay, policy.base_delay);
+    }
+
     #[tokio::test]
     async fn test_execute_with_retry_success_first_try() {
         let policy = RetryPolicy::default();
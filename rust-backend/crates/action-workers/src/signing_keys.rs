@@ -0,0 +1,81 @@
+//! Organization signing keys
+//!
+//! Looks up the keys an organization's deliveries are signed with: its
+//! current key plus, for the grace window after a rotation, the previous one
+//! (see [`shared::signing`]). Managed through
+//! `/api/v1/organizations/{id}/signing-keys`.
+
+use async_trait::async_trait;
+use shared::signing::SigningKeys;
+use sqlx::PgPool;
+
+use crate::error::WorkerResult;
+
+/// Looks up the signing keys of a job's organization
+#[async_trait]
+pub trait SigningKeyStore: Send + Sync {
+    /// Get the organization's valid keys, current first (`None` if it has none)
+    ///
+    /// `organization_id` is absent on jobs from older producers, in which case
+    /// the organization is resolved from the trigger.
+    async fn find_keys(
+        &self,
+        organization_id: Option<&str>,
+        trigger_id: &str,
+    ) -> WorkerResult<Option<SigningKeys>>;
+}
+
+/// PostgreSQL-backed signing key lookup
+pub struct PostgresSigningKeyStore {
+    pool: PgPool,
+}
+
+impl PostgresSigningKeyStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SigningKeyStore for PostgresSigningKeyStore {
+    async fn find_keys(
+        &self,
+        organization_id: Option<&str>,
+        trigger_id: &str,
+    ) -> WorkerResult<Option<SigningKeys>> {
+        let secrets: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT secret FROM organization_signing_keys
+            WHERE organization_id = COALESCE(
+                  $1, (SELECT organization_id FROM triggers WHERE id = $2)
+              )
+              AND (retired_at IS NULL OR expires_at > NOW())
+            ORDER BY retired_at IS NOT NULL, created_at DESC
+            "#,
+        )
+        .bind(organization_id)
+        .bind(trigger_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(SigningKeys::from_keys(
+            secrets.into_iter().map(|(secret,)| secret).collect(),
+        ))
+    }
+}
+
+/// Store returning the same keys for every organization (tests)
+#[cfg(test)]
+pub struct StaticSigningKeyStore(pub Option<SigningKeys>);
+
+#[cfg(test)]
+#[async_trait]
+impl SigningKeyStore for StaticSigningKeyStore {
+    async fn find_keys(
+        &self,
+        _organization_id: Option<&str>,
+        _trigger_id: &str,
+    ) -> WorkerResult<Option<SigningKeys>> {
+        Ok(self.0.clone())
+    }
+}
@@ -6,9 +6,23 @@ use async_trait::async_trait;
 use secrecy::Secret;
 use serde::Deserialize;
 use teloxide::prelude::*;
-use teloxide::types::ParseMode;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
 
 use crate::error::WorkerError;
+use crate::telegram_format;
+use crate::template::{render_template, render_template_escaped};
+
+/// Telegram's limit on buttons per keyboard row
+const MAX_BUTTONS_PER_ROW: usize = 8;
+
+/// Telegram's limit on buttons per keyboard
+const MAX_BUTTONS: usize = 100;
+
+/// Telegram's limit on callback data length (bytes)
+const MAX_CALLBACK_DATA_LENGTH: usize = 64;
+
+/// URL schemes allowed for keyboard buttons
+const BUTTON_URL_SCHEMES: &[&str] = &["http", "https", "tg"];
 
 /// Telegram action configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -17,24 +31,116 @@ pub struct TelegramConfig {
     pub chat_id: String,
     /// Message template with {{variable}} placeholders
     pub message_template: String,
-    /// Parse mode: "Markdown", "MarkdownV2", or "HTML"
-    #[serde(default = "default_parse_mode")]
-    pub parse_mode: String,
+    /// Parse mode: "Markdown", "MarkdownV2", or "HTML" (plain text if unset)
+    #[serde(default)]
+    pub parse_mode: Option<String>,
+    /// Inline keyboard rows shown under the message
+    #[serde(default)]
+    pub inline_keyboard: Vec<Vec<InlineButtonConfig>>,
+}
+
+/// Inline keyboard button; all fields are templates
+#[derive(Debug, Clone, Deserialize)]
+pub struct InlineButtonConfig {
+    /// Button label
+    pub text: String,
+    /// What pressing the button does
+    #[serde(flatten)]
+    pub action: InlineButtonAction,
 }
 
-fn default_parse_mode() -> String {
-    "MarkdownV2".to_string()
+/// Inline keyboard button action
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineButtonAction {
+    /// Open a URL (`http`, `https` or `tg`)
+    Url(String),
+    /// Send a callback query with this data (1-64 bytes) to the bot
+    CallbackData(String),
 }
 
 impl TelegramConfig {
     /// Get teloxide ParseMode from config string
-    pub fn get_parse_mode(&self) -> ParseMode {
-        match self.parse_mode.to_lowercase().as_str() {
-            "html" => ParseMode::Html,
-            "markdown" => ParseMode::MarkdownV2, // Use MarkdownV2 for better compatibility
-            "markdownv2" => ParseMode::MarkdownV2,
-            _ => ParseMode::MarkdownV2,
+    ///
+    /// `None` means plain text. "Markdown" selects MarkdownV2, as the legacy
+    /// mode is deprecated by Telegram.
+    pub fn get_parse_mode(&self) -> Result<Option<ParseMode>, WorkerError> {
+        let Some(parse_mode) = &self.parse_mode else {
+            return Ok(None);
+        };
+        match parse_mode.to_lowercase().as_str() {
+            "html" => Ok(Some(ParseMode::Html)),
+            "markdown" | "markdownv2" => Ok(Some(ParseMode::MarkdownV2)),
+            _ => Err(WorkerError::invalid_config(format!(
+                "Invalid parse_mode: '{}' (expected \"MarkdownV2\" or \"HTML\")",
+                sanitize_for_logging(parse_mode)
+            ))),
+        }
+    }
+
+    /// Render the message and keyboard for an event
+    ///
+    /// With a parse mode, event values are escaped and the rendered markup is
+    /// validated, so formatting mistakes fail here as a permanent
+    /// [`WorkerError::Template`] rather than as a 400 from Telegram.
+    pub fn render(&self, event_data: &serde_json::Value) -> Result<TelegramMessage, WorkerError> {
+        let parse_mode = self.get_parse_mode()?;
+
+        let text = match parse_mode {
+            Some(mode) => {
+                let text = render_template_escaped(&self.message_template, event_data, |value| {
+                    telegram_format::escape(value, mode)
+                })?;
+                telegram_format::validate(&text, mode)?;
+                text
+            }
+            None => render_template(&self.message_template, event_data)?,
+        };
+
+        Ok(TelegramMessage {
+            text,
+            parse_mode,
+            reply_markup: self.render_keyboard(event_data)?,
+        })
+    }
+
+    /// Render the inline keyboard, if any
+    fn render_keyboard(
+        &self,
+        event_data: &serde_json::Value,
+    ) -> Result<Option<InlineKeyboardMarkup>, WorkerError> {
+        if self.inline_keyboard.is_empty() {
+            return Ok(None);
         }
+
+        let total: usize = self.inline_keyboard.iter().map(Vec::len).sum();
+        if total > MAX_BUTTONS {
+            return Err(WorkerError::invalid_config(format!(
+                "Inline keyboard has {} buttons (max: {})",
+                total, MAX_BUTTONS
+            )));
+        }
+
+        let rows = self
+            .inline_keyboard
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                if row.is_empty() || row.len() > MAX_BUTTONS_PER_ROW {
+                    return Err(WorkerError::invalid_config(format!(
+                        "Inline keyboard row {} has {} buttons (expected 1-{})",
+                        index + 1,
+                        row.len(),
+                        MAX_BUTTONS_PER_ROW
+                    )));
+                }
+                row.iter()
+                    .map(|button| button.render(event_data))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(InlineKeyboardMarkup::new(rows)))
     }
 
     /// Validate chat ID format
@@ -52,6 +158,68 @@ impl TelegramConfig {
     }
 }
 
+impl InlineButtonConfig {
+    /// Render the button for an event
+    fn render(&self, event_data: &serde_json::Value) -> Result<InlineKeyboardButton, WorkerError> {
+        let text = render_template(&self.text, event_data)?;
+        if text.trim().is_empty() {
+            return Err(WorkerError::invalid_config(
+                "Inline keyboard button text cannot be empty",
+            ));
+        }
+
+        match &self.action {
+            InlineButtonAction::Url(url) => {
+                let rendered = render_template(url, event_data)?;
+                let url = url::Url::parse(&rendered)
+                    .ok()
+                    .filter(|url| BUTTON_URL_SCHEMES.contains(&url.scheme()))
+                    .ok_or_else(|| {
+                        WorkerError::invalid_config(format!(
+                            "Invalid inline keyboard URL: '{}' (must be an http, https or tg URL)",
+                            sanitize_for_logging(&rendered)
+                        ))
+                    })?;
+                Ok(InlineKeyboardButton::url(text, url))
+            }
+            InlineButtonAction::CallbackData(data) => {
+                let data = render_template(data, event_data)?;
+                if data.is_empty() || data.len() > MAX_CALLBACK_DATA_LENGTH {
+                    return Err(WorkerError::invalid_config(format!(
+                        "Inline keyboard callback_data is {} bytes (expected 1-{})",
+                        data.len(),
+                        MAX_CALLBACK_DATA_LENGTH
+                    )));
+                }
+                Ok(InlineKeyboardButton::callback(text, data))
+            }
+        }
+    }
+}
+
+/// A rendered Telegram message
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelegramMessage {
+    /// Message text (markup if `parse_mode` is set)
+    pub text: String,
+    /// Parse mode for the text; `None` for plain text
+    pub parse_mode: Option<ParseMode>,
+    /// Inline keyboard shown under the message
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl TelegramMessage {
+    /// Plain text message without a keyboard
+    #[cfg(test)]
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            parse_mode: None,
+            reply_markup: None,
+        }
+    }
+}
+
 /// Validate a Telegram chat ID
 ///
 /// Chat IDs must be numeric (optionally prefixed with `-` for groups).
@@ -103,13 +271,11 @@ pub trait TelegramClient: Send + Sync {
     /// # Arguments
     ///
     /// * `chat_id` - Chat ID to send to
-    /// * `text` - Message text
-    /// * `parse_mode` - Parse mode for formatting
+    /// * `message` - Text, parse mode and keyboard to send
     async fn send_message(
         &self,
         chat_id: &str,
-        text: &str,
-        parse_mode: ParseMode,
+        message: &TelegramMessage,
     ) -> Result<(), WorkerError>;
 }
 
@@ -167,8 +333,7 @@ impl TelegramClient for TeloxideTelegramClient {
     async fn send_message(
         &self,
         chat_id: &str,
-        text: &str,
-        parse_mode: ParseMode,
+        message: &TelegramMessage,
     ) -> Result<(), WorkerError> {
         // Validate and parse chat_id to i64
         validate_chat_id(chat_id)?;
@@ -181,20 +346,23 @@ impl TelegramClient for TeloxideTelegramClient {
         })?;
 
         // Send message
-        self.bot
-            .send_message(ChatId(chat_id_num), text)
-            .parse_mode(parse_mode)
-            .await
-            .map_err(|e| {
-                // Log error details for debugging (in dev mode only)
-                tracing::error!(
-                    chat_id = sanitize_for_logging(chat_id),
-                    error = %e,
-                    error_debug = ?e,
-                    "Failed to send Telegram message"
-                );
-                WorkerError::telegram(format!("Failed to send message to Telegram API: {}", e))
-            })?;
+        let mut request = self.bot.send_message(ChatId(chat_id_num), &message.text);
+        if let Some(parse_mode) = message.parse_mode {
+            request = request.parse_mode(parse_mode);
+        }
+        if let Some(markup) = &message.reply_markup {
+            request = request.reply_markup(markup.clone());
+        }
+        request.await.map_err(|e| {
+            // Log error details for debugging (in dev mode only)
+            tracing::error!(
+                chat_id = sanitize_for_logging(chat_id),
+                error = %e,
+                error_debug = ?e,
+                "Failed to send Telegram message"
+            );
+            telegram_error(e)
+        })?;
 
         tracing::debug!(
             chat_id = sanitize_for_logging(chat_id),
@@ -205,6 +373,31 @@ impl TelegramClient for TeloxideTelegramClient {
     }
 }
 
+/// Map a failed Telegram request to a worker error
+fn telegram_error(e: teloxide::RequestError) -> WorkerError {
+    match e {
+        teloxide::RequestError::Network(e) if e.is_timeout() => {
+            WorkerError::timeout("no response from Telegram API")
+        }
+        teloxide::RequestError::Network(e) if e.is_connect() => {
+            WorkerError::connection("could not reach Telegram API")
+        }
+        teloxide::RequestError::RetryAfter(seconds) => {
+            WorkerError::rate_limit(format!("Telegram flood control, retry after {}", seconds))
+        }
+        // Retrying cannot fix the message itself
+        teloxide::RequestError::Api(teloxide::ApiError::CantParseEntities(details)) => {
+            WorkerError::template(format!("Telegram rejected the message markup: {}", details))
+        }
+        teloxide::RequestError::Api(
+            e @ (teloxide::ApiError::ButtonUrlInvalid
+            | teloxide::ApiError::ButtonDataInvalid
+            | teloxide::ApiError::WrongHttpUrl),
+        ) => WorkerError::invalid_config(format!("Telegram rejected the inline keyboard: {}", e)),
+        e => WorkerError::telegram(format!("Failed to send message to Telegram API: {}", e)),
+    }
+}
+
 /// Mock Telegram client for testing
 #[cfg(test)]
 #[derive(Clone, Default)]
@@ -213,6 +406,8 @@ pub struct MockTelegramClient {
     messages: std::sync::Arc<std::sync::Mutex<Vec<SentMessage>>>,
     /// Simulate failures
     should_fail: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Error returned by failing sends (defaults to a Telegram API error)
+    error: std::sync::Arc<std::sync::Mutex<Option<WorkerError>>>,
 }
 
 /// Record of a sent message
@@ -222,7 +417,8 @@ pub struct MockTelegramClient {
 pub struct SentMessage {
     pub chat_id: String,
     pub text: String,
-    pub parse_mode: ParseMode,
+    pub parse_mode: Option<ParseMode>,
+    pub reply_markup: Option<InlineKeyboardMarkup>,
 }
 
 #[cfg(test)]
@@ -240,6 +436,13 @@ impl MockTelegramClient {
         client
     }
 
+    /// Create a client that always fails with `error`
+    pub fn failing_with(error: WorkerError) -> Self {
+        let client = Self::failing();
+        *client.error.lock().unwrap() = Some(error);
+        client
+    }
+
     /// Get all sent messages
     pub fn sent_messages(&self) -> Vec<SentMessage> {
         self.messages.lock().unwrap().clone()
@@ -257,17 +460,20 @@ impl TelegramClient for MockTelegramClient {
     async fn send_message(
         &self,
         chat_id: &str,
-        text: &str,
-        parse_mode: ParseMode,
+        message: &TelegramMessage,
     ) -> Result<(), WorkerError> {
         if self.should_fail.load(std::sync::atomic::Ordering::SeqCst) {
-            return Err(WorkerError::telegram("Mock failure"));
+            return Err(match &*self.error.lock().unwrap() {
+                Some(error) => error.duplicate(),
+                None => WorkerError::telegram("Mock failure"),
+            });
         }
 
         self.messages.lock().unwrap().push(SentMessage {
             chat_id: chat_id.to_string(),
-            text: text.to_string(),
-            parse_mode,
+            text: message.text.clone(),
+            parse_mode: message.parse_mode,
+            reply_markup: message.reply_markup.clone(),
         });
 
         Ok(())
@@ -287,7 +493,9 @@ mod tests {
 
         let config: TelegramConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.chat_id, "123456789");
-        assert_eq!(config.parse_mode, "MarkdownV2");
+        assert_eq!(config.parse_mode, None);
+        assert!(config.get_parse_mode().unwrap().is_none());
+        assert!(config.inline_keyboard.is_empty());
     }
 
     #[test]
@@ -299,25 +507,147 @@ mod tests {
         }"#;
 
         let config: TelegramConfig = serde_json::from_str(json).unwrap();
-        assert_eq!(config.parse_mode, "HTML");
-        assert!(matches!(config.get_parse_mode(), ParseMode::Html));
+        assert_eq!(config.parse_mode.as_deref(), Some("HTML"));
+        assert_eq!(config.get_parse_mode().unwrap(), Some(ParseMode::Html));
+    }
+
+    fn config(template: &str, parse_mode: Option<&str>) -> TelegramConfig {
+        TelegramConfig {
+            chat_id: "123".to_string(),
+            message_template: template.to_string(),
+            parse_mode: parse_mode.map(str::to_string),
+            inline_keyboard: Vec::new(),
+        }
     }
 
     #[test]
     fn test_parse_mode_conversion() {
-        let config = TelegramConfig {
-            chat_id: "123".to_string(),
-            message_template: "test".to_string(),
-            parse_mode: "markdown".to_string(),
-        };
-        assert!(matches!(config.get_parse_mode(), ParseMode::MarkdownV2));
+        for (name, expected) in [
+            ("markdown", ParseMode::MarkdownV2),
+            ("MarkdownV2", ParseMode::MarkdownV2),
+            ("html", ParseMode::Html),
+        ] {
+            assert_eq!(
+                config("test", Some(name)).get_parse_mode().unwrap(),
+                Some(expected)
+            );
+        }
 
-        let config = TelegramConfig {
-            chat_id: "123".to_string(),
-            message_template: "test".to_string(),
-            parse_mode: "html".to_string(),
-        };
-        assert!(matches!(config.get_parse_mode(), ParseMode::Html));
+        let error = config("test", Some("bbcode")).get_parse_mode().unwrap_err();
+        assert!(matches!(error, WorkerError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_render_plain_text_is_not_escaped() {
+        let message = config("Score: {{score}}!", None)
+            .render(&serde_json::json!({"score": "9.5"}))
+            .unwrap();
+
+        assert_eq!(message, TelegramMessage::plain("Score: 9.5!"));
+    }
+
+    #[test]
+    fn test_render_escapes_values_for_parse_mode() {
+        let data = serde_json::json!({"agent_id": "a_b", "owner": "<x&y>"});
+
+        let message = config("*Agent* {{agent_id}}", Some("MarkdownV2"))
+            .render(&data)
+            .unwrap();
+        assert_eq!(message.text, "*Agent* a\\_b");
+        assert_eq!(message.parse_mode, Some(ParseMode::MarkdownV2));
+
+        let message = config("<b>Owner</b> {{owner}}", Some("HTML"))
+            .render(&data)
+            .unwrap();
+        assert_eq!(message.text, "<b>Owner</b> &lt;x&amp;y&gt;");
+        assert_eq!(message.parse_mode, Some(ParseMode::Html));
+    }
+
+    #[test]
+    fn test_render_rejects_invalid_markup() {
+        let error = config("Score: {{score}}!", Some("MarkdownV2"))
+            .render(&serde_json::json!({"score": "9.5"}))
+            .unwrap_err();
+
+        assert!(matches!(error, WorkerError::Template(_)));
+        assert!(!error.is_retryable());
+        assert!(error.to_string().contains("unescaped '!'"), "{}", error);
+    }
+
+    fn keyboard_config(keyboard: serde_json::Value) -> TelegramConfig {
+        serde_json::from_value(serde_json::json!({
+            "chat_id": "123",
+            "message_template": "New event",
+            "inline_keyboard": keyboard
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_inline_keyboard_serialization() {
+        let config = keyboard_config(serde_json::json!([
+            [
+                {"text": "View tx", "url": "https://etherscan.io/tx/{{transaction_hash}}"},
+                {"text": "Ack {{agent_id}}", "callback_data": "ack:{{event_id}}"}
+            ],
+            [{"text": "Open", "url": "tg://resolve?domain=agentauri"}]
+        ]));
+        let data = serde_json::json!({
+            "transaction_hash": "0xabc",
+            "agent_id": 42,
+            "event_id": "evt-1"
+        });
+
+        let message = config.render(&data).unwrap();
+        let markup = serde_json::to_value(message.reply_markup.unwrap()).unwrap();
+
+        assert_eq!(
+            markup,
+            serde_json::json!({
+                "inline_keyboard": [
+                    [
+                        {"text": "View tx", "url": "https://etherscan.io/tx/0xabc"},
+                        {"text": "Ack 42", "callback_data": "ack:evt-1"}
+                    ],
+                    [{"text": "Open", "url": "tg://resolve?domain=agentauri"}]
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_inline_keyboard_invalid_buttons() {
+        let data = serde_json::json!({});
+        let long_data = "x".repeat(MAX_CALLBACK_DATA_LENGTH + 1);
+        let full_row: Vec<_> = (0..=MAX_BUTTONS_PER_ROW)
+            .map(|_| serde_json::json!({"text": "x", "callback_data": "x"}))
+            .collect();
+
+        for keyboard in [
+            serde_json::json!([[{"text": "x", "url": "javascript:alert(1)"}]]),
+            serde_json::json!([[{"text": "x", "url": "not a url"}]]),
+            serde_json::json!([[{"text": "x", "callback_data": ""}]]),
+            serde_json::json!([[{"text": "x", "callback_data": long_data}]]),
+            serde_json::json!([[{"text": " ", "callback_data": "x"}]]),
+            serde_json::json!([[]]),
+            serde_json::json!([full_row]),
+        ] {
+            let error = keyboard_config(keyboard.clone()).render(&data).unwrap_err();
+            assert!(
+                matches!(error, WorkerError::InvalidConfig(_)),
+                "{}: {}",
+                keyboard,
+                error
+            );
+        }
+
+        // A button needs exactly one action
+        let parsed: Result<TelegramConfig, _> = serde_json::from_value(serde_json::json!({
+            "chat_id": "123",
+            "message_template": "x",
+            "inline_keyboard": [[{"text": "x"}]]
+        }));
+        assert!(parsed.is_err());
     }
 
     #[tokio::test]
@@ -325,7 +655,7 @@ mod tests {
         let client = MockTelegramClient::new();
 
         let result = client
-            .send_message("123", "Hello", ParseMode::MarkdownV2)
+            .send_message("123", &TelegramMessage::plain("Hello"))
             .await;
 
         assert!(result.is_ok());
@@ -341,20 +671,50 @@ mod tests {
         let client = MockTelegramClient::failing();
 
         let result = client
-            .send_message("123", "Hello", ParseMode::MarkdownV2)
+            .send_message("123", &TelegramMessage::plain("Hello"))
             .await;
 
         assert!(result.is_err());
         assert_eq!(client.message_count(), 0);
     }
 
+    #[test]
+    fn test_telegram_error_classification() {
+        use crate::result_logger::FailureCategory;
+        use teloxide::types::Seconds;
+        use teloxide::{ApiError, RequestError};
+
+        let flood = telegram_error(RequestError::RetryAfter(Seconds::from_seconds(5)));
+        assert_eq!(
+            flood.failure_reason().category,
+            FailureCategory::RateLimited
+        );
+
+        let blocked = telegram_error(RequestError::Api(ApiError::BotBlocked));
+        assert_eq!(blocked.failure_reason().category, FailureCategory::Upstream);
+        assert!(blocked.to_string().contains("Failed to send message"));
+
+        let markup = telegram_error(RequestError::Api(ApiError::CantParseEntities(
+            "Bad Request: can't parse entities".to_string(),
+        )));
+        assert_eq!(markup.failure_reason().category, FailureCategory::Template);
+        assert!(!markup.is_retryable());
+
+        let button = telegram_error(RequestError::Api(ApiError::ButtonUrlInvalid));
+        assert_eq!(button.failure_reason().category, FailureCategory::Config);
+        assert!(!button.is_retryable());
+    }
+
     #[tokio::test]
     async fn test_mock_client_multiple_messages() {
         let client = MockTelegramClient::new();
 
         for i in 0..3 {
             client
-                .send_message(&i.to_string(), &format!("Message {}", i), ParseMode::Html)
+                .send_message(
+                    &i.to_string(),
+                    &TelegramMessage::plain(format!("Message {}", i)),
+                )
                 .await
                 .unwrap();
         }
@@ -407,15 +767,13 @@ mod tests {
     fn test_telegram_config_validate_chat_id() {
         let valid_config = TelegramConfig {
             chat_id: "123456789".to_string(),
-            message_template: "test".to_string(),
-            parse_mode: "MarkdownV2".to_string(),
+            ..config("test", Some("MarkdownV2"))
         };
         assert!(valid_config.validate_chat_id().is_ok());
 
         let invalid_config = TelegramConfig {
             chat_id: "invalid".to_string(),
-            message_template: "test".to_string(),
-            parse_mode: "MarkdownV2".to_string(),
+            ..config("test", Some("MarkdownV2"))
         };
         assert!(invalid_config.validate_chat_id().is_err());
     }
@@ -0,0 +1,472 @@
+//! Telegram message formatting
+//!
+//! Escaping and validation for the `MarkdownV2` and `HTML` parse modes.
+//!
+//! Template variables are escaped before substitution, so only the markup
+//! written in the template itself is interpreted. The rendered message is then
+//! checked locally: markup Telegram would reject ("can't parse entities")
+//! fails the job with a [`WorkerError::Template`] naming the offending
+//! character, instead of an opaque 400 from the Bot API.
+
+use teloxide::types::ParseMode;
+
+use crate::error::WorkerError;
+
+/// Characters with a meaning in MarkdownV2 (must be escaped in plain text)
+const MARKDOWN_V2_SPECIAL: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+/// Characters with a meaning in legacy Markdown
+const MARKDOWN_SPECIAL: &[char] = &['_', '*', '`', '['];
+
+/// Tags supported by Telegram's HTML parse mode
+const HTML_TAGS: &[&str] = &[
+    "b",
+    "strong",
+    "i",
+    "em",
+    "u",
+    "ins",
+    "s",
+    "strike",
+    "del",
+    "span",
+    "tg-spoiler",
+    "a",
+    "tg-emoji",
+    "code",
+    "pre",
+    "blockquote",
+];
+
+/// Named entities supported by Telegram's HTML parse mode
+const HTML_ENTITIES: &[&str] = &["lt", "gt", "amp", "quot"];
+
+/// Escape text so it renders literally in the given parse mode
+pub fn escape(text: &str, parse_mode: ParseMode) -> String {
+    match parse_mode {
+        ParseMode::MarkdownV2 => escape_chars(text, MARKDOWN_V2_SPECIAL),
+        ParseMode::Html => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;"),
+        #[allow(deprecated)]
+        ParseMode::Markdown => escape_chars(text, MARKDOWN_SPECIAL),
+    }
+}
+
+fn escape_chars(text: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Check that a rendered message is well-formed for the given parse mode
+///
+/// # Errors
+///
+/// [`WorkerError::Template`] describing the first problem found
+pub fn validate(text: &str, parse_mode: ParseMode) -> Result<(), WorkerError> {
+    let result = match parse_mode {
+        ParseMode::MarkdownV2 => validate_markdown_v2(text),
+        ParseMode::Html => validate_html(text),
+        // Never produced from config ("Markdown" selects MarkdownV2)
+        #[allow(deprecated)]
+        ParseMode::Markdown => Ok(()),
+    };
+
+    result.map_err(|problem| {
+        WorkerError::template(format!(
+            "Invalid {} message: {}",
+            parse_mode_name(parse_mode),
+            problem
+        ))
+    })
+}
+
+fn parse_mode_name(parse_mode: ParseMode) -> &'static str {
+    match parse_mode {
+        ParseMode::MarkdownV2 => "MarkdownV2",
+        ParseMode::Html => "HTML",
+        #[allow(deprecated)]
+        ParseMode::Markdown => "Markdown",
+    }
+}
+
+/// MarkdownV2 inline styles (may nest, must not overlap)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Spoiler,
+}
+
+impl Style {
+    fn marker(self) -> &'static str {
+        match self {
+            Style::Bold => "*",
+            Style::Italic => "_",
+            Style::Underline => "__",
+            Style::Strikethrough => "~",
+            Style::Spoiler => "||",
+        }
+    }
+}
+
+/// Validate MarkdownV2 following <https://core.telegram.org/bots/api#markdownv2-style>
+///
+/// Positions in errors are 1-based character offsets.
+fn validate_markdown_v2(text: &str) -> Result<(), String> {
+    let chars: Vec<char> = text.chars().collect();
+    let at = |i: usize| chars.get(i).copied();
+
+    let mut styles: Vec<Style> = Vec::new();
+    // Position of the open '[' and the style depth when it was opened
+    let mut link: Option<(usize, usize)> = None;
+    let mut line_start = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let mut next = i + 1;
+
+        match c {
+            '\\' => {
+                if at(i + 1).is_none() {
+                    return Err(format!("trailing '\\' at character {}", i + 1));
+                }
+                next = i + 2;
+            }
+            '`' if at(i + 1) == Some('`') && at(i + 2) == Some('`') => {
+                next = close_pre(&chars, i)?;
+            }
+            '`' => {
+                next = close_delimited(&chars, i + 1, '`')
+                    .ok_or_else(|| format!("unclosed '`' opened at character {}", i + 1))?;
+            }
+            '*' => toggle(&mut styles, Style::Bold, i)?,
+            '~' => toggle(&mut styles, Style::Strikethrough, i)?,
+            '_' if at(i + 1) == Some('_') => {
+                toggle(&mut styles, Style::Underline, i)?;
+                next = i + 2;
+            }
+            '_' => toggle(&mut styles, Style::Italic, i)?,
+            '|' if at(i + 1) == Some('|') => {
+                toggle(&mut styles, Style::Spoiler, i)?;
+                next = i + 2;
+            }
+            // '![' opens a custom emoji, which has link syntax
+            '[' | '!' if c == '[' || at(i + 1) == Some('[') => {
+                if link.is_some() {
+                    return Err(format!("nested link at character {}", i + 1));
+                }
+                link = Some((i, styles.len()));
+                next = if c == '[' { i + 1 } else { i + 2 };
+            }
+            ']' if link.is_some() => {
+                let (_, depth) = link.take().unwrap_or_default();
+                if styles.len() != depth {
+                    return Err(format!(
+                        "'{}' not closed inside link text ending at character {}",
+                        styles.last().map(|s| s.marker()).unwrap_or_default(),
+                        i + 1
+                    ));
+                }
+                if at(i + 1) != Some('(') {
+                    return Err(format!(
+                        "link text ending at character {} must be followed by '(url)'",
+                        i + 1
+                    ));
+                }
+                next = close_delimited(&chars, i + 2, ')')
+                    .ok_or_else(|| format!("unclosed link URL at character {}", i + 2))?;
+            }
+            '>' if line_start => {}
+            c if MARKDOWN_V2_SPECIAL.contains(&c) => {
+                return Err(format!(
+                    "unescaped '{}' at character {} (write it as '\\{}')",
+                    c,
+                    i + 1,
+                    c
+                ));
+            }
+            _ => {}
+        }
+
+        line_start = c == '\n';
+        i = next;
+    }
+
+    if let Some(style) = styles.last() {
+        return Err(format!("unclosed '{}'", style.marker()));
+    }
+    if let Some((start, _)) = link {
+        return Err(format!("unclosed link opened at character {}", start + 1));
+    }
+    Ok(())
+}
+
+/// Open `style`, or close it if it is the innermost open style
+fn toggle(styles: &mut Vec<Style>, style: Style, i: usize) -> Result<(), String> {
+    if styles.last() == Some(&style) {
+        styles.pop();
+    } else if styles.contains(&style) {
+        return Err(format!(
+            "'{}' at character {} closes over another open style",
+            style.marker(),
+            i + 1
+        ));
+    } else {
+        styles.push(style);
+    }
+    Ok(())
+}
+
+/// Index just past the unescaped `close` at or after `from`
+fn close_delimited(chars: &[char], from: usize, close: char) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            c if c == close => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Index just past the "```" closing the pre block opened at `start`
+fn close_pre(chars: &[char], start: usize) -> Result<usize, String> {
+    let mut i = start + 3;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '`' if chars.get(i + 1) == Some(&'`') && chars.get(i + 2) == Some(&'`') => {
+                return Ok(i + 3)
+            }
+            '`' => {
+                return Err(format!(
+                    "unescaped '`' inside code block at character {} (write it as '\\`')",
+                    i + 1
+                ))
+            }
+            _ => i += 1,
+        }
+    }
+    Err(format!("unclosed '```' opened at character {}", start + 1))
+}
+
+/// Validate HTML following <https://core.telegram.org/bots/api#html-style>
+///
+/// Positions in errors are 1-based byte offsets.
+fn validate_html(text: &str) -> Result<(), String> {
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = text.char_indices();
+
+    while let Some((i, c)) = rest.next() {
+        match c {
+            '<' => {
+                let end = text[i..]
+                    .find('>')
+                    .map(|offset| i + offset)
+                    .ok_or_else(|| {
+                        format!("unescaped '<' at position {} (write it as &lt;)", i + 1)
+                    })?;
+                let body = &text[i + 1..end];
+                let (closing, body) = match body.strip_prefix('/') {
+                    Some(body) => (true, body),
+                    None => (false, body),
+                };
+                let name = body
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+
+                if !HTML_TAGS.contains(&name.as_str()) {
+                    return Err(format!(
+                        "unsupported tag <{}> at position {} (write a literal '<' as &lt;)",
+                        body,
+                        i + 1
+                    ));
+                }
+                if closing {
+                    if open.pop().as_deref() != Some(name.as_str()) {
+                        return Err(format!("unexpected </{}> at position {}", name, i + 1));
+                    }
+                } else {
+                    open.push(name);
+                }
+
+                // Skip past the tag
+                while rest.next().is_some_and(|(j, _)| j < end) {}
+            }
+            '>' => {
+                return Err(format!(
+                    "unescaped '>' at position {} (write it as &gt;)",
+                    i + 1
+                ))
+            }
+            '&' => {
+                let entity = text[i + 1..].split(';').next().unwrap_or_default();
+                let valid = text[i + 1..].contains(';')
+                    && (HTML_ENTITIES.contains(&entity) || is_numeric_entity(entity));
+                if !valid {
+                    return Err(format!(
+                        "unescaped '&' at position {} (write it as &amp;)",
+                        i + 1
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(tag) = open.last() {
+        return Err(format!("unclosed <{}>", tag));
+    }
+    Ok(())
+}
+
+/// `#123` or `#x1F600`
+fn is_numeric_entity(entity: &str) -> bool {
+    match entity.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => {
+            hex.len() > 1 && hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+        }
+        Some(dec) => !dec.is_empty() && dec.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markdown_error(text: &str) -> String {
+        validate_markdown_v2(text).expect_err(text)
+    }
+
+    fn html_error(text: &str) -> String {
+        validate_html(text).expect_err(text)
+    }
+
+    #[test]
+    fn test_escape_markdown_v2() {
+        assert_eq!(
+            escape("Score: 9.5 (up!) [0x1_a]", ParseMode::MarkdownV2),
+            "Score: 9\\.5 \\(up\\!\\) \\[0x1\\_a\\]"
+        );
+        assert_eq!(escape("a\\b", ParseMode::MarkdownV2), "a\\\\b");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape("<b>\"Tom & Jerry\"</b>", ParseMode::Html),
+            "&lt;b&gt;&quot;Tom &amp; Jerry&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_escaped_text_is_valid() {
+        let text = "2024-01-15 10:00:00 UTC | *score* = 9.5! <tag> & `code` ||x|| ~y~ #1 {z}";
+        for mode in [ParseMode::MarkdownV2, ParseMode::Html] {
+            let escaped = escape(text, mode);
+            assert!(validate(&escaped, mode).is_ok(), "{:?}: {}", mode, escaped);
+        }
+    }
+
+    #[test]
+    fn test_markdown_v2_valid_markup() {
+        for text in [
+            "*bold* _italic_ __underline__ ~strike~ ||spoiler||",
+            "*bold _italic bold ~strike~ __underline__ ||spoiler||_ bold*",
+            "[View on Explorer](https://etherscan.io/tx/0xabc)",
+            "[*bold link*](https://example.com/a_(b\\))",
+            "![👍](tg://emoji?id=5368324170671202286)",
+            "`inline code with * and _`",
+            "```rust\nlet x = 1 + 2;\n```",
+            "> quoted\n>another quote",
+            "Escaped: \\. \\! \\- \\# \\( \\)",
+        ] {
+            assert!(validate_markdown_v2(text).is_ok(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_markdown_v2_unescaped_characters() {
+        let error = markdown_error("High score detected!");
+        assert!(error.contains("unescaped '!' at character 20"), "{}", error);
+        assert!(error.contains("'\\!'"), "{}", error);
+
+        for text in [
+            "9.5", "a-b", "x = y", "#tag", "{x}", "a > b", "(x)", "a | b",
+        ] {
+            assert!(markdown_error(text).contains("unescaped"), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_markdown_v2_unbalanced_markup() {
+        assert!(markdown_error("*bold").contains("unclosed '*'"));
+        assert!(markdown_error("__underline").contains("unclosed '__'"));
+        assert!(markdown_error("*bold _italic* x_").contains("closes over"));
+        assert!(markdown_error("`code").contains("unclosed '`'"));
+        assert!(markdown_error("```\ncode").contains("unclosed '```'"));
+        assert!(markdown_error("```\na ` b\n```").contains("inside code block"));
+        assert!(markdown_error("[text]").contains("must be followed by '(url)'"));
+        assert!(markdown_error("[text](https://x.io").contains("unclosed link URL"));
+        assert!(markdown_error("[text").contains("unclosed link"));
+        assert!(markdown_error("[a [b](x)](y)").contains("nested link"));
+        assert!(markdown_error("[*a](x)*").contains("inside link text"));
+        assert!(markdown_error("end\\").contains("trailing '\\'"));
+    }
+
+    #[test]
+    fn test_html_valid_markup() {
+        for text in [
+            "<b>bold</b> <i>italic</i> <u>u</u> <s>s</s> <tg-spoiler>x</tg-spoiler>",
+            "<a href=\"https://etherscan.io/tx/0xabc\">View</a>",
+            "<span class=\"tg-spoiler\">x</span>",
+            "<pre><code class=\"language-rust\">1 &lt; 2</code></pre>",
+            "<B>case-insensitive</b>",
+            "&lt;&gt;&amp;&quot;&#39;&#x1F600;",
+        ] {
+            assert!(validate_html(text).is_ok(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_html_invalid_markup() {
+        assert!(html_error("<div>x</div>").contains("unsupported tag <div>"));
+        assert!(html_error("1 < 2").contains("unescaped '<'"));
+        assert!(html_error("1 < 2 > 0").contains("unsupported tag"));
+        assert!(html_error("<b>x").contains("unclosed <b>"));
+        assert!(html_error("<b><i>x</b></i>").contains("unexpected </b>"));
+        assert!(html_error("a > b").contains("unescaped '>'"));
+        assert!(html_error("Tom & Jerry").contains("unescaped '&'"));
+        assert!(html_error("&nbsp;").contains("unescaped '&'"));
+        assert!(html_error("<b").contains("unescaped '<'"));
+    }
+
+    #[test]
+    fn test_validate_returns_template_error() {
+        let error = validate("Done!", ParseMode::MarkdownV2).unwrap_err();
+        assert!(matches!(error, WorkerError::Template(_)));
+        assert!(!error.is_retryable());
+        assert!(error.to_string().contains("Invalid MarkdownV2 message"));
+
+        let error = validate("<p>x</p>", ParseMode::Html).unwrap_err();
+        assert!(error.to_string().contains("Invalid HTML message"));
+    }
+}
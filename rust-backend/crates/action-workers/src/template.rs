@@ -169,6 +169,18 @@ pub fn validate_template_length(template: &str) -> Result<(), WorkerError> {
 pub fn render_template(
     template: &str,
     variables: &serde_json::Value,
+) -> Result<String, WorkerError> {
+    render_template_escaped(template, variables, str::to_string)
+}
+
+/// Render a template, passing each substituted value through `escape`
+///
+/// Used when the template is markup (e.g. Telegram MarkdownV2) so event data
+/// renders literally instead of being interpreted.
+pub fn render_template_escaped(
+    template: &str,
+    variables: &serde_json::Value,
+    escape: impl Fn(&str) -> String,
 ) -> Result<String, WorkerError> {
     // Validate template before rendering
     validate_template_length(template)?;
@@ -182,7 +194,7 @@ pub fn render_template(
         let var_name = &cap[1]; // e.g., "agent_id"
 
         // Look up variable value (already validated to be in whitelist)
-        let value = get_variable_value(variables, var_name);
+        let value = escape(&get_variable_value(variables, var_name));
 
         // Replace all occurrences of this variable
         result = result.replace(full_match, &value);
@@ -341,6 +353,15 @@ mod tests {
         assert_eq!(result, "Bob is Bob is Bob");
     }
 
+    #[test]
+    fn test_render_escaped_only_escapes_values() {
+        let template = "*{{agent_id}}*";
+        let vars = json!({"agent_id": "a*b"});
+
+        let result = render_template_escaped(template, &vars, |v| v.replace('*', "\\*")).unwrap();
+        assert_eq!(result, "*a\\*b*");
+    }
+
     #[test]
     fn test_render_no_variables() {
         let template = "Static message with no variables";
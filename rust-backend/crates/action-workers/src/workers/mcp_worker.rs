@@ -7,6 +7,7 @@ use std::time::Instant;
 
 use shared::ActionJob;
 
+use crate::credits::ActionBilling;
 use crate::dlq::{DeadLetterQueue, DlqEntry};
 use crate::error::WorkerError;
 use crate::mcp::{McpClient, McpConfig};
@@ -26,6 +27,7 @@ where
     logger: Arc<L>,
     dlq: Arc<D>,
     retry_policy: RetryPolicy,
+    billing: Option<Arc<ActionBilling>>,
 }
 
 impl<C, L, D> McpWorker<C, L, D>
@@ -41,9 +43,16 @@ where
             logger,
             dlq,
             retry_policy,
+            billing: None,
         }
     }
 
+    /// Check and charge each job's cost to its organization
+    pub fn with_billing(mut self, billing: Arc<ActionBilling>) -> Self {
+        self.billing = Some(billing);
+        self
+    }
+
     /// Process a single MCP action job
     ///
     /// # Arguments
@@ -68,17 +77,24 @@ where
             "Processing MCP job"
         );
 
-        // Parse configuration
-        let config: McpConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
-            tracing::error!(error = %e, "Failed to parse MCP config");
-            WorkerError::invalid_config(format!("Invalid MCP config: {}", e))
-        })?;
-
-        // Validate configuration
-        config.validate()?;
+        // A job rejected before any attempt is logged but not dead-lettered
+        let (config, arguments) = match prepare_call(job, event_data) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        };
 
-        // Render arguments template
-        let arguments = render_json_template(&config.arguments_template, event_data)?;
+        // So is a job its organization cannot pay for
+        if let Some(billing) = &self.billing {
+            if let Err(e) = billing.authorize(job).await {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        }
 
         tracing::debug!(
             tool_name = %config.tool_name,
@@ -120,14 +136,21 @@ where
                 // Success - log result
                 metrics::record_job_success("mcp", duration.as_secs_f64());
 
+                if let Some(billing) = &self.billing {
+                    billing.charge(job).await;
+                }
+
                 self.logger
-                    .log(ActionResult::success(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "mcp".to_string(),
-                        duration_ms,
-                    ))
+                    .log(
+                        ActionResult::success(
+                            job.id.clone(),
+                            job.trigger_id.clone(),
+                            job.event_id.clone(),
+                            "mcp".to_string(),
+                            duration_ms,
+                        )
+                        .with_action_id(job.action_id),
+                    )
                     .await?;
 
                 tracing::info!(
@@ -156,16 +179,7 @@ where
                     .await?;
 
                 // Log failure
-                self.logger
-                    .log(ActionResult::failure(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "mcp".to_string(),
-                        duration_ms,
-                        error_msg.clone(),
-                        self.retry_policy.max_attempts as i32,
-                    ))
+                self.log_failure(job, duration_ms, &e, self.retry_policy.max_attempts as i32)
                     .await?;
 
                 tracing::error!(
@@ -180,6 +194,45 @@ where
             }
         }
     }
+
+    /// Log a failed job with the classified cause of its last error
+    async fn log_failure(
+        &self,
+        job: &ActionJob,
+        duration_ms: i64,
+        error: &WorkerError,
+        retry_count: i32,
+    ) -> Result<(), WorkerError> {
+        self.logger
+            .log(
+                ActionResult::failure(
+                    job.id.clone(),
+                    job.trigger_id.clone(),
+                    job.event_id.clone(),
+                    "mcp".to_string(),
+                    duration_ms,
+                    error,
+                    retry_count,
+                )
+                .with_action_id(job.action_id),
+            )
+            .await
+    }
+}
+
+/// Parse and validate a job's MCP configuration and render its arguments
+fn prepare_call(
+    job: &ActionJob,
+    event_data: &serde_json::Value,
+) -> Result<(McpConfig, serde_json::Value), WorkerError> {
+    let config: McpConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
+        tracing::error!(error = %e, "Failed to parse MCP config");
+        WorkerError::invalid_config(format!("Invalid MCP config: {}", e))
+    })?;
+    config.validate()?;
+
+    let arguments = render_json_template(&config.arguments_template, event_data)?;
+    Ok((config, arguments))
 }
 
 impl<C, L, D> Clone for McpWorker<C, L, D>
@@ -194,6 +247,7 @@ where
             logger: self.logger.clone(),
             dlq: self.dlq.clone(),
             retry_policy: self.retry_policy.clone(),
+            billing: self.billing.clone(),
         }
     }
 }
@@ -246,7 +300,9 @@ mod tests {
     use super::*;
     use crate::dlq::InMemoryDlq;
     use crate::mcp::MockMcpClient;
-    use crate::result_logger::{ActionStatus, InMemoryResultLogger};
+    use crate::result_logger::{
+        ActionStatus, FailureCategory, FailureReason, InMemoryResultLogger,
+    };
     use crate::retry::RetryPolicy;
     use serde_json::json;
     use shared::ActionType;
@@ -274,6 +330,25 @@ mod tests {
         )
     }
 
+    /// Process a failing job once and return the failure reasons logged
+    async fn logged_failures(
+        client: MockMcpClient,
+        config: serde_json::Value,
+    ) -> Vec<FailureReason> {
+        let logger = Arc::new(InMemoryResultLogger::new());
+        let worker = McpWorker::new(
+            Arc::new(client),
+            logger.clone(),
+            Arc::new(InMemoryDlq::new()),
+            RetryPolicy::new(1, Duration::from_millis(10), Duration::from_millis(10)),
+        );
+
+        let job = create_test_job(config).with_action_id(7);
+        assert!(worker.process(&job, &json!({})).await.is_err());
+        assert!(logger.results().iter().all(|r| r.action_id == Some(7)));
+        logger.failure_reasons()
+    }
+
     #[tokio::test]
     async fn test_process_success() {
         let client = MockMcpClient::new().with_success();
@@ -344,6 +419,68 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_failures_logged_with_category() {
+        let tool = json!({"server_url": "https://mcp.example.com", "tool_name": "test_tool"});
+        let cases = [
+            (
+                MockMcpClient::new().with_error(WorkerError::timeout("no response after 30000ms")),
+                tool.clone(),
+                FailureCategory::Timeout,
+                "request_timeout",
+            ),
+            (
+                MockMcpClient::new().with_error(WorkerError::connection("could not reach server")),
+                tool.clone(),
+                FailureCategory::Connection,
+                "connection_failed",
+            ),
+            (
+                MockMcpClient::new().with_error(WorkerError::http_status(
+                    502,
+                    "HTTP 502 Bad Gateway from MCP server: ",
+                )),
+                tool.clone(),
+                FailureCategory::ServerError,
+                "http_502",
+            ),
+            (
+                MockMcpClient::new().with_error(WorkerError::mcp("MCP tool error: boom")),
+                tool.clone(),
+                FailureCategory::Upstream,
+                "mcp_error",
+            ),
+            (
+                MockMcpClient::new(),
+                json!({
+                    "server_url": "https://mcp.example.com",
+                    "tool_name": "test_tool",
+                    "arguments_template": {"value": "{{not_allowed}}"}
+                }),
+                FailureCategory::Template,
+                "template_error",
+            ),
+            (
+                MockMcpClient::new(),
+                json!({"invalid_field": "value"}),
+                FailureCategory::Config,
+                "invalid_config",
+            ),
+        ];
+
+        for (client, config, category, code) in cases {
+            assert_eq!(
+                logged_failures(client, config).await,
+                vec![FailureReason {
+                    category,
+                    code: code.to_string()
+                }],
+                "expected {}",
+                code
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_template_rendering() {
         let client = MockMcpClient::new().with_success();
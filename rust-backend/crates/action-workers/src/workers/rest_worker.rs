@@ -1,6 +1,7 @@
 //! REST worker implementation
 //!
-//! Processes REST/HTTP action jobs from the queue.
+//! Processes REST/HTTP action jobs from the queue. Requests are signed with
+//! the organization's signing keys when it has any.
 
 use std::sync::Arc;
 #[cfg(test)]
@@ -9,12 +10,14 @@ use std::time::Instant;
 
 use shared::ActionJob;
 
+use crate::credits::ActionBilling;
 use crate::dlq::{DeadLetterQueue, DlqEntry};
 use crate::error::WorkerError;
 use crate::metrics;
 use crate::rest::{HttpClient, RestConfig};
 use crate::result_logger::{ActionResult, ResultLogger};
 use crate::retry::{execute_with_retry, RetryPolicy};
+use crate::signing_keys::SigningKeyStore;
 
 /// REST worker that processes REST/HTTP action jobs
 pub struct RestWorker<C, L, D>
@@ -27,6 +30,8 @@ where
     logger: Arc<L>,
     dlq: Arc<D>,
     retry_policy: RetryPolicy,
+    signing_keys: Option<Arc<dyn SigningKeyStore>>,
+    billing: Option<Arc<ActionBilling>>,
 }
 
 impl<C, L, D> RestWorker<C, L, D>
@@ -42,9 +47,23 @@ where
             logger,
             dlq,
             retry_policy,
+            signing_keys: None,
+            billing: None,
         }
     }
 
+    /// Sign requests with the organization's signing keys when it has any
+    pub fn with_signing_keys(mut self, signing_keys: Arc<dyn SigningKeyStore>) -> Self {
+        self.signing_keys = Some(signing_keys);
+        self
+    }
+
+    /// Check and charge each job's cost to its organization
+    pub fn with_billing(mut self, billing: Arc<ActionBilling>) -> Self {
+        self.billing = Some(billing);
+        self
+    }
+
     /// Process a single REST action job
     ///
     /// # Arguments
@@ -69,28 +88,52 @@ where
             "Processing REST job"
         );
 
-        // Parse configuration
-        let config: RestConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
-            tracing::error!(error = %e, "Failed to parse REST config");
-            WorkerError::invalid_config(format!("Invalid REST config: {}", e))
-        })?;
+        // A job rejected before any attempt is logged but not dead-lettered
+        let config = match parse_config(job) {
+            Ok(config) => config,
+            Err(e) => {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        };
 
-        // Validate configuration (security: validates URL, method, headers, etc.)
-        config.validate()?;
+        // So is a job its organization cannot pay for
+        if let Some(billing) = &self.billing {
+            if let Err(e) = billing.authorize(job).await {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        }
 
         // Clone Arc reference for the retry closure
         let client = self.client.clone();
+        let signing_key_store = self.signing_keys.clone();
         let config_clone = config.clone();
         let event_data_clone = event_data.clone();
 
         // Execute with retry
         let result = execute_with_retry(&self.retry_policy, "rest", || {
             let client = client.clone();
+            let signing_key_store = signing_key_store.clone();
             let config = config_clone.clone();
             let event_data = event_data_clone.clone();
             async move {
+                // Looked up per attempt so a rotation applies to the next retry
+                let signing_keys = match &signing_key_store {
+                    Some(store) => {
+                        store
+                            .find_keys(job.organization_id.as_deref(), &job.trigger_id)
+                            .await?
+                    }
+                    None => None,
+                };
+
                 // Execute HTTP request with template rendering
-                client.execute_request(&config, &event_data).await
+                client
+                    .execute_request(&config, &event_data, signing_keys.as_ref())
+                    .await
             }
         })
         .await;
@@ -103,14 +146,21 @@ where
                 // Success - log result
                 metrics::record_job_success("rest", duration.as_secs_f64());
 
+                if let Some(billing) = &self.billing {
+                    billing.charge(job).await;
+                }
+
                 self.logger
-                    .log(ActionResult::success(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "rest".to_string(),
-                        duration_ms,
-                    ))
+                    .log(
+                        ActionResult::success(
+                            job.id.clone(),
+                            job.trigger_id.clone(),
+                            job.event_id.clone(),
+                            "rest".to_string(),
+                            duration_ms,
+                        )
+                        .with_action_id(job.action_id),
+                    )
                     .await?;
 
                 tracing::info!(
@@ -138,16 +188,7 @@ where
                     .await?;
 
                 // Log failure
-                self.logger
-                    .log(ActionResult::failure(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "rest".to_string(),
-                        duration_ms,
-                        error_msg.clone(),
-                        self.retry_policy.max_attempts as i32,
-                    ))
+                self.log_failure(job, duration_ms, &e, self.retry_policy.max_attempts as i32)
                     .await?;
 
                 tracing::error!(
@@ -161,6 +202,42 @@ where
             }
         }
     }
+
+    /// Log a failed job with the classified cause of its last error
+    async fn log_failure(
+        &self,
+        job: &ActionJob,
+        duration_ms: i64,
+        error: &WorkerError,
+        retry_count: i32,
+    ) -> Result<(), WorkerError> {
+        self.logger
+            .log(
+                ActionResult::failure(
+                    job.id.clone(),
+                    job.trigger_id.clone(),
+                    job.event_id.clone(),
+                    "rest".to_string(),
+                    duration_ms,
+                    error,
+                    retry_count,
+                )
+                .with_action_id(job.action_id),
+            )
+            .await
+    }
+}
+
+/// Parse and validate a job's REST configuration
+///
+/// Security: validates URL, method, headers, etc.
+fn parse_config(job: &ActionJob) -> Result<RestConfig, WorkerError> {
+    let config: RestConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
+        tracing::error!(error = %e, "Failed to parse REST config");
+        WorkerError::invalid_config(format!("Invalid REST config: {}", e))
+    })?;
+    config.validate()?;
+    Ok(config)
 }
 
 impl<C, L, D> Clone for RestWorker<C, L, D>
@@ -175,6 +252,8 @@ where
             logger: self.logger.clone(),
             dlq: self.dlq.clone(),
             retry_policy: self.retry_policy.clone(),
+            signing_keys: self.signing_keys.clone(),
+            billing: self.billing.clone(),
         }
     }
 }
@@ -183,8 +262,11 @@ where
 mod tests {
     use super::*;
     use crate::dlq::InMemoryDlq;
-    use crate::rest::MockHttpClient;
-    use crate::result_logger::{ActionStatus, InMemoryResultLogger};
+    use crate::rest::{MockHttpClient, SigningKeys};
+    use crate::result_logger::{
+        ActionStatus, FailureCategory, FailureReason, InMemoryResultLogger,
+    };
+    use crate::signing_keys::StaticSigningKeyStore;
     use serde_json::json;
     use shared::ActionType;
     use std::collections::HashMap;
@@ -211,6 +293,25 @@ mod tests {
         )
     }
 
+    /// Process a failing job once and return the failure reasons logged
+    async fn logged_failures(
+        client: MockHttpClient,
+        config: serde_json::Value,
+    ) -> Vec<FailureReason> {
+        let logger = Arc::new(InMemoryResultLogger::new());
+        let worker = RestWorker::new(
+            Arc::new(client),
+            logger.clone(),
+            Arc::new(InMemoryDlq::new()),
+            RetryPolicy::new(1, Duration::from_millis(10), Duration::from_millis(10)),
+        );
+
+        let job = create_test_job(config).with_action_id(7);
+        assert!(worker.process(&job, &json!({})).await.is_err());
+        assert!(logger.results().iter().all(|r| r.action_id == Some(7)));
+        logger.failure_reasons()
+    }
+
     #[tokio::test]
     async fn test_process_get_request_success() {
         let client = MockHttpClient::new().with_response(200, Some(json!({"status": "ok"})));
@@ -264,6 +365,42 @@ mod tests {
         assert_eq!(requests[0].url, "https://api.example.com/events");
     }
 
+    #[tokio::test]
+    async fn test_process_signs_with_organization_keys() {
+        let client = MockHttpClient::new().with_response(200, None);
+        let keys = SigningKeys::new("whsec_new").with_previous("whsec_old");
+        let worker = create_worker(client.clone())
+            .with_signing_keys(Arc::new(StaticSigningKeyStore(Some(keys.clone()))));
+
+        let job = create_test_job(json!({
+            "method": "POST",
+            "url": "https://api.example.com/events",
+            "body": {"agent_id": "{{agent_id}}"}
+        }));
+        worker
+            .process(&job, &json!({"agent_id": "42"}))
+            .await
+            .unwrap();
+
+        let requests = client.requests();
+        assert_eq!(requests[0].signing_keys.as_ref(), Some(&keys));
+    }
+
+    #[tokio::test]
+    async fn test_process_unsigned_without_organization_keys() {
+        let client = MockHttpClient::new().with_response(200, None);
+        let worker =
+            create_worker(client.clone()).with_signing_keys(Arc::new(StaticSigningKeyStore(None)));
+
+        let job = create_test_job(json!({
+            "method": "GET",
+            "url": "https://api.example.com/webhook"
+        }));
+        worker.process(&job, &json!({})).await.unwrap();
+
+        assert!(client.requests()[0].signing_keys.is_none());
+    }
+
     #[tokio::test]
     async fn test_process_template_rendering_in_url() {
         let client = MockHttpClient::new().with_response(200, None);
@@ -411,6 +548,65 @@ mod tests {
         assert_eq!(dlq.len().await.unwrap(), 1);
     }
 
+    #[tokio::test]
+    async fn test_failures_logged_with_category() {
+        let webhook = json!({"method": "GET", "url": "https://api.example.com/webhook"});
+        let cases = [
+            (
+                MockHttpClient::new().with_error(WorkerError::timeout("no response after 30s")),
+                webhook.clone(),
+                FailureCategory::Timeout,
+                "request_timeout",
+            ),
+            (
+                MockHttpClient::new().with_error(WorkerError::connection("could not reach host")),
+                webhook.clone(),
+                FailureCategory::Connection,
+                "connection_failed",
+            ),
+            (
+                MockHttpClient::new().with_response(404, None),
+                webhook.clone(),
+                FailureCategory::ClientError,
+                "http_404",
+            ),
+            (
+                MockHttpClient::new().with_response(503, None),
+                webhook.clone(),
+                FailureCategory::ServerError,
+                "http_503",
+            ),
+            (
+                MockHttpClient::new(),
+                json!({
+                    "method": "POST",
+                    "url": "https://api.example.com/webhook",
+                    "body": {"value": "{{not_allowed}}"}
+                }),
+                FailureCategory::Template,
+                "template_error",
+            ),
+            (
+                MockHttpClient::new(),
+                json!({"method": "GET", "url": "not-a-valid-url"}),
+                FailureCategory::Config,
+                "invalid_config",
+            ),
+        ];
+
+        for (client, config, category, code) in cases {
+            assert_eq!(
+                logged_failures(client, config).await,
+                vec![FailureReason {
+                    category,
+                    code: code.to_string()
+                }],
+                "expected {}",
+                code
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_worker_clone() {
         let client = MockHttpClient::new();
@@ -7,14 +7,14 @@ use std::time::{Duration, Instant};
 
 use shared::ActionJob;
 
+use crate::credits::ActionBilling;
 use crate::dlq::{DeadLetterQueue, DlqEntry};
 use crate::error::WorkerError;
 use crate::metrics;
 use crate::rate_limiter::RateLimiter;
 use crate::result_logger::{ActionResult, ResultLogger};
 use crate::retry::{execute_with_retry, RetryPolicy};
-use crate::telegram::{TelegramClient, TelegramConfig};
-use crate::template::render_template;
+use crate::telegram::{TelegramClient, TelegramConfig, TelegramMessage};
 
 /// Telegram worker that processes Telegram action jobs
 pub struct TelegramWorker<C, L, D, R>
@@ -29,6 +29,7 @@ where
     dlq: Arc<D>,
     rate_limiter: Arc<R>,
     retry_policy: RetryPolicy,
+    billing: Option<Arc<ActionBilling>>,
 }
 
 impl<C, L, D, R> TelegramWorker<C, L, D, R>
@@ -52,9 +53,16 @@ where
             dlq,
             rate_limiter,
             retry_policy,
+            billing: None,
         }
     }
 
+    /// Check and charge each job's cost to its organization
+    pub fn with_billing(mut self, billing: Arc<ActionBilling>) -> Self {
+        self.billing = Some(billing);
+        self
+    }
+
     /// Process a single Telegram action job
     ///
     /// # Arguments
@@ -79,18 +87,24 @@ where
             "Processing Telegram job"
         );
 
-        // Parse configuration
-        let config: TelegramConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
-            tracing::error!(error = %e, "Failed to parse Telegram config");
-            WorkerError::invalid_config(format!("Invalid Telegram config: {}", e))
-        })?;
-
-        // Validate chat ID (security: prevent invalid/malicious chat IDs)
-        config.validate_chat_id()?;
+        // A job rejected before any attempt is logged but not dead-lettered
+        let (config, message) = match prepare_message(job, event_data) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        };
 
-        // Render message template (security: validates against whitelist, checks length)
-        let message = render_template(&config.message_template, event_data)?;
-        let parse_mode = config.get_parse_mode();
+        // So is a job its organization cannot pay for
+        if let Some(billing) = &self.billing {
+            if let Err(e) = billing.authorize(job).await {
+                self.log_failure(job, start.elapsed().as_millis() as i64, &e, 0)
+                    .await?;
+                return Err(e);
+            }
+        }
 
         // Clone Arc references for the retry closure
         let client = self.client.clone();
@@ -110,7 +124,7 @@ where
                     .await?;
 
                 // Send message
-                client.send_message(&chat_id, &message, parse_mode).await
+                client.send_message(&chat_id, &message).await
             }
         })
         .await;
@@ -123,14 +137,21 @@ where
                 // Success - log result
                 metrics::record_job_success("telegram", duration.as_secs_f64());
 
+                if let Some(billing) = &self.billing {
+                    billing.charge(job).await;
+                }
+
                 self.logger
-                    .log(ActionResult::success(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "telegram".to_string(),
-                        duration_ms,
-                    ))
+                    .log(
+                        ActionResult::success(
+                            job.id.clone(),
+                            job.trigger_id.clone(),
+                            job.event_id.clone(),
+                            "telegram".to_string(),
+                            duration_ms,
+                        )
+                        .with_action_id(job.action_id),
+                    )
                     .await?;
 
                 tracing::info!(
@@ -157,16 +178,7 @@ where
                     .await?;
 
                 // Log failure
-                self.logger
-                    .log(ActionResult::failure(
-                        job.id.clone(),
-                        job.trigger_id.clone(),
-                        job.event_id.clone(),
-                        "telegram".to_string(),
-                        duration_ms,
-                        error_msg.clone(),
-                        self.retry_policy.max_attempts as i32,
-                    ))
+                self.log_failure(job, duration_ms, &e, self.retry_policy.max_attempts as i32)
                     .await?;
 
                 tracing::error!(
@@ -180,6 +192,48 @@ where
             }
         }
     }
+
+    /// Log a failed job with the classified cause of its last error
+    async fn log_failure(
+        &self,
+        job: &ActionJob,
+        duration_ms: i64,
+        error: &WorkerError,
+        retry_count: i32,
+    ) -> Result<(), WorkerError> {
+        self.logger
+            .log(
+                ActionResult::failure(
+                    job.id.clone(),
+                    job.trigger_id.clone(),
+                    job.event_id.clone(),
+                    "telegram".to_string(),
+                    duration_ms,
+                    error,
+                    retry_count,
+                )
+                .with_action_id(job.action_id),
+            )
+            .await
+    }
+}
+
+/// Parse a job's Telegram configuration and render its message
+fn prepare_message(
+    job: &ActionJob,
+    event_data: &serde_json::Value,
+) -> Result<(TelegramConfig, TelegramMessage), WorkerError> {
+    let config: TelegramConfig = serde_json::from_value(job.config.clone()).map_err(|e| {
+        tracing::error!(error = %e, "Failed to parse Telegram config");
+        WorkerError::invalid_config(format!("Invalid Telegram config: {}", e))
+    })?;
+
+    // Validate chat ID (security: prevent invalid/malicious chat IDs)
+    config.validate_chat_id()?;
+
+    // Render message and keyboard (security: validates against whitelist, checks length)
+    let message = config.render(event_data)?;
+    Ok((config, message))
 }
 
 impl<C, L, D, R> Clone for TelegramWorker<C, L, D, R>
@@ -196,6 +250,7 @@ where
             dlq: self.dlq.clone(),
             rate_limiter: self.rate_limiter.clone(),
             retry_policy: self.retry_policy.clone(),
+            billing: self.billing.clone(),
         }
     }
 }
@@ -203,12 +258,15 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::credits::InMemoryCreditLedger;
     use crate::dlq::InMemoryDlq;
     use crate::rate_limiter::NoopRateLimiter;
-    use crate::result_logger::{ActionStatus, InMemoryResultLogger};
+    use crate::result_logger::{
+        ActionStatus, FailureCategory, FailureReason, InMemoryResultLogger,
+    };
     use crate::telegram::MockTelegramClient;
     use serde_json::json;
-    use shared::ActionType;
+    use shared::{ActionCostsConfig, ActionType};
 
     fn create_test_job(config: serde_json::Value) -> ActionJob {
         ActionJob::new(
@@ -234,6 +292,26 @@ mod tests {
         )
     }
 
+    /// Process a failing job once and return the failure reasons logged
+    async fn logged_failures(
+        client: MockTelegramClient,
+        config: serde_json::Value,
+    ) -> Vec<FailureReason> {
+        let logger = Arc::new(InMemoryResultLogger::new());
+        let worker = TelegramWorker::new(
+            Arc::new(client),
+            logger.clone(),
+            Arc::new(InMemoryDlq::new()),
+            Arc::new(NoopRateLimiter),
+            RetryPolicy::new(1, Duration::from_millis(10), Duration::from_millis(10)),
+        );
+
+        let job = create_test_job(config).with_action_id(7);
+        assert!(worker.process(&job, &json!({})).await.is_err());
+        assert!(logger.results().iter().all(|r| r.action_id == Some(7)));
+        logger.failure_reasons()
+    }
+
     #[tokio::test]
     async fn test_process_success() {
         let client = MockTelegramClient::new();
@@ -304,6 +382,144 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_failures_logged_with_category() {
+        let message = json!({"chat_id": "123456789", "message_template": "Test message"});
+        let cases = [
+            (
+                MockTelegramClient::failing(),
+                message.clone(),
+                FailureCategory::Upstream,
+                "telegram_api_error",
+            ),
+            (
+                MockTelegramClient::failing_with(WorkerError::rate_limit("retry after 5s")),
+                message.clone(),
+                FailureCategory::RateLimited,
+                "rate_limited",
+            ),
+            (
+                MockTelegramClient::failing_with(WorkerError::timeout("no response")),
+                message.clone(),
+                FailureCategory::Timeout,
+                "request_timeout",
+            ),
+            (
+                MockTelegramClient::new(),
+                json!({"chat_id": "123456789", "message_template": "{{not_allowed}}"}),
+                FailureCategory::Template,
+                "template_error",
+            ),
+            (
+                MockTelegramClient::new(),
+                json!({
+                    "chat_id": "123456789",
+                    "message_template": "Done!",
+                    "parse_mode": "MarkdownV2"
+                }),
+                FailureCategory::Template,
+                "template_error",
+            ),
+            (
+                MockTelegramClient::new(),
+                json!({"invalid_field": "value"}),
+                FailureCategory::Config,
+                "invalid_config",
+            ),
+        ];
+
+        for (client, config, category, code) in cases {
+            assert_eq!(
+                logged_failures(client, config).await,
+                vec![FailureReason {
+                    category,
+                    code: code.to_string()
+                }],
+                "expected {}",
+                code
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_billing_blocks_unpaid_jobs_and_charges_successes() {
+        let ledger = Arc::new(InMemoryCreditLedger::with_balance("org-1", 3_000));
+        let costs = ActionCostsConfig {
+            telegram: 2_000,
+            ..ActionCostsConfig::default()
+        };
+        let client = MockTelegramClient::new();
+        let logger = Arc::new(InMemoryResultLogger::new());
+        let worker = TelegramWorker::new(
+            Arc::new(client.clone()),
+            logger.clone(),
+            Arc::new(InMemoryDlq::new()),
+            Arc::new(NoopRateLimiter),
+            RetryPolicy::new(1, Duration::from_millis(10), Duration::from_millis(10)),
+        )
+        .with_billing(Arc::new(ActionBilling::new(ledger.clone(), costs)));
+        let job = || {
+            create_test_job(json!({"chat_id": "123456789", "message_template": "Hi"}))
+                .with_organization_id("org-1")
+        };
+
+        // The first job is paid for; the remaining 1000 does not cover the second
+        worker.process(&job(), &json!({})).await.unwrap();
+        assert_eq!(ledger.balance_of("org-1"), 1_000);
+        let err = worker.process(&job(), &json!({})).await.unwrap_err();
+        assert!(matches!(err, WorkerError::InsufficientCredits(_)));
+
+        assert_eq!(client.sent_messages().len(), 1);
+        assert_eq!(ledger.balance_of("org-1"), 1_000);
+        assert_eq!(
+            logger.failure_reasons(),
+            vec![FailureReason {
+                category: FailureCategory::Billing,
+                code: "insufficient_credits".to_string()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_passes_formatting_to_client() {
+        let client = MockTelegramClient::new();
+        let worker = create_worker(client.clone());
+
+        let job = create_test_job(json!({
+            "chat_id": "123",
+            "message_template": "<b>Agent {{agent_id}}</b>",
+            "parse_mode": "HTML",
+            "inline_keyboard": [[{"text": "Ack", "callback_data": "ack:{{agent_id}}"}]]
+        }));
+
+        assert!(worker.process(&job, &json!({"agent_id": 42})).await.is_ok());
+
+        let messages = client.sent_messages();
+        assert_eq!(messages[0].text, "<b>Agent 42</b>");
+        assert_eq!(
+            messages[0].parse_mode,
+            Some(teloxide::types::ParseMode::Html)
+        );
+        let markup = serde_json::to_value(messages[0].reply_markup.as_ref().unwrap()).unwrap();
+        assert_eq!(markup["inline_keyboard"][0][0]["callback_data"], "ack:42");
+    }
+
+    #[tokio::test]
+    async fn test_process_invalid_markup_not_sent() {
+        let client = MockTelegramClient::new();
+        let worker = create_worker(client.clone());
+
+        let job = create_test_job(json!({
+            "chat_id": "123",
+            "message_template": "*unclosed",
+            "parse_mode": "MarkdownV2"
+        }));
+
+        let error = worker.process(&job, &json!({})).await.unwrap_err();
+        assert!(matches!(error, WorkerError::Template(_)));
+        assert_eq!(client.message_count(), 0);
+    }
+
     #[tokio::test]
     async fn test_template_rendering() {
         let client = MockTelegramClient::new();
@@ -0,0 +1,308 @@
+//! Application assembly
+//!
+//! [`AppState`] holds the services shared by every worker thread, and
+//! [`build_app`] wires them into the actix `App` with the full middleware
+//! chain, routes and OpenAPI docs. The server binary and the integration test
+//! harness build the app the same way, so tests exercise the production
+//! middleware order.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::{middleware::Logger, web, App};
+use anyhow::Context;
+use redis::aio::ConnectionManager;
+use shared::redis::cache::EntityCache;
+use shared::secrets::AppSecrets;
+use shared::{Config, DbPools, DlqAccessor, RateLimitAlgorithms, RateLimiter};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::middleware::auth_extractor::AuthExtractor;
+use crate::middleware::body_limit;
+use crate::middleware::idempotency::IdempotencyStore;
+use crate::middleware::metrics::{guarded_metrics_handler, MetricsAuth, PrometheusMetrics};
+use crate::middleware::query_tier::QueryTierExtractor;
+use crate::middleware::request_id::RequestId;
+use crate::middleware::security_headers::SecurityHeaders;
+use crate::middleware::unified_rate_limiter::UnifiedRateLimiter;
+use crate::middleware::FailurePolicy;
+use crate::openapi::ApiDoc;
+use crate::services::{
+    A2aTaskCancellations, ActionJobQueue, AuthRateLimiter, BalanceCache, EmailService,
+    EventIngestVerifier, KillSwitchStore, PasswordResetRateLimiter, SiweConfig, SocialAuthService,
+    WalletService, WebhookVerifier,
+};
+use crate::{middleware, routes};
+
+/// Services shared by every request handler
+#[derive(Clone)]
+pub struct AppState {
+    pub db_pools: DbPools,
+    pub config: Config,
+    pub app_secrets: Option<web::Data<AppSecrets>>,
+    pub entity_cache: EntityCache,
+    pub wallet_service: WalletService,
+    pub balance_cache: BalanceCache,
+    pub siwe_config: SiweConfig,
+    pub social_auth_service: SocialAuthService,
+    pub webhook_verifier: WebhookVerifier,
+    pub event_ingest_verifier: EventIngestVerifier,
+    pub metrics_auth: MetricsAuth,
+    pub email_service: EmailService,
+    pub idempotency_store: IdempotencyStore,
+    pub action_job_queue: ActionJobQueue,
+    pub dlq_accessor: DlqAccessor,
+    pub kill_switch_store: KillSwitchStore,
+    /// Tokens of A2A tasks being worked (shared with the task processor)
+    pub a2a_cancellations: A2aTaskCancellations,
+    /// Redis handle for the readiness probe (shares the rate limiter's connection)
+    pub health_redis: ConnectionManager,
+    pub code_exchange_rate_limiter: AuthRateLimiter,
+    pub password_reset_rate_limiter: PasswordResetRateLimiter,
+    pub rate_limiter: RateLimiter,
+}
+
+impl AppState {
+    /// Create the shared services
+    ///
+    /// Connects to Redis at `config.redis`; the database pools must already be
+    /// open and migrated. Service settings not in [`Config`] are read from the
+    /// environment, as at startup.
+    pub async fn new(
+        db_pools: DbPools,
+        config: Config,
+        app_secrets: Option<web::Data<AppSecrets>>,
+    ) -> anyhow::Result<Self> {
+        // Initialize WalletService with chain configs from environment (loaded once at startup)
+        // This creates a shared HTTP client with connection pooling for RPC calls
+        let chain_configs = WalletService::load_chain_configs_from_env();
+        let login_chain_ids = WalletService::load_login_chain_ids_from_env();
+        let wallet_service =
+            WalletService::new(chain_configs.clone()).with_login_chains(login_chain_ids.clone());
+        tracing::info!(
+            "WalletService initialized with {} chain configurations (SIWE chains: {:?})",
+            chain_configs.len(),
+            login_chain_ids
+        );
+
+        // Domain SIWE wallet login messages must be issued for
+        let siwe_config = SiweConfig::from_env();
+        tracing::info!("SIWE domain: {}", siwe_config.domain);
+
+        // Initialize SocialAuthService for OAuth login (Google, GitHub)
+        let social_auth_service = SocialAuthService::from_env();
+        tracing::info!(
+            "SocialAuthService initialized (Google: {}, GitHub: {}, frontend_url: {})",
+            social_auth_service.is_google_configured(),
+            social_auth_service.is_github_configured(),
+            social_auth_service.frontend_url()
+        );
+
+        // Initialize WebhookVerifier for REST action endpoint handshakes
+        let webhook_verifier = WebhookVerifier::from_env();
+        tracing::info!(
+            "WebhookVerifier initialized (mode: {:?})",
+            webhook_verifier.mode()
+        );
+
+        // Per-source secrets for events pushed to /api/v1/events/ingest
+        let event_ingest_verifier = EventIngestVerifier::from_env();
+        tracing::info!(
+            "EventIngestVerifier initialized (sources: {:?})",
+            event_ingest_verifier.sources()
+        );
+
+        let metrics_auth = MetricsAuth::from_env();
+        tracing::info!(
+            "Metrics endpoint {}",
+            if metrics_auth.is_enabled() {
+                "requires METRICS_AUTH_TOKEN"
+            } else {
+                "is open"
+            }
+        );
+
+        // Initialize EmailService for account emails (email verification, password reset)
+        let email_service = EmailService::from_env();
+        tracing::info!(
+            "EmailService initialized (delivery configured: {})",
+            email_service.is_configured()
+        );
+
+        // Initialize AuthRateLimiter for code exchange (stricter: 10 per minute per IP)
+        // This prevents brute-force attacks on OAuth authorization codes
+        let code_exchange_rate_limiter = AuthRateLimiter::with_rates(500, 10);
+        tracing::info!("Code exchange rate limiter initialized (10 req/min per IP)");
+
+        // Forgot-password requests send email: 5 per minute per IP
+        let password_reset_rate_limiter = PasswordResetRateLimiter::new();
+
+        // Initialize Redis client for rate limiting and caching
+        let redis_client = shared::redis::create_client(&config.redis.connection_url())
+            .await
+            .context("Failed to create Redis client")?;
+        tracing::info!("Redis client connected for rate limiting");
+
+        // Create EntityCache for caching frequently accessed data (membership checks, users, orgs)
+        // Uses a separate clone of the connection manager for isolation
+        let redis_client_for_cache = shared::redis::create_client(&config.redis.connection_url())
+            .await
+            .context("Failed to create Redis client for cache")?;
+        let entity_cache = EntityCache::new(redis_client_for_cache, Some(300)); // 5 min TTL
+        tracing::info!(
+            "Entity cache initialized (enabled: {}, TTL: {}s)",
+            entity_cache.is_enabled(),
+            entity_cache.ttl().as_secs()
+        );
+
+        // Create IdempotencyStore for replaying retried POSTs (Idempotency-Key header)
+        let idempotency_store = IdempotencyStore::from_env(redis_client.clone());
+        tracing::info!(
+            "Idempotency store initialized (TTL: {}s)",
+            idempotency_store.ttl().as_secs()
+        );
+
+        // Global kill-switch (engaged via /api/v1/admin/kill-switch, read by the rate limiter)
+        let kill_switch_store = KillSwitchStore::new(redis_client.clone());
+
+        // Create ActionJobQueue for manually fired triggers
+        let action_job_queue = ActionJobQueue::new(redis_client.clone());
+
+        // Dead letter queue inspection and replay (/api/v1/dlq)
+        let dlq_accessor = DlqAccessor::new(redis_client.clone());
+
+        // A2A tasks cancelled via /api/v1/a2a stop being worked by this instance's processor
+        let a2a_cancellations = A2aTaskCancellations::new();
+
+        // Short-lived cache for /api/v1/chains/{chain_id}/balances lookups
+        let balance_cache = BalanceCache::from_env(redis_client.clone());
+        tracing::info!(
+            "Balance cache initialized (TTL: {}s)",
+            balance_cache.ttl().as_secs()
+        );
+
+        let health_redis = redis_client.clone();
+
+        // Create RateLimiter instance (shared across all requests)
+        let rate_limit_algorithms = RateLimitAlgorithms::from_env();
+        // Only fall back to in-memory limits when failing open; a limiter that
+        // fails closed must see Redis errors to reject requests
+        let rate_limit_failure_policy = UnifiedRateLimiter::configured_failure_policy();
+        let rate_limiter = RateLimiter::with_config(
+            redis_client,
+            RateLimiter::DEFAULT_WINDOW,
+            rate_limit_failure_policy == FailurePolicy::Open,
+        )
+        .await
+        .context("Failed to create rate limiter")?
+        .with_algorithms(rate_limit_algorithms);
+        tracing::info!(
+            "Rate limiter initialized (mode: {}, failure policy: {}, algorithms: ip={}, org={}, agent={})",
+            std::env::var("RATE_LIMIT_MODE").unwrap_or_else(|_| "shadow".to_string()),
+            rate_limit_failure_policy,
+            rate_limit_algorithms.ip.as_str(),
+            rate_limit_algorithms.organization.as_str(),
+            rate_limit_algorithms.agent.as_str()
+        );
+
+        Ok(Self {
+            db_pools,
+            config,
+            app_secrets,
+            entity_cache,
+            wallet_service,
+            balance_cache,
+            siwe_config,
+            social_auth_service,
+            webhook_verifier,
+            event_ingest_verifier,
+            metrics_auth,
+            email_service,
+            idempotency_store,
+            action_job_queue,
+            dlq_accessor,
+            kill_switch_store,
+            a2a_cancellations,
+            health_redis,
+            code_exchange_rate_limiter,
+            password_reset_rate_limiter,
+            rate_limiter,
+        })
+    }
+}
+
+/// Build the application: middleware, shared state, routes and API docs
+pub fn build_app(
+    state: &AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        // Turn extractors' plain-text 413s into JSON errors
+        .wrap(body_limit::payload_too_large_json())
+        // Add Prometheus metrics middleware (should be early to capture all requests)
+        .wrap(PrometheusMetrics::new())
+        // Add request ID middleware (must be first for tracing)
+        .wrap(RequestId::new())
+        // Add security headers middleware
+        .wrap(SecurityHeaders::for_api())
+        // Add logger middleware
+        .wrap(Logger::default())
+        // Add CORS middleware
+        .wrap(middleware::cors())
+        // Add rate limiting middleware chain (order matters!)
+        // 1. UnifiedRateLimiter: Checks rate limits using AuthContext + QueryTier
+        .wrap(UnifiedRateLimiter::new(state.rate_limiter.clone()))
+        // 2. QueryTierExtractor: Extracts query tier from path/query params
+        .wrap(QueryTierExtractor::new())
+        // 3. AuthExtractor: Extracts auth context (IP, API key, or wallet signature)
+        .wrap(AuthExtractor::new())
+        // Configure JSON payload size limit (1MB); routes may override it
+        .app_data(web::JsonConfig::default().limit(body_limit::DEFAULT_BODY_LIMIT))
+        // Store database pool in app state
+        .app_data(web::Data::new(state.db_pools.primary().clone()))
+        // Read/write pool pair for handlers that can serve reads from the replica
+        .app_data(web::Data::new(state.db_pools.clone()))
+        .app_data(web::Data::new(state.config.clone()))
+        // Store EntityCache in app state for caching membership checks
+        .app_data(web::Data::new(state.entity_cache.clone()))
+        // Store WalletService in app state (shared across all requests)
+        .app_data(web::Data::new(state.wallet_service.clone()))
+        .app_data(web::Data::new(state.balance_cache.clone()))
+        .app_data(web::Data::new(state.siwe_config.clone()))
+        // Store SocialAuthService in app state (shared across all requests)
+        .app_data(web::Data::new(state.social_auth_service.clone()))
+        // Store WebhookVerifier in app state (used by action create/update/verify)
+        .app_data(web::Data::new(state.webhook_verifier.clone()))
+        .app_data(web::Data::new(state.event_ingest_verifier.clone()))
+        .app_data(web::Data::new(state.metrics_auth.clone()))
+        .app_data(web::Data::new(state.email_service.clone()))
+        // Store IdempotencyStore in app state (used by Idempotency-wrapped routes)
+        .app_data(web::Data::new(state.idempotency_store.clone()))
+        .app_data(web::Data::new(state.action_job_queue.clone()))
+        .app_data(web::Data::new(state.dlq_accessor.clone()))
+        .app_data(web::Data::new(state.kill_switch_store.clone()))
+        .app_data(web::Data::new(state.a2a_cancellations.clone()))
+        .app_data(web::Data::new(state.health_redis.clone()))
+        // Store CodeExchangeRateLimiter in app state (for /auth/exchange endpoint)
+        .app_data(web::Data::new(state.code_exchange_rate_limiter.clone()))
+        .app_data(web::Data::new(state.password_reset_rate_limiter.clone()))
+        // Store loaded secrets (optional features check what is configured)
+        .configure(|cfg| {
+            if let Some(app_secrets) = &state.app_secrets {
+                cfg.app_data(app_secrets.clone());
+            }
+        })
+        // Prometheus metrics endpoint (for scraping)
+        .route("/metrics", web::get().to(guarded_metrics_handler))
+        // Configure routes
+        .configure(routes::configure)
+        // OpenAPI documentation endpoints
+        .service(SwaggerUi::new("/api-docs/{_:.*}").url("/api/v1/openapi.json", ApiDoc::openapi()))
+}
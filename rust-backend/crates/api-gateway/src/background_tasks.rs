@@ -5,8 +5,12 @@
 //! - **Nonce Cleanup**: Removes expired wallet authentication nonces
 //! - **OAuth Token Cleanup**: Removes expired OAuth access and refresh tokens
 //! - **OAuth Temp Codes Cleanup**: Removes expired/used OAuth authorization codes
+//! - **Email Verification Token Cleanup**: Removes expired/used email verification tokens
+//! - **Password Reset Token Cleanup**: Removes expired/used password reset tokens
 //! - **Payment Nonce Cleanup**: Removes expired payment idempotency keys
 //! - **Auth Failures Cleanup**: Removes old authentication failure records (30 days retention)
+//! - **Deleted Trigger Purge**: Permanently removes triggers soft-deleted longer
+//!   than the retention window (30 days by default), once a day
 //! - **Session Cleanup**: (Future) Clean up expired sessions
 //! - **Rate Limit Cleanup**: (Future) Clean up old rate limit counters
 //!
@@ -28,6 +32,7 @@
 //! - `NONCE_CLEANUP_INTERVAL_SECS`: Interval between nonce cleanups (default: 3600 = 1 hour)
 //! - `OAUTH_TOKEN_CLEANUP_INTERVAL_SECS`: Interval between OAuth token cleanups (default: 3600 = 1 hour)
 //! - `AUTH_FAILURES_RETENTION_DAYS`: Days to retain auth failure records (default: 30)
+//! - `TRIGGER_PURGE_RETENTION_DAYS`: Days a deleted trigger stays restorable (default: 30)
 //!
 //! Rust guideline compliant 2025-12-02
 
@@ -40,7 +45,10 @@ use tracing::{debug, error, info};
 
 use crate::repositories::oauth::OAuthTokenRepository;
 use crate::repositories::wallet::NonceRepository;
-use crate::repositories::OAuthTempCodeRepository;
+use crate::repositories::{
+    EmailVerificationRepository, OAuthTempCodeRepository, PasswordResetRepository,
+    TriggerRepository,
+};
 
 /// Default interval for nonce cleanup (1 hour)
 const DEFAULT_NONCE_CLEANUP_INTERVAL_SECS: u64 = 3600;
@@ -57,6 +65,12 @@ const MIN_OAUTH_TOKEN_CLEANUP_INTERVAL_SECS: u64 = 300;
 /// Default retention for auth failures (30 days)
 const DEFAULT_AUTH_FAILURES_RETENTION_DAYS: i32 = 30;
 
+/// Interval for purging soft-deleted triggers (24 hours)
+const TRIGGER_PURGE_INTERVAL_SECS: u64 = 86400;
+
+/// Default time a deleted trigger stays restorable (30 days)
+const DEFAULT_TRIGGER_PURGE_RETENTION_DAYS: i32 = 30;
+
 /// Background task configuration
 #[derive(Debug, Clone)]
 pub struct BackgroundTaskConfig {
@@ -66,6 +80,10 @@ pub struct BackgroundTaskConfig {
     pub oauth_token_cleanup_interval: Duration,
     /// Retention period for auth failures (in days)
     pub auth_failures_retention_days: i32,
+    /// Interval between purges of soft-deleted triggers
+    pub trigger_purge_interval: Duration,
+    /// Days a soft-deleted trigger is kept before it is purged
+    pub trigger_purge_retention_days: i32,
 }
 
 impl Default for BackgroundTaskConfig {
@@ -88,10 +106,18 @@ impl Default for BackgroundTaskConfig {
             .unwrap_or(DEFAULT_AUTH_FAILURES_RETENTION_DAYS)
             .max(1); // At least 1 day retention
 
+        let trigger_purge_retention_days = env::var("TRIGGER_PURGE_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TRIGGER_PURGE_RETENTION_DAYS)
+            .max(1); // At least 1 day to restore
+
         Self {
             nonce_cleanup_interval: Duration::from_secs(nonce_interval_secs),
             oauth_token_cleanup_interval: Duration::from_secs(oauth_token_interval_secs),
             auth_failures_retention_days,
+            trigger_purge_interval: Duration::from_secs(TRIGGER_PURGE_INTERVAL_SECS),
+            trigger_purge_retention_days,
         }
     }
 }
@@ -183,10 +209,55 @@ impl BackgroundTaskRunner {
             .await;
         });
 
+        // Start email verification tokens cleanup task
+        let email_tokens_token = cancel_token.clone();
+        let email_tokens_pool = self.pool.clone();
+        let email_tokens_interval = self.config.nonce_cleanup_interval; // Same interval as nonces
+
+        tokio::spawn(async move {
+            run_email_verification_tokens_cleanup(
+                email_tokens_pool,
+                email_tokens_interval,
+                email_tokens_token,
+            )
+            .await;
+        });
+
+        // Start password reset tokens cleanup task
+        let reset_tokens_token = cancel_token.clone();
+        let reset_tokens_pool = self.pool.clone();
+        let reset_tokens_interval = self.config.nonce_cleanup_interval; // Same interval as nonces
+
+        tokio::spawn(async move {
+            run_password_reset_tokens_cleanup(
+                reset_tokens_pool,
+                reset_tokens_interval,
+                reset_tokens_token,
+            )
+            .await;
+        });
+
+        // Start deleted trigger purge task
+        let trigger_purge_token = cancel_token.clone();
+        let trigger_purge_pool = self.pool.clone();
+        let trigger_purge_interval = self.config.trigger_purge_interval;
+        let trigger_purge_retention_days = self.config.trigger_purge_retention_days;
+
+        tokio::spawn(async move {
+            run_trigger_purge(
+                trigger_purge_pool,
+                trigger_purge_interval,
+                trigger_purge_retention_days,
+                trigger_purge_token,
+            )
+            .await;
+        });
+
         info!(
             nonce_cleanup_interval_secs = ?self.config.nonce_cleanup_interval.as_secs(),
             oauth_token_cleanup_interval_secs = ?self.config.oauth_token_cleanup_interval.as_secs(),
             auth_failures_retention_days = self.config.auth_failures_retention_days,
+            trigger_purge_retention_days = self.config.trigger_purge_retention_days,
             "Background tasks started"
         );
 
@@ -449,6 +520,144 @@ async fn cleanup_expired_oauth_temp_codes(pool: &DbPool) {
     }
 }
 
+/// Run the email verification tokens cleanup task
+async fn run_email_verification_tokens_cleanup(
+    pool: DbPool,
+    cleanup_interval: Duration,
+    cancel_token: CancellationToken,
+) {
+    let mut interval = interval(cleanup_interval);
+
+    // Skip the first tick (which fires immediately)
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                info!("Email verification tokens cleanup task stopping due to shutdown");
+                break;
+            }
+            _ = interval.tick() => {
+                cleanup_expired_email_verification_tokens(&pool).await;
+            }
+        }
+    }
+}
+
+/// Perform the actual email verification tokens cleanup
+async fn cleanup_expired_email_verification_tokens(pool: &DbPool) {
+    debug!("Starting email verification tokens cleanup");
+
+    match EmailVerificationRepository::cleanup_expired(pool).await {
+        Ok(count) => {
+            if count > 0 {
+                info!(
+                    deleted_count = count,
+                    "Cleaned up email verification tokens"
+                );
+            } else {
+                debug!("No email verification tokens to clean up");
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to cleanup email verification tokens");
+        }
+    }
+}
+
+/// Run the password reset tokens cleanup task
+async fn run_password_reset_tokens_cleanup(
+    pool: DbPool,
+    cleanup_interval: Duration,
+    cancel_token: CancellationToken,
+) {
+    let mut interval = interval(cleanup_interval);
+
+    // Skip the first tick (which fires immediately)
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                info!("Password reset tokens cleanup task stopping due to shutdown");
+                break;
+            }
+            _ = interval.tick() => {
+                cleanup_expired_password_reset_tokens(&pool).await;
+            }
+        }
+    }
+}
+
+/// Perform the actual password reset tokens cleanup
+async fn cleanup_expired_password_reset_tokens(pool: &DbPool) {
+    debug!("Starting password reset tokens cleanup");
+
+    match PasswordResetRepository::cleanup_expired(pool).await {
+        Ok(count) => {
+            if count > 0 {
+                info!(deleted_count = count, "Cleaned up password reset tokens");
+            } else {
+                debug!("No password reset tokens to clean up");
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to cleanup password reset tokens");
+        }
+    }
+}
+
+/// Run the deleted trigger purge task
+async fn run_trigger_purge(
+    pool: DbPool,
+    purge_interval: Duration,
+    retention_days: i32,
+    cancel_token: CancellationToken,
+) {
+    let mut interval = interval(purge_interval);
+
+    // Skip the first tick (which fires immediately)
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                info!("Trigger purge task stopping due to shutdown");
+                break;
+            }
+            _ = interval.tick() => {
+                purge_deleted_triggers(&pool, retention_days).await;
+            }
+        }
+    }
+}
+
+/// Perform the actual purge of soft-deleted triggers
+async fn purge_deleted_triggers(pool: &DbPool, retention_days: i32) {
+    debug!("Starting deleted trigger purge");
+
+    match TriggerRepository::purge_deleted(pool, retention_days).await {
+        Ok(count) => {
+            if count > 0 {
+                info!(
+                    deleted_count = count,
+                    retention_days = retention_days,
+                    "Purged deleted triggers"
+                );
+            } else {
+                debug!("No deleted triggers to purge");
+            }
+        }
+        Err(e) => {
+            error!(
+                error = %e,
+                error_id = "TRIGGER_PURGE_FAILED",
+                "Failed to purge deleted triggers, will retry next interval"
+            );
+        }
+    }
+}
+
 /// Standalone function to run payment nonce cleanup once
 ///
 /// Useful for manual cleanup or testing.
@@ -506,6 +715,11 @@ mod tests {
             config.auth_failures_retention_days,
             DEFAULT_AUTH_FAILURES_RETENTION_DAYS
         );
+        assert_eq!(
+            config.trigger_purge_interval,
+            Duration::from_secs(TRIGGER_PURGE_INTERVAL_SECS)
+        );
+        assert!(config.trigger_purge_retention_days >= 1);
     }
 
     #[test]
@@ -7,18 +7,19 @@ use actix_web::web::Bytes;
 use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
 use futures_util::stream;
 use shared::DbPool;
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 use tracing::instrument;
 use uuid::Uuid;
 
 use crate::handlers::helpers::extract_user_id_or_unauthorized;
-use crate::middleware::get_verified_organization_id;
+use crate::middleware::{get_api_key_auth, get_verified_organization_id};
 use crate::models::a2a::{
-    JsonRpcError, JsonRpcRequest, JsonRpcResponse, TaskCancelParams, TaskCancelResult,
-    TaskGetParams, TaskGetResult, TaskSendParams, TaskSendResult, TaskStatus,
+    A2aTaskEvent, JsonRpcError, JsonRpcRequest, JsonRpcResponse, TaskCancelParams,
+    TaskCancelResult, TaskGetParams, TaskGetResult, TaskSendParams, TaskSendResult, TaskStatus,
 };
-use crate::repositories::{A2aTaskRepository, CreditRepository};
-use crate::services::{A2aAuditService, AuditActor, ToolRegistry};
+use crate::repositories::{A2aTaskEventRepository, A2aTaskRepository, CreditRepository};
+use crate::services::{A2aAuditService, A2aTaskCancellations, AuditActor, ToolRegistry};
 
 // ============================================================================
 // JSON-RPC Main Endpoint
@@ -43,9 +44,10 @@ use crate::services::{A2aAuditService, AuditActor, ToolRegistry};
         ("api_key" = [])
     )
 )]
-#[instrument(skip(pool, req), fields(method = %payload.method))]
+#[instrument(skip(pool, cancellations, req), fields(method = %payload.method))]
 pub async fn a2a_rpc(
     pool: web::Data<DbPool>,
+    cancellations: web::Data<A2aTaskCancellations>,
     req: HttpRequest,
     payload: web::Json<JsonRpcRequest>,
 ) -> impl Responder {
@@ -86,7 +88,18 @@ pub async fn a2a_rpc(
     match request.method.as_str() {
         "tasks/send" => handle_tasks_send(&pool, &org_id, &request.params, request_id).await,
         "tasks/get" => handle_tasks_get(&pool, &org_id, &request.params, request_id).await,
-        "tasks/cancel" => handle_tasks_cancel(&pool, &org_id, &request.params, request_id).await,
+        "tasks/cancel" => {
+            let actor = audit_actor(&req, &user_id);
+            handle_tasks_cancel(
+                &pool,
+                &cancellations,
+                &org_id,
+                actor,
+                &request.params,
+                request_id,
+            )
+            .await
+        }
         _ => HttpResponse::Ok().json(JsonRpcResponse::<()>::error(
             JsonRpcError::method_not_found(),
             request_id,
@@ -110,6 +123,9 @@ const MAX_SSE_STREAM_DURATION_SECS: u64 = 300;
 /// SSE poll interval
 const SSE_POLL_INTERVAL_MS: u64 = 2000;
 
+/// Maximum task events sent per SSE poll
+const SSE_EVENT_BATCH_SIZE: i64 = 100;
+
 /// Handle tasks/send method
 async fn handle_tasks_send(
     pool: &DbPool,
@@ -338,7 +354,9 @@ async fn handle_tasks_get(
 /// Handle tasks/cancel method
 async fn handle_tasks_cancel(
     pool: &DbPool,
+    cancellations: &A2aTaskCancellations,
     org_id: &str,
+    actor: AuditActor,
     params: &serde_json::Value,
     request_id: serde_json::Value,
 ) -> HttpResponse {
@@ -365,27 +383,18 @@ async fn handle_tasks_cancel(
     };
 
     // Cancel task
-    match A2aTaskRepository::cancel_task(pool, &task_id, org_id).await {
-        Ok(Some(task)) => {
-            // AUDIT: Log task cancellation
-            if let Err(e) = A2aAuditService::log_cancelled(
-                pool,
-                &task.id,
-                org_id,
-                AuditActor::ApiKey(org_id.to_string()),
-            )
-            .await
-            {
-                tracing::warn!("Failed to log task cancellation audit: {:?}", e);
-            }
-
+    match cancel_org_task(pool, cancellations, &task_id, org_id, actor).await {
+        Ok(CancelOutcome::Cancelled(task_id)) => {
             let result = TaskCancelResult {
-                task_id: task.id.to_string(),
+                task_id: task_id.to_string(),
                 status: TaskStatus::Cancelled,
             };
             HttpResponse::Ok().json(JsonRpcResponse::success(result, request_id))
         }
-        Ok(None) => HttpResponse::Ok().json(JsonRpcResponse::<()>::error(
+        Ok(CancelOutcome::AlreadyFinished(status)) => HttpResponse::Ok().json(
+            JsonRpcResponse::<()>::error(JsonRpcError::task_not_cancelable(&status), request_id),
+        ),
+        Ok(CancelOutcome::NotFound) => HttpResponse::Ok().json(JsonRpcResponse::<()>::error(
             JsonRpcError::task_not_found(),
             request_id,
         )),
@@ -399,6 +408,60 @@ async fn handle_tasks_cancel(
     }
 }
 
+/// Result of a cancellation request
+enum CancelOutcome {
+    /// The task was submitted or working and is now cancelled
+    Cancelled(Uuid),
+    /// The task had already finished (with this status)
+    AlreadyFinished(String),
+    /// No such task in the organization
+    NotFound,
+}
+
+/// Cancel a submitted or working task and stop the processor working it
+async fn cancel_org_task(
+    pool: &DbPool,
+    cancellations: &A2aTaskCancellations,
+    task_id: &Uuid,
+    org_id: &str,
+    actor: AuditActor,
+) -> anyhow::Result<CancelOutcome> {
+    let Some(task) = A2aTaskRepository::cancel_task(pool, task_id, org_id).await? else {
+        // Either finished already or not visible to the organization
+        return Ok(
+            match A2aTaskRepository::find_by_id_and_org(pool, task_id, org_id).await? {
+                Some(task) => CancelOutcome::AlreadyFinished(task.status),
+                None => CancelOutcome::NotFound,
+            },
+        );
+    };
+
+    // Submitted tasks are never claimed now; working ones stop here if this
+    // instance runs them (other instances discard their result)
+    let stopped = cancellations.cancel(&task.id);
+    tracing::info!(task_id = %task.id, stopped_execution = stopped, "A2A task cancelled");
+
+    // AUDIT: Log task cancellation
+    if let Err(e) = A2aAuditService::log_cancelled(pool, &task.id, org_id, actor).await {
+        tracing::warn!("Failed to log task cancellation audit: {:?}", e);
+    }
+
+    // EVENTS: End the task's streams
+    if let Err(e) = A2aTaskEventRepository::append(pool, &task.id, &task.to_get_result()).await {
+        tracing::warn!("Failed to record task cancellation event: {:?}", e);
+    }
+
+    Ok(CancelOutcome::Cancelled(task.id))
+}
+
+/// Audit actor of a request: the API key if one authenticated it, else the user
+fn audit_actor(req: &HttpRequest, user_id: &str) -> AuditActor {
+    match get_api_key_auth(req) {
+        Some(auth) => AuditActor::ApiKey(auth.api_key.prefix),
+        None => AuditActor::User(user_id.to_string()),
+    }
+}
+
 // ============================================================================
 // REST Endpoints (for convenience)
 // ============================================================================
@@ -473,6 +536,87 @@ pub async fn get_task_status(
     }
 }
 
+/// Cancel a task (REST endpoint)
+///
+/// Submitted and working tasks are cancelled; tasks that already finished
+/// return 409.
+#[utoipa::path(
+    post,
+    path = "/api/v1/a2a/tasks/{id}/cancel",
+    tag = "A2A Protocol",
+    params(
+        ("id" = String, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Task cancelled", body = TaskCancelResult),
+        (status = 400, description = "Invalid task ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Task not found"),
+        (status = 409, description = "Task already finished")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    )
+)]
+#[instrument(skip(pool, cancellations, req))]
+pub async fn cancel_task(
+    pool: web::Data<DbPool>,
+    cancellations: web::Data<A2aTaskCancellations>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let task_id_str = path.into_inner();
+
+    // Extract user ID from JWT
+    let user_id = match extract_user_id_or_unauthorized(&req) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Extract and verify organization ID (TEXT in DB)
+    let org_id = match get_verified_organization_id(&req, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Parse task ID
+    let task_id = match Uuid::parse_str(&task_id_str) {
+        Ok(id) => id,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "invalid_request",
+                "message": "Invalid task ID format"
+            }));
+        }
+    };
+
+    let actor = audit_actor(&req, &user_id);
+    match cancel_org_task(&pool, &cancellations, &task_id, &org_id, actor).await {
+        Ok(CancelOutcome::Cancelled(task_id)) => HttpResponse::Ok().json(TaskCancelResult {
+            task_id: task_id.to_string(),
+            status: TaskStatus::Cancelled,
+        }),
+        Ok(CancelOutcome::AlreadyFinished(status)) => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": "conflict",
+                "message": format!("Task is already {}", status)
+            }))
+        }
+        Ok(CancelOutcome::NotFound) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "not_found",
+            "message": "Task not found"
+        })),
+        Err(e) => {
+            tracing::error!("Failed to cancel task: {:?}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "internal_error",
+                "message": "Failed to cancel task"
+            }))
+        }
+    }
+}
+
 // ============================================================================
 // SSE Streaming Endpoint
 // ============================================================================
@@ -481,12 +625,16 @@ pub async fn get_task_status(
 ///
 /// Returns SSE stream with task progress updates.
 /// Events: progress, complete, error
+///
+/// Each event carries an `id`. A client reconnecting with `Last-Event-ID`
+/// is sent the events after that one, then live updates.
 #[utoipa::path(
     get,
     path = "/api/v1/a2a/tasks/{id}/stream",
     tag = "A2A Protocol",
     params(
-        ("id" = String, Path, description = "Task ID")
+        ("id" = String, Path, description = "Task ID"),
+        ("Last-Event-ID" = Option<i64>, Header, description = "ID of the last event received, to resume after it")
     ),
     responses(
         (status = 200, description = "SSE stream of task updates"),
@@ -548,85 +696,171 @@ pub async fn stream_task_progress(
 
     // Create SSE stream with timeout protection
     // SECURITY FIX: Added max stream duration to prevent memory leaks from long-running connections
-    let pool_clone = pool.get_ref().clone();
-    let stream_start = Instant::now();
     let max_duration = Duration::from_secs(MAX_SSE_STREAM_DURATION_SECS);
     let poll_interval = Duration::from_millis(SSE_POLL_INTERVAL_MS);
+    let state = SseState {
+        pool: pool.get_ref().clone(),
+        task_id,
+        org_id,
+        last_event_id: last_event_id(&req),
+        pending: VecDeque::new(),
+        idle: false,
+        done: false,
+        start: Instant::now(),
+    };
 
-    let sse_stream = stream::unfold(
-        (pool_clone, task_id, org_id, false, stream_start),
-        move |(pool, task_id, org_id, done, start)| async move {
-            if done {
+    let sse_stream = stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(bytes) = state.pending.pop_front() {
+                return Some((Ok::<_, actix_web::error::Error>(bytes), state));
+            }
+            if state.done {
                 return None;
             }
 
             // SECURITY: Check if stream has exceeded max duration
-            if start.elapsed() > max_duration {
+            if state.start.elapsed() > max_duration {
                 tracing::info!(
-                    task_id = %task_id,
-                    duration_secs = start.elapsed().as_secs(),
+                    task_id = %state.task_id,
+                    duration_secs = state.start.elapsed().as_secs(),
                     "SSE stream timeout - closing connection"
                 );
-                let sse_event = format!(
+                state.pending.push_back(Bytes::from(format!(
                     "event: timeout\ndata: {{\"error\":\"stream_timeout\",\"duration_secs\":{}}}\n\n",
-                    start.elapsed().as_secs()
-                );
-                return Some((
-                    Ok(Bytes::from(sse_event)),
-                    (pool, task_id, org_id, true, start),
-                ));
+                    state.start.elapsed().as_secs()
+                )));
+                state.done = true;
+                continue;
             }
 
-            // Wait between polls (increased from 500ms to 2000ms to reduce load)
-            tokio::time::sleep(poll_interval).await;
+            // Wait between polls that found nothing new (2000ms to reduce load)
+            if state.idle {
+                tokio::time::sleep(poll_interval).await;
+                state.idle = false;
+            }
 
-            // Fetch current task status
-            match A2aTaskRepository::find_by_id_and_org(&pool, &task_id, &org_id).await {
-                Ok(Some(task)) => {
-                    let result = task.to_get_result();
-                    let is_terminal = matches!(
-                        result.status,
-                        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
-                    );
+            state.poll().await;
+        }
+    });
+
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .insert_header((header::CONNECTION, "keep-alive"))
+        .streaming(sse_stream)
+}
 
-                    let event_type = match result.status {
-                        TaskStatus::Completed => "complete",
-                        TaskStatus::Failed | TaskStatus::Cancelled => "error",
-                        _ => "progress",
-                    };
+/// Event ID a reconnecting client last received (0 if none or invalid)
+fn last_event_id(req: &HttpRequest) -> i64 {
+    req.headers()
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|id| *id >= 0)
+        .unwrap_or(0)
+}
 
-                    let event_data = serde_json::to_string(&result).unwrap_or_default();
-                    let sse_event = format!("event: {}\ndata: {}\n\n", event_type, event_data);
+/// State of a task's SSE stream between polls
+struct SseState {
+    pool: DbPool,
+    task_id: Uuid,
+    org_id: String,
+    /// ID of the last event sent (or Last-Event-ID on reconnect)
+    last_event_id: i64,
+    /// Encoded events not yet sent
+    pending: VecDeque<Bytes>,
+    /// The last poll found nothing new
+    idle: bool,
+    /// No polls after the pending events
+    done: bool,
+    start: Instant,
+}
 
-                    Some((
-                        Ok::<_, actix_web::error::Error>(Bytes::from(sse_event)),
-                        (pool, task_id, org_id, is_terminal, start),
-                    ))
-                }
-                Ok(None) => {
-                    let sse_event = "event: error\ndata: {\"error\":\"task_not_found\"}\n\n";
-                    Some((
-                        Ok(Bytes::from(sse_event)),
-                        (pool, task_id, org_id, true, start),
-                    ))
-                }
+impl SseState {
+    /// Queue the task's events after `last_event_id`
+    ///
+    /// With nothing new, ends the stream if the task has finished.
+    async fn poll(&mut self) {
+        match A2aTaskEventRepository::list_after(
+            &self.pool,
+            &self.task_id,
+            self.last_event_id,
+            SSE_EVENT_BATCH_SIZE,
+        )
+        .await
+        {
+            Ok(events) if !events.is_empty() => return self.queue(events),
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Failed to fetch task events for SSE: {:?}", e);
+                return self.fail("internal_error");
+            }
+        }
+
+        let status =
+            match A2aTaskRepository::find_by_id_and_org(&self.pool, &self.task_id, &self.org_id)
+                .await
+            {
+                Ok(Some(task)) => task.to_get_result(),
+                Ok(None) => return self.fail("task_not_found"),
                 Err(e) => {
                     tracing::error!("Failed to fetch task for SSE: {:?}", e);
-                    let sse_event = "event: error\ndata: {\"error\":\"internal_error\"}\n\n";
-                    Some((
-                        Ok(Bytes::from(sse_event)),
-                        (pool, task_id, org_id, true, start),
-                    ))
+                    return self.fail("internal_error");
                 }
+            };
+        if !status.status.is_terminal() {
+            self.idle = true;
+            return;
+        }
+
+        // The final event commits with the status; look again in case it did
+        // so after the first query
+        match A2aTaskEventRepository::list_after(
+            &self.pool,
+            &self.task_id,
+            self.last_event_id,
+            SSE_EVENT_BATCH_SIZE,
+        )
+        .await
+        {
+            Ok(events) if !events.is_empty() => self.queue(events),
+            Ok(_) => {
+                // Finished without a final event (e.g. before events were recorded)
+                let event_data = serde_json::to_string(&status).unwrap_or_default();
+                self.pending.push_back(Bytes::from(format!(
+                    "event: {}\ndata: {}\n\n",
+                    status.status.event_type(),
+                    event_data
+                )));
+                self.done = true;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch task events for SSE: {:?}", e);
+                self.fail("internal_error");
             }
-        },
-    );
+        }
+    }
 
-    HttpResponse::Ok()
-        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
-        .insert_header((header::CACHE_CONTROL, "no-cache"))
-        .insert_header((header::CONNECTION, "keep-alive"))
-        .streaming(sse_stream)
+    /// Queue events up to and including the first terminal one
+    fn queue(&mut self, events: Vec<A2aTaskEvent>) {
+        for event in events {
+            self.last_event_id = event.id;
+            self.pending.push_back(Bytes::from(event.to_sse()));
+            if event.is_terminal() {
+                self.done = true;
+                break;
+            }
+        }
+    }
+
+    /// Queue an error event and end the stream
+    fn fail(&mut self, error: &str) {
+        self.pending.push_back(Bytes::from(format!(
+            "event: error\ndata: {{\"error\":\"{}\"}}\n\n",
+            error
+        )));
+        self.done = true;
+    }
 }
 
 // ============================================================================
@@ -695,4 +929,22 @@ mod tests {
         // Unknown tool returns 0
         assert_eq!(ToolRegistry::get_cost_micro_usdc("unknownTool"), 0);
     }
+
+    #[test]
+    fn test_last_event_id() {
+        let req = actix_web::test::TestRequest::default().to_http_request();
+        assert_eq!(last_event_id(&req), 0);
+
+        let req = actix_web::test::TestRequest::default()
+            .insert_header(("Last-Event-ID", "42"))
+            .to_http_request();
+        assert_eq!(last_event_id(&req), 42);
+
+        for invalid in ["abc", "-1", ""] {
+            let req = actix_web::test::TestRequest::default()
+                .insert_header(("Last-Event-ID", invalid))
+                .to_http_request();
+            assert_eq!(last_event_id(&req), 0, "{:?}", invalid);
+        }
+    }
 }
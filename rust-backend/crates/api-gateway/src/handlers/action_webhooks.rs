@@ -0,0 +1,188 @@
+//! Action Result Webhook Handlers
+//!
+//! Lets an organization register an endpoint that is notified after each of
+//! its actions executes, so customers can observe successes and failures
+//! without polling. The action workers deliver the summaries; see
+//! `action-workers/src/result_webhook.rs` for the payload and signature.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/organizations/{id}/action-webhook` - Get the webhook (any member)
+//! - `PUT /api/v1/organizations/{id}/action-webhook` - Create or update (admin+)
+//! - `DELETE /api/v1/organizations/{id}/action-webhook` - Remove (admin+)
+//!
+//! The signing secret is only returned when it is created or rotated.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::DbPool;
+
+use crate::{
+    handlers::helpers::{
+        extract_user_id_or_unauthorized, forbidden, handle_db_error, require_found,
+        validate_request,
+    },
+    middleware::get_verified_organization,
+    models::{
+        can_manage_org, ActionResultWebhookResponse, ErrorResponse, SetActionResultWebhookRequest,
+        SuccessResponse,
+    },
+    repositories::ActionWebhookRepository,
+};
+
+/// Get the organization's action result webhook
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{id}/action-webhook",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Action result webhook", body = SuccessResponse<ActionResultWebhookResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Organization or webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_action_webhook(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.id,
+        Err(resp) => return resp,
+    };
+
+    let webhook = match handle_db_error(
+        ActionWebhookRepository::find_by_organization(&pool, &org_id).await,
+        "fetch action webhook",
+    )
+    .and_then(|webhook| require_found(webhook, "Action webhook"))
+    {
+        Ok(webhook) => webhook,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(ActionResultWebhookResponse::new(
+        webhook, false,
+    )))
+}
+
+/// Create or update the organization's action result webhook
+///
+/// The response includes the signing secret when the webhook is created or
+/// `rotate_secret` is set. Store it: it is not shown again.
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{id}/action-webhook",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    request_body = SetActionResultWebhookRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Action result webhook saved", body = SuccessResponse<ActionResultWebhookResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_action_webhook(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    req: web::Json<SetActionResultWebhookRequest>,
+) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    let (org_id, role) =
+        match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+            Ok(org) => (org.id, org.role),
+            Err(resp) => return resp,
+        };
+    if !can_manage_org(&role) {
+        return forbidden("Insufficient permissions to manage the action webhook");
+    }
+
+    let (webhook, secret_is_new) = match handle_db_error(
+        ActionWebhookRepository::upsert(
+            &pool,
+            &org_id,
+            &req.url,
+            req.enabled.unwrap_or(true),
+            req.rotate_secret,
+        )
+        .await,
+        "save action webhook",
+    ) {
+        Ok(saved) => saved,
+        Err(resp) => return resp,
+    };
+
+    tracing::info!(
+        organization_id = %org_id,
+        enabled = webhook.enabled,
+        secret_rotated = secret_is_new,
+        "Action result webhook saved"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(ActionResultWebhookResponse::new(
+        webhook,
+        secret_is_new,
+    )))
+}
+
+/// Remove the organization's action result webhook
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/{id}/action-webhook",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Action result webhook removed"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization or webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_action_webhook(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let (org_id, role) =
+        match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+            Ok(org) => (org.id, org.role),
+            Err(resp) => return resp,
+        };
+    if !can_manage_org(&role) {
+        return forbidden("Insufficient permissions to manage the action webhook");
+    }
+
+    match handle_db_error(
+        ActionWebhookRepository::delete(&pool, &org_id).await,
+        "delete action webhook",
+    ) {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound()
+            .json(ErrorResponse::new("not_found", "Action webhook not found")),
+        Err(resp) => resp,
+    }
+}
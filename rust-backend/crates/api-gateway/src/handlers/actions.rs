@@ -1,20 +1,43 @@
 //! Trigger action handlers
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use shared::DbPool;
+use chrono::{DateTime, Duration, Utc};
+use shared::{DbPool, DbPools};
 
 use crate::{
     handlers::helpers::{
-        extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
+        bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
     },
     middleware::{get_verified_organization_id, get_verified_organization_id_with_role},
     models::{
-        can_write, ActionResponse, CreateActionRequest, ErrorResponse, SuccessResponse,
-        UpdateActionRequest,
+        can_write, ActionResponse, ActionResultSummaryQuery, ActionResultSummaryResponse,
+        CreateActionRequest, ErrorResponse, SuccessResponse, UpdateActionRequest,
+        VerificationStatus,
     },
     repositories::{ActionRepository, TriggerRepository},
+    services::{VerificationOutcome, WebhookVerifier},
 };
 
+/// Run the webhook handshake for a REST action when verification is enabled
+///
+/// Returns the verification state to persist. In `enforce` mode a failed
+/// handshake is returned as a 400 response and the action must not be saved.
+pub(crate) async fn run_verification(
+    verifier: &WebhookVerifier,
+    action_type: &str,
+    config: &serde_json::Value,
+) -> Result<(VerificationStatus, Option<DateTime<Utc>>), HttpResponse> {
+    verifier
+        .status_for_action(action_type, config)
+        .await
+        .map_err(|reason| {
+            HttpResponse::BadRequest().json(ErrorResponse::new(
+                "webhook_verification_failed",
+                format!("Webhook verification failed: {}", reason),
+            ))
+        })
+}
+
 /// Create a new action for a trigger
 ///
 /// Creates a new action to execute when the trigger matches. Requires write permission.
@@ -29,7 +52,7 @@ use crate::{
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 201, description = "Action created", body = SuccessResponse<ActionResponse>),
-        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 400, description = "Validation error or webhook verification failed", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Insufficient permissions", body = ErrorResponse),
         (status = 404, description = "Trigger not found", body = ErrorResponse)
@@ -37,6 +60,7 @@ use crate::{
 )]
 pub async fn create_action(
     pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
     req_http: HttpRequest,
     path: web::Path<String>,
     req: web::Json<CreateActionRequest>,
@@ -79,6 +103,13 @@ pub async fn create_action(
         return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Trigger not found"));
     }
 
+    // Verify the webhook endpoint before storing REST actions
+    let (verification_status, verified_at) =
+        match run_verification(&verifier, &req.action_type, &req.config).await {
+            Ok(result) => result,
+            Err(resp) => return resp,
+        };
+
     // Create action
     let action = match handle_db_error(
         ActionRepository::create(
@@ -87,6 +118,8 @@ pub async fn create_action(
             &req.action_type,
             req.priority.unwrap_or(0),
             &req.config,
+            verification_status,
+            verified_at,
         )
         .await,
         "create action",
@@ -177,7 +210,7 @@ pub async fn list_actions(
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 200, description = "Action updated", body = SuccessResponse<ActionResponse>),
-        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 400, description = "Validation error or webhook verification failed", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Insufficient permissions", body = ErrorResponse),
         (status = 404, description = "Trigger or action not found", body = ErrorResponse)
@@ -185,6 +218,7 @@ pub async fn list_actions(
 )]
 pub async fn update_action(
     pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
     req_http: HttpRequest,
     path: web::Path<(String, i32)>,
     req: web::Json<UpdateActionRequest>,
@@ -244,8 +278,32 @@ pub async fn update_action(
         return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Action not found"));
     }
 
+    // Changing the type or target invalidates any previous verification
+    let verification = if req.action_type.is_some() || req.config.is_some() {
+        let existing = match handle_db_error(
+            ActionRepository::find_by_id(&pool, action_id).await,
+            "get action",
+        ) {
+            Ok(Some(action)) => action,
+            Ok(None) => {
+                return HttpResponse::NotFound()
+                    .json(ErrorResponse::new("not_found", "Action not found"));
+            }
+            Err(resp) => return resp,
+        };
+
+        let action_type = req.action_type.as_deref().unwrap_or(&existing.action_type);
+        let config = req.config.as_ref().unwrap_or(&existing.config);
+        match run_verification(&verifier, action_type, config).await {
+            Ok(result) => Some(result),
+            Err(resp) => return resp,
+        }
+    } else {
+        None
+    };
+
     // Update action
-    let action = match handle_db_error(
+    let mut action = match handle_db_error(
         ActionRepository::update(
             &pool,
             action_id,
@@ -260,6 +318,17 @@ pub async fn update_action(
         Err(resp) => return resp,
     };
 
+    if let Some((verification_status, verified_at)) = verification {
+        action = match handle_db_error(
+            ActionRepository::set_verification(&pool, action_id, verification_status, verified_at)
+                .await,
+            "update action verification",
+        ) {
+            Ok(action) => action,
+            Err(resp) => return resp,
+        };
+    }
+
     let response = ActionResponse::from(action);
     HttpResponse::Ok().json(SuccessResponse::new(response))
 }
@@ -353,3 +422,189 @@ pub async fn delete_action(
 
     HttpResponse::NoContent().finish()
 }
+
+/// Verify an action's webhook endpoint
+///
+/// Runs the verification handshake on demand (regardless of the configured
+/// mode) and records the result. Only REST actions can be verified.
+/// Requires write permission.
+#[utoipa::path(
+    post,
+    path = "/api/v1/triggers/{trigger_id}/actions/{id}/verify",
+    tag = "Actions",
+    params(
+        ("trigger_id" = String, Path, description = "Trigger ID"),
+        ("id" = i32, Path, description = "Action ID")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Handshake completed; see verification_status", body = SuccessResponse<ActionResponse>),
+        (status = 400, description = "Action is not a REST action", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Trigger or action not found", body = ErrorResponse)
+    )
+)]
+pub async fn verify_action(
+    pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
+    req_http: HttpRequest,
+    path: web::Path<(String, i32)>,
+) -> impl Responder {
+    let (trigger_id, action_id) = path.into_inner();
+
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Get and verify organization_id from header (also gets role)
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    // Check user has write access
+    if !can_write(&role) {
+        return forbidden("Insufficient permissions to verify actions");
+    }
+
+    // Check if trigger belongs to the organization
+    let belongs = match handle_db_error(
+        TriggerRepository::belongs_to_organization(&pool, &trigger_id, &organization_id).await,
+        "check trigger organization",
+    ) {
+        Ok(belongs) => belongs,
+        Err(resp) => return resp,
+    };
+
+    if !belongs {
+        return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Trigger not found"));
+    }
+
+    let action = match handle_db_error(
+        ActionRepository::find_by_id(&pool, action_id).await,
+        "get action",
+    ) {
+        Ok(Some(action)) if action.trigger_id == trigger_id => action,
+        Ok(_) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Action not found"));
+        }
+        Err(resp) => return resp,
+    };
+
+    if action.action_type != "rest" {
+        return bad_request("Only REST actions require webhook verification");
+    }
+
+    let (verification_status, verified_at) =
+        match verifier.verify_action_config(&action.config).await {
+            VerificationOutcome::Verified => (VerificationStatus::Verified, Some(Utc::now())),
+            VerificationOutcome::Failed(reason) => {
+                tracing::info!(
+                    action_id = action_id,
+                    reason = %reason,
+                    "Webhook verification failed"
+                );
+                (VerificationStatus::Failed, None)
+            }
+        };
+
+    let action = match handle_db_error(
+        ActionRepository::set_verification(&pool, action_id, verification_status, verified_at)
+            .await,
+        "update action verification",
+    ) {
+        Ok(action) => action,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(ActionResponse::from(action)))
+}
+
+/// Summarize an action's results
+///
+/// Counts the action's executions over the last `days` days and groups its
+/// failures by category and code, so the most common reason an action fails
+/// is visible without reading individual results.
+#[utoipa::path(
+    get,
+    path = "/api/v1/actions/{id}/results/summary",
+    tag = "Actions",
+    params(
+        ("id" = i32, Path, description = "Action ID"),
+        ActionResultSummaryQuery
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Result summary", body = SuccessResponse<ActionResultSummaryResponse>),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Action not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_action_result_summary(
+    pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
+    req_http: HttpRequest,
+    path: web::Path<i32>,
+    query: web::Query<ActionResultSummaryQuery>,
+) -> impl Responder {
+    let action_id = path.into_inner();
+
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Get and verify organization_id from header (any role can view)
+    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    if let Err(e) = query.validate() {
+        return bad_request(&e);
+    }
+
+    // Actions of other organizations' triggers are reported as missing
+    let trigger_id = match handle_db_error(
+        ActionRepository::get_trigger_id(&pool, action_id).await,
+        "get action trigger_id",
+    ) {
+        Ok(Some(id)) => id,
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Action not found"));
+        }
+        Err(resp) => return resp,
+    };
+    match handle_db_error(
+        TriggerRepository::belongs_to_organization(&pool, &trigger_id, &organization_id).await,
+        "check trigger organization",
+    ) {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Action not found"));
+        }
+        Err(resp) => return resp,
+    }
+
+    let since = Utc::now() - Duration::days(query.days);
+    let counts = match handle_db_error(
+        ActionRepository::count_results(pools.read(), action_id, since).await,
+        "count action results",
+    ) {
+        Ok(counts) => counts,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(
+        ActionResultSummaryResponse::from_counts(action_id, since, counts),
+    ))
+}
@@ -11,26 +11,31 @@
 //! - `DELETE /api/v1/agents/{agent_id}/follow` - Stop following an agent
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 use shared::DbPool;
 use tracing::info;
 
 use crate::{
+    handlers::actions::run_verification,
     handlers::helpers::{
-        extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
+        bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
     },
     middleware::{get_verified_organization_id, get_verified_organization_id_with_role},
     models::{
         agent_follows::{
             redact_secrets, AgentFollowDetailResponse, AgentFollowPath, AgentFollowResponse,
             ChainIdQuery, FollowActionRequest, FollowActionSummary, FollowAgentRequest,
-            ListFollowsQuery, TriggerIds, UpdateFollowRequest,
+            FollowedAgentResponse, FollowedAgentsResponse, ListFollowsQuery, TriggerIds,
+            UpdateFollowRequest,
         },
-        can_write, ErrorResponse, PaginatedResponse, PaginationMeta, PaginationParams,
-        SuccessResponse,
+        can_write, ErrorResponse, PaginationMeta, PaginationParams, SuccessResponse,
+        VerificationStatus,
     },
     repositories::{
-        ActionRepository, AgentFollowRepository, ConditionRepository, TriggerRepository,
+        ActionRepository, AgentFollowRepository, AgentLinkRepository, ConditionRepository,
+        FollowCursor, TriggerRepository,
     },
+    services::WebhookVerifier,
 };
 
 const MAX_FOLLOWS_PER_ORG: i64 = 100;
@@ -57,7 +62,7 @@ const REGISTRIES: [&str; 3] = ["identity", "reputation", "validation"];
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 201, description = "Agent followed", body = SuccessResponse<AgentFollowDetailResponse>),
-        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 400, description = "Validation error or webhook verification failed", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Insufficient permissions", body = ErrorResponse),
         (status = 409, description = "Already following this agent", body = ErrorResponse),
@@ -66,6 +71,7 @@ const REGISTRIES: [&str; 3] = ["identity", "reputation", "validation"];
 )]
 pub async fn follow_agent(
     pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
     req_http: HttpRequest,
     path: web::Path<AgentFollowPath>,
     req: web::Json<FollowAgentRequest>,
@@ -131,6 +137,12 @@ pub async fn follow_agent(
         ));
     }
 
+    // Verify REST endpoints before anything is stored
+    let verifications = match verify_follow_actions(&verifier, &req.actions).await {
+        Ok(verifications) => verifications,
+        Err(resp) => return resp,
+    };
+
     // Create 3 triggers (one per registry) in a transaction
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
@@ -163,6 +175,8 @@ pub async fn follow_agent(
             registry,
             true,  // enabled
             false, // not stateful
+            0,     // no cooldown
+            None,  // no schedule
         )
         .await
         {
@@ -181,9 +195,9 @@ pub async fn follow_agent(
         if let Err(e) = ConditionRepository::create_in_tx(
             &mut *tx,
             &trigger.id,
-            "field_match",
+            "agent_id_equals",
             "agent_id",
-            "equals",
+            "=",
             &agent_id.to_string(),
             None,
         )
@@ -198,13 +212,17 @@ pub async fn follow_agent(
         }
 
         // Create actions
-        for (idx, action) in req.actions.iter().enumerate() {
+        for (idx, (action, (verification_status, verified_at))) in
+            req.actions.iter().zip(&verifications).enumerate()
+        {
             if let Err(e) = ActionRepository::create_in_tx(
                 &mut *tx,
                 &trigger.id,
                 &action.action_type,
                 idx as i32,
                 &action.config,
+                *verification_status,
+                *verified_at,
             )
             .await
             {
@@ -276,6 +294,9 @@ pub async fn follow_agent(
 /// List followed agents
 ///
 /// GET /api/v1/agents/following
+///
+/// Newest follows first, each with the followed agent's link. Pages either
+/// skip `offset` follows or continue from a previous page's `next_cursor`.
 #[utoipa::path(
     get,
     path = "/api/v1/agents/following",
@@ -284,11 +305,13 @@ pub async fn follow_agent(
         ("chain_id" = Option<i32>, Query, description = "Filter by chain ID"),
         ("enabled" = Option<bool>, Query, description = "Filter by enabled status"),
         ("limit" = Option<i64>, Query, description = "Maximum items per page"),
-        ("offset" = Option<i64>, Query, description = "Number of items to skip")
+        ("offset" = Option<i64>, Query, description = "Number of items to skip; cannot be combined with `cursor`"),
+        ("cursor" = Option<String>, Query, description = "Cursor from a previous page's `next_cursor`")
     ),
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
-        (status = 200, description = "List of followed agents", body = PaginatedResponse<AgentFollowResponse>),
+        (status = 200, description = "List of followed agents", body = FollowedAgentsResponse),
+        (status = 400, description = "Invalid cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse)
     )
 )]
@@ -310,21 +333,26 @@ pub async fn list_following(
 
     let limit = pagination.limit.min(100);
     let offset = pagination.offset;
+    let after = match query.after(offset) {
+        Ok(after) => after,
+        Err(message) => return bad_request(&message),
+    };
 
-    // Parallel count and list
-    let (total_result, follows_result) = tokio::join!(
+    // Parallel count and list; one extra row tells whether another page follows
+    let (total_result, rows_result) = tokio::join!(
         AgentFollowRepository::count_by_organization(
             &pool,
             &organization_id,
             query.chain_id,
             query.enabled
         ),
-        AgentFollowRepository::list_by_organization(
+        AgentLinkRepository::list_follows_with_links(
             &pool,
             &organization_id,
             query.chain_id,
             query.enabled,
-            limit,
+            after.as_ref(),
+            limit + 1,
             offset
         )
     );
@@ -334,17 +362,28 @@ pub async fn list_following(
         Err(resp) => return resp,
     };
 
-    let follows = match handle_db_error(follows_result, "list follows") {
-        Ok(follows) => follows,
+    let mut rows = match handle_db_error(rows_result, "list follows") {
+        Ok(rows) => rows,
         Err(resp) => return resp,
     };
 
-    // Build responses
-    let responses: Vec<AgentFollowResponse> = follows.into_iter().map(|f| f.into()).collect();
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_cursor = rows.last().filter(|_| has_more).map(|last| {
+        FollowCursor {
+            created_at: last.follow.created_at,
+            id: last.follow.id.clone(),
+        }
+        .encode()
+    });
+
+    let mut pagination = PaginationMeta::new(total, limit, offset);
+    pagination.has_more = has_more;
 
-    HttpResponse::Ok().json(PaginatedResponse {
-        data: responses,
-        pagination: PaginationMeta::new(total, limit, offset),
+    HttpResponse::Ok().json(FollowedAgentsResponse {
+        data: rows.into_iter().map(FollowedAgentResponse::from).collect(),
+        pagination,
+        next_cursor,
     })
 }
 
@@ -468,6 +507,9 @@ pub async fn unfollow_agent(
 /// Update follow settings
 ///
 /// PUT /api/v1/agents/{agent_id}/follow?chain_id=xxx
+///
+/// `notification_preferences` limits the event types notified about and the
+/// channels (action types) they are sent through.
 #[utoipa::path(
     put,
     path = "/api/v1/agents/{agent_id}/follow",
@@ -480,7 +522,7 @@ pub async fn unfollow_agent(
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 200, description = "Follow updated", body = SuccessResponse<AgentFollowResponse>),
-        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 400, description = "Validation error or webhook verification failed", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Insufficient permissions", body = ErrorResponse),
         (status = 404, description = "Not following this agent", body = ErrorResponse)
@@ -488,6 +530,7 @@ pub async fn unfollow_agent(
 )]
 pub async fn update_follow(
     pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
     req_http: HttpRequest,
     path: web::Path<AgentFollowPath>,
     query: web::Query<ChainIdQuery>,
@@ -534,6 +577,15 @@ pub async fn update_follow(
         Err(resp) => return resp,
     };
 
+    // Verify REST endpoints of replacement actions before anything is stored
+    let verifications = match &req.actions {
+        Some(actions) => match verify_follow_actions(&verifier, actions).await {
+            Ok(verifications) => verifications,
+            Err(resp) => return resp,
+        },
+        None => Vec::new(),
+    };
+
     // Start transaction
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
@@ -584,13 +636,17 @@ pub async fn update_follow(
             }
 
             // Create new actions
-            for (idx, action) in actions.iter().enumerate() {
+            for (idx, (action, (verification_status, verified_at))) in
+                actions.iter().zip(&verifications).enumerate()
+            {
                 if let Err(e) = ActionRepository::create_in_tx(
                     &mut *tx,
                     trigger_id,
                     &action.action_type,
                     idx as i32,
                     &action.config,
+                    *verification_status,
+                    *verified_at,
                 )
                 .await
                 {
@@ -606,8 +662,8 @@ pub async fn update_follow(
     }
 
     // Update follow record enabled status
-    let updated_follow = if req.enabled.is_some() {
-        match AgentFollowRepository::update_enabled(&pool, &follow.id, req.enabled.unwrap()).await {
+    let updated_follow = if let Some(enabled) = req.enabled {
+        match AgentFollowRepository::update_enabled(&mut *tx, &follow.id, enabled).await {
             Ok(f) => f,
             Err(e) => {
                 tracing::error!("Failed to update follow: {}", e);
@@ -622,6 +678,29 @@ pub async fn update_follow(
         follow
     };
 
+    // The event-processor reads the preferences when a follow's trigger fires
+    let updated_follow = if let Some(preferences) = &req.notification_preferences {
+        match AgentFollowRepository::update_notification_preferences(
+            &mut *tx,
+            &updated_follow.id,
+            preferences.as_ref(),
+        )
+        .await
+        {
+            Ok(f) => f,
+            Err(e) => {
+                tracing::error!("Failed to update notification preferences: {}", e);
+                let _ = tx.rollback().await;
+                return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                    "update_failed",
+                    "Failed to update notification preferences",
+                ));
+            }
+        }
+    } else {
+        updated_follow
+    };
+
     if let Err(e) = tx.commit().await {
         tracing::error!("Failed to commit transaction: {}", e);
         return HttpResponse::InternalServerError().json(ErrorResponse::new(
@@ -678,7 +757,24 @@ fn build_follow_response(
                 config_preview: redact_secrets(&a.config),
             })
             .collect(),
+        notification_preferences: follow.notification_preferences.clone(),
         created_at: follow.created_at,
         updated_at: follow.updated_at,
     }
 }
+
+/// Run the webhook handshake for each follow action
+///
+/// The same actions are created on all 3 triggers, so each endpoint is only
+/// verified once. Returns the verification state per action, or a 400 response
+/// if a handshake fails in `enforce` mode.
+async fn verify_follow_actions(
+    verifier: &WebhookVerifier,
+    actions: &[FollowActionRequest],
+) -> Result<Vec<(VerificationStatus, Option<DateTime<Utc>>)>, HttpResponse> {
+    let mut verifications = Vec::with_capacity(actions.len());
+    for action in actions {
+        verifications.push(run_verification(verifier, &action.action_type, &action.config).await?);
+    }
+    Ok(verifications)
+}
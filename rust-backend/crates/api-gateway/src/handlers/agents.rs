@@ -26,6 +26,7 @@ use validator::Validate;
 
 use crate::{
     handlers::helpers::{extract_user_id_or_unauthorized, handle_db_error, validate_request},
+    middleware::get_verified_organization,
     models::{can_manage_org, ErrorResponse, SuccessResponse},
     repositories::{wallet::NonceRepository, AgentLinkRepository, MemberRepository},
     services::WalletService,
@@ -68,12 +69,6 @@ pub struct AgentLinkResponse {
     pub created_at: String,
 }
 
-/// Query for organization filter
-#[derive(Debug, Deserialize)]
-pub struct OrgIdQuery {
-    pub organization_id: String,
-}
-
 /// Path parameter for agent ID
 #[derive(Debug, Deserialize)]
 pub struct AgentIdPath {
@@ -84,7 +79,6 @@ pub struct AgentIdPath {
 #[derive(Debug, Deserialize)]
 pub struct ChainIdQuery {
     pub chain_id: i32,
-    pub organization_id: String,
 }
 
 // ============================================================================
@@ -288,7 +282,7 @@ pub async fn link_agent(
         organization_id: link.organization_id,
         wallet_address: link.wallet_address,
         status: link.status,
-        created_at: link.created_at.to_rfc3339(),
+        created_at: shared::timestamp::format(&link.created_at),
     })
 }
 
@@ -300,7 +294,7 @@ pub async fn link_agent(
     path = "/api/v1/agents/linked",
     tag = "Agents",
     params(
-        ("organization_id" = String, Query, description = "Organization ID")
+        ("organization_id" = Option<String>, Query, description = "Organization ID (when no X-Organization-ID header)")
     ),
     security(("bearer_auth" = [])),
     responses(
@@ -309,35 +303,22 @@ pub async fn link_agent(
         (status = 404, description = "Organization not found", body = ErrorResponse)
     )
 )]
-pub async fn list_linked_agents(
-    pool: web::Data<DbPool>,
-    req_http: HttpRequest,
-    query: web::Query<OrgIdQuery>,
-) -> impl Responder {
+pub async fn list_linked_agents(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
         Err(resp) => return resp,
     };
 
-    // Check organization membership
-    match handle_db_error(
-        MemberRepository::get_role(&pool, &query.organization_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(_)) => {} // Any member can view
-        Ok(None) => {
-            return HttpResponse::NotFound().json(ErrorResponse::new(
-                "not_found",
-                "Organization not found or you are not a member",
-            ))
-        }
+    // Resolve the organization; any member can view
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, None).await {
+        Ok(org) => org.id,
         Err(resp) => return resp,
-    }
+    };
 
     // Get linked agents
     let links = match handle_db_error(
-        AgentLinkRepository::find_by_organization(&pool, &query.organization_id).await,
+        AgentLinkRepository::find_by_organization(&pool, &org_id).await,
         "list agent links",
     ) {
         Ok(l) => l,
@@ -353,7 +334,7 @@ pub async fn list_linked_agents(
             organization_id: l.organization_id,
             wallet_address: l.wallet_address,
             status: l.status,
-            created_at: l.created_at.to_rfc3339(),
+            created_at: shared::timestamp::format(&l.created_at),
         })
         .collect();
 
@@ -370,7 +351,7 @@ pub async fn list_linked_agents(
     params(
         ("agent_id" = i64, Path, description = "Agent token ID"),
         ("chain_id" = i32, Query, description = "Chain ID"),
-        ("organization_id" = String, Query, description = "Organization ID")
+        ("organization_id" = Option<String>, Query, description = "Organization ID (when no X-Organization-ID header)")
     ),
     security(("bearer_auth" = [])),
     responses(
@@ -392,20 +373,12 @@ pub async fn unlink_agent(
         Err(resp) => return resp,
     };
 
-    // Check organization membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &query.organization_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound().json(ErrorResponse::new(
-                "not_found",
-                "Organization not found or you are not a member",
-            ))
-        }
-        Err(resp) => return resp,
-    };
+    // Resolve the organization and the caller's role
+    let (organization_id, role) =
+        match get_verified_organization(&req_http, &pool, &user_id, None).await {
+            Ok(org) => (org.id, org.role),
+            Err(resp) => return resp,
+        };
 
     // Must be admin or owner to unlink agents
     if !can_manage_org(&role) {
@@ -420,7 +393,7 @@ pub async fn unlink_agent(
         AgentLinkRepository::get_organization_for_agent(&pool, path.agent_id, query.chain_id).await,
         "get agent link",
     ) {
-        Ok(Some(org_id)) if org_id == query.organization_id => {}
+        Ok(Some(org_id)) if org_id == organization_id => {}
         Ok(Some(_)) => {
             return HttpResponse::Forbidden().json(ErrorResponse::new(
                 "forbidden",
@@ -512,33 +485,18 @@ fn extract_expiration_from_challenge(challenge: &str) -> Result<DateTime<Utc>, &
         (status = 404, description = "Organization not found", body = ErrorResponse)
     )
 )]
-pub async fn list_org_agents(
-    pool: web::Data<DbPool>,
-    req_http: HttpRequest,
-    path: web::Path<String>,
-) -> impl Responder {
-    let org_id = path.into_inner();
-
+pub async fn list_org_agents(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
         Err(resp) => return resp,
     };
 
-    // Check organization membership
-    match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(_)) => {} // Any member can view
-        Ok(None) => {
-            return HttpResponse::NotFound().json(ErrorResponse::new(
-                "not_found",
-                "Organization not found or you are not a member",
-            ))
-        }
+    // Resolve the organization; any member can view
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.id,
         Err(resp) => return resp,
-    }
+    };
 
     // Get linked agents
     let links = match handle_db_error(
@@ -558,7 +516,7 @@ pub async fn list_org_agents(
             organization_id: l.organization_id,
             wallet_address: l.wallet_address,
             status: l.status,
-            created_at: l.created_at.to_rfc3339(),
+            created_at: shared::timestamp::format(&l.created_at),
         })
         .collect();
 
@@ -23,7 +23,8 @@
 //!
 //! # Security
 //!
-//! - Full API key is shown ONLY ONCE at creation time
+//! - Full API key is shown ONLY ONCE at creation time; an `Idempotency-Key`
+//!   replay of the creation returns the response without `key`
 //! - Keys are stored as Argon2id hashes (never plaintext)
 //! - All operations are logged to audit trail
 //! - Revoked keys are kept for audit purposes
@@ -35,8 +36,9 @@ use shared::DbPool;
 use crate::{
     handlers::helpers::{
         bad_request, extract_request_context, extract_user_id_or_unauthorized, forbidden,
-        handle_db_error, handle_error, require_found, validate_request,
+        handle_db_error, handle_error, require_found, require_verified_email, validate_request,
     },
+    middleware::get_verified_organization,
     models::{
         can_manage_org, ApiKeyCreatedResponse, ApiKeyListResponse, ApiKeyResponse,
         ApiKeyStatsResponse, CreateApiKeyRequest, ErrorResponse, KeysByEnvironment, KeysByType,
@@ -47,6 +49,9 @@ use crate::{
     services::ApiKeyService,
 };
 
+/// Response field holding the plaintext key, left out of idempotent replays
+pub const CREATED_KEY_POINTER: &str = "/data/key";
+
 // ============================================================================
 // API Key Handlers
 // ============================================================================
@@ -56,13 +61,14 @@ use crate::{
 /// POST /api/v1/api-keys
 ///
 /// Returns the full API key ONLY at creation time. The key will never be
-/// shown again - the client MUST save it immediately.
+/// shown again - the client MUST save it immediately. A retry with the same
+/// `Idempotency-Key` returns the created key's details without `key`.
 #[utoipa::path(
     post,
     path = "/api/v1/api-keys",
     tag = "API Keys",
     params(
-        ("organization_id" = String, Query, description = "Organization ID")
+        ("organization_id" = Option<String>, Query, description = "Organization ID (when no X-Organization-ID header)")
     ),
     request_body = CreateApiKeyRequest,
     security(("bearer_auth" = [])),
@@ -70,7 +76,7 @@ use crate::{
         (status = 201, description = "API key created - full key shown once", body = SuccessResponse<ApiKeyCreatedResponse>),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Forbidden - admin required", body = ErrorResponse),
+        (status = 403, description = "Forbidden - admin required, or email not verified", body = ErrorResponse),
         (status = 404, description = "Organization not found", body = ErrorResponse)
     )
 )]
@@ -90,34 +96,9 @@ pub async fn create_api_key(
         return resp;
     }
 
-    // Get organization_id from query param or request body
-    // For now, we'll require it in the query string
-    let org_id = match req_http.match_info().get("org_id") {
-        Some(id) => id.to_string(),
-        None => {
-            // Try to get from query string
-            match web::Query::<OrgIdQuery>::from_query(req_http.query_string()) {
-                Ok(q) => q.organization_id.clone(),
-                Err(_) => {
-                    return HttpResponse::BadRequest().json(ErrorResponse::new(
-                        "bad_request",
-                        "organization_id query parameter is required",
-                    ))
-                }
-            }
-        }
-    };
-
-    // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    // Resolve the organization and check membership
+    let (org_id, role) = match get_verified_organization(&req_http, &pool, &user_id, None).await {
+        Ok(org) => (org.id, org.role),
         Err(resp) => return resp,
     };
 
@@ -126,6 +107,10 @@ pub async fn create_api_key(
         return forbidden("Insufficient permissions to create API keys");
     }
 
+    if let Err(resp) = require_verified_email(&pool, &user_id).await {
+        return resp;
+    }
+
     // Generate the API key
     let api_key_service = ApiKeyService::new();
     let generated = match handle_error(
@@ -219,7 +204,7 @@ pub async fn create_api_key(
     path = "/api/v1/api-keys",
     tag = "API Keys",
     params(
-        ("organization_id" = String, Query, description = "Organization ID"),
+        ("organization_id" = Option<String>, Query, description = "Organization ID (when no X-Organization-ID header)"),
         ("limit" = Option<i64>, Query, description = "Maximum items per page"),
         ("offset" = Option<i64>, Query, description = "Number of items to skip"),
         ("include_revoked" = Option<bool>, Query, description = "Include revoked keys")
@@ -255,27 +240,16 @@ pub async fn list_api_keys(
         ));
     }
 
-    // Check membership
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &query.organization_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    // Resolve the organization; all members can view keys (masked)
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, None).await {
+        Ok(org) => org.id,
         Err(resp) => return resp,
     };
 
-    // All members can view keys (masked)
-    let _ = role; // Role checked above for membership
-
     // Get total count
     let include_revoked = query.include_revoked.unwrap_or(false);
     let total = match handle_db_error(
-        ApiKeyRepository::count_by_organization(&pool, &query.organization_id, include_revoked)
-            .await,
+        ApiKeyRepository::count_by_organization(&pool, &org_id, include_revoked).await,
         "count API keys",
     ) {
         Ok(count) => count,
@@ -286,7 +260,7 @@ pub async fn list_api_keys(
     let keys = match handle_db_error(
         ApiKeyRepository::list_by_organization(
             &pool,
-            &query.organization_id,
+            &org_id,
             include_revoked,
             pagination.limit,
             pagination.offset,
@@ -677,11 +651,8 @@ pub async fn rotate_api_key(
 pub async fn list_org_api_keys(
     pool: web::Data<DbPool>,
     req_http: HttpRequest,
-    path: web::Path<String>,
     query: web::Query<OrgApiKeyListQuery>,
 ) -> impl Responder {
-    let org_id = path.into_inner();
-
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
@@ -700,22 +671,12 @@ pub async fn list_org_api_keys(
         ));
     }
 
-    // Check membership
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    // Resolve the organization; all members can view keys (masked)
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.id,
         Err(resp) => return resp,
     };
 
-    // All members can view keys (masked)
-    let _ = role; // Role checked above for membership
-
     // Get total count
     let include_revoked = query.include_revoked.unwrap_or(false);
     let total = match handle_db_error(
@@ -772,18 +733,15 @@ pub async fn list_org_api_keys(
         (status = 201, description = "API key created - full key shown once", body = SuccessResponse<ApiKeyCreatedResponse>),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Forbidden - admin required", body = ErrorResponse),
+        (status = 403, description = "Forbidden - admin required, or email not verified", body = ErrorResponse),
         (status = 404, description = "Organization not found", body = ErrorResponse)
     )
 )]
 pub async fn create_org_api_key(
     pool: web::Data<DbPool>,
     req_http: HttpRequest,
-    path: web::Path<String>,
     req: web::Json<CreateApiKeyRequest>,
 ) -> impl Responder {
-    let org_id = path.into_inner();
-
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
@@ -795,24 +753,22 @@ pub async fn create_org_api_key(
         return resp;
     }
 
-    // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
-        Err(resp) => return resp,
-    };
+    // Resolve the organization and check membership
+    let (org_id, role) =
+        match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+            Ok(org) => (org.id, org.role),
+            Err(resp) => return resp,
+        };
 
     // Check if user can manage org (owner or admin)
     if !can_manage_org(&role) {
         return forbidden("Insufficient permissions to create API keys");
     }
 
+    if let Err(resp) = require_verified_email(&pool, &user_id).await {
+        return resp;
+    }
+
     // Generate the API key
     let api_key_service = ApiKeyService::new();
     let generated = match handle_error(
@@ -1015,32 +971,19 @@ pub async fn update_api_key(
 pub async fn get_org_api_key_stats(
     pool: web::Data<DbPool>,
     req_http: HttpRequest,
-    path: web::Path<String>,
 ) -> impl Responder {
-    let org_id = path.into_inner();
-
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
         Err(resp) => return resp,
     };
 
-    // Check membership (any role can view stats)
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    // Resolve the organization; any member can view stats (viewer, member, admin, owner)
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.id,
         Err(resp) => return resp,
     };
 
-    // Any member can view stats (viewer, member, admin, owner)
-    let _ = role; // Role is validated, any role is allowed
-
     // Get stats from repository
     let stats = match handle_db_error(
         ApiKeyRepository::get_stats_by_organization(&pool, &org_id).await,
@@ -1080,12 +1023,6 @@ pub async fn get_org_api_key_stats(
 // Query Parameter Structs
 // ============================================================================
 
-/// Query parameters for organization ID
-#[derive(Debug, serde::Deserialize)]
-pub struct OrgIdQuery {
-    pub organization_id: String,
-}
-
 /// Query parameters for listing API keys (org-scoped, org_id from path)
 #[derive(Debug, serde::Deserialize)]
 pub struct OrgApiKeyListQuery {
@@ -1095,9 +1032,11 @@ pub struct OrgApiKeyListQuery {
 }
 
 /// Query parameters for listing API keys
+///
+/// The organization comes from the `X-Organization-ID` header or the
+/// `organization_id` query parameter (see [`crate::middleware::organization`]).
 #[derive(Debug, serde::Deserialize)]
 pub struct ApiKeyListQuery {
-    pub organization_id: String,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub include_revoked: Option<bool>,
@@ -1120,18 +1059,10 @@ mod tests {
     // Query Parameter Tests
     // ========================================================================
 
-    #[test]
-    fn test_org_id_query_deserialize() {
-        let query_string = "organization_id=org_123";
-        let query: OrgIdQuery = serde_urlencoded::from_str(query_string).unwrap();
-        assert_eq!(query.organization_id, "org_123");
-    }
-
     #[test]
     fn test_api_key_list_query_deserialize_minimal() {
         let query_string = "organization_id=org_123";
         let query: ApiKeyListQuery = serde_urlencoded::from_str(query_string).unwrap();
-        assert_eq!(query.organization_id, "org_123");
         assert!(query.limit.is_none());
         assert!(query.offset.is_none());
         assert!(query.include_revoked.is_none());
@@ -1141,7 +1072,6 @@ mod tests {
     fn test_api_key_list_query_deserialize_full() {
         let query_string = "organization_id=org_456&limit=50&offset=100&include_revoked=true";
         let query: ApiKeyListQuery = serde_urlencoded::from_str(query_string).unwrap();
-        assert_eq!(query.organization_id, "org_456");
         assert_eq!(query.limit, Some(50));
         assert_eq!(query.offset, Some(100));
         assert_eq!(query.include_revoked, Some(true));
@@ -1151,7 +1081,6 @@ mod tests {
     fn test_api_key_list_query_deserialize_partial() {
         let query_string = "organization_id=org_789&limit=25";
         let query: ApiKeyListQuery = serde_urlencoded::from_str(query_string).unwrap();
-        assert_eq!(query.organization_id, "org_789");
         assert_eq!(query.limit, Some(25));
         assert!(query.offset.is_none());
         assert!(query.include_revoked.is_none());
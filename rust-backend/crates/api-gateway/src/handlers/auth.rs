@@ -7,25 +7,36 @@ use argon2::{
     Argon2,
 };
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
-use shared::{Config, DbPool};
+use shared::{models::AuthProvider, Config, DbPool};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     models::{
-        AuthResponse, Claims, ErrorResponse, ExchangeCodeRequest, LoginRequest, LogoutResponse,
-        MeResponse, NonceResponse, OrganizationInfo, RefreshTokenRequest, RefreshTokenResponse,
-        RegisterRequest, UserResponse, WalletInfo, WalletLoginRequest, ROLE_OWNER,
+        AuthResponse, Claims, EmailVerificationResponse, ErrorResponse, ExchangeCodeRequest,
+        ForgotPasswordRequest, LoginRequest, LogoutResponse, MeResponse, NonceResponse,
+        OrganizationInfo, PasswordResetResponse, RefreshTokenRequest, RefreshTokenResponse,
+        RegisterRequest, ResetPasswordRequest, RevokeSessionsResponse, SessionListResponse,
+        SessionResponse, UserResponse, VerifyEmailRequest, WalletInfo, WalletLoginRequest,
+        ROLE_OWNER,
     },
     repositories::{
-        MemberRepository, OrganizationRepository, UserIdentityRepository, UserRepository,
+        FailedLogin, MemberRepository, OrganizationRepository, UserIdentityRepository,
+        UserRepository,
+    },
+    services::{
+        EmailService, EmailVerificationError, EmailVerificationService, PasswordResetError,
+        PasswordResetRateLimiter, PasswordResetService, SiweConfig, SiweError, SiweMessage,
+        WalletService,
     },
 };
 
 /// Register a new user
 ///
 /// Creates a new user account with username, email, and password.
-/// Also creates a personal organization for the user.
+/// Also creates a personal organization for the user. The account starts with
+/// an unverified email and a verification link is sent to it; creating API
+/// keys and OAuth clients requires verifying first.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/register",
@@ -41,6 +52,8 @@ use crate::{
 pub async fn register(
     pool: web::Data<DbPool>,
     config: web::Data<Config>,
+    email_service: web::Data<EmailService>,
+    http_req: HttpRequest,
     req: web::Json<RegisterRequest>,
 ) -> impl Responder {
     // Validate request
@@ -208,28 +221,22 @@ pub async fn register(
         ));
     }
 
-    // Generate JWT token
-    let claims = Claims::new(user.id.clone(), user.username.clone(), 1); // 1 hour
-    let token = match encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
-    ) {
-        Ok(token) => token,
-        Err(e) => {
-            tracing::error!("Failed to generate JWT: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to generate authentication token",
-            ));
-        }
-    };
+    // The user can ask for another link if this one is not delivered
+    if let Err(e) = send_verification_email(&pool, &email_service, &user, false).await {
+        tracing::warn!(user_id = %user.id, "Failed to send verification email: {}", e);
+    }
 
-    // Generate refresh token
+    // Generate refresh token (starts the login session)
     use crate::services::{UserRefreshTokenService, ACCESS_TOKEN_VALIDITY_SECS};
+    let (user_agent, ip_address) = client_info(&http_req);
     let refresh_service = UserRefreshTokenService::new();
     let refresh_token = match refresh_service
-        .create_refresh_token(&pool, &user.id, None, None)
+        .create_refresh_token(
+            &pool,
+            &user.id,
+            user_agent.as_deref(),
+            ip_address.as_deref(),
+        )
         .await
     {
         Ok(rt) => rt,
@@ -242,9 +249,27 @@ pub async fn register(
         }
     };
 
+    // Generate JWT token
+    let claims = Claims::new(user.id.clone(), user.username.clone(), 1) // 1 hour
+        .with_session(&refresh_token.session_id);
+    let token = match encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
+    ) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to generate JWT: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to generate authentication token",
+            ));
+        }
+    };
+
     let response = AuthResponse {
         token,
-        refresh_token,
+        refresh_token: refresh_token.token,
         expires_in: ACCESS_TOKEN_VALIDITY_SECS,
         user: UserResponse::from(user),
     };
@@ -273,6 +298,7 @@ pub async fn register(
 pub async fn login(
     pool: web::Data<DbPool>,
     config: web::Data<Config>,
+    http_req: HttpRequest,
     req: web::Json<LoginRequest>,
 ) -> impl Responder {
     // Validate request
@@ -380,11 +406,21 @@ pub async fn login(
         .is_err()
     {
         // Record failed login attempt (may trigger lockout)
-        match UserRepository::record_failed_login(&pool, &user.id).await {
-            Ok(attempts) => {
+        match UserRepository::record_failed_login(&pool, &user.id, &config.auth).await {
+            Ok(FailedLogin {
+                locked_until: Some(until),
+                ..
+            }) => {
+                tracing::warn!(
+                    user_id = %user.id,
+                    locked_until = %until,
+                    "Failed login attempt - account locked"
+                );
+            }
+            Ok(failed) => {
                 tracing::warn!(
                     user_id = %user.id,
-                    failed_attempts = attempts,
+                    failed_attempts = failed.attempts,
                     "Failed login attempt"
                 );
             }
@@ -409,16 +445,22 @@ pub async fn login(
         tracing::warn!("Failed to update last login: {}", e);
     }
 
-    // Generate JWT token
-    let claims = Claims::new(user.id.clone(), user.username.clone(), 1); // 1 hour
-    let token = match encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
-    ) {
-        Ok(token) => token,
+    // Generate refresh token (starts the login session)
+    use crate::services::{UserRefreshTokenService, ACCESS_TOKEN_VALIDITY_SECS};
+    let (user_agent, ip_address) = client_info(&http_req);
+    let refresh_service = UserRefreshTokenService::new();
+    let refresh_token = match refresh_service
+        .create_refresh_token(
+            &pool,
+            &user.id,
+            user_agent.as_deref(),
+            ip_address.as_deref(),
+        )
+        .await
+    {
+        Ok(rt) => rt,
         Err(e) => {
-            tracing::error!("Failed to generate JWT: {}", e);
+            tracing::error!("Failed to generate refresh token: {}", e);
             return HttpResponse::InternalServerError().json(ErrorResponse::new(
                 "internal_error",
                 "Failed to generate authentication token",
@@ -426,16 +468,17 @@ pub async fn login(
         }
     };
 
-    // Generate refresh token
-    use crate::services::{UserRefreshTokenService, ACCESS_TOKEN_VALIDITY_SECS};
-    let refresh_service = UserRefreshTokenService::new();
-    let refresh_token = match refresh_service
-        .create_refresh_token(&pool, &user.id, None, None)
-        .await
-    {
-        Ok(rt) => rt,
+    // Generate JWT token
+    let claims = Claims::new(user.id.clone(), user.username.clone(), 1) // 1 hour
+        .with_session(&refresh_token.session_id);
+    let token = match encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
+    ) {
+        Ok(token) => token,
         Err(e) => {
-            tracing::error!("Failed to generate refresh token: {}", e);
+            tracing::error!("Failed to generate JWT: {}", e);
             return HttpResponse::InternalServerError().json(ErrorResponse::new(
                 "internal_error",
                 "Failed to generate authentication token",
@@ -445,7 +488,7 @@ pub async fn login(
 
     let response = AuthResponse {
         token,
-        refresh_token,
+        refresh_token: refresh_token.token,
         expires_in: ACCESS_TOKEN_VALIDITY_SECS,
         user: UserResponse::from(user),
     };
@@ -495,7 +538,8 @@ fn generate_personal_slug(username: &str) -> String {
 /// Generate a nonce for SIWE wallet authentication
 ///
 /// Returns a nonce and challenge message for wallet signature verification.
-/// The wallet address is required to generate a proper SIWE message.
+/// The wallet address is required to generate a proper SIWE message; the
+/// message carries the requested chain ID, which must be a supported chain.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/nonce",
@@ -503,12 +547,14 @@ fn generate_personal_slug(username: &str) -> String {
     request_body = crate::models::NonceRequest,
     responses(
         (status = 200, description = "Nonce generated", body = NonceResponse),
-        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 400, description = "Validation error or unsupported chain", body = ErrorResponse),
         (status = 500, description = "Failed to generate nonce", body = ErrorResponse)
     )
 )]
 pub async fn generate_nonce(
     pool: web::Data<DbPool>,
+    wallet_service: web::Data<WalletService>,
+    siwe_config: web::Data<SiweConfig>,
     req: web::Json<crate::models::NonceRequest>,
 ) -> impl Responder {
     // Validate request
@@ -519,9 +565,24 @@ pub async fn generate_nonce(
         ));
     }
 
-    let nonce = Uuid::new_v4().to_string();
-    let expires_at = chrono::Utc::now() + chrono::Duration::minutes(10);
+    if let Err(resp) = require_login_chain(&wallet_service, req.chain_id) {
+        return resp;
+    }
+
+    let address = match req.address.parse::<Address>() {
+        Ok(addr) => addr,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(ErrorResponse::new(
+                "invalid_address",
+                "Invalid wallet address format",
+            ));
+        }
+    };
+
+    // EIP-4361 nonces are alphanumeric
+    let nonce = Uuid::new_v4().simple().to_string();
     let issued_at = chrono::Utc::now();
+    let expires_at = issued_at + chrono::Duration::minutes(10);
 
     // Store nonce in database for verification
     let result = sqlx::query(
@@ -532,7 +593,7 @@ pub async fn generate_nonce(
         "#,
     )
     .bind(&nonce)
-    .bind(&req.address)
+    .bind(address.to_checksum(None))
     .bind(expires_at)
     .execute(pool.get_ref())
     .await;
@@ -547,12 +608,9 @@ pub async fn generate_nonce(
 
     // Build SIWE (Sign-In With Ethereum) message format
     // https://eips.ethereum.org/EIPS/eip-4361
-    let message = format!(
-        "agentauri.ai wants you to sign in with your Ethereum account:\n{}\n\nSign in to AgentAuri\n\nURI: https://agentauri.ai\nVersion: 1\nChain ID: 1\nNonce: {}\nIssued At: {}",
-        req.address,
-        nonce,
-        issued_at.to_rfc3339()
-    );
+    let message = siwe_config
+        .message(address, req.chain_id, &nonce, issued_at, expires_at)
+        .to_string();
 
     HttpResponse::Ok().json(crate::models::NonceResponse { nonce, message })
 }
@@ -579,33 +637,9 @@ pub async fn get_me(
     pool: web::Data<DbPool>,
     config: web::Data<Config>,
 ) -> impl Responder {
-    // Extract token from Authorization header or cookie
-    let token = extract_token(&req);
-
-    let token = match token {
-        Some(t) => t,
-        None => {
-            return HttpResponse::Unauthorized().json(ErrorResponse::new(
-                "unauthorized",
-                "Authentication required",
-            ));
-        }
-    };
-
-    // Validate JWT
-    let claims = match decode::<Claims>(
-        &token,
-        &DecodingKey::from_secret(config.server.jwt_secret.as_bytes()),
-        &Validation::new(Algorithm::HS256),
-    ) {
-        Ok(data) => data.claims,
-        Err(e) => {
-            tracing::debug!("Invalid JWT token: {}", e);
-            return HttpResponse::Unauthorized().json(ErrorResponse::new(
-                "invalid_token",
-                "Invalid or expired token",
-            ));
-        }
+    let claims = match authenticated_claims(&req, &config) {
+        Ok(c) => c,
+        Err(resp) => return resp,
     };
 
     // Fetch user
@@ -679,6 +713,7 @@ pub async fn get_me(
         email: user.email,
         name: user.display_name,
         avatar: user.avatar_url,
+        email_verified: user.email_verified_at.is_some(),
         wallets,
         providers,
         organizations,
@@ -709,10 +744,473 @@ pub async fn logout() -> impl Responder {
     })
 }
 
+/// Verify an email address
+///
+/// Consumes the token from a verification link. Tokens are single-use,
+/// expire after 24 hours, and stop working once a newer link is sent.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify-email",
+    tag = "Authentication",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email verified", body = EmailVerificationResponse),
+        (status = 400, description = "Invalid, used or expired token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn verify_email(
+    pool: web::Data<DbPool>,
+    body: web::Json<VerifyEmailRequest>,
+) -> impl Responder {
+    if let Err(e) = body.validate() {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            format!("Validation failed: {}", e),
+        ));
+    }
+
+    match EmailVerificationService::new()
+        .verify(&pool, &body.token)
+        .await
+    {
+        Ok(user_id) => {
+            tracing::info!(user_id = %user_id, "Email verified");
+            HttpResponse::Ok().json(EmailVerificationResponse {
+                success: true,
+                message: "Email verified".to_string(),
+            })
+        }
+        Err(EmailVerificationError::InvalidToken) => HttpResponse::BadRequest().json(
+            ErrorResponse::new("invalid_token", "Invalid or expired verification token"),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to verify email: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to verify email",
+            ))
+        }
+    }
+}
+
+/// Send a new verification email
+///
+/// Invalidates earlier verification links. At most one email per minute.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/resend-verification",
+    tag = "Authentication",
+    security(
+        ("bearer_auth" = []),
+        ("cookie_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Verification email sent", body = EmailVerificationResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 409, description = "Email already verified", body = ErrorResponse),
+        (status = 429, description = "Verification email sent too recently", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn resend_verification(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    email_service: web::Data<EmailService>,
+) -> impl Responder {
+    let claims = match authenticated_claims(&req, &config) {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user = match UserRepository::find_by_id(&pool, &claims.sub).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return HttpResponse::Unauthorized()
+                .json(ErrorResponse::new("user_not_found", "User not found"));
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch user: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to process request",
+            ));
+        }
+    };
+
+    if !user.email_verification_pending() {
+        return HttpResponse::Conflict().json(ErrorResponse::new(
+            "email_already_verified",
+            "Email address is already verified",
+        ));
+    }
+
+    match send_verification_email(&pool, &email_service, &user, true).await {
+        Ok(()) => HttpResponse::Ok().json(EmailVerificationResponse {
+            success: true,
+            message: "Verification email sent".to_string(),
+        }),
+        Err(SendVerificationError::Token(EmailVerificationError::TooSoon(retry_after))) => {
+            HttpResponse::TooManyRequests()
+                .insert_header(("Retry-After", retry_after.to_string()))
+                .json(ErrorResponse::new(
+                    "rate_limit_exceeded",
+                    "Verification email sent too recently",
+                ))
+        }
+        Err(e) => {
+            tracing::error!(user_id = %user.id, "Failed to send verification email: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to send verification email",
+            ))
+        }
+    }
+}
+
+/// Why a verification email could not be sent
+#[derive(Debug, thiserror::Error)]
+enum SendVerificationError {
+    #[error(transparent)]
+    Token(#[from] EmailVerificationError),
+    #[error(transparent)]
+    Email(#[from] crate::services::EmailError),
+}
+
+/// Issue a verification token for the user's email and mail the link
+///
+/// `resend` applies the resend cooldown.
+async fn send_verification_email(
+    pool: &DbPool,
+    email_service: &EmailService,
+    user: &shared::models::User,
+    resend: bool,
+) -> Result<(), SendVerificationError> {
+    let service = EmailVerificationService::new();
+    let token = if resend {
+        service
+            .create_resend_token(pool, &user.id, &user.email)
+            .await?
+    } else {
+        service.create_token(pool, &user.id, &user.email).await?
+    };
+
+    email_service
+        .send(&email_service.verification_email(&user.email, &token))
+        .await?;
+    Ok(())
+}
+
+/// Request a password reset email
+///
+/// Always returns 200 with the same body, whether or not an account uses the
+/// address, so the endpoint cannot be used to discover accounts. If an active
+/// account with a deliverable email exists, a reset link valid for 1 hour is
+/// sent to it. The lookup and the email happen after the response.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/forgot-password",
+    tag = "Authentication",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 200, description = "Request accepted", body = PasswordResetResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded (max 5 requests per minute per IP)", body = ErrorResponse)
+    )
+)]
+pub async fn forgot_password(
+    pool: web::Data<DbPool>,
+    email_service: web::Data<EmailService>,
+    rate_limiter: web::Data<PasswordResetRateLimiter>,
+    req: HttpRequest,
+    body: web::Json<ForgotPasswordRequest>,
+) -> impl Responder {
+    let ip_address = req
+        .connection_info()
+        .realip_remote_addr()
+        .map(|s| s.to_string());
+    let ip_for_limit = ip_address.as_deref().unwrap_or("unknown");
+    if let Err(e) = rate_limiter.check(ip_for_limit) {
+        tracing::warn!(ip = ip_for_limit, "Forgot password rate limit exceeded");
+        return HttpResponse::TooManyRequests()
+            .json(ErrorResponse::new("rate_limit_exceeded", e.message));
+    }
+
+    if let Err(e) = body.validate() {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            format!("Validation failed: {}", e),
+        ));
+    }
+
+    // Respond before looking the address up so timing does not reveal accounts
+    let email = body.into_inner().email;
+    tokio::spawn(async move {
+        if let Err(e) = send_password_reset_email(&pool, &email_service, &email).await {
+            tracing::error!("Failed to send password reset email: {}", e);
+        }
+    });
+
+    HttpResponse::Ok().json(PasswordResetResponse {
+        success: true,
+        message: "If an account exists for this email, a password reset link has been sent"
+            .to_string(),
+    })
+}
+
+/// Mail a reset link to the account using `email`, if there is one
+async fn send_password_reset_email(
+    pool: &DbPool,
+    email_service: &EmailService,
+    email: &str,
+) -> anyhow::Result<()> {
+    let Some(user) = UserRepository::find_by_email(pool, email).await? else {
+        tracing::info!("Password reset requested for unknown email");
+        return Ok(());
+    };
+    // Wallet accounts have a placeholder address that cannot receive mail
+    if !user.is_active
+        || user.primary_auth_provider.as_deref() == Some(AuthProvider::Wallet.as_str())
+    {
+        tracing::info!(user_id = %user.id, "Password reset requested for ineligible account");
+        return Ok(());
+    }
+
+    let token = PasswordResetService::new()
+        .create_token(pool, &user.id, &user.email)
+        .await?;
+    email_service
+        .send(&email_service.password_reset_email(&user.email, &token))
+        .await?;
+
+    tracing::info!(user_id = %user.id, "Password reset email sent");
+    Ok(())
+}
+
+/// Reset a password
+///
+/// Consumes the token from a reset link and sets the new password. Tokens are
+/// single-use and expire after 1 hour. All of the user's sessions are revoked;
+/// access tokens already issued stay valid until they expire.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/reset-password",
+    tag = "Authentication",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset", body = PasswordResetResponse),
+        (status = 400, description = "Validation error, or invalid, used or expired token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn reset_password(
+    pool: web::Data<DbPool>,
+    body: web::Json<ResetPasswordRequest>,
+) -> impl Responder {
+    if let Err(e) = body.validate() {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            format!("Validation failed: {}", e),
+        ));
+    }
+
+    match PasswordResetService::new()
+        .reset_password(&pool, &body.token, &body.new_password)
+        .await
+    {
+        Ok(user_id) => {
+            tracing::info!(user_id = %user_id, "Password reset; all sessions revoked");
+            HttpResponse::Ok().json(PasswordResetResponse {
+                success: true,
+                message: "Password has been reset".to_string(),
+            })
+        }
+        Err(PasswordResetError::InvalidToken) => HttpResponse::BadRequest().json(
+            ErrorResponse::new("invalid_token", "Invalid or expired password reset token"),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to reset password: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to reset password",
+            ))
+        }
+    }
+}
+
+/// List the current user's active sessions
+///
+/// A session is a login: it lasts as long as the refresh token issued at
+/// login keeps being rotated. Sessions are ordered by last use.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/sessions",
+    tag = "Authentication",
+    security(
+        ("bearer_auth" = []),
+        ("cookie_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Active sessions", body = SessionListResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn list_sessions(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+) -> impl Responder {
+    use crate::services::UserRefreshTokenService;
+
+    let claims = match authenticated_claims(&req, &config) {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let sessions = match UserRefreshTokenService::new()
+        .list_sessions(&pool, &claims.sub)
+        .await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!("Failed to list sessions: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to list sessions",
+            ));
+        }
+    };
+
+    let sessions = sessions
+        .into_iter()
+        .map(|s| SessionResponse {
+            current: claims.sid.as_deref() == Some(s.id.as_str()),
+            id: s.id,
+            user_agent: s.user_agent,
+            ip_address: s.ip_address,
+            created_at: s.started_at,
+            last_used_at: s.last_used_at,
+            expires_at: s.expires_at,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(SessionListResponse { sessions })
+}
+
+/// Log out one session
+///
+/// Revokes the session's refresh token immediately. Access tokens already
+/// issued to it remain valid until they expire (at most one hour).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions/{id}",
+    tag = "Authentication",
+    params(
+        ("id" = String, Path, description = "Session ID")
+    ),
+    security(
+        ("bearer_auth" = []),
+        ("cookie_auth" = [])
+    ),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn revoke_session(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+    path: web::Path<String>,
+) -> impl Responder {
+    use crate::services::UserRefreshTokenService;
+
+    let claims = match authenticated_claims(&req, &config) {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+    let session_id = path.into_inner();
+
+    match UserRefreshTokenService::new()
+        .revoke_session(&pool, &claims.sub, &session_id)
+        .await
+    {
+        Ok(true) => {
+            tracing::info!(user_id = %claims.sub, session_id = %session_id, "Session revoked");
+            HttpResponse::NoContent().finish()
+        }
+        Ok(false) => {
+            HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Session not found"))
+        }
+        Err(e) => {
+            tracing::error!("Failed to revoke session: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to revoke session",
+            ))
+        }
+    }
+}
+
+/// Log out all other sessions
+///
+/// Revokes every session of the current user except the one the access token
+/// was issued to. Access tokens issued before sessions were tracked carry no
+/// session, in which case every session is revoked.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions",
+    tag = "Authentication",
+    security(
+        ("bearer_auth" = []),
+        ("cookie_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Sessions revoked", body = RevokeSessionsResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn revoke_other_sessions(
+    req: HttpRequest,
+    pool: web::Data<DbPool>,
+    config: web::Data<Config>,
+) -> impl Responder {
+    use crate::services::UserRefreshTokenService;
+
+    let claims = match authenticated_claims(&req, &config) {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    match UserRefreshTokenService::new()
+        .revoke_other_sessions(&pool, &claims.sub, claims.sid.as_deref())
+        .await
+    {
+        Ok(revoked) => {
+            tracing::info!(user_id = %claims.sub, revoked = revoked, "Other sessions revoked");
+            HttpResponse::Ok().json(RevokeSessionsResponse { revoked })
+        }
+        Err(e) => {
+            tracing::error!("Failed to revoke sessions: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to revoke sessions",
+            ))
+        }
+    }
+}
+
 /// Refresh access token using a refresh token
 ///
 /// Exchanges a valid refresh token for a new access token and refresh token.
-/// Implements token rotation: the old refresh token is invalidated.
+/// Implements token rotation: the old refresh token is invalidated. Presenting
+/// an already-rotated token is treated as theft and revokes every refresh
+/// token descended from the same login.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/refresh",
@@ -742,15 +1240,7 @@ pub async fn refresh_token(
     }
 
     // Extract client info for audit
-    let user_agent = req
-        .headers()
-        .get("user-agent")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-    let ip_address = req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
+    let (user_agent, ip_address) = client_info(&req);
 
     // Validate and rotate the refresh token
     let refresh_service = UserRefreshTokenService::new();
@@ -790,8 +1280,9 @@ pub async fn refresh_token(
         }
     };
 
-    // Generate new JWT
-    let claims = Claims::new(user.id.clone(), user.username.clone(), 1);
+    // Generate new JWT for the same session
+    let claims = Claims::new(user.id.clone(), user.username.clone(), 1)
+        .with_session(&new_refresh_token.session_id);
     let token = match encode(
         &Header::new(Algorithm::HS256),
         &claims,
@@ -824,7 +1315,7 @@ pub async fn refresh_token(
         .cookie(cookie)
         .json(RefreshTokenResponse {
             token,
-            refresh_token: new_refresh_token,
+            refresh_token: new_refresh_token.token,
             expires_in: ACCESS_TOKEN_VALIDITY_SECS,
         })
 }
@@ -934,33 +1425,8 @@ pub async fn exchange_code(
         "OAuth code exchange successful"
     );
 
-    // Generate JWT
-    let claims = Claims::new(user.id.clone(), user.username.clone(), 1);
-    let token = match encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
-    ) {
-        Ok(t) => t,
-        Err(e) => {
-            tracing::error!("Failed to generate JWT: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to generate token",
-            ));
-        }
-    };
-
     // Generate refresh token
-    let user_agent = req
-        .headers()
-        .get("user-agent")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-    let ip_address = req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
+    let (user_agent, ip_address) = client_info(&req);
 
     let refresh_service = UserRefreshTokenService::new();
     let refresh_token = match refresh_service
@@ -982,6 +1448,24 @@ pub async fn exchange_code(
         }
     };
 
+    // Generate JWT
+    let claims = Claims::new(user.id.clone(), user.username.clone(), 1)
+        .with_session(&refresh_token.session_id);
+    let token = match encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
+    ) {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Failed to generate JWT: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to generate token",
+            ));
+        }
+    };
+
     // Set auth-token cookie
     let is_production = std::env::var("ENVIRONMENT")
         .map(|e| e.to_lowercase() == "production")
@@ -997,7 +1481,7 @@ pub async fn exchange_code(
 
     HttpResponse::Ok().cookie(cookie).json(AuthResponse {
         token,
-        refresh_token,
+        refresh_token: refresh_token.token,
         expires_in: ACCESS_TOKEN_VALIDITY_SECS,
         user: UserResponse::from(user),
     })
@@ -1009,8 +1493,13 @@ pub async fn exchange_code(
 
 /// Login with wallet using SIWE (Sign-In With Ethereum)
 ///
-/// Verifies an EIP-191 signed message and returns a JWT token.
-/// Creates a new user if this is the first login with this wallet.
+/// Verifies an EIP-191 signed EIP-4361 message and returns a JWT token. The
+/// message must be issued for our domain (`SIWE_DOMAIN`), the claimed address
+/// and chain, and be inside its validity window.
+///
+/// Creates a new user if this is the first login with this wallet. The wallet
+/// identity is stored per chain; a wallet's first login on another chain adds
+/// an identity for that chain to the same user.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/wallet",
@@ -1018,14 +1507,16 @@ pub async fn exchange_code(
     request_body = WalletLoginRequest,
     responses(
         (status = 200, description = "Login successful", body = AuthResponse),
-        (status = 400, description = "Invalid request or signature", body = ErrorResponse),
-        (status = 401, description = "Invalid or expired nonce", body = ErrorResponse),
+        (status = 400, description = "Invalid request or signature, or unsupported chain", body = ErrorResponse),
+        (status = 401, description = "Invalid or expired nonce or message", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn wallet_login(
     pool: web::Data<DbPool>,
     config: web::Data<Config>,
+    wallet_service: web::Data<WalletService>,
+    siwe_config: web::Data<SiweConfig>,
     http_req: HttpRequest,
     req: web::Json<WalletLoginRequest>,
 ) -> impl Responder {
@@ -1039,6 +1530,10 @@ pub async fn wallet_login(
         ));
     }
 
+    if let Err(resp) = require_login_chain(&wallet_service, req.chain_id) {
+        return resp;
+    }
+
     // Parse wallet address
     let address = match req.address.parse::<Address>() {
         Ok(addr) => addr,
@@ -1050,17 +1545,32 @@ pub async fn wallet_login(
         }
     };
 
-    // Extract nonce from message
-    let nonce = match extract_nonce_from_message(&req.message) {
-        Some(n) => n,
-        None => {
-            return HttpResponse::BadRequest().json(ErrorResponse::new(
-                "invalid_message",
-                "Could not extract nonce from message",
-            ));
-        }
+    // Parse the EIP-4361 message and check it was issued for us and is
+    // still valid (rejects messages signed for other apps)
+    let siwe = match SiweMessage::parse(&req.message).and_then(|message| {
+        message
+            .verify(&siwe_config.domain, chrono::Utc::now())
+            .map(|_| message)
+    }) {
+        Ok(message) => message,
+        Err(e) => return siwe_error_response(e),
     };
 
+    // The signed message must be for the claimed address and chain
+    if siwe.address != address {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "address_mismatch",
+            "Message is not for the claimed address",
+        ));
+    }
+    if siwe.chain_id != req.chain_id {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "chain_mismatch",
+            format!("Message is not for chain {}", req.chain_id),
+        ));
+    }
+    let nonce = siwe.nonce;
+
     // Verify nonce exists and is not expired
     let nonce_valid: Option<chrono::DateTime<chrono::Utc>> = match sqlx::query_scalar(
         r#"
@@ -1150,30 +1660,51 @@ pub async fn wallet_login(
     }
 
     // Checksummed address for storage
-    let checksummed_address = format!("{:?}", address);
+    let checksummed_address = address.to_checksum(None);
+    let chain_id = req.chain_id;
 
-    // Find or create user for this wallet (using chain_id 1 for Ethereum mainnet)
-    let chain_id = 1i32;
-
-    // Check if wallet identity exists
+    // Check if wallet identity exists on this chain, or failing that on any
+    // other chain (same wallet, same user)
     let identity =
         match UserIdentityRepository::find_by_wallet(&pool, &checksummed_address, chain_id).await {
-            Ok(id) => id,
-            Err(e) => {
-                tracing::error!("Failed to find wallet identity: {}", e);
-                return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                    "internal_error",
-                    "Failed to process wallet login",
-                ));
+            Ok(None) => {
+                UserIdentityRepository::find_by_wallet_any_chain(&pool, &checksummed_address).await
             }
+            found => found,
         };
+    let identity = match identity {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("Failed to find wallet identity: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to process wallet login",
+            ));
+        }
+    };
 
     let user = if let Some(identity) = identity {
         // Existing wallet - fetch user
         match UserRepository::find_by_id(&pool, &identity.user_id).await {
             Ok(Some(user)) => {
-                // Update last used
-                let _ = UserIdentityRepository::update_last_used(&pool, &identity.id).await;
+                // Update last used, or record the identity on the wallet's
+                // first login on this chain
+                if identity.chain_id == Some(chain_id) {
+                    let _ = UserIdentityRepository::update_last_used(&pool, &identity.id).await;
+                } else if let Err(e) = UserIdentityRepository::create_wallet(
+                    pool.get_ref(),
+                    &user.id,
+                    &checksummed_address,
+                    chain_id,
+                )
+                .await
+                {
+                    tracing::error!("Failed to create wallet identity: {}", e);
+                    return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                        "internal_error",
+                        "Failed to link wallet",
+                    ));
+                }
                 user
             }
             Ok(None) => {
@@ -1219,6 +1750,7 @@ pub async fn wallet_login(
             "wallet",
             None,
             Some(&short_address),
+            false,
         )
         .await
         {
@@ -1306,63 +1838,120 @@ pub async fn wallet_login(
     // Update last login
     let _ = UserRepository::update_last_login(&pool, &user.id).await;
 
-    // Generate JWT
-    let claims = Claims::new(user.id.clone(), user.username.clone(), 1);
-    let token = match encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
-    ) {
+    // Generate refresh token
+    let refresh_service = UserRefreshTokenService::new();
+    let (user_agent, ip_address) = client_info(&http_req);
+    let refresh_token = match refresh_service
+        .create_refresh_token(
+            &pool,
+            &user.id,
+            user_agent.as_deref(),
+            ip_address.as_deref(),
+        )
+        .await
+    {
         Ok(token) => token,
         Err(e) => {
-            tracing::error!("Failed to generate JWT: {}", e);
+            tracing::error!("Failed to generate refresh token: {}", e);
             return HttpResponse::InternalServerError().json(ErrorResponse::new(
                 "internal_error",
-                "Failed to generate token",
+                "Failed to generate refresh token",
             ));
         }
     };
 
-    // Generate refresh token
-    let refresh_service = UserRefreshTokenService::new();
-    let user_agent = http_req
-        .headers()
-        .get("User-Agent")
-        .and_then(|h| h.to_str().ok());
-    let ip_address = http_req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
-    let refresh_token = match refresh_service
-        .create_refresh_token(&pool, &user.id, user_agent, ip_address.as_deref())
-        .await
-    {
+    // Generate JWT
+    let claims = Claims::new(user.id.clone(), user.username.clone(), 1)
+        .with_session(&refresh_token.session_id);
+    let token = match encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(config.server.jwt_secret.as_bytes()),
+    ) {
         Ok(token) => token,
         Err(e) => {
-            tracing::error!("Failed to generate refresh token: {}", e);
+            tracing::error!("Failed to generate JWT: {}", e);
             return HttpResponse::InternalServerError().json(ErrorResponse::new(
                 "internal_error",
-                "Failed to generate refresh token",
+                "Failed to generate token",
             ));
         }
     };
 
     HttpResponse::Ok().json(AuthResponse {
         token,
-        refresh_token,
+        refresh_token: refresh_token.token,
         expires_in: ACCESS_TOKEN_VALIDITY_SECS,
         user: UserResponse::from(user),
     })
 }
 
-/// Extract nonce from SIWE message
-fn extract_nonce_from_message(message: &str) -> Option<String> {
-    for line in message.lines() {
-        if let Some(nonce) = line.strip_prefix("Nonce: ") {
-            return Some(nonce.trim().to_string());
+/// Map a rejected SIWE message to 400 (malformed or foreign) or 401 (outside
+/// its validity window)
+fn siwe_error_response(e: SiweError) -> HttpResponse {
+    match e {
+        SiweError::Malformed(_) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new("invalid_message", e.to_string()))
         }
+        SiweError::DomainMismatch(_) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new("domain_mismatch", e.to_string()))
+        }
+        SiweError::Expired => {
+            HttpResponse::Unauthorized().json(ErrorResponse::new("message_expired", e.to_string()))
+        }
+        SiweError::NotYetValid => HttpResponse::Unauthorized()
+            .json(ErrorResponse::new("message_not_yet_valid", e.to_string())),
     }
-    None
+}
+
+/// Reject chains users cannot sign in on with 400
+fn require_login_chain(wallet_service: &WalletService, chain_id: i32) -> Result<(), HttpResponse> {
+    if wallet_service.supports_login_chain(chain_id) {
+        Ok(())
+    } else {
+        Err(HttpResponse::BadRequest().json(ErrorResponse::new(
+            "unsupported_chain",
+            format!("Wallet login is not supported on chain {}", chain_id),
+        )))
+    }
+}
+
+/// User agent and IP address of the client, recorded with its session
+fn client_info(req: &HttpRequest) -> (Option<String>, Option<String>) {
+    let user_agent = req
+        .headers()
+        .get("user-agent")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let ip_address = req
+        .connection_info()
+        .realip_remote_addr()
+        .map(|s| s.to_string());
+    (user_agent, ip_address)
+}
+
+/// Validate the JWT from the Authorization header or auth-token cookie
+fn authenticated_claims(req: &HttpRequest, config: &Config) -> Result<Claims, HttpResponse> {
+    let Some(token) = extract_token(req) else {
+        return Err(HttpResponse::Unauthorized().json(ErrorResponse::new(
+            "unauthorized",
+            "Authentication required",
+        )));
+    };
+
+    decode::<Claims>(
+        &token,
+        &DecodingKey::from_secret(config.server.jwt_secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    )
+    .map(|data| data.claims)
+    .map_err(|e| {
+        tracing::debug!("Invalid JWT token: {}", e);
+        HttpResponse::Unauthorized().json(ErrorResponse::new(
+            "invalid_token",
+            "Invalid or expired token",
+        ))
+    })
 }
 
 /// Extract JWT token from Authorization header or auth-token cookie
@@ -0,0 +1,129 @@
+//! Entity Cache Admin Handlers
+//!
+//! Operator endpoint for dropping entries from the Redis entity cache, e.g.
+//! after editing rows directly in PostgreSQL. Like the other `/admin`
+//! endpoints it requires the `X-Admin-Token` header.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/admin/cache/flush` - Flush a namespace, an entity, or a key
+//!
+//! Flushing only removes cached copies; the next read repopulates them from
+//! the database.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::redis::cache::{
+    org_keys_pattern, trigger_keys_pattern, user_keys_pattern, EntityCache,
+};
+use shared::redis::RedisKey;
+
+use crate::{
+    handlers::helpers::{require_admin_token, validate_request},
+    models::{
+        CacheNamespace, ErrorResponse, FlushCacheRequest, FlushCacheResponse, SuccessResponse,
+    },
+};
+
+/// SCAN pattern for a namespace, or for one entity within it
+fn namespace_pattern(namespace: CacheNamespace, id: Option<&str>) -> String {
+    match (namespace, id) {
+        (CacheNamespace::Users, Some(id)) => user_keys_pattern(id),
+        (CacheNamespace::Organizations, Some(id)) => org_keys_pattern(id),
+        (CacheNamespace::Triggers, Some(id)) => trigger_keys_pattern(id),
+        (CacheNamespace::Users, None) => RedisKey::cache("user").part("*").into(),
+        (CacheNamespace::Organizations, None) => RedisKey::cache("org").part("*").into(),
+        (CacheNamespace::Triggers, None) => RedisKey::cache("trigger").part("*").into(),
+    }
+}
+
+/// Flush entries from the entity cache
+///
+/// Send `namespace` with an `id` to drop one entity's cached entries, or
+/// `namespace` alone (with `confirm` set to the namespace name) to drop the
+/// whole namespace. Send `key` instead to drop a single cache key.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/cache/flush",
+    tag = "Admin",
+    request_body = FlushCacheRequest,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Cache entries flushed", body = SuccessResponse<FlushCacheResponse>),
+        (status = 400, description = "Validation error or missing confirmation", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 503, description = "Entity cache unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn flush_cache(
+    req_http: HttpRequest,
+    req: web::Json<FlushCacheRequest>,
+) -> impl Responder {
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    let Some(cache) = req_http.app_data::<web::Data<EntityCache>>() else {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse::new(
+            "cache_unavailable",
+            "Entity cache is not configured",
+        ));
+    };
+
+    let (target, result) = match (req.namespace, &req.key) {
+        (Some(namespace), _) => {
+            let pattern = namespace_pattern(namespace, req.id.as_deref());
+            let result = cache.flush_pattern(&pattern).await;
+            (pattern, result)
+        }
+        (None, Some(key)) => (key.clone(), cache.flush_key(key).await),
+        // Rejected by validation
+        (None, None) => unreachable!("flush request without a target"),
+    };
+
+    let keys_deleted = match result {
+        Ok(count) => count,
+        Err(e) => {
+            tracing::error!(flush_target = %target, error = %e, "Cache flush failed");
+            return HttpResponse::ServiceUnavailable().json(ErrorResponse::new(
+                "cache_unavailable",
+                "Failed to flush cache",
+            ));
+        }
+    };
+
+    tracing::info!(
+        flush_target = %target,
+        keys_deleted = keys_deleted,
+        "Entity cache flushed"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(FlushCacheResponse {
+        target,
+        keys_deleted,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_patterns() {
+        assert_eq!(
+            namespace_pattern(CacheNamespace::Users, None),
+            "agentauri:cache:user:*"
+        );
+        assert_eq!(
+            namespace_pattern(CacheNamespace::Organizations, Some("org_1")),
+            org_keys_pattern("org_1")
+        );
+        assert_eq!(
+            namespace_pattern(CacheNamespace::Triggers, Some("t1")),
+            "agentauri:cache:trigger:*:t1"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Chain Query Handlers
+//!
+//! Read-only on-chain lookups through the RPC endpoints configured for
+//! [`WalletService`].
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/chains/{chain_id}/balances/{address}` - Native token balance
+//! - `GET /api/v1/chains/{chain_id}/transactions/{tx_hash}` - Transaction status
+//!
+//! # Authorization
+//!
+//! Both endpoints require JWT authentication. Balances are cached in Redis for
+//! a few seconds (see [`BalanceCache`]).
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    handlers::helpers::extract_user_id_or_unauthorized,
+    models::{ErrorResponse, SuccessResponse},
+    services::{BalanceCache, TransactionState, WalletError, WalletService},
+};
+
+// ============================================================================
+// Request/Response DTOs
+// ============================================================================
+
+/// Path parameters for a balance lookup
+#[derive(Debug, Deserialize)]
+pub struct BalancePath {
+    pub chain_id: i32,
+    pub address: String,
+}
+
+/// Path parameters for a transaction lookup
+#[derive(Debug, Deserialize)]
+pub struct TransactionPath {
+    pub chain_id: i32,
+    pub tx_hash: String,
+}
+
+/// Native token balance of an address
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BalanceResponse {
+    pub chain_id: i32,
+    pub address: String,
+    /// Balance in wei, as a decimal string (may exceed 64 bits)
+    pub balance_wei: String,
+}
+
+/// Status of a transaction
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransactionStatusResponse {
+    pub chain_id: i32,
+    pub tx_hash: String,
+    /// One of: not_found, pending, success, failed
+    pub status: String,
+    /// Block the transaction was mined in (success and failed only)
+    pub block_number: Option<u64>,
+}
+
+// ============================================================================
+// Chain Query Handlers
+// ============================================================================
+
+/// Get the native token balance of an address
+///
+/// GET /api/v1/chains/{chain_id}/balances/{address}
+#[utoipa::path(
+    get,
+    path = "/api/v1/chains/{chain_id}/balances/{address}",
+    tag = "Chains",
+    params(
+        ("chain_id" = i32, Path, description = "Chain ID"),
+        ("address" = String, Path, description = "0x-prefixed address")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Balance", body = BalanceResponse),
+        (status = 400, description = "Unsupported chain or invalid address", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 502, description = "RPC endpoint error", body = ErrorResponse)
+    )
+)]
+pub async fn get_balance(
+    wallet_service: web::Data<WalletService>,
+    req_http: HttpRequest,
+    path: web::Path<BalancePath>,
+) -> impl Responder {
+    if let Err(resp) = extract_user_id_or_unauthorized(&req_http) {
+        return resp;
+    }
+
+    let BalancePath { chain_id, address } = path.into_inner();
+    let cache = req_http.app_data::<web::Data<BalanceCache>>();
+
+    let cached = match cache {
+        Some(cache) => cache.get(chain_id, &address).await,
+        None => None,
+    };
+    let balance = match cached {
+        Some(balance) => balance,
+        None => match wallet_service.get_balance(chain_id, &address).await {
+            Ok(balance) => {
+                if let Some(cache) = cache {
+                    cache.set(chain_id, &address, balance).await;
+                }
+                balance
+            }
+            Err(e) => return chain_query_error(e),
+        },
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(BalanceResponse {
+        chain_id,
+        address,
+        balance_wei: balance.to_string(),
+    }))
+}
+
+/// Get the status of a transaction
+///
+/// GET /api/v1/chains/{chain_id}/transactions/{tx_hash}
+#[utoipa::path(
+    get,
+    path = "/api/v1/chains/{chain_id}/transactions/{tx_hash}",
+    tag = "Chains",
+    params(
+        ("chain_id" = i32, Path, description = "Chain ID"),
+        ("tx_hash" = String, Path, description = "0x-prefixed transaction hash")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Transaction status", body = TransactionStatusResponse),
+        (status = 400, description = "Unsupported chain or invalid hash", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 502, description = "RPC endpoint error", body = ErrorResponse)
+    )
+)]
+pub async fn get_transaction_status(
+    wallet_service: web::Data<WalletService>,
+    req_http: HttpRequest,
+    path: web::Path<TransactionPath>,
+) -> impl Responder {
+    if let Err(resp) = extract_user_id_or_unauthorized(&req_http) {
+        return resp;
+    }
+
+    let TransactionPath { chain_id, tx_hash } = path.into_inner();
+    let status = match wallet_service
+        .get_transaction_status(chain_id, &tx_hash)
+        .await
+    {
+        Ok(status) => status,
+        Err(e) => return chain_query_error(e),
+    };
+
+    let state = match status.state {
+        TransactionState::NotFound => "not_found",
+        TransactionState::Pending => "pending",
+        TransactionState::Success => "success",
+        TransactionState::Failed => "failed",
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(TransactionStatusResponse {
+        chain_id,
+        tx_hash,
+        status: state.to_string(),
+        block_number: status.block_number,
+    }))
+}
+
+/// Map a chain query error: bad input is 400, RPC failures are 502
+fn chain_query_error(e: WalletError) -> HttpResponse {
+    match e {
+        WalletError::UnsupportedChain(chain_id) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(
+                "unsupported_chain",
+                format!("Chain {} is not configured", chain_id),
+            ))
+        }
+        WalletError::InvalidAddress(msg) | WalletError::InvalidTransactionHash(msg) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new("validation_error", msg))
+        }
+        e => {
+            tracing::warn!(error = %e, "Chain query failed");
+            HttpResponse::BadGateway().json(ErrorResponse::new(
+                "rpc_error",
+                "The chain's RPC endpoints could not answer the query",
+            ))
+        }
+    }
+}
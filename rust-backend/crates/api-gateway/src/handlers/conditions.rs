@@ -1,7 +1,7 @@
 //! Trigger condition handlers
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use shared::DbPool;
+use shared::{ConditionType, DbPool};
 
 use crate::{
     handlers::helpers::{
@@ -9,12 +9,41 @@ use crate::{
     },
     middleware::{get_verified_organization_id, get_verified_organization_id_with_role},
     models::{
-        can_write, ConditionResponse, CreateConditionRequest, ErrorResponse, SuccessResponse,
-        UpdateConditionRequest,
+        can_write, ConditionResponse, ConditionValidationResponse, CreateConditionRequest,
+        ErrorResponse, SuccessResponse, UpdateConditionRequest,
     },
     repositories::{ConditionRepository, TriggerRepository},
 };
 
+/// Reject a `json_path` condition the event processor could not evaluate
+///
+/// Its path is parsed when the condition is saved, so a typo fails here
+/// rather than on every event. Other condition types are checked on request
+/// (see [`validate_condition`]).
+fn check_json_path(
+    condition_type: &str,
+    field: &str,
+    operator: &str,
+    value: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<(), HttpResponse> {
+    if condition_type != ConditionType::JsonPath.as_str() {
+        return Ok(());
+    }
+
+    let result = ConditionValidationResponse::check(condition_type, field, operator, value, config);
+    if result.valid {
+        return Ok(());
+    }
+
+    let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+    Err(HttpResponse::BadRequest().json(ErrorResponse::with_details(
+        "validation_error",
+        format!("Invalid condition: {}", messages.join("; ")),
+        serde_json::json!(result.errors),
+    )))
+}
+
 /// Create a new condition for a trigger
 ///
 /// Creates a new matching condition for the trigger. Requires write permission.
@@ -65,6 +94,15 @@ pub async fn create_condition(
     if let Err(resp) = validate_request(&*req) {
         return resp;
     }
+    if let Err(resp) = check_json_path(
+        &req.condition_type,
+        &req.field,
+        &req.operator,
+        &req.value,
+        req.config.as_ref(),
+    ) {
+        return resp;
+    }
 
     // Check if trigger belongs to the organization
     let belongs = match handle_db_error(
@@ -250,6 +288,41 @@ pub async fn update_condition(
             .json(ErrorResponse::new("not_found", "Condition not found"));
     }
 
+    // Check a JSONPath condition as it will be after the update
+    if req.condition_type.is_some()
+        || req.field.is_some()
+        || req.operator.is_some()
+        || req.value.is_some()
+    {
+        let existing = match handle_db_error(
+            ConditionRepository::find_by_id(&pool, condition_id).await,
+            "get condition",
+        ) {
+            Ok(Some(condition)) => condition,
+            Ok(None) => {
+                return HttpResponse::NotFound()
+                    .json(ErrorResponse::new("not_found", "Condition not found"));
+            }
+            Err(resp) => return resp,
+        };
+
+        let existing_value = match &existing.value {
+            serde_json::Value::String(value) => value.clone(),
+            other => other.to_string(),
+        };
+        if let Err(resp) = check_json_path(
+            req.condition_type
+                .as_deref()
+                .unwrap_or(&existing.condition_type),
+            req.field.as_deref().unwrap_or(&existing.field),
+            req.operator.as_deref().unwrap_or(&existing.operator),
+            req.value.as_deref().unwrap_or(&existing_value),
+            req.config.as_ref().or(existing.config.as_ref()),
+        ) {
+            return resp;
+        }
+    }
+
     // Update condition
     let condition = match handle_db_error(
         ConditionRepository::update(
@@ -363,3 +436,42 @@ pub async fn delete_condition(
 
     HttpResponse::NoContent().finish()
 }
+
+/// Validate a condition
+///
+/// Checks a condition against the event fields and the rules of its condition
+/// type (fields, operators, value type) without saving it. Returns 200 with
+/// the problems found, so an invalid condition is not an error response.
+#[utoipa::path(
+    post,
+    path = "/api/v1/conditions/validate",
+    tag = "Conditions",
+    request_body = CreateConditionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Validation result", body = SuccessResponse<ConditionValidationResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    )
+)]
+pub async fn validate_condition(
+    req_http: HttpRequest,
+    req: web::Json<CreateConditionRequest>,
+) -> impl Responder {
+    if let Err(resp) = extract_user_id_or_unauthorized(&req_http) {
+        return resp;
+    }
+
+    // Validate request
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    HttpResponse::Ok().json(SuccessResponse::new(ConditionValidationResponse::check(
+        &req.condition_type,
+        &req.field,
+        &req.operator,
+        &req.value,
+        req.config.as_ref(),
+    )))
+}
@@ -3,20 +3,42 @@
 //! Implements the /.well-known/ endpoints for system discoverability and security:
 //! - `/.well-known/agent.json` - Agent Card for API discovery
 //! - `/.well-known/security.txt` - Security contact information (RFC 9116)
+//!
+//! `GET /api/v1/discovery` searches the indexed agents themselves (see
+//! [`crate::repositories::agent_directory`] for how they are ranked).
 
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpResponse, Responder, Result};
 use chrono::Utc;
 use redis::AsyncCommands;
+use serde::Deserialize;
+use shared::redis::RedisKey;
+use shared::DbPools;
 use tracing::{debug, error, info, instrument};
+use utoipa::IntoParams;
 
+use crate::handlers::helpers::bad_request;
+use crate::handlers::ponder::get_ponder_namespace;
 use crate::models::discovery::{
-    AgentCardResponse, Authentication, AuthenticationEndpoints, Capabilities, ChainInfo, Contact,
-    Endpoints, PullLayer, PushLayer, RateLimitTier, RateLimiting,
+    AgentCardResponse, AgentDiscoveryResponse, Authentication, AuthenticationEndpoints,
+    Capabilities, ChainInfo, Contact, DiscoveredAgent, Endpoints, PullLayer, PushLayer,
+    RateLimitTier, RateLimiting,
+};
+use crate::models::ErrorResponse;
+use crate::repositories::{
+    AgentCursor, AgentDirectoryRepository, AgentDirectoryRow, AgentSearch, AgentSort,
 };
 
-const CACHE_KEY: &str = "discovery:agent_card";
 const CACHE_TTL_SECONDS: u64 = 3600; // 1 hour
 
+/// Page size when `limit` is omitted
+const DEFAULT_DISCOVERY_LIMIT: i64 = 20;
+
+/// Largest accepted `limit`
+pub const MAX_DISCOVERY_LIMIT: i64 = 100;
+
+/// Maximum length of the `q` search term
+const MAX_QUERY_LEN: usize = 200;
+
 /// GET /.well-known/agent.json
 ///
 /// Returns the Agent Card with system metadata and capabilities.
@@ -62,11 +84,16 @@ pub async fn get_agent_card(config: web::Data<shared::Config>) -> Result<HttpRes
     Ok(build_response(json_body))
 }
 
+/// Redis key for the cached Agent Card
+fn cache_key() -> RedisKey {
+    RedisKey::cache("discovery").part("agent_card")
+}
+
 /// Try to get cached Agent Card from Redis
 async fn try_get_from_cache(redis_url: &str) -> anyhow::Result<Option<String>> {
     let client = redis::Client::open(redis_url)?;
     let mut conn = client.get_multiplexed_async_connection().await?;
-    let cached: Option<String> = conn.get(CACHE_KEY).await?;
+    let cached: Option<String> = conn.get(cache_key()).await?;
     Ok(cached)
 }
 
@@ -74,7 +101,9 @@ async fn try_get_from_cache(redis_url: &str) -> anyhow::Result<Option<String>> {
 async fn try_set_cache(redis_url: &str, json_body: &str) -> anyhow::Result<()> {
     let client = redis::Client::open(redis_url)?;
     let mut conn = client.get_multiplexed_async_connection().await?;
-    let _: () = conn.set_ex(CACHE_KEY, json_body, CACHE_TTL_SECONDS).await?;
+    let _: () = conn
+        .set_ex(cache_key(), json_body, CACHE_TTL_SECONDS)
+        .await?;
     Ok(())
 }
 
@@ -310,6 +339,170 @@ pub async fn get_security_txt() -> Result<HttpResponse> {
         .body(content))
 }
 
+/// Query parameters for `GET /discovery`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiscoveryQuery {
+    /// Full-text search over agent name and description (web search syntax:
+    /// quoted phrases, `or`, `-excluded`)
+    pub q: Option<String>,
+    /// `relevance` (default), `followers` or `recent`
+    pub sort: Option<String>,
+    /// Items per page (default: 20, max: 100)
+    #[param(minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+    /// Cursor from a previous page's `next_cursor` (same `sort`)
+    pub cursor: Option<String>,
+}
+
+impl DiscoveryQuery {
+    /// Validate the parameters; `now` is the recency reference for a first page
+    fn to_search(&self, now: i64) -> Result<AgentSearch, String> {
+        let query = self
+            .q
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .map(str::to_string);
+        if query
+            .as_ref()
+            .is_some_and(|q| q.chars().count() > MAX_QUERY_LEN)
+        {
+            return Err(format!("q must be at most {} characters", MAX_QUERY_LEN));
+        }
+
+        let sort = match self.sort.as_deref() {
+            None | Some("") => AgentSort::default(),
+            Some(sort) => {
+                AgentSort::parse(sort).ok_or("sort must be one of: relevance, followers, recent")?
+            }
+        };
+
+        let limit = self.limit.unwrap_or(DEFAULT_DISCOVERY_LIMIT);
+        if !(1..=MAX_DISCOVERY_LIMIT).contains(&limit) {
+            return Err(format!(
+                "limit must be between 1 and {}",
+                MAX_DISCOVERY_LIMIT
+            ));
+        }
+
+        let after = match &self.cursor {
+            Some(cursor) => {
+                let cursor = AgentCursor::decode(cursor).ok_or("Invalid cursor")?;
+                if cursor.sort != sort {
+                    return Err("cursor was issued for a different sort".to_string());
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        Ok(AgentSearch {
+            query,
+            sort,
+            as_of: after.as_ref().map_or(now, |c| c.as_of),
+            after,
+            limit,
+        })
+    }
+}
+
+impl From<AgentDirectoryRow> for DiscoveredAgent {
+    fn from(row: AgentDirectoryRow) -> Self {
+        Self {
+            chain_id: row.chain_id,
+            agent_id: row.agent_id,
+            name: row.name,
+            description: row.description,
+            agent_uri: row.agent_uri,
+            followers: row.followers,
+            last_activity_at: row.last_activity,
+            score: row.score,
+        }
+    }
+}
+
+/// Search indexed agents
+///
+/// Public. Lists agents seen by the indexer, optionally matching a full-text
+/// query over their on-chain name and description, ranked by relevance,
+/// follower count or recent activity. Follow `next_cursor` for further
+/// pages.
+#[utoipa::path(
+    get,
+    path = "/api/v1/discovery",
+    tag = "Discovery",
+    params(DiscoveryQuery),
+    responses(
+        (status = 200, description = "Matching agents, best first", body = AgentDiscoveryResponse),
+        (status = 400, description = "Invalid query, sort, limit or cursor", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn search_agents(
+    pools: web::Data<DbPools>,
+    query: web::Query<DiscoveryQuery>,
+) -> impl Responder {
+    let search = match query.to_search(Utc::now().timestamp()) {
+        Ok(search) => search,
+        Err(message) => return bad_request(&message),
+    };
+    let empty = || {
+        HttpResponse::Ok().json(AgentDiscoveryResponse {
+            data: vec![],
+            next_cursor: None,
+        })
+    };
+
+    let read_pool = pools.read();
+    let namespace = get_ponder_namespace(read_pool).await;
+    if namespace.is_empty() {
+        return empty();
+    }
+
+    // One extra row tells whether another page follows
+    let page = AgentSearch {
+        limit: search.limit + 1,
+        ..search.clone()
+    };
+    let mut rows = match pools
+        .timed(
+            "search_agents",
+            AgentDirectoryRepository::search(read_pool, &namespace, &page),
+        )
+        .await
+    {
+        Ok(rows) => rows,
+        // The indexer may not have created its tables yet
+        Err(e) if format!("{:#}", e).contains("does not exist") => return empty(),
+        Err(e) => {
+            error!("Failed to search agents: {:#}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to search agents",
+            ));
+        }
+    };
+
+    let has_more = rows.len() as i64 > search.limit;
+    rows.truncate(search.limit as usize);
+    let next_cursor = rows.last().filter(|_| has_more).map(|last| {
+        AgentCursor {
+            sort: search.sort,
+            as_of: search.as_of,
+            score: last.score,
+            chain_id: last.chain_id,
+            agent_id: last.agent_id.clone(),
+        }
+        .encode()
+    });
+
+    HttpResponse::Ok().json(AgentDiscoveryResponse {
+        data: rows.into_iter().map(DiscoveredAgent::from).collect(),
+        next_cursor,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,4 +686,78 @@ mod tests {
         assert!(json.contains("Linea Sepolia"));
         assert!(json.contains("Polygon Amoy"));
     }
+
+    #[test]
+    fn test_discovery_query_defaults() {
+        let search = DiscoveryQuery {
+            q: Some("  ".to_string()),
+            ..Default::default()
+        }
+        .to_search(1_700_000_000)
+        .unwrap();
+        assert_eq!(search.query, None);
+        assert_eq!(search.sort, AgentSort::Relevance);
+        assert_eq!(search.limit, DEFAULT_DISCOVERY_LIMIT);
+        assert_eq!(search.as_of, 1_700_000_000);
+        assert!(search.after.is_none());
+    }
+
+    #[test]
+    fn test_discovery_query_cursor_keeps_reference_time() {
+        let cursor = AgentCursor {
+            sort: AgentSort::Followers,
+            as_of: 1_600_000_000,
+            score: 3.0,
+            chain_id: 84532,
+            agent_id: "42".to_string(),
+        };
+        let query = DiscoveryQuery {
+            sort: Some("followers".to_string()),
+            cursor: Some(cursor.encode()),
+            ..Default::default()
+        };
+
+        let search = query.to_search(1_700_000_000).unwrap();
+        assert_eq!(search.as_of, 1_600_000_000);
+        assert_eq!(search.after, Some(cursor));
+    }
+
+    #[test]
+    fn test_discovery_query_rejects_invalid_params() {
+        let followers_cursor = AgentCursor {
+            sort: AgentSort::Followers,
+            as_of: 1,
+            score: 3.0,
+            chain_id: 84532,
+            agent_id: "42".to_string(),
+        }
+        .encode();
+
+        for query in [
+            DiscoveryQuery {
+                sort: Some("popular".to_string()),
+                ..Default::default()
+            },
+            DiscoveryQuery {
+                limit: Some(MAX_DISCOVERY_LIMIT + 1),
+                ..Default::default()
+            },
+            DiscoveryQuery {
+                q: Some("x".repeat(MAX_QUERY_LEN + 1)),
+                ..Default::default()
+            },
+            DiscoveryQuery {
+                cursor: Some("garbage".to_string()),
+                ..Default::default()
+            },
+            // A cursor only continues the sort it was issued for
+            DiscoveryQuery {
+                sort: Some("recent".to_string()),
+                cursor: Some(followers_cursor),
+                ..Default::default()
+            },
+        ] {
+            assert!(query.to_search(0).is_err(), "{:?}", query);
+        }
+    }
 }
@@ -0,0 +1,250 @@
+//! Dead Letter Queue Handlers
+//!
+//! Lets organization admins see why action jobs failed after all retries, and
+//! replay them, without access to Redis (see [`shared::dlq`]). Entries are
+//! scoped to the organization in `X-Organization-ID`: one organization never
+//! sees or replays another's jobs.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/dlq` - List failed jobs, newest first (admin+)
+//! - `POST /api/v1/dlq/{id}/replay` - Requeue one failed job (admin+)
+
+use std::collections::HashSet;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::{DbPool, DlqAccessor, DlqEntry};
+
+use crate::{
+    handlers::helpers::{extract_user_id_or_unauthorized, forbidden, handle_db_error},
+    middleware::get_verified_organization_id_with_role,
+    models::{
+        can_manage_org, DlqEntryResponse, ErrorResponse, PaginatedResponse, PaginationMeta,
+        PaginationParams, SuccessResponse,
+    },
+    repositories::TriggerRepository,
+    services::ActionJobQueue,
+};
+
+/// The DLQ entries an organization may access
+struct OrgScope {
+    organization_id: String,
+    /// The organization's triggers, for entries from jobs without
+    /// `organization_id` (enqueued by older producers)
+    trigger_ids: HashSet<String>,
+}
+
+impl OrgScope {
+    fn contains(&self, entry: &DlqEntry) -> bool {
+        match &entry.job.organization_id {
+            Some(org) => *org == self.organization_id,
+            None => self.trigger_ids.contains(&entry.job.trigger_id),
+        }
+    }
+}
+
+/// Check the caller is an owner or admin of the organization and load its scope
+async fn require_dlq_admin(
+    req_http: &HttpRequest,
+    pool: &DbPool,
+) -> Result<OrgScope, HttpResponse> {
+    let user_id = extract_user_id_or_unauthorized(req_http)?;
+    let (organization_id, role) =
+        get_verified_organization_id_with_role(req_http, pool, &user_id).await?;
+    if !can_manage_org(&role) {
+        return Err(forbidden(
+            "Only organization admins can access the dead letter queue",
+        ));
+    }
+
+    let trigger_ids = handle_db_error(
+        TriggerRepository::list_ids_by_organization(pool, &organization_id).await,
+        "list trigger IDs",
+    )?;
+
+    Ok(OrgScope {
+        organization_id,
+        trigger_ids: trigger_ids.into_iter().collect(),
+    })
+}
+
+fn dlq_unavailable(error: &shared::Error) -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(
+        ErrorResponse::new("service_unavailable", "Dead letter queue unavailable")
+            .with_code(error.code()),
+    )
+}
+
+/// List the organization's failed action jobs
+///
+/// Jobs land here after exhausting their retries. Each entry has the error
+/// from the last attempt, the attempt count and when it failed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dlq",
+    tag = "Dead Letter Queue",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items per page"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Failed jobs, newest first", body = PaginatedResponse<DlqEntryResponse>),
+        (status = 400, description = "Invalid pagination", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin role required", body = ErrorResponse),
+        (status = 503, description = "Dead letter queue unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn list_dlq_entries(
+    pool: web::Data<DbPool>,
+    dlq: web::Data<DlqAccessor>,
+    req_http: HttpRequest,
+    query: web::Query<PaginationParams>,
+) -> impl Responder {
+    let scope = match require_dlq_admin(&req_http, &pool).await {
+        Ok(scope) => scope,
+        Err(resp) => return resp,
+    };
+
+    if let Err(e) = query.validate() {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            format!("Invalid pagination: {}", e),
+        ));
+    }
+
+    let page = match dlq
+        .list(
+            |entry| scope.contains(entry),
+            query.offset as usize,
+            query.limit as usize,
+        )
+        .await
+    {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to list DLQ entries");
+            return dlq_unavailable(&e);
+        }
+    };
+
+    HttpResponse::Ok().json(PaginatedResponse {
+        data: page
+            .entries
+            .into_iter()
+            .map(DlqEntryResponse::from)
+            .collect(),
+        pagination: PaginationMeta::new(page.total as i64, query.limit, query.offset),
+    })
+}
+
+/// Replay a failed action job
+///
+/// Removes the entry from the dead letter queue and requeues the job for the
+/// action workers, even if an equivalent job ran recently. Fix the cause of
+/// the failure first, or the job will land here again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/dlq/{id}/replay",
+    tag = "Dead Letter Queue",
+    params(
+        ("id" = String, Path, description = "DLQ entry ID (the failed job's ID)")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 202, description = "Job requeued", body = SuccessResponse<DlqEntryResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin role required", body = ErrorResponse),
+        (status = 404, description = "DLQ entry not found", body = ErrorResponse),
+        (status = 503, description = "Dead letter queue unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn replay_dlq_entry(
+    pool: web::Data<DbPool>,
+    dlq: web::Data<DlqAccessor>,
+    queue: web::Data<ActionJobQueue>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let entry_id = path.into_inner();
+
+    let scope = match require_dlq_admin(&req_http, &pool).await {
+        Ok(scope) => scope,
+        Err(resp) => return resp,
+    };
+
+    let entry = match dlq
+        .replay(
+            &entry_id,
+            |entry| scope.contains(entry),
+            |job| queue.lane(job),
+        )
+        .await
+    {
+        Ok(Some(entry)) => entry,
+        // Other organizations' entries are reported as missing too
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "DLQ entry not found"));
+        }
+        Err(e) => {
+            tracing::error!(entry_id = %entry_id, error = %e, "Failed to replay DLQ entry");
+            return dlq_unavailable(&e);
+        }
+    };
+
+    tracing::info!(
+        organization_id = %scope.organization_id,
+        job_id = %entry.job.id,
+        trigger_id = %entry.job.trigger_id,
+        action_type = %entry.job.action_type,
+        "DLQ entry replayed"
+    );
+
+    HttpResponse::Accepted().json(SuccessResponse::new(DlqEntryResponse::from(entry)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{ActionJob, ActionType};
+
+    fn entry(organization_id: Option<&str>, trigger_id: &str) -> DlqEntry {
+        let mut job = ActionJob::new(
+            trigger_id,
+            "event-1",
+            ActionType::Rest,
+            1,
+            serde_json::json!({"url": "https://example.com/hook"}),
+            serde_json::json!({}),
+        );
+        job.organization_id = organization_id.map(String::from);
+        DlqEntry::new(job, "HTTP 500".to_string(), 3)
+    }
+
+    fn scope() -> OrgScope {
+        OrgScope {
+            organization_id: "org_a".to_string(),
+            trigger_ids: ["trigger_a".to_string()].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_scope_matches_own_entries_only() {
+        let scope = scope();
+
+        assert!(scope.contains(&entry(Some("org_a"), "trigger_a")));
+        assert!(!scope.contains(&entry(Some("org_b"), "trigger_b")));
+        // The job's organization wins over the trigger lookup
+        assert!(!scope.contains(&entry(Some("org_b"), "trigger_a")));
+    }
+
+    #[test]
+    fn test_scope_attributes_legacy_entries_by_trigger() {
+        let scope = scope();
+
+        assert!(scope.contains(&entry(None, "trigger_a")));
+        assert!(!scope.contains(&entry(None, "trigger_b")));
+    }
+}
@@ -1,26 +1,55 @@
 //! Event handlers for querying blockchain events
 //!
 //! Provides endpoints to query and filter events indexed by Ponder.
+//!
+//! `GET /events` lists every indexed event by default. Passing
+//! `organization_id` or `trigger_id` narrows the list to events that ran an
+//! action of that organization's triggers; the caller must be a member of the
+//! organization. Pages are either offset-based (`page`) or follow the opaque
+//! `next_cursor` returned with each page (`cursor`).
+//!
+//! `POST /events/ingest` lets an indexer push a signed event instead of
+//! writing it to the database.
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
-use shared::DbPool;
+use shared::signing::{SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use shared::{DbPool, DbPools};
 use utoipa::{IntoParams, ToSchema};
 
-use crate::handlers::helpers::extract_user_id_or_unauthorized;
+use crate::handlers::helpers::{
+    bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, require_found,
+    unauthorized, validate_request,
+};
 use crate::middleware::get_verified_organization_id;
-use crate::models::{ErrorResponse, PaginationMeta};
+use crate::models::{ErrorResponse, IngestEventRequest, IngestEventResponse, PaginationMeta};
+use crate::repositories::{
+    EventCursor, EventListFilter, EventRepository, EventRow, MemberRepository, PonderRepository,
+    TriggerRepository,
+};
+use crate::services::{EventIngestVerifier, SOURCE_HEADER};
+
+/// Page size when `limit` is omitted
+const DEFAULT_EVENTS_LIMIT: i64 = 20;
+
+/// Largest accepted `limit`
+pub const MAX_EVENTS_LIMIT: i64 = 100;
+
+/// Widest accepted `from`..`to` window (90 days)
+pub const MAX_EVENTS_WINDOW_SECS: i64 = 90 * 24 * 60 * 60;
 
 /// Query parameters for listing events
-#[derive(Debug, Deserialize, IntoParams)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct EventsQuery {
-    /// Page number (1-indexed)
+    /// Page number (1-indexed); cannot be combined with `cursor`
     #[param(minimum = 1)]
     pub page: Option<i64>,
     /// Items per page (default: 20, max: 100)
     #[param(minimum = 1, maximum = 100)]
     pub limit: Option<i64>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
     /// Filter by chain ID
     #[serde(rename = "chainId")]
     pub chain_id: Option<i64>,
@@ -34,6 +63,84 @@ pub struct EventsQuery {
     pub agent_id: Option<String>,
     /// Search in event data
     pub search: Option<String>,
+    /// Only events that ran an action of this trigger
+    pub trigger_id: Option<String>,
+    /// Only events that ran an action of this organization's triggers
+    pub organization_id: Option<String>,
+    /// Earliest event timestamp (Unix seconds, inclusive)
+    #[param(minimum = 0)]
+    pub from: Option<i64>,
+    /// Latest event timestamp (Unix seconds, inclusive); requires `from`
+    #[param(minimum = 0)]
+    pub to: Option<i64>,
+}
+
+/// Validated paging and filters for `GET /events`
+#[derive(Debug)]
+struct ListEventsParams {
+    filter: EventListFilter,
+    limit: i64,
+    offset: i64,
+    cursor: Option<EventCursor>,
+}
+
+impl EventsQuery {
+    /// Validate the parameters; `now` bounds a window without `to`
+    fn to_params(&self, now: i64) -> Result<ListEventsParams, String> {
+        let limit = self.limit.unwrap_or(DEFAULT_EVENTS_LIMIT);
+        if !(1..=MAX_EVENTS_LIMIT).contains(&limit) {
+            return Err(format!("limit must be between 1 and {}", MAX_EVENTS_LIMIT));
+        }
+
+        let cursor = match &self.cursor {
+            Some(_) if self.page.is_some() => {
+                return Err("page and cursor cannot be combined".to_string());
+            }
+            Some(cursor) => Some(EventCursor::decode(cursor).ok_or("Invalid cursor")?),
+            None => None,
+        };
+        let page = self.page.unwrap_or(1);
+        if page < 1 {
+            return Err("page must be at least 1".to_string());
+        }
+
+        if self.from.is_some_and(|t| t < 0) || self.to.is_some_and(|t| t < 0) {
+            return Err("from and to must not be negative".to_string());
+        }
+        match (self.from, self.to) {
+            (None, Some(_)) => return Err("to requires from".to_string()),
+            (Some(from), to) => {
+                let to = to.unwrap_or(now);
+                if from > to {
+                    return Err("from must not be after to".to_string());
+                }
+                if to - from > MAX_EVENTS_WINDOW_SECS {
+                    return Err(format!(
+                        "Time window must not exceed {} days",
+                        MAX_EVENTS_WINDOW_SECS / 86_400
+                    ));
+                }
+            }
+            (None, None) => {}
+        }
+
+        Ok(ListEventsParams {
+            filter: EventListFilter {
+                chain_id: self.chain_id,
+                registry: self.registry.clone(),
+                event_type: self.event_type.clone(),
+                agent_id: self.agent_id.clone(),
+                search: self.search.clone(),
+                trigger_id: self.trigger_id.clone(),
+                organization_id: self.organization_id.clone(),
+                from_timestamp: self.from,
+                to_timestamp: self.to,
+            },
+            limit,
+            offset: (page - 1) * limit,
+            cursor,
+        })
+    }
 }
 
 /// Event response item
@@ -62,6 +169,23 @@ pub struct EventResponse {
     pub data: serde_json::Value,
 }
 
+impl From<EventRow> for EventResponse {
+    fn from(row: EventRow) -> Self {
+        Self {
+            id: row.id,
+            chain_id: row.chain_id,
+            block_number: row.block_number,
+            tx_hash: row.tx_hash,
+            log_index: row.log_index,
+            event_type: row.event_type,
+            registry: row.registry,
+            agent_id: row.agent_id,
+            timestamp: row.timestamp,
+            data: row.event_data,
+        }
+    }
+}
+
 /// Paginated events response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PaginatedEventsResponse {
@@ -69,6 +193,18 @@ pub struct PaginatedEventsResponse {
     pub data: Vec<EventResponse>,
     /// Pagination metadata
     pub pagination: PaginationMeta,
+    /// Pass as `cursor` to fetch the next page; null on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl PaginatedEventsResponse {
+    fn empty(limit: i64, offset: i64) -> Self {
+        Self {
+            data: vec![],
+            pagination: PaginationMeta::new(0, limit, offset),
+            next_cursor: None,
+        }
+    }
 }
 
 /// Get the active Ponder namespace from the database
@@ -103,6 +239,8 @@ async fn get_ponder_namespace(pool: &sqlx::PgPool) -> String {
 /// List blockchain events
 ///
 /// Returns paginated list of indexed blockchain events with optional filters.
+/// `organization_id` and `trigger_id` restrict the list to events that ran
+/// one of that organization's (or trigger's) actions.
 #[utoipa::path(
     get,
     path = "/api/v1/events",
@@ -111,12 +249,16 @@ async fn get_ponder_namespace(pool: &sqlx::PgPool) -> String {
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 200, description = "List of events", body = PaginatedEventsResponse),
+        (status = 400, description = "Invalid filter, window or cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Trigger not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn list_events(
     pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
     req_http: HttpRequest,
     query: web::Query<EventsQuery>,
 ) -> impl Responder {
@@ -127,223 +269,291 @@ pub async fn list_events(
     };
 
     // Verify organization membership
-    let _organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+    let header_org_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
         Ok(id) => id,
         Err(response) => return response,
     };
 
-    // Parse pagination
-    let page = query.page.unwrap_or(1).max(1);
-    let limit = query.limit.unwrap_or(20).clamp(1, 100);
-    let offset = (page - 1) * limit;
+    let params = match query.to_params(chrono::Utc::now().timestamp()) {
+        Ok(params) => params,
+        Err(message) => return bad_request(&message),
+    };
+    let (limit, offset) = (params.limit, params.offset);
+
+    // Scoping to another organization requires membership there too
+    let scope_org_id = match &query.organization_id {
+        Some(org_id) if *org_id != header_org_id => {
+            match handle_db_error(
+                MemberRepository::is_member(&pool, org_id, &user_id).await,
+                "verify organization access",
+            ) {
+                Ok(true) => org_id.clone(),
+                Ok(false) => return forbidden("Not a member of the specified organization"),
+                Err(resp) => return resp,
+            }
+        }
+        Some(org_id) => org_id.clone(),
+        None => header_org_id,
+    };
+
+    // Another organization's trigger is reported as missing
+    if let Some(trigger_id) = &query.trigger_id {
+        if let Err(resp) = handle_db_error(
+            TriggerRepository::find_by_id(&pool, trigger_id).await,
+            "fetch trigger",
+        )
+        .and_then(|t| require_found(t.filter(|t| t.organization_id == scope_org_id), "Trigger"))
+        {
+            return resp;
+        }
+    }
+
+    // Event queries are read-only, so serve them from the read replica
+    let read_pool = pools.read();
 
     // Get Ponder namespace
-    let namespace = get_ponder_namespace(pool.get_ref()).await;
+    let namespace = get_ponder_namespace(read_pool).await;
 
     if namespace.is_empty() {
-        return HttpResponse::Ok().json(PaginatedEventsResponse {
-            data: vec![],
-            pagination: PaginationMeta::new(0, limit, offset),
-        });
+        return HttpResponse::Ok().json(PaginatedEventsResponse::empty(limit, offset));
     }
 
-    // Build dynamic WHERE clause
-    let mut conditions: Vec<String> = vec![];
-    let mut param_index = 1;
+    let total = match pools
+        .timed(
+            "count_events",
+            PonderRepository::count_listed_events(read_pool, &namespace, &params.filter),
+        )
+        .await
+    {
+        Ok(count) => count,
+        // The indexer may not have created its tables yet
+        Err(e) if format!("{:#}", e).contains("does not exist") => {
+            return HttpResponse::Ok().json(PaginatedEventsResponse::empty(limit, offset));
+        }
+        Err(e) => {
+            tracing::error!("Failed to count events: {:#}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to query events",
+            ));
+        }
+    };
 
-    if query.chain_id.is_some() {
-        conditions.push(format!(r#""chainId"::bigint = ${}"#, param_index));
-        param_index += 1;
-    }
+    // One extra row tells whether another page follows
+    let mut rows = match pools
+        .timed(
+            "list_events",
+            PonderRepository::list_events(
+                read_pool,
+                &namespace,
+                &params.filter,
+                params.cursor.as_ref(),
+                limit + 1,
+                offset,
+            ),
+        )
+        .await
+    {
+        Ok(rows) => rows,
+        Err(e) if format!("{:#}", e).contains("does not exist") => {
+            return HttpResponse::Ok().json(PaginatedEventsResponse::empty(limit, offset));
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch events: {:#}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to query events",
+            ));
+        }
+    };
 
-    if query.registry.is_some() {
-        conditions.push(format!(r#""registry" = ${}"#, param_index));
-        param_index += 1;
-    }
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    let next_cursor = rows.last().filter(|_| has_more).map(|last| {
+        EventCursor {
+            timestamp: last.timestamp,
+            block_number: last.block_number,
+            id: last.id.clone(),
+        }
+        .encode()
+    });
 
-    if query.event_type.is_some() {
-        conditions.push(format!(r#""eventType" = ${}"#, param_index));
-        param_index += 1;
-    }
+    let mut pagination = PaginationMeta::new(total, limit, offset);
+    pagination.has_more = has_more;
 
-    if query.agent_id.is_some() {
-        conditions.push(format!(r#""agentId" = ${}"#, param_index));
-        param_index += 1;
-    }
+    HttpResponse::Ok().json(PaginatedEventsResponse {
+        data: rows.into_iter().map(EventResponse::from).collect(),
+        pagination,
+        next_cursor,
+    })
+}
 
-    if query.search.is_some() {
-        conditions.push(format!(
-            r#"("eventType" ILIKE ${} OR "agentId" ILIKE ${} OR "registry" ILIKE ${})"#,
-            param_index,
-            param_index + 1,
-            param_index + 2
-        ));
-        param_index += 3;
+/// Push an event from an indexer
+///
+/// Alternative to the indexer writing to the database: the request must be
+/// signed with the source's secret (see [`crate::services::event_ingest`]).
+/// The event is stored in `events`, whose insert trigger sends the same
+/// `new_event` NOTIFY the event-processor listens on. Pushing an event that
+/// is already stored is a no-op.
+#[utoipa::path(
+    post,
+    path = "/api/v1/events/ingest",
+    tag = "Events",
+    request_body = IngestEventRequest,
+    params(
+        ("X-AgentAuri-Source" = String, Header, description = "Source whose secret signed the request"),
+        ("X-AgentAuri-Timestamp" = i64, Header, description = "Unix timestamp covered by the signature"),
+        ("X-AgentAuri-Signature" = String, Header, description = "sha256=<hex HMAC-SHA256 of \"{timestamp}.{body}\">")
+    ),
+    responses(
+        (status = 201, description = "Event stored", body = IngestEventResponse),
+        (status = 200, description = "Event was already stored", body = IngestEventResponse),
+        (status = 400, description = "Invalid event", body = ErrorResponse),
+        (status = 401, description = "Missing, stale or mismatched signature", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn ingest_event(
+    pool: web::Data<DbPool>,
+    verifier: web::Data<EventIngestVerifier>,
+    req_http: HttpRequest,
+    body: web::Bytes,
+) -> impl Responder {
+    let header = |name: &str| req_http.headers().get(name).and_then(|h| h.to_str().ok());
+    let source = header(SOURCE_HEADER);
+
+    if let Err(rejection) = verifier.verify(
+        source,
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        &body,
+        chrono::Utc::now().timestamp(),
+    ) {
+        tracing::warn!(
+            source = source.unwrap_or("-"),
+            reason = rejection.as_str(),
+            "Pushed event rejected"
+        );
+        return unauthorized("Missing or invalid event signature");
     }
 
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
+    let event: IngestEventRequest = match serde_json::from_slice(&body) {
+        Ok(event) => event,
+        Err(e) => return bad_request(&format!("Invalid event: {}", e)),
     };
+    if let Err(resp) = validate_request(&event) {
+        return resp;
+    }
 
-    // Count query
-    let count_query = format!(
-        r#"SELECT COUNT(*)::bigint FROM public."{namespace}__Event" {where_clause}"#,
-        namespace = namespace,
-        where_clause = where_clause
-    );
+    let created = match handle_db_error(
+        EventRepository::insert_if_absent(&pool, &event).await,
+        "store event",
+    ) {
+        Ok(created) => created,
+        Err(resp) => return resp,
+    };
 
-    // Data query
-    let data_query = format!(
-        r#"
-        SELECT
-            id,
-            "chainId"::bigint as chain_id,
-            block_number::bigint,
-            COALESCE("transactionHash", '') as tx_hash,
-            COALESCE(log_index, 0)::bigint as log_index,
-            "eventType",
-            COALESCE("registry", 'unknown') as registry,
-            "agentId",
-            "timestamp"::bigint,
-            COALESCE("eventData", '{{}}'::jsonb) as event_data
-        FROM public."{namespace}__Event"
-        {where_clause}
-        ORDER BY "timestamp" DESC, block_number DESC
-        LIMIT ${limit_param} OFFSET ${offset_param}
-        "#,
-        namespace = namespace,
-        where_clause = where_clause,
-        limit_param = param_index,
-        offset_param = param_index + 1
+    tracing::info!(
+        source = source.unwrap_or("-"),
+        event_id = %event.id,
+        created = created,
+        "Pushed event ingested"
     );
 
-    // Build queries with dynamic bindings
-    let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query);
-    let mut data_builder = sqlx::query_as::<
-        _,
-        (
-            String,
-            i64,
-            i64,
-            String,
-            i64,
-            String,
-            String,
-            Option<String>,
-            i64,
-            serde_json::Value,
-        ),
-    >(&data_query);
-
-    // Bind parameters in order
-    if let Some(chain_id) = query.chain_id {
-        count_builder = count_builder.bind(chain_id);
-        data_builder = data_builder.bind(chain_id);
+    let response = IngestEventResponse {
+        event_id: event.id,
+        created,
+    };
+    if created {
+        HttpResponse::Created().json(response)
+    } else {
+        HttpResponse::Ok().json(response)
     }
+}
 
-    if let Some(ref registry) = query.registry {
-        count_builder = count_builder.bind(registry);
-        data_builder = data_builder.bind(registry);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if let Some(ref event_type) = query.event_type {
-        count_builder = count_builder.bind(event_type);
-        data_builder = data_builder.bind(event_type);
-    }
+    const NOW: i64 = 1_700_000_000;
 
-    if let Some(ref agent_id) = query.agent_id {
-        count_builder = count_builder.bind(agent_id);
-        data_builder = data_builder.bind(agent_id);
+    #[test]
+    fn test_query_defaults() {
+        let params = EventsQuery::default().to_params(NOW).unwrap();
+        assert_eq!(params.limit, DEFAULT_EVENTS_LIMIT);
+        assert_eq!(params.offset, 0);
+        assert!(params.cursor.is_none());
+        assert!(params.filter.from_timestamp.is_none());
     }
 
-    // Create search pattern outside the if block so it lives long enough
-    let search_pattern = query.search.as_ref().map(|s| format!("%{}%", s));
-
-    if let Some(ref pattern) = search_pattern {
-        count_builder = count_builder
-            .bind(pattern.clone())
-            .bind(pattern.clone())
-            .bind(pattern.clone());
-        data_builder = data_builder
-            .bind(pattern.clone())
-            .bind(pattern.clone())
-            .bind(pattern.clone());
-    }
+    #[test]
+    fn test_query_limit_is_capped() {
+        for limit in [0, -1, MAX_EVENTS_LIMIT + 1] {
+            let query = EventsQuery {
+                limit: Some(limit),
+                ..Default::default()
+            };
+            assert!(query.to_params(NOW).is_err(), "{}", limit);
+        }
 
-    // Bind limit and offset
-    data_builder = data_builder.bind(limit).bind(offset);
+        let query = EventsQuery {
+            limit: Some(MAX_EVENTS_LIMIT),
+            page: Some(3),
+            ..Default::default()
+        };
+        let params = query.to_params(NOW).unwrap();
+        assert_eq!(params.offset, 2 * MAX_EVENTS_LIMIT);
+    }
 
-    // Execute count query
-    let total = match count_builder.fetch_one(pool.get_ref()).await {
-        Ok(count) => count,
-        Err(e) => {
-            let error_str = e.to_string();
-            if error_str.contains("does not exist") {
-                return HttpResponse::Ok().json(PaginatedEventsResponse {
-                    data: vec![],
-                    pagination: PaginationMeta::new(0, limit, offset),
-                });
+    #[test]
+    fn test_query_time_window() {
+        let window = |from: Option<i64>, to: Option<i64>| {
+            EventsQuery {
+                from,
+                to,
+                ..Default::default()
             }
-            tracing::error!("Failed to count events: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to query events",
-            ));
-        }
-    };
+            .to_params(NOW)
+        };
 
-    // Execute data query
-    let events = match data_builder.fetch_all(pool.get_ref()).await {
-        Ok(rows) => rows
-            .into_iter()
-            .map(
-                |(
-                    id,
-                    chain_id,
-                    block_number,
-                    tx_hash,
-                    log_index,
-                    event_type,
-                    registry,
-                    agent_id,
-                    timestamp,
-                    data,
-                )| {
-                    EventResponse {
-                        id,
-                        chain_id,
-                        block_number,
-                        tx_hash,
-                        log_index,
-                        event_type,
-                        registry,
-                        agent_id,
-                        timestamp,
-                        data,
-                    }
-                },
-            )
-            .collect(),
-        Err(e) => {
-            let error_str = e.to_string();
-            if error_str.contains("does not exist") {
-                return HttpResponse::Ok().json(PaginatedEventsResponse {
-                    data: vec![],
-                    pagination: PaginationMeta::new(0, limit, offset),
-                });
-            }
-            tracing::error!("Failed to fetch events: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to query events",
-            ));
-        }
-    };
+        let params = window(Some(NOW - 3600), Some(NOW)).unwrap();
+        assert_eq!(params.filter.from_timestamp, Some(NOW - 3600));
+        assert_eq!(params.filter.to_timestamp, Some(NOW));
 
-    HttpResponse::Ok().json(PaginatedEventsResponse {
-        data: events,
-        pagination: PaginationMeta::new(total, limit, offset),
-    })
+        // Open-ended windows are measured up to now
+        assert!(window(Some(NOW - MAX_EVENTS_WINDOW_SECS), None).is_ok());
+        assert!(window(Some(NOW - MAX_EVENTS_WINDOW_SECS - 1), None).is_err());
+
+        assert!(window(Some(0), Some(MAX_EVENTS_WINDOW_SECS + 1)).is_err());
+        assert!(window(Some(NOW), Some(NOW - 1)).is_err());
+        assert!(window(None, Some(NOW)).is_err());
+        assert!(window(Some(-1), Some(NOW)).is_err());
+    }
+
+    #[test]
+    fn test_query_cursor() {
+        let cursor = EventCursor {
+            timestamp: NOW,
+            block_number: 100,
+            id: "event-1".to_string(),
+        };
+        let query = EventsQuery {
+            cursor: Some(cursor.encode()),
+            ..Default::default()
+        };
+        assert_eq!(query.to_params(NOW).unwrap().cursor, Some(cursor.clone()));
+
+        let with_page = EventsQuery {
+            cursor: Some(cursor.encode()),
+            page: Some(2),
+            ..Default::default()
+        };
+        assert!(with_page.to_params(NOW).is_err());
+
+        let invalid = EventsQuery {
+            cursor: Some("garbage".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(invalid.to_params(NOW).unwrap_err(), "Invalid cursor");
+    }
 }
@@ -1,6 +1,15 @@
 //! Health check and service endpoints
+//!
+//! - `/health`: database connectivity (kept for existing monitors)
+//! - `/health/live`: liveness, answers as long as the process serves requests
+//! - `/health/ready`: readiness, checks PostgreSQL, Redis and the secrets
+//!   backend concurrently and answers 503 if any is unhealthy
+
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 use actix_web::{web, HttpResponse, Responder};
+use redis::aio::ConnectionManager;
 use serde::Serialize;
 use shared::DbPool;
 use utoipa::{OpenApi, ToSchema};
@@ -52,6 +61,154 @@ pub async fn health_check(pool: web::Data<DbPool>) -> impl Responder {
     }
 }
 
+/// Longest a single readiness check may take before it counts as unhealthy
+///
+/// Bounds the probe even when a dependency hangs; configure the orchestrator's
+/// probe timeout above it (e.g. Kubernetes `timeoutSeconds: 3`).
+const DEPENDENCY_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Liveness response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    /// Always "alive"
+    pub status: String,
+    pub version: String,
+}
+
+/// Status of one dependency checked by the readiness probe
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DependencyStatus {
+    /// "healthy" or "unhealthy"
+    pub status: String,
+    /// How long the check took
+    pub latency_ms: u64,
+    /// Why the check failed (absent when healthy)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DependencyStatus {
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Readiness response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// "ready" when every dependency is healthy, otherwise "not_ready"
+    pub status: String,
+    pub version: String,
+    pub database: DependencyStatus,
+    pub redis: DependencyStatus,
+    pub secrets: DependencyStatus,
+}
+
+impl ReadinessResponse {
+    fn new(database: DependencyStatus, redis: DependencyStatus, secrets: DependencyStatus) -> Self {
+        let ready = all_healthy(&[&database, &redis, &secrets]);
+        Self {
+            status: if ready { "ready" } else { "not_ready" }.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            database,
+            redis,
+            secrets,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.status == "ready"
+    }
+}
+
+/// Whether every dependency is healthy
+fn all_healthy(dependencies: &[&DependencyStatus]) -> bool {
+    dependencies
+        .iter()
+        .all(|dependency| dependency.is_healthy())
+}
+
+/// Run one dependency check, failing it after `limit`
+async fn check_dependency<F, E>(limit: Duration, check: F) -> DependencyStatus
+where
+    F: Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    let start = Instant::now();
+    let error = match tokio::time::timeout(limit, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("timed out after {}ms", limit.as_millis())),
+    };
+    DependencyStatus {
+        status: if error.is_none() {
+            "healthy"
+        } else {
+            "unhealthy"
+        }
+        .to_string(),
+        latency_ms: start.elapsed().as_millis() as u64,
+        error,
+    }
+}
+
+/// Liveness probe
+///
+/// Confirms the process is up and serving requests. Checks no dependencies,
+/// so an orchestrator does not restart the gateway over a database outage.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/live",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Process is up", body = LivenessResponse)
+    )
+)]
+pub async fn liveness() -> impl Responder {
+    HttpResponse::Ok().json(LivenessResponse {
+        status: "alive".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Readiness probe
+///
+/// Checks PostgreSQL (`SELECT 1`), Redis (`PING`) and the secrets backend
+/// concurrently, each with a 2 second timeout, and reports every dependency.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/ready",
+    tag = "Health",
+    responses(
+        (status = 200, description = "All dependencies are healthy", body = ReadinessResponse),
+        (status = 503, description = "At least one dependency is unhealthy", body = ReadinessResponse)
+    )
+)]
+pub async fn readiness(
+    pool: web::Data<DbPool>,
+    redis: web::Data<ConnectionManager>,
+) -> impl Responder {
+    let mut redis_conn = redis.get_ref().clone();
+    let (database, redis, secrets) = tokio::join!(
+        check_dependency(DEPENDENCY_CHECK_TIMEOUT, shared::db::check_health(&pool)),
+        check_dependency(
+            DEPENDENCY_CHECK_TIMEOUT,
+            shared::redis::check_health(&mut redis_conn)
+        ),
+        check_dependency(DEPENDENCY_CHECK_TIMEOUT, async {
+            shared::secrets::check_backend().await.map(|_| ())
+        }),
+    );
+
+    let response = ReadinessResponse::new(database, redis, secrets);
+    if response.is_ready() {
+        HttpResponse::Ok().json(response)
+    } else {
+        tracing::warn!(?response, "Readiness check failed");
+        HttpResponse::ServiceUnavailable().json(response)
+    }
+}
+
 /// OpenAPI JSON endpoint
 ///
 /// Returns the OpenAPI 3.0 specification for the API.
@@ -90,4 +247,90 @@ mod tests {
         assert!(json.contains("healthy"));
         assert!(json.contains("connected"));
     }
+
+    fn healthy() -> DependencyStatus {
+        DependencyStatus {
+            status: "healthy".to_string(),
+            latency_ms: 1,
+            error: None,
+        }
+    }
+
+    fn unhealthy(error: &str) -> DependencyStatus {
+        DependencyStatus {
+            status: "unhealthy".to_string(),
+            latency_ms: 1,
+            error: Some(error.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_readiness_requires_every_dependency() {
+        assert!(ReadinessResponse::new(healthy(), healthy(), healthy()).is_ready());
+
+        for response in [
+            ReadinessResponse::new(unhealthy("down"), healthy(), healthy()),
+            ReadinessResponse::new(healthy(), unhealthy("down"), healthy()),
+            ReadinessResponse::new(healthy(), healthy(), unhealthy("down")),
+            ReadinessResponse::new(unhealthy("down"), unhealthy("down"), unhealthy("down")),
+        ] {
+            assert!(!response.is_ready());
+            assert_eq!(response.status, "not_ready");
+        }
+    }
+
+    #[test]
+    fn test_readiness_response_serialization() {
+        let response = ReadinessResponse::new(healthy(), unhealthy("PING failed"), healthy());
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(json["status"], "not_ready");
+        assert_eq!(json["database"]["status"], "healthy");
+        assert!(json["database"].get("error").is_none());
+        assert_eq!(json["redis"]["status"], "unhealthy");
+        assert_eq!(json["redis"]["error"], "PING failed");
+    }
+
+    #[tokio::test]
+    async fn test_check_dependency_reports_errors() {
+        let ok = check_dependency(DEPENDENCY_CHECK_TIMEOUT, async { Ok::<_, String>(()) }).await;
+        assert!(ok.is_healthy());
+        assert_eq!(ok.status, "healthy");
+
+        let failed = check_dependency(DEPENDENCY_CHECK_TIMEOUT, async {
+            Err::<(), _>("connection refused".to_string())
+        })
+        .await;
+        assert!(!failed.is_healthy());
+        assert_eq!(failed.error.as_deref(), Some("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_check_dependency_times_out() {
+        let hung = check_dependency(Duration::from_millis(50), async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<_, String>(())
+        })
+        .await;
+
+        assert_eq!(hung.status, "unhealthy");
+        assert_eq!(hung.error.as_deref(), Some("timed out after 50ms"));
+    }
+
+    #[tokio::test]
+    async fn test_checks_run_concurrently() {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok::<_, String>(())
+        };
+        let start = Instant::now();
+        let (a, b, c) = tokio::join!(
+            check_dependency(DEPENDENCY_CHECK_TIMEOUT, slow()),
+            check_dependency(DEPENDENCY_CHECK_TIMEOUT, slow()),
+            check_dependency(DEPENDENCY_CHECK_TIMEOUT, slow()),
+        );
+
+        assert!(all_healthy(&[&a, &b, &c]));
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
 }
@@ -8,6 +8,8 @@
 //!
 //! ## Authentication
 //! - [`extract_user_id_or_unauthorized`] - Extract user_id from JWT or return 401
+//! - [`require_admin_token`] - Gate operator endpoints on `X-Admin-Token` or return 403
+//! - [`require_verified_email`] - Gate actions on a verified email or return 403
 //!
 //! ## Validation
 //! - [`validate_request`] - Validate a request or return 400
@@ -15,16 +17,22 @@
 //! ## Error Handling
 //! - [`handle_db_error`] - Convert database errors to HTTP responses with logging
 //! - [`require_found`] - Convert Option<T> to T or return 404
+//! - [`feature_not_configured`] - 503 for features whose optional secrets are missing
+//! - [`error_response`] - Respond with a `shared::Error`, including its machine-readable code
 //!
 //! ## Request Context
 //! - [`RequestContext`] - Structured request metadata for audit logging
 //! - [`extract_request_context`] - Extract context from HTTP request
 
 use actix_web::{HttpRequest, HttpResponse};
+use once_cell::sync::Lazy;
+use shared::OptionalFeature;
+use subtle::ConstantTimeEq;
 use validator::Validate;
 
 use crate::middleware::get_user_id;
 use crate::models::ErrorResponse;
+use crate::repositories::UserRepository;
 
 // ============================================================================
 // Authentication Helpers
@@ -58,6 +66,74 @@ pub fn extract_user_id_or_unauthorized(req: &HttpRequest) -> Result<String, Http
     })
 }
 
+/// Require the user to have verified their email address, or return 403
+///
+/// Gates actions that issue credentials (API keys, OAuth clients) until a
+/// password registration has proven ownership of its email.
+///
+/// # Example
+///
+/// ```ignore
+/// if let Err(resp) = require_verified_email(&pool, &user_id).await {
+///     return resp;
+/// }
+/// ```
+pub async fn require_verified_email(
+    pool: &shared::DbPool,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    match handle_db_error(
+        UserRepository::find_by_id(pool, user_id).await,
+        "look up user",
+    )? {
+        Some(user) if user.email_verification_pending() => Err(HttpResponse::Forbidden().json(
+            ErrorResponse::new("email_not_verified", "Verify your email address first"),
+        )),
+        Some(_) => Ok(()),
+        None => Err(HttpResponse::Unauthorized().json(ErrorResponse::new(
+            "unauthorized",
+            "Authentication required",
+        ))),
+    }
+}
+
+/// Operator token loaded from environment variable
+static ADMIN_API_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
+    std::env::var("ADMIN_API_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+});
+
+/// Compare the provided token against the configured one in constant time
+fn admin_token_matches(expected: Option<&str>, provided: Option<&str>) -> bool {
+    match (expected, provided) {
+        (Some(expected), Some(provided)) => {
+            let expected = expected.as_bytes();
+            let provided = provided.as_bytes();
+            expected.len() == provided.len() && expected.ct_eq(provided).into()
+        }
+        _ => false,
+    }
+}
+
+/// Reject the request unless it carries a valid `X-Admin-Token`
+///
+/// Operator endpoints under `/api/v1/admin` are not gated by organization
+/// role: the header must match the `ADMIN_API_TOKEN` environment variable,
+/// and the endpoints are disabled when it is unset.
+pub fn require_admin_token(req: &HttpRequest) -> Result<(), HttpResponse> {
+    let provided = req
+        .headers()
+        .get("X-Admin-Token")
+        .and_then(|h| h.to_str().ok());
+
+    if admin_token_matches(ADMIN_API_TOKEN.as_deref(), provided) {
+        Ok(())
+    } else {
+        Err(forbidden("Admin token required"))
+    }
+}
+
 // ============================================================================
 // Validation Helpers
 // ============================================================================
@@ -201,6 +277,13 @@ pub fn forbidden(message: &str) -> HttpResponse {
     HttpResponse::Forbidden().json(ErrorResponse::new("forbidden", message))
 }
 
+/// Respond with a [`shared::Error`]: its status, code and client-safe message
+pub fn error_response(error: &shared::Error) -> HttpResponse {
+    let status = actix_web::http::StatusCode::from_u16(error.status_code())
+        .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR);
+    HttpResponse::build(status).json(ErrorResponse::from(error))
+}
+
 /// Return a 400 Bad Request response with a custom message
 ///
 /// # Example
@@ -227,6 +310,26 @@ pub fn unauthorized(message: &str) -> HttpResponse {
     HttpResponse::Unauthorized().json(ErrorResponse::new("unauthorized", message))
 }
 
+/// Return a 503 Service Unavailable for a feature disabled by missing secrets
+///
+/// Optional secrets (Stripe, Telegram) may be absent, typically in
+/// development. The feature is then off and its endpoints answer with this
+/// response instead of failing at request time.
+///
+/// # Example
+///
+/// ```ignore
+/// if !secrets.is_enabled(OptionalFeature::Payments) {
+///     return feature_not_configured(OptionalFeature::Payments);
+/// }
+/// ```
+pub fn feature_not_configured(feature: OptionalFeature) -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(ErrorResponse::new(
+        "feature_not_configured",
+        format!("{} is not configured on this server", feature),
+    ))
+}
+
 /// Return a safe internal server error that doesn't leak implementation details
 ///
 /// SECURITY: Use this for errors where the cause should never be exposed to clients,
@@ -361,3 +464,53 @@ pub fn extract_request_context(req: &HttpRequest) -> RequestContext {
         endpoint,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{body::to_bytes, http::StatusCode};
+
+    #[actix_web::test]
+    async fn test_feature_not_configured_response() {
+        let response = feature_not_configured(OptionalFeature::Payments);
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "feature_not_configured");
+        assert_eq!(body["message"], "Payments is not configured on this server");
+    }
+
+    #[actix_web::test]
+    async fn test_error_response_carries_code() {
+        let response = error_response(&shared::Error::from(sqlx::Error::PoolTimedOut));
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "service_unavailable");
+        assert_eq!(body["code"], "ERR_DB_TIMEOUT");
+        assert_eq!(body["message"], "Database timed out");
+
+        let response = error_response(&shared::Error::not_found("Trigger", "t1"));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_admin_token_matches() {
+        assert!(admin_token_matches(Some("secret"), Some("secret")));
+    }
+
+    #[test]
+    fn test_admin_token_mismatch() {
+        assert!(!admin_token_matches(Some("secret"), Some("secreT")));
+        assert!(!admin_token_matches(Some("secret"), Some("secret2")));
+        assert!(!admin_token_matches(Some("secret"), None));
+    }
+
+    #[test]
+    fn test_admin_endpoints_disabled_without_token() {
+        assert!(!admin_token_matches(None, Some("anything")));
+        assert!(!admin_token_matches(None, None));
+    }
+}
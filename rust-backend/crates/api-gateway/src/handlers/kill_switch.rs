@@ -0,0 +1,163 @@
+//! Global Kill-Switch Admin Handlers
+//!
+//! Operator endpoints for capping or pausing request acceptance across all
+//! callers during an abuse incident (see [`crate::models::kill_switch`]). Like
+//! the other `/admin` endpoints they require the `X-Admin-Token` header, and
+//! they are never affected by the switch itself.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/admin/kill-switch` - Current status
+//! - `PUT /api/v1/admin/kill-switch` - Engage (or replace) the switch
+//! - `DELETE /api/v1/admin/kill-switch` - Release the switch
+//!
+//! Changes apply on the next request on every gateway instance.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::Utc;
+
+use crate::{
+    handlers::helpers::{require_admin_token, validate_request},
+    models::{ErrorResponse, KillSwitchStatusResponse, SetKillSwitchRequest, SuccessResponse},
+    services::KillSwitchStore,
+};
+
+fn store_unavailable(message: &str) -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(ErrorResponse::new("kill_switch_unavailable", message))
+}
+
+/// Get the registered store, or 503
+fn require_store(req_http: &HttpRequest) -> Result<&web::Data<KillSwitchStore>, HttpResponse> {
+    req_http
+        .app_data::<web::Data<KillSwitchStore>>()
+        .ok_or_else(|| store_unavailable("Kill-switch is not configured"))
+}
+
+/// Get the global kill-switch status
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/kill-switch",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Kill-switch status", body = SuccessResponse<KillSwitchStatusResponse>),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 503, description = "Kill-switch storage unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn get_kill_switch(req_http: HttpRequest) -> impl Responder {
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+    let store = match require_store(&req_http) {
+        Ok(store) => store,
+        Err(resp) => return resp,
+    };
+
+    match store.get().await {
+        Ok(kill_switch) => {
+            HttpResponse::Ok().json(SuccessResponse::new(KillSwitchStatusResponse {
+                active: kill_switch.is_some(),
+                kill_switch,
+            }))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to read kill-switch");
+            store_unavailable("Failed to read kill-switch")
+        }
+    }
+}
+
+/// Engage the global kill-switch
+///
+/// `cap` admits at most `requests_per_minute` across all callers (429 beyond
+/// that); `pause` rejects every request with 503 and `Retry-After`. Callers on
+/// the allowlists are not affected. Replaces any engaged switch.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/kill-switch",
+    tag = "Admin",
+    request_body = SetKillSwitchRequest,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Kill-switch engaged", body = SuccessResponse<KillSwitchStatusResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 503, description = "Kill-switch storage unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn set_kill_switch(
+    req_http: HttpRequest,
+    req: web::Json<SetKillSwitchRequest>,
+) -> impl Responder {
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+    let store = match require_store(&req_http) {
+        Ok(store) => store,
+        Err(resp) => return resp,
+    };
+
+    let state = req.into_inner().into_state(Utc::now());
+    if let Err(e) = store.set(&state).await {
+        tracing::error!(error = %e, "Failed to engage kill-switch");
+        return store_unavailable("Failed to engage kill-switch");
+    }
+
+    tracing::warn!(
+        mode = state.mode.as_str(),
+        requests_per_minute = ?state.requests_per_minute,
+        retry_after_seconds = state.retry_after_seconds,
+        allow_organizations = ?state.allow_organizations,
+        allow_ips = ?state.allow_ips,
+        expires_at = ?state.expires_at,
+        reason = %state.reason,
+        "Global kill-switch ENGAGED"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(KillSwitchStatusResponse {
+        active: true,
+        kill_switch: Some(state),
+    }))
+}
+
+/// Release the global kill-switch
+///
+/// Succeeds whether or not a switch was engaged.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/kill-switch",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Kill-switch released", body = SuccessResponse<KillSwitchStatusResponse>),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 503, description = "Kill-switch storage unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn release_kill_switch(req_http: HttpRequest) -> impl Responder {
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+    let store = match require_store(&req_http) {
+        Ok(store) => store,
+        Err(resp) => return resp,
+    };
+
+    match store.clear().await {
+        Ok(was_engaged) => {
+            tracing::warn!(was_engaged = was_engaged, "Global kill-switch RELEASED");
+            HttpResponse::Ok().json(SuccessResponse::new(KillSwitchStatusResponse {
+                active: false,
+                kill_switch: None,
+            }))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to release kill-switch");
+            store_unavailable("Failed to release kill-switch")
+        }
+    }
+}
@@ -1,23 +1,31 @@
 //! Request handlers for API endpoints
 
 pub mod a2a;
+pub mod action_webhooks;
 pub mod actions;
 pub mod agent_follows;
 pub mod agents;
 pub mod api_keys;
 pub mod auth;
 pub mod billing;
+pub mod cache_admin;
+pub mod chains;
 pub mod circuit_breaker;
 pub mod conditions;
 pub mod discovery;
+pub mod dlq;
 pub mod events;
 pub mod health;
 pub mod helpers;
+pub mod kill_switch;
 pub mod oauth;
 pub mod organizations;
 pub mod ponder;
+pub mod rate_limits;
+pub mod signing_keys;
 pub mod social_auth;
 pub mod triggers;
+pub mod webhooks;
 
 // Re-export commonly used handlers
 pub use actions::*;
@@ -33,12 +41,17 @@ pub use triggers::*;
 // Note: For utoipa to work properly with #[utoipa::path] macros, we need to use
 // wildcard re-exports so the generated __path_* types are also accessible.
 
-// Explicitly re-export agent handlers (avoid OrgIdQuery conflict with billing)
+// Explicitly re-export agent handlers
 pub use agents::{
     __path_link_agent, __path_list_linked_agents, __path_list_org_agents, __path_unlink_agent,
     link_agent, list_linked_agents, list_org_agents, unlink_agent,
 };
 
+// Explicitly re-export chain query handlers
+pub use chains::{
+    __path_get_balance, __path_get_transaction_status, get_balance, get_transaction_status,
+};
+
 // Explicitly re-export agent follow handlers
 pub use agent_follows::{
     __path_follow_agent, __path_list_following, __path_unfollow_agent, __path_update_follow,
@@ -48,16 +61,18 @@ pub use agent_follows::{
 // Explicitly re-export billing handlers
 pub use billing::{
     __path_get_credits, __path_get_org_credits, __path_get_subscription,
-    __path_handle_stripe_webhook, __path_list_org_transactions, __path_list_transactions,
-    __path_purchase_credits, get_credits, get_org_credits, get_subscription, handle_stripe_webhook,
-    list_org_transactions, list_transactions, purchase_credits,
+    __path_get_transaction_receipt, __path_handle_stripe_webhook, __path_list_org_transactions,
+    __path_list_transactions, __path_purchase_credits, __path_set_low_balance_threshold,
+    get_credits, get_org_credits, get_subscription, get_transaction_receipt, handle_stripe_webhook,
+    list_org_transactions, list_transactions, purchase_credits, set_low_balance_threshold,
+    STRIPE_WEBHOOK_MAX_BODY_BYTES, STRIPE_WEBHOOK_MAX_CONCURRENCY,
 };
 
 // Explicitly re-export OAuth handlers
 pub use oauth::{
-    __path_create_oauth_client, __path_delete_oauth_client, __path_list_oauth_clients,
-    __path_token_endpoint, create_oauth_client, delete_oauth_client, list_oauth_clients,
-    token_endpoint,
+    __path_authorize, __path_create_oauth_client, __path_delete_oauth_client,
+    __path_list_oauth_clients, __path_token_endpoint, authorize, create_oauth_client,
+    delete_oauth_client, list_oauth_clients, token_endpoint,
 };
 
 // Explicitly re-export social auth handlers
@@ -72,14 +87,47 @@ pub use ponder::{
     __path_get_ponder_events, __path_get_ponder_status, get_ponder_events, get_ponder_status,
 };
 
+// Explicitly re-export action result webhook handlers
+pub use action_webhooks::{
+    __path_delete_action_webhook, __path_get_action_webhook, __path_set_action_webhook,
+    delete_action_webhook, get_action_webhook, set_action_webhook,
+};
+
+// Explicitly re-export rate limit admin handlers
+pub use cache_admin::{__path_flush_cache, flush_cache};
+pub use dlq::{
+    __path_list_dlq_entries, __path_replay_dlq_entry, list_dlq_entries, replay_dlq_entry,
+};
+pub use kill_switch::{
+    __path_get_kill_switch, __path_release_kill_switch, __path_set_kill_switch, get_kill_switch,
+    release_kill_switch, set_kill_switch,
+};
+pub use rate_limits::{
+    __path_get_org_rate_limits, __path_set_org_rate_limits, get_org_rate_limits,
+    set_org_rate_limits,
+};
+
 // Explicitly re-export A2A Protocol handlers
 pub use a2a::{
-    __path_a2a_rpc, __path_get_task_status, __path_stream_task_progress, a2a_rpc, get_task_status,
-    stream_task_progress,
+    __path_a2a_rpc, __path_cancel_task, __path_get_task_status, __path_stream_task_progress,
+    a2a_rpc, cancel_task, get_task_status, stream_task_progress,
 };
 
 // Explicitly re-export Events handlers
-pub use events::{__path_list_events, list_events};
+pub use events::{__path_ingest_event, __path_list_events, ingest_event, list_events};
+
+// Explicitly re-export trigger fire webhook handlers
+pub use webhooks::{
+    __path_create_webhook, __path_delete_webhook, __path_get_webhook,
+    __path_list_webhook_deliveries, __path_list_webhooks, create_webhook, delete_webhook,
+    get_webhook, list_webhook_deliveries, list_webhooks,
+};
+
+// Explicitly re-export organization signing key handlers
+pub use signing_keys::{
+    __path_create_signing_key, __path_list_signing_keys, __path_rotate_signing_key,
+    create_signing_key, list_signing_keys, rotate_signing_key,
+};
 
 // Note: helpers module is not re-exported to avoid polluting the namespace
 // Import helpers directly: use crate::handlers::helpers::{...}
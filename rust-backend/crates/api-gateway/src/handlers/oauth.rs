@@ -12,6 +12,9 @@
 //! - `GET /api/v1/oauth/clients` - List organization's OAuth clients
 //! - `DELETE /api/v1/oauth/clients/:id` - Delete an OAuth client
 //!
+//! ## Authorization Endpoint (JWT Auth Required)
+//! - `POST /api/v1/oauth/authorize` - Issue an authorization code to a client
+//!
 //! ## Token Endpoints (Public - Client Credentials Auth)
 //! - `POST /api/v1/oauth/token` - Issue access and refresh tokens
 //! - `POST /api/v1/oauth/token/refresh` - Refresh an access token
@@ -23,30 +26,38 @@
 //! - Client secrets shown ONLY ONCE at creation
 //! - Tokens expire after configurable duration (default: 1 hour for access, 7 days for refresh)
 //! - All secrets are hashed before storage (never stored in plaintext)
+//! - Public clients (no secret) must use PKCE (RFC 7636) for authorization codes
 //!
 //! # OAuth 2.0 Grant Types
 //!
-//! 1. **authorization_code** - Standard OAuth flow with authorization
+//! 1. **authorization_code** - Standard OAuth flow with authorization (PKCE supported)
 //! 2. **client_credentials** - Machine-to-machine authentication
 //! 3. **refresh_token** - Refresh an expired access token
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use chrono::{Duration, Utc};
+use reqwest::Url;
+use shared::models::OAuthClient;
 use shared::DbPool;
 
 use crate::{
     handlers::helpers::{
-        bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, unauthorized,
-        validate_request,
+        bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error,
+        require_verified_email, unauthorized, validate_request,
     },
+    middleware::get_verified_organization,
     models::{
-        can_manage_org, CreateOAuthClientRequest, CreateOAuthClientResponse, ErrorResponse,
-        OAuthClientListResponse, OAuthClientResponse, SuccessResponse, TokenRequest, TokenResponse,
+        can_manage_org, AuthorizeRequest, AuthorizeResponse, CreateOAuthClientRequest,
+        CreateOAuthClientResponse, ErrorResponse, OAuthClientListResponse, OAuthClientResponse,
+        SuccessResponse, TokenRequest, TokenResponse,
     },
     repositories::{
         MemberRepository, OAuthClientRepository, OAuthTokenRepository, OrganizationRepository,
     },
-    services::{OAuthClientService, OAuthTokenService},
+    services::{
+        AuthorizationGrant, OAuthClientService, OAuthCodeError, OAuthCodeService,
+        OAuthTokenService, PkceChallenge,
+    },
 };
 
 // ============================================================================
@@ -76,7 +87,7 @@ const REFRESH_TOKEN_EXPIRES_IN_SECONDS: i64 = 604800;
         (status = 201, description = "OAuth client created", body = SuccessResponse<CreateOAuthClientResponse>),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions, or email not verified", body = ErrorResponse),
         (status = 404, description = "Organization not found", body = ErrorResponse),
         (status = 500, description = "Failed to create client", body = ErrorResponse)
     )
@@ -97,6 +108,11 @@ pub async fn create_oauth_client(
         return resp;
     }
 
+    // A public client has no secret to authenticate with
+    if req.is_public && req.grant_types.iter().any(|g| g == "client_credentials") {
+        return bad_request("Public clients cannot use the client_credentials grant");
+    }
+
     // Get user's organization (for now, use their personal organization)
     // TODO: Allow specifying organization_id in request body or query param
     let org = match handle_db_error(
@@ -132,6 +148,10 @@ pub async fn create_oauth_client(
         return forbidden("Insufficient permissions to create OAuth clients");
     }
 
+    if let Err(resp) = require_verified_email(&pool, &user_id).await {
+        return resp;
+    }
+
     // Generate client ID and secret
     let oauth_client_service = OAuthClientService::new();
     let generated = match oauth_client_service.generate_client() {
@@ -156,6 +176,7 @@ pub async fn create_oauth_client(
         &org.id,
         &req.grant_types,
         req.is_trusted,
+        req.is_public,
     )
     .await
     {
@@ -170,14 +191,16 @@ pub async fn create_oauth_client(
     };
 
     // Return response with FULL client_secret (shown only once!)
+    // Public clients never use theirs, so it is not handed out at all
     let response = CreateOAuthClientResponse {
         client_id: client.client_id,
-        client_secret: generated.client_secret, // ONLY shown at creation
+        client_secret: (!client.is_public).then_some(generated.client_secret), // ONLY shown at creation
         client_name: client.client_name,
         redirect_uris: client.redirect_uris,
         scopes: client.scopes,
         grant_types: client.grant_types,
         is_trusted: client.is_trusted,
+        is_public: client.is_public,
         created_at: client.created_at,
     };
 
@@ -350,13 +373,160 @@ pub async fn delete_oauth_client(
     HttpResponse::NoContent().finish()
 }
 
+// ============================================================================
+// Authorization Endpoint (JWT Auth Required)
+// ============================================================================
+
+/// OAuth 2.0 authorization endpoint
+///
+/// Called by the consent page once the signed-in user approves the client.
+/// Issues an authorization code for the organization selected with
+/// `X-Organization-ID`, bound to the PKCE challenge if one is given (required
+/// for public clients).
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/authorize",
+    tag = "OAuth Clients",
+    request_body = AuthorizeRequest,
+    params(
+        ("X-Organization-ID" = String, Header, description = "Organization the client is granted access to")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Authorization code issued", body = AuthorizeResponse),
+        (status = 400, description = "Invalid authorization request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 500, description = "Failed to issue code", body = ErrorResponse)
+    )
+)]
+pub async fn authorize(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    req: web::Json<AuthorizeRequest>,
+) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    if req.response_type != "code" {
+        return oauth_error(
+            "unsupported_response_type",
+            "Only the \"code\" response type is supported",
+        );
+    }
+
+    let org_id = match get_verified_organization(&req_http, &pool, &user_id, None).await {
+        Ok(org) => org.id,
+        Err(resp) => return resp,
+    };
+
+    let client = match handle_db_error(
+        OAuthClientRepository::find_by_client_id(&pool, &req.client_id).await,
+        "fetch OAuth client",
+    ) {
+        Ok(Some(c)) => c,
+        Ok(None) => return oauth_error("invalid_client", "Unknown client_id"),
+        Err(resp) => return resp,
+    };
+
+    if !client
+        .grant_types
+        .contains(&"authorization_code".to_string())
+    {
+        return oauth_error(
+            "unauthorized_client",
+            "authorization_code grant type not allowed for this client",
+        );
+    }
+
+    // Exact match against the registered URIs (no prefix or pattern matching)
+    if !client.redirect_uris.contains(&req.redirect_uri) {
+        return oauth_error(
+            "invalid_request",
+            "redirect_uri is not registered for this client",
+        );
+    }
+
+    let scopes = match &req.scope {
+        Some(s) => crate::models::parse_scopes(s),
+        None => client.scopes.clone(),
+    };
+    if scopes.iter().any(|scope| !client.scopes.contains(scope)) {
+        return oauth_error("invalid_scope", "Scope not allowed for this client");
+    }
+
+    let pkce = match (&req.code_challenge, &req.code_challenge_method) {
+        (Some(challenge), method) => match PkceChallenge::new(challenge, method.as_deref()) {
+            Ok(pkce) => Some(pkce),
+            Err(e) => return oauth_error("invalid_request", &e.to_string()),
+        },
+        (None, Some(_)) => {
+            return oauth_error(
+                "invalid_request",
+                "code_challenge_method requires a code_challenge",
+            )
+        }
+        (None, None) if client.is_public => {
+            return oauth_error(
+                "invalid_request",
+                "code_challenge is required for public clients",
+            )
+        }
+        (None, None) => None,
+    };
+
+    let grant = AuthorizationGrant {
+        user_id,
+        client_id: client.client_id,
+        redirect_uri: req.redirect_uri.clone(),
+        organization_id: org_id,
+        scopes,
+        pkce,
+    };
+
+    let code = match OAuthCodeService::new()
+        .create_authorization_code(&pool, &grant)
+        .await
+    {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::error!("Failed to create authorization code: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to issue authorization code",
+            ));
+        }
+    };
+
+    let mut redirect_to = match Url::parse(&grant.redirect_uri) {
+        Ok(url) => url,
+        Err(_) => return oauth_error("invalid_request", "redirect_uri is not a valid URL"),
+    };
+    {
+        let mut query = redirect_to.query_pairs_mut();
+        query.append_pair("code", &code);
+        if let Some(state) = &req.state {
+            query.append_pair("state", state);
+        }
+    }
+
+    HttpResponse::Ok().json(AuthorizeResponse {
+        code,
+        state: req.state.clone(),
+        redirect_to: redirect_to.to_string(),
+    })
+}
+
 // ============================================================================
 // Token Endpoints (Public - Client Credentials Auth)
 // ============================================================================
 
 /// OAuth 2.0 token endpoint
 ///
-/// Issues access and refresh tokens. Supports client_credentials and refresh_token grants.
+/// Issues access and refresh tokens. Supports authorization_code (with PKCE),
+/// client_credentials and refresh_token grants.
 #[utoipa::path(
     post,
     path = "/api/v1/oauth/token",
@@ -367,7 +537,6 @@ pub async fn delete_oauth_client(
         (status = 400, description = "Invalid request or unsupported grant type", body = ErrorResponse),
         (status = 401, description = "Invalid client credentials", body = ErrorResponse),
         (status = 500, description = "Failed to generate token", body = ErrorResponse),
-        (status = 501, description = "Grant type not implemented", body = ErrorResponse)
     )
 )]
 pub async fn token_endpoint(
@@ -385,18 +554,96 @@ pub async fn token_endpoint(
 
 /// Handle authorization_code grant
 ///
-/// This is the standard OAuth 2.0 authorization code flow.
-/// For now, we return an error as the full flow requires an authorization server.
-async fn handle_authorization_code_grant(_pool: &DbPool, _req: &TokenRequest) -> HttpResponse {
-    // TODO: Implement authorization code flow with PKCE
-    // This requires:
-    // 1. Authorization endpoint to get the code
-    // 2. Code verification and exchange
-    // 3. PKCE code_challenge verification
-    HttpResponse::NotImplemented().json(ErrorResponse::new(
-        "not_implemented",
-        "Authorization code grant is not yet implemented",
-    ))
+/// Exchanges a code from [`authorize`] for tokens acting as the user who
+/// approved the client. The code is single-use and must be presented by the
+/// client it was issued to, with the same redirect_uri and, if the
+/// authorization request had a PKCE challenge, the matching code_verifier.
+async fn handle_authorization_code_grant(pool: &DbPool, req: &TokenRequest) -> HttpResponse {
+    let code = match &req.code {
+        Some(c) if !c.is_empty() => c,
+        _ => return bad_request("code is required for authorization_code grant"),
+    };
+
+    let redirect_uri = match &req.redirect_uri {
+        Some(r) if !r.is_empty() => r,
+        _ => return bad_request("redirect_uri is required for authorization_code grant"),
+    };
+
+    let client = match authenticate_client(pool, &req.client_id, req.client_secret.as_deref()).await
+    {
+        Ok(Some(c)) => c,
+        Ok(None) => return unauthorized("Invalid client credentials"),
+        Err(resp) => return resp,
+    };
+
+    if !client
+        .grant_types
+        .contains(&"authorization_code".to_string())
+    {
+        return bad_request("authorization_code grant type not allowed for this client");
+    }
+
+    // Consumes the code: a failed check below cannot be retried with it
+    let grant = match OAuthCodeService::new()
+        .exchange_authorization_code(pool, code)
+        .await
+    {
+        Ok(g) => g,
+        Err(OAuthCodeError::InvalidCode) => {
+            return invalid_grant("Invalid or expired authorization code")
+        }
+        Err(e) => {
+            tracing::error!("Failed to exchange authorization code: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to exchange authorization code",
+            ));
+        }
+    };
+
+    if grant.client_id != client.client_id {
+        return invalid_grant("Authorization code was issued to another client");
+    }
+
+    if grant.redirect_uri != *redirect_uri {
+        return invalid_grant("redirect_uri does not match the authorization request");
+    }
+
+    if let Err(resp) = verify_code_verifier(&client, &grant, req.code_verifier.as_deref()) {
+        return resp;
+    }
+
+    issue_tokens(
+        pool,
+        &client.client_id,
+        &grant.user_id,
+        &grant.organization_id,
+        &grant.scopes,
+    )
+    .await
+}
+
+/// Check the token request's code_verifier against the code's PKCE challenge
+///
+/// A verifier without a challenge is rejected too (RFC 9700 Section 2.1.1), as
+/// is a public client's code without a challenge.
+fn verify_code_verifier(
+    client: &OAuthClient,
+    grant: &AuthorizationGrant,
+    code_verifier: Option<&str>,
+) -> Result<(), HttpResponse> {
+    match (&grant.pkce, code_verifier) {
+        (Some(pkce), Some(verifier)) if pkce.verify(verifier) => Ok(()),
+        (Some(_), Some(_)) => Err(invalid_grant(
+            "code_verifier does not match the code_challenge",
+        )),
+        (Some(_), None) => Err(invalid_grant("code_verifier is required")),
+        (None, Some(_)) => Err(invalid_grant(
+            "code_verifier given but the authorization request had no code_challenge",
+        )),
+        (None, None) if client.is_public => Err(invalid_grant("Public clients must use PKCE")),
+        (None, None) => Ok(()),
+    }
 }
 
 /// Handle client_credentials grant
@@ -443,70 +690,18 @@ async fn handle_client_credentials_grant(pool: &DbPool, req: &TokenRequest) -> H
         }
     }
 
-    // Generate tokens
-    let oauth_token_service = OAuthTokenService::new();
-
-    let access_token = match oauth_token_service.generate_access_token() {
-        Ok(t) => t,
-        Err(e) => {
-            tracing::error!("Failed to generate access token: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to generate access token",
-            ));
-        }
-    };
-
-    let refresh_token = match oauth_token_service.generate_refresh_token() {
-        Ok(t) => t,
-        Err(e) => {
-            tracing::error!("Failed to generate refresh token: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse::new(
-                "internal_error",
-                "Failed to generate refresh token",
-            ));
-        }
-    };
-
-    // Calculate expiration times
-    let access_token_expires_at = Utc::now() + Duration::seconds(ACCESS_TOKEN_EXPIRES_IN_SECONDS);
-    let refresh_token_expires_at = Utc::now() + Duration::seconds(REFRESH_TOKEN_EXPIRES_IN_SECONDS);
-
     // For client_credentials grant, we use the client's organization as the user
     // This is a special case - typically there would be a user_id from the authorization flow
     let user_id = client.owner_organization_id.clone(); // Use org_id as "user_id" for M2M
 
-    // Store token in database
-    if let Err(e) = OAuthTokenRepository::create(
+    issue_tokens(
         pool,
-        &access_token.hash,
-        Some(&refresh_token.hash),
         &client.client_id,
         &user_id,
         &client.owner_organization_id,
         &requested_scopes,
-        access_token_expires_at,
-        Some(refresh_token_expires_at),
     )
     .await
-    {
-        tracing::error!("Failed to store OAuth token: {}", e);
-        return HttpResponse::InternalServerError().json(ErrorResponse::new(
-            "internal_error",
-            "Failed to create token",
-        ));
-    }
-
-    // Return token response
-    let response = TokenResponse {
-        access_token: access_token.token,
-        token_type: "Bearer".to_string(),
-        expires_in: ACCESS_TOKEN_EXPIRES_IN_SECONDS,
-        refresh_token: Some(refresh_token.token),
-        scope: crate::models::scopes_to_string(&requested_scopes),
-    };
-
-    HttpResponse::Ok().json(response)
 }
 
 /// Handle refresh_token grant
@@ -519,13 +714,9 @@ async fn handle_refresh_token_grant(pool: &DbPool, req: &TokenRequest) -> HttpRe
         _ => return bad_request("refresh_token is required for refresh_token grant"),
     };
 
-    // Verify client credentials
-    let client_secret = match &req.client_secret {
-        Some(s) => s,
-        None => return bad_request("client_secret is required"),
-    };
-
-    let client = match verify_client_credentials(pool, &req.client_id, client_secret).await {
+    // Verify client credentials (public clients are identified by client_id alone)
+    let client = match authenticate_client(pool, &req.client_id, req.client_secret.as_deref()).await
+    {
         Ok(Some(c)) => c,
         Ok(None) => return unauthorized("Invalid client credentials"),
         Err(resp) => return resp,
@@ -591,8 +782,31 @@ async fn handle_refresh_token_grant(pool: &DbPool, req: &TokenRequest) -> HttpRe
         // Continue anyway - better to issue new token than fail
     }
 
-    // Generate new tokens
-    let new_access_token = match oauth_token_service.generate_access_token() {
+    issue_tokens(
+        pool,
+        &client.client_id,
+        &stored_token.user_id,
+        &stored_token.organization_id,
+        &stored_token.scopes,
+    )
+    .await
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Issue and store a new access/refresh token pair
+async fn issue_tokens(
+    pool: &DbPool,
+    client_id: &str,
+    user_id: &str,
+    organization_id: &str,
+    scopes: &[String],
+) -> HttpResponse {
+    let oauth_token_service = OAuthTokenService::new();
+
+    let access_token = match oauth_token_service.generate_access_token() {
         Ok(t) => t,
         Err(e) => {
             tracing::error!("Failed to generate access token: {}", e);
@@ -603,7 +817,7 @@ async fn handle_refresh_token_grant(pool: &DbPool, req: &TokenRequest) -> HttpRe
         }
     };
 
-    let new_refresh_token = match oauth_token_service.generate_refresh_token() {
+    let refresh_token = match oauth_token_service.generate_refresh_token() {
         Ok(t) => t,
         Err(e) => {
             tracing::error!("Failed to generate refresh token: {}", e);
@@ -618,21 +832,21 @@ async fn handle_refresh_token_grant(pool: &DbPool, req: &TokenRequest) -> HttpRe
     let access_token_expires_at = Utc::now() + Duration::seconds(ACCESS_TOKEN_EXPIRES_IN_SECONDS);
     let refresh_token_expires_at = Utc::now() + Duration::seconds(REFRESH_TOKEN_EXPIRES_IN_SECONDS);
 
-    // Store new token in database
+    // Store token in database
     if let Err(e) = OAuthTokenRepository::create(
         pool,
-        &new_access_token.hash,
-        Some(&new_refresh_token.hash),
-        &client.client_id,
-        &stored_token.user_id,
-        &stored_token.organization_id,
-        &stored_token.scopes,
+        &access_token.hash,
+        Some(&refresh_token.hash),
+        client_id,
+        user_id,
+        organization_id,
+        scopes,
         access_token_expires_at,
         Some(refresh_token_expires_at),
     )
     .await
     {
-        tracing::error!("Failed to store new OAuth token: {}", e);
+        tracing::error!("Failed to store OAuth token: {}", e);
         return HttpResponse::InternalServerError().json(ErrorResponse::new(
             "internal_error",
             "Failed to create token",
@@ -641,19 +855,52 @@ async fn handle_refresh_token_grant(pool: &DbPool, req: &TokenRequest) -> HttpRe
 
     // Return token response
     let response = TokenResponse {
-        access_token: new_access_token.token,
+        access_token: access_token.token,
         token_type: "Bearer".to_string(),
         expires_in: ACCESS_TOKEN_EXPIRES_IN_SECONDS,
-        refresh_token: Some(new_refresh_token.token),
-        scope: crate::models::scopes_to_string(&stored_token.scopes),
+        refresh_token: Some(refresh_token.token),
+        scope: crate::models::scopes_to_string(scopes),
     };
 
     HttpResponse::Ok().json(response)
 }
 
-// ============================================================================
-// Helper Functions
-// ============================================================================
+/// 400 with an OAuth 2.0 error code (RFC 6749 Section 4.1.2.1 / 5.2)
+fn oauth_error(error: &str, message: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse::new(error, message))
+}
+
+/// 400 `invalid_grant`: the code, redirect_uri or code_verifier was rejected
+fn invalid_grant(message: &str) -> HttpResponse {
+    oauth_error("invalid_grant", message)
+}
+
+/// Authenticate the client of a token request
+///
+/// Confidential clients must present their secret. Public clients are
+/// identified by client_id alone; PKCE proves they started the flow.
+async fn authenticate_client(
+    pool: &DbPool,
+    client_id: &str,
+    client_secret: Option<&str>,
+) -> Result<Option<OAuthClient>, HttpResponse> {
+    if let Some(secret) = client_secret.filter(|s| !s.is_empty()) {
+        return verify_client_credentials(pool, client_id, secret).await;
+    }
+
+    match OAuthClientRepository::find_by_client_id(pool, client_id).await {
+        Ok(Some(client)) if client.is_public => Ok(Some(client)),
+        Ok(Some(_)) => Err(bad_request("client_secret is required")),
+        Ok(None) => Ok(None),
+        Err(e) => {
+            tracing::error!("Failed to find OAuth client: {}", e);
+            Err(HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to verify client credentials",
+            )))
+        }
+    }
+}
 
 /// Verify client credentials (client_id + client_secret)
 ///
@@ -663,7 +910,7 @@ async fn verify_client_credentials(
     pool: &DbPool,
     client_id: &str,
     client_secret: &str,
-) -> Result<Option<shared::models::OAuthClient>, HttpResponse> {
+) -> Result<Option<OAuthClient>, HttpResponse> {
     let oauth_client_service = OAuthClientService::new();
 
     // Find client by client_id
@@ -741,4 +988,65 @@ mod tests {
     fn test_refresh_token_expiration_constant() {
         assert_eq!(REFRESH_TOKEN_EXPIRES_IN_SECONDS, 604800); // 7 days
     }
+
+    // RFC 7636 Appendix B
+    const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    fn client(is_public: bool) -> OAuthClient {
+        OAuthClient {
+            id: "id".to_string(),
+            client_id: "client_abc".to_string(),
+            client_secret_hash: String::new(),
+            client_name: "Test".to_string(),
+            redirect_uris: vec!["https://app.example.com/callback".to_string()],
+            scopes: vec!["read".to_string()],
+            owner_organization_id: "org_1".to_string(),
+            grant_types: vec!["authorization_code".to_string()],
+            is_trusted: false,
+            is_public,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn grant(pkce: Option<PkceChallenge>) -> AuthorizationGrant {
+        AuthorizationGrant {
+            user_id: "user_1".to_string(),
+            client_id: "client_abc".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            organization_id: "org_1".to_string(),
+            scopes: vec!["read".to_string()],
+            pkce,
+        }
+    }
+
+    fn s256() -> Option<PkceChallenge> {
+        Some(PkceChallenge::new(CHALLENGE, Some("S256")).unwrap())
+    }
+
+    #[test]
+    fn test_verify_code_verifier_matching() {
+        assert!(verify_code_verifier(&client(true), &grant(s256()), Some(VERIFIER)).is_ok());
+        assert!(verify_code_verifier(&client(false), &grant(s256()), Some(VERIFIER)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_code_verifier_rejects_wrong_or_missing_verifier() {
+        let wrong = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXx";
+        let resp = verify_code_verifier(&client(true), &grant(s256()), Some(wrong)).unwrap_err();
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        assert!(verify_code_verifier(&client(true), &grant(s256()), None).is_err());
+    }
+
+    #[test]
+    fn test_verify_code_verifier_without_challenge() {
+        // Confidential clients may skip PKCE
+        assert!(verify_code_verifier(&client(false), &grant(None), None).is_ok());
+        // Public clients may not
+        assert!(verify_code_verifier(&client(true), &grant(None), None).is_err());
+        // A verifier for a code issued without a challenge is an injection attempt
+        assert!(verify_code_verifier(&client(false), &grant(None), Some(VERIFIER)).is_err());
+    }
 }
@@ -49,6 +49,7 @@ use crate::{
     handlers::helpers::{
         bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
     },
+    middleware::get_verified_organization,
     models::{
         can_delete_org, can_manage_members, can_manage_org, is_owner, AddMemberRequest,
         CreateOrganizationRequest, ErrorResponse, MemberResponse, OrganizationResponse,
@@ -351,15 +352,8 @@ pub async fn update_organization(
     }
 
     // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -420,15 +414,8 @@ pub async fn delete_organization(
     };
 
     // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -521,15 +508,8 @@ pub async fn add_member(
     }
 
     // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -765,15 +745,8 @@ pub async fn update_member_role(
     }
 
     // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -871,15 +844,8 @@ pub async fn remove_member(
     };
 
     // Check membership and role
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -971,15 +937,8 @@ pub async fn transfer_ownership(
     }
 
     // Check membership and role - must be owner
-    let role = match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
+    let role = match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+        Ok(org) => org.role,
         Err(resp) => return resp,
     };
 
@@ -6,7 +6,20 @@
 use actix_web::{web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use shared::DbPool;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::helpers::bad_request;
+use crate::models::ErrorResponse;
+use crate::repositories::{PonderEventFilter, PonderRepository};
+
+/// Widest block range accepted by `GET /ponder/events`
+pub const MAX_PONDER_BLOCK_RANGE: i64 = 1_000_000;
+
+/// Maximum length of the `q` search term
+const MAX_SEARCH_LEN: usize = 100;
+
+/// Registries indexed by Ponder, accepted by the `contract` filter
+const REGISTRIES: &[&str] = &["identity", "reputation", "validation"];
 
 /// Chain sync status
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -47,8 +60,80 @@ pub struct PonderStatusError {
     pub details: Option<String>,
 }
 
+/// Query parameters for `GET /ponder/events`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PonderEventsQuery {
+    /// Registry contract (identity, reputation, validation)
+    pub contract: Option<String>,
+    /// Event name (e.g. AgentRegistered)
+    pub event: Option<String>,
+    /// First block to include
+    #[param(minimum = 0)]
+    pub from_block: Option<i64>,
+    /// Last block to include (at most 1,000,000 blocks after from_block)
+    #[param(minimum = 0)]
+    pub to_block: Option<i64>,
+    /// Case-insensitive text search over decoded event arguments
+    pub q: Option<String>,
+}
+
+impl PonderEventsQuery {
+    /// Validate the parameters and turn them into a repository filter
+    fn to_filter(&self) -> Result<PonderEventFilter, String> {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        let registry = non_empty(&self.contract).map(|c| c.to_lowercase());
+        if let Some(registry) = &registry {
+            if !REGISTRIES.contains(&registry.as_str()) {
+                return Err(format!(
+                    "contract must be one of: {}",
+                    REGISTRIES.join(", ")
+                ));
+            }
+        }
+
+        if self.from_block.is_some_and(|b| b < 0) || self.to_block.is_some_and(|b| b < 0) {
+            return Err("Block numbers must not be negative".to_string());
+        }
+        if let (Some(from), Some(to)) = (self.from_block, self.to_block) {
+            if from > to {
+                return Err("from_block must be less than or equal to to_block".to_string());
+            }
+            if to - from > MAX_PONDER_BLOCK_RANGE {
+                return Err(format!(
+                    "Block range must not exceed {} blocks",
+                    MAX_PONDER_BLOCK_RANGE
+                ));
+            }
+        }
+
+        let search = non_empty(&self.q);
+        if search
+            .as_ref()
+            .is_some_and(|q| q.chars().count() > MAX_SEARCH_LEN)
+        {
+            return Err(format!("q must be at most {} characters", MAX_SEARCH_LEN));
+        }
+
+        Ok(PonderEventFilter {
+            registry,
+            event_type: non_empty(&self.event),
+            from_block: self.from_block,
+            to_block: self.to_block,
+            search,
+        })
+    }
+}
+
 /// Get the active Ponder namespace from the database
-async fn get_ponder_namespace(pool: &sqlx::PgPool) -> String {
+pub(crate) async fn get_ponder_namespace(pool: &sqlx::PgPool) -> String {
     // Try to find namespace from _ponder_meta table
     let namespace_result: Result<Option<String>, sqlx::Error> =
         sqlx::query_scalar(r#"SELECT value FROM public._ponder_meta WHERE key = 'app'"#)
@@ -227,17 +312,29 @@ pub async fn get_ponder_status(pool: web::Data<DbPool>) -> impl Responder {
 
 /// Get event counts per chain
 ///
-/// Returns detailed event statistics grouped by chain and event type.
+/// Returns detailed event statistics grouped by chain and event type,
+/// optionally narrowed by registry, event name, block range and a text search
+/// over decoded event arguments.
 #[utoipa::path(
     get,
     path = "/api/v1/ponder/events",
     tag = "Ponder",
+    params(PonderEventsQuery),
     responses(
         (status = 200, description = "Event statistics retrieved successfully"),
+        (status = 400, description = "Invalid filter", body = ErrorResponse),
         (status = 500, description = "Failed to retrieve event statistics", body = PonderStatusError)
     )
 )]
-pub async fn get_ponder_events(pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_ponder_events(
+    pool: web::Data<DbPool>,
+    query: web::Query<PonderEventsQuery>,
+) -> impl Responder {
+    let filter = match query.to_filter() {
+        Ok(filter) => filter,
+        Err(message) => return bad_request(&message),
+    };
+
     // Find the active Ponder namespace first
     let namespace = get_ponder_namespace(pool.get_ref()).await;
 
@@ -248,44 +345,14 @@ pub async fn get_ponder_events(pool: web::Data<DbPool>) -> impl Responder {
         }));
     }
 
-    // Get event counts grouped by chain and type using namespaced table
-    let query = format!(
-        r#"
-        SELECT
-            "chainId"::bigint,
-            "eventType",
-            COUNT(*)::bigint as count
-        FROM public."{namespace}__Event"
-        GROUP BY "chainId", "eventType"
-        ORDER BY "chainId", count DESC
-        "#,
-        namespace = namespace
-    );
-
-    let events_result: Result<Vec<(i64, String, i64)>, sqlx::Error> =
-        sqlx::query_as(&query).fetch_all(pool.get_ref()).await;
-
-    match events_result {
-        Ok(rows) => {
-            let events: Vec<serde_json::Value> = rows
-                .into_iter()
-                .map(|(chain_id, event_name, count)| {
-                    serde_json::json!({
-                        "chain_id": chain_id,
-                        "event_name": event_name,
-                        "count": count
-                    })
-                })
-                .collect();
-
-            HttpResponse::Ok().json(serde_json::json!({
-                "events": events,
-                "total_types": events.len()
-            }))
-        }
+    match PonderRepository::count_events(pool.get_ref(), &namespace, &filter).await {
+        Ok(events) => HttpResponse::Ok().json(serde_json::json!({
+            "total_types": events.len(),
+            "events": events,
+        })),
         Err(e) => {
-            let error_str = e.to_string();
-            if error_str.contains("does not exist") {
+            // The indexer may not have created its tables yet
+            if format!("{:#}", e).contains("does not exist") {
                 return HttpResponse::Ok().json(serde_json::json!({
                     "events": [],
                     "total_types": 0
@@ -324,4 +391,80 @@ mod tests {
         assert!(json.contains("ethereumSepolia"));
         assert!(json.contains("11155111"));
     }
+
+    #[test]
+    fn test_events_query_builds_filter() {
+        let query = PonderEventsQuery {
+            contract: Some(" Reputation ".to_string()),
+            event: Some("NewFeedback".to_string()),
+            from_block: Some(100),
+            to_block: Some(200),
+            q: Some("ipfs://".to_string()),
+        };
+
+        let filter = query.to_filter().unwrap();
+        assert_eq!(filter.registry.as_deref(), Some("reputation"));
+        assert_eq!(filter.event_type.as_deref(), Some("NewFeedback"));
+        assert_eq!(filter.from_block, Some(100));
+        assert_eq!(filter.to_block, Some(200));
+        assert_eq!(filter.search.as_deref(), Some("ipfs://"));
+    }
+
+    #[test]
+    fn test_events_query_treats_blank_values_as_unset() {
+        let query = PonderEventsQuery {
+            contract: Some("".to_string()),
+            q: Some("   ".to_string()),
+            ..Default::default()
+        };
+
+        let filter = query.to_filter().unwrap();
+        assert!(filter.registry.is_none());
+        assert!(filter.search.is_none());
+    }
+
+    #[test]
+    fn test_events_query_rejects_invalid_block_ranges() {
+        let reversed = PonderEventsQuery {
+            from_block: Some(200),
+            to_block: Some(100),
+            ..Default::default()
+        };
+        assert!(reversed.to_filter().is_err());
+
+        let too_wide = PonderEventsQuery {
+            from_block: Some(0),
+            to_block: Some(MAX_PONDER_BLOCK_RANGE + 1),
+            ..Default::default()
+        };
+        assert!(too_wide.to_filter().is_err());
+
+        let negative = PonderEventsQuery {
+            from_block: Some(-1),
+            ..Default::default()
+        };
+        assert!(negative.to_filter().is_err());
+
+        let widest = PonderEventsQuery {
+            from_block: Some(0),
+            to_block: Some(MAX_PONDER_BLOCK_RANGE),
+            ..Default::default()
+        };
+        assert!(widest.to_filter().is_ok());
+    }
+
+    #[test]
+    fn test_events_query_rejects_unknown_contract_and_long_search() {
+        let contract = PonderEventsQuery {
+            contract: Some("payments".to_string()),
+            ..Default::default()
+        };
+        assert!(contract.to_filter().is_err());
+
+        let search = PonderEventsQuery {
+            q: Some("x".repeat(MAX_SEARCH_LEN + 1)),
+            ..Default::default()
+        };
+        assert!(search.to_filter().is_err());
+    }
 }
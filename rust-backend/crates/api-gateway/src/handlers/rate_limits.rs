@@ -0,0 +1,156 @@
+//! Organization Rate Limit Admin Handlers
+//!
+//! Operator endpoints for per-organization rate limit overrides. Organizations
+//! must not be able to raise their own limits, so these endpoints are not gated
+//! by organization role: they require the `X-Admin-Token` header to match the
+//! `ADMIN_API_TOKEN` environment variable, and are disabled when it is unset.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/admin/organizations/{id}/rate-limits` - Get overrides
+//! - `PUT /api/v1/admin/organizations/{id}/rate-limits` - Replace overrides
+//!
+//! Changes invalidate the cached overrides, so the rate limiter applies them
+//! on the next request without a restart.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::redis::cache::EntityCache;
+use shared::DbPool;
+
+use crate::{
+    handlers::helpers::{handle_db_error, require_admin_token, validate_request},
+    models::{
+        ErrorResponse, OrganizationRateLimitsResponse, RateLimitTierResponse,
+        SetOrganizationRateLimitsRequest, SuccessResponse,
+    },
+    repositories::{OrganizationRateLimitRepository, OrganizationRepository},
+};
+
+/// Return 404 unless the organization exists
+async fn require_organization(pool: &DbPool, org_id: &str) -> Result<(), HttpResponse> {
+    match handle_db_error(
+        OrganizationRepository::find_by_id(pool, org_id).await,
+        "fetch organization",
+    )? {
+        Some(_) => Ok(()),
+        None => Err(HttpResponse::NotFound()
+            .json(ErrorResponse::new("not_found", "Organization not found"))),
+    }
+}
+
+/// Get an organization's rate limit overrides
+///
+/// Tiers that are not listed use the organization's plan limit.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/organizations/{id}/rate-limits",
+    tag = "Admin",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Rate limit overrides", body = SuccessResponse<OrganizationRateLimitsResponse>),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_org_rate_limits(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let org_id = path.into_inner();
+
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+
+    if let Err(resp) = require_organization(&pool, &org_id).await {
+        return resp;
+    }
+
+    let limits = match handle_db_error(
+        OrganizationRateLimitRepository::list_by_organization(&pool, &org_id).await,
+        "list rate limits",
+    ) {
+        Ok(limits) => limits,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(OrganizationRateLimitsResponse {
+        organization_id: org_id,
+        limits: limits
+            .into_iter()
+            .map(RateLimitTierResponse::from)
+            .collect(),
+    }))
+}
+
+/// Replace an organization's rate limit overrides
+///
+/// The submitted list replaces all existing overrides; send an empty list to
+/// revert the organization to its plan limits.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/organizations/{id}/rate-limits",
+    tag = "Admin",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    request_body = SetOrganizationRateLimitsRequest,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Rate limit overrides updated", body = SuccessResponse<OrganizationRateLimitsResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 403, description = "Missing or invalid admin token", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_org_rate_limits(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<SetOrganizationRateLimitsRequest>,
+) -> impl Responder {
+    let org_id = path.into_inner();
+
+    if let Err(resp) = require_admin_token(&req_http) {
+        return resp;
+    }
+
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    if let Err(resp) = require_organization(&pool, &org_id).await {
+        return resp;
+    }
+
+    let limits = match handle_db_error(
+        OrganizationRateLimitRepository::replace_for_organization(&pool, &org_id, &req.limits)
+            .await,
+        "update rate limits",
+    ) {
+        Ok(limits) => limits,
+        Err(resp) => return resp,
+    };
+
+    if let Some(cache) = req_http.app_data::<web::Data<EntityCache>>() {
+        OrganizationRateLimitRepository::invalidate_cache(cache.get_ref(), &org_id).await;
+    }
+
+    tracing::info!(
+        organization_id = %org_id,
+        overrides = limits.len(),
+        "Organization rate limits updated"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(OrganizationRateLimitsResponse {
+        organization_id: org_id,
+        limits: limits
+            .into_iter()
+            .map(RateLimitTierResponse::from)
+            .collect(),
+    }))
+}
@@ -0,0 +1,201 @@
+//! Organization Signing Key Handlers
+//!
+//! Manages the key each organization's outbound deliveries are signed with
+//! (REST actions and action result webhooks; see [`shared::signing`]).
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/organizations/{id}/signing-keys` - List keys (admin+)
+//! - `POST /api/v1/organizations/{id}/signing-keys` - Create the first key (admin+)
+//! - `POST /api/v1/organizations/{id}/signing-keys/rotate` - Rotate (admin+)
+//!
+//! Secrets are only returned when a key is created. After a rotation the
+//! previous key keeps signing alongside the new one for the grace window, so
+//! receivers can switch keys without dropping deliveries.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::DbPool;
+
+use crate::{
+    handlers::helpers::{
+        extract_user_id_or_unauthorized, forbidden, handle_db_error, require_found,
+        validate_request,
+    },
+    middleware::get_verified_organization,
+    models::{
+        can_manage_org, ErrorResponse, RotateSigningKeyRequest, RotateSigningKeyResponse,
+        SigningKeyResponse, SuccessResponse,
+    },
+    repositories::SigningKeyRepository,
+};
+
+/// Check the caller is an owner or admin of the organization in the path
+///
+/// Answers 404 for non-members so key management does not reveal which
+/// organizations exist. Returns the caller's user ID and the organization ID.
+async fn require_key_manager(
+    req_http: &HttpRequest,
+    pool: &DbPool,
+) -> Result<(String, String), HttpResponse> {
+    let user_id = extract_user_id_or_unauthorized(req_http)?;
+
+    let org = get_verified_organization(req_http, pool, &user_id, Some("id")).await?;
+    if !can_manage_org(&org.role) {
+        return Err(forbidden("Insufficient permissions to manage signing keys"));
+    }
+
+    Ok((user_id, org.id))
+}
+
+/// List the organization's signing keys
+///
+/// Secrets are never included; keys are identified by their prefix.
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{id}/signing-keys",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Signing keys, newest first", body = SuccessResponse<Vec<SigningKeyResponse>>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_signing_keys(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
+    let org_id = match require_key_manager(&req_http, &pool).await {
+        Ok((_, org_id)) => org_id,
+        Err(resp) => return resp,
+    };
+
+    let keys = match handle_db_error(
+        SigningKeyRepository::list_by_organization(&pool, &org_id).await,
+        "list signing keys",
+    ) {
+        Ok(keys) => keys,
+        Err(resp) => return resp,
+    };
+
+    let keys: Vec<SigningKeyResponse> = keys
+        .into_iter()
+        .map(|key| SigningKeyResponse::new(key, false))
+        .collect();
+    HttpResponse::Ok().json(SuccessResponse::new(keys))
+}
+
+/// Create the organization's signing key
+///
+/// Deliveries are signed with it from then on. The response includes the
+/// secret: store it, it is not shown again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{id}/signing-keys",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Signing key created - secret shown once", body = SuccessResponse<SigningKeyResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse),
+        (status = 409, description = "Organization already has a signing key (rotate it)", body = ErrorResponse)
+    )
+)]
+pub async fn create_signing_key(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
+    let (user_id, org_id) = match require_key_manager(&req_http, &pool).await {
+        Ok(ids) => ids,
+        Err(resp) => return resp,
+    };
+
+    let key = match handle_db_error(
+        SigningKeyRepository::create(&pool, &org_id, &user_id).await,
+        "create signing key",
+    ) {
+        Ok(Some(key)) => key,
+        Ok(None) => {
+            return HttpResponse::Conflict().json(ErrorResponse::new(
+                "signing_key_exists",
+                "Organization already has a signing key, rotate it instead",
+            ))
+        }
+        Err(resp) => return resp,
+    };
+
+    tracing::info!(
+        organization_id = %org_id,
+        key_id = %key.id,
+        created_by = %user_id,
+        "Signing key created"
+    );
+
+    HttpResponse::Created().json(SuccessResponse::new(SigningKeyResponse::new(key, true)))
+}
+
+/// Rotate the organization's signing key
+///
+/// Generates a new current key. The previous key keeps signing alongside it
+/// for `grace_period_hours` (default 24; 0 revokes it at once, e.g. after a
+/// leak). The response includes the new secret: store it, it is not shown
+/// again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{id}/signing-keys/rotate",
+    tag = "Organizations",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    request_body(content = Option<RotateSigningKeyRequest>, description = "Optional grace window for the previous key"),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Signing key rotated - new secret shown once", body = SuccessResponse<RotateSigningKeyResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization or signing key not found", body = ErrorResponse)
+    )
+)]
+pub async fn rotate_signing_key(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    req: Option<web::Json<RotateSigningKeyRequest>>,
+) -> impl Responder {
+    let req = req.map(web::Json::into_inner).unwrap_or_default();
+
+    if let Err(resp) = validate_request(&req) {
+        return resp;
+    }
+
+    let (user_id, org_id) = match require_key_manager(&req_http, &pool).await {
+        Ok(ids) => ids,
+        Err(resp) => return resp,
+    };
+
+    let (current, previous) = match handle_db_error(
+        SigningKeyRepository::rotate(&pool, &org_id, req.grace_period_secs(), &user_id).await,
+        "rotate signing key",
+    )
+    .and_then(|rotated| require_found(rotated, "Signing key"))
+    {
+        Ok(rotated) => rotated,
+        Err(resp) => return resp,
+    };
+
+    tracing::info!(
+        organization_id = %org_id,
+        key_id = %current.id,
+        previous_key_id = %previous.id,
+        grace_period_secs = req.grace_period_secs(),
+        rotated_by = %user_id,
+        "Signing key rotated"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(RotateSigningKeyResponse {
+        current: SigningKeyResponse::new(current, true),
+        previous: SigningKeyResponse::new(previous, false),
+    }))
+}
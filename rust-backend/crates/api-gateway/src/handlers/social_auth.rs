@@ -495,6 +495,14 @@ async fn handle_oauth_callback(
     // Handle errors explicitly to prevent creating duplicate accounts
     match UserRepository::find_by_email(&pool, &email).await {
         Ok(Some(existing_user)) => {
+            // The provider vouching for the address verifies it
+            if profile.email_verified && existing_user.email_verification_pending() {
+                if let Err(e) = UserRepository::mark_email_verified(&pool, &existing_user.id).await
+                {
+                    tracing::warn!("Failed to mark email verified: {}", e);
+                }
+            }
+
             // Email matches existing user - link this identity to that user
             return link_and_login(
                 pool,
@@ -768,6 +776,7 @@ async fn create_new_user(
         provider,
         profile.avatar_url.as_deref(),
         profile.display_name.as_deref(),
+        profile.email_verified,
     )
     .await
     {
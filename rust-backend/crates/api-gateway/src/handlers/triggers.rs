@@ -1,19 +1,28 @@
 //! Trigger handlers
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use shared::DbPool;
+use shared::{ActionJob, ActionType, DbPool, DbPools};
+use std::str::FromStr;
+use uuid::Uuid;
 
 use crate::{
     handlers::helpers::{
         extract_user_id_or_unauthorized, forbidden, handle_db_error, validate_request,
     },
-    middleware::{get_verified_organization_id, get_verified_organization_id_with_role},
+    middleware::{
+        get_verified_organization, get_verified_organization_id,
+        get_verified_organization_id_with_role,
+    },
     models::{
-        can_write, ActionResponse, ConditionResponse, CreateTriggerRequest, ErrorResponse,
+        can_manage_org, can_write, ActionResponse, ConditionResponse, CreateTriggerRequest,
+        ErrorResponse, FireTriggerRequest, FireTriggerResponse, FiredJobResponse,
         PaginatedResponse, PaginationMeta, PaginationParams, SuccessResponse,
-        TriggerDetailResponse, TriggerResponse, UpdateTriggerRequest,
+        TriggerDetailResponse, TriggerExportBundle, TriggerImportQuery, TriggerImportReport,
+        TriggerListQuery, TriggerResponse, TriggerStateResponse, UpdateTriggerRequest,
+        MAX_TRIGGERS_PER_IMPORT, TRIGGER_BUNDLE_VERSION,
     },
-    repositories::{ActionRepository, ConditionRepository, MemberRepository, TriggerRepository},
+    repositories::{ActionRepository, ConditionRepository, TriggerRepository},
+    services::{ActionJobQueue, TriggerExportService, WebhookVerifier},
 };
 
 /// Create a new trigger
@@ -72,6 +81,8 @@ pub async fn create_trigger(
             &req.registry,
             req.enabled.unwrap_or(true),
             req.is_stateful.unwrap_or(false),
+            req.cooldown_secs.unwrap_or(0),
+            req.schedule.as_ref(),
         )
         .await,
         "create trigger",
@@ -93,18 +104,22 @@ pub async fn create_trigger(
     tag = "Triggers",
     params(
         ("limit" = Option<i64>, Query, description = "Maximum items per page"),
-        ("offset" = Option<i64>, Query, description = "Number of items to skip")
+        ("offset" = Option<i64>, Query, description = "Number of items to skip"),
+        ("include_deleted" = Option<bool>, Query, description = "Also list soft-deleted triggers (admins only)")
     ),
     security(("bearer_auth" = []), ("organization_id" = [])),
     responses(
         (status = 200, description = "List of triggers", body = PaginatedResponse<TriggerResponse>),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "include_deleted requires admin role", body = ErrorResponse)
     )
 )]
 pub async fn list_triggers(
     pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
     req_http: HttpRequest,
     query: web::Query<PaginationParams>,
+    filter: web::Query<TriggerListQuery>,
 ) -> impl Responder {
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
@@ -113,10 +128,16 @@ pub async fn list_triggers(
     };
 
     // Get and verify organization_id from header (any role can list)
-    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
-        Ok(id) => id,
-        Err(response) => return response,
-    };
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    // Only admins see deleted triggers
+    if filter.include_deleted && !can_manage_org(&role) {
+        return forbidden("Only organization admins can list deleted triggers");
+    }
 
     // Validate pagination
     if let Err(e) = query.validate() {
@@ -126,10 +147,26 @@ pub async fn list_triggers(
         ));
     }
 
-    // Execute count and list in parallel for better performance
+    // Execute count and list in parallel on the read pool for better performance
     let (total_result, triggers_result) = tokio::join!(
-        TriggerRepository::count_by_organization(&pool, &organization_id),
-        TriggerRepository::list_by_organization(&pool, &organization_id, query.limit, query.offset)
+        pools.timed(
+            "count_triggers",
+            TriggerRepository::count_by_organization(
+                pools.read(),
+                &organization_id,
+                filter.include_deleted
+            )
+        ),
+        pools.timed(
+            "list_triggers",
+            TriggerRepository::list_by_organization(
+                pools.read(),
+                &organization_id,
+                filter.include_deleted,
+                query.limit,
+                query.offset
+            )
+        )
     );
 
     // Handle count result
@@ -319,6 +356,8 @@ pub async fn update_trigger(
             req.registry.as_deref(),
             req.enabled,
             req.is_stateful,
+            req.cooldown_secs,
+            req.schedule.as_ref().map(Option::as_ref),
         )
         .await,
         "update trigger",
@@ -333,7 +372,9 @@ pub async fn update_trigger(
 
 /// Delete a trigger
 ///
-/// Permanently deletes a trigger and its conditions/actions. Requires write permission.
+/// Soft-deletes a trigger: it stops firing and disappears from listings, but
+/// can be restored with `POST /api/v1/triggers/{id}/restore` until it is
+/// purged after the retention window. Requires write permission.
 #[utoipa::path(
     delete,
     path = "/api/v1/triggers/{id}",
@@ -387,9 +428,9 @@ pub async fn delete_trigger(
         return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Trigger not found"));
     }
 
-    // Delete trigger
+    // Soft-delete trigger (restorable until purged)
     let deleted = match handle_db_error(
-        TriggerRepository::delete(&pool, &trigger_id).await,
+        TriggerRepository::soft_delete(&pool, &trigger_id).await,
         "delete trigger",
     ) {
         Ok(deleted) => deleted,
@@ -403,6 +444,331 @@ pub async fn delete_trigger(
     HttpResponse::NoContent().finish()
 }
 
+/// Restore a deleted trigger
+///
+/// Undoes a delete, provided the trigger has not been purged yet. The trigger
+/// comes back with its conditions, actions and enabled flag unchanged.
+/// Requires write permission.
+#[utoipa::path(
+    post,
+    path = "/api/v1/triggers/{id}/restore",
+    tag = "Triggers",
+    params(
+        ("id" = String, Path, description = "Trigger ID")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Trigger restored", body = SuccessResponse<TriggerResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "No deleted trigger with this ID", body = ErrorResponse)
+    )
+)]
+pub async fn restore_trigger(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let trigger_id = path.into_inner();
+
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Get and verify organization_id from header (also gets role)
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    // Check user has write access
+    if !can_write(&role) {
+        return forbidden("Insufficient permissions to restore triggers");
+    }
+
+    // Restore only matches deleted triggers of this organization
+    let trigger = match handle_db_error(
+        TriggerRepository::restore(&pool, &trigger_id, &organization_id).await,
+        "restore trigger",
+    ) {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Deleted trigger not found"));
+        }
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(TriggerResponse::from(trigger)))
+}
+
+/// Fire a trigger manually
+///
+/// Enqueues one job per trigger action without waiting for a matching event,
+/// which is useful for testing action configuration and templates. Conditions
+/// are not evaluated and the trigger fires even when disabled. Requires write
+/// permission.
+#[utoipa::path(
+    post,
+    path = "/api/v1/triggers/{id}/fire",
+    tag = "Triggers",
+    params(
+        ("id" = String, Path, description = "Trigger ID")
+    ),
+    request_body = FireTriggerRequest,
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 202, description = "Action jobs enqueued", body = SuccessResponse<FireTriggerResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Trigger not found", body = ErrorResponse),
+        (status = 422, description = "Trigger has no actions", body = ErrorResponse),
+        (status = 503, description = "Job queue unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn fire_trigger(
+    pool: web::Data<DbPool>,
+    queue: web::Data<ActionJobQueue>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<FireTriggerRequest>,
+) -> impl Responder {
+    let trigger_id = path.into_inner();
+
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Get and verify organization_id from header (also gets role)
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    // Check user has write access
+    if !can_write(&role) {
+        return forbidden("Insufficient permissions to fire triggers");
+    }
+
+    // Validate request
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    // Check if trigger belongs to the organization
+    let belongs = match handle_db_error(
+        TriggerRepository::belongs_to_organization(&pool, &trigger_id, &organization_id).await,
+        "check trigger organization",
+    ) {
+        Ok(belongs) => belongs,
+        Err(resp) => return resp,
+    };
+
+    if !belongs {
+        return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Trigger not found"));
+    }
+
+    let trigger = match handle_db_error(
+        TriggerRepository::find_by_id(&pool, &trigger_id).await,
+        "fetch trigger",
+    ) {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Trigger not found"));
+        }
+        Err(resp) => return resp,
+    };
+
+    let actions = match handle_db_error(
+        ActionRepository::list_by_trigger(&pool, &trigger_id).await,
+        "fetch actions",
+    ) {
+        Ok(actions) => actions,
+        Err(resp) => return resp,
+    };
+
+    if actions.is_empty() {
+        return HttpResponse::UnprocessableEntity().json(ErrorResponse::new(
+            "no_actions",
+            "Trigger has no actions to fire",
+        ));
+    }
+
+    // Synthetic event: caller-supplied fields win over the defaults
+    let event_id = format!("manual-{}", Uuid::new_v4());
+    let correlation_id = Uuid::new_v4().to_string();
+    let mut event_data = req
+        .into_inner()
+        .event_data
+        .and_then(|data| data.as_object().cloned())
+        .unwrap_or_default();
+    event_data
+        .entry("event_id")
+        .or_insert_with(|| event_id.clone().into());
+    event_data
+        .entry("registry")
+        .or_insert_with(|| trigger.registry.clone().into());
+    event_data
+        .entry("chain_id")
+        .or_insert_with(|| trigger.chain_id.into());
+    let event_data = serde_json::Value::Object(event_data);
+
+    let mut jobs = Vec::with_capacity(actions.len());
+    for action in &actions {
+        let action_type = match ActionType::from_str(&action.action_type) {
+            Ok(action_type) => action_type,
+            Err(e) => {
+                tracing::warn!(
+                    trigger_id = %trigger_id,
+                    action_id = action.id,
+                    error = %e,
+                    "Skipping action with unknown type on manual fire"
+                );
+                continue;
+            }
+        };
+
+        let job = ActionJob::new(
+            &trigger_id,
+            &event_id,
+            action_type,
+            action.priority,
+            action.config.clone(),
+            event_data.clone(),
+        )
+        .with_correlation_id(correlation_id.as_str())
+        .with_organization_id(organization_id.as_str())
+        .with_action_id(action.id);
+
+        if let Err(e) = queue.enqueue(&job).await {
+            tracing::error!(
+                trigger_id = %trigger_id,
+                job_id = %job.id,
+                error = %e,
+                "Failed to enqueue manual action job"
+            );
+            return HttpResponse::ServiceUnavailable().json(ErrorResponse::new(
+                "service_unavailable",
+                format!(
+                    "Job queue unavailable; {} of {} action jobs were enqueued",
+                    jobs.len(),
+                    actions.len()
+                ),
+            ));
+        }
+
+        jobs.push(FiredJobResponse {
+            job_id: job.id,
+            action_id: action.id,
+            action_type: action.action_type.clone(),
+        });
+    }
+
+    tracing::info!(
+        trigger_id = %trigger_id,
+        user_id = %user_id,
+        event_id = %event_id,
+        jobs = jobs.len(),
+        "Trigger fired manually"
+    );
+
+    HttpResponse::Accepted().json(SuccessResponse::new(FireTriggerResponse {
+        trigger_id,
+        event_id,
+        correlation_id,
+        jobs,
+    }))
+}
+
+/// Get stateful evaluator state for a trigger
+///
+/// Returns the EMA and rate counter state kept by the event processor.
+/// `state` is null until the trigger is first evaluated.
+#[utoipa::path(
+    get,
+    path = "/api/v1/triggers/{id}/state",
+    tag = "Triggers",
+    params(
+        ("id" = String, Path, description = "Trigger ID")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Trigger state", body = SuccessResponse<TriggerStateResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Trigger not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_trigger_state(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let trigger_id = path.into_inner();
+
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Get and verify organization_id from header (any role can view)
+    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    // Check if trigger belongs to the organization
+    let belongs = match handle_db_error(
+        TriggerRepository::belongs_to_organization(&pool, &trigger_id, &organization_id).await,
+        "check trigger organization",
+    ) {
+        Ok(belongs) => belongs,
+        Err(resp) => return resp,
+    };
+
+    if !belongs {
+        return HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Trigger not found"));
+    }
+
+    let (trigger_result, state_result) = tokio::join!(
+        TriggerRepository::find_by_id(&pool, &trigger_id),
+        TriggerRepository::get_state(&pool, &trigger_id)
+    );
+
+    let trigger = match handle_db_error(trigger_result, "fetch trigger") {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .json(ErrorResponse::new("not_found", "Trigger not found"));
+        }
+        Err(resp) => return resp,
+    };
+
+    let state = match handle_db_error(state_result, "fetch trigger state") {
+        Ok(state) => state,
+        Err(resp) => return resp,
+    };
+
+    let response = TriggerStateResponse {
+        trigger_id,
+        is_stateful: trigger.is_stateful,
+        last_updated: state.as_ref().map(|s| s.last_updated),
+        last_fired_at: state.as_ref().and_then(|s| s.last_fired_at),
+        // A stateless trigger only has a state row to track its cooldown
+        state: state.map(|s| s.state_data).filter(|_| trigger.is_stateful),
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(response))
+}
+
 // =============================================================================
 // Organization-scoped endpoints (path parameter for org_id)
 // =============================================================================
@@ -424,17 +790,17 @@ pub async fn delete_trigger(
     responses(
         (status = 200, description = "List of triggers", body = PaginatedResponse<TriggerResponse>),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "include_deleted requires admin role", body = ErrorResponse),
         (status = 404, description = "Organization not found", body = ErrorResponse)
     )
 )]
 pub async fn list_org_triggers(
     pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
     req_http: HttpRequest,
-    path: web::Path<String>,
     query: web::Query<PaginationParams>,
+    filter: web::Query<TriggerListQuery>,
 ) -> impl Responder {
-    let org_id = path.into_inner();
-
     // Get authenticated user_id
     let user_id = match extract_user_id_or_unauthorized(&req_http) {
         Ok(id) => id,
@@ -449,23 +815,34 @@ pub async fn list_org_triggers(
         ));
     }
 
-    // Check membership (any role can list)
-    match handle_db_error(
-        MemberRepository::get_role(&pool, &org_id, &user_id).await,
-        "check membership",
-    ) {
-        Ok(Some(_)) => {} // User is a member
-        Ok(None) => {
-            return HttpResponse::NotFound()
-                .json(ErrorResponse::new("not_found", "Organization not found"))
-        }
-        Err(resp) => return resp,
-    };
+    // Resolve the organization and check membership (any role can list)
+    let (org_id, role) =
+        match get_verified_organization(&req_http, &pool, &user_id, Some("id")).await {
+            Ok(org) => (org.id, org.role),
+            Err(resp) => return resp,
+        };
 
-    // Execute count and list in parallel for better performance
+    // Only admins see deleted triggers
+    if filter.include_deleted && !can_manage_org(&role) {
+        return forbidden("Only organization admins can list deleted triggers");
+    }
+
+    // Execute count and list in parallel on the read pool for better performance
     let (total_result, triggers_result) = tokio::join!(
-        TriggerRepository::count_by_organization(&pool, &org_id),
-        TriggerRepository::list_by_organization(&pool, &org_id, query.limit, query.offset)
+        pools.timed(
+            "count_triggers",
+            TriggerRepository::count_by_organization(pools.read(), &org_id, filter.include_deleted)
+        ),
+        pools.timed(
+            "list_triggers",
+            TriggerRepository::list_by_organization(
+                pools.read(),
+                &org_id,
+                filter.include_deleted,
+                query.limit,
+                query.offset
+            )
+        )
     );
 
     // Handle count result
@@ -487,3 +864,220 @@ pub async fn list_org_triggers(
 
     HttpResponse::Ok().json(response)
 }
+
+/// Export all triggers of an organization
+///
+/// Returns a portable JSON bundle of every trigger with its conditions and
+/// actions. Ids and ownership are stripped so the bundle can be imported into
+/// another organization. Triggers managed by agent follows are listed under
+/// `excluded`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{id}/triggers/export",
+    tag = "Triggers",
+    params(
+        ("id" = String, Path, description = "Organization ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Trigger bundle", body = SuccessResponse<TriggerExportBundle>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn export_org_triggers(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
+    export_bundle(&pool, &req_http, Some("id")).await
+}
+
+/// Export all triggers of the organization
+///
+/// Same as `GET /api/v1/organizations/{id}/triggers/export`, for the
+/// organization given by `X-Organization-ID` or `organization_id`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/triggers/export",
+    tag = "Triggers",
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Trigger bundle", body = SuccessResponse<TriggerExportBundle>),
+        (status = 400, description = "Missing organization", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn export_triggers(pool: web::Data<DbPool>, req_http: HttpRequest) -> impl Responder {
+    export_bundle(&pool, &req_http, None).await
+}
+
+/// Import triggers into an organization
+///
+/// Validates and creates every trigger in the bundle inside a single
+/// transaction. Each trigger is applied independently, so invalid entries are
+/// reported without blocking the others. With `dry_run=true` the import is
+/// rolled back and only the report is returned. Requires write permission.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{id}/triggers/import",
+    tag = "Triggers",
+    params(
+        ("id" = String, Path, description = "Organization ID"),
+        ("dry_run" = Option<bool>, Query, description = "Report what would be imported without creating anything")
+    ),
+    request_body = TriggerExportBundle,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Per-trigger import results", body = SuccessResponse<TriggerImportReport>),
+        (status = 400, description = "Unsupported bundle version or too many triggers", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn import_org_triggers(
+    pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
+    req_http: HttpRequest,
+    query: web::Query<TriggerImportQuery>,
+    bundle: web::Json<TriggerExportBundle>,
+) -> impl Responder {
+    import_bundle(
+        &pool,
+        &verifier,
+        &req_http,
+        Some("id"),
+        &bundle,
+        query.dry_run,
+    )
+    .await
+}
+
+/// Import triggers into the organization
+///
+/// Same as `POST /api/v1/organizations/{id}/triggers/import`, for the
+/// organization given by `X-Organization-ID` or `organization_id`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/triggers/import",
+    tag = "Triggers",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Report what would be imported without creating anything")
+    ),
+    request_body = TriggerExportBundle,
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Per-trigger import results", body = SuccessResponse<TriggerImportReport>),
+        (status = 400, description = "Missing organization, unsupported bundle version or too many triggers", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Organization not found", body = ErrorResponse)
+    )
+)]
+pub async fn import_triggers(
+    pool: web::Data<DbPool>,
+    verifier: web::Data<WebhookVerifier>,
+    req_http: HttpRequest,
+    query: web::Query<TriggerImportQuery>,
+    bundle: web::Json<TriggerExportBundle>,
+) -> impl Responder {
+    import_bundle(&pool, &verifier, &req_http, None, &bundle, query.dry_run).await
+}
+
+/// Export the triggers of the organization resolved from `path_param`, the
+/// header or the query
+async fn export_bundle(
+    pool: &DbPool,
+    req_http: &HttpRequest,
+    path_param: Option<&str>,
+) -> HttpResponse {
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Resolve the organization and check membership (any role can export)
+    let org_id = match get_verified_organization(req_http, pool, &user_id, path_param).await {
+        Ok(org) => org.id,
+        Err(resp) => return resp,
+    };
+
+    let bundle = match handle_db_error(
+        TriggerExportService::export_organization(pool, &org_id).await,
+        "export triggers",
+    ) {
+        Ok(bundle) => bundle,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(SuccessResponse::new(bundle))
+}
+
+/// Import a bundle into the organization resolved from `path_param`, the
+/// header or the query
+async fn import_bundle(
+    pool: &DbPool,
+    verifier: &WebhookVerifier,
+    req_http: &HttpRequest,
+    path_param: Option<&str>,
+    bundle: &TriggerExportBundle,
+    dry_run: bool,
+) -> HttpResponse {
+    // Get authenticated user_id
+    let user_id = match extract_user_id_or_unauthorized(req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    // Resolve the organization and check membership and write access
+    let (org_id, role) = match get_verified_organization(req_http, pool, &user_id, path_param).await
+    {
+        Ok(org) => (org.id, org.role),
+        Err(resp) => return resp,
+    };
+
+    if !can_write(&role) {
+        return forbidden("Insufficient permissions to import triggers");
+    }
+
+    if bundle.version != TRIGGER_BUNDLE_VERSION {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "unsupported_version",
+            format!(
+                "Unsupported bundle version {} (expected {})",
+                bundle.version, TRIGGER_BUNDLE_VERSION
+            ),
+        ));
+    }
+
+    if bundle.triggers.len() > MAX_TRIGGERS_PER_IMPORT {
+        return HttpResponse::BadRequest().json(ErrorResponse::new(
+            "validation_error",
+            format!(
+                "Bundle contains {} triggers (maximum {} per import)",
+                bundle.triggers.len(),
+                MAX_TRIGGERS_PER_IMPORT
+            ),
+        ));
+    }
+
+    let report = match handle_db_error(
+        TriggerExportService::import_into_organization(
+            pool, verifier, &org_id, &user_id, bundle, dry_run,
+        )
+        .await,
+        "import triggers",
+    ) {
+        Ok(report) => report,
+        Err(resp) => return resp,
+    };
+
+    tracing::info!(
+        organization_id = %org_id,
+        imported = report.imported,
+        failed = report.failed,
+        dry_run = dry_run,
+        "Trigger bundle imported"
+    );
+
+    HttpResponse::Ok().json(SuccessResponse::new(report))
+}
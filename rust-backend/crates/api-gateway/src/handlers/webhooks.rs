@@ -0,0 +1,350 @@
+//! Trigger Fire Webhook Handlers
+//!
+//! Lets an organization register endpoints that receive every trigger fire
+//! as a signed event, in addition to the trigger's own actions. The event
+//! processor queues and delivers the events; see
+//! `event-processor/src/webhooks.rs` for the payload, signature and retries.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/webhooks` - Register a webhook (admin+)
+//! - `GET /api/v1/webhooks` - List webhooks (any member)
+//! - `GET /api/v1/webhooks/{id}` - Get a webhook (any member)
+//! - `DELETE /api/v1/webhooks/{id}` - Remove a webhook (admin+)
+//! - `GET /api/v1/webhooks/{id}/deliveries` - Delivery history (any member)
+//!
+//! The organization comes from the `X-Organization-ID` header. The signing
+//! secret is only returned when the webhook is created.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use shared::{DbPool, DbPools};
+
+use crate::{
+    handlers::helpers::{
+        bad_request, extract_user_id_or_unauthorized, forbidden, handle_db_error, require_found,
+        validate_request,
+    },
+    middleware::{get_verified_organization_id, get_verified_organization_id_with_role},
+    models::{
+        can_manage_org, CreateWebhookRequest, ErrorResponse, PaginatedResponse, PaginationMeta,
+        PaginationParams, SuccessResponse, WebhookDeliveriesQuery, WebhookDeliveryResponse,
+        WebhookResponse,
+    },
+    repositories::WebhookRepository,
+};
+
+/// Register a trigger fire webhook
+///
+/// The response includes the signing secret. Store it: it is not shown again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    tag = "Webhooks",
+    request_body = CreateWebhookRequest,
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 201, description = "Webhook created", body = SuccessResponse<WebhookResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse)
+    )
+)]
+pub async fn create_webhook(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    req: web::Json<CreateWebhookRequest>,
+) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    if !can_manage_org(&role) {
+        return forbidden("Insufficient permissions to manage webhooks");
+    }
+
+    if let Err(resp) = validate_request(&*req) {
+        return resp;
+    }
+
+    let webhook = match handle_db_error(
+        WebhookRepository::create(
+            &pool,
+            &organization_id,
+            &req.url,
+            &req.event_types,
+            req.description.as_deref(),
+            req.enabled.unwrap_or(true),
+            &user_id,
+        )
+        .await,
+        "create webhook",
+    ) {
+        Ok(webhook) => webhook,
+        Err(resp) => return resp,
+    };
+
+    tracing::info!(
+        organization_id = %organization_id,
+        webhook_id = %webhook.id,
+        event_types = ?webhook.event_types,
+        "Webhook created"
+    );
+
+    HttpResponse::Created().json(SuccessResponse::new(WebhookResponse::new(webhook, true)))
+}
+
+/// List the organization's webhooks
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    tag = "Webhooks",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum items per page"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "List of webhooks", body = PaginatedResponse<WebhookResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    )
+)]
+pub async fn list_webhooks(
+    pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
+    req_http: HttpRequest,
+    query: web::Query<PaginationParams>,
+) -> impl Responder {
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    if let Err(e) = query.validate() {
+        return bad_request(&format!("Invalid pagination: {}", e));
+    }
+
+    let (total_result, webhooks_result) = tokio::join!(
+        WebhookRepository::count_by_organization(pools.read(), &organization_id),
+        WebhookRepository::list_by_organization(
+            pools.read(),
+            &organization_id,
+            query.limit,
+            query.offset
+        )
+    );
+
+    let total = match handle_db_error(total_result, "count webhooks") {
+        Ok(count) => count,
+        Err(resp) => return resp,
+    };
+    let webhooks = match handle_db_error(webhooks_result, "list webhooks") {
+        Ok(webhooks) => webhooks,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(PaginatedResponse {
+        data: webhooks
+            .into_iter()
+            .map(|webhook| WebhookResponse::new(webhook, false))
+            .collect(),
+        pagination: PaginationMeta::new(total, query.limit, query.offset),
+    })
+}
+
+/// Get a webhook
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{id}",
+    tag = "Webhooks",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Webhook details", body = SuccessResponse<WebhookResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_webhook(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let webhook_id = path.into_inner();
+
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    match handle_db_error(
+        WebhookRepository::find_by_id_for_organization(&pool, &webhook_id, &organization_id).await,
+        "fetch webhook",
+    )
+    .and_then(|webhook| require_found(webhook, "Webhook"))
+    {
+        Ok(webhook) => {
+            HttpResponse::Ok().json(SuccessResponse::new(WebhookResponse::new(webhook, false)))
+        }
+        Err(resp) => resp,
+    }
+}
+
+/// Remove a webhook
+///
+/// Pending deliveries and the delivery history are removed with it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/{id}",
+    tag = "Webhooks",
+    params(
+        ("id" = String, Path, description = "Webhook ID")
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Insufficient permissions", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_webhook(
+    pool: web::Data<DbPool>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let webhook_id = path.into_inner();
+
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let (organization_id, role) =
+        match get_verified_organization_id_with_role(&req_http, &pool, &user_id).await {
+            Ok(result) => result,
+            Err(response) => return response,
+        };
+
+    if !can_manage_org(&role) {
+        return forbidden("Insufficient permissions to manage webhooks");
+    }
+
+    match handle_db_error(
+        WebhookRepository::delete(&pool, &webhook_id, &organization_id).await,
+        "delete webhook",
+    ) {
+        Ok(true) => {
+            tracing::info!(
+                organization_id = %organization_id,
+                webhook_id = %webhook_id,
+                "Webhook deleted"
+            );
+            HttpResponse::NoContent().finish()
+        }
+        Ok(false) => {
+            HttpResponse::NotFound().json(ErrorResponse::new("not_found", "Webhook not found"))
+        }
+        Err(resp) => resp,
+    }
+}
+
+/// List a webhook's deliveries
+///
+/// Shows each trigger fire sent to the webhook with its status, attempt
+/// count and the last error, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{id}/deliveries",
+    tag = "Webhooks",
+    params(
+        ("id" = String, Path, description = "Webhook ID"),
+        WebhookDeliveriesQuery
+    ),
+    security(("bearer_auth" = []), ("organization_id" = [])),
+    responses(
+        (status = 200, description = "Webhook deliveries", body = PaginatedResponse<WebhookDeliveryResponse>),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_webhook_deliveries(
+    pool: web::Data<DbPool>,
+    pools: web::Data<DbPools>,
+    req_http: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<WebhookDeliveriesQuery>,
+) -> impl Responder {
+    let webhook_id = path.into_inner();
+
+    let user_id = match extract_user_id_or_unauthorized(&req_http) {
+        Ok(id) => id,
+        Err(resp) => return resp,
+    };
+
+    let organization_id = match get_verified_organization_id(&req_http, &pool, &user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    if let Err(e) = query.validate() {
+        return bad_request(&e);
+    }
+
+    if let Err(resp) = handle_db_error(
+        WebhookRepository::find_by_id_for_organization(&pool, &webhook_id, &organization_id).await,
+        "fetch webhook",
+    )
+    .and_then(|webhook| require_found(webhook, "Webhook"))
+    {
+        return resp;
+    }
+
+    let status = query.status.as_deref();
+    let (total_result, deliveries_result) = tokio::join!(
+        WebhookRepository::count_deliveries(pools.read(), &webhook_id, status),
+        WebhookRepository::list_deliveries(
+            pools.read(),
+            &webhook_id,
+            status,
+            query.limit,
+            query.offset
+        )
+    );
+
+    let total = match handle_db_error(total_result, "count webhook deliveries") {
+        Ok(count) => count,
+        Err(resp) => return resp,
+    };
+    let deliveries = match handle_db_error(deliveries_result, "list webhook deliveries") {
+        Ok(deliveries) => deliveries,
+        Err(resp) => return resp,
+    };
+
+    HttpResponse::Ok().json(PaginatedResponse {
+        data: deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
+        pagination: PaginationMeta::new(total, query.limit, query.offset),
+    })
+}
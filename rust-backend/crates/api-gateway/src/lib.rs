@@ -3,6 +3,7 @@
 //! This library module exposes the core functionality of the API Gateway
 //! for use in integration tests and potential future library consumers.
 
+pub mod app;
 pub mod background_tasks;
 pub mod handlers;
 pub mod middleware;
@@ -11,4 +12,5 @@ pub mod openapi;
 pub mod repositories;
 pub mod routes;
 pub mod services;
+pub mod shutdown;
 pub mod validators;
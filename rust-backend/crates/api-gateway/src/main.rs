@@ -2,135 +2,121 @@
 //!
 //! REST API server providing trigger management and system queries.
 
-use actix_web::{middleware::Logger, web, App, HttpServer};
+use actix_web::{web, HttpServer};
 use anyhow::Context;
-use shared::redis::cache::EntityCache;
-use shared::{db, secrets, Config, RateLimiter};
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
+use shared::diagnostics::{self, DiagnosticsReport};
+use shared::{
+    db, secrets, Config, DbPools, PoolMetricsReporter, DEFAULT_POOL_METRICS_INTERVAL_SECS,
+};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
+use api_gateway::app::{build_app, AppState};
 use api_gateway::background_tasks::BackgroundTaskRunner;
-use api_gateway::middleware::auth_extractor::AuthExtractor;
-use api_gateway::middleware::metrics::{init_metrics, metrics_handler, PrometheusMetrics};
-use api_gateway::middleware::query_tier::QueryTierExtractor;
-use api_gateway::middleware::request_id::RequestId;
-use api_gateway::middleware::security_headers::SecurityHeaders;
-use api_gateway::middleware::unified_rate_limiter::UnifiedRateLimiter;
-use api_gateway::openapi::ApiDoc;
+use api_gateway::middleware::metrics::init_metrics;
 use api_gateway::services::{
-    start_a2a_task_processor, AuthRateLimiter, SocialAuthService, WalletService,
+    start_a2a_task_processor, StripeIpRefreshConfig, StripeIpRefresher, STRIPE_WEBHOOK_IPS,
 };
-use api_gateway::{middleware, routes};
+use api_gateway::shutdown::{shutdown_signal, ShutdownSequence};
 
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize tracing
     shared::init_tracing();
 
+    if diagnostics::is_check_mode() {
+        return run_diagnostics().await;
+    }
+
     tracing::info!("Starting API Gateway...");
 
     // Log secrets backend configuration
-    // In production, set SECRETS_BACKEND=aws or SECRETS_BACKEND=vault
+    // In production, set SECRETS_BACKEND=aws, vault or azure
     let secrets_backend = secrets::SecretsBackend::from_env();
     tracing::info!(
         backend = ?secrets_backend,
-        "Secrets backend configured (set SECRETS_BACKEND=aws or vault for production)"
+        "Secrets backend configured (set SECRETS_BACKEND=aws, vault or azure for production)"
     );
 
-    // Load secrets from configured backend (AWS/Vault in prod, .env in dev)
-    // This validates that secrets loading works and logs any issues early
-    match secrets::load_secrets().await {
+    // Load secrets from configured backend (AWS/Vault/Azure in prod, .env in dev)
+    // Only required secrets must be present; features whose optional secrets
+    // are missing stay disabled and their endpoints answer 503, unless the
+    // feature is listed in REQUIRED_FEATURES
+    let required_features =
+        secrets::OptionalFeature::required_from_env().context("Invalid REQUIRED_FEATURES")?;
+    let loaded_secrets = match secrets::load_secrets().await {
+        Ok(app_secrets) => app_secrets
+            .validate_for(&required_features)
+            .map(|()| app_secrets),
+        Err(e) => Err(e),
+    };
+    let app_secrets = match loaded_secrets {
         Ok(app_secrets) => {
             tracing::info!(
                 redacted = ?app_secrets.redacted(),
                 "Application secrets loaded successfully"
             );
+            for feature in app_secrets.disabled_features() {
+                tracing::warn!(
+                    feature = %feature,
+                    secrets = ?feature.required_secrets(),
+                    "Optional secrets not set - feature disabled"
+                );
+            }
+            Some(web::Data::new(app_secrets))
         }
         Err(e) => {
             // In development, this is a warning (secrets may not all be configured)
-            // In production with AWS/Vault backend, this should be fatal
+            // With a production backend, refuse to start rather than fail on first use
             if matches!(secrets_backend, secrets::SecretsBackend::Env) {
                 tracing::warn!(
                     error = %e,
-                    "Failed to load all secrets from .env (some features may be disabled)"
+                    "Failed to load required secrets from .env (falling back to environment variables)"
                 );
+                None
             } else {
                 return Err(anyhow::anyhow!(
-                    "Failed to load secrets from {:?}: {}",
+                    "Refusing to start: secrets from {:?} are incomplete: {}",
                     secrets_backend,
                     e
                 ));
             }
         }
-    }
+    };
 
     // Load configuration from environment
-    let config = Config::from_env().context("Failed to load configuration")?;
-
-    // Create database connection pool
-    let db_pool = db::create_pool(&config.database)
+    let config = Config::load().context("Failed to load configuration")?;
+    let config_summary = config
+        .validate_and_summarize()
+        .context("Invalid configuration")?;
+    tracing::info!("Effective configuration: {}", config_summary);
+
+    // Namespace Redis keys before anything builds one
+    shared::redis::init_key_prefix(config.redis.key_prefix.as_deref())
+        .context("Invalid Redis key prefix")?;
+
+    // Create database connection pools (primary, plus read replica if configured)
+    let db_pools = DbPools::from_config(&config.database)
         .await
-        .context("Failed to create database pool")?;
+        .context("Failed to create database pools")?;
+    let db_pool = db_pools.primary().clone();
 
-    // Run database migrations
-    db::run_migrations(&db_pool)
+    // Apply, verify or skip migrations (DB_MIGRATION_MODE)
+    db::run_migrations(&db_pool, &config.database)
         .await
-        .context("Failed to run database migrations")?;
+        .context("Database migrations failed")?;
 
-    // Check database health
-    db::check_health(&db_pool)
+    // Check database health (primary and read replica)
+    db_pools
+        .check_health()
         .await
         .context("Database health check failed")?;
+    tracing::info!("Database pools healthy: {}", db_pools.stats());
 
-    // Initialize WalletService with chain configs from environment (loaded once at startup)
-    // This creates a shared HTTP client with connection pooling for RPC calls
-    let chain_configs = WalletService::load_chain_configs_from_env();
-    let wallet_service = WalletService::new(chain_configs.clone());
-    tracing::info!(
-        "WalletService initialized with {} chain configurations",
-        chain_configs.len()
-    );
-
-    // Initialize SocialAuthService for OAuth login (Google, GitHub)
-    let social_auth_service = SocialAuthService::from_env();
-    tracing::info!(
-        "SocialAuthService initialized (Google: {}, GitHub: {}, frontend_url: {})",
-        social_auth_service.is_google_configured(),
-        social_auth_service.is_github_configured(),
-        social_auth_service.frontend_url()
-    );
-
-    // Initialize AuthRateLimiter for code exchange (stricter: 10 per minute per IP)
-    // This prevents brute-force attacks on OAuth authorization codes
-    let code_exchange_rate_limiter = AuthRateLimiter::with_rates(500, 10);
-    tracing::info!("Code exchange rate limiter initialized (10 req/min per IP)");
-
-    // Initialize Redis client for rate limiting and caching
-    let redis_client = shared::redis::create_client(&config.redis.connection_url())
-        .await
-        .context("Failed to create Redis client")?;
-    tracing::info!("Redis client connected for rate limiting");
-
-    // Create EntityCache for caching frequently accessed data (membership checks, users, orgs)
-    // Uses a separate clone of the connection manager for isolation
-    let redis_client_for_cache = shared::redis::create_client(&config.redis.connection_url())
-        .await
-        .context("Failed to create Redis client for cache")?;
-    let entity_cache = EntityCache::new(redis_client_for_cache, Some(300)); // 5 min TTL
-    tracing::info!(
-        "Entity cache initialized (enabled: {}, TTL: {}s)",
-        entity_cache.is_enabled(),
-        entity_cache.ttl().as_secs()
-    );
-
-    // Create RateLimiter instance (shared across all requests)
-    let rate_limiter = RateLimiter::new(redis_client)
+    // Shared services (Redis clients, rate limiter, caches, OAuth, wallets)
+    let state = AppState::new(db_pools.clone(), config.clone(), app_secrets)
         .await
-        .context("Failed to create rate limiter")?;
-    tracing::info!(
-        "Rate limiter initialized (mode: {})",
-        std::env::var("RATE_LIMIT_MODE").unwrap_or_else(|_| "shadow".to_string())
-    );
+        .context("Failed to initialize application services")?;
 
     // Initialize Prometheus metrics recorder (must be done before any metrics are recorded)
     let _prometheus_handle = init_metrics();
@@ -144,77 +130,107 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Start A2A task processor (processes submitted A2A Protocol tasks)
-    let a2a_shutdown_token = start_a2a_task_processor(db_pool.clone());
+    let (a2a_shutdown_token, a2a_processor) =
+        start_a2a_task_processor(db_pool.clone(), state.a2a_cancellations.clone());
     tracing::info!("A2A Task Processor started");
 
+    // Start database pool metrics (primary, plus read replica if configured)
+    let pool_metrics_token = CancellationToken::new();
+    let acquire_warn_threshold = PoolMetricsReporter::acquire_warn_threshold_from_env();
+    let mut monitored_pools = vec![("primary", db_pools.primary().clone())];
+    if db_pools.has_read_replica() {
+        monitored_pools.push(("replica", db_pools.read().clone()));
+    }
+    for (name, pool) in monitored_pools {
+        let reporter = PoolMetricsReporter::new(pool, name, acquire_warn_threshold);
+        let token = pool_metrics_token.clone();
+        tokio::spawn(async move {
+            reporter
+                .run(
+                    Duration::from_secs(DEFAULT_POOL_METRICS_INTERVAL_SECS),
+                    token,
+                )
+                .await;
+        });
+    }
+
+    // Refresh the Stripe webhook IP allowlist from Stripe's published list
+    let stripe_ips_token = CancellationToken::new();
+    let stripe_ip_config = StripeIpRefreshConfig::from_env();
+    if stripe_ip_config.enabled {
+        let refresher = StripeIpRefresher::new(stripe_ip_config, STRIPE_WEBHOOK_IPS.clone());
+        tokio::spawn(refresher.run(stripe_ips_token.clone()));
+    }
+
     let server_addr = format!("{}:{}", config.server.host, config.server.port);
+    let shutdown_timeout_secs = config.server.shutdown_timeout_secs;
+    // Closed after the server drains (the app factory takes its own clone)
+    let pools_to_close = db_pools.clone();
     tracing::info!("API Gateway listening on {}", server_addr);
 
     // Start HTTP server
-    let server = HttpServer::new(move || {
-        App::new()
-            // Add Prometheus metrics middleware (should be early to capture all requests)
-            .wrap(PrometheusMetrics::new())
-            // Add request ID middleware (must be first for tracing)
-            .wrap(RequestId::new())
-            // Add security headers middleware
-            .wrap(SecurityHeaders::for_api())
-            // Add logger middleware
-            .wrap(Logger::default())
-            // Add CORS middleware
-            .wrap(middleware::cors())
-            // Add rate limiting middleware chain (order matters!)
-            // 1. UnifiedRateLimiter: Checks rate limits using AuthContext + QueryTier
-            .wrap(UnifiedRateLimiter::new(rate_limiter.clone()))
-            // 2. QueryTierExtractor: Extracts query tier from path/query params
-            .wrap(QueryTierExtractor::new())
-            // 3. AuthExtractor: Extracts auth context (IP, API key, or wallet signature)
-            .wrap(AuthExtractor::new())
-            // Configure JSON payload size limit (1MB)
-            .app_data(web::JsonConfig::default().limit(1_048_576))
-            // Store database pool in app state
-            .app_data(web::Data::new(db_pool.clone()))
-            .app_data(web::Data::new(config.clone()))
-            // Store EntityCache in app state for caching membership checks
-            .app_data(web::Data::new(entity_cache.clone()))
-            // Store WalletService in app state (shared across all requests)
-            .app_data(web::Data::new(wallet_service.clone()))
-            // Store SocialAuthService in app state (shared across all requests)
-            .app_data(web::Data::new(social_auth_service.clone()))
-            // Store CodeExchangeRateLimiter in app state (for /auth/exchange endpoint)
-            .app_data(web::Data::new(code_exchange_rate_limiter.clone()))
-            // Prometheus metrics endpoint (for scraping)
-            .route("/metrics", web::get().to(metrics_handler))
-            // Configure routes
-            .configure(routes::configure)
-            // OpenAPI documentation endpoints
-            .service(
-                SwaggerUi::new("/api-docs/{_:.*}").url("/api/v1/openapi.json", ApiDoc::openapi()),
-            )
-    })
-    .bind(&server_addr)
-    .with_context(|| format!("Failed to bind to {}", server_addr))?;
-
-    let server_handle = server.run();
-
-    // Register graceful shutdown handler
-    tokio::spawn(async move {
-        match tokio::signal::ctrl_c().await {
-            Ok(()) => {
-                tracing::info!("Shutdown signal received, stopping background tasks...");
-                shutdown_token.cancel();
-                a2a_shutdown_token.cancel();
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to listen for shutdown signal");
-            }
-        }
-    });
+    let server = HttpServer::new(move || build_app(&state))
+        // Drain in-flight requests for up to SERVER_SHUTDOWN_TIMEOUT_SECS on shutdown
+        .shutdown_timeout(shutdown_timeout_secs)
+        // Signals are handled by ShutdownSequence so background tasks stop after the drain
+        .disable_signals()
+        .bind(&server_addr)
+        .with_context(|| format!("Failed to bind to {}", server_addr))?;
+
+    // Run until SIGTERM/Ctrl-C: stop accepting, drain, then cancel background tasks
+    // (see api_gateway::shutdown for the ordering)
+    ShutdownSequence::new()
+        .cancel_after_drain(shutdown_token)
+        .cancel_after_drain(a2a_shutdown_token)
+        .cancel_after_drain(pool_metrics_token)
+        .cancel_after_drain(stripe_ips_token)
+        .run(server.run(), shutdown_signal())
+        .await
+        .context("Server error")?;
 
-    // Run server and wait for completion
-    server_handle.await.context("Server error")?;
+    // Executing A2A tasks finish or are requeued while the pool is still open
+    if let Err(e) = a2a_processor.await {
+        tracing::error!(error = %e, "A2A Task Processor panicked");
+    }
+
+    // Last step: release database connections
+    pools_to_close.close().await;
 
     tracing::info!("API Gateway shutdown complete");
 
     Ok(())
 }
+
+/// Check every dependency once and print a report (`--check`)
+async fn run_diagnostics() -> anyhow::Result<()> {
+    let mut report = DiagnosticsReport::new("api-gateway");
+    diagnostics::check_core(&mut report).await;
+
+    let mut app_secrets = None;
+    report
+        .run("secrets", async {
+            let required = secrets::OptionalFeature::required_from_env()?;
+            let loaded = secrets::load_secrets()
+                .await
+                .context("Failed to load required secrets")?;
+            loaded.validate_for(&required)?;
+            let disabled = loaded.disabled_features();
+            app_secrets = Some(loaded);
+            Ok(if disabled.is_empty() {
+                "all features enabled".to_string()
+            } else {
+                let names: Vec<_> = disabled.iter().map(ToString::to_string).collect();
+                format!("disabled features: {}", names.join(", "))
+            })
+        })
+        .await;
+
+    match app_secrets.and_then(|s| s.stripe_secret_key) {
+        Some(key) => {
+            report.run("stripe", diagnostics::ping_stripe(&key)).await;
+        }
+        None => report.skip("stripe", "STRIPE_SECRET_KEY not configured"),
+    }
+
+    report.finish()
+}
@@ -14,9 +14,11 @@
 //!
 //! # Organization Verification
 //!
-//! These functions verify that a user has access to the organization specified
-//! in the `X-Organization-ID` header:
+//! These functions verify that a user has access to the organization a request
+//! names in its path, `X-Organization-ID` header or `organization_id` query
+//! parameter (in that order of precedence, see [`organization`]):
 //!
+//! - [`get_verified_organization()`] - Verify membership, return org, role and source
 //! - [`get_verified_organization_id()`] - Verify membership, return org_id
 //! - [`get_verified_organization_id_with_role()`] - Verify membership, return (org_id, role)
 //!
@@ -33,21 +35,42 @@
 //! - [`query_tier`] - Extract query tier for cost calculation
 //! - [`unified_rate_limiter`] - Unified rate limiting middleware
 //!
+//! # Idempotency
+//!
+//! - [`idempotency`] - Replays stored responses for retried `Idempotency-Key` requests
+//!
+//! # Dependency Failures
+//!
+//! - [`failure_policy`] - Whether each middleware fails open or closed when Redis or the database errors
+//!
 //! # Security Headers
 //!
 //! - [`security_headers`] - Adds security headers (HSTS, X-Frame-Options, etc.)
 //!
+//! # Body Size Limits
+//!
+//! - [`body_limit`] - Per-route body caps and JSON 413 responses
+//!
+//! # Deprecation
+//!
+//! - [`deprecation`] - Adds `Deprecation`/`Sunset` headers to deprecated routes and logs their use
+//!
 //! # Security Notes
 //!
 //! - JWT tokens are validated using HS256 algorithm
-//! - The `X-Organization-ID` header is untrusted and always verified against membership
+//! - The organization ID is untrusted in every source and always verified against membership
 //! - All verification functions return appropriate HTTP error responses on failure
 //! - Security headers protect against common web vulnerabilities
 
 pub mod auth_extractor;
+pub mod body_limit;
 pub mod cors;
+pub mod deprecation;
+pub mod failure_policy;
+pub mod idempotency;
 pub mod ip_extractor;
 pub mod metrics;
+pub mod organization;
 pub mod query_tier;
 pub mod request_id;
 pub mod security_headers;
@@ -67,15 +90,17 @@ use std::{
 };
 
 use crate::models::{Claims, ErrorResponse};
-use crate::repositories::{
-    ApiKeyAuditRepository, ApiKeyRepository, AuthFailureRepository, MemberRepository,
-};
+use crate::repositories::{ApiKeyAuditRepository, ApiKeyRepository, AuthFailureRepository};
 use crate::services::{ApiKeyService, AuthRateLimiter};
 
 // Re-export middleware components
 #[allow(unused_imports)] // Used in integration tests
 pub use auth_extractor::{AuthContext, AuthLayer};
 pub use cors::cors;
+pub use failure_policy::FailurePolicy;
+pub use organization::{
+    get_verified_organization, resolve_organization_id, OrganizationIdSource, VerifiedOrganization,
+};
 #[allow(unused_imports)] // Used in integration tests
 pub use query_tier::{QueryTier, QueryTierExtractor};
 #[allow(unused_imports)] // Used in integration tests
@@ -210,85 +235,40 @@ pub fn get_user_id(req: &actix_web::HttpRequest) -> Result<String, Error> {
         .ok_or_else(|| ErrorUnauthorized("User not authenticated"))
 }
 
-/// Helper to extract and verify X-Organization-ID header
+/// Helper to resolve and verify the request's organization
 ///
-/// This function extracts the organization ID from the request header AND
-/// verifies that the authenticated user is a member of that organization.
-/// This prevents horizontal privilege escalation via header spoofing.
+/// Takes the organization from the `X-Organization-ID` header, or the
+/// `organization_id` query parameter when the header is absent, AND verifies
+/// that the authenticated user is a member of that organization. This prevents
+/// horizontal privilege escalation via header spoofing. See
+/// [`organization`] for the resolution rules.
 ///
 /// Uses Redis caching when EntityCache is available in app state to avoid
 /// database lookups on every request. Cache TTL is 5 minutes.
 ///
 /// # Arguments
-/// * `req` - The HTTP request containing the X-Organization-ID header
+/// * `req` - The HTTP request naming the organization
 /// * `pool` - Database connection pool
 /// * `user_id` - The authenticated user's ID
 ///
 /// # Returns
 /// * `Ok(String)` - The verified organization ID
-/// * `Err(HttpResponse)` - Error response if header missing, invalid, or user not a member
+/// * `Err(HttpResponse)` - Error response if no organization is named or user not a member
 pub async fn get_verified_organization_id(
     req: &HttpRequest,
     pool: &DbPool,
     user_id: &str,
 ) -> Result<String, HttpResponse> {
-    // Extract X-Organization-ID header
-    let org_id = req
-        .headers()
-        .get("X-Organization-ID")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string())
-        .ok_or_else(|| {
-            HttpResponse::BadRequest().json(ErrorResponse::new(
-                "missing_organization",
-                "X-Organization-ID header is required",
-            ))
-        })?;
-
-    // CRITICAL: Verify user belongs to the organization
-    // Try to use cached version if EntityCache is available
-    let is_member = if let Some(cache) =
-        req.app_data::<actix_web::web::Data<shared::redis::cache::EntityCache>>()
-    {
-        MemberRepository::is_member_cached(pool, cache.get_ref(), &org_id, user_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify organization membership: {}", e);
-                HttpResponse::InternalServerError().json(ErrorResponse::new(
-                    "internal_error",
-                    "Failed to verify organization access",
-                ))
-            })?
-    } else {
-        // Fallback to non-cached version if cache not available
-        MemberRepository::is_member(pool, &org_id, user_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify organization membership: {}", e);
-                HttpResponse::InternalServerError().json(ErrorResponse::new(
-                    "internal_error",
-                    "Failed to verify organization access",
-                ))
-            })?
-    };
-
-    if !is_member {
-        return Err(HttpResponse::Forbidden().json(ErrorResponse::new(
-            "forbidden",
-            "Not a member of the specified organization",
-        )));
-    }
-
-    Ok(org_id)
+    get_verified_organization(req, pool, user_id, None)
+        .await
+        .map(|org| org.id)
 }
 
-/// Helper to extract and verify X-Organization-ID header with role check
+/// Helper to resolve and verify the request's organization with role check
 ///
 /// Same as `get_verified_organization_id` but also returns the user's role
 /// in the organization for permission checks.
 ///
-/// Uses Redis caching when EntityCache is available in app state.
-///
 /// # Returns
 /// * `Ok((String, String))` - Tuple of (organization_id, user_role)
 pub async fn get_verified_organization_id_with_role(
@@ -296,59 +276,9 @@ pub async fn get_verified_organization_id_with_role(
     pool: &DbPool,
     user_id: &str,
 ) -> Result<(String, String), HttpResponse> {
-    // Extract X-Organization-ID header
-    let org_id = req
-        .headers()
-        .get("X-Organization-ID")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string())
-        .ok_or_else(|| {
-            HttpResponse::BadRequest().json(ErrorResponse::new(
-                "missing_organization",
-                "X-Organization-ID header is required",
-            ))
-        })?;
-
-    // Get user's role in the organization (also verifies membership)
-    // Try to use cached version if EntityCache is available
-    let role = if let Some(cache) =
-        req.app_data::<actix_web::web::Data<shared::redis::cache::EntityCache>>()
-    {
-        MemberRepository::get_role_cached(pool, cache.get_ref(), &org_id, user_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get organization role: {}", e);
-                HttpResponse::InternalServerError().json(ErrorResponse::new(
-                    "internal_error",
-                    "Failed to verify organization access",
-                ))
-            })?
-            .ok_or_else(|| {
-                HttpResponse::Forbidden().json(ErrorResponse::new(
-                    "forbidden",
-                    "Not a member of the specified organization",
-                ))
-            })?
-    } else {
-        // Fallback to non-cached version if cache not available
-        MemberRepository::get_role(pool, &org_id, user_id)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get organization role: {}", e);
-                HttpResponse::InternalServerError().json(ErrorResponse::new(
-                    "internal_error",
-                    "Failed to verify organization access",
-                ))
-            })?
-            .ok_or_else(|| {
-                HttpResponse::Forbidden().json(ErrorResponse::new(
-                    "forbidden",
-                    "Not a member of the specified organization",
-                ))
-            })?
-    };
-
-    Ok((org_id, role))
+    get_verified_organization(req, pool, user_id, None)
+        .await
+        .map(|org| (org.id, org.role))
 }
 
 // ============================================================================
@@ -727,7 +657,8 @@ async fn validate_api_key(
             return Err("Invalid API key".to_string());
         }
         Err(e) => {
-            tracing::error!("Database error looking up API key: {}", e);
+            // Never fail open: an unverifiable key is not authenticated
+            FailurePolicy::Closed.on_failure("api_key_auth", &e);
             return Err("Authentication error".to_string());
         }
     };
@@ -870,6 +801,7 @@ mod tests {
             username: username.to_string(),
             exp,
             iat: now,
+            sid: None,
         };
 
         encode(
@@ -942,6 +874,7 @@ mod tests {
             username: "testuser".to_string(),
             exp: now + 3600,
             iat: now,
+            sid: None,
         };
         let token = encode(
             &Header::default(),
@@ -1064,4 +997,20 @@ mod tests {
         assert!(claims.exp > claims.iat);
         assert_eq!(claims.exp - claims.iat, 3600); // 1 hour
     }
+
+    #[actix_web::test]
+    async fn test_api_key_lookup_failure_fails_closed() {
+        // Any query on this pool fails
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+        let key = format!("sk_test_{}", "A".repeat(43));
+        assert!(ApiKeyService::is_valid_format(&key));
+
+        let err = validate_api_key(&pool, &key, None, None, None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Authentication error");
+    }
 }
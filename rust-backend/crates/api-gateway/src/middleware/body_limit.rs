@@ -0,0 +1,157 @@
+//! Request body size limits
+//!
+//! Every JSON body is capped at [`DEFAULT_BODY_LIMIT`] by the app-wide
+//! `JsonConfig`. Routes needing a different cap register their own extractor
+//! config where they are registered (see `routes::configure`), which takes
+//! precedence over the app-wide one:
+//!
+//! ```ignore
+//! web::scope("/auth").app_data(web::JsonConfig::default().limit(AUTH_BODY_LIMIT))
+//! ```
+//!
+//! Extractors answer an oversized body with a plain-text 413;
+//! [`payload_too_large_json`] rewrites those into our JSON error format.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceResponse;
+use actix_web::http::{header, StatusCode};
+use actix_web::middleware::{ErrorHandlerResponse, ErrorHandlers};
+use actix_web::HttpResponse;
+
+use crate::models::ErrorResponse;
+
+/// App-wide cap on JSON bodies (1MB)
+pub const DEFAULT_BODY_LIMIT: usize = 1_048_576;
+
+/// Cap on `/auth/*` and `/oauth/token` bodies (credentials and tokens only)
+pub const AUTH_BODY_LIMIT: usize = 16 * 1024;
+
+/// Cap on trigger import bundles (up to 100 triggers with their conditions and actions)
+pub const TRIGGER_IMPORT_BODY_LIMIT: usize = 4 * 1_048_576;
+
+/// Cap on events pushed to `/events/ingest`
+pub const EVENT_INGEST_BODY_LIMIT: usize = 256 * 1024;
+
+/// Give 413 responses a JSON body
+///
+/// Responses that already are JSON (e.g. from a handler enforcing its own cap)
+/// are left alone.
+pub fn payload_too_large_json<B: MessageBody + 'static>() -> ErrorHandlers<B> {
+    ErrorHandlers::new().handler(StatusCode::PAYLOAD_TOO_LARGE, |res: ServiceResponse<B>| {
+        let is_json = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json"));
+        if is_json {
+            return Ok(ErrorHandlerResponse::Response(res.map_into_left_body()));
+        }
+
+        let response = HttpResponse::PayloadTooLarge().json(ErrorResponse::new(
+            "payload_too_large",
+            "Request body exceeds the size limit for this endpoint",
+        ));
+        Ok(ErrorHandlerResponse::Response(
+            res.into_response(response).map_into_right_body(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+    use serde_json::Value;
+
+    async fn echo(body: web::Json<Value>) -> HttpResponse {
+        HttpResponse::Ok().json(body.into_inner())
+    }
+
+    async fn own_limit() -> HttpResponse {
+        HttpResponse::PayloadTooLarge().json(ErrorResponse::new("payload_too_large", "Own limit"))
+    }
+
+    fn body_of(len: usize) -> Value {
+        serde_json::json!({ "data": "x".repeat(len) })
+    }
+
+    #[actix_web::test]
+    async fn test_route_limit_overrides_app_limit() {
+        let app = test::init_service(
+            App::new()
+                .wrap(payload_too_large_json())
+                .app_data(web::JsonConfig::default().limit(1024))
+                .service(
+                    web::resource("/tight")
+                        .app_data(web::JsonConfig::default().limit(64))
+                        .route(web::post().to(echo)),
+                )
+                .service(
+                    web::resource("/loose")
+                        .app_data(web::JsonConfig::default().limit(4096))
+                        .route(web::post().to(echo)),
+                )
+                .route("/default", web::post().to(echo)),
+        )
+        .await;
+
+        // 2KB: over the tight and app-wide limits, within the loose one
+        for (uri, status) in [
+            ("/tight", StatusCode::PAYLOAD_TOO_LARGE),
+            ("/default", StatusCode::PAYLOAD_TOO_LARGE),
+            ("/loose", StatusCode::OK),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(uri)
+                .set_json(body_of(2048))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "{}", uri);
+        }
+
+        // Small bodies pass everywhere
+        let req = test::TestRequest::post()
+            .uri("/tight")
+            .set_json(body_of(8))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_payload_too_large_has_json_body() {
+        let app = test::init_service(
+            App::new()
+                .wrap(payload_too_large_json())
+                .app_data(web::JsonConfig::default().limit(64))
+                .route("/echo", web::post().to(echo)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/echo")
+            .set_json(body_of(1024))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "payload_too_large");
+    }
+
+    #[actix_web::test]
+    async fn test_json_413_left_alone() {
+        let app = test::init_service(
+            App::new()
+                .wrap(payload_too_large_json())
+                .route("/own", web::post().to(own_limit)),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/own").to_request();
+        let body: Value = test::read_body_json(test::call_service(&app, req).await).await;
+        assert_eq!(body["message"], "Own limit");
+    }
+}
@@ -7,7 +7,8 @@
 //!
 //! - **Production Safety**: Only HTTPS origins allowed in production
 //! - **Environment-Based Whitelist**: Configurable via CORS_ALLOWED_ORIGINS
-//! - **Strict Validation**: Origins must match exactly (no wildcards in production)
+//! - **Strict Validation**: Origins match exactly, or as one subdomain of a
+//!   wildcard entry (`https://*.example.com`); a bare `*` is never accepted
 //! - **CORS Violation Logging**: All violations are logged for security monitoring
 //!
 //! # Usage
@@ -27,6 +28,9 @@
 //!   - Development default: `http://localhost:3000,http://localhost:8080`
 //!   - Production: Must be set explicitly with HTTPS URLs
 //!   - Example: `https://app.example.com,https://admin.example.com`
+//!   - `https://*.example.com` allows any single-label subdomain with that
+//!     scheme (e.g. `https://pr-42.example.com`), not `example.com` itself or
+//!     deeper subdomains. Useful for preview deployments.
 //!
 //! - `ENVIRONMENT`: Set to "production" to enforce HTTPS-only origins
 //!
@@ -37,14 +41,121 @@ use actix_web::http::header::{self, HeaderName};
 use std::env;
 use tracing::{debug, warn};
 
+/// An entry of `CORS_ALLOWED_ORIGINS`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginPattern {
+    /// `scheme://host[:port]`, matched exactly
+    Exact(String),
+    /// `scheme://*.domain[:port]`: one subdomain label of `domain`
+    Subdomain {
+        /// `scheme://`
+        scheme: String,
+        /// `.domain[:port]`, lowercase
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    /// Parse an entry, `None` if it is not a valid origin or pattern
+    ///
+    /// A wildcard must be the whole first label of a domain with at least two
+    /// labels, so `https://*.com` and `https://app-*.example.com` are invalid.
+    pub fn parse(entry: &str) -> Option<Self> {
+        let (scheme, host) = ["https://", "http://"]
+            .iter()
+            .find_map(|scheme| entry.strip_prefix(scheme).map(|host| (*scheme, host)))?;
+        if host.is_empty() || host.contains('/') {
+            return None;
+        }
+
+        if !host.contains('*') {
+            return Some(Self::Exact(entry.to_string()));
+        }
+        let domain = host.strip_prefix("*.")?;
+        let name = domain.split(':').next().unwrap_or_default();
+        if domain.contains('*') || name.split('.').filter(|l| !l.is_empty()).count() < 2 {
+            return None;
+        }
+        Some(Self::Subdomain {
+            scheme: scheme.to_string(),
+            suffix: format!(".{}", domain.to_lowercase()),
+        })
+    }
+
+    /// Whether a request's `Origin` header value matches
+    pub fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Exact(allowed) => allowed == origin,
+            Self::Subdomain { scheme, suffix } => {
+                let Some(host) = origin.strip_prefix(scheme.as_str()) else {
+                    return false;
+                };
+                let host = host.to_lowercase();
+                host.strip_suffix(suffix.as_str()).is_some_and(is_dns_label)
+            }
+        }
+    }
+}
+
+/// One DNS label: letters, digits and inner hyphens, at most 63 characters
+fn is_dns_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Parse `CORS_ALLOWED_ORIGINS`, dropping (and logging) invalid entries
+///
+/// In production only `https://` entries are kept.
+pub fn parse_allowed_origins(list: &str, is_production: bool) -> Vec<OriginPattern> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter_map(|origin| {
+            // Validate origin format
+            if is_production && !origin.starts_with("https://") {
+                warn!(
+                    "Rejecting non-HTTPS origin in production: {}. \
+                     Only HTTPS origins are allowed in production for security.",
+                    origin
+                );
+                return None;
+            }
+
+            // Validate origin is not a wildcard
+            if origin == "*" {
+                warn!(
+                    "Wildcard (*) origin is not allowed for security reasons. \
+                     Specify explicit origins in CORS_ALLOWED_ORIGINS."
+                );
+                return None;
+            }
+
+            let pattern = OriginPattern::parse(origin);
+            if pattern.is_none() {
+                warn!(
+                    "Invalid origin format: {}. Origins must look like https://host[:port] \
+                     or https://*.domain[:port]",
+                    origin
+                );
+            }
+            pattern
+        })
+        .collect()
+}
+
 /// Create CORS middleware with security-hardened configuration
 ///
 /// # Security Requirements
 ///
 /// - Production mode enforces HTTPS-only origins
-/// - No wildcard (*) origins in production
-/// - Origin validation with exact matching
-/// - Credentials disabled (stateless JWT authentication)
+/// - No bare wildcard (*) origin
+/// - Origin validation with exact or single-subdomain matching
+/// - Credentials allowed, with the matched origin reflected
 ///
 /// # Returns
 ///
@@ -69,44 +180,11 @@ pub fn cors() -> Cors {
         }
     });
 
-    // Parse and validate origins
-    let allowed_origins: Vec<String> = allowed_origins_str
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .filter(|origin| {
-            // Validate origin format
-            if is_production && !origin.starts_with("https://") {
-                warn!(
-                    "Rejecting non-HTTPS origin in production: {}. \
-                     Only HTTPS origins are allowed in production for security.",
-                    origin
-                );
-                return false;
-            }
-
-            // Validate origin is not a wildcard
-            if origin == "*" {
-                warn!(
-                    "Wildcard (*) origin is not allowed for security reasons. \
-                     Specify explicit origins in CORS_ALLOWED_ORIGINS."
-                );
-                return false;
-            }
-
-            // Basic URL validation (must start with http:// or https://)
-            if !origin.starts_with("http://") && !origin.starts_with("https://") {
-                warn!(
-                    "Invalid origin format: {}. Origins must start with http:// or https://",
-                    origin
-                );
-                return false;
-            }
-
-            true
-        })
-        .collect();
+    cors_for(parse_allowed_origins(&allowed_origins_str, is_production))
+}
 
+/// Create the CORS middleware for an already parsed origin list
+pub fn cors_for(allowed_origins: Vec<OriginPattern>) -> Cors {
     debug!(
         "CORS middleware initialized with {} allowed origins",
         allowed_origins.len()
@@ -121,9 +199,15 @@ pub fn cors() -> Cors {
         warn!("No valid CORS origins configured. Cross-origin requests will be blocked.");
     } else {
         for origin in &allowed_origins {
-            cors = cors.allowed_origin(origin);
-            debug!("CORS: Allowing origin: {}", origin);
+            debug!("CORS: Allowing origin: {:?}", origin);
         }
+        // Matching origins are reflected in Access-Control-Allow-Origin, which
+        // keeps credentialed requests working with wildcard entries
+        cors = cors.allowed_origin_fn(move |origin, _req| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| allowed_origins.iter().any(|p| p.matches(origin)))
+        });
     }
 
     // Configure allowed methods and headers
@@ -137,7 +221,14 @@ pub fn cors() -> Cors {
             HeaderName::from_static("x-csrf-token"),
             HeaderName::from_static("x-organization-id"),
         ])
-        .expose_headers(vec![header::CONTENT_TYPE])
+        .expose_headers(vec![
+            header::CONTENT_TYPE,
+            // Deprecation notices (see deprecation middleware)
+            HeaderName::from_static("deprecation"),
+            HeaderName::from_static("sunset"),
+            header::LINK,
+            header::WARNING,
+        ])
         // Max age for preflight requests (1 hour)
         .max_age(3600);
 
@@ -321,6 +412,111 @@ mod tests {
         env::remove_var("CORS_ALLOWED_ORIGINS");
     }
 
+    #[::core::prelude::v1::test]
+    fn test_origin_pattern_exact() {
+        let pattern = OriginPattern::parse("https://app.example.com").unwrap();
+        assert_eq!(
+            pattern,
+            OriginPattern::Exact("https://app.example.com".to_string())
+        );
+
+        assert!(pattern.matches("https://app.example.com"));
+        assert!(!pattern.matches("http://app.example.com"));
+        assert!(!pattern.matches("https://app.example.com:8443"));
+        assert!(!pattern.matches("https://evil.app.example.com"));
+    }
+
+    #[::core::prelude::v1::test]
+    fn test_origin_pattern_wildcard_subdomain() {
+        let pattern = OriginPattern::parse("https://*.agentauri.ai").unwrap();
+
+        assert!(pattern.matches("https://pr-42.agentauri.ai"));
+        assert!(pattern.matches("https://App.AgentAuri.ai"));
+
+        // Not the apex, deeper subdomains, other schemes, ports or domains
+        assert!(!pattern.matches("https://agentauri.ai"));
+        assert!(!pattern.matches("https://.agentauri.ai"));
+        assert!(!pattern.matches("https://a.b.agentauri.ai"));
+        assert!(!pattern.matches("http://pr-42.agentauri.ai"));
+        assert!(!pattern.matches("https://pr-42.agentauri.ai:8443"));
+        assert!(!pattern.matches("https://pr-42.agentauri.ai.evil.com"));
+        assert!(!pattern.matches("https://evilagentauri.ai"));
+        assert!(!pattern.matches("https://-pr.agentauri.ai"));
+        assert!(!pattern.matches("https://pr_42.agentauri.ai"));
+
+        let with_port = OriginPattern::parse("http://*.localhost.test:3000").unwrap();
+        assert!(with_port.matches("http://web.localhost.test:3000"));
+        assert!(!with_port.matches("http://web.localhost.test"));
+    }
+
+    #[::core::prelude::v1::test]
+    fn test_origin_pattern_rejects_invalid_entries() {
+        for entry in [
+            "*",
+            "https://*",
+            "https://*.com",
+            "https://app-*.example.com",
+            "https://*.*.example.com",
+            "https://app.*.example.com",
+            "https://app.example.com/path",
+            "ftp://app.example.com",
+            "app.example.com",
+            "https://",
+        ] {
+            assert_eq!(OriginPattern::parse(entry), None, "{}", entry);
+        }
+    }
+
+    #[::core::prelude::v1::test]
+    fn test_parse_allowed_origins() {
+        let list = "https://app.example.com, https://*.agentauri.ai,*,http://localhost:3000";
+
+        assert_eq!(parse_allowed_origins(list, false).len(), 3);
+
+        // Production keeps HTTPS entries only
+        let production = parse_allowed_origins(list, true);
+        assert_eq!(production.len(), 2);
+        assert!(production
+            .iter()
+            .all(|p| !p.matches("http://localhost:3000")));
+    }
+
+    #[actix_web::test]
+    async fn test_cors_wildcard_subdomain_reflects_origin() {
+        let app = test::init_service(
+            App::new()
+                .wrap(cors_for(parse_allowed_origins(
+                    "https://*.agentauri.ai",
+                    true,
+                )))
+                .route("/test", web::get().to(test_handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/test")
+            .insert_header(("Origin", "https://pr-42.agentauri.ai"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let headers = resp.headers();
+        assert_eq!(
+            headers.get("access-control-allow-origin").unwrap(),
+            "https://pr-42.agentauri.ai"
+        );
+        assert_eq!(
+            headers.get("access-control-allow-credentials").unwrap(),
+            "true"
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/test")
+            .insert_header(("Origin", "https://agentauri.ai.evil.com"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(!resp.headers().contains_key("access-control-allow-origin"));
+    }
+
     #[::core::prelude::v1::test]
     fn test_cors_configuration_parsing() {
         // Test that the cors() function can be called and returns valid middleware
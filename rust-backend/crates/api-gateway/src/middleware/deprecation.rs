@@ -0,0 +1,302 @@
+//! Deprecation Middleware
+//!
+//! Marks a route as deprecated so clients and SDKs can warn before it is
+//! removed. Wrap each deprecated route:
+//!
+//! ```ignore
+//! use actix_web::web;
+//! use api_gateway::middleware::deprecation::Deprecated;
+//!
+//! web::get()
+//!     .to(handlers::get_credits)
+//!     .wrap(Deprecated::new("2026-10-15", "2027-04-15")
+//!         .successor("/api/v1/organizations/{id}/credits/balance"));
+//! ```
+//!
+//! # Response Headers
+//!
+//! Added to every response from the route, errors included:
+//!
+//! - `Deprecation`: when the route was deprecated, `@<unix seconds>` (RFC 9745)
+//! - `Sunset`: when the route may be removed, as an HTTP date (RFC 8594)
+//! - `Link`: the replacement, with `rel="successor-version"` (if configured)
+//! - `Warning`: `299` with a human-readable notice
+//!
+//! Each call is logged with the caller (user, API key or IP) and counted in
+//! `api_deprecated_requests_total`, so migration off the route can be tracked.
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue, LINK, WARNING},
+    Error, HttpMessage,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::future::LocalBoxFuture;
+use metrics::counter;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+use tracing::info;
+
+use super::auth_extractor::AuthContext;
+use super::ApiKeyAuth;
+use crate::models::Claims;
+
+/// Deprecation policy for one route
+#[derive(Debug, Clone)]
+pub struct Deprecated {
+    deprecated_at: DateTime<Utc>,
+    sunset_at: DateTime<Utc>,
+    successor: Option<String>,
+}
+
+impl Deprecated {
+    /// Deprecated since `deprecated_on`, removable after `sunset_on`
+    /// (both `YYYY-MM-DD`, midnight UTC)
+    ///
+    /// # Panics
+    ///
+    /// Panics if a date is not a valid `YYYY-MM-DD` literal or the sunset
+    /// is before the deprecation, so a typo fails at startup.
+    pub fn new(deprecated_on: &str, sunset_on: &str) -> Self {
+        let deprecated_at = parse_date(deprecated_on);
+        let sunset_at = parse_date(sunset_on);
+        assert!(
+            sunset_at >= deprecated_at,
+            "sunset date {} is before deprecation date {}",
+            sunset_on,
+            deprecated_on
+        );
+        Self {
+            deprecated_at,
+            sunset_at,
+            successor: None,
+        }
+    }
+
+    /// Path of the endpoint that replaces this one
+    pub fn successor(mut self, path: impl Into<String>) -> Self {
+        self.successor = Some(path.into());
+        self
+    }
+
+    /// Header values, built once per worker
+    fn headers(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let mut notice = format!(
+            "Deprecated API: this endpoint will be removed after {}",
+            self.sunset_at.format("%Y-%m-%d")
+        );
+        if let Some(successor) = &self.successor {
+            notice.push_str(&format!("; use {} instead", successor));
+        }
+
+        let mut headers = vec![
+            (
+                HeaderName::from_static("deprecation"),
+                format!("@{}", self.deprecated_at.timestamp()),
+            ),
+            (
+                HeaderName::from_static("sunset"),
+                self.sunset_at
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string(),
+            ),
+            (WARNING, format!("299 - \"{}\"", notice)),
+        ];
+        if let Some(successor) = &self.successor {
+            headers.push((LINK, format!("<{}>; rel=\"successor-version\"", successor)));
+        }
+
+        headers
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, HeaderValue::try_from(value).ok()?)))
+            .collect()
+    }
+}
+
+fn parse_date(date: &str) -> DateTime<Utc> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .unwrap_or_else(|e| panic!("invalid deprecation date '{}': {}", date, e))
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+/// Who called a deprecated route, for the usage log
+fn caller(req: &ServiceRequest) -> String {
+    let extensions = req.extensions();
+    if let Some(api_key) = extensions.get::<ApiKeyAuth>() {
+        return format!(
+            "api_key:{} org:{}",
+            api_key.api_key.prefix, api_key.api_key.organization_id
+        );
+    }
+    if let Some(claims) = extensions.get::<Claims>() {
+        return format!("user:{}", claims.sub);
+    }
+    if let Some(ctx) = extensions.get::<AuthContext>() {
+        return format!("ip:{}", ctx.ip_address);
+    }
+    req.connection_info()
+        .realip_remote_addr()
+        .map(|ip| format!("ip:{}", ip))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Deprecated
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = DeprecatedMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DeprecatedMiddleware {
+            service: Rc::new(service),
+            headers: Rc::new(self.headers()),
+        }))
+    }
+}
+
+pub struct DeprecatedMiddleware<S> {
+    service: Rc<S>,
+    headers: Rc<Vec<(HeaderName, HeaderValue)>>,
+}
+
+impl<S, B> Service<ServiceRequest> for DeprecatedMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let headers = self.headers.clone();
+
+        Box::pin(async move {
+            let route = req
+                .match_pattern()
+                .unwrap_or_else(|| req.path().to_string());
+            info!(
+                method = %req.method(),
+                route = %route,
+                caller = %caller(&req),
+                "Deprecated endpoint called"
+            );
+            counter!("api_deprecated_requests_total", "route" => route).increment(1);
+
+            let mut res = service.call(req).await?;
+            for (name, value) in headers.iter() {
+                res.headers_mut().insert(name.clone(), value.clone());
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    async fn ok_handler() -> HttpResponse {
+        HttpResponse::Ok().body("ok")
+    }
+
+    async fn error_handler() -> HttpResponse {
+        HttpResponse::BadRequest().body("bad")
+    }
+
+    fn policy() -> Deprecated {
+        Deprecated::new("2026-10-15", "2027-04-15").successor("/v2/credits")
+    }
+
+    #[actix_web::test]
+    async fn test_headers_on_deprecated_route_only() {
+        let app = init_service(
+            App::new()
+                .route("/old", web::get().to(ok_handler).wrap(policy()))
+                .route("/new", web::get().to(ok_handler)),
+        )
+        .await;
+
+        let res = call_service(&app, TestRequest::get().uri("/old").to_request()).await;
+        let headers = res.headers();
+        assert_eq!(headers.get("deprecation").unwrap(), "@1792022400");
+        assert_eq!(
+            headers.get("sunset").unwrap(),
+            "Thu, 15 Apr 2027 00:00:00 GMT"
+        );
+        assert_eq!(
+            headers.get(LINK).unwrap(),
+            "</v2/credits>; rel=\"successor-version\""
+        );
+        let warning = headers.get(WARNING).unwrap().to_str().unwrap();
+        assert!(warning.starts_with("299 - \"Deprecated API"), "{}", warning);
+        assert!(warning.contains("2027-04-15"), "{}", warning);
+        assert!(warning.contains("/v2/credits"), "{}", warning);
+
+        let res = call_service(&app, TestRequest::get().uri("/new").to_request()).await;
+        for name in ["deprecation", "sunset", "link", "warning"] {
+            assert!(
+                res.headers().get(name).is_none(),
+                "{} on current route",
+                name
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_headers_on_error_responses() {
+        let app =
+            init_service(App::new().route("/old", web::get().to(error_handler).wrap(policy())))
+                .await;
+
+        let res = call_service(&app, TestRequest::get().uri("/old").to_request()).await;
+        assert_eq!(res.status(), 400);
+        assert!(res.headers().get("deprecation").is_some());
+        assert!(res.headers().get("sunset").is_some());
+    }
+
+    #[actix_web::test]
+    async fn test_link_omitted_without_successor() {
+        let app = init_service(
+            App::new().route(
+                "/old",
+                web::get()
+                    .to(ok_handler)
+                    .wrap(Deprecated::new("2026-10-15", "2027-04-15")),
+            ),
+        )
+        .await;
+
+        let res = call_service(&app, TestRequest::get().uri("/old").to_request()).await;
+        assert!(res.headers().get("deprecation").is_some());
+        assert!(res.headers().get(LINK).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid deprecation date")]
+    fn test_invalid_date_panics() {
+        Deprecated::new("2026-13-01", "2027-04-15");
+    }
+
+    #[test]
+    #[should_panic(expected = "is before deprecation date")]
+    fn test_sunset_before_deprecation_panics() {
+        Deprecated::new("2027-04-15", "2026-10-15");
+    }
+}
@@ -0,0 +1,142 @@
+//! What middleware does when its own dependency fails
+//!
+//! Middleware backed by Redis or the database has to decide what happens to a
+//! request when that dependency errors: let it through without the check
+//! (fail open) or reject it (fail closed). Each middleware states its choice
+//! as a [`FailurePolicy`]:
+//!
+//! | Middleware | Dependency | Policy | Override |
+//! |---|---|---|---|
+//! | API key authentication ([`DualAuth`](super::DualAuth)) | Database | closed (401) | - |
+//! | Organization verification ([`get_verified_organization`](super::get_verified_organization)) | Database / cache | closed (500) | - |
+//! | [`UnifiedRateLimiter`](super::UnifiedRateLimiter) | Redis | open in shadow mode, closed when enforcing (503) | `RATE_LIMIT_FAILURE_POLICY` |
+//! | [`Idempotency`](super::idempotency::Idempotency) | Redis | open | `IDEMPOTENCY_FAILURE_POLICY` |
+//!
+//! Authentication and authorization are not configurable: letting requests
+//! through unchecked is never an acceptable degradation for them.
+//!
+//! Every dependency failure is logged at error level and counted in
+//! `api_middleware_dependency_failures_total{middleware, policy}`, so requests
+//! let through unchecked are visible on dashboards and alerts.
+
+use metrics::counter;
+use std::fmt;
+use tracing::{error, warn};
+
+/// What happens to a request when a middleware's dependency fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Let the request through without the check
+    Open,
+    /// Reject the request
+    Closed,
+}
+
+impl FailurePolicy {
+    /// Parse `open` or `closed`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(Self::Open),
+            "closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
+
+    /// Read the policy from an environment variable
+    ///
+    /// Returns `None` if the variable is unset. An unrecognized value is
+    /// logged and ignored, so a typo cannot silently weaken the default.
+    pub fn from_env(var: &str) -> Option<Self> {
+        let value = std::env::var(var).ok()?;
+        let policy = Self::parse(&value);
+        if policy.is_none() {
+            warn!(
+                var = var,
+                value = %value,
+                "Unrecognized failure policy (expected \"open\" or \"closed\") - using the default"
+            );
+        }
+        policy
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+        }
+    }
+
+    /// Record a dependency failure in `middleware`
+    ///
+    /// Logs and counts the failure under this policy, and returns whether the
+    /// request may proceed.
+    pub fn on_failure(self, middleware: &'static str, err: &dyn fmt::Display) -> bool {
+        counter!(
+            "api_middleware_dependency_failures_total",
+            "middleware" => middleware,
+            "policy" => self.as_str()
+        )
+        .increment(1);
+
+        match self {
+            Self::Open => {
+                error!(
+                    middleware = middleware,
+                    error = %err,
+                    "FAILING OPEN: dependency unavailable - request proceeds without this check"
+                );
+                true
+            }
+            Self::Closed => {
+                error!(
+                    middleware = middleware,
+                    error = %err,
+                    "Failing closed: dependency unavailable - request rejected"
+                );
+                false
+            }
+        }
+    }
+}
+
+impl fmt::Display for FailurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(FailurePolicy::parse("open"), Some(FailurePolicy::Open));
+        assert_eq!(FailurePolicy::parse("closed"), Some(FailurePolicy::Closed));
+        assert_eq!(FailurePolicy::parse("Open"), None);
+        assert_eq!(FailurePolicy::parse(""), None);
+    }
+
+    #[test]
+    fn test_from_env() {
+        let var = "TEST_FAILURE_POLICY_FROM_ENV";
+
+        std::env::remove_var(var);
+        assert_eq!(FailurePolicy::from_env(var), None);
+
+        std::env::set_var(var, "closed");
+        assert_eq!(FailurePolicy::from_env(var), Some(FailurePolicy::Closed));
+
+        // Typos fall back to the caller's default
+        std::env::set_var(var, "opne");
+        assert_eq!(FailurePolicy::from_env(var), None);
+
+        std::env::remove_var(var);
+    }
+
+    #[test]
+    fn test_on_failure() {
+        assert!(FailurePolicy::Open.on_failure("test", &"connection refused"));
+        assert!(!FailurePolicy::Closed.on_failure("test", &"connection refused"));
+    }
+}
@@ -0,0 +1,680 @@
+//! Idempotency-Key Middleware
+//!
+//! Lets clients safely retry mutating POST requests (creating an API key,
+//! purchasing credits) after a network failure without executing them twice.
+//!
+//! # Behavior
+//!
+//! When a request carries an `Idempotency-Key` header:
+//!
+//! 1. The first request executes and its response (status, content type and
+//!    body) is stored in Redis under (caller, organization, route, key) for
+//!    `IDEMPOTENCY_TTL_SECS` (default: 24 hours)
+//! 2. Later requests with the same key get the stored response back, marked
+//!    with `Idempotent-Replayed: true`, and the handler is not called
+//! 3. Concurrent requests with the same key serialize on a Redis lock: one
+//!    executes while the others wait for its stored response
+//!
+//! Reusing a key with a different request body returns 422. Server errors
+//! (5xx) are not stored, so the request can be retried with the same key.
+//!
+//! # Secrets
+//!
+//! Responses that carry a secret shown only once (e.g. a new API key) must
+//! not be kept in Redis. Name the secret fields with [`Idempotency::redact`]:
+//! they are removed from the stored copy, so a replay returns the rest of the
+//! response without them. A body that cannot be redacted is not stored.
+//! Requests without the header are passed through unchanged, as are all
+//! requests when no [`IdempotencyStore`] is registered.
+//!
+//! # Redis Failures
+//!
+//! When Redis is down, requests with the header fail open by default: they
+//! execute without replay protection. With `IDEMPOTENCY_FAILURE_POLICY=closed`
+//! they get 503 instead, so a client never risks a duplicate and can retry
+//! with the same key later.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use actix_web::{web, App};
+//! use api_gateway::middleware::idempotency::{Idempotency, IdempotencyStore};
+//!
+//! let app = App::new()
+//!     .app_data(web::Data::new(IdempotencyStore::from_env(redis_conn)))
+//!     .route("/api-keys", web::post().to(create_api_key).wrap(Idempotency::new()));
+//! ```
+
+use actix_web::{
+    body::{self, BoxBody, MessageBody},
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER},
+        StatusCode,
+    },
+    web, Error, HttpMessage, HttpResponse,
+};
+use base64::Engine;
+use futures_util::future::LocalBoxFuture;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::redis::RedisKey;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::middleware::{ApiKeyAuth, FailurePolicy};
+use crate::models::{Claims, ErrorResponse};
+
+/// Request header carrying the client-chosen idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Maximum idempotency key length
+pub const MAX_KEY_LENGTH: usize = 255;
+
+/// Default time a stored response is replayed (24 hours)
+const DEFAULT_TTL_SECS: u64 = 86_400;
+
+/// How long a request may hold the key before another one may take over
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delay between checks while another request holds the key
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Name in failure logs and metrics
+const MIDDLEWARE_NAME: &str = "idempotency";
+
+/// Response stored for replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StoredResponse {
+    /// Hash of the request that produced the response
+    fingerprint: String,
+    status: u16,
+    content_type: Option<String>,
+    /// Base64-encoded response body
+    body: String,
+}
+
+/// Redis storage for idempotent responses
+///
+/// Register as `web::Data<IdempotencyStore>`; [`Idempotency`] looks it up on
+/// each request.
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    conn: ConnectionManager,
+    ttl: Duration,
+    lock_timeout: Duration,
+    failure_policy: FailurePolicy,
+}
+
+impl IdempotencyStore {
+    /// Create a store keeping responses for `ttl`
+    pub fn new(conn: ConnectionManager, ttl: Duration) -> Self {
+        Self {
+            conn,
+            ttl,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            failure_policy: FailurePolicy::Open,
+        }
+    }
+
+    /// Create a store with the TTL from `IDEMPOTENCY_TTL_SECS` (default: 24 hours)
+    /// and the failure policy from `IDEMPOTENCY_FAILURE_POLICY` (default: open)
+    pub fn from_env(conn: ConnectionManager) -> Self {
+        let ttl_secs = std::env::var("IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TTL_SECS);
+        let store = Self::new(conn, Duration::from_secs(ttl_secs));
+        match FailurePolicy::from_env("IDEMPOTENCY_FAILURE_POLICY") {
+            Some(policy) => store.with_failure_policy(policy),
+            None => store,
+        }
+    }
+
+    /// Override what happens to requests while Redis is unavailable
+    pub fn with_failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+
+    /// Override how long a request may hold a key
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Time a stored response is replayed
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    async fn get(&self, key: &str) -> redis::RedisResult<Option<StoredResponse>> {
+        let mut conn = self.conn.clone();
+        let value: Option<String> = conn.get(response_key(key)).await?;
+        // An unreadable record is treated as missing and gets overwritten
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    async fn put(&self, key: &str, response: &StoredResponse) -> redis::RedisResult<()> {
+        let mut conn = self.conn.clone();
+        let value = serde_json::to_string(response).unwrap_or_default();
+        conn.set_ex(response_key(key), value, self.ttl.as_secs().max(1))
+            .await
+    }
+
+    async fn try_lock(&self, key: &str, token: &str) -> redis::RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let acquired: Option<String> = redis::cmd("SET")
+            .arg(lock_key(key))
+            .arg(token)
+            .arg("NX")
+            .arg("PX")
+            .arg(self.lock_timeout.as_millis() as u64)
+            .query_async(&mut conn)
+            .await?;
+        Ok(acquired.is_some())
+    }
+
+    async fn unlock(&self, key: &str, token: &str) -> redis::RedisResult<()> {
+        // Only release the lock if it is still ours (it may have expired and
+        // been taken by another request)
+        let mut conn = self.conn.clone();
+        redis::Script::new(
+            r"if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) else return 0 end",
+        )
+        .key(lock_key(key))
+        .arg(token)
+        .invoke_async::<i64>(&mut conn)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Idempotency-Key middleware
+///
+/// Wrap individual routes: `web::post().to(handler).wrap(Idempotency::new())`.
+pub struct Idempotency {
+    /// JSON pointers of response fields never stored for replay
+    redacted: Rc<[&'static str]>,
+}
+
+impl Idempotency {
+    /// Create a new idempotency middleware
+    pub fn new() -> Self {
+        Self {
+            redacted: Rc::new([]),
+        }
+    }
+
+    /// Leave the response field at `pointer` (e.g. `/data/key`) out of the
+    /// stored response
+    pub fn redact(mut self, pointer: &'static str) -> Self {
+        let mut redacted = self.redacted.to_vec();
+        redacted.push(pointer);
+        self.redacted = redacted.into();
+        self
+    }
+}
+
+impl Default for Idempotency {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Idempotency
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = IdempotencyMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+            redacted: self.redacted.clone(),
+        }))
+    }
+}
+
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+    redacted: Rc<[&'static str]>,
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let redacted = self.redacted.clone();
+
+        Box::pin(async move {
+            let idempotency_key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+                None => return Ok(service.call(req).await?.map_into_boxed_body()),
+                Some(value) => match value.to_str().ok().filter(|k| is_valid_key(k)) {
+                    Some(key) => key.to_string(),
+                    None => {
+                        return Ok(reject(
+                            req,
+                            StatusCode::BAD_REQUEST,
+                            "invalid_idempotency_key",
+                            "Idempotency-Key must be 1-255 printable ASCII characters",
+                        ))
+                    }
+                },
+            };
+
+            let store = match req.app_data::<web::Data<IdempotencyStore>>().cloned() {
+                Some(store) => store,
+                None => return Ok(service.call(req).await?.map_into_boxed_body()),
+            };
+
+            // Buffer the body to fingerprint it, then hand it back to the handler
+            let body = req.extract::<web::Bytes>().await?;
+            req.set_payload(Payload::from(body.clone()));
+
+            let key = storage_key(&req, &idempotency_key);
+            let fingerprint = fingerprint(&req, &body);
+            let token = Uuid::new_v4().to_string();
+
+            // Replay a stored response, or wait until we hold the key
+            let started = Instant::now();
+            loop {
+                let stored = match store.get(&key).await {
+                    Ok(stored) => stored,
+                    Err(e) => return store_unavailable(&service, req, &store, &e).await,
+                };
+                if let Some(stored) = stored {
+                    if stored.fingerprint != fingerprint {
+                        return Ok(reject(
+                            req,
+                            StatusCode::UNPROCESSABLE_ENTITY,
+                            "idempotency_key_reused",
+                            "Idempotency-Key was already used with a different request",
+                        ));
+                    }
+                    debug!(key = %key, "Replaying stored idempotent response");
+                    return Ok(replay(req, &stored));
+                }
+
+                match store.try_lock(&key, &token).await {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(e) => return store_unavailable(&service, req, &store, &e).await,
+                }
+
+                if started.elapsed() >= store.lock_timeout {
+                    return Ok(reject(
+                        req,
+                        StatusCode::CONFLICT,
+                        "idempotency_key_in_use",
+                        "A request with this Idempotency-Key is still in progress",
+                    ));
+                }
+                tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+            }
+
+            let result =
+                execute_and_store(&service, req, &store, &key, fingerprint, &redacted).await;
+
+            if let Err(e) = store.unlock(&key, &token).await {
+                warn!(error = %e, "Failed to release idempotency lock");
+            }
+
+            result
+        })
+    }
+}
+
+/// Handle a request whose key cannot be checked, following the store's
+/// failure policy
+async fn store_unavailable<S, B>(
+    service: &Rc<S>,
+    req: ServiceRequest,
+    store: &IdempotencyStore,
+    err: &redis::RedisError,
+) -> Result<ServiceResponse<BoxBody>, Error>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    if store.failure_policy.on_failure(MIDDLEWARE_NAME, err) {
+        return Ok(service.call(req).await?.map_into_boxed_body());
+    }
+
+    let mut res = reject(
+        req,
+        StatusCode::SERVICE_UNAVAILABLE,
+        "idempotency_unavailable",
+        "Idempotency-Key cannot be checked right now; retry later with the same key",
+    );
+    res.headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from_static("5"));
+    Ok(res)
+}
+
+/// Run the handler, store a replayable response without the `redacted`
+/// fields and return the full response
+async fn execute_and_store<S, B>(
+    service: &Rc<S>,
+    req: ServiceRequest,
+    store: &IdempotencyStore,
+    key: &str,
+    fingerprint: String,
+    redacted: &[&str],
+) -> Result<ServiceResponse<BoxBody>, Error>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    let res = service.call(req).await?;
+
+    // Server errors are not stored so the client can retry with the same key
+    if res.status().is_server_error() {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    let (req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let body = body::to_bytes(body)
+        .await
+        .map_err(|_| actix_web::error::ErrorInternalServerError("Failed to read response"))?;
+
+    match redact_body(&body, redacted) {
+        Some(stored_body) => {
+            let stored = StoredResponse {
+                fingerprint,
+                status: res.status().as_u16(),
+                content_type: res
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+                body: base64::engine::general_purpose::STANDARD.encode(&stored_body),
+            };
+            if let Err(e) = store.put(key, &stored).await {
+                warn!(error = %e, "Failed to store idempotent response");
+            }
+        }
+        None => warn!("Response with secret fields is not JSON, not storing it for replay"),
+    }
+
+    Ok(ServiceResponse::new(req, res.set_body(BoxBody::new(body))))
+}
+
+/// Copy of a response body without the fields at `pointers`
+///
+/// Returns `None` if fields must be removed but the body is not JSON, so a
+/// secret is never stored by mistake.
+fn redact_body(body: &[u8], pointers: &[&str]) -> Option<Vec<u8>> {
+    if pointers.is_empty() {
+        return Some(body.to_vec());
+    }
+
+    let mut json: serde_json::Value = serde_json::from_slice(body).ok()?;
+    for pointer in pointers {
+        let Some((parent, field)) = pointer.rsplit_once('/') else {
+            continue;
+        };
+        if let Some(object) = json.pointer_mut(parent).and_then(|v| v.as_object_mut()) {
+            object.remove(field);
+        }
+    }
+    serde_json::to_vec(&json).ok()
+}
+
+/// Build the stored response for a replay
+fn replay(req: ServiceRequest, stored: &StoredResponse) -> ServiceResponse<BoxBody> {
+    let body = base64::engine::general_purpose::STANDARD
+        .decode(&stored.body)
+        .unwrap_or_default();
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+
+    let mut response = HttpResponse::build(status);
+    if let Some(content_type) = &stored.content_type {
+        response.insert_header((CONTENT_TYPE, content_type.as_str()));
+    }
+    response.insert_header((
+        HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER),
+        HeaderValue::from_static("true"),
+    ));
+
+    req.into_response(response.body(body))
+}
+
+fn reject(
+    req: ServiceRequest,
+    status: StatusCode,
+    error: &str,
+    message: &str,
+) -> ServiceResponse<BoxBody> {
+    req.into_response(HttpResponse::build(status).json(ErrorResponse::new(error, message)))
+}
+
+/// Check that a key is 1-255 visible ASCII characters
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LENGTH && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Hash scoping the idempotency key to caller, organization and route
+///
+/// Keys from different callers never collide, even if they pick the same
+/// idempotency key. The stored response and the lock are keyed by this hash.
+fn storage_key(req: &ServiceRequest, idempotency_key: &str) -> String {
+    let extensions = req.extensions();
+    let (caller, organization) = match (extensions.get::<Claims>(), extensions.get::<ApiKeyAuth>())
+    {
+        (Some(claims), _) => (
+            format!("user:{}", claims.sub),
+            req.headers()
+                .get("X-Organization-ID")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("-")
+                .to_string(),
+        ),
+        (None, Some(auth)) => (
+            format!("key:{}", auth.api_key.id),
+            auth.api_key.organization_id.clone(),
+        ),
+        (None, None) => ("anonymous".to_string(), "-".to_string()),
+    };
+
+    let mut hasher = Sha256::new();
+    for part in [
+        caller.as_str(),
+        organization.as_str(),
+        req.method().as_str(),
+        req.path(),
+        idempotency_key,
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Redis key holding the stored response
+fn response_key(key: &str) -> RedisKey {
+    RedisKey::dedup("idempotency").part(key)
+}
+
+/// Redis key held while the first request executes
+fn lock_key(key: &str) -> RedisKey {
+    RedisKey::lock("idempotency").part(key)
+}
+
+/// Hash of the query string and body, to detect a key reused for another request
+fn fingerprint(req: &ServiceRequest, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(req.query_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_is_valid_key() {
+        assert!(is_valid_key("a"));
+        assert!(is_valid_key("9f1c2b44-7a1e-4d6b-9a55-1f0c3e2d8b7a"));
+        assert!(is_valid_key(&"k".repeat(MAX_KEY_LENGTH)));
+
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key(&"k".repeat(MAX_KEY_LENGTH + 1)));
+        assert!(!is_valid_key("has space"));
+        assert!(!is_valid_key("tab\tkey"));
+    }
+
+    #[test]
+    fn test_storage_key_scoped_by_caller_route_and_organization() {
+        let key = |path: &str, org: &str, user: &str| {
+            let req = TestRequest::post()
+                .uri(path)
+                .insert_header(("X-Organization-ID", org))
+                .to_srv_request();
+            req.extensions_mut()
+                .insert(Claims::new(user.to_string(), user.to_string(), 1));
+            storage_key(&req, "retry-1")
+        };
+
+        let base = key("/api/v1/api-keys", "org_a", "user_1");
+        assert_eq!(base.len(), 64);
+        assert_eq!(base, key("/api/v1/api-keys", "org_a", "user_1"));
+        assert_ne!(
+            base,
+            key("/api/v1/billing/credits/purchase", "org_a", "user_1")
+        );
+        assert_ne!(base, key("/api/v1/api-keys", "org_b", "user_1"));
+        assert_ne!(base, key("/api/v1/api-keys", "org_a", "user_2"));
+
+        assert_eq!(
+            response_key(&base).as_str(),
+            format!("agentauri:dedup:idempotency:{}", base)
+        );
+        assert_eq!(
+            lock_key(&base).as_str(),
+            format!("agentauri:lock:idempotency:{}", base)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_covers_query_and_body() {
+        let req = |uri: &str| TestRequest::post().uri(uri).to_srv_request();
+
+        let base = fingerprint(&req("/x"), br#"{"a":1}"#);
+        assert_eq!(base, fingerprint(&req("/x"), br#"{"a":1}"#));
+        assert_ne!(base, fingerprint(&req("/x"), br#"{"a":2}"#));
+        assert_ne!(base, fingerprint(&req("/x?dry_run=true"), br#"{"a":1}"#));
+    }
+
+    mod redis_down {
+        use super::*;
+        use actix_web::{test, App};
+        use shared::redis::testing::FailingRedis;
+
+        async fn call(policy: FailurePolicy) -> ServiceResponse<BoxBody> {
+            let redis = FailingRedis::start().await;
+            let store = IdempotencyStore::new(redis.connect().await, Duration::from_secs(60))
+                .with_failure_policy(policy);
+            let app = test::init_service(
+                App::new().app_data(web::Data::new(store)).route(
+                    "/api/v1/api-keys",
+                    web::post()
+                        .to(HttpResponse::Created)
+                        .wrap(Idempotency::new()),
+                ),
+            )
+            .await;
+
+            let req = test::TestRequest::post()
+                .uri("/api/v1/api-keys")
+                .insert_header((IDEMPOTENCY_KEY_HEADER, "retry-1"))
+                .set_payload("{}")
+                .to_request();
+            test::call_service(&app, req).await
+        }
+
+        #[actix_web::test]
+        async fn test_fail_open_executes_request() {
+            let res = call(FailurePolicy::Open).await;
+            assert_eq!(res.status(), StatusCode::CREATED);
+        }
+
+        #[actix_web::test]
+        async fn test_fail_closed_rejects_request() {
+            let res = call(FailurePolicy::Closed).await;
+            assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert!(res.headers().contains_key(RETRY_AFTER));
+        }
+    }
+
+    #[test]
+    fn test_redact_body_removes_secret_fields() {
+        let body = br#"{"data":{"id":"key_1","key":"sk_live_secret","name":"ci"}}"#;
+
+        let stored = redact_body(body, &["/data/key"]).unwrap();
+        let stored: serde_json::Value = serde_json::from_slice(&stored).unwrap();
+        assert_eq!(
+            stored,
+            serde_json::json!({"data": {"id": "key_1", "name": "ci"}})
+        );
+
+        // Error responses have nothing to remove
+        let error = br#"{"error":"validation_error","message":"bad"}"#;
+        assert!(redact_body(error, &["/data/key"]).is_some());
+
+        // Without secrets the body is stored as is; with them, only as JSON
+        assert_eq!(redact_body(b"plain", &[]).unwrap(), b"plain");
+        assert!(redact_body(b"sk_live_secret", &["/data/key"]).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_replay_restores_status_and_body() {
+        let stored = StoredResponse {
+            fingerprint: "f".to_string(),
+            status: 201,
+            content_type: Some("application/json".to_string()),
+            body: base64::engine::general_purpose::STANDARD.encode(br#"{"id":"key_1"}"#),
+        };
+
+        let res = replay(TestRequest::post().to_srv_request(), &stored);
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(
+            res.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(&body[..], br#"{"id":"key_1"}"#);
+    }
+}
@@ -25,11 +25,17 @@
 //!     .wrap(metrics.clone())
 //!     .route("/metrics", web::get().to(metrics_handler))
 //! ```
+//!
+//! # Access
+//!
+//! `/metrics` is open unless `METRICS_AUTH_TOKEN` is set; then scrapers must
+//! send `Authorization: Bearer <token>` (see [`guarded_metrics_handler`]).
 
 use actix_web::{
     body::EitherBody,
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpResponse,
+    http::header,
+    web, Error, HttpRequest, HttpResponse,
 };
 use futures_util::future::LocalBoxFuture;
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
@@ -40,6 +46,9 @@ use std::{
     sync::Arc,
     time::Instant,
 };
+use subtle::ConstantTimeEq;
+
+use crate::models::ErrorResponse;
 
 /// Global Prometheus handle for the /metrics endpoint
 static PROMETHEUS_HANDLE: OnceCell<PrometheusHandle> = OnceCell::new();
@@ -117,6 +126,71 @@ pub async fn metrics_handler() -> HttpResponse {
     }
 }
 
+/// Optional bearer token required to scrape `/metrics`
+///
+/// Create once at startup and share via app state.
+#[derive(Clone, Default)]
+pub struct MetricsAuth {
+    token: Option<String>,
+}
+
+impl std::fmt::Debug for MetricsAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsAuth")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+impl MetricsAuth {
+    /// Require `token` (`None` or empty: metrics stay open)
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// Load the token from `METRICS_AUTH_TOKEN`
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("METRICS_AUTH_TOKEN").ok())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Whether the request may read metrics
+    fn allows(&self, req: &HttpRequest) -> bool {
+        let Some(expected) = &self.token else {
+            return true;
+        };
+        req.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .is_some_and(|provided| {
+                provided.len() == expected.len()
+                    && provided.as_bytes().ct_eq(expected.as_bytes()).into()
+            })
+    }
+}
+
+/// [`metrics_handler`] behind the optional [`MetricsAuth`] token
+///
+/// Only compares one header, so scraping stays as cheap as without a token.
+/// Returns 401 Unauthorized when a token is configured and not presented.
+pub async fn guarded_metrics_handler(
+    req: HttpRequest,
+    auth: web::Data<MetricsAuth>,
+) -> HttpResponse {
+    if !auth.allows(&req) {
+        return HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .json(ErrorResponse::new("unauthorized", "Metrics token required"));
+    }
+    metrics_handler().await
+}
+
 /// Prometheus metrics middleware for Actix-web
 ///
 /// Collects metrics for every HTTP request:
@@ -327,6 +401,56 @@ mod tests {
         HttpResponse::InternalServerError().body("error")
     }
 
+    async fn scrape(auth: MetricsAuth, authorization: Option<&str>) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth))
+                .route("/metrics", web::get().to(guarded_metrics_handler)),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri("/metrics");
+        if let Some(value) = authorization {
+            req = req.insert_header((header::AUTHORIZATION, value));
+        }
+        test::call_service(&app, req.to_request()).await.status()
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_open_without_token() {
+        let auth = MetricsAuth::new(None);
+        assert!(!auth.is_enabled());
+        assert_ne!(scrape(auth, None).await, StatusCode::UNAUTHORIZED);
+        assert_ne!(
+            scrape(MetricsAuth::new(Some(String::new())), None).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_require_configured_token() {
+        let auth = || MetricsAuth::new(Some("scrape-token".to_string()));
+
+        for authorization in [
+            None,
+            Some("Bearer wrong-token"),
+            Some("Bearer scrape-token2"),
+            Some("Basic scrape-token"),
+            Some("scrape-token"),
+        ] {
+            assert_eq!(
+                scrape(auth(), authorization).await,
+                StatusCode::UNAUTHORIZED,
+                "{:?}",
+                authorization
+            );
+        }
+        assert_ne!(
+            scrape(auth(), Some("Bearer scrape-token")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
     #[actix_web::test]
     async fn test_normalize_path_uuid() {
         let path = "/api/v1/triggers/123e4567-e89b-12d3-a456-426614174000";
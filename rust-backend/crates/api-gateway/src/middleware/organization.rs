@@ -0,0 +1,279 @@
+//! Organization ID resolution
+//!
+//! Organization-scoped handlers learn which organization a request targets
+//! from one of three places. When a request carries several, the first one in
+//! this order wins:
+//!
+//! 1. **Path** - the organization segment of nested routes
+//!    (`/organizations/{id}/...`). Only considered when the handler names the
+//!    segment, since `{id}` elsewhere identifies other resources.
+//! 2. **Header** - `X-Organization-ID`
+//! 3. **Query** - `?organization_id=`
+//!
+//! Every source is untrusted: [`get_verified_organization()`] always checks the
+//! caller's membership before returning the ID. A non-member gets 404 when the
+//! organization is addressed in the path or query (so the endpoint does not
+//! reveal which organizations exist) and 403 when it was selected with the
+//! header. If membership cannot be checked the request fails closed (500).
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use shared::DbPool;
+
+use crate::middleware::FailurePolicy;
+use crate::models::ErrorResponse;
+use crate::repositories::MemberRepository;
+
+/// Header selecting the organization for JWT-authenticated requests
+pub const ORGANIZATION_ID_HEADER: &str = "X-Organization-ID";
+
+/// Where a request named its organization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrganizationIdSource {
+    Path,
+    Header,
+    Query,
+}
+
+impl OrganizationIdSource {
+    /// Response for a caller who is not a member of the organization
+    fn not_member_response(self) -> HttpResponse {
+        match self {
+            Self::Header => HttpResponse::Forbidden().json(ErrorResponse::new(
+                "forbidden",
+                "Not a member of the specified organization",
+            )),
+            Self::Path | Self::Query => HttpResponse::NotFound().json(ErrorResponse::new(
+                "not_found",
+                "Organization not found or you are not a member",
+            )),
+        }
+    }
+}
+
+/// Organization a request targets, with the caller's verified role in it
+#[derive(Debug, Clone)]
+pub struct VerifiedOrganization {
+    pub id: String,
+    /// Caller's role (`owner`, `admin`, `member` or `viewer`)
+    pub role: String,
+    pub source: OrganizationIdSource,
+}
+
+/// The `organization_id` query parameter
+#[derive(Deserialize)]
+struct OrganizationIdQuery {
+    organization_id: Option<String>,
+}
+
+/// Find the organization ID a request names, without verifying it
+///
+/// # Arguments
+/// * `req` - The HTTP request
+/// * `path_param` - Name of the route segment holding the organization ID, for
+///   routes nested under an organization (e.g. `Some("id")`)
+///
+/// # Returns
+/// The ID and its source, following the precedence in the [module docs](self),
+/// or `None` if no source has a non-empty ID.
+pub fn resolve_organization_id(
+    req: &HttpRequest,
+    path_param: Option<&str>,
+) -> Option<(String, OrganizationIdSource)> {
+    let non_empty = |id: &str| (!id.is_empty()).then(|| id.to_string());
+
+    if let Some(id) = path_param.and_then(|name| req.match_info().get(name)) {
+        if let Some(id) = non_empty(id) {
+            return Some((id, OrganizationIdSource::Path));
+        }
+    }
+
+    if let Some(id) = req
+        .headers()
+        .get(ORGANIZATION_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(non_empty)
+    {
+        return Some((id, OrganizationIdSource::Header));
+    }
+
+    web::Query::<OrganizationIdQuery>::from_query(req.query_string())
+        .ok()
+        .and_then(|q| q.into_inner().organization_id)
+        .as_deref()
+        .and_then(non_empty)
+        .map(|id| (id, OrganizationIdSource::Query))
+}
+
+/// Resolve the organization a request targets and verify the caller's membership
+///
+/// Uses Redis caching when EntityCache is available in app state.
+///
+/// # Arguments
+/// * `req` - The HTTP request
+/// * `pool` - Database connection pool
+/// * `user_id` - The authenticated user's ID
+/// * `path_param` - See [`resolve_organization_id()`]
+///
+/// # Returns
+/// * `Ok(VerifiedOrganization)` - The organization and the caller's role
+/// * `Err(HttpResponse)` - 400 if no organization is named, 403/404 if the
+///   caller is not a member, 500 if membership cannot be checked
+pub async fn get_verified_organization(
+    req: &HttpRequest,
+    pool: &DbPool,
+    user_id: &str,
+    path_param: Option<&str>,
+) -> Result<VerifiedOrganization, HttpResponse> {
+    let (org_id, source) = resolve_organization_id(req, path_param).ok_or_else(|| {
+        HttpResponse::BadRequest().json(ErrorResponse::new(
+            "missing_organization",
+            "X-Organization-ID header or organization_id query parameter is required",
+        ))
+    })?;
+
+    // CRITICAL: Verify user belongs to the organization
+    // Try to use cached version if EntityCache is available
+    let role =
+        if let Some(cache) = req.app_data::<web::Data<shared::redis::cache::EntityCache>>() {
+            MemberRepository::get_role_cached(pool, cache.get_ref(), &org_id, user_id).await
+        } else {
+            // Fallback to non-cached version if cache not available
+            MemberRepository::get_role(pool, &org_id, user_id).await
+        }
+        .map_err(|e| {
+            // Never fail open: membership is the authorization check
+            FailurePolicy::Closed.on_failure("organization", &e);
+            HttpResponse::InternalServerError().json(ErrorResponse::new(
+                "internal_error",
+                "Failed to verify organization access",
+            ))
+        })?
+        .ok_or_else(|| source.not_member_response())?;
+
+    Ok(VerifiedOrganization {
+        id: org_id,
+        role,
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_resolve_from_path() {
+        let req = TestRequest::default()
+            .param("id", "org_path")
+            .to_http_request();
+
+        assert_eq!(
+            resolve_organization_id(&req, Some("id")),
+            Some(("org_path".to_string(), OrganizationIdSource::Path))
+        );
+        // The segment is only an organization when the handler says so
+        assert_eq!(resolve_organization_id(&req, None), None);
+    }
+
+    #[test]
+    fn test_resolve_from_header() {
+        let req = TestRequest::default()
+            .insert_header((ORGANIZATION_ID_HEADER, "org_header"))
+            .to_http_request();
+
+        assert_eq!(
+            resolve_organization_id(&req, Some("id")),
+            Some(("org_header".to_string(), OrganizationIdSource::Header))
+        );
+    }
+
+    #[test]
+    fn test_resolve_from_query() {
+        let req = TestRequest::default()
+            .uri("/api/v1/billing/credits?limit=5&organization_id=org_query")
+            .to_http_request();
+
+        assert_eq!(
+            resolve_organization_id(&req, None),
+            Some(("org_query".to_string(), OrganizationIdSource::Query))
+        );
+    }
+
+    #[test]
+    fn test_resolve_missing() {
+        let req = TestRequest::default()
+            .uri("/api/v1/triggers?limit=5")
+            .to_http_request();
+        assert_eq!(resolve_organization_id(&req, Some("id")), None);
+
+        // Empty values do not count
+        let req = TestRequest::default()
+            .uri("/api/v1/triggers?organization_id=")
+            .insert_header((ORGANIZATION_ID_HEADER, ""))
+            .to_http_request();
+        assert_eq!(resolve_organization_id(&req, None), None);
+    }
+
+    #[test]
+    fn test_path_wins_over_header_and_query() {
+        let req = TestRequest::default()
+            .uri("/api/v1/organizations/org_path/triggers?organization_id=org_query")
+            .param("id", "org_path")
+            .insert_header((ORGANIZATION_ID_HEADER, "org_header"))
+            .to_http_request();
+
+        assert_eq!(
+            resolve_organization_id(&req, Some("id")),
+            Some(("org_path".to_string(), OrganizationIdSource::Path))
+        );
+    }
+
+    #[test]
+    fn test_header_wins_over_query() {
+        let req = TestRequest::default()
+            .uri("/api/v1/billing/credits?organization_id=org_query")
+            .insert_header((ORGANIZATION_ID_HEADER, "org_header"))
+            .to_http_request();
+
+        assert_eq!(
+            resolve_organization_id(&req, None),
+            Some(("org_header".to_string(), OrganizationIdSource::Header))
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_membership_lookup_failure_fails_closed() {
+        // Any query on this pool fails
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+        let req = TestRequest::default()
+            .insert_header((ORGANIZATION_ID_HEADER, "org_header"))
+            .to_http_request();
+
+        let resp = get_verified_organization(&req, &pool, "user_1", None)
+            .await
+            .unwrap_err();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_not_member_response_per_source() {
+        assert_eq!(
+            OrganizationIdSource::Header.not_member_response().status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            OrganizationIdSource::Path.not_member_response().status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            OrganizationIdSource::Query.not_member_response().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
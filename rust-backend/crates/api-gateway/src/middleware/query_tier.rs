@@ -48,6 +48,16 @@ impl QueryTier {
         }
     }
 
+    /// Get the tier number (0-3)
+    pub fn level(self) -> u8 {
+        match self {
+            QueryTier::Tier0 => 0,
+            QueryTier::Tier1 => 1,
+            QueryTier::Tier2 => 2,
+            QueryTier::Tier3 => 3,
+        }
+    }
+
     /// Get the tier name as a string
     pub fn as_str(self) -> &'static str {
         match self {
@@ -4,14 +4,22 @@
 //! - Authentication layer (Anonymous, API Key, Wallet Signature)
 //! - Query tier (Tier 0-3 with different cost multipliers)
 //! - Organization subscription plan
+//! - Per-organization overrides (`organization_rate_limits` table)
 //!
 //! # Features
 //!
 //! - Extracts authentication context from request extensions
 //! - Applies tier-based cost multipliers
-//! - Returns 429 Too Many Requests when limit exceeded
-//! - Adds X-RateLimit-* headers to all responses
-//! - Graceful degradation when Redis is unavailable
+//! - Returns 429 Too Many Requests (with `Retry-After`) when limit exceeded
+//! - Adds X-RateLimit-* headers to all rate-limited responses, allowed or not
+//! - Explicit failure policy when Redis is unavailable (see below)
+//!
+//! # Organization Overrides
+//!
+//! Organizations can have a limit and window configured per query tier. An
+//! override replaces the plan limit for that tier and is counted under its own
+//! scope ([`RateLimitScope::OrganizationTier`]). Overrides are read through the
+//! entity cache and take effect as soon as the cache entry is invalidated.
 //!
 //! # Response Headers
 //!
@@ -19,6 +27,14 @@
 //! - `X-RateLimit-Remaining`: Remaining quota
 //! - `X-RateLimit-Reset`: Unix timestamp when limit resets
 //! - `X-RateLimit-Window`: Window size in seconds
+//! - `X-RateLimit-Algorithm`: `sliding_window` or `token_bucket`
+//! - `X-RateLimit-Refill-After`: Seconds until the full quota is available again
+//!
+//! With the token bucket, `X-RateLimit-Remaining` is the number of whole tokens
+//! left and `X-RateLimit-Reset` is when the bucket will be full.
+//!
+//! 429 responses carry the same headers (with `X-RateLimit-Remaining: 0`) plus
+//! `Retry-After`, the number of seconds until the request would be allowed.
 //!
 //! # Error Response (429)
 //!
@@ -34,22 +50,54 @@
 //! }
 //! ```
 //!
+//! # Global Kill-Switch
+//!
+//! When an operator engages the kill-switch (`/api/v1/admin/kill-switch`), it
+//! is checked before the per-caller limit, for every caller that is not on its
+//! allowlist:
+//!
+//! - `cap`: all such callers share one budget of `requests_per_minute`
+//!   ([`RateLimitScope::Global`]); requests beyond it get 429
+//! - `pause`: every request gets 503 with `Retry-After`
+//!
+//! Both responses carry `X-RateLimit-Status: kill-switch`. The switch is read
+//! from Redis on each request, so changes apply immediately; if it cannot be
+//! read the failure policy applies. Admin endpoints are never affected, so the
+//! switch can always be released.
+//!
+//! # Redis Failures
+//!
+//! When Redis cannot be reached the [`FailurePolicy`] decides:
+//!
+//! - `open`: the request proceeds unchecked, with `X-RateLimit-Status: degraded`
+//! - `closed`: 503 with `Retry-After` and `X-RateLimit-Status: unavailable`
+//!
+//! The default follows the mode: open in shadow mode (which never blocks
+//! anyway), closed when enforcing. `RATE_LIMIT_FAILURE_POLICY=open|closed`
+//! overrides it.
+//!
 //! # Monitoring Token Bypass
 //!
 //! Requests with a valid `X-Monitoring-Token` header bypass rate limiting entirely.
 //! This is intended for infrastructure monitoring (Grafana, Prometheus, health checkers).
 //! The token is configured via the `MONITORING_TOKEN` environment variable.
 
-use crate::middleware::{auth_extractor::AuthContext, query_tier::QueryTier};
+use crate::middleware::{auth_extractor::AuthContext, query_tier::QueryTier, FailurePolicy};
+use crate::models::{KillSwitchMode, KillSwitchState};
+use crate::repositories::OrganizationRateLimitRepository;
+use crate::services::KillSwitchStore;
 use actix_web::{
+    body::EitherBody,
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    error::ErrorTooManyRequests,
-    http::header::{HeaderName, HeaderValue},
-    Error, HttpMessage,
+    http::header::{HeaderName, HeaderValue, RETRY_AFTER},
+    web, Error, HttpMessage, HttpResponse,
 };
 use futures_util::future::LocalBoxFuture;
+use metrics::counter;
 use once_cell::sync::Lazy;
-use shared::RateLimiter;
+use shared::models::OrganizationRateLimit;
+use shared::redis::cache::EntityCache;
+use shared::{DbPool, RateLimitAlgorithm, RateLimitResult, RateLimitScope, RateLimiter};
 use std::{
     future::{ready, Ready},
     rc::Rc,
@@ -60,6 +108,18 @@ use tracing::{debug, error, warn};
 /// Default window size in seconds (1 hour)
 const DEFAULT_WINDOW_SECONDS: i64 = 3600;
 
+/// Window of the kill-switch cap (`requests_per_minute`)
+const KILL_SWITCH_WINDOW_SECONDS: i64 = 60;
+
+/// Paths the kill-switch never applies to, so it can always be released
+const KILL_SWITCH_EXEMPT_PREFIX: &str = "/api/v1/admin/";
+
+/// `Retry-After` of the 503 sent when failing closed
+const UNAVAILABLE_RETRY_AFTER_SECONDS: u32 = 5;
+
+/// Name in failure logs and metrics
+const MIDDLEWARE_NAME: &str = "rate_limiter";
+
 /// Monitoring token loaded from environment variable
 /// If set, requests with matching X-Monitoring-Token header bypass rate limiting
 static MONITORING_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
@@ -68,35 +128,66 @@ static MONITORING_TOKEN: Lazy<Option<String>> = Lazy::new(|| {
         .filter(|t| !t.is_empty())
 });
 
+/// What happens to a request over its limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Log the violation and let the request through
+    /// (`X-RateLimit-Status: shadow-violation`)
+    Shadow,
+    /// Reject with 429
+    Enforcing,
+}
+
+impl RateLimitMode {
+    /// Parse `RATE_LIMIT_MODE` (anything other than `shadow` enforces)
+    pub fn parse(s: &str) -> Self {
+        if s == "shadow" {
+            Self::Shadow
+        } else {
+            Self::Enforcing
+        }
+    }
+
+    /// Failure policy unless one is configured: a limiter that blocks fails
+    /// closed, one that only observes fails open
+    pub fn default_failure_policy(self) -> FailurePolicy {
+        match self {
+            Self::Shadow => FailurePolicy::Open,
+            Self::Enforcing => FailurePolicy::Closed,
+        }
+    }
+}
+
 /// Rate limit mode configuration (loaded once at startup)
-/// Returns (is_production, mode_string)
-static RATE_LIMIT_CONFIG: Lazy<(bool, String)> = Lazy::new(|| {
+/// Returns (is_production, mode, failure policy from `RATE_LIMIT_FAILURE_POLICY`)
+static RATE_LIMIT_CONFIG: Lazy<(bool, RateLimitMode, Option<FailurePolicy>)> = Lazy::new(|| {
     let is_production = std::env::var("ENVIRONMENT")
         .map(|e| e == "production")
         .unwrap_or(false);
     let default_mode = if is_production { "enforcing" } else { "shadow" };
     let mode = std::env::var("RATE_LIMIT_MODE").unwrap_or_else(|_| default_mode.to_string());
-    (is_production, mode)
+    let failure_policy = FailurePolicy::from_env("RATE_LIMIT_FAILURE_POLICY");
+    (is_production, RateLimitMode::parse(&mode), failure_policy)
 });
 
 /// Add rate limit headers to a response
 ///
 /// # Arguments
 /// * `headers` - Mutable reference to response headers
-/// * `limit` - Maximum requests allowed in window
-/// * `remaining` - Remaining quota
-/// * `reset_at` - Unix timestamp when limit resets
+/// * `result` - Outcome of the rate limit check
+/// * `remaining` - Remaining quota to report
 /// * `window_seconds` - Window size in seconds
+/// * `algorithm` - Algorithm that produced the result
 fn add_rate_limit_headers(
     headers: &mut actix_web::http::header::HeaderMap,
-    limit: i64,
+    result: &RateLimitResult,
     remaining: i64,
-    reset_at: i64,
     window_seconds: i64,
+    algorithm: RateLimitAlgorithm,
 ) {
     headers.insert(
         HeaderName::from_static("x-ratelimit-limit"),
-        HeaderValue::from(limit),
+        HeaderValue::from(result.limit),
     );
     headers.insert(
         HeaderName::from_static("x-ratelimit-remaining"),
@@ -104,12 +195,261 @@ fn add_rate_limit_headers(
     );
     headers.insert(
         HeaderName::from_static("x-ratelimit-reset"),
-        HeaderValue::from(reset_at),
+        HeaderValue::from(result.reset_at),
     );
     headers.insert(
         HeaderName::from_static("x-ratelimit-window"),
         HeaderValue::from(window_seconds),
     );
+    headers.insert(
+        HeaderName::from_static("x-ratelimit-algorithm"),
+        HeaderValue::from_static(algorithm.as_str()),
+    );
+    headers.insert(
+        HeaderName::from_static("x-ratelimit-refill-after"),
+        HeaderValue::from(result.refill_after),
+    );
+}
+
+/// Build the 429 response for a rejected request
+///
+/// Carries the standard rate limit headers (nothing remaining) and
+/// `Retry-After`, which is at least one second so clients never spin.
+fn rate_limited_response(
+    result: &RateLimitResult,
+    window_seconds: i64,
+    algorithm: RateLimitAlgorithm,
+) -> HttpResponse {
+    let retry_after = result.retry_after.max(1);
+
+    let mut response = HttpResponse::TooManyRequests().json(serde_json::json!({
+        "error": {
+            "code": "RATE_LIMITED",
+            "message": format!("Rate limit exceeded. Try again in {} seconds.", retry_after),
+            "retry_after": retry_after,
+            "limit": result.limit,
+            "window": window_seconds,
+        }
+    }));
+
+    let headers = response.headers_mut();
+    add_rate_limit_headers(headers, result, 0, window_seconds, algorithm);
+    headers.insert(RETRY_AFTER, HeaderValue::from(retry_after));
+
+    response
+}
+
+/// Build the 503 response for a request rejected because Redis is unavailable
+fn unavailable_response() -> HttpResponse {
+    let mut response = HttpResponse::ServiceUnavailable().json(serde_json::json!({
+        "error": {
+            "code": "RATE_LIMIT_UNAVAILABLE",
+            "message": format!(
+                "Rate limiting is temporarily unavailable. Try again in {} seconds.",
+                UNAVAILABLE_RETRY_AFTER_SECONDS
+            ),
+            "retry_after": UNAVAILABLE_RETRY_AFTER_SECONDS,
+        }
+    }));
+
+    let headers = response.headers_mut();
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from(UNAVAILABLE_RETRY_AFTER_SECONDS),
+    );
+    headers.insert(
+        HeaderName::from_static("x-ratelimit-status"),
+        HeaderValue::from_static("unavailable"),
+    );
+    response
+}
+
+/// Mark a response as produced by the kill-switch
+fn mark_kill_switch(response: &mut HttpResponse) {
+    response.headers_mut().insert(
+        HeaderName::from_static("x-ratelimit-status"),
+        HeaderValue::from_static("kill-switch"),
+    );
+}
+
+/// Build the 503 response for a request rejected while paused
+fn paused_response(retry_after: u32) -> HttpResponse {
+    let retry_after = retry_after.max(1);
+
+    let mut response = HttpResponse::ServiceUnavailable().json(serde_json::json!({
+        "error": {
+            "code": "SERVICE_PAUSED",
+            "message": format!(
+                "Request acceptance is temporarily paused. Try again in {} seconds.",
+                retry_after
+            ),
+            "retry_after": retry_after,
+        }
+    }));
+
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    mark_kill_switch(&mut response);
+    response
+}
+
+/// What the kill-switch does with one request
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KillSwitchAction {
+    /// Allowlisted caller: continue to the per-caller check
+    Bypass,
+    /// Count against the global cap of `limit` per minute
+    Cap { limit: i64 },
+    /// Reject with 503
+    Pause { retry_after: u32 },
+}
+
+fn kill_switch_action(state: &KillSwitchState, auth_ctx: &AuthContext) -> KillSwitchAction {
+    if state.is_allowlisted(auth_ctx.organization_id.as_deref(), &auth_ctx.ip_address) {
+        return KillSwitchAction::Bypass;
+    }
+
+    match state.mode {
+        KillSwitchMode::Cap => KillSwitchAction::Cap {
+            limit: i64::from(state.requests_per_minute.unwrap_or(0)),
+        },
+        KillSwitchMode::Pause => KillSwitchAction::Pause {
+            retry_after: state.retry_after_seconds,
+        },
+    }
+}
+
+/// Apply the global kill-switch, if one is engaged
+///
+/// Returns the rejection response, or `None` to continue with the per-caller
+/// check. When the switch or the global counter can't be read,
+/// `failure_policy` decides.
+async fn check_kill_switch(
+    req: &ServiceRequest,
+    auth_ctx: &AuthContext,
+    rate_limiter: &RateLimiter,
+    failure_policy: FailurePolicy,
+) -> Option<HttpResponse> {
+    if req.path().starts_with(KILL_SWITCH_EXEMPT_PREFIX) {
+        return None;
+    }
+    let store = req.app_data::<web::Data<KillSwitchStore>>()?;
+
+    let state = match store.get().await {
+        Ok(state) => state?,
+        Err(e) => {
+            let err = format!("failed to read kill-switch: {}", e);
+            return (!failure_policy.on_failure(MIDDLEWARE_NAME, &err)).then(unavailable_response);
+        }
+    };
+
+    match kill_switch_action(&state, auth_ctx) {
+        KillSwitchAction::Bypass => None,
+        KillSwitchAction::Pause { retry_after } => {
+            debug!(scope = ?auth_ctx.get_scope(), "Kill-switch paused request");
+            counter!("api_kill_switch_rejections_total", "mode" => "pause").increment(1);
+            Some(paused_response(retry_after))
+        }
+        KillSwitchAction::Cap { limit } => {
+            let result = match rate_limiter
+                .check_with_window(RateLimitScope::Global, limit, KILL_SWITCH_WINDOW_SECONDS, 1)
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    let err = format!("kill-switch cap check failed: {}", e);
+                    return (!failure_policy.on_failure(MIDDLEWARE_NAME, &err))
+                        .then(unavailable_response);
+                }
+            };
+            if result.allowed {
+                return None;
+            }
+
+            debug!(scope = ?auth_ctx.get_scope(), "Kill-switch cap exceeded");
+            counter!("api_kill_switch_rejections_total", "mode" => "cap").increment(1);
+            let mut response = rate_limited_response(
+                &result,
+                KILL_SWITCH_WINDOW_SECONDS,
+                rate_limiter.algorithm_for(&RateLimitScope::Global),
+            );
+            mark_kill_switch(&mut response);
+            Some(response)
+        }
+    }
+}
+
+/// Rate limit parameters applied to a single request
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EffectiveLimit {
+    scope: RateLimitScope,
+    limit: i64,
+    window_seconds: i64,
+}
+
+/// Pick the limit for a request, preferring an organization override for the tier
+fn resolve_limit(
+    auth_ctx: &AuthContext,
+    tier: QueryTier,
+    overrides: &[OrganizationRateLimit],
+    default_window: i64,
+) -> EffectiveLimit {
+    let scope = auth_ctx.get_scope();
+
+    if let RateLimitScope::Organization(ref org_id) = scope {
+        if let Some(o) = overrides.iter().find(|o| o.tier == i16::from(tier.level())) {
+            return EffectiveLimit {
+                scope: RateLimitScope::OrganizationTier {
+                    organization_id: org_id.clone(),
+                    tier: tier.level(),
+                },
+                limit: i64::from(o.request_limit),
+                window_seconds: i64::from(o.window_seconds),
+            };
+        }
+    }
+
+    EffectiveLimit {
+        scope,
+        limit: auth_ctx.get_rate_limit() as i64,
+        window_seconds: default_window,
+    }
+}
+
+/// Load an organization's overrides from app state (cache first, then database)
+///
+/// Returns no overrides when the pool isn't registered or the lookup fails, so
+/// requests fall back to the plan limits.
+async fn load_overrides(
+    pool: Option<web::Data<DbPool>>,
+    cache: Option<web::Data<EntityCache>>,
+    organization_id: &str,
+) -> Vec<OrganizationRateLimit> {
+    let Some(pool) = pool else {
+        return Vec::new();
+    };
+
+    let result = match cache {
+        Some(cache) => {
+            OrganizationRateLimitRepository::list_by_organization_cached(
+                &pool,
+                &cache,
+                organization_id,
+            )
+            .await
+        }
+        None => OrganizationRateLimitRepository::list_by_organization(&pool, organization_id).await,
+    };
+
+    result.unwrap_or_else(|e| {
+        warn!(
+            error = %e,
+            organization_id = %organization_id,
+            "Failed to load rate limit overrides - using plan defaults"
+        );
+        Vec::new()
+    })
 }
 
 /// Unified rate limiter middleware
@@ -117,6 +457,9 @@ pub struct UnifiedRateLimiter {
     rate_limiter: Rc<RateLimiter>,
     /// Window size in seconds for rate limit headers
     window_seconds: i64,
+    mode: RateLimitMode,
+    /// Configured policy; `None` follows the mode
+    failure_policy: Option<FailurePolicy>,
 }
 
 impl UnifiedRateLimiter {
@@ -136,10 +479,48 @@ impl UnifiedRateLimiter {
     /// * `rate_limiter` - The rate limiter instance (shared across requests)
     /// * `window_seconds` - Window size in seconds for rate limiting
     pub fn with_window(rate_limiter: RateLimiter, window_seconds: i64) -> Self {
-        Self {
+        let (is_production, mode, failure_policy) = *RATE_LIMIT_CONFIG;
+        if is_production && mode == RateLimitMode::Shadow {
+            warn!("Rate limiting is in SHADOW mode in PRODUCTION - requests will NOT be blocked");
+        }
+
+        let limiter = Self {
             rate_limiter: Rc::new(rate_limiter),
             window_seconds,
+            mode,
+            failure_policy,
+        };
+        if limiter.failure_policy() == FailurePolicy::Open {
+            warn!("Rate limiter fails OPEN - requests are not limited while Redis is unavailable");
         }
+        limiter
+    }
+
+    /// Override the mode from `RATE_LIMIT_MODE`
+    pub fn with_mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Override the failure policy from `RATE_LIMIT_FAILURE_POLICY`
+    pub fn with_failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = Some(failure_policy);
+        self
+    }
+
+    /// What happens to requests while Redis is unavailable
+    pub fn failure_policy(&self) -> FailurePolicy {
+        self.failure_policy
+            .unwrap_or_else(|| self.mode.default_failure_policy())
+    }
+
+    /// Failure policy from `RATE_LIMIT_MODE` and `RATE_LIMIT_FAILURE_POLICY`
+    ///
+    /// For building the [`RateLimiter`]: its in-memory fallback would hide
+    /// Redis errors from a limiter that should fail closed.
+    pub fn configured_failure_policy() -> FailurePolicy {
+        let (_, mode, failure_policy) = *RATE_LIMIT_CONFIG;
+        failure_policy.unwrap_or_else(|| mode.default_failure_policy())
     }
 }
 
@@ -149,7 +530,7 @@ where
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type InitError = ();
     type Transform = UnifiedRateLimiterMiddleware<S>;
@@ -160,6 +541,8 @@ where
             service: Rc::new(service),
             rate_limiter: self.rate_limiter.clone(),
             window_seconds: self.window_seconds,
+            mode: self.mode,
+            failure_policy: self.failure_policy(),
         }))
     }
 }
@@ -168,6 +551,8 @@ pub struct UnifiedRateLimiterMiddleware<S> {
     service: Rc<S>,
     rate_limiter: Rc<RateLimiter>,
     window_seconds: i64,
+    mode: RateLimitMode,
+    failure_policy: FailurePolicy,
 }
 
 impl<S, B> Service<ServiceRequest> for UnifiedRateLimiterMiddleware<S>
@@ -176,7 +561,7 @@ where
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -185,7 +570,9 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = self.service.clone();
         let rate_limiter = self.rate_limiter.clone();
-        let window_seconds = self.window_seconds;
+        let default_window = self.window_seconds;
+        let mode = self.mode;
+        let failure_policy = self.failure_policy;
 
         Box::pin(async move {
             // Check for monitoring token bypass
@@ -204,7 +591,7 @@ where
                         && expected_bytes.ct_eq(provided_bytes).into()
                     {
                         debug!("Monitoring token valid - bypassing rate limit");
-                        return service.call(req).await;
+                        return service.call(req).await.map(|res| res.map_into_left_body());
                     }
                 }
             }
@@ -214,6 +601,7 @@ where
             // and OAuth providers (Google, GitHub) during authentication flow
             let path = req.path();
             if path == "/api/v1/health"
+                || path.starts_with("/api/v1/health/")
                 || path == "/metrics"
                 || path == "/api/v1/openapi.json"
                 || path.starts_with("/api-docs")
@@ -222,7 +610,7 @@ where
                 || path.starts_with("/api/v1/auth/link/")
                 || path == "/api/v1/auth/exchange"
             {
-                return service.call(req).await;
+                return service.call(req).await.map(|res| res.map_into_left_body());
             }
 
             // Extract authentication context (set by AuthExtractor or DualAuth middleware)
@@ -240,6 +628,13 @@ where
                 }
             };
 
+            // Global kill-switch (abuse response) takes precedence over per-caller limits
+            if let Some(response) =
+                check_kill_switch(&req, &auth_ctx, &rate_limiter, failure_policy).await
+            {
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+
             // Extract query tier (default to Tier 0 if not set)
             let query_tier = req
                 .extensions()
@@ -247,29 +642,46 @@ where
                 .copied()
                 .unwrap_or(QueryTier::Tier0);
 
-            // Get rate limit parameters
-            let scope = auth_ctx.get_scope();
-            let limit = auth_ctx.get_rate_limit() as i64;
+            // Get rate limit parameters (organization overrides take precedence)
+            let overrides = match auth_ctx.get_scope() {
+                RateLimitScope::Organization(org_id) => {
+                    load_overrides(
+                        req.app_data::<web::Data<DbPool>>().cloned(),
+                        req.app_data::<web::Data<EntityCache>>().cloned(),
+                        &org_id,
+                    )
+                    .await
+                }
+                _ => Vec::new(),
+            };
+            let EffectiveLimit {
+                scope,
+                limit,
+                window_seconds,
+            } = resolve_limit(&auth_ctx, query_tier, &overrides, default_window);
             let cost = query_tier.cost_multiplier();
 
             debug!(
                 scope = ?scope,
                 limit = limit,
+                window_seconds = window_seconds,
                 tier = %query_tier.as_str(),
                 cost = cost,
                 "Checking rate limit"
             );
 
             // Check rate limit
-            let result = match rate_limiter.check(scope.clone(), limit, cost).await {
+            let result = match rate_limiter
+                .check_with_window(scope.clone(), limit, window_seconds, cost)
+                .await
+            {
                 Ok(r) => r,
                 Err(e) => {
-                    // Redis error - log and fail open (allow request)
-                    error!(
-                        error = %e,
-                        scope = ?scope,
-                        "Rate limiter error - failing open"
-                    );
+                    if !failure_policy.on_failure(MIDDLEWARE_NAME, &e) {
+                        return Ok(req
+                            .into_response(unavailable_response())
+                            .map_into_right_body());
+                    }
 
                     // Still call the service (graceful degradation)
                     let mut res = service.call(req).await?;
@@ -281,23 +693,13 @@ where
                         HeaderValue::from_static("degraded"),
                     );
 
-                    return Ok(res);
+                    return Ok(res.map_into_left_body());
                 }
             };
 
             // Check if rate limit exceeded
             if !result.allowed {
-                // Use pre-computed rate limit mode from static config
-                let (is_production, ref mode) = *RATE_LIMIT_CONFIG;
-
-                // Warn if shadow mode is used in production (should be intentional)
-                if is_production && mode == "shadow" {
-                    warn!(
-                        "Rate limiting is in SHADOW mode in PRODUCTION - requests will NOT be blocked"
-                    );
-                }
-
-                if mode == "shadow" {
+                if mode == RateLimitMode::Shadow {
                     // Shadow mode: Log violation but allow request
                     warn!(
                         mode = "SHADOW",
@@ -318,12 +720,12 @@ where
                     );
                     add_rate_limit_headers(
                         headers,
-                        result.limit,
+                        &result,
                         0,
-                        result.reset_at,
                         window_seconds,
+                        rate_limiter.algorithm_for(&scope),
                     );
-                    return Ok(res);
+                    return Ok(res.map_into_left_body());
                 } else {
                     // Enforcing mode: Block request
                     warn!(
@@ -335,11 +737,12 @@ where
                         "Rate limit exceeded"
                     );
 
-                    // Return 429 Too Many Requests error
-                    return Err(ErrorTooManyRequests(format!(
-                        "Rate limit exceeded. Try again in {} seconds. (Limit: {}, Window: {}s)",
-                        result.retry_after, result.limit, window_seconds
-                    )));
+                    let response = rate_limited_response(
+                        &result,
+                        window_seconds,
+                        rate_limiter.algorithm_for(&scope),
+                    );
+                    return Ok(req.into_response(response).map_into_right_body());
                 }
             }
 
@@ -356,13 +759,13 @@ where
             // Add rate limit headers to response
             add_rate_limit_headers(
                 res.headers_mut(),
-                result.limit,
+                &result,
                 result.remaining,
-                result.reset_at,
                 window_seconds,
+                rate_limiter.algorithm_for(&scope),
             );
 
-            Ok(res)
+            Ok(res.map_into_left_body())
         })
     }
 }
@@ -371,6 +774,13 @@ where
 mod tests {
     use super::*;
     use crate::middleware::auth_extractor::AuthContext;
+    #[test]
+    fn test_rate_limit_mode_parse() {
+        assert_eq!(RateLimitMode::parse("shadow"), RateLimitMode::Shadow);
+        assert_eq!(RateLimitMode::parse("enforcing"), RateLimitMode::Enforcing);
+        // Unknown values fail safe
+        assert_eq!(RateLimitMode::parse("Shadow"), RateLimitMode::Enforcing);
+    }
 
     #[test]
     fn test_query_tier_cost_applied() {
@@ -386,6 +796,269 @@ mod tests {
         assert_eq!(ctx.get_rate_limit(), 10);
     }
 
+    fn org_override(tier: i16, request_limit: i32, window_seconds: i32) -> OrganizationRateLimit {
+        OrganizationRateLimit {
+            organization_id: "org_123".to_string(),
+            tier,
+            request_limit,
+            window_seconds,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn org_ctx() -> AuthContext {
+        AuthContext::wallet_signature(
+            "user_1".to_string(),
+            "org_123".to_string(),
+            42,
+            "10.0.0.1".to_string(),
+            "starter".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_resolve_limit_defaults_without_override() {
+        let resolved = resolve_limit(&org_ctx(), QueryTier::Tier1, &[], DEFAULT_WINDOW_SECONDS);
+
+        assert_eq!(
+            resolved,
+            EffectiveLimit {
+                scope: RateLimitScope::Organization("org_123".to_string()),
+                limit: 100,
+                window_seconds: DEFAULT_WINDOW_SECONDS,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_limit_org_override_takes_precedence() {
+        let overrides = vec![org_override(1, 5000, 600)];
+        let resolved = resolve_limit(
+            &org_ctx(),
+            QueryTier::Tier1,
+            &overrides,
+            DEFAULT_WINDOW_SECONDS,
+        );
+
+        assert_eq!(
+            resolved,
+            EffectiveLimit {
+                scope: RateLimitScope::OrganizationTier {
+                    organization_id: "org_123".to_string(),
+                    tier: 1,
+                },
+                limit: 5000,
+                window_seconds: 600,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_limit_override_only_applies_to_its_tier() {
+        let overrides = vec![org_override(3, 20, 60)];
+        let resolved = resolve_limit(
+            &org_ctx(),
+            QueryTier::Tier0,
+            &overrides,
+            DEFAULT_WINDOW_SECONDS,
+        );
+
+        assert_eq!(resolved.limit, 100);
+        assert_eq!(
+            resolved.scope,
+            RateLimitScope::Organization("org_123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_limit_ignores_overrides_for_anonymous() {
+        let ctx = AuthContext::anonymous("192.168.1.1".to_string());
+        let overrides = vec![org_override(0, 5000, 600)];
+        let resolved = resolve_limit(&ctx, QueryTier::Tier0, &overrides, DEFAULT_WINDOW_SECONDS);
+
+        assert_eq!(resolved.limit, 10);
+        assert_eq!(
+            resolved.scope,
+            RateLimitScope::Ip("192.168.1.1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_headers_include_algorithm_and_refill() {
+        let result = RateLimitResult {
+            allowed: true,
+            current_usage: 3,
+            limit: 10,
+            reset_at: 1_732_800_618,
+            retry_after: 0,
+            remaining: 7,
+            refill_after: 18,
+        };
+        let mut headers = actix_web::http::header::HeaderMap::new();
+
+        add_rate_limit_headers(
+            &mut headers,
+            &result,
+            result.remaining,
+            60,
+            RateLimitAlgorithm::TokenBucket,
+        );
+
+        assert_eq!(headers.get("x-ratelimit-remaining").unwrap(), "7");
+        assert_eq!(headers.get("x-ratelimit-reset").unwrap(), "1732800618");
+        assert_eq!(
+            headers.get("x-ratelimit-algorithm").unwrap(),
+            "token_bucket"
+        );
+        assert_eq!(headers.get("x-ratelimit-refill-after").unwrap(), "18");
+    }
+
+    #[test]
+    fn test_rate_limited_response_headers() {
+        let result = RateLimitResult {
+            allowed: false,
+            current_usage: 10,
+            limit: 10,
+            reset_at: 1_732_800_618,
+            retry_after: 42,
+            remaining: 0,
+            refill_after: 42,
+        };
+
+        let response = rate_limited_response(&result, 3600, RateLimitAlgorithm::SlidingWindow);
+
+        assert_eq!(
+            response.status(),
+            actix_web::http::StatusCode::TOO_MANY_REQUESTS
+        );
+        let headers = response.headers();
+        assert_eq!(headers.get("retry-after").unwrap(), "42");
+        assert_eq!(headers.get("x-ratelimit-limit").unwrap(), "10");
+        assert_eq!(headers.get("x-ratelimit-remaining").unwrap(), "0");
+        assert_eq!(headers.get("x-ratelimit-reset").unwrap(), "1732800618");
+        assert_eq!(headers.get("x-ratelimit-window").unwrap(), "3600");
+    }
+
+    #[test]
+    fn test_rate_limited_response_retry_after_at_least_one_second() {
+        let result = RateLimitResult {
+            allowed: false,
+            current_usage: 10,
+            limit: 10,
+            reset_at: 1_732_800_618,
+            retry_after: 0,
+            remaining: 0,
+            refill_after: 0,
+        };
+
+        let response = rate_limited_response(&result, 60, RateLimitAlgorithm::TokenBucket);
+        assert_eq!(response.headers().get("retry-after").unwrap(), "1");
+    }
+
+    fn kill_switch(mode: KillSwitchMode) -> KillSwitchState {
+        KillSwitchState {
+            mode,
+            requests_per_minute: (mode == KillSwitchMode::Cap).then_some(600),
+            retry_after_seconds: 120,
+            allow_organizations: vec!["org_ops".to_string()],
+            allow_ips: vec!["10.0.0.9".to_string()],
+            reason: "abuse".to_string(),
+            engaged_at: chrono::Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_kill_switch_cap_counts_against_global_limit() {
+        let state = kill_switch(KillSwitchMode::Cap);
+
+        assert_eq!(
+            kill_switch_action(&state, &org_ctx()),
+            KillSwitchAction::Cap { limit: 600 }
+        );
+        let anonymous = AuthContext::anonymous("203.0.113.1".to_string());
+        assert_eq!(
+            kill_switch_action(&state, &anonymous),
+            KillSwitchAction::Cap { limit: 600 }
+        );
+    }
+
+    #[test]
+    fn test_kill_switch_cap_rejection_response() {
+        let result = RateLimitResult {
+            allowed: false,
+            current_usage: 600,
+            limit: 600,
+            reset_at: 1_732_800_618,
+            retry_after: 0,
+            remaining: 0,
+            refill_after: 1,
+        };
+        let mut response = rate_limited_response(
+            &result,
+            KILL_SWITCH_WINDOW_SECONDS,
+            RateLimitAlgorithm::TokenBucket,
+        );
+        mark_kill_switch(&mut response);
+
+        assert_eq!(
+            response.status(),
+            actix_web::http::StatusCode::TOO_MANY_REQUESTS
+        );
+        let headers = response.headers();
+        assert_eq!(headers.get("retry-after").unwrap(), "1");
+        assert_eq!(headers.get("x-ratelimit-limit").unwrap(), "600");
+        assert_eq!(headers.get("x-ratelimit-window").unwrap(), "60");
+        assert_eq!(headers.get("x-ratelimit-status").unwrap(), "kill-switch");
+    }
+
+    #[test]
+    fn test_kill_switch_pause_rejects_with_retry_after() {
+        let state = kill_switch(KillSwitchMode::Pause);
+        let action = kill_switch_action(&state, &org_ctx());
+        assert_eq!(action, KillSwitchAction::Pause { retry_after: 120 });
+
+        let response = paused_response(120);
+        assert_eq!(
+            response.status(),
+            actix_web::http::StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(response.headers().get("retry-after").unwrap(), "120");
+        assert_eq!(
+            response.headers().get("x-ratelimit-status").unwrap(),
+            "kill-switch"
+        );
+        assert_eq!(
+            paused_response(0).headers().get("retry-after").unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_kill_switch_allowlisted_callers_bypass() {
+        let allowlisted_org = AuthContext::wallet_signature(
+            "user_1".to_string(),
+            "org_ops".to_string(),
+            42,
+            "203.0.113.1".to_string(),
+            "starter".to_string(),
+        );
+        let allowlisted_ip = AuthContext::anonymous("10.0.0.9".to_string());
+
+        for mode in [KillSwitchMode::Cap, KillSwitchMode::Pause] {
+            let state = kill_switch(mode);
+            assert_eq!(
+                kill_switch_action(&state, &allowlisted_org),
+                KillSwitchAction::Bypass
+            );
+            assert_eq!(
+                kill_switch_action(&state, &allowlisted_ip),
+                KillSwitchAction::Bypass
+            );
+        }
+    }
+
     #[test]
     fn test_rate_limiter_requires_auth_context() {
         // This test verifies that the middleware expects AuthContext in extensions
@@ -443,6 +1116,244 @@ mod tests {
         std::env::remove_var("ENVIRONMENT");
         std::env::remove_var("RATE_LIMIT_MODE");
     }
+
+    #[test]
+    fn test_default_failure_policy_follows_mode() {
+        assert_eq!(
+            RateLimitMode::Shadow.default_failure_policy(),
+            FailurePolicy::Open
+        );
+        assert_eq!(
+            RateLimitMode::Enforcing.default_failure_policy(),
+            FailurePolicy::Closed
+        );
+    }
+
+    #[test]
+    fn test_unavailable_response() {
+        let resp = unavailable_response();
+
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "5");
+        assert_eq!(
+            resp.headers().get("x-ratelimit-status").unwrap(),
+            "unavailable"
+        );
+    }
+
+    /// Redis errors on every command
+    mod redis_down {
+        use super::*;
+        use actix_web::{
+            body::MessageBody,
+            http::StatusCode,
+            middleware::{from_fn, Next},
+            test, App,
+        };
+        use shared::redis::testing::FailingRedis;
+
+        async fn anonymous_caller(
+            req: ServiceRequest,
+            next: Next<impl MessageBody>,
+        ) -> Result<ServiceResponse<impl MessageBody>, Error> {
+            req.extensions_mut()
+                .insert(AuthContext::anonymous("10.0.0.1".to_string()));
+            next.call(req).await
+        }
+
+        async fn call(limiter: UnifiedRateLimiter) -> ServiceResponse<impl MessageBody> {
+            let app = test::init_service(
+                App::new()
+                    .wrap(limiter)
+                    .wrap(from_fn(anonymous_caller))
+                    .route("/api/v1/triggers", web::get().to(HttpResponse::Ok)),
+            )
+            .await;
+            let req = test::TestRequest::get()
+                .uri("/api/v1/triggers")
+                .to_request();
+            test::call_service(&app, req).await
+        }
+
+        /// Limiter without the in-memory fallback, so errors reach the middleware
+        async fn limiter(redis: &FailingRedis) -> RateLimiter {
+            RateLimiter::with_config(redis.connect().await, 3600, false)
+                .await
+                .unwrap()
+        }
+
+        #[actix_web::test]
+        async fn test_fail_closed_rejects() {
+            let redis = FailingRedis::start().await;
+            let limiter = UnifiedRateLimiter::new(limiter(&redis).await)
+                .with_mode(RateLimitMode::Shadow)
+                .with_failure_policy(FailurePolicy::Closed);
+
+            let resp = call(limiter).await;
+            assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(
+                resp.headers().get("x-ratelimit-status").unwrap(),
+                "unavailable"
+            );
+        }
+
+        #[actix_web::test]
+        async fn test_fail_open_allows() {
+            let redis = FailingRedis::start().await;
+            let limiter = UnifiedRateLimiter::new(limiter(&redis).await)
+                .with_mode(RateLimitMode::Enforcing)
+                .with_failure_policy(FailurePolicy::Open);
+
+            let resp = call(limiter).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                resp.headers().get("x-ratelimit-status").unwrap(),
+                "degraded"
+            );
+        }
+
+        #[actix_web::test]
+        async fn test_enforcing_fails_closed_by_default() {
+            if std::env::var("RATE_LIMIT_FAILURE_POLICY").is_ok() {
+                return; // Configured explicitly in this environment
+            }
+            let redis = FailingRedis::start().await;
+            let limiter =
+                UnifiedRateLimiter::new(limiter(&redis).await).with_mode(RateLimitMode::Enforcing);
+
+            let resp = call(limiter).await;
+            assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+
+        #[actix_web::test]
+        async fn test_kill_switch_read_failure_follows_policy() {
+            let redis = FailingRedis::start().await;
+            let app = |policy: FailurePolicy| {
+                let redis = &redis;
+                async move {
+                    test::init_service(
+                        App::new()
+                            .app_data(web::Data::new(KillSwitchStore::new(redis.connect().await)))
+                            .wrap(
+                                UnifiedRateLimiter::new(limiter(redis).await)
+                                    .with_failure_policy(policy),
+                            )
+                            .wrap(from_fn(anonymous_caller))
+                            .route("/api/v1/triggers", web::get().to(HttpResponse::Ok)),
+                    )
+                    .await
+                }
+            };
+
+            let closed = app(FailurePolicy::Closed).await;
+            let req = test::TestRequest::get()
+                .uri("/api/v1/triggers")
+                .to_request();
+            assert_eq!(
+                test::call_service(&closed, req).await.status(),
+                StatusCode::SERVICE_UNAVAILABLE
+            );
+
+            let open = app(FailurePolicy::Open).await;
+            let req = test::TestRequest::get()
+                .uri("/api/v1/triggers")
+                .to_request();
+            assert_eq!(
+                test::call_service(&open, req).await.status(),
+                StatusCode::OK
+            );
+        }
+    }
+
+    /// Shadow vs enforcing against Redis (`--features integration-tests`)
+    #[cfg(feature = "integration-tests")]
+    mod modes {
+        use super::*;
+        use actix_web::{
+            body::MessageBody,
+            http::StatusCode,
+            middleware::{from_fn, Next},
+            test, App,
+        };
+        use shared::redis::testing::TestRedis;
+        use shared::ManualClock;
+
+        /// Anonymous caller with the IP in `X-Test-Ip`
+        async fn anonymous_caller(
+            req: ServiceRequest,
+            next: Next<impl MessageBody>,
+        ) -> Result<ServiceResponse<impl MessageBody>, Error> {
+            let ip = req
+                .headers()
+                .get("X-Test-Ip")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("127.0.0.1")
+                .to_string();
+            req.extensions_mut().insert(AuthContext::anonymous(ip));
+            next.call(req).await
+        }
+
+        /// Send one more request than the anonymous limit (10/hour); return the
+        /// last response
+        async fn exceed_anonymous_limit(mode: RateLimitMode) -> ServiceResponse<impl MessageBody> {
+            let redis = TestRedis::start().await;
+            let limiter = RateLimiter::with_config(redis.connect().await, 3600, false)
+                .await
+                .unwrap()
+                .with_clock(ManualClock::new(1_700_000_040_000));
+            let app = test::init_service(
+                App::new()
+                    .wrap(UnifiedRateLimiter::new(limiter).with_mode(mode))
+                    .wrap(from_fn(anonymous_caller))
+                    .route("/api/v1/triggers", web::get().to(HttpResponse::Ok)),
+            )
+            .await;
+            let ip = format!("10.0.{}.{}", rand::random::<u8>(), rand::random::<u8>());
+
+            for remaining in (0..10).rev() {
+                let req = test::TestRequest::get()
+                    .uri("/api/v1/triggers")
+                    .insert_header(("X-Test-Ip", ip.as_str()))
+                    .to_request();
+                let resp = test::call_service(&app, req).await;
+                assert_eq!(resp.status(), StatusCode::OK);
+                assert_eq!(
+                    resp.headers().get("x-ratelimit-remaining").unwrap(),
+                    remaining.to_string().as_str()
+                );
+            }
+
+            let req = test::TestRequest::get()
+                .uri("/api/v1/triggers")
+                .insert_header(("X-Test-Ip", ip.as_str()))
+                .to_request();
+            test::call_service(&app, req).await
+        }
+
+        #[actix_web::test]
+        async fn test_shadow_mode_does_not_block() {
+            let resp = exceed_anonymous_limit(RateLimitMode::Shadow).await;
+
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                resp.headers().get("x-ratelimit-status").unwrap(),
+                "shadow-violation"
+            );
+            assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+        }
+
+        #[actix_web::test]
+        async fn test_enforcing_mode_blocks() {
+            let resp = exceed_anonymous_limit(RateLimitMode::Enforcing).await;
+
+            assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert!(resp.headers().get("x-ratelimit-status").is_none());
+            assert!(resp.headers().contains_key(RETRY_AFTER));
+        }
+    }
 }
 
 // Rust guideline compliant 2025-01-28
@@ -155,6 +155,14 @@ impl JsonRpcError {
     pub fn task_expired() -> Self {
         Self::new(-32004, "Task result expired")
     }
+
+    pub fn task_not_cancelable(status: &str) -> Self {
+        Self::with_data(
+            -32005,
+            "Task cannot be cancelled",
+            serde_json::json!({ "status": status }),
+        )
+    }
 }
 
 // ============================================================================
@@ -185,6 +193,25 @@ impl std::fmt::Display for TaskStatus {
     }
 }
 
+impl TaskStatus {
+    /// Whether the task has finished (no further events follow)
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+        )
+    }
+
+    /// SSE event type reporting a task in this status
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            TaskStatus::Completed => "complete",
+            TaskStatus::Failed | TaskStatus::Cancelled => "error",
+            TaskStatus::Submitted | TaskStatus::Working => "progress",
+        }
+    }
+}
+
 /// Task definition in tasks/send request
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TaskDefinition {
@@ -330,6 +357,34 @@ impl A2aTask {
     }
 }
 
+/// A2A task event database row (one SSE event)
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct A2aTaskEvent {
+    /// Monotonic event ID, sent as the SSE `id:` field
+    pub id: i64,
+    pub task_id: Uuid,
+    /// progress, complete or error
+    pub event_type: String,
+    /// Task status when the event was recorded (a [`TaskGetResult`])
+    pub data: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl A2aTaskEvent {
+    /// Whether this event ends the task's stream
+    pub fn is_terminal(&self) -> bool {
+        self.event_type != "progress"
+    }
+
+    /// Encode as a Server-Sent Event
+    pub fn to_sse(&self) -> String {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.id, self.event_type, self.data
+        )
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -406,5 +461,33 @@ mod tests {
         );
         assert_eq!(JsonRpcError::rate_limited().code, -32002);
         assert_eq!(JsonRpcError::task_not_found().code, -32003);
+        assert_eq!(JsonRpcError::task_not_cancelable("completed").code, -32005);
+    }
+
+    #[test]
+    fn test_task_status_event_type() {
+        assert_eq!(TaskStatus::Submitted.event_type(), "progress");
+        assert_eq!(TaskStatus::Working.event_type(), "progress");
+        assert_eq!(TaskStatus::Completed.event_type(), "complete");
+        assert_eq!(TaskStatus::Failed.event_type(), "error");
+        assert_eq!(TaskStatus::Cancelled.event_type(), "error");
+        assert!(!TaskStatus::Working.is_terminal());
+        assert!(TaskStatus::Cancelled.is_terminal());
+    }
+
+    #[test]
+    fn test_task_event_to_sse() {
+        let event = A2aTaskEvent {
+            id: 42,
+            task_id: Uuid::nil(),
+            event_type: "progress".to_string(),
+            data: serde_json::json!({"status": "working"}),
+            created_at: chrono::Utc::now(),
+        };
+        assert_eq!(
+            event.to_sse(),
+            "id: 42\nevent: progress\ndata: {\"status\":\"working\"}\n\n"
+        );
+        assert!(!event.is_terminal());
     }
 }
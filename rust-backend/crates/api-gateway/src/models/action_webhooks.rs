@@ -0,0 +1,111 @@
+//! Action Result Webhook DTOs
+//!
+//! An organization can register one endpoint that receives a signed summary
+//! after each of its actions executes. Deliveries are signed with a secret
+//! generated by the server, which is only returned when it is created or
+//! rotated.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::services::webhook_verification::is_private_host;
+
+/// Request to create or update the organization's action result webhook
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"url": "https://hooks.example.com/agentauri", "enabled": true}))]
+pub struct SetActionResultWebhookRequest {
+    /// Endpoint that receives action result summaries (http/https, public host)
+    #[validate(length(max = 2048), custom(function = "validate_webhook_url"))]
+    pub url: String,
+
+    /// Whether deliveries are active (default: true)
+    pub enabled: Option<bool>,
+
+    /// Generate a new signing secret (default: false)
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// An organization's action result webhook
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionResultWebhookResponse {
+    pub organization_id: String,
+    pub url: String,
+    pub enabled: bool,
+    /// Signing secret, only present when it was just created or rotated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(with = "shared::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ActionResultWebhookResponse {
+    /// Build a response, revealing the secret only when asked to
+    pub fn new(webhook: shared::models::OrganizationActionWebhook, reveal_secret: bool) -> Self {
+        Self {
+            organization_id: webhook.organization_id,
+            url: webhook.url,
+            enabled: webhook.enabled,
+            secret: reveal_secret.then_some(webhook.secret),
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+pub(crate) fn validate_webhook_url(url: &str) -> Result<(), ValidationError> {
+    let invalid = |message: &'static str| {
+        let mut error = ValidationError::new("invalid_url");
+        error.message = Some(message.into());
+        error
+    };
+
+    let parsed = reqwest::Url::parse(url).map_err(|_| invalid("url must be a valid URL"))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(invalid("url must use http or https"));
+    }
+
+    if is_private_host(parsed.host_str().unwrap_or_default()) {
+        return Err(invalid("url must not target private or internal hosts"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str) -> SetActionResultWebhookRequest {
+        SetActionResultWebhookRequest {
+            url: url.to_string(),
+            enabled: None,
+            rotate_secret: false,
+        }
+    }
+
+    #[test]
+    fn test_valid_url() {
+        assert!(request("https://hooks.example.com/agentauri")
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_non_http_scheme() {
+        assert!(request("ftp://hooks.example.com").validate().is_err());
+        assert!(request("not a url").validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_private_hosts() {
+        assert!(request("http://localhost:8080/hook").validate().is_err());
+        assert!(request("http://10.0.0.5/hook").validate().is_err());
+        assert!(request("http://169.254.169.254/latest").validate().is_err());
+    }
+}
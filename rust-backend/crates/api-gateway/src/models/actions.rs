@@ -1,9 +1,15 @@
 //! Trigger Action DTOs
 
-use serde::Deserialize;
-use utoipa::ToSchema;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+/// Category reported for failures logged before categories were recorded
+pub const UNCATEGORIZED_FAILURE: &str = "uncategorized";
+
 /// Request to create a new action
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[schema(example = json!({"action_type": "telegram", "priority": 10, "config": {"chat_id": "123456789", "message_template": "Alert: {{event}}"}}))]
@@ -30,6 +36,166 @@ pub struct UpdateActionRequest {
     pub config: Option<serde_json::Value>,
 }
 
+/// Verification state persisted in `trigger_actions.verification_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    /// Action type has no endpoint to verify (telegram, mcp)
+    NotRequired,
+    /// REST action that has not completed the handshake
+    Unverified,
+    /// Endpoint echoed the challenge token
+    Verified,
+    /// Handshake was attempted and failed
+    Failed,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotRequired => "not_required",
+            Self::Unverified => "unverified",
+            Self::Verified => "verified",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Status assigned to a new action before any handshake runs
+    pub fn initial_for(action_type: &str) -> Self {
+        if action_type == "rest" {
+            Self::Unverified
+        } else {
+            Self::NotRequired
+        }
+    }
+}
+
+/// Query parameters for an action's result summary
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ActionResultSummaryQuery {
+    /// Days of results to summarize (1-90, default 7)
+    #[serde(default = "default_summary_days")]
+    pub days: i64,
+}
+
+fn default_summary_days() -> i64 {
+    7
+}
+
+impl ActionResultSummaryQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.days < 1 || self.days > 90 {
+            return Err("Days must be between 1 and 90".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Number of an action's results with one status and failure reason
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ActionResultCount {
+    pub status: String,
+    pub error_category: Option<String>,
+    pub error_code: Option<String>,
+    pub count: i64,
+}
+
+/// Outcomes of an action's executions over a period
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionResultSummaryResponse {
+    pub action_id: i32,
+    /// Start of the summarized period
+    #[serde(with = "shared::timestamp")]
+    pub since: DateTime<Utc>,
+    pub total: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    /// Failures grouped by category, most frequent first
+    pub failures_by_category: Vec<FailureCategorySummary>,
+}
+
+/// Failures of one category
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FailureCategorySummary {
+    /// `timeout`, `connection`, `client_error`, `server_error`, `rate_limited`,
+    /// `upstream`, `template`, `config`, `internal`, `billing`, or
+    /// `uncategorized` for failures logged before categories were recorded
+    pub category: String,
+    pub count: i64,
+    /// Failure codes within the category (e.g. `http_503`), most frequent first
+    pub codes: Vec<FailureCodeCount>,
+}
+
+/// Failures with one code
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FailureCodeCount {
+    pub code: String,
+    pub count: i64,
+}
+
+impl ActionResultSummaryResponse {
+    /// Build the summary from per-status, per-reason counts
+    pub fn from_counts(
+        action_id: i32,
+        since: DateTime<Utc>,
+        counts: Vec<ActionResultCount>,
+    ) -> Self {
+        let mut summary = Self {
+            action_id,
+            since,
+            total: 0,
+            succeeded: 0,
+            failed: 0,
+            failures_by_category: Vec::new(),
+        };
+        let mut by_category: HashMap<String, HashMap<String, i64>> = HashMap::new();
+
+        for row in counts {
+            summary.total += row.count;
+            match row.status.as_str() {
+                "success" => summary.succeeded += row.count,
+                "failed" => {
+                    summary.failed += row.count;
+                    let category = row
+                        .error_category
+                        .unwrap_or_else(|| UNCATEGORIZED_FAILURE.to_string());
+                    let code = row
+                        .error_code
+                        .unwrap_or_else(|| UNCATEGORIZED_FAILURE.to_string());
+                    *by_category
+                        .entry(category)
+                        .or_default()
+                        .entry(code)
+                        .or_default() += row.count;
+                }
+                _ => {}
+            }
+        }
+
+        summary.failures_by_category = by_category
+            .into_iter()
+            .map(|(category, codes)| {
+                let mut codes: Vec<FailureCodeCount> = codes
+                    .into_iter()
+                    .map(|(code, count)| FailureCodeCount { code, count })
+                    .collect();
+                codes.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.code.cmp(&b.code)));
+                FailureCategorySummary {
+                    category,
+                    count: codes.iter().map(|c| c.count).sum(),
+                    codes,
+                }
+            })
+            .collect();
+        summary.failures_by_category.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+
+        summary
+    }
+}
+
 /// Custom validator for action_type field
 fn validate_action_type(action_type: &str) -> Result<(), validator::ValidationError> {
     if !["telegram", "rest", "mcp"].contains(&action_type) {
@@ -206,4 +372,100 @@ mod tests {
         assert!(validate_action_type("email").is_err());
         assert!(validate_action_type("webhook").is_err());
     }
+
+    // ========================================================================
+    // VerificationStatus tests
+    // ========================================================================
+
+    #[test]
+    fn test_verification_status_initial_for_action_type() {
+        assert_eq!(
+            VerificationStatus::initial_for("rest"),
+            VerificationStatus::Unverified
+        );
+        assert_eq!(
+            VerificationStatus::initial_for("telegram"),
+            VerificationStatus::NotRequired
+        );
+        assert_eq!(
+            VerificationStatus::initial_for("mcp"),
+            VerificationStatus::NotRequired
+        );
+    }
+
+    #[test]
+    fn test_verification_status_as_str() {
+        assert_eq!(VerificationStatus::NotRequired.as_str(), "not_required");
+        assert_eq!(VerificationStatus::Unverified.as_str(), "unverified");
+        assert_eq!(VerificationStatus::Verified.as_str(), "verified");
+        assert_eq!(VerificationStatus::Failed.as_str(), "failed");
+    }
+
+    // ========================================================================
+    // ActionResultSummaryResponse tests
+    // ========================================================================
+
+    fn count(status: &str, reason: Option<(&str, &str)>, count: i64) -> ActionResultCount {
+        ActionResultCount {
+            status: status.to_string(),
+            error_category: reason.map(|(category, _)| category.to_string()),
+            error_code: reason.map(|(_, code)| code.to_string()),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_summary_groups_failures_by_category() {
+        let summary = ActionResultSummaryResponse::from_counts(
+            7,
+            Utc::now(),
+            vec![
+                count("success", None, 40),
+                count("failed", Some(("server_error", "http_503")), 3),
+                count("failed", Some(("timeout", "request_timeout")), 2),
+                count("failed", Some(("server_error", "http_500")), 4),
+                count("failed", None, 1),
+                count("retrying", None, 5),
+            ],
+        );
+
+        assert_eq!(summary.total, 55);
+        assert_eq!(summary.succeeded, 40);
+        assert_eq!(summary.failed, 10);
+
+        let categories: Vec<_> = summary
+            .failures_by_category
+            .iter()
+            .map(|c| (c.category.as_str(), c.count))
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                ("server_error", 7),
+                ("timeout", 2),
+                (UNCATEGORIZED_FAILURE, 1)
+            ]
+        );
+
+        let codes: Vec<_> = summary.failures_by_category[0]
+            .codes
+            .iter()
+            .map(|c| (c.code.as_str(), c.count))
+            .collect();
+        assert_eq!(codes, vec![("http_500", 4), ("http_503", 3)]);
+    }
+
+    #[test]
+    fn test_summary_without_results() {
+        let summary = ActionResultSummaryResponse::from_counts(7, Utc::now(), vec![]);
+        assert_eq!(summary.total, 0);
+        assert!(summary.failures_by_category.is_empty());
+    }
+
+    #[test]
+    fn test_summary_query_days_range() {
+        assert!(ActionResultSummaryQuery { days: 7 }.validate().is_ok());
+        assert!(ActionResultSummaryQuery { days: 0 }.validate().is_err());
+        assert!(ActionResultSummaryQuery { days: 91 }.validate().is_err());
+    }
 }
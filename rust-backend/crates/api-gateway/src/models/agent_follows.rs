@@ -5,9 +5,14 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::NotificationPreferences;
 use utoipa::ToSchema;
 use validator::Validate;
 
+use super::triggers::deserialize_explicit_null;
+use super::PaginationMeta;
+use crate::repositories::{FollowCursor, FollowedAgentRow};
+
 /// Request to follow an agent
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[schema(example = json!({
@@ -45,7 +50,8 @@ pub struct FollowActionRequest {
     "actions": [{
         "action_type": "telegram",
         "config": {"chat_id": "987654321"}
-    }]
+    }],
+    "notification_preferences": {"event_types": ["NewFeedback"], "channels": ["telegram"]}
 }))]
 pub struct UpdateFollowRequest {
     /// Enable or disable the follow
@@ -54,6 +60,13 @@ pub struct UpdateFollowRequest {
     /// Replace all actions with new configuration
     #[validate(length(min = 1, max = 10))]
     pub actions: Option<Vec<FollowActionRequest>>,
+
+    /// Event types and channels to notify about; `null` notifies about
+    /// everything again, omit to keep the current preferences
+    #[serde(default, deserialize_with = "deserialize_explicit_null")]
+    #[validate(custom(function = "validate_preferences"))]
+    #[schema(value_type = Option<Object>)]
+    pub notification_preferences: Option<Option<NotificationPreferences>>,
 }
 
 /// Response for agent follow
@@ -68,10 +81,48 @@ pub struct AgentFollowResponse {
     pub registries_monitored: i32,
     /// Summary of configured actions
     pub actions: Vec<FollowActionSummary>,
+    /// Event types and channels notified about (null = everything)
+    #[schema(value_type = Option<Object>)]
+    pub notification_preferences: Option<NotificationPreferences>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
+/// Followed agent as listed by `GET /agents/following`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowedAgentResponse {
+    #[serde(flatten)]
+    pub follow: AgentFollowResponse,
+    /// Basic profile of the followed agent
+    pub agent: FollowedAgentProfile,
+}
+
+/// What is known about a followed agent beyond its token ID
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowedAgentProfile {
+    /// Whether the agent is linked to an organization
+    pub linked: bool,
+    /// Whether the agent is linked to the requesting organization
+    pub linked_to_organization: bool,
+    /// Wallet that linked the agent
+    pub wallet_address: Option<String>,
+    /// When the agent was linked
+    #[serde(with = "shared::timestamp::option")]
+    pub linked_at: Option<DateTime<Utc>>,
+}
+
+/// Page of followed agents
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowedAgentsResponse {
+    pub data: Vec<FollowedAgentResponse>,
+    /// Total follows matching the filters
+    pub pagination: PaginationMeta,
+    /// Pass as `cursor` to fetch the next page; null on the last page
+    pub next_cursor: Option<String>,
+}
+
 /// Detailed follow response including underlying trigger IDs
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AgentFollowDetailResponse {
@@ -116,6 +167,32 @@ pub struct ListFollowsQuery {
     pub chain_id: Option<i32>,
     /// Filter by enabled status (optional)
     pub enabled: Option<bool>,
+    /// Cursor from a previous page's `next_cursor`
+    pub cursor: Option<String>,
+}
+
+impl ListFollowsQuery {
+    /// Decode `cursor`, which cannot be combined with a non-zero `offset`
+    pub fn after(&self, offset: i64) -> Result<Option<FollowCursor>, String> {
+        match &self.cursor {
+            Some(_) if offset != 0 => Err("offset and cursor cannot be combined".to_string()),
+            Some(cursor) => FollowCursor::decode(cursor)
+                .map(Some)
+                .ok_or_else(|| "Invalid cursor".to_string()),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Custom validator for notification preferences
+fn validate_preferences(
+    preferences: &NotificationPreferences,
+) -> Result<(), validator::ValidationError> {
+    preferences.validate().map_err(|e| {
+        let mut error = validator::ValidationError::new("invalid_notification_preferences");
+        error.message = Some(e.to_string().into());
+        error
+    })
 }
 
 /// Custom validator for action_type
@@ -126,6 +203,22 @@ fn validate_action_type(action_type: &str) -> Result<(), validator::ValidationEr
     Ok(())
 }
 
+impl From<FollowedAgentRow> for FollowedAgentResponse {
+    fn from(row: FollowedAgentRow) -> Self {
+        let linked_to_organization =
+            row.link_organization_id.as_deref() == Some(row.follow.organization_id.as_str());
+        Self {
+            agent: FollowedAgentProfile {
+                linked: row.link_organization_id.is_some(),
+                linked_to_organization,
+                wallet_address: row.link_wallet_address,
+                linked_at: row.linked_at,
+            },
+            follow: row.follow.into(),
+        }
+    }
+}
+
 impl From<shared::models::AgentFollow> for AgentFollowResponse {
     fn from(follow: shared::models::AgentFollow) -> Self {
         Self {
@@ -136,6 +229,7 @@ impl From<shared::models::AgentFollow> for AgentFollowResponse {
             enabled: follow.enabled,
             registries_monitored: 3,
             actions: vec![], // Actions are loaded separately
+            notification_preferences: follow.notification_preferences,
             created_at: follow.created_at,
             updated_at: follow.updated_at,
         }
@@ -248,6 +342,7 @@ mod tests {
                 action_type: "rest".to_string(),
                 config: serde_json::json!({"url": "https://example.com/webhook"}),
             }]),
+            notification_preferences: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -257,8 +352,36 @@ mod tests {
         let req = UpdateFollowRequest {
             enabled: None,
             actions: None,
+            notification_preferences: None,
+        };
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_update_follow_request_notification_preferences() {
+        let parse = |body: serde_json::Value| -> UpdateFollowRequest {
+            serde_json::from_value(body).unwrap()
         };
+
+        let req = parse(serde_json::json!({
+            "notification_preferences": {"event_types": ["NewFeedback"], "channels": ["telegram"]}
+        }));
         assert!(req.validate().is_ok());
+        let preferences = req.notification_preferences.unwrap().unwrap();
+        assert!(preferences.allows("NewFeedback", "telegram"));
+
+        // null resets the preferences, omitting keeps them
+        let req = parse(serde_json::json!({"notification_preferences": null}));
+        assert_eq!(req.notification_preferences, Some(None));
+        assert_eq!(parse(serde_json::json!({})).notification_preferences, None);
+
+        let req = parse(serde_json::json!({"notification_preferences": {"channels": ["email"]}}));
+        assert!(req.validate().is_err());
+
+        let unknown: Result<UpdateFollowRequest, _> = serde_json::from_value(
+            serde_json::json!({"notification_preferences": {"categories": []}}),
+        );
+        assert!(unknown.is_err());
     }
 
     #[test]
@@ -293,6 +416,7 @@ mod tests {
                 action_type: "telegram".to_string(),
                 config_preview: serde_json::json!({"chat_id": "123"}),
             }],
+            notification_preferences: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -302,4 +426,73 @@ mod tests {
         assert!(json.contains("42"));
         assert!(json.contains("telegram"));
     }
+
+    fn list_query(cursor: Option<String>) -> ListFollowsQuery {
+        ListFollowsQuery {
+            chain_id: None,
+            enabled: None,
+            cursor,
+        }
+    }
+
+    #[test]
+    fn test_list_follows_query_after() {
+        assert_eq!(list_query(None).after(40), Ok(None));
+
+        let cursor = FollowCursor {
+            created_at: DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+            id: "follow-123".to_string(),
+        };
+        let query = list_query(Some(cursor.encode()));
+        assert_eq!(query.after(0), Ok(Some(cursor)));
+        assert!(query.after(20).is_err());
+
+        assert_eq!(
+            list_query(Some("garbage".to_string())).after(0),
+            Err("Invalid cursor".to_string())
+        );
+    }
+
+    #[test]
+    fn test_followed_agent_response_profile() {
+        let now = chrono::Utc::now();
+        let follow = shared::models::AgentFollow {
+            id: "follow-123".to_string(),
+            agent_id: 42,
+            chain_id: 84532,
+            organization_id: "org-456".to_string(),
+            user_id: "user-1".to_string(),
+            trigger_identity_id: "t1".to_string(),
+            trigger_reputation_id: "t2".to_string(),
+            trigger_validation_id: "t3".to_string(),
+            enabled: true,
+            notification_preferences: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let unlinked = FollowedAgentResponse::from(FollowedAgentRow {
+            follow: follow.clone(),
+            link_wallet_address: None,
+            link_organization_id: None,
+            linked_at: None,
+        });
+        assert!(!unlinked.agent.linked);
+        let json = serde_json::to_value(&unlinked).unwrap();
+        assert_eq!(json["id"], "follow-123");
+        assert!(json["agent"]["linked_at"].is_null());
+
+        let linked_elsewhere = FollowedAgentResponse::from(FollowedAgentRow {
+            follow,
+            link_wallet_address: Some("0xabc".to_string()),
+            link_organization_id: Some("org-other".to_string()),
+            linked_at: Some(now),
+        });
+        assert!(linked_elsewhere.agent.linked);
+        assert!(!linked_elsewhere.agent.linked_to_organization);
+        assert_eq!(
+            linked_elsewhere.agent.wallet_address.as_deref(),
+            Some("0xabc")
+        );
+    }
 }
@@ -84,6 +84,7 @@ pub struct CreateApiKeyRequest {
     pub rate_limit_override: Option<i32>,
 
     /// Optional expiration timestamp
+    #[serde(default, with = "shared::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
@@ -104,6 +105,7 @@ pub struct RotateApiKeyRequest {
     pub name: Option<String>,
 
     /// Optional new expiration
+    #[serde(default, with = "shared::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
@@ -124,6 +126,7 @@ pub struct UpdateApiKeyRequest {
     pub name: Option<String>,
 
     /// New expiration timestamp
+    #[serde(default, with = "shared::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
@@ -158,9 +161,11 @@ pub struct ApiKeyCreatedResponse {
     pub permissions: Vec<String>,
 
     /// When the key was created
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 
     /// When the key expires (if set)
+    #[serde(with = "shared::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
 }
 
@@ -174,11 +179,15 @@ pub struct ApiKeyResponse {
     pub key_type: String,
     pub permissions: Vec<String>,
     pub rate_limit_override: Option<i32>,
+    #[serde(with = "shared::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(with = "shared::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
     pub created_by: String,
     pub is_revoked: bool,
+    #[serde(with = "shared::timestamp::option")]
     pub revoked_at: Option<DateTime<Utc>>,
 }
 
@@ -222,6 +231,7 @@ pub struct RotateApiKeyResponse {
     pub old_key_id: String,
 
     /// When the old key was revoked
+    #[serde(with = "shared::timestamp")]
     pub old_key_revoked_at: DateTime<Utc>,
 }
 
@@ -644,6 +654,54 @@ mod tests {
         // one invalid
     }
 
+    // ========================================================================
+    // Timestamp serialization tests
+    // ========================================================================
+
+    #[test]
+    fn test_api_key_response_timestamps_are_canonical() {
+        use chrono::TimeZone;
+
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 15, 10, 30, 0).unwrap();
+        let response = ApiKeyResponse {
+            id: "key_123".to_string(),
+            name: "Production Key".to_string(),
+            prefix: "sk_live_abc".to_string(),
+            environment: "live".to_string(),
+            key_type: "standard".to_string(),
+            permissions: vec!["read".to_string()],
+            rate_limit_override: None,
+            last_used_at: None,
+            expires_at: Some(created_at + chrono::Duration::days(90)),
+            created_at,
+            created_by: "user_123".to_string(),
+            is_revoked: false,
+            revoked_at: None,
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["created_at"], "2026-01-15T10:30:00.000000Z");
+        assert_eq!(json["expires_at"], "2026-04-15T10:30:00.000000Z");
+        assert!(json["last_used_at"].is_null());
+        assert!(json["revoked_at"].is_null());
+    }
+
+    #[test]
+    fn test_create_api_key_request_accepts_offset_timestamps() {
+        let req: CreateApiKeyRequest = serde_json::from_str(
+            r#"{"name": "Key", "environment": "live", "expires_at": "2026-01-15T12:30:00+02:00"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            req.expires_at.map(|t| shared::timestamp::format(&t)),
+            Some("2026-01-15T10:30:00.000000Z".to_string())
+        );
+
+        let req: CreateApiKeyRequest =
+            serde_json::from_str(r#"{"name": "Key", "environment": "live"}"#).unwrap();
+        assert!(req.expires_at.is_none());
+    }
+
     // ========================================================================
     // Helper function tests
     // ========================================================================
@@ -125,6 +125,50 @@ pub struct AuthResponse {
     pub user: UserResponse,
 }
 
+/// Request to verify an email address
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VerifyEmailRequest {
+    /// Token from the verification link
+    #[validate(length(min = 1, max = 128))]
+    pub token: String,
+}
+
+/// Response from the email verification endpoints
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmailVerificationResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Request a password reset email
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"email": "john@example.com"}))]
+pub struct ForgotPasswordRequest {
+    #[validate(email)]
+    pub email: String,
+}
+
+/// Set a new password with the token from a reset email
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequest {
+    /// Token from the reset link
+    #[validate(length(min = 1, max = 128))]
+    pub token: String,
+
+    #[validate(
+        length(min = 12, max = 128),
+        custom(function = "validate_password_strength")
+    )]
+    pub new_password: String,
+}
+
+/// Response from the password reset endpoints
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PasswordResetResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 /// Request to refresh an access token
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct RefreshTokenRequest {
@@ -194,9 +238,14 @@ pub struct UserResponse {
     pub email: String,
     pub name: Option<String>,
     pub avatar: Option<String>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default, with = "shared::timestamp::option")]
     pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
     pub is_active: bool,
+    /// Whether the user has verified their email address
+    #[serde(default)]
+    pub email_verified: bool,
 }
 
 impl From<shared::models::User> for UserResponse {
@@ -210,6 +259,7 @@ impl From<shared::models::User> for UserResponse {
             created_at: user.created_at,
             last_login_at: user.last_login_at,
             is_active: user.is_active,
+            email_verified: user.email_verified_at.is_some(),
         }
     }
 }
@@ -222,9 +272,12 @@ pub struct MeResponse {
     pub email: String,
     pub name: Option<String>,
     pub avatar: Option<String>,
+    /// Whether the user has verified their email address
+    pub email_verified: bool,
     pub wallets: Vec<WalletInfo>,
     pub providers: Vec<String>,
     pub organizations: Vec<OrganizationInfo>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -250,6 +303,10 @@ pub struct NonceRequest {
     /// Wallet address (checksummed, e.g., 0x1234...abcd)
     #[validate(length(min = 42, max = 42))]
     pub address: String,
+
+    /// Chain the wallet signs in on (default: 1, Ethereum mainnet)
+    #[serde(default = "default_chain_id")]
+    pub chain_id: i32,
 }
 
 /// Nonce response for SIWE wallet authentication
@@ -269,8 +326,17 @@ pub struct WalletLoginRequest {
     /// EIP-191 signed message
     pub signature: String,
 
-    /// The message that was signed (includes nonce)
+    /// The EIP-4361 message from `/auth/nonce` that was signed
     pub message: String,
+
+    /// Chain the wallet signs in on; must match the message's `Chain ID`
+    /// (default: 1, Ethereum mainnet)
+    #[serde(default = "default_chain_id")]
+    pub chain_id: i32,
+}
+
+fn default_chain_id() -> i32 {
+    1
 }
 
 /// Logout response
@@ -280,6 +346,40 @@ pub struct LogoutResponse {
     pub message: String,
 }
 
+/// Active login session (one per refresh token family)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponse {
+    /// Session ID
+    pub id: String,
+    /// User agent of the device that last used the session
+    pub user_agent: Option<String>,
+    /// IP address the session was last used from
+    pub ip_address: Option<String>,
+    /// When the user logged in
+    #[serde(with = "shared::timestamp")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the session's refresh token was last issued (login or refresh)
+    #[serde(with = "shared::timestamp")]
+    pub last_used_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "shared::timestamp")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Whether this is the session of the access token making the request
+    pub current: bool,
+}
+
+/// Active login sessions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionListResponse {
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Result of revoking sessions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevokeSessionsResponse {
+    /// Number of sessions revoked
+    pub revoked: u64,
+}
+
 /// JWT claims
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -287,6 +387,9 @@ pub struct Claims {
     pub username: String, // Username for convenience
     pub exp: i64,         // Expiration time (as UTC timestamp)
     pub iat: i64,         // Issued at (as UTC timestamp)
+    /// Login session (refresh token family) the token was issued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
 impl Claims {
@@ -299,8 +402,15 @@ impl Claims {
             username,
             exp,
             iat: now,
+            sid: None,
         }
     }
+
+    /// Tie the token to a login session
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.sid = Some(session_id.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -486,6 +596,34 @@ mod tests {
         assert!(errors.field_errors().contains_key("password"));
     }
 
+    // ========================================================================
+    // Password reset request validation tests
+    // ========================================================================
+
+    #[test]
+    fn test_forgot_password_request_invalid_email() {
+        let req = ForgotPasswordRequest {
+            email: "not-an-email".to_string(),
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_reset_password_request_enforces_password_strength() {
+        let req = ResetPasswordRequest {
+            token: "prt_token".to_string(),
+            new_password: "SecurePass123!".to_string(),
+        };
+        assert!(req.validate().is_ok());
+
+        let req = ResetPasswordRequest {
+            token: "prt_token".to_string(),
+            new_password: "password1234".to_string(),
+        };
+        let errors = req.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("new_password"));
+    }
+
     // ========================================================================
     // Claims tests
     // ========================================================================
@@ -507,6 +645,23 @@ mod tests {
         assert_eq!(claims.exp - claims.iat, 86400); // 24 hours in seconds
     }
 
+    #[test]
+    fn test_claims_session_id_roundtrip() {
+        let claims = Claims::new("user-123".to_string(), "testuser".to_string(), 1);
+        let json = serde_json::to_value(&claims).unwrap();
+        assert!(json.get("sid").is_none());
+
+        // Tokens issued before sessions were tracked still decode
+        let decoded: Claims = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.sid, None);
+
+        let claims = claims.with_session("family-1");
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["sid"], "family-1");
+        let decoded: Claims = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.sid.as_deref(), Some("family-1"));
+    }
+
     // ========================================================================
     // Serialization tests
     // ========================================================================
@@ -526,6 +681,7 @@ mod tests {
                 created_at: chrono::Utc::now(),
                 last_login_at: None,
                 is_active: true,
+                email_verified: false,
             },
         };
 
@@ -548,10 +704,12 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_login_at: Some(chrono::Utc::now()),
             is_active: true,
+            email_verified: true,
         };
 
         let json = serde_json::to_string(&user).unwrap();
         assert!(json.contains("user-123"));
         assert!(json.contains("is_active"));
+        assert!(json.contains("\"email_verified\":true"));
     }
 }
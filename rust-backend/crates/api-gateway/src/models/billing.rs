@@ -36,6 +36,41 @@ impl CreditBalanceResponse {
     }
 }
 
+/// Request to set the low-balance alert threshold of an organization
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"threshold": 1000000}))]
+pub struct SetLowBalanceThresholdRequest {
+    /// Balance in micro-USDC below which an alert is sent (null disables alerts)
+    #[validate(range(min = 1))]
+    pub threshold: Option<i64>,
+}
+
+/// Low-balance alert settings of an organization
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example = json!({"threshold": 1000000, "threshold_formatted": "1.000000 USDC", "balance": 5000000, "notified_at": null}))]
+pub struct LowBalanceThresholdResponse {
+    /// Threshold in micro-USDC (null = alerts disabled)
+    pub threshold: Option<i64>,
+    /// Threshold in human-readable format
+    pub threshold_formatted: Option<String>,
+    /// Current balance in micro-USDC
+    pub balance: i64,
+    /// When the current alert was sent (null = the next crossing alerts)
+    #[serde(with = "shared::timestamp::option")]
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+impl From<Credit> for LowBalanceThresholdResponse {
+    fn from(credit: Credit) -> Self {
+        Self {
+            threshold: credit.low_balance_threshold,
+            threshold_formatted: credit.low_balance_threshold.map(format_micro_usdc),
+            balance: credit.balance,
+            notified_at: credit.low_balance_notified_at,
+        }
+    }
+}
+
 /// Format micro-USDC to human-readable string (e.g., 1000000 -> "1.00 USDC")
 fn format_micro_usdc(micro: i64) -> String {
     let whole = micro / 1_000_000;
@@ -63,9 +98,79 @@ pub struct CreditTransactionResponse {
     /// Balance after transaction
     pub balance_after: i64,
     /// Transaction timestamp
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
+/// Receipt for a credit transaction
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example = json!({
+    "receipt_number": "AA-000000000042",
+    "transaction_id": 42,
+    "organization": {"id": "org_abc123", "name": "Acme", "slug": "acme"},
+    "transaction_type": "purchase",
+    "description": "Stripe checkout purchase",
+    "amount": 10000000,
+    "amount_formatted": "10.000000 USDC",
+    "currency": "USDC",
+    "reference_id": "cs_test_a1b2c3",
+    "balance_after": 15000000,
+    "date": "2024-01-15T10:00:00.000000Z"
+}))]
+pub struct ReceiptResponse {
+    /// Receipt number (stable per transaction)
+    pub receipt_number: String,
+    pub transaction_id: i64,
+    /// Organization the transaction belongs to
+    pub organization: ReceiptOrganization,
+    pub transaction_type: String,
+    pub description: Option<String>,
+    /// Amount in micro-USDC (positive for additions, negative for deductions)
+    pub amount: i64,
+    /// Amount in human-readable format
+    pub amount_formatted: String,
+    /// Currency (always "USDC")
+    pub currency: String,
+    /// External reference ID (Stripe session, refund or dispute ID, etc.)
+    pub reference_id: Option<String>,
+    /// Balance after the transaction, in micro-USDC
+    pub balance_after: i64,
+    /// Transaction timestamp
+    #[serde(with = "shared::timestamp")]
+    pub date: DateTime<Utc>,
+}
+
+/// Organization named on a receipt
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReceiptOrganization {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+}
+
+impl ReceiptResponse {
+    /// Receipt for `tx`, issued to `organization`
+    pub fn new(tx: CreditTransaction, organization: &shared::models::Organization) -> Self {
+        Self {
+            receipt_number: format!("AA-{:012}", tx.id),
+            transaction_id: tx.id,
+            organization: ReceiptOrganization {
+                id: organization.id.clone(),
+                name: organization.name.clone(),
+                slug: organization.slug.clone(),
+            },
+            transaction_type: tx.transaction_type,
+            description: tx.description,
+            amount: tx.amount,
+            amount_formatted: format_micro_usdc(tx.amount),
+            currency: "USDC".to_string(),
+            reference_id: tx.reference_id,
+            balance_after: tx.balance_after,
+            date: tx.created_at,
+        }
+    }
+}
+
 /// Query parameters for listing transactions
 #[derive(Debug, Deserialize, Validate)]
 pub struct TransactionListQuery {
@@ -132,10 +237,13 @@ pub struct SubscriptionResponse {
     /// Subscription status
     pub status: String,
     /// Current period start
+    #[serde(with = "shared::timestamp::option")]
     pub current_period_start: Option<DateTime<Utc>>,
     /// Current period end
+    #[serde(with = "shared::timestamp::option")]
     pub current_period_end: Option<DateTime<Utc>>,
     /// Cancelation timestamp (if canceled)
+    #[serde(with = "shared::timestamp::option")]
     pub canceled_at: Option<DateTime<Utc>>,
 }
 
@@ -151,6 +259,12 @@ pub struct Credit {
     pub organization_id: String,
     pub balance: i64,
     pub currency: String,
+    /// Balance below which a low-balance alert is sent (None = no alerts)
+    #[sqlx(default)]
+    pub low_balance_threshold: Option<i64>,
+    /// When the current low-balance alert was sent (None = armed)
+    #[sqlx(default)]
+    pub low_balance_notified_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -159,7 +273,6 @@ pub struct Credit {
 #[derive(Debug, sqlx::FromRow)]
 pub struct CreditTransaction {
     pub id: i64,
-    #[allow(dead_code)] // Used in SQL queries and database operations
     pub organization_id: String,
     pub amount: i64,
     pub transaction_type: String,
@@ -266,6 +379,90 @@ mod tests {
         assert_eq!(response.balance_formatted, "1000000.000000 USDC");
     }
 
+    // ========================================================================
+    // Low-balance threshold tests
+    // ========================================================================
+
+    #[test]
+    fn test_set_low_balance_threshold_validation() {
+        let request: SetLowBalanceThresholdRequest =
+            serde_json::from_value(serde_json::json!({"threshold": 1_000_000})).unwrap();
+        assert!(request.validate().is_ok());
+
+        let request: SetLowBalanceThresholdRequest =
+            serde_json::from_value(serde_json::json!({"threshold": null})).unwrap();
+        assert_eq!(request.threshold, None);
+        assert!(request.validate().is_ok());
+
+        let request = SetLowBalanceThresholdRequest { threshold: Some(0) };
+        assert!(request.validate().is_err());
+        let request = SetLowBalanceThresholdRequest {
+            threshold: Some(-5),
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_low_balance_threshold_response_from_credit() {
+        let credit = Credit {
+            id: "credit_1".to_string(),
+            organization_id: "org_1".to_string(),
+            balance: 500_000,
+            currency: "USDC".to_string(),
+            low_balance_threshold: Some(1_000_000),
+            low_balance_notified_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let response = LowBalanceThresholdResponse::from(credit);
+        assert_eq!(response.threshold, Some(1_000_000));
+        assert_eq!(
+            response.threshold_formatted.as_deref(),
+            Some("1.000000 USDC")
+        );
+        assert_eq!(response.balance, 500_000);
+        assert_eq!(response.notified_at, None);
+    }
+
+    // ========================================================================
+    // Receipt tests
+    // ========================================================================
+
+    #[test]
+    fn test_receipt_from_transaction() {
+        let created_at = DateTime::from_timestamp(1_705_312_800, 0).unwrap();
+        let organization = shared::models::Organization {
+            id: "org_abc123".to_string(),
+            name: "Acme".to_string(),
+            slug: "acme".to_string(),
+            description: None,
+            owner_id: "user_1".to_string(),
+            plan: "free".to_string(),
+            is_personal: false,
+            created_at,
+            updated_at: created_at,
+        };
+        let tx = CreditTransaction {
+            id: 42,
+            organization_id: "org_abc123".to_string(),
+            amount: 10_000_000,
+            transaction_type: "purchase".to_string(),
+            description: Some("Stripe checkout purchase".to_string()),
+            reference_id: Some("cs_test_a1b2c3".to_string()),
+            balance_after: 15_000_000,
+            metadata: None,
+            created_at,
+        };
+
+        let receipt = serde_json::to_value(ReceiptResponse::new(tx, &organization)).unwrap();
+        assert_eq!(receipt["receipt_number"], "AA-000000000042");
+        assert_eq!(receipt["organization"]["name"], "Acme");
+        assert_eq!(receipt["amount"], 10_000_000);
+        assert_eq!(receipt["amount_formatted"], "10.000000 USDC");
+        assert_eq!(receipt["reference_id"], "cs_test_a1b2c3");
+        assert_eq!(receipt["date"], "2024-01-15T10:00:00.000000Z");
+    }
+
     // ========================================================================
     // TransactionListQuery tests
     // ========================================================================
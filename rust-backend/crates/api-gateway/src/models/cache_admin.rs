@@ -0,0 +1,190 @@
+//! Entity Cache Admin DTOs
+//!
+//! A flush targets either a namespace of the entity cache (optionally narrowed
+//! to one entity ID) or a single raw cache key. Flushing a whole namespace
+//! requires `confirm` to repeat the namespace name.
+
+use serde::{Deserialize, Serialize};
+use shared::redis::{key_space, KeyNamespace};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// Entity cache namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheNamespace {
+    Users,
+    Organizations,
+    Triggers,
+}
+
+impl CacheNamespace {
+    /// Name as used in requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheNamespace::Users => "users",
+            CacheNamespace::Organizations => "organizations",
+            CacheNamespace::Triggers => "triggers",
+        }
+    }
+}
+
+impl std::fmt::Display for CacheNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Request to flush entries from the entity cache
+///
+/// Set either `namespace` (with an optional `id`) or `key`, not both.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_flush_target"))]
+#[schema(example = json!({"namespace": "organizations", "id": "org_123"}))]
+pub struct FlushCacheRequest {
+    /// Namespace to flush
+    pub namespace: Option<CacheNamespace>,
+
+    /// Only flush keys for this entity ID
+    #[validate(length(min = 1, max = 255), custom(function = "validate_no_glob"))]
+    pub id: Option<String>,
+
+    /// Flush this exact cache key (must be in the `agentauri:cache` namespace)
+    #[validate(length(max = 512))]
+    pub key: Option<String>,
+
+    /// Namespace name, required when flushing a whole namespace
+    pub confirm: Option<String>,
+}
+
+/// Result of a cache flush
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FlushCacheResponse {
+    /// Pattern or key that was flushed
+    pub target: String,
+    /// Number of keys deleted
+    pub keys_deleted: usize,
+}
+
+fn validation_error(code: &'static str, message: &'static str) -> ValidationError {
+    let mut error = ValidationError::new(code);
+    error.message = Some(message.into());
+    error
+}
+
+/// Reject IDs that would widen the SCAN pattern
+fn validate_no_glob(id: &str) -> Result<(), ValidationError> {
+    if id.contains(['*', '?', '[', ']', '\\']) {
+        return Err(validation_error(
+            "invalid_id",
+            "id must not contain pattern characters",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_flush_target(req: &FlushCacheRequest) -> Result<(), ValidationError> {
+    match (&req.namespace, &req.key) {
+        (Some(_), Some(_)) | (None, None) => Err(validation_error(
+            "invalid_target",
+            "exactly one of namespace or key is required",
+        )),
+        (None, Some(key)) => {
+            if req.id.is_some() {
+                return Err(validation_error(
+                    "invalid_target",
+                    "id can only be used with namespace",
+                ));
+            }
+            let prefix = key_space().namespace_prefix(KeyNamespace::Cache);
+            if !key.starts_with(&prefix) || key.len() == prefix.len() {
+                return Err(validation_error(
+                    "invalid_key",
+                    "key must be an entity cache key (agentauri:cache:...)",
+                ));
+            }
+            Ok(())
+        }
+        (Some(namespace), None) => {
+            if req.id.is_none() && req.confirm.as_deref() != Some(namespace.as_str()) {
+                return Err(validation_error(
+                    "confirmation_required",
+                    "flushing a whole namespace requires confirm to match the namespace",
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        namespace: Option<CacheNamespace>,
+        id: Option<&str>,
+        key: Option<&str>,
+        confirm: Option<&str>,
+    ) -> FlushCacheRequest {
+        FlushCacheRequest {
+            namespace,
+            id: id.map(String::from),
+            key: key.map(String::from),
+            confirm: confirm.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_namespace_with_id_is_valid() {
+        let req = request(Some(CacheNamespace::Users), Some("user_1"), None, None);
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_whole_namespace_requires_confirmation() {
+        let ns = Some(CacheNamespace::Triggers);
+        assert!(request(ns, None, None, None).validate().is_err());
+        assert!(request(ns, None, None, Some("users")).validate().is_err());
+        assert!(request(ns, None, None, Some("triggers")).validate().is_ok());
+    }
+
+    #[test]
+    fn test_exactly_one_target() {
+        assert!(request(None, None, None, None).validate().is_err());
+        let both = request(
+            Some(CacheNamespace::Users),
+            Some("user_1"),
+            Some("agentauri:cache:user:id:user_1"),
+            None,
+        );
+        assert!(both.validate().is_err());
+    }
+
+    #[test]
+    fn test_key_must_be_entity_cache_key() {
+        let ok = request(None, None, Some("agentauri:cache:org:id:org_1"), None);
+        assert!(ok.validate().is_ok());
+        for key in ["agentauri:rl:org:org_1", "agentauri:cache:", "action_jobs"] {
+            assert!(request(None, None, Some(key), None).validate().is_err());
+        }
+        let with_id = request(None, Some("x"), Some("agentauri:cache:org:id:x"), None);
+        assert!(with_id.validate().is_err());
+    }
+
+    #[test]
+    fn test_id_cannot_contain_pattern_characters() {
+        for id in ["*", "user_1*", "user_?", "[a]"] {
+            let req = request(Some(CacheNamespace::Users), Some(id), None, None);
+            assert!(req.validate().is_err(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_namespace_serde() {
+        let req: FlushCacheRequest =
+            serde_json::from_str(r#"{"namespace":"organizations","id":"org_1"}"#).unwrap();
+        assert_eq!(req.namespace, Some(CacheNamespace::Organizations));
+        assert!(serde_json::from_str::<FlushCacheRequest>(r#"{"namespace":"orgs"}"#).is_err());
+    }
+}
@@ -20,9 +20,11 @@ pub struct CircuitBreakerStateResponse {
     pub failure_count: u32,
     /// Timestamp of last failure
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "shared::timestamp::option")]
     pub last_failure_time: Option<DateTime<Utc>>,
     /// Timestamp when circuit was opened
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "shared::timestamp::option")]
     pub opened_at: Option<DateTime<Utc>>,
     /// Number of calls made in half-open state
     pub half_open_calls: u32,
@@ -5,12 +5,15 @@ use utoipa::ToSchema;
 
 /// Standard error response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
-#[schema(example = json!({"error": "not_found", "message": "Resource not found"}))]
+#[schema(example = json!({"error": "not_found", "message": "Resource not found", "code": "ERR_NOT_FOUND"}))]
 pub struct ErrorResponse {
     /// Error code (e.g., "not_found", "validation_error")
     pub error: String,
     /// Human-readable error message
     pub message: String,
+    /// Stable machine-readable code of a [`shared::Error`] (e.g. "ERR_DB_TIMEOUT")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
     /// Additional error details (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
@@ -21,11 +24,17 @@ impl ErrorResponse {
         Self {
             error: error.into(),
             message: message.into(),
+            code: None,
             details: None,
         }
     }
 
-    #[allow(dead_code)]
+    /// Attach the machine-readable code of the error behind this response
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
     pub fn with_details(
         error: impl Into<String>,
         message: impl Into<String>,
@@ -34,11 +43,27 @@ impl ErrorResponse {
         Self {
             error: error.into(),
             message: message.into(),
+            code: None,
             details: Some(details),
         }
     }
 }
 
+impl From<&shared::Error> for ErrorResponse {
+    /// Response body for an error, with its code and a message safe for clients
+    fn from(error: &shared::Error) -> Self {
+        let kind = match error.status_code() {
+            400 => "validation_error",
+            401 => "unauthorized",
+            403 => "forbidden",
+            404 => "not_found",
+            503 => "service_unavailable",
+            _ => "internal_error",
+        };
+        Self::new(kind, error.public_message()).with_code(error.code())
+    }
+}
+
 /// Standard success response wrapper
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SuccessResponse<T> {
@@ -156,6 +181,65 @@ mod tests {
         assert!(json.contains("key"));
     }
 
+    #[test]
+    fn test_error_response_from_shared_error() {
+        let cases = [
+            (
+                shared::Error::from(sqlx::Error::PoolTimedOut),
+                "service_unavailable",
+                "ERR_DB_TIMEOUT",
+            ),
+            (
+                shared::Error::not_found("Trigger", "t1"),
+                "not_found",
+                "ERR_NOT_FOUND",
+            ),
+            (
+                shared::Error::validation("bad limit"),
+                "validation_error",
+                "ERR_VALIDATION",
+            ),
+            (
+                shared::Error::authentication("expired"),
+                "unauthorized",
+                "ERR_UNAUTHENTICATED",
+            ),
+            (
+                shared::Error::authorization("not a member"),
+                "forbidden",
+                "ERR_FORBIDDEN",
+            ),
+            (
+                shared::Error::internal("redis://secret@host"),
+                "internal_error",
+                "ERR_INTERNAL",
+            ),
+        ];
+
+        for (error, kind, code) in cases {
+            let err = ErrorResponse::from(&error);
+            assert_eq!(err.error, kind);
+            assert_eq!(err.code.as_deref(), Some(code));
+            assert_eq!(err.message, error.public_message());
+        }
+    }
+
+    #[test]
+    fn test_error_response_code_serialization() {
+        let json = serde_json::to_value(ErrorResponse::new("not_found", "Missing")).unwrap();
+        assert!(json.get("code").is_none());
+
+        let json =
+            serde_json::to_value(ErrorResponse::from(&shared::Error::internal("x"))).unwrap();
+        assert_eq!(json["code"], "ERR_INTERNAL");
+        assert_eq!(json["message"], "Internal server error");
+
+        // Bodies without a code still deserialize
+        let parsed: ErrorResponse =
+            serde_json::from_str(r#"{"error": "not_found", "message": "Missing"}"#).unwrap();
+        assert_eq!(parsed.code, None);
+    }
+
     // ========================================================================
     // SuccessResponse tests
     // ========================================================================
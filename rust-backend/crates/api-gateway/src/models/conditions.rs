@@ -1,6 +1,7 @@
 //! Trigger Condition DTOs
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use shared::ConditionError;
 use utoipa::ToSchema;
 use validator::Validate;
 
@@ -42,6 +43,62 @@ pub struct UpdateConditionRequest {
     pub config: Option<serde_json::Value>,
 }
 
+/// A problem found by condition validation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConditionIssue {
+    /// Error code (`unknown_condition_type`, `unknown_field`, `unsupported_field`,
+    /// `unsupported_operator`, `type_mismatch` or `missing_config`)
+    pub code: String,
+    /// The condition property at fault
+    pub property: String,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl From<ConditionError> for ConditionIssue {
+    fn from(error: ConditionError) -> Self {
+        Self {
+            code: error.code().to_string(),
+            property: error.property().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Result of validating a condition
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(example = json!({"valid": false, "errors": [{"code": "unsupported_operator", "property": "operator", "message": "Operator '~' is not supported by score_threshold (expected <, >, =, ==, <=, >=, !=, <>)"}]}))]
+pub struct ConditionValidationResponse {
+    /// Whether the event processor can evaluate the condition
+    pub valid: bool,
+    /// Problems found; empty when valid
+    pub errors: Vec<ConditionIssue>,
+}
+
+impl ConditionValidationResponse {
+    /// Validate a condition without saving it
+    pub fn check(
+        condition_type: &str,
+        field: &str,
+        operator: &str,
+        value: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Self {
+        let errors: Vec<ConditionIssue> =
+            shared::validate_condition(condition_type, field, operator, value, config)
+                .err()
+                .unwrap_or_default()
+                .into_iter()
+                .map(ConditionIssue::from)
+                .collect();
+
+        Self {
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +222,56 @@ mod tests {
         assert!(errors.field_errors().contains_key("value"));
     }
 
+    // ========================================================================
+    // ConditionValidationResponse tests
+    // ========================================================================
+
+    #[test]
+    fn test_condition_validation_response_valid() {
+        let req = CreateConditionRequest {
+            condition_type: "score_threshold".to_string(),
+            field: "score".to_string(),
+            operator: "<".to_string(),
+            value: "60".to_string(),
+            config: None,
+        };
+        let response = ConditionValidationResponse::check(
+            &req.condition_type,
+            &req.field,
+            &req.operator,
+            &req.value,
+            req.config.as_ref(),
+        );
+        assert!(response.valid);
+        assert!(response.errors.is_empty());
+    }
+
+    #[test]
+    fn test_condition_validation_response_lists_errors() {
+        let req = CreateConditionRequest {
+            condition_type: "score_threshold".to_string(),
+            field: "rating".to_string(),
+            operator: "~".to_string(),
+            value: "high".to_string(),
+            config: None,
+        };
+        let response = ConditionValidationResponse::check(
+            &req.condition_type,
+            &req.field,
+            &req.operator,
+            &req.value,
+            req.config.as_ref(),
+        );
+        assert!(!response.valid);
+        let codes: Vec<_> = response.errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            vec!["unknown_field", "unsupported_operator", "type_mismatch"]
+        );
+        assert_eq!(response.errors[0].property, "field");
+        assert_eq!(response.errors[0].message, "Unknown field 'rating'");
+    }
+
     // ========================================================================
     // UpdateConditionRequest validation tests
     // ========================================================================
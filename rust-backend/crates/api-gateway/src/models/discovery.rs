@@ -1,6 +1,7 @@
 //! Discovery endpoint data structures
 //!
-//! Agent Card specification for /.well-known/agent.json endpoint.
+//! Agent Card specification for /.well-known/agent.json endpoint, and the
+//! agent directory returned by `GET /api/v1/discovery`.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,7 @@ pub struct AgentCardResponse {
     pub endpoints: Endpoints,
     pub contact: Contact,
     pub protocol_version: String,
+    #[serde(with = "shared::timestamp")]
     pub generated_at: DateTime<Utc>,
 }
 
@@ -101,6 +103,34 @@ pub struct Contact {
     pub documentation: String,
 }
 
+/// An indexed agent found by discovery search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiscoveredAgent {
+    pub chain_id: i64,
+    /// ERC-8004 token ID
+    pub agent_id: String,
+    /// Latest `name` metadata set on chain
+    pub name: Option<String>,
+    /// Latest `description` metadata set on chain
+    pub description: Option<String>,
+    /// Latest registration URI
+    pub agent_uri: Option<String>,
+    /// Organizations following the agent
+    pub followers: i64,
+    /// Unix time of the agent's latest event
+    pub last_activity_at: i64,
+    /// Score the results are ordered by (depends on `sort`)
+    pub score: f64,
+}
+
+/// One page of discovery results
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AgentDiscoveryResponse {
+    pub data: Vec<DiscoveredAgent>,
+    /// Pass as `cursor` to fetch the next page; null on the last page
+    pub next_cursor: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,123 @@
+//! Dead Letter Queue DTOs
+//!
+//! Action jobs that failed after all retries, as seen by the organization
+//! that owns them (see [`shared::dlq`]).
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::{ActionJob, ActionType, DlqEntry};
+use utoipa::ToSchema;
+
+/// A failed action job in the dead letter queue
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DlqEntryResponse {
+    /// Entry ID (the failed job's ID)
+    pub id: String,
+    pub trigger_id: String,
+    /// Trigger action the job executed (absent on older jobs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<i32>,
+    /// `telegram`, `rest` or `mcp`
+    pub action_type: String,
+    /// Where the action was delivered: URL for REST, chat ID for Telegram,
+    /// server URL for MCP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub event_id: String,
+    pub correlation_id: String,
+    /// Error from the last attempt
+    pub last_error: String,
+    /// Attempts made before the job was dead-lettered
+    pub attempts: u32,
+    /// Times the job was already replayed from the dead letter queue
+    pub replay_count: u32,
+    /// When the job was created
+    #[serde(with = "shared::timestamp")]
+    pub created_at: DateTime<Utc>,
+    /// When the last attempt failed
+    #[serde(with = "shared::timestamp")]
+    pub failed_at: DateTime<Utc>,
+}
+
+impl From<DlqEntry> for DlqEntryResponse {
+    fn from(entry: DlqEntry) -> Self {
+        Self {
+            target: job_target(&entry.job),
+            id: entry.job.id,
+            trigger_id: entry.job.trigger_id,
+            action_id: entry.job.action_id,
+            action_type: entry.job.action_type.to_string(),
+            event_id: entry.job.event_id,
+            correlation_id: entry.job.correlation_id,
+            last_error: entry.error,
+            attempts: entry.attempts,
+            replay_count: entry.job.replay_count,
+            created_at: entry.job.created_at,
+            failed_at: entry.failed_at,
+        }
+    }
+}
+
+/// Destination of a job, from its action configuration
+fn job_target(job: &ActionJob) -> Option<String> {
+    let field = match job.action_type {
+        ActionType::Rest => "url",
+        ActionType::Telegram => "chat_id",
+        ActionType::Mcp => "server_url",
+    };
+    match job.config.get(field)? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        // Telegram chat IDs may be configured as numbers
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(action_type: ActionType, config: serde_json::Value) -> DlqEntry {
+        let job = ActionJob::new("trigger-1", "event-1", action_type, 1, config, json!({}))
+            .with_action_id(7);
+        DlqEntry::new(job, "HTTP 500".to_string(), 3)
+    }
+
+    #[test]
+    fn test_response_carries_failure_details() {
+        let entry = entry(
+            ActionType::Rest,
+            json!({"url": "https://example.com/hook", "method": "POST"}),
+        );
+        let id = entry.id().to_string();
+
+        let response = serde_json::to_value(DlqEntryResponse::from(entry)).unwrap();
+
+        assert_eq!(response["id"], id);
+        assert_eq!(response["action_type"], "rest");
+        assert_eq!(response["target"], "https://example.com/hook");
+        assert_eq!(response["last_error"], "HTTP 500");
+        assert_eq!(response["attempts"], 3);
+        assert_eq!(response["action_id"], 7);
+        assert!(response["failed_at"].is_string());
+    }
+
+    #[test]
+    fn test_target_per_action_type() {
+        let telegram = entry(ActionType::Telegram, json!({"chat_id": -100123}));
+        assert_eq!(job_target(&telegram.job).as_deref(), Some("-100123"));
+
+        let mcp = entry(
+            ActionType::Mcp,
+            json!({"server_url": "https://mcp.example.com", "tool_name": "t"}),
+        );
+        assert_eq!(
+            job_target(&mcp.job).as_deref(),
+            Some("https://mcp.example.com")
+        );
+
+        let missing = entry(ActionType::Rest, json!({}));
+        assert_eq!(job_target(&missing.job), None);
+    }
+}
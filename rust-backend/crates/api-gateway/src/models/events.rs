@@ -0,0 +1,77 @@
+//! Event ingest DTOs
+//!
+//! Pushed events use the columns of the `events` table, which are also the
+//! fields of [`shared::models::Event`] (minus `created_at`, set on insert).
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::triggers::validate_registry;
+
+/// An event pushed by an indexer to `POST /api/v1/events/ingest`
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({
+    "id": "84532-1000-0",
+    "chain_id": 84532,
+    "block_number": 1000,
+    "block_hash": "0xabc",
+    "transaction_hash": "0xdef",
+    "log_index": 0,
+    "registry": "reputation",
+    "event_type": "NewFeedback",
+    "agent_id": 42,
+    "timestamp": 1700000000,
+    "client_address": "0x123",
+    "score": 55
+}))]
+pub struct IngestEventRequest {
+    /// Event ID, by convention `{chain_id}-{block_number}-{log_index}`
+    #[validate(length(min = 1, max = 255))]
+    pub id: String,
+    #[validate(range(min = 1))]
+    pub chain_id: i32,
+    #[validate(range(min = 0))]
+    pub block_number: i64,
+    #[validate(length(min = 1, max = 100))]
+    pub block_hash: String,
+    #[validate(length(min = 1, max = 100))]
+    pub transaction_hash: String,
+    #[validate(range(min = 0))]
+    pub log_index: i32,
+    #[validate(custom(function = "validate_registry"))]
+    pub registry: String,
+    #[validate(length(min = 1, max = 100))]
+    pub event_type: String,
+    pub agent_id: Option<i64>,
+    /// Block timestamp (Unix seconds)
+    pub timestamp: i64,
+    // Identity Registry fields
+    pub owner: Option<String>,
+    pub token_uri: Option<String>,
+    pub metadata_key: Option<String>,
+    pub metadata_value: Option<String>,
+    // Reputation Registry fields
+    pub client_address: Option<String>,
+    pub feedback_index: Option<i64>,
+    pub score: Option<i32>,
+    pub tag1: Option<String>,
+    pub tag2: Option<String>,
+    pub file_uri: Option<String>,
+    pub file_hash: Option<String>,
+    // Validation Registry fields
+    pub validator_address: Option<String>,
+    pub request_hash: Option<String>,
+    pub response: Option<i32>,
+    pub response_uri: Option<String>,
+    pub response_hash: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Result of pushing an event
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestEventResponse {
+    pub event_id: String,
+    /// False if an event with this ID was already stored (nothing changed)
+    pub created: bool,
+}
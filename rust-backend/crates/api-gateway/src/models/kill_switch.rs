@@ -0,0 +1,208 @@
+//! Global Kill-Switch DTOs
+//!
+//! During an abuse incident an operator can cap or pause request acceptance
+//! for all callers at once, beyond the per-caller limits. The switch is stored
+//! in Redis and checked by the rate limiter on every request, so it applies to
+//! all gateway instances without a redeploy.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// `Retry-After` sent while paused when the request does not set one
+pub const DEFAULT_KILL_SWITCH_RETRY_AFTER_SECS: u32 = 60;
+
+/// What the kill-switch does to callers that are not allowlisted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KillSwitchMode {
+    /// Accept at most `requests_per_minute` across all callers, 429 beyond that
+    Cap,
+    /// Reject every request with 503 and `Retry-After`
+    Pause,
+}
+
+impl KillSwitchMode {
+    /// Name as used in requests, logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KillSwitchMode::Cap => "cap",
+            KillSwitchMode::Pause => "pause",
+        }
+    }
+}
+
+/// Request to engage (or replace) the global kill-switch
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_cap_limit"))]
+#[schema(example = json!({
+    "mode": "cap",
+    "requests_per_minute": 600,
+    "allow_organizations": ["org_123"],
+    "reason": "Credential stuffing from rotating IPs",
+    "expires_in_minutes": 60
+}))]
+pub struct SetKillSwitchRequest {
+    pub mode: KillSwitchMode,
+
+    /// Requests per minute accepted across all callers (required for `cap`)
+    #[validate(range(min = 1))]
+    pub requests_per_minute: Option<u32>,
+
+    /// Seconds clients are told to wait when paused (default: 60, max: 3600)
+    #[validate(range(min = 1, max = 3600))]
+    pub retry_after_seconds: Option<u32>,
+
+    /// Organizations that are not affected
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub allow_organizations: Vec<String>,
+
+    /// Client IPs that are not affected
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub allow_ips: Vec<String>,
+
+    /// Why the switch was engaged (logged and shown by `GET`)
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+
+    /// Release automatically after this many minutes (default: stays engaged
+    /// until released; max: 7 days)
+    #[validate(range(min = 1, max = 10080))]
+    pub expires_in_minutes: Option<u32>,
+}
+
+impl SetKillSwitchRequest {
+    /// State to store, engaged at `now`
+    pub fn into_state(self, now: DateTime<Utc>) -> KillSwitchState {
+        KillSwitchState {
+            mode: self.mode,
+            requests_per_minute: match self.mode {
+                KillSwitchMode::Cap => self.requests_per_minute,
+                KillSwitchMode::Pause => None,
+            },
+            retry_after_seconds: self
+                .retry_after_seconds
+                .unwrap_or(DEFAULT_KILL_SWITCH_RETRY_AFTER_SECS),
+            allow_organizations: self.allow_organizations,
+            allow_ips: self.allow_ips,
+            reason: self.reason,
+            engaged_at: now,
+            expires_at: self
+                .expires_in_minutes
+                .map(|minutes| now + Duration::minutes(i64::from(minutes))),
+        }
+    }
+}
+
+fn validate_cap_limit(req: &SetKillSwitchRequest) -> Result<(), ValidationError> {
+    if req.mode == KillSwitchMode::Cap && req.requests_per_minute.is_none() {
+        let mut error = ValidationError::new("missing_limit");
+        error.message = Some("cap mode requires requests_per_minute".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// The engaged kill-switch, as stored in Redis
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct KillSwitchState {
+    pub mode: KillSwitchMode,
+    /// Requests per minute accepted across all callers (`cap` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    /// Seconds clients are told to wait when paused
+    pub retry_after_seconds: u32,
+    pub allow_organizations: Vec<String>,
+    pub allow_ips: Vec<String>,
+    pub reason: String,
+    #[serde(with = "shared::timestamp")]
+    pub engaged_at: DateTime<Utc>,
+    /// When the switch releases itself
+    #[serde(
+        default,
+        with = "shared::timestamp::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl KillSwitchState {
+    /// Whether a caller is exempt from the switch
+    pub fn is_allowlisted(&self, organization_id: Option<&str>, ip: &str) -> bool {
+        organization_id.is_some_and(|org| self.allow_organizations.iter().any(|a| a == org))
+            || self.allow_ips.iter().any(|a| a == ip)
+    }
+}
+
+/// Current kill-switch status
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KillSwitchStatusResponse {
+    pub active: bool,
+    /// The engaged switch, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kill_switch: Option<KillSwitchState>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(mode: KillSwitchMode, requests_per_minute: Option<u32>) -> SetKillSwitchRequest {
+        SetKillSwitchRequest {
+            mode,
+            requests_per_minute,
+            retry_after_seconds: None,
+            allow_organizations: vec!["org_ops".to_string()],
+            allow_ips: vec!["10.0.0.9".to_string()],
+            reason: "abuse".to_string(),
+            expires_in_minutes: Some(30),
+        }
+    }
+
+    #[test]
+    fn test_cap_requires_limit() {
+        assert!(request(KillSwitchMode::Cap, None).validate().is_err());
+        assert!(request(KillSwitchMode::Cap, Some(0)).validate().is_err());
+        assert!(request(KillSwitchMode::Cap, Some(600)).validate().is_ok());
+        assert!(request(KillSwitchMode::Pause, None).validate().is_ok());
+    }
+
+    #[test]
+    fn test_into_state_defaults_and_expiry() {
+        let now = Utc::now();
+        let state = request(KillSwitchMode::Pause, Some(600)).into_state(now);
+
+        assert_eq!(state.requests_per_minute, None);
+        assert_eq!(
+            state.retry_after_seconds,
+            DEFAULT_KILL_SWITCH_RETRY_AFTER_SECS
+        );
+        assert_eq!(state.engaged_at, now);
+        assert_eq!(state.expires_at, Some(now + Duration::minutes(30)));
+    }
+
+    #[test]
+    fn test_allowlist_matches_organization_or_ip() {
+        let state = request(KillSwitchMode::Cap, Some(10)).into_state(Utc::now());
+
+        assert!(state.is_allowlisted(Some("org_ops"), "203.0.113.1"));
+        assert!(state.is_allowlisted(None, "10.0.0.9"));
+        assert!(!state.is_allowlisted(Some("org_other"), "203.0.113.1"));
+        assert!(!state.is_allowlisted(None, "203.0.113.1"));
+    }
+
+    #[test]
+    fn test_state_round_trips_through_json() {
+        let state = request(KillSwitchMode::Cap, Some(10)).into_state(Utc::now());
+        let json = serde_json::to_string(&state).unwrap();
+        let parsed: KillSwitchState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.mode, KillSwitchMode::Cap);
+        assert_eq!(parsed.requests_per_minute, Some(10));
+        assert_eq!(parsed.allow_organizations, state.allow_organizations);
+        assert!(parsed.expires_at.is_some());
+    }
+}
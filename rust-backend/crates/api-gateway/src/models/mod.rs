@@ -1,31 +1,49 @@
 //! Data Transfer Objects (DTOs) for API requests and responses
 
 pub mod a2a;
+pub mod action_webhooks;
 pub mod actions;
 pub mod agent_follows;
 pub mod api_keys;
 pub mod auth;
 pub mod billing;
+pub mod cache_admin;
 pub mod circuit_breaker;
 pub mod common;
 pub mod conditions;
 pub mod discovery;
+pub mod dlq;
+pub mod events;
+pub mod kill_switch;
 pub mod oauth;
 pub mod organizations;
+pub mod rate_limits;
+pub mod signing_keys;
+pub mod trigger_export;
 pub mod triggers;
 pub mod wallet;
+pub mod webhooks;
 
 // Re-exports for commonly used types
+pub use action_webhooks::*;
 pub use actions::*;
 pub use agent_follows::*;
 pub use api_keys::*;
 pub use auth::*;
+pub use cache_admin::*;
 pub use circuit_breaker::*;
 pub use common::*;
 pub use conditions::*;
+pub use dlq::*;
+pub use events::*;
+pub use kill_switch::*;
 pub use oauth::*;
 pub use organizations::*;
+pub use rate_limits::*;
+pub use signing_keys::*;
+pub use trigger_export::*;
 pub use triggers::*;
+pub use webhooks::*;
 
 // Billing and wallet types are accessed via their modules
 // (e.g., crate::models::billing::CreditBalanceResponse)
@@ -67,6 +67,39 @@ pub struct CreateOAuthClientRequest {
     /// Whether this is a trusted first-party application
     #[serde(default)]
     pub is_trusted: bool,
+
+    /// Whether this is a public client (native or browser app that cannot keep
+    /// a secret). Public clients get no secret and must use PKCE.
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+/// Authorization request (RFC 6749 Section 4.1.1, RFC 7636 Section 4.3)
+///
+/// Sent by the consent page once the signed-in user approves the client.
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(example = json!({"response_type": "code", "client_id": "oauth_client_abc123", "redirect_uri": "https://example.com/callback", "scope": "read:triggers", "state": "xyz", "code_challenge": "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM", "code_challenge_method": "S256"}))]
+pub struct AuthorizeRequest {
+    /// Must be "code"
+    pub response_type: String,
+
+    /// Client requesting access
+    pub client_id: String,
+
+    /// One of the client's registered redirect URIs
+    pub redirect_uri: String,
+
+    /// Requested scopes (space-separated, defaults to all client scopes)
+    pub scope: Option<String>,
+
+    /// Opaque value returned to the client unchanged
+    pub state: Option<String>,
+
+    /// PKCE code challenge (required for public clients)
+    pub code_challenge: Option<String>,
+
+    /// PKCE challenge method: "S256" or "plain" (default)
+    pub code_challenge_method: Option<String>,
 }
 
 /// OAuth token request (RFC 6749 Section 4.1.3)
@@ -79,7 +112,7 @@ pub struct TokenRequest {
     /// Client ID (required)
     pub client_id: String,
 
-    /// Client secret (required for confidential clients)
+    /// Client secret (required for confidential clients, omitted by public clients)
     pub client_secret: Option<String>,
 
     /// Authorization code (required for authorization_code grant)
@@ -94,7 +127,7 @@ pub struct TokenRequest {
     /// Scope (optional, space-separated)
     pub scope: Option<String>,
 
-    /// PKCE code verifier (optional)
+    /// PKCE code verifier (required when the authorization request had a code_challenge)
     pub code_verifier: Option<String>,
 }
 
@@ -108,8 +141,9 @@ pub struct CreateOAuthClientResponse {
     /// The client ID (public identifier)
     pub client_id: String,
 
-    /// The client secret (SHOWN ONLY ONCE!)
-    pub client_secret: String,
+    /// The client secret (SHOWN ONLY ONCE!, absent for public clients)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
 
     /// Display name
     pub client_name: String,
@@ -126,7 +160,11 @@ pub struct CreateOAuthClientResponse {
     /// Whether this is a trusted application
     pub is_trusted: bool,
 
+    /// Whether this is a public client (uses PKCE instead of a secret)
+    pub is_public: bool,
+
     /// Creation timestamp
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -154,10 +192,15 @@ pub struct OAuthClientResponse {
     /// Whether this is a trusted application
     pub is_trusted: bool,
 
+    /// Whether this is a public client (uses PKCE instead of a secret)
+    pub is_public: bool,
+
     /// Creation timestamp
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 
     /// Last updated timestamp
+    #[serde(with = "shared::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -171,12 +214,27 @@ impl From<OAuthClient> for OAuthClientResponse {
             scopes: client.scopes,
             grant_types: client.grant_types,
             is_trusted: client.is_trusted,
+            is_public: client.is_public,
             created_at: client.created_at,
             updated_at: client.updated_at,
         }
     }
 }
 
+/// Authorization response (RFC 6749 Section 4.1.2)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorizeResponse {
+    /// Authorization code (single use, valid for 5 minutes)
+    pub code: String,
+
+    /// State from the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    /// Redirect URI with `code` and `state` appended, for the consent page to navigate to
+    pub redirect_to: String,
+}
+
 /// OAuth token response (RFC 6749 Section 5.1)
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TokenResponse {
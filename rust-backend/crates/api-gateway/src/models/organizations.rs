@@ -132,7 +132,9 @@ pub struct OrganizationResponse {
     pub owner_id: String,
     pub plan: String,
     pub is_personal: bool,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -185,6 +187,7 @@ pub struct MemberResponse {
     pub username: String,
     pub email: String,
     pub role: String,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
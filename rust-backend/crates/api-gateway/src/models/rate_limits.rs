@@ -0,0 +1,156 @@
+//! Organization Rate Limit DTOs
+//!
+//! Per-organization overrides of the plan-based rate limits, one entry per
+//! query tier. Tiers without an entry keep using the organization's plan limit
+//! and the default 1-hour window.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// Minimum override window (one sliding-window bucket)
+pub const MIN_RATE_LIMIT_WINDOW_SECS: i32 = 60;
+
+/// Maximum override window (24 hours)
+pub const MAX_RATE_LIMIT_WINDOW_SECS: i32 = 86_400;
+
+/// Rate limit override for a single query tier
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"tier": 0, "limit": 5000, "window_seconds": 3600}))]
+pub struct RateLimitTierConfig {
+    /// Query tier (0-3)
+    #[validate(range(min = 0, max = 3))]
+    pub tier: i16,
+
+    /// Maximum request cost allowed per window
+    #[validate(range(min = 1, max = 10_000_000))]
+    pub limit: i32,
+
+    /// Sliding window size in seconds (multiple of 60, up to 24 hours)
+    #[validate(custom(function = "validate_window_seconds"))]
+    pub window_seconds: i32,
+}
+
+/// Request to replace an organization's rate limit overrides
+///
+/// Replaces the full set: tiers left out fall back to the plan defaults, and
+/// an empty list removes all overrides.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"limits": [{"tier": 0, "limit": 5000, "window_seconds": 3600}]}))]
+pub struct SetOrganizationRateLimitsRequest {
+    #[validate(length(max = 4), nested)]
+    #[validate(custom(function = "validate_unique_tiers"))]
+    pub limits: Vec<RateLimitTierConfig>,
+}
+
+/// Rate limit override as stored
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RateLimitTierResponse {
+    pub tier: i16,
+    pub limit: i32,
+    pub window_seconds: i32,
+    #[serde(with = "shared::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<shared::models::OrganizationRateLimit> for RateLimitTierResponse {
+    fn from(row: shared::models::OrganizationRateLimit) -> Self {
+        Self {
+            tier: row.tier,
+            limit: row.request_limit,
+            window_seconds: row.window_seconds,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// An organization's rate limit overrides
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationRateLimitsResponse {
+    pub organization_id: String,
+    /// Overrides by tier (tiers not listed use the plan defaults)
+    pub limits: Vec<RateLimitTierResponse>,
+}
+
+fn validate_window_seconds(window: i32) -> Result<(), ValidationError> {
+    if !(MIN_RATE_LIMIT_WINDOW_SECS..=MAX_RATE_LIMIT_WINDOW_SECS).contains(&window)
+        || window % MIN_RATE_LIMIT_WINDOW_SECS != 0
+    {
+        let mut error = ValidationError::new("invalid_window");
+        error.message = Some("window_seconds must be a multiple of 60 between 60 and 86400".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+fn validate_unique_tiers(limits: &[RateLimitTierConfig]) -> Result<(), ValidationError> {
+    let mut seen = [false; 4];
+    for config in limits {
+        // Out-of-range tiers are reported by the per-entry range check
+        let Some(slot) = usize::try_from(config.tier)
+            .ok()
+            .and_then(|t| seen.get_mut(t))
+        else {
+            continue;
+        };
+        if *slot {
+            let mut error = ValidationError::new("duplicate_tier");
+            error.message = Some(format!("tier {} is listed more than once", config.tier).into());
+            return Err(error);
+        }
+        *slot = true;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(tier: i16, limit: i32, window_seconds: i32) -> RateLimitTierConfig {
+        RateLimitTierConfig {
+            tier,
+            limit,
+            window_seconds,
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let req = SetOrganizationRateLimitsRequest {
+            limits: vec![config(0, 5000, 3600), config(3, 100, 60)],
+        };
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_request_is_valid() {
+        let req = SetOrganizationRateLimitsRequest { limits: vec![] };
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_tier() {
+        let req = SetOrganizationRateLimitsRequest {
+            limits: vec![config(4, 100, 3600)],
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_window_must_be_whole_minutes() {
+        assert!(config(0, 100, 90).validate().is_err());
+        assert!(config(0, 100, 30).validate().is_err());
+        assert!(config(0, 100, 86_460).validate().is_err());
+        assert!(config(0, 100, 86_400).validate().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_tiers_rejected() {
+        let req = SetOrganizationRateLimitsRequest {
+            limits: vec![config(1, 100, 3600), config(1, 200, 3600)],
+        };
+        assert!(req.validate().is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Organization Signing Key DTOs
+//!
+//! Each organization can hold its own key for signing outbound deliveries
+//! (REST actions and action result webhooks), so a leaked key only affects
+//! that organization. Secrets are generated by the server and only returned
+//! when a key is created or rotated in.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Grace window applied when a rotation does not specify one
+pub const DEFAULT_SIGNING_KEY_GRACE_HOURS: u32 = 24;
+
+/// Longest grace window a rotation may request (7 days)
+pub const MAX_SIGNING_KEY_GRACE_HOURS: u32 = 168;
+
+/// Request to rotate the organization's signing key
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"grace_period_hours": 24}))]
+pub struct RotateSigningKeyRequest {
+    /// How long the previous key keeps signing alongside the new one
+    /// (default: 24, max: 168; 0 revokes it immediately, e.g. after a leak)
+    #[validate(range(max = 168))]
+    pub grace_period_hours: Option<u32>,
+}
+
+impl RotateSigningKeyRequest {
+    /// Grace window in seconds
+    pub fn grace_period_secs(&self) -> i64 {
+        i64::from(
+            self.grace_period_hours
+                .unwrap_or(DEFAULT_SIGNING_KEY_GRACE_HOURS),
+        ) * 3600
+    }
+}
+
+/// Lifecycle state of a signing key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningKeyStatus {
+    /// The key new deliveries are signed with
+    Current,
+    /// Rotated out, still signing alongside the current key until `expires_at`
+    Grace,
+    /// Rotated out and no longer used
+    Expired,
+}
+
+impl SigningKeyStatus {
+    /// Status of a key at `now`
+    pub fn of(key: &shared::models::OrganizationSigningKey, now: DateTime<Utc>) -> Self {
+        match (key.retired_at, key.expires_at) {
+            (None, _) => Self::Current,
+            (Some(_), Some(expires_at)) if expires_at > now => Self::Grace,
+            (Some(_), _) => Self::Expired,
+        }
+    }
+}
+
+/// An organization signing key
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SigningKeyResponse {
+    pub id: String,
+    pub organization_id: String,
+    /// First characters of the secret, to tell keys apart
+    pub key_prefix: String,
+    pub status: SigningKeyStatus,
+    /// Secret, only present when the key was just created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_by: Option<String>,
+    #[serde(with = "shared::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(
+        with = "shared::timestamp::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retired_at: Option<DateTime<Utc>>,
+    /// When a rotated-out key stops signing
+    #[serde(
+        with = "shared::timestamp::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl SigningKeyResponse {
+    /// Build a response, revealing the secret only when asked to
+    pub fn new(key: shared::models::OrganizationSigningKey, reveal_secret: bool) -> Self {
+        Self {
+            status: SigningKeyStatus::of(&key, Utc::now()),
+            id: key.id,
+            organization_id: key.organization_id,
+            key_prefix: key.key_prefix,
+            secret: reveal_secret.then_some(key.secret),
+            created_by: key.created_by,
+            created_at: key.created_at,
+            retired_at: key.retired_at,
+            expires_at: key.expires_at,
+        }
+    }
+}
+
+/// Result of a rotation: the new key (with its secret) and the retired one
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateSigningKeyResponse {
+    pub current: SigningKeyResponse,
+    pub previous: SigningKeyResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use shared::models::OrganizationSigningKey;
+
+    fn key(
+        retired_at: Option<DateTime<Utc>>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> OrganizationSigningKey {
+        OrganizationSigningKey {
+            id: "key-1".to_string(),
+            organization_id: "org-1".to_string(),
+            secret: "whsec_0123456789abcdef".to_string(),
+            key_prefix: "whsec_01234567".to_string(),
+            created_by: Some("user-1".to_string()),
+            created_at: Utc::now() - Duration::days(30),
+            retired_at,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_status_through_rotation_grace_window() {
+        let rotated_at = Utc::now();
+        let retired = key(Some(rotated_at), Some(rotated_at + Duration::hours(24)));
+
+        assert_eq!(
+            SigningKeyStatus::of(&key(None, None), rotated_at),
+            SigningKeyStatus::Current
+        );
+        assert_eq!(
+            SigningKeyStatus::of(&retired, rotated_at + Duration::hours(23)),
+            SigningKeyStatus::Grace
+        );
+        assert_eq!(
+            SigningKeyStatus::of(&retired, rotated_at + Duration::hours(24)),
+            SigningKeyStatus::Expired
+        );
+
+        // A zero grace window revokes the key at once
+        let revoked = key(Some(rotated_at), Some(rotated_at));
+        assert_eq!(
+            SigningKeyStatus::of(&revoked, rotated_at),
+            SigningKeyStatus::Expired
+        );
+    }
+
+    #[test]
+    fn test_secret_only_revealed_on_request() {
+        let hidden = serde_json::to_value(SigningKeyResponse::new(key(None, None), false)).unwrap();
+        assert!(hidden.get("secret").is_none());
+        assert_eq!(hidden["status"], "current");
+        assert_eq!(hidden["key_prefix"], "whsec_01234567");
+        assert!(hidden.get("expires_at").is_none());
+
+        let shown = serde_json::to_value(SigningKeyResponse::new(key(None, None), true)).unwrap();
+        assert_eq!(shown["secret"], "whsec_0123456789abcdef");
+    }
+
+    #[test]
+    fn test_rotate_request_grace_period() {
+        assert_eq!(
+            RotateSigningKeyRequest::default().grace_period_secs(),
+            24 * 3600
+        );
+
+        let revoke = RotateSigningKeyRequest {
+            grace_period_hours: Some(0),
+        };
+        assert_eq!(revoke.grace_period_secs(), 0);
+        assert!(revoke.validate().is_ok());
+
+        let too_long = RotateSigningKeyRequest {
+            grace_period_hours: Some(MAX_SIGNING_KEY_GRACE_HOURS + 1),
+        };
+        assert!(too_long.validate().is_err());
+    }
+}
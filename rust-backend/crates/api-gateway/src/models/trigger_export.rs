@@ -0,0 +1,258 @@
+//! Trigger Export/Import DTOs
+//!
+//! Portable JSON bundle of an organization's triggers (with conditions and
+//! actions). Database ids, owners and timestamps are stripped on export so the
+//! bundle can be imported into any organization.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::{CreateActionRequest, CreateConditionRequest, CreateTriggerRequest};
+
+/// Current bundle format version
+pub const TRIGGER_BUNDLE_VERSION: u32 = 1;
+
+/// Maximum number of triggers accepted in a single import
+pub const MAX_TRIGGERS_PER_IMPORT: usize = 100;
+
+/// Portable bundle of trigger configurations
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TriggerExportBundle {
+    /// Bundle format version
+    pub version: u32,
+    /// When the bundle was exported
+    #[serde(with = "shared::timestamp")]
+    pub exported_at: DateTime<Utc>,
+    /// Organization the bundle was exported from (used to remap references)
+    #[serde(default)]
+    pub source_organization_id: Option<String>,
+    pub triggers: Vec<ExportedTrigger>,
+    /// Triggers left out of the export, with the reason
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded: Vec<ExcludedTrigger>,
+}
+
+/// Trigger configuration without ids or ownership
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedTrigger {
+    pub name: String,
+    pub description: Option<String>,
+    /// Chain ID to match, or null for wildcard (matches all chains)
+    pub chain_id: Option<i32>,
+    pub registry: String,
+    pub enabled: bool,
+    pub is_stateful: bool,
+    /// Minimum seconds between two fires (0 = no cooldown)
+    #[serde(default)]
+    pub cooldown_secs: i32,
+    /// Weekly window in which the trigger may fire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub schedule: Option<shared::TriggerSchedule>,
+    #[serde(default)]
+    pub conditions: Vec<ExportedCondition>,
+    #[serde(default)]
+    pub actions: Vec<ExportedAction>,
+}
+
+/// Condition configuration without ids
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedCondition {
+    pub condition_type: String,
+    pub field: String,
+    pub operator: String,
+    pub value: String,
+    pub config: Option<serde_json::Value>,
+}
+
+/// Action configuration without ids
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportedAction {
+    pub action_type: String,
+    pub priority: i32,
+    pub config: serde_json::Value,
+}
+
+/// Trigger that was not included in an export
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExcludedTrigger {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Outcome of importing a single trigger
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TriggerImportResult {
+    /// Position of the trigger in the submitted bundle
+    pub index: usize,
+    pub name: String,
+    pub success: bool,
+    /// ID of the created trigger (on success, not set in a dry run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Non-fatal adjustments made during import (e.g. remapped references)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Summary of a bulk import
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TriggerImportReport {
+    /// Nothing was created; the counts are what an import would do
+    pub dry_run: bool,
+    pub imported: usize,
+    pub failed: usize,
+    pub results: Vec<TriggerImportResult>,
+}
+
+/// Options for importing a bundle
+#[derive(Debug, Default, Deserialize)]
+pub struct TriggerImportQuery {
+    /// Validate and create the triggers, then roll everything back
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ExportedTrigger {
+    /// Validate the trigger, its conditions and actions with the same rules
+    /// as the individual create endpoints
+    ///
+    /// Returns a human-readable description of the first problem found.
+    pub fn validate_for_import(&self) -> Result<(), String> {
+        let trigger = CreateTriggerRequest {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            chain_id: self.chain_id,
+            registry: self.registry.clone(),
+            enabled: Some(self.enabled),
+            is_stateful: Some(self.is_stateful),
+            cooldown_secs: Some(self.cooldown_secs),
+            schedule: self.schedule.clone(),
+        };
+        trigger
+            .validate()
+            .map_err(|e| format!("Invalid trigger: {}", e))?;
+
+        for (idx, condition) in self.conditions.iter().enumerate() {
+            let request = CreateConditionRequest {
+                condition_type: condition.condition_type.clone(),
+                field: condition.field.clone(),
+                operator: condition.operator.clone(),
+                value: condition.value.clone(),
+                config: condition.config.clone(),
+            };
+            request
+                .validate()
+                .map_err(|e| format!("Invalid condition #{}: {}", idx, e))?;
+        }
+
+        for (idx, action) in self.actions.iter().enumerate() {
+            let request = CreateActionRequest {
+                action_type: action.action_type.clone(),
+                priority: Some(action.priority),
+                config: action.config.clone(),
+            };
+            request
+                .validate()
+                .map_err(|e| format!("Invalid action #{}: {}", idx, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_trigger() -> ExportedTrigger {
+        ExportedTrigger {
+            name: "Low Score Alert".to_string(),
+            description: Some("Alert when score drops".to_string()),
+            chain_id: Some(84532),
+            registry: "reputation".to_string(),
+            enabled: true,
+            is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
+            conditions: vec![ExportedCondition {
+                condition_type: "score_threshold".to_string(),
+                field: "score".to_string(),
+                operator: "<".to_string(),
+                value: "60".to_string(),
+                config: None,
+            }],
+            actions: vec![ExportedAction {
+                action_type: "telegram".to_string(),
+                priority: 1,
+                config: serde_json::json!({"chat_id": "123", "message_template": "Hi"}),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_validate_for_import_valid() {
+        assert!(sample_trigger().validate_for_import().is_ok());
+    }
+
+    #[test]
+    fn test_validate_for_import_invalid_registry() {
+        let mut trigger = sample_trigger();
+        trigger.registry = "unknown".to_string();
+        let err = trigger.validate_for_import().unwrap_err();
+        assert!(err.starts_with("Invalid trigger"));
+    }
+
+    #[test]
+    fn test_validate_for_import_invalid_condition() {
+        let mut trigger = sample_trigger();
+        trigger.conditions[0].operator = String::new();
+        let err = trigger.validate_for_import().unwrap_err();
+        assert!(err.contains("condition #0"));
+    }
+
+    #[test]
+    fn test_validate_for_import_invalid_action() {
+        let mut trigger = sample_trigger();
+        trigger.actions[0].action_type = "email".to_string();
+        let err = trigger.validate_for_import().unwrap_err();
+        assert!(err.contains("action #0"));
+    }
+
+    #[test]
+    fn test_bundle_deserialize_minimal() {
+        let json = serde_json::json!({
+            "version": 1,
+            "exported_at": "2026-01-01T00:00:00Z",
+            "triggers": [{
+                "name": "T",
+                "description": null,
+                "chain_id": null,
+                "registry": "identity",
+                "enabled": true,
+                "is_stateful": false
+            }]
+        });
+
+        let bundle: TriggerExportBundle = serde_json::from_value(json).unwrap();
+        assert_eq!(bundle.version, TRIGGER_BUNDLE_VERSION);
+        assert!(bundle.source_organization_id.is_none());
+        assert!(bundle.triggers[0].conditions.is_empty());
+        assert!(bundle.triggers[0].actions.is_empty());
+        assert!(bundle.excluded.is_empty());
+    }
+
+    #[test]
+    fn test_import_query_dry_run_defaults_to_false() {
+        let query = actix_web::web::Query::<TriggerImportQuery>::from_query("").unwrap();
+        assert!(!query.dry_run);
+
+        let query =
+            actix_web::web::Query::<TriggerImportQuery>::from_query("dry_run=true").unwrap();
+        assert!(query.dry_run);
+    }
+}
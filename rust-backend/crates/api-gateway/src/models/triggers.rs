@@ -1,7 +1,8 @@
 //! Trigger DTOs
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use shared::TriggerSchedule;
 use utoipa::ToSchema;
 use validator::Validate;
 
@@ -24,6 +25,15 @@ pub struct CreateTriggerRequest {
     pub enabled: Option<bool>,
 
     pub is_stateful: Option<bool>,
+
+    /// Minimum seconds between two fires of the trigger (0 = no cooldown)
+    #[validate(range(min = 0))]
+    pub cooldown_secs: Option<i32>,
+
+    /// Weekly window in which the trigger may fire (omit to fire at any time)
+    #[validate(custom(function = "validate_schedule"))]
+    #[schema(value_type = Option<Object>, example = json!({"days": ["mon", "tue", "wed", "thu", "fri"], "start": "09:00", "end": "17:00", "timezone": "Europe/Rome"}))]
+    pub schedule: Option<TriggerSchedule>,
 }
 
 /// Request to update a trigger
@@ -44,6 +54,16 @@ pub struct UpdateTriggerRequest {
     pub enabled: Option<bool>,
 
     pub is_stateful: Option<bool>,
+
+    /// Minimum seconds between two fires of the trigger (0 = no cooldown)
+    #[validate(range(min = 0))]
+    pub cooldown_secs: Option<i32>,
+
+    /// New schedule; `null` removes the schedule, omit to keep it
+    #[serde(default, deserialize_with = "deserialize_explicit_null")]
+    #[validate(custom(function = "validate_schedule"))]
+    #[schema(value_type = Option<Object>)]
+    pub schedule: Option<Option<TriggerSchedule>>,
 }
 
 /// Trigger response (basic info without conditions/actions)
@@ -59,8 +79,19 @@ pub struct TriggerResponse {
     pub registry: String,
     pub enabled: bool,
     pub is_stateful: bool,
+    /// Minimum seconds between two fires (0 = no cooldown)
+    pub cooldown_secs: i32,
+    /// Weekly window in which the trigger may fire (null = any time)
+    #[schema(value_type = Option<Object>)]
+    pub schedule: Option<TriggerSchedule>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp")]
     pub updated_at: DateTime<Utc>,
+    /// When the trigger was deleted (only listed with `include_deleted=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "shared::timestamp::option")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<shared::models::Trigger> for TriggerResponse {
@@ -75,12 +106,23 @@ impl From<shared::models::Trigger> for TriggerResponse {
             registry: trigger.registry,
             enabled: trigger.enabled,
             is_stateful: trigger.is_stateful,
+            cooldown_secs: trigger.cooldown_secs,
+            schedule: trigger.schedule,
             created_at: trigger.created_at,
             updated_at: trigger.updated_at,
+            deleted_at: trigger.deleted_at,
         }
     }
 }
 
+/// Filters for listing triggers
+#[derive(Debug, Default, Deserialize)]
+pub struct TriggerListQuery {
+    /// Also list soft-deleted triggers (organization admins only)
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
 /// Detailed trigger response with conditions and actions
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TriggerDetailResponse {
@@ -100,6 +142,7 @@ pub struct ConditionResponse {
     pub operator: String,
     pub value: String,
     pub config: Option<serde_json::Value>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -131,6 +174,11 @@ pub struct ActionResponse {
     pub action_type: String,
     pub priority: i32,
     pub config: serde_json::Value,
+    /// Webhook verification state (`not_required`, `unverified`, `verified`, `failed`)
+    pub verification_status: String,
+    #[serde(with = "shared::timestamp::option")]
+    pub verified_at: Option<DateTime<Utc>>,
+    #[serde(with = "shared::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -142,13 +190,89 @@ impl From<shared::models::TriggerAction> for ActionResponse {
             action_type: action.action_type,
             priority: action.priority,
             config: action.config,
+            verification_status: action.verification_status,
+            verified_at: action.verified_at,
             created_at: action.created_at,
         }
     }
 }
 
+/// Request to fire a trigger manually
+///
+/// The fired actions see `event_data` as the matched event, so templates can
+/// be exercised without waiting for an on-chain event.
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({"event_data": {"agent_id": 42, "score": 55}}))]
+pub struct FireTriggerRequest {
+    /// Event fields exposed to action templates (must be a JSON object)
+    #[validate(custom(function = "validate_event_data"))]
+    pub event_data: Option<serde_json::Value>,
+}
+
+/// Result of a manual trigger fire
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FireTriggerResponse {
+    pub trigger_id: String,
+    /// Synthetic event ID recorded on the jobs and their action results
+    pub event_id: String,
+    pub correlation_id: String,
+    /// One job per trigger action
+    pub jobs: Vec<FiredJobResponse>,
+}
+
+/// Action job enqueued by a manual fire
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FiredJobResponse {
+    pub job_id: String,
+    pub action_id: i32,
+    pub action_type: String,
+}
+
+/// Stateful evaluator state for a trigger
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TriggerStateResponse {
+    pub trigger_id: String,
+    pub is_stateful: bool,
+    /// Evaluator state (EMA values, rate counters), or null before the first evaluation
+    pub state: Option<serde_json::Value>,
+    #[serde(with = "shared::timestamp::option")]
+    pub last_updated: Option<DateTime<Utc>>,
+    /// When the trigger last fired (only tracked for triggers with a cooldown)
+    #[serde(with = "shared::timestamp::option")]
+    pub last_fired_at: Option<DateTime<Utc>>,
+}
+
+/// Custom validator for manual fire event data
+fn validate_event_data(event_data: &serde_json::Value) -> Result<(), validator::ValidationError> {
+    if !event_data.is_object() {
+        return Err(validator::ValidationError::new("event_data_not_object"));
+    }
+    Ok(())
+}
+
+/// Custom validator for trigger schedules
+fn validate_schedule(schedule: &TriggerSchedule) -> Result<(), validator::ValidationError> {
+    schedule.validate().map_err(|e| {
+        let mut error = validator::ValidationError::new("invalid_schedule");
+        error.message = Some(e.to_string().into());
+        error
+    })
+}
+
+/// Deserialize a present field as `Some`, so an explicit `null` becomes
+/// `Some(None)` while a missing field stays `None` (via `#[serde(default)]`)
+pub(crate) fn deserialize_explicit_null<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 /// Custom validator for registry field
-fn validate_registry(registry: &str) -> Result<(), validator::ValidationError> {
+pub(crate) fn validate_registry(registry: &str) -> Result<(), validator::ValidationError> {
     if !["identity", "reputation", "validation"].contains(&registry) {
         return Err(validator::ValidationError::new("invalid_registry"));
     }
@@ -173,6 +297,8 @@ mod tests {
             registry: "reputation".to_string(),
             enabled: Some(true),
             is_stateful: Some(false),
+            cooldown_secs: None,
+            schedule: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -186,6 +312,8 @@ mod tests {
             registry: "identity".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -200,6 +328,8 @@ mod tests {
             registry: "identity".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -213,6 +343,8 @@ mod tests {
             registry: "reputation".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         let result = req.validate();
         assert!(result.is_err());
@@ -229,6 +361,8 @@ mod tests {
             registry: "reputation".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         let result = req.validate();
         assert!(result.is_err());
@@ -245,6 +379,8 @@ mod tests {
             registry: "reputation".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         let result = req.validate();
         assert!(result.is_err());
@@ -261,6 +397,8 @@ mod tests {
             registry: "invalid".to_string(),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         let result = req.validate();
         assert!(result.is_err());
@@ -278,6 +416,8 @@ mod tests {
                 registry: registry.to_string(),
                 enabled: None,
                 is_stateful: None,
+                cooldown_secs: None,
+                schedule: None,
             };
             assert!(
                 req.validate().is_ok(),
@@ -287,6 +427,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_trigger_request_cooldown() {
+        let mut req = CreateTriggerRequest {
+            name: "Test".to_string(),
+            description: None,
+            chain_id: Some(1),
+            registry: "reputation".to_string(),
+            enabled: None,
+            is_stateful: None,
+            cooldown_secs: Some(0),
+            schedule: None,
+        };
+        assert!(req.validate().is_ok());
+
+        req.cooldown_secs = Some(300);
+        assert!(req.validate().is_ok());
+
+        req.cooldown_secs = Some(-1);
+        let errors = req.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("cooldown_secs"));
+    }
+
     // ========================================================================
     // UpdateTriggerRequest validation tests
     // ========================================================================
@@ -300,6 +462,8 @@ mod tests {
             registry: Some("validation".to_string()),
             enabled: Some(false),
             is_stateful: Some(true),
+            cooldown_secs: None,
+            schedule: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -313,6 +477,8 @@ mod tests {
             registry: None,
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -326,11 +492,77 @@ mod tests {
             registry: Some("invalid".to_string()),
             enabled: None,
             is_stateful: None,
+            cooldown_secs: None,
+            schedule: None,
         };
         let result = req.validate();
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_update_trigger_request_negative_cooldown() {
+        let req = UpdateTriggerRequest {
+            name: None,
+            description: None,
+            chain_id: None,
+            registry: None,
+            enabled: None,
+            is_stateful: None,
+            cooldown_secs: Some(-30),
+            schedule: None,
+        };
+        let errors = req.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("cooldown_secs"));
+    }
+
+    #[test]
+    fn test_create_trigger_request_schedule_is_validated() {
+        let mut req: CreateTriggerRequest = serde_json::from_value(serde_json::json!({
+            "name": "Business hours alert",
+            "registry": "reputation",
+            "schedule": {
+                "days": ["mon", "tue", "wed", "thu", "fri"],
+                "start": "09:00",
+                "end": "17:00",
+                "timezone": "Europe/Rome"
+            }
+        }))
+        .unwrap();
+        assert!(req.validate().is_ok());
+
+        req.schedule.as_mut().unwrap().timezone = "Europe/Atlantis".to_string();
+        let errors = req.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("schedule"));
+
+        req.schedule.as_mut().unwrap().timezone = "UTC".to_string();
+        req.schedule.as_mut().unwrap().end = "5pm".to_string();
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_trigger_request_schedule_null_removes_it() {
+        let req: UpdateTriggerRequest = serde_json::from_value(serde_json::json!({
+            "schedule": null
+        }))
+        .unwrap();
+        assert_eq!(req.schedule, Some(None));
+        assert!(req.validate().is_ok());
+
+        let req: UpdateTriggerRequest =
+            serde_json::from_value(serde_json::json!({"name": "Renamed"})).unwrap();
+        assert_eq!(req.schedule, None);
+    }
+
+    #[test]
+    fn test_update_trigger_request_invalid_schedule() {
+        let req: UpdateTriggerRequest = serde_json::from_value(serde_json::json!({
+            "schedule": {"days": ["someday"], "start": "09:00", "end": "17:00", "timezone": "UTC"}
+        }))
+        .unwrap();
+        let errors = req.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("schedule"));
+    }
+
     // ========================================================================
     // Serialization tests
     // ========================================================================
@@ -347,8 +579,11 @@ mod tests {
             registry: "reputation".to_string(),
             enabled: true,
             is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            deleted_at: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -356,6 +591,46 @@ mod tests {
         assert!(json.contains("org-789"));
         assert!(json.contains("Test Trigger"));
         assert!(json.contains("reputation"));
+        assert!(!json.contains("deleted_at"));
+    }
+
+    #[test]
+    fn test_trigger_response_timestamps_are_canonical() {
+        use chrono::TimeZone;
+
+        let created_at = chrono::Utc
+            .with_ymd_and_hms(2026, 1, 15, 10, 30, 0)
+            .unwrap();
+        let response = TriggerResponse {
+            id: "trigger-123".to_string(),
+            user_id: "user-456".to_string(),
+            organization_id: "org-789".to_string(),
+            name: "Test Trigger".to_string(),
+            description: None,
+            chain_id: Some(84532),
+            registry: "reputation".to_string(),
+            enabled: true,
+            is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
+            created_at,
+            updated_at: created_at + chrono::Duration::microseconds(123_456),
+            deleted_at: Some(created_at + chrono::Duration::days(1)),
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["created_at"], "2026-01-15T10:30:00.000000Z");
+        assert_eq!(json["updated_at"], "2026-01-15T10:30:00.123456Z");
+        assert_eq!(json["deleted_at"], "2026-01-16T10:30:00.000000Z");
+    }
+
+    #[test]
+    fn test_trigger_list_query_defaults_to_live_triggers() {
+        let query: TriggerListQuery = serde_urlencoded::from_str("limit=10").unwrap();
+        assert!(!query.include_deleted);
+        let query: TriggerListQuery =
+            serde_urlencoded::from_str("include_deleted=true&offset=5").unwrap();
+        assert!(query.include_deleted);
     }
 
     #[test]
@@ -371,8 +646,11 @@ mod tests {
             registry: "identity".to_string(),
             enabled: true,
             is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            deleted_at: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -410,6 +688,8 @@ mod tests {
                 "chat_id": "123456789",
                 "message_template": "Hello {{agent_id}}!"
             }),
+            verification_status: "not_required".to_string(),
+            verified_at: None,
             created_at: chrono::Utc::now(),
         };
 
@@ -436,4 +716,25 @@ mod tests {
         assert!(validate_registry("Identity").is_err()); // case-sensitive
         assert!(validate_registry("REPUTATION").is_err());
     }
+
+    // ========================================================================
+    // FireTriggerRequest validation tests
+    // ========================================================================
+
+    #[test]
+    fn test_fire_trigger_request_accepts_object_or_nothing() {
+        let req = FireTriggerRequest {
+            event_data: Some(serde_json::json!({"agent_id": 42, "score": 55})),
+        };
+        assert!(req.validate().is_ok());
+        assert!(FireTriggerRequest::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_fire_trigger_request_rejects_non_object() {
+        let req = FireTriggerRequest {
+            event_data: Some(serde_json::json!([1, 2, 3])),
+        };
+        assert!(req.validate().is_err());
+    }
 }
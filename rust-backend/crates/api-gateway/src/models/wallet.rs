@@ -41,6 +41,7 @@ pub struct WalletChallengeResponse {
     /// Unique nonce (expires in 5 minutes)
     pub nonce: String,
     /// Challenge expiration time
+    #[serde(with = "shared::timestamp")]
     pub expires_at: DateTime<Utc>,
 }
 
@@ -118,6 +119,7 @@ pub struct AgentLinkResponse {
     /// Link status
     pub status: String,
     /// When the link was created
+    #[serde(with = "shared::timestamp")]
     pub linked_at: DateTime<Utc>,
 }
 
@@ -0,0 +1,245 @@
+//! Trigger Fire Webhook DTOs
+//!
+//! An organization can register any number of endpoints that receive every
+//! trigger fire as a signed `trigger.fired` event, optionally filtered by the
+//! blockchain event type. Each webhook gets its own server-generated signing
+//! secret, which is only returned when the webhook is created.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use super::action_webhooks::validate_webhook_url;
+
+/// Maximum event types per webhook filter
+pub const MAX_WEBHOOK_EVENT_TYPES: usize = 20;
+
+/// Delivery statuses accepted by the deliveries filter
+pub const WEBHOOK_DELIVERY_STATUSES: &[&str] = &["pending", "succeeded", "failed"];
+
+/// Request to register a trigger fire webhook
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[schema(example = json!({
+    "url": "https://hooks.example.com/agentauri",
+    "event_types": ["NewFeedback"],
+    "description": "Feedback alerts"
+}))]
+pub struct CreateWebhookRequest {
+    /// Endpoint that receives trigger fires (http/https, public host)
+    #[validate(length(max = 2048), custom(function = "validate_webhook_url"))]
+    pub url: String,
+
+    /// Event types to deliver (e.g. NewFeedback); empty delivers every fire
+    #[serde(default)]
+    #[validate(custom(function = "validate_event_types"))]
+    pub event_types: Vec<String>,
+
+    /// Optional description
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+
+    /// Whether deliveries are active (default: true)
+    pub enabled: Option<bool>,
+}
+
+/// A trigger fire webhook
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub organization_id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    /// Signing secret, only present in the create response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(with = "shared::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookResponse {
+    /// Build a response, revealing the secret only when asked to
+    pub fn new(webhook: shared::models::Webhook, reveal_secret: bool) -> Self {
+        Self {
+            id: webhook.id,
+            organization_id: webhook.organization_id,
+            url: webhook.url,
+            event_types: webhook.event_types,
+            description: webhook.description,
+            enabled: webhook.enabled,
+            secret: reveal_secret.then_some(webhook.secret),
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+/// One delivery of a trigger fire to a webhook
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: i64,
+    pub trigger_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    /// pending (queued or retrying), succeeded, or failed (attempts exhausted)
+    pub status: String,
+    pub attempts: i32,
+    /// When the next attempt is due (pending deliveries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "shared::timestamp::option")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    /// Body that was (or will be) sent
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    #[serde(with = "shared::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "shared::timestamp::option")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<shared::models::WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: shared::models::WebhookDelivery) -> Self {
+        let pending = delivery.status == "pending";
+        Self {
+            id: delivery.id,
+            trigger_id: delivery.trigger_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: pending.then_some(delivery.next_attempt_at),
+            last_response_status: delivery.last_response_status,
+            last_error: delivery.last_error,
+            payload: delivery.payload,
+            created_at: delivery.created_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
+
+/// Query parameters for listing deliveries
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WebhookDeliveriesQuery {
+    /// Maximum items per page (1-100, default 20)
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Number of items to skip
+    #[serde(default)]
+    pub offset: i64,
+    /// Only deliveries with this status (pending, succeeded, failed)
+    pub status: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+impl WebhookDeliveriesQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.limit < 1 || self.limit > 100 {
+            return Err("Limit must be between 1 and 100".to_string());
+        }
+        if self.offset < 0 {
+            return Err("Offset must be non-negative".to_string());
+        }
+        if let Some(status) = &self.status {
+            if !WEBHOOK_DELIVERY_STATUSES.contains(&status.as_str()) {
+                return Err(format!(
+                    "status must be one of: {}",
+                    WEBHOOK_DELIVERY_STATUSES.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_event_types(event_types: &[String]) -> Result<(), ValidationError> {
+    if event_types.len() > MAX_WEBHOOK_EVENT_TYPES {
+        return Err(ValidationError::new("too_many_event_types"));
+    }
+
+    for event_type in event_types {
+        if event_type.is_empty()
+            || event_type.len() > 64
+            || !event_type.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            let mut error = ValidationError::new("invalid_event_type");
+            error.message = Some("event types must be event names like NewFeedback".into());
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, event_types: &[&str]) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: url.to_string(),
+            event_types: event_types.iter().map(|t| t.to_string()).collect(),
+            description: None,
+            enabled: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        assert!(request("https://hooks.example.com/agentauri", &[])
+            .validate()
+            .is_ok());
+        assert!(request(
+            "https://hooks.example.com",
+            &["NewFeedback", "AgentRegistered"]
+        )
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_url() {
+        assert!(request("ftp://hooks.example.com", &[]).validate().is_err());
+        assert!(request("http://127.0.0.1/hook", &[]).validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_event_types() {
+        assert!(request("https://hooks.example.com", &[""])
+            .validate()
+            .is_err());
+        assert!(request("https://hooks.example.com", &["New Feedback"])
+            .validate()
+            .is_err());
+
+        let many: Vec<String> = (0..=MAX_WEBHOOK_EVENT_TYPES)
+            .map(|i| format!("Event{}", i))
+            .collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        assert!(request("https://hooks.example.com", &many)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_deliveries_query_validation() {
+        let query = |status: Option<&str>| WebhookDeliveriesQuery {
+            limit: 20,
+            offset: 0,
+            status: status.map(str::to_string),
+        };
+
+        assert!(query(None).validate().is_ok());
+        assert!(query(Some("failed")).validate().is_ok());
+        assert!(query(Some("lost")).validate().is_err());
+    }
+}
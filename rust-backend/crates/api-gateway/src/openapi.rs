@@ -9,7 +9,10 @@ use utoipa::{Modify, OpenApi};
 use crate::handlers;
 use crate::handlers::agents::{AgentLinkResponse, LinkAgentRequest};
 use crate::handlers::billing::PurchaseCreditsRequestWithOrg;
-use crate::handlers::health::HealthResponse;
+use crate::handlers::chains::{BalanceResponse, TransactionStatusResponse};
+use crate::handlers::health::{
+    DependencyStatus, HealthResponse, LivenessResponse, ReadinessResponse,
+};
 use crate::handlers::ponder::{ChainSyncStatus, PonderStatusError, PonderStatusResponse};
 use crate::models;
 
@@ -19,7 +22,7 @@ use crate::models;
     info(
         title = "AgentAuri API",
         version = "1.0.0",
-        description = "Real-time backend infrastructure for monitoring and reacting to ERC-8004 on-chain agent economy events.\n\n## Authentication\n\nThe API supports a 3-layer authentication system:\n\n- **Layer 0 (Anonymous)**: IP-based rate limiting (10 calls/hour)\n- **Layer 1 (API Key)**: Account-based access with `X-API-Key` header\n- **Layer 2 (JWT)**: Full user access with `Authorization: Bearer <token>`\n\n## Rate Limiting\n\nAll endpoints are rate limited. Check response headers for quota information:\n- `X-RateLimit-Limit`: Maximum requests per hour\n- `X-RateLimit-Remaining`: Remaining requests\n- `X-RateLimit-Reset`: Unix timestamp when limit resets",
+        description = "Real-time backend infrastructure for monitoring and reacting to ERC-8004 on-chain agent economy events.\n\n## Authentication\n\nThe API supports a 3-layer authentication system:\n\n- **Layer 0 (Anonymous)**: IP-based rate limiting (10 calls/hour)\n- **Layer 1 (API Key)**: Account-based access with `X-API-Key` header\n- **Layer 2 (JWT)**: Full user access with `Authorization: Bearer <token>`\n\n## Rate Limiting\n\nAll endpoints are rate limited. Check response headers for quota information:\n- `X-RateLimit-Limit`: Maximum requests per hour\n- `X-RateLimit-Remaining`: Remaining requests\n- `X-RateLimit-Reset`: Unix timestamp when limit resets\n\n## Timestamps\n\nTimestamps in responses are RFC 3339 in UTC with a `Z` suffix and microsecond precision, e.g. `2026-01-15T10:30:00.123456Z`. Timestamps in requests may use any RFC 3339 offset.\n\n## Deprecation\n\nDeprecated endpoints keep working until their sunset date and add these response headers:\n- `Deprecation`: `@<unix timestamp>` when the endpoint was deprecated\n- `Sunset`: HTTP date after which it may be removed\n- `Link`: the replacement endpoint (`rel=\"successor-version\"`)\n- `Warning`: `299` with a human-readable notice",
         contact(
             name = "AgentAuri Team",
             email = "support@agentauri.ai",
@@ -46,21 +49,28 @@ use crate::models;
         (name = "Actions", description = "Trigger action management"),
         (name = "Circuit Breaker", description = "Circuit breaker state and configuration"),
         (name = "Agents", description = "On-chain agent linking"),
+        (name = "Chains", description = "On-chain balance and transaction lookups"),
         (name = "Agent Follows", description = "Simplified agent monitoring across all registries"),
         (name = "Billing", description = "Credit balance and transactions"),
         (name = "Discovery", description = "API discovery and metadata"),
         (name = "Ponder", description = "Blockchain indexer status and metrics"),
         (name = "Events", description = "Blockchain event queries"),
-        (name = "A2A Protocol", description = "Agent-to-Agent JSON-RPC 2.0 protocol for async task queries")
+        (name = "Webhooks", description = "Organization webhooks notified of every trigger fire"),
+        (name = "Dead Letter Queue", description = "Action jobs that failed after all retries"),
+        (name = "A2A Protocol", description = "Agent-to-Agent JSON-RPC 2.0 protocol for async task queries"),
+        (name = "Admin", description = "Operator endpoints (require X-Admin-Token)")
     ),
     modifiers(&SecurityAddon),
     paths(
         // Health
         handlers::health_check,
+        handlers::liveness,
+        handlers::readiness,
         // Discovery
         handlers::openapi_json,
         handlers::get_agent_card,
         handlers::get_security_txt,
+        handlers::search_agents,
         // Authentication
         handlers::register,
         handlers::login,
@@ -79,6 +89,13 @@ use crate::models;
         handlers::wallet_login,
         handlers::refresh_token,
         handlers::exchange_code,
+        handlers::verify_email,
+        handlers::resend_verification,
+        handlers::forgot_password,
+        handlers::reset_password,
+        handlers::list_sessions,
+        handlers::revoke_session,
+        handlers::revoke_other_sessions,
         // Organizations
         handlers::create_organization,
         handlers::list_organizations,
@@ -106,6 +123,7 @@ use crate::models;
         handlers::create_oauth_client,
         handlers::list_oauth_clients,
         handlers::delete_oauth_client,
+        handlers::authorize,
         handlers::token_endpoint,
         // Triggers
         handlers::create_trigger,
@@ -113,18 +131,28 @@ use crate::models;
         handlers::get_trigger,
         handlers::update_trigger,
         handlers::delete_trigger,
+        handlers::restore_trigger,
+        handlers::fire_trigger,
+        handlers::get_trigger_state,
         // Triggers (organization-scoped)
         handlers::list_org_triggers,
+        handlers::export_org_triggers,
+        handlers::import_org_triggers,
+        handlers::export_triggers,
+        handlers::import_triggers,
         // Conditions
         handlers::create_condition,
         handlers::list_conditions,
         handlers::update_condition,
         handlers::delete_condition,
+        handlers::validate_condition,
         // Actions
         handlers::create_action,
         handlers::list_actions,
         handlers::update_action,
         handlers::delete_action,
+        handlers::verify_action,
+        handlers::get_action_result_summary,
         // Circuit Breaker
         handlers::get_circuit_breaker_state,
         handlers::update_circuit_breaker_config,
@@ -135,6 +163,9 @@ use crate::models;
         handlers::unlink_agent,
         // Agents (organization-scoped)
         handlers::list_org_agents,
+        // Chains
+        handlers::get_balance,
+        handlers::get_transaction_status,
         // Agent Follows
         handlers::follow_agent,
         handlers::list_following,
@@ -144,20 +175,48 @@ use crate::models;
         handlers::get_credits,
         handlers::purchase_credits,
         handlers::list_transactions,
+        handlers::get_transaction_receipt,
         handlers::get_subscription,
         handlers::handle_stripe_webhook,
         // Billing (organization-scoped)
         handlers::get_org_credits,
+        handlers::set_low_balance_threshold,
         handlers::list_org_transactions,
         // Ponder
         handlers::get_ponder_status,
         handlers::get_ponder_events,
         // Events
         handlers::list_events,
+        handlers::ingest_event,
         // A2A Protocol
         handlers::a2a_rpc,
         handlers::get_task_status,
+        handlers::cancel_task,
         handlers::stream_task_progress,
+        // Action result webhook
+        handlers::get_action_webhook,
+        handlers::set_action_webhook,
+        handlers::delete_action_webhook,
+        // Organization signing keys
+        handlers::list_signing_keys,
+        handlers::create_signing_key,
+        handlers::rotate_signing_key,
+        // Trigger fire webhooks
+        handlers::create_webhook,
+        handlers::list_webhooks,
+        handlers::get_webhook,
+        handlers::delete_webhook,
+        handlers::list_webhook_deliveries,
+        // Dead letter queue
+        handlers::list_dlq_entries,
+        handlers::replay_dlq_entry,
+        // Admin
+        handlers::get_org_rate_limits,
+        handlers::set_org_rate_limits,
+        handlers::flush_cache,
+        handlers::get_kill_switch,
+        handlers::set_kill_switch,
+        handlers::release_kill_switch,
     ),
     components(
         schemas(
@@ -180,6 +239,14 @@ use crate::models;
             models::RefreshTokenRequest,
             models::RefreshTokenResponse,
             models::ExchangeCodeRequest,
+            models::VerifyEmailRequest,
+            models::EmailVerificationResponse,
+            models::ForgotPasswordRequest,
+            models::ResetPasswordRequest,
+            models::PasswordResetResponse,
+            models::SessionResponse,
+            models::SessionListResponse,
+            models::RevokeSessionsResponse,
             // Organizations
             models::CreateOrganizationRequest,
             models::UpdateOrganizationRequest,
@@ -205,6 +272,8 @@ use crate::models;
             models::CreateOAuthClientRequest,
             models::CreateOAuthClientResponse,
             models::OAuthClientResponse,
+            models::AuthorizeRequest,
+            models::AuthorizeResponse,
             models::TokenRequest,
             models::TokenResponse,
             // Triggers
@@ -212,21 +281,41 @@ use crate::models;
             models::UpdateTriggerRequest,
             models::TriggerResponse,
             models::TriggerDetailResponse,
+            models::FireTriggerRequest,
+            models::FireTriggerResponse,
+            models::FiredJobResponse,
+            models::TriggerStateResponse,
+            models::TriggerExportBundle,
+            models::ExportedTrigger,
+            models::ExportedCondition,
+            models::ExportedAction,
+            models::ExcludedTrigger,
+            models::TriggerImportReport,
+            models::TriggerImportResult,
             // Conditions
             models::CreateConditionRequest,
             models::UpdateConditionRequest,
             models::ConditionResponse,
+            models::ConditionValidationResponse,
+            models::ConditionIssue,
             // Actions
             models::CreateActionRequest,
             models::UpdateActionRequest,
             models::ActionResponse,
+            models::ActionResultSummaryResponse,
+            models::FailureCategorySummary,
+            models::FailureCodeCount,
             // Circuit Breaker
             models::CircuitBreakerStateResponse,
             models::CircuitBreakerConfigResponse,
             models::UpdateCircuitBreakerConfigRequest,
             // Billing
             models::billing::CreditBalanceResponse,
+            models::billing::SetLowBalanceThresholdRequest,
+            models::billing::LowBalanceThresholdResponse,
             models::billing::CreditTransactionResponse,
+            models::billing::ReceiptResponse,
+            models::billing::ReceiptOrganization,
             models::billing::PurchaseCreditsRequest,
             models::billing::PurchaseCreditsResponse,
             models::billing::SubscriptionResponse,
@@ -234,18 +323,29 @@ use crate::models;
             // Agents
             LinkAgentRequest,
             AgentLinkResponse,
+            // Chains
+            BalanceResponse,
+            TransactionStatusResponse,
             // Agent Follows
             models::agent_follows::FollowAgentRequest,
             models::agent_follows::FollowActionRequest,
             models::agent_follows::UpdateFollowRequest,
             models::agent_follows::AgentFollowResponse,
             models::agent_follows::AgentFollowDetailResponse,
+            models::agent_follows::FollowedAgentResponse,
+            models::agent_follows::FollowedAgentProfile,
+            models::agent_follows::FollowedAgentsResponse,
             models::agent_follows::FollowActionSummary,
             models::agent_follows::TriggerIds,
             // Discovery
             models::discovery::AgentCardResponse,
+            models::discovery::AgentDiscoveryResponse,
+            models::discovery::DiscoveredAgent,
             // Health
             HealthResponse,
+            LivenessResponse,
+            ReadinessResponse,
+            DependencyStatus,
             // Ponder
             PonderStatusResponse,
             PonderStatusError,
@@ -253,6 +353,8 @@ use crate::models;
             // Events
             handlers::events::EventResponse,
             handlers::events::PaginatedEventsResponse,
+            models::IngestEventRequest,
+            models::IngestEventResponse,
             // A2A Protocol
             models::a2a::JsonRpcRequest,
             models::a2a::JsonRpcResponse<serde_json::Value>,
@@ -265,6 +367,32 @@ use crate::models;
             models::a2a::TaskSendResult,
             models::a2a::TaskGetResult,
             models::a2a::TaskCancelResult,
+            // Action result webhook
+            models::SetActionResultWebhookRequest,
+            models::ActionResultWebhookResponse,
+            // Organization signing keys
+            models::RotateSigningKeyRequest,
+            models::RotateSigningKeyResponse,
+            models::SigningKeyResponse,
+            models::SigningKeyStatus,
+            // Trigger fire webhooks
+            models::CreateWebhookRequest,
+            models::WebhookResponse,
+            models::WebhookDeliveryResponse,
+            // Dead letter queue
+            models::DlqEntryResponse,
+            // Admin
+            models::SetOrganizationRateLimitsRequest,
+            models::RateLimitTierConfig,
+            models::RateLimitTierResponse,
+            models::OrganizationRateLimitsResponse,
+            models::CacheNamespace,
+            models::FlushCacheRequest,
+            models::FlushCacheResponse,
+            models::KillSwitchMode,
+            models::SetKillSwitchRequest,
+            models::KillSwitchState,
+            models::KillSwitchStatusResponse,
         )
     )
 )]
@@ -309,6 +437,15 @@ impl Modify for SecurityAddon {
             ))),
         );
 
+        // Operator token for admin endpoints
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-Admin-Token",
+                "Operator token configured via ADMIN_API_TOKEN. Required for /api/v1/admin endpoints.",
+            ))),
+        );
+
         // Cookie-based authentication (HttpOnly auth-token cookie)
         components.add_security_scheme(
             "cookie_auth",
@@ -0,0 +1,72 @@
+//! A2A task events repository
+//!
+//! Events are appended as tasks change state and replayed by the SSE stream
+//! from a client's `Last-Event-ID`.
+
+use anyhow::{Context, Result};
+use shared::DbPool;
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+use crate::models::a2a::{A2aTaskEvent, TaskGetResult};
+
+pub struct A2aTaskEventRepository;
+
+impl A2aTaskEventRepository {
+    /// Record a task's status as an event
+    ///
+    /// The event type follows the status (see [`TaskStatus::event_type`]).
+    ///
+    /// [`TaskStatus::event_type`]: crate::models::a2a::TaskStatus::event_type
+    pub async fn append<'e, E>(
+        executor: E,
+        task_id: &Uuid,
+        status: &TaskGetResult,
+    ) -> Result<A2aTaskEvent>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let data = serde_json::to_value(status).context("Failed to encode A2A task event")?;
+
+        let event = sqlx::query_as::<_, A2aTaskEvent>(
+            r#"
+            INSERT INTO a2a_task_events (task_id, event_type, data)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(task_id)
+        .bind(status.status.event_type())
+        .bind(data)
+        .fetch_one(executor)
+        .await
+        .context("Failed to append A2A task event")?;
+
+        Ok(event)
+    }
+
+    /// Events of a task after `after_id`, oldest first
+    pub async fn list_after(
+        pool: &DbPool,
+        task_id: &Uuid,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<A2aTaskEvent>> {
+        let events = sqlx::query_as::<_, A2aTaskEvent>(
+            r#"
+            SELECT * FROM a2a_task_events
+            WHERE task_id = $1 AND id > $2
+            ORDER BY id ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(task_id)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list A2A task events")?;
+
+        Ok(events)
+    }
+}
@@ -8,6 +8,11 @@ use uuid::Uuid;
 
 use crate::models::a2a::A2aTask;
 
+/// Columns of [`A2aTask`]; decimals are read as text
+pub(crate) const TASK_COLUMNS: &str = "id, organization_id, tool, arguments, status, \
+    progress::TEXT AS progress, result, error, cost::TEXT AS cost, \
+    started_at, completed_at, created_at, updated_at";
+
 pub struct A2aTaskRepository;
 
 impl A2aTaskRepository {
@@ -18,13 +23,13 @@ impl A2aTaskRepository {
         tool: &str,
         arguments: &serde_json::Value,
     ) -> Result<A2aTask> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             INSERT INTO a2a_tasks (organization_id, tool, arguments, status)
             VALUES ($1, $2, $3, 'submitted')
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(organization_id)
         .bind(tool)
         .bind(arguments)
@@ -37,12 +42,12 @@ impl A2aTaskRepository {
 
     /// Find task by ID
     pub async fn find_by_id(pool: &DbPool, task_id: &Uuid) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
-            SELECT * FROM a2a_tasks
+            SELECT {TASK_COLUMNS} FROM a2a_tasks
             WHERE id = $1
-            "#,
-        )
+            "#
+        ))
         .bind(task_id)
         .fetch_optional(pool)
         .await
@@ -57,12 +62,12 @@ impl A2aTaskRepository {
         task_id: &Uuid,
         organization_id: &str,
     ) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
-            SELECT * FROM a2a_tasks
+            SELECT {TASK_COLUMNS} FROM a2a_tasks
             WHERE id = $1 AND organization_id = $2
-            "#,
-        )
+            "#
+        ))
         .bind(task_id)
         .bind(organization_id)
         .fetch_optional(pool)
@@ -74,14 +79,14 @@ impl A2aTaskRepository {
 
     /// Update task status to 'working' and set started_at
     pub async fn start_task(pool: &DbPool, task_id: &Uuid) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET status = 'working', started_at = NOW()
             WHERE id = $1 AND status = 'submitted'
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .fetch_optional(pool)
         .await
@@ -96,14 +101,14 @@ impl A2aTaskRepository {
         task_id: &Uuid,
         progress: f64,
     ) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET progress = $2::DECIMAL(3,2)
             WHERE id = $1 AND status = 'working'
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(progress)
         .fetch_optional(pool)
@@ -120,7 +125,7 @@ impl A2aTaskRepository {
         result: &serde_json::Value,
         cost: Option<f64>,
     ) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET status = 'completed',
@@ -129,9 +134,9 @@ impl A2aTaskRepository {
                 cost = $3::DECIMAL(20,8),
                 completed_at = NOW()
             WHERE id = $1 AND status IN ('submitted', 'working')
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(result)
         .bind(cost)
@@ -144,16 +149,16 @@ impl A2aTaskRepository {
 
     /// Fail task with error message
     pub async fn fail_task(pool: &DbPool, task_id: &Uuid, error: &str) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET status = 'failed',
                 error = $2,
                 completed_at = NOW()
             WHERE id = $1 AND status IN ('submitted', 'working')
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(error)
         .fetch_optional(pool)
@@ -169,16 +174,16 @@ impl A2aTaskRepository {
         task_id: &Uuid,
         organization_id: &str,
     ) -> Result<Option<A2aTask>> {
-        let task = sqlx::query_as::<_, A2aTask>(
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET status = 'cancelled', completed_at = NOW()
             WHERE id = $1
               AND organization_id = $2
               AND status IN ('submitted', 'working')
-            RETURNING *
-            "#,
-        )
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(organization_id)
         .fetch_optional(pool)
@@ -195,14 +200,14 @@ impl A2aTaskRepository {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<A2aTask>> {
-        let tasks = sqlx::query_as::<_, A2aTask>(
+        let tasks = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
-            SELECT * FROM a2a_tasks
+            SELECT {TASK_COLUMNS} FROM a2a_tasks
             WHERE organization_id = $1
             ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
-            "#,
-        )
+            "#
+        ))
         .bind(organization_id)
         .bind(limit)
         .bind(offset)
@@ -0,0 +1,84 @@
+//! Organization action result webhook repository
+
+use anyhow::{Context, Result};
+use rand::RngCore;
+use shared::models::OrganizationActionWebhook;
+use shared::DbPool;
+
+pub struct ActionWebhookRepository;
+
+impl ActionWebhookRepository {
+    /// Get an organization's webhook, if one is configured
+    pub async fn find_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Option<OrganizationActionWebhook>> {
+        let webhook = sqlx::query_as::<_, OrganizationActionWebhook>(
+            "SELECT * FROM organization_action_webhooks WHERE organization_id = $1",
+        )
+        .bind(organization_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch action webhook")?;
+
+        Ok(webhook)
+    }
+
+    /// Create or update an organization's webhook
+    ///
+    /// A secret is generated when the webhook is created or `rotate_secret` is
+    /// set; otherwise the existing secret is kept. Returns the stored row and
+    /// whether its secret is new.
+    pub async fn upsert(
+        pool: &DbPool,
+        organization_id: &str,
+        url: &str,
+        enabled: bool,
+        rotate_secret: bool,
+    ) -> Result<(OrganizationActionWebhook, bool)> {
+        let candidate_secret = generate_secret();
+
+        let webhook = sqlx::query_as::<_, OrganizationActionWebhook>(
+            r#"
+            INSERT INTO organization_action_webhooks (organization_id, url, secret, enabled)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (organization_id) DO UPDATE SET
+                url = EXCLUDED.url,
+                enabled = EXCLUDED.enabled,
+                secret = CASE WHEN $5 THEN EXCLUDED.secret
+                              ELSE organization_action_webhooks.secret END
+            RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(url)
+        .bind(&candidate_secret)
+        .bind(enabled)
+        .bind(rotate_secret)
+        .fetch_one(pool)
+        .await
+        .context("Failed to save action webhook")?;
+
+        let secret_is_new = webhook.secret == candidate_secret;
+        Ok((webhook, secret_is_new))
+    }
+
+    /// Remove an organization's webhook, returning whether one existed
+    pub async fn delete(pool: &DbPool, organization_id: &str) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM organization_action_webhooks WHERE organization_id = $1")
+                .bind(organization_id)
+                .execute(pool)
+                .await
+                .context("Failed to delete action webhook")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Generate a signing secret (`whsec_` + 64 hex chars)
+pub(crate) fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
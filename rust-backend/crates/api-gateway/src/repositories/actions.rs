@@ -1,27 +1,36 @@
 //! Trigger action repository for database operations
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use shared::models::TriggerAction;
 use shared::DbPool;
 use sqlx::{Executor, Postgres};
 
+use crate::models::{ActionResultCount, VerificationStatus};
+
 pub struct ActionRepository;
 
 impl ActionRepository {
     /// Create a new action
+    ///
+    /// `verification_status` is the outcome of the webhook handshake (if any);
+    /// `verified_at` should be set only when the status is `verified`.
     pub async fn create(
         pool: &DbPool,
         trigger_id: &str,
         action_type: &str,
         priority: i32,
         config: &serde_json::Value,
+        verification_status: VerificationStatus,
+        verified_at: Option<DateTime<Utc>>,
     ) -> Result<TriggerAction> {
         let now = chrono::Utc::now();
 
         let action = sqlx::query_as::<_, TriggerAction>(
             r#"
-            INSERT INTO trigger_actions (trigger_id, action_type, priority, config, created_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO trigger_actions
+                (trigger_id, action_type, priority, config, verification_status, verified_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
             "#,
         )
@@ -29,6 +38,8 @@ impl ActionRepository {
         .bind(action_type)
         .bind(priority)
         .bind(config)
+        .bind(verification_status.as_str())
+        .bind(verified_at)
         .bind(now)
         .fetch_one(pool)
         .await
@@ -38,12 +49,16 @@ impl ActionRepository {
     }
 
     /// Create a new action within a transaction
+    ///
+    /// See [`Self::create`] for `verification_status` and `verified_at`.
     pub async fn create_in_tx<'e, E>(
         executor: E,
         trigger_id: &str,
         action_type: &str,
         priority: i32,
         config: &serde_json::Value,
+        verification_status: VerificationStatus,
+        verified_at: Option<DateTime<Utc>>,
     ) -> Result<TriggerAction>
     where
         E: Executor<'e, Database = Postgres>,
@@ -52,8 +67,9 @@ impl ActionRepository {
 
         let action = sqlx::query_as::<_, TriggerAction>(
             r#"
-            INSERT INTO trigger_actions (trigger_id, action_type, priority, config, created_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO trigger_actions
+                (trigger_id, action_type, priority, config, verification_status, verified_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
             "#,
         )
@@ -61,6 +77,8 @@ impl ActionRepository {
         .bind(action_type)
         .bind(priority)
         .bind(config)
+        .bind(verification_status.as_str())
+        .bind(verified_at)
         .bind(now)
         .fetch_one(executor)
         .await
@@ -89,7 +107,6 @@ impl ActionRepository {
     }
 
     /// Find action by ID
-    #[allow(dead_code)]
     pub async fn find_by_id(pool: &DbPool, action_id: i32) -> Result<Option<TriggerAction>> {
         let action = sqlx::query_as::<_, TriggerAction>(
             r#"
@@ -122,6 +139,26 @@ impl ActionRepository {
         Ok(actions)
     }
 
+    /// List actions for several triggers with a single query
+    pub async fn list_by_triggers(
+        pool: &DbPool,
+        trigger_ids: &[String],
+    ) -> Result<Vec<TriggerAction>> {
+        let actions = sqlx::query_as::<_, TriggerAction>(
+            r#"
+            SELECT * FROM trigger_actions
+            WHERE trigger_id = ANY($1)
+            ORDER BY trigger_id, priority ASC, id ASC
+            "#,
+        )
+        .bind(trigger_ids)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list actions")?;
+
+        Ok(actions)
+    }
+
     /// Update action
     ///
     /// Uses a safe COALESCE pattern instead of dynamic SQL.
@@ -171,6 +208,32 @@ impl ActionRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Record the outcome of a webhook verification handshake
+    pub async fn set_verification(
+        pool: &DbPool,
+        action_id: i32,
+        verification_status: VerificationStatus,
+        verified_at: Option<DateTime<Utc>>,
+    ) -> Result<TriggerAction> {
+        let action = sqlx::query_as::<_, TriggerAction>(
+            r#"
+            UPDATE trigger_actions SET
+                verification_status = $1,
+                verified_at = $2
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(verification_status.as_str())
+        .bind(verified_at)
+        .bind(action_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to update action verification status")?;
+
+        Ok(action)
+    }
+
     /// Get trigger_id for an action
     pub async fn get_trigger_id(pool: &DbPool, action_id: i32) -> Result<Option<String>> {
         let trigger_id = sqlx::query_scalar::<_, String>(
@@ -186,4 +249,27 @@ impl ActionRepository {
 
         Ok(trigger_id)
     }
+
+    /// Count an action's results since `since`, per status and failure reason
+    pub async fn count_results(
+        pool: &DbPool,
+        action_id: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ActionResultCount>> {
+        let counts = sqlx::query_as::<_, ActionResultCount>(
+            r#"
+            SELECT status, error_category, error_code, COUNT(*) AS count
+            FROM action_results
+            WHERE action_id = $1 AND executed_at >= $2
+            GROUP BY status, error_category, error_code
+            "#,
+        )
+        .bind(action_id)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+        .context("Failed to count action results")?;
+
+        Ok(counts)
+    }
 }
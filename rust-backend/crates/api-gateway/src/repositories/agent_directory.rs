@@ -0,0 +1,300 @@
+//! Agent directory for `GET /discovery`
+//!
+//! Agents are derived from the namespaced `{namespace}__Event` table written
+//! by Ponder: every `(chainId, agentId)` with at least one event is listed.
+//! An agent's name and description are the latest `name` and `description`
+//! metadata set on chain, its follower count comes from enabled
+//! `agent_follows`, and its last activity is its latest event.
+//!
+//! Matching and ranking run in SQL. Every sort orders by a single score,
+//! then `(chain_id, agent_id)`, all descending:
+//!
+//! - `relevance`: text rank (1 without a query) x `ln(2 + followers)` x a
+//!   recency factor that halves every 30 days without activity
+//! - `followers`: follower count
+//! - `recent`: Unix time of the latest event
+//!
+//! Recency is measured against the `as_of` time carried in the cursor, so the
+//! scores, and with them the pages, stay put while a client pages through.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sqlx::{FromRow, Postgres, QueryBuilder};
+
+use shared::DbPool;
+
+use super::ponder::validate_namespace;
+
+/// Inactivity after which the relevance score is halved (30 days)
+const RECENCY_HALF_LIFE_SECS: f64 = 30.0 * 24.0 * 60.0 * 60.0;
+
+/// Order of discovered agents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AgentSort {
+    /// Text match, followers and recent activity combined
+    #[default]
+    Relevance,
+    /// Most followed first
+    Followers,
+    /// Most recently active first
+    Recent,
+}
+
+impl AgentSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Followers => "followers",
+            Self::Recent => "recent",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "relevance" => Some(Self::Relevance),
+            "followers" => Some(Self::Followers),
+            "recent" => Some(Self::Recent),
+            _ => None,
+        }
+    }
+}
+
+/// Position after the last agent of a page
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentCursor {
+    /// Sort the cursor was issued for
+    pub sort: AgentSort,
+    /// Reference time for recency, fixed by the first page
+    pub as_of: i64,
+    pub score: f64,
+    pub chain_id: i64,
+    pub agent_id: String,
+}
+
+impl AgentCursor {
+    /// Opaque string form handed to clients
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}:{}:{}:{}",
+            self.sort.as_str(),
+            self.as_of,
+            self.score,
+            self.chain_id,
+            self.agent_id
+        ))
+    }
+
+    /// Parse a cursor produced by [`AgentCursor::encode`]
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let mut parts = raw.splitn(5, ':');
+        let sort = AgentSort::parse(parts.next()?)?;
+        let as_of = parts.next()?.parse().ok()?;
+        let score = parts
+            .next()?
+            .parse::<f64>()
+            .ok()
+            .filter(|s| s.is_finite())?;
+        let chain_id = parts.next()?.parse().ok()?;
+        let agent_id = parts.next().filter(|id| !id.is_empty())?.to_string();
+        Some(Self {
+            sort,
+            as_of,
+            score,
+            chain_id,
+            agent_id,
+        })
+    }
+}
+
+/// One page request against the directory
+#[derive(Debug, Clone)]
+pub struct AgentSearch {
+    /// Full-text query over name and description (web search syntax)
+    pub query: Option<String>,
+    pub sort: AgentSort,
+    /// Reference time for recency (Unix seconds)
+    pub as_of: i64,
+    /// Continue after this agent
+    pub after: Option<AgentCursor>,
+    pub limit: i64,
+}
+
+/// One agent as listed by `GET /discovery`
+#[derive(Debug, Clone, FromRow)]
+pub struct AgentDirectoryRow {
+    pub chain_id: i64,
+    pub agent_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub agent_uri: Option<String>,
+    pub followers: i64,
+    pub last_activity: i64,
+    pub score: f64,
+}
+
+pub struct AgentDirectoryRepository;
+
+impl AgentDirectoryRepository {
+    /// One page of agents matching `search`, best first
+    ///
+    /// Aggregates the whole event table per agent, so this is meant for
+    /// directories of thousands of agents, not millions.
+    pub async fn search(
+        pool: &DbPool,
+        namespace: &str,
+        search: &AgentSearch,
+    ) -> Result<Vec<AgentDirectoryRow>> {
+        validate_namespace(namespace)?;
+
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            r#"
+            WITH agents AS (
+                SELECT
+                    "chainId"::bigint AS chain_id,
+                    "agentId"::text AS agent_id,
+                    MAX("timestamp")::bigint AS last_activity,
+                    (ARRAY_AGG("metadataValue" ORDER BY "timestamp" DESC, id DESC)
+                        FILTER (WHERE LOWER("metadataKey") = 'name'))[1] AS name,
+                    (ARRAY_AGG("metadataValue" ORDER BY "timestamp" DESC, id DESC)
+                        FILTER (WHERE LOWER("metadataKey") = 'description'))[1] AS description,
+                    (ARRAY_AGG("agentUri" ORDER BY "timestamp" DESC, id DESC)
+                        FILTER (WHERE "agentUri" IS NOT NULL))[1] AS agent_uri
+                FROM public."{}__Event"
+                WHERE "agentId" IS NOT NULL
+                GROUP BY 1, 2
+            ),
+            follower_counts AS (
+                SELECT chain_id::bigint AS chain_id, agent_id::text AS agent_id,
+                       COUNT(*)::bigint AS followers
+                FROM agent_follows
+                WHERE enabled = true
+                GROUP BY 1, 2
+            ),
+            matched AS (
+                SELECT a.*, COALESCE(f.followers, 0) AS followers, "#,
+            namespace
+        ));
+
+        match &search.query {
+            Some(query) => {
+                builder
+                    .push(
+                        "ts_rank(to_tsvector('simple', COALESCE(a.name, '') || ' ' || \
+                         COALESCE(a.description, '')), q.query)::float8 AS text_rank \
+                         FROM agents a \
+                         LEFT JOIN follower_counts f USING (chain_id, agent_id) \
+                         CROSS JOIN websearch_to_tsquery('simple', ",
+                    )
+                    .push_bind(query)
+                    .push(
+                        ") AS q(query) \
+                         WHERE to_tsvector('simple', COALESCE(a.name, '') || ' ' || \
+                         COALESCE(a.description, '')) @@ q.query",
+                    );
+            }
+            None => {
+                builder.push(
+                    "1.0::float8 AS text_rank \
+                     FROM agents a \
+                     LEFT JOIN follower_counts f USING (chain_id, agent_id)",
+                );
+            }
+        }
+
+        builder.push("), scored AS (SELECT *, ");
+        match search.sort {
+            AgentSort::Relevance => {
+                builder
+                    .push("text_rank * LN(2 + followers::float8) * POWER(0.5::float8, GREATEST(")
+                    .push_bind(search.as_of)
+                    .push(" - last_activity, 0) / ")
+                    .push_bind(RECENCY_HALF_LIFE_SECS)
+                    .push(")");
+            }
+            AgentSort::Followers => {
+                builder.push("followers::float8");
+            }
+            AgentSort::Recent => {
+                builder.push("last_activity::float8");
+            }
+        }
+        builder.push(
+            " AS score FROM matched) \
+             SELECT chain_id, agent_id, name, description, agent_uri, followers, \
+             last_activity, score FROM scored",
+        );
+
+        if let Some(cursor) = &search.after {
+            builder
+                .push(" WHERE (score, chain_id, agent_id) < (")
+                .push_bind(cursor.score)
+                .push(", ")
+                .push_bind(cursor.chain_id)
+                .push(", ")
+                .push_bind(&cursor.agent_id)
+                .push(")");
+        }
+
+        builder
+            .push(" ORDER BY score DESC, chain_id DESC, agent_id DESC LIMIT ")
+            .push_bind(search.limit);
+
+        builder
+            .build_query_as::<AgentDirectoryRow>()
+            .fetch_all(pool)
+            .await
+            .context("Failed to search agent directory")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_cursor_round_trip() {
+        let cursor = AgentCursor {
+            sort: AgentSort::Relevance,
+            as_of: 1_700_000_000,
+            score: 0.060_792_710_632_085_8,
+            chain_id: 84532,
+            agent_id: "42:x".to_string(),
+        };
+        let decoded = AgentCursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded, cursor);
+        // The score must survive exactly for the keyset comparison
+        assert_eq!(decoded.score.to_bits(), cursor.score.to_bits());
+    }
+
+    #[test]
+    fn test_agent_cursor_rejects_garbage() {
+        assert_eq!(AgentCursor::decode("not a cursor"), None);
+        for raw in [
+            "relevance:1:0.5:84532",
+            "relevance:1:0.5:84532:",
+            "best:1:0.5:84532:42",
+            "recent:1:NaN:84532:42",
+            "recent:x:0.5:84532:42",
+        ] {
+            assert_eq!(
+                AgentCursor::decode(&URL_SAFE_NO_PAD.encode(raw)),
+                None,
+                "{}",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn test_agent_sort_parse() {
+        for sort in [
+            AgentSort::Relevance,
+            AgentSort::Followers,
+            AgentSort::Recent,
+        ] {
+            assert_eq!(AgentSort::parse(sort.as_str()), Some(sort));
+        }
+        assert_eq!(AgentSort::parse("Followers"), None);
+    }
+}
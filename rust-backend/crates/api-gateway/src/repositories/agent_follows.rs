@@ -2,7 +2,8 @@
 
 use anyhow::{Context, Result};
 use shared::models::AgentFollow;
-use shared::DbPool;
+use shared::{DbPool, NotificationPreferences};
+use sqlx::types::Json;
 use sqlx::{Executor, Postgres};
 use uuid::Uuid;
 
@@ -150,7 +151,10 @@ impl AgentFollowRepository {
     }
 
     /// Update follow enabled status
-    pub async fn update_enabled(pool: &DbPool, id: &str, enabled: bool) -> Result<AgentFollow> {
+    pub async fn update_enabled<'e, E>(executor: E, id: &str, enabled: bool) -> Result<AgentFollow>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let now = chrono::Utc::now();
 
         let follow = sqlx::query_as::<_, AgentFollow>(
@@ -164,13 +168,40 @@ impl AgentFollowRepository {
         .bind(enabled)
         .bind(now)
         .bind(id)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
         .context("Failed to update agent follow")?;
 
         Ok(follow)
     }
 
+    /// Replace the notification preferences of a follow (`None` = notify
+    /// about everything)
+    pub async fn update_notification_preferences<'e, E>(
+        executor: E,
+        id: &str,
+        preferences: Option<&NotificationPreferences>,
+    ) -> Result<AgentFollow>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let follow = sqlx::query_as::<_, AgentFollow>(
+            r#"
+            UPDATE agent_follows
+            SET notification_preferences = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(preferences.map(Json))
+        .bind(id)
+        .fetch_one(executor)
+        .await
+        .context("Failed to update agent follow notification preferences")?;
+
+        Ok(follow)
+    }
+
     /// Delete a follow by ID (within a transaction)
     pub async fn delete<'e, E>(executor: E, id: &str) -> Result<bool>
     where
@@ -219,6 +250,26 @@ impl AgentFollowRepository {
         Ok(result)
     }
 
+    /// List the IDs of all follow-managed triggers in an organization
+    pub async fn list_managed_trigger_ids(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT unnest(ARRAY[trigger_identity_id, trigger_reputation_id, trigger_validation_id])
+            FROM agent_follows
+            WHERE organization_id = $1
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list follow-managed trigger IDs")?;
+
+        Ok(ids)
+    }
+
     /// Check if follow belongs to organization
     pub async fn belongs_to_organization(
         pool: &DbPool,
@@ -3,15 +3,63 @@
 //! **Note**: Agent linking is a Layer 2 feature that will be completed in Phase 4-5.
 //! Some repository methods are implemented but not yet integrated with handlers.
 //!
-//! Handles storage and retrieval of agent-to-organization links.
+//! Handles storage and retrieval of agent-to-organization links, and lists an
+//! organization's followed agents together with their links.
 
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use shared::models::AgentFollow;
 use shared::DbPool;
-use sqlx::{Executor, Postgres};
+use sqlx::{Executor, FromRow, Postgres};
 use uuid::Uuid;
 
 use crate::models::wallet::AgentLink;
 
+/// Position after the last follow of a page (newest first)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl FollowCursor {
+    /// Opaque string form handed to clients
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}",
+            self.created_at.timestamp_micros(),
+            self.id
+        ))
+    }
+
+    /// Parse a cursor produced by [`FollowCursor::encode`]
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (micros, id) = raw.split_once(':')?;
+        let created_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+        if id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            created_at,
+            id: id.to_string(),
+        })
+    }
+}
+
+/// A follow with the active link of the followed agent, if any
+#[derive(Debug, Clone, FromRow)]
+pub struct FollowedAgentRow {
+    #[sqlx(flatten)]
+    pub follow: AgentFollow,
+    /// Wallet that linked the agent
+    pub link_wallet_address: Option<String>,
+    /// Organization the agent is linked to
+    pub link_organization_id: Option<String>,
+    pub linked_at: Option<DateTime<Utc>>,
+}
+
 pub struct AgentLinkRepository;
 
 impl AgentLinkRepository {
@@ -173,4 +221,75 @@ impl AgentLinkRepository {
 
         Ok(result)
     }
+
+    /// One page of an organization's follows, newest first, each joined with
+    /// the followed agent's active link
+    ///
+    /// Pages either continue `after` a cursor or skip `offset` follows.
+    pub async fn list_follows_with_links(
+        pool: &DbPool,
+        organization_id: &str,
+        chain_id: Option<i32>,
+        enabled: Option<bool>,
+        after: Option<&FollowCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<FollowedAgentRow>> {
+        let rows = sqlx::query_as::<_, FollowedAgentRow>(
+            r#"
+            SELECT f.*,
+                   l.wallet_address AS link_wallet_address,
+                   l.organization_id AS link_organization_id,
+                   l.created_at AS linked_at
+            FROM agent_follows f
+            LEFT JOIN agent_links l
+              ON l.agent_id = f.agent_id AND l.chain_id = f.chain_id AND l.status = 'active'
+            WHERE f.organization_id = $1
+              AND ($2::INTEGER IS NULL OR f.chain_id = $2)
+              AND ($3::BOOLEAN IS NULL OR f.enabled = $3)
+              AND ($4::TIMESTAMPTZ IS NULL OR (f.created_at, f.id) < ($4, $5))
+            ORDER BY f.created_at DESC, f.id DESC
+            LIMIT $6 OFFSET $7
+            "#,
+        )
+        .bind(organization_id)
+        .bind(chain_id)
+        .bind(enabled)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list followed agents")?;
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_cursor_round_trip() {
+        let cursor = FollowCursor {
+            created_at: DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+            id: "0b7e:follow".to_string(),
+        };
+        assert_eq!(FollowCursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn test_follow_cursor_rejects_garbage() {
+        assert_eq!(FollowCursor::decode("not a cursor"), None);
+        for raw in ["1700000000123456", "1700000000123456:", "soon:abc"] {
+            assert_eq!(
+                FollowCursor::decode(&URL_SAFE_NO_PAD.encode(raw)),
+                None,
+                "{}",
+                raw
+            );
+        }
+    }
 }
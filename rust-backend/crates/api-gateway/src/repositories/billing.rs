@@ -6,6 +6,16 @@
 //!
 //! Handles storage and retrieval of organization credits, transactions,
 //! and subscription data.
+//!
+//! # Low-balance alerts
+//!
+//! An organization may set `low_balance_threshold`. The deduction that takes
+//! the balance below it records `low_balance_notified_at` and, in the same
+//! statement, queues a [`LOW_BALANCE_EVENT_TYPE`] delivery to each enabled
+//! webhook of the organization that accepts it. The event-processor's webhook
+//! dispatcher sends it like a trigger fire. Later deductions do not alert
+//! again until a top-up brings the balance back to the threshold, or the
+//! threshold is changed, which clears `low_balance_notified_at`.
 
 use anyhow::{Context, Result};
 use shared::DbPool;
@@ -14,6 +24,34 @@ use uuid::Uuid;
 
 use crate::models::billing::{Credit, CreditTransaction, PaymentNonce, Subscription};
 
+/// Event type of low-balance webhook deliveries (webhooks subscribe to it
+/// through `event_types`)
+pub const LOW_BALANCE_EVENT_TYPE: &str = "LowCreditBalance";
+
+/// Stands in for the trigger ID of low-balance webhook deliveries
+pub const LOW_BALANCE_SOURCE: &str = "credits";
+
+/// Outcome of a credit deduction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow)]
+pub struct Deduction {
+    /// Balance after the deduction
+    pub balance: i64,
+    /// The deduction took the balance below the low-balance threshold and
+    /// queued the alert
+    pub low_balance_alert: bool,
+}
+
+/// Outcome of taking back refunded or disputed credits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefundDeduction {
+    /// Credits of the charge not taken back by earlier refunds or disputes
+    pub outstanding: i64,
+    /// Credits taken back (less than outstanding if already spent)
+    pub deducted: i64,
+    /// Balance after the deduction
+    pub balance: i64,
+}
+
 pub struct CreditRepository;
 
 impl CreditRepository {
@@ -58,6 +96,8 @@ impl CreditRepository {
     }
 
     /// Add credits to an organization (returns new balance)
+    ///
+    /// A balance back at the low-balance threshold re-arms the alert.
     pub async fn add_credits<'e, E>(executor: E, organization_id: &str, amount: i64) -> Result<i64>
     where
         E: Executor<'e, Database = Postgres>,
@@ -66,7 +106,13 @@ impl CreditRepository {
         let new_balance = sqlx::query_scalar::<_, i64>(
             r#"
             UPDATE credits
-            SET balance = balance + $1, updated_at = NOW()
+            SET balance = balance + $1,
+                low_balance_notified_at = CASE
+                    WHEN low_balance_threshold IS NULL OR balance + $1 >= low_balance_threshold
+                        THEN NULL
+                    ELSE low_balance_notified_at
+                END,
+                updated_at = NOW()
             WHERE organization_id = $2
             RETURNING balance
             "#,
@@ -80,7 +126,9 @@ impl CreditRepository {
         Ok(new_balance)
     }
 
-    /// Deduct credits from an organization (returns new balance, fails if insufficient)
+    /// Deduct credits from an organization (fails if insufficient)
+    ///
+    /// Queues the low-balance alert if this deduction crosses the threshold.
     ///
     /// NOTE: This method is NOT safe for concurrent requests. Use `deduct_credits_atomic`
     /// instead when handling concurrent credit deductions.
@@ -89,21 +137,62 @@ impl CreditRepository {
         executor: E,
         organization_id: &str,
         amount: i64,
-    ) -> Result<i64>
+    ) -> Result<Deduction>
     where
         E: Executor<'e, Database = Postgres>,
     {
-        // Use a WHERE clause to prevent going negative
-        let result = sqlx::query_scalar::<_, i64>(
+        // Use a WHERE clause to prevent going negative. `armed` is read
+        // before the update, so only the crossing deduction alerts.
+        let result = sqlx::query_as::<_, Deduction>(
             r#"
-            UPDATE credits
-            SET balance = balance - $1, updated_at = NOW()
-            WHERE organization_id = $2 AND balance >= $1
-            RETURNING balance
+            WITH locked AS (
+                SELECT id, low_balance_notified_at IS NULL AS armed
+                FROM credits
+                WHERE organization_id = $2
+                FOR UPDATE
+            ),
+            updated AS (
+                UPDATE credits c
+                SET balance = c.balance - $1,
+                    low_balance_notified_at = CASE
+                        WHEN locked.armed AND c.balance - $1 < c.low_balance_threshold
+                            THEN NOW()
+                        ELSE c.low_balance_notified_at
+                    END,
+                    updated_at = NOW()
+                FROM locked
+                WHERE c.id = locked.id AND c.balance >= $1
+                RETURNING c.organization_id, c.balance, c.low_balance_threshold,
+                          c.low_balance_notified_at,
+                          locked.armed AND c.low_balance_notified_at IS NOT NULL AS low_balance_alert
+            ),
+            alerts AS (
+                INSERT INTO webhook_deliveries (webhook_id, trigger_id, event_id, event_type, payload)
+                SELECT w.id, $3,
+                       u.organization_id || ':' || EXTRACT(EPOCH FROM u.low_balance_notified_at)::TEXT,
+                       $4,
+                       jsonb_build_object(
+                           'type', 'credits.low_balance',
+                           'fired_at', u.low_balance_notified_at,
+                           'organization_id', u.organization_id,
+                           'balance', u.balance,
+                           'threshold', u.low_balance_threshold,
+                           'currency', 'USDC'
+                       )
+                FROM updated u
+                JOIN webhooks w ON w.organization_id = u.organization_id
+                WHERE u.low_balance_alert
+                  AND w.enabled = TRUE
+                  AND (cardinality(w.event_types) = 0 OR $4 = ANY(w.event_types))
+                ON CONFLICT (webhook_id, trigger_id, event_id) DO NOTHING
+            )
+            SELECT balance, low_balance_alert FROM updated
             "#,
         )
         .bind(amount)
         .bind(organization_id)
+        .bind(LOW_BALANCE_SOURCE)
+        .bind(LOW_BALANCE_EVENT_TYPE)
         .fetch_optional(executor)
         .await
         .context("Failed to deduct credits")?;
@@ -120,7 +209,7 @@ impl CreditRepository {
         pool: &DbPool,
         organization_id: &str,
         amount: i64,
-    ) -> Result<i64> {
+    ) -> Result<Deduction> {
         let mut tx = pool.begin().await.context("Failed to begin transaction")?;
 
         // Lock the row for update - prevents concurrent reads until this transaction commits
@@ -146,23 +235,135 @@ impl CreditRepository {
             ));
         }
 
-        // Update balance
-        let new_balance = sqlx::query_scalar::<_, i64>(
+        // Update balance (and queue the low-balance alert in the same transaction)
+        let deduction = Self::deduct_credits(&mut *tx, organization_id, amount).await?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(deduction)
+    }
+
+    /// Take back refunded or disputed credits, at most once per `reference_id`
+    ///
+    /// `charge_total` is the total refunded or disputed of `charge_id` so far.
+    /// Only what earlier refunds of the charge have not taken back is
+    /// deducted: a dispute and a refund of the same charge cover the same
+    /// funds, so the larger total counts, not their sum.
+    ///
+    /// Credits already spent cannot be taken back: the deduction stops at a
+    /// zero balance. Records a `refund` transaction for the amount deducted.
+    /// Returns `None` if a refund with `reference_id` was already applied.
+    pub async fn apply_refund(
+        pool: &DbPool,
+        organization_id: &str,
+        charge_id: &str,
+        charge_total: i64,
+        reference_id: &str,
+        description: &str,
+        mut metadata: serde_json::Value,
+    ) -> Result<Option<RefundDeduction>> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        // The lock also serializes refunds of the organization, so the total
+        // taken back below can't change until this one is recorded
+        let current_balance = sqlx::query_scalar::<_, i64>(
             r#"
-            UPDATE credits
-            SET balance = balance - $1, updated_at = NOW()
-            WHERE organization_id = $2
-            RETURNING balance
+            SELECT balance FROM credits
+            WHERE organization_id = $1
+            FOR UPDATE
             "#,
         )
-        .bind(amount)
         .bind(organization_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to get balance with lock")?
+        .ok_or_else(|| anyhow::anyhow!("Credits not initialized for organization"))?;
+
+        let taken_back = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COALESCE(MAX((metadata->>'charge_total')::BIGINT), 0)
+            FROM credit_transactions
+            WHERE organization_id = $1
+              AND transaction_type = 'refund'
+              AND metadata->>'charge_id' = $2
+            "#,
+        )
+        .bind(organization_id)
+        .bind(charge_id)
         .fetch_one(&mut *tx)
         .await
-        .context("Failed to deduct credits")?;
+        .context("Failed to get refunded total of charge")?;
+
+        let outstanding = (charge_total - taken_back).max(0);
+        if let Some(fields) = metadata.as_object_mut() {
+            fields.insert("charge_id".into(), charge_id.into());
+            fields.insert("charge_total".into(), charge_total.into());
+        }
+
+        let deducted = outstanding.clamp(0, current_balance);
+        let balance = if deducted > 0 {
+            Self::deduct_credits(&mut *tx, organization_id, deducted)
+                .await?
+                .balance
+        } else {
+            current_balance
+        };
+
+        // A duplicate rolls the deduction back when `tx` is dropped
+        let recorded = TransactionRepository::create_idempotent(
+            &mut *tx,
+            CreateTransactionParams {
+                organization_id,
+                amount: -deducted,
+                transaction_type: "refund",
+                description: Some(description),
+                reference_id: Some(reference_id),
+                balance_after: balance,
+                metadata: Some(metadata),
+            },
+        )
+        .await?;
+        if recorded.is_none() {
+            return Ok(None);
+        }
 
         tx.commit().await.context("Failed to commit transaction")?;
-        Ok(new_balance)
+        Ok(Some(RefundDeduction {
+            outstanding,
+            deducted,
+            balance,
+        }))
+    }
+
+    /// Set or clear (`None`) the low-balance alert threshold
+    ///
+    /// Re-arms the alert unless the balance is still below the new threshold
+    /// and an alert was already sent. Returns `None` if credits are not
+    /// initialized for the organization.
+    pub async fn set_low_balance_threshold(
+        pool: &DbPool,
+        organization_id: &str,
+        threshold: Option<i64>,
+    ) -> Result<Option<Credit>> {
+        let credit = sqlx::query_as::<_, Credit>(
+            r#"
+            UPDATE credits
+            SET low_balance_threshold = $2,
+                low_balance_notified_at = CASE
+                    WHEN $2::BIGINT IS NULL OR balance >= $2 THEN NULL
+                    ELSE low_balance_notified_at
+                END,
+                updated_at = NOW()
+            WHERE organization_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(threshold)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to set low balance threshold")?;
+
+        Ok(credit)
     }
 
     /// Check if organization has sufficient balance
@@ -241,32 +442,70 @@ impl TransactionRepository {
         E: Executor<'e, Database = Postgres>,
     {
         // Use INSERT ... ON CONFLICT DO NOTHING for atomic idempotency
-        // The UNIQUE index on (reference_id) WHERE reference_id IS NOT NULL AND transaction_type = 'purchase'
-        // ensures this works for purchase transactions
-        let tx = sqlx::query_as::<_, CreditTransaction>(
+        // The UNIQUE indexes on (reference_id) WHERE reference_id IS NOT NULL AND
+        // transaction_type = 'purchase' (or 'refund') ensure this works for
+        // purchase and refund transactions
+        let sql = match params.transaction_type {
+            "purchase" => {
+                r#"
+                INSERT INTO credit_transactions (
+                    organization_id, amount, transaction_type,
+                    description, reference_id, balance_after, metadata, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+                ON CONFLICT (reference_id) WHERE reference_id IS NOT NULL AND transaction_type = 'purchase'
+                DO NOTHING
+                RETURNING *
+                "#
+            }
+            "refund" => {
+                r#"
+                INSERT INTO credit_transactions (
+                    organization_id, amount, transaction_type,
+                    description, reference_id, balance_after, metadata, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+                ON CONFLICT (reference_id) WHERE reference_id IS NOT NULL AND transaction_type = 'refund'
+                DO NOTHING
+                RETURNING *
+                "#
+            }
+            other => anyhow::bail!("No idempotency index for {} transactions", other),
+        };
+        let tx = sqlx::query_as::<_, CreditTransaction>(sql)
+            .bind(params.organization_id)
+            .bind(params.amount)
+            .bind(params.transaction_type)
+            .bind(params.description)
+            .bind(params.reference_id)
+            .bind(params.balance_after)
+            .bind(params.metadata)
+            .fetch_optional(executor)
+            .await
+            .context("Failed to create idempotent transaction")?;
+
+        Ok(tx)
+    }
+
+    /// Organization whose Stripe purchase went through `payment_intent_id`
+    pub async fn find_purchase_organization(
+        pool: &DbPool,
+        payment_intent_id: &str,
+    ) -> Result<Option<String>> {
+        let organization_id = sqlx::query_scalar::<_, String>(
             r#"
-            INSERT INTO credit_transactions (
-                organization_id, amount, transaction_type,
-                description, reference_id, balance_after, metadata, created_at
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
-            ON CONFLICT (reference_id) WHERE reference_id IS NOT NULL AND transaction_type = 'purchase'
-            DO NOTHING
-            RETURNING *
+            SELECT organization_id FROM credit_transactions
+            WHERE transaction_type = 'purchase'
+              AND metadata->>'payment_intent_id' = $1
+            LIMIT 1
             "#,
         )
-        .bind(params.organization_id)
-        .bind(params.amount)
-        .bind(params.transaction_type)
-        .bind(params.description)
-        .bind(params.reference_id)
-        .bind(params.balance_after)
-        .bind(params.metadata)
-        .fetch_optional(executor)
+        .bind(payment_intent_id)
+        .fetch_optional(pool)
         .await
-        .context("Failed to create idempotent transaction")?;
+        .context("Failed to find purchase by payment intent")?;
 
-        Ok(tx)
+        Ok(organization_id)
     }
 
     /// Record a credit transaction
@@ -348,7 +587,6 @@ impl TransactionRepository {
     }
 
     /// Get transaction by ID
-    #[allow(dead_code)] // Future feature: GET /api/v1/billing/transactions/:id endpoint
     pub async fn find_by_id(pool: &DbPool, id: i64) -> Result<Option<CreditTransaction>> {
         let tx = sqlx::query_as::<_, CreditTransaction>(
             r#"
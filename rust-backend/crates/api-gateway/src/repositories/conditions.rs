@@ -97,7 +97,6 @@ impl ConditionRepository {
     }
 
     /// Find condition by ID
-    #[allow(dead_code)]
     pub async fn find_by_id(pool: &DbPool, condition_id: i32) -> Result<Option<TriggerCondition>> {
         let condition = sqlx::query_as::<_, TriggerCondition>(
             r#"
@@ -130,6 +129,26 @@ impl ConditionRepository {
         Ok(conditions)
     }
 
+    /// List conditions for several triggers with a single query
+    pub async fn list_by_triggers(
+        pool: &DbPool,
+        trigger_ids: &[String],
+    ) -> Result<Vec<TriggerCondition>> {
+        let conditions = sqlx::query_as::<_, TriggerCondition>(
+            r#"
+            SELECT * FROM trigger_conditions
+            WHERE trigger_id = ANY($1)
+            ORDER BY trigger_id, id ASC
+            "#,
+        )
+        .bind(trigger_ids)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list conditions")?;
+
+        Ok(conditions)
+    }
+
     /// Update condition
     ///
     /// Uses a safe COALESCE/CASE pattern instead of dynamic SQL.
@@ -0,0 +1,134 @@
+//! Email Verification Token Repository
+//!
+//! Stores hashed, single-use email verification tokens. A token verifies the
+//! address it was sent to, so it stops working if the user's email changes.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use shared::DbPool;
+
+pub struct EmailVerificationRepository;
+
+impl EmailVerificationRepository {
+    /// Store a new token for a user, invalidating the user's earlier tokens
+    pub async fn create(
+        pool: &DbPool,
+        user_id: &str,
+        email: &str,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        sqlx::query(
+            r#"
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to invalidate earlier verification tokens")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_verification_tokens (user_id, email, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(email)
+        .bind(token_hash)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create verification token")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// When the user's most recent token was issued
+    pub async fn last_issued_at(pool: &DbPool, user_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let issued_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            r#"
+            SELECT MAX(created_at) FROM email_verification_tokens
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to look up last verification token")?;
+
+        Ok(issued_at)
+    }
+
+    /// Consume a token and verify the address it was sent to
+    ///
+    /// Marks the token used and the user's email verified in one transaction.
+    /// Returns the user_id, or None if the token is unknown, used, expired, or
+    /// was sent to an address the user no longer has.
+    pub async fn consume(pool: &DbPool, token_hash: &str) -> Result<Option<String>> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        let token = sqlx::query_as::<_, (String, String)>(
+            r#"
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1
+              AND used_at IS NULL
+              AND expires_at > NOW()
+            RETURNING user_id, email
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to consume verification token")?;
+
+        let Some((user_id, email)) = token else {
+            tx.rollback().await.ok();
+            return Ok(None);
+        };
+
+        let verified = sqlx::query(
+            r#"
+            UPDATE users
+            SET email_verified_at = COALESCE(email_verified_at, NOW()), updated_at = NOW()
+            WHERE id = $1 AND email = $2
+            "#,
+        )
+        .bind(&user_id)
+        .bind(&email)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to mark email verified")?
+        .rows_affected()
+            > 0;
+
+        // The token is spent either way
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(verified.then_some(user_id))
+    }
+
+    /// Delete expired and used tokens (cleanup task)
+    pub async fn cleanup_expired(pool: &DbPool) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM email_verification_tokens
+            WHERE expires_at < NOW() - INTERVAL '1 day'
+               OR used_at < NOW() - INTERVAL '1 day'
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to cleanup email verification tokens")?;
+
+        Ok(result.rows_affected())
+    }
+}
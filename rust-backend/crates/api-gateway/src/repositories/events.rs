@@ -0,0 +1,65 @@
+//! Repository for events pushed by indexers
+
+use anyhow::{Context, Result};
+use shared::DbPool;
+
+use crate::models::IngestEventRequest;
+
+pub struct EventRepository;
+
+impl EventRepository {
+    /// Insert a pushed event into `events` unless its ID is already stored
+    ///
+    /// The table's `AFTER INSERT` trigger sends the `new_event` NOTIFY, so the
+    /// event-processor picks the event up as soon as the insert commits.
+    /// Returns whether a row was inserted. Two concurrent pushes of the same
+    /// event can both insert; the processor still handles each ID once.
+    pub async fn insert_if_absent(pool: &DbPool, event: &IngestEventRequest) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO events (
+                id, chain_id, block_number, block_hash, transaction_hash, log_index,
+                registry, event_type, agent_id, timestamp, owner, token_uri, metadata_key,
+                metadata_value, client_address, feedback_index, score, tag1, tag2,
+                file_uri, file_hash, validator_address, request_hash, response,
+                response_uri, response_hash, tag
+            )
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                   $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27
+            WHERE NOT EXISTS (SELECT 1 FROM events WHERE id = $1)
+            "#,
+        )
+        .bind(&event.id)
+        .bind(event.chain_id)
+        .bind(event.block_number)
+        .bind(&event.block_hash)
+        .bind(&event.transaction_hash)
+        .bind(event.log_index)
+        .bind(&event.registry)
+        .bind(&event.event_type)
+        .bind(event.agent_id)
+        .bind(event.timestamp)
+        .bind(&event.owner)
+        .bind(&event.token_uri)
+        .bind(&event.metadata_key)
+        .bind(&event.metadata_value)
+        .bind(&event.client_address)
+        .bind(event.feedback_index)
+        .bind(event.score)
+        .bind(&event.tag1)
+        .bind(&event.tag2)
+        .bind(&event.file_uri)
+        .bind(&event.file_hash)
+        .bind(&event.validator_address)
+        .bind(&event.request_hash)
+        .bind(event.response)
+        .bind(&event.response_uri)
+        .bind(&event.response_hash)
+        .bind(&event.tag)
+        .execute(pool)
+        .await
+        .context("Failed to insert pushed event")?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -1,33 +1,61 @@
 //! Repository layer for database access
+//!
+//! Repository functions take a plain `&DbPool`. Read-only queries may be
+//! given `DbPools::read()` to run against the read replica; anything that
+//! writes or opens a transaction must use the primary pool.
 
+pub mod a2a_task_events;
 pub mod a2a_tasks;
+pub mod action_webhooks;
 pub mod actions;
+pub mod agent_directory;
 pub mod agent_follows;
 pub mod agent_links;
 pub mod api_keys;
 pub mod billing;
 pub mod conditions;
+pub mod email_verification;
+pub mod events;
 pub mod oauth;
 pub mod oauth_temp_codes;
 pub mod organizations;
+pub mod password_reset;
+pub mod ponder;
+pub mod rate_limits;
 pub mod refresh_tokens;
+pub mod signing_keys;
 pub mod triggers;
 pub mod user_identities;
 pub mod users;
 pub mod wallet;
+pub mod webhooks;
 
 // Re-exports for commonly used repositories
+pub use a2a_task_events::A2aTaskEventRepository;
 pub use a2a_tasks::A2aTaskRepository;
+pub use action_webhooks::ActionWebhookRepository;
 pub use actions::ActionRepository;
+pub use agent_directory::{
+    AgentCursor, AgentDirectoryRepository, AgentDirectoryRow, AgentSearch, AgentSort,
+};
 pub use agent_follows::AgentFollowRepository;
-pub use agent_links::AgentLinkRepository;
+pub use agent_links::{AgentLinkRepository, FollowCursor, FollowedAgentRow};
 pub use api_keys::{ApiKeyAuditRepository, ApiKeyRepository, AuthFailureRepository};
 pub use billing::CreditRepository;
 pub use conditions::ConditionRepository;
+pub use email_verification::EmailVerificationRepository;
+pub use events::EventRepository;
 pub use oauth::{OAuthClientRepository, OAuthTokenRepository};
-pub use oauth_temp_codes::OAuthTempCodeRepository;
+pub use oauth_temp_codes::{AuthorizationCodeRecord, OAuthTempCodeRepository};
 pub use organizations::{MemberRepository, OrganizationRepository, OrganizationWithRole};
-pub use refresh_tokens::RefreshTokenRepository;
+pub use password_reset::PasswordResetRepository;
+pub use ponder::{
+    EventCursor, EventListFilter, EventRow, PonderEventCount, PonderEventFilter, PonderRepository,
+};
+pub use rate_limits::OrganizationRateLimitRepository;
+pub use refresh_tokens::{RefreshTokenRepository, RotateOutcome, SessionRecord};
+pub use signing_keys::SigningKeyRepository;
 pub use triggers::TriggerRepository;
 pub use user_identities::UserIdentityRepository;
-pub use users::UserRepository;
+pub use users::{FailedLogin, UserRepository};
+pub use webhooks::WebhookRepository;
@@ -45,6 +45,7 @@ impl OAuthClientRepository {
         owner_organization_id: &str,
         grant_types: &[String],
         is_trusted: bool,
+        is_public: bool,
     ) -> Result<OAuthClient> {
         let id = Uuid::new_v4().to_string();
 
@@ -53,9 +54,9 @@ impl OAuthClientRepository {
             INSERT INTO oauth_clients (
                 id, client_id, client_secret_hash, client_name,
                 redirect_uris, scopes, owner_organization_id,
-                grant_types, is_trusted
+                grant_types, is_trusted, is_public
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING *
             "#,
         )
@@ -68,6 +69,7 @@ impl OAuthClientRepository {
         .bind(owner_organization_id)
         .bind(grant_types)
         .bind(is_trusted)
+        .bind(is_public)
         .fetch_one(pool)
         .await
         .context("Failed to create OAuth client")?;
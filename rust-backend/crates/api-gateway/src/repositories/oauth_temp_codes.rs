@@ -3,6 +3,11 @@
 //! Handles storage and validation of temporary authorization codes for OAuth flows.
 //! These codes are short-lived (5 minutes) and single-use, exchanged for tokens
 //! to avoid exposing tokens in URLs.
+//!
+//! The table holds two kinds of code: social login codes (exchanged for the
+//! user's own session) and authorization codes issued to OAuth clients, which
+//! also record the client, redirect URI, grant and PKCE challenge. Each
+//! exchange only accepts its own kind.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -20,6 +25,18 @@ pub struct OAuthTempCodeRecord {
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// Authorization code issued to an OAuth client
+#[derive(Debug, Clone, FromRow)]
+pub struct AuthorizationCodeRecord {
+    pub user_id: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub organization_id: String,
+    pub scopes: Vec<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+}
+
 pub struct OAuthTempCodeRepository;
 
 impl OAuthTempCodeRepository {
@@ -57,6 +74,7 @@ impl OAuthTempCodeRepository {
             SELECT id, code_hash, user_id, expires_at, used_at, created_at
             FROM oauth_temp_codes
             WHERE code_hash = $1
+              AND client_id IS NULL
               AND expires_at > NOW()
               AND used_at IS NULL
             "#,
@@ -82,6 +100,7 @@ impl OAuthTempCodeRepository {
             SELECT id, code_hash, user_id, expires_at, used_at, created_at
             FROM oauth_temp_codes
             WHERE code_hash = $1
+              AND client_id IS NULL
               AND expires_at > NOW()
               AND used_at IS NULL
             FOR UPDATE
@@ -117,6 +136,67 @@ impl OAuthTempCodeRepository {
         Ok(Some(user_id))
     }
 
+    /// Create an authorization code for an OAuth client
+    pub async fn create_authorization_code(
+        pool: &DbPool,
+        code_hash: &str,
+        record: &AuthorizationCodeRecord,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String> {
+        let id = sqlx::query_scalar::<_, String>(
+            r#"
+            INSERT INTO oauth_temp_codes (
+                user_id, code_hash, expires_at, client_id, redirect_uri,
+                organization_id, scopes, code_challenge, code_challenge_method
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&record.user_id)
+        .bind(code_hash)
+        .bind(expires_at)
+        .bind(&record.client_id)
+        .bind(&record.redirect_uri)
+        .bind(&record.organization_id)
+        .bind(&record.scopes)
+        .bind(&record.code_challenge)
+        .bind(&record.code_challenge_method)
+        .fetch_one(pool)
+        .await
+        .context("Failed to create OAuth authorization code")?;
+
+        Ok(id)
+    }
+
+    /// Atomically find and mark an OAuth client's authorization code as used
+    ///
+    /// Returns None if the code is not found, expired or already used. The
+    /// code is consumed even if the caller then rejects the token request.
+    pub async fn exchange_authorization_code(
+        pool: &DbPool,
+        code_hash: &str,
+    ) -> Result<Option<AuthorizationCodeRecord>> {
+        let record = sqlx::query_as::<_, AuthorizationCodeRecord>(
+            r#"
+            UPDATE oauth_temp_codes
+            SET used_at = NOW()
+            WHERE code_hash = $1
+              AND client_id IS NOT NULL
+              AND expires_at > NOW()
+              AND used_at IS NULL
+            RETURNING user_id, client_id, redirect_uri, organization_id, scopes,
+                      code_challenge, code_challenge_method
+            "#,
+        )
+        .bind(code_hash)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to exchange OAuth authorization code")?;
+
+        Ok(record)
+    }
+
     /// Delete expired and used codes (cleanup task)
     pub async fn cleanup_expired(pool: &DbPool) -> Result<u64> {
         let result = sqlx::query(
@@ -509,7 +509,7 @@ impl MemberRepository {
         user_id: &str,
     ) -> Result<Option<String>> {
         // Use a separate cache key for role to store the actual role string
-        let cache_key = format!("org:role:{}:{}", org_id, user_id);
+        let cache_key = shared::redis::cache::member_role_key(org_id, user_id);
 
         // Try cache first
         if let Some(role) = cache.get::<String>(&cache_key).await {
@@ -536,7 +536,7 @@ impl MemberRepository {
         user_id: &str,
     ) {
         let membership_key = shared::redis::cache::membership_key(org_id, user_id);
-        let role_key = format!("org:role:{}:{}", org_id, user_id);
+        let role_key = shared::redis::cache::member_role_key(org_id, user_id);
         cache.delete(&membership_key).await;
         cache.delete(&role_key).await;
     }
@@ -0,0 +1,145 @@
+//! Password Reset Token Repository
+//!
+//! Stores hashed, single-use password reset tokens. Like verification tokens,
+//! a reset token is bound to the address it was sent to.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use shared::DbPool;
+
+pub struct PasswordResetRepository;
+
+impl PasswordResetRepository {
+    /// Store a new token for a user, invalidating the user's earlier tokens
+    pub async fn create(
+        pool: &DbPool,
+        user_id: &str,
+        email: &str,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        sqlx::query(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to invalidate earlier password reset tokens")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO password_reset_tokens (user_id, email, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(email)
+        .bind(token_hash)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to create password reset token")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// Consume a token and set the user's new password
+    ///
+    /// In one transaction: marks the token used, replaces the password hash,
+    /// clears any login lockout, marks the email verified (the user proved
+    /// they receive it) and revokes all of the user's refresh tokens.
+    /// Returns the user_id, or None if the token is unknown, used, expired, or
+    /// was sent to an address the user no longer has.
+    pub async fn consume(
+        pool: &DbPool,
+        token_hash: &str,
+        password_hash: &str,
+    ) -> Result<Option<String>> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        let token = sqlx::query_as::<_, (String, String)>(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1
+              AND used_at IS NULL
+              AND expires_at > NOW()
+            RETURNING user_id, email
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to consume password reset token")?;
+
+        let Some((user_id, email)) = token else {
+            tx.rollback().await.ok();
+            return Ok(None);
+        };
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE users SET
+                password_hash = $3,
+                failed_login_attempts = 0,
+                locked_until = NULL,
+                last_failed_login = NULL,
+                lockout_count = 0,
+                email_verified_at = COALESCE(email_verified_at, NOW()),
+                updated_at = NOW()
+            WHERE id = $1 AND email = $2 AND is_active = true
+            "#,
+        )
+        .bind(&user_id)
+        .bind(&email)
+        .bind(password_hash)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update password")?
+        .rows_affected()
+            > 0;
+
+        if updated {
+            sqlx::query(
+                r#"
+                UPDATE user_refresh_tokens
+                SET revoked_at = NOW()
+                WHERE user_id = $1 AND revoked_at IS NULL
+                "#,
+            )
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to revoke refresh tokens")?;
+        }
+
+        // The token is spent either way
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(updated.then_some(user_id))
+    }
+
+    /// Delete expired and used tokens (cleanup task)
+    pub async fn cleanup_expired(pool: &DbPool) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM password_reset_tokens
+            WHERE expires_at < NOW() - INTERVAL '1 day'
+               OR used_at < NOW() - INTERVAL '1 day'
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to cleanup password reset tokens")?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,370 @@
+//! Ponder event repository
+//!
+//! Queries the namespaced `{namespace}__Event` table written by the Ponder
+//! indexer. The namespace is resolved at request time, so table names are
+//! formatted into the SQL after validation and every filter value is bound.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Serialize;
+use shared::DbPool;
+use sqlx::{FromRow, Postgres, QueryBuilder};
+
+/// Event columns holding decoded arguments, matched by the text search
+const SEARCHABLE_COLUMNS: &[&str] = &[
+    "owner",
+    "agentUri",
+    "metadataKey",
+    "metadataValue",
+    "clientAddress",
+    "tag1",
+    "tag2",
+    "fileUri",
+    "endpoint",
+    "validatorAddress",
+    "requestHash",
+    "responseUri",
+    "tag",
+];
+
+/// Filters for Ponder event queries (all optional, combined with AND)
+#[derive(Debug, Clone, Default)]
+pub struct PonderEventFilter {
+    /// Registry contract (identity, reputation, validation)
+    pub registry: Option<String>,
+    /// Event name (e.g. AgentRegistered)
+    pub event_type: Option<String>,
+    /// First block to include
+    pub from_block: Option<i64>,
+    /// Last block to include
+    pub to_block: Option<i64>,
+    /// Case-insensitive substring matched against decoded event arguments
+    pub search: Option<String>,
+}
+
+/// Filters for listing individual events (all optional, combined with AND)
+#[derive(Debug, Clone, Default)]
+pub struct EventListFilter {
+    pub chain_id: Option<i64>,
+    pub registry: Option<String>,
+    pub event_type: Option<String>,
+    pub agent_id: Option<String>,
+    /// Case-insensitive pattern matched against event type, agent and registry
+    pub search: Option<String>,
+    /// Only events that ran an action of this trigger
+    pub trigger_id: Option<String>,
+    /// Only events that ran an action of one of this organization's triggers
+    pub organization_id: Option<String>,
+    /// Earliest event timestamp (Unix seconds, inclusive)
+    pub from_timestamp: Option<i64>,
+    /// Latest event timestamp (Unix seconds, inclusive)
+    pub to_timestamp: Option<i64>,
+}
+
+/// Position after the last event of a page, in listing order
+///
+/// Events are ordered by `(timestamp, block_number, id)` descending, so the
+/// cursor stays valid when newer events are indexed between requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCursor {
+    pub timestamp: i64,
+    pub block_number: i64,
+    pub id: String,
+}
+
+impl EventCursor {
+    /// Opaque string form handed to clients
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}:{}",
+            self.timestamp, self.block_number, self.id
+        ))
+    }
+
+    /// Parse a cursor produced by [`EventCursor::encode`]
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let mut parts = raw.splitn(3, ':');
+        let timestamp = parts.next()?.parse().ok()?;
+        let block_number = parts.next()?.parse().ok()?;
+        let id = parts.next().filter(|id| !id.is_empty())?.to_string();
+        Some(Self {
+            timestamp,
+            block_number,
+            id,
+        })
+    }
+}
+
+/// One indexed event as listed by `GET /events`
+#[derive(Debug, Clone, FromRow)]
+pub struct EventRow {
+    pub id: String,
+    pub chain_id: i64,
+    pub block_number: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub event_type: String,
+    pub registry: String,
+    pub agent_id: Option<String>,
+    pub timestamp: i64,
+    pub event_data: serde_json::Value,
+}
+
+/// Number of events of one type on one chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+pub struct PonderEventCount {
+    pub chain_id: i64,
+    pub event_name: String,
+    pub count: i64,
+}
+
+pub struct PonderRepository;
+
+impl PonderRepository {
+    /// Count matching events grouped by chain and event type
+    ///
+    /// Ponder only creates the primary key on `id`, so these filters scan the
+    /// table unless the indexer schema declares indexes for them. Equality
+    /// filters and block ranges benefit from a btree index on
+    /// `("chainId", "registry", "eventType", "blockNumber")`; the text search
+    /// is an `ILIKE` over several columns and needs a `pg_trgm` GIN index to
+    /// avoid a sequential scan. Keep block ranges bounded on large tables.
+    pub async fn count_events(
+        pool: &DbPool,
+        namespace: &str,
+        filter: &PonderEventFilter,
+    ) -> Result<Vec<PonderEventCount>> {
+        validate_namespace(namespace)?;
+
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            r#"SELECT "chainId"::bigint AS chain_id, "eventType" AS event_name, COUNT(*)::bigint AS count FROM public."{}__Event" WHERE TRUE"#,
+            namespace
+        ));
+
+        if let Some(registry) = &filter.registry {
+            builder.push(r#" AND "registry" = "#).push_bind(registry);
+        }
+        if let Some(event_type) = &filter.event_type {
+            builder.push(r#" AND "eventType" = "#).push_bind(event_type);
+        }
+        if let Some(from_block) = filter.from_block {
+            builder
+                .push(r#" AND "blockNumber" >= "#)
+                .push_bind(from_block);
+        }
+        if let Some(to_block) = filter.to_block {
+            builder
+                .push(r#" AND "blockNumber" <= "#)
+                .push_bind(to_block);
+        }
+        if let Some(search) = &filter.search {
+            let columns = SEARCHABLE_COLUMNS
+                .iter()
+                .map(|c| format!(r#""{}""#, c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            builder
+                .push(format!(" AND concat_ws(' ', {}) ILIKE ", columns))
+                .push_bind(format!("%{}%", escape_like(search)));
+        }
+
+        builder.push(
+            r#" GROUP BY "chainId", "eventType" ORDER BY "chainId", count DESC, "eventType""#,
+        );
+
+        let counts = builder
+            .build_query_as::<PonderEventCount>()
+            .fetch_all(pool)
+            .await
+            .context("Failed to count Ponder events")?;
+
+        Ok(counts)
+    }
+
+    /// List events newest first, starting after `after` when given
+    ///
+    /// Returns at most `limit` rows after skipping `offset`; fetch one more
+    /// than the page size to learn whether another page follows.
+    pub async fn list_events(
+        pool: &DbPool,
+        namespace: &str,
+        filter: &EventListFilter,
+        after: Option<&EventCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<EventRow>> {
+        validate_namespace(namespace)?;
+
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            r#"
+            SELECT
+                id,
+                "chainId"::bigint as chain_id,
+                block_number::bigint,
+                COALESCE("transactionHash", '') as tx_hash,
+                COALESCE(log_index, 0)::bigint as log_index,
+                "eventType" as event_type,
+                COALESCE("registry", 'unknown') as registry,
+                "agentId" as agent_id,
+                "timestamp"::bigint,
+                COALESCE("eventData", '{{}}'::jsonb) as event_data
+            FROM public."{}__Event" WHERE TRUE"#,
+            namespace
+        ));
+        push_event_list_filter(&mut builder, filter);
+
+        if let Some(cursor) = after {
+            builder
+                .push(r#" AND ("timestamp"::bigint, block_number::bigint, id) < ("#)
+                .push_bind(cursor.timestamp)
+                .push(", ")
+                .push_bind(cursor.block_number)
+                .push(", ")
+                .push_bind(&cursor.id)
+                .push(")");
+        }
+
+        builder
+            .push(r#" ORDER BY "timestamp" DESC, block_number DESC, id DESC LIMIT "#)
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        builder
+            .build_query_as::<EventRow>()
+            .fetch_all(pool)
+            .await
+            .context("Failed to list Ponder events")
+    }
+
+    /// Count the events [`PonderRepository::list_events`] would return
+    /// across all pages
+    pub async fn count_listed_events(
+        pool: &DbPool,
+        namespace: &str,
+        filter: &EventListFilter,
+    ) -> Result<i64> {
+        validate_namespace(namespace)?;
+
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            r#"SELECT COUNT(*)::bigint FROM public."{}__Event" WHERE TRUE"#,
+            namespace
+        ));
+        push_event_list_filter(&mut builder, filter);
+
+        builder
+            .build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await
+            .context("Failed to count Ponder events")
+    }
+}
+
+/// Reject namespaces that are unsafe to format into a table name
+pub(crate) fn validate_namespace(namespace: &str) -> Result<()> {
+    if namespace.is_empty()
+        || !namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        bail!("Invalid Ponder namespace: {:?}", namespace);
+    }
+    Ok(())
+}
+
+/// Append the `AND ...` clauses for an [`EventListFilter`]
+fn push_event_list_filter<'a>(
+    builder: &mut QueryBuilder<'a, Postgres>,
+    filter: &'a EventListFilter,
+) {
+    if let Some(chain_id) = filter.chain_id {
+        builder
+            .push(r#" AND "chainId"::bigint = "#)
+            .push_bind(chain_id);
+    }
+    if let Some(registry) = &filter.registry {
+        builder.push(r#" AND "registry" = "#).push_bind(registry);
+    }
+    if let Some(event_type) = &filter.event_type {
+        builder.push(r#" AND "eventType" = "#).push_bind(event_type);
+    }
+    if let Some(agent_id) = &filter.agent_id {
+        builder.push(r#" AND "agentId" = "#).push_bind(agent_id);
+    }
+    if let Some(search) = &filter.search {
+        let pattern = format!("%{}%", search);
+        builder
+            .push(r#" AND ("eventType" ILIKE "#)
+            .push_bind(pattern.clone())
+            .push(r#" OR "agentId" ILIKE "#)
+            .push_bind(pattern.clone())
+            .push(r#" OR "registry" ILIKE "#)
+            .push_bind(pattern)
+            .push(")");
+    }
+    if let Some(trigger_id) = &filter.trigger_id {
+        builder
+            .push(" AND id IN (SELECT event_id FROM action_results WHERE trigger_id = ")
+            .push_bind(trigger_id)
+            .push(")");
+    }
+    if let Some(organization_id) = &filter.organization_id {
+        builder
+            .push(
+                " AND id IN (SELECT ar.event_id FROM action_results ar \
+                 JOIN triggers t ON t.id = ar.trigger_id WHERE t.organization_id = ",
+            )
+            .push_bind(organization_id)
+            .push(")");
+    }
+    if let Some(from) = filter.from_timestamp {
+        builder
+            .push(r#" AND "timestamp"::bigint >= "#)
+            .push_bind(from);
+    }
+    if let Some(to) = filter.to_timestamp {
+        builder
+            .push(r#" AND "timestamp"::bigint <= "#)
+            .push_bind(to);
+    }
+}
+
+/// Escape `LIKE` wildcards so the search matches them literally
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_cursor_round_trip() {
+        let cursor = EventCursor {
+            timestamp: 1_700_000_000,
+            block_number: 12345,
+            id: "0xabc:1".to_string(),
+        };
+        let encoded = cursor.encode();
+        assert!(!encoded.contains(':'));
+        assert_eq!(EventCursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn test_event_cursor_rejects_garbage() {
+        assert_eq!(EventCursor::decode("not a cursor"), None);
+        assert_eq!(EventCursor::decode(&URL_SAFE_NO_PAD.encode("1:2")), None);
+        assert_eq!(EventCursor::decode(&URL_SAFE_NO_PAD.encode("1:2:")), None);
+        assert_eq!(EventCursor::decode(&URL_SAFE_NO_PAD.encode("x:2:id")), None);
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("ipfs://abc"), "ipfs://abc");
+        assert_eq!(escape_like("100%_done\\"), "100\\%\\_done\\\\");
+    }
+}
@@ -0,0 +1,104 @@
+//! Organization rate limit override repository
+
+use anyhow::{Context, Result};
+use shared::models::OrganizationRateLimit;
+use shared::redis::cache::{org_rate_limits_key, EntityCache};
+use shared::DbPool;
+
+use crate::models::RateLimitTierConfig;
+
+pub struct OrganizationRateLimitRepository;
+
+impl OrganizationRateLimitRepository {
+    /// List an organization's overrides, ordered by tier
+    pub async fn list_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Vec<OrganizationRateLimit>> {
+        let limits = sqlx::query_as::<_, OrganizationRateLimit>(
+            r#"
+            SELECT * FROM organization_rate_limits
+            WHERE organization_id = $1
+            ORDER BY tier ASC
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list organization rate limits")?;
+
+        Ok(limits)
+    }
+
+    /// List an organization's overrides (with caching)
+    ///
+    /// Organizations without overrides are cached too (as an empty list), since
+    /// this runs on every authenticated request.
+    pub async fn list_by_organization_cached(
+        pool: &DbPool,
+        cache: &EntityCache,
+        organization_id: &str,
+    ) -> Result<Vec<OrganizationRateLimit>> {
+        let cache_key = org_rate_limits_key(organization_id);
+
+        if let Some(limits) = cache.get::<Vec<OrganizationRateLimit>>(&cache_key).await {
+            return Ok(limits);
+        }
+
+        let limits = Self::list_by_organization(pool, organization_id).await?;
+        cache.set(&cache_key, &limits).await;
+
+        Ok(limits)
+    }
+
+    /// Replace all of an organization's overrides
+    ///
+    /// Runs in a transaction so the rate limiter never sees a partial update.
+    pub async fn replace_for_organization(
+        pool: &DbPool,
+        organization_id: &str,
+        limits: &[RateLimitTierConfig],
+    ) -> Result<Vec<OrganizationRateLimit>> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        sqlx::query("DELETE FROM organization_rate_limits WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear organization rate limits")?;
+
+        let mut stored = Vec::with_capacity(limits.len());
+        for limit in limits {
+            let row = sqlx::query_as::<_, OrganizationRateLimit>(
+                r#"
+                INSERT INTO organization_rate_limits (
+                    organization_id, tier, request_limit, window_seconds
+                )
+                VALUES ($1, $2, $3, $4)
+                RETURNING *
+                "#,
+            )
+            .bind(organization_id)
+            .bind(limit.tier)
+            .bind(limit.limit)
+            .bind(limit.window_seconds)
+            .fetch_one(&mut *tx)
+            .await
+            .context("Failed to insert organization rate limit")?;
+            stored.push(row);
+        }
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        stored.sort_by_key(|row| row.tier);
+        Ok(stored)
+    }
+
+    /// Invalidate the cached overrides for an organization
+    ///
+    /// Call this after any change so the rate limiter picks it up on the next
+    /// request.
+    pub async fn invalidate_cache(cache: &EntityCache, organization_id: &str) {
+        cache.delete(&org_rate_limits_key(organization_id)).await;
+    }
+}
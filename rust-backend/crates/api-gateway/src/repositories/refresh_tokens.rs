@@ -1,11 +1,18 @@
 //! Refresh Token Repository
 //!
 //! Handles storage and validation of user refresh tokens for JWT authentication.
+//!
+//! Tokens are grouped into families: a login starts one (its `family_id` is
+//! the first token's ID) and each rotation adds the replacement to it, linking
+//! the old token through `replaced_by`. A family is what users see as a login
+//! session: it has at most one live token, and revoking the family logs the
+//! session out.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use shared::DbPool;
 use sqlx::FromRow;
+use uuid::Uuid;
 
 /// Refresh token record from database
 #[derive(Debug, FromRow)]
@@ -18,12 +25,51 @@ pub struct RefreshTokenRecord {
     pub revoked_at: Option<DateTime<Utc>>,
     pub user_agent: Option<String>,
     pub ip_address: Option<String>,
+    /// First token of the login session this token was rotated from
+    pub family_id: String,
+    /// Token that replaced this one on rotation
+    pub replaced_by: Option<String>,
+    /// When the login that started the family happened
+    pub session_started_at: DateTime<Utc>,
+}
+
+/// Login session: the live token of a refresh token family
+#[derive(Debug, FromRow)]
+pub struct SessionRecord {
+    /// Family ID
+    pub id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// When the live token was issued (login or last refresh)
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Outcome of [`RefreshTokenRepository::atomic_rotate`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum RotateOutcome {
+    /// The token was valid and has been replaced
+    Rotated {
+        user_id: String,
+        family_id: String,
+        new_token_id: String,
+    },
+    /// The token had already been replaced: its family has been revoked
+    Reused {
+        user_id: String,
+        family_id: String,
+        /// Live tokens of the family that were revoked
+        revoked: u64,
+    },
+    /// Unknown, expired or revoked (not by rotation)
+    Invalid,
 }
 
 pub struct RefreshTokenRepository;
 
 impl RefreshTokenRepository {
-    /// Create a new refresh token
+    /// Create a new refresh token, starting a new family
     pub async fn create(
         pool: &DbPool,
         user_id: &str,
@@ -34,11 +80,12 @@ impl RefreshTokenRepository {
     ) -> Result<String> {
         let id = sqlx::query_scalar::<_, String>(
             r#"
-            INSERT INTO user_refresh_tokens (user_id, token_hash, expires_at, user_agent, ip_address)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO user_refresh_tokens (id, family_id, user_id, token_hash, expires_at, user_agent, ip_address)
+            VALUES ($1, $1, $2, $3, $4, $5, $6)
             RETURNING id
             "#,
         )
+        .bind(Uuid::new_v4().to_string())
         .bind(user_id)
         .bind(token_hash)
         .bind(expires_at)
@@ -58,7 +105,8 @@ impl RefreshTokenRepository {
     ) -> Result<Option<RefreshTokenRecord>> {
         let result = sqlx::query_as::<_, RefreshTokenRecord>(
             r#"
-            SELECT id, user_id, token_hash, expires_at, created_at, revoked_at, user_agent, ip_address
+            SELECT id, user_id, token_hash, expires_at, created_at, revoked_at, user_agent, ip_address,
+                   family_id, replaced_by, session_started_at
             FROM user_refresh_tokens
             WHERE token_hash = $1
               AND expires_at > NOW()
@@ -107,6 +155,75 @@ impl RefreshTokenRepository {
         Ok(result.rows_affected())
     }
 
+    /// List a user's active sessions, most recently used first
+    pub async fn list_sessions(pool: &DbPool, user_id: &str) -> Result<Vec<SessionRecord>> {
+        let sessions = sqlx::query_as::<_, SessionRecord>(
+            r#"
+            SELECT family_id AS id, user_agent, ip_address,
+                   session_started_at AS started_at,
+                   COALESCE(created_at, session_started_at) AS last_used_at,
+                   expires_at
+            FROM user_refresh_tokens
+            WHERE user_id = $1
+              AND expires_at > NOW()
+              AND revoked_at IS NULL
+            ORDER BY last_used_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list sessions")?;
+
+        Ok(sessions)
+    }
+
+    /// Revoke one of a user's sessions (its whole token family)
+    ///
+    /// Returns false if the user has no active session with that ID.
+    pub async fn revoke_session(pool: &DbPool, user_id: &str, family_id: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_refresh_tokens
+            SET revoked_at = NOW()
+            WHERE family_id = $1 AND user_id = $2 AND revoked_at IS NULL
+              AND expires_at > NOW()
+            "#,
+        )
+        .bind(family_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .context("Failed to revoke session")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revoke every session of a user except `keep_family_id`
+    ///
+    /// Returns the number of sessions revoked.
+    pub async fn revoke_other_sessions(
+        pool: &DbPool,
+        user_id: &str,
+        keep_family_id: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND family_id <> $2 AND revoked_at IS NULL
+              AND expires_at > NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(keep_family_id)
+        .execute(pool)
+        .await
+        .context("Failed to revoke other sessions")?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Delete expired and revoked tokens (cleanup task)
     pub async fn cleanup_expired(pool: &DbPool) -> Result<u64> {
         let result = sqlx::query(
@@ -147,7 +264,9 @@ impl RefreshTokenRepository {
     /// Uses SELECT ... FOR UPDATE to prevent race conditions where two concurrent
     /// requests could both validate the same token before either revokes it.
     ///
-    /// Returns (user_id, new_token_id) on success.
+    /// A token that was already rotated is being reused (it was copied, or the
+    /// copy was used first): every live token of its family is revoked in the
+    /// same transaction and [`RotateOutcome::Reused`] returned.
     pub async fn atomic_rotate(
         pool: &DbPool,
         token_hash: &str,
@@ -155,17 +274,17 @@ impl RefreshTokenRepository {
         new_expires_at: DateTime<Utc>,
         user_agent: Option<&str>,
         ip_address: Option<&str>,
-    ) -> Result<Option<(String, String)>> {
+    ) -> Result<RotateOutcome> {
         let mut tx = pool.begin().await.context("Failed to begin transaction")?;
 
-        // Find and lock the token row (FOR UPDATE prevents concurrent access)
+        // Find and lock the token row (FOR UPDATE prevents concurrent access).
+        // Revoked and expired tokens are included to detect reuse.
         let record = sqlx::query_as::<_, RefreshTokenRecord>(
             r#"
-            SELECT id, user_id, token_hash, expires_at, created_at, revoked_at, user_agent, ip_address
+            SELECT id, user_id, token_hash, expires_at, created_at, revoked_at, user_agent, ip_address,
+                   family_id, replaced_by, session_started_at
             FROM user_refresh_tokens
             WHERE token_hash = $1
-              AND expires_at > NOW()
-              AND revoked_at IS NULL
             FOR UPDATE
             "#,
         )
@@ -178,45 +297,80 @@ impl RefreshTokenRepository {
             Some(r) => r,
             None => {
                 tx.rollback().await.ok();
-                return Ok(None);
+                return Ok(RotateOutcome::Invalid);
             }
         };
 
-        let user_id = record.user_id.clone();
+        if record.replaced_by.is_some() {
+            let revoked = sqlx::query(
+                r#"
+                UPDATE user_refresh_tokens
+                SET revoked_at = NOW()
+                WHERE family_id = $1 AND revoked_at IS NULL
+                "#,
+            )
+            .bind(&record.family_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to revoke refresh token family")?
+            .rows_affected();
 
-        // Revoke the old token
-        sqlx::query(
-            r#"
-            UPDATE user_refresh_tokens
-            SET revoked_at = NOW()
-            WHERE id = $1
-            "#,
-        )
-        .bind(&record.id)
-        .execute(&mut *tx)
-        .await
-        .context("Failed to revoke old refresh token")?;
+            tx.commit().await.context("Failed to commit transaction")?;
+
+            return Ok(RotateOutcome::Reused {
+                user_id: record.user_id,
+                family_id: record.family_id,
+                revoked,
+            });
+        }
 
-        // Create the new token
+        if record.revoked_at.is_some() || record.expires_at <= Utc::now() {
+            tx.rollback().await.ok();
+            return Ok(RotateOutcome::Invalid);
+        }
+
+        // Create the new token in the same family
         let new_id = sqlx::query_scalar::<_, String>(
             r#"
-            INSERT INTO user_refresh_tokens (user_id, token_hash, expires_at, user_agent, ip_address)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO user_refresh_tokens
+                (id, family_id, user_id, token_hash, expires_at, user_agent, ip_address, session_started_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id
             "#,
         )
-        .bind(&user_id)
+        .bind(Uuid::new_v4().to_string())
+        .bind(&record.family_id)
+        .bind(&record.user_id)
         .bind(new_token_hash)
         .bind(new_expires_at)
         .bind(user_agent)
         .bind(ip_address)
+        .bind(record.session_started_at)
         .fetch_one(&mut *tx)
         .await
         .context("Failed to create new refresh token")?;
 
+        // Revoke the old token, linking it to its replacement
+        sqlx::query(
+            r#"
+            UPDATE user_refresh_tokens
+            SET revoked_at = NOW(), replaced_by = $2
+            WHERE id = $1
+            "#,
+        )
+        .bind(&record.id)
+        .bind(&new_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to revoke old refresh token")?;
+
         tx.commit().await.context("Failed to commit transaction")?;
 
-        Ok(Some((user_id, new_id)))
+        Ok(RotateOutcome::Rotated {
+            user_id: record.user_id,
+            family_id: record.family_id,
+            new_token_id: new_id,
+        })
     }
 
     /// Revoke oldest sessions if user exceeds max allowed sessions
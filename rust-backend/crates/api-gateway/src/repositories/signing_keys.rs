@@ -0,0 +1,156 @@
+//! Organization signing key repository
+
+use anyhow::{Context, Result};
+use shared::models::OrganizationSigningKey;
+use shared::DbPool;
+
+use super::action_webhooks::generate_secret;
+
+/// Characters of the secret kept in `key_prefix` (`whsec_` + 8 hex chars)
+const KEY_PREFIX_LENGTH: usize = 14;
+
+pub struct SigningKeyRepository;
+
+impl SigningKeyRepository {
+    /// List an organization's keys, newest first
+    ///
+    /// Includes retired keys whose grace window has ended, as an audit trail.
+    pub async fn list_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Vec<OrganizationSigningKey>> {
+        let keys = sqlx::query_as::<_, OrganizationSigningKey>(
+            r#"
+            SELECT * FROM organization_signing_keys
+            WHERE organization_id = $1
+            ORDER BY created_at DESC, id
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list signing keys")?;
+
+        Ok(keys)
+    }
+
+    /// Create the organization's first signing key
+    ///
+    /// Returns `None` if the organization already has a current key (rotate it
+    /// instead).
+    pub async fn create(
+        pool: &DbPool,
+        organization_id: &str,
+        created_by: &str,
+    ) -> Result<Option<OrganizationSigningKey>> {
+        let secret = generate_secret();
+
+        let key = sqlx::query_as::<_, OrganizationSigningKey>(
+            r#"
+            INSERT INTO organization_signing_keys (id, organization_id, secret, key_prefix, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (organization_id) WHERE retired_at IS NULL DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(organization_id)
+        .bind(&secret)
+        .bind(key_prefix(&secret))
+        .bind(created_by)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to create signing key")?;
+
+        Ok(key)
+    }
+
+    /// Replace the current key with a new one
+    ///
+    /// The previous key keeps signing for `grace_period_secs` (0 revokes it
+    /// immediately). Keys retired by earlier rotations stop signing now, so at
+    /// most two keys are ever valid. Returns `(new, previous)`, or `None` if the
+    /// organization has no current key.
+    pub async fn rotate(
+        pool: &DbPool,
+        organization_id: &str,
+        grace_period_secs: i64,
+        created_by: &str,
+    ) -> Result<Option<(OrganizationSigningKey, OrganizationSigningKey)>> {
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+        let previous = sqlx::query_as::<_, OrganizationSigningKey>(
+            r#"
+            UPDATE organization_signing_keys
+            SET retired_at = NOW(),
+                expires_at = NOW() + make_interval(secs => $2)
+            WHERE organization_id = $1 AND retired_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(grace_period_secs as f64)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to retire current signing key")?;
+
+        let Some(previous) = previous else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE organization_signing_keys
+            SET expires_at = NOW()
+            WHERE organization_id = $1 AND id <> $2 AND expires_at > NOW()
+            "#,
+        )
+        .bind(organization_id)
+        .bind(&previous.id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to expire older signing keys")?;
+
+        let secret = generate_secret();
+        let current = sqlx::query_as::<_, OrganizationSigningKey>(
+            r#"
+            INSERT INTO organization_signing_keys (id, organization_id, secret, key_prefix, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(organization_id)
+        .bind(&secret)
+        .bind(key_prefix(&secret))
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to insert signing key")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(Some((current, previous)))
+    }
+}
+
+/// Leading characters identifying a key in listings
+fn key_prefix(secret: &str) -> String {
+    secret.chars().take(KEY_PREFIX_LENGTH).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_prefix_hides_secret() {
+        let secret = generate_secret();
+        let prefix = key_prefix(&secret);
+
+        assert_eq!(prefix.len(), KEY_PREFIX_LENGTH);
+        assert!(prefix.starts_with("whsec_"));
+        assert!(secret.starts_with(&prefix));
+        assert!(secret.len() > prefix.len() + 32);
+    }
+}
@@ -1,8 +1,9 @@
 //! Trigger repository for database operations
 
 use anyhow::{Context, Result};
-use shared::models::Trigger;
-use shared::DbPool;
+use shared::models::{Trigger, TriggerState};
+use shared::{DbPool, TriggerSchedule};
+use sqlx::types::Json;
 use sqlx::{Executor, Postgres};
 use uuid::Uuid;
 
@@ -23,14 +24,16 @@ impl TriggerRepository {
         registry: &str,
         enabled: bool,
         is_stateful: bool,
+        cooldown_secs: i32,
+        schedule: Option<&TriggerSchedule>,
     ) -> Result<Trigger> {
         let trigger_id = Uuid::new_v4().to_string();
         let now = chrono::Utc::now();
 
         let trigger = sqlx::query_as::<_, Trigger>(
             r#"
-            INSERT INTO triggers (id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            INSERT INTO triggers (id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful, cooldown_secs, schedule, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#,
         )
@@ -43,6 +46,8 @@ impl TriggerRepository {
         .bind(registry)
         .bind(enabled)
         .bind(is_stateful)
+        .bind(cooldown_secs)
+        .bind(schedule.map(Json))
         .bind(now)
         .bind(now)
         .fetch_one(pool)
@@ -66,6 +71,8 @@ impl TriggerRepository {
         registry: &str,
         enabled: bool,
         is_stateful: bool,
+        cooldown_secs: i32,
+        schedule: Option<&TriggerSchedule>,
     ) -> Result<Trigger>
     where
         E: Executor<'e, Database = Postgres>,
@@ -75,8 +82,8 @@ impl TriggerRepository {
 
         let trigger = sqlx::query_as::<_, Trigger>(
             r#"
-            INSERT INTO triggers (id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            INSERT INTO triggers (id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful, cooldown_secs, schedule, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#,
         )
@@ -89,6 +96,8 @@ impl TriggerRepository {
         .bind(registry)
         .bind(enabled)
         .bind(is_stateful)
+        .bind(cooldown_secs)
+        .bind(schedule.map(Json))
         .bind(now)
         .bind(now)
         .fetch_one(executor)
@@ -146,12 +155,12 @@ impl TriggerRepository {
         Ok(())
     }
 
-    /// Find trigger by ID
+    /// Find trigger by ID (soft-deleted triggers are not found)
     pub async fn find_by_id(pool: &DbPool, trigger_id: &str) -> Result<Option<Trigger>> {
         let trigger = sqlx::query_as::<_, Trigger>(
             r#"
             SELECT * FROM triggers
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(trigger_id)
@@ -173,7 +182,7 @@ impl TriggerRepository {
         let triggers = sqlx::query_as::<_, Trigger>(
             r#"
             SELECT * FROM triggers
-            WHERE user_id = $1
+            WHERE user_id = $1 AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
             "#,
@@ -189,21 +198,25 @@ impl TriggerRepository {
     }
 
     /// List triggers for an organization with pagination
+    ///
+    /// Soft-deleted triggers are left out unless `include_deleted` is set.
     pub async fn list_by_organization(
         pool: &DbPool,
         organization_id: &str,
+        include_deleted: bool,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Trigger>> {
         let triggers = sqlx::query_as::<_, Trigger>(
             r#"
             SELECT * FROM triggers
-            WHERE organization_id = $1
+            WHERE organization_id = $1 AND ($2 OR deleted_at IS NULL)
             ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
+            LIMIT $3 OFFSET $4
             "#,
         )
         .bind(organization_id)
+        .bind(include_deleted)
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
@@ -213,13 +226,57 @@ impl TriggerRepository {
         Ok(triggers)
     }
 
+    /// List every live trigger for an organization (oldest first, no pagination)
+    ///
+    /// Used by bulk export; callers should not expose the result unpaginated.
+    pub async fn list_all_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Vec<Trigger>> {
+        let triggers = sqlx::query_as::<_, Trigger>(
+            r#"
+            SELECT * FROM triggers
+            WHERE organization_id = $1 AND deleted_at IS NULL
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list triggers")?;
+
+        Ok(triggers)
+    }
+
+    /// IDs of every trigger an organization has owned, soft-deleted included
+    ///
+    /// Used to attribute dead-lettered jobs that predate `organization_id`
+    /// on jobs.
+    pub async fn list_ids_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT id FROM triggers
+            WHERE organization_id = $1
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list trigger IDs")?;
+
+        Ok(ids)
+    }
+
     /// Count total triggers for a user (deprecated, use count_by_organization)
     #[allow(dead_code)]
     pub async fn count_by_user(pool: &DbPool, user_id: &str) -> Result<i64> {
         let count = sqlx::query_scalar::<_, i64>(
             r#"
             SELECT COUNT(*) FROM triggers
-            WHERE user_id = $1
+            WHERE user_id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(user_id)
@@ -231,14 +288,21 @@ impl TriggerRepository {
     }
 
     /// Count total triggers for an organization
-    pub async fn count_by_organization(pool: &DbPool, organization_id: &str) -> Result<i64> {
+    ///
+    /// Soft-deleted triggers are left out unless `include_deleted` is set.
+    pub async fn count_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+        include_deleted: bool,
+    ) -> Result<i64> {
         let count = sqlx::query_scalar::<_, i64>(
             r#"
             SELECT COUNT(*) FROM triggers
-            WHERE organization_id = $1
+            WHERE organization_id = $1 AND ($2 OR deleted_at IS NULL)
             "#,
         )
         .bind(organization_id)
+        .bind(include_deleted)
         .fetch_one(pool)
         .await
         .context("Failed to count triggers")?;
@@ -251,8 +315,8 @@ impl TriggerRepository {
     /// Uses a safe COALESCE/CASE pattern instead of dynamic SQL to prevent potential issues
     /// and make the query easier to audit.
     ///
-    /// - Simple fields (`name`, `chain_id`, `registry`, `enabled`, `is_stateful`): `None` = keep existing, `Some(value)` = update
-    /// - `description`: `None` = keep existing, `Some(None)` = set to NULL, `Some(Some(value))` = update to value
+    /// - Simple fields (`name`, `chain_id`, `registry`, `enabled`, `is_stateful`, `cooldown_secs`): `None` = keep existing, `Some(value)` = update
+    /// - `description`, `schedule`: `None` = keep existing, `Some(None)` = set to NULL, `Some(Some(value))` = update to value
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &DbPool,
@@ -263,12 +327,15 @@ impl TriggerRepository {
         registry: Option<&str>,
         enabled: Option<bool>,
         is_stateful: Option<bool>,
+        cooldown_secs: Option<i32>,
+        schedule: Option<Option<&TriggerSchedule>>,
     ) -> Result<Trigger> {
         let now = chrono::Utc::now();
 
         // Use a static query with COALESCE/CASE patterns for safe updates
         // $1 = updated_at, $2 = name, $3 = should_update_description, $4 = description,
-        // $5 = chain_id, $6 = registry, $7 = enabled, $8 = is_stateful, $9 = trigger_id
+        // $5 = chain_id, $6 = registry, $7 = enabled, $8 = is_stateful, $9 = cooldown_secs,
+        // $10 = should_update_schedule, $11 = schedule, $12 = trigger_id
         let trigger = sqlx::query_as::<_, Trigger>(
             r#"
             UPDATE triggers SET
@@ -278,8 +345,10 @@ impl TriggerRepository {
                 chain_id = COALESCE($5, chain_id),
                 registry = COALESCE($6, registry),
                 enabled = COALESCE($7, enabled),
-                is_stateful = COALESCE($8, is_stateful)
-            WHERE id = $9
+                is_stateful = COALESCE($8, is_stateful),
+                cooldown_secs = COALESCE($9, cooldown_secs),
+                schedule = CASE WHEN $10 THEN $11 ELSE schedule END
+            WHERE id = $12
             RETURNING *
             "#,
         )
@@ -291,6 +360,9 @@ impl TriggerRepository {
         .bind(registry)
         .bind(enabled)
         .bind(is_stateful)
+        .bind(cooldown_secs)
+        .bind(schedule.is_some())
+        .bind(schedule.flatten().map(Json))
         .bind(trigger_id)
         .fetch_one(pool)
         .await
@@ -299,12 +371,17 @@ impl TriggerRepository {
         Ok(trigger)
     }
 
-    /// Delete trigger (cascade will handle conditions and actions)
-    pub async fn delete(pool: &DbPool, trigger_id: &str) -> Result<bool> {
+    /// Soft-delete a trigger
+    ///
+    /// The trigger stops matching events and disappears from lookups, but
+    /// keeps its conditions and actions until [`Self::purge_deleted`] removes
+    /// it. Returns false if the trigger does not exist or is already deleted.
+    pub async fn soft_delete(pool: &DbPool, trigger_id: &str) -> Result<bool> {
         let result = sqlx::query(
             r#"
-            DELETE FROM triggers
-            WHERE id = $1
+            UPDATE triggers SET
+                deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(trigger_id)
@@ -315,6 +392,50 @@ impl TriggerRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Restore a soft-deleted trigger of an organization
+    ///
+    /// Returns `None` if there is no deleted trigger with this ID in the
+    /// organization (never existed, not deleted, or already purged).
+    pub async fn restore(
+        pool: &DbPool,
+        trigger_id: &str,
+        organization_id: &str,
+    ) -> Result<Option<Trigger>> {
+        let trigger = sqlx::query_as::<_, Trigger>(
+            r#"
+            UPDATE triggers SET
+                deleted_at = NULL
+            WHERE id = $1 AND organization_id = $2 AND deleted_at IS NOT NULL
+            RETURNING *
+            "#,
+        )
+        .bind(trigger_id)
+        .bind(organization_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to restore trigger")?;
+
+        Ok(trigger)
+    }
+
+    /// Permanently delete triggers soft-deleted more than `retention_days` ago
+    ///
+    /// Conditions, actions and state are removed by cascade.
+    pub async fn purge_deleted(pool: &DbPool, retention_days: i32) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM triggers
+            WHERE deleted_at < NOW() - INTERVAL '1 day' * $1
+            "#,
+        )
+        .bind(retention_days as f64)
+        .execute(pool)
+        .await
+        .context("Failed to purge deleted triggers")?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Check if trigger belongs to user (deprecated, use belongs_to_organization)
     #[allow(dead_code)]
     pub async fn belongs_to_user(pool: &DbPool, trigger_id: &str, user_id: &str) -> Result<bool> {
@@ -322,7 +443,7 @@ impl TriggerRepository {
             r#"
             SELECT EXISTS(
                 SELECT 1 FROM triggers
-                WHERE id = $1 AND user_id = $2
+                WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
             )
             "#,
         )
@@ -335,7 +456,7 @@ impl TriggerRepository {
         Ok(result)
     }
 
-    /// Check if trigger belongs to an organization
+    /// Check if a live (not soft-deleted) trigger belongs to an organization
     pub async fn belongs_to_organization(
         pool: &DbPool,
         trigger_id: &str,
@@ -345,7 +466,7 @@ impl TriggerRepository {
             r#"
             SELECT EXISTS(
                 SELECT 1 FROM triggers
-                WHERE id = $1 AND organization_id = $2
+                WHERE id = $1 AND organization_id = $2 AND deleted_at IS NULL
             )
             "#,
         )
@@ -358,6 +479,25 @@ impl TriggerRepository {
         Ok(result)
     }
 
+    /// Get the stateful evaluator state for a trigger
+    ///
+    /// Returns `None` until the event processor first evaluates the trigger.
+    pub async fn get_state(pool: &DbPool, trigger_id: &str) -> Result<Option<TriggerState>> {
+        let state = sqlx::query_as::<_, TriggerState>(
+            r#"
+            SELECT trigger_id, state_data, last_updated, last_fired_at
+            FROM trigger_state
+            WHERE trigger_id = $1
+            "#,
+        )
+        .bind(trigger_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch trigger state")?;
+
+        Ok(state)
+    }
+
     /// Get circuit breaker info for a trigger
     ///
     /// Returns trigger name along with circuit breaker config and state.
@@ -64,7 +64,7 @@ impl UserIdentityRepository {
     {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let provider_user_id = format!("{}:{}", chain_id, wallet_address.to_lowercase());
+        let provider_user_id = wallet_provider_user_id(wallet_address, chain_id);
 
         let identity = sqlx::query_as::<_, UserIdentity>(
             r#"
@@ -111,6 +111,8 @@ impl UserIdentityRepository {
     }
 
     /// Find identity by wallet address and chain
+    ///
+    /// Addresses match case-insensitively (checksummed or not).
     pub async fn find_by_wallet(
         pool: &DbPool,
         wallet_address: &str,
@@ -120,12 +122,34 @@ impl UserIdentityRepository {
             r#"
             SELECT * FROM user_identities
             WHERE provider = 'wallet'
-              AND wallet_address = $1
-              AND chain_id = $2
+              AND provider_user_id = $1
+            "#,
+        )
+        .bind(wallet_provider_user_id(wallet_address, chain_id))
+        .fetch_optional(pool)
+        .await
+        .context("Failed to find identity by wallet")?;
+
+        Ok(identity)
+    }
+
+    /// Find the oldest identity of a wallet address on any chain
+    ///
+    /// Used to link a wallet's first login on a new chain to the same user.
+    pub async fn find_by_wallet_any_chain(
+        pool: &DbPool,
+        wallet_address: &str,
+    ) -> Result<Option<UserIdentity>> {
+        let identity = sqlx::query_as::<_, UserIdentity>(
+            r#"
+            SELECT * FROM user_identities
+            WHERE provider = 'wallet'
+              AND LOWER(wallet_address) = LOWER($1)
+            ORDER BY created_at ASC
+            LIMIT 1
             "#,
         )
         .bind(wallet_address)
-        .bind(chain_id)
         .fetch_optional(pool)
         .await
         .context("Failed to find identity by wallet")?;
@@ -268,14 +292,34 @@ impl UserIdentityRepository {
     }
 }
 
+/// Provider user ID of a wallet identity: `{chain_id}:{lowercase address}`
+fn wallet_provider_user_id(wallet_address: &str, chain_id: i32) -> String {
+    format!("{}:{}", chain_id, wallet_address.to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_provider_user_id_format() {
         // Wallet provider_user_id format
-        let chain_id = 1;
-        let wallet = "0x1234567890abcdef";
-        let provider_user_id = format!("{}:{}", chain_id, wallet.to_lowercase());
-        assert_eq!(provider_user_id, "1:0x1234567890abcdef");
+        assert_eq!(
+            wallet_provider_user_id("0x1234567890abcdef", 1),
+            "1:0x1234567890abcdef"
+        );
+    }
+
+    #[test]
+    fn test_provider_user_id_normalizes_address() {
+        let checksummed = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb4";
+        assert_eq!(
+            wallet_provider_user_id(checksummed, 8453),
+            wallet_provider_user_id(&checksummed.to_lowercase(), 8453)
+        );
+        assert_ne!(
+            wallet_provider_user_id(checksummed, 8453),
+            wallet_provider_user_id(checksummed, 1)
+        );
     }
 }
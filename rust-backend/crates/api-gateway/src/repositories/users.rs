@@ -1,13 +1,71 @@
 //! User repository for database operations
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use shared::models::User;
-use shared::DbPool;
+use shared::{AuthConfig, DbPool};
 use sqlx::{Executor, Postgres};
 use uuid::Uuid;
 
 pub struct UserRepository;
 
+/// Outcome of [`UserRepository::record_failed_login`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedLogin {
+    /// Failed attempts counted toward the next lockout (0 right after a lockout)
+    pub attempts: i32,
+    /// Set if this failure locked the account
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+/// Lockout columns of a user
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+struct LockoutState {
+    failed_login_attempts: i32,
+    last_failed_login: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+    lockout_count: i32,
+}
+
+impl LockoutState {
+    /// State after one more failed login at `now`
+    fn after_failure(&self, policy: &AuthConfig, now: DateTime<Utc>) -> Self {
+        // The window runs from the last failure, or from the end of a lockout
+        let last_activity = match (self.last_failed_login, self.locked_until) {
+            (Some(failed), Some(until)) => Some(failed.max(until)),
+            (failed, until) => failed.or(until),
+        };
+        let window =
+            chrono::Duration::from_std(policy.attempt_window()).unwrap_or(chrono::Duration::MAX);
+        let forgotten = last_activity.is_none_or(|at| now - at > window);
+
+        let (attempts, lockouts) = if forgotten {
+            (0, 0)
+        } else {
+            (self.failed_login_attempts, self.lockout_count)
+        };
+        let attempts = attempts + 1;
+
+        if attempts < policy.max_failed_attempts as i32 {
+            return Self {
+                failed_login_attempts: attempts,
+                last_failed_login: Some(now),
+                locked_until: self.locked_until,
+                lockout_count: lockouts,
+            };
+        }
+
+        let duration = chrono::Duration::from_std(policy.lockout_duration(lockouts as u32))
+            .unwrap_or(chrono::Duration::MAX);
+        Self {
+            failed_login_attempts: 0,
+            last_failed_login: Some(now),
+            locked_until: Some(now + duration),
+            lockout_count: lockouts + 1,
+        }
+    }
+}
+
 impl UserRepository {
     /// Create a new user
     #[allow(dead_code)]
@@ -156,6 +214,7 @@ impl UserRepository {
     ///
     /// This function creates a user without a password for social-only authentication.
     /// The user can later add a password or link additional providers.
+    /// `email_verified` is whether the provider verified the email address.
     pub async fn create_social_user<'e, E>(
         executor: E,
         username: &str,
@@ -163,6 +222,7 @@ impl UserRepository {
         primary_auth_provider: &str,
         avatar_url: Option<&str>,
         display_name: Option<&str>,
+        email_verified: bool,
     ) -> Result<User>
     where
         E: Executor<'e, Database = Postgres>,
@@ -175,9 +235,9 @@ impl UserRepository {
             INSERT INTO users (
                 id, username, email, password_hash,
                 created_at, updated_at, is_active,
-                primary_auth_provider, avatar_url, display_name
+                primary_auth_provider, avatar_url, display_name, email_verified_at
             )
-            VALUES ($1, $2, $3, NULL, $4, $5, true, $6, $7, $8)
+            VALUES ($1, $2, $3, NULL, $4, $5, true, $6, $7, $8, $9)
             RETURNING *
             "#,
         )
@@ -189,6 +249,7 @@ impl UserRepository {
         .bind(primary_auth_provider)
         .bind(avatar_url)
         .bind(display_name)
+        .bind(email_verified.then_some(now))
         .fetch_one(executor)
         .await
         .context("Failed to create social user")?;
@@ -196,79 +257,96 @@ impl UserRepository {
         Ok(user)
     }
 
+    /// Mark the user's current email address as verified
+    pub async fn mark_email_verified(pool: &DbPool, user_id: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET email_verified_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND email_verified_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .context("Failed to mark email verified")?;
+
+        Ok(())
+    }
+
     // ========================================================================
     // Account Lockout Methods
     // ========================================================================
 
     /// Record a failed login attempt and potentially lock the account
     ///
-    /// Implements progressive lockout:
-    /// - Threshold: 5 failed attempts
-    /// - Lockout duration: 15min base, doubles each time (up to 4h max)
-    ///
-    /// Returns the number of failed attempts after this failure.
-    pub async fn record_failed_login(pool: &DbPool, user_id: &str) -> Result<i32> {
-        let now = chrono::Utc::now();
-
-        // Lockout configuration constants
-        const MAX_FAILED_ATTEMPTS: i32 = 5;
-        const BASE_LOCKOUT_MINUTES: i64 = 15;
-        const MAX_LOCKOUT_MULTIPLIER: i32 = 4; // Max 4h lockout (15 * 2^4 = 240min)
+    /// Applies `policy` (see [`AuthConfig`]): attempts older than the window
+    /// are forgotten, and reaching the threshold locks the account for a
+    /// duration that doubles with each lockout since the last successful
+    /// login. The row is locked while it is updated, so concurrent failures
+    /// are all counted.
+    pub async fn record_failed_login(
+        pool: &DbPool,
+        user_id: &str,
+        policy: &AuthConfig,
+    ) -> Result<FailedLogin> {
+        let now = Utc::now();
+        let mut tx = pool.begin().await.context("Failed to begin transaction")?;
 
-        // Get current failed attempts to calculate lockout duration
-        let current = sqlx::query_scalar::<_, i32>(
-            r#"SELECT failed_login_attempts FROM users WHERE id = $1"#,
+        let current = sqlx::query_as::<_, LockoutState>(
+            r#"
+            SELECT failed_login_attempts, last_failed_login, locked_until, lockout_count
+            FROM users WHERE id = $1
+            FOR UPDATE
+            "#,
         )
         .bind(user_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await
         .context("Failed to get current failed attempts")?;
 
-        let new_attempts = current + 1;
-
-        // Calculate lockout time if threshold reached
-        let locked_until = if new_attempts >= MAX_FAILED_ATTEMPTS {
-            // Progressive lockout: 15min, 30min, 60min, 120min, 240min (max 4h)
-            let lockout_cycles = ((new_attempts - MAX_FAILED_ATTEMPTS) / MAX_FAILED_ATTEMPTS)
-                .min(MAX_LOCKOUT_MULTIPLIER);
-            let multiplier = 2_i64.pow(lockout_cycles as u32);
-            let lockout_duration = chrono::Duration::minutes(BASE_LOCKOUT_MINUTES * multiplier);
-            Some(now + lockout_duration)
-        } else {
-            None
-        };
+        let next = current.after_failure(policy, now);
 
         sqlx::query(
             r#"
             UPDATE users SET
                 failed_login_attempts = $1,
                 last_failed_login = $2,
-                locked_until = $3
-            WHERE id = $4
+                locked_until = $3,
+                lockout_count = $4
+            WHERE id = $5
             "#,
         )
-        .bind(new_attempts)
+        .bind(next.failed_login_attempts)
         .bind(now)
-        .bind(locked_until)
+        .bind(next.locked_until)
+        .bind(next.lockout_count)
         .bind(user_id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
         .context("Failed to record failed login")?;
 
-        Ok(new_attempts)
+        tx.commit().await.context("Failed to commit transaction")?;
+
+        Ok(FailedLogin {
+            attempts: next.failed_login_attempts,
+            locked_until: next.locked_until.filter(|until| *until > now),
+        })
     }
 
     /// Reset failed login attempts (call on successful login)
     ///
-    /// This clears the failed_login_attempts counter, locked_until, and last_failed_login
-    /// fields, effectively unlocking the account.
+    /// This clears the failed_login_attempts counter, locked_until, last_failed_login
+    /// and lockout_count fields, effectively unlocking the account and restarting
+    /// the lockout progression.
     pub async fn reset_failed_login(pool: &DbPool, user_id: &str) -> Result<()> {
         sqlx::query(
             r#"
             UPDATE users SET
                 failed_login_attempts = 0,
                 locked_until = NULL,
-                last_failed_login = NULL
+                last_failed_login = NULL,
+                lockout_count = 0
             WHERE id = $1
             "#,
         )
@@ -308,3 +386,111 @@ impl UserRepository {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AuthConfig {
+        AuthConfig {
+            max_failed_attempts: 3,
+            lockout_secs: 60,
+            attempt_window_secs: 600,
+        }
+    }
+
+    fn fresh() -> LockoutState {
+        LockoutState {
+            failed_login_attempts: 0,
+            last_failed_login: None,
+            locked_until: None,
+            lockout_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_crossing_threshold_locks_account() {
+        let now = Utc::now();
+        let mut state = fresh();
+
+        for expected in 1..3 {
+            state = state.after_failure(&policy(), now);
+            assert_eq!(state.failed_login_attempts, expected);
+            assert_eq!(state.locked_until, None);
+        }
+
+        state = state.after_failure(&policy(), now);
+        assert_eq!(
+            state.locked_until,
+            Some(now + chrono::Duration::seconds(60))
+        );
+        assert_eq!(state.failed_login_attempts, 0);
+        assert_eq!(state.lockout_count, 1);
+    }
+
+    #[test]
+    fn test_repeated_lockouts_double_duration() {
+        let mut now = Utc::now();
+        let mut state = fresh();
+        let mut durations = Vec::new();
+
+        for _ in 0..6 {
+            for _ in 0..3 {
+                state = state.after_failure(&policy(), now);
+            }
+            let until = state.locked_until.unwrap();
+            durations.push((until - now).num_seconds());
+            // Retry as soon as the lock ends
+            now = until;
+        }
+
+        assert_eq!(durations, vec![60, 120, 240, 480, 960, 960]);
+    }
+
+    #[test]
+    fn test_attempts_outside_window_are_forgotten() {
+        let start = Utc::now();
+        let mut state = fresh();
+        state = state.after_failure(&policy(), start);
+        state = state.after_failure(&policy(), start);
+
+        let later = start + chrono::Duration::seconds(601);
+        state = state.after_failure(&policy(), later);
+        assert_eq!(state.failed_login_attempts, 1);
+        assert_eq!(state.locked_until, None);
+    }
+
+    #[test]
+    fn test_lockout_progression_resets_after_quiet_window() {
+        let start = Utc::now();
+        let mut state = LockoutState {
+            failed_login_attempts: 0,
+            last_failed_login: Some(start),
+            locked_until: Some(start + chrono::Duration::seconds(60)),
+            lockout_count: 3,
+        };
+
+        // Within the window after the lock ends, the next lockout is longer
+        let soon = start + chrono::Duration::seconds(120);
+        let mut next = state.clone();
+        for _ in 0..3 {
+            next = next.after_failure(&policy(), soon);
+        }
+        assert_eq!(next.lockout_count, 4);
+        assert_eq!(
+            next.locked_until,
+            Some(soon + chrono::Duration::seconds(480))
+        );
+
+        // After a quiet window, the progression starts over
+        let much_later = start + chrono::Duration::seconds(60 + 601);
+        for _ in 0..3 {
+            state = state.after_failure(&policy(), much_later);
+        }
+        assert_eq!(state.lockout_count, 1);
+        assert_eq!(
+            state.locked_until,
+            Some(much_later + chrono::Duration::seconds(60))
+        );
+    }
+}
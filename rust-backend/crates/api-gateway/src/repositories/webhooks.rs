@@ -0,0 +1,158 @@
+//! Trigger fire webhook repository
+
+use anyhow::{Context, Result};
+use shared::models::{Webhook, WebhookDelivery};
+use shared::DbPool;
+
+use super::action_webhooks::generate_secret;
+
+pub struct WebhookRepository;
+
+impl WebhookRepository {
+    /// Register a webhook with a newly generated signing secret
+    pub async fn create(
+        pool: &DbPool,
+        organization_id: &str,
+        url: &str,
+        event_types: &[String],
+        description: Option<&str>,
+        enabled: bool,
+        created_by: &str,
+    ) -> Result<Webhook> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            INSERT INTO webhooks (id, organization_id, url, secret, event_types, description, enabled, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(organization_id)
+        .bind(url)
+        .bind(generate_secret())
+        .bind(event_types)
+        .bind(description)
+        .bind(enabled)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await
+        .context("Failed to create webhook")?;
+
+        Ok(webhook)
+    }
+
+    /// List an organization's webhooks, newest first
+    pub async fn list_by_organization(
+        pool: &DbPool,
+        organization_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            r#"
+            SELECT * FROM webhooks
+            WHERE organization_id = $1
+            ORDER BY created_at DESC, id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(organization_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list webhooks")?;
+
+        Ok(webhooks)
+    }
+
+    /// Count an organization's webhooks
+    pub async fn count_by_organization(pool: &DbPool, organization_id: &str) -> Result<i64> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM webhooks WHERE organization_id = $1")
+                .bind(organization_id)
+                .fetch_one(pool)
+                .await
+                .context("Failed to count webhooks")?;
+
+        Ok(count.0)
+    }
+
+    /// Get a webhook if it belongs to the organization
+    pub async fn find_by_id_for_organization(
+        pool: &DbPool,
+        id: &str,
+        organization_id: &str,
+    ) -> Result<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "SELECT * FROM webhooks WHERE id = $1 AND organization_id = $2",
+        )
+        .bind(id)
+        .bind(organization_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to fetch webhook")?;
+
+        Ok(webhook)
+    }
+
+    /// Delete a webhook and its delivery history, returning whether it existed
+    pub async fn delete(pool: &DbPool, id: &str, organization_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1 AND organization_id = $2")
+            .bind(id)
+            .bind(organization_id)
+            .execute(pool)
+            .await
+            .context("Failed to delete webhook")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List a webhook's deliveries, newest first
+    pub async fn list_deliveries(
+        pool: &DbPool,
+        webhook_id: &str,
+        status: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT * FROM webhook_deliveries
+            WHERE webhook_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list webhook deliveries")?;
+
+        Ok(deliveries)
+    }
+
+    /// Count a webhook's deliveries
+    pub async fn count_deliveries(
+        pool: &DbPool,
+        webhook_id: &str,
+        status: Option<&str>,
+    ) -> Result<i64> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM webhook_deliveries
+            WHERE webhook_id = $1 AND ($2::TEXT IS NULL OR status = $2)
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count webhook deliveries")?;
+
+        Ok(count.0)
+    }
+}
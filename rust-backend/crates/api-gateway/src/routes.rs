@@ -2,9 +2,15 @@
 
 use actix_web::web;
 
+use crate::middleware::body_limit::{
+    AUTH_BODY_LIMIT, EVENT_INGEST_BODY_LIMIT, TRIGGER_IMPORT_BODY_LIMIT,
+};
 use crate::{handlers, middleware};
 
 /// Configure all routes
+///
+/// JSON bodies are capped at 1MB app-wide; routes with a different cap set
+/// it here (see [`middleware::body_limit`]).
 pub fn configure(cfg: &mut web::ServiceConfig) {
     // Get JWT secret from config (will be passed from app_data)
     let jwt_secret = std::env::var("JWT_SECRET")
@@ -22,6 +28,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::scope("/api/v1")
             // Health check endpoint (no auth required)
             .route("/health", web::get().to(handlers::health_check))
+            .route("/health/live", web::get().to(handlers::liveness))
+            .route("/health/ready", web::get().to(handlers::readiness))
             // OpenAPI JSON endpoint (no auth required - used by Swagger UI)
             .route("/openapi.json", web::get().to(handlers::openapi_json))
             // Ponder indexer status endpoints (no auth required - for monitoring)
@@ -30,9 +38,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route("/status", web::get().to(handlers::get_ponder_status))
                     .route("/events", web::get().to(handlers::get_ponder_events)),
             )
+            // Agent discovery search (no auth required)
+            .route("/discovery", web::get().to(handlers::search_agents))
             // Authentication endpoints (no auth required)
             .service(
                 web::scope("/auth")
+                    .app_data(json_limit(AUTH_BODY_LIMIT))
                     .route("/register", web::post().to(handlers::register))
                     .route("/login", web::post().to(handlers::login))
                     // SIWE wallet login
@@ -43,6 +54,24 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route("/logout", web::post().to(handlers::logout))
                     .route("/refresh", web::post().to(handlers::refresh_token))
                     .route("/exchange", web::post().to(handlers::exchange_code))
+                    // Email verification
+                    .route("/verify-email", web::post().to(handlers::verify_email))
+                    .route(
+                        "/resend-verification",
+                        web::post().to(handlers::resend_verification),
+                    )
+                    // Password reset
+                    .route(
+                        "/forgot-password",
+                        web::post().to(handlers::forgot_password),
+                    )
+                    .route("/reset-password", web::post().to(handlers::reset_password))
+                    .route("/sessions", web::get().to(handlers::list_sessions))
+                    .route(
+                        "/sessions",
+                        web::delete().to(handlers::revoke_other_sessions),
+                    )
+                    .route("/sessions/{id}", web::delete().to(handlers::revoke_session))
                     // Social login endpoints (OAuth 2.0)
                     .route("/google", web::get().to(handlers::google_auth))
                     .route("/google/callback", web::get().to(handlers::google_callback))
@@ -53,12 +82,41 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route("/link/github", web::get().to(handlers::link_github)),
             )
             // OAuth token endpoints (public - client credentials auth)
-            .route("/oauth/token", web::post().to(handlers::token_endpoint))
+            .service(
+                web::resource("/oauth/token")
+                    .app_data(json_limit(AUTH_BODY_LIMIT))
+                    .route(web::post().to(handlers::token_endpoint)),
+            )
             // Stripe webhook (no auth - uses signature verification)
             .route(
                 "/billing/webhook",
                 web::post().to(handlers::handle_stripe_webhook),
             )
+            // Pushed events from indexers (no auth - uses signature verification)
+            .service(
+                web::resource("/events/ingest")
+                    .app_data(web::PayloadConfig::new(EVENT_INGEST_BODY_LIMIT))
+                    .route(web::post().to(handlers::ingest_event)),
+            )
+            // Operator endpoints (X-Admin-Token, checked in the handlers)
+            .service(
+                web::scope("/admin")
+                    .route(
+                        "/organizations/{id}/rate-limits",
+                        web::get().to(handlers::get_org_rate_limits),
+                    )
+                    .route(
+                        "/organizations/{id}/rate-limits",
+                        web::put().to(handlers::set_org_rate_limits),
+                    )
+                    .route("/cache/flush", web::post().to(handlers::flush_cache))
+                    .route("/kill-switch", web::get().to(handlers::get_kill_switch))
+                    .route("/kill-switch", web::put().to(handlers::set_kill_switch))
+                    .route(
+                        "/kill-switch",
+                        web::delete().to(handlers::release_kill_switch),
+                    ),
+            )
             // Protected routes (JWT or API Key auth)
             .service(
                 web::scope("")
@@ -91,14 +149,52 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                             .route("/{id}/api-keys", web::get().to(handlers::list_org_api_keys))
                             .route(
                                 "/{id}/api-keys",
-                                web::post().to(handlers::create_org_api_key),
+                                web::post().to(handlers::create_org_api_key).wrap(
+                                    middleware::idempotency::Idempotency::new()
+                                        .redact(handlers::CREATED_KEY_POINTER),
+                                ),
                             )
                             .route(
                                 "/{id}/api-keys/stats",
                                 web::get().to(handlers::get_org_api_key_stats),
                             )
+                            // Action result webhook (customer observability)
+                            .route(
+                                "/{id}/action-webhook",
+                                web::get().to(handlers::get_action_webhook),
+                            )
+                            .route(
+                                "/{id}/action-webhook",
+                                web::put().to(handlers::set_action_webhook),
+                            )
+                            .route(
+                                "/{id}/action-webhook",
+                                web::delete().to(handlers::delete_action_webhook),
+                            )
+                            // Keys signing the organization's outbound deliveries
+                            .route(
+                                "/{id}/signing-keys",
+                                web::get().to(handlers::list_signing_keys),
+                            )
+                            .route(
+                                "/{id}/signing-keys",
+                                web::post().to(handlers::create_signing_key),
+                            )
+                            .route(
+                                "/{id}/signing-keys/rotate",
+                                web::post().to(handlers::rotate_signing_key),
+                            )
                             // Triggers nested under organization
                             .route("/{id}/triggers", web::get().to(handlers::list_org_triggers))
+                            .route(
+                                "/{id}/triggers/export",
+                                web::get().to(handlers::export_org_triggers),
+                            )
+                            .service(
+                                web::resource("/{id}/triggers/import")
+                                    .app_data(json_limit(TRIGGER_IMPORT_BODY_LIMIT))
+                                    .route(web::post().to(handlers::import_org_triggers)),
+                            )
                             // Agents nested under organization
                             .route("/{id}/agents", web::get().to(handlers::list_org_agents))
                             // Credits nested under organization
@@ -106,6 +202,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                                 "/{id}/credits/balance",
                                 web::get().to(handlers::get_org_credits),
                             )
+                            .route(
+                                "/{id}/credits/low-balance-threshold",
+                                web::put().to(handlers::set_low_balance_threshold),
+                            )
                             .route(
                                 "/{id}/credits/transactions",
                                 web::get().to(handlers::list_org_transactions),
@@ -114,7 +214,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     // API Key endpoints (standalone - for backwards compat)
                     .service(
                         web::scope("/api-keys")
-                            .route("", web::post().to(handlers::create_api_key))
+                            .route(
+                                "",
+                                web::post().to(handlers::create_api_key).wrap(
+                                    middleware::idempotency::Idempotency::new()
+                                        .redact(handlers::CREATED_KEY_POINTER),
+                                ),
+                            )
                             .route("", web::get().to(handlers::list_api_keys))
                             .route("/{id}", web::get().to(handlers::get_api_key))
                             .route("/{id}", web::patch().to(handlers::update_api_key))
@@ -122,6 +228,18 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                             .route("/{id}/rotate", web::post().to(handlers::rotate_api_key))
                             .route("/{id}/regenerate", web::post().to(handlers::rotate_api_key)),
                     )
+                    // Trigger fire webhooks (organization from X-Organization-ID)
+                    .service(
+                        web::scope("/webhooks")
+                            .route("", web::post().to(handlers::create_webhook))
+                            .route("", web::get().to(handlers::list_webhooks))
+                            .route("/{id}", web::get().to(handlers::get_webhook))
+                            .route("/{id}", web::delete().to(handlers::delete_webhook))
+                            .route(
+                                "/{id}/deliveries",
+                                web::get().to(handlers::list_webhook_deliveries),
+                            ),
+                    )
                     // OAuth client management endpoints (JWT auth required)
                     .service(
                         web::scope("/oauth/clients")
@@ -129,6 +247,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                             .route("", web::get().to(handlers::list_oauth_clients))
                             .route("/{id}", web::delete().to(handlers::delete_oauth_client)),
                     )
+                    // OAuth authorization endpoint (JWT auth required - the approving user)
+                    .route("/oauth/authorize", web::post().to(handlers::authorize))
                     // Agent endpoints
                     .service(
                         web::scope("/agents")
@@ -145,17 +265,57 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                                 web::delete().to(handlers::unfollow_agent),
                             ),
                     )
+                    // On-chain lookups
+                    .service(
+                        web::scope("/chains/{chain_id}")
+                            .route("/balances/{address}", web::get().to(handlers::get_balance))
+                            .route(
+                                "/transactions/{tx_hash}",
+                                web::get().to(handlers::get_transaction_status),
+                            ),
+                    )
                     // Billing endpoints
                     .service(
                         web::scope("/billing")
-                            .route("/credits", web::get().to(handlers::get_credits))
+                            .route(
+                                "/credits",
+                                web::get().to(handlers::get_credits).wrap(
+                                    billing_query_param_deprecation(
+                                        "/api/v1/organizations/{id}/credits/balance",
+                                    ),
+                                ),
+                            )
                             .route(
                                 "/credits/purchase",
-                                web::post().to(handlers::purchase_credits),
+                                web::post()
+                                    .to(handlers::purchase_credits)
+                                    .wrap(middleware::idempotency::Idempotency::new()),
+                            )
+                            .route(
+                                "/transactions",
+                                web::get().to(handlers::list_transactions).wrap(
+                                    billing_query_param_deprecation(
+                                        "/api/v1/organizations/{id}/credits/transactions",
+                                    ),
+                                ),
+                            )
+                            .route(
+                                "/transactions/{id}/receipt",
+                                web::get().to(handlers::get_transaction_receipt),
                             )
-                            .route("/transactions", web::get().to(handlers::list_transactions))
                             .route("/subscription", web::get().to(handlers::get_subscription)),
                     )
+                    // Action result summaries (organization from X-Organization-ID)
+                    .route(
+                        "/actions/{id}/results/summary",
+                        web::get().to(handlers::get_action_result_summary),
+                    )
+                    // Dead letter queue (organization from X-Organization-ID)
+                    .service(
+                        web::scope("/dlq")
+                            .route("", web::get().to(handlers::list_dlq_entries))
+                            .route("/{id}/replay", web::post().to(handlers::replay_dlq_entry)),
+                    )
                     // Events endpoint (blockchain events from Ponder)
                     .route("/events", web::get().to(handlers::list_events))
                     // A2A Protocol endpoints (JSON-RPC 2.0)
@@ -163,19 +323,35 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                         web::scope("/a2a")
                             .route("/rpc", web::post().to(handlers::a2a_rpc))
                             .route("/tasks/{id}", web::get().to(handlers::get_task_status))
+                            .route("/tasks/{id}/cancel", web::post().to(handlers::cancel_task))
                             .route(
                                 "/tasks/{id}/stream",
                                 web::get().to(handlers::stream_task_progress),
                             ),
                     )
+                    // Condition validation (nothing is saved)
+                    .route(
+                        "/conditions/validate",
+                        web::post().to(handlers::validate_condition),
+                    )
                     // Trigger endpoints
                     .service(
                         web::scope("/triggers")
                             .route("", web::post().to(handlers::create_trigger))
                             .route("", web::get().to(handlers::list_triggers))
+                            // Before /{id} so "export" is not taken for a trigger id
+                            .route("/export", web::get().to(handlers::export_triggers))
+                            .service(
+                                web::resource("/import")
+                                    .app_data(json_limit(TRIGGER_IMPORT_BODY_LIMIT))
+                                    .route(web::post().to(handlers::import_triggers)),
+                            )
                             .route("/{id}", web::get().to(handlers::get_trigger))
                             .route("/{id}", web::put().to(handlers::update_trigger))
                             .route("/{id}", web::delete().to(handlers::delete_trigger))
+                            .route("/{id}/restore", web::post().to(handlers::restore_trigger))
+                            .route("/{id}/fire", web::post().to(handlers::fire_trigger))
+                            .route("/{id}/state", web::get().to(handlers::get_trigger_state))
                             // Circuit breaker management endpoints
                             .route(
                                 "/{id}/circuit-breaker",
@@ -222,8 +398,23 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                             .route(
                                 "/{trigger_id}/actions/{id}",
                                 web::delete().to(handlers::delete_action),
+                            )
+                            .route(
+                                "/{trigger_id}/actions/{id}/verify",
+                                web::post().to(handlers::verify_action),
                             ),
                     ),
             ),
     );
 }
+
+/// Billing reads taking `?organization_id=`, replaced by the path-based
+/// `/organizations/{id}/credits/*` routes
+fn billing_query_param_deprecation(successor: &str) -> middleware::deprecation::Deprecated {
+    middleware::deprecation::Deprecated::new("2026-10-15", "2027-04-15").successor(successor)
+}
+
+/// JSON extractor config capping bodies at `limit` bytes
+fn json_limit(limit: usize) -> web::JsonConfig {
+    web::JsonConfig::default().limit(limit)
+}
@@ -10,24 +10,54 @@
 //! 3. Executes the query using QueryExecutor
 //! 4. Updates task with result/error and sets `completed_at`
 //!
+//! Steps 2 and 4 also append an event to `a2a_task_events`, which the SSE
+//! stream replays to clients that reconnect with `Last-Event-ID`.
+//!
 //! ## Concurrency
 //!
-//! - Single processor instance to avoid duplicate processing
-//! - Database-level locking via status update
-//! - Future: Add worker pool for parallel processing
+//! - Database-level locking via status update (`FOR UPDATE SKIP LOCKED`)
+//! - Up to `max_concurrent_tasks` tasks execute at once; a task is only
+//!   claimed when a slot is free, so the rest stay 'submitted' in the queue
+//! - At most `max_dequeue_per_sec` tasks are claimed per second (token bucket)
+//! - `a2a.processor.tasks_in_flight` reports the tasks executing
+//!
+//! ## Cancellation
+//!
+//! Each task being worked has a cancellation token in [`A2aTaskCancellations`],
+//! shared with the API handlers. Cancelling a task marks it 'cancelled' in the
+//! database and triggers its token, which stops execution. Results are only
+//! written while a task is still 'working', so a task cancelled on another
+//! instance is never overwritten.
+//!
+//! ## Shutdown
+//!
+//! When the processor is stopped it claims no more tasks and waits up to
+//! `shutdown_timeout` for the tasks it is executing. Tasks still running
+//! after that are aborted and returned to 'submitted', so another instance
+//! (or this one after a restart) picks them up instead of leaving them
+//! 'working' forever.
 
 use metrics::{counter, gauge, histogram};
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::{JoinHandle, JoinSet};
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, trace, warn};
 
+use shared::error::is_transient_db_error;
 use shared::DbPool;
 use uuid::Uuid;
 
 use super::a2a_audit::A2aAuditService;
 use super::query_executor::QueryExecutor;
 use super::tool_registry::ToolRegistry;
+use crate::models::a2a::{A2aTask, TaskGetResult, TaskStatus};
+use crate::repositories::a2a_tasks::TASK_COLUMNS;
+use crate::repositories::A2aTaskEventRepository;
 
 /// Default poll interval for checking new tasks
 const DEFAULT_POLL_INTERVAL_SECS: u64 = 1;
@@ -35,6 +65,15 @@ const DEFAULT_POLL_INTERVAL_SECS: u64 = 1;
 /// Maximum tasks to claim per poll cycle
 const MAX_TASKS_PER_CYCLE: i64 = 10;
 
+/// Default maximum tasks executing at once
+const DEFAULT_MAX_CONCURRENT_TASKS: usize = 4;
+
+/// Default maximum tasks dequeued per second
+const DEFAULT_MAX_DEQUEUE_PER_SEC: u32 = 10;
+
+/// Default time executing tasks get to finish on shutdown
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 10;
+
 /// Query execution timeout (prevents stuck queries)
 const QUERY_EXECUTION_TIMEOUT_SECS: u64 = 30;
 
@@ -51,6 +90,13 @@ pub struct A2aTaskProcessorConfig {
     pub poll_interval: Duration,
     /// Maximum tasks to claim per cycle
     pub max_tasks_per_cycle: i64,
+    /// Maximum tasks executing at once
+    pub max_concurrent_tasks: usize,
+    /// Maximum tasks dequeued per second
+    pub max_dequeue_per_sec: u32,
+    /// How long executing tasks get to finish on shutdown before they are
+    /// requeued
+    pub shutdown_timeout: Duration,
 }
 
 impl Default for A2aTaskProcessorConfig {
@@ -59,21 +105,131 @@ impl Default for A2aTaskProcessorConfig {
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        let max_concurrent_tasks = std::env::var("A2A_MAX_CONCURRENT_TASKS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_TASKS);
+        let max_dequeue_per_sec = std::env::var("A2A_MAX_DEQUEUE_PER_SEC")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_DEQUEUE_PER_SEC);
+        let shutdown_timeout_secs = std::env::var("A2A_SHUTDOWN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS);
 
         Self {
             poll_interval: Duration::from_secs(poll_interval_secs),
             max_tasks_per_cycle: MAX_TASKS_PER_CYCLE,
+            max_concurrent_tasks,
+            max_dequeue_per_sec,
+            shutdown_timeout: Duration::from_secs(shutdown_timeout_secs),
         }
     }
 }
 
+/// Limits how many tasks are dequeued per second
+///
+/// Token bucket holding up to one second's worth of tasks, so short bursts
+/// are allowed but the long-run rate is capped.
+#[derive(Debug)]
+struct DequeueRateGate {
+    per_sec: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl DequeueRateGate {
+    fn new(per_sec: u32, now: Instant) -> Self {
+        let per_sec = f64::from(per_sec.max(1));
+        Self {
+            per_sec,
+            tokens: per_sec,
+            refilled_at: now,
+        }
+    }
+
+    /// Tasks that may be dequeued at `now`
+    fn available(&mut self, now: Instant) -> usize {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.per_sec).min(self.per_sec);
+        self.refilled_at = now;
+        self.tokens.floor() as usize
+    }
+
+    /// Record `count` dequeued tasks
+    fn consume(&mut self, count: usize) {
+        self.tokens = (self.tokens - count as f64).max(0.0);
+    }
+}
+
+/// Cancellation tokens of the tasks this instance is working on
+///
+/// Cloning is cheap; clones share the same tokens.
+#[derive(Debug, Clone, Default)]
+pub struct A2aTaskCancellations {
+    tokens: Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
+}
+
+impl A2aTaskCancellations {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop working a task
+    ///
+    /// Returns `false` if this instance is not working the task.
+    pub fn cancel(&self, task_id: &Uuid) -> bool {
+        match self.lock().remove(task_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Track a task being worked; returns its token
+    fn register(&self, task_id: Uuid) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.lock().insert(task_id, token.clone());
+        token
+    }
+
+    /// Stop tracking a task once it is no longer worked
+    fn remove(&self, task_id: &Uuid) {
+        self.lock().remove(task_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, CancellationToken>> {
+        // The map stays consistent even if a holder panicked
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Tasks executing on this instance
+#[derive(Debug, Default)]
+struct InFlightTasks {
+    tasks: JoinSet<()>,
+    /// IDs of the claimed tasks; a task removes its ID once it finishes
+    task_ids: HashSet<Uuid>,
+}
+
 /// A2A Task Processor
 ///
 /// Runs in the background and processes A2A tasks.
+#[derive(Clone)]
 pub struct A2aTaskProcessor {
     pool: DbPool,
     config: A2aTaskProcessorConfig,
     executor: QueryExecutor,
+    cancellations: A2aTaskCancellations,
+    /// One permit per task slot
+    slots: Arc<Semaphore>,
+    in_flight: Arc<Mutex<InFlightTasks>>,
 }
 
 impl A2aTaskProcessor {
@@ -85,22 +241,37 @@ impl A2aTaskProcessor {
     /// Create a new task processor with custom configuration
     pub fn with_config(pool: DbPool, config: A2aTaskProcessorConfig) -> Self {
         let executor = QueryExecutor::new(pool.clone());
+        let slots = Arc::new(Semaphore::new(config.max_concurrent_tasks.max(1)));
         Self {
             pool,
             config,
             executor,
+            cancellations: A2aTaskCancellations::new(),
+            slots,
+            in_flight: Arc::default(),
         }
     }
 
+    /// Use a cancellation registry shared with the API handlers
+    pub fn with_cancellations(mut self, cancellations: A2aTaskCancellations) -> Self {
+        self.cancellations = cancellations;
+        self
+    }
+
     /// Start the task processor
     ///
-    /// Runs until the cancellation token is triggered.
+    /// Runs until the cancellation token is triggered, then waits for the
+    /// tasks it is executing (see [`Self::shutdown`]).
     pub async fn run(&self, cancel_token: CancellationToken) {
         let mut poll_interval = interval(self.config.poll_interval);
+        let mut rate_gate = DequeueRateGate::new(self.config.max_dequeue_per_sec, Instant::now());
 
         info!(
             poll_interval_ms = ?self.config.poll_interval.as_millis(),
             max_tasks_per_cycle = self.config.max_tasks_per_cycle,
+            max_concurrent_tasks = self.config.max_concurrent_tasks,
+            max_dequeue_per_sec = self.config.max_dequeue_per_sec,
+            shutdown_timeout_secs = self.config.shutdown_timeout.as_secs(),
             "A2A Task Processor started"
         );
 
@@ -111,20 +282,79 @@ impl A2aTaskProcessor {
                     break;
                 }
                 _ = poll_interval.tick() => {
-                    if let Err(e) = self.process_pending_tasks().await {
+                    if let Err(e) = self.process_pending_tasks(&mut rate_gate).await {
                         error!(error = %e, "Error processing A2A tasks");
                     }
                 }
             }
         }
 
+        self.shutdown().await;
         info!("A2A Task Processor stopped");
     }
 
+    /// Wait for executing tasks, then requeue those that did not finish
+    ///
+    /// Tasks still running after `shutdown_timeout` are aborted and set back
+    /// to 'submitted' (unless they were cancelled or finished meanwhile).
+    async fn shutdown(&self) {
+        let unfinished = self.drain(self.config.shutdown_timeout).await;
+        if unfinished.is_empty() {
+            return;
+        }
+
+        match self.requeue_tasks(&unfinished).await {
+            Ok(requeued) => warn!(
+                unfinished = unfinished.len(),
+                requeued = requeued,
+                "A2A tasks did not finish before shutdown, returned to the queue"
+            ),
+            Err(e) => error!(
+                error = %e,
+                unfinished = unfinished.len(),
+                "Failed to requeue A2A tasks interrupted by shutdown"
+            ),
+        }
+    }
+
+    /// Wait up to `timeout` for executing tasks, then abort the rest
+    ///
+    /// Returns the IDs of the tasks that did not finish.
+    async fn drain(&self, timeout: Duration) -> Vec<Uuid> {
+        let mut tasks = std::mem::take(&mut self.lock_in_flight().tasks);
+        if !tasks.is_empty() {
+            info!(in_flight = tasks.len(), "Waiting for A2A tasks to finish");
+            let finished = tokio::time::timeout(timeout, async {
+                while let Some(result) = tasks.join_next().await {
+                    log_task_panic(result);
+                }
+            })
+            .await;
+            if finished.is_err() {
+                tasks.shutdown().await;
+            }
+        }
+
+        self.lock_in_flight().task_ids.drain().collect()
+    }
+
     /// Process pending tasks
-    async fn process_pending_tasks(&self) -> anyhow::Result<()> {
+    ///
+    /// Claims no more tasks than there are free slots and the rate allows;
+    /// the rest wait in the queue for a later cycle.
+    async fn process_pending_tasks(&self, rate_gate: &mut DequeueRateGate) -> anyhow::Result<()> {
+        let limit = self.dequeue_limit(rate_gate, Instant::now());
+        if limit == 0 {
+            trace!(
+                in_flight = self.in_flight(),
+                "A2A task processor at capacity, leaving tasks queued"
+            );
+            return Ok(());
+        }
+
         // Claim tasks atomically by updating status from 'submitted' to 'working'
-        let tasks = self.claim_tasks().await?;
+        let tasks = self.claim_tasks(limit as i64).await?;
+        rate_gate.consume(tasks.len());
 
         if tasks.is_empty() {
             // Use trace! to avoid log spam - this polls every second
@@ -136,14 +366,72 @@ impl A2aTaskProcessor {
         gauge!("a2a.processor.tasks_claimed").set(tasks.len() as f64);
         info!(count = tasks.len(), "Processing A2A tasks");
 
-        // Process each task
+        // Process each task in its own slot
         for task in tasks {
-            self.process_task(&task).await;
+            let processor = self.clone();
+            self.spawn_in_slot(task.id, async move { processor.process_task(&task).await })
+                .await;
         }
 
         Ok(())
     }
 
+    /// Tasks that may be claimed now: bounded by free slots, the dequeue
+    /// rate and the per-cycle maximum
+    fn dequeue_limit(&self, rate_gate: &mut DequeueRateGate, now: Instant) -> usize {
+        let max_per_cycle = usize::try_from(self.config.max_tasks_per_cycle).unwrap_or(0);
+        self.slots
+            .available_permits()
+            .min(rate_gate.available(now))
+            .min(max_per_cycle)
+    }
+
+    /// Run the work of task `task_id` in the background once a slot is free,
+    /// holding the slot until it finishes
+    async fn spawn_in_slot<F>(&self, task_id: Uuid, work: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let permit = self
+            .slots
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("task slots are never closed");
+        self.report_in_flight();
+
+        let processor = self.clone();
+        let mut in_flight = self.lock_in_flight();
+        while let Some(result) = in_flight.tasks.try_join_next() {
+            log_task_panic(result);
+        }
+        in_flight.task_ids.insert(task_id);
+        in_flight.tasks.spawn(async move {
+            work.await;
+            processor.lock_in_flight().task_ids.remove(&task_id);
+            drop(permit);
+            processor.report_in_flight();
+        });
+    }
+
+    fn lock_in_flight(&self) -> std::sync::MutexGuard<'_, InFlightTasks> {
+        // The set stays consistent even if a holder panicked
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Number of tasks executing
+    pub fn in_flight(&self) -> usize {
+        self.config
+            .max_concurrent_tasks
+            .max(1)
+            .saturating_sub(self.slots.available_permits())
+    }
+
+    fn report_in_flight(&self) {
+        // METRICS: Tasks executing right now
+        gauge!("a2a.processor.tasks_in_flight").set(self.in_flight() as f64);
+    }
+
     /// Claim tasks for processing with retry logic for transient errors
     ///
     /// SECURITY FIX: Uses a single atomic CTE to prevent race conditions.
@@ -153,7 +441,7 @@ impl A2aTaskProcessor {
     ///
     /// Includes retry logic for transient database errors (connection issues,
     /// deadlocks, etc.) with exponential backoff.
-    async fn claim_tasks(&self) -> anyhow::Result<Vec<ClaimedTask>> {
+    async fn claim_tasks(&self, limit: i64) -> anyhow::Result<Vec<ClaimedTask>> {
         let mut retries = 0;
 
         loop {
@@ -174,13 +462,13 @@ impl A2aTaskProcessor {
                 RETURNING t.id, t.organization_id, t.tool, t.arguments
                 "#,
             )
-            .bind(self.config.max_tasks_per_cycle)
+            .bind(limit)
             .fetch_all(&self.pool)
             .await;
 
             match result {
                 Ok(tasks) => return Ok(tasks),
-                Err(e) if is_transient_error(&e) && retries < MAX_DB_RETRIES => {
+                Err(e) if is_transient_db_error(&e) && retries < MAX_DB_RETRIES => {
                     retries += 1;
                     let delay_ms = RETRY_BASE_DELAY_MS * (1 << retries); // Exponential backoff
                     warn!(
@@ -219,15 +507,45 @@ impl A2aTaskProcessor {
             warn!("Failed to log task started audit: {:?}", e);
         }
 
+        // EVENTS: Tell stream clients the task is being worked
+        let working = TaskGetResult {
+            task_id: task.id.to_string(),
+            status: TaskStatus::Working,
+            progress: Some(0.0),
+            result: None,
+            error: None,
+            cost: None,
+            duration_ms: None,
+        };
+        if let Err(e) = A2aTaskEventRepository::append(&self.pool, &task.id, &working).await {
+            warn!("Failed to record task started event: {:?}", e);
+        }
+
         let start = std::time::Instant::now();
         let timeout_duration = Duration::from_secs(QUERY_EXECUTION_TIMEOUT_SECS);
 
-        // Execute the query with timeout
-        let execution_result = tokio::time::timeout(
-            timeout_duration,
-            self.executor.execute(&task.tool, &task.arguments),
-        )
-        .await;
+        // Execute the query with timeout, unless the task is cancelled first
+        let cancel_token = self.cancellations.register(task.id);
+        let execution_result = tokio::select! {
+            _ = cancel_token.cancelled() => None,
+            result = tokio::time::timeout(
+                timeout_duration,
+                self.executor.execute(&task.tool, &task.arguments),
+            ) => Some(result),
+        };
+        self.cancellations.remove(&task.id);
+
+        let Some(execution_result) = execution_result else {
+            // The cancelling handler already updated and audited the task
+            counter!("a2a.tasks.cancelled", "tool" => task.tool.clone()).increment(1);
+            info!(
+                task_id = %task.id,
+                tool = %task.tool,
+                duration_ms = start.elapsed().as_millis(),
+                "A2A task cancelled while working"
+            );
+            return;
+        };
 
         match execution_result {
             Ok(Ok((result, cost))) => {
@@ -238,39 +556,44 @@ impl A2aTaskProcessor {
                 let cost_micro_usdc = ToolRegistry::get_cost_micro_usdc(&task.tool);
 
                 // Update task as completed
-                if let Err(e) = self.complete_task(&task.id, &result, cost).await {
-                    error!(
+                match self.complete_task(&task.id, &result, cost).await {
+                    Err(e) => error!(
                         task_id = %task.id,
                         error = %e,
                         "Failed to mark task as completed"
-                    );
-                } else {
-                    // METRICS: Track completed tasks
-                    counter!("a2a.tasks.completed", "tool" => task.tool.clone()).increment(1);
-                    histogram!("a2a.tasks.duration_ms", "tool" => task.tool.clone())
-                        .record(duration_ms as f64);
-
-                    // AUDIT: Log task completed
-                    if let Err(e) = A2aAuditService::log_completed(
-                        &self.pool,
-                        &task.id,
-                        &task.organization_id,
-                        &task.tool,
-                        cost_micro_usdc,
-                        duration_ms,
-                    )
-                    .await
-                    {
-                        warn!("Failed to log task completed audit: {:?}", e);
-                    }
-
-                    info!(
+                    ),
+                    Ok(false) => info!(
                         task_id = %task.id,
-                        tool = %task.tool,
-                        duration_ms = duration.as_millis(),
-                        cost = cost,
-                        "A2A task completed successfully"
-                    );
+                        "A2A task was cancelled before its result was saved"
+                    ),
+                    Ok(true) => {
+                        // METRICS: Track completed tasks
+                        counter!("a2a.tasks.completed", "tool" => task.tool.clone()).increment(1);
+                        histogram!("a2a.tasks.duration_ms", "tool" => task.tool.clone())
+                            .record(duration_ms as f64);
+
+                        // AUDIT: Log task completed
+                        if let Err(e) = A2aAuditService::log_completed(
+                            &self.pool,
+                            &task.id,
+                            &task.organization_id,
+                            &task.tool,
+                            cost_micro_usdc,
+                            duration_ms,
+                        )
+                        .await
+                        {
+                            warn!("Failed to log task completed audit: {:?}", e);
+                        }
+
+                        info!(
+                            task_id = %task.id,
+                            tool = %task.tool,
+                            duration_ms = duration.as_millis(),
+                            cost = cost,
+                            "A2A task completed successfully"
+                        );
+                    }
                 }
             }
             Ok(Err(e)) => {
@@ -283,12 +606,17 @@ impl A2aTaskProcessor {
                     .increment(1);
 
                 // Update task as failed (query error)
-                if let Err(update_err) = self.fail_task(&task.id, &error_msg).await {
-                    error!(
+                match self.fail_task(&task.id, &error_msg).await {
+                    Err(update_err) => error!(
                         task_id = %task.id,
                         error = %update_err,
                         "Failed to mark task as failed"
-                    );
+                    ),
+                    Ok(false) => {
+                        info!(task_id = %task.id, "A2A task was cancelled before its error was saved");
+                        return;
+                    }
+                    Ok(true) => {}
                 }
 
                 // AUDIT: Log task failed
@@ -327,12 +655,17 @@ impl A2aTaskProcessor {
                     QUERY_EXECUTION_TIMEOUT_SECS
                 );
 
-                if let Err(update_err) = self.fail_task(&task.id, &timeout_error).await {
-                    error!(
+                match self.fail_task(&task.id, &timeout_error).await {
+                    Err(update_err) => error!(
                         task_id = %task.id,
                         error = %update_err,
                         "Failed to mark task as timed out"
-                    );
+                    ),
+                    Ok(false) => {
+                        info!(task_id = %task.id, "A2A task was cancelled before its timeout was saved");
+                        return;
+                    }
+                    Ok(true) => {}
                 }
 
                 // AUDIT: Log task timeout
@@ -359,14 +692,18 @@ impl A2aTaskProcessor {
         }
     }
 
-    /// Mark a task as completed
+    /// Mark a working task as completed
+    ///
+    /// Returns `false` if the task is no longer working (e.g. it was cancelled).
     async fn complete_task(
         &self,
         task_id: &Uuid,
         result: &serde_json::Value,
         cost: f64,
-    ) -> anyhow::Result<()> {
-        sqlx::query(
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET
@@ -376,21 +713,26 @@ impl A2aTaskProcessor {
                 cost = $3,
                 completed_at = NOW(),
                 updated_at = NOW()
-            WHERE id = $1
-            "#,
-        )
+            WHERE id = $1 AND status = 'working'
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(result)
         .bind(cost)
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await?;
 
-        Ok(())
+        Self::finish(tx, task).await
     }
 
-    /// Mark a task as failed
-    async fn fail_task(&self, task_id: &Uuid, error: &str) -> anyhow::Result<()> {
-        sqlx::query(
+    /// Mark a working task as failed
+    ///
+    /// Returns `false` if the task is no longer working (e.g. it was cancelled).
+    async fn fail_task(&self, task_id: &Uuid, error: &str) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let task = sqlx::query_as::<_, A2aTask>(&format!(
             r#"
             UPDATE a2a_tasks
             SET
@@ -398,15 +740,74 @@ impl A2aTaskProcessor {
                 error = $2,
                 completed_at = NOW(),
                 updated_at = NOW()
-            WHERE id = $1
-            "#,
-        )
+            WHERE id = $1 AND status = 'working'
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
         .bind(task_id)
         .bind(error)
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await?;
 
-        Ok(())
+        Self::finish(tx, task).await
+    }
+
+    /// Return working tasks to the queue
+    ///
+    /// Tasks no longer 'working' (cancelled, or finished) are left alone.
+    /// Returns the number of tasks requeued.
+    async fn requeue_tasks(&self, task_ids: &[Uuid]) -> anyhow::Result<usize> {
+        let mut tx = self.pool.begin().await?;
+
+        let tasks = sqlx::query_as::<_, A2aTask>(&format!(
+            r#"
+            UPDATE a2a_tasks
+            SET
+                status = 'submitted',
+                started_at = NULL,
+                updated_at = NOW()
+            WHERE id = ANY($1) AND status = 'working'
+            RETURNING {TASK_COLUMNS}
+            "#
+        ))
+        .bind(task_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Stream clients see the task queued again
+        for task in &tasks {
+            A2aTaskEventRepository::append(&mut *tx, &task.id, &task.to_get_result()).await?;
+        }
+        tx.commit().await?;
+
+        Ok(tasks.len())
+    }
+
+    /// Record the final event of a task updated in `tx` and commit
+    ///
+    /// The event commits with the status, so a stream that sees the task
+    /// finished also sees its final event.
+    async fn finish(
+        mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+        task: Option<A2aTask>,
+    ) -> anyhow::Result<bool> {
+        let Some(task) = task else {
+            return Ok(false);
+        };
+
+        A2aTaskEventRepository::append(&mut *tx, &task.id, &task.to_get_result()).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
+
+/// Log a task that panicked instead of finishing
+fn log_task_panic(result: Result<(), tokio::task::JoinError>) {
+    if let Err(e) = result {
+        if e.is_panic() {
+            error!(error = %e, "A2A task panicked");
+        }
     }
 }
 
@@ -419,63 +820,31 @@ struct ClaimedTask {
     arguments: serde_json::Value,
 }
 
-/// Check if a database error is transient and can be retried
-///
-/// Transient errors include:
-/// - Connection errors (pool exhausted, network issues)
-/// - Deadlocks (serialization failures)
-/// - Lock timeouts
-fn is_transient_error(error: &sqlx::Error) -> bool {
-    match error {
-        // Connection pool errors
-        sqlx::Error::PoolTimedOut => true,
-        sqlx::Error::PoolClosed => false, // Pool closed is not transient
-
-        // IO/Network errors
-        sqlx::Error::Io(_) => true,
-
-        // Database errors - check for specific PostgreSQL error codes
-        sqlx::Error::Database(db_err) => {
-            // PostgreSQL error codes for transient errors:
-            // 40001 - serialization_failure (deadlock)
-            // 40P01 - deadlock_detected
-            // 55P03 - lock_not_available
-            // 57P01 - admin_shutdown
-            // 08xxx - connection exceptions
-            if let Some(code) = db_err.code() {
-                let code_str = code.as_ref();
-                matches!(
-                    code_str,
-                    "40001" | "40P01" | "55P03" | "57P01" | "57P02" | "57P03"
-                ) || code_str.starts_with("08")
-            } else {
-                false
-            }
-        }
-
-        // All other errors are not transient
-        _ => false,
-    }
-}
-
 /// Start the A2A task processor as a background task
 ///
-/// Returns a cancellation token to stop the processor.
-pub fn start_a2a_task_processor(pool: DbPool) -> CancellationToken {
-    let processor = A2aTaskProcessor::new(pool);
+/// Tasks cancelled through `cancellations` stop being worked.
+/// Returns a cancellation token to stop the processor, and its handle, which
+/// resolves once executing tasks have finished or been requeued. Await it
+/// before closing the pool.
+pub fn start_a2a_task_processor(
+    pool: DbPool,
+    cancellations: A2aTaskCancellations,
+) -> (CancellationToken, JoinHandle<()>) {
+    let processor = A2aTaskProcessor::new(pool).with_cancellations(cancellations);
     let cancel_token = CancellationToken::new();
     let token_clone = cancel_token.clone();
 
-    tokio::spawn(async move {
+    let handle = tokio::spawn(async move {
         processor.run(token_clone).await;
     });
 
-    cancel_token
+    (cancel_token, handle)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_default_config() {
@@ -485,6 +854,12 @@ mod tests {
             Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS)
         );
         assert_eq!(config.max_tasks_per_cycle, MAX_TASKS_PER_CYCLE);
+        assert_eq!(config.max_concurrent_tasks, DEFAULT_MAX_CONCURRENT_TASKS);
+        assert_eq!(config.max_dequeue_per_sec, DEFAULT_MAX_DEQUEUE_PER_SEC);
+        assert_eq!(
+            config.shutdown_timeout,
+            Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS)
+        );
     }
 
     #[test]
@@ -492,8 +867,183 @@ mod tests {
         let config = A2aTaskProcessorConfig {
             poll_interval: Duration::from_secs(5),
             max_tasks_per_cycle: 5,
+            max_concurrent_tasks: 2,
+            max_dequeue_per_sec: 3,
+            shutdown_timeout: Duration::from_secs(20),
         };
         assert_eq!(config.poll_interval, Duration::from_secs(5));
         assert_eq!(config.max_tasks_per_cycle, 5);
+        assert_eq!(config.max_concurrent_tasks, 2);
+        assert_eq!(config.max_dequeue_per_sec, 3);
+        assert_eq!(config.shutdown_timeout, Duration::from_secs(20));
+    }
+
+    /// Processor whose pool is never connected
+    fn processor(max_concurrent_tasks: usize, max_dequeue_per_sec: u32) -> A2aTaskProcessor {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+        A2aTaskProcessor::with_config(
+            pool,
+            A2aTaskProcessorConfig {
+                poll_interval: Duration::from_secs(1),
+                max_tasks_per_cycle: MAX_TASKS_PER_CYCLE,
+                max_concurrent_tasks,
+                max_dequeue_per_sec,
+                shutdown_timeout: Duration::from_secs(1),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_slots_bound_concurrency() {
+        let processor = processor(2, 100);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        for _ in 0..5 {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            let done_tx = done_tx.clone();
+            processor
+                .spawn_in_slot(Uuid::new_v4(), async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    done_tx.send(()).unwrap();
+                })
+                .await;
+            assert!(processor.in_flight() <= 2);
+        }
+        for _ in 0..5 {
+            done_rx.recv().await.unwrap();
+        }
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tasks_stay_queued_at_capacity() {
+        let processor = processor(2, 100);
+        let mut rate_gate = DequeueRateGate::new(100, Instant::now());
+        let release = CancellationToken::new();
+
+        assert_eq!(processor.dequeue_limit(&mut rate_gate, Instant::now()), 2);
+
+        // Fill both slots
+        for _ in 0..2 {
+            let release = release.clone();
+            processor
+                .spawn_in_slot(Uuid::new_v4(), async move { release.cancelled().await })
+                .await;
+        }
+        assert_eq!(processor.in_flight(), 2);
+        assert_eq!(processor.dequeue_limit(&mut rate_gate, Instant::now()), 0);
+
+        // Slots free up once the tasks finish
+        release.cancel();
+        for _ in 0..100 {
+            if processor.in_flight() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(processor.in_flight(), 0);
+        assert_eq!(processor.dequeue_limit(&mut rate_gate, Instant::now()), 2);
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_tasks_and_reports_unfinished() {
+        let processor = processor(4, 100);
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        let quick = Uuid::new_v4();
+        let stuck = Uuid::new_v4();
+        let done = finished.clone();
+        processor
+            .spawn_in_slot(quick, async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                done.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+        processor.spawn_in_slot(stuck, std::future::pending()).await;
+        assert_eq!(processor.in_flight(), 2);
+
+        // The quick task finishes within the timeout; the stuck one is
+        // aborted, freeing its slot, and reported for requeueing
+        let unfinished = processor.drain(Duration::from_millis(200)).await;
+        assert_eq!(unfinished, vec![stuck]);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        assert_eq!(processor.in_flight(), 0);
+
+        // Nothing is left to drain
+        assert!(processor.drain(Duration::ZERO).await.is_empty());
+    }
+
+    #[test]
+    fn test_rate_gate_limits_dequeues_per_second() {
+        let start = Instant::now();
+        let mut gate = DequeueRateGate::new(4, start);
+
+        // A second's worth up front
+        assert_eq!(gate.available(start), 4);
+        gate.consume(4);
+        assert_eq!(gate.available(start), 0);
+
+        // Refills at the configured rate
+        assert_eq!(gate.available(start + Duration::from_millis(500)), 2);
+        gate.consume(1);
+        assert_eq!(gate.available(start + Duration::from_millis(500)), 1);
+
+        // Never holds more than a second's worth
+        assert_eq!(gate.available(start + Duration::from_secs(60)), 4);
+    }
+
+    #[tokio::test]
+    async fn test_dequeue_limit_follows_rate() {
+        let processor = processor(8, 3);
+        let now = Instant::now();
+        let mut rate_gate = DequeueRateGate::new(3, now);
+
+        assert_eq!(processor.dequeue_limit(&mut rate_gate, now), 3);
+        rate_gate.consume(3);
+        assert_eq!(processor.dequeue_limit(&mut rate_gate, now), 0);
+    }
+
+    #[test]
+    fn test_cancel_triggers_registered_token() {
+        let cancellations = A2aTaskCancellations::new();
+        let task_id = Uuid::new_v4();
+        let token = cancellations.register(task_id);
+
+        assert!(cancellations.cancel(&task_id));
+        assert!(token.is_cancelled());
+        // The task is no longer tracked once cancelled
+        assert!(!cancellations.cancel(&task_id));
+    }
+
+    #[test]
+    fn test_cancel_unknown_or_finished_task() {
+        let cancellations = A2aTaskCancellations::new();
+        assert!(!cancellations.cancel(&Uuid::new_v4()));
+
+        let task_id = Uuid::new_v4();
+        let token = cancellations.register(task_id);
+        cancellations.remove(&task_id);
+        assert!(!cancellations.cancel(&task_id));
+        assert!(!token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_tokens() {
+        let cancellations = A2aTaskCancellations::new();
+        let handler_side = cancellations.clone();
+        let task_id = Uuid::new_v4();
+        let token = cancellations.register(task_id);
+
+        assert!(handler_side.cancel(&task_id));
+        token.cancelled().await;
     }
 }
@@ -0,0 +1,63 @@
+//! Action job enqueueing from the gateway
+//!
+//! Triggers normally reach the action workers through the event processor.
+//! Manually fired triggers skip event matching and push their jobs onto the
+//! same Redis lists (routed by action type and priority lane like the event
+//! processor), so workers cannot tell the two apart.
+
+use anyhow::{Context, Result};
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use shared::redis::queue_key;
+use shared::{ActionJob, ActionType, ActionWorkerPools};
+
+/// Redis-backed producer for the action job queue
+#[derive(Clone)]
+pub struct ActionJobQueue {
+    conn: ConnectionManager,
+    pools: ActionWorkerPools,
+}
+
+impl ActionJobQueue {
+    /// Create a new queue producer
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self {
+            conn,
+            pools: ActionWorkerPools::from_env(),
+        }
+    }
+
+    /// Redis list jobs of `action_type` are pushed to
+    pub fn queue_name(&self, action_type: &ActionType) -> String {
+        queue_key(&self.pools.queue_for(action_type))
+    }
+
+    /// Redis list `job` is pushed to: the lane of its priority in the queue
+    /// for its action type
+    pub fn lane(&self, job: &ActionJob) -> String {
+        job.queue_priority()
+            .lane(&self.queue_name(&job.action_type))
+    }
+
+    /// Push a job for the action workers
+    pub async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        let job_json = serde_json::to_string(job).context("Failed to serialize action job")?;
+        let queue_name = self.lane(job);
+
+        let mut conn = self.conn.clone();
+        conn.lpush::<_, _, ()>(&queue_name, &job_json)
+            .await
+            .context("Failed to enqueue action job to Redis")?;
+
+        tracing::debug!(
+            job_id = %job.id,
+            trigger_id = %job.trigger_id,
+            correlation_id = %job.correlation_id,
+            action_type = %job.action_type,
+            queue = %queue_name,
+            "Enqueued manual action job"
+        );
+
+        Ok(())
+    }
+}
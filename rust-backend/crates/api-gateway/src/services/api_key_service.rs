@@ -18,6 +18,13 @@
 //!
 //! The prefix stored in the database is the first 16 characters (e.g., `sk_live_XXXXXXXX`)
 //! for efficient lookup without exposing the full key.
+//!
+//! # Deterministic Keys in Tests
+//!
+//! With the `test-support` feature (always on for unit tests),
+//! [`ApiKeyService::with_seed`] builds a service whose generated keys are
+//! reproducible for a given seed. Production code can only construct the
+//! `OsRng`-backed service.
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
@@ -27,6 +34,11 @@ use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use once_cell::sync::Lazy;
 use thiserror::Error;
 
+#[cfg(any(test, feature = "test-support"))]
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+#[cfg(any(test, feature = "test-support"))]
+use std::sync::{Arc, Mutex};
+
 /// Length of random bytes for key generation (256 bits of entropy)
 const KEY_ENTROPY_BYTES: usize = 32;
 
@@ -107,6 +119,9 @@ pub struct GeneratedApiKey {
 #[derive(Clone)]
 pub struct ApiKeyService {
     argon2: Argon2<'static>,
+    /// Seeded RNG replacing `OsRng` for key entropy (test builds only)
+    #[cfg(any(test, feature = "test-support"))]
+    seeded_rng: Option<Arc<Mutex<StdRng>>>,
 }
 
 impl Default for ApiKeyService {
@@ -133,7 +148,39 @@ impl ApiKeyService {
 
         let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
 
-        Self { argon2 }
+        Self {
+            argon2,
+            #[cfg(any(test, feature = "test-support"))]
+            seeded_rng: None,
+        }
+    }
+
+    /// Create an ApiKeyService that generates a reproducible sequence of keys
+    ///
+    /// Two services created with the same seed produce the same keys in the
+    /// same order. Only the key entropy is seeded: hashes still use random
+    /// salts, so hashing and verification behave exactly as in production.
+    ///
+    /// Only available in tests and with the `test-support` feature.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seeded_rng: Some(Arc::new(Mutex::new(StdRng::seed_from_u64(seed)))),
+            ..Self::new()
+        }
+    }
+
+    /// Fill `dest` with key entropy from the seeded RNG or the OS CSPRNG
+    fn fill_entropy(&self, dest: &mut [u8]) -> Result<(), ApiKeyError> {
+        #[cfg(any(test, feature = "test-support"))]
+        if let Some(rng) = &self.seeded_rng {
+            rng.lock()
+                .map_err(|_| ApiKeyError::GenerationError("Seeded RNG poisoned".to_string()))?
+                .fill_bytes(dest);
+            return Ok(());
+        }
+
+        getrandom::fill(dest).map_err(|e| ApiKeyError::GenerationError(e.to_string()))
     }
 
     /// Generate a new API key with secure random entropy
@@ -151,8 +198,7 @@ impl ApiKeyService {
     pub fn generate_key(&self, environment: &str) -> Result<GeneratedApiKey, ApiKeyError> {
         // Generate 32 bytes of random data using OS CSPRNG
         let mut random_bytes = [0u8; KEY_ENTROPY_BYTES];
-        getrandom::fill(&mut random_bytes)
-            .map_err(|e| ApiKeyError::GenerationError(e.to_string()))?;
+        self.fill_entropy(&mut random_bytes)?;
 
         // Encode as URL-safe base64 (no padding) - produces 43 chars
         let encoded = URL_SAFE_NO_PAD.encode(random_bytes);
@@ -363,6 +409,54 @@ mod tests {
         assert_ne!(key1.hash, key2.hash);
     }
 
+    #[test]
+    fn test_seeded_keys_are_reproducible() {
+        let service_a = ApiKeyService::with_seed(42);
+        let service_b = ApiKeyService::with_seed(42);
+
+        let a1 = service_a.generate_key("live").unwrap();
+        let a2 = service_a.generate_key("test").unwrap();
+        let b1 = service_b.generate_key("live").unwrap();
+        let b2 = service_b.generate_key("test").unwrap();
+
+        assert_eq!(a1.key, b1.key);
+        assert_eq!(a1.prefix, b1.prefix);
+        assert_eq!(a2.key, b2.key);
+        // Successive keys from one service still differ
+        assert_ne!(a1.key[8..], a2.key[8..]);
+    }
+
+    #[test]
+    fn test_seeded_keys_keep_format_and_hashing() {
+        let service = ApiKeyService::with_seed(7);
+        let generated = service.generate_key("live").unwrap();
+
+        assert!(ApiKeyService::is_valid_format(&generated.key));
+        assert!(generated.hash.starts_with("$argon2id$"));
+        assert!(service.verify_key(&generated.key, &generated.hash).unwrap());
+
+        // Salts stay random, so the same seeded key hashes differently
+        let again = ApiKeyService::with_seed(7).generate_key("live").unwrap();
+        assert_eq!(generated.key, again.key);
+        assert_ne!(generated.hash, again.hash);
+    }
+
+    #[test]
+    fn test_different_seeds_produce_different_keys() {
+        let key1 = ApiKeyService::with_seed(1).generate_key("live").unwrap();
+        let key2 = ApiKeyService::with_seed(2).generate_key("live").unwrap();
+
+        assert_ne!(key1.key, key2.key);
+    }
+
+    #[test]
+    fn test_default_service_is_not_seeded() {
+        let key1 = ApiKeyService::new().generate_key("live").unwrap();
+        let key2 = ApiKeyService::new().generate_key("live").unwrap();
+
+        assert_ne!(key1.key, key2.key);
+    }
+
     #[test]
     fn test_verify_key_correct() {
         let service = ApiKeyService::new();
@@ -22,6 +22,7 @@ use governor::{
 };
 use std::collections::HashMap;
 use std::num::NonZeroU32;
+use std::ops::Deref;
 use std::sync::{Arc, Mutex};
 
 /// Default rate limit: 20 auth attempts per minute per IP
@@ -173,6 +174,33 @@ impl Default for AuthRateLimiter {
     }
 }
 
+/// Rate limiter for `POST /auth/forgot-password` (5 per minute per IP)
+///
+/// Its own type so it is shared through app data separately from the code
+/// exchange limiter.
+#[derive(Clone)]
+pub struct PasswordResetRateLimiter(AuthRateLimiter);
+
+impl PasswordResetRateLimiter {
+    pub fn new() -> Self {
+        Self(AuthRateLimiter::with_rates(200, 5))
+    }
+}
+
+impl Default for PasswordResetRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for PasswordResetRateLimiter {
+    type Target = AuthRateLimiter;
+
+    fn deref(&self) -> &AuthRateLimiter {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
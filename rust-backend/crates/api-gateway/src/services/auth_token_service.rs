@@ -67,10 +67,7 @@ impl AuthTokenService {
         user_agent: Option<&str>,
         ip_address: Option<&str>,
     ) -> Result<AuthResponse, HttpResponse> {
-        // Generate JWT
-        let token = self.generate_jwt(&user.id, &user.username)?;
-
-        // Generate refresh token
+        // Generate refresh token (starts the login session)
         let refresh_token = self
             .refresh_service
             .create_refresh_token(pool, &user.id, user_agent, ip_address)
@@ -83,9 +80,12 @@ impl AuthTokenService {
                 ))
             })?;
 
+        // Generate JWT
+        let token = self.generate_jwt(&user.id, &user.username, Some(&refresh_token.session_id))?;
+
         Ok(AuthResponse {
             token,
-            refresh_token,
+            refresh_token: refresh_token.token,
             expires_in: ACCESS_TOKEN_VALIDITY_SECS,
             user: UserResponse::from(user.clone()),
         })
@@ -97,12 +97,19 @@ impl AuthTokenService {
     ///
     /// * `user_id` - User ID to include in claims
     /// * `username` - Username to include in claims
+    /// * `session_id` - Login session the token belongs to, if any
     ///
     /// # Returns
     ///
     /// JWT string on success, or `HttpResponse` error on failure
-    pub fn generate_jwt(&self, user_id: &str, username: &str) -> Result<String, HttpResponse> {
-        let claims = Claims::new(user_id.to_string(), username.to_string(), 1); // 1 hour
+    pub fn generate_jwt(
+        &self,
+        user_id: &str,
+        username: &str,
+        session_id: Option<&str>,
+    ) -> Result<String, HttpResponse> {
+        let mut claims = Claims::new(user_id.to_string(), username.to_string(), 1); // 1 hour
+        claims.sid = session_id.map(str::to_string);
         encode(
             &Header::new(Algorithm::HS256),
             &claims,
@@ -141,8 +148,6 @@ impl AuthTokenService {
         user_agent: Option<&str>,
         ip_address: Option<&str>,
     ) -> Result<(String, String), HttpResponse> {
-        let token = self.generate_jwt(user_id, username)?;
-
         let refresh_token = self
             .refresh_service
             .create_refresh_token(pool, user_id, user_agent, ip_address)
@@ -155,7 +160,9 @@ impl AuthTokenService {
                 ))
             })?;
 
-        Ok((token, refresh_token))
+        let token = self.generate_jwt(user_id, username, Some(&refresh_token.session_id))?;
+
+        Ok((token, refresh_token.token))
     }
 }
 
@@ -166,7 +173,7 @@ mod tests {
     #[test]
     fn test_generate_jwt() {
         let service = AuthTokenService::new("test_secret_key_for_testing");
-        let result = service.generate_jwt("user123", "testuser");
+        let result = service.generate_jwt("user123", "testuser", None);
         assert!(result.is_ok());
         let token = result.unwrap();
         assert!(!token.is_empty());
@@ -177,7 +184,9 @@ mod tests {
     #[test]
     fn test_jwt_contains_valid_structure() {
         let service = AuthTokenService::new("another_test_secret");
-        let token = service.generate_jwt("user456", "anotheruser").unwrap();
+        let token = service
+            .generate_jwt("user456", "anotheruser", Some("session-1"))
+            .unwrap();
 
         // Decode the header to verify it's HS256
         let parts: Vec<&str> = token.split('.').collect();
@@ -0,0 +1,80 @@
+//! Short-lived cache of native token balances
+//!
+//! `GET /api/v1/chains/{chain_id}/balances/{address}` is cheap to call and
+//! each miss costs an RPC request, so balances are kept in Redis for a few
+//! seconds. Cache failures are logged and treated as misses.
+
+use alloy::primitives::U256;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use shared::redis::RedisKey;
+use std::time::Duration;
+
+/// Default time a balance is served from cache
+const DEFAULT_TTL_SECS: u64 = 15;
+
+/// Redis key of a cached balance (addresses are case-insensitive)
+fn balance_key(chain_id: i32, address: &str) -> RedisKey {
+    RedisKey::cache("balance")
+        .part(chain_id)
+        .part(address.to_lowercase())
+}
+
+/// Redis-backed balance cache
+#[derive(Clone)]
+pub struct BalanceCache {
+    conn: ConnectionManager,
+    ttl: Duration,
+}
+
+impl BalanceCache {
+    /// Create a cache keeping balances for `ttl`
+    pub fn new(conn: ConnectionManager, ttl: Duration) -> Self {
+        Self { conn, ttl }
+    }
+
+    /// Create a cache with the TTL from `BALANCE_CACHE_TTL_SECS` (default: 15s)
+    pub fn from_env(conn: ConnectionManager) -> Self {
+        let ttl_secs = std::env::var("BALANCE_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TTL_SECS);
+        Self::new(conn, Duration::from_secs(ttl_secs))
+    }
+
+    /// Time a balance is served from cache
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached balance in wei, if any
+    pub async fn get(&self, chain_id: i32, address: &str) -> Option<U256> {
+        let mut conn = self.conn.clone();
+        match conn
+            .get::<_, Option<String>>(balance_key(chain_id, address))
+            .await
+        {
+            Ok(value) => value.and_then(|v| v.parse().ok()),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read cached balance");
+                None
+            }
+        }
+    }
+
+    /// Cache a balance in wei
+    pub async fn set(&self, chain_id: i32, address: &str, balance: U256) {
+        let mut conn = self.conn.clone();
+        if let Err(e) = conn
+            .set_ex::<_, _, ()>(
+                balance_key(chain_id, address),
+                balance.to_string(),
+                self.ttl.as_secs().max(1),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to cache balance");
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Transactional Email
+//!
+//! Sends account emails (email verification, password reset) through an HTTP email
+//! API that accepts a JSON message with a bearer key:
+//!
+//! ```json
+//! { "from": "...", "to": ["..."], "subject": "...", "text": "..." }
+//! ```
+//!
+//! This is the format of Resend's `POST /emails`; other providers can be
+//! reached through a relay that accepts it.
+//!
+//! # Configuration
+//!
+//! - `EMAIL_API_URL`: provider endpoint. When unset emails are not sent: the
+//!   message is logged in development so the flow can be completed by hand,
+//!   and skipped with a warning elsewhere.
+//! - `EMAIL_API_KEY`: bearer key for the provider
+//! - `EMAIL_FROM`: sender address (default: `AgentAuri <noreply@agentauri.ai>`)
+//! - `FRONTEND_URL`: base URL of the links in emails (default: `http://localhost:3000`)
+//! - `EMAIL_TIMEOUT_MS`: request timeout (default: 10000)
+
+use serde::Serialize;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default sender address
+const DEFAULT_FROM: &str = "AgentAuri <noreply@agentauri.ai>";
+
+/// Default frontend URL for links
+const DEFAULT_FRONTEND_URL: &str = "http://localhost:3000";
+
+/// Default request timeout in milliseconds
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// Errors that can occur while sending email
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("Email request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Email provider returned status {0}")]
+    Rejected(reqwest::StatusCode),
+}
+
+/// Email delivery configuration
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    /// Provider endpoint (None = emails are not sent)
+    pub api_url: Option<String>,
+    pub api_key: Option<String>,
+    pub from: String,
+    /// Base URL of links in emails, without trailing slash
+    pub frontend_url: String,
+    pub timeout: Duration,
+    /// Log undelivered emails in full (development only: they contain tokens)
+    pub log_undelivered: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            api_url: None,
+            api_key: None,
+            from: DEFAULT_FROM.to_string(),
+            frontend_url: DEFAULT_FRONTEND_URL.to_string(),
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            log_undelivered: false,
+        }
+    }
+}
+
+impl EmailConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let non_empty = |var: &str| std::env::var(var).ok().filter(|v| !v.is_empty());
+
+        config.api_url = non_empty("EMAIL_API_URL");
+        config.api_key = non_empty("EMAIL_API_KEY");
+        if let Some(from) = non_empty("EMAIL_FROM") {
+            config.from = from;
+        }
+        if let Some(url) = non_empty("FRONTEND_URL") {
+            config.frontend_url = url.trim_end_matches('/').to_string();
+        }
+        if let Ok(value) = std::env::var("EMAIL_TIMEOUT_MS") {
+            match value.parse::<u64>() {
+                Ok(ms) if ms > 0 => config.timeout = Duration::from_millis(ms),
+                _ => tracing::warn!(
+                    value = %value,
+                    "Invalid EMAIL_TIMEOUT_MS, using {}ms",
+                    DEFAULT_TIMEOUT_MS
+                ),
+            }
+        }
+        config.log_undelivered = std::env::var("ENVIRONMENT")
+            .map(|e| e == "development")
+            .unwrap_or(false);
+
+        config
+    }
+}
+
+/// An email ready to send
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+}
+
+/// Request body of the provider API
+#[derive(Serialize)]
+struct SendRequest<'a> {
+    from: &'a str,
+    to: [&'a str; 1],
+    subject: &'a str,
+    text: &'a str,
+}
+
+/// Sends transactional email
+///
+/// Create once at startup and share via app state.
+#[derive(Clone)]
+pub struct EmailService {
+    config: EmailConfig,
+    http_client: reqwest::Client,
+}
+
+impl EmailService {
+    pub fn new(config: EmailConfig) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(EmailConfig::from_env())
+    }
+
+    /// Whether emails are actually delivered
+    pub fn is_configured(&self) -> bool {
+        self.config.api_url.is_some()
+    }
+
+    /// Email with the link that verifies `to`
+    pub fn verification_email(&self, to: &str, token: &str) -> EmailMessage {
+        let link = format!("{}/verify-email?token={}", self.config.frontend_url, token);
+        EmailMessage {
+            to: to.to_string(),
+            subject: "Verify your AgentAuri email address".to_string(),
+            text: format!(
+                "Confirm that this is your email address by opening the link below:\n\n\
+                 {}\n\n\
+                 The link expires in 24 hours. If you did not create an AgentAuri \
+                 account, you can ignore this email.\n",
+                link
+            ),
+        }
+    }
+
+    /// Email with the link that resets the password of the account at `to`
+    pub fn password_reset_email(&self, to: &str, token: &str) -> EmailMessage {
+        let link = format!(
+            "{}/reset-password?token={}",
+            self.config.frontend_url, token
+        );
+        EmailMessage {
+            to: to.to_string(),
+            subject: "Reset your AgentAuri password".to_string(),
+            text: format!(
+                "Someone asked to reset the password of your AgentAuri account. \
+                 To choose a new password, open the link below:\n\n\
+                 {}\n\n\
+                 The link expires in 1 hour. Resetting your password signs you out \
+                 everywhere. If you did not ask for this, you can ignore this email.\n",
+                link
+            ),
+        }
+    }
+
+    /// Send an email
+    ///
+    /// Without a provider the email is logged (development) or skipped, and
+    /// `Ok` is returned.
+    pub async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        let Some(api_url) = &self.config.api_url else {
+            if self.config.log_undelivered {
+                tracing::info!(
+                    to = %message.to,
+                    subject = %message.subject,
+                    text = %message.text,
+                    "EMAIL_API_URL not set - email not sent"
+                );
+            } else {
+                tracing::warn!(
+                    subject = %message.subject,
+                    "EMAIL_API_URL not set - email not sent"
+                );
+            }
+            return Ok(());
+        };
+
+        let mut request = self.http_client.post(api_url).json(&SendRequest {
+            from: &self.config.from,
+            to: [&message.to],
+            subject: &message.subject,
+            text: &message.text,
+        });
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(EmailError::Rejected(response.status()));
+        }
+
+        tracing::debug!(subject = %message.subject, "Email sent");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_email_links_to_frontend() {
+        let service = EmailService::new(EmailConfig {
+            frontend_url: "https://app.example.com".to_string(),
+            ..Default::default()
+        });
+
+        let email = service.verification_email("user@example.com", "evt_abc");
+        assert_eq!(email.to, "user@example.com");
+        assert!(email
+            .text
+            .contains("https://app.example.com/verify-email?token=evt_abc"));
+    }
+
+    #[test]
+    fn test_password_reset_email_links_to_frontend() {
+        let service = EmailService::new(EmailConfig {
+            frontend_url: "https://app.example.com".to_string(),
+            ..Default::default()
+        });
+
+        let email = service.password_reset_email("user@example.com", "prt_abc");
+        assert_eq!(email.to, "user@example.com");
+        assert!(email
+            .text
+            .contains("https://app.example.com/reset-password?token=prt_abc"));
+    }
+
+    #[test]
+    fn test_send_request_format() {
+        let body = serde_json::to_value(SendRequest {
+            from: DEFAULT_FROM,
+            to: ["user@example.com"],
+            subject: "Subject",
+            text: "Body",
+        })
+        .unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "from": DEFAULT_FROM,
+                "to": ["user@example.com"],
+                "subject": "Subject",
+                "text": "Body",
+            })
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_send_without_provider_is_skipped() {
+        let service = EmailService::new(EmailConfig::default());
+        assert!(!service.is_configured());
+
+        let email = service.verification_email("user@example.com", "evt_abc");
+        assert!(service.send(&email).await.is_ok());
+    }
+}
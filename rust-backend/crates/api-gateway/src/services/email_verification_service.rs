@@ -0,0 +1,198 @@
+//! Email Verification Service
+//!
+//! Issues and consumes the tokens in email verification links.
+//!
+//! # Security Features
+//!
+//! - **32 bytes of entropy**: Uses CSPRNG for token generation
+//! - **SHA-256 hashing**: Only the hash is stored
+//! - **Single-use**: A token is spent on first use, and issuing a new one
+//!   invalidates the user's earlier tokens
+//! - **24-hour expiration**
+//! - **Bound to the address**: A token only verifies the email it was sent to
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::repositories::EmailVerificationRepository;
+use shared::DbPool;
+
+/// Token prefix for email verification tokens
+const TOKEN_PREFIX: &str = "evt_"; // "email verification token"
+
+/// Length of random bytes for token generation (256 bits of entropy)
+const TOKEN_ENTROPY_BYTES: usize = 32;
+
+/// Token validity in hours
+pub const VERIFICATION_TOKEN_VALIDITY_HOURS: i64 = 24;
+
+/// Minimum time between two verification emails to the same user
+pub const RESEND_COOLDOWN_SECS: i64 = 60;
+
+/// Errors that can occur during email verification
+#[derive(Debug, Error)]
+pub enum EmailVerificationError {
+    #[error("Failed to generate token: {0}")]
+    GenerationError(String),
+
+    #[error("Invalid or expired verification token")]
+    InvalidToken,
+
+    #[error("Verification email sent too recently; retry in {0} seconds")]
+    TooSoon(i64),
+
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+/// Service for email verification tokens
+#[derive(Clone, Default)]
+pub struct EmailVerificationService;
+
+impl EmailVerificationService {
+    /// Create a new EmailVerificationService
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a new token (raw value, not stored yet)
+    fn generate_token(&self) -> Result<String, EmailVerificationError> {
+        let mut random_bytes = [0u8; TOKEN_ENTROPY_BYTES];
+        getrandom::fill(&mut random_bytes)
+            .map_err(|e| EmailVerificationError::GenerationError(e.to_string()))?;
+
+        Ok(format!(
+            "{}{}",
+            TOKEN_PREFIX,
+            URL_SAFE_NO_PAD.encode(random_bytes)
+        ))
+    }
+
+    /// Hash a token using SHA-256 (hex encoded)
+    fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Create and store a token verifying `email` for a user
+    ///
+    /// Earlier tokens of the user stop working. Returns the raw token for the
+    /// verification link.
+    pub async fn create_token(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        email: &str,
+    ) -> Result<String, EmailVerificationError> {
+        let token = self.generate_token()?;
+        let expires_at = Utc::now() + Duration::hours(VERIFICATION_TOKEN_VALIDITY_HOURS);
+
+        EmailVerificationRepository::create(
+            pool,
+            user_id,
+            email,
+            &Self::hash_token(&token),
+            expires_at,
+        )
+        .await
+        .map_err(|e| EmailVerificationError::DatabaseError(e.to_string()))?;
+
+        Ok(token)
+    }
+
+    /// Like [`create_token`](Self::create_token), unless the user was sent a
+    /// token less than [`RESEND_COOLDOWN_SECS`] ago
+    pub async fn create_resend_token(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        email: &str,
+    ) -> Result<String, EmailVerificationError> {
+        let last_issued = EmailVerificationRepository::last_issued_at(pool, user_id)
+            .await
+            .map_err(|e| EmailVerificationError::DatabaseError(e.to_string()))?;
+
+        if let Some(last_issued) = last_issued {
+            let elapsed = (Utc::now() - last_issued).num_seconds();
+            if elapsed < RESEND_COOLDOWN_SECS {
+                return Err(EmailVerificationError::TooSoon(
+                    RESEND_COOLDOWN_SECS - elapsed,
+                ));
+            }
+        }
+
+        self.create_token(pool, user_id, email).await
+    }
+
+    /// Consume a token and mark the address it was sent to as verified
+    ///
+    /// Returns the user_id of the verified user.
+    pub async fn verify(
+        &self,
+        pool: &DbPool,
+        token: &str,
+    ) -> Result<String, EmailVerificationError> {
+        if !token.starts_with(TOKEN_PREFIX) {
+            return Err(EmailVerificationError::InvalidToken);
+        }
+
+        match EmailVerificationRepository::consume(pool, &Self::hash_token(token)).await {
+            Ok(Some(user_id)) => Ok(user_id),
+            Ok(None) => Err(EmailVerificationError::InvalidToken),
+            Err(e) => {
+                tracing::error!(error = %e, "Database error during email verification");
+                Err(EmailVerificationError::DatabaseError(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Local getrandom wrapper using rand
+mod getrandom {
+    use rand::RngCore;
+
+    pub fn fill(dest: &mut [u8]) -> Result<(), rand::Error> {
+        rand::rngs::OsRng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    #[test]
+    fn test_generate_token_format() {
+        let service = EmailVerificationService::new();
+        let token = service.generate_token().unwrap();
+
+        assert!(token.starts_with("evt_"));
+        // Base64 of 32 bytes = 43 chars, plus prefix
+        assert_eq!(token.len(), 4 + 43);
+        assert_ne!(token, service.generate_token().unwrap());
+    }
+
+    #[test]
+    fn test_hash_token() {
+        let hash = EmailVerificationService::hash_token("evt_test");
+
+        assert_eq!(hash, EmailVerificationService::hash_token("evt_test"));
+        assert_ne!(hash, EmailVerificationService::hash_token("evt_other"));
+        assert_eq!(hash.len(), 64);
+    }
+
+    #[actix_web::test]
+    async fn test_verify_rejects_foreign_tokens_without_lookup() {
+        // Unreachable database: the prefix check must fail first
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+
+        let result = EmailVerificationService::new()
+            .verify(&pool, "urt_not_a_verification_token")
+            .await;
+        assert!(matches!(result, Err(EmailVerificationError::InvalidToken)));
+    }
+}
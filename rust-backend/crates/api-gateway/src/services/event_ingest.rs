@@ -0,0 +1,300 @@
+//! Signature checks for pushed events
+//!
+//! Indexers (Ponder or others) can push events to `POST /api/v1/events/ingest`
+//! instead of writing to the database. Each source has its own shared secret,
+//! and requests are signed the same way as our outbound deliveries (see
+//! [`shared::signing`]):
+//!
+//! ```text
+//! X-AgentAuri-Source: ponder
+//! X-AgentAuri-Timestamp: 1700000000
+//! X-AgentAuri-Signature: sha256=<hex(HMAC-SHA256(secret, "{timestamp}.{body}"))>
+//! ```
+//!
+//! # Configuration
+//!
+//! - `EVENT_INGEST_SECRETS`: comma-separated `source:secret` pairs, e.g.
+//!   `ponder:s3cr3t,backfill:0th3r` (unset: every request is rejected)
+//! - `EVENT_INGEST_TOLERANCE_SECS`: accepted clock skew between the signed
+//!   timestamp and now (default: 300)
+
+use shared::signing::verify_signature;
+use std::collections::HashMap;
+
+/// Header naming the source whose secret signed the request
+pub const SOURCE_HEADER: &str = "X-AgentAuri-Source";
+
+/// Default accepted age (either direction) of a signed timestamp
+const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+/// Why a pushed event was not accepted as authentic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestRejection {
+    /// Source, timestamp or signature header is missing
+    MissingHeaders,
+    /// No secret is configured for the source
+    UnknownSource,
+    /// Timestamp is malformed or outside the tolerance window
+    StaleTimestamp,
+    /// No signature matches the source's secret
+    SignatureMismatch,
+}
+
+impl IngestRejection {
+    /// Reason for logs and metrics; clients only see a generic 401
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingHeaders => "missing_headers",
+            Self::UnknownSource => "unknown_source",
+            Self::StaleTimestamp => "stale_timestamp",
+            Self::SignatureMismatch => "signature_mismatch",
+        }
+    }
+}
+
+/// Per-source secrets for pushed events
+///
+/// Create once at startup and share via app state.
+#[derive(Clone)]
+pub struct EventIngestVerifier {
+    secrets: HashMap<String, String>,
+    tolerance_secs: i64,
+}
+
+impl std::fmt::Debug for EventIngestVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventIngestVerifier")
+            .field("sources", &self.sources())
+            .field("tolerance_secs", &self.tolerance_secs)
+            .finish()
+    }
+}
+
+impl EventIngestVerifier {
+    pub fn new(secrets: HashMap<String, String>) -> Self {
+        Self {
+            secrets,
+            tolerance_secs: DEFAULT_TOLERANCE_SECS,
+        }
+    }
+
+    /// Accept timestamps up to `tolerance_secs` away from now
+    pub fn with_tolerance(mut self, tolerance_secs: i64) -> Self {
+        self.tolerance_secs = tolerance_secs;
+        self
+    }
+
+    /// Load secrets from environment variables
+    ///
+    /// Malformed entries are skipped with a warning.
+    pub fn from_env() -> Self {
+        let secrets = std::env::var("EVENT_INGEST_SECRETS")
+            .map(|value| parse_secrets(&value))
+            .unwrap_or_default();
+        let mut verifier = Self::new(secrets);
+
+        if let Ok(value) = std::env::var("EVENT_INGEST_TOLERANCE_SECS") {
+            match value.parse::<i64>() {
+                Ok(secs) if secs > 0 => verifier.tolerance_secs = secs,
+                _ => tracing::warn!(
+                    value = %value,
+                    "Invalid EVENT_INGEST_TOLERANCE_SECS, using {}s",
+                    DEFAULT_TOLERANCE_SECS
+                ),
+            }
+        }
+
+        verifier
+    }
+
+    /// Sources with a configured secret, sorted
+    pub fn sources(&self) -> Vec<&str> {
+        let mut sources: Vec<&str> = self.secrets.keys().map(String::as_str).collect();
+        sources.sort_unstable();
+        sources
+    }
+
+    /// Check the signature headers of a pushed request against its body
+    ///
+    /// `now` is the current Unix time in seconds.
+    pub fn verify(
+        &self,
+        source: Option<&str>,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), IngestRejection> {
+        let (Some(source), Some(timestamp), Some(signature)) = (source, timestamp, signature)
+        else {
+            return Err(IngestRejection::MissingHeaders);
+        };
+        let secret = self
+            .secrets
+            .get(source)
+            .ok_or(IngestRejection::UnknownSource)?;
+        let timestamp: i64 = timestamp
+            .parse()
+            .map_err(|_| IngestRejection::StaleTimestamp)?;
+        if (now - timestamp).abs() > self.tolerance_secs {
+            return Err(IngestRejection::StaleTimestamp);
+        }
+
+        if verify_signature(secret, timestamp, body, signature) {
+            Ok(())
+        } else {
+            Err(IngestRejection::SignatureMismatch)
+        }
+    }
+}
+
+/// Parse `source:secret` pairs, skipping malformed entries
+fn parse_secrets(value: &str) -> HashMap<String, String> {
+    let mut secrets = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once(':') {
+            Some((source, secret)) if !source.trim().is_empty() && !secret.is_empty() => {
+                secrets.insert(source.trim().to_string(), secret.to_string());
+            }
+            _ => tracing::warn!(
+                "Skipping malformed EVENT_INGEST_SECRETS entry (expected source:secret)"
+            ),
+        }
+    }
+    secrets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::signing::sign_payload;
+
+    const NOW: i64 = 1_700_000_000;
+    const BODY: &[u8] = br#"{"id":"84532-1000-0"}"#;
+
+    fn verifier() -> EventIngestVerifier {
+        EventIngestVerifier::new(parse_secrets("ponder:secret, backfill:other"))
+    }
+
+    #[test]
+    fn test_parse_secrets() {
+        let secrets = parse_secrets("ponder:a:b, backfill:other,,broken, :x,empty:");
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets["ponder"], "a:b");
+        assert_eq!(secrets["backfill"], "other");
+    }
+
+    #[test]
+    fn test_verify_accepts_signature_from_source_secret() {
+        let signature = sign_payload("secret", NOW, BODY);
+        assert_eq!(
+            verifier().verify(
+                Some("ponder"),
+                Some(&NOW.to_string()),
+                Some(&signature),
+                BODY,
+                NOW + 10
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_missing_headers() {
+        let signature = sign_payload("secret", NOW, BODY);
+        let timestamp = NOW.to_string();
+        let v = verifier();
+
+        for (source, timestamp, signature) in [
+            (None, Some(timestamp.as_str()), Some(signature.as_str())),
+            (Some("ponder"), None, Some(signature.as_str())),
+            (Some("ponder"), Some(timestamp.as_str()), None),
+        ] {
+            assert_eq!(
+                v.verify(source, timestamp, signature, BODY, NOW),
+                Err(IngestRejection::MissingHeaders)
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_other_sources_secret() {
+        // Signed with backfill's secret but claiming to be ponder
+        let signature = sign_payload("other", NOW, BODY);
+        let timestamp = NOW.to_string();
+        let v = verifier();
+
+        assert_eq!(
+            v.verify(
+                Some("ponder"),
+                Some(&timestamp),
+                Some(&signature),
+                BODY,
+                NOW
+            ),
+            Err(IngestRejection::SignatureMismatch)
+        );
+        assert_eq!(
+            v.verify(
+                Some("unknown"),
+                Some(&timestamp),
+                Some(&signature),
+                BODY,
+                NOW
+            ),
+            Err(IngestRejection::UnknownSource)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_body() {
+        let signature = sign_payload("secret", NOW, BODY);
+        assert_eq!(
+            verifier().verify(
+                Some("ponder"),
+                Some(&NOW.to_string()),
+                Some(&signature),
+                br#"{"id":"84532-1000-1"}"#,
+                NOW
+            ),
+            Err(IngestRejection::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_stale_timestamp() {
+        let v = verifier().with_tolerance(60);
+        let old = NOW - 61;
+        let signature = sign_payload("secret", old, BODY);
+
+        assert_eq!(
+            v.verify(
+                Some("ponder"),
+                Some(&old.to_string()),
+                Some(&signature),
+                BODY,
+                NOW
+            ),
+            Err(IngestRejection::StaleTimestamp)
+        );
+        assert_eq!(
+            v.verify(Some("ponder"), Some("soon"), Some(&signature), BODY, NOW),
+            Err(IngestRejection::StaleTimestamp)
+        );
+    }
+
+    #[test]
+    fn test_without_secrets_everything_is_rejected() {
+        let signature = sign_payload("", NOW, BODY);
+        assert_eq!(
+            EventIngestVerifier::new(HashMap::new()).verify(
+                Some("ponder"),
+                Some(&NOW.to_string()),
+                Some(&signature),
+                BODY,
+                NOW
+            ),
+            Err(IngestRejection::UnknownSource)
+        );
+    }
+}
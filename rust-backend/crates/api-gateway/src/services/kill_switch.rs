@@ -0,0 +1,78 @@
+//! Global kill-switch storage
+//!
+//! The engaged switch is a single JSON value in Redis, shared by every gateway
+//! instance. [`UnifiedRateLimiter`](crate::middleware::unified_rate_limiter)
+//! reads it on each request, so engaging or releasing it takes effect on the
+//! next request. A switch with an expiry is stored with a matching TTL and
+//! releases itself.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use shared::redis::RedisKey;
+
+use crate::models::KillSwitchState;
+
+/// Redis key holding the engaged switch
+fn kill_switch_key() -> RedisKey {
+    RedisKey::rate_limit("kill_switch")
+}
+
+/// Redis-backed store for the global kill-switch
+#[derive(Clone)]
+pub struct KillSwitchStore {
+    conn: ConnectionManager,
+}
+
+impl KillSwitchStore {
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    /// Get the engaged switch, if any
+    ///
+    /// An unreadable value is treated as released, so a bad write cannot
+    /// block traffic.
+    pub async fn get(&self) -> Result<Option<KillSwitchState>> {
+        let mut conn = self.conn.clone();
+        let value: Option<String> = conn
+            .get(kill_switch_key())
+            .await
+            .context("Failed to read kill-switch")?;
+
+        Ok(value.and_then(|v| match serde_json::from_str(&v) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                tracing::error!(error = %e, "Ignoring unreadable kill-switch state");
+                None
+            }
+        }))
+    }
+
+    /// Engage the switch, replacing any engaged one
+    pub async fn set(&self, state: &KillSwitchState) -> Result<()> {
+        let value = serde_json::to_string(state).context("Failed to serialize kill-switch")?;
+        let mut conn = self.conn.clone();
+
+        match state.expires_at {
+            Some(expires_at) => {
+                let ttl_secs = (expires_at - Utc::now()).num_seconds().max(1) as u64;
+                conn.set_ex::<_, _, ()>(kill_switch_key(), value, ttl_secs)
+                    .await
+            }
+            None => conn.set::<_, _, ()>(kill_switch_key(), value).await,
+        }
+        .context("Failed to store kill-switch")
+    }
+
+    /// Release the switch; returns whether one was engaged
+    pub async fn clear(&self) -> Result<bool> {
+        let mut conn = self.conn.clone();
+        let deleted: usize = conn
+            .del(kill_switch_key())
+            .await
+            .context("Failed to release kill-switch")?;
+        Ok(deleted > 0)
+    }
+}
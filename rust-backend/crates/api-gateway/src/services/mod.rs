@@ -5,34 +5,75 @@
 
 pub mod a2a_audit;
 pub mod a2a_task_processor;
+pub mod action_job_queue;
 pub mod api_key_service;
 pub mod auth_rate_limiter;
 pub mod auth_token_service;
+pub mod balance_cache;
+pub mod email_service;
+pub mod email_verification_service;
+pub mod event_ingest;
+pub mod kill_switch;
 pub mod oauth_client_service;
 pub mod oauth_code_service;
 pub mod oauth_token_service;
+pub mod password_reset_service;
+pub mod payment_provider;
 pub mod query_executor;
+pub mod siwe;
 pub mod social_auth_service;
+pub mod stripe_ips;
 pub mod stripe_service;
 pub mod tool_registry;
+pub mod trigger_export_service;
 pub mod user_refresh_token_service;
 pub mod wallet_service;
+pub mod webhook_verification;
 
 pub use a2a_audit::{A2aAuditService, AuditActor, AuditEventType, AuditLogParams};
-pub use a2a_task_processor::{start_a2a_task_processor, A2aTaskProcessor, A2aTaskProcessorConfig};
+pub use a2a_task_processor::{
+    start_a2a_task_processor, A2aTaskCancellations, A2aTaskProcessor, A2aTaskProcessorConfig,
+};
+pub use action_job_queue::ActionJobQueue;
 pub use api_key_service::ApiKeyService;
-pub use auth_rate_limiter::AuthRateLimiter;
+pub use auth_rate_limiter::{AuthRateLimiter, PasswordResetRateLimiter};
 pub use auth_token_service::AuthTokenService;
+pub use balance_cache::BalanceCache;
+pub use email_service::{EmailConfig, EmailError, EmailMessage, EmailService};
+pub use email_verification_service::{
+    EmailVerificationError, EmailVerificationService, RESEND_COOLDOWN_SECS,
+    VERIFICATION_TOKEN_VALIDITY_HOURS,
+};
+pub use event_ingest::{EventIngestVerifier, IngestRejection, SOURCE_HEADER};
+pub use kill_switch::KillSwitchStore;
 pub use oauth_client_service::OAuthClientService;
-pub use oauth_code_service::{OAuthCodeError, OAuthCodeService};
+pub use oauth_code_service::{
+    AuthorizationGrant, CodeChallengeMethod, OAuthCodeError, OAuthCodeService, PkceChallenge,
+};
 pub use oauth_token_service::OAuthTokenService;
+pub use password_reset_service::{
+    PasswordResetError, PasswordResetService, PASSWORD_RESET_TOKEN_VALIDITY_MINUTES,
+};
+pub use payment_provider::{
+    CheckoutResult, PaymentError, PaymentEvent, PaymentProvider, PaymentProviderKind,
+};
 pub use query_executor::QueryExecutor;
+pub use siwe::{SiweConfig, SiweError, SiweMessage};
 pub use social_auth_service::{OAuthUserProfile, SocialAuthError, SocialAuthService};
+pub use stripe_ips::{
+    StripeIpAllowlist, StripeIpRefreshConfig, StripeIpRefresher, STRIPE_WEBHOOK_IPS,
+};
 pub use stripe_service::{StripeConfig, StripeService, WebhookEvent};
 pub use tool_registry::{ToolDefinition, ToolRegistry, ToolTier};
+pub use trigger_export_service::TriggerExportService;
 pub use user_refresh_token_service::{
     RefreshTokenError, UserRefreshTokenService, ACCESS_TOKEN_VALIDITY_SECS,
     REFRESH_TOKEN_VALIDITY_DAYS,
 };
 #[allow(unused_imports)] // ChainConfig used in main.rs
-pub use wallet_service::{ChainConfig, WalletService};
+pub use wallet_service::{
+    ChainConfig, RpcFailoverConfig, TransactionState, TransactionStatus, WalletError, WalletService,
+};
+pub use webhook_verification::{
+    VerificationMode, VerificationOutcome, WebhookVerificationConfig, WebhookVerifier,
+};
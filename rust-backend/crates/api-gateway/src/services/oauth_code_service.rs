@@ -3,19 +3,28 @@
 //! Handles generation and exchange of temporary authorization codes for OAuth flows.
 //! This implements the Authorization Code pattern to avoid exposing tokens in URLs.
 //!
+//! Two flows use these codes:
+//! - **Social login**: the code stands for the user's own session
+//!   ([`create_code`](OAuthCodeService::create_code))
+//! - **OAuth clients**: the code carries the grant a user approved for a
+//!   client, optionally bound to a PKCE challenge
+//!   ([`create_authorization_code`](OAuthCodeService::create_authorization_code))
+//!
 //! # Security Features
 //!
 //! - **32 bytes of entropy**: Uses CSPRNG for code generation
 //! - **SHA-256 hashing**: Fast lookup with 256-bit collision resistance
 //! - **Single-use**: Code is invalidated after first exchange
 //! - **5-minute expiration**: Short-lived to minimize attack window
+//! - **PKCE (RFC 7636)**: `S256` and `plain` challenges, verified in constant time
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
 use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 
-use crate::repositories::OAuthTempCodeRepository;
+use crate::repositories::{AuthorizationCodeRecord, OAuthTempCodeRepository};
 use shared::DbPool;
 
 /// Code prefix for OAuth authorization codes
@@ -27,6 +36,10 @@ const CODE_ENTROPY_BYTES: usize = 32;
 /// Code validity in seconds (5 minutes)
 pub const CODE_VALIDITY_SECS: i64 = 300;
 
+/// Length limits of PKCE code verifiers and challenges (RFC 7636 Section 4.1)
+const PKCE_MIN_LENGTH: usize = 43;
+const PKCE_MAX_LENGTH: usize = 128;
+
 /// Errors that can occur during OAuth code operations
 #[derive(Debug, Error)]
 pub enum OAuthCodeError {
@@ -36,10 +49,151 @@ pub enum OAuthCodeError {
     #[error("Invalid or expired authorization code")]
     InvalidCode,
 
+    #[error("Invalid code_challenge: {0}")]
+    InvalidCodeChallenge(&'static str),
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 }
 
+/// PKCE code challenge method (RFC 7636 Section 4.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeChallengeMethod {
+    /// `BASE64URL(SHA256(code_verifier))`
+    S256,
+    /// The challenge is the verifier itself
+    Plain,
+}
+
+impl CodeChallengeMethod {
+    /// Parse the `code_challenge_method` parameter (case-sensitive)
+    pub fn parse(method: &str) -> Option<Self> {
+        match method {
+            "S256" => Some(Self::S256),
+            "plain" => Some(Self::Plain),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::S256 => "S256",
+            Self::Plain => "plain",
+        }
+    }
+}
+
+/// PKCE challenge from an authorization request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkceChallenge {
+    pub challenge: String,
+    pub method: CodeChallengeMethod,
+}
+
+impl PkceChallenge {
+    /// Validate a `code_challenge` and `code_challenge_method`
+    ///
+    /// The method defaults to `plain` when omitted (RFC 7636 Section 4.3).
+    pub fn new(challenge: &str, method: Option<&str>) -> Result<Self, OAuthCodeError> {
+        let method = match method {
+            Some(method) => CodeChallengeMethod::parse(method).ok_or(
+                OAuthCodeError::InvalidCodeChallenge("code_challenge_method must be S256 or plain"),
+            )?,
+            None => CodeChallengeMethod::Plain,
+        };
+
+        if !is_valid_pkce_value(challenge) {
+            return Err(OAuthCodeError::InvalidCodeChallenge(
+                "must be 43-128 characters of A-Z, a-z, 0-9, '-', '.', '_' or '~'",
+            ));
+        }
+
+        Ok(Self {
+            challenge: challenge.to_string(),
+            method,
+        })
+    }
+
+    /// Check a `code_verifier` against this challenge in constant time
+    pub fn verify(&self, code_verifier: &str) -> bool {
+        if !is_valid_pkce_value(code_verifier) {
+            return false;
+        }
+
+        let expected = match self.method {
+            CodeChallengeMethod::S256 => {
+                URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+            }
+            CodeChallengeMethod::Plain => code_verifier.to_string(),
+        };
+
+        expected.len() == self.challenge.len()
+            && expected.as_bytes().ct_eq(self.challenge.as_bytes()).into()
+    }
+}
+
+/// Whether a code verifier or challenge has the RFC 7636 length and alphabet
+fn is_valid_pkce_value(value: &str) -> bool {
+    (PKCE_MIN_LENGTH..=PKCE_MAX_LENGTH).contains(&value.len())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
+}
+
+/// Access a user granted to an OAuth client, carried by an authorization code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationGrant {
+    pub user_id: String,
+    pub client_id: String,
+    /// Redirect URI of the authorization request (must be repeated when exchanging)
+    pub redirect_uri: String,
+    pub organization_id: String,
+    pub scopes: Vec<String>,
+    pub pkce: Option<PkceChallenge>,
+}
+
+impl From<&AuthorizationGrant> for AuthorizationCodeRecord {
+    fn from(grant: &AuthorizationGrant) -> Self {
+        Self {
+            user_id: grant.user_id.clone(),
+            client_id: grant.client_id.clone(),
+            redirect_uri: grant.redirect_uri.clone(),
+            organization_id: grant.organization_id.clone(),
+            scopes: grant.scopes.clone(),
+            code_challenge: grant.pkce.as_ref().map(|p| p.challenge.clone()),
+            code_challenge_method: grant.pkce.as_ref().map(|p| p.method.as_str().to_string()),
+        }
+    }
+}
+
+impl TryFrom<AuthorizationCodeRecord> for AuthorizationGrant {
+    type Error = OAuthCodeError;
+
+    fn try_from(record: AuthorizationCodeRecord) -> Result<Self, Self::Error> {
+        let pkce = match (record.code_challenge, record.code_challenge_method) {
+            (Some(challenge), Some(method)) => Some(PkceChallenge {
+                challenge,
+                method: CodeChallengeMethod::parse(&method).ok_or_else(|| {
+                    OAuthCodeError::DatabaseError(format!(
+                        "Unknown code_challenge_method: {}",
+                        method
+                    ))
+                })?,
+            }),
+            _ => None,
+        };
+
+        Ok(Self {
+            user_id: record.user_id,
+            client_id: record.client_id,
+            redirect_uri: record.redirect_uri,
+            organization_id: record.organization_id,
+            scopes: record.scopes,
+            pkce,
+        })
+    }
+}
+
 /// Service for OAuth authorization code operations
 #[derive(Clone, Default)]
 pub struct OAuthCodeService;
@@ -117,6 +271,58 @@ impl OAuthCodeService {
     }
 }
 
+impl OAuthCodeService {
+    /// Create and store an authorization code for an OAuth client
+    ///
+    /// Returns the raw code to be included in the client's redirect URL.
+    pub async fn create_authorization_code(
+        &self,
+        pool: &DbPool,
+        grant: &AuthorizationGrant,
+    ) -> Result<String, OAuthCodeError> {
+        let code = self.generate_code()?;
+        let code_hash = Self::hash_code(&code);
+
+        let expires_at = Utc::now() + Duration::seconds(CODE_VALIDITY_SECS);
+
+        OAuthTempCodeRepository::create_authorization_code(
+            pool,
+            &code_hash,
+            &grant.into(),
+            expires_at,
+        )
+        .await
+        .map_err(|e| OAuthCodeError::DatabaseError(e.to_string()))?;
+
+        Ok(code)
+    }
+
+    /// Exchange an OAuth client's authorization code for the grant it carries
+    ///
+    /// The code is consumed whether or not the caller goes on to accept the
+    /// token request, so a failed PKCE check cannot be retried.
+    pub async fn exchange_authorization_code(
+        &self,
+        pool: &DbPool,
+        code: &str,
+    ) -> Result<AuthorizationGrant, OAuthCodeError> {
+        if !code.starts_with(CODE_PREFIX) {
+            return Err(OAuthCodeError::InvalidCode);
+        }
+
+        let code_hash = Self::hash_code(code);
+
+        match OAuthTempCodeRepository::exchange_authorization_code(pool, &code_hash).await {
+            Ok(Some(record)) => record.try_into(),
+            Ok(None) => Err(OAuthCodeError::InvalidCode),
+            Err(e) => {
+                tracing::error!(error = %e, "Database error during authorization code exchange");
+                Err(OAuthCodeError::DatabaseError(e.to_string()))
+            }
+        }
+    }
+}
+
 /// Local getrandom wrapper using rand
 mod getrandom {
     use rand::RngCore;
@@ -162,4 +368,78 @@ mod tests {
         assert_ne!(code1, code2);
         assert_ne!(hash1, hash2);
     }
+
+    /// Verifier and challenge from RFC 7636 Appendix B
+    const RFC_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const RFC_S256_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    #[test]
+    fn test_pkce_s256_verification() {
+        let pkce = PkceChallenge::new(RFC_S256_CHALLENGE, Some("S256")).unwrap();
+        assert_eq!(pkce.method, CodeChallengeMethod::S256);
+
+        assert!(pkce.verify(RFC_VERIFIER));
+        assert!(!pkce.verify(&RFC_VERIFIER.replace('d', "e")));
+        // The challenge itself is not a valid verifier for S256
+        assert!(!pkce.verify(RFC_S256_CHALLENGE));
+    }
+
+    #[test]
+    fn test_pkce_plain_verification() {
+        let pkce = PkceChallenge::new(RFC_VERIFIER, None).unwrap();
+        assert_eq!(pkce.method, CodeChallengeMethod::Plain);
+
+        assert!(pkce.verify(RFC_VERIFIER));
+        assert!(!pkce.verify(RFC_S256_CHALLENGE));
+    }
+
+    #[test]
+    fn test_pkce_rejects_malformed_verifier() {
+        let pkce = PkceChallenge::new(RFC_S256_CHALLENGE, Some("S256")).unwrap();
+
+        assert!(!pkce.verify(""));
+        assert!(!pkce.verify(&RFC_VERIFIER[..42]));
+        assert!(!pkce.verify(&"a".repeat(129)));
+        assert!(!pkce.verify(&format!("{}+", &RFC_VERIFIER[..42])));
+    }
+
+    #[test]
+    fn test_pkce_challenge_validation() {
+        assert!(matches!(
+            PkceChallenge::new(RFC_S256_CHALLENGE, Some("s256")),
+            Err(OAuthCodeError::InvalidCodeChallenge(_))
+        ));
+        assert!(matches!(
+            PkceChallenge::new("too-short", Some("S256")),
+            Err(OAuthCodeError::InvalidCodeChallenge(_))
+        ));
+        assert!(matches!(
+            PkceChallenge::new(&format!("{}=", &RFC_S256_CHALLENGE[..42]), Some("S256")),
+            Err(OAuthCodeError::InvalidCodeChallenge(_))
+        ));
+    }
+
+    #[test]
+    fn test_authorization_grant_record_round_trip() {
+        let grant = AuthorizationGrant {
+            user_id: "user-1".to_string(),
+            client_id: "client-1".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            organization_id: "org-1".to_string(),
+            scopes: vec!["read:triggers".to_string()],
+            pkce: Some(PkceChallenge::new(RFC_S256_CHALLENGE, Some("S256")).unwrap()),
+        };
+
+        let record = AuthorizationCodeRecord::from(&grant);
+        assert_eq!(record.code_challenge_method.as_deref(), Some("S256"));
+        assert_eq!(AuthorizationGrant::try_from(record).unwrap(), grant);
+
+        let without_pkce = AuthorizationGrant {
+            pkce: None,
+            ..grant
+        };
+        let record = AuthorizationCodeRecord::from(&without_pkce);
+        assert!(record.code_challenge.is_none());
+        assert_eq!(AuthorizationGrant::try_from(record).unwrap(), without_pkce);
+    }
 }
@@ -0,0 +1,184 @@
+//! Password Reset Service
+//!
+//! Issues and consumes the tokens in password reset links.
+//!
+//! # Security Features
+//!
+//! - **32 bytes of entropy**: Uses CSPRNG for token generation
+//! - **SHA-256 hashing**: Only the hash is stored
+//! - **Single-use**: A token is spent on first use, and issuing a new one
+//!   invalidates the user's earlier tokens
+//! - **1-hour expiration**
+//! - **Session revocation**: A reset revokes all of the user's refresh tokens
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::repositories::PasswordResetRepository;
+use shared::DbPool;
+
+/// Token prefix for password reset tokens
+const TOKEN_PREFIX: &str = "prt_"; // "password reset token"
+
+/// Length of random bytes for token generation (256 bits of entropy)
+const TOKEN_ENTROPY_BYTES: usize = 32;
+
+/// Token validity in minutes
+pub const PASSWORD_RESET_TOKEN_VALIDITY_MINUTES: i64 = 60;
+
+/// Errors that can occur during password reset
+#[derive(Debug, Error)]
+pub enum PasswordResetError {
+    #[error("Failed to generate token: {0}")]
+    GenerationError(String),
+
+    #[error("Invalid or expired password reset token")]
+    InvalidToken,
+
+    #[error("Failed to hash password: {0}")]
+    HashError(String),
+
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+/// Service for password reset tokens
+#[derive(Clone, Default)]
+pub struct PasswordResetService;
+
+impl PasswordResetService {
+    /// Create a new PasswordResetService
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a new token (raw value, not stored yet)
+    fn generate_token(&self) -> Result<String, PasswordResetError> {
+        let mut random_bytes = [0u8; TOKEN_ENTROPY_BYTES];
+        getrandom::fill(&mut random_bytes)
+            .map_err(|e| PasswordResetError::GenerationError(e.to_string()))?;
+
+        Ok(format!(
+            "{}{}",
+            TOKEN_PREFIX,
+            URL_SAFE_NO_PAD.encode(random_bytes)
+        ))
+    }
+
+    /// Hash a token using SHA-256 (hex encoded)
+    fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Create and store a reset token sent to `email` for a user
+    ///
+    /// Earlier tokens of the user stop working. Returns the raw token for the
+    /// reset link.
+    pub async fn create_token(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        email: &str,
+    ) -> Result<String, PasswordResetError> {
+        let token = self.generate_token()?;
+        let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TOKEN_VALIDITY_MINUTES);
+
+        PasswordResetRepository::create(
+            pool,
+            user_id,
+            email,
+            &Self::hash_token(&token),
+            expires_at,
+        )
+        .await
+        .map_err(|e| PasswordResetError::DatabaseError(e.to_string()))?;
+
+        Ok(token)
+    }
+
+    /// Consume a token and set a new password
+    ///
+    /// The caller validates password strength. All of the user's refresh
+    /// tokens are revoked. Returns the user_id.
+    pub async fn reset_password(
+        &self,
+        pool: &DbPool,
+        token: &str,
+        new_password: &str,
+    ) -> Result<String, PasswordResetError> {
+        if !token.starts_with(TOKEN_PREFIX) {
+            return Err(PasswordResetError::InvalidToken);
+        }
+
+        let salt = SaltString::generate(&mut OsRng);
+        let password_hash = Argon2::default()
+            .hash_password(new_password.as_bytes(), &salt)
+            .map_err(|e| PasswordResetError::HashError(e.to_string()))?
+            .to_string();
+
+        match PasswordResetRepository::consume(pool, &Self::hash_token(token), &password_hash).await
+        {
+            Ok(Some(user_id)) => Ok(user_id),
+            Ok(None) => Err(PasswordResetError::InvalidToken),
+            Err(e) => {
+                tracing::error!(error = %e, "Database error during password reset");
+                Err(PasswordResetError::DatabaseError(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Local getrandom wrapper using rand
+mod getrandom {
+    use rand::RngCore;
+
+    pub fn fill(dest: &mut [u8]) -> Result<(), rand::Error> {
+        rand::rngs::OsRng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    #[test]
+    fn test_generate_token_format() {
+        let service = PasswordResetService::new();
+        let token = service.generate_token().unwrap();
+
+        assert!(token.starts_with("prt_"));
+        // Base64 of 32 bytes = 43 chars, plus prefix
+        assert_eq!(token.len(), 4 + 43);
+        assert_ne!(token, service.generate_token().unwrap());
+    }
+
+    #[test]
+    fn test_hash_token() {
+        let hash = PasswordResetService::hash_token("prt_test");
+
+        assert_eq!(hash, PasswordResetService::hash_token("prt_test"));
+        assert_ne!(hash, PasswordResetService::hash_token("prt_other"));
+        assert_eq!(hash.len(), 64);
+    }
+
+    #[actix_web::test]
+    async fn test_reset_rejects_foreign_tokens_without_lookup() {
+        // Unreachable database: the prefix check must fail first
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_millis(100))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+
+        let result = PasswordResetService::new()
+            .reset_password(&pool, "evt_not_a_reset_token", "Correct-Horse-Battery-9")
+            .await;
+        assert!(matches!(result, Err(PasswordResetError::InvalidToken)));
+    }
+}
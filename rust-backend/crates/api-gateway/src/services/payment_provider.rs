@@ -0,0 +1,148 @@
+//! Payment Provider Abstraction
+//!
+//! Billing handlers talk to payment providers through [`PaymentProvider`],
+//! which creates checkouts and turns verified webhooks into provider-agnostic
+//! [`PaymentEvent`]s. Stripe ([`StripeService`](super::StripeService)) is the
+//! only implementation so far.
+//!
+//! # Configuration
+//!
+//! - `PAYMENT_PROVIDER`: `stripe` (default)
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// Errors returned by payment providers
+#[derive(Debug, Error)]
+pub enum PaymentError {
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("{0}")]
+    Provider(String),
+}
+
+/// Result of creating a checkout
+#[derive(Debug, Clone)]
+pub struct CheckoutResult {
+    /// Provider session ID
+    pub session_id: String,
+    /// Checkout URL for redirect
+    pub checkout_url: String,
+}
+
+/// Verified webhook event, independent of the provider
+///
+/// Amounts are in micro-USDC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentEvent {
+    /// Credit purchase paid
+    PurchaseCompleted {
+        /// Checkout session, applied at most once
+        reference_id: String,
+        organization_id: String,
+        amount: i64,
+        /// Provider payment (Stripe payment intent) refunds refer to
+        payment_id: Option<String>,
+    },
+    /// Credit purchase payment failed
+    PaymentFailed {
+        reference_id: String,
+        organization_id: String,
+        error_message: Option<String>,
+    },
+    SubscriptionCreated {
+        subscription_id: String,
+        customer_id: String,
+        plan: String,
+        organization_id: String,
+    },
+    SubscriptionUpdated {
+        subscription_id: String,
+        status: String,
+    },
+    SubscriptionCanceled {
+        subscription_id: String,
+    },
+    /// Purchase refunded or disputed
+    Refund {
+        /// Refund or dispute ID, applied at most once
+        refund_id: String,
+        charge_id: String,
+        /// Total refunded (or disputed) of the charge so far, in micro-USDC
+        amount: i64,
+        /// Organization named by the provider, if any
+        organization_id: Option<String>,
+        /// Payment of the refunded purchase
+        payment_id: Option<String>,
+        dispute: bool,
+    },
+    /// Event the billing handlers ignore
+    Unknown(String),
+}
+
+/// Payment provider used for credit purchases
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Display name, used in transaction descriptions (e.g. "Stripe")
+    fn name(&self) -> &'static str;
+
+    /// Create a checkout for `amount_usdc` whole USDC of credits
+    async fn create_checkout(
+        &self,
+        organization_id: &str,
+        amount_usdc: i64,
+        success_url: &str,
+        cancel_url: &str,
+    ) -> Result<CheckoutResult, PaymentError>;
+
+    /// Verify a webhook's signature and parse its event
+    fn verify_webhook(&self, payload: &str, signature: &str) -> Result<PaymentEvent, PaymentError>;
+}
+
+/// Configured payment provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentProviderKind {
+    Stripe,
+}
+
+impl PaymentProviderKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "stripe" => Some(Self::Stripe),
+            _ => None,
+        }
+    }
+
+    /// Read `PAYMENT_PROVIDER` (default: stripe)
+    ///
+    /// Returns the unsupported value as the error.
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("PAYMENT_PROVIDER") {
+            Ok(value) => Self::parse(&value).ok_or(value),
+            Err(_) => Ok(Self::Stripe),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_kind_parse() {
+        assert_eq!(
+            PaymentProviderKind::parse("stripe"),
+            Some(PaymentProviderKind::Stripe)
+        );
+        assert_eq!(
+            PaymentProviderKind::parse(" Stripe "),
+            Some(PaymentProviderKind::Stripe)
+        );
+        assert_eq!(PaymentProviderKind::parse("paypal"), None);
+        assert_eq!(PaymentProviderKind::parse(""), None);
+    }
+}
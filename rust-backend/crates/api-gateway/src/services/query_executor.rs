@@ -23,6 +23,7 @@ pub mod costs {
 }
 
 /// Query executor service
+#[derive(Clone)]
 pub struct QueryExecutor {
     pool: DbPool,
 }
@@ -608,6 +609,7 @@ struct FeedbackRow {
     file_uri: Option<String>,
     file_hash: Option<String>,
     timestamp: i64,
+    #[serde(with = "shared::timestamp")]
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -681,6 +683,7 @@ struct ValidationRow {
     response_hash: Option<String>,
     tag: Option<String>,
     timestamp: i64,
+    #[serde(with = "shared::timestamp")]
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
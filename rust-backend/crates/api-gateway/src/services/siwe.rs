@@ -0,0 +1,425 @@
+//! Sign-In With Ethereum (EIP-4361) messages
+//!
+//! `POST /api/v1/auth/nonce` builds the message a wallet signs and
+//! `POST /api/v1/auth/wallet` parses it back. Only messages issued for our
+//! domain and inside their validity window are accepted, so a message signed
+//! for another app cannot be replayed here.
+//!
+//! See <https://eips.ethereum.org/EIPS/eip-4361> for the format.
+//!
+//! # Configuration
+//!
+//! - `SIWE_DOMAIN`: domain messages must be issued for (default: `agentauri.ai`)
+//! - `SIWE_URI`: URI written into generated messages (default: `https://agentauri.ai`)
+
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use thiserror::Error;
+
+/// Default domain and URI of generated messages
+const DEFAULT_DOMAIN: &str = "agentauri.ai";
+const DEFAULT_URI: &str = "https://agentauri.ai";
+
+/// Statement of generated messages
+const STATEMENT: &str = "Sign in to AgentAuri";
+
+/// End of the first line, after the domain
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// Minimum nonce length (EIP-4361)
+const MIN_NONCE_LENGTH: usize = 8;
+
+/// Errors parsing or verifying a SIWE message
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SiweError {
+    #[error("Malformed SIWE message: {0}")]
+    Malformed(String),
+
+    #[error("Message was issued for another domain: {0}")]
+    DomainMismatch(String),
+
+    #[error("Message has expired")]
+    Expired,
+
+    #[error("Message is not valid yet")]
+    NotYetValid,
+}
+
+/// Domain and URI of our SIWE messages
+#[derive(Debug, Clone)]
+pub struct SiweConfig {
+    pub domain: String,
+    pub uri: String,
+}
+
+impl Default for SiweConfig {
+    fn default() -> Self {
+        Self {
+            domain: DEFAULT_DOMAIN.to_string(),
+            uri: DEFAULT_URI.to_string(),
+        }
+    }
+}
+
+impl SiweConfig {
+    /// Load from `SIWE_DOMAIN` and `SIWE_URI`, with defaults for unset values
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self {
+            domain: var("SIWE_DOMAIN").unwrap_or(default.domain),
+            uri: var("SIWE_URI").unwrap_or(default.uri),
+        }
+    }
+
+    /// Message for `address` to sign in on `chain_id`
+    pub fn message(
+        &self,
+        address: Address,
+        chain_id: i32,
+        nonce: &str,
+        issued_at: DateTime<Utc>,
+        expiration_time: DateTime<Utc>,
+    ) -> SiweMessage {
+        SiweMessage {
+            domain: self.domain.clone(),
+            address,
+            statement: Some(STATEMENT.to_string()),
+            uri: self.uri.clone(),
+            version: "1".to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time: Some(expiration_time),
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+}
+
+/// A parsed EIP-4361 message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: i32,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Parse a message, rejecting anything not in EIP-4361 format
+    pub fn parse(message: &str) -> Result<Self, SiweError> {
+        let mut lines = message.split('\n').peekable();
+
+        let header = next_line(&mut lines, "header")?;
+        let domain = header
+            .strip_suffix(HEADER_SUFFIX)
+            .ok_or_else(|| malformed("invalid header"))?;
+        // The scheme is optional and not part of the domain
+        let domain = domain.split_once("://").map_or(domain, |(_, d)| d);
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(malformed("invalid domain"));
+        }
+
+        let address = next_line(&mut lines, "address")?;
+        let address = Address::parse_checksummed(address, None)
+            .map_err(|_| malformed("address is not EIP-55 checksummed"))?;
+        expect_blank(&mut lines)?;
+
+        // Without a statement there are two blank lines
+        let statement = match next_line(&mut lines, "statement")? {
+            "" => None,
+            statement => {
+                expect_blank(&mut lines)?;
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = field(&mut lines, "URI")?.to_string();
+        let version = field(&mut lines, "Version")?;
+        if version != "1" {
+            return Err(malformed("unsupported version"));
+        }
+        let chain_id = field(&mut lines, "Chain ID")?
+            .parse::<i32>()
+            .ok()
+            .filter(|id| *id > 0)
+            .ok_or_else(|| malformed("invalid chain ID"))?;
+        let nonce = field(&mut lines, "Nonce")?;
+        if nonce.len() < MIN_NONCE_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(malformed("invalid nonce"));
+        }
+        let issued_at = timestamp(field(&mut lines, "Issued At")?)?;
+
+        let expiration_time = optional_field(&mut lines, "Expiration Time")
+            .map(timestamp)
+            .transpose()?;
+        let not_before = optional_field(&mut lines, "Not Before")
+            .map(timestamp)
+            .transpose()?;
+        let request_id = optional_field(&mut lines, "Request ID").map(str::to_string);
+
+        let mut resources = Vec::new();
+        if lines.next_if_eq(&"Resources:").is_some() {
+            while let Some(resource) = lines.next_if(|line| line.starts_with("- ")) {
+                resources.push(resource[2..].to_string());
+            }
+        }
+
+        if lines.next().is_some() {
+            return Err(malformed("unexpected content after the fields"));
+        }
+
+        Ok(Self {
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri,
+            version: version.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+
+    /// Check the message was issued for `domain` and is valid at `now`
+    pub fn verify(&self, domain: &str, now: DateTime<Utc>) -> Result<(), SiweError> {
+        if !self.domain.eq_ignore_ascii_case(domain) {
+            return Err(SiweError::DomainMismatch(self.domain.clone()));
+        }
+        if self.expiration_time.is_some_and(|exp| now >= exp) {
+            return Err(SiweError::Expired);
+        }
+        if self.not_before.is_some_and(|nbf| now < nbf) {
+            return Err(SiweError::NotYetValid);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}\n{}\n\n", self.domain, HEADER_SUFFIX, self.address)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        write!(
+            f,
+            "\nURI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}",
+            self.uri,
+            self.version,
+            self.chain_id,
+            self.nonce,
+            shared::timestamp::format(&self.issued_at)
+        )?;
+        if let Some(exp) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", shared::timestamp::format(exp))?;
+        }
+        if let Some(nbf) = &self.not_before {
+            write!(f, "\nNot Before: {}", shared::timestamp::format(nbf))?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+type Lines<'a> = std::iter::Peekable<std::str::Split<'a, char>>;
+
+fn malformed(reason: &str) -> SiweError {
+    SiweError::Malformed(reason.to_string())
+}
+
+fn next_line<'a>(lines: &mut Lines<'a>, what: &str) -> Result<&'a str, SiweError> {
+    lines
+        .next()
+        .ok_or_else(|| malformed(&format!("missing {}", what)))
+}
+
+fn expect_blank(lines: &mut Lines<'_>) -> Result<(), SiweError> {
+    match lines.next() {
+        Some("") => Ok(()),
+        _ => Err(malformed("expected a blank line")),
+    }
+}
+
+/// Value of the required `{name}: {value}` line
+fn field<'a>(lines: &mut Lines<'a>, name: &str) -> Result<&'a str, SiweError> {
+    next_line(lines, name)?
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(": "))
+        .ok_or_else(|| malformed(&format!("missing {}", name)))
+}
+
+/// Value of an optional `{name}: {value}` line, consumed only if present
+fn optional_field<'a>(lines: &mut Lines<'a>, name: &str) -> Option<&'a str> {
+    let value = lines
+        .peek()?
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(": "))?;
+    lines.next();
+    Some(value)
+}
+
+fn timestamp(value: &str) -> Result<DateTime<Utc>, SiweError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| malformed(&format!("invalid timestamp: {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const ADDRESS: &str = "0x742d35CC6634c0532925a3B844bC9e7595F0BeB4";
+
+    fn issued_at() -> DateTime<Utc> {
+        "2026-01-15T10:30:00Z".parse().unwrap()
+    }
+
+    fn message() -> SiweMessage {
+        SiweConfig::default().message(
+            ADDRESS.parse().unwrap(),
+            8453,
+            "a1b2c3d4e5f6a7b8",
+            issued_at(),
+            issued_at() + Duration::minutes(10),
+        )
+    }
+
+    #[test]
+    fn test_generated_message_format() {
+        assert_eq!(
+            message().to_string(),
+            "agentauri.ai wants you to sign in with your Ethereum account:\n\
+             0x742d35CC6634c0532925a3B844bC9e7595F0BeB4\n\
+             \n\
+             Sign in to AgentAuri\n\
+             \n\
+             URI: https://agentauri.ai\n\
+             Version: 1\n\
+             Chain ID: 8453\n\
+             Nonce: a1b2c3d4e5f6a7b8\n\
+             Issued At: 2026-01-15T10:30:00.000000Z\n\
+             Expiration Time: 2026-01-15T10:40:00.000000Z"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let mut message = message();
+        message.not_before = Some(issued_at());
+        message.request_id = Some("req-1".to_string());
+        message.resources = vec!["ipfs://Qm1".to_string(), "https://example.com".to_string()];
+
+        assert_eq!(SiweMessage::parse(&message.to_string()).unwrap(), message);
+    }
+
+    #[test]
+    fn test_parse_without_statement_or_optional_fields() {
+        let text = format!(
+            "https://agentauri.ai wants you to sign in with your Ethereum account:\n{}\n\n\nURI: https://agentauri.ai\nVersion: 1\nChain ID: 1\nNonce: 32891756\nIssued At: 2026-01-15T10:30:00Z",
+            ADDRESS
+        );
+        let parsed = SiweMessage::parse(&text).unwrap();
+        assert_eq!(parsed.domain, "agentauri.ai");
+        assert_eq!(parsed.statement, None);
+        assert_eq!(parsed.chain_id, 1);
+        assert_eq!(parsed.nonce, "32891756");
+        assert_eq!(parsed.expiration_time, None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let message = message();
+        let now = issued_at() + Duration::minutes(1);
+        assert_eq!(message.verify("agentauri.ai", now), Ok(()));
+        assert_eq!(message.verify("AgentAuri.ai", now), Ok(()));
+        assert_eq!(
+            message.verify("evil.example", now),
+            Err(SiweError::DomainMismatch("agentauri.ai".to_string()))
+        );
+        assert_eq!(
+            message.verify("agentauri.ai", issued_at() + Duration::minutes(10)),
+            Err(SiweError::Expired)
+        );
+
+        let mut message = message;
+        message.not_before = Some(issued_at() + Duration::minutes(5));
+        assert_eq!(
+            message.verify("agentauri.ai", now),
+            Err(SiweError::NotYetValid)
+        );
+    }
+
+    #[test]
+    fn test_tampered_messages_are_rejected() {
+        let valid = message().to_string();
+        let tampered = [
+            // Header rewritten
+            valid.replace("wants you to sign in", "asks you to sign in"),
+            // Address not checksummed
+            valid.replace(ADDRESS, &ADDRESS.to_lowercase()),
+            // Missing blank line after the statement
+            valid.replace("AgentAuri\n\nURI", "AgentAuri\nURI"),
+            // Fields out of order
+            valid.replace("Version: 1\nChain ID: 8453", "Chain ID: 8453\nVersion: 1"),
+            valid.replace("Version: 1", "Version: 2"),
+            valid.replace("Chain ID: 8453", "Chain ID: base"),
+            valid.replace("Nonce: a1b2c3d4e5f6a7b8", "Nonce: a1b2-c3d4"),
+            valid.replace("Nonce: a1b2c3d4e5f6a7b8", "Nonce: abc"),
+            valid.replace(
+                "Issued At: 2026-01-15T10:30:00.000000Z",
+                "Issued At: yesterday",
+            ),
+            // Trailing content
+            format!("{}\nNonce: 0000000000000000", valid),
+            format!("{}\n", valid),
+            // Nonce scanned from a non-SIWE message
+            "Please sign\nNonce: a1b2c3d4e5f6a7b8".to_string(),
+            String::new(),
+        ];
+
+        for text in &tampered {
+            assert!(
+                matches!(SiweMessage::parse(text), Err(SiweError::Malformed(_))),
+                "accepted: {:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrong_domain_parses_but_fails_verification() {
+        let text = message()
+            .to_string()
+            .replace("agentauri.ai wants", "evil.example wants");
+        let parsed = SiweMessage::parse(&text).unwrap();
+        assert_eq!(
+            parsed.verify("agentauri.ai", issued_at()),
+            Err(SiweError::DomainMismatch("evil.example".to_string()))
+        );
+    }
+}
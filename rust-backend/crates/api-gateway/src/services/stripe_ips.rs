@@ -0,0 +1,334 @@
+//! Stripe Webhook IP Refresh
+//!
+//! Keeps the Stripe webhook IP allowlist in line with the list Stripe
+//! publishes at [`STRIPE_WEBHOOK_IPS_URL`]. The fetched ranges replace the
+//! compiled-in list in the billing handler while they are fresh; if a fetch
+//! fails or the ranges expire, the compiled-in list is used again.
+//!
+//! # Configuration
+//!
+//! - `STRIPE_IP_REFRESH_ENABLED`: fetch Stripe's published list (default: false)
+//! - `STRIPE_IP_REFRESH_INTERVAL_SECS`: time between fetches (default: 3600)
+//! - `STRIPE_IP_CACHE_TTL_SECS`: how long fetched ranges are trusted without a
+//!   successful refresh (default: 86400)
+
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Stripe's published list of webhook source IPs
+pub const STRIPE_WEBHOOK_IPS_URL: &str = "https://stripe.com/files/ips/ips_webhooks.json";
+
+const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_CACHE_TTL_SECS: u64 = 86400;
+
+/// Timeout of a single fetch
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Allowlist shared by the refresher and the webhook handler
+pub static STRIPE_WEBHOOK_IPS: Lazy<Arc<StripeIpAllowlist>> =
+    Lazy::new(|| Arc::new(StripeIpAllowlist::default()));
+
+/// Webhook IP ranges fetched from Stripe, valid until their TTL runs out
+#[derive(Debug, Default)]
+pub struct StripeIpAllowlist {
+    fetched: RwLock<Option<FetchedRanges>>,
+}
+
+#[derive(Debug)]
+struct FetchedRanges {
+    ranges: Vec<String>,
+    expires_at: Instant,
+}
+
+impl StripeIpAllowlist {
+    /// Replace the fetched ranges, trusting them for `ttl`
+    pub fn set(&self, ranges: Vec<String>, ttl: Duration) {
+        let fetched = FetchedRanges {
+            ranges,
+            expires_at: Instant::now() + ttl,
+        };
+        *self.fetched.write().unwrap_or_else(|e| e.into_inner()) = Some(fetched);
+    }
+
+    /// Run `f` on the fetched ranges
+    ///
+    /// Returns `None` when nothing was fetched yet or the ranges expired, in
+    /// which case callers fall back to the compiled-in list.
+    pub fn with_ranges<R>(&self, f: impl FnOnce(&[String]) -> R) -> Option<R> {
+        let fetched = self.fetched.read().unwrap_or_else(|e| e.into_inner());
+        match fetched.as_ref() {
+            Some(fetched) if fetched.expires_at > Instant::now() => Some(f(&fetched.ranges)),
+            _ => None,
+        }
+    }
+}
+
+/// Stripe IP refresh configuration
+#[derive(Debug, Clone)]
+pub struct StripeIpRefreshConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub interval: Duration,
+    pub ttl: Duration,
+}
+
+impl Default for StripeIpRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: STRIPE_WEBHOOK_IPS_URL.to_string(),
+            interval: Duration::from_secs(DEFAULT_REFRESH_INTERVAL_SECS),
+            ttl: Duration::from_secs(DEFAULT_CACHE_TTL_SECS),
+        }
+    }
+}
+
+impl StripeIpRefreshConfig {
+    /// Load from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let secs = |name: &str, default: Duration| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default)
+        };
+
+        Self {
+            enabled: std::env::var("STRIPE_IP_REFRESH_ENABLED")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            interval: secs("STRIPE_IP_REFRESH_INTERVAL_SECS", defaults.interval),
+            ttl: secs("STRIPE_IP_CACHE_TTL_SECS", defaults.ttl),
+            url: defaults.url,
+        }
+    }
+}
+
+/// Body of Stripe's IP list
+#[derive(Debug, Deserialize)]
+struct StripeIpList {
+    #[serde(rename = "WEBHOOKS")]
+    webhooks: Vec<String>,
+}
+
+/// Periodically fetches Stripe's webhook IPs into a [`StripeIpAllowlist`]
+pub struct StripeIpRefresher {
+    config: StripeIpRefreshConfig,
+    allowlist: Arc<StripeIpAllowlist>,
+    http_client: reqwest::Client,
+}
+
+impl StripeIpRefresher {
+    pub fn new(config: StripeIpRefreshConfig, allowlist: Arc<StripeIpAllowlist>) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::error!(error = %e, "Failed to create custom HTTP client, using default");
+                reqwest::Client::new()
+            });
+
+        Self {
+            config,
+            allowlist,
+            http_client,
+        }
+    }
+
+    /// Fetch the list once and store it, returning the number of ranges
+    ///
+    /// On error the allowlist is left untouched.
+    pub async fn refresh_once(&self) -> Result<usize> {
+        let response = self
+            .http_client
+            .get(&self.config.url)
+            .send()
+            .await
+            .context("Failed to fetch Stripe webhook IPs")?
+            .error_for_status()
+            .context("Stripe webhook IP list request failed")?;
+        let list: StripeIpList = response
+            .json()
+            .await
+            .context("Invalid Stripe webhook IP list")?;
+
+        let ranges = parse_ranges(&list.webhooks)?;
+        let count = ranges.len();
+        self.allowlist.set(ranges, self.config.ttl);
+        Ok(count)
+    }
+
+    /// Refresh now, then every interval until cancelled
+    pub async fn run(self, cancel: CancellationToken) {
+        tracing::info!(
+            url = %self.config.url,
+            interval_secs = self.config.interval.as_secs(),
+            ttl_secs = self.config.ttl.as_secs(),
+            "Stripe webhook IP refresh started"
+        );
+
+        loop {
+            match self.refresh_once().await {
+                Ok(count) => tracing::debug!(ranges = count, "Stripe webhook IPs refreshed"),
+                Err(e) => tracing::warn!(
+                    error = %format!("{:#}", e),
+                    "Stripe webhook IP refresh failed, keeping the previous list"
+                ),
+            }
+
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    tracing::debug!("Stripe webhook IP refresh stopping");
+                    break;
+                }
+                _ = tokio::time::sleep(self.config.interval) => {}
+            }
+        }
+    }
+}
+
+/// Normalize published entries to CIDR ranges (bare IPs become /32 or /128)
+fn parse_ranges(entries: &[String]) -> Result<Vec<String>> {
+    if entries.is_empty() {
+        bail!("Stripe webhook IP list is empty");
+    }
+
+    entries
+        .iter()
+        .map(|entry| {
+            let entry = entry.trim();
+            let (addr, prefix) = match entry.split_once('/') {
+                Some((addr, prefix)) => (addr, Some(prefix)),
+                None => (entry, None),
+            };
+            let ip = IpAddr::from_str(addr)
+                .with_context(|| format!("Invalid Stripe webhook IP: {}", entry))?;
+            let max_prefix = if ip.is_ipv4() { 32 } else { 128 };
+            let prefix = match prefix {
+                Some(prefix) => prefix
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|p| *p <= max_prefix)
+                    .with_context(|| format!("Invalid Stripe webhook IP range: {}", entry))?,
+                None => max_prefix,
+            };
+            Ok(format!("{}/{}", ip, prefix))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one request with `response` and return the URL
+    async fn spawn_server(response: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 8192];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+        });
+
+        format!("http://{}/ips_webhooks.json", addr)
+    }
+
+    fn http_response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    fn refresher(url: String) -> (StripeIpRefresher, Arc<StripeIpAllowlist>) {
+        let allowlist = Arc::new(StripeIpAllowlist::default());
+        let config = StripeIpRefreshConfig {
+            enabled: true,
+            url,
+            ..Default::default()
+        };
+        (StripeIpRefresher::new(config, allowlist.clone()), allowlist)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_stores_published_ranges() {
+        let body = r#"{"WEBHOOKS": ["3.18.12.63", "10.1.0.0/16", "2600:1f18::1"]}"#;
+        let url = spawn_server(http_response("200 OK", body)).await;
+        let (refresher, allowlist) = refresher(url);
+
+        assert_eq!(refresher.refresh_once().await.unwrap(), 3);
+        assert_eq!(
+            allowlist.with_ranges(|ranges| ranges.to_vec()),
+            Some(vec![
+                "3.18.12.63/32".to_string(),
+                "10.1.0.0/16".to_string(),
+                "2600:1f18::1/128".to_string(),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_allowlist_empty() {
+        for response in [
+            http_response("500 Internal Server Error", "oops"),
+            http_response("200 OK", "not json"),
+            http_response("200 OK", r#"{"WEBHOOKS": []}"#),
+            http_response("200 OK", r#"{"WEBHOOKS": ["not-an-ip"]}"#),
+        ] {
+            let url = spawn_server(response).await;
+            let (refresher, allowlist) = refresher(url);
+
+            assert!(refresher.refresh_once().await.is_err());
+            assert!(allowlist.with_ranges(|_| ()).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_previous_ranges() {
+        // Nothing listens on this port once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        let (refresher, allowlist) = refresher(url);
+        allowlist.set(vec!["3.18.12.63/32".to_string()], Duration::from_secs(60));
+
+        assert!(refresher.refresh_once().await.is_err());
+        assert_eq!(allowlist.with_ranges(|ranges| ranges.len()), Some(1));
+    }
+
+    #[test]
+    fn test_expired_ranges_are_ignored() {
+        let allowlist = StripeIpAllowlist::default();
+        assert!(allowlist.with_ranges(|_| ()).is_none());
+
+        allowlist.set(vec!["3.18.12.63/32".to_string()], Duration::ZERO);
+        assert!(allowlist.with_ranges(|_| ()).is_none());
+    }
+
+    #[test]
+    fn test_parse_ranges_rejects_bad_prefix() {
+        assert!(parse_ranges(&["1.2.3.4/33".to_string()]).is_err());
+        assert!(parse_ranges(&["::1/129".to_string()]).is_err());
+        assert_eq!(
+            parse_ranges(&[" 1.2.3.4 ".to_string()]).unwrap(),
+            vec!["1.2.3.4/32".to_string()]
+        );
+    }
+}
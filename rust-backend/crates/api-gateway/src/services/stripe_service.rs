@@ -7,20 +7,29 @@
 //! - **Checkout Session creation**: Creates Stripe Checkout sessions for credit purchases
 //! - **Webhook verification**: Verifies and processes Stripe webhook events
 //! - **Subscription management**: Manages subscription lifecycle events
+//! - **Refunds and disputes**: Reports refunded or disputed purchases so their
+//!   credits can be taken back
+//!
+//! Billing handlers use it through [`PaymentProvider`], which reports events
+//! as [`PaymentEvent`]s with amounts converted to micro-USDC.
 //!
 //! # Security
 //!
 //! - Webhook signatures are verified using the Stripe-Signature header
 //! - All amounts are in micro-USDC (6 decimals) internally, converted to cents for Stripe
 
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use stripe::{
     CheckoutSession, CheckoutSessionMode, Client, CreateCheckoutSession,
     CreateCheckoutSessionLineItems, CreateCheckoutSessionLineItemsPriceData,
-    CreateCheckoutSessionLineItemsPriceDataProductData, Currency, EventType, Webhook,
+    CreateCheckoutSessionLineItemsPriceDataProductData, CreateCheckoutSessionPaymentIntentData,
+    Currency, EventType, Webhook,
 };
 use thiserror::Error;
 
+use super::payment_provider::{CheckoutResult, PaymentError, PaymentEvent, PaymentProvider};
+
 /// Micro-USDC per whole USDC (6 decimals)
 const MICRO_USDC_MULTIPLIER: i64 = 1_000_000;
 
@@ -49,15 +58,6 @@ pub enum StripeError {
     ConfigError(String),
 }
 
-/// Result of creating a checkout session
-#[derive(Debug, Clone)]
-pub struct CheckoutResult {
-    /// Stripe session ID
-    pub session_id: String,
-    /// Checkout URL for redirect
-    pub checkout_url: String,
-}
-
 /// Parsed webhook event
 #[derive(Debug)]
 pub enum WebhookEvent {
@@ -68,6 +68,8 @@ pub enum WebhookEvent {
         customer_id: Option<String>,
         amount_paid: i64,
         organization_id: String,
+        /// Payment intent of the purchase (refunds and disputes reference it)
+        payment_intent_id: Option<String>,
     },
     /// Payment failed
     PaymentFailed {
@@ -95,6 +97,18 @@ pub enum WebhookEvent {
         #[allow(dead_code)] // Future feature: Track cancellation history
         canceled_at: DateTime<Utc>,
     },
+    /// Purchase refunded (`charge.refunded`) or disputed (`charge.dispute.created`)
+    Refund {
+        /// Refund (`re_...`) or dispute (`dp_...`) ID, applied at most once
+        refund_id: String,
+        charge_id: String,
+        /// Total refunded (or disputed) of the charge so far, in cents
+        amount: i64,
+        /// From the charge metadata (set at checkout), if present
+        organization_id: Option<String>,
+        payment_intent_id: Option<String>,
+        dispute: bool,
+    },
     /// Unknown event type (ignored)
     Unknown(String),
 }
@@ -205,6 +219,16 @@ impl StripeService {
         );
         params.metadata = Some(metadata);
 
+        // Charges inherit the payment intent's metadata, so refunds and
+        // disputes can be traced back to the organization
+        params.payment_intent_data = Some(CreateCheckoutSessionPaymentIntentData {
+            metadata: Some(std::collections::HashMap::from([(
+                "organization_id".to_string(),
+                organization_id.to_string(),
+            )])),
+            ..Default::default()
+        });
+
         // Create the session
         let session = CheckoutSession::create(&self.client, params)
             .await
@@ -245,6 +269,8 @@ impl StripeService {
             EventType::CustomerSubscriptionCreated => self.parse_subscription_created(&event),
             EventType::CustomerSubscriptionUpdated => self.parse_subscription_updated(&event),
             EventType::CustomerSubscriptionDeleted => self.parse_subscription_canceled(&event),
+            EventType::ChargeRefunded => self.parse_charge_refunded(&event),
+            EventType::ChargeDisputeCreated => self.parse_dispute_created(&event),
             other => Ok(WebhookEvent::Unknown(format!("{:?}", other))),
         }
     }
@@ -264,6 +290,7 @@ impl StripeService {
         let session_id = session.id.to_string();
         let customer_id = session.customer.as_ref().map(|c| c.id().to_string());
         let amount_paid = session.amount_total.unwrap_or(0);
+        let payment_intent_id = session.payment_intent.as_ref().map(|p| p.id().to_string());
 
         // Get organization_id from metadata or client_reference_id
         let organization_id = session
@@ -281,6 +308,7 @@ impl StripeService {
             customer_id,
             amount_paid,
             organization_id,
+            payment_intent_id,
         })
     }
 
@@ -409,6 +437,67 @@ impl StripeService {
         })
     }
 
+    /// Parse a charge.refunded event
+    ///
+    /// The event is sent for every refund of the charge and reports the
+    /// charge's total refunded amount, keyed on the newest refund. Without the
+    /// refund list (not expanded by newer API versions) it is keyed on the
+    /// charge and that total, so each partial refund gets its own key while a
+    /// redelivered event still has the same one.
+    fn parse_charge_refunded(&self, event: &stripe::Event) -> Result<WebhookEvent, StripeError> {
+        let charge = match &event.data.object {
+            stripe::EventObject::Charge(c) => c,
+            _ => {
+                return Err(StripeError::WebhookError(
+                    "Expected Charge object".to_string(),
+                ))
+            }
+        };
+
+        let charge_id = charge.id.to_string();
+        let refund_id = match charge
+            .refunds
+            .as_ref()
+            .and_then(|refunds| refunds.data.iter().max_by_key(|r| r.created))
+        {
+            Some(refund) => refund.id.to_string(),
+            None => format!("{}:{}", charge_id, charge.amount_refunded),
+        };
+
+        Ok(WebhookEvent::Refund {
+            refund_id,
+            charge_id,
+            amount: charge.amount_refunded,
+            organization_id: charge.metadata.get("organization_id").cloned(),
+            payment_intent_id: charge.payment_intent.as_ref().map(|p| p.id().to_string()),
+            dispute: false,
+        })
+    }
+
+    /// Parse a charge.dispute.created event
+    ///
+    /// Disputed funds are withheld as soon as the dispute opens, so the
+    /// credits are taken back then rather than when it is lost.
+    fn parse_dispute_created(&self, event: &stripe::Event) -> Result<WebhookEvent, StripeError> {
+        let dispute = match &event.data.object {
+            stripe::EventObject::Dispute(d) => d,
+            _ => {
+                return Err(StripeError::WebhookError(
+                    "Expected Dispute object".to_string(),
+                ))
+            }
+        };
+
+        Ok(WebhookEvent::Refund {
+            refund_id: dispute.id.to_string(),
+            charge_id: dispute.charge.id().to_string(),
+            amount: dispute.amount,
+            organization_id: dispute.metadata.get("organization_id").cloned(),
+            payment_intent_id: dispute.payment_intent.as_ref().map(|p| p.id().to_string()),
+            dispute: true,
+        })
+    }
+
     /// Convert cents to micro-USDC
     ///
     /// # Arguments
@@ -435,6 +524,105 @@ impl StripeService {
     }
 }
 
+impl From<StripeError> for PaymentError {
+    fn from(err: StripeError) -> Self {
+        match err {
+            StripeError::InvalidSignature => PaymentError::InvalidSignature,
+            StripeError::InvalidAmount(msg) => PaymentError::InvalidAmount(msg),
+            other => PaymentError::Provider(other.to_string()),
+        }
+    }
+}
+
+impl From<WebhookEvent> for PaymentEvent {
+    fn from(event: WebhookEvent) -> Self {
+        match event {
+            WebhookEvent::CheckoutCompleted {
+                session_id,
+                amount_paid,
+                organization_id,
+                payment_intent_id,
+                ..
+            } => PaymentEvent::PurchaseCompleted {
+                reference_id: session_id,
+                organization_id,
+                amount: StripeService::cents_to_micro_usdc(amount_paid),
+                payment_id: payment_intent_id,
+            },
+            WebhookEvent::PaymentFailed {
+                session_id,
+                organization_id,
+                error_message,
+            } => PaymentEvent::PaymentFailed {
+                reference_id: session_id,
+                organization_id,
+                error_message,
+            },
+            WebhookEvent::SubscriptionCreated {
+                subscription_id,
+                customer_id,
+                plan,
+                organization_id,
+            } => PaymentEvent::SubscriptionCreated {
+                subscription_id,
+                customer_id,
+                plan,
+                organization_id,
+            },
+            WebhookEvent::SubscriptionUpdated {
+                subscription_id,
+                status,
+                ..
+            } => PaymentEvent::SubscriptionUpdated {
+                subscription_id,
+                status,
+            },
+            WebhookEvent::SubscriptionCanceled {
+                subscription_id, ..
+            } => PaymentEvent::SubscriptionCanceled { subscription_id },
+            WebhookEvent::Refund {
+                refund_id,
+                charge_id,
+                amount,
+                organization_id,
+                payment_intent_id,
+                dispute,
+            } => PaymentEvent::Refund {
+                refund_id,
+                charge_id,
+                amount: StripeService::cents_to_micro_usdc(amount),
+                organization_id,
+                payment_id: payment_intent_id,
+                dispute,
+            },
+            WebhookEvent::Unknown(event_type) => PaymentEvent::Unknown(event_type),
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for StripeService {
+    fn name(&self) -> &'static str {
+        "Stripe"
+    }
+
+    async fn create_checkout(
+        &self,
+        organization_id: &str,
+        amount_usdc: i64,
+        success_url: &str,
+        cancel_url: &str,
+    ) -> Result<CheckoutResult, PaymentError> {
+        Ok(self
+            .create_checkout_session(organization_id, amount_usdc, success_url, cancel_url)
+            .await?)
+    }
+
+    fn verify_webhook(&self, payload: &str, signature: &str) -> Result<PaymentEvent, PaymentError> {
+        Ok(StripeService::verify_webhook(self, payload, signature)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -495,6 +683,282 @@ mod tests {
         }
     }
 
+    fn service() -> StripeService {
+        StripeService::new(StripeConfig {
+            secret_key: "sk_test_xxx".to_string(),
+            webhook_secret: "whsec_xxx".to_string(),
+            currency: "usd".to_string(),
+        })
+        .unwrap()
+    }
+
+    fn event(event_type: &str, object: serde_json::Value) -> stripe::Event {
+        serde_json::from_value(serde_json::json!({
+            "id": "evt_test",
+            "object": "event",
+            "type": event_type,
+            "created": 1_700_000_000,
+            "livemode": false,
+            "pending_webhooks": 0,
+            "data": {"object": object}
+        }))
+        .unwrap()
+    }
+
+    fn charge(refunds: Option<serde_json::Value>) -> serde_json::Value {
+        serde_json::json!({
+            "id": "ch_test",
+            "object": "charge",
+            "amount": 10000,
+            "amount_captured": 10000,
+            "amount_refunded": 7500,
+            "billing_details": {},
+            "captured": true,
+            "created": 1_700_000_000,
+            "currency": "usd",
+            "disputed": false,
+            "livemode": false,
+            "metadata": {"organization_id": "org_refund"},
+            "paid": true,
+            "payment_intent": "pi_test",
+            "refunded": false,
+            "refunds": refunds,
+            "status": "succeeded"
+        })
+    }
+
+    fn refund(id: &str, amount: i64, created: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "object": "refund",
+            "amount": amount,
+            "created": created,
+            "currency": "usd",
+            "metadata": {}
+        })
+    }
+
+    #[test]
+    fn test_charge_refunded_reports_newest_refund() {
+        let refunds = serde_json::json!({
+            "object": "list",
+            "data": [refund("re_first", 5000, 1_700_000_100), refund("re_second", 2500, 1_700_000_200)],
+            "has_more": false,
+            "total_count": 2,
+            "url": "/v1/charges/ch_test/refunds"
+        });
+        let parsed = service()
+            .parse_charge_refunded(&event("charge.refunded", charge(Some(refunds))))
+            .unwrap();
+
+        match parsed {
+            WebhookEvent::Refund {
+                refund_id,
+                charge_id,
+                amount,
+                organization_id,
+                payment_intent_id,
+                dispute,
+            } => {
+                assert_eq!(refund_id, "re_second");
+                assert_eq!(charge_id, "ch_test");
+                assert_eq!(amount, 7500);
+                assert_eq!(organization_id.as_deref(), Some("org_refund"));
+                assert_eq!(payment_intent_id.as_deref(), Some("pi_test"));
+                assert!(!dispute);
+            }
+            other => panic!("Expected Refund event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_charge_refunded_without_refund_list_uses_total() {
+        let parsed = service()
+            .parse_charge_refunded(&event("charge.refunded", charge(None)))
+            .unwrap();
+
+        match parsed {
+            WebhookEvent::Refund {
+                refund_id, amount, ..
+            } => {
+                assert_eq!(refund_id, "ch_test:7500");
+                assert_eq!(amount, 7500);
+            }
+            other => panic!("Expected Refund event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_partial_refunds_without_refund_list_are_distinct() {
+        let refunded = |amount_refunded: i64| {
+            let mut charge = charge(None);
+            charge["amount_refunded"] = serde_json::json!(amount_refunded);
+            match service()
+                .parse_charge_refunded(&event("charge.refunded", charge))
+                .unwrap()
+            {
+                WebhookEvent::Refund {
+                    refund_id, amount, ..
+                } => (refund_id, amount),
+                other => panic!("Expected Refund event, got {:?}", other),
+            }
+        };
+
+        // Two partial refunds of 25.00 each: the second is not a duplicate of
+        // the first, and reports the running total
+        let first = refunded(2500);
+        let second = refunded(5000);
+        assert_eq!(first, ("ch_test:2500".to_string(), 2500));
+        assert_eq!(second, ("ch_test:5000".to_string(), 5000));
+
+        // A redelivery of the second keeps its key
+        assert_eq!(refunded(5000), second);
+    }
+
+    #[test]
+    fn test_dispute_created_is_refund() {
+        let dispute = serde_json::json!({
+            "id": "dp_test",
+            "object": "dispute",
+            "amount": 10000,
+            "balance_transactions": [],
+            "charge": "ch_test",
+            "created": 1_700_000_000,
+            "currency": "usd",
+            "evidence": {},
+            "evidence_details": {
+                "has_evidence": false,
+                "past_due": false,
+                "submission_count": 0
+            },
+            "is_charge_refundable": false,
+            "livemode": false,
+            "metadata": {},
+            "payment_intent": "pi_test",
+            "reason": "fraudulent",
+            "status": "needs_response"
+        });
+        let parsed = service()
+            .parse_dispute_created(&event("charge.dispute.created", dispute))
+            .unwrap();
+
+        match parsed {
+            WebhookEvent::Refund {
+                refund_id,
+                charge_id,
+                amount,
+                organization_id,
+                payment_intent_id,
+                dispute,
+            } => {
+                assert_eq!(refund_id, "dp_test");
+                assert_eq!(charge_id, "ch_test");
+                assert_eq!(amount, 10000);
+                assert_eq!(organization_id, None);
+                assert_eq!(payment_intent_id.as_deref(), Some("pi_test"));
+                assert!(dispute);
+            }
+            other => panic!("Expected Refund event, got {:?}", other),
+        }
+    }
+
+    // ========================================================================
+    // PaymentProvider tests
+    // ========================================================================
+
+    /// Signed `Stripe-Signature` header for `payload`, as Stripe sends it
+    fn sign(payload: &str, secret: &str) -> String {
+        use hmac::{Hmac, Mac};
+
+        let timestamp = Utc::now().timestamp();
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+        format!(
+            "t={},v1={}",
+            timestamp,
+            hex::encode(mac.finalize().into_bytes())
+        )
+    }
+
+    fn checkout_completed_payload() -> String {
+        serde_json::json!({
+            "id": "evt_checkout",
+            "object": "event",
+            "type": "checkout.session.completed",
+            "created": 1_700_000_000,
+            "livemode": false,
+            "pending_webhooks": 0,
+            "data": {"object": {
+                "id": "cs_test",
+                "object": "checkout.session",
+                "amount_total": 2500,
+                "automatic_tax": {"enabled": false},
+                "client_reference_id": "org_buyer",
+                "created": 1_700_000_000,
+                "custom_fields": [],
+                "custom_text": {},
+                "expires_at": 1_700_086_400,
+                "livemode": false,
+                "metadata": {"organization_id": "org_buyer", "amount_usdc": "25"},
+                "mode": "payment",
+                "payment_intent": "pi_test",
+                "payment_method_types": ["card"],
+                "payment_status": "paid",
+                "shipping_options": [],
+                "status": "complete"
+            }}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_provider_reports_purchase_in_micro_usdc() {
+        let provider: Box<dyn PaymentProvider> = Box::new(service());
+        let payload = checkout_completed_payload();
+
+        let event = provider
+            .verify_webhook(&payload, &sign(&payload, "whsec_xxx"))
+            .unwrap();
+        assert_eq!(
+            event,
+            PaymentEvent::PurchaseCompleted {
+                reference_id: "cs_test".to_string(),
+                organization_id: "org_buyer".to_string(),
+                amount: 25_000_000,
+                payment_id: Some("pi_test".to_string()),
+            }
+        );
+        assert_eq!(provider.name(), "Stripe");
+    }
+
+    #[test]
+    fn test_provider_rejects_bad_signature() {
+        let provider: Box<dyn PaymentProvider> = Box::new(service());
+        let payload = checkout_completed_payload();
+
+        let result = provider.verify_webhook(&payload, &sign(&payload, "whsec_other"));
+        assert!(matches!(result, Err(PaymentError::InvalidSignature)));
+    }
+
+    #[test]
+    fn test_refund_event_converted_to_micro_usdc() {
+        let event = service()
+            .parse_charge_refunded(&event("charge.refunded", charge(None)))
+            .unwrap();
+
+        assert_eq!(
+            PaymentEvent::from(event),
+            PaymentEvent::Refund {
+                refund_id: "ch_test:7500".to_string(),
+                charge_id: "ch_test".to_string(),
+                amount: 75_000_000,
+                organization_id: Some("org_refund".to_string()),
+                payment_id: Some("pi_test".to_string()),
+                dispute: false,
+            }
+        );
+    }
+
     // ========================================================================
     // Config tests
     // ========================================================================
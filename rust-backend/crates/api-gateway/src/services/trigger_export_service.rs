@@ -0,0 +1,616 @@
+//! Trigger Export/Import Service
+//!
+//! Builds portable bundles of an organization's triggers and re-creates them in
+//! another organization.
+//!
+//! # Org-specific references
+//!
+//! - Triggers managed by an agent follow are excluded from exports (the follow
+//!   record cannot be carried over; follow the agent in the target org instead).
+//! - String values in condition/action configs that equal the source
+//!   organization id are rewritten to the target organization id.
+//! - REST actions go through the webhook handshake like newly created ones. In
+//!   `enforce` mode a trigger whose REST endpoint fails it is not imported.
+//!
+//! # Transactions
+//!
+//! An import runs in a single transaction with one savepoint per trigger, so a
+//! failing trigger is rolled back on its own and reported while the rest of the
+//! bundle is still created. A dry run goes through the same steps and rolls the
+//! whole transaction back, so database constraints are checked too. Handshakes
+//! run before the transaction starts, so it is never held open for them.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use shared::models::{Trigger, TriggerAction, TriggerCondition};
+use shared::DbPool;
+use sqlx::Acquire;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{
+    ExcludedTrigger, ExportedAction, ExportedCondition, ExportedTrigger, TriggerExportBundle,
+    TriggerImportReport, TriggerImportResult, VerificationStatus, TRIGGER_BUNDLE_VERSION,
+};
+use crate::repositories::{
+    ActionRepository, AgentFollowRepository, ConditionRepository, TriggerRepository,
+};
+use crate::services::WebhookVerifier;
+
+/// Verification state to store with an imported action
+type ActionVerification = (VerificationStatus, Option<DateTime<Utc>>);
+
+/// Reason recorded for follow-managed triggers left out of an export
+const FOLLOW_MANAGED_REASON: &str =
+    "Managed by an agent follow; follow the agent in the target organization instead";
+
+/// Service for bulk export/import of trigger configurations
+pub struct TriggerExportService;
+
+impl TriggerExportService {
+    /// Export all triggers of an organization as a portable bundle
+    pub async fn export_organization(
+        pool: &DbPool,
+        organization_id: &str,
+    ) -> Result<TriggerExportBundle> {
+        let triggers = TriggerRepository::list_all_by_organization(pool, organization_id).await?;
+        let trigger_ids: Vec<String> = triggers.iter().map(|t| t.id.clone()).collect();
+
+        let (conditions, actions, managed_ids) = tokio::try_join!(
+            ConditionRepository::list_by_triggers(pool, &trigger_ids),
+            ActionRepository::list_by_triggers(pool, &trigger_ids),
+            AgentFollowRepository::list_managed_trigger_ids(pool, organization_id),
+        )?;
+
+        let managed: HashSet<String> = managed_ids.into_iter().collect();
+
+        Ok(build_bundle(
+            organization_id,
+            triggers,
+            conditions,
+            actions,
+            &managed,
+        ))
+    }
+
+    /// Import a bundle into an organization
+    ///
+    /// The caller is responsible for checking the bundle version and size.
+    /// Per-trigger failures (validation or database) are reported in the
+    /// result; an `Err` is only returned if the transaction itself fails.
+    /// With `dry_run` nothing is kept and no trigger ids are reported.
+    pub async fn import_into_organization(
+        pool: &DbPool,
+        verifier: &WebhookVerifier,
+        organization_id: &str,
+        user_id: &str,
+        bundle: &TriggerExportBundle,
+        dry_run: bool,
+    ) -> Result<TriggerImportReport> {
+        // Validate and verify every trigger before the transaction starts
+        let mut pending = Vec::with_capacity(bundle.triggers.len());
+
+        for (index, exported) in bundle.triggers.iter().enumerate() {
+            let mut result = TriggerImportResult {
+                index,
+                name: exported.name.clone(),
+                success: false,
+                trigger_id: None,
+                error: None,
+                warnings: Vec::new(),
+            };
+
+            if let Err(e) = exported.validate_for_import() {
+                result.error = Some(e);
+                pending.push((result, None));
+                continue;
+            }
+
+            let prepared = prepare_for_import(
+                exported,
+                bundle.source_organization_id.as_deref(),
+                organization_id,
+                &mut result.warnings,
+            );
+
+            match verify_actions(verifier, &prepared).await {
+                Ok(verifications) => {
+                    result
+                        .warnings
+                        .extend(verification_warnings(&verifications));
+                    pending.push((result, Some((prepared, verifications))));
+                }
+                Err(reason) => {
+                    result.error = Some(format!("Webhook verification failed: {}", reason));
+                    pending.push((result, None));
+                }
+            }
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .context("Failed to start import transaction")?;
+
+        let mut results = Vec::with_capacity(pending.len());
+
+        for (mut result, ready) in pending {
+            let Some((prepared, verifications)) = ready else {
+                results.push(result);
+                continue;
+            };
+
+            let mut savepoint = tx
+                .begin()
+                .await
+                .context("Failed to create import savepoint")?;
+
+            match create_trigger_in_tx(
+                &mut savepoint,
+                organization_id,
+                user_id,
+                &prepared,
+                &verifications,
+            )
+            .await
+            {
+                Ok(trigger_id) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .context("Failed to release import savepoint")?;
+                    result.success = true;
+                    result.trigger_id = (!dry_run).then_some(trigger_id);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        organization_id = %organization_id,
+                        index = result.index,
+                        error = %e,
+                        "Failed to import trigger"
+                    );
+                    savepoint
+                        .rollback()
+                        .await
+                        .context("Failed to roll back import savepoint")?;
+                    result.error = Some("Failed to create trigger".to_string());
+                }
+            }
+
+            results.push(result);
+        }
+
+        if dry_run {
+            tx.rollback()
+                .await
+                .context("Failed to roll back dry-run import")?;
+        } else {
+            tx.commit()
+                .await
+                .context("Failed to commit import transaction")?;
+        }
+
+        let imported = results.iter().filter(|r| r.success).count();
+        Ok(TriggerImportReport {
+            dry_run,
+            imported,
+            failed: results.len() - imported,
+            results,
+        })
+    }
+}
+
+/// Create a trigger with its conditions and actions inside a transaction
+async fn create_trigger_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    organization_id: &str,
+    user_id: &str,
+    exported: &ExportedTrigger,
+    verifications: &[ActionVerification],
+) -> Result<String> {
+    let trigger = TriggerRepository::create_in_tx(
+        &mut **tx,
+        user_id,
+        organization_id,
+        &exported.name,
+        exported.description.as_deref(),
+        exported.chain_id,
+        &exported.registry,
+        exported.enabled,
+        exported.is_stateful,
+        exported.cooldown_secs,
+        exported.schedule.as_ref(),
+    )
+    .await?;
+
+    for condition in &exported.conditions {
+        ConditionRepository::create_in_tx(
+            &mut **tx,
+            &trigger.id,
+            &condition.condition_type,
+            &condition.field,
+            &condition.operator,
+            &condition.value,
+            condition.config.as_ref(),
+        )
+        .await?;
+    }
+
+    for (action, (verification_status, verified_at)) in exported.actions.iter().zip(verifications) {
+        ActionRepository::create_in_tx(
+            &mut **tx,
+            &trigger.id,
+            &action.action_type,
+            action.priority,
+            &action.config,
+            *verification_status,
+            *verified_at,
+        )
+        .await?;
+    }
+
+    Ok(trigger.id)
+}
+
+/// Run the webhook handshake for each action of a trigger
+///
+/// Returns the verification state per action, or the reason a handshake
+/// failed in `enforce` mode.
+async fn verify_actions(
+    verifier: &WebhookVerifier,
+    exported: &ExportedTrigger,
+) -> Result<Vec<ActionVerification>, String> {
+    let mut verifications = Vec::with_capacity(exported.actions.len());
+    for action in &exported.actions {
+        verifications.push(
+            verifier
+                .status_for_action(&action.action_type, &action.config)
+                .await?,
+        );
+    }
+    Ok(verifications)
+}
+
+/// Build an export bundle from already-loaded rows
+///
+/// Follow-managed triggers are listed in `excluded` instead of `triggers`.
+pub fn build_bundle(
+    organization_id: &str,
+    triggers: Vec<Trigger>,
+    conditions: Vec<TriggerCondition>,
+    actions: Vec<TriggerAction>,
+    managed_trigger_ids: &HashSet<String>,
+) -> TriggerExportBundle {
+    let mut conditions_by_trigger: HashMap<String, Vec<ExportedCondition>> = HashMap::new();
+    for condition in conditions {
+        let value = match condition.value {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        };
+        conditions_by_trigger
+            .entry(condition.trigger_id)
+            .or_default()
+            .push(ExportedCondition {
+                condition_type: condition.condition_type,
+                field: condition.field,
+                operator: condition.operator,
+                value,
+                config: condition.config,
+            });
+    }
+
+    let mut actions_by_trigger: HashMap<String, Vec<ExportedAction>> = HashMap::new();
+    for action in actions {
+        actions_by_trigger
+            .entry(action.trigger_id)
+            .or_default()
+            .push(ExportedAction {
+                action_type: action.action_type,
+                priority: action.priority,
+                config: action.config,
+            });
+    }
+
+    let mut exported = Vec::with_capacity(triggers.len());
+    let mut excluded = Vec::new();
+
+    for trigger in triggers {
+        if managed_trigger_ids.contains(&trigger.id) {
+            excluded.push(ExcludedTrigger {
+                name: trigger.name,
+                reason: FOLLOW_MANAGED_REASON.to_string(),
+            });
+            continue;
+        }
+
+        exported.push(ExportedTrigger {
+            conditions: conditions_by_trigger
+                .remove(&trigger.id)
+                .unwrap_or_default(),
+            actions: actions_by_trigger.remove(&trigger.id).unwrap_or_default(),
+            name: trigger.name,
+            description: trigger.description,
+            chain_id: trigger.chain_id,
+            registry: trigger.registry,
+            enabled: trigger.enabled,
+            is_stateful: trigger.is_stateful,
+            cooldown_secs: trigger.cooldown_secs,
+            schedule: trigger.schedule,
+        });
+    }
+
+    TriggerExportBundle {
+        version: TRIGGER_BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        source_organization_id: Some(organization_id.to_string()),
+        triggers: exported,
+        excluded,
+    }
+}
+
+/// Rewrite org-specific references in a trigger for the target organization
+///
+/// Adjustments are appended to `warnings`.
+pub fn prepare_for_import(
+    exported: &ExportedTrigger,
+    source_organization_id: Option<&str>,
+    target_organization_id: &str,
+    warnings: &mut Vec<String>,
+) -> ExportedTrigger {
+    let mut prepared = exported.clone();
+
+    if let Some(source) = source_organization_id.filter(|s| *s != target_organization_id) {
+        for (idx, condition) in prepared.conditions.iter_mut().enumerate() {
+            if let Some(config) = condition.config.as_mut() {
+                let count = remap_string_values(config, source, target_organization_id);
+                if count > 0 {
+                    warnings.push(format!(
+                        "Remapped {} organization reference(s) in condition #{}",
+                        count, idx
+                    ));
+                }
+            }
+        }
+
+        for (idx, action) in prepared.actions.iter_mut().enumerate() {
+            let count = remap_string_values(&mut action.config, source, target_organization_id);
+            if count > 0 {
+                warnings.push(format!(
+                    "Remapped {} organization reference(s) in action #{}",
+                    count, idx
+                ));
+            }
+        }
+    }
+
+    prepared
+}
+
+/// Warnings for REST actions that will be stored without a passed handshake
+fn verification_warnings(verifications: &[ActionVerification]) -> Vec<String> {
+    verifications
+        .iter()
+        .enumerate()
+        .filter(|(_, (status, _))| {
+            matches!(
+                status,
+                VerificationStatus::Unverified | VerificationStatus::Failed
+            )
+        })
+        .map(|(idx, (status, _))| {
+            format!(
+                "REST action #{} imported as {}; re-run webhook verification",
+                idx,
+                status.as_str()
+            )
+        })
+        .collect()
+}
+
+/// Replace every string equal to `from` with `to`, returning the number replaced
+fn remap_string_values(value: &mut serde_json::Value, from: &str, to: &str) -> usize {
+    match value {
+        serde_json::Value::String(s) if s == from => {
+            *s = to.to_string();
+            1
+        }
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .map(|item| remap_string_values(item, from, to))
+            .sum(),
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .map(|item| remap_string_values(item, from, to))
+            .sum(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn trigger(id: &str, org: &str, name: &str) -> Trigger {
+        Trigger {
+            id: id.to_string(),
+            user_id: "user_a".to_string(),
+            organization_id: org.to_string(),
+            name: name.to_string(),
+            description: Some(format!("{} description", name)),
+            chain_id: Some(84532),
+            registry: "reputation".to_string(),
+            enabled: true,
+            is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn condition(trigger_id: &str) -> TriggerCondition {
+        TriggerCondition {
+            id: "c1".to_string(),
+            trigger_id: trigger_id.to_string(),
+            condition_type: "score_threshold".to_string(),
+            field: "score".to_string(),
+            operator: "<".to_string(),
+            value: json!("60"),
+            config: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn action(trigger_id: &str, action_type: &str, config: serde_json::Value) -> TriggerAction {
+        TriggerAction {
+            id: 1,
+            trigger_id: trigger_id.to_string(),
+            action_type: action_type.to_string(),
+            priority: 2,
+            config,
+            verification_status: "not_required".to_string(),
+            verified_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_build_bundle_strips_ids_and_groups_children() {
+        let bundle = build_bundle(
+            "org_a",
+            vec![
+                trigger("t1", "org_a", "First"),
+                trigger("t2", "org_a", "Second"),
+            ],
+            vec![condition("t1")],
+            vec![action("t2", "telegram", json!({"chat_id": "1"}))],
+            &HashSet::new(),
+        );
+
+        assert_eq!(bundle.version, TRIGGER_BUNDLE_VERSION);
+        assert_eq!(bundle.source_organization_id.as_deref(), Some("org_a"));
+        assert_eq!(bundle.triggers.len(), 2);
+        assert_eq!(bundle.triggers[0].conditions.len(), 1);
+        assert!(bundle.triggers[0].actions.is_empty());
+        assert!(bundle.triggers[1].conditions.is_empty());
+        assert_eq!(bundle.triggers[1].actions[0].priority, 2);
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("\"t1\""));
+        assert!(!json.contains("user_a"));
+    }
+
+    #[test]
+    fn test_build_bundle_excludes_follow_managed_triggers() {
+        let managed: HashSet<String> = ["t2".to_string()].into_iter().collect();
+        let bundle = build_bundle(
+            "org_a",
+            vec![
+                trigger("t1", "org_a", "Manual"),
+                trigger("t2", "org_a", "Follow"),
+            ],
+            vec![],
+            vec![],
+            &managed,
+        );
+
+        assert_eq!(bundle.triggers.len(), 1);
+        assert_eq!(bundle.triggers[0].name, "Manual");
+        assert_eq!(bundle.excluded.len(), 1);
+        assert_eq!(bundle.excluded[0].name, "Follow");
+    }
+
+    #[test]
+    fn test_export_import_round_trip_into_different_org() {
+        let bundle = build_bundle(
+            "org_a",
+            vec![trigger("t1", "org_a", "Alert")],
+            vec![condition("t1")],
+            vec![
+                action(
+                    "t1",
+                    "rest",
+                    json!({"url": "https://example.com/hook", "headers": {"X-Org": "org_a"}}),
+                ),
+                action("t1", "telegram", json!({"chat_id": "123"})),
+            ],
+            &HashSet::new(),
+        );
+
+        // Round-trip through JSON as a client would
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed: TriggerExportBundle = serde_json::from_str(&json).unwrap();
+
+        let mut warnings = Vec::new();
+        let prepared = prepare_for_import(
+            &parsed.triggers[0],
+            parsed.source_organization_id.as_deref(),
+            "org_b",
+            &mut warnings,
+        );
+
+        assert!(prepared.validate_for_import().is_ok());
+        assert_eq!(prepared.name, "Alert");
+        assert_eq!(prepared.conditions[0].value, "60");
+        assert_eq!(prepared.actions[0].config["headers"]["X-Org"], "org_b");
+        assert_eq!(prepared.actions[1].config["chat_id"], "123");
+        assert!(warnings.iter().any(|w| w.contains("action #0")));
+    }
+
+    #[test]
+    fn test_verification_warnings_only_for_unverified_rest_actions() {
+        let warnings = verification_warnings(&[
+            (VerificationStatus::NotRequired, None),
+            (VerificationStatus::Unverified, None),
+            (VerificationStatus::Verified, Some(Utc::now())),
+            (VerificationStatus::Failed, None),
+        ]);
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("action #1 imported as unverified"));
+        assert!(warnings[1].contains("action #3 imported as failed"));
+    }
+
+    #[test]
+    fn test_prepare_for_import_same_org_does_not_remap() {
+        let exported = ExportedTrigger {
+            name: "T".to_string(),
+            description: None,
+            chain_id: None,
+            registry: "identity".to_string(),
+            enabled: true,
+            is_stateful: false,
+            cooldown_secs: 0,
+            schedule: None,
+            conditions: vec![],
+            actions: vec![ExportedAction {
+                action_type: "mcp".to_string(),
+                priority: 0,
+                config: json!({"org": "org_a"}),
+            }],
+        };
+
+        let mut warnings = Vec::new();
+        let prepared = prepare_for_import(&exported, Some("org_a"), "org_a", &mut warnings);
+        assert_eq!(prepared.actions[0].config["org"], "org_a");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_remap_string_values_nested() {
+        let mut value = json!({
+            "a": "org_a",
+            "b": ["org_a", "other", {"c": "org_a"}],
+            "d": "prefix-org_a",
+            "e": 42
+        });
+
+        let count = remap_string_values(&mut value, "org_a", "org_b");
+        assert_eq!(count, 3);
+        assert_eq!(value["a"], "org_b");
+        assert_eq!(value["b"][0], "org_b");
+        assert_eq!(value["b"][2]["c"], "org_b");
+        assert_eq!(value["d"], "prefix-org_a");
+    }
+}
@@ -7,6 +7,9 @@
 //! - **32 bytes of entropy**: Uses `getrandom` (CSPRNG) for token generation
 //! - **SHA-256 hashing**: Fast lookup with 256-bit collision resistance
 //! - **Token rotation**: Old token invalidated when new one is issued
+//! - **Reuse detection**: Presenting an already-rotated token revokes every
+//!   token rotated from the same login (the token family), since either the
+//!   client or an attacker holds a stolen copy
 //! - **30-day expiration**: Refresh tokens expire after 30 days
 //!
 //! # Why SHA-256 instead of Argon2?
@@ -17,10 +20,13 @@
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
+use metrics::counter;
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
-use crate::repositories::RefreshTokenRepository;
+use crate::repositories::{
+    AuthFailureRepository, RefreshTokenRepository, RotateOutcome, SessionRecord,
+};
 use shared::DbPool;
 
 /// Token prefix for user refresh tokens
@@ -50,6 +56,9 @@ pub enum RefreshTokenError {
     #[error("Token has been revoked")]
     TokenRevoked,
 
+    #[error("Token was already rotated; its family has been revoked")]
+    TokenReused,
+
     #[error("User not found")]
     UserNotFound,
 
@@ -57,6 +66,15 @@ pub enum RefreshTokenError {
     DatabaseError(String),
 }
 
+/// A newly issued refresh token
+#[derive(Debug)]
+pub struct IssuedRefreshToken {
+    /// Raw token value, returned to the client once
+    pub token: String,
+    /// Login session (token family) the token belongs to
+    pub session_id: String,
+}
+
 /// Service for user refresh token operations
 #[derive(Clone, Default)]
 pub struct UserRefreshTokenService;
@@ -98,13 +116,13 @@ impl UserRefreshTokenService {
         user_id: &str,
         user_agent: Option<&str>,
         ip_address: Option<&str>,
-    ) -> Result<String, RefreshTokenError> {
+    ) -> Result<IssuedRefreshToken, RefreshTokenError> {
         let token = self.generate_token()?;
         let token_hash = Self::hash_token(&token);
 
         let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_VALIDITY_DAYS);
 
-        RefreshTokenRepository::create(
+        let session_id = RefreshTokenRepository::create(
             pool,
             user_id,
             &token_hash,
@@ -127,7 +145,7 @@ impl UserRefreshTokenService {
             );
         }
 
-        Ok(token)
+        Ok(IssuedRefreshToken { token, session_id })
     }
 
     /// Validate a refresh token and return the user_id
@@ -166,14 +184,19 @@ impl UserRefreshTokenService {
     /// Uses database row locking (SELECT ... FOR UPDATE) to prevent race conditions
     /// where two concurrent requests could both validate the same token.
     ///
-    /// Returns (user_id, new_refresh_token).
+    /// If the token was already rotated, its whole family is revoked, a
+    /// `refresh_token_reuse` auth failure is recorded and
+    /// [`RefreshTokenError::TokenReused`] returned.
+    ///
+    /// Returns (user_id, new_refresh_token); the new token stays in the same
+    /// session.
     pub async fn validate_and_rotate(
         &self,
         pool: &DbPool,
         refresh_token: &str,
         user_agent: Option<&str>,
         ip_address: Option<&str>,
-    ) -> Result<(String, String), RefreshTokenError> {
+    ) -> Result<(String, IssuedRefreshToken), RefreshTokenError> {
         // Validate token format
         if !refresh_token.starts_with(TOKEN_PREFIX) {
             return Err(RefreshTokenError::InvalidToken);
@@ -197,7 +220,21 @@ impl UserRefreshTokenService {
         .await
         .map_err(|e| RefreshTokenError::DatabaseError(e.to_string()))?;
 
-        let (user_id, _new_token_id) = result.ok_or(RefreshTokenError::InvalidToken)?;
+        let (user_id, session_id) = match result {
+            RotateOutcome::Rotated {
+                user_id, family_id, ..
+            } => (user_id, family_id),
+            RotateOutcome::Invalid => return Err(RefreshTokenError::InvalidToken),
+            RotateOutcome::Reused {
+                user_id,
+                family_id,
+                revoked,
+            } => {
+                Self::record_reuse(pool, &user_id, &family_id, revoked, user_agent, ip_address)
+                    .await;
+                return Err(RefreshTokenError::TokenReused);
+            }
+        };
 
         // Enforce session limit after creating new token
         if let Err(e) =
@@ -211,7 +248,54 @@ impl UserRefreshTokenService {
             );
         }
 
-        Ok((user_id, new_token))
+        Ok((
+            user_id,
+            IssuedRefreshToken {
+                token: new_token,
+                session_id,
+            },
+        ))
+    }
+
+    /// Log, count and audit the reuse of a rotated refresh token
+    async fn record_reuse(
+        pool: &DbPool,
+        user_id: &str,
+        family_id: &str,
+        revoked: u64,
+        user_agent: Option<&str>,
+        ip_address: Option<&str>,
+    ) {
+        tracing::error!(
+            user_id = %user_id,
+            family_id = %family_id,
+            revoked_tokens = revoked,
+            ip = ?ip_address,
+            "SECURITY: Rotated refresh token reused - revoked its token family"
+        );
+        counter!("auth_refresh_token_reuse_total").increment(1);
+
+        if let Err(e) = AuthFailureRepository::log(
+            pool,
+            "refresh_token_reuse",
+            Some(TOKEN_PREFIX),
+            ip_address,
+            user_agent,
+            Some("/api/v1/auth/refresh"),
+            Some(serde_json::json!({
+                "user_id": user_id,
+                "family_id": family_id,
+                "revoked_tokens": revoked,
+            })),
+        )
+        .await
+        {
+            tracing::error!(
+                error = %e,
+                user_id = %user_id,
+                "CRITICAL: Failed to log refresh token reuse to audit trail"
+            );
+        }
     }
 
     /// Revoke a specific refresh token
@@ -246,6 +330,50 @@ impl UserRefreshTokenService {
             .await
             .map_err(|e| RefreshTokenError::DatabaseError(e.to_string()))
     }
+
+    /// List a user's active login sessions
+    pub async fn list_sessions(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+    ) -> Result<Vec<SessionRecord>, RefreshTokenError> {
+        RefreshTokenRepository::list_sessions(pool, user_id)
+            .await
+            .map_err(|e| RefreshTokenError::DatabaseError(e.to_string()))
+    }
+
+    /// Revoke one of a user's sessions; its refresh token stops working at once
+    ///
+    /// Returns false if the user has no active session with that ID.
+    pub async fn revoke_session(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        session_id: &str,
+    ) -> Result<bool, RefreshTokenError> {
+        RefreshTokenRepository::revoke_session(pool, user_id, session_id)
+            .await
+            .map_err(|e| RefreshTokenError::DatabaseError(e.to_string()))
+    }
+
+    /// Revoke every session of a user except `current_session_id`
+    ///
+    /// Without a current session (access token issued before sessions were
+    /// tracked in it) all sessions are revoked.
+    pub async fn revoke_other_sessions(
+        &self,
+        pool: &DbPool,
+        user_id: &str,
+        current_session_id: Option<&str>,
+    ) -> Result<u64, RefreshTokenError> {
+        let result = match current_session_id {
+            Some(current) => {
+                RefreshTokenRepository::revoke_other_sessions(pool, user_id, current).await
+            }
+            None => RefreshTokenRepository::revoke_all_for_user(pool, user_id).await,
+        };
+        result.map_err(|e| RefreshTokenError::DatabaseError(e.to_string()))
+    }
 }
 
 /// Local getrandom wrapper using rand
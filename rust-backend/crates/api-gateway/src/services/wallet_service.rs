@@ -13,17 +13,33 @@
 //! - **EIP-191 signature verification**: Verifies personal_sign signatures
 //! - **On-chain ownership verification**: Checks IdentityRegistry.ownerOf() for agent NFTs
 //! - **Nonce management**: Prevents replay attacks with single-use nonces
+//! - **Chain queries**: Native balances and transaction status
+//! - **RPC failover**: Tries each chain's RPC endpoints in order, skipping
+//!   endpoints that keep failing
 //!
 //! # Security
 //!
 //! - Challenges expire after 5 minutes
 //! - Nonces are single-use and stored in the database
 //! - Signatures are verified using recovered public key matching
-
-use alloy::primitives::{PrimitiveSignature, B256};
+//!
+//! # Configuration
+//!
+//! Per chain (`ETHEREUM_SEPOLIA`, `BASE_SEPOLIA`, `LINEA_SEPOLIA`):
+//! - `{CHAIN}_RPC_URL`: primary RPC endpoint
+//! - `{CHAIN}_RPC_FALLBACK_URLS`: comma-separated endpoints tried after it
+//! - `{CHAIN}_IDENTITY_ADDRESS`: IdentityRegistry contract address
+//! - `SIWE_CHAIN_IDS`: comma-separated chains users can sign in on, in
+//!   addition to the chains above (default: Ethereum, Optimism, Base, Arbitrum)
+
+use alloy::primitives::{PrimitiveSignature, B256, U256};
 use alloy::signers::k256::ecdsa::VerifyingKey;
 use chrono::{DateTime, Duration, Utc};
+use metrics::counter;
 use rand::RngCore;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use thiserror::Error;
 
 /// Challenge expiration time in minutes
@@ -34,6 +50,19 @@ const CHALLENGE_EXPIRATION_MINUTES: i64 = 5;
 #[allow(dead_code)] // Future feature: Layer 2 wallet authentication
 const NONCE_LENGTH: usize = 32;
 
+/// Consecutive failures after which an RPC endpoint is skipped
+const RPC_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a failing RPC endpoint is skipped before it is tried again
+const RPC_COOLDOWN_SECS: u64 = 30;
+
+/// Timeout of a single RPC request, after which the next endpoint is tried
+const RPC_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Chains users can sign in on without `SIWE_CHAIN_IDS`: Ethereum, Optimism,
+/// Base and Arbitrum One
+const DEFAULT_LOGIN_CHAIN_IDS: [i32; 4] = [1, 10, 8453, 42161];
+
 /// Errors that can occur during wallet operations
 #[derive(Debug, Error)]
 pub enum WalletError {
@@ -57,6 +86,12 @@ pub enum WalletError {
     #[error("Invalid address format: {0}")]
     InvalidAddress(String),
 
+    #[error("Invalid transaction hash: {0}")]
+    InvalidTransactionHash(String),
+
+    #[error("Unsupported chain ID: {0}")]
+    UnsupportedChain(i32),
+
     #[error("Agent not found: {0}")]
     AgentNotFound(i64),
 
@@ -83,17 +118,67 @@ pub struct GeneratedChallenge {
     pub expires_at: DateTime<Utc>,
 }
 
+/// Outcome of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    /// Unknown to the node
+    NotFound,
+    /// Known but not mined yet
+    Pending,
+    /// Mined and succeeded
+    Success,
+    /// Mined and reverted
+    Failed,
+}
+
+/// Status of a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatus {
+    pub state: TransactionState,
+    /// Block the transaction was mined in
+    pub block_number: Option<u64>,
+}
+
 /// Configuration for on-chain verification
 #[derive(Debug, Clone)]
 pub struct ChainConfig {
     /// Chain ID
     pub chain_id: i32,
-    /// RPC URL for the chain
-    pub rpc_url: String,
+    /// RPC URLs for the chain, in failover order
+    pub rpc_urls: Vec<String>,
     /// IdentityRegistry contract address
     pub identity_registry_address: String,
 }
 
+/// Circuit breaker settings for RPC endpoints
+#[derive(Debug, Clone)]
+pub struct RpcFailoverConfig {
+    /// Consecutive failures after which an endpoint is skipped
+    pub failure_threshold: u32,
+    /// How long a skipped endpoint stays skipped
+    pub cooldown: std::time::Duration,
+    /// Timeout of a single RPC request
+    pub request_timeout: std::time::Duration,
+}
+
+impl Default for RpcFailoverConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: RPC_FAILURE_THRESHOLD,
+            cooldown: std::time::Duration::from_secs(RPC_COOLDOWN_SECS),
+            request_timeout: std::time::Duration::from_secs(RPC_REQUEST_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// Recent failures of one RPC endpoint
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    /// Skipped until then (circuit open)
+    open_until: Option<Instant>,
+}
+
 /// Service for wallet authentication operations
 ///
 /// This service is designed to be created once at startup and shared across
@@ -105,6 +190,11 @@ pub struct WalletService {
     chain_configs: Vec<ChainConfig>,
     /// HTTP client with connection pooling for RPC calls
     http_client: reqwest::Client,
+    failover: RpcFailoverConfig,
+    /// Circuit breaker state by RPC URL (shared by clones)
+    endpoint_health: Arc<Mutex<HashMap<String, EndpointHealth>>>,
+    /// Chains accepted for SIWE login besides those in `chain_configs`
+    login_chain_ids: Vec<i32>,
 }
 
 impl Default for WalletService {
@@ -119,6 +209,11 @@ impl WalletService {
     /// This constructor creates a shared HTTP client with connection pooling.
     /// The service should be created once at startup and shared via app state.
     pub fn new(chain_configs: Vec<ChainConfig>) -> Self {
+        Self::with_failover(chain_configs, RpcFailoverConfig::default())
+    }
+
+    /// Create a WalletService with custom RPC circuit breaker settings
+    pub fn with_failover(chain_configs: Vec<ChainConfig>, failover: RpcFailoverConfig) -> Self {
         let http_client = reqwest::Client::builder()
             .pool_max_idle_per_host(3) // Reduced from 10 for memory efficiency
             .pool_idle_timeout(std::time::Duration::from_secs(30)) // Reduced from 90s for faster recycling
@@ -130,50 +225,64 @@ impl WalletService {
         Self {
             chain_configs,
             http_client,
+            failover,
+            endpoint_health: Arc::new(Mutex::new(HashMap::new())),
+            login_chain_ids: DEFAULT_LOGIN_CHAIN_IDS.to_vec(),
         }
     }
 
+    /// Replace the chains accepted for SIWE login (configured chains are
+    /// always accepted)
+    pub fn with_login_chains(mut self, chain_ids: Vec<i32>) -> Self {
+        self.login_chain_ids = chain_ids;
+        self
+    }
+
     /// Load chain configurations from environment variables
     ///
     /// This function reads RPC URLs and contract addresses from environment
     /// variables for each supported chain.
     pub fn load_chain_configs_from_env() -> Vec<ChainConfig> {
-        let mut configs = Vec::new();
-
-        // Ethereum Sepolia (chain ID 11155111)
-        if let Ok(rpc_url) = std::env::var("ETHEREUM_SEPOLIA_RPC_URL") {
-            if let Ok(identity_addr) = std::env::var("ETHEREUM_SEPOLIA_IDENTITY_ADDRESS") {
-                configs.push(ChainConfig {
-                    chain_id: 11155111,
-                    rpc_url,
-                    identity_registry_address: identity_addr,
-                });
-            }
-        }
-
-        // Base Sepolia (chain ID 84532)
-        if let Ok(rpc_url) = std::env::var("BASE_SEPOLIA_RPC_URL") {
-            if let Ok(identity_addr) = std::env::var("BASE_SEPOLIA_IDENTITY_ADDRESS") {
-                configs.push(ChainConfig {
-                    chain_id: 84532,
-                    rpc_url,
-                    identity_registry_address: identity_addr,
-                });
-            }
-        }
+        [
+            // Ethereum Sepolia
+            (11155111, "ETHEREUM_SEPOLIA"),
+            // Base Sepolia
+            (84532, "BASE_SEPOLIA"),
+            // Linea Sepolia
+            (59141, "LINEA_SEPOLIA"),
+        ]
+        .into_iter()
+        .filter_map(|(chain_id, prefix)| {
+            let rpc_urls = rpc_urls(
+                std::env::var(format!("{}_RPC_URL", prefix)).ok(),
+                std::env::var(format!("{}_RPC_FALLBACK_URLS", prefix)).ok(),
+            );
+            let identity_addr = std::env::var(format!("{}_IDENTITY_ADDRESS", prefix)).ok()?;
+            (!rpc_urls.is_empty()).then_some(ChainConfig {
+                chain_id,
+                rpc_urls,
+                identity_registry_address: identity_addr,
+            })
+        })
+        .collect()
+    }
 
-        // Linea Sepolia (chain ID 59141)
-        if let Ok(rpc_url) = std::env::var("LINEA_SEPOLIA_RPC_URL") {
-            if let Ok(identity_addr) = std::env::var("LINEA_SEPOLIA_IDENTITY_ADDRESS") {
-                configs.push(ChainConfig {
-                    chain_id: 59141,
-                    rpc_url,
-                    identity_registry_address: identity_addr,
-                });
-            }
+    /// Load the SIWE login chains from `SIWE_CHAIN_IDS`
+    ///
+    /// Falls back to the default chains when unset or when no entry parses.
+    pub fn load_login_chain_ids_from_env() -> Vec<i32> {
+        let chain_ids = login_chain_ids(std::env::var("SIWE_CHAIN_IDS").ok());
+        if chain_ids.is_empty() {
+            DEFAULT_LOGIN_CHAIN_IDS.to_vec()
+        } else {
+            chain_ids
         }
+    }
 
-        configs
+    /// Whether users can sign in with a wallet on `chain_id`
+    pub fn supports_login_chain(&self, chain_id: i32) -> bool {
+        self.login_chain_ids.contains(&chain_id)
+            || self.chain_configs.iter().any(|c| c.chain_id == chain_id)
     }
 
     /// Generate a new challenge for a wallet address to sign
@@ -204,7 +313,7 @@ impl WalletService {
             "Sign this message to authenticate with ERC-8004 API\n\nWallet: {}\nNonce: {}\nExpires: {}",
             wallet_address,
             nonce,
-            expires_at.format("%Y-%m-%dT%H:%M:%SZ")
+            shared::timestamp::format(&expires_at)
         );
 
         Ok(GeneratedChallenge {
@@ -302,19 +411,10 @@ impl WalletService {
         agent_id: i64,
         chain_id: i32,
     ) -> Result<(), WalletError> {
-        // Get chain config
-        let config = self
-            .chain_configs
-            .iter()
-            .find(|c| c.chain_id == chain_id)
-            .ok_or_else(|| {
-                WalletError::OnChainError(format!("Unsupported chain ID: {}", chain_id))
-            })?;
+        let config = self.chain_config(chain_id)?;
 
         // Call IdentityRegistry.ownerOf(agentId)
-        let owner = self
-            .call_owner_of(&config.rpc_url, &config.identity_registry_address, agent_id)
-            .await?;
+        let owner = self.call_owner_of(config, agent_id).await?;
 
         // Compare addresses (case-insensitive)
         if owner.to_lowercase() != wallet_address.to_lowercase() {
@@ -327,6 +427,87 @@ impl WalletService {
         Ok(())
     }
 
+    /// Native token balance of `address`, in wei
+    pub async fn get_balance(&self, chain_id: i32, address: &str) -> Result<U256, WalletError> {
+        self.validate_address(address)?;
+        let chain = self.chain_config(chain_id)?;
+
+        let result = self
+            .rpc_result(
+                chain,
+                "eth_getBalance",
+                serde_json::json!([address, "latest"]),
+            )
+            .await?;
+        result
+            .as_str()
+            .and_then(parse_quantity)
+            .ok_or_else(|| WalletError::OnChainError(format!("Invalid balance: {}", result)))
+    }
+
+    /// Whether a transaction is unknown, pending, succeeded or reverted
+    pub async fn get_transaction_status(
+        &self,
+        chain_id: i32,
+        tx_hash: &str,
+    ) -> Result<TransactionStatus, WalletError> {
+        let hash = tx_hash.strip_prefix("0x").unwrap_or_default();
+        if hash.len() != 64 || hex::decode(hash).is_err() {
+            return Err(WalletError::InvalidTransactionHash(
+                "Expected 0x followed by 64 hex characters".to_string(),
+            ));
+        }
+        let chain = self.chain_config(chain_id)?;
+
+        let receipt = self
+            .rpc_result(
+                chain,
+                "eth_getTransactionReceipt",
+                serde_json::json!([tx_hash]),
+            )
+            .await?;
+        if receipt.is_null() {
+            // No receipt: either still in the mempool or unknown
+            let transaction = self
+                .rpc_result(
+                    chain,
+                    "eth_getTransactionByHash",
+                    serde_json::json!([tx_hash]),
+                )
+                .await?;
+            let state = if transaction.is_null() {
+                TransactionState::NotFound
+            } else {
+                TransactionState::Pending
+            };
+            return Ok(TransactionStatus {
+                state,
+                block_number: None,
+            });
+        }
+
+        let state = match receipt.get("status").and_then(|s| s.as_str()) {
+            Some("0x1") => TransactionState::Success,
+            Some("0x0") => TransactionState::Failed,
+            other => {
+                return Err(WalletError::OnChainError(format!(
+                    "Invalid receipt status: {:?}",
+                    other
+                )))
+            }
+        };
+        let block_number = receipt
+            .get("blockNumber")
+            .and_then(|b| b.as_str())
+            .and_then(parse_quantity)
+            .and_then(|b| u64::try_from(b).ok());
+
+        Ok(TransactionStatus {
+            state,
+            block_number,
+        })
+    }
+
     /// Validate that a challenge hasn't expired
     pub fn validate_challenge_expiration(
         &self,
@@ -404,8 +585,7 @@ impl WalletService {
     /// repeated RPC calls.
     async fn call_owner_of(
         &self,
-        rpc_url: &str,
-        contract_address: &str,
+        chain: &ChainConfig,
         token_id: i64,
     ) -> Result<String, WalletError> {
         use alloy::primitives::keccak256;
@@ -425,27 +605,21 @@ impl WalletService {
         call_data.extend_from_slice(selector);
         call_data.extend_from_slice(&token_bytes);
 
-        // Make eth_call request using shared HTTP client (connection pooling)
-        let response = self
-            .http_client
-            .post(rpc_url)
-            .json(&serde_json::json!({
-                "jsonrpc": "2.0",
-                "method": "eth_call",
-                "params": [{
-                    "to": contract_address,
-                    "data": format!("0x{}", hex::encode(&call_data))
-                }, "latest"],
-                "id": 1
-            }))
-            .send()
-            .await
-            .map_err(|e| WalletError::OnChainError(format!("RPC request failed: {}", e)))?;
-
-        let json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| WalletError::OnChainError(format!("Failed to parse response: {}", e)))?;
+        // Make eth_call request, failing over between the chain's endpoints
+        let json = self
+            .rpc_call(
+                chain,
+                &serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_call",
+                    "params": [{
+                        "to": chain.identity_registry_address,
+                        "data": format!("0x{}", hex::encode(&call_data))
+                    }, "latest"],
+                    "id": 1
+                }),
+            )
+            .await?;
 
         // Check for errors
         if let Some(error) = json.get("error") {
@@ -481,11 +655,236 @@ impl WalletService {
         let address = format!("0x{}", &result_hex[24..]);
         Ok(address)
     }
+
+    /// Configuration of a supported chain
+    fn chain_config(&self, chain_id: i32) -> Result<&ChainConfig, WalletError> {
+        self.chain_configs
+            .iter()
+            .find(|c| c.chain_id == chain_id)
+            .ok_or(WalletError::UnsupportedChain(chain_id))
+    }
+
+    /// Call `method` and return its `result` (JSON-RPC errors become
+    /// `OnChainError`)
+    async fn rpc_result(
+        &self,
+        chain: &ChainConfig,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        let mut json = self
+            .rpc_call(
+                chain,
+                &serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": method,
+                    "params": params,
+                    "id": 1
+                }),
+            )
+            .await?;
+
+        if let Some(error) = json.get("error") {
+            let msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error");
+            return Err(WalletError::OnChainError(msg.to_string()));
+        }
+        Ok(json
+            .get_mut("result")
+            .map(serde_json::Value::take)
+            .unwrap_or_default())
+    }
+
+    /// Send a JSON-RPC request to the first of the chain's endpoints that answers
+    ///
+    /// Endpoints are tried in configured order, skipping those whose circuit
+    /// is open (unless all of them are). Transport errors, timeouts, non-2xx
+    /// statuses and unparseable bodies fail over to the next endpoint; a
+    /// JSON-RPC error is an answer and is returned as is.
+    async fn rpc_call(
+        &self,
+        chain: &ChainConfig,
+        request: &serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        let mut last_error = WalletError::OnChainError(format!(
+            "No RPC endpoint configured for chain {}",
+            chain.chain_id
+        ));
+
+        for rpc_url in self.endpoints_to_try(chain) {
+            let endpoint = endpoint_label(rpc_url);
+            match self.send_rpc(rpc_url, request).await {
+                Ok(json) => {
+                    self.record_endpoint_result(rpc_url, true);
+                    counter!(
+                        "wallet_rpc_requests_total",
+                        "chain_id" => chain.chain_id.to_string(),
+                        "endpoint" => endpoint.clone(),
+                        "outcome" => "success"
+                    )
+                    .increment(1);
+                    tracing::debug!(
+                        chain_id = chain.chain_id,
+                        endpoint = %endpoint,
+                        "RPC request served"
+                    );
+                    return Ok(json);
+                }
+                Err(e) => {
+                    self.record_endpoint_result(rpc_url, false);
+                    counter!(
+                        "wallet_rpc_requests_total",
+                        "chain_id" => chain.chain_id.to_string(),
+                        "endpoint" => endpoint.clone(),
+                        "outcome" => "failure"
+                    )
+                    .increment(1);
+                    tracing::warn!(
+                        chain_id = chain.chain_id,
+                        endpoint = %endpoint,
+                        error = %e,
+                        "RPC endpoint failed, trying the next one"
+                    );
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Send a JSON-RPC request to one endpoint
+    async fn send_rpc(
+        &self,
+        rpc_url: &str,
+        request: &serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        let response = self
+            .http_client
+            .post(rpc_url)
+            .timeout(self.failover.request_timeout)
+            .json(request)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| WalletError::OnChainError(format!("RPC request failed: {}", e)))?;
+
+        response
+            .json()
+            .await
+            .map_err(|e| WalletError::OnChainError(format!("Failed to parse response: {}", e)))
+    }
+
+    /// The chain's endpoints with a closed (or cooled down) circuit, or all
+    /// of them when every circuit is open
+    fn endpoints_to_try<'a>(&self, chain: &'a ChainConfig) -> Vec<&'a str> {
+        let health = self
+            .endpoint_health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let available: Vec<&str> = chain
+            .rpc_urls
+            .iter()
+            .filter(|url| {
+                health
+                    .get(url.as_str())
+                    .and_then(|h| h.open_until)
+                    .is_none_or(|until| until <= now)
+            })
+            .map(String::as_str)
+            .collect();
+
+        if available.is_empty() {
+            chain.rpc_urls.iter().map(String::as_str).collect()
+        } else {
+            available
+        }
+    }
+
+    /// Update an endpoint's circuit after a request
+    ///
+    /// Failures open the circuit once they reach the threshold; a failure
+    /// after the cooldown reopens it straight away. A success closes it.
+    fn record_endpoint_result(&self, rpc_url: &str, success: bool) {
+        let mut health = self
+            .endpoint_health
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let entry = health.entry(rpc_url.to_string()).or_default();
+        if success {
+            *entry = EndpointHealth::default();
+            return;
+        }
+
+        entry.consecutive_failures = entry.consecutive_failures.saturating_add(1);
+        if entry.consecutive_failures >= self.failover.failure_threshold {
+            entry.open_until = Some(Instant::now() + self.failover.cooldown);
+        }
+    }
+}
+
+/// Parse a JSON-RPC hex quantity ("0x1bc16d674ec80000")
+fn parse_quantity(value: &str) -> Option<U256> {
+    let digits = value.strip_prefix("0x")?;
+    if digits.is_empty() {
+        return None;
+    }
+    U256::from_str_radix(digits, 16).ok()
+}
+
+/// Positive chain IDs from a comma-separated list, ignoring blanks and
+/// invalid entries
+fn login_chain_ids(value: Option<String>) -> Vec<i32> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .filter(|id| *id > 0)
+        .collect()
+}
+
+/// Primary RPC URL followed by the comma-separated fallbacks, without
+/// blanks or duplicates
+fn rpc_urls(primary: Option<String>, fallbacks: Option<String>) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    let candidates = primary.into_iter().chain(
+        fallbacks
+            .iter()
+            .flat_map(|f| f.split(',').map(str::to_string)),
+    );
+    for url in candidates {
+        let url = url.trim();
+        if !url.is_empty() && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// Endpoint name for logs and metrics
+///
+/// Only the host: RPC URLs often carry an API key in their path.
+fn endpoint_label(rpc_url: &str) -> String {
+    reqwest::Url::parse(rpc_url)
+        .ok()
+        .and_then(|url| {
+            url.host_str().map(|host| match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            })
+        })
+        .unwrap_or_else(|| "invalid".to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
 
     fn create_service() -> WalletService {
         WalletService::new(vec![])
@@ -660,4 +1059,341 @@ mod tests {
 
         assert_ne!(challenge1.nonce, challenge2.nonce);
     }
+
+    // ========================================================================
+    // RPC failover tests
+    // ========================================================================
+
+    const OWNER: &str = "0x742d35cc6634c0532925a3b844bc9e7595f0beb4";
+
+    /// JSON-RPC response body for a request, by method and params
+    type RpcHandler = fn(&str, &serde_json::Value) -> serde_json::Value;
+
+    /// Answers `ownerOf` with [`OWNER`]
+    fn owner_of(_method: &str, _params: &serde_json::Value) -> serde_json::Value {
+        serde_json::json!({"result": format!("0x{:0>64}", &OWNER[2..])})
+    }
+
+    /// Mock RPC endpoint: answers with `handler` while `healthy`, 502 otherwise
+    struct RpcServer {
+        url: String,
+        healthy: Arc<AtomicBool>,
+        hits: Arc<AtomicUsize>,
+    }
+
+    impl RpcServer {
+        async fn spawn(healthy: bool) -> Self {
+            Self::spawn_with(healthy, owner_of).await
+        }
+
+        async fn spawn_with(healthy: bool, handler: RpcHandler) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/v2/secret_key", listener.local_addr().unwrap());
+            let healthy = Arc::new(AtomicBool::new(healthy));
+            let hits = Arc::new(AtomicUsize::new(0));
+
+            let (server_healthy, server_hits) = (healthy.clone(), hits.clone());
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    server_hits.fetch_add(1, Ordering::SeqCst);
+                    let request: serde_json::Value =
+                        serde_json::from_slice(&read_request(&mut socket).await)
+                            .unwrap_or_default();
+
+                    let response = if server_healthy.load(Ordering::SeqCst) {
+                        let method = request["method"].as_str().unwrap_or_default();
+                        let mut body = handler(method, &request["params"]);
+                        body["jsonrpc"] = "2.0".into();
+                        body["id"] = 1.into();
+                        let body = body.to_string();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    } else {
+                        "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    };
+                    socket.write_all(response.as_bytes()).await.ok();
+                    socket.shutdown().await.ok();
+                }
+            });
+
+            Self { url, healthy, hits }
+        }
+
+        fn hits(&self) -> usize {
+            self.hits.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Read a request and return its body
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> Vec<u8> {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            if n == 0 {
+                return Vec::new();
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if request.len() >= header_end + 4 + content_length {
+                    return request[header_end + 4..].to_vec();
+                }
+            }
+        }
+    }
+
+    /// URL nothing listens on
+    async fn dead_endpoint() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        url
+    }
+
+    fn failover_service(rpc_urls: Vec<String>, failover: RpcFailoverConfig) -> WalletService {
+        WalletService::with_failover(
+            vec![ChainConfig {
+                chain_id: 84532,
+                rpc_urls,
+                identity_registry_address: "0x8004AA63c570c570eBF15376c0dB199918BFe9Fb".to_string(),
+            }],
+            failover,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_failing_primary_falls_through_to_secondary() {
+        let secondary = RpcServer::spawn(true).await;
+        let service = failover_service(
+            vec![dead_endpoint().await, secondary.url.clone()],
+            RpcFailoverConfig::default(),
+        );
+
+        service
+            .verify_agent_ownership(OWNER, 42, 84532)
+            .await
+            .unwrap();
+        assert_eq!(secondary.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_endpoint_used_again() {
+        let primary = RpcServer::spawn(false).await;
+        let secondary = RpcServer::spawn(true).await;
+        let service = failover_service(
+            vec![primary.url.clone(), secondary.url.clone()],
+            RpcFailoverConfig {
+                failure_threshold: 1,
+                cooldown: std::time::Duration::from_millis(200),
+                ..Default::default()
+            },
+        );
+
+        // Primary fails, secondary serves
+        service
+            .verify_agent_ownership(OWNER, 42, 84532)
+            .await
+            .unwrap();
+        assert_eq!((primary.hits(), secondary.hits()), (1, 1));
+
+        // Primary's circuit is open: skipped
+        service
+            .verify_agent_ownership(OWNER, 42, 84532)
+            .await
+            .unwrap();
+        assert_eq!((primary.hits(), secondary.hits()), (1, 2));
+
+        // After the cooldown the recovered primary serves again
+        primary.healthy.store(true, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        service
+            .verify_agent_ownership(OWNER, 42, 84532)
+            .await
+            .unwrap();
+        service
+            .verify_agent_ownership(OWNER, 42, 84532)
+            .await
+            .unwrap();
+        assert_eq!((primary.hits(), secondary.hits()), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn test_all_endpoints_down_still_tried() {
+        let primary = RpcServer::spawn(false).await;
+        let service = failover_service(
+            vec![primary.url.clone()],
+            RpcFailoverConfig {
+                failure_threshold: 1,
+                ..Default::default()
+            },
+        );
+
+        for _ in 0..2 {
+            let result = service.verify_agent_ownership(OWNER, 42, 84532).await;
+            assert!(matches!(result, Err(WalletError::OnChainError(_))));
+        }
+        // The only endpoint is tried even with its circuit open
+        assert_eq!(primary.hits(), 2);
+    }
+
+    // ========================================================================
+    // Chain query tests
+    // ========================================================================
+
+    const TX_HASH: &str = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+
+    /// Node knowing three transactions: mined (0x...4b), reverted (0x...4c)
+    /// and pending (0x...4d)
+    fn node(method: &str, params: &serde_json::Value) -> serde_json::Value {
+        let hash = params[0].as_str().unwrap_or_default();
+        let result = match (method, hash.chars().last()) {
+            ("eth_getBalance", _) => serde_json::json!("0x1bc16d674ec80000"),
+            ("eth_getTransactionReceipt", Some('b')) => {
+                serde_json::json!({"status": "0x1", "blockNumber": "0x10"})
+            }
+            ("eth_getTransactionReceipt", Some('c')) => {
+                serde_json::json!({"status": "0x0", "blockNumber": "0x11"})
+            }
+            ("eth_getTransactionByHash", Some('d')) => serde_json::json!({"hash": hash}),
+            _ => serde_json::Value::Null,
+        };
+        serde_json::json!({ "result": result })
+    }
+
+    #[tokio::test]
+    async fn test_get_balance() {
+        let server = RpcServer::spawn_with(true, node).await;
+        let service = failover_service(vec![server.url.clone()], RpcFailoverConfig::default());
+
+        let balance = service.get_balance(84532, OWNER).await.unwrap();
+        // 2 ETH
+        assert_eq!(balance.to_string(), "2000000000000000000");
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_status() {
+        let server = RpcServer::spawn_with(true, node).await;
+        let service = failover_service(vec![server.url.clone()], RpcFailoverConfig::default());
+        let status = |last: char| {
+            let hash = format!("{}{}", &TX_HASH[..TX_HASH.len() - 1], last);
+            let service = service.clone();
+            async move { service.get_transaction_status(84532, &hash).await.unwrap() }
+        };
+
+        assert_eq!(
+            status('b').await,
+            TransactionStatus {
+                state: TransactionState::Success,
+                block_number: Some(16),
+            }
+        );
+        assert_eq!(
+            status('c').await,
+            TransactionStatus {
+                state: TransactionState::Failed,
+                block_number: Some(17),
+            }
+        );
+        assert_eq!(status('d').await.state, TransactionState::Pending);
+        assert_eq!(status('e').await.state, TransactionState::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_chain_query_errors() {
+        fn rpc_error(_method: &str, _params: &serde_json::Value) -> serde_json::Value {
+            serde_json::json!({"error": {"code": -32000, "message": "header not found"}})
+        }
+        let server = RpcServer::spawn_with(true, rpc_error).await;
+        let service = failover_service(vec![server.url.clone()], RpcFailoverConfig::default());
+
+        assert!(matches!(
+            service.get_balance(1, OWNER).await,
+            Err(WalletError::UnsupportedChain(1))
+        ));
+        assert!(matches!(
+            service.get_balance(84532, "0x1234").await,
+            Err(WalletError::InvalidAddress(_))
+        ));
+        assert!(matches!(
+            service.get_transaction_status(84532, "0x1234").await,
+            Err(WalletError::InvalidTransactionHash(_))
+        ));
+        assert!(matches!(
+            service.get_balance(84532, OWNER).await,
+            Err(WalletError::OnChainError(msg)) if msg == "header not found"
+        ));
+    }
+
+    #[test]
+    fn test_rpc_urls_from_env_values() {
+        assert_eq!(
+            rpc_urls(
+                Some("https://a.example".to_string()),
+                Some(" https://b.example, ,https://a.example,https://c.example".to_string()),
+            ),
+            vec![
+                "https://a.example",
+                "https://b.example",
+                "https://c.example"
+            ]
+        );
+        assert_eq!(
+            rpc_urls(None, Some("https://b.example".to_string())),
+            vec!["https://b.example"]
+        );
+        assert!(rpc_urls(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_login_chain_ids_from_env_value() {
+        assert_eq!(
+            login_chain_ids(Some("1, 8453,,abc,-5,42161".to_string())),
+            vec![1, 8453, 42161]
+        );
+        assert!(login_chain_ids(None).is_empty());
+    }
+
+    #[test]
+    fn test_supports_login_chain() {
+        let service = WalletService::new(vec![ChainConfig {
+            chain_id: 84532,
+            rpc_urls: vec!["http://127.0.0.1:1".to_string()],
+            identity_registry_address: "0x0000000000000000000000000000000000000001".to_string(),
+        }]);
+        assert!(service.supports_login_chain(1));
+        assert!(service.supports_login_chain(8453));
+        // Chains configured for on-chain queries are accepted too
+        assert!(service.supports_login_chain(84532));
+        assert!(!service.supports_login_chain(56));
+
+        let service = service.with_login_chains(vec![56]);
+        assert!(service.supports_login_chain(56));
+        assert!(service.supports_login_chain(84532));
+        assert!(!service.supports_login_chain(1));
+    }
+
+    #[test]
+    fn test_endpoint_label_hides_path() {
+        assert_eq!(
+            endpoint_label("https://base-sepolia.g.alchemy.com/v2/secret_key"),
+            "base-sepolia.g.alchemy.com"
+        );
+        assert_eq!(endpoint_label("http://127.0.0.1:8545/"), "127.0.0.1:8545");
+        assert_eq!(endpoint_label("not a url"), "invalid");
+    }
 }
@@ -0,0 +1,591 @@
+//! Webhook Verification Handshake
+//!
+//! Confirms that a REST action's target endpoint is reachable and controlled by
+//! the user before real events are delivered to it.
+//!
+//! # Protocol
+//!
+//! The gateway POSTs a JSON challenge to the configured URL:
+//!
+//! ```json
+//! { "type": "webhook_verification", "challenge": "<64 hex chars>" }
+//! ```
+//!
+//! The same token is also sent in the `X-AgentAuri-Challenge` header. The
+//! endpoint passes verification by responding with a 2xx status within the
+//! timeout and echoing the token in one of:
+//! - the `X-AgentAuri-Challenge` response header
+//! - a JSON body `{"challenge": "<token>"}`
+//! - a plain-text body containing only the token
+//!
+//! # Configuration
+//!
+//! - `WEBHOOK_VERIFICATION_MODE`: `off` (default), `flag` or `enforce`
+//!   - `off`: no handshake, REST actions are stored as `unverified`
+//!   - `flag`: handshake runs, failures are stored as `failed`
+//!   - `enforce`: handshake runs, failures reject the request; the event
+//!     processor also skips REST actions that are not `verified`
+//! - `WEBHOOK_VERIFICATION_TIMEOUT_MS`: handshake timeout (default: 5000)
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::models::VerificationStatus;
+
+/// Header carrying the challenge token (request and response)
+pub const CHALLENGE_HEADER: &str = "X-AgentAuri-Challenge";
+
+/// Default handshake timeout in milliseconds
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+
+/// Maximum response body size read during the handshake (4KB)
+const MAX_RESPONSE_BYTES: usize = 4096;
+
+/// How handshake results affect action creation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// Handshake is not performed
+    Off,
+    /// Handshake runs; failed actions are created but flagged
+    Flag,
+    /// Handshake runs; failed actions are rejected
+    Enforce,
+}
+
+impl VerificationMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "off" | "disabled" | "false" => Some(Self::Off),
+            "flag" => Some(Self::Flag),
+            "enforce" | "block" => Some(Self::Enforce),
+            _ => None,
+        }
+    }
+}
+
+/// Webhook verification configuration
+#[derive(Debug, Clone)]
+pub struct WebhookVerificationConfig {
+    pub mode: VerificationMode,
+    pub timeout: Duration,
+    /// Allow loopback/private targets (tests and local development only)
+    pub allow_private_hosts: bool,
+}
+
+impl Default for WebhookVerificationConfig {
+    fn default() -> Self {
+        Self {
+            mode: VerificationMode::Off,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            allow_private_hosts: false,
+        }
+    }
+}
+
+impl WebhookVerificationConfig {
+    /// Load configuration from environment variables
+    ///
+    /// Unknown or unparsable values fall back to the defaults with a warning.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("WEBHOOK_VERIFICATION_MODE") {
+            match VerificationMode::parse(&value) {
+                Some(mode) => config.mode = mode,
+                None => tracing::warn!(
+                    value = %value,
+                    "Invalid WEBHOOK_VERIFICATION_MODE (expected off, flag or enforce), using off"
+                ),
+            }
+        }
+
+        if let Ok(value) = std::env::var("WEBHOOK_VERIFICATION_TIMEOUT_MS") {
+            match value.parse::<u64>() {
+                Ok(ms) if ms > 0 => config.timeout = Duration::from_millis(ms),
+                _ => tracing::warn!(
+                    value = %value,
+                    "Invalid WEBHOOK_VERIFICATION_TIMEOUT_MS, using {}ms",
+                    DEFAULT_TIMEOUT_MS
+                ),
+            }
+        }
+
+        config
+    }
+}
+
+/// Result of a verification handshake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    Verified,
+    /// Handshake failed, with a user-facing reason
+    Failed(String),
+}
+
+/// Performs the challenge/echo handshake against webhook endpoints
+///
+/// Create once at startup and share via app state.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    config: WebhookVerificationConfig,
+    http_client: reqwest::Client,
+}
+
+impl WebhookVerifier {
+    pub fn new(config: WebhookVerificationConfig) -> Self {
+        let http_client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            // Never follow redirects: the endpoint itself must answer the challenge
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(WebhookVerificationConfig::from_env())
+    }
+
+    pub fn mode(&self) -> VerificationMode {
+        self.config.mode
+    }
+
+    /// Whether the handshake should run automatically on action create/update
+    pub fn is_enabled(&self) -> bool {
+        self.config.mode != VerificationMode::Off
+    }
+
+    /// Verification state to store for a new or changed action
+    ///
+    /// Runs the handshake for REST actions when verification is enabled.
+    /// Every path that stores an action goes through here, so `enforce` mode
+    /// cannot be bypassed.
+    ///
+    /// # Returns
+    /// * `Ok((status, verified_at))` - The state to persist with the action
+    /// * `Err(reason)` - The handshake failed in `enforce` mode; the action
+    ///   must not be saved
+    pub async fn status_for_action(
+        &self,
+        action_type: &str,
+        config: &serde_json::Value,
+    ) -> Result<(VerificationStatus, Option<DateTime<Utc>>), String> {
+        let initial = VerificationStatus::initial_for(action_type);
+        if initial == VerificationStatus::NotRequired || !self.is_enabled() {
+            return Ok((initial, None));
+        }
+
+        match self.verify_action_config(config).await {
+            VerificationOutcome::Verified => Ok((VerificationStatus::Verified, Some(Utc::now()))),
+            VerificationOutcome::Failed(reason) => {
+                if self.config.mode == VerificationMode::Enforce {
+                    return Err(reason);
+                }
+                tracing::warn!(reason = %reason, "Webhook verification failed, flagging action");
+                Ok((VerificationStatus::Failed, None))
+            }
+        }
+    }
+
+    /// Run the handshake for a REST action config (expects a `url` field)
+    pub async fn verify_action_config(&self, config: &serde_json::Value) -> VerificationOutcome {
+        match config.get("url").and_then(|v| v.as_str()) {
+            Some(url) => self.verify(url).await,
+            None => VerificationOutcome::Failed("REST action config has no url".to_string()),
+        }
+    }
+
+    /// Send a challenge to `url` and check that it is echoed back
+    pub async fn verify(&self, url: &str) -> VerificationOutcome {
+        let parsed = match reqwest::Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(_) => return VerificationOutcome::Failed("Invalid webhook URL".to_string()),
+        };
+
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return VerificationOutcome::Failed("Webhook URL must use http or https".to_string());
+        }
+
+        let host = parsed.host_str().unwrap_or_default();
+        if !self.config.allow_private_hosts && is_private_host(host) {
+            return VerificationOutcome::Failed(
+                "Webhook URL must not target private or internal hosts".to_string(),
+            );
+        }
+
+        let challenge = generate_challenge();
+        let body = serde_json::json!({
+            "type": "webhook_verification",
+            "challenge": challenge,
+        });
+
+        let response = match self
+            .http_client
+            .post(parsed)
+            .header(CHALLENGE_HEADER, &challenge)
+            .json(&body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if e.is_timeout() => {
+                return VerificationOutcome::Failed(format!(
+                    "Endpoint did not respond within {}ms",
+                    self.config.timeout.as_millis()
+                ));
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Webhook verification request failed");
+                return VerificationOutcome::Failed("Endpoint is not reachable".to_string());
+            }
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            return VerificationOutcome::Failed(format!(
+                "Endpoint responded with HTTP {}",
+                status.as_u16()
+            ));
+        }
+
+        let header_echo = response
+            .headers()
+            .get(CHALLENGE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim() == challenge)
+            .unwrap_or(false);
+        if header_echo {
+            return VerificationOutcome::Verified;
+        }
+
+        let body = match read_capped(response).await {
+            Ok(Some(body)) => body,
+            Ok(None) => {
+                return VerificationOutcome::Failed(format!(
+                    "Endpoint response exceeds {} bytes",
+                    MAX_RESPONSE_BYTES
+                ));
+            }
+            Err(e) if e.is_timeout() => {
+                return VerificationOutcome::Failed(format!(
+                    "Endpoint did not respond within {}ms",
+                    self.config.timeout.as_millis()
+                ));
+            }
+            Err(_) => {
+                return VerificationOutcome::Failed("Failed to read endpoint response".to_string())
+            }
+        };
+
+        if body_echoes_challenge(&body, &challenge) {
+            VerificationOutcome::Verified
+        } else {
+            VerificationOutcome::Failed("Endpoint did not echo the challenge token".to_string())
+        }
+    }
+}
+
+/// Generate a random 32-byte challenge token (hex-encoded)
+fn generate_challenge() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Read a response body of at most `MAX_RESPONSE_BYTES`
+///
+/// Returns `Ok(None)` as soon as the body is known to be larger, without
+/// reading the rest of it.
+async fn read_capped(mut response: reqwest::Response) -> reqwest::Result<Option<Vec<u8>>> {
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_RESPONSE_BYTES as u64)
+    {
+        return Ok(None);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_RESPONSE_BYTES {
+            return Ok(None);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(Some(body))
+}
+
+/// Check whether a response body echoes the challenge (JSON or plain text)
+fn body_echoes_challenge(body: &[u8], challenge: &str) -> bool {
+    if body.is_empty() || body.len() > MAX_RESPONSE_BYTES {
+        return false;
+    }
+
+    if let Ok(json) = serde_json::from_slice::<serde_json::Value>(body) {
+        if json.get("challenge").and_then(|v| v.as_str()) == Some(challenge) {
+            return true;
+        }
+    }
+
+    std::str::from_utf8(body)
+        .map(|text| text.trim() == challenge)
+        .unwrap_or(false)
+}
+
+/// Check if a host is loopback, private or otherwise internal (SSRF protection)
+pub(crate) fn is_private_host(host: &str) -> bool {
+    let trimmed = host.trim_start_matches('[').trim_end_matches(']');
+
+    if let Ok(ip) = trimmed.parse::<IpAddr>() {
+        return match ip {
+            IpAddr::V4(ipv4) => {
+                ipv4.is_loopback()
+                    || ipv4.is_private()
+                    || ipv4.is_link_local()
+                    || ipv4.is_broadcast()
+                    || ipv4.is_unspecified()
+            }
+            IpAddr::V6(ipv6) => {
+                ipv6.is_loopback()
+                    || ipv6.is_unspecified()
+                    || ipv6
+                        .to_ipv4_mapped()
+                        .map(|v4| v4.is_loopback() || v4.is_private() || v4.is_link_local())
+                        .unwrap_or(false)
+            }
+        };
+    }
+
+    let lower = trimmed.to_lowercase();
+    lower.is_empty()
+        || lower == "localhost"
+        || lower.ends_with(".localhost")
+        || lower.ends_with(".local")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn test_verifier(timeout_ms: u64) -> WebhookVerifier {
+        WebhookVerifier::new(WebhookVerificationConfig {
+            mode: VerificationMode::Enforce,
+            timeout: Duration::from_millis(timeout_ms),
+            allow_private_hosts: true,
+        })
+    }
+
+    /// Extract the challenge header value from a raw HTTP request
+    fn challenge_from_request(raw: &str) -> String {
+        raw.lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case(CHALLENGE_HEADER)
+                    .then(|| value.trim().to_string())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Spawn a one-shot HTTP server that builds its response from the challenge
+    async fn spawn_server<F>(respond: F) -> String
+    where
+        F: FnOnce(String) -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 8192];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let response = respond(challenge_from_request(&request));
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+        });
+
+        format!("http://{}/webhook", addr)
+    }
+
+    fn http_response(status: &str, extra_headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+            status,
+            body.len(),
+            extra_headers,
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handshake_succeeds_with_body_echo() {
+        let url = spawn_server(|challenge| http_response("200 OK", "", &challenge)).await;
+
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert_eq!(outcome, VerificationOutcome::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_succeeds_with_json_echo() {
+        let url = spawn_server(|challenge| {
+            let body = serde_json::json!({ "challenge": challenge }).to_string();
+            http_response("200 OK", "Content-Type: application/json\r\n", &body)
+        })
+        .await;
+
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert_eq!(outcome, VerificationOutcome::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_succeeds_with_header_echo() {
+        let url = spawn_server(|challenge| {
+            let header = format!("{}: {}\r\n", CHALLENGE_HEADER, challenge);
+            http_response("204 No Content", &header, "")
+        })
+        .await;
+
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert_eq!(outcome, VerificationOutcome::Verified);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_fails_on_wrong_token() {
+        let url = spawn_server(|_| http_response("200 OK", "", "not-the-token")).await;
+
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert!(matches!(outcome, VerificationOutcome::Failed(msg) if msg.contains("echo")));
+    }
+
+    #[tokio::test]
+    async fn test_handshake_fails_on_error_status() {
+        let url =
+            spawn_server(|challenge| http_response("500 Internal Server Error", "", &challenge))
+                .await;
+
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert!(matches!(outcome, VerificationOutcome::Failed(msg) if msg.contains("HTTP 500")));
+    }
+
+    #[tokio::test]
+    async fn test_handshake_fails_on_non_responsive_endpoint() {
+        // Accept the connection but never write a response
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let outcome = test_verifier(200)
+            .verify(&format!("http://{}/webhook", addr))
+            .await;
+        assert!(
+            matches!(&outcome, VerificationOutcome::Failed(msg) if msg.contains("did not respond")),
+            "unexpected outcome: {:?}",
+            outcome
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handshake_stops_reading_oversized_body() {
+        // Stream a chunked body that never ends
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 8192];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await
+                .unwrap();
+            let chunk = format!("400\r\n{}\r\n", "a".repeat(0x400));
+            while socket.write_all(chunk.as_bytes()).await.is_ok() {}
+        });
+
+        let outcome = test_verifier(5000)
+            .verify(&format!("http://{}/webhook", addr))
+            .await;
+        assert!(
+            matches!(&outcome, VerificationOutcome::Failed(msg) if msg.contains("exceeds")),
+            "unexpected outcome: {:?}",
+            outcome
+        );
+
+        // A declared oversized body is not read at all
+        let url = spawn_server(|_| http_response("200 OK", "", &"a".repeat(8192))).await;
+        let outcome = test_verifier(2000).verify(&url).await;
+        assert!(matches!(outcome, VerificationOutcome::Failed(msg) if msg.contains("exceeds")));
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejects_private_hosts_by_default() {
+        let verifier = WebhookVerifier::new(WebhookVerificationConfig::default());
+
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://localhost/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.0.0.5/hook",
+            "http://[::1]/hook",
+        ] {
+            let outcome = verifier.verify(url).await;
+            assert!(
+                matches!(&outcome, VerificationOutcome::Failed(msg) if msg.contains("private")),
+                "{} should be rejected, got {:?}",
+                url,
+                outcome
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejects_invalid_urls() {
+        let verifier = test_verifier(200);
+
+        assert!(matches!(
+            verifier.verify("not a url").await,
+            VerificationOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            verifier.verify("ftp://example.com/hook").await,
+            VerificationOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            verifier.verify_action_config(&serde_json::json!({})).await,
+            VerificationOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_mode_parse() {
+        assert_eq!(VerificationMode::parse("off"), Some(VerificationMode::Off));
+        assert_eq!(
+            VerificationMode::parse("FLAG"),
+            Some(VerificationMode::Flag)
+        );
+        assert_eq!(
+            VerificationMode::parse("enforce"),
+            Some(VerificationMode::Enforce)
+        );
+        assert_eq!(VerificationMode::parse("sometimes"), None);
+    }
+
+    #[test]
+    fn test_generate_challenge_is_unique_hex() {
+        let a = generate_challenge();
+        let b = generate_challenge();
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+}
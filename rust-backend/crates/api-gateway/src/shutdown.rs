@@ -0,0 +1,203 @@
+//! Graceful shutdown sequence
+//!
+//! On SIGTERM or Ctrl-C the gateway shuts down in this order:
+//!
+//! 1. Stop accepting new connections
+//! 2. Let in-flight requests finish, for up to `SERVER_SHUTDOWN_TIMEOUT_SECS`
+//!    (connections still busy after that are dropped)
+//! 3. Cancel background tasks (cleanup jobs, A2A processor, pool metrics)
+//! 4. Wait for the A2A processor, which gives executing tasks up to
+//!    `A2A_SHUTDOWN_TIMEOUT_SECS` and requeues the rest
+//! 5. Close the database pools
+//!
+//! Background tasks are cancelled only after the server has drained, so no
+//! request loses work it started. actix's built-in signal handling is
+//! disabled (`HttpServer::disable_signals`) so this is the only shutdown path.
+
+use std::future::Future;
+
+use actix_web::dev::Server;
+use tokio_util::sync::CancellationToken;
+
+/// Resolve when the process receives SIGTERM or Ctrl-C (SIGINT)
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Ctrl-C received, shutting down"),
+        _ = terminate => tracing::info!("SIGTERM received, shutting down"),
+    }
+}
+
+/// Ordered shutdown of the HTTP server and the background tasks behind it
+#[derive(Default)]
+pub struct ShutdownSequence {
+    background: Vec<CancellationToken>,
+}
+
+impl ShutdownSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel `token` once the server has drained
+    pub fn cancel_after_drain(mut self, token: CancellationToken) -> Self {
+        self.background.push(token);
+        self
+    }
+
+    /// Run the server until `signal` resolves, then drain it and stop background work
+    ///
+    /// The server must be built with `shutdown_timeout` (the drain bound) and
+    /// `disable_signals`. Returns once the server has stopped and every
+    /// registered token has been cancelled.
+    pub async fn run<F>(self, server: Server, signal: F) -> std::io::Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let handle = server.handle();
+        tokio::spawn(async move {
+            signal.await;
+            tracing::info!("Stopping HTTP server, draining in-flight requests");
+            // Graceful: close listeners now, wait for workers up to the timeout
+            handle.stop(true).await;
+        });
+
+        let result = server.await;
+        tracing::info!(
+            "HTTP server drained, stopping {} background task groups",
+            self.background.len()
+        );
+        for token in &self.background {
+            token.cancel();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use std::time::{Duration, Instant};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::sync::oneshot;
+
+    /// Handler that outlives the shutdown signal and reports whether the
+    /// background token was already cancelled when it finished
+    async fn slow(token: web::Data<CancellationToken>) -> HttpResponse {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        HttpResponse::Ok().body(format!("cancelled={}", token.is_cancelled()))
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> std::io::Result<String> {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream
+            .write_all(
+                format!(
+                    "GET {} HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n",
+                    path
+                )
+                .as_bytes(),
+            )
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    #[actix_web::test]
+    async fn test_in_flight_request_drains_before_background_cancel() {
+        let token = CancellationToken::new();
+        let app_token = token.clone();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(app_token.clone()))
+                .route("/slow", web::get().to(slow))
+        })
+        .workers(1)
+        .shutdown_timeout(5)
+        .disable_signals()
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let addr = server.addrs()[0];
+
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let sequence = ShutdownSequence::new().cancel_after_drain(token.clone());
+        let running = tokio::spawn(sequence.run(server.run(), async {
+            let _ = signal_rx.await;
+        }));
+
+        // Start a request, then signal shutdown while it is in flight
+        let in_flight = tokio::spawn(get(addr, "/slow"));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        signal_tx.send(()).unwrap();
+
+        let response = in_flight.await.unwrap().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("cancelled=false"), "{}", response);
+
+        running.await.unwrap().unwrap();
+        assert!(token.is_cancelled());
+
+        // The listener is closed
+        assert!(get(addr, "/slow").await.is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_drain_is_bounded_by_shutdown_timeout() {
+        let token = CancellationToken::new();
+        let server = HttpServer::new(|| {
+            App::new().route(
+                "/hang",
+                web::get().to(|| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    HttpResponse::Ok().finish()
+                }),
+            )
+        })
+        .workers(1)
+        .shutdown_timeout(1)
+        .disable_signals()
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let addr = server.addrs()[0];
+
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let sequence = ShutdownSequence::new().cancel_after_drain(token.clone());
+        let running = tokio::spawn(sequence.run(server.run(), async {
+            let _ = signal_rx.await;
+        }));
+
+        let _hung = tokio::spawn(get(addr, "/hang"));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let start = Instant::now();
+        signal_tx.send(()).unwrap();
+
+        running.await.unwrap().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(token.is_cancelled());
+    }
+}
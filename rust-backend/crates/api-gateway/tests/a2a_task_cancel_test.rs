@@ -0,0 +1,163 @@
+//! End-to-end tests for A2A task cancellation
+//!
+//! # Test Coverage
+//!
+//! - Submitted tasks are cancelled over REST, audited and their streams ended
+//! - Finished tasks cannot be cancelled (409 / JSON-RPC -32005)
+//! - Tasks of other organizations are not found
+//!
+//! # Running Tests
+//!
+//! Requires Docker:
+//!
+//! ```bash
+//! cargo test -p api-gateway --features integration-tests --test a2a_task_cancel_test
+//! ```
+
+#![cfg(feature = "integration-tests")]
+
+mod common;
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use api_gateway::repositories::{A2aTaskEventRepository, A2aTaskRepository};
+
+use crate::common::containers::TestEnv;
+use crate::common::{create_test_app, register_with_personal_org};
+
+/// POST /api/v1/a2a/tasks/{id}/cancel; returns the status and body
+async fn cancel<S, B>(app: &S, bearer: &str, org_id: &str, task_id: &Uuid) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/v1/a2a/tasks/{}/cancel", task_id))
+        .insert_header(("Authorization", bearer))
+        .insert_header(("X-Organization-ID", org_id))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+#[actix_web::test]
+async fn test_cancel_submitted_task() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_cancel_user").await;
+
+    let task = A2aTaskRepository::create(env.pool(), &org_id, "getReputationSummary", &json!({}))
+        .await
+        .unwrap();
+
+    let (status, body) = cancel(&app, &bearer, &org_id, &task.id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["task_id"], task.id.to_string());
+    assert_eq!(body["status"], "cancelled");
+
+    // The processor will never claim it
+    let stored = A2aTaskRepository::find_by_id(env.pool(), &task.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, "cancelled");
+    assert!(stored.completed_at.is_some());
+
+    // The cancellation is audited with the user as actor
+    let (actor_type,): (String,) = sqlx::query_as(
+        "SELECT actor_type FROM a2a_task_audit_log WHERE task_id = $1 AND event_type = 'cancelled'",
+    )
+    .bind(task.id)
+    .fetch_one(env.pool())
+    .await
+    .unwrap();
+    assert_eq!(actor_type, "user");
+
+    // Streams of the task are sent a final event
+    let events = A2aTaskEventRepository::list_after(env.pool(), &task.id, 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, "error");
+    assert_eq!(events[0].data["status"], "cancelled");
+
+    // A cancelled task cannot be cancelled again
+    let (status, _) = cancel(&app, &bearer, &org_id, &task.id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[actix_web::test]
+async fn test_cancel_completed_task_conflicts() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_completed_user").await;
+
+    let task = A2aTaskRepository::create(env.pool(), &org_id, "getReputationSummary", &json!({}))
+        .await
+        .unwrap();
+    A2aTaskRepository::complete_task(env.pool(), &task.id, &json!({"score": 90}), Some(0.01))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (status, body) = cancel(&app, &bearer, &org_id, &task.id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "conflict");
+
+    // Same over JSON-RPC
+    let req = test::TestRequest::post()
+        .uri("/api/v1/a2a/rpc")
+        .insert_header(("Authorization", bearer.as_str()))
+        .insert_header(("X-Organization-ID", org_id.as_str()))
+        .set_json(json!({
+            "jsonrpc": "2.0",
+            "method": "tasks/cancel",
+            "params": { "task_id": task.id.to_string() },
+            "id": 1
+        }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["error"]["code"], -32005);
+    assert_eq!(body["error"]["data"]["status"], "completed");
+
+    // The result is kept
+    let stored = A2aTaskRepository::find_by_id(env.pool(), &task.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, "completed");
+    assert_eq!(stored.result, Some(json!({"score": 90})));
+}
+
+#[actix_web::test]
+async fn test_cancel_other_organizations_task_not_found() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (_, owner_org_id) = register_with_personal_org(&app, "a2a_owner").await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_other").await;
+
+    let task = A2aTaskRepository::create(
+        env.pool(),
+        &owner_org_id,
+        "getReputationSummary",
+        &json!({}),
+    )
+    .await
+    .unwrap();
+
+    let (status, _) = cancel(&app, &bearer, &org_id, &task.id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let stored = A2aTaskRepository::find_by_id(env.pool(), &task.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, "submitted");
+}
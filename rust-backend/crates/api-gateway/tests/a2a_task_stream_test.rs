@@ -0,0 +1,175 @@
+//! End-to-end tests for A2A task progress streaming (SSE)
+//!
+//! # Test Coverage
+//!
+//! - Every recorded event is sent with its ID, ending at the final event
+//! - Reconnecting with `Last-Event-ID` sends only the later events
+//! - After the replay, events recorded later are sent live
+//!
+//! # Running Tests
+//!
+//! Requires Docker:
+//!
+//! ```bash
+//! cargo test -p api-gateway --features integration-tests --test a2a_task_stream_test
+//! ```
+
+#![cfg(feature = "integration-tests")]
+
+mod common;
+
+use std::time::Duration;
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::test;
+use serde_json::json;
+use uuid::Uuid;
+
+use api_gateway::models::a2a::{TaskGetResult, TaskStatus};
+use api_gateway::repositories::{A2aTaskEventRepository, A2aTaskRepository};
+use shared::DbPool;
+
+use crate::common::containers::TestEnv;
+use crate::common::{create_test_app, register_with_personal_org};
+
+fn status(task_id: &Uuid, status: TaskStatus, progress: f64) -> TaskGetResult {
+    TaskGetResult {
+        task_id: task_id.to_string(),
+        status,
+        progress: Some(progress),
+        result: None,
+        error: None,
+        cost: None,
+        duration_ms: None,
+    }
+}
+
+/// Record progress events at 0%, 50% and a completion; returns their IDs
+async fn record_events(pool: &DbPool, task_id: &Uuid) -> Vec<i64> {
+    let mut ids = Vec::new();
+    for (task_status, progress) in [
+        (TaskStatus::Working, 0.0),
+        (TaskStatus::Working, 0.5),
+        (TaskStatus::Completed, 1.0),
+    ] {
+        let event =
+            A2aTaskEventRepository::append(pool, task_id, &status(task_id, task_status, progress))
+                .await
+                .unwrap();
+        ids.push(event.id);
+    }
+    ids
+}
+
+/// GET the task's stream until it ends; returns the body
+async fn stream<S, B>(
+    app: &S,
+    bearer: &str,
+    org_id: &str,
+    task_id: &Uuid,
+    last_event_id: Option<i64>,
+) -> String
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let mut req = test::TestRequest::get()
+        .uri(&format!("/api/v1/a2a/tasks/{}/stream", task_id))
+        .insert_header(("Authorization", bearer))
+        .insert_header(("X-Organization-ID", org_id));
+    if let Some(id) = last_event_id {
+        req = req.insert_header(("Last-Event-ID", id.to_string()));
+    }
+    let resp = test::call_service(app, req.to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+}
+
+/// IDs of the events in an SSE body, in order
+fn event_ids(body: &str) -> Vec<i64> {
+    body.lines()
+        .filter_map(|line| line.strip_prefix("id: "))
+        .map(|id| id.parse().unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_stream_sends_all_events() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_stream_user").await;
+
+    let task = A2aTaskRepository::create(env.pool(), &org_id, "getReputationSummary", &json!({}))
+        .await
+        .unwrap();
+    let ids = record_events(env.pool(), &task.id).await;
+
+    let body = stream(&app, &bearer, &org_id, &task.id, None).await;
+    assert_eq!(event_ids(&body), ids);
+    assert!(body.ends_with("\n\n"));
+    assert!(body.contains("event: complete\n"));
+}
+
+#[actix_web::test]
+async fn test_reconnect_sends_only_later_events() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_reconnect_user").await;
+
+    let task = A2aTaskRepository::create(env.pool(), &org_id, "getReputationSummary", &json!({}))
+        .await
+        .unwrap();
+    let ids = record_events(env.pool(), &task.id).await;
+
+    let body = stream(&app, &bearer, &org_id, &task.id, Some(ids[0])).await;
+    assert_eq!(event_ids(&body), ids[1..]);
+    assert!(!body.contains("\"progress\":0.0"));
+}
+
+#[actix_web::test]
+async fn test_reconnect_continues_live() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (bearer, org_id) = register_with_personal_org(&app, "a2a_live_user").await;
+
+    let task = A2aTaskRepository::create(env.pool(), &org_id, "getReputationSummary", &json!({}))
+        .await
+        .unwrap();
+    A2aTaskRepository::start_task(env.pool(), &task.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let started = A2aTaskEventRepository::append(
+        env.pool(),
+        &task.id,
+        &status(&task.id, TaskStatus::Working, 0.0),
+    )
+    .await
+    .unwrap();
+
+    // The task completes while the client is connected
+    let pool = env.pool().clone();
+    let task_id = task.id;
+    let completion = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let completed = A2aTaskEventRepository::append(
+            &pool,
+            &task_id,
+            &status(&task_id, TaskStatus::Completed, 1.0),
+        )
+        .await
+        .unwrap();
+        A2aTaskRepository::complete_task(&pool, &task_id, &json!({}), None)
+            .await
+            .unwrap();
+        completed.id
+    });
+
+    let body = stream(&app, &bearer, &org_id, &task.id, Some(started.id)).await;
+    let completed_id = completion.await.unwrap();
+    assert_eq!(event_ids(&body), vec![completed_id]);
+    assert!(body.contains("event: complete\n"));
+}
@@ -0,0 +1,60 @@
+//! Integration tests for the gateway's action job producer
+//!
+//! Tests cover:
+//! - Jobs being pushed onto the priority lane of their action type's queue
+//!
+//! # Running Tests
+//!
+//! The tests require Redis:
+//!
+//! ```bash
+//! export TEST_REDIS_URL="redis://localhost:6379"
+//! cargo test --test action_job_queue_test -- --ignored
+//! ```
+
+use api_gateway::services::ActionJobQueue;
+use redis::AsyncCommands;
+use shared::{ActionJob, ActionType, QueuePriority};
+
+async fn setup_redis() -> redis::aio::ConnectionManager {
+    let redis_url =
+        std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    shared::redis::create_client(&redis_url)
+        .await
+        .expect("Failed to connect to Redis")
+}
+
+fn job(priority: i32) -> ActionJob {
+    ActionJob::new(
+        "trigger-lanes",
+        "manual-lanes",
+        ActionType::Rest,
+        priority,
+        serde_json::json!({"url": "https://example.com/hook"}),
+        serde_json::json!({}),
+    )
+}
+
+/// Whether `lane` holds `job`, removing it so the workers never see it
+async fn take(conn: &mut redis::aio::ConnectionManager, lane: &str, job: &ActionJob) -> bool {
+    let payload = serde_json::to_string(job).unwrap();
+    let removed: i64 = conn.lrem(lane, 1, payload).await.unwrap();
+    removed == 1
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_enqueue_uses_job_priority_lane() {
+    let mut conn = setup_redis().await;
+    let queue = ActionJobQueue::new(conn.clone());
+    let queue_name = queue.queue_name(&ActionType::Rest);
+
+    let high = job(QueuePriority::HIGH_MIN);
+    let normal = job(1);
+    queue.enqueue(&high).await.unwrap();
+    queue.enqueue(&normal).await.unwrap();
+
+    assert_eq!(queue.lane(&high), format!("{}:priority:high", queue_name));
+    assert!(take(&mut conn, &format!("{}:priority:high", queue_name), &high).await);
+    assert!(take(&mut conn, &queue_name, &normal).await);
+}
@@ -385,6 +385,19 @@ mod unit_tests {
         assert_ne!(live_key.key, another_key.key);
     }
 
+    #[test]
+    fn test_seeded_api_key_generation_is_reproducible() {
+        use api_gateway::services::ApiKeyService;
+
+        // Fixtures can rely on exact key values for a given seed
+        let first = ApiKeyService::with_seed(2024).generate_key("test").unwrap();
+        let second = ApiKeyService::with_seed(2024).generate_key("test").unwrap();
+
+        assert_eq!(first.key, second.key);
+        assert_eq!(first.prefix, second.prefix);
+        assert!(ApiKeyService::is_valid_format(&first.key));
+    }
+
     #[test]
     fn test_api_key_verification() {
         use api_gateway::services::ApiKeyService;
@@ -0,0 +1,907 @@
+//! End-to-end tests against the full application
+//!
+//! Each test runs the production app (middleware, routes, Redis-backed
+//! services) on a freshly migrated database; see [`common::containers`].
+//!
+//! # Test Coverage
+//!
+//! - Register → verify email → login → create API key
+//! - Email verification: gating, single use, expiry and resend
+//! - Password reset: session revocation, expiry and enumeration resistance
+//! - Account lockout after repeated failed logins
+//! - Refresh token rotation, reuse detection and family revocation
+//! - Session listing and remote logout
+//! - Per-route body limits: auth routes reject bodies other routes accept
+//! - Wallet (SIWE) login on a non-mainnet chain, per-chain identities and
+//!   address normalization
+//! - SIWE messages for another chain, domain or in another format are rejected
+//!
+//! # Running Tests
+//!
+//! Requires Docker:
+//!
+//! ```bash
+//! cargo test -p api-gateway --features integration-tests --test auth_flow_integration_test
+//! ```
+
+#![cfg(feature = "integration-tests")]
+
+mod common;
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::test;
+use alloy::primitives::{eip191_hash_message, Address, PrimitiveSignature};
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::utils::public_key_to_address;
+use serde_json::{json, Value};
+
+use api_gateway::services::{EmailVerificationService, PasswordResetService};
+
+use crate::common::containers::TestEnv;
+use crate::common::create_test_app;
+
+#[actix_web::test]
+async fn test_register_login_and_create_api_key() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    // Register (also creates the user's personal organization)
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register")
+        .set_json(json!({
+            "username": "e2e_user",
+            "email": "e2e_user@example.com",
+            "password": "Correct-Horse-Battery-9"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let registered: Value = test::read_body_json(resp).await;
+    assert_eq!(registered["user"]["username"], "e2e_user");
+    assert_eq!(registered["user"]["email_verified"], false);
+
+    // Follow the emailed verification link
+    let token = verification_token(&env, &registered["user"]).await;
+    assert_eq!(verify_email(&app, &token).await.0, StatusCode::OK);
+
+    // Log in with the same credentials
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(json!({
+            "username_or_email": "e2e_user@example.com",
+            "password": "Correct-Horse-Battery-9"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let login: Value = test::read_body_json(resp).await;
+    let token = login["token"].as_str().expect("login returns a token");
+    let bearer = format!("Bearer {}", token);
+
+    // Find the personal organization
+    let req = test::TestRequest::get()
+        .uri("/api/v1/organizations")
+        .insert_header(("Authorization", bearer.as_str()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let orgs: Value = test::read_body_json(resp).await;
+    let org = orgs["data"]
+        .as_array()
+        .and_then(|orgs| orgs.iter().find(|org| org["is_personal"] == true))
+        .expect("registration creates a personal organization");
+    assert_eq!(org["my_role"], "owner");
+    let org_id = org["id"].as_str().unwrap();
+
+    // Create an API key in it
+    let req = test::TestRequest::post()
+        .uri("/api/v1/api-keys")
+        .insert_header(("Authorization", bearer.as_str()))
+        .insert_header(("X-Organization-ID", org_id))
+        .set_json(json!({
+            "name": "E2E key",
+            "environment": "test"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created: Value = test::read_body_json(resp).await;
+    let key = created["data"]["key"]
+        .as_str()
+        .expect("full key is returned");
+    assert!(
+        key.starts_with("sk_test_"),
+        "unexpected key format: {}",
+        key
+    );
+    assert_eq!(created["data"]["name"], "E2E key");
+
+    // The key is listed for the organization (without the secret)
+    let req = test::TestRequest::get()
+        .uri("/api/v1/api-keys")
+        .insert_header(("Authorization", bearer.as_str()))
+        .insert_header(("X-Organization-ID", org_id))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let listed: Value = test::read_body_json(resp).await;
+    assert_eq!(listed["items"][0]["id"], created["data"]["id"]);
+    assert!(listed["items"][0].get("key").is_none());
+}
+
+/// Issue a verification token for a registered user, as the emailed link would carry
+async fn verification_token(env: &TestEnv, user: &Value) -> String {
+    EmailVerificationService::new()
+        .create_token(
+            env.pool(),
+            user["id"].as_str().unwrap(),
+            user["email"].as_str().unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// POST /auth/verify-email; returns the status and body
+async fn verify_email<S, B>(app: &S, token: &str) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/verify-email")
+        .set_json(json!({ "token": token }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+/// POST /api/v1/api-keys in the user's personal organization; returns the status and body
+async fn create_personal_api_key<S, B>(app: &S, access_token: &str) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let bearer = format!("Bearer {}", access_token);
+    let req = test::TestRequest::get()
+        .uri("/api/v1/organizations")
+        .insert_header(("Authorization", bearer.as_str()))
+        .to_request();
+    let orgs: Value = test::call_and_read_body_json(app, req).await;
+    let org_id = orgs["data"]
+        .as_array()
+        .and_then(|orgs| orgs.iter().find(|org| org["is_personal"] == true))
+        .and_then(|org| org["id"].as_str())
+        .unwrap()
+        .to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/api-keys")
+        .insert_header(("Authorization", bearer.as_str()))
+        .insert_header(("X-Organization-ID", org_id))
+        .set_json(json!({ "name": "Gated key", "environment": "test" }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+#[actix_web::test]
+async fn test_email_verification_gates_api_keys() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login = register_and_login(&app, "unverified_user").await;
+    let access_token = login["token"].as_str().unwrap();
+
+    let (status, body) = create_personal_api_key(&app, access_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "email_not_verified");
+
+    let token = verification_token(&env, &login["user"]).await;
+    let (status, body) = verify_email(&app, &token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    // Tokens are single-use
+    let (status, body) = verify_email(&app, &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token)))
+        .to_request();
+    let me: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(me["email_verified"], true);
+
+    let (status, _) = create_personal_api_key(&app, access_token).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn test_expired_verification_token_is_rejected() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login = register_and_login(&app, "expired_token_user").await;
+    let token = verification_token(&env, &login["user"]).await;
+
+    sqlx::query("UPDATE email_verification_tokens SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(env.pool())
+        .await
+        .unwrap();
+
+    let (status, body) = verify_email(&app, &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+}
+
+#[actix_web::test]
+async fn test_resend_verification() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login = register_and_login(&app, "resend_user").await;
+    let bearer = format!("Bearer {}", login["token"].as_str().unwrap());
+    let resend = || {
+        test::TestRequest::post()
+            .uri("/api/v1/auth/resend-verification")
+            .insert_header(("Authorization", bearer.as_str()))
+            .to_request()
+    };
+
+    // Registration just sent one
+    let resp = test::call_service(&app, resend()).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key("Retry-After"));
+
+    // A newer token invalidates the older ones
+    let older = verification_token(&env, &login["user"]).await;
+    let newer = verification_token(&env, &login["user"]).await;
+    assert_eq!(verify_email(&app, &older).await.0, StatusCode::BAD_REQUEST);
+    assert_eq!(verify_email(&app, &newer).await.0, StatusCode::OK);
+
+    let resp = test::call_service(&app, resend()).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+/// Issue a password reset token for a registered user, as the emailed link would carry
+async fn password_reset_token(env: &TestEnv, user: &Value) -> String {
+    PasswordResetService::new()
+        .create_token(
+            env.pool(),
+            user["id"].as_str().unwrap(),
+            user["email"].as_str().unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// POST /auth/reset-password; returns the status and body
+async fn reset_password<S, B>(app: &S, token: &str, new_password: &str) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/reset-password")
+        .set_json(json!({ "token": token, "new_password": new_password }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+#[actix_web::test]
+async fn test_password_reset() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_response = register_and_login(&app, "reset_user").await;
+    let token = password_reset_token(&env, &login_response["user"]).await;
+
+    let (status, body) = reset_password(&app, &token, "New-Horse-Battery-7").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    // Tokens are single-use
+    let (status, body) = reset_password(&app, &token, "Other-Horse-Battery-5").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+
+    // Existing sessions are revoked
+    let (status, _) = refresh(&app, login_response["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Only the new password works
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(json!({
+            "username_or_email": "reset_user@example.com",
+            "password": "Correct-Horse-Battery-9"
+        }))
+        .to_request();
+    assert_eq!(
+        test::call_service(&app, req).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+    login(&app, "reset_user@example.com", "New-Horse-Battery-7").await;
+}
+
+#[actix_web::test]
+async fn test_expired_password_reset_token_is_rejected() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_response = register_and_login(&app, "expired_reset_user").await;
+    let token = password_reset_token(&env, &login_response["user"]).await;
+
+    sqlx::query("UPDATE password_reset_tokens SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(env.pool())
+        .await
+        .unwrap();
+
+    let (status, body) = reset_password(&app, &token, "New-Horse-Battery-7").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_token");
+
+    // The old password still works
+    login(
+        &app,
+        "expired_reset_user@example.com",
+        "Correct-Horse-Battery-9",
+    )
+    .await;
+}
+
+#[actix_web::test]
+async fn test_forgot_password_does_not_reveal_accounts() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    register_and_login(&app, "forgetful_user").await;
+
+    let forgot = |email: &str| {
+        test::TestRequest::post()
+            .uri("/api/v1/auth/forgot-password")
+            .set_json(json!({ "email": email }))
+            .to_request()
+    };
+    let known = test::call_service(&app, forgot("forgetful_user@example.com")).await;
+    assert_eq!(known.status(), StatusCode::OK);
+    let known: Value = test::read_body_json(known).await;
+    let unknown = test::call_service(&app, forgot("nobody@example.com")).await;
+    assert_eq!(unknown.status(), StatusCode::OK);
+    let unknown: Value = test::read_body_json(unknown).await;
+    assert_eq!(known, unknown);
+
+    // The email is sent after the response; only the real account gets a token
+    let mut issued = 0;
+    for _ in 0..50 {
+        issued = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM password_reset_tokens")
+            .fetch_one(env.pool())
+            .await
+            .unwrap();
+        if issued > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(issued, 1);
+}
+
+#[actix_web::test]
+async fn test_failed_logins_lock_account() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_response = register_and_login(&app, "lockout_user").await;
+    let attempt = |password: &str| {
+        test::TestRequest::post()
+            .uri("/api/v1/auth/login")
+            .set_json(json!({
+                "username_or_email": "lockout_user@example.com",
+                "password": password
+            }))
+            .to_request()
+    };
+
+    for _ in 0..env.state.config.auth.max_failed_attempts {
+        let resp = test::call_service(&app, attempt("Wrong-Horse-Battery-1")).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Locked: even the right password is refused
+    let resp = test::call_service(&app, attempt("Correct-Horse-Battery-9")).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "account_locked");
+
+    let (lockout_count, remaining): (i32, f64) = sqlx::query_as(
+        "SELECT lockout_count, EXTRACT(EPOCH FROM locked_until - NOW())::float8 FROM users WHERE id = $1",
+    )
+    .bind(login_response["user"]["id"].as_str().unwrap())
+    .fetch_one(env.pool())
+    .await
+    .unwrap();
+    assert_eq!(lockout_count, 1);
+    assert!(remaining <= env.state.config.auth.lockout_secs as f64);
+    assert!(remaining > 0.0);
+}
+
+/// Register a user and log in; returns the login response
+async fn register_and_login<S, B>(app: &S, username: &str) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let password = "Correct-Horse-Battery-9";
+    let email = format!("{}@example.com", username);
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register")
+        .set_json(json!({ "username": username, "email": email, "password": password }))
+        .to_request();
+    assert_eq!(
+        test::call_service(app, req).await.status(),
+        StatusCode::CREATED
+    );
+
+    login(app, &email, password).await
+}
+
+async fn login<S, B>(app: &S, email: &str, password: &str) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(json!({ "username_or_email": email, "password": password }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    test::read_body_json(resp).await
+}
+
+/// POST /auth/refresh; returns the status and body
+async fn refresh<S, B>(app: &S, refresh_token: &str) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/refresh")
+        .set_json(json!({ "refresh_token": refresh_token }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+/// GET /auth/sessions as the holder of `access_token`; returns the sessions
+async fn list_sessions<S, B>(app: &S, access_token: &str) -> Vec<Value>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/sessions")
+        .insert_header(("Authorization", format!("Bearer {}", access_token)))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(resp).await;
+    body["sessions"].as_array().unwrap().clone()
+}
+
+#[actix_web::test]
+async fn test_refresh_token_rotation() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login = register_and_login(&app, "rotation_user").await;
+    let first = login["refresh_token"].as_str().unwrap();
+
+    let (status, body) = refresh(&app, first).await;
+    assert_eq!(status, StatusCode::OK);
+    let second = body["refresh_token"].as_str().unwrap();
+    assert_ne!(first, second);
+
+    // The replacement keeps working
+    let (status, _) = refresh(&app, second).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_refresh_token_reuse_revokes_family() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_a = register_and_login(&app, "reuse_user").await;
+    // A second session of the same user is a separate family
+    let login_b = login(&app, "reuse_user@example.com", "Correct-Horse-Battery-9").await;
+
+    let stolen = login_a["refresh_token"].as_str().unwrap();
+    let (status, body) = refresh(&app, stolen).await;
+    assert_eq!(status, StatusCode::OK);
+    let rotated = body["refresh_token"].as_str().unwrap().to_string();
+
+    // Replaying the rotated token is rejected...
+    let (status, body) = refresh(&app, stolen).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "invalid_refresh_token");
+
+    // ...and revokes the token it was rotated into
+    let (status, body) = refresh(&app, &rotated).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "invalid_refresh_token");
+
+    // Other sessions are unaffected
+    let (status, _) = refresh(&app, login_b["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The reuse is on the audit trail
+    let (details,): (Value,) = sqlx::query_as(
+        "SELECT details FROM auth_failures WHERE failure_type = 'refresh_token_reuse'",
+    )
+    .fetch_one(env.pool())
+    .await
+    .unwrap();
+    assert_eq!(details["revoked_tokens"], 1);
+    assert_eq!(details["user_id"], login_a["user"]["id"]);
+}
+
+#[actix_web::test]
+async fn test_list_sessions() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_a = register_and_login(&app, "sessions_user").await;
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .insert_header(("User-Agent", "session-test-phone"))
+        .set_json(json!({
+            "username_or_email": "sessions_user@example.com",
+            "password": "Correct-Horse-Battery-9"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let login_b: Value = test::read_body_json(resp).await;
+
+    let token_a = login_a["token"].as_str().unwrap();
+    let sessions = list_sessions(&app, token_a).await;
+    // Registering does not count: its session is a third one
+    assert_eq!(sessions.len(), 3);
+    assert_eq!(sessions.iter().filter(|s| s["current"] == true).count(), 1);
+
+    let phone = sessions
+        .iter()
+        .find(|s| s["user_agent"] == "session-test-phone")
+        .expect("session of the second login");
+    assert_eq!(phone["current"], false);
+
+    // Refreshing keeps the session, and the new access token is tied to it
+    let (status, body) = refresh(&app, login_b["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let sessions = list_sessions(&app, body["token"].as_str().unwrap()).await;
+    assert_eq!(sessions.len(), 3);
+    let current = sessions.iter().find(|s| s["current"] == true).unwrap();
+    assert_eq!(current["id"], phone["id"]);
+    assert_eq!(current["created_at"], phone["created_at"]);
+
+    // Unauthenticated requests are rejected
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/sessions")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+/// The session the access token in `login_response` was issued to
+async fn current_session<S, B>(app: &S, login_response: &Value) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    list_sessions(app, login_response["token"].as_str().unwrap())
+        .await
+        .into_iter()
+        .find(|s| s["current"] == true)
+        .expect("current session")
+}
+
+/// DELETE /auth/sessions/{id} as the holder of `access_token`
+async fn revoke_session<S, B>(app: &S, access_token: &str, session_id: &Value) -> StatusCode
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::delete()
+        .uri(&format!(
+            "/api/v1/auth/sessions/{}",
+            session_id.as_str().unwrap()
+        ))
+        .insert_header(("Authorization", format!("Bearer {}", access_token)))
+        .to_request();
+    test::call_service(app, req).await.status()
+}
+
+#[actix_web::test]
+async fn test_revoke_session() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_a = register_and_login(&app, "revoke_user").await;
+    let login_b = login(&app, "revoke_user@example.com", "Correct-Horse-Battery-9").await;
+    let token_a = login_a["token"].as_str().unwrap();
+    let session_a = current_session(&app, &login_a).await;
+    let session_b = current_session(&app, &login_b).await;
+
+    assert_eq!(
+        revoke_session(&app, token_a, &session_b["id"]).await,
+        StatusCode::NO_CONTENT
+    );
+
+    // Its refresh token stops working at once; the others keep working
+    let (status, body) = refresh(&app, login_b["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "invalid_refresh_token");
+    let (status, _) = refresh(&app, login_a["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let sessions = list_sessions(&app, token_a).await;
+    assert!(sessions.iter().all(|s| s["id"] != session_b["id"]));
+
+    // Revoking it again is not found
+    assert_eq!(
+        revoke_session(&app, token_a, &session_b["id"]).await,
+        StatusCode::NOT_FOUND
+    );
+
+    // So is another user's session
+    let intruder = register_and_login(&app, "revoke_intruder").await;
+    assert_eq!(
+        revoke_session(&app, intruder["token"].as_str().unwrap(), &session_a["id"]).await,
+        StatusCode::NOT_FOUND
+    );
+    assert!(list_sessions(&app, token_a)
+        .await
+        .iter()
+        .any(|s| s["id"] == session_a["id"]));
+}
+
+#[actix_web::test]
+async fn test_revoke_other_sessions() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login_a = register_and_login(&app, "logout_others_user").await;
+    let login_b = login(
+        &app,
+        "logout_others_user@example.com",
+        "Correct-Horse-Battery-9",
+    )
+    .await;
+    let token_a = login_a["token"].as_str().unwrap();
+
+    let req = test::TestRequest::delete()
+        .uri("/api/v1/auth/sessions")
+        .insert_header(("Authorization", format!("Bearer {}", token_a)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(resp).await;
+    // The registration session and session B
+    assert_eq!(body["revoked"], 2);
+
+    let sessions = list_sessions(&app, token_a).await;
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["current"], true);
+
+    let (status, _) = refresh(&app, login_b["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = refresh(&app, login_a["refresh_token"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_auth_routes_have_tighter_body_limit() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+
+    let login = register_and_login(&app, "body_limit_user").await;
+    let bearer = format!("Bearer {}", login["token"].as_str().unwrap());
+    // Over the auth limit (16KB), well under the default (1MB)
+    let padding = "x".repeat(32 * 1024);
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(json!({
+            "username_or_email": "body_limit_user@example.com",
+            "password": "Correct-Horse-Battery-9",
+            "padding": padding
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "payload_too_large");
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/conditions/validate")
+        .insert_header(("Authorization", bearer.as_str()))
+        .set_json(json!({
+            "condition_type": "rate_limit",
+            "field": "event_count",
+            "operator": ">",
+            "value": "10",
+            "config": {"time_window": "1h", "padding": padding}
+        }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+}
+
+/// Wallet with a fixed test key
+fn test_wallet() -> (SigningKey, Address) {
+    let key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+    let address = public_key_to_address(key.verifying_key());
+    (key, address)
+}
+
+/// POST /auth/nonce; returns the status and body
+async fn wallet_nonce<S, B>(app: &S, address: &str, chain_id: i32) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/nonce")
+        .set_json(json!({ "address": address, "chain_id": chain_id }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+/// Sign `message` (EIP-191) and POST /auth/wallet; returns the status and body
+async fn wallet_login<S, B>(
+    app: &S,
+    key: &SigningKey,
+    address: &str,
+    message: &str,
+    chain_id: i32,
+) -> (StatusCode, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(eip191_hash_message(message.as_bytes()).as_slice())
+        .unwrap();
+    let signature =
+        PrimitiveSignature::from_signature_and_parity(signature, recovery_id.is_y_odd());
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/wallet")
+        .set_json(json!({
+            "address": address,
+            "signature": format!("0x{}", hex::encode(signature.as_bytes())),
+            "message": message,
+            "chain_id": chain_id
+        }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+/// GET /auth/me as the holder of `access_token`
+async fn me<S, B>(app: &S, access_token: &str) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/me")
+        .insert_header(("Authorization", format!("Bearer {}", access_token)))
+        .to_request();
+    test::call_and_read_body_json(app, req).await
+}
+
+#[actix_web::test]
+async fn test_wallet_login_on_base() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (key, address) = test_wallet();
+    let checksummed = address.to_checksum(None);
+
+    // A lowercase address gets a message for the checksummed one
+    let (status, nonce) = wallet_nonce(&app, &checksummed.to_lowercase(), 8453).await;
+    assert_eq!(status, StatusCode::OK);
+    let message = nonce["message"].as_str().unwrap();
+    assert!(message.contains(&format!("\n{}\n", checksummed)));
+    assert!(message.contains("\nChain ID: 8453\n"));
+
+    let (status, login) = wallet_login(&app, &key, &checksummed, message, 8453).await;
+    assert_eq!(status, StatusCode::OK);
+    let user_id = login["user"]["id"].clone();
+
+    let profile = me(&app, login["token"].as_str().unwrap()).await;
+    assert_eq!(
+        profile["wallets"],
+        json!([{ "address": checksummed, "chain_id": 8453 }])
+    );
+
+    // Same wallet on mainnet, sent lowercase: same user, one identity per chain
+    let (_, nonce) = wallet_nonce(&app, &checksummed, 1).await;
+    let message = nonce["message"].as_str().unwrap();
+    let (status, login) = wallet_login(&app, &key, &checksummed.to_lowercase(), message, 1).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(login["user"]["id"], user_id);
+
+    let profile = me(&app, login["token"].as_str().unwrap()).await;
+    assert_eq!(
+        profile["wallets"],
+        json!([
+            { "address": checksummed, "chain_id": 8453 },
+            { "address": checksummed, "chain_id": 1 }
+        ])
+    );
+
+    // Logging in again on Base reuses its identity
+    let (_, nonce) = wallet_nonce(&app, &checksummed, 8453).await;
+    let message = nonce["message"].as_str().unwrap();
+    let (status, login) = wallet_login(&app, &key, &checksummed, message, 8453).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(login["user"]["id"], user_id);
+    let profile = me(&app, login["token"].as_str().unwrap()).await;
+    assert_eq!(profile["wallets"].as_array().unwrap().len(), 2);
+}
+
+#[actix_web::test]
+async fn test_wallet_login_rejects_mismatched_messages() {
+    let env = TestEnv::start().await;
+    let app = create_test_app(&env.state).await;
+    let (key, address) = test_wallet();
+    let checksummed = address.to_checksum(None);
+
+    // BNB Smart Chain is not a login chain
+    let (status, body) = wallet_nonce(&app, &checksummed, 56).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unsupported_chain");
+
+    // A message signed for Base cannot log in on Optimism
+    let (_, nonce) = wallet_nonce(&app, &checksummed, 8453).await;
+    let message = nonce["message"].as_str().unwrap();
+    let (status, body) = wallet_login(&app, &key, &checksummed, message, 10).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "chain_mismatch");
+
+    // The same message issued for another app (validly signed)
+    let foreign = message.replace("agentauri.ai wants", "evil.example wants");
+    let (status, body) = wallet_login(&app, &key, &checksummed, &foreign, 8453).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "domain_mismatch");
+
+    // Not in EIP-4361 format
+    let (status, body) = wallet_login(
+        &app,
+        &key,
+        &checksummed,
+        &format!("Sign in\nNonce: {}", nonce["nonce"].as_str().unwrap()),
+        8453,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_message");
+
+    // The nonce was not consumed by the rejected attempts
+    let (status, _) = wallet_login(&app, &key, &checksummed, message, 8453).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,153 @@
+//! Integration tests for the entity cache flush endpoint
+//!
+//! Tests cover:
+//! - Flushing one entity in a namespace, leaving other namespaces intact
+//! - Flushing a single key
+//! - Rejecting requests without the admin token or confirmation
+//!
+//! # Running Tests
+//!
+//! The flush tests require Redis:
+//!
+//! ```bash
+//! export TEST_REDIS_URL="redis://localhost:6379"
+//! cargo test --test cache_admin_test -- --ignored
+//! ```
+
+use actix_web::{http::StatusCode, test, web, App};
+use api_gateway::handlers::flush_cache;
+use redis::AsyncCommands;
+use serde_json::{json, Value};
+use shared::redis::cache::{
+    org_key_by_id, org_rate_limits_key, trigger_key_by_id, user_key_by_id, EntityCache,
+};
+use uuid::Uuid;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Set the admin token before the handler first reads it
+fn init_admin_token() {
+    std::env::set_var("ADMIN_API_TOKEN", ADMIN_TOKEN);
+}
+
+async fn setup_redis() -> redis::aio::ConnectionManager {
+    let redis_url =
+        std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    shared::redis::create_client(&redis_url)
+        .await
+        .expect("Failed to connect to Redis")
+}
+
+fn flush_request(body: Value) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/admin/cache/flush")
+        .insert_header(("X-Admin-Token", ADMIN_TOKEN))
+        .set_json(body)
+}
+
+async fn exists(conn: &mut redis::aio::ConnectionManager, key: &str) -> bool {
+    conn.exists(key).await.unwrap()
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_flush_organization_keeps_other_namespaces() {
+    init_admin_token();
+    let mut conn = setup_redis().await;
+    let org_id = format!("org_{}", Uuid::new_v4());
+    let other_org_id = format!("org_{}", Uuid::new_v4());
+    let user_id = format!("user_{}", Uuid::new_v4());
+    let trigger_id = format!("trigger_{}", Uuid::new_v4());
+
+    let flushed = [org_key_by_id(&org_id), org_rate_limits_key(&org_id)];
+    let kept = [
+        org_key_by_id(&other_org_id),
+        user_key_by_id(&user_id),
+        trigger_key_by_id(&trigger_id),
+    ];
+    for key in flushed.iter().chain(kept.iter()) {
+        conn.set_ex::<_, _, ()>(key, "{}", 60).await.unwrap();
+    }
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(EntityCache::new(conn.clone(), None)))
+            .route("/admin/cache/flush", web::post().to(flush_cache)),
+    )
+    .await;
+
+    let req = flush_request(json!({"namespace": "organizations", "id": org_id})).to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["data"]["keys_deleted"], 2);
+
+    for key in &flushed {
+        assert!(!exists(&mut conn, key).await, "{} not flushed", key);
+    }
+    for key in &kept {
+        assert!(exists(&mut conn, key).await, "{} flushed", key);
+        conn.del::<_, ()>(key).await.unwrap();
+    }
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_flush_single_key() {
+    init_admin_token();
+    let mut conn = setup_redis().await;
+    let user_id = format!("user_{}", Uuid::new_v4());
+    let target = user_key_by_id(&user_id);
+    let sibling = shared::redis::cache::user_key_by_email(&format!("{}@example.com", user_id));
+    conn.set_ex::<_, _, ()>(&target, "{}", 60).await.unwrap();
+    conn.set_ex::<_, _, ()>(&sibling, "{}", 60).await.unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(EntityCache::new(conn.clone(), None)))
+            .route("/admin/cache/flush", web::post().to(flush_cache)),
+    )
+    .await;
+
+    let req = flush_request(json!({"key": target})).to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["data"]["keys_deleted"], 1);
+
+    assert!(!exists(&mut conn, &target).await);
+    assert!(exists(&mut conn, &sibling).await);
+    conn.del::<_, ()>(&sibling).await.unwrap();
+
+    // Flushing again is not an error
+    let req = flush_request(json!({"key": target})).to_request();
+    let body: Value = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(body["data"]["keys_deleted"], 0);
+}
+
+#[actix_web::test]
+async fn test_flush_requires_admin_token() {
+    init_admin_token();
+    let app =
+        test::init_service(App::new().route("/admin/cache/flush", web::post().to(flush_cache)))
+            .await;
+
+    let req = test::TestRequest::post()
+        .uri("/admin/cache/flush")
+        .set_json(json!({"namespace": "users", "confirm": "users"}))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_whole_namespace_flush_requires_confirmation() {
+    init_admin_token();
+    let app =
+        test::init_service(App::new().route("/admin/cache/flush", web::post().to(flush_cache)))
+            .await;
+
+    let req = flush_request(json!({"namespace": "triggers"})).to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,223 @@
+//! Integration tests for on-chain balance and transaction lookups
+//!
+//! Tests cover:
+//! - Authentication is required
+//! - Unconfigured chains and malformed input get 400
+//! - RPC errors get 502
+//! - Balances and transaction statuses from a mock RPC endpoint
+//! - Cached balances are served without an RPC call
+//!
+//! # Running Tests
+//!
+//! The cache test requires Redis:
+//!
+//! ```bash
+//! export TEST_REDIS_URL="redis://localhost:6379"
+//! cargo test --test chain_query_test -- --ignored
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{http::StatusCode, test, web, App, HttpMessage};
+use api_gateway::handlers::{get_balance, get_transaction_status};
+use api_gateway::models::Claims;
+use api_gateway::services::{BalanceCache, ChainConfig, WalletService};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const CHAIN_ID: i32 = 84532;
+const ADDRESS: &str = "0x742d35cc6634c0532925a3b844bc9e7595f0beb4";
+const TX_HASH: &str = "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b";
+
+/// Mock RPC endpoint answering every request with `body`; returns its URL
+/// and the number of requests it received
+async fn spawn_rpc(body: Value) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let server_hits = hits.clone();
+    let body = body.to_string();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            server_hits.fetch_add(1, Ordering::SeqCst);
+            // JSON-RPC requests are small; one read holds headers and body
+            let mut buf = [0u8; 8192];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+
+    (url, hits)
+}
+
+fn wallet_service(rpc_url: &str) -> WalletService {
+    WalletService::new(vec![ChainConfig {
+        chain_id: CHAIN_ID,
+        rpc_urls: vec![rpc_url.to_string()],
+        identity_registry_address: "0x0000000000000000000000000000000000000001".to_string(),
+    }])
+}
+
+fn authenticated(uri: &str) -> actix_http::Request {
+    let req = test::TestRequest::get().uri(uri).to_request();
+    req.extensions_mut()
+        .insert(Claims::new("user_1".to_string(), "alice".to_string(), 1));
+    req
+}
+
+fn chain_app(
+    wallet_service: WalletService,
+) -> App<
+    impl actix_web::dev::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .app_data(web::Data::new(wallet_service))
+        .route(
+            "/chains/{chain_id}/balances/{address}",
+            web::get().to(get_balance),
+        )
+        .route(
+            "/chains/{chain_id}/transactions/{tx_hash}",
+            web::get().to(get_transaction_status),
+        )
+}
+
+#[actix_web::test]
+async fn test_requires_authentication() {
+    let app = test::init_service(chain_app(wallet_service("http://127.0.0.1:1"))).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/chains/{}/balances/{}", CHAIN_ID, ADDRESS))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_invalid_requests_are_bad_requests() {
+    let app = test::init_service(chain_app(wallet_service("http://127.0.0.1:1"))).await;
+
+    let res = test::call_service(
+        &app,
+        authenticated(&format!("/chains/1/balances/{}", ADDRESS)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["error"], "unsupported_chain");
+
+    let res = test::call_service(
+        &app,
+        authenticated(&format!("/chains/{}/transactions/0x1234", CHAIN_ID)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["error"], "validation_error");
+}
+
+#[actix_web::test]
+async fn test_rpc_error_is_bad_gateway() {
+    let (url, _) = spawn_rpc(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "error": {"code": -32000, "message": "header not found"}
+    }))
+    .await;
+    let app = test::init_service(chain_app(wallet_service(&url))).await;
+
+    let res = test::call_service(
+        &app,
+        authenticated(&format!("/chains/{}/balances/{}", CHAIN_ID, ADDRESS)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["error"], "rpc_error");
+}
+
+#[actix_web::test]
+async fn test_get_balance() {
+    let (url, _) =
+        spawn_rpc(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": "0x1bc16d674ec80000"}))
+            .await;
+    let app = test::init_service(chain_app(wallet_service(&url))).await;
+
+    let res = test::call_service(
+        &app,
+        authenticated(&format!("/chains/{}/balances/{}", CHAIN_ID, ADDRESS)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["data"]["chain_id"], CHAIN_ID);
+    assert_eq!(body["data"]["balance_wei"], "2000000000000000000");
+}
+
+#[actix_web::test]
+async fn test_get_transaction_status() {
+    let (url, _) = spawn_rpc(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {"status": "0x0", "blockNumber": "0x11"}
+    }))
+    .await;
+    let app = test::init_service(chain_app(wallet_service(&url))).await;
+
+    let res = test::call_service(
+        &app,
+        authenticated(&format!("/chains/{}/transactions/{}", CHAIN_ID, TX_HASH)),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["data"]["status"], "failed");
+    assert_eq!(body["data"]["block_number"], 17);
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_balance_served_from_cache() {
+    let redis_url =
+        std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let conn = shared::redis::create_client(&redis_url)
+        .await
+        .expect("Failed to connect to Redis");
+    let (url, hits) =
+        spawn_rpc(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": "0x2a"})).await;
+    // A random address so earlier runs cannot have cached it
+    let address = format!("0x{}", hex::encode(uuid::Uuid::new_v4().as_bytes())) + "00000000";
+
+    let app = test::init_service(chain_app(wallet_service(&url)).app_data(web::Data::new(
+        BalanceCache::new(conn, Duration::from_secs(60)),
+    )))
+    .await;
+
+    for _ in 0..2 {
+        let res = test::call_service(
+            &app,
+            authenticated(&format!("/chains/{}/balances/{}", CHAIN_ID, address)),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: Value = test::read_body_json(res).await;
+        assert_eq!(body["data"]["balance_wei"], "42");
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,121 @@
+//! Ephemeral Postgres and Redis for integration tests
+//!
+//! [`TestEnv`] starts a TimescaleDB and a Redis container, applies the
+//! migrations from `database/migrations` and builds the same [`AppState`] the
+//! server uses. Each test gets its own containers, removed when the
+//! environment is dropped.
+//!
+//! Needs a Docker daemon and the `integration-tests` feature:
+//!
+//! ```bash
+//! cargo test -p api-gateway --features integration-tests
+//! ```
+
+use api_gateway::app::AppState;
+use shared::config::MigrationMode;
+use shared::{db, Config, DbPool, DbPools};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::redis::{Redis, REDIS_PORT};
+use testcontainers_modules::testcontainers::runners::AsyncRunner;
+use testcontainers_modules::testcontainers::{ContainerAsync, ImageExt};
+
+use super::TEST_JWT_SECRET;
+
+/// Same images as docker-compose.yml (migrations need TimescaleDB)
+const POSTGRES_IMAGE: &str = "timescale/timescaledb";
+const POSTGRES_TAG: &str = "2.23.1-pg15";
+const REDIS_TAG: &str = "7.4-alpine";
+const POSTGRES_PORT: u16 = 5432;
+
+/// Migrated database, Redis and application services for one test
+pub struct TestEnv {
+    pub state: AppState,
+    _postgres: ContainerAsync<Postgres>,
+    _redis: ContainerAsync<Redis>,
+}
+
+impl TestEnv {
+    /// Start the containers, migrate the database and build the app state
+    ///
+    /// # Panics
+    ///
+    /// If Docker is unavailable or any step fails
+    #[allow(dead_code)] // Used by integration-tests suites
+    pub async fn start() -> Self {
+        let postgres = Postgres::default()
+            .with_name(POSTGRES_IMAGE)
+            .with_tag(POSTGRES_TAG)
+            .start()
+            .await
+            .expect("Failed to start Postgres container (is Docker running?)");
+        let redis = Redis::default()
+            .with_tag(REDIS_TAG)
+            .start()
+            .await
+            .expect("Failed to start Redis container (is Docker running?)");
+
+        let config = test_config(&postgres, &redis).await;
+
+        let db_pools = DbPools::from_config(&config.database)
+            .await
+            .expect("Failed to connect to test database");
+        db::run_migrations(db_pools.primary(), &config.database)
+            .await
+            .expect("Failed to migrate test database");
+
+        let state = AppState::new(db_pools, config, None)
+            .await
+            .expect("Failed to build application state");
+
+        Self {
+            state,
+            _postgres: postgres,
+            _redis: redis,
+        }
+    }
+
+    /// Primary pool of the migrated test database
+    #[allow(dead_code)] // Used by tests that seed or inspect rows directly
+    pub fn pool(&self) -> &DbPool {
+        self.state.db_pools.primary()
+    }
+}
+
+/// Configuration pointing at the containers, with defaults for everything else
+async fn test_config(postgres: &ContainerAsync<Postgres>, redis: &ContainerAsync<Redis>) -> Config {
+    // DualAuth reads the secret from the environment rather than Config;
+    // every test sets the same value, so concurrent tests do not conflict
+    std::env::set_var("JWT_SECRET", TEST_JWT_SECRET);
+
+    let mut config = Config::from_env().expect("Failed to load configuration");
+
+    config.database.host = postgres
+        .get_host()
+        .await
+        .expect("Postgres container host")
+        .to_string();
+    config.database.port = postgres
+        .get_host_port_ipv4(POSTGRES_PORT)
+        .await
+        .expect("Postgres container port");
+    config.database.name = "postgres".to_string();
+    config.database.user = "postgres".to_string();
+    config.database.password = "postgres".to_string();
+    config.database.ssl_mode = "disable".to_string();
+    config.database.read_replica = None;
+    config.database.migration_mode = MigrationMode::Run;
+    config.database.migrations_dir =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../../../database/migrations").to_string();
+
+    let redis_host = redis.get_host().await.expect("Redis container host");
+    let redis_port = redis
+        .get_host_port_ipv4(REDIS_PORT)
+        .await
+        .expect("Redis container port");
+    config.redis.url = Some(format!("redis://{}:{}", redis_host, redis_port));
+    config.redis.database = None;
+
+    config.server.jwt_secret = TEST_JWT_SECRET.to_string();
+
+    config
+}
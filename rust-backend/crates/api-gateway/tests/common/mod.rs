@@ -3,7 +3,18 @@
 //! This module provides helper functions and utilities for integration testing
 //! the api-gateway application, including test app setup, JWT token generation,
 //! and database mocking.
+//!
+//! With the `integration-tests` feature, [`containers::TestEnv`] provides a
+//! migrated Postgres and a Redis in throwaway containers.
+
+#[cfg(feature = "integration-tests")]
+pub mod containers;
 
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::test;
+use api_gateway::app::{build_app, AppState};
 use chrono::Utc;
 use jsonwebtoken::{encode, EncodingKey, Header};
 use shared::DbPool;
@@ -25,12 +36,13 @@ pub struct TestClaims {
 
 /// Create a test Actix-web application with all routes configured
 ///
-/// This function creates a fully configured test app instance that can be
-/// used with Actix's testing utilities.
+/// Builds the production app (middleware, shared state, routes and API docs)
+/// with [`build_app`], so requests go through the same stack as in the
+/// server.
 ///
 /// # Arguments
 ///
-/// * `pool` - Database connection pool (can be real or mocked)
+/// * `state` - Application services, e.g. from [`containers::TestEnv`]
 ///
 /// # Returns
 ///
@@ -39,8 +51,8 @@ pub struct TestClaims {
 /// # Example
 ///
 /// ```ignore
-/// let pool = create_test_pool().await;
-/// let app = create_test_app(pool).await;
+/// let env = TestEnv::start().await;
+/// let app = create_test_app(&env.state).await;
 ///
 /// let req = test::TestRequest::get()
 ///     .uri("/api/v1/health")
@@ -50,18 +62,10 @@ pub struct TestClaims {
 /// assert_eq!(resp.status(), 200);
 /// ```
 #[allow(dead_code)]
-pub async fn create_test_app(pool: DbPool) {
-    // Note: This function is currently a placeholder because the complex return type
-    // causes compilation issues. In actual integration tests with a real database,
-    // you would use test::init_service directly in each test.
-    //
-    // Example usage in tests:
-    //   let app = test::init_service(
-    //       App::new()
-    //           .app_data(web::Data::new(pool.clone()))
-    //           .configure(routes::configure)
-    //   ).await;
-    let _ = pool;
+pub async fn create_test_app(
+    state: &AppState,
+) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = actix_web::Error> {
+    test::init_service(build_app(state)).await
 }
 
 /// Generate a JWT token for testing
@@ -162,6 +166,9 @@ pub fn create_expired_jwt(user_id: &str, username: &str) -> String {
 /// 1. Set up a test PostgreSQL database
 /// 2. Run migrations on it
 /// 3. Use the connection URL in TEST_DATABASE_URL env var
+///
+/// Or enable the `integration-tests` feature and use
+/// [`containers::TestEnv`], which does all three in Docker.
 #[allow(dead_code)]
 pub async fn create_test_pool() -> DbPool {
     // Try to connect to test database if TEST_DATABASE_URL is set
@@ -180,6 +187,49 @@ pub async fn create_test_pool() -> DbPool {
     }
 }
 
+/// Register and log in a user through the API
+///
+/// # Returns
+///
+/// The `Authorization` header value and the ID of the personal organization
+/// created at registration
+#[allow(dead_code)] // Used by integration-tests suites
+pub async fn register_with_personal_org<S, B>(app: &S, username: &str) -> (String, String)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let email = format!("{}@example.com", username);
+    let password = "Correct-Horse-Battery-9";
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register")
+        .set_json(serde_json::json!({ "username": username, "email": email, "password": password }))
+        .to_request();
+    assert_eq!(test::call_service(app, req).await.status(), 201);
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .set_json(serde_json::json!({ "username_or_email": email, "password": password }))
+        .to_request();
+    let login: serde_json::Value = test::call_and_read_body_json(app, req).await;
+    let bearer = format!("Bearer {}", login["token"].as_str().unwrap());
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/organizations")
+        .insert_header(("Authorization", bearer.as_str()))
+        .to_request();
+    let orgs: serde_json::Value = test::call_and_read_body_json(app, req).await;
+    let org_id = orgs["data"]
+        .as_array()
+        .and_then(|orgs| orgs.iter().find(|org| org["is_personal"] == true))
+        .and_then(|org| org["id"].as_str())
+        .expect("registration creates a personal organization")
+        .to_string();
+
+    (bearer, org_id)
+}
+
 /// Test organization data for consistent test setup
 #[allow(dead_code)] // Used in organization integration tests
 #[derive(Debug, Clone)]
@@ -0,0 +1,169 @@
+//! Tests for the condition validation endpoint
+//!
+//! # Test Coverage
+//!
+//! - A valid condition of each kind (stateless, stateful)
+//! - Each class of invalid condition: unknown condition type, unknown field,
+//!   unsupported field, unsupported operator, type mismatch, missing config,
+//!   invalid JSONPath
+//! - Authentication is required
+//!
+//! The endpoint does not touch the database, so these tests need no setup.
+
+use actix_web::{http::StatusCode, test, web, App, HttpMessage};
+use api_gateway::handlers::validate_condition;
+use api_gateway::models::Claims;
+use serde_json::{json, Value};
+
+async fn validate(body: Value) -> (StatusCode, Value) {
+    let app = test::init_service(
+        App::new().route("/conditions/validate", web::post().to(validate_condition)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/conditions/validate")
+        .set_json(body)
+        .to_request();
+    req.extensions_mut()
+        .insert(Claims::new("user_1".to_string(), "user".to_string(), 1));
+
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+    (status, test::read_body_json(resp).await)
+}
+
+/// Validate a condition that should fail; returns its error codes
+async fn error_codes(body: Value) -> Vec<String> {
+    let (status, body) = validate(body).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["valid"], false);
+    body["data"]["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_valid_conditions() {
+    let conditions = [
+        json!({"condition_type": "score_threshold", "field": "score", "operator": "<", "value": "60"}),
+        json!({"condition_type": "tag_equals", "field": "tag1", "operator": "=", "value": "trade"}),
+        json!({"condition_type": "agent_id_equals", "field": "agent_id", "operator": "=", "value": "42"}),
+        json!({
+            "condition_type": "rate_limit",
+            "field": "event_count",
+            "operator": ">",
+            "value": "10",
+            "config": {"time_window": "1h"}
+        }),
+    ];
+
+    for condition in conditions {
+        let (status, body) = validate(condition.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["valid"], true, "{}", condition);
+        assert_eq!(body["data"]["errors"], json!([]));
+    }
+}
+
+#[actix_web::test]
+async fn test_unknown_condition_type() {
+    let codes = error_codes(
+        json!({"condition_type": "score_above", "field": "score", "operator": "<", "value": "60"}),
+    )
+    .await;
+    assert_eq!(codes, vec!["unknown_condition_type"]);
+}
+
+#[actix_web::test]
+async fn test_unknown_field() {
+    let (_, body) = validate(
+        json!({"condition_type": "score_threshold", "field": "rating", "operator": "<", "value": "60"}),
+    )
+    .await;
+    let error = &body["data"]["errors"][0];
+    assert_eq!(error["code"], "unknown_field");
+    assert_eq!(error["property"], "field");
+    assert_eq!(error["message"], "Unknown field 'rating'");
+}
+
+#[actix_web::test]
+async fn test_unsupported_field() {
+    let codes = error_codes(
+        json!({"condition_type": "tag_equals", "field": "score", "operator": "=", "value": "trade"}),
+    )
+    .await;
+    assert_eq!(codes, vec!["unsupported_field"]);
+}
+
+#[actix_web::test]
+async fn test_unsupported_operator() {
+    let codes = error_codes(
+        json!({"condition_type": "tag_equals", "field": "tag1", "operator": ">", "value": "trade"}),
+    )
+    .await;
+    assert_eq!(codes, vec!["unsupported_operator"]);
+}
+
+#[actix_web::test]
+async fn test_type_mismatch() {
+    let codes = error_codes(
+        json!({"condition_type": "score_threshold", "field": "score", "operator": "<", "value": "sixty"}),
+    )
+    .await;
+    assert_eq!(codes, vec!["type_mismatch"]);
+}
+
+#[actix_web::test]
+async fn test_missing_config() {
+    let codes = error_codes(
+        json!({"condition_type": "ema_threshold", "field": "score", "operator": "<", "value": "70"}),
+    )
+    .await;
+    assert_eq!(codes, vec!["missing_config"]);
+}
+
+#[actix_web::test]
+async fn test_json_path() {
+    let (_, body) = validate(
+        json!({"condition_type": "json_path", "field": "$.logs[0].topics[1]", "operator": "=", "value": "0x01"}),
+    )
+    .await;
+    assert_eq!(body["data"]["valid"], true);
+
+    let (_, body) = validate(
+        json!({"condition_type": "json_path", "field": "$.logs[0", "operator": "=", "value": "0x01"}),
+    )
+    .await;
+    let error = &body["data"]["errors"][0];
+    assert_eq!(error["code"], "invalid_path");
+    assert_eq!(error["property"], "field");
+}
+
+#[actix_web::test]
+async fn test_malformed_request_is_rejected() {
+    let (status, body) =
+        validate(json!({"condition_type": "", "field": "score", "operator": "<", "value": "60"}))
+            .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "validation_error");
+}
+
+#[actix_web::test]
+async fn test_requires_authentication() {
+    let app = test::init_service(
+        App::new().route("/conditions/validate", web::post().to(validate_condition)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/conditions/validate")
+        .set_json(json!({"condition_type": "score_threshold", "field": "score", "operator": "<", "value": "60"}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,229 @@
+//! Integration tests for the Idempotency-Key middleware
+//!
+//! Tests cover:
+//! - Replaying the stored response instead of re-executing the handler
+//! - Serializing concurrent requests that share a key
+//! - Rejecting a key reused with a different body
+//! - Not storing server errors
+//! - Leaving secret response fields out of the stored response
+//!
+//! # Running Tests
+//!
+//! These tests require Redis:
+//!
+//! ```bash
+//! export TEST_REDIS_URL="redis://localhost:6379"
+//! cargo test --test idempotency_test -- --ignored
+//! ```
+
+use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+use api_gateway::middleware::idempotency::{
+    Idempotency, IdempotencyStore, IDEMPOTENT_REPLAYED_HEADER,
+};
+use serde_json::json;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use uuid::Uuid;
+
+async fn setup_store() -> IdempotencyStore {
+    let redis_url =
+        std::env::var("TEST_REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let conn = shared::redis::create_client(&redis_url)
+        .await
+        .expect("Failed to connect to Redis");
+    IdempotencyStore::new(conn, Duration::from_secs(60))
+}
+
+/// Counts executions and echoes a new resource id
+async fn create_handler(calls: web::Data<Arc<AtomicUsize>>) -> HttpResponse {
+    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    // Slow enough for a concurrent duplicate to arrive while we run
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    HttpResponse::Created().json(json!({ "id": format!("resource_{}", n) }))
+}
+
+/// Returns a secret that must only be shown once
+async fn secret_handler() -> HttpResponse {
+    HttpResponse::Created().json(json!({ "data": { "id": "key_1", "key": "sk_live_secret" } }))
+}
+
+async fn failing_handler(calls: web::Data<Arc<AtomicUsize>>) -> HttpResponse {
+    calls.fetch_add(1, Ordering::SeqCst);
+    HttpResponse::ServiceUnavailable().json(json!({ "error": "unavailable" }))
+}
+
+fn post(key: &str, body: serde_json::Value) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/create")
+        .insert_header(("Idempotency-Key", key))
+        .set_json(body)
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_replay_returns_stored_response() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(setup_store().await))
+            .app_data(web::Data::new(calls.clone()))
+            .route(
+                "/create",
+                web::post().to(create_handler).wrap(Idempotency::new()),
+            ),
+    )
+    .await;
+    let key = Uuid::new_v4().to_string();
+
+    let first = test::call_service(&app, post(&key, json!({"name": "a"})).to_request()).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+    let first_body = test::read_body(first).await;
+
+    let second = test::call_service(&app, post(&key, json!({"name": "a"})).to_request()).await;
+    assert_eq!(second.status(), StatusCode::CREATED);
+    assert_eq!(
+        second.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+        "true"
+    );
+    assert_eq!(test::read_body(second).await, first_body);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Requests without the header always execute
+    let req = test::TestRequest::post()
+        .uri("/create")
+        .set_json(json!({"name": "a"}))
+        .to_request();
+    assert_eq!(
+        test::call_service(&app, req).await.status(),
+        StatusCode::CREATED
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_concurrent_duplicates_execute_once() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(setup_store().await))
+            .app_data(web::Data::new(calls.clone()))
+            .route(
+                "/create",
+                web::post().to(create_handler).wrap(Idempotency::new()),
+            ),
+    )
+    .await;
+    let key = Uuid::new_v4().to_string();
+
+    let (a, b) = futures_util::future::join(
+        test::call_service(&app, post(&key, json!({"name": "a"})).to_request()),
+        test::call_service(&app, post(&key, json!({"name": "a"})).to_request()),
+    )
+    .await;
+
+    assert_eq!(a.status(), StatusCode::CREATED);
+    assert_eq!(b.status(), StatusCode::CREATED);
+    let replayed = [&a, &b]
+        .iter()
+        .filter(|res| res.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER))
+        .count();
+    assert_eq!(replayed, 1);
+    assert_eq!(test::read_body(a).await, test::read_body(b).await);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_key_reused_with_different_body_is_rejected() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(setup_store().await))
+            .app_data(web::Data::new(calls.clone()))
+            .route(
+                "/create",
+                web::post().to(create_handler).wrap(Idempotency::new()),
+            ),
+    )
+    .await;
+    let key = Uuid::new_v4().to_string();
+
+    let first = test::call_service(&app, post(&key, json!({"name": "a"})).to_request()).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    let second = test::call_service(&app, post(&key, json!({"name": "b"})).to_request()).await;
+    assert_eq!(second.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_server_errors_are_not_stored() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(setup_store().await))
+            .app_data(web::Data::new(calls.clone()))
+            .route(
+                "/create",
+                web::post().to(failing_handler).wrap(Idempotency::new()),
+            ),
+    )
+    .await;
+    let key = Uuid::new_v4().to_string();
+
+    for _ in 0..2 {
+        let res = test::call_service(&app, post(&key, json!({})).to_request()).await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(res.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[actix_web::test]
+#[ignore] // Requires TEST_REDIS_URL (integration test)
+async fn test_secret_fields_are_not_replayed() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(setup_store().await))
+            .route(
+                "/create",
+                web::post()
+                    .to(secret_handler)
+                    .wrap(Idempotency::new().redact("/data/key")),
+            ),
+    )
+    .await;
+    let key = Uuid::new_v4().to_string();
+
+    // The first response carries the secret
+    let first: serde_json::Value =
+        test::call_and_read_body_json(&app, post(&key, json!({})).to_request()).await;
+    assert_eq!(first["data"]["key"], "sk_live_secret");
+
+    // The replay does not: it was never stored
+    let second = test::call_service(&app, post(&key, json!({})).to_request()).await;
+    assert_eq!(second.status(), StatusCode::CREATED);
+    assert!(second.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_some());
+    let second: serde_json::Value = test::read_body_json(second).await;
+    assert_eq!(second, json!({ "data": { "id": "key_1" } }));
+}
+
+#[actix_web::test]
+async fn test_invalid_key_is_rejected() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let app = test::init_service(App::new().app_data(web::Data::new(calls.clone())).route(
+        "/create",
+        web::post().to(create_handler).wrap(Idempotency::new()),
+    ))
+    .await;
+
+    let res = test::call_service(&app, post("has space", json!({})).to_request()).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
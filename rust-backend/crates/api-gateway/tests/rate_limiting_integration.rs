@@ -8,6 +8,7 @@
 //! - Rate limit headers (X-RateLimit-*)
 //! - 429 error responses
 //! - Auth layer precedence (L2 > L1 > L0)
+//! - Per-organization overrides (precedence and runtime updates)
 //!
 //! All tests use real Redis and PostgreSQL instances.
 //!
@@ -35,11 +36,14 @@ use api_gateway::{
         query_tier::{QueryTier, QueryTierExtractor},
         unified_rate_limiter::UnifiedRateLimiter,
     },
+    models::RateLimitTierConfig,
+    repositories::OrganizationRateLimitRepository,
     services::ApiKeyService,
 };
 use chrono::Utc;
 use futures_util::future::LocalBoxFuture;
 use serde_json::json;
+use shared::redis::cache::EntityCache;
 use shared::{DbPool, RateLimiter};
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -110,6 +114,73 @@ where
     }
 }
 
+/// Organization auth middleware for testing
+///
+/// Creates a wallet-signature AuthContext for the organization named in the
+/// `X-Test-Org-Id` header (starter plan: 100 requests/hour).
+pub struct TestOrgExtractor;
+
+impl<S, B> Transform<S, ServiceRequest> for TestOrgExtractor
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = TestOrgExtractorMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TestOrgExtractorMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct TestOrgExtractorMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for TestOrgExtractorMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let org_id = req
+                .headers()
+                .get("X-Test-Org-Id")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let ip = ip_extractor::extract_ip(req.request());
+
+            let auth_ctx = AuthContext::wallet_signature(
+                "test_user".to_string(),
+                org_id,
+                1,
+                ip,
+                "starter".to_string(),
+            );
+            req.extensions_mut().insert(auth_ctx);
+
+            service.call(req).await
+        })
+    }
+}
+
 // ============================================================================
 // Test Setup Helpers
 // ============================================================================
@@ -766,8 +837,226 @@ async fn test_rate_limit_headers_on_429() {
     let resp = test::call_service(&app, req).await;
     assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
 
-    // Note: In full implementation, you'd check for Retry-After header
-    // This requires custom error handling in the middleware
+    let headers = resp.headers();
+    let retry_after = headers
+        .get("retry-after")
+        .expect("Missing Retry-After header")
+        .to_str()
+        .unwrap()
+        .parse::<i64>()
+        .unwrap();
+    assert!(
+        (1..=3600).contains(&retry_after),
+        "Retry-After should be within the window"
+    );
+    assert_eq!(headers.get("x-ratelimit-limit").unwrap(), "10");
+    assert_eq!(headers.get("x-ratelimit-remaining").unwrap(), "0");
+    assert!(headers.contains_key("x-ratelimit-reset"));
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"]["code"], "RATE_LIMITED");
+    assert_eq!(body["error"]["retry_after"], retry_after);
+}
+
+#[actix_web::test]
+#[ignore]
+async fn test_rate_limit_headers_decrement_across_requests() {
+    let mut test_app = TestApp::new().await;
+    test_app.flush_redis().await;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(UnifiedRateLimiter::new((*test_app.rate_limiter).clone()))
+            .wrap(QueryTierExtractor::new())
+            .wrap(TestIpExtractor)
+            .route("/test", web::get().to(success_handler)),
+    )
+    .await;
+
+    let ip = "192.168.1.252";
+    let header = |resp: &ServiceResponse<_>, name: &str| -> i64 {
+        resp.headers()
+            .get(name)
+            .unwrap_or_else(|| panic!("missing {} header", name))
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+
+    let mut previous_remaining = None;
+    for expected_remaining in (0..10).rev() {
+        let req = test::TestRequest::get()
+            .uri("/test")
+            .insert_header(("X-Forwarded-For", ip))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        assert_eq!(header(&resp, "x-ratelimit-limit"), 10);
+        let remaining = header(&resp, "x-ratelimit-remaining");
+        assert_eq!(remaining, expected_remaining);
+        if let Some(previous) = previous_remaining {
+            assert_eq!(
+                remaining,
+                previous - 1,
+                "Remaining should drop by the request cost"
+            );
+        }
+        previous_remaining = Some(remaining);
+
+        let reset = header(&resp, "x-ratelimit-reset");
+        assert!(
+            reset > Utc::now().timestamp(),
+            "Reset should be in the future"
+        );
+        assert!(!resp.headers().contains_key("retry-after"));
+    }
+}
+
+// ============================================================================
+// Organization Override Tests
+// ============================================================================
+
+/// Request to the test route authenticated as `org_id`
+fn org_request(org_id: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri("/test")
+        .insert_header(("X-Test-Org-Id", org_id))
+}
+
+/// Extract the (limit, window) rate limit headers from a response
+fn limit_headers<B>(resp: &ServiceResponse<B>) -> (String, String) {
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .unwrap_or_else(|| panic!("missing {} header", name))
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+    (header("x-ratelimit-limit"), header("x-ratelimit-window"))
+}
+
+#[actix_web::test]
+#[ignore]
+async fn test_org_override_takes_precedence_over_plan_limit() {
+    let mut test_app = TestApp::new().await;
+    test_app.flush_redis().await;
+
+    let (org_id, _) = create_test_org(&test_app.pool, "starter").await;
+    let (other_org_id, _) = create_test_org(&test_app.pool, "starter").await;
+
+    OrganizationRateLimitRepository::replace_for_organization(
+        &test_app.pool,
+        &org_id,
+        &[RateLimitTierConfig {
+            tier: 0,
+            limit: 7,
+            window_seconds: 600,
+        }],
+    )
+    .await
+    .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(test_app.pool.clone()))
+            .wrap(UnifiedRateLimiter::new((*test_app.rate_limiter).clone()))
+            .wrap(QueryTierExtractor::new())
+            .wrap(TestOrgExtractor)
+            .route("/test", web::get().to(success_handler)),
+    )
+    .await;
+
+    // Override applies to its organization and tier
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&org_id).to_request()).await),
+        ("7".to_string(), "600".to_string())
+    );
+
+    // Other organizations keep the starter plan default
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&other_org_id).to_request()).await),
+        ("100".to_string(), "3600".to_string())
+    );
+
+    cleanup_test_data(&test_app.pool, &org_id).await;
+    cleanup_test_data(&test_app.pool, &other_org_id).await;
+}
+
+#[actix_web::test]
+#[ignore]
+async fn test_org_override_change_takes_effect_without_restart() {
+    let mut test_app = TestApp::new().await;
+    test_app.flush_redis().await;
+
+    let (org_id, _) = create_test_org(&test_app.pool, "starter").await;
+    let cache = EntityCache::new(test_app.redis.clone(), Some(300));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(test_app.pool.clone()))
+            .app_data(web::Data::new(cache.clone()))
+            .wrap(UnifiedRateLimiter::new((*test_app.rate_limiter).clone()))
+            .wrap(QueryTierExtractor::new())
+            .wrap(TestOrgExtractor)
+            .route("/test", web::get().to(success_handler)),
+    )
+    .await;
+
+    // No override yet (the empty result is now cached)
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&org_id).to_request()).await).0,
+        "100"
+    );
+
+    // Set an override on the running app, as the admin endpoint does
+    let set_limit = |limit: i32| {
+        let pool = test_app.pool.clone();
+        let cache = cache.clone();
+        let org_id = org_id.clone();
+        async move {
+            OrganizationRateLimitRepository::replace_for_organization(
+                &pool,
+                &org_id,
+                &[RateLimitTierConfig {
+                    tier: 0,
+                    limit,
+                    window_seconds: 3600,
+                }],
+            )
+            .await
+            .unwrap();
+            OrganizationRateLimitRepository::invalidate_cache(&cache, &org_id).await;
+        }
+    };
+
+    set_limit(250).await;
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&org_id).to_request()).await).0,
+        "250"
+    );
+
+    set_limit(40).await;
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&org_id).to_request()).await).0,
+        "40"
+    );
+
+    // Clearing the overrides reverts to the plan limit
+    OrganizationRateLimitRepository::replace_for_organization(&test_app.pool, &org_id, &[])
+        .await
+        .unwrap();
+    OrganizationRateLimitRepository::invalidate_cache(&cache, &org_id).await;
+    assert_eq!(
+        limit_headers(&test::call_service(&app, org_request(&org_id).to_request()).await).0,
+        "100"
+    );
+
+    cleanup_test_data(&test_app.pool, &org_id).await;
 }
 
 // ============================================================================
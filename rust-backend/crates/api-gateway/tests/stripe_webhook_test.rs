@@ -0,0 +1,114 @@
+//! Integration tests for the Stripe webhook abuse limits
+//!
+//! Tests cover:
+//! - Oversized bodies rejected (declared or streamed) before signature checks
+//! - Malformed Stripe-Signature headers rejected before the body is read
+//! - Small spoofed payloads still reaching signature verification
+//!
+//! No database or Stripe account is needed: every request is rejected before
+//! the handler touches either.
+
+use actix_web::{http::header, http::StatusCode, test, web, App};
+use api_gateway::handlers::{handle_stripe_webhook, STRIPE_WEBHOOK_MAX_BODY_BYTES};
+use serde_json::Value;
+use shared::AppSecrets;
+use sqlx::postgres::PgPoolOptions;
+
+const SPOOFED_SIGNATURE: &str =
+    "t=1492774577,v1=5257a869e7ecebeda32affa62cdca3fa51cad7e77a0e56ff536d0ce8e108d8bd";
+
+fn stripe_secrets() -> AppSecrets {
+    AppSecrets {
+        database_url: "postgresql://localhost/test".to_string(),
+        redis_url: "redis://localhost:6379".to_string(),
+        jwt_secret: "test_jwt_secret_at_least_32_characters_long".to_string(),
+        stripe_secret_key: Some("sk_test_123".to_string()),
+        stripe_webhook_secret: Some("whsec_test_123".to_string()),
+        ethereum_sepolia_rpc_url: "https://eth-sepolia.example.com".to_string(),
+        base_sepolia_rpc_url: "https://base-sepolia.example.com".to_string(),
+        linea_sepolia_rpc_url: None,
+        api_encryption_key: "base64encodedkey12345678901234567890".to_string(),
+        telegram_bot_token: None,
+    }
+}
+
+/// Call the webhook handler with the IP whitelist off and a lazy (unused) pool
+async fn call_webhook(req: test::TestRequest) -> (StatusCode, Value) {
+    std::env::set_var("STRIPE_IP_WHITELIST_ENABLED", "false");
+    let pool = PgPoolOptions::new()
+        .connect_lazy("postgresql://localhost/unused")
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool))
+            .app_data(web::Data::new(stripe_secrets()))
+            .route("/billing/webhook", web::post().to(handle_stripe_webhook)),
+    )
+    .await;
+
+    let res = test::call_service(&app, req.uri("/billing/webhook").to_request()).await;
+    let status = res.status();
+    (status, test::read_body_json(res).await)
+}
+
+fn oversized_body() -> String {
+    format!(
+        r#"{{"id": "evt_spoofed", "padding": "{}"}}"#,
+        "x".repeat(STRIPE_WEBHOOK_MAX_BODY_BYTES)
+    )
+}
+
+#[actix_web::test]
+async fn test_oversized_body_rejected_before_signature_check() {
+    let (status, body) = call_webhook(
+        test::TestRequest::post()
+            .insert_header(("Stripe-Signature", SPOOFED_SIGNATURE))
+            .set_payload(oversized_body()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"], "payload_too_large");
+}
+
+#[actix_web::test]
+async fn test_oversized_body_with_false_content_length_rejected() {
+    // Content-Length claims a small body; the stream cap still applies
+    let (status, body) = call_webhook(
+        test::TestRequest::post()
+            .insert_header(("Stripe-Signature", SPOOFED_SIGNATURE))
+            .set_payload(oversized_body())
+            .insert_header((header::CONTENT_LENGTH, "64")),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"], "payload_too_large");
+}
+
+#[actix_web::test]
+async fn test_malformed_signature_rejected_before_body() {
+    let (status, body) = call_webhook(
+        test::TestRequest::post()
+            .insert_header(("Stripe-Signature", "not-a-stripe-signature"))
+            .set_payload(oversized_body()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Malformed Stripe-Signature header");
+}
+
+#[actix_web::test]
+async fn test_small_spoofed_body_reaches_signature_verification() {
+    let (status, body) = call_webhook(
+        test::TestRequest::post()
+            .insert_header(("Stripe-Signature", SPOOFED_SIGNATURE))
+            .set_payload(r#"{"id": "evt_spoofed", "type": "checkout.session.completed"}"#),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Webhook signature verification failed");
+}
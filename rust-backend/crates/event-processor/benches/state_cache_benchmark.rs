@@ -423,8 +423,9 @@ async fn bench_cache_hit_rate() {
         if i % 10 == 0 {
             let mut conn = redis.clone();
             use redis::AsyncCommands;
-            let _: Result<(), redis::RedisError> =
-                conn.del(format!("trigger:state:{}", trigger_id)).await;
+            let _: Result<(), redis::RedisError> = conn
+                .del(shared::redis::cache::trigger_state_key(trigger_id))
+                .await;
             cache_misses += 1;
         } else {
             cache_hits += 1;
@@ -14,7 +14,7 @@
 //!
 //! - **Write-through**: Updates are written to both PostgreSQL and Redis
 //! - **TTL**: 5 minutes (configurable via STATE_CACHE_TTL_SECS)
-//! - **Key format**: `trigger:state:{trigger_id}`
+//! - **Key format**: `agentauri:cache:trigger:state:{trigger_id}`
 //! - **Graceful degradation**: Falls back to PostgreSQL if Redis unavailable
 //!
 //! # Example
@@ -101,9 +101,9 @@ impl CachedStateManager {
 
     /// Build Redis cache key for a trigger
     ///
-    /// Format: `trigger:state:{trigger_id}`
+    /// Format: `agentauri:cache:trigger:state:{trigger_id}`
     fn cache_key(&self, trigger_id: &str) -> String {
-        format!("trigger:state:{}", trigger_id)
+        shared::redis::cache::trigger_state_key(trigger_id)
     }
 
     /// Load state for a trigger (with caching)
@@ -298,6 +298,9 @@ impl CachedStateManager {
 
         let deleted = result.rows_affected();
 
+        #[cfg(feature = "metrics")]
+        metrics::counter!("event_processor.state_cleanup_deleted").increment(deleted);
+
         if deleted > 0 {
             warn!(
                 deleted = deleted,
@@ -493,7 +496,9 @@ mod tests {
 
         // Clean up any existing test cache keys
         let mut conn = manager.clone();
-        let _: Result<(), redis::RedisError> = conn.del("trigger:state:test_cached_*").await;
+        let _: Result<(), redis::RedisError> = conn
+            .del("agentauri:cache:trigger:state:test_cached_*")
+            .await;
 
         manager
     }
@@ -552,11 +557,11 @@ mod tests {
 
         assert_eq!(
             manager.cache_key("trigger_123"),
-            "trigger:state:trigger_123"
+            "agentauri:cache:trigger:state:trigger_123"
         );
         assert_eq!(
             manager.cache_key("abc-def-ghi"),
-            "trigger:state:abc-def-ghi"
+            "agentauri:cache:trigger:state:abc-def-ghi"
         );
     }
 
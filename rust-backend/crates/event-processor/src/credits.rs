@@ -0,0 +1,170 @@
+//! Credit checks before enqueuing actions
+//!
+//! Action workers deduct [`ActionCostsConfig`] from the organization's credits
+//! after each successful execution. The processor does not enqueue an action
+//! the organization cannot pay for: it loads the balances of the organizations
+//! whose triggers matched once per event ([`CreditBudget::load`]) and reserves
+//! each action's cost locally, so one event cannot enqueue more than the
+//! balance covers. Workers check again before executing.
+//!
+//! Costs are set once at startup ([`set_action_costs`]); with the default
+//! (every action free) no balance is loaded and nothing is blocked.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use anyhow::{Context, Result};
+use shared::{ActionCostsConfig, ActionType, DbPool};
+
+static ACTION_COSTS: RwLock<ActionCostsConfig> = RwLock::new(ActionCostsConfig {
+    telegram: 0,
+    rest: 0,
+    mcp: 0,
+});
+
+/// Set the per-action-type costs used by [`CreditBudget`]
+pub fn set_action_costs(costs: ActionCostsConfig) {
+    *ACTION_COSTS.write().unwrap_or_else(|e| e.into_inner()) = costs;
+}
+
+/// Current per-action-type costs
+pub fn action_costs() -> ActionCostsConfig {
+    *ACTION_COSTS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// An action the organization cannot pay for
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("balance {balance} is below the {action_type} action cost of {cost} (micro-USDC)")]
+pub struct InsufficientCredits {
+    pub balance: i64,
+    pub cost: i64,
+    pub action_type: ActionType,
+}
+
+/// Remaining credits per organization while the actions of one event are enqueued
+#[derive(Debug, Clone)]
+pub struct CreditBudget {
+    costs: ActionCostsConfig,
+    /// `None`: balances were not loaded, every action is allowed
+    balances: Option<HashMap<String, i64>>,
+}
+
+impl CreditBudget {
+    /// Budget that allows every action
+    pub fn unlimited() -> Self {
+        Self {
+            costs: action_costs(),
+            balances: None,
+        }
+    }
+
+    /// Budget from known balances (organizations not listed have none)
+    pub fn new(costs: ActionCostsConfig, balances: HashMap<String, i64>) -> Self {
+        Self {
+            costs,
+            balances: Some(balances),
+        }
+    }
+
+    /// Load the balances of `organization_ids` with the configured costs
+    pub async fn load(organization_ids: &[String], db_pool: &DbPool) -> Result<Self> {
+        let costs = action_costs();
+        if costs.is_free() || organization_ids.is_empty() {
+            return Ok(Self::unlimited());
+        }
+
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT organization_id, balance FROM credits WHERE organization_id = ANY($1)",
+        )
+        .bind(organization_ids)
+        .fetch_all(db_pool)
+        .await
+        .context("Failed to fetch credit balances")?;
+
+        Ok(Self::new(costs, rows.into_iter().collect()))
+    }
+
+    /// Reserve the cost of one `action_type` action for `organization_id`
+    pub fn reserve(
+        &mut self,
+        organization_id: &str,
+        action_type: &ActionType,
+    ) -> Result<(), InsufficientCredits> {
+        let cost = self.costs.cost(action_type);
+        let Some(balances) = self.balances.as_mut() else {
+            return Ok(());
+        };
+        if cost <= 0 {
+            return Ok(());
+        }
+
+        let balance = balances.entry(organization_id.to_string()).or_insert(0);
+        if *balance < cost {
+            return Err(InsufficientCredits {
+                balance: *balance,
+                cost,
+                action_type: action_type.clone(),
+            });
+        }
+        *balance -= cost;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn costs() -> ActionCostsConfig {
+        ActionCostsConfig {
+            telegram: 2_000,
+            rest: 0,
+            mcp: 5_000,
+        }
+    }
+
+    #[test]
+    fn test_reserve_deducts_until_balance_runs_out() {
+        let mut budget = CreditBudget::new(costs(), HashMap::from([("org_a".to_string(), 9_000)]));
+
+        assert!(budget.reserve("org_a", &ActionType::Mcp).is_ok());
+        assert!(budget.reserve("org_a", &ActionType::Telegram).is_ok());
+        assert_eq!(
+            budget.reserve("org_a", &ActionType::Mcp),
+            Err(InsufficientCredits {
+                balance: 2_000,
+                cost: 5_000,
+                action_type: ActionType::Mcp,
+            })
+        );
+        // A cheaper action still fits the remaining balance
+        assert!(budget.reserve("org_a", &ActionType::Telegram).is_ok());
+    }
+
+    #[test]
+    fn test_zero_balance_is_blocked_with_reason() {
+        let mut budget = CreditBudget::new(costs(), HashMap::new());
+
+        let err = budget
+            .reserve("org_without_credits", &ActionType::Mcp)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "balance 0 is below the mcp action cost of 5000 (micro-USDC)"
+        );
+        // Free action types are never blocked
+        assert!(budget
+            .reserve("org_without_credits", &ActionType::Rest)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unloaded_budget_allows_everything() {
+        let mut budget = CreditBudget {
+            costs: costs(),
+            balances: None,
+        };
+
+        assert!(budget.reserve("org_a", &ActionType::Mcp).is_ok());
+    }
+}
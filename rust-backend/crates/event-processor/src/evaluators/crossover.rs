@@ -0,0 +1,460 @@
+//! Moving-average crossover evaluator
+//!
+//! Tracks a fast and a slow EMA of scores and fires when the fast one crosses
+//! the slow one: a rising trend overtaking the longer-term average
+//! (`crosses_above`) or falling through it (`crosses_below`).
+//!
+//! The condition fires on the event where the crossing happens, not while one
+//! average stays above the other: after a crossing it fires again only once
+//! the averages have crossed back and then over again. `min_spread` adds
+//! hysteresis, so the averages must separate by more than that much before a
+//! side change counts.
+//!
+//! # Example
+//!
+//! ```json
+//! {
+//!   "condition_type": "ema_crossover",
+//!   "field": "score",
+//!   "operator": "crosses_above",
+//!   "value": "0",
+//!   "config": {
+//!     "fast_period": 5,
+//!     "slow_period": 20,
+//!     "min_spread": 0.5
+//!   }
+//! }
+//! ```
+//!
+//! Operators: `crosses_above`, `crosses_below`, or `crosses` (either
+//! direction). The condition value is not used.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::models::{Event, TriggerCondition};
+
+use super::ema::{EmaEvaluator, EmaState};
+
+/// Direction of a crossing, from the point of view of the fast EMA
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossDirection {
+    /// Fast EMA moved from below the slow EMA to above it
+    Above,
+    /// Fast EMA moved from above the slow EMA to below it
+    Below,
+}
+
+/// A crossing and when it was seen
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Crossing {
+    pub direction: CrossDirection,
+    pub at: DateTime<Utc>,
+}
+
+/// Crossover state stored in trigger_state table
+///
+/// Both averages are persisted together so they always describe the same
+/// events.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CrossoverState {
+    pub fast: EmaState,
+    pub slow: EmaState,
+    /// Side of the slow EMA the fast EMA is on (None until they separate)
+    pub side: Option<CrossDirection>,
+    /// Most recent crossing
+    pub last_crossing: Option<Crossing>,
+}
+
+/// EMA crossover evaluator
+#[derive(Debug)]
+pub struct CrossoverEvaluator {
+    fast: EmaEvaluator,
+    slow: EmaEvaluator,
+    min_spread: f64,
+}
+
+impl CrossoverEvaluator {
+    /// Create a crossover evaluator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_processor::evaluators::crossover::CrossoverEvaluator;
+    ///
+    /// let evaluator = CrossoverEvaluator::new(5, 20, 0.0);
+    /// ```
+    pub fn new(fast_period: usize, slow_period: usize, min_spread: f64) -> Self {
+        Self {
+            fast: EmaEvaluator::new(fast_period),
+            slow: EmaEvaluator::new(slow_period),
+            min_spread,
+        }
+    }
+
+    /// Create evaluator from condition config JSONB
+    ///
+    /// # Config Format
+    ///
+    /// ```json
+    /// {
+    ///   "fast_period": 5,
+    ///   "slow_period": 20,
+    ///   "min_spread": 0.0
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a period is missing or 0, the fast period is not
+    /// shorter than the slow one, or min_spread is negative
+    pub fn from_config(config: &serde_json::Value) -> Result<Self> {
+        let period = |key: &str| -> Result<usize> {
+            let value = config
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .with_context(|| format!("Missing or invalid {} in config", key))?;
+            if value == 0 {
+                anyhow::bail!("{} must be greater than 0", key);
+            }
+            Ok(value as usize)
+        };
+        let fast_period = period("fast_period")?;
+        let slow_period = period("slow_period")?;
+        if fast_period >= slow_period {
+            anyhow::bail!("fast_period must be less than slow_period");
+        }
+
+        let min_spread = match config.get("min_spread") {
+            None => 0.0,
+            Some(v) => v.as_f64().context("Invalid min_spread in config")?,
+        };
+        if min_spread < 0.0 {
+            anyhow::bail!("min_spread must not be negative");
+        }
+
+        Ok(Self::new(fast_period, slow_period, min_spread))
+    }
+
+    /// Evaluate the crossover condition against an event
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (matches, new_state):
+    /// - matches: true if this event made the averages cross in the
+    ///   direction the operator asks for
+    /// - new_state: updated state for persistence
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the event has no score or the operator is invalid
+    pub fn evaluate(
+        &self,
+        event: &Event,
+        condition: &TriggerCondition,
+        current_state: Option<CrossoverState>,
+    ) -> Result<(bool, CrossoverState)> {
+        let wanted: &[CrossDirection] = match condition.operator.as_str() {
+            "crosses_above" => &[CrossDirection::Above],
+            "crosses_below" => &[CrossDirection::Below],
+            "crosses" => &[CrossDirection::Above, CrossDirection::Below],
+            other => anyhow::bail!("Invalid operator: {}", other),
+        };
+
+        let score = event.score.context("Event has no score field")? as f64;
+        let (new_state, crossed) = self.update(score, current_state);
+
+        let matches = crossed.is_some_and(|direction| wanted.contains(&direction));
+
+        tracing::debug!(
+            fast_ema = new_state.fast.ema,
+            slow_ema = new_state.slow.ema,
+            crossed = ?crossed,
+            matches = matches,
+            "EMA crossover evaluation complete"
+        );
+
+        Ok((matches, new_state))
+    }
+
+    /// Fold one score into both averages; returns the new state and the
+    /// direction of the crossing this score caused, if any
+    fn update(
+        &self,
+        score: f64,
+        current_state: Option<CrossoverState>,
+    ) -> (CrossoverState, Option<CrossDirection>) {
+        let fast = self.fast.update(score, current_state.map(|s| s.fast));
+        let slow = self.slow.update(score, current_state.map(|s| s.slow));
+        let previous_side = current_state.and_then(|s| s.side);
+
+        let spread = fast.ema - slow.ema;
+        let side = if spread > self.min_spread {
+            Some(CrossDirection::Above)
+        } else if spread < -self.min_spread {
+            Some(CrossDirection::Below)
+        } else {
+            // Inside the dead band: stay on the last side
+            previous_side
+        };
+
+        // A crossing needs an established side to cross from
+        let crossed = match (previous_side, side) {
+            (Some(from), Some(to)) if from != to => Some(to),
+            _ => None,
+        };
+        let last_crossing = match crossed {
+            Some(direction) => Some(Crossing {
+                direction,
+                at: fast.last_updated,
+            }),
+            None => current_state.and_then(|s| s.last_crossing),
+        };
+
+        let new_state = CrossoverState {
+            fast,
+            slow,
+            side,
+            last_crossing,
+        };
+        (new_state, crossed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_event(score: i32) -> Event {
+        Event {
+            id: "test-event".to_string(),
+            chain_id: 84532,
+            block_number: 1000,
+            block_hash: "0xabc".to_string(),
+            transaction_hash: "0xdef".to_string(),
+            log_index: 0,
+            registry: "reputation".to_string(),
+            event_type: "NewFeedback".to_string(),
+            agent_id: Some(42),
+            timestamp: 1234567890,
+            owner: None,
+            token_uri: None,
+            metadata_key: None,
+            metadata_value: None,
+            client_address: Some("0x123".to_string()),
+            feedback_index: Some(0),
+            score: Some(score),
+            tag1: None,
+            tag2: None,
+            file_uri: None,
+            file_hash: None,
+            validator_address: None,
+            request_hash: None,
+            response: None,
+            response_uri: None,
+            response_hash: None,
+            tag: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn create_test_condition(operator: &str) -> TriggerCondition {
+        TriggerCondition {
+            id: "test-condition-1".to_string(),
+            trigger_id: "test-trigger".to_string(),
+            condition_type: "ema_crossover".to_string(),
+            field: "score".to_string(),
+            operator: operator.to_string(),
+            value: serde_json::Value::String("0".to_string()),
+            config: Some(serde_json::json!({ "fast_period": 3, "slow_period": 10 })),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Declines, recovers well above its start, then collapses
+    fn synthetic_series() -> Vec<i32> {
+        let mut series = vec![80, 75, 70, 65, 60];
+        series.extend([70, 80, 90, 95, 95, 95, 95, 95, 95, 95]);
+        series.extend([60, 40, 30, 30, 30, 30, 30, 30]);
+        series
+    }
+
+    /// Run the series; returns the index of each event that matched
+    fn matching_events(evaluator: &CrossoverEvaluator, operator: &str) -> Vec<usize> {
+        let condition = create_test_condition(operator);
+        let mut state = None;
+        let mut matched = Vec::new();
+
+        for (i, score) in synthetic_series().into_iter().enumerate() {
+            let (matches, new_state) = evaluator
+                .evaluate(&create_test_event(score), &condition, state)
+                .unwrap();
+            if matches {
+                matched.push(i);
+            }
+            state = Some(new_state);
+        }
+
+        matched
+    }
+
+    #[test]
+    fn test_crossover_from_config() {
+        let config = serde_json::json!({ "fast_period": 5, "slow_period": 20, "min_spread": 1.5 });
+        let evaluator = CrossoverEvaluator::from_config(&config).unwrap();
+        assert_eq!(evaluator.min_spread, 1.5);
+
+        let config = serde_json::json!({ "fast_period": 5, "slow_period": 20 });
+        assert_eq!(
+            CrossoverEvaluator::from_config(&config).unwrap().min_spread,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_crossover_from_config_invalid() {
+        for (config, message) in [
+            (serde_json::json!({ "slow_period": 20 }), "fast_period"),
+            (
+                serde_json::json!({ "fast_period": 0, "slow_period": 20 }),
+                "greater than 0",
+            ),
+            (
+                serde_json::json!({ "fast_period": 20, "slow_period": 20 }),
+                "less than slow_period",
+            ),
+            (
+                serde_json::json!({ "fast_period": 5, "slow_period": 20, "min_spread": -1 }),
+                "negative",
+            ),
+        ] {
+            let err = CrossoverEvaluator::from_config(&config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_series_crosses_once_each_way() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+
+        let above = matching_events(&evaluator, "crosses_above");
+        let below = matching_events(&evaluator, "crosses_below");
+        assert_eq!(above.len(), 1);
+        assert_eq!(below.len(), 1);
+        // The recovery crosses first, then the collapse crosses back
+        assert!((5..15).contains(&above[0]), "{:?}", above);
+        assert!((15..23).contains(&below[0]), "{:?}", below);
+
+        assert_eq!(
+            matching_events(&evaluator, "crosses"),
+            vec![above[0], below[0]]
+        );
+    }
+
+    #[test]
+    fn test_crossing_recorded_with_direction() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+        let condition = create_test_condition("crosses");
+        let mut state = None;
+        let mut directions = Vec::new();
+
+        for score in synthetic_series() {
+            let (matches, new_state) = evaluator
+                .evaluate(&create_test_event(score), &condition, state)
+                .unwrap();
+            if matches {
+                directions.push(new_state.last_crossing.unwrap().direction);
+            }
+            state = Some(new_state);
+        }
+
+        assert_eq!(
+            directions,
+            vec![CrossDirection::Above, CrossDirection::Below]
+        );
+        let state = state.unwrap();
+        assert_eq!(state.side, Some(CrossDirection::Below));
+        assert_eq!(state.fast.count, synthetic_series().len());
+        assert_eq!(state.slow.count, synthetic_series().len());
+    }
+
+    #[test]
+    fn test_first_separation_is_not_a_crossing() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+        let condition = create_test_condition("crosses");
+
+        // Flat start: the averages are equal, so no side is established
+        let (matches, state) = evaluator
+            .evaluate(&create_test_event(50), &condition, None)
+            .unwrap();
+        assert!(!matches);
+        assert_eq!(state.side, None);
+
+        // Rising from there separates them without crossing anything
+        let (matches, state) = evaluator
+            .evaluate(&create_test_event(90), &condition, Some(state))
+            .unwrap();
+        assert!(!matches);
+        assert_eq!(state.side, Some(CrossDirection::Above));
+        assert_eq!(state.last_crossing, None);
+    }
+
+    #[test]
+    fn test_min_spread_ignores_small_wobbles() {
+        let condition = create_test_condition("crosses");
+        let run = |evaluator: &CrossoverEvaluator| {
+            let mut state = None;
+            let mut fired = 0;
+            for score in [50, 50, 50, 56, 44, 56, 44, 56, 44] {
+                let (matches, new_state) = evaluator
+                    .evaluate(&create_test_event(score), &condition, state)
+                    .unwrap();
+                fired += matches as usize;
+                state = Some(new_state);
+            }
+            fired
+        };
+
+        assert!(run(&CrossoverEvaluator::new(3, 10, 0.0)) > 0);
+        assert_eq!(run(&CrossoverEvaluator::new(3, 10, 5.0)), 0);
+    }
+
+    #[test]
+    fn test_crossover_invalid_operator() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+        let result = evaluator.evaluate(&create_test_event(50), &create_test_condition(">"), None);
+        assert!(result.unwrap_err().to_string().contains("Invalid operator"));
+    }
+
+    #[test]
+    fn test_crossover_accepts_declared_operators() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+        for operator in shared::ConditionType::EmaCrossover.operators() {
+            let condition = create_test_condition(operator);
+            assert!(
+                evaluator
+                    .evaluate(&create_test_event(50), &condition, None)
+                    .is_ok(),
+                "{}",
+                operator
+            );
+        }
+    }
+
+    #[test]
+    fn test_crossover_state_serialization() {
+        let evaluator = CrossoverEvaluator::new(3, 10, 0.0);
+        let mut state = None;
+        for score in synthetic_series() {
+            state = Some(evaluator.update(score as f64, state).0);
+        }
+        let state = state.unwrap();
+
+        let json = serde_json::to_value(state).unwrap();
+        assert_eq!(json["side"], "below");
+        assert_eq!(json["last_crossing"]["direction"], "below");
+        let deserialized: CrossoverState = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, state);
+    }
+}
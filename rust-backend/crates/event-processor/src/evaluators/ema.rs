@@ -27,6 +27,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use shared::models::{Event, TriggerCondition};
+use shared::Comparison;
 
 /// Extract string value from JSON for parsing
 fn json_value_as_str(value: &serde_json::Value) -> String {
@@ -104,6 +105,24 @@ impl EmaEvaluator {
         Ok(Self::new(window_size))
     }
 
+    /// Fold one score into the EMA
+    ///
+    /// The first score (no current state) seeds the average.
+    pub fn update(&self, score: f64, current_state: Option<EmaState>) -> EmaState {
+        let ema = match current_state {
+            // EMA formula: EMA_new = alpha * score + (1 - alpha) * EMA_old
+            Some(state) => self.alpha * score + (1.0 - self.alpha) * state.ema,
+            // First value: EMA = score
+            None => score,
+        };
+
+        EmaState {
+            ema,
+            count: current_state.map(|s| s.count).unwrap_or(0) + 1,
+            last_updated: Utc::now(),
+        }
+    }
+
     /// Evaluate EMA condition against an event
     ///
     /// # Arguments
@@ -139,26 +158,9 @@ impl EmaEvaluator {
             "Evaluating EMA condition"
         );
 
-        // Calculate new EMA
-        let new_ema = match current_state {
-            Some(state) => {
-                // EMA formula: EMA_new = alpha * score + (1 - alpha) * EMA_old
-                self.alpha * score + (1.0 - self.alpha) * state.ema
-            }
-            None => {
-                // First value: EMA = score
-                score
-            }
-        };
-
-        let new_count = current_state.map(|s| s.count).unwrap_or(0) + 1;
-
-        // Create new state
-        let new_state = EmaState {
-            ema: new_ema,
-            count: new_count,
-            last_updated: Utc::now(),
-        };
+        let new_state = self.update(score, current_state);
+        let new_ema = new_state.ema;
+        let new_count = new_state.count;
 
         // Extract threshold and operator from condition
         let value_str = json_value_as_str(&condition.value);
@@ -169,14 +171,11 @@ impl EmaEvaluator {
         let operator = condition.operator.as_str();
 
         // Evaluate condition
-        let matches = match operator {
-            "<" => new_ema < threshold,
-            ">" => new_ema > threshold,
-            "<=" => new_ema <= threshold,
-            ">=" => new_ema >= threshold,
-            "=" | "==" => (new_ema - threshold).abs() < f64::EPSILON,
-            "!=" | "<>" => (new_ema - threshold).abs() >= f64::EPSILON,
-            _ => anyhow::bail!("Invalid operator: {}", operator),
+        let matches = match Comparison::parse(operator) {
+            Some(Comparison::Equal) => (new_ema - threshold).abs() < f64::EPSILON,
+            Some(Comparison::NotEqual) => (new_ema - threshold).abs() >= f64::EPSILON,
+            Some(comparison) => comparison.compare(new_ema, threshold),
+            None => anyhow::bail!("Invalid operator: {}", operator),
         };
 
         tracing::debug!(
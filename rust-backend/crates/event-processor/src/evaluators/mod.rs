@@ -2,10 +2,13 @@
 //!
 //! This module provides evaluators for stateful trigger conditions:
 //! - EMA (Exponential Moving Average): Smooth score trends
+//! - EMA crossover: Fast EMA crossing a slow EMA
 //! - Rate Counter: Count events in sliding time window
 
+pub mod crossover;
 pub mod ema;
 pub mod rate_counter;
 
+pub use crossover::{CrossDirection, Crossing, CrossoverEvaluator, CrossoverState};
 pub use ema::{EmaEvaluator, EmaState};
 pub use rate_counter::{RateCounterEvaluator, RateCounterState};
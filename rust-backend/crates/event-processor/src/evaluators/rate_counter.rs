@@ -27,6 +27,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use shared::models::{Event, TriggerCondition};
+use shared::Comparison;
 
 /// Extract string value from JSON for parsing
 fn json_value_as_str(value: &serde_json::Value) -> String {
@@ -187,14 +188,9 @@ impl RateCounterEvaluator {
         let operator = condition.operator.as_str();
 
         // Evaluate condition
-        let matches = match operator {
-            ">" => state.count > threshold,
-            ">=" => state.count >= threshold,
-            "<" => state.count < threshold,
-            "<=" => state.count <= threshold,
-            "=" | "==" => state.count == threshold,
-            "!=" | "<>" => state.count != threshold,
-            _ => anyhow::bail!("Invalid operator: {}", operator),
+        let matches = match Comparison::parse(operator) {
+            Some(comparison) => comparison.compare(state.count, threshold),
+            None => anyhow::bail!("Invalid operator: {}", operator),
         };
 
         tracing::debug!(
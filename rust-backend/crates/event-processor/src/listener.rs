@@ -12,30 +12,81 @@
 //! - 30-second timeout per event processing
 //! - Panic detection and recovery
 //! - Metrics for task failures/panics
+//!
+//! # Payloads
+//!
+//! NOTIFY payloads are capped at 8000 bytes, so they are treated purely as a
+//! pointer: only the event ID is read from them and the full event is always
+//! fetched from the database. A notification may arrive before its event is
+//! visible through `ponder_events`; such events are retried briefly and
+//! otherwise left to the polling fallback.
+//!
+//! Events pushed to the API gateway are inserted into `events`, whose insert
+//! trigger sends the same notification; they are read from `events` when
+//! `ponder_events` has no such ID.
+//!
+//! # Reconnection
+//!
+//! When the connection behind the listener drops (Postgres restart, failover,
+//! idle connection reaper), the listener reconnects and re-subscribes with
+//! exponential backoff (1s doubling to 60s), retrying for as long as the
+//! errors are transient. Only errors a retry cannot fix (e.g. rejected
+//! credentials) end the task. Notifications sent while disconnected are lost;
+//! the polling fallback processes those events.
+//!
+//! To check this by hand, start the processor and terminate its listener
+//! connection:
+//!
+//! ```sql
+//! SELECT pg_terminate_backend(pid) FROM pg_stat_activity
+//! WHERE query LIKE 'LISTEN%new_event%';
+//! ```
+//!
+//! The log shows `LISTENER_RECONNECT` (or `LISTENER_TRANSIENT_ERROR`) followed
+//! by "Listener reconnected", and `event_processor_listener_reconnects` goes
+//! up. `test_listener_reconnects_after_connection_is_killed` runs the same
+//! check against `DATABASE_URL`.
 
 use anyhow::{Context, Result};
-use event_processor::processor::process_event;
+use event_processor::processor::{
+    process_event, record_processed, EventNotVisible, ProcessingPath,
+};
 use event_processor::queue::RedisJobQueue;
 use event_processor::state_manager::TriggerStateManager;
-use redis::aio::MultiplexedConnection;
 use serde::Deserialize;
+use shared::error::is_transient_db_error;
 use shared::DbPool;
 use sqlx::postgres::PgListener;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
 /// Event notification payload from PostgreSQL NOTIFY
+///
+/// Only the ID is used; any other fields the trigger function sends are
+/// ignored, since the event itself is read from the database.
 #[derive(Debug, Deserialize)]
 struct EventNotification {
     event_id: String,
-    chain_id: i32,
-    block_number: i64,
-    event_type: String,
-    registry: String,
 }
 
+/// Longest raw (non-JSON) payload accepted as an event ID
+const MAX_EVENT_ID_LEN: usize = 255;
+
+/// Attempts to process an event that is not visible in the database yet
+const NOT_VISIBLE_MAX_ATTEMPTS: u32 = 4;
+
+/// Wait before the first not-visible retry (doubles for each further retry)
+const NOT_VISIBLE_INITIAL_DELAY: Duration = Duration::from_millis(50);
+
+/// Channel the event insert triggers notify on
+const CHANNEL: &str = "new_event";
+
+/// Longest wait between listener reconnect attempts
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
 /// Maximum concurrent event processing tasks
 /// Prevents unbounded task spawning during NOTIFY floods
 const MAX_CONCURRENT_EVENTS: usize = 100;
@@ -49,7 +100,7 @@ const EVENT_PROCESSING_TIMEOUT: Duration = Duration::from_secs(30);
 /// # Arguments
 ///
 /// * `db_pool` - Database connection pool
-/// * `redis_conn` - Redis connection for job queueing
+/// * `job_queue` - Queue for action jobs
 ///
 /// # Critical Fix
 ///
@@ -59,22 +110,16 @@ const EVENT_PROCESSING_TIMEOUT: Duration = Duration::from_secs(30);
 /// - JoinSet tracks all spawned tasks (detects panics)
 /// - 30-second timeout per event (prevents hangs)
 /// - Metrics for task failures and panics
-pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection) -> Result<()> {
-    // Create PostgreSQL listener
-    let mut listener = PgListener::connect_with(&db_pool)
-        .await
-        .context("Failed to create PostgreSQL listener")?;
-
-    // Listen to the 'new_event' channel
-    listener
-        .listen("new_event")
+/// - Reconnects on connection loss (see the module docs)
+pub async fn start_listening(db_pool: DbPool, job_queue: RedisJobQueue) -> Result<()> {
+    let mut listener = connect_listener(&db_pool)
         .await
-        .context("Failed to listen to 'new_event' channel")?;
-
-    tracing::info!("Listening for PostgreSQL NOTIFY events on channel 'new_event'");
+        .with_context(|| format!("Failed to listen to '{}' channel", CHANNEL))?;
 
-    // Create job queue
-    let job_queue = RedisJobQueue::new(redis_conn);
+    tracing::info!(
+        "Listening for PostgreSQL NOTIFY events on channel '{}'",
+        CHANNEL
+    );
 
     // CRITICAL FIX: Bounded concurrency with semaphore
     let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_EVENTS));
@@ -82,10 +127,6 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
     // CRITICAL FIX: Task tracking with JoinSet
     let mut tasks: JoinSet<Result<String>> = JoinSet::new();
 
-    // Track consecutive errors for exponential backoff
-    let mut consecutive_errors = 0u32;
-    const MAX_CONSECUTIVE_ERRORS: u32 = 10;
-
     // Metrics tracking
     let mut total_tasks_spawned = 0u64;
     let mut total_tasks_succeeded = 0u64;
@@ -96,37 +137,27 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
     loop {
         tokio::select! {
             // Handle incoming NOTIFY events
-            notification_result = listener.recv() => {
+            notification_result = listener.try_recv() => {
                 match notification_result {
-                    Ok(notification) => {
-                        // Reset error counter on success
-                        consecutive_errors = 0;
-
+                    Ok(None) => {
+                        tracing::warn!(
+                            error_id = "LISTENER_RECONNECT",
+                            "Listener connection lost, reconnecting"
+                        );
+                        listener = reconnect_listener(&db_pool).await?;
+                    }
+                    Ok(Some(notification)) => {
                         let payload = notification.payload();
-
-                        // Try to parse the enhanced JSON payload, fall back to raw event_id
-                        let event_id = match serde_json::from_str::<EventNotification>(payload) {
-                            Ok(event_notif) => {
-                                tracing::debug!(
-                                    "Received event notification: {} (chain_id={}, block={}, type={}, registry={})",
-                                    event_notif.event_id,
-                                    event_notif.chain_id,
-                                    event_notif.block_number,
-                                    event_notif.event_type,
-                                    event_notif.registry
-                                );
-                                event_notif.event_id
-                            }
-                            Err(parse_err) => {
-                                // Fall back to treating payload as raw event_id for backward compatibility
-                                tracing::warn!(
-                                    error = %parse_err,
-                                    payload = %payload,
-                                    "Failed to parse EventNotification JSON, treating as raw event_id"
-                                );
-                                payload.to_string()
-                            }
+                        let Some(event_id) = parse_notification(payload) else {
+                            // The polling fallback will pick the event up
+                            tracing::warn!(
+                                payload_len = payload.len(),
+                                error_id = "NOTIFY_PAYLOAD_INVALID",
+                                "Ignoring notification without a usable event ID"
+                            );
+                            continue;
                         };
+                        tracing::debug!(event_id = %event_id, "Received event notification");
 
                         // CRITICAL FIX: Acquire semaphore permit (blocks if at max concurrency)
                         let permit = semaphore.clone().acquire_owned().await.unwrap();
@@ -147,11 +178,16 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
                             // CRITICAL FIX: Wrap with timeout
                             let result = tokio::time::timeout(
                                 EVENT_PROCESSING_TIMEOUT,
-                                process_event(&event_id_clone, &db_pool, &job_queue, &state_manager)
+                                process_when_visible(&event_id_clone, || {
+                                    process_event(&event_id_clone, &db_pool, &job_queue, &state_manager)
+                                })
                             ).await;
 
                             match result {
-                                Ok(Ok(())) => {
+                                Ok(Ok(processed)) => {
+                                    if processed {
+                                        record_processed(ProcessingPath::Notify);
+                                    }
                                     tracing::debug!(event_id = %event_id_clone, "Event processed successfully");
                                     Ok(event_id_clone)
                                 }
@@ -191,16 +227,8 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
                         }
                     }
                     Err(e) => {
-                        consecutive_errors += 1;
-
                         // FIX 3.5: Distinguish fatal vs transient errors (Medium Priority)
-                        let error_str = e.to_string().to_lowercase();
-                        let is_fatal = error_str.contains("authentication")
-                            || error_str.contains("permission denied")
-                            || error_str.contains("database does not exist")
-                            || error_str.contains("relation does not exist");
-
-                        if is_fatal {
+                        if !is_transient_db_error(&e) {
                             tracing::error!(
                                 error = %e,
                                 error_id = "LISTENER_FATAL_ERROR",
@@ -209,37 +237,15 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
                             anyhow::bail!("Fatal listener error (unrecoverable): {}", e);
                         }
 
-                        // Calculate exponential backoff: min(2^errors, 60) seconds
-                        let backoff_secs = std::cmp::min(2u64.pow(consecutive_errors), 60);
-
                         tracing::error!(
                             error = %e,
-                            consecutive_errors = consecutive_errors,
-                            backoff_secs = backoff_secs,
                             error_id = "LISTENER_TRANSIENT_ERROR",
-                            "Transient error receiving notification, will retry with backoff"
+                            "Transient error receiving notification, reconnecting"
                         );
-
-                        // After too many consecutive errors, exit to trigger app restart
-                        if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                            tracing::error!(
-                                consecutive_errors = consecutive_errors,
-                                max_allowed = MAX_CONSECUTIVE_ERRORS,
-                                error_id = "LISTENER_MAX_ERRORS_EXCEEDED",
-                                "Listener exceeded maximum consecutive errors, exiting for restart"
-                            );
-                            anyhow::bail!(
-                                "Listener exceeded {} consecutive errors, exiting for restart",
-                                MAX_CONSECUTIVE_ERRORS
-                            );
-                        }
-
-                        // Emit metric for transient errors
                         #[cfg(feature = "metrics")]
                         metrics::counter!("event_processor.listener_transient_errors").increment(1);
 
-                        // Exponential backoff before retry
-                        tokio::time::sleep(tokio::time::Duration::from_secs(backoff_secs)).await;
+                        listener = reconnect_listener(&db_pool).await?;
                     }
                 }
             }
@@ -292,6 +298,122 @@ pub async fn start_listening(db_pool: DbPool, redis_conn: MultiplexedConnection)
     }
 }
 
+/// Open a listener connection subscribed to [`CHANNEL`]
+async fn connect_listener(db_pool: &DbPool) -> std::result::Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect_with(db_pool).await?;
+    listener.listen(CHANNEL).await?;
+    Ok(listener)
+}
+
+/// Wait before reconnect attempt `attempt` (1-based): 1s, 2s, 4s, ... up to 60s
+fn reconnect_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(6)).min(MAX_RECONNECT_BACKOFF)
+}
+
+/// Replace a lost listener connection, backing off between attempts
+///
+/// Retries transient errors indefinitely; fails only on errors a retry cannot
+/// fix, such as rejected credentials.
+async fn reconnect_listener(db_pool: &DbPool) -> Result<PgListener> {
+    let mut attempt = 1;
+
+    loop {
+        let backoff = reconnect_backoff(attempt);
+        tokio::time::sleep(backoff).await;
+
+        match connect_listener(db_pool).await {
+            Ok(listener) => {
+                tracing::info!(attempts = attempt, "Listener reconnected to '{}'", CHANNEL);
+                #[cfg(feature = "metrics")]
+                metrics::counter!("event_processor.listener_reconnects").increment(1);
+                return Ok(listener);
+            }
+            Err(e) if is_transient_db_error(&e) => {
+                tracing::warn!(
+                    error = %e,
+                    attempt = attempt,
+                    backoff_secs = backoff.as_secs(),
+                    error_id = "LISTENER_RECONNECT_FAILED",
+                    "Listener reconnect failed, retrying"
+                );
+                attempt += 1;
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    error_id = "LISTENER_FATAL_ERROR",
+                    "Listener cannot reconnect - exiting for restart"
+                );
+                return Err(e).context("Listener reconnect failed (unrecoverable)");
+            }
+        }
+    }
+}
+
+/// Extract the event ID from a NOTIFY payload
+///
+/// Accepts the JSON payload sent by the Ponder trigger (`{"event_id": ...}`)
+/// and the raw ID sent by the legacy `events` trigger. Returns `None` for a
+/// malformed or truncated payload.
+fn parse_notification(payload: &str) -> Option<String> {
+    let payload = payload.trim();
+    let event_id = if payload.starts_with('{') {
+        serde_json::from_str::<EventNotification>(payload)
+            .ok()?
+            .event_id
+    } else {
+        payload.to_string()
+    };
+
+    if event_id.is_empty() || event_id.len() > MAX_EVENT_ID_LEN {
+        return None;
+    }
+    Some(event_id)
+}
+
+/// Run `process`, retrying briefly while the event is not visible yet
+///
+/// Returns `Ok(true)` once processed. Gives up with `Ok(false)` after
+/// [`NOT_VISIBLE_MAX_ATTEMPTS`] attempts: the event stays unprocessed, so the
+/// polling fallback handles it once it is visible. Other errors are returned
+/// immediately.
+async fn process_when_visible<F, Fut>(event_id: &str, mut process: F) -> Result<bool>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut delay = NOT_VISIBLE_INITIAL_DELAY;
+    let mut attempt = 1;
+
+    loop {
+        match process().await {
+            Err(e) if e.downcast_ref::<EventNotVisible>().is_some() => {
+                if attempt >= NOT_VISIBLE_MAX_ATTEMPTS {
+                    tracing::warn!(
+                        event_id = %event_id,
+                        attempts = attempt,
+                        "Event not visible yet, leaving it to the polling fallback"
+                    );
+                    #[cfg(feature = "metrics")]
+                    metrics::counter!("event_processor.listener_events_not_visible").increment(1);
+                    return Ok(false);
+                }
+
+                tracing::debug!(
+                    event_id = %event_id,
+                    attempt = attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "Event not visible yet, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result.map(|()| true),
+        }
+    }
+}
+
 // Note: process_event is now defined in processor.rs module
 // This module only handles the NOTIFY/LISTEN mechanism
 
@@ -338,13 +460,141 @@ mod tests {
             "registry": "reputation"
         }"#;
 
-        let notif: EventNotification = serde_json::from_str(json).unwrap();
+        assert_eq!(parse_notification(json).as_deref(), Some("test-123"));
+        assert_eq!(
+            parse_notification(r#"{"event_id":"test-456"}"#).as_deref(),
+            Some("test-456")
+        );
+
+        // Legacy trigger sends the raw ID
+        assert_eq!(
+            parse_notification("test-789\n").as_deref(),
+            Some("test-789")
+        );
+    }
+
+    #[test]
+    fn test_notification_only_uses_event_id() {
+        // Event data in the payload is ignored, however large
+        let payload = serde_json::json!({
+            "event_id": "test-123",
+            "response_uri": "x".repeat(7000),
+        })
+        .to_string();
+        assert_eq!(parse_notification(&payload).as_deref(), Some("test-123"));
+    }
 
-        assert_eq!(notif.event_id, "test-123");
-        assert_eq!(notif.chain_id, 84532);
-        assert_eq!(notif.block_number, 1000);
-        assert_eq!(notif.event_type, "NewFeedback");
-        assert_eq!(notif.registry, "reputation");
+    #[test]
+    fn test_invalid_notification_is_ignored() {
+        // Truncated JSON must not be mistaken for a raw ID
+        assert_eq!(parse_notification(r#"{"event_id": "test-1"#), None);
+        assert_eq!(parse_notification(r#"{"chain_id": 1}"#), None);
+        assert_eq!(parse_notification(r#"{"event_id": ""}"#), None);
+        assert_eq!(parse_notification(""), None);
+        assert_eq!(parse_notification(&"a".repeat(MAX_EVENT_ID_LEN + 1)), None);
+    }
+
+    fn not_visible() -> anyhow::Error {
+        anyhow::Error::new(EventNotVisible {
+            event_id: "test-1".to_string(),
+        })
+        .context("Failed to fetch event test-1")
+    }
+
+    #[tokio::test]
+    async fn test_not_visible_event_is_retried() {
+        let attempts = Mutex::new(0);
+        let result = process_when_visible("test-1", || async {
+            let mut attempts = attempts.lock().unwrap();
+            *attempts += 1;
+            if *attempts < 3 {
+                Err(not_visible())
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+
+        assert!(result.unwrap());
+        assert_eq!(*attempts.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_not_visible_event_is_left_to_polling_fallback() {
+        let attempts = Mutex::new(0);
+        let result = process_when_visible("test-1", || async {
+            *attempts.lock().unwrap() += 1;
+            Err(not_visible())
+        })
+        .await;
+
+        assert!(!result.unwrap());
+        assert_eq!(*attempts.lock().unwrap(), NOT_VISIBLE_MAX_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_not_retried() {
+        let attempts = Mutex::new(0);
+        let result = process_when_visible("test-1", || async {
+            *attempts.lock().unwrap() += 1;
+            anyhow::bail!("database unavailable")
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_reconnect_backoff_doubles_up_to_cap() {
+        let secs: Vec<u64> = (1..=8).map(|a| reconnect_backoff(a).as_secs()).collect();
+        assert_eq!(secs, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(reconnect_backoff(u32::MAX), MAX_RECONNECT_BACKOFF);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires DATABASE_URL (integration test)
+    async fn test_listener_reconnects_after_connection_is_killed() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+        let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+
+        let mut listener = connect_listener(&pool).await.unwrap();
+        sqlx::query(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+             WHERE query LIKE 'LISTEN%new_event%' AND pid <> pg_backend_pid()",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // The lost connection is reported as something to reconnect after
+        let lost = tokio::time::timeout(Duration::from_secs(5), listener.try_recv())
+            .await
+            .expect("Connection loss was not detected");
+        // (as a closed connection or as the server's admin shutdown error)
+        match lost {
+            Ok(None) => {}
+            Err(e) => assert!(is_transient_db_error(&e), "{}", e),
+            Ok(Some(_)) => panic!("Unexpected notification"),
+        }
+
+        let mut listener = reconnect_listener(&pool).await.unwrap();
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(CHANNEL)
+            .bind(r#"{"event_id":"reconnect-test"}"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let notification = tokio::time::timeout(Duration::from_secs(5), listener.recv())
+            .await
+            .expect("No notification after reconnect")
+            .unwrap();
+        assert_eq!(
+            parse_notification(notification.payload()).as_deref(),
+            Some("reconnect-test")
+        );
     }
 
     #[tokio::test]
@@ -6,23 +6,54 @@
 //! 2. FALLBACK: Polling → discover unprocessed → process_event (1% of events)
 
 use anyhow::{Context, Result};
-use event_processor::{PollingFallback, TriggerStateManager};
+use event_processor::webhooks::DEFAULT_DISPATCH_INTERVAL_SECS;
+use event_processor::{
+    PollingFallback, TriggerStateManager, WebhookDispatchConfig, WebhookDispatcher,
+};
+use shared::diagnostics::{self, DiagnosticsReport};
 use shared::{db, Config};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
+use tokio_util::sync::CancellationToken;
 
 // These modules are only used by listener which is specific to the binary
 mod listener;
+#[cfg(feature = "metrics")]
+mod metrics;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing
     shared::init_tracing();
 
+    if diagnostics::is_check_mode() {
+        let mut report = DiagnosticsReport::new("event-processor");
+        diagnostics::check_core(&mut report).await;
+        return report.finish();
+    }
+
     tracing::info!("Starting Event Processor...");
 
+    // Initialize Prometheus metrics exporter (METRICS_ADDR, default 0.0.0.0:9091)
+    #[cfg(feature = "metrics")]
+    if metrics::init_metrics_default() {
+        metrics::spawn_stall_gauge();
+    }
+
     // Load configuration
-    let config = Config::from_env().context("Failed to load configuration")?;
+    let config = Config::load().context("Failed to load configuration")?;
+    let config_summary = config
+        .validate_and_summarize()
+        .context("Invalid configuration")?;
+    tracing::info!("Effective configuration: {}", config_summary);
+
+    // Namespace Redis keys before anything builds one
+    shared::redis::init_key_prefix(config.redis.key_prefix.as_deref())
+        .context("Invalid Redis key prefix")?;
+
+    // Actions the organization cannot pay for are not enqueued
+    event_processor::credits::set_action_costs(config.action_costs);
 
     // Create database connection pool
     let db_pool = db::create_pool(&config.database)
@@ -49,7 +80,8 @@ async fn main() -> Result<()> {
     let state_manager = Arc::new(TriggerStateManager::new(db_pool.clone()));
 
     // Create job queue for action enqueueing (use lib version to match PollingFallback)
-    let job_queue = event_processor::queue::RedisJobQueue::new(redis_conn.clone());
+    let job_queue =
+        event_processor::queue::RedisJobQueue::new(redis_conn, config.action_queue.clone());
 
     // Start polling fallback (runs every 60 seconds)
     let polling_fallback = Arc::new(PollingFallback::new(
@@ -66,31 +98,26 @@ async fn main() -> Result<()> {
     tracing::info!("Started polling fallback (60s interval)");
 
     // FIX 4.2: Start automatic state cleanup (Production Readiness)
-    // Cleans up trigger state older than 30 days every 24 hours
+    // Cleans up trigger state older than STATE_RETENTION_DAYS every
+    // STATE_CLEANUP_INTERVAL_SECS, starting shortly after startup
+    let cleanup_config = config.state_cleanup.clone();
     let cleanup_handle = tokio::spawn({
         let db_pool = db_pool.clone();
         async move {
-            const CLEANUP_INTERVAL_SECS: u64 = 86400; // 24 hours
-            const RETENTION_DAYS: i32 = 30;
+            // Let startup settle before the first cleanup
+            const INITIAL_DELAY_SECS: u64 = 60;
 
-            tracing::info!(
-                "Starting automatic state cleanup (interval: {}h, retention: {}d)",
-                CLEANUP_INTERVAL_SECS / 3600,
-                RETENTION_DAYS
-            );
+            let retention_days = cleanup_config.retention_days;
+            let state_manager = TriggerStateManager::new(db_pool);
+            tokio::time::sleep(Duration::from_secs(INITIAL_DELAY_SECS)).await;
 
             loop {
-                // Wait 24 hours before first cleanup
-                tokio::time::sleep(tokio::time::Duration::from_secs(CLEANUP_INTERVAL_SECS)).await;
-
-                // Run cleanup
-                let state_manager = TriggerStateManager::new(db_pool.clone());
-                match state_manager.cleanup_expired(RETENTION_DAYS).await {
+                match state_manager.cleanup_expired(retention_days).await {
                     Ok(deleted) => {
                         if deleted > 0 {
                             tracing::info!(
                                 deleted = deleted,
-                                retention_days = RETENTION_DAYS,
+                                retention_days = retention_days,
                                 "State cleanup completed successfully"
                             );
                         } else {
@@ -101,22 +128,39 @@ async fn main() -> Result<()> {
                         tracing::error!(
                             error = %e,
                             error_id = "STATE_CLEANUP_FAILED",
-                            "State cleanup failed, will retry in 24 hours"
+                            retry_in_secs = cleanup_config.interval_secs,
+                            "State cleanup failed, will retry next interval"
                         );
                         // Don't exit - continue with next iteration
                     }
                 }
+
+                tokio::time::sleep(cleanup_config.interval()).await;
             }
         }
     });
 
-    tracing::info!("Started automatic state cleanup (24h interval, 30d retention)");
+    tracing::info!(
+        "Started automatic state cleanup (interval: {}s, retention: {}d)",
+        config.state_cleanup.interval_secs,
+        config.state_cleanup.retention_days
+    );
+
+    // Deliver trigger fires to organization webhooks
+    let webhook_token = CancellationToken::new();
+    let webhook_dispatcher =
+        WebhookDispatcher::new(db_pool.clone(), WebhookDispatchConfig::from_env())
+            .context("Failed to create webhook dispatcher")?;
+    tokio::spawn(webhook_dispatcher.run(
+        Duration::from_secs(DEFAULT_DISPATCH_INTERVAL_SECS),
+        webhook_token.clone(),
+    ));
 
     // Start listening to PostgreSQL NOTIFY (primary path)
     let listener_handle = tokio::spawn({
         let db_pool = db_pool.clone();
-        let redis_conn = redis_conn.clone();
-        async move { listener::start_listening(db_pool, redis_conn).await }
+        let job_queue = job_queue.clone();
+        async move { listener::start_listening(db_pool, job_queue).await }
     });
 
     tracing::info!("Started PostgreSQL NOTIFY listener (primary path)");
@@ -126,6 +170,7 @@ async fn main() -> Result<()> {
         result = signal::ctrl_c() => {
             result.context("Failed to listen for shutdown signal")?;
             tracing::info!("Shutdown signal received, stopping Event Processor...");
+            webhook_token.cancel();
         }
         result = listener_handle => {
             match result {
@@ -0,0 +1,78 @@
+//! Prometheus exporter for the event processor
+//!
+//! Serves every counter the processor records for scraping, among them:
+//!
+//! - `trigger_evaluations_total`, `trigger_fires_total`
+//! - `event_processor_events_processed{path="notify"|"polling"}`
+//! - `event_processor_listener_reconnects`
+//! - `event_processor_state_cleanup_deleted`
+//! - `event_processor_seconds_since_last_event`: alert on this to catch
+//!   stalls (counts from startup until the first event is processed)
+
+use event_processor::processor::seconds_since_last_processed;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Default scrape address (action-workers uses 9090)
+const DEFAULT_ADDR: &str = "0.0.0.0:9091";
+
+/// How often the seconds-since-last-event gauge is refreshed
+const STALL_GAUGE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Singleton to ensure metrics are only initialized once
+static METRICS_INITIALIZED: OnceLock<()> = OnceLock::new();
+
+/// Initialize the Prometheus metrics exporter listening on `addr`
+///
+/// Returns `true` if the exporter was installed, `false` if installation
+/// failed or was already done.
+pub fn init_metrics(addr: SocketAddr) -> bool {
+    let mut success = false;
+    METRICS_INITIALIZED.get_or_init(|| {
+        match PrometheusBuilder::new().with_http_listener(addr).install() {
+            Ok(()) => {
+                tracing::info!(addr = %addr, "Prometheus metrics exporter initialized");
+                success = true;
+            }
+            Err(e) => {
+                // Metrics are optional: keep processing events without them
+                tracing::error!(
+                    addr = %addr,
+                    error = %e,
+                    "Failed to install Prometheus exporter - metrics will be unavailable"
+                );
+            }
+        }
+    });
+    success
+}
+
+/// Initialize metrics on `METRICS_ADDR`, or 0.0.0.0:9091 when unset
+pub fn init_metrics_default() -> bool {
+    let addr = std::env::var("METRICS_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    match addr.parse::<SocketAddr>() {
+        Ok(addr) => init_metrics(addr),
+        Err(e) => {
+            tracing::error!(addr = %addr, error = %e, "Invalid METRICS_ADDR, metrics disabled");
+            false
+        }
+    }
+}
+
+/// Keep `event_processor.seconds_since_last_event` up to date
+///
+/// Must be called from within a Tokio runtime.
+pub fn spawn_stall_gauge() {
+    let started_at = chrono::Utc::now().timestamp();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STALL_GAUGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = chrono::Utc::now().timestamp();
+            let seconds = seconds_since_last_processed(now).unwrap_or(now - started_at);
+            metrics::gauge!("event_processor.seconds_since_last_event").set(seconds as f64);
+        }
+    });
+}
@@ -22,7 +22,7 @@
 //! 3. **Observability**: Metrics show how often fallback is used
 //! 4. **Low overhead**: Polling interval is 60 seconds, minimal DB impact
 
-use crate::processor::process_event;
+use crate::processor::{process_event, record_processed, ProcessingPath};
 use crate::queue::RedisJobQueue;
 use crate::state_manager::TriggerStateManager;
 use anyhow::{Context, Result};
@@ -104,7 +104,7 @@ impl PollingFallback {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// let fallback = PollingFallback::new(db_pool, redis_conn, state_manager);
     /// tokio::spawn(async move {
     ///     if let Err(e) = fallback.start().await {
@@ -274,6 +274,7 @@ impl PollingFallback {
             {
                 Ok(_) => {
                     succeeded_count += 1;
+                    record_processed(ProcessingPath::Polling);
                 }
                 Err(e) => {
                     failed_count += 1;
@@ -7,15 +7,87 @@
 use anyhow::{Context, Result};
 use serde_json::json;
 use shared::models::{Event, Trigger, TriggerAction, TriggerCondition};
-use shared::{ActionJob, ActionType, DbPool};
+use shared::{ActionJob, ActionType, DbPool, NotificationPreferences, SystemClock};
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::LazyLock;
 use std::time::Instant;
 
 use crate::circuit_breaker::CircuitBreaker;
+use crate::credits::CreditBudget;
 use crate::queue::JobQueue;
 use crate::state_manager::TriggerStateManager;
 use crate::trigger_engine;
+use crate::webhooks;
+
+/// The event an ID points to is not in `ponder_events` or `events` (yet)
+///
+/// NOTIFY payloads only carry the event ID, so the event is always read back
+/// from the database. A notification can arrive before the row is visible
+/// through the view (e.g. while Ponder switches namespaces); callers may
+/// retry, and the polling fallback picks the event up later either way.
+#[derive(Debug, thiserror::Error)]
+#[error("Event {event_id} not found in ponder_events")]
+pub struct EventNotVisible {
+    pub event_id: String,
+}
+
+/// How an event reached [`process_event`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessingPath {
+    /// PostgreSQL NOTIFY listener (primary path)
+    Notify,
+    /// Polling fallback
+    Polling,
+}
+
+impl ProcessingPath {
+    /// Label value for metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Notify => "notify",
+            Self::Polling => "polling",
+        }
+    }
+}
+
+/// Whether REST actions only run once their endpoint passed the webhook
+/// handshake (`WEBHOOK_VERIFICATION_MODE=enforce`, shared with the API gateway)
+static ENFORCE_WEBHOOK_VERIFICATION: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("WEBHOOK_VERIFICATION_MODE")
+        .map(|mode| enforces_webhook_verification(&mode))
+        .unwrap_or(false)
+});
+
+/// Whether a `WEBHOOK_VERIFICATION_MODE` value is `enforce` (or its alias)
+fn enforces_webhook_verification(mode: &str) -> bool {
+    matches!(mode.to_lowercase().as_str(), "enforce" | "block")
+}
+
+/// Unix time of the last event processed through either path (0: none yet)
+static LAST_PROCESSED_AT: AtomicI64 = AtomicI64::new(0);
+
+/// Record an event processed through `path`
+///
+/// Counts `event_processor.events_processed{path}` and remembers the time
+/// for [`seconds_since_last_processed`].
+pub fn record_processed(path: ProcessingPath) {
+    LAST_PROCESSED_AT.store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+
+    #[cfg(feature = "metrics")]
+    metrics::counter!("event_processor.events_processed", "path" => path.as_str()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = path;
+}
+
+/// Seconds between the last processed event and `now` (Unix time), if any
+pub fn seconds_since_last_processed(now: i64) -> Option<i64> {
+    match LAST_PROCESSED_AT.load(Ordering::Relaxed) {
+        0 => None,
+        last => Some((now - last).max(0)),
+    }
+}
 
 /// Get hostname for processor instance tracking
 fn get_hostname() -> String {
@@ -47,7 +119,7 @@ fn get_hostname() -> String {
 /// - `tag1`, `tag2` - Tags (reputation registry)
 /// - `validator_address` - Validator address (validation registry)
 /// - `response` - Validation response code (validation registry)
-fn event_to_template_data(event: &Event) -> serde_json::Value {
+pub(crate) fn event_to_template_data(event: &Event) -> serde_json::Value {
     json!({
         // Core event fields (always present)
         "event_id": event.id,
@@ -90,8 +162,10 @@ fn event_to_template_data(event: &Event) -> serde_json::Value {
 ///
 /// This function ensures that each event is processed exactly once by:
 /// 1. Checking if the event has already been processed
-/// 2. Processing the event (matching triggers, enqueueing actions)
-/// 3. Marking the event as processed in an atomic transaction
+/// 2. Claiming the event, so a concurrent call for the same ID (NOTIFY and
+///    polling overlapping, or another instance) skips it
+/// 3. Processing the event (matching triggers, enqueueing actions)
+/// 4. Marking the event as processed and releasing the claim
 ///
 /// # Arguments
 ///
@@ -100,15 +174,22 @@ fn event_to_template_data(event: &Event) -> serde_json::Value {
 /// * `job_queue` - Job queue for enqueueing actions
 /// * `state_manager` - State manager for stateful triggers
 ///
+/// # Agent follows
+///
+/// The triggers of an agent follow respect the follow's notification
+/// preferences: an event of a type the follow is not notified about does not
+/// fire them, and only actions on allowed channels are enqueued.
+///
 /// # Idempotency
 ///
 /// This function can be called multiple times with the same event_id without
 /// side effects. The first call will process the event, subsequent calls will
-/// be no-ops (returning Ok immediately).
+/// be no-ops (returning Ok immediately). This holds for concurrent calls too:
+/// while one call holds the claim, the others return Ok without firing.
 ///
 /// # Example
 ///
-/// ```rust,no_run
+/// ```rust,ignore
 /// use event_processor::process_event;
 ///
 /// // This will process the event
@@ -141,10 +222,46 @@ pub async fn process_event<Q: JobQueue>(
         return Ok(());
     }
 
+    // Both paths (and other instances) may reach this point for the same
+    // event; only the one holding the claim evaluates triggers
+    if !claim_event(event_id, db_pool).await? {
+        tracing::debug!(
+            event_id = %event_id,
+            "Event claimed by a concurrent processor, skipping"
+        );
+        return Ok(());
+    }
+
+    let result = process_claimed_event(event_id, db_pool, job_queue, state_manager, start).await;
+
+    // Once processed, processed_events keeps it from being claimed again;
+    // after a failure, releasing lets the next attempt retry right away
+    if let Err(e) = release_claim(event_id, db_pool).await {
+        tracing::warn!(
+            event_id = %event_id,
+            error = %e,
+            "Failed to release event claim, it will expire"
+        );
+    }
+
+    result
+}
+
+/// Steps 2-6 of [`process_event`], for an event this processor has claimed
+async fn process_claimed_event<Q: JobQueue>(
+    event_id: &str,
+    db_pool: &DbPool,
+    job_queue: &Q,
+    state_manager: &TriggerStateManager,
+    start: Instant,
+) -> Result<()> {
     // STEP 2: Fetch event from database
     let event = fetch_event(event_id, db_pool)
         .await
-        .context(format!("Failed to fetch event {}", event_id))?;
+        .context(format!("Failed to fetch event {}", event_id))?
+        .ok_or_else(|| EventNotVisible {
+            event_id: event_id.to_string(),
+        })?;
 
     tracing::info!(
         "Processing event: {} (chain_id={}, registry={}, event_type={})",
@@ -205,12 +322,40 @@ pub async fn process_event<Q: JobQueue>(
             trigger_ids.len()
         ))?;
 
+    let preferences_map = fetch_follow_preferences(&trigger_ids, db_pool)
+        .await
+        .context(format!(
+            "Failed to load follow notification preferences for event {}",
+            event_id
+        ))?;
     tracing::debug!(
         "Batch loaded conditions and actions for {} triggers (3 queries total)",
         triggers.len()
     );
 
+    // Actions the organization cannot pay for are not enqueued; if balances
+    // cannot be read, enqueue anyway (workers check again before executing)
+    let mut organization_ids: Vec<String> =
+        triggers.iter().map(|t| t.organization_id.clone()).collect();
+    organization_ids.sort();
+    organization_ids.dedup();
+    let mut credit_budget = match CreditBudget::load(&organization_ids, db_pool).await {
+        Ok(budget) => budget,
+        Err(e) => {
+            tracing::error!(
+                event_id = %event_id,
+                error = %e,
+                error_id = "CREDIT_BALANCE_CHECK_FAILED",
+                "Failed to load credit balances, enqueuing actions unchecked"
+            );
+            CreditBudget::unlimited()
+        }
+    };
+
     // STEP 5: Evaluate each trigger
+    // All jobs created from this event share one correlation ID so worker logs
+    // can be traced back to this processing run
+    let correlation_id = uuid::Uuid::new_v4().to_string();
     let mut matched_count = 0;
     let mut actions_enqueued = 0;
     let trigger_count = triggers.len();
@@ -279,14 +424,8 @@ pub async fn process_event<Q: JobQueue>(
             .map(|v| v.as_slice())
             .unwrap_or(&[]);
 
-        // Evaluate conditions against the event
-        // Use stateful evaluation if trigger is stateful, otherwise use stateless
-        let matches = if trigger.is_stateful {
-            trigger_engine::evaluate_trigger_stateful(trigger, conditions, &event, state_manager)
-                .await
-        } else {
-            trigger_engine::evaluate_trigger(conditions, &event)
-        };
+        // Evaluate conditions against the event (stateful triggers with state)
+        let matches = trigger_engine::evaluate(trigger, conditions, &event, state_manager).await;
 
         // Handle evaluation result with circuit breaker
         match matches {
@@ -306,6 +445,63 @@ pub async fn process_event<Q: JobQueue>(
                         .increment(1);
                 }
 
+                // Outside its schedule a trigger matches but does not fire (and
+                // does not start a cooldown)
+                match trigger_engine::within_schedule(trigger, &SystemClock) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::info!(
+                            trigger_id = %trigger.id,
+                            trigger_name = %trigger.name,
+                            "Trigger matched outside its schedule - not firing"
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            trigger_id = %trigger.id,
+                            error = %e,
+                            error_id = "TRIGGER_SCHEDULE_INVALID",
+                            "Failed to check trigger schedule, firing anyway"
+                        );
+                    }
+                }
+
+                // A follow not notified about this event type does not fire
+                let preferences = preferences_map.get(&trigger.id);
+                if preferences.is_some_and(|p| !p.allows_event_type(&event.event_type)) {
+                    tracing::info!(
+                        trigger_id = %trigger.id,
+                        trigger_name = %trigger.name,
+                        event_type = %event.event_type,
+                        "Trigger matched an event type its follow is not notified about - not firing"
+                    );
+                    continue;
+                }
+
+                // A trigger in its cooldown matched but does not fire; if the
+                // cooldown cannot be checked, fire rather than drop the event
+                match trigger_engine::claim_fire(trigger, state_manager).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::info!(
+                            trigger_id = %trigger.id,
+                            trigger_name = %trigger.name,
+                            cooldown_secs = trigger.cooldown_secs,
+                            "Trigger matched during cooldown - not firing"
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            trigger_id = %trigger.id,
+                            error = %e,
+                            error_id = "TRIGGER_COOLDOWN_CHECK_FAILED",
+                            "Failed to check trigger cooldown, firing anyway"
+                        );
+                    }
+                }
+
                 matched_count += 1;
                 tracing::info!(
                     trigger_id = %trigger.id,
@@ -313,16 +509,46 @@ pub async fn process_event<Q: JobQueue>(
                     "Trigger matched"
                 );
 
+                // Queue the fire for the organization's webhooks; a failure here
+                // must not block the trigger's own actions
+                if let Err(e) = webhooks::enqueue_trigger_fire(
+                    db_pool,
+                    trigger,
+                    &event,
+                    &event_to_template_data(&event),
+                    &correlation_id,
+                )
+                .await
+                {
+                    tracing::error!(
+                        trigger_id = %trigger.id,
+                        error = %e,
+                        error_id = "WEBHOOK_ENQUEUE_FAILED",
+                        "Failed to queue webhook deliveries for trigger fire"
+                    );
+                }
+
                 // Get actions for this trigger from the batch-loaded map
                 let actions = actions_map
                     .get(&trigger.id)
                     .map(|v| v.as_slice())
                     .unwrap_or(&[]);
+                trigger_engine::record_fire(actions);
 
                 // FIX 2.2: Process all actions, don't abort on single failure (High Priority)
                 let mut failed_actions = 0;
 
                 for action in actions {
+                    if preferences.is_some_and(|p| !p.allows_channel(&action.action_type)) {
+                        tracing::debug!(
+                            trigger_id = %trigger.id,
+                            action_id = action.id,
+                            action_type = %action.action_type,
+                            "Skipping action on a channel the follow is not notified through"
+                        );
+                        continue;
+                    }
+
                     // Parse action_type string to ActionType enum
                     // FIX 2.2: Continue on parse error instead of aborting
                     let action_type = match ActionType::from_str(&action.action_type) {
@@ -346,6 +572,23 @@ pub async fn process_event<Q: JobQueue>(
                         }
                     };
 
+                    if let Err(reason) =
+                        credit_budget.reserve(&trigger.organization_id, &action_type)
+                    {
+                        tracing::info!(
+                            trigger_id = %trigger.id,
+                            action_id = action.id,
+                            organization_id = %trigger.organization_id,
+                            reason = %reason,
+                            error_id = "INSUFFICIENT_CREDITS",
+                            "Insufficient credits, not enqueuing action"
+                        );
+                        #[cfg(feature = "metrics")]
+                        metrics::counter!("event_processor.actions_blocked_insufficient_credits")
+                            .increment(1);
+                        continue;
+                    }
+
                     // Build event_data JSON for template variable substitution
                     let event_data = event_to_template_data(&event);
 
@@ -356,7 +599,10 @@ pub async fn process_event<Q: JobQueue>(
                         action.priority,
                         action.config.clone(),
                         event_data,
-                    );
+                    )
+                    .with_correlation_id(correlation_id.as_str())
+                    .with_organization_id(trigger.organization_id.as_str())
+                    .with_action_id(action.id);
 
                     // FIX 2.2: Continue on enqueue error instead of aborting
                     // This allows other actions/triggers to proceed even if Redis is down
@@ -366,6 +612,7 @@ pub async fn process_event<Q: JobQueue>(
                             tracing::debug!(
                                 job_id = %job.id,
                                 trigger_id = %trigger.id,
+                                correlation_id = %job.correlation_id,
                                 action_type = %job.action_type,
                                 "Enqueued action job"
                             );
@@ -466,6 +713,7 @@ pub async fn process_event<Q: JobQueue>(
 
     tracing::info!(
         event_id = %event_id,
+        correlation_id = %correlation_id,
         triggers_evaluated = trigger_count,
         triggers_matched = matched_count,
         actions_enqueued = actions_enqueued,
@@ -476,6 +724,49 @@ pub async fn process_event<Q: JobQueue>(
     Ok(())
 }
 
+/// How long a claim blocks other processors before it can be taken over
+///
+/// Only matters when a processor dies while holding a claim; it is well above
+/// the time processing one event can take.
+const CLAIM_TIMEOUT_SECS: f64 = 300.0;
+
+/// Claim an event for processing
+///
+/// Returns `false` if the event is already processed or another processor
+/// holds an unexpired claim. The insert is atomic, so of two concurrent
+/// claims exactly one succeeds.
+async fn claim_event(event_id: &str, db_pool: &DbPool) -> Result<bool> {
+    let claimed = sqlx::query_scalar::<_, String>(
+        r#"
+        INSERT INTO event_claims (event_id, processor_instance)
+        SELECT $1, $2
+        WHERE NOT EXISTS (SELECT 1 FROM processed_events WHERE event_id = $1)
+        ON CONFLICT (event_id) DO UPDATE
+            SET claimed_at = NOW(), processor_instance = EXCLUDED.processor_instance
+            WHERE event_claims.claimed_at < NOW() - make_interval(secs => $3)
+        RETURNING event_id
+        "#,
+    )
+    .bind(event_id)
+    .bind(get_hostname())
+    .bind(CLAIM_TIMEOUT_SECS)
+    .fetch_optional(db_pool)
+    .await
+    .context("Failed to claim event")?;
+
+    Ok(claimed.is_some())
+}
+
+/// Drop this processor's claim on an event
+async fn release_claim(event_id: &str, db_pool: &DbPool) -> Result<()> {
+    sqlx::query("DELETE FROM event_claims WHERE event_id = $1")
+        .bind(event_id)
+        .execute(db_pool)
+        .await
+        .context("Failed to release event claim")?;
+    Ok(())
+}
+
 /// Mark an event as processed in the database
 ///
 /// This function inserts a record into the `processed_events` table to prevent
@@ -521,8 +812,18 @@ async fn mark_event_processed(
 /// Reads from `ponder_events` view which maps Ponder's camelCase columns
 /// to snake_case for compatibility with the existing Event model.
 /// This view reads from the shared `ponder."Event"` table.
-async fn fetch_event(event_id: &str, db_pool: &DbPool) -> Result<Event> {
-    match sqlx::query_as::<_, Event>(
+/// Events pushed to the API gateway (`POST /api/v1/events/ingest`) are stored
+/// in `events` instead, which is read when the view has no such ID.
+/// Returns `None` when no row with this ID is visible.
+async fn fetch_event(event_id: &str, db_pool: &DbPool) -> Result<Option<Event>> {
+    match fetch_event_from("ponder_events", event_id, db_pool).await? {
+        Some(event) => Ok(Some(event)),
+        None => fetch_event_from("events", event_id, db_pool).await,
+    }
+}
+
+async fn fetch_event_from(table: &str, event_id: &str, db_pool: &DbPool) -> Result<Option<Event>> {
+    match sqlx::query_as::<_, Event>(&format!(
         r#"
         SELECT
             id, chain_id, block_number, block_hash, transaction_hash, log_index,
@@ -530,18 +831,19 @@ async fn fetch_event(event_id: &str, db_pool: &DbPool) -> Result<Event> {
             metadata_value, client_address, feedback_index, score, tag1, tag2,
             file_uri, file_hash, validator_address, request_hash, response,
             response_uri, response_hash, tag, created_at
-        FROM ponder_events
+        FROM {table}
         WHERE id = $1
-        "#,
-    )
+        "#
+    ))
     .bind(event_id)
-    .fetch_one(db_pool)
+    .fetch_optional(db_pool)
     .await
     {
         Ok(event) => Ok(event),
         Err(e) => {
             tracing::error!(
                 event_id = %event_id,
+                table = table,
                 error = ?e,
                 error_id = "FETCH_EVENT_SQLX_ERROR",
                 "SQLx error fetching event (full debug)"
@@ -558,12 +860,15 @@ async fn fetch_event(event_id: &str, db_pool: &DbPool) -> Result<Event> {
 ///
 /// If a trigger has chain_id = NULL, it matches ALL chains (wildcard).
 /// If a trigger has a specific chain_id, it only matches that chain.
+/// Soft-deleted triggers never match.
 async fn fetch_triggers(chain_id: i32, registry: &str, db_pool: &DbPool) -> Result<Vec<Trigger>> {
     sqlx::query_as::<_, Trigger>(
         r#"
-        SELECT id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful, created_at, updated_at
+        SELECT id, user_id, organization_id, name, description, chain_id, registry, enabled, is_stateful,
+               cooldown_secs, schedule, created_at, updated_at
         FROM triggers
         WHERE (chain_id = $1 OR chain_id IS NULL) AND registry = $2 AND enabled = true
+          AND deleted_at IS NULL
         "#,
     )
     .bind(chain_id)
@@ -573,6 +878,40 @@ async fn fetch_triggers(chain_id: i32, registry: &str, db_pool: &DbPool) -> Resu
     .context("Failed to fetch triggers from database")
 }
 
+/// Notification preferences of the agent follows owning any of `trigger_ids`,
+/// keyed by trigger ID
+///
+/// Triggers that belong to no follow, or to a follow without preferences, are
+/// not in the map.
+async fn fetch_follow_preferences(
+    trigger_ids: &[String],
+    db_pool: &DbPool,
+) -> Result<HashMap<String, NotificationPreferences>> {
+    if trigger_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = sqlx::query_as::<_, (String, sqlx::types::Json<NotificationPreferences>)>(
+        r#"
+        SELECT t.trigger_id, f.notification_preferences
+        FROM agent_follows f
+        CROSS JOIN LATERAL (
+            VALUES (f.trigger_identity_id), (f.trigger_reputation_id), (f.trigger_validation_id)
+        ) AS t(trigger_id)
+        WHERE t.trigger_id = ANY($1) AND f.notification_preferences IS NOT NULL
+        "#,
+    )
+    .bind(trigger_ids)
+    .fetch_all(db_pool)
+    .await
+    .context("Failed to fetch follow notification preferences")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(trigger_id, preferences)| (trigger_id, preferences.0))
+        .collect())
+}
+
 /// Batch fetch conditions and actions for multiple triggers
 ///
 /// This function solves the N+1 query problem by loading all conditions and actions
@@ -616,16 +955,20 @@ async fn fetch_trigger_relations(
         }
     };
 
-    // Batch fetch all actions with a single query using ANY($1)
+    // Batch fetch all actions with a single query using ANY($1). In enforce
+    // mode, REST actions that have not passed the handshake are skipped.
     let actions = match sqlx::query_as::<_, TriggerAction>(
         r#"
-        SELECT id, trigger_id, action_type, priority, config, created_at
+        SELECT id, trigger_id, action_type, priority, config,
+               verification_status, verified_at, created_at
         FROM trigger_actions
         WHERE trigger_id = ANY($1)
+          AND (NOT $2 OR action_type <> 'rest' OR verification_status = 'verified')
         ORDER BY trigger_id, priority DESC, id
         "#,
     )
     .bind(trigger_ids)
+    .bind(*ENFORCE_WEBHOOK_VERIFICATION)
     .fetch_all(db_pool)
     .await
     {
@@ -669,6 +1012,14 @@ async fn fetch_trigger_relations(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_enforces_webhook_verification() {
+        assert!(enforces_webhook_verification("enforce"));
+        assert!(enforces_webhook_verification("BLOCK"));
+        assert!(!enforces_webhook_verification("flag"));
+        assert!(!enforces_webhook_verification("off"));
+    }
+
     #[test]
     fn test_get_hostname() {
         let hostname = get_hostname();
@@ -679,4 +1030,74 @@ mod tests {
             "Hostname should be valid or 'unknown'"
         );
     }
+
+    #[test]
+    fn test_record_processed_tracks_last_event() {
+        record_processed(ProcessingPath::Polling);
+        let now = chrono::Utc::now().timestamp();
+
+        let since = seconds_since_last_processed(now + 30).unwrap();
+        assert!((30..=31).contains(&since));
+        // Clock skew never yields a negative age
+        assert_eq!(seconds_since_last_processed(0), Some(0));
+        assert_eq!(ProcessingPath::Notify.as_str(), "notify");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires DATABASE_URL (integration test)
+    async fn test_fetch_triggers_skips_soft_deleted() {
+        let database_url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+        let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        let (user_id, org_id) = (
+            format!("deleted_user_{}", suffix),
+            format!("deleted_org_{}", suffix),
+        );
+        let (live_id, deleted_id) = (format!("live_{}", suffix), format!("deleted_{}", suffix));
+
+        sqlx::query("INSERT INTO users (id, username, email, password_hash) VALUES ($1, $1, $1 || '@example.com', 'hash')")
+            .bind(&user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO organizations (id, name, slug, owner_id, plan, is_personal) VALUES ($1, $1, $1, $2, 'free', false)")
+            .bind(&org_id)
+            .bind(&user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, deleted_at) in [(&live_id, None), (&deleted_id, Some(chrono::Utc::now()))] {
+            sqlx::query(
+                "INSERT INTO triggers (id, user_id, organization_id, name, chain_id, registry, enabled, deleted_at) VALUES ($1, $2, $3, $1, 999001, 'reputation', true, $4)",
+            )
+            .bind(id)
+            .bind(&user_id)
+            .bind(&org_id)
+            .bind(deleted_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let ids: Vec<String> = fetch_triggers(999001, "reputation", &pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .filter(|id| id.ends_with(&suffix))
+            .collect();
+        assert_eq!(ids, [live_id]);
+
+        sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(&org_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(&user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
 }
@@ -2,25 +2,38 @@
 //!
 //! Provides a trait-based abstraction over the job queue to enable testing.
 //!
-//! # Queue Overflow Protection (FIX 2.1)
+//! [`RedisJobQueue`] puts each job on the priority lane of its queue that
+//! matches the job's priority (see [`QueuePriority`]).
 //!
-//! This module implements queue depth monitoring to prevent Redis memory exhaustion.
-//! If queue depth exceeds threshold, warnings are logged and metrics emitted.
-//! The polling fallback ensures events are not lost even if enqueue is rejected.
+//! # Backpressure
+//!
+//! If the action workers fall behind, their queues would grow until Redis runs
+//! out of memory. [`RedisJobQueue`] checks the depth of a job's queue (all its
+//! lanes) before enqueueing it; over `ACTION_QUEUE_MAX_DEPTH` (see [`ActionQueueConfig`]):
+//!
+//! - low-priority jobs go straight to the dead letter queue
+//! - the others wait with exponential backoff for the queue to drain, and go
+//!   to the dead letter queue if it is still full after `ACTION_QUEUE_MAX_WAIT_MS`
+//!
+//! Waiting slows down event processing, which is the point: the backlog stays
+//! in PostgreSQL instead of Redis. Dead-lettered jobs can be replayed once the
+//! workers have caught up.
 
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use redis::aio::MultiplexedConnection;
 use redis::AsyncCommands;
-use shared::{ActionJob, ACTION_JOBS_QUEUE};
+use shared::redis::queue_key;
+use shared::{
+    ActionJob, ActionQueueConfig, ActionWorkerPools, DlqEntry, QueuePriority, ACTION_JOBS_DLQ,
+};
+use std::time::Duration;
 
-/// Maximum queue depth before warnings (High Priority Fix 2.1)
-/// Prevents Redis memory exhaustion under sustained load
-const MAX_QUEUE_DEPTH: usize = 10_000;
+/// First wait for a queue over its high-water mark
+const BACKOFF_INITIAL: Duration = Duration::from_millis(50);
 
-/// Critical queue depth threshold (triggers backpressure)
-/// If queue grows beyond this, new enqueues are rejected
-const CRITICAL_QUEUE_DEPTH: usize = 50_000;
+/// Longest single wait for a queue over its high-water mark
+const BACKOFF_MAX: Duration = Duration::from_secs(1);
 
 /// Abstract job queue interface for testability
 #[async_trait]
@@ -33,79 +46,174 @@ pub trait JobQueue: Send + Sync {
     async fn enqueue(&self, job: &ActionJob) -> Result<()>;
 }
 
+/// What to do with a job, given the depth of its queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The queue is below its high-water mark
+    Enqueue,
+    /// Wait this long and check the depth again
+    Wait(Duration),
+    /// Move the job to the dead letter queue
+    DeadLetter,
+}
+
+/// Backpressure policy from queue depth (see the [module docs](self))
+#[derive(Debug, Clone, Default)]
+pub struct Backpressure {
+    config: ActionQueueConfig,
+}
+
+impl Backpressure {
+    pub fn new(config: ActionQueueConfig) -> Self {
+        Self { config }
+    }
+
+    /// Decide what to do with a job of `priority` that already waited `waited`
+    ///
+    /// Each wait is as long as all previous ones together, so waits double
+    /// from 50ms up to 1s.
+    pub fn admit(&self, depth: usize, priority: i32, waited: Duration) -> Admission {
+        if depth < self.config.max_depth {
+            return Admission::Enqueue;
+        }
+        let max_wait = self.config.max_wait();
+        if priority <= self.config.low_priority || waited >= max_wait {
+            return Admission::DeadLetter;
+        }
+
+        let delay = waited.clamp(BACKOFF_INITIAL, BACKOFF_MAX);
+        Admission::Wait(delay.min(max_wait - waited))
+    }
+}
+
 /// Redis-backed job queue implementation
+///
+/// Jobs go to the shared queue, or to their action type's queue when the
+/// workers run a dedicated pool for it (see [`ActionWorkerPools`]).
 #[derive(Clone)]
 pub struct RedisJobQueue {
     conn: MultiplexedConnection,
+    pools: ActionWorkerPools,
+    backpressure: Backpressure,
 }
 
 impl RedisJobQueue {
-    /// Create a new Redis job queue
+    /// Create a new Redis job queue routed by the pool layout in the environment
     ///
     /// # Arguments
     ///
     /// * `conn` - Multiplexed Redis connection
-    pub fn new(conn: MultiplexedConnection) -> Self {
-        Self { conn }
+    /// * `config` - Queue high-water mark and backpressure settings
+    pub fn new(conn: MultiplexedConnection, config: ActionQueueConfig) -> Self {
+        Self::with_pools(conn, ActionWorkerPools::from_env(), config)
     }
+
+    /// Create a new Redis job queue routed by an explicit pool layout
+    pub fn with_pools(
+        conn: MultiplexedConnection,
+        pools: ActionWorkerPools,
+        config: ActionQueueConfig,
+    ) -> Self {
+        Self {
+            conn,
+            pools,
+            backpressure: Backpressure::new(config),
+        }
+    }
+
+    /// Park a job the queue has no room for in the dead letter queue
+    async fn dead_letter(&self, job: &ActionJob, queue_depth: usize) -> Result<()> {
+        let error = format!(
+            "Action queue depth {} reached ACTION_QUEUE_MAX_DEPTH {}",
+            queue_depth, self.backpressure.config.max_depth
+        );
+        let entry = serde_json::to_string(&DlqEntry::new(job.clone(), error, 0))
+            .context("Failed to serialize DLQ entry")?;
+
+        let mut conn = self.conn.clone();
+        conn.lpush::<_, _, ()>(queue_key(ACTION_JOBS_DLQ), &entry)
+            .await
+            .context("Failed to move action job to the DLQ")?;
+
+        tracing::warn!(
+            queue_depth = queue_depth,
+            max_depth = self.backpressure.config.max_depth,
+            job_id = %job.id,
+            trigger_id = %job.trigger_id,
+            correlation_id = %job.correlation_id,
+            priority = job.priority,
+            error_id = "QUEUE_BACKPRESSURE_DLQ",
+            "Action queue full - moved job to the DLQ"
+        );
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!("event_processor.queue_dead_lettered").increment(1);
+
+        Ok(())
+    }
+}
+
+/// Jobs waiting in `queue`, across its priority lanes
+async fn queue_depth(conn: &mut MultiplexedConnection, queue: &str) -> Result<usize> {
+    let mut pipe = redis::pipe();
+    for priority in QueuePriority::ALL {
+        pipe.llen(priority.lane(queue));
+    }
+    let depths: Vec<usize> = pipe
+        .query_async(conn)
+        .await
+        .context("Failed to get queue depth from Redis")?;
+    Ok(depths.into_iter().sum())
 }
 
 #[async_trait]
 impl JobQueue for RedisJobQueue {
     async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        let queue_name = queue_key(&self.pools.queue_for(&job.action_type));
+
         // FIX 2.1: Check queue depth BEFORE enqueuing (High Priority)
         let mut conn = self.conn.clone();
-        let queue_depth: usize = conn
-            .llen(ACTION_JOBS_QUEUE)
-            .await
-            .context("Failed to get queue depth from Redis")?;
-
-        // CRITICAL: Reject if queue is at critical depth (backpressure)
-        if queue_depth >= CRITICAL_QUEUE_DEPTH {
-            tracing::error!(
-                queue_depth = queue_depth,
-                critical_threshold = CRITICAL_QUEUE_DEPTH,
-                job_id = %job.id,
-                trigger_id = %job.trigger_id,
-                error_id = "QUEUE_CRITICAL_DEPTH",
-                "CRITICAL: Redis queue at critical depth, rejecting new job (backpressure)"
-            );
-
-            // Emit metric for monitoring
-            #[cfg(feature = "metrics")]
-            metrics::counter!("event_processor.queue_rejections").increment(1);
-
-            bail!(
-                "Redis queue depth {} exceeds critical threshold {} - rejecting job to prevent memory exhaustion",
-                queue_depth,
-                CRITICAL_QUEUE_DEPTH
-            );
-        }
+        let mut waited = Duration::ZERO;
+        let queue_depth = loop {
+            let queue_depth = queue_depth(&mut conn, &queue_name).await?;
 
-        // WARNING: Log warning if queue exceeds normal threshold
-        if queue_depth >= MAX_QUEUE_DEPTH {
-            tracing::warn!(
-                queue_depth = queue_depth,
-                max_threshold = MAX_QUEUE_DEPTH,
-                job_id = %job.id,
-                trigger_id = %job.trigger_id,
-                error_id = "QUEUE_HIGH_DEPTH",
-                "Redis queue depth exceeds threshold - action workers may be falling behind"
-            );
-
-            // Emit metric for monitoring
-            #[cfg(feature = "metrics")]
-            metrics::gauge!("event_processor.queue_depth_high").set(1.0);
-        }
+            match self.backpressure.admit(queue_depth, job.priority, waited) {
+                Admission::Enqueue => break queue_depth,
+                Admission::Wait(delay) => {
+                    if waited.is_zero() {
+                        tracing::warn!(
+                            queue_depth = queue_depth,
+                            max_depth = self.backpressure.config.max_depth,
+                            job_id = %job.id,
+                            trigger_id = %job.trigger_id,
+                            correlation_id = %job.correlation_id,
+                            error_id = "QUEUE_HIGH_DEPTH",
+                            "Redis queue depth exceeds threshold - waiting for action workers to catch up"
+                        );
+
+                        #[cfg(feature = "metrics")]
+                        metrics::counter!("event_processor.queue_backpressure_waits").increment(1);
+                    }
+
+                    #[cfg(feature = "metrics")]
+                    metrics::gauge!("event_processor.queue_depth_high").set(1.0);
+
+                    tokio::time::sleep(delay).await;
+                    waited += delay;
+                }
+                Admission::DeadLetter => return self.dead_letter(job, queue_depth).await,
+            }
+        };
+
+        #[cfg(feature = "metrics")]
+        metrics::gauge!("event_processor.queue_depth_high").set(0.0);
 
         // Serialize job
         let job_json = serde_json::to_string(job).context("Failed to serialize action job")?;
 
-        // NOTE: LPUSH maintains FIFO order and ignores job.priority field.
-        // Priority-based consumption can be implemented in action workers if needed
-        // by batching jobs and sorting by priority before execution.
-        // For now, we use simple FIFO ordering (LPUSH + BRPOP).
-        conn.lpush::<_, _, ()>(ACTION_JOBS_QUEUE, &job_json)
+        // LPUSH + RPOPLPUSH keeps each priority lane FIFO
+        let lane = job.queue_priority().lane(&queue_name);
+        conn.lpush::<_, _, ()>(&lane, &job_json)
             .await
             .context("Failed to enqueue action job to Redis")?;
 
@@ -116,7 +224,10 @@ impl JobQueue for RedisJobQueue {
         tracing::debug!(
             job_id = %job.id,
             trigger_id = %job.trigger_id,
+            event_id = %job.event_id,
+            correlation_id = %job.correlation_id,
             action_type = %job.action_type,
+            queue = %lane,
             queue_depth = queue_depth,
             "Enqueued action job"
         );
@@ -160,4 +271,61 @@ mod tests {
         let result = mock_queue.enqueue(&job).await;
         assert!(result.is_ok());
     }
+
+    fn backpressure(max_depth: usize, max_wait_ms: u64) -> Backpressure {
+        Backpressure::new(ActionQueueConfig {
+            max_depth,
+            max_wait_ms,
+            low_priority: 0,
+        })
+    }
+
+    #[test]
+    fn test_admit_below_high_water_mark() {
+        let policy = backpressure(100, 5_000);
+
+        assert_eq!(policy.admit(0, 1, Duration::ZERO), Admission::Enqueue);
+        assert_eq!(policy.admit(99, 0, Duration::ZERO), Admission::Enqueue);
+    }
+
+    #[test]
+    fn test_admit_waits_with_exponential_backoff() {
+        let policy = backpressure(100, 5_000);
+
+        let mut waited = Duration::ZERO;
+        let mut delays = Vec::new();
+        while let Admission::Wait(delay) = policy.admit(100, 1, waited) {
+            delays.push(delay.as_millis());
+            waited += delay;
+        }
+
+        assert_eq!(
+            delays,
+            vec![50, 50, 100, 200, 400, 800, 1000, 1000, 1000, 400]
+        );
+        assert_eq!(waited, Duration::from_secs(5));
+        // Still full after the longest wait
+        assert_eq!(policy.admit(100, 1, waited), Admission::DeadLetter);
+        // Drained in time
+        assert_eq!(policy.admit(99, 1, waited), Admission::Enqueue);
+    }
+
+    #[test]
+    fn test_admit_sheds_low_priority_without_waiting() {
+        let policy = backpressure(100, 5_000);
+
+        assert_eq!(policy.admit(100, 0, Duration::ZERO), Admission::DeadLetter);
+        assert_eq!(policy.admit(500, -5, Duration::ZERO), Admission::DeadLetter);
+        assert!(matches!(
+            policy.admit(500, 10, Duration::ZERO),
+            Admission::Wait(_)
+        ));
+    }
+
+    #[test]
+    fn test_admit_without_wait_budget() {
+        let policy = backpressure(100, 0);
+
+        assert_eq!(policy.admit(100, 10, Duration::ZERO), Admission::DeadLetter);
+    }
 }
@@ -1,9 +1,11 @@
 //! Trigger state manager
 //!
 //! Manages persistent state for stateful triggers (EMA, rate counters, etc.)
+//! and the last fire of triggers with a cooldown.
 //! Uses PostgreSQL JSONB storage with UPSERT for atomic updates.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::PgPool;
 use tracing::{debug, warn};
@@ -97,6 +99,55 @@ impl TriggerStateManager {
         Ok(())
     }
 
+    /// Record a fire of a trigger unless it is cooling down
+    ///
+    /// The fire is recorded at `now` when the trigger never fired or last
+    /// fired at least `cooldown_secs` before `now`. The check and the update
+    /// are one statement, so concurrent processors cannot both fire.
+    ///
+    /// # Arguments
+    ///
+    /// * `trigger_id` - ID of the trigger
+    /// * `cooldown_secs` - Minimum seconds between two fires
+    /// * `now` - Time of this fire
+    ///
+    /// # Returns
+    ///
+    /// `true` if the fire was recorded, `false` if the trigger is cooling down
+    ///
+    /// # Errors
+    ///
+    /// Returns error if database query fails
+    pub async fn record_fire(
+        &self,
+        trigger_id: &str,
+        cooldown_secs: i32,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        debug!(trigger_id = trigger_id, "Recording trigger fire");
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO trigger_state (trigger_id, state_data, last_updated, last_fired_at)
+            VALUES ($1, '{}'::jsonb, $2, $2)
+            ON CONFLICT (trigger_id)
+            DO UPDATE SET
+                last_fired_at = EXCLUDED.last_fired_at,
+                last_updated = EXCLUDED.last_updated
+            WHERE trigger_state.last_fired_at IS NULL
+               OR trigger_state.last_fired_at <= EXCLUDED.last_fired_at - make_interval(secs => $3)
+            "#,
+        )
+        .bind(trigger_id)
+        .bind(now)
+        .bind(cooldown_secs as f64)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("Failed to record fire for trigger {}", trigger_id))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Delete state for a trigger
     ///
     /// # Arguments
@@ -158,6 +209,9 @@ impl TriggerStateManager {
 
         let deleted = result.rows_affected();
 
+        #[cfg(feature = "metrics")]
+        metrics::counter!("event_processor.state_cleanup_deleted").increment(deleted);
+
         if deleted > 0 {
             warn!(
                 deleted = deleted,
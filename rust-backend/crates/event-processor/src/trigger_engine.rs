@@ -7,28 +7,55 @@
 //! - score_threshold: Compare score with threshold
 //! - tag_equals: Match tag1 or tag2
 //! - event_type_equals: Match event type
+//! - json_path: Compare values a JSONPath selects from the event data
 //!
 //! # Stateful Conditions (Week 14)
 //! - ema_threshold: Exponential moving average of scores
+//! - ema_crossover: Fast EMA of scores crossing the slow EMA
 //! - rate_limit: Event count in sliding time window
+//!
+//! # Cooldown
+//! A trigger with `cooldown_secs > 0` fires at most once per cooldown; see
+//! [`claim_fire`].
+//!
+//! # Schedule
+//! A trigger with a schedule only fires inside its weekly time window; see
+//! [`within_schedule`].
+//!
+//! # Metrics (`metrics` feature)
+//! - `trigger_evaluations_total{result}`: evaluations by `matched`,
+//!   `unmatched` or `error` (see [`evaluate`])
+//! - `trigger_fires_total{action_type}`: actions of fired triggers (see
+//!   [`record_fire`])
 
 use anyhow::{bail, Context, Result};
-use shared::models::{Event, Trigger, TriggerCondition};
-
-use crate::evaluators::{EmaEvaluator, EmaState, RateCounterEvaluator, RateCounterState};
+use chrono::{DateTime, Utc};
+use shared::conditions::{json_path_matches, parse_json_path};
+use shared::models::{Event, Trigger, TriggerAction, TriggerCondition};
+use shared::{Clock, Comparison};
+
+use crate::evaluators::{
+    CrossoverEvaluator, CrossoverState, EmaEvaluator, EmaState, RateCounterEvaluator,
+    RateCounterState,
+};
+use crate::processor::event_to_template_data;
 use crate::state_manager::TriggerStateManager;
 
-/// Supported condition types
+/// Supported condition types (see [`shared::conditions`])
 pub mod condition_types {
+    use shared::ConditionType;
+
     // Stateless conditions
-    pub const AGENT_ID_EQUALS: &str = "agent_id_equals";
-    pub const SCORE_THRESHOLD: &str = "score_threshold";
-    pub const TAG_EQUALS: &str = "tag_equals";
-    pub const EVENT_TYPE_EQUALS: &str = "event_type_equals";
+    pub const AGENT_ID_EQUALS: &str = ConditionType::AgentIdEquals.as_str();
+    pub const SCORE_THRESHOLD: &str = ConditionType::ScoreThreshold.as_str();
+    pub const TAG_EQUALS: &str = ConditionType::TagEquals.as_str();
+    pub const EVENT_TYPE_EQUALS: &str = ConditionType::EventTypeEquals.as_str();
+    pub const JSON_PATH: &str = ConditionType::JsonPath.as_str();
 
     // Stateful conditions
-    pub const EMA_THRESHOLD: &str = "ema_threshold";
-    pub const RATE_LIMIT: &str = "rate_limit";
+    pub const EMA_THRESHOLD: &str = ConditionType::EmaThreshold.as_str();
+    pub const EMA_CROSSOVER: &str = ConditionType::EmaCrossover.as_str();
+    pub const RATE_LIMIT: &str = ConditionType::RateLimit.as_str();
 }
 
 /// Evaluate a single condition against an event
@@ -47,6 +74,7 @@ pub fn evaluate_condition(condition: &TriggerCondition, event: &Event) -> Result
         condition_types::SCORE_THRESHOLD => evaluate_score_threshold(condition, event),
         condition_types::TAG_EQUALS => evaluate_tag_equals(condition, event),
         condition_types::EVENT_TYPE_EQUALS => evaluate_event_type_equals(condition, event),
+        condition_types::JSON_PATH => evaluate_json_path(condition, event),
         unknown => bail!("Unknown condition type: {}", unknown),
     };
 
@@ -94,7 +122,7 @@ fn evaluate_agent_id_equals(condition: &TriggerCondition, event: &Event) -> Resu
 /// Supported operators: <, >, =, <=, >=, !=
 fn evaluate_score_threshold(condition: &TriggerCondition, event: &Event) -> Result<bool> {
     let value_str = json_value_as_str(&condition.value);
-    let threshold: i32 = value_str
+    let threshold: i64 = value_str
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid score threshold value: {}", value_str))?;
 
@@ -109,17 +137,10 @@ fn evaluate_score_threshold(condition: &TriggerCondition, event: &Event) -> Resu
         }
     };
 
-    let result = match condition.operator.as_str() {
-        "<" => score < threshold,
-        ">" => score > threshold,
-        "=" | "==" => score == threshold,
-        "<=" => score <= threshold,
-        ">=" => score >= threshold,
-        "!=" | "<>" => score != threshold,
-        op => bail!("Invalid score_threshold operator: {}", op),
-    };
-
-    Ok(result)
+    match Comparison::parse(&condition.operator) {
+        Some(comparison) => Ok(comparison.compare(i64::from(score), threshold)),
+        None => bail!("Invalid score_threshold operator: {}", condition.operator),
+    }
 }
 
 /// Evaluate tag_equals condition
@@ -152,6 +173,26 @@ fn evaluate_event_type_equals(condition: &TriggerCondition, event: &Event) -> Re
     Ok(event.event_type == condition.value)
 }
 
+/// Evaluate json_path condition
+///
+/// Resolves the JSONPath in the condition field against the event data that
+/// actions receive. A path that selects no value does not match.
+fn evaluate_json_path(condition: &TriggerCondition, event: &Event) -> Result<bool> {
+    let path = parse_json_path(&condition.field)?;
+    let value_str = json_value_as_str(&condition.value);
+
+    let matches = json_path_matches(
+        &path,
+        &condition.operator,
+        &value_str,
+        &event_to_template_data(event),
+    );
+    match matches {
+        Some(matches) => Ok(matches),
+        None => bail!("Invalid json_path operator: {}", condition.operator),
+    }
+}
+
 /// Evaluate all conditions against an event (AND logic) - STATELESS ONLY
 ///
 /// # Arguments
@@ -190,6 +231,52 @@ pub fn evaluate_trigger(conditions: &[TriggerCondition], event: &Event) -> Resul
     Ok(true)
 }
 
+/// Evaluate a trigger's conditions, with state if the trigger is stateful
+///
+/// Counts the outcome in `trigger_evaluations_total`.
+///
+/// # Errors
+///
+/// See [`evaluate_trigger`] and [`evaluate_trigger_stateful`].
+pub async fn evaluate(
+    trigger: &Trigger,
+    conditions: &[TriggerCondition],
+    event: &Event,
+    state_manager: &TriggerStateManager,
+) -> Result<bool> {
+    let outcome = if trigger.is_stateful {
+        evaluate_trigger_stateful(trigger, conditions, event, state_manager).await
+    } else {
+        evaluate_trigger(conditions, event)
+    };
+
+    #[cfg(feature = "metrics")]
+    {
+        let result = match &outcome {
+            Ok(true) => "matched",
+            Ok(false) => "unmatched",
+            Err(_) => "error",
+        };
+        metrics::counter!("trigger_evaluations_total", "result" => result).increment(1);
+    }
+
+    outcome
+}
+
+/// Count a fire of a trigger with these actions in `trigger_fires_total`
+///
+/// Called once the trigger passed its schedule and cooldown checks; each
+/// action counts once under its `action_type`.
+pub fn record_fire(actions: &[TriggerAction]) {
+    #[cfg(feature = "metrics")]
+    for action in actions {
+        metrics::counter!("trigger_fires_total", "action_type" => action.action_type.clone())
+            .increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = actions;
+}
+
 /// Evaluate all conditions against an event with state management (AND logic)
 ///
 /// # Arguments
@@ -264,6 +351,31 @@ pub async fn evaluate_trigger_stateful(
 
                 condition_matches
             }
+            condition_types::EMA_CROSSOVER => {
+                let config = condition
+                    .config
+                    .as_ref()
+                    .context("EMA crossover condition missing config")?;
+
+                let evaluator = CrossoverEvaluator::from_config(config).with_context(|| {
+                    format!(
+                        "Invalid EMA crossover config for condition {}",
+                        condition.id
+                    )
+                })?;
+
+                // Both EMAs live in one state object
+                let crossover_state = current_state
+                    .as_ref()
+                    .and_then(|s| serde_json::from_value::<CrossoverState>(s.clone()).ok());
+
+                let (condition_matches, updated_state) =
+                    evaluator.evaluate(event, condition, crossover_state)?;
+
+                new_state = Some(serde_json::to_value(updated_state)?);
+
+                condition_matches
+            }
             condition_types::RATE_LIMIT => {
                 // Rate counter evaluation
                 let config = condition
@@ -337,10 +449,63 @@ pub async fn evaluate_trigger_stateful(
     Ok(true)
 }
 
+/// Check whether the schedule of a matched trigger lets it fire now
+///
+/// Triggers without a schedule always fire.
+///
+/// # Errors
+///
+/// Returns error if the stored schedule does not parse
+pub fn within_schedule(trigger: &Trigger, clock: &dyn Clock) -> Result<bool> {
+    let Some(schedule) = &trigger.schedule else {
+        return Ok(true);
+    };
+
+    let now = DateTime::from_timestamp_millis(clock.now_ms()).context("Clock out of range")?;
+    schedule
+        .contains(now)
+        .with_context(|| format!("Invalid schedule for trigger {}", trigger.id))
+}
+
+/// Check the cooldown of a matched trigger and record the fire
+///
+/// Call before enqueueing the trigger's actions. Triggers without a cooldown
+/// always fire and are not tracked.
+///
+/// # Returns
+///
+/// `true` if the trigger fires, `false` if it fired less than
+/// `cooldown_secs` ago
+///
+/// # Errors
+///
+/// Returns error if the fire cannot be recorded
+pub async fn claim_fire(trigger: &Trigger, state_manager: &TriggerStateManager) -> Result<bool> {
+    if trigger.cooldown_secs <= 0 {
+        return Ok(true);
+    }
+
+    let fired = state_manager
+        .record_fire(&trigger.id, trigger.cooldown_secs, Utc::now())
+        .await
+        .with_context(|| format!("Failed to check cooldown for trigger {}", trigger.id))?;
+
+    if !fired {
+        tracing::debug!(
+            trigger_id = %trigger.id,
+            cooldown_secs = trigger.cooldown_secs,
+            "Trigger is cooling down"
+        );
+    }
+
+    Ok(fired)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use shared::ManualClock;
 
     /// Create a test event with customizable fields
     fn create_test_event() -> Event {
@@ -376,6 +541,26 @@ mod tests {
         }
     }
 
+    /// Create a test trigger with an optional schedule
+    fn create_test_trigger(schedule: Option<shared::TriggerSchedule>) -> Trigger {
+        Trigger {
+            id: "test-trigger".to_string(),
+            user_id: "test-user".to_string(),
+            organization_id: "test-org".to_string(),
+            name: "Test Trigger".to_string(),
+            description: None,
+            chain_id: Some(84532),
+            registry: "reputation".to_string(),
+            enabled: true,
+            is_stateful: false,
+            cooldown_secs: 0,
+            schedule,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
     /// Create a test condition
     fn create_condition(
         condition_type: &str,
@@ -551,6 +736,76 @@ mod tests {
         assert!(evaluate_condition(&condition, &event).is_err());
     }
 
+    #[test]
+    fn test_validated_stateless_conditions_evaluate() {
+        use shared::conditions::ValueType;
+
+        let event = create_test_event();
+        for kind in shared::ConditionType::ALL {
+            if kind.is_stateful() {
+                continue;
+            }
+            let value = match kind.value_type() {
+                Some(ValueType::Text) | None => "trade",
+                Some(_) => "42",
+            };
+            for field in kind.fields() {
+                for operator in kind.operators() {
+                    shared::validate_condition(kind.as_str(), field, operator, value, None)
+                        .unwrap();
+                    let condition = create_condition(kind.as_str(), field, operator, value);
+                    assert!(
+                        evaluate_condition(&condition, &event).is_ok(),
+                        "{} {} {}",
+                        kind.as_str(),
+                        field,
+                        operator
+                    );
+                }
+            }
+        }
+    }
+
+    // ========================================================================
+    // json_path tests
+    // ========================================================================
+
+    #[test]
+    fn test_json_path_match() {
+        let event = create_test_event();
+        let condition = create_condition("json_path", "$.tag1", "=", "trade");
+
+        assert!(evaluate_condition(&condition, &event).unwrap());
+    }
+
+    #[test]
+    fn test_json_path_numeric_comparison() {
+        let event = create_test_event();
+        let condition = create_condition("json_path", "$.score", ">", "80");
+
+        assert!(evaluate_condition(&condition, &event).unwrap());
+    }
+
+    #[test]
+    fn test_json_path_missing_path_no_match() {
+        let mut event = create_test_event();
+        event.tag2 = None;
+
+        let condition = create_condition("json_path", "$.logs[0].topics[1]", "=", "0x01");
+        assert!(!evaluate_condition(&condition, &event).unwrap());
+        let condition = create_condition("json_path", "$.tag2", "exists", "");
+        assert!(!evaluate_condition(&condition, &event).unwrap());
+    }
+
+    #[test]
+    fn test_json_path_invalid_path() {
+        let event = create_test_event();
+        let condition = create_condition("json_path", "$.logs[", "=", "x");
+
+        let error = evaluate_condition(&condition, &event).unwrap_err();
+        assert!(format!("{:#}", error).contains("Invalid JSONPath"));
+    }
+
     // ========================================================================
     // tag_equals tests
     // ========================================================================
@@ -684,6 +939,60 @@ mod tests {
         assert!(evaluate_trigger(&conditions, &event).is_err());
     }
 
+    // ========================================================================
+    // Schedule tests
+    // ========================================================================
+
+    fn business_hours() -> shared::TriggerSchedule {
+        shared::TriggerSchedule {
+            days: ["mon", "tue", "wed", "thu", "fri"]
+                .iter()
+                .map(|d| d.to_string())
+                .collect(),
+            start: "09:00".to_string(),
+            end: "17:00".to_string(),
+            timezone: "America/New_York".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_trigger_fires_inside_schedule() {
+        let trigger = create_test_trigger(Some(business_hours()));
+        // Thursday 2026-01-15 15:00 UTC = 10:00 in New York
+        let clock = ManualClock::new(1_768_489_200_000);
+
+        assert!(within_schedule(&trigger, &clock).unwrap());
+    }
+
+    #[test]
+    fn test_trigger_suppressed_outside_schedule() {
+        let trigger = create_test_trigger(Some(business_hours()));
+        // Thursday 2026-01-15 23:00 UTC = 18:00 in New York
+        let clock = ManualClock::new(1_768_518_000_000);
+        assert!(!within_schedule(&trigger, &clock).unwrap());
+
+        // Saturday 2026-01-17 15:00 UTC = 10:00 in New York
+        clock.advance(std::time::Duration::from_secs(40 * 3600));
+        assert!(!within_schedule(&trigger, &clock).unwrap());
+    }
+
+    #[test]
+    fn test_trigger_without_schedule_always_fires() {
+        let trigger = create_test_trigger(None);
+        let clock = ManualClock::new(1_768_518_000_000);
+
+        assert!(within_schedule(&trigger, &clock).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_stored_schedule_is_error() {
+        let mut schedule = business_hours();
+        schedule.timezone = "Nowhere/Special".to_string();
+        let trigger = create_test_trigger(Some(schedule));
+
+        assert!(within_schedule(&trigger, &ManualClock::new(0)).is_err());
+    }
+
     // ========================================================================
     // Unknown condition type test
     // ========================================================================
@@ -695,4 +1004,63 @@ mod tests {
 
         assert!(evaluate_condition(&condition, &event).is_err());
     }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_matching_evaluation_counts_evaluation_and_fire() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let _guard = runtime.enter();
+
+        // Stateless triggers never touch the state manager's pool
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgresql://localhost/unused")
+            .unwrap();
+        let state_manager = TriggerStateManager::new(pool);
+        let trigger = create_test_trigger(None);
+        let event = create_test_event();
+        let action = |action_type: &str| TriggerAction {
+            id: 1,
+            trigger_id: trigger.id.clone(),
+            action_type: action_type.to_string(),
+            priority: 1,
+            config: serde_json::json!({}),
+            verification_status: "not_required".to_string(),
+            verified_at: None,
+            created_at: Utc::now(),
+        };
+
+        metrics::with_local_recorder(&recorder, || {
+            let matching = [create_condition("agent_id_equals", "agent_id", "=", "42")];
+            let other = [create_condition("agent_id_equals", "agent_id", "=", "7")];
+
+            assert!(runtime
+                .block_on(evaluate(&trigger, &matching, &event, &state_manager))
+                .unwrap());
+            record_fire(&[action("telegram"), action("rest")]);
+            assert!(!runtime
+                .block_on(evaluate(&trigger, &other, &event, &state_manager))
+                .unwrap());
+        });
+
+        let rendered = handle.render();
+        for line in [
+            r#"trigger_evaluations_total{result="matched"} 1"#,
+            r#"trigger_evaluations_total{result="unmatched"} 1"#,
+            r#"trigger_fires_total{action_type="telegram"} 1"#,
+            r#"trigger_fires_total{action_type="rest"} 1"#,
+        ] {
+            assert!(
+                rendered.contains(line),
+                "missing {} in:\n{}",
+                line,
+                rendered
+            );
+        }
+    }
 }
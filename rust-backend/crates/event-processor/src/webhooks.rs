@@ -0,0 +1,423 @@
+//! Trigger fire webhooks
+//!
+//! Organizations can register endpoints (`webhooks` table) that receive every
+//! trigger fire as a signed event, independent of the trigger's own actions.
+//!
+//! Delivery is a transactional outbox: when a trigger matches, the processor
+//! inserts one `webhook_deliveries` row per matching webhook
+//! ([`enqueue_trigger_fire`]). [`WebhookDispatcher`] then claims due rows,
+//! POSTs them and records the outcome, retrying failures with exponential
+//! backoff until `max_attempts` is reached. The delivery rows double as the
+//! history shown by `GET /api/v1/webhooks/{id}/deliveries`.
+//!
+//! # Signature
+//!
+//! Payloads are signed like every other outbound delivery (see
+//! [`shared::signing`]); the row id is sent in `X-AgentAuri-Delivery` so
+//! receivers can drop duplicates after a retry.
+//!
+//! # Configuration
+//!
+//! - `WEBHOOK_DELIVERY_TIMEOUT_MS`: per-request timeout (default: 5000)
+//! - `WEBHOOK_MAX_ATTEMPTS`: attempts before a delivery is marked failed (default: 6)
+//! - `WEBHOOK_RETRY_BASE_SECS`: delay after the first failure, doubling each
+//!   attempt up to one hour (default: 30)
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use reqwest::{header, Client};
+use serde_json::json;
+use shared::models::{Event, Trigger};
+use shared::signing::{sign_payload, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use shared::DbPool;
+use tokio_util::sync::CancellationToken;
+
+/// Header carrying the delivery id (stable across retries)
+pub const DELIVERY_HEADER: &str = "X-AgentAuri-Delivery";
+
+/// How often the dispatcher looks for due deliveries
+pub const DEFAULT_DISPATCH_INTERVAL_SECS: u64 = 5;
+
+/// Default per-request timeout in milliseconds
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+
+/// Default number of attempts per delivery
+const DEFAULT_MAX_ATTEMPTS: i32 = 6;
+
+/// Default delay after the first failed attempt in seconds
+const DEFAULT_RETRY_BASE_SECS: u64 = 30;
+
+/// Longest delay between attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+/// Deliveries claimed per dispatch pass
+const BATCH_SIZE: i64 = 50;
+
+/// Longest error message stored on a delivery
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// Queue deliveries of a trigger fire to the organization's webhooks
+///
+/// Only enabled webhooks whose `event_types` is empty or contains the event's
+/// type receive it. Reprocessing the same event does not queue duplicates.
+///
+/// # Returns
+///
+/// Number of deliveries queued
+pub async fn enqueue_trigger_fire(
+    db_pool: &DbPool,
+    trigger: &Trigger,
+    event: &Event,
+    event_data: &serde_json::Value,
+    correlation_id: &str,
+) -> Result<u64> {
+    let payload = json!({
+        "type": "trigger.fired",
+        "fired_at": Utc::now(),
+        "organization_id": trigger.organization_id,
+        "correlation_id": correlation_id,
+        "trigger": {
+            "id": trigger.id,
+            "name": trigger.name,
+        },
+        "event": event_data,
+    });
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO webhook_deliveries (webhook_id, trigger_id, event_id, event_type, payload)
+        SELECT id, $2, $3, $4, $5
+        FROM webhooks
+        WHERE organization_id = $1
+          AND enabled = TRUE
+          AND (cardinality(event_types) = 0 OR $4 = ANY(event_types))
+        ON CONFLICT (webhook_id, trigger_id, event_id) DO NOTHING
+        "#,
+    )
+    .bind(&trigger.organization_id)
+    .bind(&trigger.id)
+    .bind(&event.id)
+    .bind(&event.event_type)
+    .bind(&payload)
+    .execute(db_pool)
+    .await
+    .context("Failed to queue webhook deliveries")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Dispatcher configuration
+#[derive(Debug, Clone)]
+pub struct WebhookDispatchConfig {
+    /// Per-request timeout
+    pub timeout: Duration,
+    /// Attempts before a delivery is marked failed
+    pub max_attempts: i32,
+    /// Delay after the first failed attempt (doubles each attempt)
+    pub retry_base: Duration,
+}
+
+impl Default for WebhookDispatchConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_base: Duration::from_secs(DEFAULT_RETRY_BASE_SECS),
+        }
+    }
+}
+
+impl WebhookDispatchConfig {
+    /// Load from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let env_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+        };
+
+        Self {
+            timeout: Duration::from_millis(
+                env_u64("WEBHOOK_DELIVERY_TIMEOUT_MS").unwrap_or(DEFAULT_TIMEOUT_MS),
+            ),
+            max_attempts: env_u64("WEBHOOK_MAX_ATTEMPTS")
+                .and_then(|v| i32::try_from(v).ok())
+                .unwrap_or(DEFAULT_MAX_ATTEMPTS),
+            retry_base: Duration::from_secs(
+                env_u64("WEBHOOK_RETRY_BASE_SECS").unwrap_or(DEFAULT_RETRY_BASE_SECS),
+            ),
+        }
+    }
+
+    /// Delay before the next attempt after `attempts` failed attempts
+    pub fn retry_delay(&self, attempts: i32) -> Duration {
+        let exponent = u32::try_from(attempts.saturating_sub(1)).unwrap_or(0);
+        let factor = 2u32.saturating_pow(exponent);
+        std::cmp::min(self.retry_base.saturating_mul(factor), MAX_RETRY_DELAY)
+    }
+}
+
+/// Deliveries handled by one dispatch pass
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchReport {
+    pub succeeded: u64,
+    /// Failed attempts that will be retried
+    pub retrying: u64,
+    /// Deliveries that used their last attempt
+    pub failed: u64,
+}
+
+/// A claimed delivery with its endpoint
+#[derive(Debug, sqlx::FromRow)]
+struct ClaimedDelivery {
+    id: i64,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+/// Outcome of one HTTP attempt
+struct AttemptOutcome {
+    status: Option<u16>,
+    error: Option<String>,
+}
+
+/// Sends queued trigger fire deliveries
+pub struct WebhookDispatcher {
+    db_pool: DbPool,
+    client: Client,
+    config: WebhookDispatchConfig,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher
+    pub fn new(db_pool: DbPool, config: WebhookDispatchConfig) -> Result<Self> {
+        // Redirects are not followed so a registered URL cannot bounce the
+        // request to an internal host
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .context("Failed to create webhook HTTP client")?;
+
+        Ok(Self {
+            db_pool,
+            client,
+            config,
+        })
+    }
+
+    /// Deliver every due delivery once
+    pub async fn dispatch_once(&self) -> Result<DispatchReport> {
+        // Claiming pushes next_attempt_at past the request timeout, so a
+        // delivery held by a crashed process becomes due again on its own
+        let lease_secs = (self.config.timeout.as_secs_f64() * 2.0).max(30.0);
+        let claimed = sqlx::query_as::<_, ClaimedDelivery>(
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM webhook_deliveries d
+                JOIN webhooks w ON w.id = d.webhook_id
+                WHERE d.status = 'pending'
+                  AND d.next_attempt_at <= NOW()
+                  AND w.enabled = TRUE
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET attempts = d.attempts + 1,
+                next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, webhooks w
+            WHERE d.id = due.id AND w.id = d.webhook_id
+            RETURNING d.id, d.payload, d.attempts, w.url, w.secret
+            "#,
+        )
+        .bind(BATCH_SIZE)
+        .bind(lease_secs)
+        .fetch_all(&self.db_pool)
+        .await
+        .context("Failed to claim webhook deliveries")?;
+
+        let mut report = DispatchReport::default();
+        for delivery in claimed {
+            let outcome = self.attempt(&delivery).await;
+            let recorded = match &outcome.error {
+                None => {
+                    report.succeeded += 1;
+                    self.record_success(&delivery, outcome.status).await
+                }
+                Some(error) => {
+                    let exhausted = delivery.attempts >= self.config.max_attempts;
+                    if exhausted {
+                        report.failed += 1;
+                    } else {
+                        report.retrying += 1;
+                    }
+                    tracing::warn!(
+                        delivery_id = delivery.id,
+                        attempts = delivery.attempts,
+                        exhausted = exhausted,
+                        error = %error,
+                        "Webhook delivery failed"
+                    );
+                    self.record_failure(&delivery, outcome.status, error, exhausted)
+                        .await
+                }
+            };
+            if let Err(e) = recorded {
+                tracing::error!(
+                    delivery_id = delivery.id,
+                    error = %e,
+                    "Failed to record webhook delivery outcome"
+                );
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Dispatch due deliveries every interval until cancelled
+    pub async fn run(self, interval: Duration, cancel: CancellationToken) {
+        tracing::info!(
+            interval_secs = interval.as_secs(),
+            max_attempts = self.config.max_attempts,
+            "Webhook dispatcher started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    tracing::info!("Webhook dispatcher stopping");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    match self.dispatch_once().await {
+                        Ok(report) if report != DispatchReport::default() => {
+                            tracing::info!(
+                                succeeded = report.succeeded,
+                                retrying = report.retrying,
+                                failed = report.failed,
+                                "Dispatched webhook deliveries"
+                            );
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::error!(error = %e, "Webhook dispatch failed");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// POST one delivery
+    async fn attempt(&self, delivery: &ClaimedDelivery) -> AttemptOutcome {
+        let body = match serde_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(e) => {
+                return AttemptOutcome {
+                    status: None,
+                    error: Some(format!("Failed to serialize payload: {}", e)),
+                }
+            }
+        };
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&delivery.secret, timestamp, &body);
+
+        let response = self
+            .client
+            .post(&delivery.url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => AttemptOutcome {
+                status: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => AttemptOutcome {
+                status: Some(response.status().as_u16()),
+                error: Some(format!("Endpoint returned status {}", response.status())),
+            },
+            Err(e) => AttemptOutcome {
+                status: None,
+                error: Some(format!("Request failed: {}", e)),
+            },
+        }
+    }
+
+    async fn record_success(&self, delivery: &ClaimedDelivery, status: Option<u16>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'succeeded', delivered_at = NOW(),
+                last_response_status = $2, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(status.map(i32::from))
+        .execute(&self.db_pool)
+        .await
+        .context("Failed to mark webhook delivery succeeded")?;
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        delivery: &ClaimedDelivery,
+        status: Option<u16>,
+        error: &str,
+        exhausted: bool,
+    ) -> Result<()> {
+        let error: String = error.chars().take(MAX_ERROR_LENGTH).collect();
+        let retry_secs = self.config.retry_delay(delivery.attempts).as_secs_f64();
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = CASE WHEN $4 THEN 'failed' ELSE 'pending' END,
+                next_attempt_at = NOW() + make_interval(secs => $5),
+                last_response_status = $2, last_error = $3
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(status.map(i32::from))
+        .bind(error)
+        .bind(exhausted)
+        .bind(retry_secs)
+        .execute(&self.db_pool)
+        .await
+        .context("Failed to record webhook delivery failure")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_cap() {
+        let config = WebhookDispatchConfig::default();
+
+        assert_eq!(config.retry_delay(1), Duration::from_secs(30));
+        assert_eq!(config.retry_delay(2), Duration::from_secs(60));
+        assert_eq!(config.retry_delay(3), Duration::from_secs(120));
+        assert_eq!(config.retry_delay(20), MAX_RETRY_DELAY);
+        assert_eq!(config.retry_delay(i32::MAX), MAX_RETRY_DELAY);
+    }
+}
@@ -0,0 +1,217 @@
+//! Integration tests for credit checks before enqueuing actions
+//!
+//! Tests cover:
+//! - An organization without credits getting no paid action enqueued
+//! - Actions enqueued in priority order until the balance runs out
+//! - An organization with enough credits getting every action enqueued
+
+use anyhow::Result;
+use event_processor::credits::set_action_costs;
+use event_processor::processor::process_event;
+use event_processor::queue::JobQueue;
+use event_processor::state_manager::TriggerStateManager;
+use serde_json::json;
+use shared::{ActionCostsConfig, ActionJob};
+use sqlx::PgPool;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+const COSTS: ActionCostsConfig = ActionCostsConfig {
+    telegram: 2_000,
+    rest: 3_000,
+    mcp: 5_000,
+};
+
+/// Job queue that keeps enqueued jobs in memory
+#[derive(Default)]
+struct RecordingJobQueue {
+    jobs: Mutex<Vec<ActionJob>>,
+}
+
+impl RecordingJobQueue {
+    /// Action types of the enqueued jobs, in enqueue order
+    fn channels(&self) -> Vec<String> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|job| job.action_type.to_string())
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl JobQueue for RecordingJobQueue {
+    async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        self.jobs.lock().unwrap().push(job.clone());
+        Ok(())
+    }
+}
+
+async fn setup_test_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database")
+}
+
+/// Organization with `balance` credits (no credits row for `None`) and one
+/// trigger with a telegram action (higher priority) and a rest action.
+/// Returns the chain ID, which no other test uses.
+async fn create_test_trigger(pool: &PgPool, suffix: &str, balance: Option<i64>) -> i32 {
+    let user_id = format!("credits_user_{}", suffix);
+    let org_id = format!("credits_org_{}", suffix);
+    let trigger_id = format!("credits_trigger_{}", suffix);
+    let chain_id = 900_000_000 + (Uuid::new_v4().as_u128() % 100_000_000) as i32;
+
+    sqlx::query("INSERT INTO users (id, username, email, password_hash) VALUES ($1, $1, $1 || '@example.com', 'hash')")
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO organizations (id, name, slug, owner_id, plan, is_personal) VALUES ($1, $1, $1, $2, 'free', false)")
+        .bind(&org_id)
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    if let Some(balance) = balance {
+        sqlx::query("INSERT INTO credits (organization_id, balance) VALUES ($1, $2)")
+            .bind(&org_id)
+            .bind(balance)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO triggers (id, organization_id, user_id, name, chain_id, registry, enabled, is_stateful)
+        VALUES ($1, $2, $3, 'Paid Trigger', $4, 'reputation', true, false)
+        "#,
+    )
+    .bind(&trigger_id)
+    .bind(&org_id)
+    .bind(&user_id)
+    .bind(chain_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    for (action_type, priority, config) in [
+        ("telegram", 1, json!({"chat_id": "123456789"})),
+        ("rest", 0, json!({"url": "https://example.com/hook"})),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO trigger_actions (trigger_id, action_type, priority, config)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(&trigger_id)
+        .bind(action_type)
+        .bind(priority)
+        .bind(config)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    chain_id
+}
+
+/// Process one event matching the trigger and return the channels enqueued
+async fn fire(pool: &PgPool, suffix: &str, chain_id: i32) -> Vec<String> {
+    let event_id = format!("credits_event_{}", suffix);
+    sqlx::query(
+        r#"
+        INSERT INTO events (
+            id, chain_id, block_number, block_hash, transaction_hash, log_index,
+            registry, event_type, agent_id, timestamp, score
+        )
+        VALUES ($1, $2, 1000, '0xabc', '0xdef', 1, 'reputation', 'NewFeedback', 42,
+                EXTRACT(EPOCH FROM NOW())::BIGINT + 1, 50)
+        "#,
+    )
+    .bind(&event_id)
+    .bind(chain_id)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    set_action_costs(COSTS);
+    let queue = RecordingJobQueue::default();
+    let state_manager = TriggerStateManager::new(pool.clone());
+    process_event(&event_id, pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    queue.channels()
+}
+
+async fn cleanup(pool: &PgPool, suffix: &str) {
+    let event_id = format!("credits_event_{}", suffix);
+    sqlx::query("DELETE FROM processed_events WHERE event_id = $1")
+        .bind(&event_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM events WHERE id = $1")
+        .bind(&event_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    // Deleting the organization cascades to its credits and triggers
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(format!("credits_org_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(format!("credits_user_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_organization_without_credits_enqueues_nothing() {
+    let pool = setup_test_db().await;
+
+    for balance in [None, Some(0)] {
+        let suffix = Uuid::new_v4().simple().to_string();
+        let chain_id = create_test_trigger(&pool, &suffix, balance).await;
+
+        assert!(fire(&pool, &suffix, chain_id).await.is_empty());
+
+        cleanup(&pool, &suffix).await;
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_actions_enqueued_until_balance_runs_out() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let chain_id = create_test_trigger(&pool, &suffix, Some(4_000)).await;
+
+    // Telegram (2000) fits, rest (3000) no longer does
+    assert_eq!(fire(&pool, &suffix, chain_id).await, vec!["telegram"]);
+
+    cleanup(&pool, &suffix).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_sufficient_credits_enqueue_every_action() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let chain_id = create_test_trigger(&pool, &suffix, Some(5_000)).await;
+
+    assert_eq!(
+        fire(&pool, &suffix, chain_id).await,
+        vec!["telegram", "rest"]
+    );
+
+    cleanup(&pool, &suffix).await;
+}
@@ -0,0 +1,238 @@
+//! Integration tests for event claims
+//!
+//! Tests cover:
+//! - Concurrent processing of the same event firing its trigger once
+//! - An event claimed elsewhere being skipped until the claim expires
+
+use anyhow::Result;
+use event_processor::processor::process_event;
+use event_processor::queue::JobQueue;
+use event_processor::state_manager::TriggerStateManager;
+use serde_json::json;
+use shared::ActionJob;
+use sqlx::PgPool;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Job queue that keeps enqueued jobs in memory
+#[derive(Default)]
+struct RecordingJobQueue {
+    jobs: Mutex<Vec<ActionJob>>,
+}
+
+impl RecordingJobQueue {
+    fn job_count(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+}
+
+#[async_trait::async_trait]
+impl JobQueue for RecordingJobQueue {
+    async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        // Widen the window in which a second processor could overlap
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.jobs.lock().unwrap().push(job.clone());
+        Ok(())
+    }
+}
+
+async fn setup_test_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database")
+}
+
+/// Create a user, organization and a `score < 60` trigger with one action
+async fn create_test_trigger(pool: &PgPool, suffix: &str) {
+    let user_id = format!("claim_user_{}", suffix);
+    let org_id = format!("claim_org_{}", suffix);
+    let trigger_id = format!("claim_trigger_{}", suffix);
+
+    sqlx::query("INSERT INTO users (id, username, email, password_hash) VALUES ($1, $1, $1 || '@example.com', 'hash')")
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO organizations (id, name, slug, owner_id, plan, is_personal) VALUES ($1, $1, $1, $2, 'free', false)")
+        .bind(&org_id)
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO triggers (id, organization_id, user_id, name, chain_id, registry, enabled, is_stateful)
+        VALUES ($1, $2, $3, 'Claim Trigger', 84532, 'reputation', true, false)
+        "#,
+    )
+    .bind(&trigger_id)
+    .bind(&org_id)
+    .bind(&user_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO trigger_conditions (trigger_id, condition_type, field, operator, value)
+        VALUES ($1, 'score_threshold', 'score', '<', '60')
+        "#,
+    )
+    .bind(&trigger_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO trigger_actions (trigger_id, action_type, priority, config)
+        VALUES ($1, 'rest', 1, $2)
+        "#,
+    )
+    .bind(&trigger_id)
+    .bind(json!({"url": "https://example.com/hook"}))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Insert a feedback event with a matching score (50)
+async fn create_test_event(pool: &PgPool, event_id: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO events (
+            id, chain_id, block_number, block_hash, transaction_hash, log_index,
+            registry, event_type, agent_id, timestamp, score
+        )
+        VALUES ($1, 84532, 1000, '0xabc', '0xdef', 1, 'reputation', 'NewFeedback', 42, EXTRACT(EPOCH FROM NOW())::BIGINT + 1, 50)
+        "#,
+    )
+    .bind(event_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn is_processed(pool: &PgPool, event_id: &str) -> bool {
+    sqlx::query_scalar("SELECT is_event_processed($1)")
+        .bind(event_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn cleanup(pool: &PgPool, suffix: &str) {
+    let pattern = format!("claim_event_{}_%", suffix);
+    for table in ["event_claims", "processed_events"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE event_id LIKE $1", table))
+            .bind(&pattern)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query("DELETE FROM events WHERE id LIKE $1")
+        .bind(&pattern)
+        .execute(pool)
+        .await
+        .unwrap();
+    // Deleting the organization cascades to the trigger
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(format!("claim_org_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(format!("claim_user_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_concurrent_processing_fires_once() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    create_test_trigger(&pool, &suffix).await;
+
+    for i in 0..5 {
+        let event_id = format!("claim_event_{}_{}", suffix, i);
+        create_test_event(&pool, &event_id).await;
+
+        // The NOTIFY listener and the polling fallback each use their own
+        // state manager and share nothing but the database
+        let queue = RecordingJobQueue::default();
+        let (first, second) = tokio::join!(
+            tokio::spawn({
+                let (pool, event_id) = (pool.clone(), event_id.clone());
+                async move {
+                    let queue = RecordingJobQueue::default();
+                    let state_manager = TriggerStateManager::new(pool.clone());
+                    process_event(&event_id, &pool, &queue, &state_manager)
+                        .await
+                        .map(|()| queue.job_count())
+                }
+            }),
+            async {
+                let state_manager = TriggerStateManager::new(pool.clone());
+                process_event(&event_id, &pool, &queue, &state_manager)
+                    .await
+                    .map(|()| queue.job_count())
+            }
+        );
+
+        let fired = first.unwrap().unwrap() + second.unwrap();
+        assert_eq!(fired, 1, "event {} fired {} times", event_id, fired);
+        assert!(is_processed(&pool, &event_id).await);
+    }
+
+    cleanup(&pool, &suffix).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_claimed_event_skipped_until_claim_expires() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    create_test_trigger(&pool, &suffix).await;
+    let state_manager = TriggerStateManager::new(pool.clone());
+    let queue = RecordingJobQueue::default();
+
+    let event_id = format!("claim_event_{}_1", suffix);
+    create_test_event(&pool, &event_id).await;
+
+    // Another processor holds the claim
+    sqlx::query("INSERT INTO event_claims (event_id, processor_instance) VALUES ($1, 'other')")
+        .bind(&event_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    process_event(&event_id, &pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    assert_eq!(queue.job_count(), 0);
+    assert!(!is_processed(&pool, &event_id).await);
+
+    // ...and died without releasing it
+    sqlx::query(
+        "UPDATE event_claims SET claimed_at = NOW() - INTERVAL '1 hour' WHERE event_id = $1",
+    )
+    .bind(&event_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    process_event(&event_id, &pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    assert_eq!(queue.job_count(), 1);
+    assert!(is_processed(&pool, &event_id).await);
+
+    let claims: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_claims WHERE event_id = $1")
+        .bind(&event_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(claims, 0);
+
+    cleanup(&pool, &suffix).await;
+}
@@ -0,0 +1,55 @@
+//! Integration tests for events that are not visible when notified
+//!
+//! NOTIFY payloads only carry an event ID; `process_event` reads the event
+//! from `ponder_events` and reports an ID it cannot find as `EventNotVisible`,
+//! without marking it processed, so the polling fallback can retry it.
+
+use anyhow::Result;
+use event_processor::processor::{process_event, EventNotVisible};
+use event_processor::queue::JobQueue;
+use event_processor::state_manager::TriggerStateManager;
+use shared::ActionJob;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Job queue that rejects every job (none should be enqueued)
+struct RejectingJobQueue;
+
+#[async_trait::async_trait]
+impl JobQueue for RejectingJobQueue {
+    async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        anyhow::bail!("Unexpected job for event {}", job.event_id)
+    }
+}
+
+async fn setup_test_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database")
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_missing_event_is_not_visible() {
+    let pool = setup_test_db().await;
+    let state_manager = TriggerStateManager::new(pool.clone());
+    let event_id = format!("visibility_test_{}", Uuid::new_v4());
+
+    let err = process_event(&event_id, &pool, &RejectingJobQueue, &state_manager)
+        .await
+        .unwrap_err();
+    let not_visible = err
+        .downcast_ref::<EventNotVisible>()
+        .expect("missing event should be reported as not visible");
+    assert_eq!(not_visible.event_id, event_id);
+
+    // Not marked processed, so the polling fallback still sees it
+    let (processed,): (bool,) = sqlx::query_as("SELECT is_event_processed($1)")
+        .bind(&event_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(!processed);
+}
@@ -0,0 +1,291 @@
+//! Integration tests for agent follow notification preferences
+//!
+//! Tests cover:
+//! - Events of a type the follow is not notified about enqueueing nothing
+//! - Events of an allowed type enqueueing actions
+//! - Only actions on allowed channels being enqueued
+//! - A follow without preferences notifying about everything
+
+use anyhow::Result;
+use event_processor::processor::process_event;
+use event_processor::queue::JobQueue;
+use event_processor::state_manager::TriggerStateManager;
+use serde_json::json;
+use shared::ActionJob;
+use sqlx::PgPool;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+const AGENT_ID: i64 = 42;
+
+/// Job queue that keeps enqueued jobs in memory
+#[derive(Default)]
+struct RecordingJobQueue {
+    jobs: Mutex<Vec<ActionJob>>,
+}
+
+impl RecordingJobQueue {
+    /// Action types of the enqueued jobs, sorted
+    fn channels(&self) -> Vec<String> {
+        let mut channels: Vec<String> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|job| job.action_type.to_string())
+            .collect();
+        channels.sort();
+        channels
+    }
+}
+
+#[async_trait::async_trait]
+impl JobQueue for RecordingJobQueue {
+    async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        self.jobs.lock().unwrap().push(job.clone());
+        Ok(())
+    }
+}
+
+async fn setup_test_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database")
+}
+
+/// Follow [`AGENT_ID`] the way the API does: one trigger per registry, each
+/// with a telegram and a rest action. Returns the chain ID, which no other
+/// test uses.
+async fn create_test_follow(
+    pool: &PgPool,
+    suffix: &str,
+    preferences: Option<serde_json::Value>,
+) -> i32 {
+    let user_id = format!("prefs_user_{}", suffix);
+    let org_id = format!("prefs_org_{}", suffix);
+    let chain_id = 900_000_000 + (Uuid::new_v4().as_u128() % 100_000_000) as i32;
+
+    sqlx::query("INSERT INTO users (id, username, email, password_hash) VALUES ($1, $1, $1 || '@example.com', 'hash')")
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO organizations (id, name, slug, owner_id, plan, is_personal) VALUES ($1, $1, $1, $2, 'free', false)")
+        .bind(&org_id)
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let mut trigger_ids = Vec::new();
+    for registry in ["identity", "reputation", "validation"] {
+        let trigger_id = format!("prefs_trigger_{}_{}", suffix, registry);
+        sqlx::query(
+            r#"
+            INSERT INTO triggers (id, organization_id, user_id, name, chain_id, registry, enabled, is_stateful)
+            VALUES ($1, $2, $3, 'Follow Agent', $4, $5, true, false)
+            "#,
+        )
+        .bind(&trigger_id)
+        .bind(&org_id)
+        .bind(&user_id)
+        .bind(chain_id)
+        .bind(registry)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO trigger_conditions (trigger_id, condition_type, field, operator, value)
+            VALUES ($1, 'agent_id_equals', 'agent_id', '=', $2)
+            "#,
+        )
+        .bind(&trigger_id)
+        .bind(AGENT_ID.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+        for (priority, (action_type, config)) in [
+            ("telegram", json!({"chat_id": "123456789"})),
+            ("rest", json!({"url": "https://example.com/hook"})),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            sqlx::query(
+                r#"
+                INSERT INTO trigger_actions (trigger_id, action_type, priority, config)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(&trigger_id)
+            .bind(action_type)
+            .bind(priority as i32)
+            .bind(config)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        trigger_ids.push(trigger_id);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO agent_follows
+            (agent_id, chain_id, organization_id, user_id,
+             trigger_identity_id, trigger_reputation_id, trigger_validation_id,
+             notification_preferences)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(AGENT_ID)
+    .bind(chain_id)
+    .bind(&org_id)
+    .bind(&user_id)
+    .bind(&trigger_ids[0])
+    .bind(&trigger_ids[1])
+    .bind(&trigger_ids[2])
+    .bind(preferences)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    chain_id
+}
+
+/// Insert an event of the followed agent
+async fn create_test_event(
+    pool: &PgPool,
+    event_id: &str,
+    chain_id: i32,
+    registry: &str,
+    event_type: &str,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO events (
+            id, chain_id, block_number, block_hash, transaction_hash, log_index,
+            registry, event_type, agent_id, timestamp, score
+        )
+        VALUES ($1, $2, 1000, '0xabc', '0xdef', 1, $3, $4, $5, EXTRACT(EPOCH FROM NOW())::BIGINT + 1, 50)
+        "#,
+    )
+    .bind(event_id)
+    .bind(chain_id)
+    .bind(registry)
+    .bind(event_type)
+    .bind(AGENT_ID)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Process one event of the followed agent and return the channels notified
+async fn notify(
+    pool: &PgPool,
+    suffix: &str,
+    chain_id: i32,
+    registry: &str,
+    event_type: &str,
+) -> Vec<String> {
+    let event_id = format!("prefs_event_{}_{}", suffix, event_type);
+    create_test_event(pool, &event_id, chain_id, registry, event_type).await;
+
+    let queue = RecordingJobQueue::default();
+    let state_manager = TriggerStateManager::new(pool.clone());
+    process_event(&event_id, pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    queue.channels()
+}
+
+async fn cleanup(pool: &PgPool, suffix: &str) {
+    let pattern = format!("prefs_event_{}_%", suffix);
+    sqlx::query("DELETE FROM processed_events WHERE event_id LIKE $1")
+        .bind(&pattern)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM events WHERE id LIKE $1")
+        .bind(&pattern)
+        .execute(pool)
+        .await
+        .unwrap();
+    // Deleting the organization cascades to the triggers and the follow
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(format!("prefs_org_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(format!("prefs_user_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_disabled_event_type_is_not_notified() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let chain_id = create_test_follow(
+        &pool,
+        &suffix,
+        Some(json!({"event_types": ["NewFeedback"]})),
+    )
+    .await;
+
+    assert_eq!(
+        notify(&pool, &suffix, chain_id, "reputation", "NewFeedback").await,
+        vec!["rest", "telegram"]
+    );
+    assert!(
+        notify(&pool, &suffix, chain_id, "reputation", "FeedbackRevoked")
+            .await
+            .is_empty()
+    );
+    assert!(
+        notify(&pool, &suffix, chain_id, "validation", "ValidationRequest")
+            .await
+            .is_empty()
+    );
+
+    cleanup(&pool, &suffix).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_only_allowed_channels_are_notified() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let chain_id = create_test_follow(&pool, &suffix, Some(json!({"channels": ["rest"]}))).await;
+
+    assert_eq!(
+        notify(&pool, &suffix, chain_id, "reputation", "NewFeedback").await,
+        vec!["rest"]
+    );
+
+    cleanup(&pool, &suffix).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_follow_without_preferences_notifies_everything() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let chain_id = create_test_follow(&pool, &suffix, None).await;
+
+    assert_eq!(
+        notify(&pool, &suffix, chain_id, "reputation", "FeedbackRevoked").await,
+        vec!["rest", "telegram"]
+    );
+    assert_eq!(
+        notify(&pool, &suffix, chain_id, "validation", "ValidationRequest").await,
+        vec!["rest", "telegram"]
+    );
+
+    cleanup(&pool, &suffix).await;
+}
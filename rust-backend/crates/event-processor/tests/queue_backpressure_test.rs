@@ -0,0 +1,118 @@
+//! Integration tests for action queue backpressure
+//!
+//! Tests cover:
+//! - Enqueueing below the high-water mark
+//! - Low-priority jobs going to the DLQ once the mark is reached
+//! - Other jobs waiting for the queue to drain, then going to the DLQ
+//!
+//! Keys live under a random `REDIS_KEY_PREFIX`, so the tests do not touch
+//! real queues.
+
+use event_processor::queue::{JobQueue, RedisJobQueue};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde_json::json;
+use shared::redis::queue_key;
+use shared::{
+    ActionJob, ActionQueueConfig, ActionType, ActionWorkerPools, DlqEntry, QueuePriority,
+    ACTION_JOBS_DLQ, ACTION_JOBS_QUEUE,
+};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const MAX_DEPTH: usize = 3;
+const MAX_WAIT_MS: u64 = 300;
+
+async fn setup_test_redis() -> MultiplexedConnection {
+    let prefix = format!("bp-test-{}", &Uuid::new_v4().simple().to_string()[..8]);
+    shared::redis::init_key_prefix(Some(&prefix)).expect("Failed to set Redis key prefix");
+
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    redis::Client::open(redis_url)
+        .expect("Failed to create Redis client")
+        .get_multiplexed_async_connection()
+        .await
+        .expect("Redis must be running for this test")
+}
+
+fn create_test_job(priority: i32) -> ActionJob {
+    ActionJob::new(
+        "trigger-backpressure",
+        "event-backpressure",
+        ActionType::Rest,
+        priority,
+        json!({"url": "https://example.com/hook"}),
+        json!({"agent_id": 42}),
+    )
+}
+
+async fn len(conn: &mut MultiplexedConnection, queue: &str) -> usize {
+    conn.llen(queue_key(queue)).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore] // Requires Redis (integration test)
+async fn test_enqueue_applies_backpressure_over_max_depth() {
+    let mut conn = setup_test_redis().await;
+    let queue = RedisJobQueue::with_pools(
+        conn.clone(),
+        ActionWorkerPools::default(),
+        ActionQueueConfig {
+            max_depth: MAX_DEPTH,
+            max_wait_ms: MAX_WAIT_MS,
+            low_priority: 0,
+        },
+    );
+
+    // Below the mark jobs are enqueued
+    for _ in 0..MAX_DEPTH {
+        queue.enqueue(&create_test_job(1)).await.unwrap();
+    }
+    assert_eq!(len(&mut conn, ACTION_JOBS_QUEUE).await, MAX_DEPTH);
+    assert_eq!(len(&mut conn, ACTION_JOBS_DLQ).await, 0);
+
+    // Low priority: straight to the DLQ
+    let started = Instant::now();
+    let low = create_test_job(0);
+    queue.enqueue(&low).await.unwrap();
+    assert!(started.elapsed() < Duration::from_millis(MAX_WAIT_MS));
+    assert_eq!(len(&mut conn, ACTION_JOBS_QUEUE).await, MAX_DEPTH);
+    assert_eq!(len(&mut conn, ACTION_JOBS_DLQ).await, 1);
+
+    // Other jobs wait for the queue to drain, then go to the DLQ
+    let started = Instant::now();
+    let high = create_test_job(10);
+    queue.enqueue(&high).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(MAX_WAIT_MS));
+    assert_eq!(len(&mut conn, ACTION_JOBS_QUEUE).await, MAX_DEPTH);
+    assert_eq!(len(&mut conn, ACTION_JOBS_DLQ).await, 2);
+
+    let newest: String = conn.lindex(queue_key(ACTION_JOBS_DLQ), 0).await.unwrap();
+    let entry: DlqEntry = serde_json::from_str(&newest).unwrap();
+    assert_eq!(entry.job.id, high.id);
+    assert_eq!(entry.attempts, 0);
+    assert!(entry.error.contains("ACTION_QUEUE_MAX_DEPTH"));
+
+    // A worker takes a job while the producer waits: the job is enqueued
+    let waiting = tokio::spawn({
+        let queue = queue.clone();
+        async move { queue.enqueue(&create_test_job(10)).await }
+    });
+    tokio::time::sleep(Duration::from_millis(MAX_WAIT_MS / 3)).await;
+    let _: Option<String> = conn.rpop(queue_key(ACTION_JOBS_QUEUE), None).await.unwrap();
+    waiting.await.unwrap().unwrap();
+    let high_lane = QueuePriority::High.lane(ACTION_JOBS_QUEUE);
+    assert_eq!(len(&mut conn, ACTION_JOBS_QUEUE).await, MAX_DEPTH - 1);
+    assert_eq!(len(&mut conn, &high_lane).await, 1);
+    assert_eq!(len(&mut conn, ACTION_JOBS_DLQ).await, 2);
+
+    let _: () = conn
+        .del(&[
+            queue_key(ACTION_JOBS_QUEUE),
+            queue_key(&high_lane),
+            queue_key(ACTION_JOBS_DLQ),
+        ])
+        .await
+        .unwrap();
+}
@@ -177,6 +177,58 @@ async fn test_state_manager_cleanup_expired() -> Result<()> {
     Ok(())
 }
 
+// Helper to insert state last updated `days_ago` days ago
+async fn insert_state_updated_days_ago(
+    pool: &PgPool,
+    trigger_id: &str,
+    days_ago: i32,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO trigger_state (trigger_id, state_data, last_updated)
+        VALUES ($1, $2, NOW() - make_interval(days => $3))
+        ON CONFLICT (trigger_id) DO UPDATE SET
+            state_data = EXCLUDED.state_data,
+            last_updated = EXCLUDED.last_updated
+        "#,
+    )
+    .bind(trigger_id)
+    .bind(json!({"count": days_ago}))
+    .bind(days_ago)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_state_manager_cleanup_uses_configured_retention() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let trigger_recent = "test_retention_recent";
+    let trigger_expired = "test_retention_expired";
+    create_test_trigger(&pool, trigger_recent).await?;
+    create_test_trigger(&pool, trigger_expired).await?;
+
+    insert_state_updated_days_ago(&pool, trigger_recent, 6).await?;
+    insert_state_updated_days_ago(&pool, trigger_expired, 8).await?;
+
+    let manager = TriggerStateManager::new(pool.clone());
+
+    let retention = shared::StateCleanupConfig {
+        retention_days: 7,
+        ..Default::default()
+    };
+    let deleted = manager.cleanup_expired(retention.retention_days).await?;
+    assert_eq!(deleted, 1);
+
+    assert!(manager.load_state(trigger_recent).await?.is_some());
+    assert!(manager.load_state(trigger_expired).await?.is_none());
+
+    manager.delete_state(trigger_recent).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore] // Requires DATABASE_URL (integration test)
 async fn test_state_manager_get_count() -> Result<()> {
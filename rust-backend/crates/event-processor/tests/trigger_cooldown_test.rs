@@ -0,0 +1,214 @@
+//! Integration tests for trigger cooldowns
+//!
+//! Tests cover:
+//! - Recording fires only once the cooldown has elapsed
+//! - Matching events within the cooldown not enqueueing actions
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use event_processor::processor::process_event;
+use event_processor::queue::JobQueue;
+use event_processor::state_manager::TriggerStateManager;
+use serde_json::json;
+use shared::ActionJob;
+use sqlx::PgPool;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Job queue that keeps enqueued jobs in memory
+#[derive(Default)]
+struct RecordingJobQueue {
+    jobs: Mutex<Vec<ActionJob>>,
+}
+
+impl RecordingJobQueue {
+    fn job_count(&self) -> usize {
+        self.jobs.lock().unwrap().len()
+    }
+}
+
+#[async_trait::async_trait]
+impl JobQueue for RecordingJobQueue {
+    async fn enqueue(&self, job: &ActionJob) -> Result<()> {
+        self.jobs.lock().unwrap().push(job.clone());
+        Ok(())
+    }
+}
+
+async fn setup_test_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database")
+}
+
+/// Create a user, organization and a `score < 60` trigger with one action
+async fn create_test_trigger(pool: &PgPool, suffix: &str, cooldown_secs: i32) -> String {
+    let user_id = format!("cooldown_user_{}", suffix);
+    let org_id = format!("cooldown_org_{}", suffix);
+    let trigger_id = format!("cooldown_trigger_{}", suffix);
+
+    sqlx::query("INSERT INTO users (id, username, email, password_hash) VALUES ($1, $1, $1 || '@example.com', 'hash')")
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO organizations (id, name, slug, owner_id, plan, is_personal) VALUES ($1, $1, $1, $2, 'free', false)")
+        .bind(&org_id)
+        .bind(&user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO triggers (id, organization_id, user_id, name, chain_id, registry, enabled, is_stateful, cooldown_secs)
+        VALUES ($1, $2, $3, 'Cooldown Trigger', 84532, 'reputation', true, false, $4)
+        "#,
+    )
+    .bind(&trigger_id)
+    .bind(&org_id)
+    .bind(&user_id)
+    .bind(cooldown_secs)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO trigger_conditions (trigger_id, condition_type, field, operator, value)
+        VALUES ($1, 'score_threshold', 'score', '<', '60')
+        "#,
+    )
+    .bind(&trigger_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO trigger_actions (trigger_id, action_type, priority, config)
+        VALUES ($1, 'rest', 1, $2)
+        "#,
+    )
+    .bind(&trigger_id)
+    .bind(json!({"url": "https://example.com/hook"}))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    trigger_id
+}
+
+/// Insert a feedback event with a matching score (50)
+async fn create_test_event(pool: &PgPool, event_id: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO events (
+            id, chain_id, block_number, block_hash, transaction_hash, log_index,
+            registry, event_type, agent_id, timestamp, score
+        )
+        VALUES ($1, 84532, 1000, '0xabc', '0xdef', 1, 'reputation', 'NewFeedback', 42, EXTRACT(EPOCH FROM NOW())::BIGINT + 1, 50)
+        "#,
+    )
+    .bind(event_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn cleanup(pool: &PgPool, suffix: &str) {
+    let pattern = format!("cooldown_event_{}_%", suffix);
+    sqlx::query("DELETE FROM processed_events WHERE event_id LIKE $1")
+        .bind(&pattern)
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM events WHERE id LIKE $1")
+        .bind(&pattern)
+        .execute(pool)
+        .await
+        .unwrap();
+    // Deleting the organization cascades to the trigger and its state
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(format!("cooldown_org_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(format!("cooldown_user_{}", suffix))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_record_fire_respects_cooldown() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let trigger_id = create_test_trigger(&pool, &suffix, 60).await;
+    let state_manager = TriggerStateManager::new(pool.clone());
+    let first = Utc::now();
+
+    assert!(state_manager
+        .record_fire(&trigger_id, 60, first)
+        .await
+        .unwrap());
+    assert!(!state_manager
+        .record_fire(&trigger_id, 60, first + Duration::seconds(10))
+        .await
+        .unwrap());
+    assert!(state_manager
+        .record_fire(&trigger_id, 60, first + Duration::seconds(60))
+        .await
+        .unwrap());
+    // The cooldown restarts from the last recorded fire
+    assert!(!state_manager
+        .record_fire(&trigger_id, 60, first + Duration::seconds(90))
+        .await
+        .unwrap());
+
+    cleanup(&pool, &suffix).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_event_within_cooldown_does_not_enqueue() {
+    let pool = setup_test_db().await;
+    let suffix = Uuid::new_v4().simple().to_string();
+    let trigger_id = create_test_trigger(&pool, &suffix, 3600).await;
+    let state_manager = TriggerStateManager::new(pool.clone());
+    let queue = RecordingJobQueue::default();
+
+    let first = format!("cooldown_event_{}_1", suffix);
+    create_test_event(&pool, &first).await;
+    process_event(&first, &pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    assert_eq!(queue.job_count(), 1);
+
+    // Matches again within the cooldown: nothing is enqueued
+    let second = format!("cooldown_event_{}_2", suffix);
+    create_test_event(&pool, &second).await;
+    process_event(&second, &pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    assert_eq!(queue.job_count(), 1);
+
+    // The last fire is persisted; move it past the cooldown
+    sqlx::query(
+        "UPDATE trigger_state SET last_fired_at = NOW() - INTERVAL '2 hours' WHERE trigger_id = $1",
+    )
+    .bind(&trigger_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let third = format!("cooldown_event_{}_3", suffix);
+    create_test_event(&pool, &third).await;
+    process_event(&third, &pool, &queue, &state_manager)
+        .await
+        .unwrap();
+    assert_eq!(queue.job_count(), 2);
+
+    cleanup(&pool, &suffix).await;
+}
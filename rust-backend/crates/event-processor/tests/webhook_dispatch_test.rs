@@ -0,0 +1,381 @@
+//! Integration tests for trigger fire webhooks
+//!
+//! Tests cover:
+//! - Queueing deliveries for matching, enabled webhooks only
+//! - Idempotent queueing when an event is reprocessed
+//! - Signed delivery and success recording
+//! - Retry scheduling and failure after the last attempt
+
+use anyhow::Result;
+use event_processor::webhooks::{enqueue_trigger_fire, DELIVERY_HEADER};
+use event_processor::{WebhookDispatchConfig, WebhookDispatcher};
+use serde_json::json;
+use shared::models::{Event, Trigger};
+use shared::signing::{sign_payload, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, Mutex};
+
+const SECRET: &str = "whsec_test_secret_0123456789";
+
+// The dispatcher claims every due delivery in the database, so tests that
+// queue or dispatch deliveries must not run concurrently
+static DISPATCH_LOCK: Mutex<()> = Mutex::const_new(());
+
+// Test database setup helper
+async fn setup_test_db() -> Result<PgPool> {
+    let database_url =
+        std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+
+    let pool = PgPool::connect(&database_url).await?;
+    Ok(pool)
+}
+
+// Create a user and organization owning the test webhooks
+async fn create_test_org(pool: &PgPool, suffix: &str) -> Result<String> {
+    let user_id = format!("test_user_webhook_{}", suffix);
+    let org_id = format!("test_org_webhook_{}", suffix);
+
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, username, email, password_hash, created_at)
+        VALUES ($1, $2, $3, 'test_hash', NOW())
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(&user_id)
+    .bind(format!("user_webhook_{}", suffix))
+    .bind(format!("{}@test.com", user_id))
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO organizations (id, name, slug, owner_id, is_personal, created_at)
+        VALUES ($1, $2, $3, $4, true, NOW())
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(&org_id)
+    .bind(format!("Org {}", suffix))
+    .bind(format!("org-webhook-{}", suffix))
+    .bind(&user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(org_id)
+}
+
+async fn create_test_webhook(
+    pool: &PgPool,
+    org_id: &str,
+    url: &str,
+    event_types: &[&str],
+    enabled: bool,
+) -> Result<String> {
+    let webhook_id = uuid::Uuid::new_v4().to_string();
+    let event_types: Vec<String> = event_types.iter().map(|t| t.to_string()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO webhooks (id, organization_id, url, secret, event_types, enabled)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&webhook_id)
+    .bind(org_id)
+    .bind(url)
+    .bind(SECRET)
+    .bind(&event_types)
+    .bind(enabled)
+    .execute(pool)
+    .await?;
+
+    Ok(webhook_id)
+}
+
+async fn cleanup(pool: &PgPool, suffix: &str) -> Result<()> {
+    // Deleting the organization cascades to its webhooks and deliveries
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(format!("test_org_webhook_{}", suffix))
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(format!("test_user_webhook_{}", suffix))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+fn test_trigger(org_id: &str) -> Trigger {
+    serde_json::from_value(json!({
+        "id": "trigger_webhook_test",
+        "user_id": "test_user",
+        "organization_id": org_id,
+        "name": "Low score alert",
+        "description": null,
+        "chain_id": 11155111,
+        "registry": "reputation",
+        "enabled": true,
+        "is_stateful": false,
+        "created_at": "2026-01-01T00:00:00Z",
+        "updated_at": "2026-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+fn test_event(event_type: &str) -> Event {
+    serde_json::from_value(json!({
+        "id": format!("event_webhook_{}", uuid::Uuid::new_v4()),
+        "chain_id": 11155111,
+        "block_number": 1000,
+        "block_hash": "0xblock",
+        "transaction_hash": "0xtx",
+        "log_index": 0,
+        "registry": "reputation",
+        "event_type": event_type,
+        "agent_id": 42,
+        "timestamp": 1_700_000_000,
+        "score": 20,
+        "created_at": "2026-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+fn dispatcher(pool: &PgPool, max_attempts: i32) -> WebhookDispatcher {
+    WebhookDispatcher::new(
+        pool.clone(),
+        WebhookDispatchConfig {
+            timeout: Duration::from_secs(5),
+            max_attempts,
+            retry_base: Duration::from_secs(30),
+        },
+    )
+    .unwrap()
+}
+
+/// Accept one request, answer with `status` and hand back the raw request
+async fn spawn_receiver(status: u16) -> (String, oneshot::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        // Read until the headers and the declared body have arrived
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        let _ = tx.send(String::from_utf8_lossy(&request).to_string());
+    });
+
+    (format!("http://{}/hooks/fires", addr), rx)
+}
+
+fn header_value<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    let name = name.to_ascii_lowercase();
+    request.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.to_ascii_lowercase() == name).then(|| value.trim())
+    })
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_enqueue_only_matching_enabled_webhooks() -> Result<()> {
+    let _guard = DISPATCH_LOCK.lock().await;
+    let pool = setup_test_db().await?;
+    let suffix = "enqueue";
+    cleanup(&pool, suffix).await?;
+    let org_id = create_test_org(&pool, suffix).await?;
+
+    let all = create_test_webhook(&pool, &org_id, "https://a.example.com", &[], true).await?;
+    let feedback = create_test_webhook(
+        &pool,
+        &org_id,
+        "https://b.example.com",
+        &["NewFeedback"],
+        true,
+    )
+    .await?;
+    create_test_webhook(
+        &pool,
+        &org_id,
+        "https://c.example.com",
+        &["AgentRegistered"],
+        true,
+    )
+    .await?;
+    create_test_webhook(&pool, &org_id, "https://d.example.com", &[], false).await?;
+
+    let trigger = test_trigger(&org_id);
+    let event = test_event("NewFeedback");
+    let event_data = json!({ "event_id": event.id, "score": 20 });
+
+    let queued = enqueue_trigger_fire(&pool, &trigger, &event, &event_data, "corr-1").await?;
+    assert_eq!(queued, 2);
+
+    // Reprocessing the same event queues nothing new
+    let queued = enqueue_trigger_fire(&pool, &trigger, &event, &event_data, "corr-2").await?;
+    assert_eq!(queued, 0);
+
+    let rows: Vec<(String, serde_json::Value)> = sqlx::query_as(
+        "SELECT webhook_id, payload FROM webhook_deliveries WHERE event_id = $1 ORDER BY webhook_id",
+    )
+    .bind(&event.id)
+    .fetch_all(&pool)
+    .await?;
+    let mut expected = vec![all, feedback];
+    expected.sort();
+    assert_eq!(
+        rows.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>(),
+        expected
+    );
+
+    let payload = &rows[0].1;
+    assert_eq!(payload["type"], "trigger.fired");
+    assert_eq!(payload["organization_id"], org_id.as_str());
+    assert_eq!(payload["correlation_id"], "corr-1");
+    assert_eq!(payload["trigger"]["id"], "trigger_webhook_test");
+    assert_eq!(payload["event"]["score"], 20);
+
+    cleanup(&pool, suffix).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_dispatch_signs_and_records_success() -> Result<()> {
+    let _guard = DISPATCH_LOCK.lock().await;
+    let pool = setup_test_db().await?;
+    let suffix = "success";
+    cleanup(&pool, suffix).await?;
+    let org_id = create_test_org(&pool, suffix).await?;
+
+    let (url, received) = spawn_receiver(200).await;
+    let webhook_id = create_test_webhook(&pool, &org_id, &url, &[], true).await?;
+    let event = test_event("NewFeedback");
+    enqueue_trigger_fire(&pool, &test_trigger(&org_id), &event, &json!({}), "corr").await?;
+
+    let report = dispatcher(&pool, 3).dispatch_once().await?;
+    assert!(report.succeeded >= 1);
+
+    let request = received.await?;
+    assert!(request.starts_with("POST /hooks/fires HTTP/1.1"));
+    let (_, body) = request.split_once("\r\n\r\n").unwrap();
+    let timestamp: i64 = header_value(&request, TIMESTAMP_HEADER).unwrap().parse()?;
+    assert_eq!(
+        header_value(&request, SIGNATURE_HEADER),
+        Some(sign_payload(SECRET, timestamp, body.as_bytes()).as_str())
+    );
+
+    let (id, status, attempts, response_status, delivered): (i64, String, i32, Option<i32>, bool) =
+        sqlx::query_as(
+            r#"
+        SELECT id, status, attempts, last_response_status, delivered_at IS NOT NULL
+        FROM webhook_deliveries WHERE webhook_id = $1
+        "#,
+        )
+        .bind(&webhook_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(
+        header_value(&request, DELIVERY_HEADER),
+        Some(id.to_string().as_str())
+    );
+    assert_eq!(status, "succeeded");
+    assert_eq!(attempts, 1);
+    assert_eq!(response_status, Some(200));
+    assert!(delivered);
+
+    cleanup(&pool, suffix).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore] // Requires DATABASE_URL (integration test)
+async fn test_dispatch_retries_then_fails() -> Result<()> {
+    let _guard = DISPATCH_LOCK.lock().await;
+    let pool = setup_test_db().await?;
+    let suffix = "failure";
+    cleanup(&pool, suffix).await?;
+    let org_id = create_test_org(&pool, suffix).await?;
+
+    let (url, received) = spawn_receiver(500).await;
+    let webhook_id = create_test_webhook(&pool, &org_id, &url, &[], true).await?;
+    let event = test_event("NewFeedback");
+    enqueue_trigger_fire(&pool, &test_trigger(&org_id), &event, &json!({}), "corr").await?;
+
+    dispatcher(&pool, 2).dispatch_once().await?;
+    received.await?;
+
+    let (status, attempts, response_status, retry_in_future): (String, i32, Option<i32>, bool) =
+        sqlx::query_as(
+            r#"
+            SELECT status, attempts, last_response_status, next_attempt_at > NOW()
+            FROM webhook_deliveries WHERE webhook_id = $1
+            "#,
+        )
+        .bind(&webhook_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(status, "pending");
+    assert_eq!(attempts, 1);
+    assert_eq!(response_status, Some(500));
+    assert!(retry_in_future);
+
+    // Make the retry due and fail it again: the last attempt marks it failed
+    let (url, received) = spawn_receiver(503).await;
+    sqlx::query("UPDATE webhooks SET url = $2 WHERE id = $1")
+        .bind(&webhook_id)
+        .bind(&url)
+        .execute(&pool)
+        .await?;
+    sqlx::query("UPDATE webhook_deliveries SET next_attempt_at = NOW() WHERE webhook_id = $1")
+        .bind(&webhook_id)
+        .execute(&pool)
+        .await?;
+
+    dispatcher(&pool, 2).dispatch_once().await?;
+    received.await?;
+
+    let (status, attempts, error): (String, i32, Option<String>) = sqlx::query_as(
+        "SELECT status, attempts, last_error FROM webhook_deliveries WHERE webhook_id = $1",
+    )
+    .bind(&webhook_id)
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(status, "failed");
+    assert_eq!(attempts, 2);
+    assert!(error.unwrap().contains("503"));
+
+    cleanup(&pool, suffix).await?;
+    Ok(())
+}
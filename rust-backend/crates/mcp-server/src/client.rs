@@ -1,24 +1,140 @@
 //! AgentAuri API client for MCP server
 
 use anyhow::{Context, Result};
-use reqwest::Client;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+
+/// Longest `Retry-After` the client will sleep through before retrying a 429
+pub const DEFAULT_MAX_RETRY_WAIT: Duration = Duration::from_secs(10);
+
+/// Most requests a batch helper keeps in flight at once
+pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Retries of an idempotent request after a 5xx, timeout or connection error
+const MAX_TRANSIENT_RETRIES: u32 = 2;
+
+/// Wait before the first transient retry (doubles for each further retry)
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Per-request timeout, so a hung API does not block the tool call
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// API client for AgentAuri backend
+///
+/// Holds one `reqwest::Client`, so connections are pooled and reused across
+/// tool calls.
 pub struct AgentAuriClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
+    max_retry_wait: Duration,
 }
 
 impl AgentAuriClient {
     pub fn new(base_url: String, api_key: Option<String>) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .unwrap_or_default();
+
         Self {
-            client: Client::new(),
+            client,
             base_url,
             api_key,
+            max_retry_wait: DEFAULT_MAX_RETRY_WAIT,
+        }
+    }
+
+    /// Set the longest wait honored before retrying a rate-limited request
+    ///
+    /// Requests whose `Retry-After` exceeds this fail immediately with
+    /// [`RateLimited`] so the caller is not blocked; zero disables retries.
+    pub fn with_max_retry_wait(mut self, max_retry_wait: Duration) -> Self {
+        self.max_retry_wait = max_retry_wait;
+        self
+    }
+
+    /// Send a request, retrying transient failures of idempotent requests
+    ///
+    /// GET, PUT and DELETE requests that fail with a 5xx, a timeout or a
+    /// connection error are retried up to twice with exponential backoff.
+    /// POST requests are never retried, since the first attempt may have
+    /// created something. When retries run out, the last 5xx response is
+    /// returned (and becomes an [`ApiError`]) or the last transport error.
+    async fn send(&self, mut req: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let idempotent = req
+            .try_clone()
+            .and_then(|r| r.build().ok())
+            .is_some_and(|r| matches!(*r.method(), Method::GET | Method::PUT | Method::DELETE));
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 0.. {
+            let retry = if idempotent && attempt < MAX_TRANSIENT_RETRIES {
+                req.try_clone()
+            } else {
+                None
+            };
+
+            let result = self.send_once(req).await;
+            let Some(next) = retry else {
+                return result;
+            };
+            match &result {
+                Ok(response) if !response.status().is_server_error() => return result,
+                Err(e) if error_kind(e) != ErrorKind::Transient => return result,
+                Ok(response) => tracing::warn!(
+                    status = %response.status(),
+                    backoff_ms = backoff.as_millis() as u64,
+                    "API server error, retrying"
+                ),
+                Err(e) => tracing::warn!(
+                    error = %e,
+                    backoff_ms = backoff.as_millis() as u64,
+                    "API request failed, retrying"
+                ),
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+            req = next;
+        }
+
+        unreachable!("the retry loop always returns")
+    }
+
+    /// Send a request, retrying once after the gateway's `Retry-After` on 429
+    async fn send_once(&self, req: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let retry = req.try_clone();
+        let response = req.send().await.context("Failed to send request")?;
+
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
         }
+
+        let wait = retry_after(response.headers(), chrono::Utc::now().timestamp());
+        let (Some(retry), Some(wait)) = (retry, wait) else {
+            return Err(RateLimited::from_wait(wait).into());
+        };
+        if wait > self.max_retry_wait {
+            return Err(RateLimited::from_wait(Some(wait)).into());
+        }
+
+        tracing::info!(wait_secs = wait.as_secs(), "Rate limited by API, retrying");
+        tokio::time::sleep(wait).await;
+
+        let response = retry.send().await.context("Failed to send request")?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let wait = retry_after(response.headers(), chrono::Utc::now().timestamp());
+            return Err(RateLimited::from_wait(wait).into());
+        }
+
+        Ok(response)
     }
 
     fn build_request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
@@ -47,12 +163,10 @@ impl AgentAuriClient {
             req = req.query(&[("per_page", pp.to_string())]);
         }
 
-        let response = req.send().await.context("Failed to send request")?;
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("API error {}: {}", status, body);
+            return Err(ApiError::from_response(response).await.into());
         }
 
         response
@@ -64,16 +178,11 @@ impl AgentAuriClient {
     /// Get a specific trigger by ID
     pub async fn get_trigger(&self, trigger_id: &str) -> Result<TriggerResponse> {
         let path = format!("/api/v1/triggers/{}", trigger_id);
-        let response = self
-            .build_request(reqwest::Method::GET, &path)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, &path);
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("API error {}: {}", status, body);
+            return Err(ApiError::from_response(response).await.into());
         }
 
         response
@@ -82,35 +191,133 @@ impl AgentAuriClient {
             .context("Failed to parse trigger response")
     }
 
-    /// Create a new trigger
-    pub async fn create_trigger(&self, request: CreateTriggerRequest) -> Result<TriggerResponse> {
-        let response = self
+    /// Get several triggers, with at most [`MAX_CONCURRENT_REQUESTS`] in flight
+    ///
+    /// Results are in the order of `trigger_ids`; each lookup fails or
+    /// succeeds on its own.
+    pub async fn get_triggers(&self, trigger_ids: &[String]) -> Vec<Result<TriggerResponse>> {
+        fetch_all(trigger_ids, MAX_CONCURRENT_REQUESTS, |id| {
+            self.get_trigger(id)
+        })
+        .await
+    }
+
+    /// Create a new trigger in an organization
+    pub async fn create_trigger(
+        &self,
+        organization_id: &str,
+        request: &CreateTriggerRequest,
+    ) -> Result<CreatedTrigger> {
+        let req = self
             .build_request(reqwest::Method::POST, "/api/v1/triggers")
-            .json(&request)
-            .send()
+            .header("X-Organization-ID", organization_id)
+            .json(request);
+        let response = self.send(req).await?;
+
+        let envelope: DataEnvelope<CreatedTrigger> = parse_api_response(response)
+            .await
+            .context("Failed to create trigger")?;
+        Ok(envelope.data)
+    }
+
+    /// Add a condition to a trigger
+    pub async fn create_condition(
+        &self,
+        organization_id: &str,
+        trigger_id: &str,
+        request: &CreateConditionRequest,
+    ) -> Result<CreatedResource> {
+        let path = format!("/api/v1/triggers/{}/conditions", trigger_id);
+        let req = self
+            .build_request(reqwest::Method::POST, &path)
+            .header("X-Organization-ID", organization_id)
+            .json(request);
+        let response = self.send(req).await?;
+
+        let envelope: DataEnvelope<CreatedResource> = parse_api_response(response)
             .await
-            .context("Failed to send request")?;
+            .context("Failed to create condition")?;
+        Ok(envelope.data)
+    }
+
+    /// Add an action to a trigger
+    pub async fn create_action(
+        &self,
+        organization_id: &str,
+        trigger_id: &str,
+        request: &CreateActionRequest,
+    ) -> Result<CreatedResource> {
+        let path = format!("/api/v1/triggers/{}/actions", trigger_id);
+        let req = self
+            .build_request(reqwest::Method::POST, &path)
+            .header("X-Organization-ID", organization_id)
+            .json(request);
+        let response = self.send(req).await?;
+
+        let envelope: DataEnvelope<CreatedResource> = parse_api_response(response)
+            .await
+            .context("Failed to create action")?;
+        Ok(envelope.data)
+    }
+
+    /// Delete a trigger in an organization
+    pub async fn delete_org_trigger(&self, organization_id: &str, trigger_id: &str) -> Result<()> {
+        let path = format!("/api/v1/triggers/{}", trigger_id);
+        let req = self
+            .build_request(reqwest::Method::DELETE, &path)
+            .header("X-Organization-ID", organization_id);
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("API error {}: {}", status, body);
+            return Err(ApiError::from_response(response).await.into());
         }
 
-        response
-            .json()
+        Ok(())
+    }
+
+    /// Enqueue a trigger's actions without waiting for a matching event
+    pub async fn fire_trigger(
+        &self,
+        organization_id: &str,
+        trigger_id: &str,
+        request: &FireTriggerRequest,
+    ) -> Result<FiredTrigger> {
+        let path = format!("/api/v1/triggers/{}/fire", trigger_id);
+        let req = self
+            .build_request(reqwest::Method::POST, &path)
+            .header("X-Organization-ID", organization_id)
+            .json(request);
+        let response = self.send(req).await?;
+
+        let envelope: DataEnvelope<FiredTrigger> = parse_api_response(response)
             .await
-            .context("Failed to parse create trigger response")
+            .context("Failed to fire trigger")?;
+        Ok(envelope.data)
+    }
+
+    /// Get the stateful evaluator state of a trigger
+    pub async fn get_trigger_state(
+        &self,
+        organization_id: &str,
+        trigger_id: &str,
+    ) -> Result<TriggerState> {
+        let path = format!("/api/v1/triggers/{}/state", trigger_id);
+        let req = self
+            .build_request(reqwest::Method::GET, &path)
+            .header("X-Organization-ID", organization_id);
+        let response = self.send(req).await?;
+
+        let envelope: DataEnvelope<TriggerState> = parse_api_response(response)
+            .await
+            .context("Failed to get trigger state")?;
+        Ok(envelope.data)
     }
 
     /// Delete a trigger
     pub async fn delete_trigger(&self, trigger_id: &str) -> Result<()> {
         let path = format!("/api/v1/triggers/{}", trigger_id);
-        let response = self
-            .build_request(reqwest::Method::DELETE, &path)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::DELETE, &path);
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -123,11 +330,8 @@ impl AgentAuriClient {
 
     /// List linked agents
     pub async fn list_linked_agents(&self) -> Result<AgentListResponse> {
-        let response = self
-            .build_request(reqwest::Method::GET, "/api/v1/agents/linked")
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, "/api/v1/agents/linked");
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -143,11 +347,8 @@ impl AgentAuriClient {
 
     /// List followed agents
     pub async fn list_following(&self) -> Result<FollowingListResponse> {
-        let response = self
-            .build_request(reqwest::Method::GET, "/api/v1/agents/following")
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, "/api/v1/agents/following");
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -176,12 +377,10 @@ impl AgentAuriClient {
             req = req.query(&[("limit", l.to_string())]);
         }
 
-        let response = req.send().await.context("Failed to send request")?;
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("API error {}: {}", status, body);
+            return Err(ApiError::from_response(response).await.into());
         }
 
         response
@@ -192,11 +391,8 @@ impl AgentAuriClient {
 
     /// Get Ponder indexer status
     pub async fn get_ponder_status(&self) -> Result<PonderStatusResponse> {
-        let response = self
-            .build_request(reqwest::Method::GET, "/api/v1/ponder/status")
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, "/api/v1/ponder/status");
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -212,11 +408,8 @@ impl AgentAuriClient {
 
     /// Get credit balance
     pub async fn get_credits(&self) -> Result<CreditBalanceResponse> {
-        let response = self
-            .build_request(reqwest::Method::GET, "/api/v1/billing/credits")
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, "/api/v1/billing/credits");
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -232,11 +425,8 @@ impl AgentAuriClient {
 
     /// List organizations
     pub async fn list_organizations(&self) -> Result<OrganizationListResponse> {
-        let response = self
-            .build_request(reqwest::Method::GET, "/api/v1/organizations")
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let req = self.build_request(reqwest::Method::GET, "/api/v1/organizations");
+        let response = self.send(req).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -251,8 +441,154 @@ impl AgentAuriClient {
     }
 }
 
+/// Run `fetch` for every item with at most `limit` futures in flight
+///
+/// Results are returned in the order of `items`.
+pub async fn fetch_all<I, T, F, Fut>(items: I, limit: usize, fetch: F) -> Vec<Result<T>>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    stream::iter(items)
+        .map(fetch)
+        .buffered(limit.max(1))
+        .collect()
+        .await
+}
+
+/// Broad cause of a failed API call, for deciding what to tell the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Missing, invalid or revoked API key, or insufficient role (401/403)
+    Auth,
+    /// The resource does not exist (404)
+    NotFound,
+    /// The API rejected the parameters (400/409/422)
+    InvalidRequest,
+    /// Still rate limited after the allowed retry
+    RateLimited,
+    /// Server error, timeout or connection failure; trying later may work
+    Transient,
+    /// Anything else (e.g. an unparseable response)
+    Other,
+}
+
+/// Classify an error returned by [`AgentAuriClient`]
+pub fn error_kind(error: &anyhow::Error) -> ErrorKind {
+    if let Some(api_error) = error.downcast_ref::<ApiError>() {
+        return api_error.kind();
+    }
+    if error.downcast_ref::<RateLimited>().is_some() {
+        return ErrorKind::RateLimited;
+    }
+    match error.downcast_ref::<reqwest::Error>() {
+        Some(e) if e.is_timeout() || e.is_connect() || e.is_request() => ErrorKind::Transient,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Non-success response from the AgentAuri API
+#[derive(Debug, thiserror::Error)]
+#[error("API error {status}: {body}")]
+pub struct ApiError {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+impl ApiError {
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Self { status, body }
+    }
+
+    /// Broad cause of the failure
+    pub fn kind(&self) -> ErrorKind {
+        match self.status.as_u16() {
+            401 | 403 => ErrorKind::Auth,
+            404 => ErrorKind::NotFound,
+            400 | 409 | 422 => ErrorKind::InvalidRequest,
+            429 => ErrorKind::RateLimited,
+            500..=599 => ErrorKind::Transient,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// The gateway's error message, falling back to the raw body
+    pub fn message(&self) -> String {
+        serde_json::from_str::<Value>(&self.body)
+            .ok()
+            .and_then(|v| v.get("message")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| self.body.clone())
+    }
+}
+
+/// The gateway is still rate limiting the client after the allowed retry
+#[derive(Debug, thiserror::Error)]
+pub struct RateLimited {
+    pub retry_after_secs: Option<u64>,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.retry_after_secs {
+            Some(1) => write!(f, "rate limited, try again in 1 second"),
+            Some(secs) => write!(f, "rate limited, try again in {} seconds", secs),
+            None => write!(f, "rate limited, try again later"),
+        }
+    }
+}
+
+impl RateLimited {
+    fn from_wait(wait: Option<Duration>) -> Self {
+        Self {
+            retry_after_secs: wait.map(|w| w.as_secs().max(1)),
+        }
+    }
+}
+
+/// How long the gateway asks the client to wait
+///
+/// Prefers `Retry-After` (delay in seconds), then derives the wait from
+/// `X-RateLimit-Reset` (Unix timestamp) when the quota is exhausted.
+fn retry_after(headers: &HeaderMap, now: i64) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<i64>().ok())
+    };
+
+    if let Some(secs) = header("retry-after") {
+        return Some(Duration::from_secs(secs.max(0) as u64));
+    }
+
+    if header("x-ratelimit-remaining").is_some_and(|r| r > 0) {
+        return None;
+    }
+    header("x-ratelimit-reset").map(|reset| Duration::from_secs((reset - now).max(0) as u64))
+}
+
+/// Deserialize a success response, or return an [`ApiError`]
+async fn parse_api_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T> {
+    if !response.status().is_success() {
+        return Err(ApiError::from_response(response).await.into());
+    }
+
+    response.json().await.context("Failed to parse response")
+}
+
 // Response types
 
+/// Gateway success wrapper (`{"data": ...}`)
+#[derive(Debug, Deserialize)]
+struct DataEnvelope<T> {
+    data: T,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TriggerListResponse {
     pub data: Vec<TriggerResponse>,
@@ -283,12 +619,78 @@ pub struct PaginationMeta {
 #[derive(Debug, Serialize)]
 pub struct CreateTriggerRequest {
     pub name: String,
-    pub registry: String,
-    pub event_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub chain_id: Option<String>,
+    pub description: Option<String>,
+    /// Chain to match, or None for all chains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<i32>,
+    pub registry: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_stateful: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateConditionRequest {
+    pub condition_type: String,
+    pub field: String,
+    pub operator: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateActionRequest {
+    pub action_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    pub config: Value,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatedTrigger {
+    pub id: String,
+    pub name: String,
+    pub registry: String,
+    pub chain_id: Option<i32>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatedResource {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FireTriggerRequest {
+    /// Fields the fired actions see as the matched event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_data: Option<Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FiredTrigger {
+    pub trigger_id: String,
+    pub event_id: String,
+    pub correlation_id: String,
+    pub jobs: Vec<FiredJob>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FiredJob {
+    pub job_id: String,
+    pub action_id: i32,
+    pub action_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TriggerState {
+    pub trigger_id: String,
+    pub is_stateful: bool,
+    pub state: Option<Value>,
+    pub last_updated: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -358,3 +760,203 @@ pub struct OrganizationResponse {
     pub role: String,
     pub created_at: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_gateway, MockResponse};
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+    use std::time::Instant;
+
+    fn rate_limited(retry_after: u64) -> MockResponse {
+        MockResponse::json(
+            "429 Too Many Requests",
+            json!({"error": {"code": "RATE_LIMITED", "message": "Rate limit exceeded"}}),
+        )
+        .with_header("Retry-After", retry_after)
+        .with_header("X-RateLimit-Remaining", 0)
+    }
+
+    fn credits() -> MockResponse {
+        MockResponse::json("200 OK", json!({"balance": 500, "currency": "USDC"}))
+    }
+
+    #[tokio::test]
+    async fn test_retries_once_after_retry_after() {
+        let (url, recorded) = spawn_gateway(vec![rate_limited(1), credits()]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let started = Instant::now();
+        let balance = client.get_credits().await.unwrap();
+
+        assert_eq!(balance.balance, 500);
+        assert_eq!(recorded.lock().unwrap().len(), 2);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_still_limited_after_retry() {
+        let (url, recorded) = spawn_gateway(vec![rate_limited(1), rate_limited(30)]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let err = client.get_credits().await.unwrap_err();
+
+        assert_eq!(err.to_string(), "rate limited, try again in 30 seconds");
+        assert_eq!(recorded.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_beyond_cap_fails_without_retry() {
+        let (url, recorded) = spawn_gateway(vec![rate_limited(120), credits()]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let started = Instant::now();
+        let err = client.get_credits().await.unwrap_err();
+
+        assert_eq!(err.to_string(), "rate limited, try again in 120 seconds");
+        assert!(err.downcast_ref::<RateLimited>().is_some());
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_zero_cap_disables_retry() {
+        let (url, recorded) = spawn_gateway(vec![rate_limited(1), credits()]).await;
+        let client = AgentAuriClient::new(url, None).with_max_retry_wait(Duration::ZERO);
+
+        let err = client.get_credits().await.unwrap_err();
+
+        assert_eq!(err.to_string(), "rate limited, try again in 1 second");
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+    }
+
+    fn server_error() -> MockResponse {
+        MockResponse::json(
+            "503 Service Unavailable",
+            json!({"error": "unavailable", "message": "Try again"}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_retries_server_errors_with_backoff() {
+        let (url, recorded) = spawn_gateway(vec![server_error(), server_error(), credits()]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let started = Instant::now();
+        let balance = client.get_credits().await.unwrap();
+
+        assert_eq!(balance.balance, 500);
+        assert_eq!(recorded.lock().unwrap().len(), 3);
+        // 200ms, then 400ms
+        assert!(started.elapsed() >= Duration::from_millis(600));
+    }
+
+    #[tokio::test]
+    async fn test_server_error_is_transient_after_retries() {
+        let (url, recorded) =
+            spawn_gateway(vec![server_error(), server_error(), server_error()]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let err = client.get_trigger("trig_1").await.unwrap_err();
+
+        assert_eq!(error_kind(&err), ErrorKind::Transient);
+        assert_eq!(recorded.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_retried() {
+        let (url, recorded) = spawn_gateway(vec![server_error(), credits()]).await;
+        let client = AgentAuriClient::new(url, None);
+
+        let request = FireTriggerRequest { event_data: None };
+        let err = client
+            .fire_trigger("org_1", "trig_1", &request)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error_kind(&err), ErrorKind::Transient);
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_auth_failure_is_not_retried() {
+        let (url, recorded) = spawn_gateway(vec![MockResponse::json(
+            "401 Unauthorized",
+            json!({"error": "unauthorized", "message": "Invalid API key"}),
+        )])
+        .await;
+        let client = AgentAuriClient::new(url, None);
+
+        let err = client.get_trigger("trig_1").await.unwrap_err();
+
+        assert_eq!(error_kind(&err), ErrorKind::Auth);
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_failure_is_transient() {
+        // Nothing listens on port 1
+        let client = AgentAuriClient::new("http://127.0.0.1:1".to_string(), None);
+        let err = client.get_credits().await.unwrap_err();
+        assert_eq!(error_kind(&err), ErrorKind::Transient);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_bounds_concurrency_and_keeps_order() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let results = fetch_all(0..10u64, 3, |i| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                // Later items finish first
+                tokio::time::sleep(Duration::from_millis(50 - i * 5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if i == 4 {
+                    anyhow::bail!("item {} failed", i);
+                }
+                Ok(i * 10)
+            }
+        })
+        .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(results.len(), 10);
+        assert!(results[4].is_err());
+        let values: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(values, [&0, &10, &20, &30, &50, &60, &70, &80, &90]);
+    }
+
+    #[test]
+    fn test_retry_after_prefers_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1000"));
+
+        assert_eq!(retry_after(&headers, 900), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_retry_after_falls_back_to_reset() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1045"));
+
+        assert_eq!(retry_after(&headers, 1000), Some(Duration::from_secs(45)));
+        assert_eq!(retry_after(&headers, 2000), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retry_after_ignores_reset_with_quota_left() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("3"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1045"));
+
+        assert_eq!(retry_after(&headers, 1000), None);
+        assert_eq!(retry_after(&HeaderMap::new(), 1000), None);
+    }
+}
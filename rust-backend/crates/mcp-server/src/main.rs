@@ -9,6 +9,8 @@
 //! Set the following environment variables:
 //! - `AGENTAURI_API_URL`: API endpoint (default: https://api.agentauri.ai)
 //! - `AGENTAURI_API_KEY`: Your API key (sk_live_xxx or sk_test_xxx)
+//! - `AGENTAURI_MAX_RETRY_WAIT_SECS`: Longest `Retry-After` to wait out before
+//!   retrying a rate-limited request once (default: 10, 0 disables retries)
 //!
 //! ## Usage with Claude Desktop
 //!
@@ -28,19 +30,28 @@
 //! ```
 
 mod client;
+mod prompts;
 mod protocol;
+mod resources;
+#[cfg(test)]
+mod test_support;
 mod tools;
 
-use crate::client::AgentAuriClient;
+use crate::client::{AgentAuriClient, DEFAULT_MAX_RETRY_WAIT};
+use crate::prompts::{get_prompt, get_prompts};
 use crate::protocol::{
-    InitializeParams, InitializeResult, JsonRpcRequest, JsonRpcResponse, ServerCapabilities,
-    ServerInfo, ToolCallParams, ToolListResult, ToolsCapability,
+    InitializeParams, InitializeResult, JsonRpcRequest, JsonRpcResponse, PromptGetParams,
+    PromptListResult, PromptsCapability, ResourceListParams, ResourceReadParams,
+    ResourceTemplateListResult, ResourcesCapability, ServerCapabilities, ServerInfo,
+    ToolCallParams, ToolListResult, ToolsCapability,
 };
+use crate::resources::{get_resource_templates, list_resources, read_resource};
 use crate::tools::{get_tools, handle_tool_call};
 
 use anyhow::Result;
 use serde_json::json;
 use std::io::{self, BufRead, Write};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
 const SERVER_NAME: &str = "agentauri-mcp";
@@ -70,10 +81,19 @@ fn main() -> Result<()> {
         warn!("AGENTAURI_API_KEY not set - API calls will fail authentication");
     }
 
-    info!(api_url = %api_url, has_api_key = api_key.is_some(), "Configuration loaded");
+    let max_retry_wait = std::env::var("AGENTAURI_MAX_RETRY_WAIT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_MAX_RETRY_WAIT);
+
+    // This server talks to the API only, so it validates its own settings
+    // rather than the backend `Config` the other services load
+    let config_summary = summarize_config(&api_url, api_key.as_deref(), max_retry_wait)?;
+    info!("Effective configuration: {}", config_summary);
 
     // Create API client
-    let client = AgentAuriClient::new(api_url, api_key);
+    let client = AgentAuriClient::new(api_url, api_key).with_max_retry_wait(max_retry_wait);
 
     // Create tokio runtime for async operations
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -84,6 +104,37 @@ fn main() -> Result<()> {
     runtime.block_on(run_server(client))
 }
 
+/// Validate the settings and describe them without revealing the API key
+fn summarize_config(
+    api_url: &str,
+    api_key: Option<&str>,
+    max_retry_wait: Duration,
+) -> Result<String> {
+    if !api_url.starts_with("https://") && !api_url.starts_with("http://") {
+        anyhow::bail!(
+            "AGENTAURI_API_URL must start with http:// or https:// (got {:?})",
+            api_url
+        );
+    }
+
+    let api_key = match api_key {
+        None => "unset".to_string(),
+        Some(key) if key.starts_with("sk_live_") => "sk_live_***".to_string(),
+        Some(key) if key.starts_with("sk_test_") => "sk_test_***".to_string(),
+        Some(_) => {
+            warn!("AGENTAURI_API_KEY does not look like an AgentAuri key (sk_live_/sk_test_)");
+            "***".to_string()
+        }
+    };
+
+    Ok(format!(
+        "api_url={} api_key={} max_retry_wait={}s",
+        api_url,
+        api_key,
+        max_retry_wait.as_secs()
+    ))
+}
+
 async fn run_server(client: AgentAuriClient) -> Result<()> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
@@ -132,6 +183,11 @@ async fn handle_request(client: &AgentAuriClient, request: JsonRpcRequest) -> Js
         "initialized" => handle_initialized(request),
         "tools/list" => handle_tools_list(request),
         "tools/call" => handle_tools_call(client, request).await,
+        "resources/list" => handle_resources_list(client, request).await,
+        "resources/templates/list" => handle_resource_templates_list(request),
+        "resources/read" => handle_resources_read(client, request).await,
+        "prompts/list" => handle_prompts_list(request),
+        "prompts/get" => handle_prompts_get(request),
         "notifications/cancelled" => {
             // Acknowledge cancellation notifications
             JsonRpcResponse::success(request.id, json!({}))
@@ -169,6 +225,13 @@ fn handle_initialize(request: JsonRpcRequest) -> JsonRpcResponse {
             tools: ToolsCapability {
                 list_changed: false,
             },
+            resources: ResourcesCapability {
+                subscribe: false,
+                list_changed: false,
+            },
+            prompts: PromptsCapability {
+                list_changed: false,
+            },
         },
         server_info: ServerInfo {
             name: SERVER_NAME.to_string(),
@@ -210,6 +273,89 @@ async fn handle_tools_call(client: &AgentAuriClient, request: JsonRpcRequest) ->
     JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap())
 }
 
+async fn handle_resources_list(
+    client: &AgentAuriClient,
+    request: JsonRpcRequest,
+) -> JsonRpcResponse {
+    // Params are optional: omitting them requests the first page
+    let params: ResourceListParams = match request.params {
+        Some(p) => match serde_json::from_value(p) {
+            Ok(params) => params,
+            Err(e) => {
+                return JsonRpcResponse::error(request.id, -32602, format!("Invalid params: {}", e))
+            }
+        },
+        None => ResourceListParams::default(),
+    };
+
+    match list_resources(client, params.cursor.as_deref()).await {
+        Ok(result) => JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap()),
+        Err(e) => JsonRpcResponse::error(request.id, e.code(), e.to_string()),
+    }
+}
+
+fn handle_resource_templates_list(request: JsonRpcRequest) -> JsonRpcResponse {
+    let result = ResourceTemplateListResult {
+        resource_templates: get_resource_templates(),
+    };
+    JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap())
+}
+
+async fn handle_resources_read(
+    client: &AgentAuriClient,
+    request: JsonRpcRequest,
+) -> JsonRpcResponse {
+    let params: ResourceReadParams = match request.params {
+        Some(p) => match serde_json::from_value(p) {
+            Ok(params) => params,
+            Err(e) => {
+                return JsonRpcResponse::error(request.id, -32602, format!("Invalid params: {}", e))
+            }
+        },
+        None => {
+            return JsonRpcResponse::error(request.id, -32602, "Missing params");
+        }
+    };
+
+    info!(uri = %params.uri, "Reading resource");
+
+    match read_resource(client, &params.uri).await {
+        Ok(result) => JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap()),
+        Err(e) => {
+            warn!(uri = %params.uri, error = %e, "Resource read failed");
+            JsonRpcResponse::error(request.id, e.code(), e.to_string())
+        }
+    }
+}
+
+fn handle_prompts_list(request: JsonRpcRequest) -> JsonRpcResponse {
+    let result = PromptListResult {
+        prompts: get_prompts(),
+    };
+    JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap())
+}
+
+fn handle_prompts_get(request: JsonRpcRequest) -> JsonRpcResponse {
+    let params: PromptGetParams = match request.params {
+        Some(p) => match serde_json::from_value(p) {
+            Ok(params) => params,
+            Err(e) => {
+                return JsonRpcResponse::error(request.id, -32602, format!("Invalid params: {}", e))
+            }
+        },
+        None => {
+            return JsonRpcResponse::error(request.id, -32602, "Missing params");
+        }
+    };
+
+    info!(prompt = %params.name, "Rendering prompt");
+
+    match get_prompt(&params.name, &params.arguments) {
+        Ok(result) => JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap()),
+        Err(message) => JsonRpcResponse::error(request.id, -32602, message),
+    }
+}
+
 fn send_response(stdout: &mut io::Stdout, response: &JsonRpcResponse) -> Result<()> {
     let json = serde_json::to_string(response)?;
     debug!(response = %json, "Sending response");
@@ -217,3 +363,90 @@ fn send_response(stdout: &mut io::Stdout, response: &JsonRpcResponse) -> Result<
     stdout.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_redacts_api_key() {
+        let summary = summarize_config(
+            "https://api.agentauri.ai",
+            Some("sk_live_abcdef123456"),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+        assert!(!summary.contains("abcdef123456"));
+        assert!(summary.contains("api_key=sk_live_***"));
+        assert!(summary.contains("max_retry_wait=10s"));
+
+        let summary =
+            summarize_config("http://localhost:8080", Some("opaque"), Duration::ZERO).unwrap();
+        assert!(!summary.contains("opaque"));
+    }
+
+    fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+        serde_json::from_value(json!({
+            "jsonrpc": "2.0", "id": 1, "method": method, "params": params
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_advertises_resources() {
+        let client = AgentAuriClient::new("http://127.0.0.1:1".to_string(), None);
+        let params = json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {"name": "test", "version": "1.0"}
+        });
+        let response = handle_request(&client, request("initialize", params)).await;
+        let capabilities = &response.result.unwrap()["capabilities"];
+        assert_eq!(
+            capabilities["resources"],
+            json!({"subscribe": false, "listChanged": false})
+        );
+        assert_eq!(capabilities["prompts"], json!({"listChanged": false}));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_resource_uri_is_json_rpc_error() {
+        let client = AgentAuriClient::new("http://127.0.0.1:1".to_string(), None);
+        let params = json!({"uri": "agentauri://nope"});
+        let response = handle_request(&client, request("resources/read", params)).await;
+        assert!(response.result.is_none());
+        let error = response.error.unwrap();
+        assert_eq!(error.code, crate::protocol::RESOURCE_NOT_FOUND);
+        assert!(error.message.contains("agentauri://nope"));
+
+        let response = handle_request(&client, request("resources/read", json!({}))).await;
+        assert_eq!(response.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_over_json_rpc() {
+        let client = AgentAuriClient::new("http://127.0.0.1:1".to_string(), None);
+        let params = json!({
+            "name": "create_score_alert",
+            "arguments": {
+                "organization_id": "org_1", "agent_id": "42",
+                "threshold": "60", "action_target": "123456789"
+            }
+        });
+        let response = handle_request(&client, request("prompts/get", params)).await;
+        let result = response.result.unwrap();
+        assert_eq!(result["messages"][0]["role"], "user");
+        assert_eq!(result["messages"][0]["content"]["type"], "text");
+
+        let params = json!({"name": "create_score_alert"});
+        let response = handle_request(&client, request("prompts/get", params)).await;
+        assert_eq!(response.error.unwrap().code, -32602);
+    }
+
+    #[test]
+    fn test_summary_rejects_invalid_api_url() {
+        assert!(summarize_config("api.agentauri.ai", None, Duration::ZERO).is_err());
+        assert!(summarize_config("ftp://api.agentauri.ai", None, Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//! MCP Prompt definitions and rendering
+//!
+//! Prompts are reusable instructions that walk the model through configuring
+//! a trigger with the `create_trigger` tool. They are rendered entirely
+//! server-side: `{name}` placeholders in a template are replaced by the
+//! caller's arguments, or by the argument's default when it is optional and
+//! omitted.
+
+use crate::protocol::{Prompt, PromptArgument, PromptContent, PromptGetResult, PromptMessage};
+use std::collections::HashMap;
+
+/// Argument of a prompt template
+struct ArgumentDef {
+    name: &'static str,
+    description: &'static str,
+    /// Substituted when the argument is omitted; `None` makes it required
+    default: Option<&'static str>,
+}
+
+/// A parameterized prompt
+struct PromptTemplate {
+    name: &'static str,
+    description: &'static str,
+    arguments: &'static [ArgumentDef],
+    template: &'static str,
+}
+
+const ORGANIZATION_ARG: ArgumentDef = ArgumentDef {
+    name: "organization_id",
+    description: "Organization that will own the trigger (see list_organizations)",
+    default: None,
+};
+
+const ACTION_TARGET_ARG: ArgumentDef = ArgumentDef {
+    name: "action_target",
+    description: "Telegram chat ID or webhook URL to notify",
+    default: None,
+};
+
+const PROMPTS: &[PromptTemplate] = &[
+    PromptTemplate {
+        name: "create_rate_trigger",
+        description: "Create a trigger that fires when events on a registry arrive faster than a given rate.",
+        arguments: &[
+            ORGANIZATION_ARG,
+            ArgumentDef {
+                name: "registry",
+                description: "Registry to watch: identity, reputation or validation",
+                default: None,
+            },
+            ArgumentDef {
+                name: "max_events",
+                description: "Number of events allowed per window before the trigger fires",
+                default: None,
+            },
+            ArgumentDef {
+                name: "time_window",
+                description: "Counting window, e.g. 10m, 1h or 7d",
+                default: None,
+            },
+            ACTION_TARGET_ARG,
+            ArgumentDef {
+                name: "agent_id",
+                description: "Only count events for this on-chain agent ID",
+                default: Some("omit it to count events for every agent"),
+            },
+            ArgumentDef {
+                name: "event_type",
+                description: "Only count this event type (e.g., NewFeedback)",
+                default: Some("omit it to count every event type"),
+            },
+        ],
+        template: "\
+Create a trigger in organization {organization_id} that notifies {action_target} when more \
+than {max_events} events arrive on the {registry} registry within {time_window}.
+
+Call the create_trigger tool with:
+- registry: {registry}
+- agent_id: {agent_id}
+- event_type: {event_type}
+- condition: type rate_limit, operator \">\", threshold {max_events}, time_window \"{time_window}\"
+- action: target {action_target}; use a telegram action for a chat ID and a rest action for an http(s) URL
+
+Choose a short, descriptive trigger name. If any value above is invalid, ask me instead of \
+guessing. Once the trigger is created, summarize it and offer to test it with fire_trigger.",
+    },
+    PromptTemplate {
+        name: "create_score_alert",
+        description: "Create a trigger that fires when an agent receives reputation feedback below a score.",
+        arguments: &[
+            ORGANIZATION_ARG,
+            ArgumentDef {
+                name: "agent_id",
+                description: "On-chain agent ID to watch",
+                default: None,
+            },
+            ArgumentDef {
+                name: "threshold",
+                description: "Alert on scores below this value (0-100)",
+                default: None,
+            },
+            ACTION_TARGET_ARG,
+            ArgumentDef {
+                name: "chain_id",
+                description: "Chain ID of the agent (e.g., 8453 for Base)",
+                default: Some("omit it to match all chains"),
+            },
+        ],
+        template: "\
+Create a trigger in organization {organization_id} that notifies {action_target} when agent \
+{agent_id} receives reputation feedback with a score below {threshold}.
+
+Call the create_trigger tool with:
+- registry: reputation
+- agent_id: {agent_id}
+- chain_id: {chain_id}
+- event_type: NewFeedback
+- condition: type score_threshold, operator \"<\", threshold {threshold}
+- action: target {action_target}; use a telegram action for a chat ID and a rest action for an http(s) URL. \
+For Telegram, include {{{{agent_id}}}} and {{{{score}}}} in the message template.
+
+Choose a short, descriptive trigger name. If any value above is invalid, ask me instead of \
+guessing. Once the trigger is created, summarize it and offer to test it with fire_trigger.",
+    },
+];
+
+/// Get all available prompts
+pub fn get_prompts() -> Vec<Prompt> {
+    PROMPTS
+        .iter()
+        .map(|prompt| Prompt {
+            name: prompt.name.to_string(),
+            description: prompt.description.to_string(),
+            arguments: prompt
+                .arguments
+                .iter()
+                .map(|arg| PromptArgument {
+                    name: arg.name.to_string(),
+                    description: arg.description.to_string(),
+                    required: arg.default.is_none(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Render a prompt with the caller's arguments
+///
+/// Fails on an unknown prompt, an unknown argument, or a missing required one.
+pub fn get_prompt(
+    name: &str,
+    arguments: &HashMap<String, String>,
+) -> Result<PromptGetResult, String> {
+    let prompt = PROMPTS
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown prompt: {}", name))?;
+
+    if let Some(unknown) = arguments
+        .keys()
+        .find(|key| !prompt.arguments.iter().any(|arg| arg.name == key.as_str()))
+    {
+        return Err(format!("Unknown argument for {}: {}", name, unknown));
+    }
+
+    let mut values = HashMap::with_capacity(prompt.arguments.len());
+    let mut missing = Vec::new();
+    for arg in prompt.arguments {
+        let provided = arguments
+            .get(arg.name)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty());
+        match (provided, arg.default) {
+            (Some(value), _) | (None, Some(value)) => {
+                values.insert(arg.name, value);
+            }
+            (None, None) => missing.push(arg.name),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "Missing required arguments for {}: {}",
+            name,
+            missing.join(", ")
+        ));
+    }
+
+    Ok(PromptGetResult {
+        description: prompt.description.to_string(),
+        messages: vec![PromptMessage {
+            role: "user".to_string(),
+            content: PromptContent::Text {
+                text: render(prompt.template, &values),
+            },
+        }],
+    })
+}
+
+/// Replace `{name}` placeholders in a single pass
+///
+/// `{{` and `}}` are literal braces, so templates write action template
+/// variables as `{{{{score}}}}`. Substituted values are never rescanned.
+fn render(template: &str, values: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+            out.push_str(&rest[..1]);
+            rest = after;
+            continue;
+        }
+
+        let placeholder = rest.strip_prefix('{').and_then(|inner| {
+            let end = inner.find('}')?;
+            values.get(&inner[..end]).map(|value| (*value, end + 2))
+        });
+        match placeholder {
+            Some((value, len)) => {
+                out.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn text(result: &PromptGetResult) -> &str {
+        match &result.messages[0].content {
+            PromptContent::Text { text } => text,
+        }
+    }
+
+    #[test]
+    fn test_list_prompts() {
+        let prompts = get_prompts();
+        let names: Vec<_> = prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["create_rate_trigger", "create_score_alert"]);
+
+        let rate = &prompts[0];
+        let required: Vec<_> = rate
+            .arguments
+            .iter()
+            .filter(|a| a.required)
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(
+            required,
+            [
+                "organization_id",
+                "registry",
+                "max_events",
+                "time_window",
+                "action_target"
+            ]
+        );
+
+        let json = serde_json::to_value(&prompts).unwrap();
+        assert_eq!(json[1]["arguments"][4]["name"], "chain_id");
+        assert_eq!(json[1]["arguments"][4]["required"], false);
+    }
+
+    #[test]
+    fn test_get_prompt_substitutes_arguments() {
+        let result = get_prompt(
+            "create_rate_trigger",
+            &args(&[
+                ("organization_id", "org_123"),
+                ("registry", "reputation"),
+                ("max_events", "20"),
+                ("time_window", "1h"),
+                ("action_target", "123456789"),
+                ("agent_id", "42"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].role, "user");
+        let text = text(&result);
+        assert!(text.contains("organization org_123"), "{}", text);
+        assert!(text.contains("more than 20 events"), "{}", text);
+        assert!(text.contains("- agent_id: 42"), "{}", text);
+        assert!(
+            text.contains("threshold 20, time_window \"1h\""),
+            "{}",
+            text
+        );
+        // Omitted optional argument falls back to its default
+        assert!(
+            text.contains("- event_type: omit it to count every event type"),
+            "{}",
+            text
+        );
+        assert!(!text.contains('{'), "{}", text);
+    }
+
+    #[test]
+    fn test_escaped_braces_and_values_are_literal() {
+        let result = get_prompt(
+            "create_score_alert",
+            &args(&[
+                ("organization_id", "{threshold}"),
+                ("agent_id", "42"),
+                ("threshold", "60"),
+                ("action_target", "https://example.com/hook"),
+            ]),
+        )
+        .unwrap();
+        let text = text(&result);
+        assert!(text.contains("{{agent_id}} and {{score}}"), "{}", text);
+        // Values are not rescanned for placeholders
+        assert!(text.contains("organization {threshold}"), "{}", text);
+    }
+
+    #[test]
+    fn test_get_prompt_rejects_bad_arguments() {
+        let err = get_prompt("create_score_alert", &args(&[("agent_id", "42")])).unwrap_err();
+        assert_eq!(
+            err,
+            "Missing required arguments for create_score_alert: organization_id, threshold, action_target"
+        );
+
+        let err = get_prompt(
+            "create_score_alert",
+            &args(&[("agent_id", "42"), ("colour", "red")]),
+        )
+        .unwrap_err();
+        assert!(err.contains("Unknown argument"), "{}", err);
+
+        // Blank values count as missing
+        let err = get_prompt("create_score_alert", &args(&[("agent_id", " ")])).unwrap_err();
+        assert!(err.contains("agent_id"), "{}", err);
+
+        assert!(get_prompt("nope", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let values = HashMap::from([("a", "1"), ("b", "{a}")]);
+        assert_eq!(render("{a}-{b}-{c}-{{a}}", &values), "1-{a}-{c}-{a}");
+        assert_eq!(render("no placeholders", &values), "no placeholders");
+        assert_eq!(render("unclosed {a", &values), "unclosed {a");
+        assert_eq!(render("stray } and {x {a}", &values), "stray } and {x 1");
+    }
+}
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// JSON-RPC 2.0 request
 #[derive(Debug, Deserialize)]
@@ -54,6 +55,9 @@ impl JsonRpcResponse {
     }
 }
 
+/// MCP error code for a `resources/read` URI that does not exist
+pub const RESOURCE_NOT_FOUND: i32 = -32002;
+
 #[derive(Debug, Serialize)]
 pub struct JsonRpcError {
     pub code: i32,
@@ -107,6 +111,8 @@ pub struct InitializeResult {
 #[derive(Debug, Serialize)]
 pub struct ServerCapabilities {
     pub tools: ToolsCapability,
+    pub resources: ResourcesCapability,
+    pub prompts: PromptsCapability,
 }
 
 #[derive(Debug, Serialize)]
@@ -115,6 +121,19 @@ pub struct ToolsCapability {
     pub list_changed: bool,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesCapability {
+    pub subscribe: bool,
+    pub list_changed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptsCapability {
+    pub list_changed: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ServerInfo {
     pub name: String,
@@ -184,3 +203,119 @@ impl ToolCallResult {
         }
     }
 }
+
+/// Resource definition
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub mime_type: String,
+}
+
+/// Resource template definition (RFC 6570 URI template)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    pub uri_template: String,
+    pub name: String,
+    pub description: String,
+    pub mime_type: String,
+}
+
+/// Resource list params
+#[derive(Debug, Default, Deserialize)]
+pub struct ResourceListParams {
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Resource list result
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceListResult {
+    pub resources: Vec<Resource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Resource template list result
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplateListResult {
+    pub resource_templates: Vec<ResourceTemplate>,
+}
+
+/// Resource read params
+#[derive(Debug, Deserialize)]
+pub struct ResourceReadParams {
+    pub uri: String,
+}
+
+/// Resource read result
+#[derive(Debug, Serialize)]
+pub struct ResourceReadResult {
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Text contents of a resource
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    pub uri: String,
+    pub mime_type: String,
+    pub text: String,
+}
+
+/// Prompt definition
+#[derive(Debug, Serialize)]
+pub struct Prompt {
+    pub name: String,
+    pub description: String,
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// Argument accepted by a prompt
+#[derive(Debug, Serialize)]
+pub struct PromptArgument {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+}
+
+/// Prompt list result
+#[derive(Debug, Serialize)]
+pub struct PromptListResult {
+    pub prompts: Vec<Prompt>,
+}
+
+/// Prompt get params
+#[derive(Debug, Deserialize)]
+pub struct PromptGetParams {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Prompt get result
+#[derive(Debug, Serialize)]
+pub struct PromptGetResult {
+    pub description: String,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// Message in a rendered prompt
+#[derive(Debug, Serialize)]
+pub struct PromptMessage {
+    pub role: String,
+    pub content: PromptContent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum PromptContent {
+    #[serde(rename = "text")]
+    Text { text: String },
+}
@@ -0,0 +1,369 @@
+//! MCP Resource definitions and handlers
+//!
+//! Resources expose read-only AgentAuri data at `agentauri://` URIs:
+//!
+//! - `agentauri://triggers/{trigger_id}` - A trigger
+//! - `agentauri://events` - Most recent indexed events
+//! - `agentauri://events/{event_type}` - Most recent events of one type
+//!
+//! `resources/list` returns the events resource followed by one entry per
+//! trigger. Trigger listings are paginated; the cursor is the next API page.
+
+use crate::client::{AgentAuriClient, ApiError, TriggerResponse};
+use crate::protocol::{
+    Resource, ResourceContents, ResourceListResult, ResourceReadResult, ResourceTemplate,
+    RESOURCE_NOT_FOUND,
+};
+use serde::Serialize;
+
+/// URI scheme for all resources
+const URI_SCHEME: &str = "agentauri://";
+
+/// MIME type of every resource
+const JSON_MIME_TYPE: &str = "application/json";
+
+/// Triggers listed per `resources/list` page
+const TRIGGERS_PER_PAGE: i32 = 50;
+
+/// Events returned by an events resource
+const EVENTS_LIMIT: i32 = 50;
+
+/// Failure of a resource request, mapped to a JSON-RPC error
+#[derive(Debug, thiserror::Error)]
+pub enum ResourceError {
+    #[error("Resource not found: {0}")]
+    NotFound(String),
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+    #[error("Failed to {0}: {1:#}")]
+    Api(&'static str, anyhow::Error),
+}
+
+impl ResourceError {
+    /// JSON-RPC error code
+    pub fn code(&self) -> i32 {
+        match self {
+            ResourceError::NotFound(_) => RESOURCE_NOT_FOUND,
+            ResourceError::InvalidParams(_) => -32602,
+            ResourceError::Api(..) => -32603,
+        }
+    }
+}
+
+/// A parsed resource URI
+#[derive(Debug, PartialEq)]
+enum ResourceUri<'a> {
+    Trigger(&'a str),
+    Events(Option<&'a str>),
+}
+
+/// Parse an `agentauri://` URI, or `None` if it names no resource
+fn parse_uri(uri: &str) -> Option<ResourceUri<'_>> {
+    let path = uri.strip_prefix(URI_SCHEME)?;
+    let is_segment = |s: &str| !s.is_empty() && !s.contains(['/', '?', '#']);
+
+    match path.split_once('/') {
+        None if path == "events" => Some(ResourceUri::Events(None)),
+        Some(("events", event_type)) if is_segment(event_type) => {
+            Some(ResourceUri::Events(Some(event_type)))
+        }
+        Some(("triggers", trigger_id)) if is_segment(trigger_id) => {
+            Some(ResourceUri::Trigger(trigger_id))
+        }
+        _ => None,
+    }
+}
+
+fn trigger_resource(trigger: &TriggerResponse) -> Resource {
+    Resource {
+        uri: format!("{}triggers/{}", URI_SCHEME, trigger.id),
+        name: trigger.name.clone(),
+        description: Some(format!(
+            "{} trigger on the {} registry ({})",
+            if trigger.enabled {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+            trigger.registry,
+            trigger.event_type
+        )),
+        mime_type: JSON_MIME_TYPE.to_string(),
+    }
+}
+
+/// Get the URI templates for addressable resources
+pub fn get_resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate {
+            uri_template: format!("{}triggers/{{trigger_id}}", URI_SCHEME),
+            name: "Trigger".to_string(),
+            description: "A trigger by ID, as returned by the API.".to_string(),
+            mime_type: JSON_MIME_TYPE.to_string(),
+        },
+        ResourceTemplate {
+            uri_template: format!("{}events/{{event_type}}", URI_SCHEME),
+            name: "Events by type".to_string(),
+            description: format!(
+                "The {} most recent indexed events of one type (e.g., AgentRegistered, NewFeedback).",
+                EVENTS_LIMIT
+            ),
+            mime_type: JSON_MIME_TYPE.to_string(),
+        },
+    ]
+}
+
+/// List resources, one page of triggers at a time
+pub async fn list_resources(
+    client: &AgentAuriClient,
+    cursor: Option<&str>,
+) -> Result<ResourceListResult, ResourceError> {
+    let page = match cursor {
+        None => 1,
+        Some(cursor) => cursor
+            .parse::<i32>()
+            .ok()
+            .filter(|page| *page > 1)
+            .ok_or_else(|| ResourceError::InvalidParams(format!("invalid cursor {:?}", cursor)))?,
+    };
+
+    let triggers = client
+        .list_triggers(Some(page), Some(TRIGGERS_PER_PAGE))
+        .await
+        .map_err(|e| ResourceError::Api("list triggers", e))?;
+
+    let mut resources = Vec::with_capacity(triggers.data.len() + 1);
+    if page == 1 {
+        resources.push(Resource {
+            uri: format!("{}events", URI_SCHEME),
+            name: "Recent events".to_string(),
+            description: Some(format!(
+                "The {} most recent events indexed from the ERC-8004 registries.",
+                EVENTS_LIMIT
+            )),
+            mime_type: JSON_MIME_TYPE.to_string(),
+        });
+    }
+    resources.extend(triggers.data.iter().map(trigger_resource));
+
+    let next_cursor = triggers
+        .meta
+        .filter(|meta| meta.page < meta.total_pages)
+        .map(|meta| (meta.page + 1).to_string());
+
+    Ok(ResourceListResult {
+        resources,
+        next_cursor,
+    })
+}
+
+/// Read a resource by URI
+pub async fn read_resource(
+    client: &AgentAuriClient,
+    uri: &str,
+) -> Result<ResourceReadResult, ResourceError> {
+    let not_found = || ResourceError::NotFound(uri.to_string());
+
+    let text = match parse_uri(uri).ok_or_else(not_found)? {
+        ResourceUri::Trigger(trigger_id) => match client.get_trigger(trigger_id).await {
+            Ok(trigger) => to_json(&trigger)?,
+            Err(e) if is_not_found(&e) => return Err(not_found()),
+            Err(e) => return Err(ResourceError::Api("get trigger", e)),
+        },
+        ResourceUri::Events(event_type) => {
+            let events = client
+                .get_ponder_events(event_type, Some(EVENTS_LIMIT))
+                .await
+                .map_err(|e| ResourceError::Api("query events", e))?;
+            to_json(&events)?
+        }
+    };
+
+    Ok(ResourceReadResult {
+        contents: vec![ResourceContents {
+            uri: uri.to_string(),
+            mime_type: JSON_MIME_TYPE.to_string(),
+            text,
+        }],
+    })
+}
+
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<ApiError>()
+        .is_some_and(|e| e.status == reqwest::StatusCode::NOT_FOUND)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, ResourceError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| ResourceError::Api("serialize resource", e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{spawn_gateway, MockResponse};
+    use serde_json::json;
+
+    fn trigger(id: &str) -> serde_json::Value {
+        json!({
+            "id": id, "name": format!("Trigger {}", id), "enabled": true,
+            "registry": "reputation", "event_type": "NewFeedback", "chain_id": null,
+            "created_at": "2026-01-01T00:00:00Z", "updated_at": "2026-01-01T00:00:00Z"
+        })
+    }
+
+    fn client(url: String) -> AgentAuriClient {
+        AgentAuriClient::new(url, Some("sk_test_key".to_string()))
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        assert_eq!(
+            parse_uri("agentauri://triggers/trig_1"),
+            Some(ResourceUri::Trigger("trig_1"))
+        );
+        assert_eq!(
+            parse_uri("agentauri://events"),
+            Some(ResourceUri::Events(None))
+        );
+        assert_eq!(
+            parse_uri("agentauri://events/NewFeedback"),
+            Some(ResourceUri::Events(Some("NewFeedback")))
+        );
+        for uri in [
+            "agentauri://triggers",
+            "agentauri://triggers/",
+            "agentauri://triggers/a/b",
+            "agentauri://triggers/../state",
+            "agentauri://agents/1",
+            "https://triggers/trig_1",
+        ] {
+            assert_eq!(parse_uri(uri), None, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_resources_paginates_triggers() {
+        let (url, recorded) = spawn_gateway(vec![
+            MockResponse::json(
+                "200 OK",
+                json!({
+                    "data": [trigger("trig_1")],
+                    "meta": {"page": 1, "per_page": 50, "total": 51, "total_pages": 2}
+                }),
+            ),
+            MockResponse::json(
+                "200 OK",
+                json!({
+                    "data": [trigger("trig_2")],
+                    "meta": {"page": 2, "per_page": 50, "total": 51, "total_pages": 2}
+                }),
+            ),
+        ])
+        .await;
+        let client = client(url);
+
+        let first = list_resources(&client, None).await.unwrap();
+        let uris: Vec<_> = first.resources.iter().map(|r| r.uri.as_str()).collect();
+        assert_eq!(uris, ["agentauri://events", "agentauri://triggers/trig_1"]);
+        assert_eq!(first.next_cursor.as_deref(), Some("2"));
+
+        let second = list_resources(&client, first.next_cursor.as_deref())
+            .await
+            .unwrap();
+        let uris: Vec<_> = second.resources.iter().map(|r| r.uri.as_str()).collect();
+        assert_eq!(uris, ["agentauri://triggers/trig_2"]);
+        assert!(second.next_cursor.is_none());
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(
+            requests[1].0,
+            "GET /api/v1/triggers?page=2&per_page=50 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_resources_rejects_invalid_cursor() {
+        let client = client("http://127.0.0.1:1".to_string());
+        for cursor in ["abc", "0", "1"] {
+            let err = list_resources(&client, Some(cursor)).await.unwrap_err();
+            assert_eq!(err.code(), -32602, "{}", cursor);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_trigger_resource() {
+        let (url, recorded) =
+            spawn_gateway(vec![MockResponse::json("200 OK", trigger("trig_1"))]).await;
+
+        let result = read_resource(&client(url), "agentauri://triggers/trig_1")
+            .await
+            .unwrap();
+        let contents = &result.contents[0];
+        assert_eq!(contents.uri, "agentauri://triggers/trig_1");
+        assert_eq!(contents.mime_type, "application/json");
+        let body: serde_json::Value = serde_json::from_str(&contents.text).unwrap();
+        assert_eq!(body["name"], "Trigger trig_1");
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests[0].0, "GET /api/v1/triggers/trig_1 HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_read_events_resource() {
+        let (url, recorded) = spawn_gateway(vec![MockResponse::json(
+            "200 OK",
+            json!({"events": [{"id": "evt_1"}], "total": 1}),
+        )])
+        .await;
+
+        let result = read_resource(&client(url), "agentauri://events/NewFeedback")
+            .await
+            .unwrap();
+        assert!(result.contents[0].text.contains("evt_1"));
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(
+            requests[0].0,
+            "GET /api/v1/ponder/events?event_type=NewFeedback&limit=50 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_unknown_resource_is_not_found() {
+        let client = client("http://127.0.0.1:1".to_string());
+        let err = read_resource(&client, "agentauri://agents/1")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), RESOURCE_NOT_FOUND);
+        assert_eq!(err.to_string(), "Resource not found: agentauri://agents/1");
+    }
+
+    #[tokio::test]
+    async fn test_read_missing_trigger_is_not_found() {
+        let (url, _) = spawn_gateway(vec![
+            MockResponse::json(
+                "404 Not Found",
+                json!({"error": "not_found", "message": "Trigger not found"}),
+            ),
+            MockResponse::json(
+                "500 Internal Server Error",
+                json!({"error": "internal_error", "message": "boom"}),
+            ),
+        ])
+        .await;
+        let client = client(url);
+
+        let err = read_resource(&client, "agentauri://triggers/missing")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), RESOURCE_NOT_FOUND);
+
+        let err = read_resource(&client, "agentauri://triggers/broken")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32603);
+        assert!(err.to_string().contains("Failed to get trigger"), "{}", err);
+    }
+}
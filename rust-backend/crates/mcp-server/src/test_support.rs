@@ -0,0 +1,97 @@
+//! Mock AgentAuri gateway for client and tool tests
+
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Requests received by the mock gateway, as (request line, body)
+pub type Recorded = Arc<Mutex<Vec<(String, String)>>>;
+
+/// Canned response served for one connection
+pub struct MockResponse {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Value,
+}
+
+impl MockResponse {
+    pub fn json(status: &'static str, body: Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Read one HTTP request (headers plus Content-Length body)
+async fn read_request(socket: &mut TcpStream) -> (String, String) {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+
+        let raw = String::from_utf8_lossy(&buf);
+        if let Some((head, body)) = raw.split_once("\r\n\r\n") {
+            let content_length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if body.len() >= content_length {
+                let request_line = head.lines().next().unwrap_or_default().to_string();
+                return (request_line, body.to_string());
+            }
+        }
+    }
+    (String::new(), String::new())
+}
+
+/// Spawn a mock gateway that answers each connection with the next response
+///
+/// Returns the base URL and the requests received so far.
+pub async fn spawn_gateway(responses: Vec<MockResponse>) -> (String, Recorded) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let recorded: Recorded = Arc::default();
+
+    let log = recorded.clone();
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut socket).await;
+            log.lock().unwrap().push(request);
+
+            let body = response.body.to_string();
+            let headers: String = response
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{}: {}\r\n", name, value))
+                .collect();
+            let raw = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+                response.status,
+                body.len(),
+                headers,
+                body
+            );
+            socket.write_all(raw.as_bytes()).await.unwrap();
+            socket.shutdown().await.ok();
+        }
+    });
+
+    (format!("http://{}", addr), recorded)
+}
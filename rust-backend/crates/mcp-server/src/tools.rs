@@ -1,6 +1,10 @@
 //! MCP Tool definitions and handlers
 
-use crate::client::{AgentAuriClient, CreateTriggerRequest};
+use crate::client::{
+    error_kind, AgentAuriClient, ApiError, CreateActionRequest, CreateConditionRequest,
+    CreateTriggerRequest, CreatedTrigger, ErrorKind, FireTriggerRequest, FiredTrigger,
+    TriggerState,
+};
 use crate::protocol::{Tool, ToolCallResult};
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -30,61 +34,82 @@ pub fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "get_trigger".to_string(),
-            description: "Get details of a specific trigger by ID, including its conditions and actions.".to_string(),
+            description: "Get details of a specific trigger by ID, including its conditions and actions. Pass trigger_ids instead to fetch several triggers at once.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "trigger_id": {
                         "type": "string",
                         "description": "The UUID of the trigger to retrieve"
+                    },
+                    "trigger_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "maxItems": MAX_BATCH_TRIGGERS,
+                        "description": "UUIDs of several triggers to retrieve (instead of trigger_id)"
                     }
-                },
-                "required": ["trigger_id"]
+                }
             }),
         },
         Tool {
             name: "create_trigger".to_string(),
-            description: "Create a new trigger to monitor blockchain events. Supported registries: identity, reputation, validation. Event types vary by registry (e.g., AgentRegistered, NewFeedback, etc.).".to_string(),
+            description: "Create a trigger end to end: the trigger itself, its conditions, and the action to run when they match. Describe what to watch (registry, optional chain, agent and event type), an optional condition with its threshold, and where to send the notification. Returns the created trigger ID and a summary.".to_string(),
+            input_schema: create_trigger_schema(),
+        },
+        Tool {
+            name: "delete_trigger".to_string(),
+            description: "Delete an existing trigger by ID.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Human-readable name for the trigger"
-                    },
-                    "registry": {
+                    "trigger_id": {
                         "type": "string",
-                        "enum": ["identity", "reputation", "validation"],
-                        "description": "The ERC-8004 registry to monitor"
-                    },
-                    "event_type": {
+                        "description": "The UUID of the trigger to delete"
+                    }
+                },
+                "required": ["trigger_id"]
+            }),
+        },
+        Tool {
+            name: "fire_trigger".to_string(),
+            description: "Fire a trigger manually to test its actions. Enqueues every action of the trigger as if a matching event had arrived; conditions are not evaluated and disabled triggers still fire. Optional event_data is exposed to action templates (e.g., {{agent_id}}, {{score}}).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "organization_id": {
                         "type": "string",
-                        "description": "The event type to monitor (e.g., AgentRegistered, NewFeedback, ValidationSubmitted)"
+                        "description": "Organization that owns the trigger (see list_organizations). Requires member or admin role."
                     },
-                    "chain_id": {
+                    "trigger_id": {
                         "type": "string",
-                        "description": "Chain ID to filter events (e.g., '8453' for Base). Use '*' or omit for all chains."
+                        "description": "The UUID of the trigger to fire"
                     },
-                    "enabled": {
-                        "type": "boolean",
-                        "description": "Whether the trigger is enabled (default: true)"
+                    "event_data": {
+                        "type": "object",
+                        "description": "Event fields for template substitution, e.g. {\"agent_id\": 42, \"score\": 55}"
                     }
                 },
-                "required": ["name", "registry", "event_type"]
+                "required": ["organization_id", "trigger_id"],
+                "additionalProperties": false
             }),
         },
         Tool {
-            name: "delete_trigger".to_string(),
-            description: "Delete an existing trigger by ID.".to_string(),
+            name: "get_trigger_state".to_string(),
+            description: "Get the current state of a stateful trigger's evaluator, such as moving averages for ema_threshold conditions and event timestamps for rate_limit conditions.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "organization_id": {
+                        "type": "string",
+                        "description": "Organization that owns the trigger (see list_organizations)"
+                    },
                     "trigger_id": {
                         "type": "string",
-                        "description": "The UUID of the trigger to delete"
+                        "description": "The UUID of the trigger to inspect"
                     }
                 },
-                "required": ["trigger_id"]
+                "required": ["organization_id", "trigger_id"],
+                "additionalProperties": false
             }),
         },
         Tool {
@@ -149,6 +174,135 @@ pub fn get_tools() -> Vec<Tool> {
     ]
 }
 
+/// Supported registries (mirrors the gateway's validation)
+const REGISTRIES: &[&str] = &["identity", "reputation", "validation"];
+
+/// Condition types the tool can build (agent and event type filters are separate fields)
+const CONDITION_TYPES: &[&str] = &[
+    "score_threshold",
+    "ema_threshold",
+    "rate_limit",
+    "tag_equals",
+];
+
+/// Comparison operators accepted by threshold conditions
+const THRESHOLD_OPERATORS: &[&str] = &["<", ">", "=", "<=", ">=", "!="];
+
+/// Action types supported by the action workers
+const ACTION_TYPES: &[&str] = &["telegram", "rest", "mcp"];
+
+/// HTTP methods accepted by REST actions
+const REST_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+/// Input schema for `create_trigger`
+fn create_trigger_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "organization_id": {
+                "type": "string",
+                "description": "Organization that will own the trigger (see list_organizations). Requires member or admin role."
+            },
+            "name": {
+                "type": "string",
+                "description": "Human-readable name for the trigger",
+                "minLength": 1,
+                "maxLength": 255
+            },
+            "description": {
+                "type": "string",
+                "maxLength": 1000
+            },
+            "registry": {
+                "type": "string",
+                "enum": REGISTRIES,
+                "description": "The ERC-8004 registry to monitor"
+            },
+            "chain_id": {
+                "type": "integer",
+                "description": "Chain ID to match (e.g., 8453 for Base). Omit to match all chains."
+            },
+            "agent_id": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Only match events for this on-chain agent ID"
+            },
+            "event_type": {
+                "type": "string",
+                "description": "Only match this event type (e.g., NewFeedback, AgentRegistered, ValidationSubmitted)"
+            },
+            "condition": {
+                "type": "object",
+                "description": "Optional extra condition on the event",
+                "properties": {
+                    "type": {
+                        "type": "string",
+                        "enum": CONDITION_TYPES,
+                        "description": "score_threshold: compare the event score; ema_threshold: compare the moving average of scores; rate_limit: compare the number of matching events in a time window; tag_equals: match a feedback tag"
+                    },
+                    "operator": {
+                        "type": "string",
+                        "enum": THRESHOLD_OPERATORS,
+                        "description": "Comparison operator (required for all types except tag_equals)"
+                    },
+                    "threshold": {
+                        "type": ["number", "string"],
+                        "description": "Value to compare against (score 0-100, event count, or the tag value for tag_equals)"
+                    },
+                    "tag_field": {
+                        "type": "string",
+                        "enum": ["tag1", "tag2"],
+                        "description": "Tag to match for tag_equals (default: tag1)"
+                    },
+                    "window_size": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of events in the moving average (ema_threshold, default: 10)"
+                    },
+                    "time_window": {
+                        "type": "string",
+                        "pattern": "^[0-9]+[smhd]$",
+                        "description": "Counting window for rate_limit, e.g. '10m', '1h', '7d'"
+                    }
+                },
+                "required": ["type", "threshold"],
+                "additionalProperties": false
+            },
+            "action": {
+                "type": "object",
+                "description": "What to do when the trigger fires",
+                "properties": {
+                    "type": {
+                        "type": "string",
+                        "enum": ACTION_TYPES
+                    },
+                    "target": {
+                        "type": "string",
+                        "description": "Telegram chat ID, REST endpoint URL, or MCP server URL"
+                    },
+                    "message_template": {
+                        "type": "string",
+                        "description": "Telegram message with {{variable}} placeholders (e.g., 'Agent {{agent_id}} scored {{score}}')"
+                    },
+                    "method": {
+                        "type": "string",
+                        "enum": REST_METHODS,
+                        "description": "HTTP method for REST actions (default: POST)"
+                    },
+                    "tool_name": {
+                        "type": "string",
+                        "description": "Tool to call for MCP actions"
+                    }
+                },
+                "required": ["type", "target"],
+                "additionalProperties": false
+            }
+        },
+        "required": ["organization_id", "name", "registry", "action"],
+        "additionalProperties": false
+    })
+}
+
 /// Handle a tool call
 pub async fn handle_tool_call(
     client: &AgentAuriClient,
@@ -162,6 +316,8 @@ pub async fn handle_tool_call(
         "get_trigger" => handle_get_trigger(client, args).await,
         "create_trigger" => handle_create_trigger(client, args).await,
         "delete_trigger" => handle_delete_trigger(client, args).await,
+        "fire_trigger" => handle_fire_trigger(client, args).await,
+        "get_trigger_state" => handle_get_trigger_state(client, args).await,
         "list_linked_agents" => handle_list_linked_agents(client).await,
         "list_following" => handle_list_following(client).await,
         "query_events" => handle_query_events(client, args).await,
@@ -190,9 +346,13 @@ async fn handle_list_triggers(client: &AgentAuriClient, args: Value) -> ToolCall
     }
 }
 
+/// Most triggers get_trigger fetches in one call
+const MAX_BATCH_TRIGGERS: usize = 20;
+
 #[derive(Debug, Deserialize)]
 struct GetTriggerArgs {
-    trigger_id: String,
+    trigger_id: Option<String>,
+    trigger_ids: Option<Vec<String>>,
 }
 
 async fn handle_get_trigger(client: &AgentAuriClient, args: Value) -> ToolCallResult {
@@ -201,38 +361,529 @@ async fn handle_get_trigger(client: &AgentAuriClient, args: Value) -> ToolCallRe
         Err(e) => return ToolCallResult::error(format!("Invalid arguments: {}", e)),
     };
 
-    match client.get_trigger(&args.trigger_id).await {
-        Ok(response) => ToolCallResult::json(&response),
-        Err(e) => ToolCallResult::error(format!("Failed to get trigger: {}", e)),
-    }
+    let trigger_ids = match (args.trigger_id, args.trigger_ids) {
+        (Some(trigger_id), None) => {
+            return match client.get_trigger(&trigger_id).await {
+                Ok(response) => ToolCallResult::json(&response),
+                Err(e) => ToolCallResult::error(format!("Failed to get trigger: {}", e)),
+            };
+        }
+        (None, Some(ids)) if !ids.is_empty() && ids.len() <= MAX_BATCH_TRIGGERS => ids,
+        (None, Some(_)) => {
+            return ToolCallResult::error(format!(
+                "Invalid arguments: trigger_ids must contain 1 to {} IDs",
+                MAX_BATCH_TRIGGERS
+            ));
+        }
+        _ => {
+            return ToolCallResult::error(
+                "Invalid arguments: provide either trigger_id or trigger_ids".to_string(),
+            );
+        }
+    };
+
+    let results = client.get_triggers(&trigger_ids).await;
+    let triggers: Vec<Value> = trigger_ids
+        .iter()
+        .zip(results)
+        .map(|(id, result)| match result {
+            Ok(trigger) => json!({ "trigger_id": id, "trigger": trigger }),
+            Err(e) => json!({ "trigger_id": id, "error": format!("{:#}", e) }),
+        })
+        .collect();
+
+    ToolCallResult::json(&json!({ "triggers": triggers }))
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct CreateTriggerArgs {
+    organization_id: String,
     name: String,
+    description: Option<String>,
     registry: String,
-    event_type: String,
-    chain_id: Option<String>,
-    enabled: Option<bool>,
+    chain_id: Option<i32>,
+    agent_id: Option<i64>,
+    event_type: Option<String>,
+    condition: Option<ConditionArgs>,
+    action: ActionArgs,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionArgs {
+    #[serde(rename = "type")]
+    condition_type: String,
+    operator: Option<String>,
+    threshold: Value,
+    tag_field: Option<String>,
+    window_size: Option<u32>,
+    time_window: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ActionArgs {
+    #[serde(rename = "type")]
+    action_type: String,
+    target: String,
+    message_template: Option<String>,
+    method: Option<String>,
+    tool_name: Option<String>,
+}
+
+impl CreateTriggerArgs {
+    /// Check the constraints the schema describes, collecting every problem
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.organization_id.trim().is_empty() {
+            errors.push("organization_id must not be empty".to_string());
+        }
+        if self.name.trim().is_empty() || self.name.len() > 255 {
+            errors.push("name must be between 1 and 255 characters".to_string());
+        }
+        if self.description.as_ref().is_some_and(|d| d.len() > 1000) {
+            errors.push("description must be at most 1000 characters".to_string());
+        }
+        if !REGISTRIES.contains(&self.registry.as_str()) {
+            errors.push(format!(
+                "registry must be one of {}, got '{}'",
+                REGISTRIES.join(", "),
+                self.registry
+            ));
+        }
+        if self.agent_id.is_some_and(|id| id < 0) {
+            errors.push("agent_id must be a non-negative integer".to_string());
+        }
+        if self
+            .event_type
+            .as_ref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            errors.push("event_type must not be empty when provided".to_string());
+        }
+
+        if let Some(condition) = &self.condition {
+            condition.validate(&mut errors);
+        }
+        self.action.validate(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Build the gateway condition requests, in evaluation order
+    fn condition_requests(&self) -> Vec<CreateConditionRequest> {
+        let mut conditions = Vec::new();
+
+        if let Some(agent_id) = self.agent_id {
+            conditions.push(CreateConditionRequest {
+                condition_type: "agent_id_equals".to_string(),
+                field: "agent_id".to_string(),
+                operator: "=".to_string(),
+                value: agent_id.to_string(),
+                config: None,
+            });
+        }
+        if let Some(event_type) = &self.event_type {
+            conditions.push(CreateConditionRequest {
+                condition_type: "event_type_equals".to_string(),
+                field: "event_type".to_string(),
+                operator: "=".to_string(),
+                value: event_type.clone(),
+                config: None,
+            });
+        }
+        if let Some(condition) = &self.condition {
+            conditions.push(condition.to_request());
+        }
+
+        conditions
+    }
+
+    /// Stateful conditions need per-trigger state in the event processor
+    fn is_stateful(&self) -> bool {
+        self.condition
+            .as_ref()
+            .is_some_and(|c| matches!(c.condition_type.as_str(), "ema_threshold" | "rate_limit"))
+    }
+}
+
+impl ConditionArgs {
+    fn validate(&self, errors: &mut Vec<String>) {
+        let kind = self.condition_type.as_str();
+        if !CONDITION_TYPES.contains(&kind) {
+            errors.push(format!(
+                "condition.type must be one of {}, got '{}'",
+                CONDITION_TYPES.join(", "),
+                kind
+            ));
+            return;
+        }
+
+        if kind == "tag_equals" {
+            if self.threshold_string().is_empty() {
+                errors.push("condition.threshold must be the tag value to match".to_string());
+            }
+            if let Some(field) = &self.tag_field {
+                if field != "tag1" && field != "tag2" {
+                    errors.push("condition.tag_field must be 'tag1' or 'tag2'".to_string());
+                }
+            }
+            return;
+        }
+
+        match self.operator.as_deref() {
+            Some(op) if THRESHOLD_OPERATORS.contains(&op) => {}
+            Some(op) => errors.push(format!(
+                "condition.operator must be one of {}, got '{}'",
+                THRESHOLD_OPERATORS.join(" "),
+                op
+            )),
+            None => errors.push(format!("condition.operator is required for {}", kind)),
+        }
+
+        match self.threshold_string().parse::<i64>() {
+            Ok(value) if kind != "rate_limit" && !(0..=100).contains(&value) => {
+                errors.push("condition.threshold must be a score between 0 and 100".to_string())
+            }
+            Ok(value) if kind == "rate_limit" && value < 0 => {
+                errors.push("condition.threshold must be a non-negative event count".to_string())
+            }
+            Ok(_) => {}
+            Err(_) => errors.push(format!(
+                "condition.threshold must be a whole number for {}",
+                kind
+            )),
+        }
+
+        if self.window_size == Some(0) {
+            errors.push("condition.window_size must be at least 1".to_string());
+        }
+
+        if kind == "rate_limit" {
+            match self.time_window.as_deref() {
+                Some(window) if is_valid_time_window(window) => {}
+                Some(window) => errors.push(format!(
+                    "condition.time_window must look like '10m', '1h' or '7d', got '{}'",
+                    window
+                )),
+                None => errors.push("condition.time_window is required for rate_limit".to_string()),
+            }
+        }
+    }
+
+    fn threshold_string(&self) -> String {
+        match &self.threshold {
+            Value::String(s) => s.trim().to_string(),
+            Value::Number(n) => n.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    fn to_request(&self) -> CreateConditionRequest {
+        let operator = self.operator.clone().unwrap_or_else(|| "=".to_string());
+        let (field, config) = match self.condition_type.as_str() {
+            "tag_equals" => (
+                self.tag_field.clone().unwrap_or_else(|| "tag1".to_string()),
+                None,
+            ),
+            "ema_threshold" => (
+                "score".to_string(),
+                Some(json!({ "window_size": self.window_size.unwrap_or(10) })),
+            ),
+            "rate_limit" => (
+                "event_count".to_string(),
+                Some(json!({ "time_window": self.time_window })),
+            ),
+            _ => ("score".to_string(), None),
+        };
+
+        CreateConditionRequest {
+            condition_type: self.condition_type.clone(),
+            field,
+            operator,
+            value: self.threshold_string(),
+            config,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let operator = self.operator.as_deref().unwrap_or("=");
+        let threshold = self.threshold_string();
+        match self.condition_type.as_str() {
+            "tag_equals" => format!(
+                "{} = '{}'",
+                self.tag_field.as_deref().unwrap_or("tag1"),
+                threshold
+            ),
+            "ema_threshold" => format!(
+                "moving average of last {} scores {} {}",
+                self.window_size.unwrap_or(10),
+                operator,
+                threshold
+            ),
+            "rate_limit" => format!(
+                "event count {} {} within {}",
+                operator,
+                threshold,
+                self.time_window.as_deref().unwrap_or_default()
+            ),
+            _ => format!("score {} {}", operator, threshold),
+        }
+    }
+}
+
+impl ActionArgs {
+    fn validate(&self, errors: &mut Vec<String>) {
+        let target = self.target.trim();
+        match self.action_type.as_str() {
+            "telegram" => {
+                if target.is_empty() {
+                    errors.push("action.target must be the Telegram chat ID".to_string());
+                }
+            }
+            "rest" => {
+                if !(target.starts_with("https://") || target.starts_with("http://")) {
+                    errors
+                        .push("action.target must be an http(s) URL for rest actions".to_string());
+                }
+                if let Some(method) = &self.method {
+                    if !REST_METHODS.contains(&method.to_uppercase().as_str()) {
+                        errors.push(format!(
+                            "action.method must be one of {}",
+                            REST_METHODS.join(", ")
+                        ));
+                    }
+                }
+            }
+            "mcp" => {
+                if !(target.starts_with("https://") || target.starts_with("http://")) {
+                    errors.push("action.target must be the MCP server's http(s) URL".to_string());
+                }
+                if self.tool_name.as_ref().is_none_or(|t| t.trim().is_empty()) {
+                    errors.push("action.tool_name is required for mcp actions".to_string());
+                }
+            }
+            other => errors.push(format!(
+                "action.type must be one of {}, got '{}'",
+                ACTION_TYPES.join(", "),
+                other
+            )),
+        }
+    }
+
+    fn to_request(&self) -> CreateActionRequest {
+        let target = self.target.trim();
+        let config = match self.action_type.as_str() {
+            "telegram" => json!({
+                "chat_id": target,
+                "message_template": self.message_template.clone().unwrap_or_else(|| {
+                    "{{event_type}} for agent {{agent_id}}".to_string()
+                }),
+            }),
+            "rest" => json!({
+                "method": self
+                    .method
+                    .as_deref()
+                    .unwrap_or("POST")
+                    .to_uppercase(),
+                "url": target,
+            }),
+            _ => json!({
+                "server_url": target,
+                "tool_name": self.tool_name.as_deref().unwrap_or_default().trim(),
+            }),
+        };
+
+        CreateActionRequest {
+            action_type: self.action_type.clone(),
+            priority: None,
+            config,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let target = self.target.trim();
+        match self.action_type.as_str() {
+            "telegram" => format!("send a Telegram message to chat {}", target),
+            "rest" => format!(
+                "call {} {}",
+                self.method.as_deref().unwrap_or("POST").to_uppercase(),
+                target
+            ),
+            _ => format!(
+                "call MCP tool '{}' on {}",
+                self.tool_name.as_deref().unwrap_or_default(),
+                target
+            ),
+        }
+    }
+}
+
+/// `<number><s|m|h|d>`, as accepted by the rate_limit evaluator
+fn is_valid_time_window(window: &str) -> bool {
+    let Some(unit) = window.chars().last() else {
+        return false;
+    };
+    let amount = &window[..window.len() - unit.len_utf8()];
+    matches!(unit, 's' | 'm' | 'h' | 'd')
+        && !amount.is_empty()
+        && amount.chars().all(|c| c.is_ascii_digit())
+        && amount.parse::<u64>().is_ok_and(|n| n > 0)
+}
+
+/// Summarize a created trigger for the model
+fn describe_trigger(args: &CreateTriggerArgs, trigger: &CreatedTrigger) -> String {
+    let chain = match trigger.chain_id {
+        Some(chain_id) => format!("chain {}", chain_id),
+        None => "all chains".to_string(),
+    };
+
+    let mut filters = Vec::new();
+    if let Some(agent_id) = args.agent_id {
+        filters.push(format!("agent {}", agent_id));
+    }
+    if let Some(event_type) = &args.event_type {
+        filters.push(format!("{} events", event_type));
+    }
+    if let Some(condition) = &args.condition {
+        filters.push(condition.summary());
+    }
+    let when = if filters.is_empty() {
+        "any event".to_string()
+    } else {
+        filters.join(" and ")
+    };
+
+    format!(
+        "Trigger '{}' watches the {} registry on {}. When {}, it will {}.{}",
+        trigger.name,
+        trigger.registry,
+        chain,
+        when,
+        args.action.summary(),
+        if trigger.enabled { "" } else { " (disabled)" }
+    )
+}
+
+/// Turn an API failure into a message the model can act on
+fn describe_api_error(operation: &str, error: &anyhow::Error, organization_id: &str) -> String {
+    if error_kind(error) == ErrorKind::Transient {
+        return format!(
+            "Failed to {}: the API is temporarily unavailable ({:#}). Try again shortly.",
+            operation, error
+        );
+    }
+    let Some(api_error) = error.downcast_ref::<ApiError>() else {
+        return format!("Failed to {}: {:#}", operation, error);
+    };
+
+    let message = api_error.message();
+    match api_error.status.as_u16() {
+        400 | 422 => format!(
+            "Failed to {}: the API rejected the parameters ({}). Correct them and try again.",
+            operation, message
+        ),
+        401 => format!(
+            "Failed to {}: authentication failed ({}). Check that AGENTAURI_API_KEY is set to a valid, unrevoked API key.",
+            operation, message
+        ),
+        403 => format!(
+            "Failed to {}: permission denied ({}). The API key needs the member or admin role in organization {}; use list_organizations to check your role.",
+            operation, message, organization_id
+        ),
+        404 => format!(
+            "Failed to {}: not found ({}). Check the ID and that it belongs to organization {}.",
+            operation, message, organization_id
+        ),
+        _ => format!("Failed to {}: {}", operation, api_error),
+    }
 }
 
 async fn handle_create_trigger(client: &AgentAuriClient, args: Value) -> ToolCallResult {
     let args: CreateTriggerArgs = match serde_json::from_value(args) {
         Ok(a) => a,
-        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {}", e)),
+        Err(e) => {
+            return ToolCallResult::error(format!(
+            "Invalid arguments: {}. See the create_trigger input schema for the expected fields.",
+            e
+        ))
+        }
     };
 
+    if let Err(errors) = args.validate() {
+        return ToolCallResult::error(format!(
+            "Invalid arguments:\n- {}\nNo trigger was created.",
+            errors.join("\n- ")
+        ));
+    }
+
+    let org_id = args.organization_id.as_str();
     let request = CreateTriggerRequest {
-        name: args.name,
-        registry: args.registry,
-        event_type: args.event_type,
+        name: args.name.clone(),
+        description: args.description.clone(),
         chain_id: args.chain_id,
-        enabled: args.enabled,
+        registry: args.registry.clone(),
+        enabled: None,
+        is_stateful: Some(args.is_stateful()),
     };
 
-    match client.create_trigger(request).await {
-        Ok(response) => ToolCallResult::json(&response),
-        Err(e) => ToolCallResult::error(format!("Failed to create trigger: {}", e)),
+    let trigger = match client.create_trigger(org_id, &request).await {
+        Ok(trigger) => trigger,
+        Err(e) => return ToolCallResult::error(describe_api_error("create trigger", &e, org_id)),
+    };
+
+    // Conditions and the action are separate calls; remove the trigger if any
+    // of them fails so a half-configured trigger never matches events
+    let mut condition_ids = Vec::new();
+    for condition in args.condition_requests() {
+        match client
+            .create_condition(org_id, &trigger.id, &condition)
+            .await
+        {
+            Ok(created) => condition_ids.push(created.id),
+            Err(e) => {
+                return rollback(client, org_id, &trigger.id, "add condition", &e).await;
+            }
+        }
+    }
+
+    let action_id = match client
+        .create_action(org_id, &trigger.id, &args.action.to_request())
+        .await
+    {
+        Ok(created) => created.id,
+        Err(e) => return rollback(client, org_id, &trigger.id, "add action", &e).await,
+    };
+
+    ToolCallResult::json(&json!({
+        "trigger_id": trigger.id,
+        "summary": describe_trigger(&args, &trigger),
+        "condition_ids": condition_ids,
+        "action_id": action_id,
+    }))
+}
+
+/// Delete a partially created trigger and report the original failure
+async fn rollback(
+    client: &AgentAuriClient,
+    organization_id: &str,
+    trigger_id: &str,
+    operation: &str,
+    error: &anyhow::Error,
+) -> ToolCallResult {
+    let message = describe_api_error(operation, error, organization_id);
+    match client.delete_org_trigger(organization_id, trigger_id).await {
+        Ok(()) => ToolCallResult::error(format!("{} No trigger was created.", message)),
+        Err(e) => ToolCallResult::error(format!(
+            "{} Trigger {} was created but could not be removed ({}); delete it with delete_trigger.",
+            message, trigger_id, e
+        )),
     }
 }
 
@@ -253,6 +904,148 @@ async fn handle_delete_trigger(client: &AgentAuriClient, args: Value) -> ToolCal
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FireTriggerArgs {
+    organization_id: String,
+    trigger_id: String,
+    event_data: Option<Value>,
+}
+
+impl FireTriggerArgs {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if self.organization_id.trim().is_empty() {
+            errors.push("organization_id must not be empty".to_string());
+        }
+        if self.trigger_id.trim().is_empty() {
+            errors.push("trigger_id must not be empty".to_string());
+        }
+        if self
+            .event_data
+            .as_ref()
+            .is_some_and(|data| !data.is_object())
+        {
+            errors.push("event_data must be an object".to_string());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Summarize a manual fire for the model
+fn describe_fired_trigger(fired: &FiredTrigger) -> String {
+    let mut text = format!(
+        "Fired trigger {}: enqueued {} action job(s) for test event {}.",
+        fired.trigger_id,
+        fired.jobs.len(),
+        fired.event_id
+    );
+    for job in &fired.jobs {
+        text.push_str(&format!(
+            "\n- {} action {} (job {})",
+            job.action_type, job.action_id, job.job_id
+        ));
+    }
+    text.push_str(&format!(
+        "\nCorrelation ID: {}. Conditions were not evaluated; the action workers will run the jobs shortly.",
+        fired.correlation_id
+    ));
+    text
+}
+
+async fn handle_fire_trigger(client: &AgentAuriClient, args: Value) -> ToolCallResult {
+    let args: FireTriggerArgs = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {}", e)),
+    };
+
+    if let Err(errors) = args.validate() {
+        return ToolCallResult::error(format!(
+            "Invalid arguments:\n- {}\nThe trigger was not fired.",
+            errors.join("\n- ")
+        ));
+    }
+
+    let request = FireTriggerRequest {
+        event_data: args.event_data,
+    };
+    match client
+        .fire_trigger(&args.organization_id, &args.trigger_id, &request)
+        .await
+    {
+        Ok(fired) => ToolCallResult::text(describe_fired_trigger(&fired)),
+        Err(e) => ToolCallResult::error(describe_api_error(
+            "fire trigger",
+            &e,
+            &args.organization_id,
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GetTriggerStateArgs {
+    organization_id: String,
+    trigger_id: String,
+}
+
+/// Summarize evaluator state for the model
+fn describe_trigger_state(state: &TriggerState) -> String {
+    let Some(data) = &state.state else {
+        return if state.is_stateful {
+            format!(
+                "Trigger {} is stateful but has not been evaluated yet, so it has no state.",
+                state.trigger_id
+            )
+        } else {
+            format!(
+                "Trigger {} is not stateful; it keeps no evaluator state.",
+                state.trigger_id
+            )
+        };
+    };
+
+    let pretty = serde_json::to_string_pretty(data).unwrap_or_else(|_| data.to_string());
+    let mut text = format!("Evaluator state for trigger {}", state.trigger_id);
+    if let Some(last_updated) = &state.last_updated {
+        text.push_str(&format!(" (last updated {})", last_updated));
+    }
+    if !state.is_stateful {
+        text.push_str(". The trigger is no longer stateful, so this state is stale");
+    }
+    text.push_str(&format!(":\n{}", pretty));
+    text
+}
+
+async fn handle_get_trigger_state(client: &AgentAuriClient, args: Value) -> ToolCallResult {
+    let args: GetTriggerStateArgs = match serde_json::from_value(args) {
+        Ok(a) => a,
+        Err(e) => return ToolCallResult::error(format!("Invalid arguments: {}", e)),
+    };
+
+    if args.organization_id.trim().is_empty() || args.trigger_id.trim().is_empty() {
+        return ToolCallResult::error(
+            "Invalid arguments: organization_id and trigger_id must not be empty",
+        );
+    }
+
+    match client
+        .get_trigger_state(&args.organization_id, &args.trigger_id)
+        .await
+    {
+        Ok(state) => ToolCallResult::text(describe_trigger_state(&state)),
+        Err(e) => ToolCallResult::error(describe_api_error(
+            "get trigger state",
+            &e,
+            &args.organization_id,
+        )),
+    }
+}
+
 async fn handle_list_linked_agents(client: &AgentAuriClient) -> ToolCallResult {
     match client.list_linked_agents().await {
         Ok(response) => ToolCallResult::json(&response),
@@ -308,3 +1101,367 @@ async fn handle_list_organizations(client: &AgentAuriClient) -> ToolCallResult {
         Err(e) => ToolCallResult::error(format!("Failed to list organizations: {}", e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ToolContent;
+    use crate::test_support::{spawn_gateway, MockResponse};
+
+    fn result_text(result: &ToolCallResult) -> &str {
+        match &result.content[0] {
+            ToolContent::Text { text } => text,
+        }
+    }
+
+    fn valid_args() -> Value {
+        json!({
+            "organization_id": "org_123",
+            "name": "Low score alert",
+            "registry": "reputation",
+            "chain_id": 8453,
+            "agent_id": 42,
+            "condition": { "type": "score_threshold", "operator": "<", "threshold": 60 },
+            "action": { "type": "telegram", "target": "123456789" }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_end_to_end() {
+        let (url, recorded) = spawn_gateway(vec![
+            MockResponse::json(
+                "201 Created",
+                json!({"data": {
+                    "id": "trig_1", "name": "Low score alert", "registry": "reputation",
+                    "chain_id": 8453, "enabled": true
+                }}),
+            ),
+            MockResponse::json("201 Created", json!({"data": {"id": "cond_1"}})),
+            MockResponse::json("201 Created", json!({"data": {"id": "cond_2"}})),
+            MockResponse::json("201 Created", json!({"data": {"id": "act_1"}})),
+        ])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let result = handle_tool_call(&client, "create_trigger", Some(valid_args())).await;
+        assert!(result.is_error.is_none(), "{}", result_text(&result));
+
+        let output: Value = serde_json::from_str(result_text(&result)).unwrap();
+        assert_eq!(output["trigger_id"], "trig_1");
+        assert_eq!(output["action_id"], "act_1");
+        assert_eq!(output["condition_ids"], json!(["cond_1", "cond_2"]));
+        let summary = output["summary"].as_str().unwrap();
+        assert!(summary.contains("agent 42 and score < 60"), "{}", summary);
+        assert!(summary.contains("chat 123456789"), "{}", summary);
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].0, "POST /api/v1/triggers HTTP/1.1");
+        let trigger: Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(trigger["chain_id"], 8453);
+        assert_eq!(trigger["is_stateful"], false);
+
+        assert_eq!(
+            requests[1].0,
+            "POST /api/v1/triggers/trig_1/conditions HTTP/1.1"
+        );
+        let agent: Value = serde_json::from_str(&requests[1].1).unwrap();
+        assert_eq!(agent["condition_type"], "agent_id_equals");
+        assert_eq!(agent["value"], "42");
+        let score: Value = serde_json::from_str(&requests[2].1).unwrap();
+        assert_eq!(score["condition_type"], "score_threshold");
+        assert_eq!(score["operator"], "<");
+        assert_eq!(score["value"], "60");
+
+        assert_eq!(
+            requests[3].0,
+            "POST /api/v1/triggers/trig_1/actions HTTP/1.1"
+        );
+        let action: Value = serde_json::from_str(&requests[3].1).unwrap();
+        assert_eq!(action["action_type"], "telegram");
+        assert_eq!(action["config"]["chat_id"], "123456789");
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_validation_failure_is_reported() {
+        // No server is listening: validation must fail before any request
+        let client = AgentAuriClient::new("http://127.0.0.1:9".to_string(), None);
+
+        let mut args = valid_args();
+        args["registry"] = json!("payments");
+        args["condition"] = json!({ "type": "rate_limit", "operator": ">", "threshold": 10 });
+        args["action"] = json!({ "type": "rest", "target": "not-a-url" });
+
+        let result = handle_tool_call(&client, "create_trigger", Some(args)).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let text = result_text(&result);
+        assert!(text.contains("registry must be one of"), "{}", text);
+        assert!(text.contains("time_window is required"), "{}", text);
+        assert!(text.contains("http(s) URL"), "{}", text);
+        assert!(text.contains("No trigger was created"), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_rejects_unknown_fields() {
+        let client = AgentAuriClient::new("http://127.0.0.1:9".to_string(), None);
+
+        let mut args = valid_args();
+        args["threshold"] = json!(60);
+
+        let result = handle_tool_call(&client, "create_trigger", Some(args)).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(result_text(&result).contains("unknown field `threshold`"));
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_permission_error_is_actionable() {
+        let (url, _) = spawn_gateway(vec![MockResponse::json(
+            "403 Forbidden",
+            json!({"error": "forbidden", "message": "Insufficient permissions to create triggers"}),
+        )])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let result = handle_tool_call(&client, "create_trigger", Some(valid_args())).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let text = result_text(&result);
+        assert!(text.contains("Insufficient permissions"), "{}", text);
+        assert!(
+            text.contains("member or admin role in organization org_123"),
+            "{}",
+            text
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_trigger_rolls_back_on_action_failure() {
+        let (url, recorded) = spawn_gateway(vec![
+            MockResponse::json(
+                "201 Created",
+                json!({"data": {
+                    "id": "trig_1", "name": "Low score alert", "registry": "reputation",
+                    "chain_id": 8453, "enabled": true
+                }}),
+            ),
+            MockResponse::json("201 Created", json!({"data": {"id": "cond_1"}})),
+            MockResponse::json("201 Created", json!({"data": {"id": "cond_2"}})),
+            MockResponse::json(
+                "400 Bad Request",
+                json!({"error": "validation_error", "message": "Validation failed: config"}),
+            ),
+            MockResponse::json("204 No Content", json!(null)),
+        ])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let result = handle_tool_call(&client, "create_trigger", Some(valid_args())).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(result_text(&result).contains("No trigger was created"));
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests[4].0, "DELETE /api/v1/triggers/trig_1 HTTP/1.1");
+    }
+
+    #[test]
+    fn test_stateful_conditions_build_config() {
+        let condition: ConditionArgs = serde_json::from_value(json!({
+            "type": "rate_limit", "operator": ">", "threshold": "10", "time_window": "1h"
+        }))
+        .unwrap();
+        let request = condition.to_request();
+        assert_eq!(request.field, "event_count");
+        assert_eq!(request.config, Some(json!({"time_window": "1h"})));
+
+        let condition: ConditionArgs = serde_json::from_value(json!({
+            "type": "ema_threshold", "operator": "<", "threshold": 70
+        }))
+        .unwrap();
+        assert_eq!(
+            condition.to_request().config,
+            Some(json!({"window_size": 10}))
+        );
+    }
+
+    #[test]
+    fn test_time_window_format() {
+        assert!(is_valid_time_window("10s"));
+        assert!(is_valid_time_window("7d"));
+        assert!(!is_valid_time_window("0h"));
+        assert!(!is_valid_time_window("h"));
+        assert!(!is_valid_time_window("1w"));
+        assert!(!is_valid_time_window(""));
+    }
+
+    #[tokio::test]
+    async fn test_fire_trigger_reports_enqueued_jobs() {
+        let (url, recorded) = spawn_gateway(vec![MockResponse::json(
+            "202 Accepted",
+            json!({"data": {
+                "trigger_id": "trig_1", "event_id": "manual-1", "correlation_id": "corr_1",
+                "jobs": [{"job_id": "job_1", "action_id": 7, "action_type": "telegram"}]
+            }}),
+        )])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let args = json!({
+            "organization_id": "org_123",
+            "trigger_id": "trig_1",
+            "event_data": {"agent_id": 42, "score": 55}
+        });
+        let result = handle_tool_call(&client, "fire_trigger", Some(args)).await;
+        assert!(result.is_error.is_none(), "{}", result_text(&result));
+
+        let text = result_text(&result);
+        assert!(text.contains("enqueued 1 action job(s)"), "{}", text);
+        assert!(text.contains("telegram action 7 (job job_1)"), "{}", text);
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests[0].0, "POST /api/v1/triggers/trig_1/fire HTTP/1.1");
+        let body: Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(body["event_data"]["score"], 55);
+    }
+
+    #[tokio::test]
+    async fn test_fire_trigger_validates_arguments() {
+        let client = AgentAuriClient::new("http://127.0.0.1:9".to_string(), None);
+
+        let args = json!({"organization_id": "", "trigger_id": "trig_1", "event_data": [1]});
+        let result = handle_tool_call(&client, "fire_trigger", Some(args)).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let text = result_text(&result);
+        assert!(
+            text.contains("organization_id must not be empty"),
+            "{}",
+            text
+        );
+        assert!(text.contains("event_data must be an object"), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_fire_trigger_api_error_is_tool_error() {
+        let (url, _) = spawn_gateway(vec![MockResponse::json(
+            "422 Unprocessable Entity",
+            json!({"error": "no_actions", "message": "Trigger has no actions to fire"}),
+        )])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let args = json!({"organization_id": "org_123", "trigger_id": "trig_1"});
+        let result = handle_tool_call(&client, "fire_trigger", Some(args)).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(result_text(&result).contains("Trigger has no actions to fire"));
+    }
+
+    #[tokio::test]
+    async fn test_get_trigger_fetches_several_ids() {
+        let trigger = |id: &str| {
+            MockResponse::json(
+                "200 OK",
+                json!({
+                    "id": id, "name": "Low score alert", "enabled": true,
+                    "registry": "reputation", "event_type": "NewFeedback", "chain_id": null,
+                    "created_at": "2026-01-01T00:00:00Z", "updated_at": "2026-01-01T00:00:00Z"
+                }),
+            )
+        };
+        let (url, recorded) = spawn_gateway(vec![
+            trigger("trig_1"),
+            trigger("trig_2"),
+            MockResponse::json(
+                "404 Not Found",
+                json!({"error": "not_found", "message": "Trigger not found"}),
+            ),
+        ])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let args = json!({"trigger_ids": ["trig_1", "trig_2", "trig_3"]});
+        let result = handle_tool_call(&client, "get_trigger", Some(args)).await;
+        assert!(result.is_error.is_none(), "{}", result_text(&result));
+
+        // Responses are served in arrival order, so only the shape is checked
+        let body: Value = serde_json::from_str(result_text(&result)).unwrap();
+        let triggers = body["triggers"].as_array().unwrap();
+        let ids: Vec<_> = triggers.iter().map(|t| &t["trigger_id"]).collect();
+        assert_eq!(ids, ["trig_1", "trig_2", "trig_3"]);
+        assert_eq!(
+            triggers.iter().filter(|t| t["error"].is_string()).count(),
+            1
+        );
+        assert_eq!(recorded.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_trigger_requires_one_form_of_id() {
+        let client = AgentAuriClient::new("http://127.0.0.1:1".to_string(), None);
+        for args in [
+            json!({}),
+            json!({"trigger_ids": []}),
+            json!({"trigger_id": "trig_1", "trigger_ids": ["trig_2"]}),
+        ] {
+            let result = handle_tool_call(&client, "get_trigger", Some(args)).await;
+            assert_eq!(result.is_error, Some(true));
+            assert!(result_text(&result).starts_with("Invalid arguments"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_trigger_state_formats_state() {
+        let (url, recorded) = spawn_gateway(vec![
+            MockResponse::json(
+                "200 OK",
+                json!({"data": {
+                    "trigger_id": "trig_1", "is_stateful": true,
+                    "state": {"ema": 71.5, "count": 12},
+                    "last_updated": "2026-01-01T00:00:00Z"
+                }}),
+            ),
+            MockResponse::json(
+                "200 OK",
+                json!({"data": {
+                    "trigger_id": "trig_2", "is_stateful": true,
+                    "state": null, "last_updated": null
+                }}),
+            ),
+        ])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let args = json!({"organization_id": "org_123", "trigger_id": "trig_1"});
+        let result = handle_tool_call(&client, "get_trigger_state", Some(args)).await;
+        assert!(result.is_error.is_none(), "{}", result_text(&result));
+        let text = result_text(&result);
+        assert!(
+            text.contains("last updated 2026-01-01T00:00:00Z"),
+            "{}",
+            text
+        );
+        assert!(text.contains("\"ema\": 71.5"), "{}", text);
+
+        let args = json!({"organization_id": "org_123", "trigger_id": "trig_2"});
+        let result = handle_tool_call(&client, "get_trigger_state", Some(args)).await;
+        assert!(result_text(&result).contains("has not been evaluated yet"));
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests[0].0, "GET /api/v1/triggers/trig_1/state HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_get_trigger_state_not_found_is_tool_error() {
+        let (url, _) = spawn_gateway(vec![MockResponse::json(
+            "404 Not Found",
+            json!({"error": "not_found", "message": "Trigger not found"}),
+        )])
+        .await;
+        let client = AgentAuriClient::new(url, Some("sk_test_key".to_string()));
+
+        let args = json!({"organization_id": "org_123", "trigger_id": "missing"});
+        let result = handle_tool_call(&client, "get_trigger_state", Some(args)).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(result_text(&result).contains("belongs to organization org_123"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Wall-clock time source
+//!
+//! Time-dependent components (e.g. the [`RateLimiter`](crate::RateLimiter))
+//! read the time through [`Clock`] so tests can substitute a [`ManualClock`]
+//! and step across window boundaries exactly, instead of sleeping.
+//!
+//! # Example
+//!
+//! ```
+//! use shared::{Clock, ManualClock};
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new(1_700_000_000_000);
+//! clock.advance(Duration::from_secs(60));
+//! assert_eq!(clock.now_ms(), 1_700_000_060_000);
+//! ```
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Current time as milliseconds since the Unix epoch
+    fn now_ms(&self) -> i64;
+
+    /// Current time as whole seconds since the Unix epoch
+    fn now_secs(&self) -> i64 {
+        self.now_ms().div_euclid(1000)
+    }
+}
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0)
+    }
+}
+
+/// Clock that only moves when told to
+///
+/// Clones share the same time, so a test can keep one and hand another to
+/// the component under test.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now_ms: Arc<AtomicI64>,
+}
+
+impl ManualClock {
+    /// Start at the given time (milliseconds since the Unix epoch)
+    pub fn new(now_ms: i64) -> Self {
+        Self {
+            now_ms: Arc::new(AtomicI64::new(now_ms)),
+        }
+    }
+
+    /// Jump to the given time (milliseconds since the Unix epoch)
+    pub fn set(&self, now_ms: i64) {
+        self.now_ms.store(now_ms, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        self.now_ms
+            .fetch_add(by.as_millis() as i64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> i64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_is_current() {
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let now = SystemClock.now_ms();
+
+        assert!(now >= before);
+        assert_eq!(SystemClock.now_secs(), now / 1000);
+    }
+
+    #[test]
+    fn test_manual_clock_moves_only_when_told() {
+        let clock = ManualClock::new(1_000);
+        assert_eq!(clock.now_ms(), 1_000);
+
+        clock.advance(Duration::from_millis(1_500));
+        assert_eq!(clock.now_ms(), 2_500);
+        assert_eq!(clock.now_secs(), 2);
+
+        clock.set(60_000);
+        assert_eq!(clock.now_ms(), 60_000);
+    }
+
+    #[test]
+    fn test_manual_clock_clones_share_time() {
+        let clock = ManualClock::new(0);
+        let handle = clock.clone();
+
+        handle.advance(Duration::from_secs(5));
+        assert_eq!(clock.now_ms(), 5_000);
+    }
+}
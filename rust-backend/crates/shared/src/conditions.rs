@@ -0,0 +1,606 @@
+//! Trigger condition definitions
+//!
+//! A condition compares one field of an event with its value:
+//!
+//! ```json
+//! { "condition_type": "score_threshold", "field": "score", "operator": "<", "value": "60" }
+//! ```
+//!
+//! This module declares the event fields conditions can read ([`EVENT_FIELDS`])
+//! and, for each [`ConditionType`], the fields it reads, the operators it
+//! accepts and what its value must parse as. The event processor evaluates
+//! with these definitions and the API gateway validates against them, so a
+//! condition that passes [`validate_condition`] does not fail evaluation for
+//! its shape.
+//!
+//! # JSONPath conditions
+//!
+//! A `json_path` condition reads nested values: its field is a JSONPath
+//! expression ([RFC 9535](https://www.rfc-editor.org/rfc/rfc9535)) resolved
+//! against the event data that actions receive as `event`:
+//!
+//! ```json
+//! { "condition_type": "json_path", "field": "$.metadata.tags[0]", "operator": "=", "value": "defi" }
+//! ```
+//!
+//! The condition matches if any value the path selects compares true with the
+//! condition value (numbers numerically, strings lexically, other values by
+//! their JSON text with `=` and `!=` only). A path that selects nothing, or
+//! only `null`, does not match. The `exists` operator matches when the path
+//! selects a non-null value and ignores the condition value.
+//!
+//! # Example
+//!
+//! ```
+//! use shared::conditions::{validate_condition, ConditionError};
+//!
+//! assert!(validate_condition("score_threshold", "score", "<", "60", None).is_ok());
+//!
+//! let errors = validate_condition("score_threshold", "score", "~", "high", None).unwrap_err();
+//! assert!(matches!(errors[0], ConditionError::UnsupportedOperator { .. }));
+//! assert!(matches!(errors[1], ConditionError::TypeMismatch { .. }));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json_path::JsonPath;
+use thiserror::Error;
+
+/// Type of an event field or condition value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueType {
+    /// Signed 64-bit integer
+    Integer,
+    /// Non-negative 32-bit integer
+    Count,
+    /// Finite decimal number
+    Number,
+    /// Any string
+    Text,
+}
+
+impl ValueType {
+    /// Whether `value` parses as this type
+    pub fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::Integer => value.parse::<i64>().is_ok(),
+            Self::Count => value.parse::<u32>().is_ok(),
+            Self::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            Self::Text => true,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Integer => "an integer",
+            Self::Count => "a non-negative integer",
+            Self::Number => "a number",
+            Self::Text => "text",
+        }
+    }
+}
+
+/// Event fields conditions can read, with their types
+///
+/// `event_count` is not stored on events: `rate_limit` counts the events in
+/// its time window.
+pub const EVENT_FIELDS: &[(&str, ValueType)] = &[
+    ("agent_id", ValueType::Integer),
+    ("event_type", ValueType::Text),
+    ("score", ValueType::Integer),
+    ("tag1", ValueType::Text),
+    ("tag2", ValueType::Text),
+    ("event_count", ValueType::Count),
+];
+
+/// Type of an event field, if conditions can read it
+pub fn field_type(field: &str) -> Option<ValueType> {
+    EVENT_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, value_type)| *value_type)
+}
+
+/// Comparison operators of threshold conditions
+const COMPARISONS: &[&str] = &["<", ">", "=", "==", "<=", ">=", "!=", "<>"];
+
+/// Operators of `ema_crossover`
+const CROSSINGS: &[&str] = &["crosses_above", "crosses_below", "crosses"];
+
+/// Operators of equality conditions
+const EQUALITY: &[&str] = &["=", "=="];
+
+/// Operators of `json_path`
+const JSON_PATH_OPERATORS: &[&str] = &["<", ">", "=", "==", "<=", ">=", "!=", "<>", "exists"];
+
+/// Operator of `json_path` that only checks the path selects a value
+pub const EXISTS: &str = "exists";
+
+/// Comparison operator of a threshold condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    Greater,
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Parse an operator (`<`, `>`, `=`/`==`, `<=`, `>=`, `!=`/`<>`)
+    pub fn parse(operator: &str) -> Option<Self> {
+        match operator {
+            "<" => Some(Self::Less),
+            ">" => Some(Self::Greater),
+            "=" | "==" => Some(Self::Equal),
+            "<=" => Some(Self::LessOrEqual),
+            ">=" => Some(Self::GreaterOrEqual),
+            "!=" | "<>" => Some(Self::NotEqual),
+            _ => None,
+        }
+    }
+
+    /// Compare `left` with `right`
+    pub fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Self::Less => left < right,
+            Self::Greater => left > right,
+            Self::Equal => left == right,
+            Self::LessOrEqual => left <= right,
+            Self::GreaterOrEqual => left >= right,
+            Self::NotEqual => left != right,
+        }
+    }
+}
+
+/// Condition types the event processor evaluates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionType {
+    AgentIdEquals,
+    ScoreThreshold,
+    TagEquals,
+    EventTypeEquals,
+    EmaThreshold,
+    EmaCrossover,
+    RateLimit,
+    JsonPath,
+}
+
+impl ConditionType {
+    pub const ALL: [Self; 8] = [
+        Self::AgentIdEquals,
+        Self::ScoreThreshold,
+        Self::TagEquals,
+        Self::EventTypeEquals,
+        Self::EmaThreshold,
+        Self::EmaCrossover,
+        Self::RateLimit,
+        Self::JsonPath,
+    ];
+
+    pub fn parse(condition_type: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == condition_type)
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AgentIdEquals => "agent_id_equals",
+            Self::ScoreThreshold => "score_threshold",
+            Self::TagEquals => "tag_equals",
+            Self::EventTypeEquals => "event_type_equals",
+            Self::EmaThreshold => "ema_threshold",
+            Self::EmaCrossover => "ema_crossover",
+            Self::RateLimit => "rate_limit",
+            Self::JsonPath => "json_path",
+        }
+    }
+
+    /// Whether evaluation keeps state across events (and needs a `config`)
+    pub fn is_stateful(self) -> bool {
+        matches!(
+            self,
+            Self::EmaThreshold | Self::EmaCrossover | Self::RateLimit
+        )
+    }
+
+    /// Event fields the condition can read
+    ///
+    /// Empty for `json_path`, whose field is a JSONPath expression.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::JsonPath => &[],
+            Self::AgentIdEquals => &["agent_id"],
+            Self::ScoreThreshold | Self::EmaThreshold | Self::EmaCrossover => &["score"],
+            Self::TagEquals => &["tag1", "tag2"],
+            Self::EventTypeEquals => &["event_type"],
+            Self::RateLimit => &["event_count"],
+        }
+    }
+
+    /// Operators the condition accepts
+    pub fn operators(self) -> &'static [&'static str] {
+        match self {
+            Self::AgentIdEquals | Self::TagEquals | Self::EventTypeEquals => EQUALITY,
+            Self::ScoreThreshold | Self::EmaThreshold | Self::RateLimit => COMPARISONS,
+            Self::EmaCrossover => CROSSINGS,
+            Self::JsonPath => JSON_PATH_OPERATORS,
+        }
+    }
+
+    /// Type the value must parse as, `None` if the value is not used
+    ///
+    /// `ema_threshold` compares an average, so its threshold may be a decimal.
+    /// The type of values a JSONPath selects is only known at evaluation.
+    pub fn value_type(self) -> Option<ValueType> {
+        match self {
+            Self::EmaThreshold => Some(ValueType::Number),
+            Self::JsonPath => Some(ValueType::Text),
+            Self::EmaCrossover => None,
+            other => field_type(other.fields()[0]),
+        }
+    }
+}
+
+/// Problems that would make a condition fail evaluation
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConditionError {
+    #[error("Unknown condition type '{0}'")]
+    UnknownConditionType(String),
+
+    #[error("Unknown field '{0}'")]
+    UnknownField(String),
+
+    #[error("Invalid JSONPath '{path}': {reason}")]
+    InvalidPath { path: String, reason: String },
+
+    #[error("Field '{field}' is not supported by {condition_type} (expected {})", .expected.join(", "))]
+    UnsupportedField {
+        field: String,
+        condition_type: &'static str,
+        expected: &'static [&'static str],
+    },
+
+    #[error("Operator '{operator}' is not supported by {condition_type} (expected {})", .expected.join(", "))]
+    UnsupportedOperator {
+        operator: String,
+        condition_type: &'static str,
+        expected: &'static [&'static str],
+    },
+
+    #[error("Value '{value}' is not {}", .expected.describe())]
+    TypeMismatch { value: String, expected: ValueType },
+
+    #[error("{0} requires a config")]
+    MissingConfig(&'static str),
+}
+
+impl ConditionError {
+    /// Machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownConditionType(_) => "unknown_condition_type",
+            Self::UnknownField(_) => "unknown_field",
+            Self::InvalidPath { .. } => "invalid_path",
+            Self::UnsupportedField { .. } => "unsupported_field",
+            Self::UnsupportedOperator { .. } => "unsupported_operator",
+            Self::TypeMismatch { .. } => "type_mismatch",
+            Self::MissingConfig(_) => "missing_config",
+        }
+    }
+
+    /// The condition property at fault
+    pub fn property(&self) -> &'static str {
+        match self {
+            Self::UnknownConditionType(_) => "condition_type",
+            Self::UnknownField(_) | Self::InvalidPath { .. } | Self::UnsupportedField { .. } => {
+                "field"
+            }
+            Self::UnsupportedOperator { .. } => "operator",
+            Self::TypeMismatch { .. } => "value",
+            Self::MissingConfig(_) => "config",
+        }
+    }
+}
+
+/// Check a condition against the event fields and its type's definition
+///
+/// Returns every problem found; an unknown condition type is the only one
+/// reported alone, since nothing else can be checked without it. The
+/// contents of a stateful condition's `config` are checked by its evaluator.
+pub fn validate_condition(
+    condition_type: &str,
+    field: &str,
+    operator: &str,
+    value: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<(), Vec<ConditionError>> {
+    let Some(kind) = ConditionType::parse(condition_type) else {
+        return Err(vec![ConditionError::UnknownConditionType(
+            condition_type.to_string(),
+        )]);
+    };
+
+    let mut errors = Vec::new();
+
+    if kind == ConditionType::JsonPath {
+        if let Err(error) = parse_json_path(field) {
+            errors.push(error);
+        }
+    } else if field_type(field).is_none() {
+        errors.push(ConditionError::UnknownField(field.to_string()));
+    } else if !kind.fields().contains(&field) {
+        errors.push(ConditionError::UnsupportedField {
+            field: field.to_string(),
+            condition_type: kind.as_str(),
+            expected: kind.fields(),
+        });
+    }
+
+    if !kind.operators().contains(&operator) {
+        errors.push(ConditionError::UnsupportedOperator {
+            operator: operator.to_string(),
+            condition_type: kind.as_str(),
+            expected: kind.operators(),
+        });
+    }
+
+    if let Some(expected) = kind.value_type() {
+        if !expected.accepts(value) {
+            errors.push(ConditionError::TypeMismatch {
+                value: value.to_string(),
+                expected,
+            });
+        }
+    }
+
+    if kind.is_stateful() && config.is_none_or(serde_json::Value::is_null) {
+        errors.push(ConditionError::MissingConfig(kind.as_str()));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse the field of a `json_path` condition
+pub fn parse_json_path(path: &str) -> Result<JsonPath, ConditionError> {
+    JsonPath::parse(path).map_err(|e| ConditionError::InvalidPath {
+        path: path.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Whether the values `path` selects from `data` satisfy a `json_path` condition
+///
+/// See the [module docs](self) for how values compare. `None` if `operator`
+/// is not one of the condition's operators.
+pub fn json_path_matches(
+    path: &JsonPath,
+    operator: &str,
+    value: &str,
+    data: &serde_json::Value,
+) -> Option<bool> {
+    let mut nodes = path.query(data).all().into_iter().filter(|n| !n.is_null());
+
+    if operator == EXISTS {
+        return Some(nodes.next().is_some());
+    }
+    let comparison = Comparison::parse(operator)?;
+
+    Some(nodes.any(|node| match node {
+        serde_json::Value::Number(number) => match (number.as_f64(), value.parse::<f64>()) {
+            (Some(left), Ok(right)) => comparison.compare(left, right),
+            _ => false,
+        },
+        serde_json::Value::String(text) => comparison.compare(text.as_str(), value),
+        other => {
+            matches!(comparison, Comparison::Equal | Comparison::NotEqual)
+                && comparison.compare(other.to_string().as_str(), value)
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_conditions() {
+        assert!(validate_condition("agent_id_equals", "agent_id", "=", "42", None).is_ok());
+        assert!(validate_condition("score_threshold", "score", "<=", "-5", None).is_ok());
+        assert!(validate_condition("tag_equals", "tag2", "==", "reliable", None).is_ok());
+        assert!(
+            validate_condition("event_type_equals", "event_type", "=", "NewFeedback", None).is_ok()
+        );
+        let ema = json!({"window_size": 10});
+        assert!(validate_condition("ema_threshold", "score", "<", "70.5", Some(&ema)).is_ok());
+        let crossover = json!({"fast_period": 5, "slow_period": 20});
+        assert!(validate_condition(
+            "ema_crossover",
+            "score",
+            "crosses_above",
+            "ignored",
+            Some(&crossover)
+        )
+        .is_ok());
+        let rate = json!({"time_window": "1h"});
+        assert!(validate_condition("rate_limit", "event_count", ">", "10", Some(&rate)).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_condition_type_is_reported_alone() {
+        assert_eq!(
+            validate_condition("score_above", "nope", "~", "x", None),
+            Err(vec![ConditionError::UnknownConditionType(
+                "score_above".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_unsupported_fields() {
+        let errors = validate_condition("score_threshold", "rating", "<", "60", None).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ConditionError::UnknownField("rating".to_string())]
+        );
+
+        let errors = validate_condition("tag_equals", "score", "=", "trade", None).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "unsupported_field");
+        assert_eq!(
+            errors[0].to_string(),
+            "Field 'score' is not supported by tag_equals (expected tag1, tag2)"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_operator() {
+        let errors = validate_condition("tag_equals", "tag1", "<", "trade", None).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "unsupported_operator");
+        assert_eq!(errors[0].property(), "operator");
+
+        let config = json!({"fast_period": 5, "slow_period": 20});
+        let errors =
+            validate_condition("ema_crossover", "score", ">", "0", Some(&config)).unwrap_err();
+        assert_eq!(errors[0].code(), "unsupported_operator");
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let cases = [
+            ("agent_id_equals", "agent_id", "=", "agent-42"),
+            ("score_threshold", "score", "<", "60.5"),
+            ("rate_limit", "event_count", ">", "-1"),
+            ("ema_threshold", "score", "<", "NaN"),
+        ];
+        for (condition_type, field, operator, value) in cases {
+            let config = json!({});
+            let errors = validate_condition(condition_type, field, operator, value, Some(&config))
+                .unwrap_err();
+            assert_eq!(errors.len(), 1, "{}", condition_type);
+            assert_eq!(errors[0].code(), "type_mismatch", "{}", condition_type);
+        }
+    }
+
+    #[test]
+    fn test_stateful_condition_requires_config() {
+        assert_eq!(
+            validate_condition("rate_limit", "event_count", ">", "10", None),
+            Err(vec![ConditionError::MissingConfig("rate_limit")])
+        );
+        assert_eq!(
+            validate_condition("ema_threshold", "score", "<", "70", Some(&json!(null))),
+            Err(vec![ConditionError::MissingConfig("ema_threshold")])
+        );
+    }
+
+    #[test]
+    fn test_all_problems_are_reported() {
+        let errors = validate_condition("score_threshold", "tag1", "~", "high", None).unwrap_err();
+        let codes: Vec<_> = errors.iter().map(ConditionError::code).collect();
+        assert_eq!(
+            codes,
+            vec!["unsupported_field", "unsupported_operator", "type_mismatch"]
+        );
+    }
+
+    #[test]
+    fn test_json_path_condition_validation() {
+        assert!(validate_condition("json_path", "$.metadata.tags[0]", "=", "defi", None).is_ok());
+        assert!(
+            validate_condition("json_path", "$.logs[?@.score < 50]", "exists", "", None).is_ok()
+        );
+
+        let errors = validate_condition("json_path", "$.logs[0", "=", "x", None).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "invalid_path");
+        assert_eq!(errors[0].property(), "field");
+
+        let errors = validate_condition("json_path", "score", "contains", "x", None).unwrap_err();
+        let codes: Vec<_> = errors.iter().map(ConditionError::code).collect();
+        assert_eq!(codes, vec!["invalid_path", "unsupported_operator"]);
+    }
+
+    fn matches(path: &str, operator: &str, value: &str, data: &serde_json::Value) -> bool {
+        json_path_matches(&parse_json_path(path).unwrap(), operator, value, data).unwrap()
+    }
+
+    #[test]
+    fn test_json_path_nested_extraction() {
+        let data = json!({"metadata": {"owner": {"name": "alice", "level": 3}}});
+
+        assert!(matches("$.metadata.owner.name", "=", "alice", &data));
+        assert!(!matches("$.metadata.owner.name", "=", "bob", &data));
+        assert!(matches("$.metadata.owner.level", ">=", "3", &data));
+        assert!(matches("$.metadata.owner.level", "==", "3.0", &data));
+        assert!(!matches("$.metadata.owner.level", "<", "3", &data));
+        assert!(matches("$.metadata.owner", "exists", "", &data));
+    }
+
+    #[test]
+    fn test_json_path_array_indexing_and_filters() {
+        let data = json!({
+            "logs": [
+                {"topics": ["0xddf2", "0x0000a1"], "score": 80},
+                {"topics": ["0x8c5b"], "score": 40}
+            ]
+        });
+
+        assert!(matches("$.logs[0].topics[1]", "=", "0x0000a1", &data));
+        assert!(matches("$.logs[-1].topics[0]", "=", "0x8c5b", &data));
+        // Any selected value may match
+        assert!(matches("$.logs[*].score", "<", "50", &data));
+        assert!(!matches("$.logs[*].score", ">", "90", &data));
+        assert!(matches(
+            "$.logs[?@.score < 50].topics[0]",
+            "=",
+            "0x8c5b",
+            &data
+        ));
+        // Non-scalar values compare by their JSON text
+        assert!(matches("$.logs[1].topics", "=", r#"["0x8c5b"]"#, &data));
+        assert!(!matches("$.logs[1].topics", "<", "x", &data));
+    }
+
+    #[test]
+    fn test_json_path_missing_values_do_not_match() {
+        let data = json!({"logs": [{"topics": []}], "tag1": null, "score": 10});
+
+        assert!(!matches("$.logs[0].topics[1]", "=", "0x01", &data));
+        assert!(!matches("$.logs[3]", "exists", "", &data));
+        assert!(!matches("$.receipt.status", "!=", "1", &data));
+        // null counts as missing
+        assert!(!matches("$.tag1", "exists", "", &data));
+        assert!(!matches("$.tag1", "!=", "trade", &data));
+        // A number does not compare with text
+        assert!(!matches("$.score", "!=", "ten", &data));
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        for operator in COMPARISONS {
+            assert!(Comparison::parse(operator).is_some(), "{}", operator);
+        }
+        assert_eq!(Comparison::parse("=>"), None);
+        assert!(Comparison::parse("<>").unwrap().compare(1, 2));
+        assert!(Comparison::parse(">=").unwrap().compare(2.0, 2.0));
+    }
+
+    #[test]
+    fn test_condition_type_round_trip() {
+        for kind in ConditionType::ALL {
+            assert_eq!(ConditionType::parse(kind.as_str()), Some(kind));
+            for field in kind.fields() {
+                assert!(field_type(field).is_some(), "{}", field);
+            }
+        }
+    }
+}
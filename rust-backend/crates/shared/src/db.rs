@@ -1,14 +1,35 @@
 //! Database connection pooling utilities
 //!
 //! Supports optional read replica configuration for read scaling.
-//! When a read replica is configured via `DB_READ_HOST`, SELECT queries
-//! can be routed to the read pool while writes go to the primary pool.
-
-use crate::config::DatabaseConfig;
-use crate::error::Result;
-use sqlx::{postgres::PgPoolOptions, PgPool};
+//! When a read replica is configured via `DATABASE_REPLICA_URL` or
+//! `DB_READ_HOST`, SELECT queries can be routed to the read pool while writes
+//! and transactions go to the primary pool. Replica connections are opened in
+//! read-only mode, so a write routed there by mistake fails instead of
+//! silently landing on the wrong server.
+//!
+//! Every connection gets a server-side `statement_timeout`
+//! (`DATABASE_STATEMENT_TIMEOUT_MS`) so a stuck query cannot hold a pool slot
+//! indefinitely, and statements slower than `DATABASE_SLOW_QUERY_MS` are
+//! logged by sqlx with their SQL and duration. Hot paths can additionally be
+//! wrapped in [`timed_query`] to count slow queries in the
+//! `db_slow_queries_total` metric.
+
+use crate::config::{DatabaseConfig, MigrationMode};
+use crate::error::{Error, Result};
+use log::LevelFilter;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, Connection, Executor, PgPool};
+use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// SQLSTATE raised when a statement is cancelled by `statement_timeout`
+const QUERY_CANCELED: &str = "57014";
+
+/// Advisory lock key serializing migration runs across replicas ("agentaur")
+const MIGRATION_LOCK_KEY: i64 = 0x6167_656e_7461_7572;
 
 /// Type alias for the database pool (backward compatibility)
 pub type DbPool = PgPool;
@@ -26,12 +47,14 @@ pub struct DbPools {
     read: Arc<PgPool>,
     /// Whether a dedicated read replica is configured
     has_read_replica: bool,
+    /// Threshold above which [`DbPools::timed`] counts a query as slow
+    slow_query_threshold: Option<Duration>,
 }
 
 impl DbPools {
     /// Create database pools from configuration
     ///
-    /// If `DB_READ_HOST` is configured, creates a separate read pool.
+    /// If a read replica is configured, creates a separate read pool.
     /// Otherwise, the read pool uses the same connection as write.
     pub async fn from_config(config: &DatabaseConfig) -> Result<Self> {
         // Create primary (write) pool
@@ -60,9 +83,30 @@ impl DbPools {
             write,
             read,
             has_read_replica,
+            slow_query_threshold: config.slow_query_threshold(),
         })
     }
 
+    /// Use one existing pool for both reads and writes (e.g. in tests)
+    pub fn from_pool(pool: PgPool) -> Self {
+        let write = Arc::new(pool);
+        Self {
+            read: Arc::clone(&write),
+            write,
+            has_read_replica: false,
+            slow_query_threshold: None,
+        }
+    }
+
+    /// Await a query, logging and counting it if it exceeds the configured
+    /// slow-query threshold (see [`timed_query`])
+    pub async fn timed<T, E, F>(&self, name: &'static str, query: F) -> std::result::Result<T, E>
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+    {
+        timed_query(name, self.slow_query_threshold, query).await
+    }
+
     /// Get the write (primary) pool for INSERT/UPDATE/DELETE operations
     #[inline]
     pub fn write(&self) -> &PgPool {
@@ -89,6 +133,35 @@ impl DbPools {
         self.has_read_replica
     }
 
+    /// Close both pools
+    ///
+    /// Waits for checked-out connections to be returned, so call it only
+    /// after request handling and background tasks have stopped.
+    pub async fn close(&self) {
+        self.write.close().await;
+        if self.has_read_replica {
+            self.read.close().await;
+        }
+    }
+
+    /// Check that both pools can serve queries
+    ///
+    /// Run on startup so a misconfigured replica fails fast instead of on the
+    /// first list request. Without a replica only the primary is checked.
+    pub async fn check_health(&self) -> Result<()> {
+        check_health(self.write())
+            .await
+            .map_err(|e| Error::internal(format!("Primary database unhealthy: {}", e)))?;
+
+        if self.has_read_replica {
+            check_health(self.read())
+                .await
+                .map_err(|e| Error::internal(format!("Read replica unhealthy: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
     /// Get pool statistics for monitoring
     pub fn stats(&self) -> DbPoolStats {
         DbPoolStats {
@@ -144,15 +217,17 @@ async fn create_read_replica_pool_with_retry(
     let base_delay = Duration::from_millis(DEFAULT_BASE_DELAY_MS);
     let mut last_error = None;
 
+    let connect_options = connect_options(replica_url, config)?;
+
     for attempt in 1..=DEFAULT_MAX_RETRIES {
-        match PgPoolOptions::new()
-            .max_connections(replica_config.max_connections)
-            .min_connections(replica_config.min_connections)
-            .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
-            .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
-            .max_lifetime(Duration::from_secs(config.max_lifetime_secs))
-            .connect(replica_url)
-            .await
+        match pool_options(
+            config,
+            replica_config.max_connections,
+            replica_config.min_connections,
+            true,
+        )
+        .connect_with(connect_options.clone())
+        .await
         {
             Ok(pool) => {
                 if attempt > 1 {
@@ -256,27 +331,109 @@ pub async fn create_pool_with_retry(
 
 /// Create a new database connection pool (internal, no retry)
 async fn create_pool_internal(config: &DatabaseConfig) -> Result<DbPool> {
-    let pool = PgPoolOptions::new()
-        .max_connections(config.max_connections)
-        .min_connections(config.min_connections)
-        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
-        .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
-        .max_lifetime(Duration::from_secs(config.max_lifetime_secs))
-        .connect(&config.connection_url())
-        .await?;
+    let pool = pool_options(
+        config,
+        config.max_connections,
+        config.min_connections,
+        false,
+    )
+    .connect_with(connect_options(&config.connection_url(), config)?)
+    .await?;
 
     tracing::info!(
-        "Database pool created: max={}, min={}, acquire_timeout={}s, idle_timeout={}s, max_lifetime={}s",
+        "Database pool created: max={}, min={}, acquire_timeout={}s, idle_timeout={}s, max_lifetime={}s, statement_timeout={}ms, slow_query={}ms",
         config.max_connections,
         config.min_connections,
         config.acquire_timeout_secs,
         config.idle_timeout_secs,
-        config.max_lifetime_secs
+        config.max_lifetime_secs,
+        config.statement_timeout_ms,
+        config.slow_query_ms
     );
 
     Ok(pool)
 }
 
+/// Pool options shared by the primary and replica pools
+///
+/// Session settings are applied when a connection is opened, so they hold
+/// for every query run on it: the statement timeout, and read-only mode for
+/// replica connections.
+fn pool_options(
+    config: &DatabaseConfig,
+    max_connections: u32,
+    min_connections: u32,
+    read_only: bool,
+) -> PgPoolOptions {
+    let statement_timeout = config.statement_timeout();
+
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .min_connections(min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+        .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
+        .max_lifetime(Duration::from_secs(config.max_lifetime_secs))
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                if let Some(timeout) = statement_timeout {
+                    let sql = format!("SET statement_timeout = {}", timeout.as_millis());
+                    conn.execute(sql.as_str()).await?;
+                }
+                if read_only {
+                    conn.execute("SET default_transaction_read_only = on")
+                        .await?;
+                }
+                Ok(())
+            })
+        })
+}
+
+/// Parse a connection URL and apply the slow-statement log threshold
+fn connect_options(url: &str, config: &DatabaseConfig) -> Result<PgConnectOptions> {
+    let options: PgConnectOptions = url.parse()?;
+    Ok(match config.slow_query_threshold() {
+        Some(threshold) => options.log_slow_statements(LevelFilter::Warn, threshold),
+        None => options.log_slow_statements(LevelFilter::Off, Duration::MAX),
+    })
+}
+
+/// Await a query, logging and counting it if it runs longer than `threshold`
+///
+/// `name` identifies the query in logs and in the `query` label of
+/// `db_slow_queries_total`, so keep it a short static string. A `None`
+/// threshold disables the check.
+pub async fn timed_query<T, E, F>(
+    name: &'static str,
+    threshold: Option<Duration>,
+    query: F,
+) -> std::result::Result<T, E>
+where
+    F: Future<Output = std::result::Result<T, E>>,
+{
+    let start = Instant::now();
+    let result = query.await;
+    let elapsed = start.elapsed();
+
+    if threshold.is_some_and(|threshold| elapsed >= threshold) {
+        tracing::warn!(
+            query = name,
+            duration_ms = elapsed.as_millis() as u64,
+            "Slow database query"
+        );
+        metrics::counter!("db_slow_queries_total", "query" => name).increment(1);
+    }
+
+    result
+}
+
+/// Check whether an error is a statement cancelled by `statement_timeout`
+pub fn is_statement_timeout(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code == QUERY_CANCELED)
+}
+
 /// Create a new database connection pool
 ///
 /// This is a convenience wrapper that uses default retry settings.
@@ -297,24 +454,163 @@ pub async fn create_pool(config: &DatabaseConfig) -> Result<DbPool> {
     create_pool_with_retry(config, None, None).await
 }
 
-/// Run database migrations
-///
-/// # Arguments
-///
-/// * `pool` - Database connection pool
+/// Apply, verify or skip database migrations according to `config.migration_mode`
 ///
-/// # Returns
-///
-/// Ok if migrations succeed
+/// - `run` applies pending migrations from `config.migrations_dir` while
+///   holding a PostgreSQL advisory lock (see [`apply_migrations`]), so
+///   replicas that boot together migrate one at a time and the later ones
+///   wait, then find nothing pending.
+/// - `verify-only` fails unless every migration in the directory is
+///   applied, successfully and with a matching checksum.
+/// - `skip` returns without touching the database or the directory.
 ///
 /// # Errors
 ///
-/// Returns an error if migrations fail
-pub async fn run_migrations(_pool: &DbPool) -> Result<()> {
-    // Note: Migrations are run manually using database/run-migrations.sh
-    // This function is a placeholder for future automatic migration support
-    // For now, it just logs a message
-    tracing::info!("Database migrations should be run manually (see database/README.md)");
+/// Returns an error if the migrations cannot be loaded or applied, or if
+/// verification finds the schema behind.
+pub async fn run_migrations(pool: &DbPool, config: &DatabaseConfig) -> Result<()> {
+    match config.migration_mode {
+        MigrationMode::Skip => {
+            tracing::info!("Database migrations skipped (DB_MIGRATION_MODE=skip)");
+        }
+        MigrationMode::Run => {
+            let migrator = load_migrator(&config.migrations_dir).await?;
+            apply_migrations(pool, &migrator).await?;
+            tracing::info!(
+                migrations = migrator.iter().count(),
+                "Database migrations applied"
+            );
+        }
+        MigrationMode::VerifyOnly => {
+            let migrator = load_migrator(&config.migrations_dir).await?;
+            let applied = applied_migrations(pool).await?;
+            verify_migrations(&migrator, &applied)?;
+            tracing::info!(
+                migrations = applied.len(),
+                "Database migrations verified as current"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Apply pending migrations while holding the migration advisory lock
+///
+/// The lock lives on a connection detached from the pool that is closed
+/// afterwards. PostgreSQL drops session locks with the session, so the lock
+/// is released even if migrating fails or this future is cancelled, and a
+/// pooled connection never carries it back into the pool.
+async fn apply_migrations(pool: &DbPool, migrator: &Migrator) -> Result<()> {
+    let mut conn = pool.acquire().await?.detach();
+
+    // Waiting on another replica, or a long migration, must not hit the
+    // pool's statement_timeout; the setting dies with the connection
+    sqlx::query("SET statement_timeout = 0")
+        .execute(&mut conn)
+        .await?;
+
+    let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .fetch_one(&mut conn)
+        .await?;
+    if !acquired {
+        tracing::info!("Another instance is applying migrations, waiting for it to finish");
+        sqlx::query("SELECT pg_advisory_lock($1)")
+            .bind(MIGRATION_LOCK_KEY)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let result = migrator
+        .run(&mut conn)
+        .await
+        .map_err(|e| Error::internal(format!("Failed to apply migrations: {}", e)));
+
+    if let Err(e) = sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut conn)
+        .await
+    {
+        tracing::warn!(error = %e, "Failed to release migration lock, closing the connection instead");
+    }
+    // Closing ends the session, which releases the lock if the unlock failed
+    if let Err(e) = conn.close().await {
+        tracing::debug!(error = %e, "Failed to close migration connection cleanly");
+    }
+
+    result
+}
+
+async fn load_migrator(dir: &str) -> Result<Migrator> {
+    Migrator::new(Path::new(dir)).await.map_err(|e| {
+        Error::config(format!(
+            "Failed to load migrations from {} ({}); set DB_MIGRATIONS_DIR or DB_MIGRATION_MODE=skip",
+            dir, e
+        ))
+    })
+}
+
+/// Row of the `_sqlx_migrations` history table
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct MigrationRecord {
+    version: i64,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+/// Read the migration history without creating the history table
+async fn applied_migrations(pool: &DbPool) -> Result<Vec<MigrationRecord>> {
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    Ok(
+        sqlx::query_as("SELECT version, success, checksum FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await?,
+    )
+}
+
+/// Check that every migration is applied, succeeded and was not edited since
+fn verify_migrations(migrator: &Migrator, applied: &[MigrationRecord]) -> Result<()> {
+    let mut problems = Vec::new();
+
+    let failed: Vec<_> = applied.iter().filter(|m| !m.success).collect();
+    if !failed.is_empty() {
+        let versions: Vec<_> = failed.iter().map(|m| m.version.to_string()).collect();
+        problems.push(format!("failed: {}", versions.join(", ")));
+    }
+
+    let mut pending = Vec::new();
+    let mut modified = Vec::new();
+    for migration in migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+    {
+        match applied.iter().find(|m| m.version == migration.version) {
+            None => pending.push(migration.version.to_string()),
+            Some(record) if record.checksum != *migration.checksum => {
+                modified.push(migration.version.to_string())
+            }
+            Some(_) => {}
+        }
+    }
+    if !pending.is_empty() {
+        problems.push(format!("{} pending: {}", pending.len(), pending.join(", ")));
+    }
+    if !modified.is_empty() {
+        problems.push(format!("modified after apply: {}", modified.join(", ")));
+    }
+
+    if !problems.is_empty() {
+        return Err(Error::internal(format!(
+            "Database schema is not current ({})",
+            problems.join("; ")
+        )));
+    }
     Ok(())
 }
 
@@ -335,3 +631,249 @@ pub async fn check_health(pool: &DbPool) -> Result<()> {
     sqlx::query("SELECT 1").execute(pool).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(statement_timeout_ms: u64) -> DatabaseConfig {
+        DatabaseConfig {
+            host: "localhost".to_string(),
+            port: 5432,
+            name: "agentauri_backend".to_string(),
+            user: "postgres".to_string(),
+            password: String::new(),
+            max_connections: 2,
+            min_connections: 0,
+            acquire_timeout_secs: 5,
+            idle_timeout_secs: 60,
+            max_lifetime_secs: 300,
+            statement_timeout_ms,
+            slow_query_ms: 50,
+            ssl_mode: "prefer".to_string(),
+            read_replica: None,
+            migration_mode: MigrationMode::Run,
+            migrations_dir: "../database/migrations".to_string(),
+        }
+    }
+
+    async fn connect(config: &DatabaseConfig, read_only: bool) -> PgPool {
+        let database_url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+
+        pool_options(
+            config,
+            config.max_connections,
+            config.min_connections,
+            read_only,
+        )
+        .connect_with(connect_options(&database_url, config).unwrap())
+        .await
+        .expect("Failed to connect to test database")
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout_cancels_slow_query() {
+        let pool = connect(&test_config(100), false).await;
+
+        let err = sqlx::query("SELECT pg_sleep(2)")
+            .execute(&pool)
+            .await
+            .expect_err("pg_sleep should be cancelled by statement_timeout");
+
+        assert!(is_statement_timeout(&err), "unexpected error: {err}");
+
+        // The connection is still usable afterwards
+        check_health(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout_disabled() {
+        let pool = connect(&test_config(0), false).await;
+
+        let timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(timeout, "0");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_connections_reject_writes() {
+        let pool = connect(&test_config(1_000), true).await;
+
+        let err = sqlx::query("CREATE TABLE read_only_probe (id INT)")
+            .execute(&pool)
+            .await
+            .expect_err("writes must fail on a read-only connection");
+        assert_eq!(
+            err.as_database_error().and_then(|e| e.code()).as_deref(),
+            Some("25006") // read_only_sql_transaction
+        );
+    }
+
+    /// Migrator over a scratch directory holding `versions` as empty migrations
+    async fn scratch_migrator(versions: &[i64]) -> Migrator {
+        let dir = std::env::temp_dir().join(format!("migrations-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for version in versions {
+            std::fs::write(
+                dir.join(format!("{}_step.sql", version)),
+                format!("SELECT {};", version),
+            )
+            .unwrap();
+        }
+        let migrator = Migrator::new(dir.as_path()).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        migrator
+    }
+
+    fn applied(migrator: &Migrator, versions: &[i64]) -> Vec<MigrationRecord> {
+        migrator
+            .iter()
+            .filter(|m| versions.contains(&m.version))
+            .map(|m| MigrationRecord {
+                version: m.version,
+                success: true,
+                checksum: m.checksum.to_vec(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_verify_fails_when_behind() {
+        let migrator = scratch_migrator(&[1, 2, 3]).await;
+
+        let err = verify_migrations(&migrator, &applied(&migrator, &[1])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Internal error: Database schema is not current (2 pending: 2, 3)"
+        );
+
+        // Nothing applied yet (no history table) is behind too
+        assert!(verify_migrations(&migrator, &[]).is_err());
+
+        assert!(verify_migrations(&migrator, &applied(&migrator, &[1, 2, 3])).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_fails_on_failed_or_modified_migration() {
+        let migrator = scratch_migrator(&[1, 2]).await;
+
+        let mut records = applied(&migrator, &[1, 2]);
+        records[0].success = false;
+        records[1].checksum = vec![0; 48];
+        let err = verify_migrations(&migrator, &records)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("failed: 1"), "{}", err);
+        assert!(err.contains("modified after apply: 2"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_repository_migrations_load() {
+        let migrator = load_migrator("../../../database/migrations").await.unwrap();
+        assert!(migrator.iter().count() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_skip_does_not_touch_database() {
+        // Any query on this pool fails, and the migrations directory does not exist
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap();
+        let mut config = test_config(0);
+        config.migrations_dir = "/nonexistent/migrations".to_string();
+
+        config.migration_mode = MigrationMode::Skip;
+        run_migrations(&pool, &config).await.unwrap();
+
+        config.migration_mode = MigrationMode::VerifyOnly;
+        let err = run_migrations(&pool, &config).await.unwrap_err();
+        assert!(err.to_string().contains("DB_MIGRATIONS_DIR"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_migrations_run_once() {
+        // Isolate both the probe table and the migration history in a scratch schema
+        let schema = format!("migration_lock_{}", uuid::Uuid::new_v4().simple());
+        let admin = connect(&test_config(0), false).await;
+        sqlx::query(&format!("CREATE SCHEMA {}", schema))
+            .execute(&admin)
+            .await
+            .unwrap();
+
+        let database_url = std::env::var("DATABASE_URL").unwrap();
+        let replica = || async {
+            let config = test_config(0);
+            let options = connect_options(&database_url, &config)
+                .unwrap()
+                .options([("search_path", schema.as_str())]);
+            pool_options(&config, 2, 0, false)
+                .connect_with(options)
+                .await
+                .unwrap()
+        };
+        let (first, second) = (replica().await, replica().await);
+
+        // Slow enough that the second replica arrives while the first holds the lock
+        let dir = std::env::temp_dir().join(format!("migrations-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("1_probe.sql"),
+            "CREATE TABLE migration_runs (n INT); INSERT INTO migration_runs VALUES (1); SELECT pg_sleep(0.5);",
+        )
+        .unwrap();
+        let migrator = Migrator::new(dir.as_path()).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (a, b) = tokio::join!(
+            apply_migrations(&first, &migrator),
+            apply_migrations(&second, &migrator)
+        );
+        a.unwrap();
+        b.unwrap();
+
+        let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM migration_runs")
+            .fetch_one(&first)
+            .await
+            .unwrap();
+        assert_eq!(runs, 1);
+        for pool in [&first, &second] {
+            let applied = applied_migrations(pool).await.unwrap();
+            verify_migrations(&migrator, &applied).unwrap();
+        }
+
+        // Both locks were released
+        let held: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pg_locks WHERE locktype = 'advisory' \
+             AND (classid::bigint << 32) | objid::bigint = $1",
+        )
+        .bind(MIGRATION_LOCK_KEY)
+        .fetch_one(&admin)
+        .await
+        .unwrap();
+        assert_eq!(held, 0);
+
+        sqlx::query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .execute(&admin)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_timed_query_passes_result_through() {
+        let fast: std::result::Result<u32, ()> =
+            timed_query("fast", Some(Duration::from_secs(1)), async { Ok(7) }).await;
+        assert_eq!(fast, Ok(7));
+
+        let slow: std::result::Result<(), &str> =
+            timed_query("slow", Some(Duration::from_millis(1)), async {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                Err("boom")
+            })
+            .await;
+        assert_eq!(slow, Err("boom"));
+    }
+}
@@ -0,0 +1,425 @@
+//! Dependency self-test for `--check` mode
+//!
+//! Every binary accepts `--check` (or `diagnose`) to load its configuration,
+//! try each dependency once, print a pass/fail report and exit instead of
+//! starting. The process exits non-zero when any check fails, so the mode
+//! works as a CI smoke test and for readiness debugging.
+//!
+//! Checks shared by all services live here ([`check_core`]); a binary adds
+//! its own (e.g. Stripe for the gateway) through [`DiagnosticsReport::run`].
+
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+
+use crate::config::Config;
+use crate::db;
+
+/// Longest a single check may take before it counts as failed
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+const STRIPE_API_URL: &str = "https://api.stripe.com";
+
+/// Whether the command line asks for diagnostics instead of a normal start
+pub fn is_check_mode() -> bool {
+    has_check_arg(std::env::args().skip(1))
+}
+
+fn has_check_arg<I: IntoIterator<Item = String>>(args: I) -> bool {
+    args.into_iter()
+        .any(|arg| arg == "--check" || arg == "diagnose")
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not applicable, e.g. an optional integration that is not configured
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        })
+    }
+}
+
+/// Result of one check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    /// What was found on success, the error on failure, or why it was skipped
+    pub detail: String,
+    pub duration: Duration,
+}
+
+/// Pass/fail report for one service
+#[derive(Debug)]
+pub struct DiagnosticsReport {
+    service: String,
+    checks: Vec<CheckResult>,
+}
+
+impl DiagnosticsReport {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// Run a check, recording its detail on success and its error on failure
+    ///
+    /// Checks that exceed [`CHECK_TIMEOUT`] fail.
+    pub async fn run<F>(&mut self, name: &str, check: F) -> CheckStatus
+    where
+        F: Future<Output = anyhow::Result<String>>,
+    {
+        self.run_with_timeout(name, CHECK_TIMEOUT, check).await
+    }
+
+    async fn run_with_timeout<F>(&mut self, name: &str, limit: Duration, check: F) -> CheckStatus
+    where
+        F: Future<Output = anyhow::Result<String>>,
+    {
+        let start = Instant::now();
+        let (status, detail) = match tokio::time::timeout(limit, check).await {
+            Ok(Ok(detail)) => (CheckStatus::Pass, detail),
+            Ok(Err(e)) => (CheckStatus::Fail, error_detail(&e)),
+            Err(_) => (CheckStatus::Fail, format!("timed out after {:?}", limit)),
+        };
+        self.checks.push(CheckResult {
+            name: name.to_string(),
+            status,
+            detail,
+            duration: start.elapsed(),
+        });
+        status
+    }
+
+    /// Record a check that does not apply
+    pub fn skip(&mut self, name: &str, reason: &str) {
+        self.checks.push(CheckResult {
+            name: name.to_string(),
+            status: CheckStatus::Skip,
+            detail: reason.to_string(),
+            duration: Duration::ZERO,
+        });
+    }
+
+    pub fn checks(&self) -> &[CheckResult] {
+        &self.checks
+    }
+
+    /// True when no check failed (skipped checks do not count)
+    pub fn passed(&self) -> bool {
+        !self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Print the report and turn a failed check into an error
+    pub fn finish(self) -> anyhow::Result<()> {
+        println!("{}", self);
+        if !self.passed() {
+            bail!(
+                "{} of {} dependency checks failed",
+                self.count(CheckStatus::Fail),
+                self.checks.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} diagnostics", self.service)?;
+        for check in &self.checks {
+            write!(f, "  [{}] {}", check.status, check.name)?;
+            if check.status != CheckStatus::Skip {
+                write!(f, " ({}ms)", check.duration.as_millis())?;
+            }
+            writeln!(f, ": {}", check.detail)?;
+        }
+        write!(
+            f,
+            "{}: {} passed, {} failed, {} skipped",
+            if self.passed() { "OK" } else { "FAILED" },
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip)
+        )
+    }
+}
+
+/// Join an error chain, dropping causes already quoted by the error above
+fn error_detail(error: &anyhow::Error) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for cause in error.chain() {
+        let message = cause.to_string();
+        if parts.last().is_some_and(|prev| prev.ends_with(&message)) {
+            continue;
+        }
+        parts.push(message);
+    }
+    parts.join(": ")
+}
+
+/// Check configuration, PostgreSQL (connection, query, migrations) and Redis
+///
+/// Returns the configuration when it loaded, so callers can run further
+/// checks against it.
+pub async fn check_core(report: &mut DiagnosticsReport) -> Option<Config> {
+    let mut config = None;
+    report
+        .run("config", async {
+            let loaded = Config::load().context("Failed to load configuration")?;
+            let summary = loaded.validate_and_summarize()?;
+            config = Some(loaded);
+            Ok(summary)
+        })
+        .await;
+
+    let Some(config) = config else {
+        for name in ["database", "migrations", "redis"] {
+            report.skip(name, "configuration did not load");
+        }
+        return None;
+    };
+
+    let mut pool = None;
+    report
+        .run("database", async {
+            let connected = db::create_pool_with_retry(&config.database, Some(1), None)
+                .await
+                .context("Failed to connect")?;
+            db::check_health(&connected)
+                .await
+                .context("SELECT 1 failed")?;
+            pool = Some(connected);
+            Ok(format!(
+                "connected to {}:{}/{}",
+                config.database.host, config.database.port, config.database.name
+            ))
+        })
+        .await;
+
+    match pool {
+        Some(pool) => {
+            report.run("migrations", migration_status(&pool)).await;
+        }
+        None => report.skip("migrations", "database unavailable"),
+    }
+
+    report.run("redis", ping_redis(&config)).await;
+
+    Some(config)
+}
+
+/// Summarize the sqlx migration history, failing on a failed migration
+async fn migration_status(pool: &db::DbPool) -> anyhow::Result<String> {
+    let (applied, failed, latest): (i64, i64, Option<i64>) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COUNT(*) FILTER (WHERE NOT success), MAX(version)
+        FROM _sqlx_migrations
+        "#,
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to read _sqlx_migrations (have migrations been run?)")?;
+
+    if failed > 0 {
+        bail!("{} of {} migrations failed", failed, applied);
+    }
+    Ok(match latest {
+        Some(latest) => format!("{} applied, latest {}", applied, latest),
+        None => "no migrations applied".to_string(),
+    })
+}
+
+async fn ping_redis(config: &Config) -> anyhow::Result<String> {
+    let client = redis::Client::open(config.redis.connection_url())
+        .context("Invalid Redis connection URL")?;
+    let mut conn = client
+        .get_multiplexed_async_connection()
+        .await
+        .context("Failed to connect")?;
+    let reply: String = redis::cmd("PING")
+        .query_async(&mut conn)
+        .await
+        .context("PING failed")?;
+    Ok(format!(
+        "{} from {}:{}",
+        reply, config.redis.host, config.redis.port
+    ))
+}
+
+/// Check a Telegram bot token with `getMe`
+///
+/// Errors drop the request URL, which contains the token.
+pub async fn ping_telegram(bot_token: &str) -> anyhow::Result<String> {
+    let response: serde_json::Value = http_client()?
+        .get(format!("{}/bot{}/getMe", TELEGRAM_API_URL, bot_token))
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .context("Failed to reach Telegram")?
+        .json()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .context("Invalid Telegram response")?;
+
+    if response["ok"] != true {
+        bail!(
+            "Telegram rejected the bot token: {}",
+            response["description"].as_str().unwrap_or("unknown error")
+        );
+    }
+    Ok(format!(
+        "bot @{}",
+        response["result"]["username"].as_str().unwrap_or("unknown")
+    ))
+}
+
+/// Check a Stripe secret key by reading the account balance
+pub async fn ping_stripe(secret_key: &str) -> anyhow::Result<String> {
+    let response = http_client()?
+        .get(format!("{}/v1/balance", STRIPE_API_URL))
+        .bearer_auth(secret_key)
+        .send()
+        .await
+        .context("Failed to reach Stripe")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        bail!("Stripe returned {}", status);
+    }
+    let mode = if secret_key.starts_with("sk_live_") {
+        "live"
+    } else {
+        "test"
+    };
+    Ok(format!("authenticated ({} mode)", mode))
+}
+
+fn http_client() -> anyhow::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_mode_args() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(has_check_arg(args(&["--check"])));
+        assert!(has_check_arg(args(&["diagnose"])));
+        assert!(!has_check_arg(args(&[])));
+        assert!(!has_check_arg(args(&["--checks", "check"])));
+    }
+
+    #[tokio::test]
+    async fn test_report_with_mixed_results() {
+        let mut report = DiagnosticsReport::new("test-service");
+        let pass = report
+            .run("database", async { Ok("connected".to_string()) })
+            .await;
+        let fail = report
+            .run("redis", async {
+                Err(anyhow::anyhow!("connection refused")).context("Failed to connect")
+            })
+            .await;
+        report.skip("telegram", "TELEGRAM_BOT_TOKEN not set");
+
+        assert_eq!(pass, CheckStatus::Pass);
+        assert_eq!(fail, CheckStatus::Fail);
+        assert!(!report.passed());
+
+        let checks = report.checks();
+        let names: Vec<_> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["database", "redis", "telegram"]);
+        assert_eq!(checks[1].detail, "Failed to connect: connection refused");
+        assert_eq!(checks[2].status, CheckStatus::Skip);
+
+        let text = report.to_string();
+        assert!(text.starts_with("test-service diagnostics\n"), "{}", text);
+        assert!(text.contains("[PASS] database ("), "{}", text);
+        assert!(
+            text.contains("[FAIL] redis") && text.contains("connection refused"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("[SKIP] telegram: TELEGRAM_BOT_TOKEN not set"),
+            "{}",
+            text
+        );
+        assert!(
+            text.ends_with("FAILED: 1 passed, 1 failed, 1 skipped"),
+            "{}",
+            text
+        );
+
+        let err = report.finish().unwrap_err();
+        assert_eq!(err.to_string(), "1 of 3 dependency checks failed");
+    }
+
+    #[test]
+    fn test_error_detail_drops_repeated_causes() {
+        let wrapped = anyhow::Error::new(crate::Error::from(sqlx::Error::PoolTimedOut))
+            .context("Failed to connect");
+        assert_eq!(
+            error_detail(&wrapped),
+            "Failed to connect: Database error: pool timed out while waiting for an open connection"
+        );
+
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let chained = anyhow::Error::new(io).context("PING failed");
+        assert_eq!(error_detail(&chained), "PING failed: refused");
+    }
+
+    #[tokio::test]
+    async fn test_report_passes_with_skipped_checks() {
+        let mut report = DiagnosticsReport::new("test-service");
+        report.run("config", async { Ok("ok".to_string()) }).await;
+        report.skip("stripe", "not configured");
+
+        assert!(report.passed());
+        assert!(report
+            .to_string()
+            .ends_with("OK: 1 passed, 0 failed, 1 skipped"));
+        assert!(report.finish().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_slow_check_times_out() {
+        let mut report = DiagnosticsReport::new("test-service");
+        let status = report
+            .run_with_timeout(
+                "redis",
+                Duration::from_millis(10),
+                std::future::pending::<anyhow::Result<String>>(),
+            )
+            .await;
+
+        assert_eq!(status, CheckStatus::Fail);
+        assert_eq!(report.checks()[0].detail, "timed out after 10ms");
+        assert!(!report.passed());
+    }
+}
@@ -0,0 +1,326 @@
+//! Dead letter queue (DLQ) for failed action jobs
+//!
+//! Jobs that fail after all retries are pushed by the action workers onto the
+//! `ACTION_JOBS_DLQ` Redis list as [`DlqEntry`] JSON, newest first. The workers
+//! also trim it (see `action-workers`' `DlqTrimmer`).
+//!
+//! [`DlqAccessor`] lets other services inspect the entries and replay one onto
+//! its action job queue without touching Redis directly. Replay removes the
+//! entry and enqueues the job in one atomic step, so an entry is never replayed
+//! twice or lost in between.
+
+use chrono::{DateTime, Utc};
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::jobs::{ActionJob, ACTION_JOBS_DLQ};
+use crate::redis::queue_key;
+
+/// Entries read per `LRANGE` while scanning the DLQ
+const SCAN_BATCH_SIZE: isize = 500;
+
+/// Move one entry from the DLQ onto a job queue
+///
+/// KEYS[1] = DLQ list, KEYS[2] = job queue, ARGV[1] = DLQ payload,
+/// ARGV[2] = job to enqueue
+///
+/// Returns 0 without enqueueing if the entry is gone (replayed or trimmed
+/// concurrently).
+const REPLAY_SCRIPT: &str = r#"
+if redis.call('LREM', KEYS[1], 1, ARGV[1]) == 0 then
+    return 0
+end
+redis.call('LPUSH', KEYS[2], ARGV[2])
+return 1
+"#;
+
+/// Entry in the Dead Letter Queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DlqEntry {
+    /// Original job that failed
+    pub job: ActionJob,
+    /// Error message from the last failure
+    pub error: String,
+    /// Number of attempts made
+    pub attempts: u32,
+    /// When the job was moved to DLQ
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DlqEntry {
+    /// Create a new DLQ entry
+    pub fn new(job: ActionJob, error: String, attempts: u32) -> Self {
+        Self {
+            job,
+            error,
+            attempts,
+            failed_at: Utc::now(),
+        }
+    }
+
+    /// Identifier of the entry (the failed job's ID)
+    pub fn id(&self) -> &str {
+        &self.job.id
+    }
+}
+
+/// One page of DLQ entries matching a filter
+#[derive(Debug, Default)]
+pub struct DlqPage {
+    /// Matching entries in the page, newest first
+    pub entries: Vec<DlqEntry>,
+    /// Matching entries in the whole DLQ
+    pub total: usize,
+}
+
+/// Select a page of matching entries from raw DLQ payloads (newest first)
+///
+/// Payloads that no longer parse are skipped.
+fn select_page<'a>(
+    payloads: impl IntoIterator<Item = &'a String>,
+    filter: impl Fn(&DlqEntry) -> bool,
+    offset: usize,
+    limit: usize,
+) -> DlqPage {
+    let mut page = DlqPage::default();
+    for entry in payloads
+        .into_iter()
+        .filter_map(|json| serde_json::from_str::<DlqEntry>(json).ok())
+        .filter(|entry| filter(entry))
+    {
+        if page.total >= offset && page.entries.len() < limit {
+            page.entries.push(entry);
+        }
+        page.total += 1;
+    }
+    page
+}
+
+/// Find the raw payload and entry for a job ID
+fn find_payload(payloads: Vec<String>, job_id: &str) -> Option<(String, DlqEntry)> {
+    payloads.into_iter().find_map(|json| {
+        let entry: DlqEntry = serde_json::from_str(&json).ok()?;
+        (entry.id() == job_id).then_some((json, entry))
+    })
+}
+
+/// Read and replay access to the DLQ
+#[derive(Clone)]
+pub struct DlqAccessor {
+    conn: ConnectionManager,
+    queue_name: String,
+}
+
+impl DlqAccessor {
+    /// Access the action job DLQ
+    pub fn new(conn: ConnectionManager) -> Self {
+        Self::with_queue_name(conn, queue_key(ACTION_JOBS_DLQ))
+    }
+
+    /// Access a DLQ under a custom (already prefixed) list name
+    pub fn with_queue_name(conn: ConnectionManager, queue_name: impl Into<String>) -> Self {
+        Self {
+            conn,
+            queue_name: queue_name.into(),
+        }
+    }
+
+    /// Number of entries in the DLQ
+    pub async fn len(&self) -> Result<u64> {
+        let mut conn = self.conn.clone();
+        conn.llen(&self.queue_name)
+            .await
+            .map_err(|e| Error::internal(format!("Failed to read DLQ length: {}", e)))
+    }
+
+    /// Every raw payload in the DLQ, newest first
+    async fn payloads(&self) -> Result<Vec<String>> {
+        let mut conn = self.conn.clone();
+        let mut payloads = Vec::new();
+        let mut start = 0;
+        loop {
+            let batch: Vec<String> = conn
+                .lrange(&self.queue_name, start, start + SCAN_BATCH_SIZE - 1)
+                .await
+                .map_err(|e| Error::internal(format!("Failed to read DLQ: {}", e)))?;
+            let done = (batch.len() as isize) < SCAN_BATCH_SIZE;
+            payloads.extend(batch);
+            if done {
+                return Ok(payloads);
+            }
+            start += SCAN_BATCH_SIZE;
+        }
+    }
+
+    /// List entries matching `filter`, newest first
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Entries to include (e.g. one organization's)
+    /// * `offset` - Matching entries to skip
+    /// * `limit` - Maximum entries to return
+    pub async fn list(
+        &self,
+        filter: impl Fn(&DlqEntry) -> bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<DlqPage> {
+        let payloads = self.payloads().await?;
+        Ok(select_page(&payloads, filter, offset, limit))
+    }
+
+    /// Get the entry for a job ID
+    pub async fn get(&self, job_id: &str) -> Result<Option<DlqEntry>> {
+        let payloads = self.payloads().await?;
+        Ok(find_payload(payloads, job_id).map(|(_, entry)| entry))
+    }
+
+    /// Remove an entry and push its job back onto its action job queue
+    ///
+    /// Only replays the entry if `filter` accepts it. `queue_for` gives the
+    /// full Redis key the job is pushed to (the priority lane of the queue
+    /// for its action type). The job keeps its ID and is marked with
+    /// [`ActionJob::replay_count`]. Returns the
+    /// replayed entry, or `None` if there is no such (accepted) entry.
+    pub async fn replay(
+        &self,
+        job_id: &str,
+        filter: impl Fn(&DlqEntry) -> bool,
+        queue_for: impl Fn(&ActionJob) -> String,
+    ) -> Result<Option<DlqEntry>> {
+        let payloads = self.payloads().await?;
+        let Some((payload, entry)) = find_payload(payloads, job_id) else {
+            return Ok(None);
+        };
+        if !filter(&entry) {
+            return Ok(None);
+        }
+
+        let mut job = entry.job.clone();
+        job.replay_count += 1;
+        let job_json = serde_json::to_string(&job)
+            .map_err(|e| Error::internal(format!("Failed to serialize job: {}", e)))?;
+
+        let mut conn = self.conn.clone();
+        let replayed: i64 = Script::new(REPLAY_SCRIPT)
+            .key(&self.queue_name)
+            .key(queue_for(&job))
+            .arg(&payload)
+            .arg(&job_json)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::internal(format!("Failed to replay DLQ entry: {}", e)))?;
+
+        Ok((replayed == 1).then_some(entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jobs::ActionType;
+
+    fn entry(org: Option<&str>, error: &str) -> DlqEntry {
+        let mut job = ActionJob::new(
+            "trigger-1",
+            "event-1",
+            ActionType::Rest,
+            1,
+            serde_json::json!({"url": "https://example.com/hook"}),
+            serde_json::json!({}),
+        );
+        job.organization_id = org.map(String::from);
+        DlqEntry::new(job, error.to_string(), 3)
+    }
+
+    fn payloads(entries: &[DlqEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_select_page_filters_and_paginates() {
+        let mut raw = payloads(&[
+            entry(Some("org_a"), "a-3"),
+            entry(Some("org_b"), "b-1"),
+            entry(Some("org_a"), "a-2"),
+            entry(Some("org_a"), "a-1"),
+        ]);
+        raw.insert(1, "not json".to_string());
+
+        let is_org_a = |e: &DlqEntry| e.job.organization_id.as_deref() == Some("org_a");
+        let page = select_page(&raw, is_org_a, 1, 1);
+
+        assert_eq!(page.total, 3);
+        let errors: Vec<_> = page.entries.iter().map(|e| e.error.as_str()).collect();
+        assert_eq!(errors, vec!["a-2"]);
+
+        let all = select_page(&raw, |_| true, 0, 10);
+        assert_eq!(all.total, 4);
+        assert_eq!(all.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_find_payload_by_job_id() {
+        let entries = [entry(Some("org_a"), "first"), entry(None, "second")];
+        let raw = payloads(&entries);
+
+        let (payload, found) = find_payload(raw.clone(), entries[1].id()).unwrap();
+        assert_eq!(found.error, "second");
+        assert_eq!(payload, raw[1]);
+        assert!(find_payload(raw, "missing").is_none());
+    }
+
+    async fn redis_conn() -> ConnectionManager {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        crate::redis::create_client(&url)
+            .await
+            .expect("Redis must be running for this test")
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_replay_moves_entry_to_queue() {
+        let mut conn = redis_conn().await;
+        let dlq_name = format!("test_dlq_{}", uuid::Uuid::new_v4());
+        let queue_name = format!("test_jobs_{}", uuid::Uuid::new_v4());
+        let dlq = DlqAccessor::with_queue_name(conn.clone(), &dlq_name);
+
+        let entries = [entry(Some("org_a"), "kept"), entry(Some("org_a"), "replay")];
+        for payload in payloads(&entries) {
+            let _: () = conn.lpush(&dlq_name, payload).await.unwrap();
+        }
+        let target = entries[1].id();
+        let queue_for = |_: &ActionJob| queue_name.clone();
+
+        // Rejected by the filter: nothing moves
+        let denied = dlq.replay(target, |_| false, queue_for).await.unwrap();
+        assert!(denied.is_none());
+        assert_eq!(dlq.len().await.unwrap(), 2);
+
+        let replayed = dlq.replay(target, |_| true, queue_for).await.unwrap();
+        assert_eq!(replayed.unwrap().error, "replay");
+        assert_eq!(dlq.len().await.unwrap(), 1);
+        assert!(dlq.get(target).await.unwrap().is_none());
+
+        let queued: Vec<String> = conn.lrange(&queue_name, 0, -1).await.unwrap();
+        assert_eq!(queued.len(), 1);
+        let job: ActionJob = serde_json::from_str(&queued[0]).unwrap();
+        assert_eq!(job.id, target);
+        assert_eq!(job.replay_count, 1);
+
+        // Already replayed
+        assert!(dlq
+            .replay(target, |_| true, queue_for)
+            .await
+            .unwrap()
+            .is_none());
+
+        let _: () = conn.del(&[&dlq_name, &queue_name]).await.unwrap();
+    }
+}
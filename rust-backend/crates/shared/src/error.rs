@@ -1,4 +1,27 @@
 //! Error types for the application
+//!
+//! Each [`Error`] has a stable machine-readable [`code`](Error::code) that
+//! API responses carry next to the human-readable message, so clients can
+//! tell error kinds apart without parsing messages:
+//!
+//! | Variant | Code | HTTP status |
+//! |---------|------|-------------|
+//! | `Database` (pool or statement timeout) | `ERR_DB_TIMEOUT` | 503 |
+//! | `Database` (no row) | `ERR_NOT_FOUND` | 404 |
+//! | `Database` (other) | `ERR_DB` | 500 |
+//! | `Redis` | `ERR_REDIS` | 503 |
+//! | `Config` | `ERR_CONFIG` | 500 |
+//! | `Validation` | `ERR_VALIDATION` | 400 |
+//! | `NotFound` | `ERR_NOT_FOUND` | 404 |
+//! | `Authentication` | `ERR_UNAUTHENTICATED` | 401 |
+//! | `Authorization` | `ERR_FORBIDDEN` | 403 |
+//! | `Internal` | `ERR_INTERNAL` | 500 |
+//!
+//! Codes are part of the API: never change or reuse one, only add new ones.
+//!
+//! [`Error::is_retryable`] tells workers and background tasks whether trying
+//! again can succeed: lost connections, timeouts, deadlocks and busy servers
+//! are transient; bad input, missing rows and failed auth are not.
 
 use thiserror::Error;
 
@@ -12,6 +35,10 @@ pub enum Error {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
+    /// Redis errors
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+
     /// Configuration errors
     #[error("Configuration error: {0}")]
     Config(String),
@@ -37,7 +64,74 @@ pub enum Error {
     Internal(String),
 }
 
+/// PostgreSQL SQLSTATE for a statement cancelled by `statement_timeout`
+const QUERY_CANCELED: &str = "57014";
+
 impl Error {
+    /// Stable machine-readable code (see the [module docs](self))
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Database(e) if is_timeout(e) => "ERR_DB_TIMEOUT",
+            Self::Database(sqlx::Error::RowNotFound) => "ERR_NOT_FOUND",
+            Self::Database(_) => "ERR_DB",
+            Self::Redis(_) => "ERR_REDIS",
+            Self::Config(_) => "ERR_CONFIG",
+            Self::Validation(_) => "ERR_VALIDATION",
+            Self::NotFound { .. } => "ERR_NOT_FOUND",
+            Self::Authentication(_) => "ERR_UNAUTHENTICATED",
+            Self::Authorization(_) => "ERR_FORBIDDEN",
+            Self::Internal(_) => "ERR_INTERNAL",
+        }
+    }
+
+    /// HTTP status code for responses reporting this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Database(e) if is_timeout(e) => 503,
+            Self::Redis(_) => 503,
+            Self::Database(sqlx::Error::RowNotFound) | Self::NotFound { .. } => 404,
+            Self::Validation(_) => 400,
+            Self::Authentication(_) => 401,
+            Self::Authorization(_) => 403,
+            Self::Database(_) | Self::Config(_) | Self::Internal(_) => 500,
+        }
+    }
+
+    /// Message safe to show API clients
+    ///
+    /// Database, configuration and internal errors may name tables, hosts or
+    /// secrets, so only their kind is reported.
+    pub fn public_message(&self) -> String {
+        match self {
+            Self::Database(e) if is_timeout(e) => "Database timed out".to_string(),
+            Self::Database(sqlx::Error::RowNotFound) => "Resource not found".to_string(),
+            Self::Redis(_) => "Service temporarily unavailable".to_string(),
+            Self::Database(_) | Self::Config(_) | Self::Internal(_) => {
+                "Internal server error".to_string()
+            }
+            other => other.to_string(),
+        }
+    }
+
+    /// Whether the failed operation may succeed if tried again
+    ///
+    /// Database and Redis errors are retryable when transient (see
+    /// [`is_transient_db_error`] and [`is_transient_redis_error`]), and
+    /// internal errors are assumed to be. Configuration, validation, not-found
+    /// and auth errors are permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Database(e) => is_transient_db_error(e),
+            Self::Redis(e) => is_transient_redis_error(e),
+            Self::Internal(_) => true,
+            Self::Config(_)
+            | Self::Validation(_)
+            | Self::NotFound { .. }
+            | Self::Authentication(_)
+            | Self::Authorization(_) => false,
+        }
+    }
+
     /// Create a NotFound error
     pub fn not_found(entity: impl Into<String>, id: impl Into<String>) -> Self {
         Self::NotFound {
@@ -71,3 +165,156 @@ impl Error {
         Self::Internal(msg.into())
     }
 }
+
+fn is_timeout(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db) => db.code().as_deref() == Some(QUERY_CANCELED),
+        _ => false,
+    }
+}
+
+/// Whether a database error is transient
+///
+/// Transient: pool timeouts, I/O errors (connection reset or refused) and the
+/// PostgreSQL classes for lost connections (`08`), insufficient resources
+/// (`53`), serialization failures and deadlocks (`40001`, `40P01`), lock
+/// timeouts (`55P03`), cancelled statements (`57014`) and server shutdown
+/// (`57P01`-`57P03`).
+pub fn is_transient_db_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::PoolTimedOut | sqlx::Error::Io(_) => true,
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| {
+            code.starts_with("08")
+                || code.starts_with("53")
+                || matches!(
+                    code.as_ref(),
+                    "40001" | "40P01" | "55P03" | QUERY_CANCELED | "57P01" | "57P02" | "57P03"
+                )
+        }),
+        _ => false,
+    }
+}
+
+/// Whether a Redis error is transient
+///
+/// Transient: I/O errors (connection reset, refused or timed out) and servers
+/// that are loading, failing over or asking to try again.
+pub fn is_transient_redis_error(error: &redis::RedisError) -> bool {
+    error.is_io_error()
+        || matches!(
+            error.kind(),
+            redis::ErrorKind::TryAgain
+                | redis::ErrorKind::BusyLoadingError
+                | redis::ErrorKind::ClusterDown
+                | redis::ErrorKind::MasterDown
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_variant_has_its_documented_code() {
+        let cases = [
+            (
+                Error::from(sqlx::Error::PoolTimedOut),
+                "ERR_DB_TIMEOUT",
+                503,
+            ),
+            (Error::from(sqlx::Error::RowNotFound), "ERR_NOT_FOUND", 404),
+            (Error::from(sqlx::Error::PoolClosed), "ERR_DB", 500),
+            (
+                Error::from(redis::RedisError::from((
+                    redis::ErrorKind::IoError,
+                    "Connection refused",
+                ))),
+                "ERR_REDIS",
+                503,
+            ),
+            (Error::config("DATABASE_URL missing"), "ERR_CONFIG", 500),
+            (Error::validation("limit too large"), "ERR_VALIDATION", 400),
+            (Error::not_found("Trigger", "t1"), "ERR_NOT_FOUND", 404),
+            (
+                Error::authentication("bad token"),
+                "ERR_UNAUTHENTICATED",
+                401,
+            ),
+            (Error::authorization("not a member"), "ERR_FORBIDDEN", 403),
+            (Error::internal("redis down"), "ERR_INTERNAL", 500),
+        ];
+
+        for (error, code, status) in cases {
+            assert_eq!(error.code(), code, "{}", error);
+            assert_eq!(error.status_code(), status, "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_public_message_hides_internal_details() {
+        assert_eq!(
+            Error::internal("redis://secret@host unreachable").public_message(),
+            "Internal server error"
+        );
+        assert_eq!(
+            Error::config("JWT_SECRET too short").public_message(),
+            "Internal server error"
+        );
+        assert_eq!(
+            Error::from(sqlx::Error::PoolTimedOut).public_message(),
+            "Database timed out"
+        );
+
+        assert_eq!(
+            Error::not_found("Trigger", "t1").public_message(),
+            "Trigger not found: t1"
+        );
+        assert_eq!(
+            Error::validation("limit too large").public_message(),
+            "Validation error: limit too large"
+        );
+    }
+
+    #[test]
+    fn test_transient_errors_are_retryable() {
+        let io = |kind| std::io::Error::new(kind, "io");
+        let retryable = [
+            Error::from(sqlx::Error::PoolTimedOut),
+            Error::from(sqlx::Error::Io(io(std::io::ErrorKind::ConnectionReset))),
+            Error::from(sqlx::Error::Io(io(std::io::ErrorKind::TimedOut))),
+            Error::from(redis::RedisError::from(io(
+                std::io::ErrorKind::ConnectionRefused,
+            ))),
+            Error::from(redis::RedisError::from((
+                redis::ErrorKind::BusyLoadingError,
+                "loading",
+            ))),
+            Error::internal("upstream returned 502"),
+        ];
+        for error in retryable {
+            assert!(error.is_retryable(), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retryable() {
+        let permanent = [
+            Error::from(sqlx::Error::RowNotFound),
+            Error::from(sqlx::Error::PoolClosed),
+            Error::from(sqlx::Error::ColumnNotFound("score".into())),
+            Error::from(redis::RedisError::from((
+                redis::ErrorKind::TypeError,
+                "WRONGTYPE",
+            ))),
+            Error::config("DATABASE_URL missing"),
+            Error::validation("limit too large"),
+            Error::not_found("Trigger", "t1"),
+            Error::authentication("bad token"),
+            Error::authorization("not a member"),
+        ];
+        for error in permanent {
+            assert!(!error.is_retryable(), "{}", error);
+        }
+    }
+}
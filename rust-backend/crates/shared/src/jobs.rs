@@ -14,6 +14,9 @@ pub const ACTION_JOBS_QUEUE: &str = "action_jobs";
 /// Dead letter queue for failed jobs
 pub const ACTION_JOBS_DLQ: &str = "action_jobs_dlq";
 
+/// Default number of workers on the shared action job queue
+pub const DEFAULT_ACTION_WORKER_COUNT: usize = 5;
+
 /// Action type enum for type safety
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -23,6 +26,11 @@ pub enum ActionType {
     Mcp,
 }
 
+impl ActionType {
+    /// Every action type
+    pub const ALL: [ActionType; 3] = [ActionType::Telegram, ActionType::Rest, ActionType::Mcp];
+}
+
 impl fmt::Display for ActionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -70,6 +78,31 @@ pub struct ActionJob {
     pub event_data: serde_json::Value,
     /// When this job was created
     pub created_at: DateTime<Utc>,
+    /// Correlation ID shared by every job created from the same event
+    ///
+    /// Jobs enqueued before this field existed get a fresh ID on deserialization.
+    #[serde(default = "new_correlation_id")]
+    pub correlation_id: String,
+    /// Organization that owns the trigger (absent on jobs from older producers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    /// Trigger action this job executes (absent on jobs from older producers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<i32>,
+    /// Times this job was replayed from the dead letter queue
+    ///
+    /// Replayed jobs are processed even if an equivalent job completed within
+    /// the workers' dedup window, since that completion was the failure.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub replay_count: u32,
+}
+
+fn new_correlation_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 impl ActionJob {
@@ -100,6 +133,168 @@ impl ActionJob {
             config,
             event_data,
             created_at: Utc::now(),
+            correlation_id: new_correlation_id(),
+            organization_id: None,
+            action_id: None,
+            replay_count: 0,
+        }
+    }
+
+    /// Set the correlation ID linking this job to its originating event
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = correlation_id.into();
+        self
+    }
+
+    /// Set the organization that owns the trigger
+    pub fn with_organization_id(mut self, organization_id: impl Into<String>) -> Self {
+        self.organization_id = Some(organization_id.into());
+        self
+    }
+
+    /// Set the trigger action this job executes
+    pub fn with_action_id(mut self, action_id: i32) -> Self {
+        self.action_id = Some(action_id);
+        self
+    }
+
+    /// Queue lane of this job
+    pub fn queue_priority(&self) -> QueuePriority {
+        QueuePriority::of(self.priority)
+    }
+}
+
+/// Queue for jobs of one action type with a dedicated worker pool
+///
+/// e.g. `action_jobs:telegram`
+pub fn action_type_queue(action_type: &ActionType) -> String {
+    format!("{}:{}", ACTION_JOBS_QUEUE, action_type)
+}
+
+/// Priority lane of an action job queue
+///
+/// Each queue is split into three Redis lists that workers drain high-first:
+/// normal jobs use the queue itself, high and low priority jobs use
+/// `<queue>:priority:high` and `<queue>:priority:low`. The lane follows the
+/// job's [`priority`](ActionJob::priority), which comes from the trigger
+/// action's priority.
+///
+/// Jobs returned to a queue (requeued in-flight jobs, DLQ replays) go to the
+/// normal lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueuePriority {
+    /// `priority >= 10`, e.g. security alerts
+    High,
+    /// `1..=9` (trigger actions default to 1)
+    Normal,
+    /// `priority <= 0`, e.g. bulk notifications
+    Low,
+}
+
+impl QueuePriority {
+    /// Lowest job priority in the high lane
+    pub const HIGH_MIN: i32 = 10;
+
+    /// Highest job priority in the low lane
+    pub const LOW_MAX: i32 = 0;
+
+    /// All lanes, highest first
+    pub const ALL: [QueuePriority; 3] = [Self::High, Self::Normal, Self::Low];
+
+    /// Lane of a job with `priority`
+    pub fn of(priority: i32) -> Self {
+        if priority >= Self::HIGH_MIN {
+            Self::High
+        } else if priority <= Self::LOW_MAX {
+            Self::Low
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// List of this lane in `queue`
+    pub fn lane(&self, queue: &str) -> String {
+        match self {
+            Self::High => format!("{}:priority:high", queue),
+            Self::Normal => queue.to_string(),
+            Self::Low => format!("{}:priority:low", queue),
+        }
+    }
+}
+
+/// Action worker pool layout
+///
+/// Read by the action workers to size their pools and by every job producer
+/// to pick a queue, so all services must see the same settings:
+///
+/// - `ACTION_WORKER_COUNT`: workers on the shared queue (default 5, at least 1)
+/// - `ACTION_WORKERS_TELEGRAM`, `ACTION_WORKERS_REST`, `ACTION_WORKERS_MCP`:
+///   size of a dedicated pool for that type (default 0, no dedicated pool)
+///
+/// Jobs of a type with a dedicated pool go to [`action_type_queue`]; all other
+/// jobs go to [`ACTION_JOBS_QUEUE`]. Shared workers handle every type, so jobs
+/// left on the shared queue by a producer that has not picked up the layout
+/// yet still run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionWorkerPools {
+    /// Workers on the shared queue
+    pub shared: usize,
+    /// Dedicated pool sizes (only types with at least one worker)
+    pub dedicated: Vec<(ActionType, usize)>,
+}
+
+impl Default for ActionWorkerPools {
+    fn default() -> Self {
+        Self {
+            shared: DEFAULT_ACTION_WORKER_COUNT,
+            dedicated: Vec::new(),
+        }
+    }
+}
+
+impl ActionWorkerPools {
+    /// Load the layout from the environment (invalid values use the default)
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let env_usize = |name: &str| lookup(name).and_then(|v| v.trim().parse::<usize>().ok());
+
+        let shared = env_usize("ACTION_WORKER_COUNT")
+            .unwrap_or(DEFAULT_ACTION_WORKER_COUNT)
+            .max(1);
+        let dedicated = ActionType::ALL
+            .into_iter()
+            .filter_map(|action_type| {
+                let name = format!("ACTION_WORKERS_{}", action_type.to_string().to_uppercase());
+                let size = env_usize(&name).unwrap_or(0);
+                (size > 0).then_some((action_type, size))
+            })
+            .collect();
+
+        Self { shared, dedicated }
+    }
+
+    /// Size of the dedicated pool for `action_type` (0 if it has none)
+    pub fn dedicated_size(&self, action_type: &ActionType) -> usize {
+        self.dedicated
+            .iter()
+            .find(|(t, _)| t == action_type)
+            .map_or(0, |(_, size)| *size)
+    }
+
+    /// Total number of workers across all pools
+    pub fn total_workers(&self) -> usize {
+        self.shared + self.dedicated.iter().map(|(_, size)| size).sum::<usize>()
+    }
+
+    /// Queue a job of `action_type` is enqueued on (without the key prefix)
+    pub fn queue_for(&self, action_type: &ActionType) -> String {
+        if self.dedicated_size(action_type) > 0 {
+            action_type_queue(action_type)
+        } else {
+            ACTION_JOBS_QUEUE.to_string()
         }
     }
 }
@@ -155,6 +350,73 @@ mod tests {
         assert_eq!(job.priority, deserialized.priority);
         assert_eq!(job.event_data, deserialized.event_data);
         assert_eq!(job.created_at, deserialized.created_at);
+        assert_eq!(job.correlation_id, deserialized.correlation_id);
+    }
+
+    #[test]
+    fn test_action_job_correlation_id_round_trip() {
+        let job = ActionJob::new("t1", "e1", ActionType::Rest, 1, json!({}), json!({}))
+            .with_correlation_id("corr-123");
+
+        let serialized = serde_json::to_string(&job).unwrap();
+        assert!(serialized.contains("\"correlation_id\":\"corr-123\""));
+
+        let deserialized: ActionJob = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.correlation_id, "corr-123");
+        assert_eq!(deserialized.trigger_id, "t1");
+        assert_eq!(deserialized.event_id, "e1");
+    }
+
+    #[test]
+    fn test_action_job_without_correlation_id_deserializes() {
+        // Jobs enqueued by an older event-processor have no correlation_id
+        let json = json!({
+            "id": "job-1",
+            "trigger_id": "t1",
+            "event_id": "e1",
+            "action_type": "telegram",
+            "priority": 1,
+            "config": {},
+            "event_data": {},
+            "created_at": "2026-01-01T00:00:00Z"
+        });
+
+        let job: ActionJob = serde_json::from_value(json).unwrap();
+        assert!(!job.correlation_id.is_empty());
+        assert!(job.organization_id.is_none());
+        assert!(job.action_id.is_none());
+    }
+
+    #[test]
+    fn test_action_job_organization_id_round_trip() {
+        let job = ActionJob::new("t1", "e1", ActionType::Mcp, 1, json!({}), json!({}))
+            .with_organization_id("org-1");
+
+        let serialized = serde_json::to_string(&job).unwrap();
+        let deserialized: ActionJob = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.organization_id.as_deref(), Some("org-1"));
+    }
+
+    #[test]
+    fn test_action_job_action_id_round_trip() {
+        let job = ActionJob::new("t1", "e1", ActionType::Rest, 1, json!({}), json!({}))
+            .with_action_id(42);
+
+        let serialized = serde_json::to_string(&job).unwrap();
+        let deserialized: ActionJob = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.action_id, Some(42));
+    }
+
+    #[test]
+    fn test_action_job_replay_count_omitted_until_replayed() {
+        let mut job = ActionJob::new("t1", "e1", ActionType::Rest, 1, json!({}), json!({}));
+        let serialized = serde_json::to_value(&job).unwrap();
+        assert!(serialized.get("replay_count").is_none());
+
+        job.replay_count = 2;
+        let serialized = serde_json::to_string(&job).unwrap();
+        let deserialized: ActionJob = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.replay_count, 2);
     }
 
     #[test]
@@ -202,4 +464,81 @@ mod tests {
         assert_eq!("REST".parse::<ActionType>().unwrap(), ActionType::Rest);
         assert_eq!("Mcp".parse::<ActionType>().unwrap(), ActionType::Mcp);
     }
+
+    fn pools_from(vars: &[(&str, &str)]) -> ActionWorkerPools {
+        ActionWorkerPools::from_lookup(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_worker_pools_default() {
+        let pools = pools_from(&[]);
+        assert_eq!(pools, ActionWorkerPools::default());
+        assert_eq!(pools.shared, DEFAULT_ACTION_WORKER_COUNT);
+        assert_eq!(pools.total_workers(), DEFAULT_ACTION_WORKER_COUNT);
+    }
+
+    #[test]
+    fn test_worker_pools_from_env_values() {
+        let pools = pools_from(&[
+            ("ACTION_WORKER_COUNT", "3"),
+            ("ACTION_WORKERS_TELEGRAM", "4"),
+            ("ACTION_WORKERS_REST", "0"),
+            ("ACTION_WORKERS_MCP", "abc"),
+        ]);
+        assert_eq!(pools.shared, 3);
+        assert_eq!(pools.dedicated, vec![(ActionType::Telegram, 4)]);
+        assert_eq!(pools.dedicated_size(&ActionType::Telegram), 4);
+        assert_eq!(pools.dedicated_size(&ActionType::Rest), 0);
+        assert_eq!(pools.total_workers(), 7);
+
+        // The shared queue always keeps a worker
+        assert_eq!(pools_from(&[("ACTION_WORKER_COUNT", "0")]).shared, 1);
+    }
+
+    #[test]
+    fn test_routing_without_dedicated_pools_uses_shared_queue() {
+        let pools = ActionWorkerPools::default();
+        for action_type in ActionType::ALL {
+            assert_eq!(pools.queue_for(&action_type), ACTION_JOBS_QUEUE);
+        }
+    }
+
+    #[test]
+    fn test_routing_to_dedicated_queues() {
+        let pools = pools_from(&[
+            ("ACTION_WORKERS_TELEGRAM", "2"),
+            ("ACTION_WORKERS_REST", "1"),
+        ]);
+
+        assert_eq!(
+            pools.queue_for(&ActionType::Telegram),
+            "action_jobs:telegram"
+        );
+        assert_eq!(pools.queue_for(&ActionType::Rest), "action_jobs:rest");
+        assert_eq!(pools.queue_for(&ActionType::Mcp), ACTION_JOBS_QUEUE);
+    }
+
+    #[test]
+    fn test_queue_priority_lanes() {
+        assert_eq!(QueuePriority::of(20), QueuePriority::High);
+        assert_eq!(QueuePriority::of(10), QueuePriority::High);
+        assert_eq!(QueuePriority::of(9), QueuePriority::Normal);
+        assert_eq!(QueuePriority::of(1), QueuePriority::Normal);
+        assert_eq!(QueuePriority::of(0), QueuePriority::Low);
+        assert_eq!(QueuePriority::of(-3), QueuePriority::Low);
+
+        assert_eq!(
+            QueuePriority::High.lane("staging:action_jobs"),
+            "staging:action_jobs:priority:high"
+        );
+        assert_eq!(QueuePriority::Normal.lane("action_jobs"), "action_jobs");
+        assert_eq!(
+            QueuePriority::Low.lane("action_jobs:rest"),
+            "action_jobs:rest:priority:low"
+        );
+    }
 }
@@ -1,29 +1,62 @@
 //! Shared library for api.agentauri.ai backend services
 //!
 //! This crate provides common functionality used across all backend services:
-//! - Database connection pooling and utilities
+//! - Database connection pooling, utilities and pool metrics
 //! - Common data models matching the PostgreSQL schema
 //! - Error handling types
 //! - Configuration management
 //! - Logging infrastructure
 //! - Job definitions for event processor and action workers
+//! - Dead letter queue inspection and replay
 //! - Redis client and rate limiting
+//! - Signing of outbound webhook deliveries
+//! - Dependency diagnostics for the `--check` mode of each binary
+//! - The canonical RFC 3339 timestamp format for API payloads
+//! - A clock seam for time-dependent components
+//! - Trigger schedules (weekly time windows in a time zone)
+//! - Notification preferences of agent follows
+//! - Trigger condition definitions and validation
 
+pub mod clock;
+pub mod conditions;
 pub mod config;
 pub mod db;
+pub mod diagnostics;
+pub mod dlq;
 pub mod error;
 pub mod jobs;
 pub mod models;
+pub mod notification_preferences;
+pub mod pool_metrics;
 pub mod redis;
+pub mod schedule;
 pub mod secrets;
+pub mod signing;
+pub mod timestamp;
 
 // Re-export commonly used types
-pub use config::{Config, DatabaseReadReplicaConfig};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use conditions::{validate_condition, Comparison, ConditionError, ConditionType};
+pub use config::{
+    ActionCostsConfig, ActionQueueConfig, AuthConfig, Config, DatabaseReadReplicaConfig,
+    StateCleanupConfig,
+};
 pub use db::{DbPool, DbPoolStats, DbPools};
+pub use dlq::{DlqAccessor, DlqEntry, DlqPage};
 pub use error::{Error, Result};
-pub use jobs::{ActionJob, ActionType, ACTION_JOBS_DLQ, ACTION_JOBS_QUEUE};
-pub use redis::{RateLimitResult, RateLimitScope, RateLimiter};
-pub use secrets::{load_secrets, AppSecrets, SecretsBackend, SecretsError};
+pub use jobs::{
+    action_type_queue, ActionJob, ActionType, ActionWorkerPools, QueuePriority, ACTION_JOBS_DLQ,
+    ACTION_JOBS_QUEUE, DEFAULT_ACTION_WORKER_COUNT,
+};
+pub use notification_preferences::{NotificationPreferences, PreferencesError};
+pub use pool_metrics::{PoolMetricsReporter, DEFAULT_POOL_METRICS_INTERVAL_SECS};
+pub use redis::{
+    RateLimitAlgorithm, RateLimitAlgorithms, RateLimitResult, RateLimitScope, RateLimiter,
+};
+pub use schedule::{ScheduleError, TriggerSchedule};
+pub use secrets::{
+    load_secrets, AppSecrets, OptionalFeature, SecretsBackend, SecretsError, SecretsValidation,
+};
 
 /// Initialize tracing subscriber for structured logging
 pub fn init_tracing() {
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use validator::Validate;
 
+use crate::notification_preferences::NotificationPreferences;
+use crate::schedule::TriggerSchedule;
+
 /// User account
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
@@ -13,8 +16,11 @@ pub struct User {
     pub email: String,
     #[serde(skip_serializing)]
     pub password_hash: Option<String>, // Optional for social-only users
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub last_login_at: Option<DateTime<Utc>>,
     pub is_active: bool,
     pub primary_auth_provider: Option<String>, // 'email', 'google', 'github', 'wallet'
@@ -26,6 +32,19 @@ pub struct User {
     pub locked_until: Option<DateTime<Utc>>,
     #[serde(skip_serializing)]
     pub last_failed_login: Option<DateTime<Utc>>,
+    /// When the user proved ownership of `email` (None = not verified)
+    #[serde(default, with = "crate::timestamp::option")]
+    pub email_verified_at: Option<DateTime<Utc>>,
+}
+
+impl User {
+    /// Whether the user still has to verify their email address
+    ///
+    /// Wallet users have a placeholder address and are never asked to.
+    pub fn email_verification_pending(&self) -> bool {
+        self.email_verified_at.is_none()
+            && self.primary_auth_provider.as_deref() != Some(AuthProvider::Wallet.as_str())
+    }
 }
 
 /// Organization for multi-tenant account model
@@ -38,7 +57,9 @@ pub struct Organization {
     pub owner_id: String,
     pub plan: String,
     pub is_personal: bool,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -50,7 +71,97 @@ pub struct OrganizationMember {
     pub user_id: String,
     pub role: String,
     pub invited_by: Option<String>,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Per-organization rate limit override for one query tier
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationRateLimit {
+    pub organization_id: String,
+    /// Query tier (0-3)
+    pub tier: i16,
+    pub request_limit: i32,
+    pub window_seconds: i32,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Per-organization endpoint notified after each action executes
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationActionWebhook {
+    pub organization_id: String,
+    pub url: String,
+    /// HMAC-SHA256 key for the `X-AgentAuri-Signature` header
+    pub secret: String,
+    pub enabled: bool,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Key an organization's outbound deliveries are signed with
+///
+/// The current key has no `retired_at`. After a rotation the previous key
+/// keeps signing alongside it until `expires_at`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationSigningKey {
+    pub id: String,
+    pub organization_id: String,
+    /// HMAC-SHA256 key for the `X-AgentAuri-Signature` header
+    pub secret: String,
+    pub key_prefix: String,
+    pub created_by: Option<String>,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp::option")]
+    pub retired_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp::option")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Organization endpoint notified of every trigger fire
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub id: String,
+    pub organization_id: String,
+    pub url: String,
+    /// HMAC-SHA256 key for the `X-AgentAuri-Signature` header
+    pub secret: String,
+    /// Event types to deliver (empty delivers every trigger fire)
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub created_by: Option<String>,
+    #[serde(with = "crate::timestamp")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One trigger fire queued for, or delivered to, a [`Webhook`]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: String,
+    pub trigger_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// pending, succeeded, or failed
+    pub status: String,
+    pub attempts: i32,
+    #[serde(with = "crate::timestamp")]
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::timestamp::option")]
+    pub delivered_at: Option<DateTime<Utc>>,
 }
 
 /// Agent follow relationship for simplified multi-registry monitoring.
@@ -73,7 +184,13 @@ pub struct AgentFollow {
     /// Auto-managed trigger for validation registry events
     pub trigger_validation_id: String,
     pub enabled: bool,
+    /// Event types and channels to notify about (None = everything)
+    #[sqlx(default, json(nullable))]
+    #[serde(default)]
+    pub notification_preferences: Option<NotificationPreferences>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -93,8 +210,23 @@ pub struct Trigger {
     pub registry: String,
     pub enabled: bool,
     pub is_stateful: bool,
+    /// Minimum seconds between two fires (0 = no cooldown)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub cooldown_secs: i32,
+    /// Weekly time window outside which the trigger does not fire (None = always)
+    #[sqlx(default, json(nullable))]
+    #[serde(default)]
+    pub schedule: Option<TriggerSchedule>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
+    /// Set when the trigger is soft-deleted; it is purged after the retention window
+    #[sqlx(default)]
+    #[serde(default)]
+    #[serde(with = "crate::timestamp::option")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// Trigger condition
@@ -109,6 +241,7 @@ pub struct TriggerCondition {
     pub value: serde_json::Value, // JSONB in production
     #[sqlx(json(nullable))]
     pub config: Option<serde_json::Value>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -121,6 +254,11 @@ pub struct TriggerAction {
     pub priority: i32,
     #[sqlx(json)]
     pub config: serde_json::Value,
+    /// Webhook verification handshake state (`not_required`, `unverified`, `verified`, `failed`)
+    pub verification_status: String,
+    #[serde(default, with = "crate::timestamp::option")]
+    pub verified_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -130,7 +268,13 @@ pub struct TriggerState {
     pub trigger_id: String,
     #[sqlx(json)]
     pub state_data: serde_json::Value,
+    #[serde(with = "crate::timestamp")]
     pub last_updated: DateTime<Utc>,
+    /// When the trigger last fired (only tracked for triggers with a cooldown)
+    #[sqlx(default)]
+    #[serde(default)]
+    #[serde(with = "crate::timestamp::option")]
+    pub last_fired_at: Option<DateTime<Utc>>,
 }
 
 /// Blockchain event
@@ -166,6 +310,7 @@ pub struct Event {
     pub response_uri: Option<String>,
     pub response_hash: Option<String>,
     pub tag: Option<String>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -175,6 +320,7 @@ pub struct Checkpoint {
     pub chain_id: i32,
     pub last_block_number: i64,
     pub last_block_hash: String,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -187,6 +333,7 @@ pub struct ActionResult {
     pub event_id: Option<String>,
     pub action_type: String,
     pub status: String,
+    #[serde(with = "crate::timestamp")]
     pub executed_at: DateTime<Utc>,
     pub duration_ms: Option<i32>,
     pub error_message: Option<String>,
@@ -210,11 +357,15 @@ pub struct ApiKey {
     #[sqlx(json)]
     pub permissions: serde_json::Value,
     pub rate_limit_override: Option<i32>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
     pub last_used_ip: Option<String>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub expires_at: Option<DateTime<Utc>>,
     pub created_by: String,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub revoked_at: Option<DateTime<Utc>>,
     pub revoked_by: Option<String>,
     pub revocation_reason: Option<String>,
@@ -301,6 +452,7 @@ pub struct ApiKeyAuditLog {
     pub actor_user_id: Option<String>,
     #[sqlx(json(nullable))]
     pub details: Option<serde_json::Value>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -343,6 +495,7 @@ pub struct AuthFailure {
     pub endpoint: Option<String>,
     #[sqlx(json(nullable))]
     pub details: Option<serde_json::Value>,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -380,7 +533,12 @@ pub struct OAuthClient {
     pub owner_organization_id: String,
     pub grant_types: Vec<String>,
     pub is_trusted: bool,
+    /// Public clients cannot keep a secret and must use PKCE
+    #[serde(default)]
+    pub is_public: bool,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(with = "crate::timestamp")]
     pub updated_at: DateTime<Utc>,
 }
 
@@ -396,9 +554,12 @@ pub struct OAuthToken {
     pub user_id: String,
     pub organization_id: String,
     pub scopes: Vec<String>,
+    #[serde(with = "crate::timestamp")]
     pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub refresh_token_expires_at: Option<DateTime<Utc>>,
     pub revoked: bool,
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -441,9 +602,12 @@ pub struct UserIdentity {
     pub refresh_token_encrypted: Option<String>,
 
     /// Token expiration time
+    #[serde(default, with = "crate::timestamp::option")]
     pub token_expires_at: Option<DateTime<Utc>>,
 
+    #[serde(with = "crate::timestamp")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, with = "crate::timestamp::option")]
     pub last_used_at: Option<DateTime<Utc>>,
 }
 
@@ -0,0 +1,217 @@
+//! Notification preferences of an agent follow
+//!
+//! A follow notifies through its actions on every event of the followed agent.
+//! Preferences narrow this down to some event types and some channels (action
+//! types):
+//!
+//! ```json
+//! { "event_types": ["NewFeedback", "FeedbackRevoked"], "channels": ["telegram"] }
+//! ```
+//!
+//! - `event_types`: event types to be notified about; omitted means all
+//! - `channels`: action types (`telegram`, `rest`, `mcp`) to notify through;
+//!   omitted means all
+//!
+//! An empty list allows nothing, so `"channels": []` mutes the follow.
+//!
+//! # Example
+//!
+//! ```
+//! use shared::NotificationPreferences;
+//!
+//! let preferences = NotificationPreferences {
+//!     event_types: Some(vec!["NewFeedback".into()]),
+//!     channels: None,
+//! };
+//!
+//! assert!(preferences.allows("NewFeedback", "telegram"));
+//! assert!(!preferences.allows("ValidationRequest", "telegram"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Action types a follow can notify through
+pub const NOTIFICATION_CHANNELS: &[&str] = &["telegram", "rest", "mcp"];
+
+/// Most event types one follow can list
+pub const MAX_EVENT_TYPES: usize = 32;
+
+/// Errors in a preferences definition
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PreferencesError {
+    #[error("Invalid event type '{0}' (expected a name such as NewFeedback)")]
+    InvalidEventType(String),
+
+    #[error("At most {MAX_EVENT_TYPES} event types can be listed")]
+    TooManyEventTypes,
+
+    #[error("Invalid channel '{0}' (expected telegram, rest or mcp)")]
+    InvalidChannel(String),
+
+    #[error("'{0}' is listed more than once")]
+    Duplicate(String),
+}
+
+/// What a follow notifies about, and how
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotificationPreferences {
+    /// Event types to be notified about (`None` = all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_types: Option<Vec<String>>,
+    /// Action types to notify through (`None` = all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<String>>,
+}
+
+impl NotificationPreferences {
+    /// Check the event type names and channels
+    pub fn validate(&self) -> Result<(), PreferencesError> {
+        if let Some(event_types) = &self.event_types {
+            if event_types.len() > MAX_EVENT_TYPES {
+                return Err(PreferencesError::TooManyEventTypes);
+            }
+            for event_type in event_types {
+                let valid = (1..=64).contains(&event_type.len())
+                    && event_type.chars().all(|c| c.is_ascii_alphanumeric());
+                if !valid {
+                    return Err(PreferencesError::InvalidEventType(event_type.clone()));
+                }
+            }
+            check_unique(event_types)?;
+        }
+
+        if let Some(channels) = &self.channels {
+            if let Some(channel) = channels
+                .iter()
+                .find(|c| !NOTIFICATION_CHANNELS.contains(&c.as_str()))
+            {
+                return Err(PreferencesError::InvalidChannel(channel.clone()));
+            }
+            check_unique(channels)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether events of `event_type` are notified at all
+    pub fn allows_event_type(&self, event_type: &str) -> bool {
+        self.event_types
+            .as_ref()
+            .is_none_or(|types| types.iter().any(|t| t == event_type))
+    }
+
+    /// Whether notifications go out through `channel`
+    pub fn allows_channel(&self, channel: &str) -> bool {
+        self.channels
+            .as_ref()
+            .is_none_or(|channels| channels.iter().any(|c| c == channel))
+    }
+
+    /// Whether an event of `event_type` is notified through `channel`
+    pub fn allows(&self, event_type: &str, channel: &str) -> bool {
+        self.allows_event_type(event_type) && self.allows_channel(channel)
+    }
+}
+
+fn check_unique(values: &[String]) -> Result<(), PreferencesError> {
+    for (i, value) in values.iter().enumerate() {
+        if values[..i].contains(value) {
+            return Err(PreferencesError::Duplicate(value.clone()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preferences(
+        event_types: Option<&[&str]>,
+        channels: Option<&[&str]>,
+    ) -> NotificationPreferences {
+        let owned = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        NotificationPreferences {
+            event_types: event_types.map(owned),
+            channels: channels.map(owned),
+        }
+    }
+
+    #[test]
+    fn test_default_allows_everything() {
+        let preferences = NotificationPreferences::default();
+        assert!(preferences.validate().is_ok());
+        assert!(preferences.allows("NewFeedback", "telegram"));
+        assert!(preferences.allows("ValidationRequest", "mcp"));
+    }
+
+    #[test]
+    fn test_allows_listed_event_types_and_channels() {
+        let preferences = preferences(Some(&["NewFeedback"]), Some(&["telegram", "rest"]));
+        assert!(preferences.validate().is_ok());
+        assert!(preferences.allows("NewFeedback", "telegram"));
+        assert!(preferences.allows("NewFeedback", "rest"));
+        assert!(!preferences.allows("NewFeedback", "mcp"));
+        assert!(!preferences.allows("ValidationRequest", "telegram"));
+        assert!(!preferences.allows_event_type("newfeedback"));
+    }
+
+    #[test]
+    fn test_empty_lists_allow_nothing() {
+        let no_events = preferences(Some(&[]), None);
+        assert!(!no_events.allows_event_type("NewFeedback"));
+
+        let muted = preferences(None, Some(&[]));
+        assert!(muted.allows_event_type("NewFeedback"));
+        assert!(!muted.allows_channel("telegram"));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_shapes() {
+        assert_eq!(
+            preferences(Some(&["New Feedback"]), None).validate(),
+            Err(PreferencesError::InvalidEventType("New Feedback".into()))
+        );
+        assert_eq!(
+            preferences(Some(&[""]), None).validate(),
+            Err(PreferencesError::InvalidEventType("".into()))
+        );
+        assert_eq!(
+            preferences(None, Some(&["email"])).validate(),
+            Err(PreferencesError::InvalidChannel("email".into()))
+        );
+        assert_eq!(
+            preferences(None, Some(&["rest", "rest"])).validate(),
+            Err(PreferencesError::Duplicate("rest".into()))
+        );
+
+        let many: Vec<String> = (0..=MAX_EVENT_TYPES)
+            .map(|i| format!("Event{}", i))
+            .collect();
+        let preferences = NotificationPreferences {
+            event_types: Some(many),
+            channels: None,
+        };
+        assert_eq!(
+            preferences.validate(),
+            Err(PreferencesError::TooManyEventTypes)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_fields() {
+        let parsed: Result<NotificationPreferences, _> =
+            serde_json::from_value(serde_json::json!({"event_type": ["NewFeedback"]}));
+        assert!(parsed.is_err());
+
+        let parsed: NotificationPreferences =
+            serde_json::from_value(serde_json::json!({"channels": ["mcp"]})).unwrap();
+        assert_eq!(parsed, preferences(None, Some(&["mcp"])));
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::json!({"channels": ["mcp"]})
+        );
+    }
+}
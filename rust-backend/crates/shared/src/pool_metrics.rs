@@ -0,0 +1,220 @@
+//! Database pool metrics
+//!
+//! [`PoolMetricsReporter`] periodically samples a sqlx pool and records it
+//! through the `metrics` facade, so it shows up on whichever Prometheus
+//! exporter the service has installed:
+//!
+//! - `db_pool_connections{pool, state="in_use"|"idle"}`
+//! - `db_pool_max_connections{pool}`
+//! - `db_pool_acquire_wait_seconds{pool}`
+//!
+//! sqlx does not expose how many tasks are waiting for a connection, so
+//! pending acquires are measured with a probe: each sample times one
+//! `acquire()`. The probe returns immediately while a connection is free and
+//! waits behind the queue once the pool is exhausted, so its latency tracks
+//! the wait callers are seeing. A warning is logged when it stays above the
+//! threshold for several consecutive samples.
+//!
+//! # Configuration
+//!
+//! - `DB_POOL_ACQUIRE_WARN_MS`: acquire wait that counts as saturated (default: 500)
+
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::db::DbPool;
+
+/// Default sampling interval in seconds
+pub const DEFAULT_POOL_METRICS_INTERVAL_SECS: u64 = 15;
+
+/// Default acquire wait above which a sample counts as saturated
+pub const DEFAULT_ACQUIRE_WARN_MS: u64 = 500;
+
+/// Consecutive saturated samples before warning
+const SATURATED_SAMPLES_BEFORE_WARN: u32 = 3;
+
+/// Upper bound on how long a probe waits for a connection
+const MAX_PROBE_WAIT: Duration = Duration::from_secs(5);
+
+/// One sample of a pool's state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSample {
+    pub in_use: u32,
+    pub idle: u32,
+    pub max: u32,
+    /// Time the probe waited for a connection (capped at the probe timeout)
+    pub acquire_wait: Duration,
+}
+
+/// Periodically records a pool's stats as metrics
+pub struct PoolMetricsReporter {
+    pool: DbPool,
+    name: &'static str,
+    acquire_warn_threshold: Duration,
+    saturated_samples: u32,
+}
+
+impl PoolMetricsReporter {
+    /// Create a reporter; `name` becomes the `pool` label (e.g. `primary`)
+    pub fn new(pool: DbPool, name: &'static str, acquire_warn_threshold: Duration) -> Self {
+        Self {
+            pool,
+            name,
+            acquire_warn_threshold,
+            saturated_samples: 0,
+        }
+    }
+
+    /// Read the saturation threshold from `DB_POOL_ACQUIRE_WARN_MS`
+    pub fn acquire_warn_threshold_from_env() -> Duration {
+        let ms = std::env::var("DB_POOL_ACQUIRE_WARN_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|ms| *ms > 0)
+            .unwrap_or(DEFAULT_ACQUIRE_WARN_MS);
+        Duration::from_millis(ms)
+    }
+
+    /// Sample the pool once and record the gauges
+    pub async fn sample_once(&mut self) -> PoolSample {
+        let start = Instant::now();
+        // Hold the probe connection only until the measurement is taken
+        let probe = tokio::time::timeout(MAX_PROBE_WAIT, self.pool.acquire()).await;
+        let acquire_wait = start.elapsed();
+        drop(probe);
+
+        let size = self.pool.size();
+        let idle = u32::try_from(self.pool.num_idle()).unwrap_or(u32::MAX);
+        let sample = PoolSample {
+            in_use: size.saturating_sub(idle),
+            idle,
+            max: self.pool.options().get_max_connections(),
+            acquire_wait,
+        };
+
+        metrics::gauge!("db_pool_connections", "pool" => self.name, "state" => "in_use")
+            .set(f64::from(sample.in_use));
+        metrics::gauge!("db_pool_connections", "pool" => self.name, "state" => "idle")
+            .set(f64::from(sample.idle));
+        metrics::gauge!("db_pool_max_connections", "pool" => self.name).set(f64::from(sample.max));
+        metrics::gauge!("db_pool_acquire_wait_seconds", "pool" => self.name)
+            .set(acquire_wait.as_secs_f64());
+
+        if acquire_wait >= self.acquire_warn_threshold {
+            self.saturated_samples += 1;
+            if self.saturated_samples == SATURATED_SAMPLES_BEFORE_WARN {
+                tracing::warn!(
+                    pool = self.name,
+                    in_use = sample.in_use,
+                    max = sample.max,
+                    acquire_wait_ms = acquire_wait.as_millis() as u64,
+                    "Database pool saturated: acquires have been waiting for several samples"
+                );
+            }
+        } else {
+            if self.saturated_samples >= SATURATED_SAMPLES_BEFORE_WARN {
+                tracing::info!(pool = self.name, "Database pool no longer saturated");
+            }
+            self.saturated_samples = 0;
+        }
+
+        sample
+    }
+
+    /// Sample the pool every interval until cancelled
+    pub async fn run(mut self, interval: Duration, cancel: CancellationToken) {
+        tracing::info!(
+            pool = self.name,
+            interval_secs = interval.as_secs(),
+            acquire_warn_ms = self.acquire_warn_threshold.as_millis() as u64,
+            "Database pool metrics started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    tracing::debug!(pool = self.name, "Database pool metrics stopping");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    self.sample_once().await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    async fn test_pool(max_connections: u32) -> DbPool {
+        let database_url = std::env::var("DATABASE_URL")
+            .expect("DATABASE_URL must be set for integration tests. See database/README.md for setup instructions.");
+
+        PgPoolOptions::new()
+            .max_connections(max_connections)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to test database")
+    }
+
+    #[tokio::test]
+    async fn test_sample_counts_connections_in_use() {
+        let pool = test_pool(3).await;
+        let held = pool.acquire().await.unwrap();
+
+        let mut reporter =
+            PoolMetricsReporter::new(pool.clone(), "test", Duration::from_millis(500));
+        let sample = reporter.sample_once().await;
+
+        assert_eq!(sample.max, 3);
+        assert!(sample.in_use >= 1);
+        assert!(sample.acquire_wait < Duration::from_millis(500));
+        assert_eq!(reporter.saturated_samples, 0);
+        drop(held);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_pool_counts_as_saturated() {
+        let pool = test_pool(1).await;
+        let held = pool.acquire().await.unwrap();
+
+        // Release the only connection after the probe has waited a while
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            drop(held);
+        });
+
+        let mut reporter = PoolMetricsReporter::new(pool, "test", Duration::from_millis(50));
+        let sample = reporter.sample_once().await;
+        release.await.unwrap();
+
+        assert!(sample.acquire_wait >= Duration::from_millis(50));
+        assert_eq!(reporter.saturated_samples, 1);
+
+        // A fast sample resets the streak
+        reporter.sample_once().await;
+        assert_eq!(reporter.saturated_samples, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_cancel() {
+        let pool = test_pool(1).await;
+        let cancel = CancellationToken::new();
+        let handle = tokio::spawn(
+            PoolMetricsReporter::new(pool, "test", Duration::from_millis(500))
+                .run(Duration::from_millis(10), cancel.clone()),
+        );
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("reporter should stop when cancelled")
+            .unwrap();
+    }
+}
@@ -12,27 +12,46 @@
 //! # Cache Strategy
 //!
 //! - **Write-through**: Updates written to both PostgreSQL and Redis
-//! - **TTL**: Configurable per entity type (default 5 minutes)
+//! - **TTL**: Configurable per entity type (default 5 minutes), with random
+//!   jitter (`ENTITY_CACHE_TTL_JITTER_PERCENT`, default ±10%) so entries
+//!   written together do not expire together
+//! - **Single-flight**: Concurrent misses for the same key within a process
+//!   share one database fetch (see [`get_or_fetch`])
 //! - **Graceful degradation**: Falls back to PostgreSQL if Redis unavailable
 //!
 //! # Key Prefixes
 //!
-//! - `user:id:{user_id}` - User by ID
-//! - `user:email:{email}` - User by email
-//! - `org:id:{org_id}` - Organization by ID
-//! - `org:member:{org_id}:{user_id}` - Membership role
-//! - `trigger:id:{trigger_id}` - Trigger by ID
-
+//! All keys live in the `agentauri:cache` namespace (see [`RedisKey`]):
+//!
+//! - `agentauri:cache:user:id:{user_id}` - User by ID
+//! - `agentauri:cache:user:email:{email}` - User by email
+//! - `agentauri:cache:org:id:{org_id}` - Organization by ID
+//! - `agentauri:cache:org:member:{org_id}:{user_id}` - Membership role
+//! - `agentauri:cache:org:role:{org_id}:{user_id}` - Member role string
+//! - `agentauri:cache:org:ratelimits:{org_id}` - Rate limit overrides
+//! - `agentauri:cache:trigger:id:{trigger_id}` - Trigger by ID
+//! - `agentauri:cache:trigger:state:{trigger_id}` - Evaluator state (event processor)
+
+use super::keys::RedisKey;
 use anyhow::Result;
+use rand::Rng;
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tracing::{debug, warn};
 
 /// Default cache TTL in seconds (5 minutes)
 const DEFAULT_TTL_SECS: u64 = 300;
 
+/// Default TTL jitter, as a percentage of the TTL in either direction
+const DEFAULT_TTL_JITTER_PERCENT: u8 = 10;
+
+/// Largest accepted TTL jitter
+const MAX_TTL_JITTER_PERCENT: u8 = 50;
+
 /// Entity cache manager for Redis
 ///
 /// Generic caching layer that can cache any serializable entity.
@@ -40,7 +59,9 @@ const DEFAULT_TTL_SECS: u64 = 300;
 pub struct EntityCache {
     redis: ConnectionManager,
     ttl: Duration,
+    ttl_jitter_percent: u8,
     enabled: bool,
+    inflight: KeyLocks,
 }
 
 impl EntityCache {
@@ -58,8 +79,15 @@ impl EntityCache {
 
         let ttl = Duration::from_secs(ttl_secs.unwrap_or(DEFAULT_TTL_SECS));
 
+        let ttl_jitter_percent = std::env::var("ENTITY_CACHE_TTL_JITTER_PERCENT")
+            .ok()
+            .and_then(|v| v.parse::<u8>().ok())
+            .unwrap_or(DEFAULT_TTL_JITTER_PERCENT)
+            .min(MAX_TTL_JITTER_PERCENT);
+
         debug!(
             ttl_secs = ttl.as_secs(),
+            ttl_jitter_percent = ttl_jitter_percent,
             enabled = enabled,
             "Initializing EntityCache"
         );
@@ -67,10 +95,18 @@ impl EntityCache {
         Self {
             redis,
             ttl,
+            ttl_jitter_percent,
             enabled,
+            inflight: KeyLocks::default(),
         }
     }
 
+    /// Override the TTL jitter percentage (capped at 50)
+    pub fn with_ttl_jitter(mut self, percent: u8) -> Self {
+        self.ttl_jitter_percent = percent.min(MAX_TTL_JITTER_PERCENT);
+        self
+    }
+
     /// Get an entity from cache
     ///
     /// Returns None if not found or on Redis error (graceful degradation)
@@ -115,10 +151,8 @@ impl EntityCache {
 
         match serde_json::to_string(entity) {
             Ok(json_str) => {
-                if let Err(e) = conn
-                    .set_ex::<_, _, ()>(key, json_str, self.ttl.as_secs())
-                    .await
-                {
+                let ttl = jittered_ttl(self.ttl, self.ttl_jitter_percent);
+                if let Err(e) = conn.set_ex::<_, _, ()>(key, json_str, ttl.as_secs()).await {
                     warn!(key = key, error = %e, "Redis cache write failed");
                 }
             }
@@ -151,29 +185,56 @@ impl EntityCache {
             return;
         }
 
-        let mut conn = self.redis.clone();
+        if let Err(e) = self.flush_pattern(pattern).await {
+            warn!(pattern = pattern, error = %e, "Redis pattern delete failed");
+        }
+    }
 
-        // Use SCAN to find matching keys (non-blocking)
-        let keys: Result<Vec<String>, _> = redis::cmd("SCAN")
-            .arg(0)
-            .arg("MATCH")
-            .arg(pattern)
-            .arg("COUNT")
-            .arg(100)
-            .query_async(&mut conn)
-            .await;
-
-        match keys {
-            Ok(keys) if !keys.is_empty() => {
-                if let Err(e) = conn.del::<_, ()>(&keys).await {
-                    warn!(pattern = pattern, error = %e, "Redis pattern delete failed");
-                }
+    /// Delete every key matching a pattern and return how many were removed
+    ///
+    /// Walks the whole keyspace with SCAN, so it is safe on a live server.
+    /// Unlike [`EntityCache::delete_pattern`], this runs even when caching is
+    /// disabled (stale entries may remain from before it was turned off) and
+    /// reports Redis errors to the caller.
+    pub async fn flush_pattern(&self, pattern: &str) -> Result<usize> {
+        let mut conn = self.redis.clone();
+        let mut cursor: u64 = 0;
+        let mut deleted = 0;
+
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut conn)
+                .await?;
+
+            if !keys.is_empty() {
+                deleted += conn.del::<_, usize>(&keys).await?;
             }
-            Err(e) => {
-                warn!(pattern = pattern, error = %e, "Redis SCAN failed");
+
+            if next == 0 {
+                break;
             }
-            _ => {}
+            cursor = next;
         }
+
+        debug!(
+            pattern = pattern,
+            deleted = deleted,
+            "Cache pattern flushed"
+        );
+        Ok(deleted)
+    }
+
+    /// Delete a single key and return how many were removed (0 or 1)
+    ///
+    /// Like [`EntityCache::flush_pattern`], runs even when caching is disabled.
+    pub async fn flush_key(&self, key: &str) -> Result<usize> {
+        let mut conn = self.redis.clone();
+        Ok(conn.del::<_, usize>(key).await?)
     }
 
     /// Check if caching is enabled
@@ -193,42 +254,128 @@ impl EntityCache {
 
 /// Build cache key for user by ID
 pub fn user_key_by_id(user_id: &str) -> String {
-    format!("user:id:{}", user_id)
+    RedisKey::cache("user").part("id").part(user_id).into()
 }
 
 /// Build cache key for user by email
 pub fn user_key_by_email(email: &str) -> String {
-    format!("user:email:{}", email.to_lowercase())
+    RedisKey::cache("user")
+        .part("email")
+        .part(email.to_lowercase())
+        .into()
 }
 
 /// Build cache key for user by username
 pub fn user_key_by_username(username: &str) -> String {
-    format!("user:username:{}", username.to_lowercase())
+    RedisKey::cache("user")
+        .part("username")
+        .part(username.to_lowercase())
+        .into()
 }
 
 /// Build cache key for organization by ID
 pub fn org_key_by_id(org_id: &str) -> String {
-    format!("org:id:{}", org_id)
+    RedisKey::cache("org").part("id").part(org_id).into()
 }
 
 /// Build cache key for organization membership role
 pub fn membership_key(org_id: &str, user_id: &str) -> String {
-    format!("org:member:{}:{}", org_id, user_id)
+    RedisKey::cache("org")
+        .part("member")
+        .part(org_id)
+        .part(user_id)
+        .into()
+}
+
+/// Build cache key for a member's role string
+pub fn member_role_key(org_id: &str, user_id: &str) -> String {
+    RedisKey::cache("org")
+        .part("role")
+        .part(org_id)
+        .part(user_id)
+        .into()
+}
+
+/// Build cache key for an organization's rate limit overrides
+pub fn org_rate_limits_key(org_id: &str) -> String {
+    RedisKey::cache("org")
+        .part("ratelimits")
+        .part(org_id)
+        .into()
 }
 
 /// Build cache key for trigger by ID
 pub fn trigger_key_by_id(trigger_id: &str) -> String {
-    format!("trigger:id:{}", trigger_id)
+    RedisKey::cache("trigger")
+        .part("id")
+        .part(trigger_id)
+        .into()
+}
+
+/// Build cache key for a trigger's evaluator state
+pub fn trigger_state_key(trigger_id: &str) -> String {
+    RedisKey::cache("trigger")
+        .part("state")
+        .part(trigger_id)
+        .into()
+}
+
+/// Build cache key pattern for all keys of one trigger
+pub fn trigger_keys_pattern(trigger_id: &str) -> String {
+    RedisKey::cache("trigger").part("*").part(trigger_id).into()
 }
 
 /// Build cache key pattern for all user keys
 pub fn user_keys_pattern(user_id: &str) -> String {
-    format!("user:*:{}*", user_id)
+    RedisKey::cache("user")
+        .part("*")
+        .part(format!("{}*", user_id))
+        .into()
 }
 
 /// Build cache key pattern for all org keys
 pub fn org_keys_pattern(org_id: &str) -> String {
-    format!("org:*:{}*", org_id)
+    RedisKey::cache("org")
+        .part("*")
+        .part(format!("{}*", org_id))
+        .into()
+}
+
+/// Spread `ttl` uniformly over ±`percent`%, never below one second
+fn jittered_ttl(ttl: Duration, percent: u8) -> Duration {
+    let secs = ttl.as_secs();
+    let spread = secs * u64::from(percent) / 100;
+    if spread == 0 {
+        return Duration::from_secs(secs.max(1));
+    }
+    let secs = rand::thread_rng().gen_range(secs - spread..=secs + spread);
+    Duration::from_secs(secs.max(1))
+}
+
+/// Per-key async locks for single-flight fetches
+///
+/// Entries are weak, so a key's lock is dropped once no fetch holds it.
+#[derive(Clone, Default)]
+struct KeyLocks(Arc<Mutex<HashMap<String, Weak<tokio::sync::Mutex<()>>>>>);
+
+impl KeyLocks {
+    /// Lock shared by every caller currently working on `key`
+    fn lock_for(&self, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(lock) = locks.get(key).and_then(Weak::upgrade) {
+            return lock;
+        }
+
+        locks.retain(|_, lock| lock.strong_count() > 0);
+        let lock = Arc::new(tokio::sync::Mutex::new(()));
+        locks.insert(key.to_string(), Arc::downgrade(&lock));
+        lock
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
 }
 
 // ============================================================================
@@ -241,6 +388,11 @@ pub fn org_keys_pattern(org_id: &str) -> String {
 /// 1. Check cache first
 /// 2. On miss, fetch from database
 /// 3. Cache the result for future reads
+///
+/// Misses are single-flight per process: when many requests miss the same
+/// key at once (e.g. a popular entry expired), one fetches from the database
+/// while the others wait and then read its cached result. Combined with TTL
+/// jitter this keeps a mass expiry from turning into a database stampede.
 pub async fn get_or_fetch<T, F, Fut>(cache: &EntityCache, key: &str, fetch: F) -> Result<Option<T>>
 where
     T: Serialize + DeserializeOwned + Clone + Send + Sync,
@@ -252,6 +404,19 @@ where
         return Ok(Some(cached));
     }
 
+    // Nothing is cached when disabled, so waiting on another fetch cannot help
+    if !cache.enabled {
+        return fetch().await;
+    }
+
+    let lock = cache.inflight.lock_for(key);
+    let _guard = lock.lock().await;
+
+    // Another caller may have filled the cache while we waited
+    if let Some(cached) = cache.get::<T>(key).await {
+        return Ok(Some(cached));
+    }
+
     // Fetch from database
     let result = fetch().await?;
 
@@ -269,40 +434,169 @@ pub trait CacheAware {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_jittered_ttl_stays_within_band() {
+        let ttl = Duration::from_secs(300);
+        let ttls: Vec<u64> = (0..1000).map(|_| jittered_ttl(ttl, 10).as_secs()).collect();
+
+        assert!(ttls.iter().all(|t| (270..=330).contains(t)), "{:?}", ttls);
+        // Entries written together must not share one expiry
+        let distinct: std::collections::HashSet<_> = ttls.iter().collect();
+        assert!(distinct.len() > 10, "only {} distinct TTLs", distinct.len());
+        assert!(ttls.iter().any(|t| *t < 300) && ttls.iter().any(|t| *t > 300));
+    }
+
+    #[test]
+    fn test_jittered_ttl_without_jitter() {
+        assert_eq!(
+            jittered_ttl(Duration::from_secs(300), 0),
+            Duration::from_secs(300)
+        );
+        // Too short to spread, and never zero (SETEX rejects 0)
+        assert_eq!(
+            jittered_ttl(Duration::from_secs(5), 10),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            jittered_ttl(Duration::from_secs(0), 10),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_key_locks_are_shared_and_released() {
+        let locks = KeyLocks::default();
+        let a = locks.lock_for("k1");
+        let b = locks.lock_for("k1");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &locks.lock_for("k2")));
+
+        drop((a, b));
+        // Released entries are pruned on the next insert
+        let _c = locks.lock_for("k3");
+        assert_eq!(locks.len(), 1);
+    }
+
+    async fn test_cache() -> EntityCache {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        let redis = crate::redis::create_client(&url)
+            .await
+            .expect("Redis must be running for this test");
+        EntityCache::new(redis, Some(300)).with_ttl_jitter(10)
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_concurrent_misses_fetch_once() {
+        let cache = test_cache().await;
+        let key = user_key_by_id(&uuid::Uuid::new_v4().to_string());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..10 {
+            let (cache, key, fetches) = (cache.clone(), key.clone(), fetches.clone());
+            tasks.spawn(async move {
+                get_or_fetch::<String, _, _>(&cache, &key, || async move {
+                    fetches.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    Ok(Some("entity".to_string()))
+                })
+                .await
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            assert_eq!(result.unwrap().unwrap().as_deref(), Some("entity"));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        let mut conn = cache.redis.clone();
+        let ttl: i64 = conn.ttl(&key).await.unwrap();
+        assert!((269..=330).contains(&ttl), "ttl {}", ttl);
+        cache.delete(&key).await;
+    }
 
     #[test]
     fn test_user_key_by_id() {
-        assert_eq!(user_key_by_id("user_123"), "user:id:user_123");
+        assert_eq!(
+            user_key_by_id("user_123"),
+            "agentauri:cache:user:id:user_123"
+        );
     }
 
     #[test]
     fn test_user_key_by_email() {
         assert_eq!(
             user_key_by_email("Test@Example.COM"),
-            "user:email:test@example.com"
+            "agentauri:cache:user:email:test@example.com"
         );
     }
 
     #[test]
     fn test_user_key_by_username() {
-        assert_eq!(user_key_by_username("TestUser"), "user:username:testuser");
+        assert_eq!(
+            user_key_by_username("TestUser"),
+            "agentauri:cache:user:username:testuser"
+        );
     }
 
     #[test]
     fn test_org_key_by_id() {
-        assert_eq!(org_key_by_id("org_456"), "org:id:org_456");
+        assert_eq!(org_key_by_id("org_456"), "agentauri:cache:org:id:org_456");
     }
 
     #[test]
     fn test_membership_key() {
         assert_eq!(
             membership_key("org_456", "user_123"),
-            "org:member:org_456:user_123"
+            "agentauri:cache:org:member:org_456:user_123"
+        );
+    }
+
+    #[test]
+    fn test_org_rate_limits_key() {
+        assert_eq!(
+            org_rate_limits_key("org_456"),
+            "agentauri:cache:org:ratelimits:org_456"
         );
     }
 
     #[test]
     fn test_trigger_key_by_id() {
-        assert_eq!(trigger_key_by_id("trigger_789"), "trigger:id:trigger_789");
+        assert_eq!(
+            trigger_key_by_id("trigger_789"),
+            "agentauri:cache:trigger:id:trigger_789"
+        );
+    }
+
+    #[test]
+    fn test_trigger_state_key() {
+        assert_eq!(
+            trigger_state_key("trigger_789"),
+            "agentauri:cache:trigger:state:trigger_789"
+        );
+        assert_ne!(trigger_state_key("x"), trigger_key_by_id("x"));
+    }
+
+    #[test]
+    fn test_member_role_key() {
+        assert_eq!(
+            member_role_key("org_456", "user_123"),
+            "agentauri:cache:org:role:org_456:user_123"
+        );
+    }
+
+    #[test]
+    fn test_patterns_cover_entity_keys() {
+        assert_eq!(
+            user_keys_pattern("user_123"),
+            "agentauri:cache:user:*:user_123*"
+        );
+        assert_eq!(
+            org_keys_pattern("org_456"),
+            "agentauri:cache:org:*:org_456*"
+        );
     }
 }
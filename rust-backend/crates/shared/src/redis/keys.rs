@@ -0,0 +1,441 @@
+//! Namespaced Redis key construction
+//!
+//! Every key the services create goes through [`RedisKey`], so all keys share
+//! one layout:
+//!
+//! ```text
+//! agentauri:<namespace>:<subsystem>[:<part>...]
+//! ```
+//!
+//! - **namespace** - what the key is for: `cache`, `rl` (rate limit), `lock`
+//!   or `dedup`
+//! - **subsystem** - the owner within that namespace (`user`, `org`, `ip`,
+//!   `idempotency`, ...). Subsystem names never contain `:`, so keys owned by
+//!   different subsystems can never collide, whatever their parts contain.
+//! - **parts** - identifiers appended in order
+//!
+//! Moving every key under [`APP_PREFIX`] also means a Redis instance shared
+//! with other applications can be flushed or migrated by prefix.
+//!
+//! # Environment prefix
+//!
+//! Several environments (e.g. staging and production) can share one Redis by
+//! setting a different `REDIS_KEY_PREFIX` in each. Services pass it to
+//! [`init_key_prefix`] at startup, and every key then starts with it:
+//!
+//! ```text
+//! staging:agentauri:cache:user:id:user_123
+//! ```
+//!
+//! There is no prefix by default, so existing keys keep their names.
+//!
+//! Job queue names (`action_jobs` and its processing lists) do not get
+//! [`APP_PREFIX`]: producers and consumers are deployed separately, and
+//! renaming a queue would strand the jobs already in it. They do get the
+//! environment prefix, through [`queue_key`].
+//!
+//! # Example
+//!
+//! ```
+//! use shared::redis::RedisKey;
+//!
+//! let key = RedisKey::cache("user").part("id").part("user_123");
+//! assert_eq!(key.as_str(), "agentauri:cache:user:id:user_123");
+//! ```
+
+use crate::error::{Error, Result};
+use redis::{RedisWrite, ToRedisArgs};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Prefix shared by every key this application writes
+pub const APP_PREFIX: &str = "agentauri";
+
+/// Separator between key segments
+const SEPARATOR: char = ':';
+
+/// Longest accepted environment prefix
+const MAX_ENV_PREFIX_LEN: usize = 32;
+
+/// Key space used by [`RedisKey`] constructors, set by [`init_key_prefix`]
+static KEY_SPACE: OnceLock<KeySpace> = OnceLock::new();
+
+/// Top-level key namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyNamespace {
+    /// Read-through caches that can be dropped at any time
+    Cache,
+    /// Rate limiting counters and buckets
+    RateLimit,
+    /// Short-lived mutual exclusion locks
+    Lock,
+    /// Markers recording that work was already done
+    Dedup,
+}
+
+impl KeyNamespace {
+    /// Segment used in the key
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyNamespace::Cache => "cache",
+            KeyNamespace::RateLimit => "rl",
+            KeyNamespace::Lock => "lock",
+            KeyNamespace::Dedup => "dedup",
+        }
+    }
+}
+
+impl fmt::Display for KeyNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where this deployment's keys live: an optional environment prefix
+/// followed by [`APP_PREFIX`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeySpace {
+    env_prefix: Option<String>,
+}
+
+impl KeySpace {
+    /// Key space for `env_prefix` (`None` or empty for no prefix)
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error unless the prefix is at most 32
+    /// characters of ASCII letters, digits, `-` and `_`. Disallowing `:`
+    /// keeps one environment from naming keys inside another's.
+    pub fn new(env_prefix: Option<&str>) -> Result<Self> {
+        let env_prefix = env_prefix.map(str::trim).filter(|p| !p.is_empty());
+        if let Some(prefix) = env_prefix {
+            let valid = prefix.len() <= MAX_ENV_PREFIX_LEN
+                && prefix
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(Error::config(format!(
+                    "REDIS_KEY_PREFIX {:?} must be at most {} characters of letters, digits, '-' and '_'",
+                    prefix, MAX_ENV_PREFIX_LEN
+                )));
+            }
+        }
+        Ok(Self {
+            env_prefix: env_prefix.map(str::to_string),
+        })
+    }
+
+    /// Start a key in `namespace` owned by `subsystem`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subsystem` is empty or contains `:`. Subsystems are
+    /// compile-time names, so this only fires on a programming error.
+    pub fn key(&self, namespace: KeyNamespace, subsystem: &str) -> RedisKey {
+        assert!(
+            !subsystem.is_empty() && !subsystem.contains(SEPARATOR),
+            "invalid Redis key subsystem: {:?}",
+            subsystem
+        );
+        RedisKey(format!("{}{}", self.namespace_prefix(namespace), subsystem))
+    }
+
+    /// Prefix shared by every key in `namespace`, ending in `:`
+    pub fn namespace_prefix(&self, namespace: KeyNamespace) -> String {
+        format!(
+            "{}{}{sep}{}{sep}",
+            self.env_segment(),
+            APP_PREFIX,
+            namespace.as_str(),
+            sep = SEPARATOR
+        )
+    }
+
+    /// Name of a job queue (or a key derived from one)
+    pub fn queue(&self, name: &str) -> String {
+        format!("{}{}", self.env_segment(), name)
+    }
+
+    /// The environment prefix and separator, or nothing
+    fn env_segment(&self) -> String {
+        match &self.env_prefix {
+            Some(prefix) => format!("{}{}", prefix, SEPARATOR),
+            None => String::new(),
+        }
+    }
+}
+
+/// Set the environment prefix for every key built in this process
+///
+/// Call once at startup, before any key is built (normally with
+/// `config.redis.key_prefix`).
+///
+/// # Errors
+///
+/// Returns a configuration error if the prefix is invalid, or if keys were
+/// already built with a different prefix.
+pub fn init_key_prefix(env_prefix: Option<&str>) -> Result<()> {
+    let space = KeySpace::new(env_prefix)?;
+    let active = KEY_SPACE.get_or_init(|| space.clone());
+    if *active != space {
+        return Err(Error::config(
+            "Redis key prefix was set after keys were already built",
+        ));
+    }
+    Ok(())
+}
+
+/// The key space of this process (no prefix unless [`init_key_prefix`] ran)
+pub fn key_space() -> &'static KeySpace {
+    KEY_SPACE.get_or_init(KeySpace::default)
+}
+
+/// Name of a job queue in this process's key space
+///
+/// Without an environment prefix this is `name` itself.
+pub fn queue_key(name: &str) -> String {
+    key_space().queue(name)
+}
+
+/// A fully qualified Redis key
+///
+/// Build one with a namespace constructor, then append identifiers with
+/// [`RedisKey::part`]. The key can be passed directly to redis commands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RedisKey(String);
+
+impl RedisKey {
+    /// Start a key in `namespace` owned by `subsystem`, in this process's
+    /// key space
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subsystem` is empty or contains `:` (see [`KeySpace::key`]).
+    pub fn new(namespace: KeyNamespace, subsystem: &str) -> Self {
+        key_space().key(namespace, subsystem)
+    }
+
+    /// Key for a cached entity
+    pub fn cache(subsystem: &str) -> Self {
+        Self::new(KeyNamespace::Cache, subsystem)
+    }
+
+    /// Key for a rate limit counter
+    pub fn rate_limit(subsystem: &str) -> Self {
+        Self::new(KeyNamespace::RateLimit, subsystem)
+    }
+
+    /// Key for a lock
+    pub fn lock(subsystem: &str) -> Self {
+        Self::new(KeyNamespace::Lock, subsystem)
+    }
+
+    /// Key for a dedup marker
+    pub fn dedup(subsystem: &str) -> Self {
+        Self::new(KeyNamespace::Dedup, subsystem)
+    }
+
+    /// Append a segment
+    ///
+    /// Pass `"*"` to build a `SCAN MATCH` pattern.
+    pub fn part(mut self, part: impl fmt::Display) -> Self {
+        use fmt::Write;
+        // Writing to a String cannot fail
+        let _ = write!(self.0, "{}{}", SEPARATOR, part);
+        self
+    }
+
+    /// The key as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RedisKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for RedisKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<RedisKey> for String {
+    fn from(key: RedisKey) -> Self {
+        key.0
+    }
+}
+
+impl ToRedisArgs for RedisKey {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.0.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_format() {
+        assert_eq!(
+            RedisKey::cache("user").part("id").part("user_123").as_str(),
+            "agentauri:cache:user:id:user_123"
+        );
+        assert_eq!(
+            RedisKey::rate_limit("org").part("org_1").as_str(),
+            "agentauri:rl:org:org_1"
+        );
+        assert_eq!(
+            RedisKey::lock("idempotency").part("abc").as_str(),
+            "agentauri:lock:idempotency:abc"
+        );
+        assert_eq!(
+            RedisKey::dedup("action_jobs").part("abc").as_str(),
+            "agentauri:dedup:action_jobs:abc"
+        );
+    }
+
+    #[test]
+    fn test_parts_accept_any_display() {
+        assert_eq!(
+            RedisKey::rate_limit("agent")
+                .part(42)
+                .part("tb")
+                .to_string(),
+            "agentauri:rl:agent:42:tb"
+        );
+    }
+
+    #[test]
+    fn test_every_key_has_app_prefix() {
+        for namespace in [
+            KeyNamespace::Cache,
+            KeyNamespace::RateLimit,
+            KeyNamespace::Lock,
+            KeyNamespace::Dedup,
+        ] {
+            let key = RedisKey::new(namespace, "x");
+            assert!(key.as_str().starts_with("agentauri:"), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_namespaces_cannot_collide() {
+        // Same subsystem and parts in different namespaces
+        let cache = RedisKey::cache("idempotency").part("abc");
+        let lock = RedisKey::lock("idempotency").part("abc");
+        let dedup = RedisKey::dedup("idempotency").part("abc");
+        assert_ne!(cache, lock);
+        assert_ne!(lock, dedup);
+        assert_ne!(cache, dedup);
+    }
+
+    #[test]
+    fn test_subsystems_cannot_collide() {
+        // Parts containing the separator cannot reach into another subsystem
+        let org = RedisKey::cache("org").part("member:x");
+        let org_member = RedisKey::cache("org_member").part("x");
+        assert_ne!(org, org_member);
+        assert!(!org
+            .as_str()
+            .starts_with(RedisKey::cache("org_member").as_str()));
+
+        // A subsystem key is never a prefix of a sibling subsystem's keys
+        let ip = RedisKey::rate_limit("ip");
+        let ip_v6 = RedisKey::rate_limit("ipv6").part("::1");
+        assert!(!ip_v6.as_str().starts_with(&format!("{}:", ip)));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid Redis key subsystem")]
+    fn test_subsystem_with_separator_is_rejected() {
+        let _ = RedisKey::cache("user:id");
+    }
+
+    #[test]
+    fn test_env_prefix_is_prepended() {
+        let staging = KeySpace::new(Some("staging")).unwrap();
+        assert_eq!(
+            staging
+                .key(KeyNamespace::Cache, "user")
+                .part("id")
+                .part("u1")
+                .as_str(),
+            "staging:agentauri:cache:user:id:u1"
+        );
+        assert_eq!(
+            staging.namespace_prefix(KeyNamespace::RateLimit),
+            "staging:agentauri:rl:"
+        );
+        assert_eq!(staging.queue("action_jobs"), "staging:action_jobs");
+    }
+
+    #[test]
+    fn test_no_env_prefix_by_default() {
+        for space in [
+            KeySpace::default(),
+            KeySpace::new(None).unwrap(),
+            KeySpace::new(Some("  ")).unwrap(),
+        ] {
+            assert_eq!(
+                space.key(KeyNamespace::Lock, "idempotency").as_str(),
+                "agentauri:lock:idempotency"
+            );
+            assert_eq!(space.queue("action_jobs"), "action_jobs");
+        }
+        // Process-wide keys are unprefixed unless init_key_prefix ran
+        assert_eq!(key_space(), &KeySpace::default());
+        assert_eq!(queue_key("action_jobs"), "action_jobs");
+    }
+
+    #[test]
+    fn test_env_prefixes_cannot_collide() {
+        let spaces = [
+            KeySpace::default(),
+            KeySpace::new(Some("staging")).unwrap(),
+            KeySpace::new(Some("prod")).unwrap(),
+            KeySpace::new(Some("agentauri")).unwrap(),
+        ];
+        for (i, a) in spaces.iter().enumerate() {
+            for b in &spaces[i + 1..] {
+                let (ka, kb) = (
+                    a.key(KeyNamespace::Cache, "org").part("id").part("x"),
+                    b.key(KeyNamespace::Cache, "org").part("id").part("x"),
+                );
+                assert_ne!(ka, kb);
+                // Neither environment's SCAN pattern can match the other's keys
+                assert!(!kb
+                    .as_str()
+                    .starts_with(&a.namespace_prefix(KeyNamespace::Cache)));
+                assert!(!ka
+                    .as_str()
+                    .starts_with(&b.namespace_prefix(KeyNamespace::Cache)));
+                assert_ne!(a.queue("action_jobs"), b.queue("action_jobs"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_env_prefix_is_rejected() {
+        for prefix in ["a:b", "has space", "staging*", &"x".repeat(33)] {
+            assert!(KeySpace::new(Some(prefix)).is_err(), "{}", prefix);
+        }
+        assert!(KeySpace::new(Some("eu-west_1")).is_ok());
+    }
+
+    #[test]
+    fn test_to_redis_args() {
+        let key = RedisKey::cache("user").part("id").part("u1");
+        assert_eq!(
+            key.to_redis_args(),
+            vec![b"agentauri:cache:user:id:u1".to_vec()]
+        );
+    }
+}
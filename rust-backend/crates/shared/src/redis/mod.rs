@@ -5,16 +5,27 @@
 //! - Rate limiting operations
 //! - Job queue management
 //! - Entity caching (users, organizations, triggers)
+//! - Namespaced key construction
+//! - A Redis harness for tests ([`testing`])
 
 pub mod cache;
+pub mod keys;
 pub mod rate_limiter;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use cache::{
-    get_or_fetch, membership_key, org_key_by_id, org_keys_pattern, trigger_key_by_id,
+    get_or_fetch, member_role_key, membership_key, org_key_by_id, org_keys_pattern,
+    org_rate_limits_key, trigger_key_by_id, trigger_keys_pattern, trigger_state_key,
     user_key_by_email, user_key_by_id, user_key_by_username, user_keys_pattern, CacheAware,
     EntityCache,
 };
-pub use rate_limiter::{RateLimitResult, RateLimitScope, RateLimiter};
+pub use keys::{
+    init_key_prefix, key_space, queue_key, KeyNamespace, KeySpace, RedisKey, APP_PREFIX,
+};
+pub use rate_limiter::{
+    RateLimitAlgorithm, RateLimitAlgorithms, RateLimitResult, RateLimitScope, RateLimiter,
+};
 
 use crate::error::{Error, Result};
 use redis::{aio::ConnectionManager, Client};
@@ -24,9 +35,20 @@ pub async fn create_client(url: &str) -> Result<ConnectionManager> {
     let client =
         Client::open(url).map_err(|e| Error::config(format!("Invalid Redis URL: {}", e)))?;
 
-    ConnectionManager::new(client)
+    ConnectionManager::new(client).await.map_err(Error::from)
+}
+
+/// Check Redis connection health with `PING`
+///
+/// # Errors
+///
+/// Returns an error if the command fails
+pub async fn check_health(conn: &mut ConnectionManager) -> Result<()> {
+    redis::cmd("PING")
+        .query_async::<String>(conn)
         .await
-        .map_err(|e| Error::internal(format!("Failed to connect to Redis: {}", e)))
+        .map(|_| ())
+        .map_err(Error::from)
 }
 
 #[cfg(test)]
@@ -11,7 +11,7 @@
 -- 5. If rejected: Return error with retry_after
 --
 -- Arguments:
---   KEYS[1]: Base key prefix (e.g., "rl:org:org_123")
+--   KEYS[1]: Base key prefix (e.g., "agentauri:rl:org:org_123")
 --   ARGV[1]: Limit (max requests per window)
 --   ARGV[2]: Window size in seconds (default: 3600)
 --   ARGV[3]: Cost multiplier (1, 2, 5, or 10 for tiers 0-3)
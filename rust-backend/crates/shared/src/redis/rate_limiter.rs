@@ -11,6 +11,20 @@
 //! - **Cost Multipliers**: Different query tiers consume different amounts (1x-10x)
 //! - **Graceful Degradation**: Falls back to in-memory rate limiting when Redis unavailable
 //!
+//! # Algorithms
+//!
+//! The algorithm is chosen per scope kind (IP, Organization, Agent):
+//!
+//! - **Sliding window** (default): the limit applies to the total cost of the
+//!   last `window` seconds. Quota used in a burst only comes back as the
+//!   buckets age out of the window.
+//! - **Token bucket**: the bucket holds `limit` tokens and refills at
+//!   `limit / window` tokens per second. Clients can burst up to the full
+//!   limit and then continue at the sustained rate, without waiting out the
+//!   whole window.
+//!
+//! Both run as Lua scripts so check-and-consume is atomic in Redis.
+//!
 //! # Fallback Mechanism
 //!
 //! When Redis is unavailable, the rate limiter automatically falls back to an
@@ -43,15 +57,25 @@
 //! }
 //! ```
 //!
+//! # Testing
+//!
+//! The limiter reads the time from a [`Clock`] (the system clock unless
+//! replaced with [`RateLimiter::with_clock`]). Tests pass a
+//! [`ManualClock`](crate::ManualClock) to step across window boundaries
+//! exactly. The Lua scripts need a real Redis: see [`super::testing`].
+//!
 //! Rust guideline compliant 2025-01-28
 
+use super::keys::RedisKey;
 use crate::error::{Error, Result};
 use dashmap::DashMap;
 use redis::{aio::ConnectionManager, AsyncCommands, Script};
 use std::sync::Arc;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+use crate::clock::{Clock, SystemClock};
+
 /// Rate limit scope (determines Redis key prefix)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RateLimitScope {
@@ -61,15 +85,29 @@ pub enum RateLimitScope {
     Organization(String),
     /// Agent-based rate limiting (Layer 2 - Agent operations)
     Agent(i64),
+    /// Organization rate limiting for a single query tier
+    ///
+    /// Used when an organization has a tier-specific override, so the override
+    /// is counted separately from the organization's plan-wide usage.
+    OrganizationTier { organization_id: String, tier: u8 },
+    /// Every caller together (the API gateway's emergency kill-switch cap)
+    Global,
 }
 
 impl RateLimitScope {
     /// Get the Redis key prefix for this scope
-    pub fn key_prefix(&self) -> String {
+    pub fn key_prefix(&self) -> RedisKey {
         match self {
-            RateLimitScope::Ip(ip) => format!("rl:ip:{}", ip),
-            RateLimitScope::Organization(org_id) => format!("rl:org:{}", org_id),
-            RateLimitScope::Agent(agent_id) => format!("rl:agent:{}", agent_id),
+            RateLimitScope::Ip(ip) => RedisKey::rate_limit("ip").part(ip),
+            RateLimitScope::Organization(org_id) => RedisKey::rate_limit("org").part(org_id),
+            RateLimitScope::Agent(agent_id) => RedisKey::rate_limit("agent").part(agent_id),
+            RateLimitScope::OrganizationTier {
+                organization_id,
+                tier,
+            } => RedisKey::rate_limit("org")
+                .part(organization_id)
+                .part(format!("t{}", tier)),
+            RateLimitScope::Global => RedisKey::rate_limit("global"),
         }
     }
 
@@ -79,6 +117,106 @@ impl RateLimitScope {
             RateLimitScope::Ip(ip) => format!("IP {}", ip),
             RateLimitScope::Organization(org_id) => format!("Organization {}", org_id),
             RateLimitScope::Agent(agent_id) => format!("Agent {}", agent_id),
+            RateLimitScope::OrganizationTier {
+                organization_id,
+                tier,
+            } => format!("Organization {} (tier {})", organization_id, tier),
+            RateLimitScope::Global => "all callers".to_string(),
+        }
+    }
+}
+
+/// Rate limiting algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitAlgorithm {
+    /// Sum of usage over a sliding window of 1-minute buckets
+    #[default]
+    SlidingWindow,
+    /// Continuously refilling token bucket (capacity = limit)
+    TokenBucket,
+}
+
+impl RateLimitAlgorithm {
+    /// Get the algorithm name as a string
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RateLimitAlgorithm::SlidingWindow => "sliding_window",
+            RateLimitAlgorithm::TokenBucket => "token_bucket",
+        }
+    }
+
+    /// Parse an algorithm name (`sliding_window` or `token_bucket`)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "sliding_window" => Some(RateLimitAlgorithm::SlidingWindow),
+            "token_bucket" => Some(RateLimitAlgorithm::TokenBucket),
+            _ => None,
+        }
+    }
+}
+
+/// Algorithm selection per scope kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateLimitAlgorithms {
+    /// Algorithm for [`RateLimitScope::Ip`]
+    pub ip: RateLimitAlgorithm,
+    /// Algorithm for [`RateLimitScope::Organization`] and [`RateLimitScope::OrganizationTier`]
+    pub organization: RateLimitAlgorithm,
+    /// Algorithm for [`RateLimitScope::Agent`]
+    pub agent: RateLimitAlgorithm,
+}
+
+impl RateLimitAlgorithms {
+    /// Use the same algorithm for every scope
+    pub fn uniform(algorithm: RateLimitAlgorithm) -> Self {
+        Self {
+            ip: algorithm,
+            organization: algorithm,
+            agent: algorithm,
+        }
+    }
+
+    /// Load from environment variables
+    ///
+    /// `RATE_LIMIT_ALGORITHM` sets the default for all scopes, and
+    /// `RATE_LIMIT_ALGORITHM_IP`, `RATE_LIMIT_ALGORITHM_ORG` and
+    /// `RATE_LIMIT_ALGORITHM_AGENT` override it per scope. Unknown values are
+    /// logged and ignored.
+    pub fn from_env() -> Self {
+        fn read(var: &str) -> Option<RateLimitAlgorithm> {
+            let value = std::env::var(var).ok()?;
+            let algorithm = RateLimitAlgorithm::parse(&value);
+            if algorithm.is_none() {
+                warn!(
+                    var = var,
+                    value = %value,
+                    "Unknown rate limit algorithm, expected sliding_window or token_bucket"
+                );
+            }
+            algorithm
+        }
+
+        let default = read("RATE_LIMIT_ALGORITHM").unwrap_or_default();
+        Self {
+            ip: read("RATE_LIMIT_ALGORITHM_IP").unwrap_or(default),
+            organization: read("RATE_LIMIT_ALGORITHM_ORG").unwrap_or(default),
+            agent: read("RATE_LIMIT_ALGORITHM_AGENT").unwrap_or(default),
+        }
+    }
+
+    /// Get the algorithm for a scope
+    ///
+    /// [`RateLimitScope::Global`] always uses the token bucket, so an
+    /// emergency cap admits traffic steadily instead of in bursts at the
+    /// start of each window.
+    pub fn for_scope(&self, scope: &RateLimitScope) -> RateLimitAlgorithm {
+        match scope {
+            RateLimitScope::Ip(_) => self.ip,
+            RateLimitScope::Organization(_) | RateLimitScope::OrganizationTier { .. } => {
+                self.organization
+            }
+            RateLimitScope::Agent(_) => self.agent,
+            RateLimitScope::Global => RateLimitAlgorithm::TokenBucket,
         }
     }
 }
@@ -96,8 +234,13 @@ pub struct RateLimitResult {
     pub reset_at: i64,
     /// Seconds until the rate limit resets (convenience field)
     pub retry_after: i64,
-    /// Remaining quota (limit - current_usage)
+    /// Remaining quota (limit - current_usage; whole tokens for token bucket)
     pub remaining: i64,
+    /// Seconds until the full quota is available again
+    ///
+    /// For the token bucket this is the time until the bucket is full; for the
+    /// sliding window it is the time until the window resets.
+    pub refill_after: i64,
 }
 
 impl RateLimitResult {
@@ -117,16 +260,31 @@ impl RateLimitResult {
             reset_at,
             retry_after,
             remaining,
+            refill_after: retry_after,
         }
     }
 
-    /// Create a "fail-open" result (allows request when Redis is down)
-    fn fail_open(limit: i64) -> Self {
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
+    /// Create a result from the token bucket Lua script response
+    fn from_token_bucket_response(response: Vec<i64>, current_time_ms: i64) -> Self {
+        let allowed = response[0] == 1;
+        let remaining = response[1].max(0);
+        let limit = response[2];
+        let retry_after = ms_to_secs_ceil(response[3]);
+        let refill_after = ms_to_secs_ceil(response[4]);
 
+        Self {
+            allowed,
+            current_usage: limit - remaining,
+            limit,
+            reset_at: current_time_ms / 1000 + refill_after,
+            retry_after,
+            remaining,
+            refill_after,
+        }
+    }
+
+    /// Create a "fail-open" result (allows request when Redis is down)
+    fn fail_open(limit: i64, current_time: i64) -> Self {
         Self {
             allowed: true,
             current_usage: 0,
@@ -134,17 +292,23 @@ impl RateLimitResult {
             reset_at: current_time + 3600,
             retry_after: 0,
             remaining: limit,
+            refill_after: 0,
         }
     }
 }
 
+/// Round milliseconds up to whole seconds
+fn ms_to_secs_ceil(ms: i64) -> i64 {
+    (ms.max(0) + 999) / 1000
+}
+
 /// Entry in the fallback rate limiter
 #[derive(Debug, Clone)]
 struct FallbackEntry {
     /// Current request count in the window
     count: i64,
-    /// When this window started
-    window_start: Instant,
+    /// When this window started (Unix epoch in milliseconds)
+    window_start_ms: i64,
 }
 
 /// Redis-based rate limiter with in-memory fallback
@@ -155,8 +319,12 @@ struct FallbackEntry {
 pub struct RateLimiter {
     /// Redis connection manager
     redis: ConnectionManager,
-    /// Lua script for rate limiting
+    /// Lua script for rate limiting (sliding window)
     script: Script,
+    /// Lua script for token bucket rate limiting
+    token_bucket_script: Script,
+    /// Algorithm used for each scope kind
+    algorithms: RateLimitAlgorithms,
     /// Window size in seconds (default: 3600 = 1 hour)
     window_seconds: i64,
     /// Whether to fail open (allow requests) when Redis is unavailable
@@ -168,6 +336,8 @@ pub struct RateLimiter {
     fallback_limit: i64,
     /// Fallback window duration
     fallback_window: Duration,
+    /// Source of the current time
+    clock: Arc<dyn Clock>,
 }
 
 impl RateLimiter {
@@ -183,6 +353,9 @@ impl RateLimiter {
     /// Lua script source (embedded at compile time)
     const LUA_SCRIPT: &'static str = include_str!("rate_limit.lua");
 
+    /// Token bucket Lua script source (embedded at compile time)
+    const TOKEN_BUCKET_SCRIPT: &'static str = include_str!("token_bucket.lua");
+
     /// Create a new rate limiter
     ///
     /// # Arguments
@@ -219,14 +392,37 @@ impl RateLimiter {
         Ok(Self {
             redis,
             script,
+            token_bucket_script: Script::new(Self::TOKEN_BUCKET_SCRIPT),
+            algorithms: RateLimitAlgorithms::default(),
             window_seconds,
             fail_open,
             fallback_limiter: Arc::new(DashMap::new()),
             fallback_limit: Self::DEFAULT_FALLBACK_LIMIT,
             fallback_window: Self::DEFAULT_FALLBACK_WINDOW,
+            clock: Arc::new(SystemClock),
         })
     }
 
+    /// Read the time from `clock` instead of the system clock
+    ///
+    /// For tests: windows, resets and the fallback limiter all follow the
+    /// given clock. Redis key expiry still runs on the server's own clock.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Set the algorithm used for each scope kind
+    pub fn with_algorithms(mut self, algorithms: RateLimitAlgorithms) -> Self {
+        self.algorithms = algorithms;
+        self
+    }
+
+    /// Get the algorithm applied to a scope
+    pub fn algorithm_for(&self, scope: &RateLimitScope) -> RateLimitAlgorithm {
+        self.algorithms.for_scope(scope)
+    }
+
     /// Check rate limit using the in-memory fallback limiter
     ///
     /// This is used when Redis is unavailable. It provides a simpler,
@@ -241,8 +437,8 @@ impl RateLimiter {
     ///
     /// `RateLimitResult` with fallback limits applied
     fn check_fallback(&self, scope: &RateLimitScope, cost: i64) -> RateLimitResult {
-        let key = scope.key_prefix();
-        let now = Instant::now();
+        let key = String::from(scope.key_prefix());
+        let now_ms = self.clock.now_ms();
         let limit = self.fallback_limit;
 
         let mut entry = self
@@ -250,14 +446,14 @@ impl RateLimiter {
             .entry(key.clone())
             .or_insert_with(|| FallbackEntry {
                 count: 0,
-                window_start: now,
+                window_start_ms: now_ms,
             });
 
         // Check if window has expired
-        if now.duration_since(entry.window_start) >= self.fallback_window {
+        if now_ms - entry.window_start_ms >= self.fallback_window.as_millis() as i64 {
             // Reset window
             entry.count = 0;
-            entry.window_start = now;
+            entry.window_start_ms = now_ms;
         }
 
         // Check if we're over limit
@@ -268,12 +464,7 @@ impl RateLimiter {
             entry.count = new_count;
         }
 
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0);
-
-        let reset_at = current_time + self.fallback_window.as_secs() as i64;
+        let reset_at = now_ms.div_euclid(1000) + self.fallback_window.as_secs() as i64;
         let retry_after = if allowed {
             0
         } else {
@@ -288,6 +479,7 @@ impl RateLimiter {
             reset_at,
             retry_after,
             remaining,
+            refill_after: self.fallback_window.as_secs() as i64,
         }
     }
 
@@ -296,11 +488,11 @@ impl RateLimiter {
     /// Should be called periodically to prevent memory growth.
     /// Entries older than 2x the fallback window are removed.
     pub fn cleanup_fallback(&self) {
-        let now = Instant::now();
-        let expiry = self.fallback_window * 2;
+        let now_ms = self.clock.now_ms();
+        let expiry_ms = (self.fallback_window * 2).as_millis() as i64;
 
         self.fallback_limiter
-            .retain(|_, entry| now.duration_since(entry.window_start) < expiry);
+            .retain(|_, entry| now_ms - entry.window_start_ms < expiry_ms);
 
         debug!(
             entries_remaining = self.fallback_limiter.len(),
@@ -337,35 +529,62 @@ impl RateLimiter {
         limit: i64,
         cost: i64,
     ) -> Result<RateLimitResult> {
-        let key_prefix = scope.key_prefix();
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| Error::internal(format!("System time error: {}", e)))?
-            .as_secs() as i64;
+        self.check_with_window(scope, limit, self.window_seconds, cost)
+            .await
+    }
+
+    /// Check rate limit with a window other than the limiter's default
+    ///
+    /// Used for per-organization overrides that configure their own window.
+    /// For the sliding window the window must be a multiple of 60 seconds (the
+    /// bucket granularity); for the token bucket it is the time to refill an
+    /// empty bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The rate limit scope
+    /// * `limit` - Maximum requests allowed in the window
+    /// * `window_seconds` - Window size in seconds
+    /// * `cost` - Cost of this request (1-10 based on query tier)
+    pub async fn check_with_window(
+        &self,
+        scope: RateLimitScope,
+        limit: i64,
+        window_seconds: i64,
+        cost: i64,
+    ) -> Result<RateLimitResult> {
+        let now_ms = self.clock.now_ms();
+
+        self.check_at(scope, limit, window_seconds, cost, now_ms)
+            .await
+    }
+
+    /// Check rate limit as of the given time (Unix epoch in milliseconds)
+    async fn check_at(
+        &self,
+        scope: RateLimitScope,
+        limit: i64,
+        window_seconds: i64,
+        cost: i64,
+        now_ms: i64,
+    ) -> Result<RateLimitResult> {
+        let algorithm = self.algorithm_for(&scope);
 
         debug!(
             scope = %scope.description(),
+            algorithm = algorithm.as_str(),
             limit = limit,
+            window_seconds = window_seconds,
             cost = cost,
             "Checking rate limit"
         );
 
-        // Execute Lua script
-        let mut conn = self.redis.clone();
         let result = self
-            .script
-            .key(&key_prefix)
-            .arg(limit)
-            .arg(self.window_seconds)
-            .arg(cost)
-            .arg(current_time)
-            .invoke_async::<Vec<i64>>(&mut conn)
+            .invoke(&scope, algorithm, limit, window_seconds, cost, now_ms)
             .await;
 
         match result {
-            Ok(response) => {
-                let result = RateLimitResult::from_lua_response(response, current_time);
-
+            Ok(result) => {
                 if result.allowed {
                     debug!(
                         scope = %scope.description(),
@@ -425,6 +644,49 @@ impl RateLimiter {
         }
     }
 
+    /// Run the Lua script for the given algorithm
+    async fn invoke(
+        &self,
+        scope: &RateLimitScope,
+        algorithm: RateLimitAlgorithm,
+        limit: i64,
+        window_seconds: i64,
+        cost: i64,
+        now_ms: i64,
+    ) -> redis::RedisResult<RateLimitResult> {
+        let mut conn = self.redis.clone();
+
+        match algorithm {
+            RateLimitAlgorithm::SlidingWindow => {
+                let current_time = now_ms / 1000;
+                let response = self
+                    .script
+                    .key(scope.key_prefix())
+                    .arg(limit)
+                    .arg(window_seconds)
+                    .arg(cost)
+                    .arg(current_time)
+                    .invoke_async::<Vec<i64>>(&mut conn)
+                    .await?;
+                Ok(RateLimitResult::from_lua_response(response, current_time))
+            }
+            RateLimitAlgorithm::TokenBucket => {
+                let response = self
+                    .token_bucket_script
+                    .key(scope.key_prefix().part("tb"))
+                    .arg(limit)
+                    .arg(window_seconds)
+                    .arg(cost)
+                    .arg(now_ms)
+                    .invoke_async::<Vec<i64>>(&mut conn)
+                    .await?;
+                Ok(RateLimitResult::from_token_bucket_response(
+                    response, now_ms,
+                ))
+            }
+        }
+    }
+
     /// Get current usage without incrementing
     ///
     /// This is useful for displaying current rate limit status without consuming quota.
@@ -442,12 +704,14 @@ impl RateLimiter {
         scope: RateLimitScope,
         limit: i64,
     ) -> Result<RateLimitResult> {
+        // A zero-cost token bucket check only applies the refill, so it is a read
+        if self.algorithm_for(&scope) == RateLimitAlgorithm::TokenBucket {
+            return self.check(scope, limit, 0).await;
+        }
+
         // Check with cost = 0 (won't increment, just reads)
         let key_prefix = scope.key_prefix();
-        let current_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| Error::internal(format!("System time error: {}", e)))?
-            .as_secs() as i64;
+        let current_time = self.clock.now_secs();
 
         // Calculate minute boundaries (same as Lua script)
         let minute_seconds = 60;
@@ -460,7 +724,7 @@ impl RateLimiter {
 
         for i in 0..buckets_per_hour {
             let bucket_time = current_minute - (i * minute_seconds);
-            let bucket_key = format!("{}:{}", key_prefix, bucket_time);
+            let bucket_key = key_prefix.clone().part(bucket_time);
 
             match redis.get::<_, Option<i64>>(&bucket_key).await {
                 Ok(Some(count)) => total_usage += count,
@@ -468,7 +732,7 @@ impl RateLimiter {
                 Err(e) => {
                     error!(error = %e, "Failed to read bucket during usage check");
                     if self.fail_open {
-                        return Ok(RateLimitResult::fail_open(limit));
+                        return Ok(RateLimitResult::fail_open(limit, current_time));
                     } else {
                         return Err(Error::internal(format!(
                             "Failed to get current usage: {}",
@@ -490,6 +754,7 @@ impl RateLimiter {
             reset_at,
             retry_after,
             remaining,
+            refill_after: retry_after,
         })
     }
 
@@ -502,7 +767,7 @@ impl RateLimiter {
     /// * `scope` - The rate limit scope to reset
     #[cfg(test)]
     pub async fn reset(&self, scope: RateLimitScope) -> Result<()> {
-        let key_pattern = format!("{}:*", scope.key_prefix());
+        let key_pattern = scope.key_prefix().part("*");
         let mut redis = self.redis.clone();
 
         // In production, you'd use SCAN for safety, but for tests this is fine
@@ -527,18 +792,40 @@ impl RateLimiter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
+    use crate::redis::testing::TestRedis;
 
     #[test]
     fn test_rate_limit_scope_key_prefix() {
         assert_eq!(
-            RateLimitScope::Ip("192.168.1.1".to_string()).key_prefix(),
-            "rl:ip:192.168.1.1"
+            RateLimitScope::Ip("192.168.1.1".to_string())
+                .key_prefix()
+                .as_str(),
+            "agentauri:rl:ip:192.168.1.1"
+        );
+        assert_eq!(
+            RateLimitScope::Organization("org_123".to_string())
+                .key_prefix()
+                .as_str(),
+            "agentauri:rl:org:org_123"
+        );
+        assert_eq!(
+            RateLimitScope::Agent(42).key_prefix().as_str(),
+            "agentauri:rl:agent:42"
+        );
+        assert_eq!(
+            RateLimitScope::OrganizationTier {
+                organization_id: "org_123".to_string(),
+                tier: 2,
+            }
+            .key_prefix()
+            .as_str(),
+            "agentauri:rl:org:org_123:t2"
         );
         assert_eq!(
-            RateLimitScope::Organization("org_123".to_string()).key_prefix(),
-            "rl:org:org_123"
+            RateLimitScope::Global.key_prefix().as_str(),
+            "agentauri:rl:global"
         );
-        assert_eq!(RateLimitScope::Agent(42).key_prefix(), "rl:agent:42");
     }
 
     #[test]
@@ -561,9 +848,90 @@ mod tests {
         assert_eq!(result.remaining, 0); // Clamped to 0
     }
 
+    #[test]
+    fn test_token_bucket_result() {
+        // Allowed with 7 of 10 tokens left, 18s until full
+        let result = RateLimitResult::from_token_bucket_response(
+            vec![1, 7, 10, 0, 18_000],
+            1_732_800_600_000,
+        );
+
+        assert!(result.allowed);
+        assert_eq!(result.remaining, 7);
+        assert_eq!(result.current_usage, 3);
+        assert_eq!(result.retry_after, 0);
+        assert_eq!(result.refill_after, 18);
+        assert_eq!(result.reset_at, 1_732_800_618);
+    }
+
+    #[test]
+    fn test_token_bucket_result_rejected_rounds_up() {
+        let result = RateLimitResult::from_token_bucket_response(
+            vec![0, 0, 10, 4_500, 60_000],
+            1_732_800_600_000,
+        );
+
+        assert!(!result.allowed);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(result.retry_after, 5);
+        assert_eq!(result.refill_after, 60);
+    }
+
+    #[test]
+    fn test_algorithm_parse() {
+        assert_eq!(
+            RateLimitAlgorithm::parse("token_bucket"),
+            Some(RateLimitAlgorithm::TokenBucket)
+        );
+        assert_eq!(
+            RateLimitAlgorithm::parse(" Sliding_Window "),
+            Some(RateLimitAlgorithm::SlidingWindow)
+        );
+        assert_eq!(RateLimitAlgorithm::parse("leaky_bucket"), None);
+        assert_eq!(
+            RateLimitAlgorithm::default(),
+            RateLimitAlgorithm::SlidingWindow
+        );
+    }
+
+    #[test]
+    fn test_algorithms_for_scope() {
+        let algorithms = RateLimitAlgorithms {
+            ip: RateLimitAlgorithm::SlidingWindow,
+            organization: RateLimitAlgorithm::TokenBucket,
+            agent: RateLimitAlgorithm::SlidingWindow,
+        };
+
+        assert_eq!(
+            algorithms.for_scope(&RateLimitScope::Ip("1.2.3.4".to_string())),
+            RateLimitAlgorithm::SlidingWindow
+        );
+        assert_eq!(
+            algorithms.for_scope(&RateLimitScope::Organization("org".to_string())),
+            RateLimitAlgorithm::TokenBucket
+        );
+        assert_eq!(
+            algorithms.for_scope(&RateLimitScope::OrganizationTier {
+                organization_id: "org".to_string(),
+                tier: 1,
+            }),
+            RateLimitAlgorithm::TokenBucket
+        );
+        assert_eq!(
+            RateLimitAlgorithms::uniform(RateLimitAlgorithm::TokenBucket)
+                .for_scope(&RateLimitScope::Agent(1)),
+            RateLimitAlgorithm::TokenBucket
+        );
+        assert_eq!(
+            RateLimitAlgorithms::uniform(RateLimitAlgorithm::SlidingWindow)
+                .for_scope(&RateLimitScope::Global),
+            RateLimitAlgorithm::TokenBucket
+        );
+    }
+
     #[test]
     fn test_fail_open_result() {
-        let result = RateLimitResult::fail_open(100);
+        let result = RateLimitResult::fail_open(100, 1_700_000_000);
 
         assert!(result.allowed);
         assert_eq!(result.current_usage, 0);
@@ -573,13 +941,13 @@ mod tests {
     #[test]
     fn test_fallback_entry_creation() {
         let fallback_limiter: DashMap<String, FallbackEntry> = DashMap::new();
-        let now = Instant::now();
+        let now_ms = SystemClock.now_ms();
 
         fallback_limiter.insert(
             "test_key".to_string(),
             FallbackEntry {
                 count: 5,
-                window_start: now,
+                window_start_ms: now_ms,
             },
         );
 
@@ -595,8 +963,8 @@ mod tests {
         let _fallback_window = Duration::from_secs(60);
 
         let scope = RateLimitScope::Ip("192.168.1.1".to_string());
-        let key = scope.key_prefix();
-        let now = Instant::now();
+        let key = String::from(scope.key_prefix());
+        let now_ms = SystemClock.now_ms();
         let cost: i64 = 1;
 
         // First request should be allowed
@@ -604,7 +972,7 @@ mod tests {
             .entry(key.clone())
             .or_insert_with(|| FallbackEntry {
                 count: 0,
-                window_start: now,
+                window_start_ms: now_ms,
             });
 
         let new_count = entry.count + cost;
@@ -636,23 +1004,23 @@ mod tests {
     fn test_fallback_limiter_window_reset() {
         let fallback_limiter: Arc<DashMap<String, FallbackEntry>> = Arc::new(DashMap::new());
         let key = "test_key".to_string();
-        let old_start = Instant::now() - Duration::from_secs(120); // 2 minutes ago
+        let old_start_ms = SystemClock.now_ms() - 120_000; // 2 minutes ago
 
         // Insert an old entry
         fallback_limiter.insert(
             key.clone(),
             FallbackEntry {
                 count: 100, // Over limit
-                window_start: old_start,
+                window_start_ms: old_start_ms,
             },
         );
 
         // Check that we can detect window expiry
-        let now = Instant::now();
+        let now_ms = SystemClock.now_ms();
         let fallback_window = Duration::from_secs(60);
 
         let entry = fallback_limiter.get(&key).unwrap();
-        let window_expired = now.duration_since(entry.window_start) >= fallback_window;
+        let window_expired = now_ms - entry.window_start_ms >= fallback_window.as_millis() as i64;
 
         assert!(window_expired, "Window should be expired");
     }
@@ -661,15 +1029,15 @@ mod tests {
     fn test_fallback_cleanup() {
         let fallback_limiter: Arc<DashMap<String, FallbackEntry>> = Arc::new(DashMap::new());
         let fallback_window = Duration::from_secs(60);
-        let expiry = fallback_window * 2;
+        let expiry_ms = (fallback_window * 2).as_millis() as i64;
 
         // Insert a fresh entry
-        let now = Instant::now();
+        let now_ms = SystemClock.now_ms();
         fallback_limiter.insert(
             "fresh".to_string(),
             FallbackEntry {
                 count: 5,
-                window_start: now,
+                window_start_ms: now_ms,
             },
         );
 
@@ -678,16 +1046,238 @@ mod tests {
             "old".to_string(),
             FallbackEntry {
                 count: 10,
-                window_start: now - Duration::from_secs(300), // 5 minutes ago
+                window_start_ms: now_ms - 300_000, // 5 minutes ago
             },
         );
 
         // Cleanup
-        fallback_limiter.retain(|_, entry| now.duration_since(entry.window_start) < expiry);
+        fallback_limiter.retain(|_, entry| now_ms - entry.window_start_ms < expiry_ms);
 
         // Fresh entry should remain
         assert!(fallback_limiter.contains_key("fresh"));
         // Old entry should be removed
         assert!(!fallback_limiter.contains_key("old"));
     }
+
+    /// Limiter on a test Redis (keep the [`TestRedis`] alive while using it)
+    async fn test_limiter(
+        algorithm: RateLimitAlgorithm,
+        window_seconds: i64,
+    ) -> (TestRedis, RateLimiter) {
+        let redis = TestRedis::start().await;
+        // Fail closed so a Redis problem can't pass as the in-memory fallback
+        let limiter = RateLimiter::with_config(redis.connect().await, window_seconds, false)
+            .await
+            .unwrap()
+            .with_algorithms(RateLimitAlgorithms::uniform(algorithm));
+        (redis, limiter)
+    }
+
+    /// Start of a minute, so sliding window buckets line up with the test's steps
+    const MINUTE_START_MS: i64 = 1_700_000_040_000;
+
+    fn unique_scope(name: &str) -> RateLimitScope {
+        RateLimitScope::Ip(format!("{}-{}", name, uuid::Uuid::new_v4()))
+    }
+
+    /// Burst 10 requests against a 10/minute limit, then try again 6s later
+    async fn burst_then_wait(limiter: &RateLimiter) -> (usize, RateLimitResult) {
+        let scope = RateLimitScope::Ip(format!("burst-{}", uuid::Uuid::new_v4()));
+        // Start of a minute so the sliding window can't roll over mid-test
+        let start_ms = (SystemClock.now_secs() / 60 * 60) * 1000;
+
+        let mut allowed = 0;
+        for _ in 0..12 {
+            let result = limiter
+                .check_at(scope.clone(), 10, 60, 1, start_ms)
+                .await
+                .unwrap();
+            if result.allowed {
+                allowed += 1;
+            }
+        }
+
+        let later = limiter
+            .check_at(scope.clone(), 10, 60, 1, start_ms + 6_000)
+            .await
+            .unwrap();
+
+        limiter.reset(scope).await.unwrap();
+        (allowed, later)
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_burst_sliding_window_waits_for_window() {
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::SlidingWindow, 60).await;
+        let (allowed, later) = burst_then_wait(&limiter).await;
+
+        // Full burst allowed, then nothing until the window moves on
+        assert_eq!(allowed, 10);
+        assert!(!later.allowed);
+        assert_eq!(later.remaining, 0);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_burst_token_bucket_refills_gradually() {
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::TokenBucket, 60).await;
+        let (allowed, later) = burst_then_wait(&limiter).await;
+
+        // Same burst capacity as the sliding window...
+        assert_eq!(allowed, 10);
+        // ...but one token (60s / 10) has refilled after 6 seconds
+        assert!(later.allowed);
+        assert_eq!(later.remaining, 0);
+        assert_eq!(later.refill_after, 60);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_token_bucket_reports_time_to_next_token() {
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::TokenBucket, 60).await;
+        let scope = RateLimitScope::Ip(format!("refill-{}", uuid::Uuid::new_v4()));
+        let now_ms = 1_700_000_000_000;
+
+        let first = limiter
+            .check_at(scope.clone(), 2, 60, 2, now_ms)
+            .await
+            .unwrap();
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 0);
+        assert_eq!(first.refill_after, 60);
+
+        // One token every 30s: half a token after 15s
+        let rejected = limiter
+            .check_at(scope.clone(), 2, 60, 1, now_ms + 15_000)
+            .await
+            .unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after, 15);
+
+        limiter.reset(scope).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_sliding_window_rollover() {
+        let clock = ManualClock::new(MINUTE_START_MS);
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::SlidingWindow, 120).await;
+        let limiter = limiter.with_clock(clock.clone());
+        let scope = unique_scope("rollover");
+
+        // Two requests in the first minute, one in the second: limit reached
+        for _ in 0..2 {
+            assert!(limiter.check(scope.clone(), 3, 1).await.unwrap().allowed);
+        }
+        clock.advance(Duration::from_secs(60));
+        assert!(limiter.check(scope.clone(), 3, 1).await.unwrap().allowed);
+        assert!(!limiter.check(scope.clone(), 3, 1).await.unwrap().allowed);
+
+        // Last second before the first minute leaves the window
+        clock.advance(Duration::from_secs(59));
+        assert!(!limiter.check(scope.clone(), 3, 1).await.unwrap().allowed);
+
+        // Its two requests age out together; the second minute's one remains
+        clock.advance(Duration::from_secs(1));
+        let result = limiter.check(scope.clone(), 3, 1).await.unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.current_usage, 2);
+
+        limiter.reset(scope).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_sliding_window_remaining_and_reset() {
+        // 25 seconds into a minute
+        let clock = ManualClock::new(MINUTE_START_MS + 25_000);
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::SlidingWindow, 3600).await;
+        let limiter = limiter.with_clock(clock.clone());
+        let scope = unique_scope("remaining");
+        let minute_start = MINUTE_START_MS / 1000;
+
+        let result = limiter.check(scope.clone(), 10, 2).await.unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.current_usage, 2);
+        assert_eq!(result.remaining, 8);
+        // Allowed: the current bucket leaves the window one window after it started
+        assert_eq!(result.reset_at, minute_start + 3600);
+        assert_eq!(result.retry_after, 3600 - 25);
+
+        // A cost that does not fit is rejected without being counted
+        let rejected = limiter.check(scope.clone(), 10, 9).await.unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.current_usage, 2);
+        assert_eq!(rejected.remaining, 8);
+        // Rejected: retry when the oldest bucket in the window expires
+        assert_eq!(rejected.reset_at, minute_start + 60);
+        assert_eq!(rejected.retry_after, 35);
+
+        // Reading usage does not consume quota
+        let usage = limiter.get_current_usage(scope.clone(), 10).await.unwrap();
+        assert_eq!(usage.current_usage, 2);
+        assert_eq!(usage.remaining, 8);
+        assert_eq!(usage.reset_at, minute_start + 3600);
+
+        limiter.reset(scope).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_token_bucket_boundary_burst() {
+        let clock = ManualClock::new(MINUTE_START_MS);
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::TokenBucket, 60).await;
+        let limiter = limiter.with_clock(clock.clone());
+        let scope = unique_scope("boundary");
+
+        // Exactly the capacity is allowed, in one burst
+        for remaining in (0..6).rev() {
+            let result = limiter.check(scope.clone(), 6, 1).await.unwrap();
+            assert!(result.allowed);
+            assert_eq!(result.remaining, remaining);
+        }
+        let rejected = limiter.check(scope.clone(), 6, 1).await.unwrap();
+        assert!(!rejected.allowed);
+        // One token every 10s
+        assert_eq!(rejected.retry_after, 10);
+        assert_eq!(rejected.refill_after, 60);
+
+        clock.advance(Duration::from_secs(10));
+        assert!(limiter.check(scope.clone(), 6, 1).await.unwrap().allowed);
+        assert!(!limiter.check(scope.clone(), 6, 1).await.unwrap().allowed);
+
+        limiter.reset(scope).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+    async fn test_fallback_window_follows_clock() {
+        let clock = ManualClock::new(MINUTE_START_MS);
+        let (_redis, limiter) = test_limiter(RateLimitAlgorithm::SlidingWindow, 60).await;
+        let limiter = limiter.with_clock(clock.clone());
+        let scope = unique_scope("fallback");
+
+        for _ in 0..RateLimiter::DEFAULT_FALLBACK_LIMIT {
+            assert!(limiter.check_fallback(&scope, 1).allowed);
+        }
+        let rejected = limiter.check_fallback(&scope, 1);
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after, 60);
+
+        // One millisecond short of the window: still limited
+        clock.advance(Duration::from_millis(59_999));
+        assert!(!limiter.check_fallback(&scope, 1).allowed);
+
+        clock.advance(Duration::from_millis(1));
+        let result = limiter.check_fallback(&scope, 1);
+        assert!(result.allowed);
+        assert_eq!(result.current_usage, 1);
+        assert_eq!(result.reset_at, MINUTE_START_MS / 1000 + 120);
+
+        // Entries are cleaned up two windows after they started
+        clock.advance(Duration::from_secs(120));
+        limiter.cleanup_fallback();
+        assert!(limiter.fallback_limiter.is_empty());
+    }
 }
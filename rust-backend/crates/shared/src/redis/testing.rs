@@ -0,0 +1,249 @@
+//! Redis for tests
+//!
+//! Code that runs Lua scripts (e.g. the [`RateLimiter`](super::RateLimiter))
+//! cannot be faked in memory, so its tests talk to a real server.
+//! [`TestRedis`] picks one:
+//!
+//! 1. `TEST_REDIS_URL`, if set
+//! 2. With the `test-redis` feature, a throwaway container (needs Docker),
+//!    removed when the [`TestRedis`] is dropped
+//! 3. Otherwise `redis://localhost:6379`
+//!
+//! Tests that need Redis are ignored unless the feature is enabled:
+//!
+//! ```ignore
+//! #[tokio::test]
+//! #[cfg_attr(not(feature = "test-redis"), ignore)] // Requires Redis
+//! async fn test_with_redis() {
+//!     let redis = TestRedis::start().await;
+//!     let conn = redis.connect().await;
+//!     // ...
+//! }
+//! ```
+//!
+//! ```bash
+//! cargo test -p shared --features test-redis
+//! # or, against a running server:
+//! TEST_REDIS_URL=redis://localhost:6379 cargo test -p shared -- --ignored
+//! ```
+//!
+//! Combine with a [`ManualClock`](crate::ManualClock) to test time windows
+//! without sleeping.
+//!
+//! To test what happens when Redis is down, [`FailingRedis`] accepts
+//! connections but answers every command with an error. It runs in-process
+//! and needs no server.
+
+use redis::aio::ConnectionManager;
+#[cfg(feature = "test-redis")]
+use testcontainers_modules::{
+    redis::{Redis, REDIS_PORT},
+    testcontainers::{runners::AsyncRunner, ContainerAsync, ImageExt},
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Same image as docker-compose.yml
+#[cfg(feature = "test-redis")]
+const REDIS_TAG: &str = "7.4-alpine";
+
+/// A Redis server for one test
+pub struct TestRedis {
+    url: String,
+    #[cfg(feature = "test-redis")]
+    _container: Option<ContainerAsync<Redis>>,
+}
+
+impl TestRedis {
+    /// Find or start a Redis server
+    ///
+    /// # Panics
+    ///
+    /// If the container cannot be started (e.g. Docker is not running)
+    pub async fn start() -> Self {
+        if let Ok(url) = std::env::var("TEST_REDIS_URL") {
+            return Self::at(url);
+        }
+
+        #[cfg(feature = "test-redis")]
+        {
+            let container = Redis::default()
+                .with_tag(REDIS_TAG)
+                .start()
+                .await
+                .expect("Failed to start Redis container (is Docker running?)");
+            let host = container.get_host().await.expect("Redis container host");
+            let port = container
+                .get_host_port_ipv4(REDIS_PORT)
+                .await
+                .expect("Redis container port");
+
+            Self {
+                url: format!("redis://{}:{}", host, port),
+                _container: Some(container),
+            }
+        }
+
+        #[cfg(not(feature = "test-redis"))]
+        Self::at("redis://localhost:6379".to_string())
+    }
+
+    fn at(url: String) -> Self {
+        Self {
+            url,
+            #[cfg(feature = "test-redis")]
+            _container: None,
+        }
+    }
+
+    /// Connection URL of the server
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Open a connection to the server
+    ///
+    /// # Panics
+    ///
+    /// If the server is unreachable
+    pub async fn connect(&self) -> ConnectionManager {
+        super::create_client(&self.url)
+            .await
+            .expect("Redis must be running for this test")
+    }
+}
+
+/// A Redis server that fails every command
+///
+/// Connections succeed, so a [`ConnectionManager`] can be created, but each
+/// command gets `-ERR` back. The server stops when this is dropped.
+pub struct FailingRedis {
+    url: String,
+    server: JoinHandle<()>,
+}
+
+impl FailingRedis {
+    /// Start the server on a free local port
+    ///
+    /// # Panics
+    ///
+    /// If no local port can be bound
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind a local port");
+        let url = format!("redis://{}", listener.local_addr().expect("Local address"));
+
+        let server = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(fail_commands(socket));
+            }
+        });
+
+        Self { url, server }
+    }
+
+    /// Connection URL of the server
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Open a connection to the server
+    pub async fn connect(&self) -> ConnectionManager {
+        super::create_client(&self.url)
+            .await
+            .expect("Failed to connect to the failing Redis server")
+    }
+}
+
+impl Drop for FailingRedis {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Answer each command on the connection with an error
+async fn fail_commands(mut socket: TcpStream) {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+
+        while let Some(len) = command_len(&buf) {
+            buf.drain(..len);
+            if socket
+                .write_all(b"-ERR unavailable (FailingRedis)\r\n")
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// Length of the first complete command in `buf`, if there is one
+///
+/// Clients send commands as RESP arrays of bulk strings
+/// (`*<n>\r\n` then `$<len>\r\n<bytes>\r\n` per argument).
+fn command_len(buf: &[u8]) -> Option<usize> {
+    fn line(buf: &[u8], at: usize, prefix: u8) -> Option<(usize, usize)> {
+        if *buf.get(at)? != prefix {
+            return None;
+        }
+        let end = at + buf[at..].windows(2).position(|w| w == b"\r\n")?;
+        let value = std::str::from_utf8(&buf[at + 1..end]).ok()?.parse().ok()?;
+        Some((value, end + 2))
+    }
+
+    let (args, mut at) = line(buf, 0, b'*')?;
+    for _ in 0..args {
+        let (len, start) = line(buf, at, b'$')?;
+        at = start + len + 2;
+        if buf.len() < at {
+            return None;
+        }
+    }
+    Some(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_len() {
+        let ping = b"*1\r\n$4\r\nPING\r\n";
+        assert_eq!(command_len(ping), Some(ping.len()));
+
+        // Incomplete commands wait for more data
+        assert_eq!(command_len(&ping[..ping.len() - 1]), None);
+        assert_eq!(command_len(b"*2\r\n$3\r\nGET\r\n"), None);
+
+        let two = [&ping[..], &ping[..]].concat();
+        assert_eq!(command_len(&two), Some(ping.len()));
+    }
+
+    #[tokio::test]
+    async fn test_failing_redis_fails_commands() {
+        let redis = FailingRedis::start().await;
+        let mut conn = redis.connect().await;
+
+        let result: redis::RedisResult<Option<String>> =
+            redis::cmd("GET").arg("key").query_async(&mut conn).await;
+        assert!(result.is_err());
+
+        // The connection stays usable, and keeps failing
+        let result: redis::RedisResult<()> = redis::cmd("SET")
+            .arg("key")
+            .arg("value")
+            .query_async(&mut conn)
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,64 @@
+-- Redis Rate Limiter - Token Bucket
+--
+-- This Lua script implements a token bucket rate limiter. The bucket holds up to
+-- `capacity` tokens and refills continuously at `capacity / window` tokens per
+-- second, so clients can burst up to the full capacity and then proceed at the
+-- sustained rate. Refill and consume happen atomically in a single script.
+--
+-- Algorithm:
+-- 1. Load the stored token count and last update time (full bucket if missing)
+-- 2. Add the tokens earned since the last update (capped at capacity)
+-- 3. If tokens >= cost: consume cost tokens and allow
+-- 4. Otherwise: reject and report how long until enough tokens are available
+-- 5. Store the new token count and timestamp with a TTL of one window
+--
+-- Arguments:
+--   KEYS[1]: Bucket key (e.g., "agentauri:rl:org:org_123:tb")
+--   ARGV[1]: Capacity (max tokens, same as the limit)
+--   ARGV[2]: Window in seconds (time to refill an empty bucket)
+--   ARGV[3]: Cost (tokens consumed by this request; 0 to only read)
+--   ARGV[4]: Current timestamp (Unix epoch in milliseconds)
+--
+-- Returns:
+--   [allowed, remaining, capacity, retry_after_ms, refill_ms]
+--   - allowed: 1 if request allowed, 0 if rejected
+--   - remaining: Whole tokens left after this request
+--   - capacity: The configured capacity
+--   - retry_after_ms: Milliseconds until `cost` tokens are available (0 if allowed)
+--   - refill_ms: Milliseconds until the bucket is full again
+
+local key = KEYS[1]
+local capacity = tonumber(ARGV[1])
+local window_ms = tonumber(ARGV[2]) * 1000
+local cost = tonumber(ARGV[3])
+local now = tonumber(ARGV[4])
+
+local stored = redis.call('HMGET', key, 'tokens', 'ts')
+local tokens = tonumber(stored[1])
+local last = tonumber(stored[2])
+
+if tokens == nil or last == nil then
+    tokens = capacity
+    last = now
+end
+
+-- Refill for the time elapsed (clock skew between callers never removes tokens)
+local elapsed = math.max(0, now - last)
+tokens = math.min(capacity, tokens + elapsed * capacity / window_ms)
+
+local allowed = 0
+local retry_after_ms = 0
+
+if tokens >= cost then
+    allowed = 1
+    tokens = tokens - cost
+else
+    retry_after_ms = math.ceil((cost - tokens) * window_ms / capacity)
+end
+
+redis.call('HSET', key, 'tokens', tostring(tokens), 'ts', tostring(math.max(now, last)))
+redis.call('PEXPIRE', key, window_ms)
+
+local refill_ms = math.ceil((capacity - tokens) * window_ms / capacity)
+
+return {allowed, math.floor(tokens), capacity, retry_after_ms, refill_ms}
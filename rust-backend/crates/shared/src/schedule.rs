@@ -0,0 +1,264 @@
+//! Trigger schedules
+//!
+//! A schedule limits when a trigger may fire to a daily time window on chosen
+//! weekdays, in an IANA time zone:
+//!
+//! ```json
+//! { "days": ["mon", "tue", "wed", "thu", "fri"], "start": "09:00", "end": "17:00", "timezone": "Europe/Rome" }
+//! ```
+//!
+//! - `days`: three-letter weekday names; omitted or empty means every day
+//! - `start`, `end`: local `HH:MM` times. The window includes `start` and
+//!   excludes `end`. An `end` before `start` makes an overnight window, which
+//!   belongs to the day it starts on (`fri` 22:00-06:00 ends Saturday morning).
+//! - `timezone`: IANA time zone name, so the window follows daylight saving time
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use shared::TriggerSchedule;
+//!
+//! let schedule = TriggerSchedule {
+//!     days: vec!["mon".into(), "tue".into(), "wed".into(), "thu".into(), "fri".into()],
+//!     start: "09:00".into(),
+//!     end: "17:00".into(),
+//!     timezone: "Europe/Rome".into(),
+//! };
+//!
+//! // Thursday 11:00 in Rome
+//! let at = Utc.with_ymd_and_hms(2026, 1, 15, 10, 0, 0).unwrap();
+//! assert!(schedule.contains(at).unwrap());
+//! ```
+
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors in a schedule definition
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ScheduleError {
+    #[error("Invalid day '{0}' (expected mon, tue, wed, thu, fri, sat or sun)")]
+    InvalidDay(String),
+
+    #[error("Invalid time '{0}' (expected HH:MM)")]
+    InvalidTime(String),
+
+    #[error("Schedule start and end must differ")]
+    EmptyWindow,
+
+    #[error("Unknown time zone '{0}' (expected an IANA name such as Europe/Rome)")]
+    InvalidTimezone(String),
+}
+
+/// Weekly time window in which a trigger may fire
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriggerSchedule {
+    /// Days of the window (`mon`..`sun`); empty means every day
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// Local start time (`HH:MM`, inclusive)
+    pub start: String,
+    /// Local end time (`HH:MM`, exclusive)
+    pub end: String,
+    /// IANA time zone of `start` and `end`
+    pub timezone: String,
+}
+
+/// A parsed [`TriggerSchedule`]
+struct Window {
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+    timezone: Tz,
+}
+
+impl Window {
+    fn on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+impl TriggerSchedule {
+    /// Check that the days, times and time zone parse
+    pub fn validate(&self) -> Result<(), ScheduleError> {
+        self.parse().map(|_| ())
+    }
+
+    /// Whether the window contains the instant `at`
+    pub fn contains(&self, at: DateTime<Utc>) -> Result<bool, ScheduleError> {
+        let window = self.parse()?;
+        let local = at.with_timezone(&window.timezone);
+        let (time, day) = (local.time(), local.weekday());
+
+        let active = if window.start < window.end {
+            window.on(day) && time >= window.start && time < window.end
+        } else {
+            // Overnight: the evening belongs to today, the early hours to yesterday
+            (window.on(day) && time >= window.start) || (window.on(day.pred()) && time < window.end)
+        };
+
+        Ok(active)
+    }
+
+    fn parse(&self) -> Result<Window, ScheduleError> {
+        let days = self
+            .days
+            .iter()
+            .map(|day| parse_day(day).ok_or_else(|| ScheduleError::InvalidDay(day.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let start = parse_time(&self.start)?;
+        let end = parse_time(&self.end)?;
+        if start == end {
+            return Err(ScheduleError::EmptyWindow);
+        }
+
+        let timezone = self
+            .timezone
+            .parse::<Tz>()
+            .map_err(|_| ScheduleError::InvalidTimezone(self.timezone.clone()))?;
+
+        Ok(Window {
+            days,
+            start,
+            end,
+            timezone,
+        })
+    }
+}
+
+fn parse_day(day: &str) -> Option<Weekday> {
+    match day {
+        "mon" => Some(Weekday::Mon),
+        "tue" => Some(Weekday::Tue),
+        "wed" => Some(Weekday::Wed),
+        "thu" => Some(Weekday::Thu),
+        "fri" => Some(Weekday::Fri),
+        "sat" => Some(Weekday::Sat),
+        "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, ScheduleError> {
+    if time.len() != 5 {
+        return Err(ScheduleError::InvalidTime(time.to_string()));
+    }
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| ScheduleError::InvalidTime(time.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(days: &[&str], start: &str, end: &str, timezone: &str) -> TriggerSchedule {
+        TriggerSchedule {
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: timezone.to_string(),
+        }
+    }
+
+    fn utc(day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        // January 2026: the 15th is a Thursday
+        Utc.with_ymd_and_hms(2026, 1, day, hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_business_hours() {
+        let business = schedule(
+            &["mon", "tue", "wed", "thu", "fri"],
+            "09:00",
+            "17:00",
+            "Europe/Rome",
+        );
+
+        // Rome is UTC+1 in January
+        assert!(!business.contains(utc(15, 7, 59)).unwrap());
+        assert!(business.contains(utc(15, 8, 0)).unwrap());
+        assert!(business.contains(utc(15, 15, 59)).unwrap());
+        assert!(!business.contains(utc(15, 16, 0)).unwrap());
+        // Saturday
+        assert!(!business.contains(utc(17, 10, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_window_follows_daylight_saving_time() {
+        let morning = schedule(&[], "09:00", "10:00", "Europe/Rome");
+
+        // 08:30 UTC is 09:30 in winter (UTC+1) and 10:30 in summer (UTC+2)
+        assert!(morning.contains(utc(15, 8, 30)).unwrap());
+        let summer = Utc.with_ymd_and_hms(2026, 7, 15, 8, 30, 0).unwrap();
+        assert!(!morning.contains(summer).unwrap());
+    }
+
+    #[test]
+    fn test_overnight_window_belongs_to_start_day() {
+        let friday_night = schedule(&["fri"], "22:00", "06:00", "UTC");
+
+        // Friday 16th, 23:00 and Saturday 17th, 05:00
+        assert!(friday_night.contains(utc(16, 23, 0)).unwrap());
+        assert!(friday_night.contains(utc(17, 5, 0)).unwrap());
+        assert!(!friday_night.contains(utc(17, 6, 0)).unwrap());
+        // Thursday night and Friday morning are outside
+        assert!(!friday_night.contains(utc(15, 23, 0)).unwrap());
+        assert!(!friday_night.contains(utc(16, 5, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_empty_days_means_every_day() {
+        let daily = schedule(&[], "00:00", "12:00", "UTC");
+
+        for day in 12..19 {
+            assert!(daily.contains(utc(day, 11, 0)).unwrap());
+            assert!(!daily.contains(utc(day, 12, 0)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_definitions() {
+        assert_eq!(
+            schedule(&["monday"], "09:00", "17:00", "UTC").validate(),
+            Err(ScheduleError::InvalidDay("monday".to_string()))
+        );
+        assert_eq!(
+            schedule(&[], "9:00", "17:00", "UTC").validate(),
+            Err(ScheduleError::InvalidTime("9:00".to_string()))
+        );
+        assert_eq!(
+            schedule(&[], "09:00", "24:00", "UTC").validate(),
+            Err(ScheduleError::InvalidTime("24:00".to_string()))
+        );
+        assert_eq!(
+            schedule(&[], "09:00", "09:00", "UTC").validate(),
+            Err(ScheduleError::EmptyWindow)
+        );
+        assert_eq!(
+            schedule(&[], "09:00", "17:00", "Mars/Olympus").validate(),
+            Err(ScheduleError::InvalidTimezone("Mars/Olympus".to_string()))
+        );
+        assert!(
+            schedule(&["sat", "sun"], "22:00", "02:00", "America/New_York")
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_without_days() {
+        let schedule: TriggerSchedule = serde_json::from_value(serde_json::json!({
+            "start": "09:00",
+            "end": "17:00",
+            "timezone": "UTC"
+        }))
+        .unwrap();
+
+        assert!(schedule.days.is_empty());
+        assert!(schedule.validate().is_ok());
+    }
+}
@@ -310,15 +310,15 @@ impl SecretsManager {
         {
             let stripe_info: serde_json::Value =
                 serde_json::from_str(&stripe_json).unwrap_or_default();
-            (
-                stripe_info["secret_key"].as_str().unwrap_or("").to_string(),
-                stripe_info["webhook_secret"]
+            let field = |name: &str| {
+                stripe_info[name]
                     .as_str()
-                    .unwrap_or("")
-                    .to_string(),
-            )
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            (field("secret_key"), field("webhook_secret"))
         } else {
-            (String::new(), String::new())
+            (None, None)
         };
 
         // RPC URLs - use the fetched URL directly
@@ -359,6 +359,19 @@ impl SecretsManager {
         }
     }
 
+    /// Confirm AWS Secrets Manager is reachable and the JWT secret readable
+    ///
+    /// Bypasses the cache so every call makes one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret cannot be read.
+    pub async fn check_reachable(&self) -> Result<(), SecretsError> {
+        let jwt_secret_name = format!("{}/jwt-secret", self.prefix);
+        self.invalidate_secret(&jwt_secret_name).await;
+        self.get_secret(&jwt_secret_name).await.map(|_| ())
+    }
+
     /// Invalidate cache for a specific secret
     ///
     /// Forces the next get_secret() call to fetch from AWS.
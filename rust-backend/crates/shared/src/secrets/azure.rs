@@ -0,0 +1,676 @@
+//! Azure Key Vault integration for secure credential management.
+//!
+//! This module provides integration with Azure Key Vault for production
+//! secret storage on Azure:
+//!
+//! - Encryption at rest with Microsoft- or customer-managed keys (HSM-backed on Premium)
+//! - Secret versioning and soft delete
+//! - Audit logging via Azure Monitor diagnostic settings
+//! - Azure RBAC or vault access policies
+//! - In-memory caching with configurable TTL
+//!
+//! The Key Vault REST API is called directly over HTTPS, so no Azure SDK
+//! (and no cargo feature) is needed.
+//!
+//! # Authentication
+//!
+//! - **Service principal**: used when `AZURE_CLIENT_SECRET` is set, together
+//!   with `AZURE_TENANT_ID` and `AZURE_CLIENT_ID`
+//! - **Managed identity**: used otherwise. `AZURE_CLIENT_ID` selects a
+//!   user-assigned identity; without it the system-assigned identity is used.
+//!   App Service and Container Apps expose `IDENTITY_ENDPOINT` and
+//!   `IDENTITY_HEADER`; elsewhere (VMs, AKS) the instance metadata service is used.
+//!
+//! The identity needs the `Key Vault Secrets User` role (RBAC) or a `get`
+//! secret permission (access policy).
+//!
+//! # Configuration
+//!
+//! Environment variables:
+//! - `AZURE_KEY_VAULT_URL`: Vault URL (e.g., https://agentauri-prod.vault.azure.net)
+//! - `AZURE_TENANT_ID`: Tenant ID (service principal only)
+//! - `AZURE_CLIENT_ID`: Service principal or user-assigned identity client ID
+//! - `AZURE_CLIENT_SECRET`: Service principal secret (optional)
+//!
+//! # Secret Naming Convention
+//!
+//! Key Vault secret names may only contain letters, digits and dashes, so the
+//! `agentauri/` names used by the other backends are mapped by replacing `/`
+//! and `_` with `-`:
+//! - `agentauri/database_url` → `agentauri-database-url`
+//! - `agentauri/redis_url` → `agentauri-redis-url`
+//! - `agentauri/jwt_secret` → `agentauri-jwt-secret`
+//! - etc.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use shared::secrets::azure::SecretsManager;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let manager = SecretsManager::new().await?;
+//!     let secrets = manager.get_app_secrets().await?;
+//!     println!("Database URL: {}", secrets.database_url);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::secrets::types::{AppSecrets, SecretsError};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Key Vault data-plane API version
+const KEY_VAULT_API_VERSION: &str = "7.4";
+
+/// OAuth resource / scope for Key Vault tokens
+const KEY_VAULT_RESOURCE: &str = "https://vault.azure.net";
+
+/// Instance metadata service token endpoint (VMs, AKS)
+const IMDS_TOKEN_URL: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+
+/// Refresh access tokens this long before they expire
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Token lifetime assumed when the token endpoint omits `expires_in`
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// HTTP timeout for token and secret requests
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Cached secret entry with expiration
+#[derive(Debug, Clone)]
+struct CachedSecret {
+    value: String,
+    expires_at: Instant,
+}
+
+impl CachedSecret {
+    fn new(value: String, ttl: Duration) -> Self {
+        Self {
+            value,
+            expires_at: Instant::now() + ttl,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+}
+
+/// How the manager obtains Key Vault access tokens
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Credential {
+    /// Client credentials flow against Microsoft Entra ID
+    ServicePrincipal {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+    /// Managed identity of the host
+    ManagedIdentity {
+        /// Client ID of a user-assigned identity
+        client_id: Option<String>,
+        /// `IDENTITY_ENDPOINT` and `IDENTITY_HEADER` (App Service, Container Apps)
+        identity_endpoint: Option<(String, String)>,
+    },
+}
+
+impl Credential {
+    /// Read the credential from the `AZURE_*` and `IDENTITY_*` variables
+    fn from_env() -> Result<Self, SecretsError> {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let identity_endpoint = var("IDENTITY_ENDPOINT").zip(var("IDENTITY_HEADER"));
+
+        Self::from_parts(
+            var("AZURE_TENANT_ID"),
+            var("AZURE_CLIENT_ID"),
+            var("AZURE_CLIENT_SECRET"),
+            identity_endpoint,
+        )
+    }
+
+    fn from_parts(
+        tenant_id: Option<String>,
+        client_id: Option<String>,
+        client_secret: Option<String>,
+        identity_endpoint: Option<(String, String)>,
+    ) -> Result<Self, SecretsError> {
+        match (client_secret, tenant_id, client_id) {
+            (Some(client_secret), Some(tenant_id), Some(client_id)) => Ok(Self::ServicePrincipal {
+                tenant_id,
+                client_id,
+                client_secret,
+            }),
+            (Some(_), _, _) => Err(SecretsError::Config(
+                "AZURE_CLIENT_SECRET requires AZURE_TENANT_ID and AZURE_CLIENT_ID".to_string(),
+            )),
+            (None, _, client_id) => Ok(Self::ManagedIdentity {
+                client_id,
+                identity_endpoint,
+            }),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::ServicePrincipal { .. } => "service principal",
+            Self::ManagedIdentity { .. } => "managed identity",
+        }
+    }
+}
+
+/// OAuth token response
+///
+/// Entra ID returns `expires_in` as a number while the managed identity
+/// endpoints return it as a string, so both are accepted.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default, deserialize_with = "deserialize_seconds")]
+    expires_in: Option<u64>,
+}
+
+fn deserialize_seconds<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Seconds {
+        Number(u64),
+        Text(String),
+    }
+
+    Ok(match Option::<Seconds>::deserialize(deserializer)? {
+        Some(Seconds::Number(secs)) => Some(secs),
+        Some(Seconds::Text(text)) => text.parse().ok(),
+        None => None,
+    })
+}
+
+/// Key Vault `GET /secrets/{name}` response (only the field we use)
+#[derive(Debug, Deserialize)]
+struct SecretBundle {
+    value: String,
+}
+
+/// Access token with its refresh deadline
+#[derive(Debug, Clone)]
+struct CachedToken {
+    token: String,
+    refresh_at: Instant,
+}
+
+/// Map an application secret name to its Key Vault name
+///
+/// `database_url` → `agentauri-database-url`
+pub fn key_vault_secret_name(secret_name: &str) -> String {
+    format!("agentauri/{}", secret_name).replace(['/', '_'], "-")
+}
+
+/// Azure Key Vault secrets manager
+pub struct SecretsManager {
+    http: reqwest::Client,
+    vault_url: String,
+    credential: Credential,
+    token: RwLock<Option<CachedToken>>,
+    cache: Arc<RwLock<HashMap<String, CachedSecret>>>,
+    cache_ttl: Duration,
+}
+
+impl SecretsManager {
+    /// Create new Azure Key Vault secrets manager client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - AZURE_KEY_VAULT_URL is not set or is not an https URL
+    /// - A service principal is only partially configured
+    pub async fn new() -> Result<Self, SecretsError> {
+        Self::with_cache_ttl(Duration::from_secs(3600)).await
+    }
+
+    /// Create new Azure Key Vault secrets manager with custom cache TTL
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - Cache time-to-live duration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Key Vault configuration is invalid.
+    pub async fn with_cache_ttl(ttl: Duration) -> Result<Self, SecretsError> {
+        let vault_url = env::var("AZURE_KEY_VAULT_URL")
+            .map_err(|_| SecretsError::Config("AZURE_KEY_VAULT_URL must be set".to_string()))?;
+        let vault_url = vault_url.trim_end_matches('/').to_string();
+        if !vault_url.starts_with("https://") {
+            return Err(SecretsError::Config(format!(
+                "AZURE_KEY_VAULT_URL must be an https URL, got '{}'",
+                vault_url
+            )));
+        }
+
+        let credential = Credential::from_env()?;
+
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| SecretsError::Azure(format!("Failed to build HTTP client: {}", e)))?;
+
+        tracing::debug!(
+            vault_url = %vault_url,
+            credential = credential.kind(),
+            "Azure Key Vault client configured"
+        );
+
+        Ok(Self {
+            http,
+            vault_url,
+            credential,
+            token: RwLock::new(None),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_ttl: ttl,
+        })
+    }
+
+    /// Get secret value from Key Vault with caching
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - Name of the secret (e.g., "database_url")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Secret does not exist (`SecretsError::NotFound`)
+    /// - Token acquisition or the Key Vault call fails
+    /// - The identity lacks read access to the secret
+    pub async fn get_secret(&self, secret_name: &str) -> Result<String, SecretsError> {
+        // Check cache first
+        {
+            let cache = self.cache.read().await;
+            if let Some(cached) = cache.get(secret_name) {
+                if !cached.is_expired() {
+                    tracing::debug!("Cache hit for secret: {}", secret_name);
+                    return Ok(cached.value.clone());
+                }
+            }
+        }
+
+        // Fetch from Key Vault
+        let vault_name = key_vault_secret_name(secret_name);
+        tracing::debug!("Fetching secret from Azure Key Vault: {}", vault_name);
+
+        let token = self.access_token().await?;
+        let response = self
+            .http
+            .get(format!("{}/secrets/{}", self.vault_url, vault_name))
+            .query(&[("api-version", KEY_VAULT_API_VERSION)])
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| {
+                SecretsError::Azure(format!("Failed to read secret {}: {}", vault_name, e))
+            })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(SecretsError::NotFound(vault_name));
+        }
+        if !status.is_success() {
+            if status == reqwest::StatusCode::UNAUTHORIZED {
+                // Force a fresh token on the next call
+                *self.token.write().await = None;
+            }
+            return Err(SecretsError::Azure(format!(
+                "Failed to read secret {}: HTTP {}",
+                vault_name, status
+            )));
+        }
+
+        let secret_value = response
+            .json::<SecretBundle>()
+            .await
+            .map_err(|e| {
+                SecretsError::Azure(format!("Invalid response for secret {}: {}", vault_name, e))
+            })?
+            .value;
+
+        // Update cache
+        {
+            let mut cache = self.cache.write().await;
+            cache.insert(
+                secret_name.to_string(),
+                CachedSecret::new(secret_value.clone(), self.cache_ttl),
+            );
+        }
+
+        Ok(secret_value)
+    }
+
+    /// Get all application secrets
+    ///
+    /// This method fetches all required secrets for the application from
+    /// Azure Key Vault using the `agentauri-` naming convention.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any required secret is missing or inaccessible.
+    pub async fn get_app_secrets(&self) -> Result<AppSecrets, SecretsError> {
+        // Acquire the token once up front so the parallel reads share it
+        self.access_token().await?;
+
+        // Fetch all secrets in parallel for performance
+        let (
+            database_url,
+            redis_url,
+            jwt_secret,
+            stripe_secret_key,
+            stripe_webhook_secret,
+            ethereum_sepolia_rpc_url,
+            base_sepolia_rpc_url,
+            linea_sepolia_rpc_url,
+            api_encryption_key,
+            telegram_bot_token,
+        ) = tokio::try_join!(
+            self.get_secret("database_url"),
+            self.get_secret("redis_url"),
+            self.get_secret("jwt_secret"),
+            self.get_secret_optional("stripe_secret_key"),
+            self.get_secret_optional("stripe_webhook_secret"),
+            self.get_secret("ethereum_sepolia_rpc_url"),
+            self.get_secret("base_sepolia_rpc_url"),
+            self.get_secret_optional("linea_sepolia_rpc_url"),
+            self.get_secret("api_encryption_key"),
+            self.get_secret_optional("telegram_bot_token"),
+        )?;
+
+        let secrets = AppSecrets {
+            database_url,
+            redis_url,
+            jwt_secret,
+            stripe_secret_key,
+            stripe_webhook_secret,
+            ethereum_sepolia_rpc_url,
+            base_sepolia_rpc_url,
+            linea_sepolia_rpc_url,
+            api_encryption_key,
+            telegram_bot_token,
+        };
+
+        // Validate all secrets
+        secrets.validate()?;
+
+        Ok(secrets)
+    }
+
+    /// Get optional secret (returns None if not found instead of error)
+    async fn get_secret_optional(&self, secret_name: &str) -> Result<Option<String>, SecretsError> {
+        match self.get_secret(secret_name).await {
+            Ok(value) => Ok(Some(value)),
+            Err(SecretsError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Return a valid access token, fetching a new one when needed
+    async fn access_token(&self) -> Result<String, SecretsError> {
+        {
+            let token = self.token.read().await;
+            if let Some(cached) = token.as_ref() {
+                if Instant::now() < cached.refresh_at {
+                    return Ok(cached.token.clone());
+                }
+            }
+        }
+
+        let mut token = self.token.write().await;
+        // Another task may have refreshed while we waited for the lock
+        if let Some(cached) = token.as_ref() {
+            if Instant::now() < cached.refresh_at {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let response = self.request_token().await?;
+        let lifetime = response
+            .expires_in
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+        let cached = CachedToken {
+            token: response.access_token,
+            refresh_at: Instant::now() + lifetime.saturating_sub(TOKEN_REFRESH_MARGIN),
+        };
+        let access_token = cached.token.clone();
+        *token = Some(cached);
+
+        tracing::debug!(
+            credential = self.credential.kind(),
+            lifetime_secs = lifetime.as_secs(),
+            "Acquired Azure Key Vault access token"
+        );
+        Ok(access_token)
+    }
+
+    async fn request_token(&self) -> Result<TokenResponse, SecretsError> {
+        let request = match &self.credential {
+            Credential::ServicePrincipal {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => {
+                let scope = format!("{}/.default", KEY_VAULT_RESOURCE);
+                self.http
+                    .post(format!(
+                        "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                        tenant_id
+                    ))
+                    .form(&[
+                        ("grant_type", "client_credentials"),
+                        ("client_id", client_id.as_str()),
+                        ("client_secret", client_secret.as_str()),
+                        ("scope", scope.as_str()),
+                    ])
+            }
+            Credential::ManagedIdentity {
+                client_id,
+                identity_endpoint: Some((endpoint, header)),
+            } => {
+                let mut query = vec![
+                    ("api-version", "2019-08-01"),
+                    ("resource", KEY_VAULT_RESOURCE),
+                ];
+                if let Some(client_id) = client_id {
+                    query.push(("client_id", client_id.as_str()));
+                }
+                self.http
+                    .get(endpoint)
+                    .query(&query)
+                    .header("X-IDENTITY-HEADER", header)
+            }
+            Credential::ManagedIdentity {
+                client_id,
+                identity_endpoint: None,
+            } => {
+                let mut query = vec![
+                    ("api-version", "2018-02-01"),
+                    ("resource", KEY_VAULT_RESOURCE),
+                ];
+                if let Some(client_id) = client_id {
+                    query.push(("client_id", client_id.as_str()));
+                }
+                self.http
+                    .get(IMDS_TOKEN_URL)
+                    .query(&query)
+                    .header("Metadata", "true")
+            }
+        };
+
+        let kind = self.credential.kind();
+        let response = request.send().await.map_err(|e| {
+            SecretsError::Azure(format!(
+                "Failed to acquire access token via {}: {}",
+                kind,
+                e.without_url()
+            ))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SecretsError::Azure(format!(
+                "Failed to acquire access token via {}: HTTP {}",
+                kind, status
+            )));
+        }
+
+        response.json::<TokenResponse>().await.map_err(|e| {
+            SecretsError::Azure(format!("Invalid token response from {}: {}", kind, e))
+        })
+    }
+
+    /// Confirm Key Vault is reachable and the JWT secret readable
+    ///
+    /// Bypasses the cache so every call makes one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token cannot be acquired or the secret read.
+    pub async fn check_reachable(&self) -> Result<(), SecretsError> {
+        self.invalidate_secret("jwt_secret").await;
+        self.get_secret("jwt_secret").await.map(|_| ())
+    }
+
+    /// Invalidate cache for a specific secret
+    ///
+    /// Forces the next get_secret() call to fetch from Key Vault.
+    pub async fn invalidate_secret(&self, secret_name: &str) {
+        let mut cache = self.cache.write().await;
+        cache.remove(secret_name);
+        tracing::debug!("Invalidated cache for secret: {}", secret_name);
+    }
+
+    /// Invalidate entire cache
+    ///
+    /// Forces all subsequent get_secret() calls to fetch from Key Vault.
+    pub async fn invalidate_cache(&self) {
+        let mut cache = self.cache.write().await;
+        cache.clear();
+        tracing::debug!("Invalidated all cached secrets");
+    }
+
+    /// Get cache statistics
+    pub async fn cache_stats(&self) -> CacheStats {
+        let cache = self.cache.read().await;
+        let total = cache.len();
+        let expired = cache.values().filter(|v| v.is_expired()).count();
+
+        CacheStats {
+            total_entries: total,
+            expired_entries: expired,
+            valid_entries: total - expired,
+        }
+    }
+}
+
+/// Cache statistics
+#[derive(Debug, Clone)]
+pub struct CacheStats {
+    pub total_entries: usize,
+    pub expired_entries: usize,
+    pub valid_entries: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn some(value: &str) -> Option<String> {
+        Some(value.to_string())
+    }
+
+    #[test]
+    fn test_key_vault_secret_name() {
+        assert_eq!(
+            key_vault_secret_name("database_url"),
+            "agentauri-database-url"
+        );
+        assert_eq!(
+            key_vault_secret_name("ethereum_sepolia_rpc_url"),
+            "agentauri-ethereum-sepolia-rpc-url"
+        );
+    }
+
+    #[test]
+    fn test_credential_selection() {
+        let credential =
+            Credential::from_parts(some("tenant"), some("client"), some("secret"), None).unwrap();
+        assert_eq!(
+            credential,
+            Credential::ServicePrincipal {
+                tenant_id: "tenant".to_string(),
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+            }
+        );
+
+        // No client secret: system-assigned managed identity
+        let credential = Credential::from_parts(None, None, None, None).unwrap();
+        assert_eq!(
+            credential,
+            Credential::ManagedIdentity {
+                client_id: None,
+                identity_endpoint: None,
+            }
+        );
+
+        // Client ID alone selects a user-assigned identity
+        let endpoint = Some((
+            "http://localhost:42356/msi/token".to_string(),
+            "h".to_string(),
+        ));
+        let credential =
+            Credential::from_parts(some("tenant"), some("client"), None, endpoint.clone()).unwrap();
+        assert_eq!(
+            credential,
+            Credential::ManagedIdentity {
+                client_id: some("client"),
+                identity_endpoint: endpoint,
+            }
+        );
+    }
+
+    #[test]
+    fn test_partial_service_principal_rejected() {
+        let result = Credential::from_parts(None, some("client"), some("secret"), None);
+        assert!(matches!(result, Err(SecretsError::Config(_))));
+    }
+
+    #[test]
+    fn test_token_response_expiry_formats() {
+        let entra: TokenResponse =
+            serde_json::from_str(r#"{"access_token":"a","expires_in":3599}"#).unwrap();
+        assert_eq!(entra.expires_in, Some(3599));
+
+        let managed_identity: TokenResponse =
+            serde_json::from_str(r#"{"access_token":"a","expires_in":"86399"}"#).unwrap();
+        assert_eq!(managed_identity.expires_in, Some(86399));
+
+        let missing: TokenResponse = serde_json::from_str(r#"{"access_token":"a"}"#).unwrap();
+        assert_eq!(missing.expires_in, None);
+    }
+
+    #[test]
+    fn test_azure_config_missing() {
+        env::remove_var("AZURE_KEY_VAULT_URL");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(SecretsManager::new());
+
+        assert!(matches!(result, Err(SecretsError::Config(_))));
+    }
+}
@@ -27,22 +27,18 @@ use std::env;
 ///
 /// # Errors
 ///
-/// Returns an error if any required secret is missing.
+/// Returns [`SecretsError::Validation`] listing every required secret that is
+/// missing or malformed.
 pub async fn load_from_env() -> Result<AppSecrets, SecretsError> {
     // Load .env file if present (ignore errors if file doesn't exist)
     dotenvy::dotenv().ok();
 
     let secrets = AppSecrets {
-        // Tier 1: Critical secrets
-        database_url: env::var("DATABASE_URL").map_err(|_| {
-            SecretsError::Env(env::VarError::NotPresent)
-                .with_hint("DATABASE_URL must be set in environment or .env file")
-        })?,
+        // Tier 1: Critical secrets (left empty when unset so validation
+        // reports every missing one together)
+        database_url: env::var("DATABASE_URL").unwrap_or_default(),
 
-        redis_url: env::var("REDIS_URL").map_err(|_| {
-            SecretsError::Env(env::VarError::NotPresent)
-                .with_hint("REDIS_URL must be set in environment or .env file")
-        })?,
+        redis_url: env::var("REDIS_URL").unwrap_or_default(),
 
         jwt_secret: env::var("JWT_SECRET").unwrap_or_else(|_| {
             tracing::warn!(
@@ -51,15 +47,9 @@ pub async fn load_from_env() -> Result<AppSecrets, SecretsError> {
             "dev_secret_change_in_production_32chars".to_string()
         }),
 
-        stripe_secret_key: env::var("STRIPE_SECRET_KEY").unwrap_or_else(|_| {
-            tracing::warn!("STRIPE_SECRET_KEY not set - using test mode default");
-            "sk_test_development_mode_only".to_string()
-        }),
-
-        stripe_webhook_secret: env::var("STRIPE_WEBHOOK_SECRET").unwrap_or_else(|_| {
-            tracing::warn!("STRIPE_WEBHOOK_SECRET not set - using test mode default");
-            "whsec_development_mode_only".to_string()
-        }),
+        // Optional: payments are disabled when either Stripe secret is missing
+        stripe_secret_key: optional_var("STRIPE_SECRET_KEY"),
+        stripe_webhook_secret: optional_var("STRIPE_WEBHOOK_SECRET"),
 
         // Tier 2: Important secrets
         ethereum_sepolia_rpc_url: env::var("ETHEREUM_SEPOLIA_RPC_URL").unwrap_or_else(|_| {
@@ -83,7 +73,7 @@ pub async fn load_from_env() -> Result<AppSecrets, SecretsError> {
             base64::engine::general_purpose::STANDARD.encode(key)
         }),
 
-        telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").ok(),
+        telegram_bot_token: optional_var("TELEGRAM_BOT_TOKEN"),
     };
 
     // Validate secrets
@@ -92,15 +82,9 @@ pub async fn load_from_env() -> Result<AppSecrets, SecretsError> {
     Ok(secrets)
 }
 
-/// Error trait extension for adding hints
-trait ErrorHint {
-    fn with_hint(self, hint: &str) -> SecretsError;
-}
-
-impl ErrorHint for SecretsError {
-    fn with_hint(self, hint: &str) -> SecretsError {
-        SecretsError::Config(format!("{}: {}", self, hint))
-    }
+/// Read an optional secret, treating an empty value as unset
+fn optional_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
 }
 
 #[cfg(test)]
@@ -133,10 +117,9 @@ mod tests {
 
         let result = load_from_env().await;
 
-        // Should succeed with defaults for optional secrets
+        // Should succeed with defaults for secrets that have them
         if let Ok(secrets) = result {
             assert!(!secrets.jwt_secret.is_empty());
-            assert!(!secrets.stripe_secret_key.is_empty());
             assert!(!secrets.ethereum_sepolia_rpc_url.is_empty());
         }
 
@@ -4,6 +4,7 @@
 //! - **EnvBackend**: Development only - reads from .env files
 //! - **AwsBackend**: Production - AWS Secrets Manager
 //! - **VaultBackend**: Production - HashiCorp Vault
+//! - **AzureBackend**: Production - Azure Key Vault
 //!
 //! # Security Features
 //!
@@ -29,11 +30,21 @@
 //! }
 //! ```
 //!
+//! # Required and Optional Secrets
+//!
+//! Loading fails only when a required secret (database, Redis, JWT, API key
+//! encryption) is missing or malformed. Optional secrets such as the Stripe
+//! keys or the Telegram bot token may be absent: the feature that needs them
+//! is reported by [`AppSecrets::disabled_features`] and stays off, unless the
+//! deployment lists it in `REQUIRED_FEATURES` and checks with
+//! [`AppSecrets::validate_for`]. Validation reports every missing or malformed
+//! secret at once ([`SecretsValidation`]).
+//!
 //! # Environment Variables
 //!
-//! - `SECRETS_BACKEND`: Backend to use (`env`, `aws`, or `vault`)
+//! - `SECRETS_BACKEND`: Backend to use (`env`, `aws`, `vault`, or `azure`)
 //!   - Default: `env` (development mode)
-//!   - Production: Set to `aws` or `vault`
+//!   - Production: Set to `aws`, `vault`, or `azure`
 //!
 //! ## AWS Secrets Manager Configuration
 //!
@@ -47,15 +58,22 @@
 //! - `VAULT_ADDR`: Vault server address (e.g., https://vault.example.com:8200)
 //! - `VAULT_TOKEN`: Authentication token
 //! - `VAULT_NAMESPACE`: Namespace (optional, for Vault Enterprise)
+//!
+//! ## Azure Key Vault Configuration
+//!
+//! - `AZURE_KEY_VAULT_URL`: Vault URL (e.g., https://agentauri-prod.vault.azure.net)
+//! - `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`, `AZURE_CLIENT_SECRET`: Service principal
+//!   (optional; managed identity is used when `AZURE_CLIENT_SECRET` is unset)
 
 pub mod aws;
+pub mod azure;
 pub mod env_backend;
 pub mod types;
 pub mod vault;
 
 use std::env;
 
-pub use types::{AppSecrets, SecretsError};
+pub use types::{AppSecrets, OptionalFeature, SecretsError, SecretsValidation};
 
 /// Secrets backend selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,6 +86,9 @@ pub enum SecretsBackend {
 
     /// Production: HashiCorp Vault
     Vault,
+
+    /// Production: Azure Key Vault
+    Azure,
 }
 
 impl SecretsBackend {
@@ -77,12 +98,14 @@ impl SecretsBackend {
     /// - `env` → EnvBackend (development)
     /// - `aws` → AWS Secrets Manager (production)
     /// - `vault` → HashiCorp Vault (production)
+    /// - `azure` → Azure Key Vault (production)
     ///
     /// Default: `env` if not set
     pub fn from_env() -> Self {
         match env::var("SECRETS_BACKEND").as_deref() {
             Ok("aws") => Self::Aws,
             Ok("vault") => Self::Vault,
+            Ok("azure") => Self::Azure,
             _ => Self::Env, // Default to .env for development
         }
     }
@@ -98,7 +121,8 @@ impl SecretsBackend {
 ///
 /// Returns an error if:
 /// - Backend configuration is invalid
-/// - Required secrets are missing
+/// - Required secrets are missing (missing optional secrets only disable
+///   their feature)
 /// - Backend communication fails
 ///
 /// # Example
@@ -129,7 +153,43 @@ pub async fn load_secrets() -> Result<AppSecrets, SecretsError> {
             let manager = vault::SecretsManager::new().await?;
             manager.get_app_secrets().await
         }
+        SecretsBackend::Azure => {
+            tracing::info!("Loading secrets from Azure Key Vault");
+            let manager = azure::SecretsManager::new().await?;
+            manager.get_app_secrets().await
+        }
+    }
+}
+
+/// Check that the configured backend is reachable
+///
+/// Reads one required secret (the JWT secret) with a fresh client, so the
+/// result reflects network access and credentials right now rather than the
+/// state at startup. The `env` backend has nothing to reach and always
+/// succeeds. Used by the readiness probe; each call costs one backend request.
+///
+/// # Errors
+///
+/// Returns an error if the client cannot be built or the secret read.
+pub async fn check_backend() -> Result<SecretsBackend, SecretsError> {
+    let backend = SecretsBackend::from_env();
+    match backend {
+        SecretsBackend::Env => {}
+        SecretsBackend::Aws => aws::SecretsManager::new().await?.check_reachable().await?,
+        SecretsBackend::Vault => {
+            vault::SecretsManager::new()
+                .await?
+                .check_reachable()
+                .await?
+        }
+        SecretsBackend::Azure => {
+            azure::SecretsManager::new()
+                .await?
+                .check_reachable()
+                .await?
+        }
     }
+    Ok(backend)
 }
 
 #[cfg(test)]
@@ -165,6 +225,16 @@ mod tests {
         env::remove_var("SECRETS_BACKEND");
     }
 
+    #[test]
+    fn test_backend_from_env_azure() {
+        env::set_var("SECRETS_BACKEND", "azure");
+
+        let backend = SecretsBackend::from_env();
+        assert_eq!(backend, SecretsBackend::Azure);
+
+        env::remove_var("SECRETS_BACKEND");
+    }
+
     #[tokio::test]
     async fn test_load_secrets_from_env() {
         // This test requires .env file to be present
@@ -284,8 +284,8 @@ impl SecretsManager {
             self.get_secret("database_url"),
             self.get_secret("redis_url"),
             self.get_secret("jwt_secret"),
-            self.get_secret("stripe_secret_key"),
-            self.get_secret("stripe_webhook_secret"),
+            self.get_secret_optional("stripe_secret_key"),
+            self.get_secret_optional("stripe_webhook_secret"),
             self.get_secret("ethereum_sepolia_rpc_url"),
             self.get_secret("base_sepolia_rpc_url"),
             self.get_secret_optional("linea_sepolia_rpc_url"),
@@ -321,6 +321,18 @@ impl SecretsManager {
         }
     }
 
+    /// Confirm Vault is reachable and the JWT secret readable
+    ///
+    /// Bypasses the cache so every call makes one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret cannot be read.
+    pub async fn check_reachable(&self) -> Result<(), SecretsError> {
+        self.invalidate_secret("jwt_secret").await;
+        self.get_secret("jwt_secret").await.map(|_| ())
+    }
+
     /// Invalidate cache for a specific secret
     ///
     /// Forces the next get_secret() call to fetch from Vault.
@@ -0,0 +1,197 @@
+//! Signatures for outbound webhook deliveries
+//!
+//! Every payload sent to a customer endpoint (REST actions with a signing
+//! secret, action result webhooks, trigger fire webhooks) is signed the same
+//! way, so receivers verify them all with one routine:
+//!
+//! ```text
+//! X-AgentAuri-Timestamp: 1700000000
+//! X-AgentAuri-Signature: sha256=<hex(HMAC-SHA256(secret, "{timestamp}.{body}"))>
+//! ```
+//!
+//! # Key Rotation
+//!
+//! Organizations can rotate their signing key. For a grace window after a
+//! rotation, deliveries carry one signature per valid key, current key first:
+//!
+//! ```text
+//! X-AgentAuri-Signature: sha256=<current>,sha256=<previous>
+//! ```
+//!
+//! A receiver accepts the delivery if any signature matches the key it holds
+//! ([`verify_signature`]), so it can switch keys at any time in the window.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying the HMAC-SHA256 signature of a signed delivery
+pub const SIGNATURE_HEADER: &str = "X-AgentAuri-Signature";
+
+/// Header carrying the Unix timestamp covered by the signature
+pub const TIMESTAMP_HEADER: &str = "X-AgentAuri-Timestamp";
+
+/// Sign a request body for delivery to a customer endpoint
+///
+/// The signature covers `"{timestamp}.{body}"`, so receivers should check the
+/// timestamp is recent to reject replays. Returns `sha256=<hex>` for the
+/// [`SIGNATURE_HEADER`].
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Keys a delivery is signed with: the current key first, then any previous
+/// keys still inside their rotation grace window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKeys {
+    keys: Vec<String>,
+}
+
+impl SigningKeys {
+    /// Sign with a single key
+    pub fn new(current: impl Into<String>) -> Self {
+        Self {
+            keys: vec![current.into()],
+        }
+    }
+
+    /// Build from keys ordered current first (`None` if there are none)
+    pub fn from_keys(keys: Vec<String>) -> Option<Self> {
+        (!keys.is_empty()).then_some(Self { keys })
+    }
+
+    /// Also sign with a previous key during its grace window
+    pub fn with_previous(mut self, previous: impl Into<String>) -> Self {
+        self.keys.push(previous.into());
+        self
+    }
+
+    /// The key new deliveries are primarily signed with
+    pub fn current(&self) -> &str {
+        &self.keys[0]
+    }
+
+    /// Value for the [`SIGNATURE_HEADER`]: one `sha256=<hex>` per key, comma-separated
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        self.keys
+            .iter()
+            .map(|key| sign_payload(key, timestamp, body))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Check a [`SIGNATURE_HEADER`] value against one key
+///
+/// Accepts a header with several comma-separated signatures (sent during a
+/// key rotation grace window) if any of them was made with `secret`.
+/// Comparison is constant-time. Receivers must also check the timestamp.
+pub fn verify_signature(secret: &str, timestamp: i64, body: &[u8], header: &str) -> bool {
+    header.split(',').any(|candidate| {
+        let Some(signature) = candidate
+            .trim()
+            .strip_prefix("sha256=")
+            .and_then(|hex_signature| hex::decode(hex_signature).ok())
+        else {
+            return false;
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+        mac.verify_slice(&signature).is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("secret", 1_700_000_000, br#"{"a":1}"#);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+
+        // Deterministic, and bound to the secret, timestamp and body
+        assert_eq!(
+            signature,
+            sign_payload("secret", 1_700_000_000, br#"{"a":1}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_payload("other", 1_700_000_000, br#"{"a":1}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_payload("secret", 1_700_000_001, br#"{"a":1}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_payload("secret", 1_700_000_000, br#"{"a":2}"#)
+        );
+    }
+
+    #[test]
+    fn test_single_key_matches_sign_payload() {
+        let keys = SigningKeys::new("whsec_current");
+        assert_eq!(keys.current(), "whsec_current");
+        assert_eq!(
+            keys.sign(1_700_000_000, b"{}"),
+            sign_payload("whsec_current", 1_700_000_000, b"{}")
+        );
+    }
+
+    #[test]
+    fn test_grace_window_signature_verifies_with_either_key() {
+        let body = br#"{"type":"action_result"}"#;
+        let keys = SigningKeys::new("whsec_new").with_previous("whsec_old");
+        let header = keys.sign(1_700_000_000, body);
+
+        assert_eq!(header.split(',').count(), 2);
+        assert!(header.starts_with(&sign_payload("whsec_new", 1_700_000_000, body)));
+        assert!(verify_signature("whsec_new", 1_700_000_000, body, &header));
+        assert!(verify_signature("whsec_old", 1_700_000_000, body, &header));
+        assert!(!verify_signature(
+            "whsec_other",
+            1_700_000_000,
+            body,
+            &header
+        ));
+        assert!(!verify_signature("whsec_old", 1_700_000_001, body, &header));
+        assert!(!verify_signature(
+            "whsec_old",
+            1_700_000_000,
+            b"{}",
+            &header
+        ));
+    }
+
+    #[test]
+    fn test_previous_key_rejected_after_grace_window() {
+        let body = b"{}";
+        let header = SigningKeys::new("whsec_new").sign(1_700_000_000, body);
+
+        assert!(verify_signature("whsec_new", 1_700_000_000, body, &header));
+        assert!(!verify_signature("whsec_old", 1_700_000_000, body, &header));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_headers() {
+        for header in ["", "sha256=", "sha256=zz", "md5=abcd", ","] {
+            assert!(!verify_signature("secret", 1, b"{}", header), "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_from_keys() {
+        assert!(SigningKeys::from_keys(Vec::new()).is_none());
+        let keys = SigningKeys::from_keys(vec!["a".to_string(), "b".to_string()]).unwrap();
+        assert_eq!(keys, SigningKeys::new("a").with_previous("b"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Canonical timestamp format for API payloads
+//!
+//! Every timestamp the backend emits is RFC 3339 in UTC with a `Z` suffix
+//! and exactly six fractional digits:
+//!
+//! ```text
+//! 2026-01-15T10:30:00.123456Z
+//! ```
+//!
+//! Microseconds match PostgreSQL `TIMESTAMPTZ` precision, so a value a client
+//! echoes back (e.g. as a `from` filter) round-trips exactly. Parsing accepts
+//! any RFC 3339 offset (`Z`, `+02:00`, `-05:00`) and any fractional precision,
+//! and normalizes to UTC.
+//!
+//! # Usage
+//!
+//! ```
+//! use chrono::{DateTime, Utc};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Example {
+//!     #[serde(with = "shared::timestamp")]
+//!     created_at: DateTime<Utc>,
+//!     #[serde(default, with = "shared::timestamp::option")]
+//!     deleted_at: Option<DateTime<Utc>>,
+//! }
+//! ```
+//!
+//! Timestamps embedded in plain strings (e.g. signed messages) use [`format`].
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Format a timestamp in the canonical form
+pub fn format(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Parse an RFC 3339 timestamp with any offset, normalized to UTC
+pub fn parse(value: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    DateTime::parse_from_rfc3339(value).map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Serialize a timestamp in the canonical form
+pub fn serialize<S>(timestamp: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format(timestamp))
+}
+
+/// Deserialize an RFC 3339 timestamp with any offset
+pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse(&value).map_err(serde::de::Error::custom)
+}
+
+/// Same contract for `Option<DateTime<Utc>>` fields (`null` when absent)
+///
+/// Pair with `#[serde(default)]` on deserialized structs so a missing field
+/// reads as `None`.
+pub mod option {
+    use super::*;
+
+    /// Serialize an optional timestamp in the canonical form
+    pub fn serialize<S>(timestamp: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match timestamp {
+            Some(timestamp) => super::serialize(timestamp, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional RFC 3339 timestamp with any offset
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| parse(&value).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde::Serialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        #[serde(with = "crate::timestamp")]
+        at: DateTime<Utc>,
+        #[serde(default, with = "crate::timestamp::option")]
+        until: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_format_is_utc_micros() {
+        let whole = Utc.with_ymd_and_hms(2026, 1, 15, 10, 30, 0).unwrap();
+        assert_eq!(format(&whole), "2026-01-15T10:30:00.000000Z");
+
+        let nanos = whole + chrono::Duration::nanoseconds(123_456_789);
+        assert_eq!(format(&nanos), "2026-01-15T10:30:00.123456Z");
+    }
+
+    #[test]
+    fn test_parse_normalizes_offsets() {
+        let expected = Utc.with_ymd_and_hms(2026, 1, 15, 10, 30, 0).unwrap();
+        for value in [
+            "2026-01-15T10:30:00Z",
+            "2026-01-15T12:30:00+02:00",
+            "2026-01-15T05:30:00.000-05:00",
+        ] {
+            assert_eq!(parse(value).unwrap(), expected, "{}", value);
+        }
+        assert!(parse("2026-01-15 10:30:00").is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 15, 10, 30, 0).unwrap();
+        let sample = Sample { at, until: None };
+
+        let json = serde_json::to_value(&sample).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "at": "2026-01-15T10:30:00.000000Z", "until": null })
+        );
+        assert_eq!(serde_json::from_value::<Sample>(json).unwrap(), sample);
+
+        let offset: Sample = serde_json::from_str(
+            r#"{"at": "2026-01-15T11:30:00+01:00", "until": "2026-01-16T10:30:00+00:00"}"#,
+        )
+        .unwrap();
+        assert_eq!(offset.at, at);
+        assert_eq!(offset.until, Some(at + chrono::Duration::days(1)));
+
+        // Missing optional field
+        let missing: Sample = serde_json::from_str(r#"{"at": "2026-01-15T10:30:00Z"}"#).unwrap();
+        assert_eq!(missing.until, None);
+    }
+}
@@ -111,6 +111,7 @@ resource "aws_ecs_task_definition" "api_gateway" {
         { name = "SERVER_HOST", value = "0.0.0.0" },
         { name = "SERVER_PORT", value = "8080" },
         { name = "DB_SSL_MODE", value = "require" }, # TODO: Use verify-full after adding RDS CA cert to image
+        { name = "DB_MIGRATION_MODE", value = "skip" },      # Image has no migrations; they run as a separate step
         { name = "FRONTEND_URL", value = "https://agentauri.ai" },
         { name = "GOOGLE_REDIRECT_URI", value = "https://${var.domain_name}/api/v1/auth/google/callback" },
         { name = "GITHUB_REDIRECT_URI", value = "https://${var.domain_name}/api/v1/auth/github/callback" }
@@ -182,7 +183,7 @@ resource "aws_ecs_task_definition" "api_gateway" {
       }
 
       healthCheck = {
-        command     = ["CMD-SHELL", "curl -f http://localhost:8080/api/v1/health || exit 1"]
+        command     = ["CMD-SHELL", "curl -f http://localhost:8080/api/v1/health/live || exit 1"]
         interval    = 30
         timeout     = 5
         retries     = 3